@@ -379,6 +379,7 @@ impl ComputepassState {
         let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
 
         let start_idx = pass_number * dispatch_per_pass;
@@ -405,6 +406,7 @@ impl ComputepassState {
         let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
 
         compute_pass.set_pipeline(self.bindless_pipeline.as_ref().unwrap());
@@ -49,6 +49,7 @@ impl DeviceState {
             memory_hints: wgpu::MemoryHints::Performance,
             label: Some("Compute/RenderPass Device"),
             trace: wgpu::Trace::Off,
+            toggles: wgpu::TogglesDescriptor::default(),
         }))
         .unwrap();
 
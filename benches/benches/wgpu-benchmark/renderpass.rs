@@ -198,11 +198,14 @@ impl RenderpassState {
                     primitive: wgpu::PrimitiveState {
                         topology: wgpu::PrimitiveTopology::TriangleList,
                         strip_index_format: None,
+                        primitive_restart: true,
                         front_face: wgpu::FrontFace::Cw,
                         cull_mode: Some(wgpu::Face::Back),
                         polygon_mode: wgpu::PolygonMode::Fill,
                         unclipped_depth: false,
                         conservative: false,
+                        line_width: 1.0,
+                        line_stipple: None,
                     },
                     depth_stencil: None,
                     multisample: wgpu::MultisampleState::default(),
@@ -213,6 +216,7 @@ impl RenderpassState {
                             format: wgpu::TextureFormat::Rgba8UnormSrgb,
                             blend: None,
                             write_mask: wgpu::ColorWrites::ALL,
+                            logic_op: None,
                         })],
                         compilation_options: wgpu::PipelineCompilationOptions::default(),
                     }),
@@ -296,11 +300,14 @@ impl RenderpassState {
                     primitive: wgpu::PrimitiveState {
                         topology: wgpu::PrimitiveTopology::TriangleList,
                         strip_index_format: None,
+                        primitive_restart: true,
                         front_face: wgpu::FrontFace::Cw,
                         cull_mode: Some(wgpu::Face::Back),
                         polygon_mode: wgpu::PolygonMode::Fill,
                         unclipped_depth: false,
                         conservative: false,
+                        line_width: 1.0,
+                        line_stipple: None,
                     },
                     depth_stencil: None,
                     multisample: wgpu::MultisampleState::default(),
@@ -311,6 +318,7 @@ impl RenderpassState {
                             format: wgpu::TextureFormat::Rgba8UnormSrgb,
                             blend: None,
                             write_mask: wgpu::ColorWrites::ALL,
+                            logic_op: None,
                         })],
                         compilation_options: wgpu::PipelineCompilationOptions::default(),
                     }),
@@ -360,6 +368,9 @@ impl RenderpassState {
                 },
             })],
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
             timestamp_writes: None,
             depth_stencil_attachment: None,
         });
@@ -407,6 +418,9 @@ impl RenderpassState {
                 },
             })],
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
             timestamp_writes: None,
             depth_stencil_attachment: None,
         });
@@ -145,6 +145,7 @@ impl GPUAdapter {
             required_limits,
             memory_hints: Default::default(),
             trace,
+            toggles: Default::default(),
         };
 
         let (device, queue) =
@@ -121,6 +121,7 @@ impl GPUCommandEncoder {
                         store_op: attachment.stencil_store_op.map(Into::into),
                         read_only: attachment.stencil_read_only,
                     },
+                    resolve: None,
                 })
             })
             .transpose()?;
@@ -139,6 +140,9 @@ impl GPUCommandEncoder {
             depth_stencil_attachment: depth_stencil_attachment.as_ref(),
             timestamp_writes: timestamp_writes.as_ref(),
             occlusion_query_set: descriptor.occlusion_query_set.map(|query_set| query_set.id),
+            pipeline_statistics_query: None,
+            flags: Default::default(),
+            multiview: None,
         };
 
         let (render_pass, err) = self
@@ -171,6 +175,7 @@ impl GPUCommandEncoder {
         let wgpu_descriptor = wgpu_core::command::ComputePassDescriptor {
             label: crate::transform_label(descriptor.label.clone()),
             timestamp_writes,
+            pipeline_statistics_query: None,
         };
 
         let (compute_pass, err) = self
@@ -424,6 +429,7 @@ impl GPUCommandEncoder {
     ) -> Result<GPUCommandBuffer, JsErrorBox> {
         let wgpu_descriptor = wgpu_types::CommandBufferDescriptor {
             label: crate::transform_label(descriptor.label.clone()),
+            reusable: false,
         };
 
         // TODO(https://github.com/gfx-rs/wgpu/issues/7812): This is not right,
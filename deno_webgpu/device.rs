@@ -424,12 +424,16 @@ impl GPUDevice {
         let wgpu_descriptor = wgpu_core::pipeline::ShaderModuleDescriptor {
             label: crate::transform_label(descriptor.label.clone()),
             runtime_checks: wgpu_types::ShaderRuntimeChecks::default(),
+            optimization: wgpu_types::ShaderOptimization::Default,
         };
 
         let (id, err) = self.instance.device_create_shader_module(
             self.id,
             &wgpu_descriptor,
-            wgpu_core::pipeline::ShaderModuleSource::Wgsl(Cow::Borrowed(&descriptor.code)),
+            wgpu_core::pipeline::ShaderModuleSource::Wgsl(
+                Cow::Borrowed(&descriptor.code),
+                wgpu_core::naga::front::wgsl::Options::new(),
+            ),
             None,
         );
 
@@ -533,6 +537,7 @@ impl GPUDevice {
             }),
             sample_count: descriptor.sample_count,
             multiview: None,
+            inheritance: wgpu_types::RenderBundleInheritance::empty(),
         };
 
         let res = wgpu_core::command::RenderBundleEncoder::new(&wgpu_descriptor, self.id, None);
@@ -713,11 +718,14 @@ impl GPUDevice {
         let primitive = wgpu_types::PrimitiveState {
             topology: descriptor.primitive.topology.into(),
             strip_index_format: descriptor.primitive.strip_index_format.map(Into::into),
+            primitive_restart: true,
             front_face: descriptor.primitive.front_face.into(),
             cull_mode: descriptor.primitive.cull_mode.into(),
             unclipped_depth: descriptor.primitive.unclipped_depth,
             polygon_mode: Default::default(),
             conservative: false,
+            line_width: 1.0,
+            line_stipple: None,
         };
 
         let depth_stencil = descriptor.depth_stencil.map(|depth_stencil| {
@@ -801,6 +809,7 @@ impl GPUDevice {
                                             .ok_or_else(|| {
                                                 JsErrorBox::type_error("usage is not valid")
                                             })?,
+                                            logic_op: None,
                                         })
                                     })
                                     .transpose()
@@ -848,8 +857,12 @@ pub enum GPUDeviceLostReason {
 impl From<wgpu_types::DeviceLostReason> for GPUDeviceLostReason {
     fn from(value: wgpu_types::DeviceLostReason) -> Self {
         match value {
-            wgpu_types::DeviceLostReason::Unknown => Self::Unknown,
             wgpu_types::DeviceLostReason::Destroyed => Self::Destroyed,
+            wgpu_types::DeviceLostReason::Unknown
+            | wgpu_types::DeviceLostReason::Hung
+            | wgpu_types::DeviceLostReason::Removed
+            | wgpu_types::DeviceLostReason::DriverReset
+            | wgpu_types::DeviceLostReason::OutOfMemory => Self::Unknown,
         }
     }
 }
@@ -167,6 +167,7 @@ impl GPU {
                         gl: wgpu_types::GlBackendOptions::default(),
                         noop: wgpu_types::NoopBackendOptions::default(),
                     },
+                    toggles: wgpu_types::TogglesDescriptor::default(),
                 },
             )));
             state.borrow::<Instance>()
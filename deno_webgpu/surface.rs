@@ -74,6 +74,9 @@ impl GPUCanvasContext {
                 .map(Into::into)
                 .unwrap_or_default(),
             alpha_mode: configuration.alpha_mode.into(),
+            color_space: wgpu_types::SurfaceColorSpace::Auto,
+            hdr_metadata: None,
+            exclusive_fullscreen: false,
             view_formats: configuration
                 .view_formats
                 .into_iter()
@@ -54,6 +54,7 @@ pub async fn execute_gpu_inner(
         let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: Some("compute pass descriptor"),
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         cpass.set_pipeline(&compute_pipeline);
         cpass.set_bind_group(0, Some(&bind_group), &[]);
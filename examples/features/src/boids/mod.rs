@@ -276,6 +276,9 @@ impl crate::framework::Example for Example {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         };
 
         // get command encoder
@@ -288,6 +291,7 @@ impl crate::framework::Example for Example {
             let mut cpass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: None,
                 timestamp_writes: None,
+                pipeline_statistics_query: None,
             });
             cpass.set_pipeline(&self.compute_pipeline);
             cpass.set_bind_group(0, &self.particle_bind_groups[self.frame_num % 2], &[]);
@@ -127,6 +127,9 @@ impl Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             rpass.set_pipeline(&self.pipeline);
             rpass.set_bind_group(0, &self.global_group, &[]);
@@ -225,6 +228,7 @@ impl crate::framework::Example for Example {
                     format: config.view_formats[0],
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::default(),
+                    logic_op: None,
                 })],
             }),
             primitive: wgpu::PrimitiveState {
@@ -288,6 +288,7 @@ impl crate::framework::Example for Example {
                             alpha: wgpu::BlendComponent::REPLACE,
                         }),
                         write_mask: wgpu::ColorWrites::ALL,
+                        logic_op: None,
                     })],
                 }),
                 primitive: wgpu::PrimitiveState {
@@ -356,6 +357,9 @@ impl crate::framework::Example for Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             rpass.push_debug_group("Prepare data for draw.");
             rpass.set_pipeline(&self.pipeline);
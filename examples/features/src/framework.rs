@@ -220,7 +220,7 @@ impl SurfaceWrapper {
         match surface.get_current_texture() {
             Ok(frame) => frame,
             // If we timed out, just try again
-            Err(wgpu::SurfaceError::Timeout) => surface
+            Err(wgpu::SurfaceError::Timeout | wgpu::SurfaceError::WouldBlock) => surface
                 .get_current_texture()
                 .expect("Failed to acquire next surface texture!"),
             Err(
@@ -294,6 +294,7 @@ impl ExampleContext {
                     Some(path) => wgpu::Trace::Directory(path.into()),
                     None => wgpu::Trace::Off,
                 },
+                toggles: wgpu::TogglesDescriptor::default(),
             })
             .await
             .expect("Unable to find a suitable GPU adapter!");
@@ -560,6 +561,9 @@ impl<E: Example + wgpu::WasmNotSendSync> From<ExampleTestParams<E>>
                         desired_maximum_frame_latency: 2,
                         present_mode: wgpu::PresentMode::Fifo,
                         alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                        color_space: wgpu::SurfaceColorSpace::Auto,
+                        hdr_metadata: None,
+                        exclusive_fullscreen: false,
                         view_formats: vec![format],
                     },
                     &ctx.adapter,
@@ -18,6 +18,7 @@ async fn run() {
             required_limits: wgpu::Limits::downlevel_defaults(),
             memory_hints: wgpu::MemoryHints::Performance,
             trace: wgpu::Trace::Off,
+            toggles: wgpu::TogglesDescriptor::default(),
         })
         .await
         .unwrap();
@@ -117,6 +118,7 @@ async fn execute(
         let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         compute_pass.set_pipeline(&patient_pipeline);
         compute_pass.set_bind_group(0, &bind_group, &[]);
@@ -139,6 +141,7 @@ async fn execute(
         let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         compute_pass.set_pipeline(&hasty_pipeline);
         compute_pass.set_bind_group(0, &bind_group, &[]);
@@ -33,6 +33,7 @@ async fn run(event_loop: EventLoop<()>, window: Window) {
                 .using_resolution(adapter.limits()),
             memory_hints: wgpu::MemoryHints::MemoryUsage,
             trace: wgpu::Trace::Off,
+            toggles: wgpu::TogglesDescriptor::default(),
         })
         .await
         .expect("Failed to create device");
@@ -129,6 +130,9 @@ async fn run(event_loop: EventLoop<()>, window: Window) {
                                     depth_stencil_attachment: None,
                                     timestamp_writes: None,
                                     occlusion_query_set: None,
+                                    pipeline_statistics_query: None,
+                                    flags: wgpu::RenderPassFlags::empty(),
+                                    multiview: None,
                                 });
                             rpass.set_pipeline(&render_pipeline);
                             rpass.draw(0..3, 0..1);
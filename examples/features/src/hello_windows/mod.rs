@@ -76,6 +76,7 @@ async fn run(event_loop: EventLoop<()>, viewports: Vec<(Arc<Window>, wgpu::Color
             required_limits: wgpu::Limits::downlevel_defaults(),
             memory_hints: wgpu::MemoryHints::MemoryUsage,
             trace: wgpu::Trace::Off,
+            toggles: wgpu::TogglesDescriptor::default(),
         })
         .await
         .expect("Failed to create device");
@@ -133,6 +134,9 @@ async fn run(event_loop: EventLoop<()>, viewports: Vec<(Arc<Window>, wgpu::Color
                                         depth_stencil_attachment: None,
                                         timestamp_writes: None,
                                         occlusion_query_set: None,
+                                        pipeline_statistics_query: None,
+                                        flags: wgpu::RenderPassFlags::empty(),
+                                        multiview: None,
                                     });
                             }
 
@@ -33,6 +33,7 @@ async fn run() {
             required_limits: wgpu::Limits::downlevel_defaults(),
             memory_hints: wgpu::MemoryHints::MemoryUsage,
             trace: wgpu::Trace::Off,
+            toggles: wgpu::TogglesDescriptor::default(),
         })
         .await
         .unwrap();
@@ -118,6 +119,7 @@ async fn run() {
         let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         compute_pass.set_pipeline(&pipeline);
         compute_pass.set_bind_group(0, &bind_group, &[]);
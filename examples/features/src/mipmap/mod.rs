@@ -170,20 +170,23 @@ impl Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: query_sets.as_ref().map(|query_sets| {
+                    wgpu::PassPipelineStatisticsQuery {
+                        query_set: &query_sets.pipeline_statistics,
+                        query_index: pipeline_query_index_base,
+                    }
+                }),
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             if let Some(query_sets) = query_sets {
                 rpass.write_timestamp(&query_sets.timestamp, timestamp_query_index_base);
-                rpass.begin_pipeline_statistics_query(
-                    &query_sets.pipeline_statistics,
-                    pipeline_query_index_base,
-                );
             }
             rpass.set_pipeline(&pipeline);
             rpass.set_bind_group(0, &bind_group, &[]);
             rpass.draw(0..3, 0..1);
             if let Some(query_sets) = query_sets {
                 rpass.write_timestamp(&query_sets.timestamp, timestamp_query_index_base + 1);
-                rpass.end_pipeline_statistics_query();
             }
         }
 
@@ -499,6 +502,9 @@ impl crate::framework::Example for Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             rpass.set_pipeline(&self.draw_pipeline);
             rpass.set_bind_group(0, &self.bind_group, &[]);
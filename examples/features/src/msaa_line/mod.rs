@@ -87,6 +87,7 @@ impl Example {
                 depth_stencil: None,
                 sample_count,
                 multiview: None,
+                inheritance: wgpu::RenderBundleInheritance::empty(),
             });
         encoder.set_pipeline(&pipeline);
         encoder.set_vertex_buffer(0, vertex_buffer.slice(..));
@@ -307,6 +308,9 @@ impl crate::framework::Example for Example {
                     depth_stencil_attachment: None,
                     timestamp_writes: None,
                     occlusion_query_set: None,
+                    pipeline_statistics_query: None,
+                    flags: wgpu::RenderPassFlags::empty(),
+                    multiview: None,
                 })
                 .execute_bundles(iter::once(&self.bundle));
         }
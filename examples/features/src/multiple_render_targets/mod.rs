@@ -182,6 +182,9 @@ impl MultiTargetRenderer {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
         rpass.set_pipeline(&self.pipeline);
         rpass.set_bind_group(0, &self.bindgroup, &[]);
@@ -261,6 +264,7 @@ impl TargetRenderer {
                     format,
                     blend: None,
                     write_mask: Default::default(),
+                    logic_op: None,
                 })],
             }),
             primitive: wgpu::PrimitiveState::default(),
@@ -339,6 +343,9 @@ impl TargetRenderer {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
         rpass.set_pipeline(&self.pipeline);
         rpass.set_bind_group(0, &self.bindgroup_left, &[]);
@@ -458,11 +465,13 @@ impl crate::framework::Example for Example {
                     format: config.view_formats[0],
                     blend: None,
                     write_mask: Default::default(),
+                    logic_op: None,
                 }),
                 Some(wgpu::ColorTargetState {
                     format: config.view_formats[0],
                     blend: None,
                     write_mask: Default::default(),
+                    logic_op: None,
                 }),
             ],
         );
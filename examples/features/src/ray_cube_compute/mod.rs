@@ -449,6 +449,7 @@ impl crate::framework::Example for Example {
             let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: None,
                 timestamp_writes: None,
+                pipeline_statistics_query: None,
             });
             cpass.set_pipeline(&self.compute_pipeline);
             cpass.set_bind_group(0, Some(&self.compute_bind_group), &[]);
@@ -470,6 +471,9 @@ impl crate::framework::Example for Example {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
 
             rpass.set_pipeline(&self.blit_pipeline);
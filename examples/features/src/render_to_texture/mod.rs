@@ -22,6 +22,7 @@ async fn run(_path: Option<String>) {
             required_limits: wgpu::Limits::downlevel_defaults(),
             memory_hints: wgpu::MemoryHints::MemoryUsage,
             trace: wgpu::Trace::Off,
+            toggles: wgpu::TogglesDescriptor::default(),
         })
         .await
         .unwrap();
@@ -93,6 +94,9 @@ async fn run(_path: Option<String>) {
             })],
             depth_stencil_attachment: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
             timestamp_writes: None,
         });
         render_pass.set_pipeline(&pipeline);
@@ -51,6 +51,7 @@ async fn compute(local_buffer: &mut [u32], context: &WgpuContext) {
         let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         compute_pass.set_pipeline(&context.pipeline);
         compute_pass.set_bind_group(0, &context.bind_group, &[]);
@@ -164,6 +165,7 @@ impl WgpuContext {
                 required_limits: wgpu::Limits::downlevel_defaults(),
                 memory_hints: wgpu::MemoryHints::Performance,
                 trace: wgpu::Trace::Off,
+                toggles: wgpu::TogglesDescriptor::default(),
             })
             .await
             .unwrap();
@@ -768,9 +768,13 @@ impl crate::framework::Example for Example {
                             store: wgpu::StoreOp::Store,
                         }),
                         stencil_ops: None,
+                        resolve: None,
                     }),
                     timestamp_writes: None,
                     occlusion_query_set: None,
+                    pipeline_statistics_query: None,
+                    flags: wgpu::RenderPassFlags::empty(),
+                    multiview: None,
                 });
                 pass.set_pipeline(&self.shadow_pass.pipeline);
                 pass.set_bind_group(0, &self.shadow_pass.bind_group, &[]);
@@ -813,9 +817,13 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Discard,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             pass.set_pipeline(&self.forward_pass.pipeline);
             pass.set_bind_group(0, &self.forward_pass.bind_group, &[]);
@@ -445,9 +445,13 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Discard,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
 
             rpass.set_bind_group(0, &self.bind_group, &[]);
@@ -138,6 +138,7 @@ impl<const SRGB: bool> crate::framework::Example for Example<SRGB> {
                     format: config.view_formats[0],
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
                 })],
             }),
             primitive: wgpu::PrimitiveState {
@@ -196,6 +197,9 @@ impl<const SRGB: bool> crate::framework::Example for Example<SRGB> {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             rpass.push_debug_group("Prepare data for draw.");
             rpass.set_pipeline(&self.pipeline);
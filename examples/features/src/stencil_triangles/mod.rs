@@ -80,6 +80,7 @@ impl crate::framework::Example for Example {
                     format: config.view_formats[0],
                     blend: None,
                     write_mask: wgpu::ColorWrites::empty(),
+                    logic_op: None,
                 })],
             }),
             primitive: Default::default(),
@@ -219,9 +220,13 @@ impl crate::framework::Example for Example {
                         load: wgpu::LoadOp::Clear(0),
                         store: wgpu::StoreOp::Store,
                     }),
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
 
             rpass.set_stencil_reference(1);
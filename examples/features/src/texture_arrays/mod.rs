@@ -402,6 +402,9 @@ impl crate::framework::Example for Example {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
 
         rpass.set_pipeline(&self.pipeline);
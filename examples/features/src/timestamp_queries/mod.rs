@@ -212,6 +212,7 @@ async fn run() {
             required_limits: wgpu::Limits::downlevel_defaults(),
             memory_hints: wgpu::MemoryHints::MemoryUsage,
             trace: wgpu::Trace::Off,
+            toggles: wgpu::TogglesDescriptor::default(),
         })
         .await
         .unwrap();
@@ -309,6 +310,7 @@ fn compute_pass(
             beginning_of_pass_write_index: Some(*next_unused_query),
             end_of_pass_write_index: Some(*next_unused_query + 1),
         }),
+        pipeline_statistics_query: None,
     });
     *next_unused_query += 2;
     cpass.set_pipeline(&compute_pipeline);
@@ -394,6 +396,9 @@ fn render_pass(
             end_of_pass_write_index: Some(*next_unused_query + 1),
         }),
         occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        flags: wgpu::RenderPassFlags::empty(),
+        multiview: None,
     });
     *next_unused_query += 2;
 
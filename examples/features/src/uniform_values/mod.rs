@@ -116,6 +116,7 @@ impl WgpuContext {
                 required_limits: wgpu::Limits::downlevel_defaults(),
                 memory_hints: wgpu::MemoryHints::MemoryUsage,
                 trace: wgpu::Trace::Off,
+                toggles: wgpu::TogglesDescriptor::default(),
             })
             .await
             .unwrap();
@@ -318,6 +319,9 @@ async fn run(event_loop: EventLoop<()>, window: Arc<Window>) {
                                         )],
                                         depth_stencil_attachment: None,
                                         occlusion_query_set: None,
+                                        pipeline_statistics_query: None,
+                                        flags: wgpu::RenderPassFlags::empty(),
+                                        multiview: None,
                                         timestamp_writes: None,
                                     });
                                 render_pass.set_pipeline(&wgpu_context_ref.pipeline);
@@ -540,6 +540,7 @@ impl crate::framework::Example for Example {
                         },
                     }),
                     write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
                 })],
             }),
             // How the triangles will be rasterized. This is more important
@@ -622,6 +623,7 @@ impl crate::framework::Example for Example {
                     }),
                     sample_count: 1,
                     multiview: None,
+                    inheritance: wgpu::RenderBundleInheritance::empty(),
                 });
             encoder.set_pipeline(&terrain_pipeline);
             encoder.set_bind_group(0, &terrain_flipped_bind_group, &[]);
@@ -747,9 +749,13 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Store,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
 
             rpass.execute_bundles([&self.terrain_bundle]);
@@ -775,9 +781,13 @@ impl crate::framework::Example for Example {
                         store: wgpu::StoreOp::Store,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             rpass.set_pipeline(&self.terrain_pipeline);
             rpass.set_bind_group(0, &self.terrain_normal_bind_group, &[]);
@@ -802,9 +812,13 @@ impl crate::framework::Example for Example {
                     view: &self.depth_buffer,
                     depth_ops: None,
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
 
             rpass.set_pipeline(&self.water_pipeline);
@@ -72,6 +72,7 @@ fn main() {
         required_limits: wgpu::Limits::downlevel_defaults(),
         memory_hints: wgpu::MemoryHints::MemoryUsage,
         trace: wgpu::Trace::Off,
+        toggles: wgpu::TogglesDescriptor::default(),
     }))
     .expect("Failed to create device");
 
@@ -190,6 +191,7 @@ fn main() {
     let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     // Set the pipeline that we want to use
@@ -60,6 +60,9 @@ impl State {
             // Request compatibility with the sRGB-format texture view we‘re going to create later.
             view_formats: vec![self.surface_format.add_srgb_suffix()],
             alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            color_space: wgpu::SurfaceColorSpace::Auto,
+            hdr_metadata: None,
+            exclusive_fullscreen: false,
             width: self.size.width,
             height: self.size.height,
             desired_maximum_frame_latency: 2,
@@ -107,6 +110,9 @@ impl State {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
 
         // If you wanted to call any drawing commands, they would go here.
@@ -52,6 +52,14 @@ impl InstanceInterface for CustomInstance {
         unimplemented!()
     }
 
+    unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        unimplemented!()
+    }
+
+    unsafe fn stop_graphics_debugger_capture(&self) {
+        unimplemented!()
+    }
+
     fn wgsl_language_features(&self) -> wgpu::WgslLanguageFeatures {
         unimplemented!()
     }
@@ -73,6 +81,14 @@ impl AdapterInterface for CustomAdapter {
         Box::pin(std::future::ready(res))
     }
 
+    fn request_device_with_queues(
+        &self,
+        _desc: &wgpu::DeviceDescriptor<'_>,
+        _queues: &[wgpu::QueueDescriptor<'_>],
+    ) -> Pin<Box<dyn wgpu::custom::RequestDeviceWithQueuesFuture>> {
+        unimplemented!()
+    }
+
     fn is_surface_supported(&self, _surface: &DispatchSurface) -> bool {
         unimplemented!()
     }
@@ -81,6 +97,10 @@ impl AdapterInterface for CustomAdapter {
         unimplemented!()
     }
 
+    fn applied_workarounds(&self) -> wgpu::Toggles {
+        unimplemented!()
+    }
+
     fn limits(&self) -> wgpu::Limits {
         unimplemented!()
     }
@@ -103,6 +123,10 @@ impl AdapterInterface for CustomAdapter {
     fn get_presentation_timestamp(&self) -> wgpu::PresentationTimestamp {
         unimplemented!()
     }
+
+    fn memory_info(&self) -> Option<wgpu::MemoryInfo> {
+        unimplemented!()
+    }
 }
 
 #[derive(Debug)]
@@ -121,6 +145,7 @@ impl DeviceInterface for CustomDevice {
         &self,
         desc: wgpu::ShaderModuleDescriptor<'_>,
         _shader_bound_checks: wgpu::ShaderRuntimeChecks,
+        _optimization: wgpu::ShaderOptimization,
     ) -> DispatchShaderModule {
         assert_eq!(desc.label, Some("shader"));
         DispatchShaderModule::custom(CustomShaderModule(self.0.clone()))
@@ -161,6 +186,13 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn create_mesh_pipeline(
+        &self,
+        _desc: &wgpu::MeshPipelineDescriptor<'_>,
+    ) -> wgpu::custom::DispatchRenderPipeline {
+        unimplemented!()
+    }
+
     fn create_compute_pipeline(
         &self,
         desc: &wgpu::ComputePipelineDescriptor<'_>,
@@ -169,6 +201,13 @@ impl DeviceInterface for CustomDevice {
         wgpu::custom::DispatchComputePipeline::custom(CustomComputePipeline(module.0.clone()))
     }
 
+    fn create_ray_tracing_pipeline(
+        &self,
+        _desc: &wgpu::RayTracingPipelineDescriptor<'_>,
+    ) -> wgpu::custom::DispatchRayTracingPipeline {
+        unimplemented!()
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         _desc: &wgpu::PipelineCacheDescriptor<'_>,
@@ -192,6 +231,19 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn create_blas_from_serialized(
+        &self,
+        _desc: &wgpu::CreateBlasDescriptor<'_>,
+        _sizes: wgpu::BlasGeometrySizeDescriptors,
+        _data: &[u8],
+    ) -> (Option<u64>, wgpu::custom::DispatchBlas) {
+        unimplemented!()
+    }
+
+    fn is_serialized_blas_compatible(&self, _data: &[u8]) -> bool {
+        unimplemented!()
+    }
+
     fn create_tlas(&self, _desc: &wgpu::CreateTlasDescriptor<'_>) -> wgpu::custom::DispatchTlas {
         unimplemented!()
     }
@@ -207,6 +259,58 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn create_memory_heap(
+        &self,
+        _desc: &wgpu::MemoryHeapDescriptor<'_>,
+    ) -> wgpu::custom::DispatchMemoryHeap {
+        unimplemented!()
+    }
+
+    fn texture_memory_requirements(
+        &self,
+        _desc: &wgpu::TextureDescriptor<'_>,
+    ) -> Option<wgpu::TextureMemoryRequirements> {
+        unimplemented!()
+    }
+
+    fn create_texture_aliased(
+        &self,
+        _heap: &wgpu::custom::DispatchMemoryHeap,
+        _offset: wgpu::BufferAddress,
+        _desc: &wgpu::TextureDescriptor<'_>,
+    ) -> wgpu::custom::DispatchTexture {
+        unimplemented!()
+    }
+
+    fn create_sparse_buffer(
+        &self,
+        _desc: &wgpu::BufferDescriptor<'_>,
+    ) -> wgpu::custom::DispatchBuffer {
+        unimplemented!()
+    }
+
+    fn create_sparse_texture(
+        &self,
+        _desc: &wgpu::TextureDescriptor<'_>,
+    ) -> wgpu::custom::DispatchTexture {
+        unimplemented!()
+    }
+
+    fn create_shared_fence(
+        &self,
+        _desc: &wgpu::SharedFenceDescriptor<'_>,
+    ) -> wgpu::custom::DispatchSharedFence {
+        unimplemented!()
+    }
+
+    unsafe fn import_shared_fence(
+        &self,
+        _handle: wgpu::SharedFenceHandle,
+        _desc: &wgpu::SharedFenceDescriptor<'_>,
+    ) -> wgpu::custom::DispatchSharedFence {
+        unimplemented!()
+    }
+
     fn create_command_encoder(
         &self,
         _desc: &wgpu::CommandEncoderDescriptor<'_>,
@@ -214,6 +318,14 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn create_command_encoder_for_queue(
+        &self,
+        _queue: &DispatchQueue,
+        _desc: &wgpu::CommandEncoderDescriptor<'_>,
+    ) -> wgpu::custom::DispatchCommandEncoder {
+        unimplemented!()
+    }
+
     fn create_render_bundle_encoder(
         &self,
         _desc: &wgpu::RenderBundleEncoderDescriptor<'_>,
@@ -225,6 +337,13 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn set_memory_budget_callback(
+        &self,
+        _memory_budget_callback: wgpu::custom::ArcMemoryBudgetCallback,
+    ) {
+        unimplemented!()
+    }
+
     fn on_uncaptured_error(&self, _handler: Box<dyn wgpu::UncapturedErrorHandler>) {
         unimplemented!()
     }
@@ -260,6 +379,22 @@ impl DeviceInterface for CustomDevice {
         unimplemented!()
     }
 
+    fn generate_memory_report(&self, _include_resources: bool) -> Option<wgpu::MemoryReport> {
+        unimplemented!()
+    }
+
+    fn trim_memory(&self) -> wgpu::TrimMemoryReport {
+        unimplemented!()
+    }
+
+    fn set_barrier_logging(&self, _enabled: bool) {
+        unimplemented!()
+    }
+
+    fn take_barrier_report(&self) -> Option<wgpu::BarrierReport> {
+        unimplemented!()
+    }
+
     fn destroy(&self) {
         unimplemented!()
     }
@@ -272,6 +407,22 @@ impl ShaderModuleInterface for CustomShaderModule {
     fn get_compilation_info(&self) -> Pin<Box<dyn wgpu::custom::ShaderCompilationInfoFuture>> {
         unimplemented!()
     }
+
+    fn binding(&self, _name: &str) -> Option<(u32, u32)> {
+        unimplemented!()
+    }
+
+    fn workgroup_memory(&self, _entry_point: &str) -> Option<wgpu::WorkgroupMemoryInfo> {
+        unimplemented!()
+    }
+
+    fn check_layout(
+        &self,
+        _name: &str,
+        _layout: &wgpu::ShaderTypeLayout,
+    ) -> Result<(), wgpu::ShaderLayoutError> {
+        unimplemented!()
+    }
 }
 
 #[derive(Debug)]
@@ -329,14 +480,45 @@ impl QueueInterface for CustomQueue {
         unimplemented!()
     }
 
+    fn submit_with_external_sync(
+        &self,
+        _command_buffers: &mut dyn Iterator<Item = wgpu::custom::DispatchCommandBuffer>,
+        _wait: &[wgpu::wgt::SharedFenceValue<&wgpu::custom::DispatchSharedFence>],
+        _signal: &[wgpu::wgt::SharedFenceValue<&wgpu::custom::DispatchSharedFence>],
+    ) -> u64 {
+        unimplemented!()
+    }
+
+    fn bind_sparse_memory(
+        &self,
+        _buffer_bindings: &[wgpu::wgt::SparseBufferBinding<
+            &wgpu::custom::DispatchBuffer,
+            &wgpu::custom::DispatchMemoryHeap,
+        >],
+        _texture_bindings: &[wgpu::wgt::SparseTextureBinding<
+            &wgpu::custom::DispatchTexture,
+            &wgpu::custom::DispatchMemoryHeap,
+        >],
+    ) {
+        unimplemented!()
+    }
+
     fn get_timestamp_period(&self) -> f32 {
         unimplemented!()
     }
 
+    fn get_timestamp_calibration(&self) -> Option<wgpu::TimestampCalibration> {
+        unimplemented!()
+    }
+
     fn on_submitted_work_done(&self, _callback: wgpu::custom::BoxSubmittedWorkDoneCallback) {
         unimplemented!()
     }
 
+    fn wait_for_submission(&self, _submission_index: u64) {
+        unimplemented!()
+    }
+
     #[cfg(all(target_arch = "wasm32", feature = "web"))]
     fn copy_external_image_to_texture(
         &self,
@@ -50,6 +50,8 @@ pub struct Config {
     pub vertex: Vec<ConfigItem>,
     pub fragment: Vec<ConfigItem>,
     pub compute: Vec<ConfigItem>,
+    /// Ray tracing entry points, which are compiled as libraries.
+    pub library: Vec<ConfigItem>,
 }
 
 impl Config {
@@ -59,6 +61,7 @@ impl Config {
             vertex: Default::default(),
             fragment: Default::default(),
             compute: Default::default(),
+            library: Default::default(),
         }
     }
 
@@ -85,8 +88,9 @@ impl Config {
             vertex,
             fragment,
             compute,
+            library,
         } = self;
-        vertex.is_empty() && fragment.is_empty() && compute.is_empty()
+        vertex.is_empty() && fragment.is_empty() && compute.is_empty() && library.is_empty()
     }
 }
 
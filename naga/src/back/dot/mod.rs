@@ -242,6 +242,15 @@ impl StatementGraph {
                     self.calls.push((id, function));
                     "Call"
                 }
+                S::DebugPrintf {
+                    format: _,
+                    ref arguments,
+                } => {
+                    for &arg in arguments {
+                        self.dependencies.push((id, arg, "arg"));
+                    }
+                    "DebugPrintf"
+                }
                 S::Atomic {
                     pointer,
                     ref fun,
@@ -307,6 +316,32 @@ impl StatementGraph {
                         crate::RayQueryFunction::Terminate => "RayQueryTerminate",
                     }
                 }
+                S::RayPipelineFunction(ref fun) => match *fun {
+                    crate::RayPipelineFunction::TraceRay {
+                        acceleration_structure,
+                        descriptor,
+                        sbt_record_offset,
+                        sbt_record_stride,
+                        miss_index,
+                        payload,
+                    } => {
+                        self.dependencies.push((
+                            id,
+                            acceleration_structure,
+                            "acceleration_structure",
+                        ));
+                        self.dependencies.push((id, descriptor, "descriptor"));
+                        self.dependencies
+                            .push((id, sbt_record_offset, "sbt_record_offset"));
+                        self.dependencies
+                            .push((id, sbt_record_stride, "sbt_record_stride"));
+                        self.dependencies.push((id, miss_index, "miss_index"));
+                        self.dependencies.push((id, payload, "payload"));
+                        "TraceRay"
+                    }
+                    crate::RayPipelineFunction::IgnoreIntersection => "IgnoreIntersection",
+                    crate::RayPipelineFunction::TerminateRay => "TerminateRay",
+                },
                 S::SubgroupBallot { result, predicate } => {
                     if let Some(predicate) = predicate {
                         self.dependencies.push((id, predicate, "predicate"));
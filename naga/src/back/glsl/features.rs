@@ -55,6 +55,12 @@ bitflags::bitflags! {
         const SUBGROUP_OPERATIONS = 1 << 24;
         /// Image atomics
         const TEXTURE_ATOMICS = 1 << 25;
+        /// Reading fragment outputs with framebuffer fetch
+        const FRAMEBUFFER_FETCH = 1 << 26;
+        /// Fragment full coverage under conservative rasterization
+        const FULLY_COVERED = 1 << 27;
+        /// Writing the viewport index and layer from vertex shaders
+        const VIEWPORT_LAYER = 1 << 28;
     }
 }
 
@@ -124,6 +130,9 @@ impl FeaturesManager {
         check_feature!(DUAL_SOURCE_BLENDING, 330, 300 /* with extension */);
         check_feature!(SUBGROUP_OPERATIONS, 430, 310);
         check_feature!(TEXTURE_ATOMICS, 420, 310);
+        check_feature!(FRAMEBUFFER_FETCH, 130, 300 /* with extension */);
+        check_feature!(FULLY_COVERED, 450, 320 /* with extension */);
+        check_feature!(VIEWPORT_LAYER, 410 /* with extension */);
         match version {
             Version::Embedded { is_webgl: true, .. } => check_feature!(MULTI_VIEW, 140, 300),
             _ => check_feature!(MULTI_VIEW, 140, 310),
@@ -283,11 +292,32 @@ impl FeaturesManager {
             writeln!(out, "#extension GL_KHR_shader_subgroup_quad : require")?;
         }
 
-        if self.0.contains(Features::TEXTURE_ATOMICS) {
+        if self.0.contains(Features::TEXTURE_ATOMICS) && options.version < Version::new_gles(320) {
             // https://www.khronos.org/registry/OpenGL/extensions/OES/OES_shader_image_atomic.txt
             writeln!(out, "#extension GL_OES_shader_image_atomic : require")?;
         }
 
+        if self.0.contains(Features::FRAMEBUFFER_FETCH) {
+            // https://registry.khronos.org/OpenGL/extensions/EXT/EXT_shader_framebuffer_fetch.txt
+            writeln!(out, "#extension GL_EXT_shader_framebuffer_fetch : require")?;
+        }
+
+        if self.0.contains(Features::FULLY_COVERED) {
+            // https://registry.khronos.org/OpenGL/extensions/NV/NV_conservative_raster_underestimation.txt
+            writeln!(
+                out,
+                "#extension GL_NV_conservative_raster_underestimation : require"
+            )?;
+        }
+
+        if self.0.contains(Features::VIEWPORT_LAYER) {
+            // https://registry.khronos.org/OpenGL/extensions/ARB/ARB_shader_viewport_layer_array.txt
+            writeln!(
+                out,
+                "#extension GL_ARB_shader_viewport_layer_array : require"
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -421,7 +451,8 @@ impl<W> Writer<'_, W> {
                             _ => {}
                         },
                         ImageClass::Sampled { multi: false, .. }
-                        | ImageClass::Depth { multi: false } => {}
+                        | ImageClass::Depth { multi: false }
+                        | ImageClass::External => {}
                     }
                 }
                 _ => {}
@@ -602,6 +633,13 @@ impl<W> Writer<'_, W> {
                     crate::BuiltIn::InstanceIndex | crate::BuiltIn::DrawID => {
                         self.features.request(Features::INSTANCE_INDEX)
                     }
+                    crate::BuiltIn::FramebufferFetch { .. } => {
+                        self.features.request(Features::FRAMEBUFFER_FETCH)
+                    }
+                    crate::BuiltIn::FullyCovered => self.features.request(Features::FULLY_COVERED),
+                    crate::BuiltIn::ViewportIndex | crate::BuiltIn::Layer => {
+                        self.features.request(Features::VIEWPORT_LAYER)
+                    }
                     _ => {}
                 },
                 Binding::Location {
@@ -140,7 +140,9 @@ impl crate::AddressSpace {
             | crate::AddressSpace::Uniform
             | crate::AddressSpace::Storage { .. }
             | crate::AddressSpace::Handle
-            | crate::AddressSpace::PushConstant => false,
+            | crate::AddressSpace::PushConstant
+            | crate::AddressSpace::RayPayload
+            | crate::AddressSpace::IncomingRayPayload => false,
         }
     }
 }
@@ -482,6 +484,8 @@ impl VaryingOptions {
 /// - Varyings with location bindings are named `_S_location_X` where `S` is a
 ///   prefix identifying which pipeline stage the varying connects, and `X` is
 ///   the location.
+/// - Framebuffer fetch inputs read the `inout` fragment output of their
+///   location, so they share its name.
 struct VaryingName<'a> {
     binding: &'a crate::Binding,
     stage: ShaderStage,
@@ -505,9 +509,19 @@ impl fmt::Display for VaryingName<'_> {
                     // fragment to pipeline
                     (ShaderStage::Fragment, true) => "fs2p",
                     (ShaderStage::Task | ShaderStage::Mesh, _) => unreachable!(),
+                    (
+                        ShaderStage::RayGeneration
+                        | ShaderStage::Miss
+                        | ShaderStage::ClosestHit
+                        | ShaderStage::AnyHit,
+                        _,
+                    ) => unreachable!(),
                 };
                 write!(f, "_{prefix}_location{location}",)
             }
+            crate::Binding::BuiltIn(crate::BuiltIn::FramebufferFetch { location }) => {
+                write!(f, "_fs2p_location{location}")
+            }
             crate::Binding::BuiltIn(built_in) => {
                 write!(f, "{}", glsl_built_in(built_in, self.options))
             }
@@ -522,6 +536,10 @@ impl ShaderStage {
             ShaderStage::Fragment => "fs",
             ShaderStage::Vertex => "vs",
             ShaderStage::Task | ShaderStage::Mesh => unreachable!(),
+            ShaderStage::RayGeneration
+            | ShaderStage::Miss
+            | ShaderStage::ClosestHit
+            | ShaderStage::AnyHit => unreachable!(),
         }
     }
 }
@@ -657,6 +675,11 @@ impl<'a, W: Write> Writer<'a, W> {
                 pipeline_options.shader_stage == ep.stage && pipeline_options.entry_point == ep.name
             })
             .ok_or(Error::EntryPointNotFound)?;
+        if module.entry_points[ep_idx].stage.is_ray_tracing() {
+            return Err(Error::Custom(
+                "ray tracing stages are not supported in GLSL".to_string(),
+            ));
+        }
 
         // Generate a map with names required to write the module
         let mut names = crate::FastHashMap::default();
@@ -1174,6 +1197,7 @@ impl<'a, W: Write> Writer<'a, W> {
         let (base, scalar, ms, comparison) = match class {
             Ic::Sampled { kind, multi: true } => ("sampler", S { kind, width: 4 }, "MS", ""),
             Ic::Sampled { kind, multi: false } => ("sampler", S { kind, width: 4 }, "", ""),
+            Ic::External => ("sampler", float, "", ""),
             Ic::Depth { multi: true } => ("sampler", float, "MS", ""),
             Ic::Depth { multi: false } => ("sampler", float, "", "Shadow"),
             Ic::Storage { format, .. } => ("image", format.into(), "", ""),
@@ -1297,6 +1321,9 @@ impl<'a, W: Write> Writer<'a, W> {
             crate::AddressSpace::Uniform => {
                 self.write_interface_block(handle, global)?;
             }
+            crate::AddressSpace::RayPayload | crate::AddressSpace::IncomingRayPayload => {
+                unreachable!()
+            }
             crate::AddressSpace::Storage { .. } => {
                 self.write_interface_block(handle, global)?;
             }
@@ -1656,6 +1683,15 @@ impl<'a, W: Write> Writer<'a, W> {
                         self.clip_distance_count = size;
                         writeln!(self.out, "out float gl_ClipDistance[{size}];")?;
                     }
+                    crate::BuiltIn::FramebufferFetch { location } => {
+                        // The input is read from the `inout` fragment output,
+                        // so that output must exist.
+                        if !self.has_fragment_output(location) {
+                            return Err(Error::Custom(format!(
+                                "Framebuffer fetch of location {location} requires a fragment output at that location"
+                            )));
+                        }
+                    }
                     _ => {}
                 }
                 return Ok(());
@@ -1671,6 +1707,10 @@ impl<'a, W: Write> Writer<'a, W> {
             ShaderStage::Fragment => !output,
             ShaderStage::Compute => false,
             ShaderStage::Task | ShaderStage::Mesh => unreachable!(),
+            ShaderStage::RayGeneration
+            | ShaderStage::Miss
+            | ShaderStage::ClosestHit
+            | ShaderStage::AnyHit => unreachable!(),
         };
 
         // Write the I/O locations, if allowed
@@ -1717,8 +1757,19 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         }
 
-        // Write the input/output qualifier.
-        write!(self.out, "{} ", if output { "out" } else { "in" })?;
+        // Write the input/output qualifier. Fragment outputs that are also
+        // read with framebuffer fetch are `inout`.
+        let qualifier = if !output {
+            "in"
+        } else if self.entry_point.stage == ShaderStage::Fragment
+            && blend_src.is_none()
+            && self.is_framebuffer_fetched(location)
+        {
+            "inout"
+        } else {
+            "out"
+        };
+        write!(self.out, "{qualifier} ")?;
 
         // Write the type
         // `write_type` adds no leading or trailing spaces
@@ -1745,6 +1796,51 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Returns whether the fragment entry point has an output at `location`.
+    fn has_fragment_output(&self, location: u32) -> bool {
+        self.entry_point
+            .function
+            .result
+            .as_ref()
+            .is_some_and(|result| {
+                self.any_varying_binding(result.binding.as_ref(), result.ty, &|binding| {
+                    matches!(
+                        *binding,
+                        crate::Binding::Location {
+                            location: loc,
+                            blend_src: None,
+                            ..
+                        } if loc == location
+                    )
+                })
+            })
+    }
+
+    /// Returns whether the entry point reads `location` with framebuffer fetch.
+    fn is_framebuffer_fetched(&self, location: u32) -> bool {
+        self.entry_point.function.arguments.iter().any(|arg| {
+            self.any_varying_binding(arg.binding.as_ref(), arg.ty, &|binding| {
+                *binding == crate::Binding::BuiltIn(crate::BuiltIn::FramebufferFetch { location })
+            })
+        })
+    }
+
+    /// Returns whether any binding of a varying, or of its struct members, satisfies `predicate`.
+    fn any_varying_binding(
+        &self,
+        binding: Option<&crate::Binding>,
+        ty: Handle<crate::Type>,
+        predicate: &dyn Fn(&crate::Binding) -> bool,
+    ) -> bool {
+        if let TypeInner::Struct { ref members, .. } = self.module.types[ty].inner {
+            members.iter().any(|member| {
+                self.any_varying_binding(member.binding.as_ref(), member.ty, predicate)
+            })
+        } else {
+            binding.is_some_and(predicate)
+        }
+    }
+
     /// Helper method used to write functions (both entry points and regular functions)
     ///
     /// # Notes
@@ -2179,7 +2275,7 @@ impl<'a, W: Write> Writer<'a, W> {
                     } = ctx.expressions[handle]
                     {
                         if let TypeInner::Image {
-                            class: crate::ImageClass::Sampled { .. },
+                            class: crate::ImageClass::Sampled { .. } | crate::ImageClass::External,
                             ..
                         } = *ctx.resolve_type(image, &self.module.types)
                         {
@@ -2466,7 +2562,11 @@ impl<'a, W: Write> Writer<'a, W> {
                                                 true,
                                             ),
                                         };
+                                        let signed = is_signed_built_in(member.binding.as_ref());
                                         write!(self.out, "{varying_name} = ")?;
+                                        if signed {
+                                            write!(self.out, "int(")?;
+                                        }
 
                                         if let Some(struct_name) = temp_struct_name {
                                             write!(self.out, "{struct_name}")?;
@@ -2475,12 +2575,16 @@ impl<'a, W: Write> Writer<'a, W> {
                                         }
 
                                         // Write field name
-                                        writeln!(
+                                        write!(
                                             self.out,
-                                            ".{};",
+                                            ".{}",
                                             &self.names
                                                 [&NameKey::StructMember(result.ty, index as u32)]
                                         )?;
+                                        if signed {
+                                            write!(self.out, ")")?;
+                                        }
+                                        writeln!(self.out, ";")?;
                                         write!(self.out, "{level}")?;
                                     }
                                 }
@@ -2493,8 +2597,15 @@ impl<'a, W: Write> Writer<'a, W> {
                                             true,
                                         ),
                                     };
+                                    let signed = is_signed_built_in(result.binding.as_ref());
                                     write!(self.out, "{name} = ")?;
+                                    if signed {
+                                        write!(self.out, "int(")?;
+                                    }
                                     self.write_expr(value, ctx)?;
+                                    if signed {
+                                        write!(self.out, ")")?;
+                                    }
                                     writeln!(self.out, ";")?;
                                     write!(self.out, "{level}")?;
                                 }
@@ -2804,6 +2915,16 @@ impl<'a, W: Write> Writer<'a, W> {
                 }
                 writeln!(self.out, ");")?;
             }
+            Statement::DebugPrintf { .. } => {
+                return Err(Error::Custom(
+                    "debugPrintf is not supported in GLSL".to_string(),
+                ));
+            }
+            Statement::RayPipelineFunction(_) => {
+                return Err(Error::Custom(
+                    "ray tracing pipeline functions are not supported in GLSL".to_string(),
+                ));
+            }
         }
 
         Ok(())
@@ -3272,7 +3393,14 @@ impl<'a, W: Write> Writer<'a, W> {
                 match query {
                     crate::ImageQuery::Size { level } => {
                         match class {
-                            ImageClass::Sampled { multi, .. } | ImageClass::Depth { multi } => {
+                            ImageClass::Sampled { .. }
+                            | ImageClass::Depth { .. }
+                            | ImageClass::External => {
+                                let multi = match class {
+                                    ImageClass::Sampled { multi, .. }
+                                    | ImageClass::Depth { multi } => multi,
+                                    _ => false,
+                                };
                                 write!(self.out, "textureSize(")?;
                                 self.write_expr(image, ctx)?;
                                 if let Some(expr) = level {
@@ -3318,7 +3446,9 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     crate::ImageQuery::NumLayers => {
                         let fun_name = match class {
-                            ImageClass::Sampled { .. } | ImageClass::Depth { .. } => "textureSize",
+                            ImageClass::Sampled { .. }
+                            | ImageClass::Depth { .. }
+                            | ImageClass::External => "textureSize",
                             ImageClass::Storage { .. } => "imageSize",
                         };
                         write!(self.out, "{fun_name}(")?;
@@ -3335,9 +3465,9 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     crate::ImageQuery::NumSamples => {
                         let fun_name = match class {
-                            ImageClass::Sampled { .. } | ImageClass::Depth { .. } => {
-                                "textureSamples"
-                            }
+                            ImageClass::Sampled { .. }
+                            | ImageClass::Depth { .. }
+                            | ImageClass::External => "textureSamples",
                             ImageClass::Storage { .. } => "imageSamples",
                         };
                         write!(self.out, "{fun_name}(")?;
@@ -4598,7 +4728,9 @@ impl<'a, W: Write> Writer<'a, W> {
         // and the policy to be used with it.
         let (fun_name, policy) = match class {
             // Sampled images inherit the policy from the user passed policies
-            crate::ImageClass::Sampled { .. } => ("texelFetch", self.policies.image_load),
+            crate::ImageClass::Sampled { .. } | crate::ImageClass::External => {
+                ("texelFetch", self.policies.image_load)
+            }
             crate::ImageClass::Storage { .. } => {
                 // OpenGL ES 3.1 mentions in Chapter "8.22 Texture Image Loads and Stores" that:
                 // "Invalid image loads will return a vector where the value of R, G, and B components
@@ -4815,6 +4947,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 // Only sampled images can reach here since storage images
                 // don't need bounds checks and depth images aren't implemented
                 crate::ImageClass::Sampled { kind, .. } => kind,
+                crate::ImageClass::External => crate::ScalarKind::Float,
                 _ => unreachable!(),
             };
 
@@ -5220,6 +5353,8 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
         Bi::PointSize => "gl_PointSize",
         Bi::VertexIndex => "uint(gl_VertexID)",
         Bi::DrawID => "gl_DrawID",
+        Bi::ViewportIndex => "gl_ViewportIndex",
+        Bi::Layer => "gl_Layer",
         // fragment
         Bi::FragDepth => "gl_FragDepth",
         Bi::PointCoord => "gl_PointCoord",
@@ -5233,6 +5368,9 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
                 "gl_SampleMaskIn"
             }
         }
+        Bi::FullyCovered => "gl_FragFullyCoveredNV",
+        // named by `VaryingName`
+        Bi::FramebufferFetch { .. } => unreachable!(),
         // compute
         Bi::GlobalInvocationId => "gl_GlobalInvocationID",
         Bi::LocalInvocationId => "gl_LocalInvocationID",
@@ -5245,9 +5383,36 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
         Bi::SubgroupId => "gl_SubgroupID",
         Bi::SubgroupSize => "gl_SubgroupSize",
         Bi::SubgroupInvocationId => "gl_SubgroupInvocationID",
+        // ray tracing
+        Bi::LaunchId
+        | Bi::LaunchSize
+        | Bi::WorldRayOrigin
+        | Bi::WorldRayDirection
+        | Bi::ObjectRayOrigin
+        | Bi::ObjectRayDirection
+        | Bi::RayTMin
+        | Bi::RayTCurrentMax
+        | Bi::IncomingRayFlags
+        | Bi::InstanceCustomData
+        | Bi::GeometryIndex
+        | Bi::ObjectToWorld
+        | Bi::WorldToObject
+        | Bi::HitKind
+        | Bi::Barycentrics => unreachable!(),
     }
 }
 
+/// Returns true if `binding` is a built-in whose GLSL variable is an `int`,
+/// while the IR value is a `u32`.
+const fn is_signed_built_in(binding: Option<&crate::Binding>) -> bool {
+    matches!(
+        binding,
+        Some(&crate::Binding::BuiltIn(
+            crate::BuiltIn::ViewportIndex | crate::BuiltIn::Layer
+        ))
+    )
+}
+
 /// Helper function that returns the string corresponding to the address space
 const fn glsl_storage_qualifier(space: crate::AddressSpace) -> Option<&'static str> {
     use crate::AddressSpace as As;
@@ -5260,6 +5425,7 @@ const fn glsl_storage_qualifier(space: crate::AddressSpace) -> Option<&'static s
         As::Handle => Some("uniform"),
         As::WorkGroup => Some("shared"),
         As::PushConstant => Some("uniform"),
+        As::RayPayload | As::IncomingRayPayload => None,
     }
 }
 
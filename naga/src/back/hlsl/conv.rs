@@ -23,11 +23,13 @@ impl crate::Scalar {
     pub(super) const fn to_hlsl_str(self) -> Result<&'static str, Error> {
         match self.kind {
             crate::ScalarKind::Sint => match self.width {
+                2 => Ok("int16_t"),
                 4 => Ok("int"),
                 8 => Ok("int64_t"),
                 _ => Err(Error::UnsupportedScalar(self)),
             },
             crate::ScalarKind::Uint => match self.width {
+                2 => Ok("uint16_t"),
                 4 => Ok("uint"),
                 8 => Ok("uint64_t"),
                 _ => Err(Error::UnsupportedScalar(self)),
@@ -157,6 +159,8 @@ impl crate::BuiltIn {
             Self::CullDistance => "SV_CullDistance",
             Self::InstanceIndex => "SV_InstanceID",
             Self::VertexIndex => "SV_VertexID",
+            Self::ViewportIndex => "SV_ViewportArrayIndex",
+            Self::Layer => "SV_RenderTargetArrayIndex",
             // fragment
             Self::FragDepth => "SV_Depth",
             Self::FrontFacing => "SV_IsFrontFace",
@@ -176,11 +180,31 @@ impl crate::BuiltIn {
             Self::SubgroupSize
             | Self::SubgroupInvocationId
             | Self::NumSubgroups
-            | Self::SubgroupId => unreachable!(),
+            | Self::SubgroupId
+            | Self::LaunchId
+            | Self::LaunchSize
+            | Self::WorldRayOrigin
+            | Self::WorldRayDirection
+            | Self::ObjectRayOrigin
+            | Self::ObjectRayDirection
+            | Self::RayTMin
+            | Self::RayTCurrentMax
+            | Self::IncomingRayFlags
+            | Self::InstanceCustomData
+            | Self::GeometryIndex
+            | Self::ObjectToWorld
+            | Self::WorldToObject
+            | Self::HitKind
+            | Self::Barycentrics => unreachable!(),
             Self::BaseInstance | Self::BaseVertex | Self::WorkGroupSize => {
                 return Err(Error::Unimplemented(format!("builtin {self:?}")));
             }
-            Self::PointSize | Self::ViewIndex | Self::PointCoord | Self::DrawID => {
+            Self::PointSize
+            | Self::ViewIndex
+            | Self::PointCoord
+            | Self::DrawID
+            | Self::FramebufferFetch { .. }
+            | Self::FullyCovered => {
                 return Err(Error::Custom(format!("Unsupported builtin {self:?}")));
             }
         })
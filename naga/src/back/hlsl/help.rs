@@ -191,6 +191,7 @@ impl<W: Write> super::Writer<'_, W> {
                 let scalar_kind_str = crate::Scalar { kind, width: 4 }.to_hlsl_str()?;
                 write!(self.out, "{multi_str}<{scalar_kind_str}4>")?
             }
+            crate::ImageClass::External => write!(self.out, "<float4>")?,
             crate::ImageClass::Storage { format, .. } => {
                 let storage_format_str = format.to_hlsl_str();
                 write!(self.out, "<{storage_format_str}>")?
@@ -292,6 +293,7 @@ impl<W: Write> super::Writer<'_, W> {
             crate::ImageClass::Depth { multi: true } => "DepthMS",
             crate::ImageClass::Depth { multi: false } => "Depth",
             crate::ImageClass::Sampled { multi: false, .. } => "",
+            crate::ImageClass::External => "External",
             crate::ImageClass::Storage { .. } => "RW",
         };
         let arrayed_str = if query.arrayed { "Array" } else { "" };
@@ -351,7 +353,9 @@ impl<W: Write> super::Writer<'_, W> {
         // extra parameter is the mip level count or the sample count
         let extra_coords = match wiq.class {
             crate::ImageClass::Storage { .. } => 0,
-            crate::ImageClass::Sampled { .. } | crate::ImageClass::Depth { .. } => 1,
+            crate::ImageClass::Sampled { .. }
+            | crate::ImageClass::Depth { .. }
+            | crate::ImageClass::External => 1,
         };
 
         // GetDimensions Overloaded Methods
@@ -745,6 +745,7 @@ pub const RESERVED: &[&str] = &[
     "WorldToObject3x4",
     "ObjectToWorld4x3",
     "WorldToObject4x3",
+    "BuiltInTriangleIntersectionAttributes",
     "dot4add_u8packed",
     "dot4add_i8packed",
     "dot2add",
@@ -835,6 +836,8 @@ pub const RESERVED: &[&str] = &[
     super::writer::F2I64_FUNCTION,
     super::writer::F2U64_FUNCTION,
     super::writer::IMAGE_SAMPLE_BASE_CLAMP_TO_EDGE_FUNCTION,
+    super::writer::RAY_PAYLOAD_VAR,
+    super::writer::RAY_ATTRIBUTES_VAR,
 ];
 
 // DXC scalar types, from https://github.com/microsoft/DirectXShaderCompiler/blob/18c9e114f9c314f93e68fbc72ce207d4ed2e65ae/tools/clang/lib/AST/ASTContextHLSL.cpp#L48-L254
@@ -939,5 +942,6 @@ pub static RESERVED_SET: RacyLock<HashSet<&'static str>> = RacyLock::new(|| {
 
 pub const RESERVED_PREFIXES: &[&str] = &[
     "__dynamic_buffer_offsets",
+    super::writer::DEBUG_PRINTF_PREFIX,
     super::help::IMAGE_STORAGE_LOAD_SCALAR_WRAPPER,
 ];
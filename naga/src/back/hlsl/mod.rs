@@ -223,7 +223,10 @@ impl crate::ShaderStage {
             Self::Vertex => "vs",
             Self::Fragment => "ps",
             Self::Compute => "cs",
-            Self::Task | Self::Mesh => unreachable!(),
+            Self::Task => "as",
+            Self::Mesh => "ms",
+            // Ray tracing stages are compiled as libraries.
+            Self::RayGeneration | Self::Miss | Self::ClosestHit | Self::AnyHit => "lib",
         }
     }
 }
@@ -365,6 +368,19 @@ pub struct Options {
     /// Add special constants to `SV_VertexIndex` and `SV_InstanceIndex`,
     /// to make them work like in Vulkan/Metal, with help of the host.
     pub special_constants_binding: Option<BindTarget>,
+    /// Bind target of the `RWByteAddressBuffer` that [`DebugPrintf`] statements
+    /// write their messages to, laid out as described in
+    /// [`DEBUG_PRINTF_HEADER_WORDS`].
+    ///
+    /// [`DebugPrintf`]: crate::Statement::DebugPrintf
+    /// [`DEBUG_PRINTF_HEADER_WORDS`]: back::DEBUG_PRINTF_HEADER_WORDS
+    pub debug_printf_binding: Option<BindTarget>,
+    /// The index of the first format string written for [`DebugPrintf`]
+    /// statements, the others following in
+    /// [`ReflectionInfo::debug_printf_formats`] order.
+    ///
+    /// [`DebugPrintf`]: crate::Statement::DebugPrintf
+    pub debug_printf_first_format: u32,
     /// Bind target of the push constant buffer
     pub push_constants_target: Option<BindTarget>,
     /// Bind target of the sampler heap and comparison sampler heap.
@@ -397,6 +413,8 @@ impl Default for Options {
             binding_map: BindingMap::default(),
             fake_missing_bindings: true,
             special_constants_binding: None,
+            debug_printf_binding: None,
+            debug_printf_first_format: 0,
             sampler_heap_target: SamplerHeapBindTargets::default(),
             sampler_buffer_binding_map: alloc::collections::BTreeMap::default(),
             push_constants_target: None,
@@ -437,6 +455,11 @@ pub struct ReflectionInfo {
     ///
     /// Note: Some entry points may fail translation because of missing bindings.
     pub entry_point_names: Vec<Result<String, EntryPointError>>,
+    /// The format strings of the [`DebugPrintf`] statements that were written,
+    /// starting at index [`Options::debug_printf_first_format`].
+    ///
+    /// [`DebugPrintf`]: crate::Statement::DebugPrintf
+    pub debug_printf_formats: Vec<String>,
 }
 
 /// A subset of options that are meant to be changed per pipeline.
@@ -453,6 +476,13 @@ pub struct PipelineOptions {
     /// If `None`, all entry points will be written. If `Some` and the entry
     /// point is not found, an error will be thrown while writing.
     pub entry_point: Option<(ir::ShaderStage, String)>,
+
+    /// The source code the module was parsed from, for `#line` directives.
+    ///
+    /// If set, a `#line` directive pointing at the line of the source each statement
+    /// comes from is written before the statement, so that shader debuggers show that
+    /// source. If `None`, no directives are written.
+    pub debug_source: Option<back::DebugSource>,
 }
 
 #[derive(Error, Debug)]
@@ -553,6 +583,8 @@ pub struct Writer<'a, W> {
     written_committed_intersection: bool,
     written_candidate_intersection: bool,
     continue_ctx: back::continue_forward::ContinueCtx,
+    /// Writes `#line` directives, if [`PipelineOptions::debug_source`] is set.
+    line_directives: Option<back::LineDirectives>,
 
     /// A reference to some part of a global variable, lowered to a series of
     /// byte offset calculations.
@@ -573,4 +605,8 @@ pub struct Writer<'a, W> {
     /// [`AccessIndex`]: crate::Expression::AccessIndex
     temp_access_chain: Vec<storage::SubAccess>,
     need_bake_expressions: back::NeedBakeExpressions,
+    /// The format strings of the [`DebugPrintf`] statements written so far.
+    ///
+    /// [`DebugPrintf`]: crate::Statement::DebugPrintf
+    debug_printf_formats: Vec<String>,
 }
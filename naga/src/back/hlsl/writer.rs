@@ -1,6 +1,7 @@
 use alloc::{
     format,
     string::{String, ToString},
+    vec,
     vec::Vec,
 };
 use core::{fmt, mem};
@@ -34,6 +35,7 @@ pub(crate) const EXTRACT_BITS_FUNCTION: &str = "naga_extractBits";
 pub(crate) const INSERT_BITS_FUNCTION: &str = "naga_insertBits";
 pub(crate) const SAMPLER_HEAP_VAR: &str = "nagaSamplerHeap";
 pub(crate) const COMPARISON_SAMPLER_HEAP_VAR: &str = "nagaComparisonSamplerHeap";
+pub(crate) const DEBUG_PRINTF_PREFIX: &str = "nagaDebugPrintf";
 pub(crate) const ABS_FUNCTION: &str = "naga_abs";
 pub(crate) const DIV_FUNCTION: &str = "naga_div";
 pub(crate) const MOD_FUNCTION: &str = "naga_mod";
@@ -44,6 +46,11 @@ pub(crate) const F2I64_FUNCTION: &str = "naga_f2i64";
 pub(crate) const F2U64_FUNCTION: &str = "naga_f2u64";
 pub(crate) const IMAGE_SAMPLE_BASE_CLAMP_TO_EDGE_FUNCTION: &str =
     "nagaTextureSampleBaseClampToEdge";
+/// The ray payload parameter of miss and hit entry points, copied to and from
+/// the incoming ray payload global variable.
+pub(crate) const RAY_PAYLOAD_VAR: &str = "nagaRayPayload";
+/// The intersection attributes parameter of hit entry points.
+pub(crate) const RAY_ATTRIBUTES_VAR: &str = "nagaRayAttributes";
 
 enum Index {
     Expression(Handle<crate::Expression>),
@@ -106,6 +113,56 @@ enum Io {
     Output,
 }
 
+/// Returns the HLSL intrinsic reading `built_in` in a ray tracing stage, or
+/// `None` if it is read from the intersection attributes.
+const fn ray_tracing_built_in_intrinsic(built_in: crate::BuiltIn) -> Option<&'static str> {
+    use crate::BuiltIn as Bi;
+
+    Some(match built_in {
+        Bi::LaunchId => "DispatchRaysIndex()",
+        Bi::LaunchSize => "DispatchRaysDimensions()",
+        Bi::WorldRayOrigin => "WorldRayOrigin()",
+        Bi::WorldRayDirection => "WorldRayDirection()",
+        Bi::ObjectRayOrigin => "ObjectRayOrigin()",
+        Bi::ObjectRayDirection => "ObjectRayDirection()",
+        Bi::RayTMin => "RayTMin()",
+        Bi::RayTCurrentMax => "RayTCurrent()",
+        Bi::IncomingRayFlags => "RayFlags()",
+        Bi::InstanceCustomData => "InstanceID()",
+        Bi::InstanceIndex => "InstanceIndex()",
+        Bi::PrimitiveIndex => "PrimitiveIndex()",
+        Bi::GeometryIndex => "GeometryIndex()",
+        Bi::HitKind => "HitKind()",
+        Bi::ObjectToWorld => "ObjectToWorld4x3()",
+        Bi::WorldToObject => "WorldToObject4x3()",
+        _ => return None,
+    })
+}
+
+const fn is_ray_tracing_builtin_binding(binding: &Option<crate::Binding>) -> bool {
+    let &Some(crate::Binding::BuiltIn(builtin)) = binding else {
+        return false;
+    };
+    matches!(
+        builtin,
+        crate::BuiltIn::LaunchId
+            | crate::BuiltIn::LaunchSize
+            | crate::BuiltIn::WorldRayOrigin
+            | crate::BuiltIn::WorldRayDirection
+            | crate::BuiltIn::ObjectRayOrigin
+            | crate::BuiltIn::ObjectRayDirection
+            | crate::BuiltIn::RayTMin
+            | crate::BuiltIn::RayTCurrentMax
+            | crate::BuiltIn::IncomingRayFlags
+            | crate::BuiltIn::InstanceCustomData
+            | crate::BuiltIn::GeometryIndex
+            | crate::BuiltIn::ObjectToWorld
+            | crate::BuiltIn::WorldToObject
+            | crate::BuiltIn::HitKind
+            | crate::BuiltIn::Barycentrics
+    )
+}
+
 const fn is_subgroup_builtin_binding(binding: &Option<crate::Binding>) -> bool {
     let &Some(crate::Binding::BuiltIn(builtin)) = binding else {
         return false;
@@ -143,8 +200,13 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             written_committed_intersection: false,
             written_candidate_intersection: false,
             continue_ctx: back::continue_forward::ContinueCtx::default(),
+            line_directives: pipeline_options
+                .debug_source
+                .as_ref()
+                .map(back::LineDirectives::new),
             temp_access_chain: Vec::new(),
             need_bake_expressions: Default::default(),
+            debug_printf_formats: Vec::new(),
         }
     }
 
@@ -164,6 +226,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         self.written_candidate_intersection = false;
         self.continue_ctx.clear();
         self.need_bake_expressions.clear();
+        self.debug_printf_formats.clear();
     }
 
     /// Generates statements to be inserted immediately before and at the very
@@ -316,6 +379,19 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             writeln!(self.out)?;
         }
 
+        if let Some(ref bt) = self.options.debug_printf_binding {
+            write!(
+                self.out,
+                "RWByteAddressBuffer {DEBUG_PRINTF_PREFIX}Buffer : register(u{}",
+                bt.register
+            )?;
+            if bt.space != 0 {
+                write!(self.out, ", space{}", bt.space)?;
+            }
+            writeln!(self.out, ");")?;
+            writeln!(self.out)?;
+        }
+
         for (group, bt) in self.options.dynamic_storage_buffer_offsets_targets.iter() {
             writeln!(self.out, "struct __dynamic_buffer_offsetsTy{} {{", group)?;
             for i in 0..bt.size {
@@ -404,6 +480,16 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         let ep_range = get_entry_points(module, self.pipeline_options.entry_point.as_ref())
             .map_err(|(stage, name)| Error::EntryPointNotFound(stage, name))?;
 
+        if self.options.shader_model < ShaderModel::V6_3
+            && module.entry_points[ep_range.clone()]
+                .iter()
+                .any(|ep| ep.stage.is_ray_tracing())
+        {
+            return Err(Error::Custom(
+                "ray tracing stages require shader model 6.3 or later".to_string(),
+            ));
+        }
+
         // Write all entry points wrapped structs
         for index in ep_range.clone() {
             let ep = &module.entry_points[index];
@@ -505,6 +591,17 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 )?;
             }
 
+            let ray_tracing_stage = match ep.stage {
+                ShaderStage::RayGeneration => Some("raygeneration"),
+                ShaderStage::Miss => Some("miss"),
+                ShaderStage::ClosestHit => Some("closesthit"),
+                ShaderStage::AnyHit => Some("anyhit"),
+                _ => None,
+            };
+            if let Some(ray_tracing_stage) = ray_tracing_stage {
+                writeln!(self.out, "[shader(\"{ray_tracing_stage}\")]")?;
+            }
+
             let name = self.names[&NameKey::EntryPoint(index as u16)].clone();
             self.write_function(module, &name, &ep.function, &ctx, info)?;
 
@@ -517,6 +614,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
         Ok(super::ReflectionInfo {
             entry_point_names: translated_ep_names,
+            debug_printf_formats: mem::take(&mut self.debug_printf_formats),
         })
     }
 
@@ -556,7 +654,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         stage: Option<(ShaderStage, Io)>,
     ) -> BackendResult {
         match *binding {
-            Some(crate::Binding::BuiltIn(builtin)) if !is_subgroup_builtin_binding(binding) => {
+            Some(crate::Binding::BuiltIn(builtin))
+                if !is_subgroup_builtin_binding(binding)
+                    && !is_ray_tracing_builtin_binding(binding) =>
+            {
                 let builtin_str = builtin.to_hlsl_str()?;
                 write!(self.out, " : {builtin_str}")?;
             }
@@ -893,6 +994,121 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Returns the incoming ray payload global variable used by the current
+    /// function, if any.
+    fn incoming_ray_payload(
+        module: &Module,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> Option<Handle<crate::GlobalVariable>> {
+        module.global_variables.iter().find_map(|(handle, global)| {
+            (global.space == crate::AddressSpace::IncomingRayPayload
+                && !func_ctx.info[handle].is_empty())
+            .then_some(handle)
+        })
+    }
+
+    /// Writes the parameters of a ray tracing entry point.
+    ///
+    /// HLSL passes the ray payload and the intersection attributes as
+    /// parameters, while Naga reads them from global variables and builtins.
+    /// The globals are initialized from the parameters by
+    /// [`Self::write_ray_tracing_ep_initialization`].
+    fn write_ray_tracing_ep_parameters(
+        &mut self,
+        module: &Module,
+        func_ctx: &back::FunctionCtx<'_>,
+        ep_index: u16,
+    ) -> BackendResult {
+        let stage = module.entry_points[ep_index as usize].stage;
+        if stage == ShaderStage::RayGeneration {
+            return Ok(());
+        }
+        let Some(payload) = Self::incoming_ray_payload(module, func_ctx) else {
+            return Err(Error::Custom(format!(
+                "{stage:?} entry points must use an incoming ray payload"
+            )));
+        };
+        write!(self.out, "inout ")?;
+        self.write_type(module, module.global_variables[payload].ty)?;
+        write!(self.out, " {RAY_PAYLOAD_VAR}")?;
+        if matches!(stage, ShaderStage::ClosestHit | ShaderStage::AnyHit) {
+            write!(
+                self.out,
+                ", in BuiltInTriangleIntersectionAttributes {RAY_ATTRIBUTES_VAR}"
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Initializes the builtin arguments and the incoming ray payload of a
+    /// ray tracing entry point.
+    fn write_ray_tracing_ep_initialization(
+        &mut self,
+        module: &Module,
+        func: &crate::Function,
+        func_ctx: &back::FunctionCtx<'_>,
+        ep_index: u16,
+    ) -> BackendResult {
+        let write_value = |out: &mut W, binding: &Option<crate::Binding>| -> BackendResult {
+            let Some(crate::Binding::BuiltIn(built_in)) = *binding else {
+                unreachable!("ray tracing stages only have builtin inputs");
+            };
+            match ray_tracing_built_in_intrinsic(built_in) {
+                Some(intrinsic) => write!(out, "{intrinsic}")?,
+                None => write!(out, "{RAY_ATTRIBUTES_VAR}.barycentrics")?,
+            }
+            Ok(())
+        };
+
+        for (arg_index, arg) in func.arguments.iter().enumerate() {
+            write!(self.out, "{}", back::INDENT)?;
+            self.write_type(module, arg.ty)?;
+            let arg_name = &self.names[&NameKey::EntryPointArgument(ep_index, arg_index as u32)];
+            write!(self.out, " {arg_name} = ")?;
+            match module.types[arg.ty].inner {
+                TypeInner::Struct { ref members, .. } => {
+                    write!(self.out, "{{ ")?;
+                    for (index, member) in members.iter().enumerate() {
+                        if index != 0 {
+                            write!(self.out, ", ")?;
+                        }
+                        write_value(&mut self.out, &member.binding)?;
+                    }
+                    write!(self.out, " }}")?;
+                }
+                _ => write_value(&mut self.out, &arg.binding)?,
+            }
+            writeln!(self.out, ";")?;
+        }
+
+        if let Some(payload) = Self::incoming_ray_payload(module, func_ctx) {
+            let name = &self.names[&NameKey::GlobalVariable(payload)];
+            writeln!(self.out, "{}{name} = {RAY_PAYLOAD_VAR};", back::INDENT)?;
+        }
+        Ok(())
+    }
+
+    /// Copies the incoming ray payload global variable back to the payload
+    /// parameter before a ray tracing entry point returns.
+    fn write_incoming_ray_payload_copy_out(
+        &mut self,
+        module: &Module,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> BackendResult {
+        let back::FunctionType::EntryPoint(ep_index) = func_ctx.ty else {
+            return Ok(());
+        };
+        if module.entry_points[ep_index as usize].stage == ShaderStage::RayGeneration {
+            return Ok(());
+        }
+        if let Some(payload) = Self::incoming_ray_payload(module, func_ctx) {
+            let name = &self.names[&NameKey::GlobalVariable(payload)];
+            writeln!(self.out, "{level}{RAY_PAYLOAD_VAR} = {name};")?;
+        }
+        Ok(())
+    }
+
     /// Helper method used to write global variables
     /// # Notes
     /// Always adds a newline
@@ -931,7 +1147,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         // https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-variable-register
         let register_ty = match global.space {
             crate::AddressSpace::Function => unreachable!("Function address space"),
-            crate::AddressSpace::Private => {
+            crate::AddressSpace::Private
+            | crate::AddressSpace::RayPayload
+            | crate::AddressSpace::IncomingRayPayload => {
                 write!(self.out, "static ")?;
                 self.write_type(module, global.ty)?;
                 ""
@@ -1039,7 +1257,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             if let TypeInner::Array { base, size, .. } = module.types[global.ty].inner {
                 self.write_array_size(module, base, size)?;
             }
-            if global.space == crate::AddressSpace::Private {
+            if matches!(
+                global.space,
+                crate::AddressSpace::Private
+                    | crate::AddressSpace::RayPayload
+                    | crate::AddressSpace::IncomingRayPayload
+            ) {
                 write!(self.out, " = ")?;
                 if let Some(init) = global.init {
                     self.write_const_expression(module, init, &module.global_expressions)?;
@@ -1507,6 +1730,13 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     }
                 }
             }
+            back::FunctionType::EntryPoint(ep_index)
+                if module.entry_points[ep_index as usize]
+                    .stage
+                    .is_ray_tracing() =>
+            {
+                self.write_ray_tracing_ep_parameters(module, func_ctx, ep_index)?;
+            }
             back::FunctionType::EntryPoint(ep_index) => {
                 if let Some(ref ep_input) =
                     self.entry_point_io.get(&(ep_index as usize)).unwrap().input
@@ -1567,6 +1797,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
         if let back::FunctionType::EntryPoint(index) = func_ctx.ty {
             self.write_ep_arguments_initialization(module, func, index)?;
+            if module.entry_points[index as usize].stage.is_ray_tracing() {
+                self.write_ray_tracing_ep_initialization(module, func, func_ctx, index)?;
+            }
         }
 
         // Write function local variables
@@ -1606,9 +1839,25 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         }
 
         // Write the function body (statement list)
-        for sta in func.body.iter() {
+        for (sta, &span) in func.body.span_iter() {
             // The indentation should always be 1 when writing the function body
-            self.write_stmt(module, sta, func_ctx, back::Level(1))?;
+            self.write_stmt(module, sta, span, func_ctx, back::Level(1))?;
+        }
+
+        // The payload is also copied out when the entry point returns
+        // explicitly, see `write_incoming_ray_payload_copy_out`.
+        let falls_through = !matches!(
+            func.body.last(),
+            Some(
+                &crate::Statement::Return { .. }
+                    | &crate::Statement::RayPipelineFunction(
+                        crate::RayPipelineFunction::IgnoreIntersection
+                            | crate::RayPipelineFunction::TerminateRay
+                    )
+            )
+        );
+        if falls_through {
+            self.write_incoming_ray_payload_copy_out(module, func_ctx, back::Level(1))?;
         }
 
         writeln!(self.out, "}}")?;
@@ -1691,8 +1940,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
             // Body
             if let Some(case) = cases.last() {
-                for sta in case.body.iter() {
-                    self.write_stmt(module, sta, func_ctx, indent_level_1)?;
+                for (sta, &span) in case.body.span_iter() {
+                    self.write_stmt(module, sta, span, func_ctx, indent_level_1)?;
                 }
             }
             // End do-while
@@ -1757,8 +2006,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     for case in &cases[i..=end_case_idx] {
                         writeln!(self.out, "{indent_level_2}{{")?;
                         let prev_len = self.named_expressions.len();
-                        for sta in case.body.iter() {
-                            self.write_stmt(module, sta, func_ctx, indent_level_3)?;
+                        for (sta, &span) in case.body.span_iter() {
+                            self.write_stmt(module, sta, span, func_ctx, indent_level_3)?;
                         }
                         // Clear all named expressions that were previously inserted by the statements in the block
                         self.named_expressions.truncate(prev_len);
@@ -1770,8 +2019,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         writeln!(self.out, "{indent_level_2}break;")?;
                     }
                 } else {
-                    for sta in case.body.iter() {
-                        self.write_stmt(module, sta, func_ctx, indent_level_2)?;
+                    for (sta, &span) in case.body.span_iter() {
+                        self.write_stmt(module, sta, span, func_ctx, indent_level_2)?;
                     }
                     if !case.fall_through && case.body.last().is_none_or(|s| !s.is_terminator()) {
                         writeln!(self.out, "{indent_level_2}break;")?;
@@ -1827,11 +2076,16 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         &mut self,
         module: &Module,
         stmt: &crate::Statement,
+        span: crate::Span,
         func_ctx: &back::FunctionCtx<'_>,
         level: back::Level,
     ) -> BackendResult {
         use crate::Statement;
 
+        if let Some(ref line_directives) = self.line_directives {
+            line_directives.write(&mut self.out, level, span)?;
+        }
+
         match *stmt {
             Statement::Emit(ref range) => {
                 for handle in range.clone() {
@@ -1863,9 +2117,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             Statement::Block(ref block) => {
                 write!(self.out, "{level}")?;
                 writeln!(self.out, "{{")?;
-                for sta in block.iter() {
+                for (sta, &span) in block.span_iter() {
                     // Increase the indentation to help with readability
-                    self.write_stmt(module, sta, func_ctx, level.next())?
+                    self.write_stmt(module, sta, span, func_ctx, level.next())?
                 }
                 writeln!(self.out, "{level}}}")?
             }
@@ -1881,9 +2135,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 writeln!(self.out, ") {{")?;
 
                 let l2 = level.next();
-                for sta in accept {
+                for (sta, &span) in accept.span_iter() {
                     // Increase indentation to help with readability
-                    self.write_stmt(module, sta, func_ctx, l2)?;
+                    self.write_stmt(module, sta, span, func_ctx, l2)?;
                 }
 
                 // If there are no statements in the reject block we skip writing it
@@ -1891,9 +2145,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 if !reject.is_empty() {
                     writeln!(self.out, "{level}}} else {{")?;
 
-                    for sta in reject {
+                    for (sta, &span) in reject.span_iter() {
                         // Increase indentation to help with readability
-                        self.write_stmt(module, sta, func_ctx, l2)?;
+                        self.write_stmt(module, sta, span, func_ctx, l2)?;
                     }
                 }
 
@@ -1902,6 +2156,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             // TODO: copy-paste from glsl-out
             Statement::Kill => writeln!(self.out, "{level}discard;")?,
             Statement::Return { value: None } => {
+                self.write_incoming_ray_payload_copy_out(module, func_ctx, level)?;
                 writeln!(self.out, "{level}return;")?;
             }
             Statement::Return { value: Some(expr) } => {
@@ -2196,8 +2451,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 if let Some(gate_name) = gate_name {
                     writeln!(self.out, "{l2}if (!{gate_name}) {{")?;
                     let l3 = l2.next();
-                    for sta in continuing.iter() {
-                        self.write_stmt(module, sta, func_ctx, l3)?;
+                    for (sta, &span) in continuing.span_iter() {
+                        self.write_stmt(module, sta, span, func_ctx, l3)?;
                     }
                     if let Some(condition) = break_if {
                         write!(self.out, "{l3}if (")?;
@@ -2210,8 +2465,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     writeln!(self.out, "{l2}{gate_name} = false;")?;
                 }
 
-                for sta in body.iter() {
-                    self.write_stmt(module, sta, func_ctx, l2)?;
+                for (sta, &span) in body.span_iter() {
+                    self.write_stmt(module, sta, span, func_ctx, l2)?;
                 }
 
                 writeln!(self.out, "{level}}}")?;
@@ -2340,23 +2595,40 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     }
                     crate::AddressSpace::Storage { .. } => {
                         let var_handle = self.fill_access_chain(module, pointer, func_ctx)?;
-                        let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
-                        let width = match func_ctx.resolve_type(value, &module.types) {
-                            &TypeInner::Scalar(Scalar { width: 8, .. }) => "64",
-                            _ => "",
+                        let var_name = self.names[&NameKey::GlobalVariable(var_handle)].clone();
+                        // HLSL has no atomic operations on floats, they are emulated.
+                        let width = match *func_ctx.resolve_type(value, &module.types) {
+                            TypeInner::Scalar(Scalar {
+                                kind: ScalarKind::Float,
+                                ..
+                            }) => None,
+                            TypeInner::Scalar(Scalar { width: 8, .. }) => Some("64"),
+                            _ => Some(""),
                         };
-                        write!(self.out, "{var_name}.Interlocked{fun_str}{width}(")?;
-                        let chain = mem::take(&mut self.temp_access_chain);
-                        self.write_storage_address(module, &chain, func_ctx)?;
-                        self.temp_access_chain = chain;
-                        self.emit_hlsl_atomic_tail(
-                            module,
-                            func_ctx,
-                            fun,
-                            compare_expr,
-                            value,
-                            &res_var_info,
-                        )?;
+                        if let Some(width) = width {
+                            write!(self.out, "{var_name}.Interlocked{fun_str}{width}(")?;
+                            let chain = mem::take(&mut self.temp_access_chain);
+                            self.write_storage_address(module, &chain, func_ctx)?;
+                            self.temp_access_chain = chain;
+                            self.emit_hlsl_atomic_tail(
+                                module,
+                                func_ctx,
+                                fun,
+                                compare_expr,
+                                value,
+                                &res_var_info,
+                            )?;
+                        } else {
+                            self.write_storage_float_atomic(
+                                module,
+                                func_ctx,
+                                &var_name,
+                                fun,
+                                value,
+                                &res_var_info,
+                                level,
+                            )?;
+                        }
                     }
                     ref other => {
                         return Err(Error::Custom(format!(
@@ -2415,6 +2687,41 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             } => {
                 self.write_switch(module, func_ctx, level, selector, cases)?;
             }
+            Statement::RayPipelineFunction(ref fun) => match *fun {
+                crate::RayPipelineFunction::TraceRay {
+                    acceleration_structure,
+                    descriptor,
+                    sbt_record_offset,
+                    sbt_record_stride,
+                    miss_index,
+                    payload,
+                } => {
+                    write!(self.out, "{level}TraceRay(")?;
+                    self.write_expr(module, acceleration_structure, func_ctx)?;
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, descriptor, func_ctx)?;
+                    write!(self.out, ".flags, ")?;
+                    self.write_expr(module, descriptor, func_ctx)?;
+                    write!(self.out, ".cull_mask, ")?;
+                    for operand in [sbt_record_offset, sbt_record_stride, miss_index] {
+                        self.write_expr(module, operand, func_ctx)?;
+                        write!(self.out, ", ")?;
+                    }
+                    write!(self.out, "RayDescFromRayDesc_(")?;
+                    self.write_expr(module, descriptor, func_ctx)?;
+                    write!(self.out, "), ")?;
+                    self.write_expr(module, payload, func_ctx)?;
+                    writeln!(self.out, ");")?;
+                }
+                crate::RayPipelineFunction::IgnoreIntersection => {
+                    self.write_incoming_ray_payload_copy_out(module, func_ctx, level)?;
+                    writeln!(self.out, "{level}IgnoreHit();")?;
+                }
+                crate::RayPipelineFunction::TerminateRay => {
+                    self.write_incoming_ray_payload_copy_out(module, func_ctx, level)?;
+                    writeln!(self.out, "{level}AcceptHitAndEndSearch();")?;
+                }
+            },
             Statement::RayQuery { query, ref fun } => match *fun {
                 RayQueryFunction::Initialize {
                     acceleration_structure,
@@ -2607,6 +2914,75 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 }
                 writeln!(self.out, ");")?;
             }
+            Statement::DebugPrintf {
+                ref format,
+                ref arguments,
+            } => {
+                if self.options.debug_printf_binding.is_none() {
+                    return Err(Error::Custom(
+                        "debugPrintf requires a `debug_printf_binding`".to_string(),
+                    ));
+                }
+                let format_index =
+                    self.options.debug_printf_first_format + self.debug_printf_formats.len() as u32;
+                self.debug_printf_formats.push(format.clone());
+
+                let buffer = format!("{DEBUG_PRINTF_PREFIX}Buffer");
+                let l2 = level.next();
+                writeln!(self.out, "{level}{{")?;
+
+                // Evaluate the arguments before reserving space, and split
+                // vectors into words.
+                let mut words = vec![format!("{format_index}u")];
+                for (index, &argument) in arguments.iter().enumerate() {
+                    let name = format!("{DEBUG_PRINTF_PREFIX}Arg{index}");
+                    match *func_ctx.resolve_type(argument, &module.types) {
+                        TypeInner::Vector { size, .. } => {
+                            write!(
+                                self.out,
+                                "{l2}uint{} {name} = asuint(",
+                                common::vector_size_str(size)
+                            )?;
+                            words.extend(
+                                back::COMPONENTS[..size as usize]
+                                    .iter()
+                                    .map(|component| format!("{name}.{component}")),
+                            );
+                        }
+                        _ => {
+                            write!(self.out, "{l2}uint {name} = asuint(")?;
+                            words.push(name);
+                        }
+                    }
+                    self.write_expr(module, argument, func_ctx)?;
+                    writeln!(self.out, ");")?;
+                }
+
+                let header = back::DEBUG_PRINTF_HEADER_WORDS;
+                let length = words.len() + 1;
+                writeln!(self.out, "{l2}uint {DEBUG_PRINTF_PREFIX}Offset;")?;
+                writeln!(
+                    self.out,
+                    "{l2}{buffer}.InterlockedAdd(0, {length}u, {DEBUG_PRINTF_PREFIX}Offset);"
+                )?;
+                writeln!(
+                    self.out,
+                    "{l2}uint {DEBUG_PRINTF_PREFIX}Mask = {buffer}.Load(4) - 1u;"
+                )?;
+                for (index, word) in words.iter().enumerate() {
+                    writeln!(
+                        self.out,
+                        "{l2}{buffer}.Store(({header}u + (({DEBUG_PRINTF_PREFIX}Offset + {}u) & {DEBUG_PRINTF_PREFIX}Mask)) * 4u, {word});",
+                        index + 1
+                    )?;
+                }
+                // The length goes last, to mark the record as complete.
+                writeln!(
+                    self.out,
+                    "{l2}{buffer}.Store(({header}u + ({DEBUG_PRINTF_PREFIX}Offset & {DEBUG_PRINTF_PREFIX}Mask)) * 4u, {length}u);"
+                )?;
+                writeln!(self.out, "{level}}}")?;
+            }
         }
 
         Ok(())
@@ -2936,7 +3312,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                                 crate::AddressSpace::Function
                                 | crate::AddressSpace::Private
                                 | crate::AddressSpace::WorkGroup
-                                | crate::AddressSpace::PushConstant,
+                                | crate::AddressSpace::PushConstant
+                                | crate::AddressSpace::RayPayload
+                                | crate::AddressSpace::IncomingRayPayload,
                             )
                             | None => true,
                             Some(crate::AddressSpace::Uniform) => {
@@ -4298,6 +4676,79 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
     }
 
     /// Helper to emit the shared tail of an HLSL atomic call (arguments, value, result)
+    /// Write an atomic operation on an `f32` in a storage buffer.
+    ///
+    /// HLSL only has atomic operations on integers, so `Add` and `Subtract` are
+    /// emulated with a compare-and-swap loop on the bits of the float, and
+    /// `Exchange` exchanges its bits.
+    #[allow(clippy::too_many_arguments)]
+    fn write_storage_float_atomic(
+        &mut self,
+        module: &Module,
+        func_ctx: &back::FunctionCtx<'_>,
+        var_name: &str,
+        fun: &crate::AtomicFunction,
+        value: Handle<crate::Expression>,
+        res_var_info: &Option<(Handle<crate::Expression>, String)>,
+        level: back::Level,
+    ) -> BackendResult {
+        let address = self.namer.call("atomic_address");
+        let operand = self.namer.call("atomic_operand");
+        let old = self.namer.call("atomic_old");
+        let l2 = level.next();
+
+        writeln!(self.out, "{{")?;
+        write!(self.out, "{l2}uint {address} = ")?;
+        let chain = mem::take(&mut self.temp_access_chain);
+        self.write_storage_address(module, &chain, func_ctx)?;
+        self.temp_access_chain = chain;
+        writeln!(self.out, ";")?;
+        write!(self.out, "{l2}float {operand} = ")?;
+        if let crate::AtomicFunction::Subtract = *fun {
+            write!(self.out, "-")?;
+        }
+        self.write_expr(module, value, func_ctx)?;
+        writeln!(self.out, ";")?;
+        writeln!(self.out, "{l2}uint {old};")?;
+        match *fun {
+            crate::AtomicFunction::Add | crate::AtomicFunction::Subtract => {
+                let expected = self.namer.call("atomic_expected");
+                let l3 = l2.next();
+                writeln!(
+                    self.out,
+                    "{l2}uint {expected} = {var_name}.Load({address});"
+                )?;
+                writeln!(self.out, "{l2}while(true) {{")?;
+                writeln!(
+                    self.out,
+                    "{l3}{var_name}.InterlockedCompareExchange({address}, {expected}, \
+                     asuint(asfloat({expected}) + {operand}), {old});"
+                )?;
+                writeln!(self.out, "{l3}if ({old} == {expected}) {{")?;
+                writeln!(self.out, "{}break;", l3.next())?;
+                writeln!(self.out, "{l3}}}")?;
+                writeln!(self.out, "{l3}{expected} = {old};")?;
+                writeln!(self.out, "{l2}}}")?;
+            }
+            crate::AtomicFunction::Exchange { compare: None } => {
+                writeln!(
+                    self.out,
+                    "{l2}{var_name}.InterlockedExchange({address}, asuint({operand}), {old});"
+                )?;
+            }
+            ref other => {
+                return Err(Error::Custom(format!(
+                    "atomic function {other:?} is not supported on floats"
+                )));
+            }
+        }
+        if let Some(&(_, ref res_name)) = res_var_info.as_ref() {
+            writeln!(self.out, "{l2}{res_name} = asfloat({old});")?;
+        }
+        writeln!(self.out, "{level}}}")?;
+        Ok(())
+    }
+
     fn emit_hlsl_atomic_tail(
         &mut self,
         module: &Module,
@@ -86,6 +86,96 @@ impl core::fmt::Display for Level {
     }
 }
 
+/// The source code of a module, for backends to refer to in `#line` directives.
+///
+/// The spans of the module must be offsets in `source_code`, which is usually the WGSL
+/// the module was parsed from. Shader debuggers like RenderDoc and Xcode then show that
+/// source instead of the generated code.
+#[cfg(any(hlsl_out, msl_out))]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct DebugSource {
+    /// The name of the source file, written in the directives.
+    pub file_name: String,
+    /// The source code the spans of the module are offsets in.
+    pub source_code: String,
+}
+
+/// Writes `#line` directives pointing at the lines of a [`DebugSource`].
+#[cfg(any(hlsl_out, msl_out))]
+#[derive(Debug)]
+struct LineDirectives {
+    /// The file name, quoted and escaped for the directives.
+    file_name: String,
+    /// The offset of the start of each line of the source code.
+    line_starts: alloc::vec::Vec<usize>,
+}
+
+#[cfg(any(hlsl_out, msl_out))]
+impl LineDirectives {
+    fn new(source: &DebugSource) -> Self {
+        let mut file_name = String::with_capacity(source.file_name.len() + 2);
+        file_name.push('"');
+        for c in source.file_name.chars() {
+            match c {
+                '"' | '\\' => {
+                    file_name.push('\\');
+                    file_name.push(c);
+                }
+                '\n' | '\r' => file_name.push(' '),
+                _ => file_name.push(c),
+            }
+        }
+        file_name.push('"');
+
+        let line_starts = core::iter::once(0)
+            .chain(source.source_code.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self {
+            file_name,
+            line_starts,
+        }
+    }
+
+    /// Writes a directive for the line `span` starts on, so that the next line of the
+    /// output is attributed to it. Nothing is written for undefined spans.
+    fn write(
+        &self,
+        out: &mut impl core::fmt::Write,
+        level: Level,
+        span: crate::Span,
+    ) -> core::fmt::Result {
+        let Some(range) = span.to_range() else {
+            return Ok(());
+        };
+        let line = self
+            .line_starts
+            .partition_point(|&start| start <= range.start);
+        writeln!(out, "{level}#line {line} {}", self.file_name)
+    }
+}
+
+/// The number of words before the ring of a debug printf buffer.
+///
+/// Backends without a native way to print from shaders write each
+/// [`DebugPrintf`] statement's message into a storage buffer of `u32`s, for the
+/// host to format and log. The first word of the buffer is the number of words
+/// shaders have reserved so far, which wraps around, and the second one is the
+/// capacity of the ring that follows, a power of two set by the host.
+///
+/// A message is a record of consecutive words in the ring, wrapping around at
+/// its end: the length of the record including this header, the index of the
+/// format string, and the arguments, each vector component being one word.
+/// Shaders reserve a record by atomically adding its length to the first word,
+/// and write the length last, so the host knows that a record whose length is
+/// still zero is not complete yet.
+///
+/// [`DebugPrintf`]: crate::Statement::DebugPrintf
+#[cfg(any(hlsl_out, msl_out))]
+pub const DEBUG_PRINTF_HEADER_WORDS: u32 = 2;
+
 /// Locate the entry point(s) to write.
 ///
 /// If `entry_point` is given, and the specified entry point exists, returns a
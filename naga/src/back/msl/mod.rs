@@ -125,6 +125,13 @@ pub struct EntryPointResources {
     /// one for the size of each bound buffer that contains a runtime array,
     /// in order of [`crate::GlobalVariable`] declarations.
     pub sizes_buffer: Option<Slot>,
+
+    /// The slot of the buffer that [`DebugPrintf`] statements write their
+    /// messages to, laid out as described in [`DEBUG_PRINTF_HEADER_WORDS`].
+    ///
+    /// [`DebugPrintf`]: crate::Statement::DebugPrintf
+    /// [`DEBUG_PRINTF_HEADER_WORDS`]: crate::back::DEBUG_PRINTF_HEADER_WORDS
+    pub debug_printf_buffer: Option<Slot>,
 }
 
 pub type EntryPointResourceMap = alloc::collections::BTreeMap<String, EntryPointResources>;
@@ -215,6 +222,8 @@ pub enum EntryPointError {
     MissingPushConstants,
     #[error("mapping for sizes buffer is missing")]
     MissingSizesBuffer,
+    #[error("mapping for debug printf buffer is missing")]
+    MissingDebugPrintfBuffer,
 }
 
 /// Points in the MSL code where we might emit a pipeline input or output.
@@ -265,6 +274,12 @@ pub struct Options {
     /// If set, loops will have code injected into them, forcing the compiler
     /// to think the number of iterations is bounded.
     pub force_loop_bounding: bool,
+    /// The index of the first format string written for [`DebugPrintf`]
+    /// statements, the others following in
+    /// [`TranslationInfo::debug_printf_formats`] order.
+    ///
+    /// [`DebugPrintf`]: crate::Statement::DebugPrintf
+    pub debug_printf_first_format: u32,
 }
 
 impl Default for Options {
@@ -278,6 +293,7 @@ impl Default for Options {
             bounds_check_policies: index::BoundsCheckPolicies::default(),
             zero_initialize_workgroup_memory: true,
             force_loop_bounding: true,
+            debug_printf_first_format: 0,
         }
     }
 }
@@ -412,6 +428,10 @@ pub struct VertexBufferMapping {
     /// True if the buffer is indexed by vertex, false if indexed
     /// by instance.
     pub indexed_by_vertex: bool,
+    /// Number of instances that share each element of a buffer indexed by
+    /// instance. Both 0 and 1 advance every instance.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub step_rate: u32,
     /// Vec of the attributes within the structure
     pub attributes: Vec<AttributeMapping>,
 }
@@ -451,6 +471,13 @@ pub struct PipelineOptions {
     /// vertex_buffer_mappings are used during shader translation to
     /// support vertex pulling.
     pub vertex_buffer_mappings: Vec<VertexBufferMapping>,
+
+    /// The source code the module was parsed from, for `#line` directives.
+    ///
+    /// If set, a `#line` directive pointing at the line of the source each statement
+    /// comes from is written before the statement, so that Xcode's shader debugger shows
+    /// that source. If `None`, no directives are written.
+    pub debug_source: Option<crate::back::DebugSource>,
 }
 
 impl Options {
@@ -601,6 +628,27 @@ impl Options {
             None => Err(EntryPointError::MissingSizesBuffer),
         }
     }
+
+    fn resolve_debug_printf_buffer(
+        &self,
+        ep: &crate::EntryPoint,
+    ) -> Result<ResolvedBinding, EntryPointError> {
+        let slot = self
+            .get_entry_point_resources(ep)
+            .and_then(|res| res.debug_printf_buffer);
+        match slot {
+            Some(slot) => Ok(ResolvedBinding::Resource(BindTarget {
+                buffer: Some(slot),
+                ..Default::default()
+            })),
+            None if self.fake_missing_bindings => Ok(ResolvedBinding::User {
+                prefix: "fake",
+                index: 0,
+                interpolation: None,
+            }),
+            None => Err(EntryPointError::MissingDebugPrintfBuffer),
+        }
+    }
 }
 
 impl ResolvedBinding {
@@ -617,6 +665,9 @@ impl ResolvedBinding {
     fn try_fmt<W: Write>(&self, out: &mut W) -> Result<(), Error> {
         write!(out, " [[")?;
         match *self {
+            Self::BuiltIn(crate::BuiltIn::FramebufferFetch { location }) => {
+                write!(out, "color({location})")?
+            }
             Self::BuiltIn(built_in) => {
                 use crate::BuiltIn as Bi;
                 let name = match built_in {
@@ -629,6 +680,8 @@ impl ResolvedBinding {
                     Bi::InstanceIndex => "instance_id",
                     Bi::PointSize => "point_size",
                     Bi::VertexIndex => "vertex_id",
+                    Bi::ViewportIndex => "viewport_array_index",
+                    Bi::Layer => "render_target_array_index",
                     // fragment
                     Bi::FragDepth => "depth(any)",
                     Bi::PointCoord => "point_coord",
@@ -648,7 +701,26 @@ impl ResolvedBinding {
                     Bi::SubgroupId => "simdgroup_index_in_threadgroup",
                     Bi::SubgroupSize => "threads_per_simdgroup",
                     Bi::SubgroupInvocationId => "thread_index_in_simdgroup",
-                    Bi::CullDistance | Bi::ViewIndex | Bi::DrawID => {
+                    Bi::FramebufferFetch { .. } => unreachable!(),
+                    Bi::CullDistance
+                    | Bi::ViewIndex
+                    | Bi::DrawID
+                    | Bi::FullyCovered
+                    | Bi::LaunchId
+                    | Bi::LaunchSize
+                    | Bi::WorldRayOrigin
+                    | Bi::WorldRayDirection
+                    | Bi::ObjectRayOrigin
+                    | Bi::ObjectRayDirection
+                    | Bi::RayTMin
+                    | Bi::RayTCurrentMax
+                    | Bi::IncomingRayFlags
+                    | Bi::InstanceCustomData
+                    | Bi::GeometryIndex
+                    | Bi::ObjectToWorld
+                    | Bi::WorldToObject
+                    | Bi::HitKind
+                    | Bi::Barycentrics => {
                         return Err(Error::UnsupportedBuiltIn(built_in));
                     }
                 };
@@ -733,6 +805,11 @@ pub struct TranslationInfo {
     ///
     ///Note: Some entry points may fail translation because of missing bindings.
     pub entry_point_names: Vec<Result<String, EntryPointError>>,
+    /// The format strings of the [`DebugPrintf`] statements that were written,
+    /// starting at index [`Options::debug_printf_first_format`].
+    ///
+    /// [`DebugPrintf`]: crate::Statement::DebugPrintf
+    pub debug_printf_formats: Vec<String>,
 }
 
 pub fn write_string(
@@ -123,6 +123,9 @@ const fn scalar_is_int(scalar: crate::Scalar) -> bool {
 /// Prefix for cached clamped level-of-detail values for `ImageLoad` expressions.
 const CLAMPED_LOD_LOAD_PREFIX: &str = "clamped_lod_e";
 
+/// Prefix for the buffer and temporaries of `DebugPrintf` statements.
+const DEBUG_PRINTF_PREFIX: &str = "naga_debug_printf";
+
 /// Prefix for reinterpreted expressions using `as_type<T>(...)`.
 const REINTERPRET_PREFIX: &str = "reinterpreted_";
 
@@ -288,6 +291,13 @@ impl Display for TypeContext<'_> {
                         let scalar = crate::Scalar { kind, width: 4 };
                         ("texture", msaa_str, scalar, access)
                     }
+                    crate::ImageClass::External => {
+                        let scalar = crate::Scalar {
+                            kind: crate::ScalarKind::Float,
+                            width: 4,
+                        };
+                        ("texture", "", scalar, "sample")
+                    }
                     crate::ImageClass::Depth { multi } => {
                         let (msaa_str, access) = if multi {
                             ("_ms", "read")
@@ -468,6 +478,15 @@ pub struct Writer<W> {
     /// Set of (struct type, struct field index) denoting which fields require
     /// padding inserted **before** them (i.e. between fields at index - 1 and index)
     struct_member_pads: FastHashSet<(Handle<crate::Type>, u32)>,
+    /// Writes `#line` directives, if [`PipelineOptions::debug_source`] is set.
+    line_directives: Option<back::LineDirectives>,
+    /// Functions that execute `DebugPrintf` statements, themselves or through
+    /// their callees, and so take the debug printf buffer as an argument.
+    debug_printf_functions: HandleSet<crate::Function>,
+    /// The index of the first format string, see [`Options::debug_printf_first_format`].
+    debug_printf_first_format: u32,
+    /// The format strings of the `DebugPrintf` statements written so far.
+    debug_printf_formats: Vec<String>,
 }
 
 impl crate::Scalar {
@@ -498,6 +517,22 @@ impl crate::Scalar {
                 kind: Sk::Uint,
                 width: 8,
             } => "ulong",
+            Self {
+                kind: Sk::Sint,
+                width: 1,
+            } => "char",
+            Self {
+                kind: Sk::Uint,
+                width: 1,
+            } => "uchar",
+            Self {
+                kind: Sk::Sint,
+                width: 2,
+            } => "short",
+            Self {
+                kind: Sk::Uint,
+                width: 2,
+            } => "ushort",
             Self {
                 kind: Sk::Bool,
                 width: _,
@@ -562,6 +597,30 @@ fn needs_array_length(ty: Handle<crate::Type>, arena: &crate::UniqueArena<crate:
     }
 }
 
+/// Returns true if `block` contains a `DebugPrintf` statement, or calls one of
+/// `functions`.
+fn uses_debug_printf(block: &crate::Block, functions: &HandleSet<crate::Function>) -> bool {
+    block.iter().any(|statement| match *statement {
+        crate::Statement::DebugPrintf { .. } => true,
+        crate::Statement::Call { function, .. } => functions.contains(function),
+        crate::Statement::Block(ref block) => uses_debug_printf(block, functions),
+        crate::Statement::If {
+            ref accept,
+            ref reject,
+            ..
+        } => uses_debug_printf(accept, functions) || uses_debug_printf(reject, functions),
+        crate::Statement::Switch { ref cases, .. } => cases
+            .iter()
+            .any(|case| uses_debug_printf(&case.body, functions)),
+        crate::Statement::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => uses_debug_printf(body, functions) || uses_debug_printf(continuing, functions),
+        _ => false,
+    })
+}
+
 impl crate::AddressSpace {
     /// Returns true if global variables in this address space are
     /// passed in function arguments. These arguments need to be
@@ -575,6 +634,8 @@ impl crate::AddressSpace {
             | Self::PushConstant
             | Self::Handle => true,
             Self::Function => false,
+            // Ray tracing stages are not supported.
+            Self::RayPayload | Self::IncomingRayPayload => false,
         }
     }
 
@@ -592,6 +653,7 @@ impl crate::AddressSpace {
             Self::Uniform | Self::PushConstant => false,
             // Not applicable.
             Self::Handle | Self::Function => false,
+            Self::RayPayload | Self::IncomingRayPayload => false,
         }
     }
 
@@ -602,6 +664,7 @@ impl crate::AddressSpace {
             Self::Storage { .. } => Some("device"),
             Self::Private | Self::Function => Some("thread"),
             Self::WorkGroup => Some("threadgroup"),
+            Self::RayPayload | Self::IncomingRayPayload => None,
         }
     }
 }
@@ -803,6 +866,10 @@ impl<W: Write> Writer<W> {
             #[cfg(test)]
             put_block_stack_pointers: Default::default(),
             struct_member_pads: FastHashSet::default(),
+            line_directives: None,
+            debug_printf_functions: HandleSet::new(),
+            debug_printf_first_format: 0,
+            debug_printf_formats: Vec::new(),
         }
     }
 
@@ -3507,7 +3574,7 @@ impl<W: Write> Writer<W> {
     fn put_block(
         &mut self,
         level: back::Level,
-        statements: &[crate::Statement],
+        statements: &crate::Block,
         context: &StatementContext,
     ) -> BackendResult {
         // Add to the set in order to track the stack size.
@@ -3515,8 +3582,11 @@ impl<W: Write> Writer<W> {
         self.put_block_stack_pointers
             .insert(ptr::from_ref(&level).cast());
 
-        for statement in statements {
+        for (statement, &span) in statements.span_iter() {
             log::trace!("statement[{}] {:?}", level.0, statement);
+            if let Some(ref line_directives) = self.line_directives {
+                line_directives.write(&mut self.out, level, span)?;
+            }
             match *statement {
                 crate::Statement::Emit(ref range) => {
                     for handle in range.clone() {
@@ -3791,9 +3861,17 @@ impl<W: Write> Writer<W> {
                     if needs_buffer_sizes {
                         if separate {
                             write!(self.out, ", ")?;
+                        } else {
+                            separate = true;
                         }
                         write!(self.out, "_buffer_sizes")?;
                     }
+                    if self.debug_printf_functions.contains(function) {
+                        if separate {
+                            write!(self.out, ", ")?;
+                        }
+                        write!(self.out, "{DEBUG_PRINTF_PREFIX}_buffer")?;
+                    }
 
                     // done
                     writeln!(self.out, ");")?;
@@ -3892,6 +3970,11 @@ impl<W: Write> Writer<W> {
                     writeln!(self.out, ";")?;
                     self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
                 }
+                crate::Statement::RayPipelineFunction(_) => {
+                    return Err(Error::FeatureNotImplemented(
+                        "ray tracing pipeline functions".to_string(),
+                    ));
+                }
                 crate::Statement::RayQuery { query, ref fun } => {
                     if context.expression.lang_version < (2, 4) {
                         return Err(Error::UnsupportedRayTracing);
@@ -4169,12 +4252,72 @@ impl<W: Write> Writer<W> {
                     }
                     writeln!(self.out, ");")?;
                 }
+                crate::Statement::DebugPrintf {
+                    ref format,
+                    ref arguments,
+                } => {
+                    let format_index =
+                        self.debug_printf_first_format + self.debug_printf_formats.len() as u32;
+                    self.debug_printf_formats.push(format.clone());
+
+                    let buffer = format!("{DEBUG_PRINTF_PREFIX}_buffer");
+                    let l2 = level.next();
+                    writeln!(self.out, "{level}{{")?;
+
+                    // Evaluate the arguments before reserving space, and split
+                    // vectors into words.
+                    let mut words = vec![format!("{format_index}u")];
+                    for (index, &argument) in arguments.iter().enumerate() {
+                        let name = format!("{DEBUG_PRINTF_PREFIX}_arg{index}");
+                        let ty_name = match *context.expression.resolve_type(argument) {
+                            crate::TypeInner::Vector { size, .. } => {
+                                words.extend(
+                                    back::COMPONENTS[..size as usize]
+                                        .iter()
+                                        .map(|component| format!("{name}.{component}")),
+                                );
+                                format!("{NAMESPACE}::uint{}", common::vector_size_str(size))
+                            }
+                            _ => {
+                                words.push(name.clone());
+                                "uint".to_string()
+                            }
+                        };
+                        write!(self.out, "{l2}{ty_name} {name} = as_type<{ty_name}>(")?;
+                        self.put_expression(argument, &context.expression, true)?;
+                        writeln!(self.out, ");")?;
+                    }
+
+                    let header = back::DEBUG_PRINTF_HEADER_WORDS;
+                    let length = words.len() + 1;
+                    writeln!(
+                        self.out,
+                        "{l2}uint {DEBUG_PRINTF_PREFIX}_offset = {NAMESPACE}::atomic_fetch_add_explicit((device {NAMESPACE}::atomic_uint*){buffer}, {length}u, {NAMESPACE}::memory_order_relaxed);"
+                    )?;
+                    writeln!(
+                        self.out,
+                        "{l2}uint {DEBUG_PRINTF_PREFIX}_mask = {buffer}[1] - 1u;"
+                    )?;
+                    for (index, word) in words.iter().enumerate() {
+                        writeln!(
+                            self.out,
+                            "{l2}{buffer}[{header}u + (({DEBUG_PRINTF_PREFIX}_offset + {}u) & {DEBUG_PRINTF_PREFIX}_mask)] = {word};",
+                            index + 1
+                        )?;
+                    }
+                    // The length goes last, to mark the record as complete.
+                    writeln!(
+                        self.out,
+                        "{l2}{buffer}[{header}u + ({DEBUG_PRINTF_PREFIX}_offset & {DEBUG_PRINTF_PREFIX}_mask)] = {length}u;"
+                    )?;
+                    writeln!(self.out, "{level}}}")?;
+                }
             }
         }
 
         // un-emit expressions
         //TODO: take care of loop/continuing?
-        for statement in statements {
+        for statement in statements.iter() {
             if let crate::Statement::Emit(ref range) = *statement {
                 for handle in range.clone() {
                     self.named_expressions.shift_remove(&handle);
@@ -4250,11 +4393,17 @@ impl<W: Write> Writer<W> {
             module,
             &super::keywords::RESERVED_SET,
             &[],
-            &[CLAMPED_LOD_LOAD_PREFIX],
+            &[CLAMPED_LOD_LOAD_PREFIX, DEBUG_PRINTF_PREFIX],
             &mut self.names,
         );
         self.wrapped_functions.clear();
         self.struct_member_pads.clear();
+        self.debug_printf_first_format = options.debug_printf_first_format;
+        self.debug_printf_formats.clear();
+        self.line_directives = pipeline_options
+            .debug_source
+            .as_ref()
+            .map(back::LineDirectives::new);
 
         writeln!(
             self.out,
@@ -5944,6 +6093,7 @@ template <typename A>
             id: u32,
             stride: u32,
             indexed_by_vertex: bool,
+            step_rate: u32,
             ty_name: String,
             param_name: String,
             elem_name: String,
@@ -5969,6 +6119,9 @@ template <typename A>
 
         let mut needs_instance_id = false;
         let i_id = self.namer.call("i_id");
+
+        let mut needs_base_instance = false;
+        let bi_id = self.namer.call("bi_id");
         if pipeline_options.vertex_pulling_transform {
             for vbm in &pipeline_options.vertex_buffer_mappings {
                 let buffer_id = vbm.id;
@@ -5983,6 +6136,7 @@ template <typename A>
                     needs_vertex_id = true;
                 } else {
                     needs_instance_id = true;
+                    needs_base_instance |= vbm.step_rate > 1;
                 }
 
                 let buffer_ty = self.namer.call(format!("vb_{buffer_id}_type").as_str());
@@ -5993,6 +6147,7 @@ template <typename A>
                     id: buffer_id,
                     stride: buffer_stride,
                     indexed_by_vertex: vbm.indexed_by_vertex,
+                    step_rate: vbm.step_rate,
                     ty_name: buffer_ty,
                     param_name: buffer_param,
                     elem_name: buffer_elem,
@@ -6023,6 +6178,14 @@ template <typename A>
             }
         }
 
+        // Callees come before their callers, so one pass is enough.
+        self.debug_printf_functions.clear();
+        for (fun_handle, fun) in module.functions.iter() {
+            if uses_debug_printf(&fun.body, &self.debug_printf_functions) {
+                self.debug_printf_functions.insert(fun_handle);
+            }
+        }
+
         let mut pass_through_globals = Vec::new();
         for (fun_handle, fun) in module.functions.iter() {
             log::trace!(
@@ -6042,6 +6205,7 @@ template <typename A>
             self.write_wrapped_functions(module, &ctx)?;
 
             let fun_info = &mod_info[fun_handle];
+            let needs_debug_printf = self.debug_printf_functions.contains(fun_handle);
             pass_through_globals.clear();
             let mut needs_buffer_sizes = false;
             for (handle, var) in module.global_variables.iter() {
@@ -6083,7 +6247,8 @@ template <typename A>
                 let separator = separate(
                     !pass_through_globals.is_empty()
                         || index + 1 != fun.arguments.len()
-                        || needs_buffer_sizes,
+                        || needs_buffer_sizes
+                        || needs_debug_printf,
                 );
                 writeln!(
                     self.out,
@@ -6103,8 +6268,11 @@ template <typename A>
 
                     reference: true,
                 };
-                let separator =
-                    separate(index + 1 != pass_through_globals.len() || needs_buffer_sizes);
+                let separator = separate(
+                    index + 1 != pass_through_globals.len()
+                        || needs_buffer_sizes
+                        || needs_debug_printf,
+                );
                 write!(self.out, "{}", back::INDENT)?;
                 tyvar.try_fmt(&mut self.out)?;
                 writeln!(self.out, "{separator}")?;
@@ -6113,7 +6281,15 @@ template <typename A>
             if needs_buffer_sizes {
                 writeln!(
                     self.out,
-                    "{}constant _mslBufferSizes& _buffer_sizes",
+                    "{}constant _mslBufferSizes& _buffer_sizes{}",
+                    back::INDENT,
+                    separate(needs_debug_printf)
+                )?;
+            }
+            if needs_debug_printf {
+                writeln!(
+                    self.out,
+                    "{}device uint* {DEBUG_PRINTF_PREFIX}_buffer",
                     back::INDENT
                 )?;
             }
@@ -6151,6 +6327,7 @@ template <typename A>
 
         let mut info = TranslationInfo {
             entry_point_names: Vec::with_capacity(ep_range.len()),
+            debug_printf_formats: Vec::new(),
         };
 
         for ep_index in ep_range {
@@ -6164,6 +6341,7 @@ template <typename A>
             // existing @builtin param, if we find one.
             let mut v_existing_id = None;
             let mut i_existing_id = None;
+            let mut bi_existing_id = None;
 
             log::trace!(
                 "entry point {:?}, index {:?}",
@@ -6200,6 +6378,14 @@ template <typename A>
                     false,
                 ),
                 crate::ShaderStage::Task | crate::ShaderStage::Mesh => unreachable!(),
+                crate::ShaderStage::RayGeneration
+                | crate::ShaderStage::Miss
+                | crate::ShaderStage::ClosestHit
+                | crate::ShaderStage::AnyHit => {
+                    return Err(Error::FeatureNotImplemented(
+                        "ray tracing stages".to_string(),
+                    ));
+                }
             };
 
             // Should this entry point be modified to do vertex pulling?
@@ -6207,6 +6393,9 @@ template <typename A>
                 && pipeline_options.vertex_pulling_transform
                 && !pipeline_options.vertex_buffer_mappings.is_empty();
 
+            // Does this entry point print anything, itself or through its callees?
+            let needs_debug_printf = uses_debug_printf(&fun.body, &self.debug_printf_functions);
+
             // Is any global variable used by this entry point dynamically sized?
             let needs_buffer_sizes = do_vertex_pulling
                 || module
@@ -6264,7 +6453,9 @@ template <typename A>
                         }
                         crate::AddressSpace::Function
                         | crate::AddressSpace::Private
-                        | crate::AddressSpace::WorkGroup => {}
+                        | crate::AddressSpace::WorkGroup
+                        | crate::AddressSpace::RayPayload
+                        | crate::AddressSpace::IncomingRayPayload => {}
                     }
                 }
                 if needs_buffer_sizes {
@@ -6272,6 +6463,11 @@ template <typename A>
                         ep_error = Some(err);
                     }
                 }
+                if needs_debug_printf {
+                    if let Err(err) = options.resolve_debug_printf_buffer(ep) {
+                        ep_error = Some(err);
+                    }
+                }
             }
 
             if let Some(err) = ep_error {
@@ -6525,6 +6721,9 @@ template <typename A>
                     crate::Binding::BuiltIn(crate::BuiltIn::InstanceIndex) => {
                         i_existing_id = Some(name.clone());
                     }
+                    crate::Binding::BuiltIn(crate::BuiltIn::BaseInstance) => {
+                        bi_existing_id = Some(name.clone());
+                    }
                     _ => {}
                 };
 
@@ -6635,6 +6834,7 @@ template <typename A>
                                 crate::TypeInner::Image { class, .. } => match class {
                                     crate::ImageClass::Sampled { .. }
                                     | crate::ImageClass::Depth { .. }
+                                    | crate::ImageClass::External
                                     | crate::ImageClass::Storage {
                                         access: crate::StorageAccess::LOAD,
                                         ..
@@ -6737,6 +6937,11 @@ template <typename A>
 
                 if needs_instance_id && i_existing_id.is_none() {
                     writeln!(self.out, "{separator} uint {i_id} [[instance_id]]")?;
+                    separator = ',';
+                }
+
+                if needs_base_instance && bi_existing_id.is_none() {
+                    writeln!(self.out, "{separator} uint {bi_id} [[base_instance]]")?;
                 }
 
                 // Iterate vbm_resolved, output one argument for every vertex buffer,
@@ -6764,6 +6969,19 @@ template <typename A>
                 )?;
                 resolved.try_fmt(&mut self.out)?;
                 writeln!(self.out)?;
+                is_first_argument = false;
+            }
+
+            if needs_debug_printf {
+                // this is checked earlier
+                let resolved = options.resolve_debug_printf_buffer(ep).unwrap();
+                let separator = if is_first_argument { ' ' } else { ',' };
+                write!(
+                    self.out,
+                    "{separator} device uint* {DEBUG_PRINTF_PREFIX}_buffer"
+                )?;
+                resolved.try_fmt(&mut self.out)?;
+                writeln!(self.out)?;
             }
 
             // end of the entry point argument list
@@ -6801,14 +7019,21 @@ template <typename A>
                     let stride = &vbm.stride;
                     let index_name = if vbm.indexed_by_vertex {
                         if let Some(ref name) = v_existing_id {
-                            name
+                            name.clone()
                         } else {
-                            &v_id
+                            v_id.clone()
                         }
-                    } else if let Some(ref name) = i_existing_id {
-                        name
                     } else {
-                        &i_id
+                        let i_name = i_existing_id.as_ref().unwrap_or(&i_id);
+                        if vbm.step_rate > 1 {
+                            // The instance ID includes the base instance, which
+                            // isn't divided by the step rate.
+                            let bi_name = bi_existing_id.as_ref().unwrap_or(&bi_id);
+                            let rate = vbm.step_rate;
+                            format!("({bi_name} + ({i_name} - {bi_name}) / {rate}u)")
+                        } else {
+                            i_name.clone()
+                        }
                     };
                     write!(
                         self.out,
@@ -7066,6 +7291,7 @@ template <typename A>
             self.named_expressions.clear();
         }
 
+        info.debug_printf_formats = core::mem::take(&mut self.debug_printf_formats);
         Ok(info)
     }
 
@@ -3630,6 +3630,12 @@ impl BlockContext<'_> {
                 Statement::RayQuery { query, ref fun } => {
                     self.write_ray_query_function(query, fun, &mut block);
                 }
+                Statement::RayPipelineFunction(ref fun) => {
+                    if let Some(terminator) = self.write_ray_pipeline_function(fun, &mut block) {
+                        self.function.consume(block, terminator);
+                        return Ok(BlockExitDisposition::Discarded);
+                    }
+                }
                 Statement::SubgroupBallot {
                     result,
                     ref predicate,
@@ -3651,6 +3657,29 @@ impl BlockContext<'_> {
                 } => {
                     self.write_subgroup_gather(mode, argument, result, &mut block)?;
                 }
+                Statement::DebugPrintf {
+                    ref format,
+                    ref arguments,
+                } => {
+                    let set_id = self.writer.get_debug_printf_ext_inst_id();
+                    // Unlike other debug instructions, the format string is needed
+                    // whether or not `WriterFlags::DEBUG` is set.
+                    let format_id = self.gen_id();
+                    Instruction::string(format, format_id)
+                        .to_words(&mut self.writer.logical_layout.debugs);
+
+                    self.temp_list.clear();
+                    self.temp_list
+                        .extend(arguments.iter().map(|&argument| self.cached[argument]));
+                    let id = self.gen_id();
+                    block.body.push(Instruction::debug_printf(
+                        set_id,
+                        self.writer.void_type,
+                        id,
+                        format_id,
+                        &self.temp_list,
+                    ));
+                }
             }
         }
 
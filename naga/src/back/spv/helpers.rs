@@ -51,9 +51,25 @@ pub(super) const fn map_storage_class(space: crate::AddressSpace) -> spirv::Stor
         crate::AddressSpace::Uniform => spirv::StorageClass::Uniform,
         crate::AddressSpace::WorkGroup => spirv::StorageClass::Workgroup,
         crate::AddressSpace::PushConstant => spirv::StorageClass::PushConstant,
+        crate::AddressSpace::RayPayload => spirv::StorageClass::RayPayloadKHR,
+        crate::AddressSpace::IncomingRayPayload => spirv::StorageClass::IncomingRayPayloadKHR,
     }
 }
 
+/// Returns whether `ir_module` has ray tracing stages or ray payloads.
+pub(super) fn uses_ray_tracing_pipeline(ir_module: &crate::Module) -> bool {
+    ir_module
+        .entry_points
+        .iter()
+        .any(|ep| ep.stage.is_ray_tracing())
+        || ir_module.global_variables.iter().any(|(_, var)| {
+            matches!(
+                var.space,
+                crate::AddressSpace::RayPayload | crate::AddressSpace::IncomingRayPayload
+            )
+        })
+}
+
 pub(super) fn contains_builtin(
     binding: Option<&crate::Binding>,
     ty: Handle<crate::Type>,
@@ -115,9 +115,9 @@ impl Load {
     ) -> Result<Load, Error> {
         let opcode = match image_class {
             crate::ImageClass::Storage { .. } => spirv::Op::ImageRead,
-            crate::ImageClass::Depth { .. } | crate::ImageClass::Sampled { .. } => {
-                spirv::Op::ImageFetch
-            }
+            crate::ImageClass::Depth { .. }
+            | crate::ImageClass::Sampled { .. }
+            | crate::ImageClass::External => spirv::Op::ImageFetch,
         };
 
         // `OpImageRead` and `OpImageFetch` instructions produce vec4<f32>
@@ -840,7 +840,18 @@ impl BlockContext<'_> {
         let sampled_image_type_id =
             self.get_type_id(LookupType::Local(LocalType::SampledImage { image_type_id }));
 
-        let sampler_id = self.get_handle_id(sampler);
+        // Images bound as combined image samplers are sampled with their own sampler.
+        let combined_id = match self.ir_function.expressions[image] {
+            crate::Expression::GlobalVariable(handle) => {
+                self.writer.global_variables[handle].combined_id
+            }
+            _ => 0,
+        };
+        let sampler_id = if combined_id == 0 {
+            self.get_handle_id(sampler)
+        } else {
+            0
+        };
 
         let coordinates = self.write_image_coordinates(coordinate, array_index, block)?;
         let coordinates_id = if clamp_to_edge {
@@ -935,13 +946,18 @@ impl BlockContext<'_> {
             coordinates.value_id
         };
 
-        let sampled_image_id = self.gen_id();
-        block.body.push(Instruction::sampled_image(
-            sampled_image_type_id,
-            sampled_image_id,
-            image_id,
-            sampler_id,
-        ));
+        let sampled_image_id = if combined_id == 0 {
+            let sampled_image_id = self.gen_id();
+            block.body.push(Instruction::sampled_image(
+                sampled_image_type_id,
+                sampled_image_id,
+                image_id,
+                sampler_id,
+            ));
+            sampled_image_id
+        } else {
+            combined_id
+        };
         let id = self.gen_id();
 
         let depth_id = depth_ref.map(|handle| self.cached[handle]);
@@ -174,6 +174,30 @@ impl super::Instruction {
         instruction
     }
 
+    /// Writes `OpExtInst DebugPrintf` from the `NonSemantic.DebugPrintf` set `set_id`,
+    /// printing the `OpString` `format_id` with `arguments`.
+    pub(super) fn debug_printf(
+        set_id: Word,
+        result_type_id: Word,
+        id: Word,
+        format_id: Word,
+        arguments: &[Word],
+    ) -> Self {
+        // The only instruction of `NonSemantic.DebugPrintf`.
+        const DEBUG_PRINTF: Word = 1;
+
+        let mut instruction = Self::new(Op::ExtInst);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(set_id);
+        instruction.add_operand(DEBUG_PRINTF);
+        instruction.add_operand(format_id);
+        for argument in arguments {
+            instruction.add_operand(*argument)
+        }
+        instruction
+    }
+
     //
     //  Mode-Setting Instructions
     //
@@ -774,6 +798,35 @@ impl super::Instruction {
         instruction
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn trace_ray(
+        acceleration_structure: Word,
+        ray_flags: Word,
+        cull_mask: Word,
+        sbt_record_offset: Word,
+        sbt_record_stride: Word,
+        miss_index: Word,
+        ray_origin: Word,
+        ray_tmin: Word,
+        ray_dir: Word,
+        ray_tmax: Word,
+        payload: Word,
+    ) -> Self {
+        let mut instruction = Self::new(Op::TraceRayKHR);
+        instruction.add_operand(acceleration_structure);
+        instruction.add_operand(ray_flags);
+        instruction.add_operand(cull_mask);
+        instruction.add_operand(sbt_record_offset);
+        instruction.add_operand(sbt_record_stride);
+        instruction.add_operand(miss_index);
+        instruction.add_operand(ray_origin);
+        instruction.add_operand(ray_tmin);
+        instruction.add_operand(ray_dir);
+        instruction.add_operand(ray_tmax);
+        instruction.add_operand(payload);
+        instruction
+    }
+
     pub(super) fn ray_query_proceed(result_type_id: Word, id: Word, query: Word) -> Self {
         let mut instruction = Self::new(Op::RayQueryProceedKHR);
         instruction.set_type(result_type_id);
@@ -1107,6 +1160,14 @@ impl super::Instruction {
         Self::new(Op::Kill)
     }
 
+    pub(super) const fn ignore_intersection() -> Self {
+        Self::new(Op::IgnoreIntersectionKHR)
+    }
+
+    pub(super) const fn terminate_ray() -> Self {
+        Self::new(Op::TerminateRayKHR)
+    }
+
     pub(super) const fn return_void() -> Self {
         Self::new(Op::Return)
     }
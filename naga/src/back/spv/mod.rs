@@ -261,6 +261,12 @@ impl LocalImageType {
                 flags: make_flags(multi, ImageTypeFlags::SAMPLED),
                 image_format: spirv::ImageFormat::Unknown,
             },
+            crate::ImageClass::External => LocalImageType {
+                sampled_type: crate::Scalar::F32,
+                dim,
+                flags: make_flags(false, ImageTypeFlags::SAMPLED),
+                image_format: spirv::ImageFormat::Unknown,
+            },
             crate::ImageClass::Depth { multi } => LocalImageType {
                 sampled_type: crate::Scalar {
                     kind: crate::ScalarKind::Float,
@@ -586,6 +592,16 @@ struct GlobalVariable {
     ///
     /// [`var_id`]: GlobalVariable::var_id
     access_id: Word,
+
+    /// The loaded combined image sampler of a global bound as a
+    /// [`BindingInfo::combined_sampler`].
+    ///
+    /// If the current function uses such a global, this is the id of the
+    /// `OpLoad` in the function's prologue, and [`handle_id`] is the
+    /// `OpImage` extracted from it. Otherwise this is zero.
+    ///
+    /// [`handle_id`]: GlobalVariable::handle_id
+    combined_id: Word,
 }
 
 impl GlobalVariable {
@@ -594,6 +610,7 @@ impl GlobalVariable {
             var_id: 0,
             handle_id: 0,
             access_id: 0,
+            combined_id: 0,
         }
     }
 
@@ -602,6 +619,7 @@ impl GlobalVariable {
             var_id: id,
             handle_id: 0,
             access_id: 0,
+            combined_id: 0,
         }
     }
 
@@ -609,6 +627,7 @@ impl GlobalVariable {
     fn reset_for_function(&mut self) {
         self.handle_id = 0;
         self.access_id = 0;
+        self.combined_id = 0;
     }
 }
 
@@ -765,6 +784,10 @@ pub struct Writer {
 
     gl450_ext_inst_id: Word,
 
+    /// The `NonSemantic.DebugPrintf` extended instruction set, imported by the
+    /// first [`DebugPrintf`](crate::Statement::DebugPrintf) statement.
+    debug_printf_ext_inst_id: Option<Word>,
+
     // Just a temporary list of SPIR-V ids
     temp_list: Vec<Word>,
 
@@ -810,6 +833,13 @@ bitflags::bitflags! {
 pub struct BindingInfo {
     /// If the binding is an unsized binding array, this overrides the size.
     pub binding_array_size: Option<u32>,
+    /// If true, the image global at this binding is declared as a combined image
+    /// sampler, and the sampler it is used with in the shader is ignored.
+    ///
+    /// This is needed for images that are sampled through an immutable sampler
+    /// with a Y'CbCr conversion.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub combined_sampler: bool,
 }
 
 // Using `BTreeMap` instead of `HashMap` so that we can hash itself.
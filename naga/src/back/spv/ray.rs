@@ -1,5 +1,5 @@
 /*!
-Generating SPIR-V for ray query operations.
+Generating SPIR-V for ray query and ray tracing pipeline operations.
 */
 
 use alloc::vec;
@@ -10,6 +10,19 @@ use super::{
 };
 use crate::arena::Handle;
 
+/// The ids of the members of a [`RayDesc`], see
+/// [`BlockContext::write_ray_desc_extraction`].
+///
+/// [`RayDesc`]: crate::SpecialTypes::ray_desc
+struct RayDescIds {
+    flags_id: spirv::Word,
+    cull_mask_id: spirv::Word,
+    tmin_id: spirv::Word,
+    tmax_id: spirv::Word,
+    origin_id: spirv::Word,
+    dir_id: spirv::Word,
+}
+
 impl Writer {
     pub(super) fn write_ray_query_get_intersection_function(
         &mut self,
@@ -451,6 +464,45 @@ impl Writer {
 }
 
 impl BlockContext<'_> {
+    /// Extracts the members of the [`RayDesc`] `descriptor` for the ray
+    /// query and ray tracing instructions, which take them as operands.
+    ///
+    /// [`RayDesc`]: crate::SpecialTypes::ray_desc
+    fn write_ray_desc_extraction(
+        &mut self,
+        descriptor: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> RayDescIds {
+        //Note: composite extract indices and types must match `generate_ray_desc_type`
+        let desc_id = self.cached[descriptor];
+
+        let flag_type_id = self.get_numeric_type_id(NumericType::Scalar(crate::Scalar::U32));
+        let scalar_type_id = self.get_numeric_type_id(NumericType::Scalar(crate::Scalar::F32));
+        let vector_type_id = self.get_numeric_type_id(NumericType::Vector {
+            size: crate::VectorSize::Tri,
+            scalar: crate::Scalar::F32,
+        });
+        let mut extract = |type_id, index| {
+            let id = self.gen_id();
+            block.body.push(Instruction::composite_extract(
+                type_id,
+                id,
+                desc_id,
+                &[index],
+            ));
+            id
+        };
+
+        RayDescIds {
+            flags_id: extract(flag_type_id, 0),
+            cull_mask_id: extract(flag_type_id, 1),
+            tmin_id: extract(scalar_type_id, 2),
+            tmax_id: extract(scalar_type_id, 3),
+            origin_id: extract(vector_type_id, 4),
+            dir_id: extract(vector_type_id, 5),
+        }
+    }
+
     pub(super) fn write_ray_query_function(
         &mut self,
         query: Handle<crate::Expression>,
@@ -463,72 +515,18 @@ impl BlockContext<'_> {
                 acceleration_structure,
                 descriptor,
             } => {
-                //Note: composite extract indices and types must match `generate_ray_desc_type`
-                let desc_id = self.cached[descriptor];
                 let acc_struct_id = self.get_handle_id(acceleration_structure);
-
-                let flag_type_id =
-                    self.get_numeric_type_id(NumericType::Scalar(crate::Scalar::U32));
-                let ray_flags_id = self.gen_id();
-                block.body.push(Instruction::composite_extract(
-                    flag_type_id,
-                    ray_flags_id,
-                    desc_id,
-                    &[0],
-                ));
-                let cull_mask_id = self.gen_id();
-                block.body.push(Instruction::composite_extract(
-                    flag_type_id,
-                    cull_mask_id,
-                    desc_id,
-                    &[1],
-                ));
-
-                let scalar_type_id =
-                    self.get_numeric_type_id(NumericType::Scalar(crate::Scalar::F32));
-                let tmin_id = self.gen_id();
-                block.body.push(Instruction::composite_extract(
-                    scalar_type_id,
-                    tmin_id,
-                    desc_id,
-                    &[2],
-                ));
-                let tmax_id = self.gen_id();
-                block.body.push(Instruction::composite_extract(
-                    scalar_type_id,
-                    tmax_id,
-                    desc_id,
-                    &[3],
-                ));
-
-                let vector_type_id = self.get_numeric_type_id(NumericType::Vector {
-                    size: crate::VectorSize::Tri,
-                    scalar: crate::Scalar::F32,
-                });
-                let ray_origin_id = self.gen_id();
-                block.body.push(Instruction::composite_extract(
-                    vector_type_id,
-                    ray_origin_id,
-                    desc_id,
-                    &[4],
-                ));
-                let ray_dir_id = self.gen_id();
-                block.body.push(Instruction::composite_extract(
-                    vector_type_id,
-                    ray_dir_id,
-                    desc_id,
-                    &[5],
-                ));
+                let desc = self.write_ray_desc_extraction(descriptor, block);
 
                 block.body.push(Instruction::ray_query_initialize(
                     query_id,
                     acc_struct_id,
-                    ray_flags_id,
-                    cull_mask_id,
-                    ray_origin_id,
-                    tmin_id,
-                    ray_dir_id,
-                    tmax_id,
+                    desc.flags_id,
+                    desc.cull_mask_id,
+                    desc.origin_id,
+                    desc.tmin_id,
+                    desc.dir_id,
+                    desc.tmax_id,
                 ));
             }
             crate::RayQueryFunction::Proceed { result } => {
@@ -557,6 +555,54 @@ impl BlockContext<'_> {
         }
     }
 
+    /// Writes a [`RayPipelineFunction`] statement.
+    ///
+    /// Returns whether the statement ends the block, which the
+    /// [`IgnoreIntersection`] and [`TerminateRay`] functions do.
+    ///
+    /// [`RayPipelineFunction`]: crate::RayPipelineFunction
+    /// [`IgnoreIntersection`]: crate::RayPipelineFunction::IgnoreIntersection
+    /// [`TerminateRay`]: crate::RayPipelineFunction::TerminateRay
+    pub(super) fn write_ray_pipeline_function(
+        &mut self,
+        function: &crate::RayPipelineFunction,
+        block: &mut Block,
+    ) -> Option<Instruction> {
+        match *function {
+            crate::RayPipelineFunction::TraceRay {
+                acceleration_structure,
+                descriptor,
+                sbt_record_offset,
+                sbt_record_stride,
+                miss_index,
+                payload,
+            } => {
+                let acc_struct_id = self.get_handle_id(acceleration_structure);
+                let desc = self.write_ray_desc_extraction(descriptor, block);
+                let payload_id = self.cached[payload];
+
+                block.body.push(Instruction::trace_ray(
+                    acc_struct_id,
+                    desc.flags_id,
+                    desc.cull_mask_id,
+                    self.cached[sbt_record_offset],
+                    self.cached[sbt_record_stride],
+                    self.cached[miss_index],
+                    desc.origin_id,
+                    desc.tmin_id,
+                    desc.dir_id,
+                    desc.tmax_id,
+                    payload_id,
+                ));
+                None
+            }
+            crate::RayPipelineFunction::IgnoreIntersection => {
+                Some(Instruction::ignore_intersection())
+            }
+            crate::RayPipelineFunction::TerminateRay => Some(Instruction::terminate_ray()),
+        }
+    }
+
     pub(super) fn write_ray_query_return_vertex_position(
         &mut self,
         query: Handle<crate::Expression>,
@@ -89,6 +89,7 @@ impl Writer {
             binding_map: options.binding_map.clone(),
             saved_cached: CachedExpressions::default(),
             gl450_ext_inst_id,
+            debug_printf_ext_inst_id: None,
             temp_list: Vec::new(),
             ray_get_committed_intersection_function: None,
             ray_get_candidate_intersection_function: None,
@@ -132,6 +133,7 @@ impl Writer {
             id_gen,
             void_type,
             gl450_ext_inst_id,
+            debug_printf_ext_inst_id: None,
 
             // Recycled:
             capabilities_used: take(&mut self.capabilities_used).recycle(),
@@ -265,10 +267,38 @@ impl Writer {
         }
     }
 
+    /// Returns the id of the `NonSemantic.DebugPrintf` extended instruction set,
+    /// importing it on first use.
+    pub(super) fn get_debug_printf_ext_inst_id(&mut self) -> Word {
+        if let Some(id) = self.debug_printf_ext_inst_id {
+            return id;
+        }
+        let id = self.id_gen.next();
+        self.use_extension("SPV_KHR_non_semantic_info");
+        Instruction::ext_inst_import(id, "NonSemantic.DebugPrintf")
+            .to_words(&mut self.logical_layout.ext_inst_imports);
+        self.debug_printf_ext_inst_id = Some(id);
+        id
+    }
+
     pub(super) fn get_handle_type_id(&mut self, handle: Handle<crate::Type>) -> Word {
         self.get_type_id(LookupType::Handle(handle))
     }
 
+    /// Returns true if `var` is an image bound as a [`BindingInfo::combined_sampler`].
+    fn is_combined_sampler(&self, ir_module: &crate::Module, var: &crate::GlobalVariable) -> bool {
+        let is_image = matches!(
+            ir_module.types[var.ty].inner,
+            crate::TypeInner::Image { .. }
+        );
+        is_image
+            && var
+                .binding
+                .as_ref()
+                .and_then(|binding| self.binding_map.get(binding))
+                .is_some_and(|info| info.combined_sampler)
+    }
+
     pub(super) fn get_expression_lookup_type(&mut self, tr: &TypeResolution) -> LookupType {
         match *tr {
             TypeResolution::Handle(ty_handle) => LookupType::Handle(ty_handle),
@@ -933,7 +963,34 @@ impl Writer {
                     gv.access_id = gv.var_id;
                 }
                 _ => {
-                    if var.space == crate::AddressSpace::Handle {
+                    if var.space == crate::AddressSpace::Handle
+                        && self.is_combined_sampler(ir_module, var)
+                    {
+                        // Load the combined image sampler, and extract the image
+                        // for the operations that don't sample it.
+                        let image_type_id = self.get_handle_type_id(var.ty);
+                        let sampled_image_type_id =
+                            self.get_type_id(LookupType::Local(LocalType::SampledImage {
+                                image_type_id,
+                            }));
+                        let combined_id = self.id_gen.next();
+                        prelude.body.push(Instruction::load(
+                            sampled_image_type_id,
+                            combined_id,
+                            gv.var_id,
+                            None,
+                        ));
+                        let id = self.id_gen.next();
+                        prelude.body.push(Instruction::unary(
+                            spirv::Op::Image,
+                            image_type_id,
+                            id,
+                            combined_id,
+                        ));
+                        gv.access_id = gv.var_id;
+                        gv.handle_id = id;
+                        gv.combined_id = combined_id;
+                    } else if var.space == crate::AddressSpace::Handle {
                         let var_type_id = self.get_handle_type_id(var.ty);
                         let id = self.id_gen.next();
                         prelude
@@ -1180,6 +1237,10 @@ impl Writer {
                 spirv::ExecutionModel::GLCompute
             }
             crate::ShaderStage::Task | crate::ShaderStage::Mesh => unreachable!(),
+            crate::ShaderStage::RayGeneration => spirv::ExecutionModel::RayGenerationKHR,
+            crate::ShaderStage::Miss => spirv::ExecutionModel::MissKHR,
+            crate::ShaderStage::ClosestHit => spirv::ExecutionModel::ClosestHitKHR,
+            crate::ShaderStage::AnyHit => spirv::ExecutionModel::AnyHitKHR,
         };
         //self.check(exec_model.required_capabilities())?;
 
@@ -1245,6 +1306,7 @@ impl Writer {
                 let sampled = match class {
                     crate::ImageClass::Sampled { .. } => true,
                     crate::ImageClass::Depth { .. } => true,
+                    crate::ImageClass::External => true,
                     crate::ImageClass::Storage { format, .. } => {
                         self.request_image_format_capabilities(format.into())?;
                         false
@@ -1276,7 +1338,13 @@ impl Writer {
                 }
             }
             crate::TypeInner::AccelerationStructure { .. } => {
-                self.require_any("Acceleration Structure", &[spirv::Capability::RayQueryKHR])?;
+                // Ray tracing pipelines declare acceleration structures without ray queries.
+                if !self
+                    .capabilities_used
+                    .contains(&spirv::Capability::RayTracingKHR)
+                {
+                    self.require_any("Acceleration Structure", &[spirv::Capability::RayQueryKHR])?;
+                }
             }
             crate::TypeInner::RayQuery { .. } => {
                 self.require_any("Ray Query", &[spirv::Capability::RayQueryKHR])?;
@@ -1307,6 +1375,30 @@ impl Writer {
                 self.require_any("16 bit floating-point", &[spirv::Capability::Float16])?;
                 self.use_extension("SPV_KHR_16bit_storage");
             }
+            // 8 and 16 bit integers declared by the module can be stored in buffers.
+            // Naga also uses 8 bit integers internally, with just the Int8 capability.
+            crate::TypeInner::Vector { scalar, .. } | crate::TypeInner::Scalar(scalar)
+                if matches!(
+                    scalar.kind,
+                    crate::ScalarKind::Sint | crate::ScalarKind::Uint
+                ) && scalar.width < 4 =>
+            {
+                if scalar.width == 1 {
+                    self.require_any("8 bit integer", &[spirv::Capability::Int8])?;
+                    self.capabilities_used
+                        .insert(spirv::Capability::StorageBuffer8BitAccess);
+                    self.capabilities_used
+                        .insert(spirv::Capability::UniformAndStorageBuffer8BitAccess);
+                    self.use_extension("SPV_KHR_8bit_storage");
+                } else {
+                    self.require_any("16 bit integer", &[spirv::Capability::Int16])?;
+                    self.capabilities_used
+                        .insert(spirv::Capability::StorageBuffer16BitAccess);
+                    self.capabilities_used
+                        .insert(spirv::Capability::UniformAndStorageBuffer16BitAccess);
+                    self.use_extension("SPV_KHR_16bit_storage");
+                }
+            }
             _ => {}
         }
         Ok(())
@@ -1907,6 +1999,16 @@ impl Writer {
         ty: Handle<crate::Type>,
         binding: &crate::Binding,
     ) -> Result<Word, Error> {
+        // Barycentrics aren't a built-in: hit stages read them from the
+        // attributes of the intersection, which are triangle barycentrics
+        // for the built-in triangle intersection.
+        let is_hit_attribute = *binding == crate::Binding::BuiltIn(crate::BuiltIn::Barycentrics);
+        let class = if is_hit_attribute {
+            spirv::StorageClass::HitAttributeKHR
+        } else {
+            class
+        };
+
         let id = self.id_gen.next();
         let pointer_type_id = self.get_handle_pointer_type_id(ty, class);
         Instruction::variable(pointer_type_id, id, class, None)
@@ -1921,6 +2023,10 @@ impl Writer {
             }
         }
 
+        if is_hit_attribute {
+            return Ok(id);
+        }
+
         use spirv::{BuiltIn, Decoration};
 
         match *binding {
@@ -2012,14 +2118,32 @@ impl Writer {
                         )?;
                         BuiltIn::CullDistance
                     }
+                    Bi::InstanceIndex if stage.is_ray_tracing() => BuiltIn::InstanceId,
                     Bi::InstanceIndex => BuiltIn::InstanceIndex,
                     Bi::PointSize => BuiltIn::PointSize,
                     Bi::VertexIndex => BuiltIn::VertexIndex,
                     Bi::DrawID => BuiltIn::DrawIndex,
+                    Bi::ViewportIndex => {
+                        self.require_any(
+                            "`viewport_index` built-in",
+                            &[spirv::Capability::ShaderViewportIndexLayerEXT],
+                        )?;
+                        self.use_extension("SPV_EXT_shader_viewport_index_layer");
+                        BuiltIn::ViewportIndex
+                    }
+                    Bi::Layer => {
+                        self.require_any(
+                            "`layer` built-in",
+                            &[spirv::Capability::ShaderViewportIndexLayerEXT],
+                        )?;
+                        self.use_extension("SPV_EXT_shader_viewport_index_layer");
+                        BuiltIn::Layer
+                    }
                     // fragment
                     Bi::FragDepth => BuiltIn::FragDepth,
                     Bi::PointCoord => BuiltIn::PointCoord,
                     Bi::FrontFacing => BuiltIn::FrontFacing,
+                    Bi::PrimitiveIndex if stage.is_ray_tracing() => BuiltIn::PrimitiveId,
                     Bi::PrimitiveIndex => {
                         self.require_any(
                             "`primitive_index` built-in",
@@ -2036,6 +2160,17 @@ impl Writer {
                         BuiltIn::SampleId
                     }
                     Bi::SampleMask => BuiltIn::SampleMask,
+                    Bi::FullyCovered => {
+                        self.require_any(
+                            "`fully_covered` built-in",
+                            &[spirv::Capability::FragmentFullyCoveredEXT],
+                        )?;
+                        self.use_extension("SPV_EXT_fragment_fully_covered");
+                        BuiltIn::FullyCoveredEXT
+                    }
+                    Bi::FramebufferFetch { .. } => {
+                        return Err(Error::FeatureNotImplemented("framebuffer fetch"));
+                    }
                     // compute
                     Bi::GlobalInvocationId => BuiltIn::GlobalInvocationId,
                     Bi::LocalInvocationId => BuiltIn::LocalInvocationId,
@@ -2078,6 +2213,23 @@ impl Writer {
                         )?;
                         BuiltIn::SubgroupLocalInvocationId
                     }
+                    // ray tracing
+                    Bi::LaunchId => BuiltIn::LaunchIdKHR,
+                    Bi::LaunchSize => BuiltIn::LaunchSizeKHR,
+                    Bi::WorldRayOrigin => BuiltIn::WorldRayOriginKHR,
+                    Bi::WorldRayDirection => BuiltIn::WorldRayDirectionKHR,
+                    Bi::ObjectRayOrigin => BuiltIn::ObjectRayOriginKHR,
+                    Bi::ObjectRayDirection => BuiltIn::ObjectRayDirectionKHR,
+                    Bi::RayTMin => BuiltIn::RayTminKHR,
+                    Bi::RayTCurrentMax => BuiltIn::RayTmaxKHR,
+                    Bi::IncomingRayFlags => BuiltIn::IncomingRayFlagsKHR,
+                    Bi::InstanceCustomData => BuiltIn::InstanceCustomIndexKHR,
+                    Bi::GeometryIndex => BuiltIn::RayGeometryIndexKHR,
+                    Bi::ObjectToWorld => BuiltIn::ObjectToWorldKHR,
+                    Bi::WorldToObject => BuiltIn::WorldToObjectKHR,
+                    Bi::HitKind => BuiltIn::HitKindKHR,
+                    // written as a hit attribute above
+                    Bi::Barycentrics => unreachable!(),
                 };
 
                 self.decorate(id, Decoration::BuiltIn, &[built_in as u32]);
@@ -2161,6 +2313,7 @@ impl Writer {
 
             if let Some(&BindingInfo {
                 binding_array_size: Some(remapped_binding_array_size),
+                ..
             }) = self.binding_map.get(res_binding)
             {
                 if let crate::TypeInner::BindingArray { base, .. } =
@@ -2176,6 +2329,14 @@ impl Writer {
                         class,
                     }));
                 }
+            } else if self.is_combined_sampler(ir_module, global_variable) {
+                let image_type_id = self.get_handle_type_id(global_variable.ty);
+                let sampled_image_type_id =
+                    self.get_type_id(LookupType::Local(LocalType::SampledImage { image_type_id }));
+                substitute_inner_type_lookup = Some(LookupType::Local(LocalType::Pointer {
+                    base: sampled_image_type_id,
+                    class,
+                }));
             }
         };
 
@@ -2376,17 +2537,32 @@ impl Writer {
             .iter()
             .flat_map(|entry| entry.function.arguments.iter())
             .any(|arg| has_view_index_check(ir_module, arg.binding.as_ref(), arg.ty));
-        let mut has_ray_query = ir_module.special_types.ray_desc.is_some()
+        // Ray tracing pipelines use ray descriptors and acceleration
+        // structures too, but only ray queries need `SPV_KHR_ray_query`.
+        let uses_ray_tracing_pipeline = super::helpers::uses_ray_tracing_pipeline(ir_module);
+        let mut has_ray_query = (ir_module.special_types.ray_desc.is_some()
+            && !uses_ray_tracing_pipeline)
             | ir_module.special_types.ray_intersection.is_some();
         let has_vertex_return = ir_module.special_types.ray_vertex_return.is_some();
 
         for (_, &crate::Type { ref inner, .. }) in ir_module.types.iter() {
             // spirv does not know whether these have vertex return - that is done by us
-            if let &crate::TypeInner::AccelerationStructure { .. }
-            | &crate::TypeInner::RayQuery { .. } = inner
-            {
-                has_ray_query = true
+            match *inner {
+                crate::TypeInner::AccelerationStructure { .. } if !uses_ray_tracing_pipeline => {
+                    has_ray_query = true
+                }
+                crate::TypeInner::RayQuery { .. } => has_ray_query = true,
+                _ => {}
+            }
+        }
+
+        if uses_ray_tracing_pipeline {
+            if self.physical_layout.version < 0x10400 {
+                let (major, minor) = self.lang_version();
+                return Err(Error::UnsupportedVersion(major, minor));
             }
+            self.require_any("ray tracing pipeline", &[spirv::Capability::RayTracingKHR])?;
+            self.use_extension("SPV_KHR_ray_tracing");
         }
 
         if self.physical_layout.version < 0x10300 && has_storage_buffers {
@@ -32,6 +32,7 @@ enum Attribute {
     Interpolate(Option<crate::Interpolation>, Option<crate::Sampling>),
     Location(u32),
     BlendSrc(u32),
+    Color(u32),
     Stage(ShaderStage),
     WorkGroupSize([u32; 3]),
 }
@@ -116,6 +117,7 @@ impl<W: Write> Writer<W> {
             .predeclared_types
             .values()
             .any(|t| *t == handle)
+            || module.special_types.ray_desc == Some(handle)
     }
 
     pub fn write(&mut self, module: &Module, info: &valid::ModuleInfo) -> BackendResult {
@@ -192,6 +194,10 @@ impl<W: Write> Writer<W> {
                     Attribute::WorkGroupSize(ep.workgroup_size),
                 ],
                 ShaderStage::Task | ShaderStage::Mesh => unreachable!(),
+                ShaderStage::RayGeneration
+                | ShaderStage::Miss
+                | ShaderStage::ClosestHit
+                | ShaderStage::AnyHit => vec![Attribute::Stage(ep.stage)],
             };
 
             self.write_attributes(&attributes)?;
@@ -227,6 +233,7 @@ impl<W: Write> Writer<W> {
         let mut needs_f16 = false;
         let mut needs_dual_source_blending = false;
         let mut needs_clip_distances = false;
+        let mut needs_framebuffer_fetch = false;
 
         // Determine which `enable` declarations are needed
         for (_, ty) in module.types.iter() {
@@ -247,6 +254,11 @@ impl<W: Write> Writer<W> {
                             crate::Binding::BuiltIn(crate::BuiltIn::ClipDistance) => {
                                 needs_clip_distances = true;
                             }
+                            crate::Binding::BuiltIn(crate::BuiltIn::FramebufferFetch {
+                                ..
+                            }) => {
+                                needs_framebuffer_fetch = true;
+                            }
                             _ => {}
                         }
                     }
@@ -254,6 +266,37 @@ impl<W: Write> Writer<W> {
                 _ => {}
             }
         }
+        // Framebuffer fetch inputs may also be entry point arguments.
+        needs_framebuffer_fetch |= module
+            .entry_points
+            .iter()
+            .flat_map(|ep| ep.function.arguments.iter())
+            .any(|arg| {
+                matches!(
+                    arg.binding,
+                    Some(crate::Binding::BuiltIn(
+                        crate::BuiltIn::FramebufferFetch { .. }
+                    ))
+                )
+            });
+
+        let needs_debug_printf = module
+            .functions
+            .iter()
+            .map(|(_, function)| function)
+            .chain(module.entry_points.iter().map(|ep| &ep.function))
+            .any(|function| uses_debug_printf(&function.body));
+
+        let needs_ray_tracing_pipeline = module
+            .entry_points
+            .iter()
+            .any(|ep| ep.stage.is_ray_tracing())
+            || module.global_variables.iter().any(|(_, var)| {
+                matches!(
+                    var.space,
+                    crate::AddressSpace::RayPayload | crate::AddressSpace::IncomingRayPayload
+                )
+            });
 
         // Write required declarations
         let mut any_written = false;
@@ -269,6 +312,18 @@ impl<W: Write> Writer<W> {
             writeln!(self.out, "enable clip_distances;")?;
             any_written = true;
         }
+        if needs_framebuffer_fetch {
+            writeln!(self.out, "enable wgpu_framebuffer_fetch;")?;
+            any_written = true;
+        }
+        if needs_debug_printf {
+            writeln!(self.out, "enable wgpu_debug_printf;")?;
+            any_written = true;
+        }
+        if needs_ray_tracing_pipeline {
+            writeln!(self.out, "enable wgpu_ray_tracing_pipeline;")?;
+            any_written = true;
+        }
         if any_written {
             // Empty line for readability
             writeln!(self.out)?;
@@ -378,6 +433,7 @@ impl<W: Write> Writer<W> {
             match *attribute {
                 Attribute::Location(id) => write!(self.out, "@location({id}) ")?,
                 Attribute::BlendSrc(blend_src) => write!(self.out, "@blend_src({blend_src}) ")?,
+                Attribute::Color(location) => write!(self.out, "@color({location}) ")?,
                 Attribute::BuiltIn(builtin_attrib) => {
                     let builtin = builtin_attrib.to_wgsl_if_implemented()?;
                     write!(self.out, "@builtin({builtin}) ")?;
@@ -388,6 +444,10 @@ impl<W: Write> Writer<W> {
                         ShaderStage::Fragment => "fragment",
                         ShaderStage::Compute => "compute",
                         ShaderStage::Task | ShaderStage::Mesh => unreachable!(),
+                        ShaderStage::RayGeneration => "ray_generation",
+                        ShaderStage::Miss => "miss",
+                        ShaderStage::ClosestHit => "closest_hit",
+                        ShaderStage::AnyHit => "any_hit",
                     };
                     write!(self.out, "@{stage_str} ")?;
                 }
@@ -840,6 +900,32 @@ impl<W: Write> Writer<W> {
                 }
             }
             Statement::RayQuery { .. } => unreachable!(),
+            Statement::RayPipelineFunction(ref fun) => match *fun {
+                crate::RayPipelineFunction::TraceRay {
+                    acceleration_structure,
+                    descriptor,
+                    sbt_record_offset,
+                    sbt_record_stride,
+                    miss_index,
+                    payload,
+                } => {
+                    write!(self.out, "{level}traceRay(")?;
+                    self.write_expr(module, acceleration_structure, func_ctx)?;
+                    for operand in [descriptor, sbt_record_offset, sbt_record_stride, miss_index] {
+                        write!(self.out, ", ")?;
+                        self.write_expr(module, operand, func_ctx)?;
+                    }
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, payload, func_ctx)?;
+                    writeln!(self.out, ");")?;
+                }
+                crate::RayPipelineFunction::IgnoreIntersection => {
+                    writeln!(self.out, "{level}ignoreIntersection();")?;
+                }
+                crate::RayPipelineFunction::TerminateRay => {
+                    writeln!(self.out, "{level}terminateRay();")?;
+                }
+            },
             Statement::SubgroupBallot { result, predicate } => {
                 write!(self.out, "{level}")?;
                 let res_name = Baked(result).to_string();
@@ -968,6 +1054,27 @@ impl<W: Write> Writer<W> {
                 }
                 writeln!(self.out, ");")?;
             }
+            Statement::DebugPrintf {
+                ref format,
+                ref arguments,
+            } => {
+                write!(self.out, "{level}debugPrintf(\"")?;
+                for c in format.chars() {
+                    match c {
+                        '"' => write!(self.out, "\\\"")?,
+                        '\\' => write!(self.out, "\\\\")?,
+                        '\n' => write!(self.out, "\\n")?,
+                        '\t' => write!(self.out, "\\t")?,
+                        _ => write!(self.out, "{c}")?,
+                    }
+                }
+                write!(self.out, "\"")?;
+                for &argument in arguments {
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, argument, func_ctx)?;
+                }
+                writeln!(self.out, ");")?;
+            }
         }
 
         Ok(())
@@ -1452,6 +1559,14 @@ impl<W: Write> Writer<W> {
                     write!(self.out, ", ")?;
                     self.write_expr(module, array_index, func_ctx)?;
                 }
+                // WGSL doesn't let loads from external textures give their level.
+                let level = match *func_ctx.resolve_type(image, &module.types) {
+                    TypeInner::Image {
+                        class: crate::ImageClass::External,
+                        ..
+                    } => None,
+                    _ => level,
+                };
                 if let Some(index) = sample.or(level) {
                     write!(self.out, ", ")?;
                     self.write_expr(module, index, func_ctx)?;
@@ -1792,8 +1907,36 @@ impl TypeContext for WriterTypeContext<'_> {
     }
 }
 
+/// Returns whether `block` or any block nested in it contains a
+/// [`DebugPrintf`](crate::Statement::DebugPrintf) statement.
+fn uses_debug_printf(block: &crate::Block) -> bool {
+    use crate::Statement;
+
+    block.iter().any(|statement| match *statement {
+        Statement::DebugPrintf { .. } => true,
+        Statement::Block(ref block) => uses_debug_printf(block),
+        Statement::If {
+            ref accept,
+            ref reject,
+            ..
+        } => uses_debug_printf(accept) || uses_debug_printf(reject),
+        Statement::Switch { ref cases, .. } => {
+            cases.iter().any(|case| uses_debug_printf(&case.body))
+        }
+        Statement::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => uses_debug_printf(body) || uses_debug_printf(continuing),
+        _ => false,
+    })
+}
+
 fn map_binding_to_attribute(binding: &crate::Binding) -> Vec<Attribute> {
     match *binding {
+        crate::Binding::BuiltIn(crate::BuiltIn::FramebufferFetch { location }) => {
+            vec![Attribute::Color(location)]
+        }
         crate::Binding::BuiltIn(built_in) => {
             if let crate::BuiltIn::Position { invariant: true } = built_in {
                 vec![Attribute::BuiltIn(built_in), Attribute::Invariant]
@@ -0,0 +1,155 @@
+/*!
+Caching validated modules as bytes.
+
+Parsing and validating many shaders can take a noticeable part of a program's startup time.
+Instead, the shaders can be parsed and validated once, offline or on the first run, and their
+[`Module`]s and [`ModuleInfo`]s written to a cache with [`to_bytes`], to be loaded with
+[`from_bytes`] afterwards.
+
+The bytes are only meant to be read by the version of Naga which wrote them, since the IR
+changes between versions. They start with that version, and [`from_bytes`] returns
+[`CacheError::VersionMismatch`] for bytes written by another version, in which case the cache
+should be rebuilt from the shader sources.
+
+```
+# use naga::{cache, valid};
+# let module = naga::Module::default();
+let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+    .validate(&module)?;
+
+let bytes = cache::to_bytes(&module, &info);
+let (module, info) = cache::from_bytes(&bytes)?;
+# Ok::<(), Box<dyn core::error::Error>>(())
+```
+*/
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{Module, valid::ModuleInfo};
+
+/// The bytes every cached module starts with.
+const MAGIC: &[u8; 8] = b"NAGA-IR\0";
+
+/// The version of Naga, which cached modules are written by and can be read by.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An error returned by [`from_bytes`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum CacheError {
+    #[error("the data is not a cached Naga module")]
+    NotACachedModule,
+    #[error("the module was cached by Naga {found}, but this is Naga {VERSION}")]
+    VersionMismatch { found: String },
+    #[error("the cached module is corrupt: {0}")]
+    Corrupt(String),
+}
+
+/// Serializes a validated `module` and its `info` to bytes, to be read by [`from_bytes`].
+///
+/// Only cache modules `info` was returned for by the validator, since [`from_bytes`] doesn't
+/// validate them again.
+pub fn to_bytes(module: &Module, info: &ModuleInfo) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION.len() as u8);
+    bytes.extend_from_slice(VERSION.as_bytes());
+    let data = bincode::serde::encode_to_vec((module, info), config())
+        .expect("modules can always be serialized");
+    bytes.extend_from_slice(&data);
+    bytes
+}
+
+/// Deserializes a module and its info written by [`to_bytes`].
+///
+/// The module isn't validated again, so this is only as trustworthy as the cache the bytes
+/// come from.
+pub fn from_bytes(bytes: &[u8]) -> Result<(Module, ModuleInfo), CacheError> {
+    let bytes = bytes
+        .strip_prefix(MAGIC)
+        .ok_or(CacheError::NotACachedModule)?;
+    let (&version_len, bytes) = bytes.split_first().ok_or(CacheError::NotACachedModule)?;
+    if bytes.len() < version_len as usize {
+        return Err(CacheError::NotACachedModule);
+    }
+    let (version, data) = bytes.split_at(version_len as usize);
+    if version != VERSION.as_bytes() {
+        return Err(CacheError::VersionMismatch {
+            found: String::from_utf8_lossy(version).into_owned(),
+        });
+    }
+    let (module, _) = bincode::serde::decode_from_slice(data, config())
+        .map_err(|error| CacheError::Corrupt(error.to_string()))?;
+    Ok(module)
+}
+
+const fn config() -> bincode::config::Configuration {
+    bincode::config::standard()
+}
+
+#[cfg(all(test, feature = "wgsl-in"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{CacheError, from_bytes, to_bytes};
+    use crate::valid::{Capabilities, ValidationFlags, Validator};
+
+    fn validated_module() -> (crate::Module, crate::valid::ModuleInfo) {
+        let module = crate::front::wgsl::parse_str(
+            "
+            @group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+            @compute @workgroup_size(64)
+            fn main(@builtin(global_invocation_id) id: vec3u) {
+                data[id.x] *= 2.0;
+            }
+            ",
+        )
+        .unwrap();
+        let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .unwrap();
+        (module, info)
+    }
+
+    fn cached_module() -> Vec<u8> {
+        let (module, info) = validated_module();
+        to_bytes(&module, &info)
+    }
+
+    #[test]
+    fn round_trip() {
+        let (original_module, original_info) = validated_module();
+        let bytes = to_bytes(&original_module, &original_info);
+        let (module, info) = from_bytes(&bytes).unwrap();
+
+        assert_eq!(module.entry_points[0].name, "main");
+        assert_eq!(module.global_variables.len(), 1);
+        assert_eq!(
+            info.get_entry_point(0).available_stages,
+            original_info.get_entry_point(0).available_stages
+        );
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        assert!(matches!(
+            from_bytes(b"@compute fn main() {}"),
+            Err(CacheError::NotACachedModule)
+        ));
+
+        let mut bytes = cached_module();
+        // Replace the first digit of the version.
+        bytes[9] = b'X';
+        assert!(matches!(
+            from_bytes(&bytes),
+            Err(CacheError::VersionMismatch { .. })
+        ));
+
+        let mut bytes = cached_module();
+        bytes.truncate(bytes.len() / 2);
+        assert!(matches!(from_bytes(&bytes), Err(CacheError::Corrupt(_))));
+    }
+}
@@ -166,11 +166,14 @@ impl TryToWgsl for crate::BuiltIn {
             Bi::InstanceIndex => "instance_index",
             Bi::VertexIndex => "vertex_index",
             Bi::ClipDistance => "clip_distances",
+            Bi::ViewportIndex => "viewport_index",
+            Bi::Layer => "layer",
             Bi::FragDepth => "frag_depth",
             Bi::FrontFacing => "front_facing",
             Bi::PrimitiveIndex => "primitive_index",
             Bi::SampleIndex => "sample_index",
             Bi::SampleMask => "sample_mask",
+            Bi::FullyCovered => "fully_covered",
             Bi::GlobalInvocationId => "global_invocation_id",
             Bi::LocalInvocationId => "local_invocation_id",
             Bi::LocalInvocationIndex => "local_invocation_index",
@@ -180,6 +183,21 @@ impl TryToWgsl for crate::BuiltIn {
             Bi::SubgroupId => "subgroup_id",
             Bi::SubgroupSize => "subgroup_size",
             Bi::SubgroupInvocationId => "subgroup_invocation_id",
+            Bi::LaunchId => "ray_launch_id",
+            Bi::LaunchSize => "ray_launch_size",
+            Bi::WorldRayOrigin => "world_ray_origin",
+            Bi::WorldRayDirection => "world_ray_direction",
+            Bi::ObjectRayOrigin => "object_ray_origin",
+            Bi::ObjectRayDirection => "object_ray_direction",
+            Bi::RayTMin => "ray_t_min",
+            Bi::RayTCurrentMax => "ray_t_current_max",
+            Bi::IncomingRayFlags => "incoming_ray_flags",
+            Bi::InstanceCustomData => "instance_custom_data",
+            Bi::GeometryIndex => "geometry_index",
+            Bi::ObjectToWorld => "object_to_world",
+            Bi::WorldToObject => "world_to_object",
+            Bi::HitKind => "hit_kind",
+            Bi::Barycentrics => "barycentrics",
 
             // Non-standard built-ins.
             Bi::BaseInstance
@@ -189,6 +207,9 @@ impl TryToWgsl for crate::BuiltIn {
             | Bi::DrawID
             | Bi::PointCoord
             | Bi::WorkGroupSize => return None,
+
+            // Written as the `@color` attribute instead.
+            Bi::FramebufferFetch { .. } => return None,
         })
     }
 }
@@ -279,6 +300,10 @@ impl TryToWgsl for crate::Scalar {
             Scalar::U32 => "u32",
             Scalar::I64 => "i64",
             Scalar::U64 => "u64",
+            Scalar::I8 => "i8",
+            Scalar::U8 => "u8",
+            Scalar::I16 => "i16",
+            Scalar::U16 => "u16",
             Scalar::BOOL => "bool",
             _ => return None,
         })
@@ -352,6 +377,8 @@ pub const fn address_space_str(
             As::WorkGroup => "workgroup",
             As::Handle => return (None, None),
             As::Function => "function",
+            As::RayPayload => "ray_payload",
+            As::IncomingRayPayload => "incoming_ray_payload",
         }),
         None,
     )
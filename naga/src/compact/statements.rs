@@ -152,6 +152,15 @@ impl FunctionTracer<'_> {
                         self.expressions_used.insert(argument);
                         self.expressions_used.insert(result);
                     }
+                    St::DebugPrintf {
+                        format: _,
+                        ref arguments,
+                    } => {
+                        for expr in arguments {
+                            self.expressions_used.insert(*expr);
+                        }
+                    }
+                    St::RayPipelineFunction(ref fun) => self.trace_ray_pipeline_function(fun),
 
                     // Trivial statements.
                     St::Break
@@ -204,6 +213,28 @@ impl FunctionTracer<'_> {
             Qf::Terminate => {}
         }
     }
+
+    fn trace_ray_pipeline_function(&mut self, fun: &crate::RayPipelineFunction) {
+        use crate::RayPipelineFunction as Pf;
+        match *fun {
+            Pf::TraceRay {
+                acceleration_structure,
+                descriptor,
+                sbt_record_offset,
+                sbt_record_stride,
+                miss_index,
+                payload,
+            } => {
+                self.expressions_used.insert(acceleration_structure);
+                self.expressions_used.insert(descriptor);
+                self.expressions_used.insert(sbt_record_offset);
+                self.expressions_used.insert(sbt_record_stride);
+                self.expressions_used.insert(miss_index);
+                self.expressions_used.insert(payload);
+            }
+            Pf::IgnoreIntersection | Pf::TerminateRay => {}
+        }
+    }
 }
 
 impl FunctionMap {
@@ -371,6 +402,17 @@ impl FunctionMap {
                         adjust(argument);
                         adjust(result);
                     }
+                    St::DebugPrintf {
+                        format: _,
+                        ref mut arguments,
+                    } => {
+                        for expr in arguments {
+                            adjust(expr);
+                        }
+                    }
+                    St::RayPipelineFunction(ref mut fun) => {
+                        self.adjust_ray_pipeline_function(fun);
+                    }
 
                     // Trivial statements.
                     St::Break
@@ -423,4 +465,26 @@ impl FunctionMap {
             Qf::Terminate => {}
         }
     }
+
+    fn adjust_ray_pipeline_function(&self, fun: &mut crate::RayPipelineFunction) {
+        use crate::RayPipelineFunction as Pf;
+        match *fun {
+            Pf::TraceRay {
+                ref mut acceleration_structure,
+                ref mut descriptor,
+                ref mut sbt_record_offset,
+                ref mut sbt_record_stride,
+                ref mut miss_index,
+                ref mut payload,
+            } => {
+                self.expressions.adjust(acceleration_structure);
+                self.expressions.adjust(descriptor);
+                self.expressions.adjust(sbt_record_offset);
+                self.expressions.adjust(sbt_record_stride);
+                self.expressions.adjust(miss_index);
+                self.expressions.adjust(payload);
+            }
+            Pf::IgnoreIntersection | Pf::TerminateRay => {}
+        }
+    }
 }
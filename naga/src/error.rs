@@ -1,19 +1,85 @@
-use alloc::{boxed::Box, string::String};
-use core::{error::Error, fmt};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{error::Error, fmt, ops::Range};
+
+use codespan_reporting::files::SimpleFiles;
+
+use crate::{SourceLocation, Span};
 
 #[derive(Clone, Debug)]
 pub struct ShaderError<E> {
     /// The source code of the shader.
     pub source: String,
+    /// The names of the files the shader is made of, and their ranges in `source`, or
+    /// nothing if it is a single file.
+    ///
+    /// The spans of `inner` are offsets in `source`, and are shown in the file they are in.
+    pub files: Vec<(String, Range<usize>)>,
     pub label: Option<String>,
     pub inner: Box<E>,
 }
 
+impl<E> ShaderError<E> {
+    /// Returns the name of the file `span` is in, if the shader is made of several files,
+    /// the source code of that file, and the span relative to the start of the file.
+    pub fn file(&self, span: Span) -> Option<(Option<&str>, &str, Span)> {
+        if self.files.is_empty() {
+            return Some((None, &self.source, span));
+        }
+        let (index, range) = locate(&self.files, span)?;
+        let (ref name, ref file) = self.files[index];
+        Some((
+            Some(name.as_str()),
+            &self.source[file.clone()],
+            range.into(),
+        ))
+    }
+
+    /// Returns the name of the file `span` is in, if the shader is made of several files,
+    /// and the [`SourceLocation`] of the span in that file.
+    pub fn location(&self, span: Span) -> Option<(Option<&str>, SourceLocation)> {
+        let (name, source, span) = self.file(span)?;
+        Some((name, span.location(source)))
+    }
+}
+
+/// Returns the index of the file in `files` which `span` is in, and the range of the span in
+/// that file, or `None` if `span` is undefined.
+///
+/// `files` are ranges of the same source, in order. Spans covering several files are cut at
+/// the end of the file they start in.
+pub(crate) fn locate(
+    files: &[(String, Range<usize>)],
+    span: Span,
+) -> Option<(usize, Range<usize>)> {
+    let range = span.to_range()?;
+    let index = files.partition_point(|&(_, ref file)| file.end < range.start);
+    let (_, ref file) = *files.get(index)?;
+    let end = range.end.min(file.end);
+    Some((index, range.start - file.start..end - file.start))
+}
+
+/// The `files` of `source`, with the file ids of [`locate`], for `codespan-reporting`.
+pub(crate) fn codespan_files<'a>(
+    source: &'a str,
+    files: &'a [(String, Range<usize>)],
+) -> SimpleFiles<&'a str, &'a str> {
+    let mut codespan_files = SimpleFiles::new();
+    for &(ref name, ref range) in files {
+        codespan_files.add(name.as_str(), &source[range.clone()]);
+    }
+    codespan_files
+}
+
 #[cfg(feature = "wgsl-in")]
 impl fmt::Display for ShaderError<crate::front::wgsl::ParseError> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let label = self.label.as_deref().unwrap_or_default();
-        let string = self.inner.emit_to_string(&self.source);
+        let string = if self.files.is_empty() {
+            self.inner.emit_to_string(&self.source)
+        } else {
+            self.inner
+                .emit_to_string_in_files(&self.source, &self.files)
+        };
         write!(f, "\nShader '{label}' parsing {string}")
     }
 }
@@ -41,18 +107,26 @@ impl fmt::Display for ShaderError<crate::WithSpan<crate::valid::ValidationError>
         use codespan_reporting::{files::SimpleFile, term};
 
         let label = self.label.as_deref().unwrap_or_default();
-        let files = SimpleFile::new(label, &self.source);
         let config = term::Config::default();
 
         let writer = {
             let mut writer = DiagnosticBuffer::new();
-            term::emit(
-                writer.inner_mut(),
-                &config,
-                &files,
-                &self.inner.diagnostic(),
-            )
-            .expect("cannot write error");
+            let result = if self.files.is_empty() {
+                let files = SimpleFile::new(label, &self.source);
+                term::emit(
+                    writer.inner_mut(),
+                    &config,
+                    &files,
+                    &self.inner.diagnostic(),
+                )
+            } else {
+                let files = codespan_files(&self.source, &self.files);
+                let diagnostic = self
+                    .inner
+                    .diagnostic_in_files(|span| locate(&self.files, span));
+                term::emit(writer.inner_mut(), &config, &files, &diagnostic)
+            };
+            result.expect("cannot write error");
             writer.into_string()
         };
 
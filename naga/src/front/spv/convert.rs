@@ -142,6 +142,8 @@ pub(super) fn map_builtin(word: spirv::Word, invariant: bool) -> Result<crate::B
         Some(Bi::PointSize) => crate::BuiltIn::PointSize,
         Some(Bi::VertexIndex) => crate::BuiltIn::VertexIndex,
         Some(Bi::DrawIndex) => crate::BuiltIn::DrawID,
+        Some(Bi::ViewportIndex) => crate::BuiltIn::ViewportIndex,
+        Some(Bi::Layer) => crate::BuiltIn::Layer,
         // fragment
         Some(Bi::FragDepth) => crate::BuiltIn::FragDepth,
         Some(Bi::PointCoord) => crate::BuiltIn::PointCoord,
@@ -149,6 +151,7 @@ pub(super) fn map_builtin(word: spirv::Word, invariant: bool) -> Result<crate::B
         Some(Bi::PrimitiveId) => crate::BuiltIn::PrimitiveIndex,
         Some(Bi::SampleId) => crate::BuiltIn::SampleIndex,
         Some(Bi::SampleMask) => crate::BuiltIn::SampleMask,
+        Some(Bi::FullyCoveredEXT) => crate::BuiltIn::FullyCovered,
         // compute
         Some(Bi::GlobalInvocationId) => crate::BuiltIn::GlobalInvocationId,
         Some(Bi::LocalInvocationId) => crate::BuiltIn::LocalInvocationId,
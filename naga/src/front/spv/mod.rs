@@ -4619,7 +4619,9 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 | S::RayQuery { .. }
                 | S::SubgroupBallot { .. }
                 | S::SubgroupCollectiveOperation { .. }
-                | S::SubgroupGather { .. } => {}
+                | S::SubgroupGather { .. }
+                | S::DebugPrintf { .. }
+                | S::RayPipelineFunction(_) => {}
                 S::Call {
                     function: ref mut callee,
                     ref arguments,
@@ -5,6 +5,7 @@ use crate::diagnostic_filter::ConflictingDiagnosticRuleError;
 use crate::proc::{Alignment, ConstantEvaluatorError, ResolveError};
 use crate::{Scalar, SourceLocation, Span};
 
+use super::Sources;
 use super::parse::directive::enable_extension::{EnableExtension, UnimplementedEnableExtension};
 use super::parse::directive::language_extension::{
     LanguageExtension, UnimplementedLanguageExtension,
@@ -45,15 +46,17 @@ impl ParseError {
         &self.message
     }
 
-    fn diagnostic(&self) -> Diagnostic<()> {
+    /// Builds the diagnostic, with `locate` mapping each label's span to a file and a range
+    /// in it, or to `None` if the span is undefined.
+    fn diagnostic<F>(&self, locate: impl Fn(Span) -> Option<(F, Range<usize>)>) -> Diagnostic<F> {
         let diagnostic = Diagnostic::error()
             .with_message(self.message.to_string())
             .with_labels(
                 self.labels
                     .iter()
-                    .filter_map(|label| label.0.to_range().map(|range| (label, range)))
-                    .map(|(label, range)| {
-                        Label::primary((), range).with_message(label.1.to_string())
+                    .filter_map(|label| locate(label.0).map(|location| (label, location)))
+                    .map(|(label, (file, range))| {
+                        Label::primary(file, range).with_message(label.1.to_string())
                     })
                     .collect(),
             )
@@ -90,8 +93,8 @@ impl ParseError {
             }
         }
 
-        term::emit(&mut writer.lock(), &config, &files, &self.diagnostic())
-            .expect("cannot write error");
+        let diagnostic = self.diagnostic(single_file);
+        term::emit(&mut writer.lock(), &config, &files, &diagnostic).expect("cannot write error");
     }
 
     /// Emits a summary of the error to a string.
@@ -109,7 +112,58 @@ impl ParseError {
         let config = term::Config::default();
 
         let mut writer = crate::error::DiagnosticBuffer::new();
-        term::emit(writer.inner_mut(), &config, &files, &self.diagnostic())
+        let diagnostic = self.diagnostic(single_file);
+        term::emit(writer.inner_mut(), &config, &files, &diagnostic).expect("cannot write error");
+        writer.into_string()
+    }
+
+    /// Emits a summary of the error to standard error stream, with the labels in the files
+    /// they refer to.
+    ///
+    /// Use this for errors returned by [`Frontend::parse_with_imports`], whose spans refer
+    /// to `sources`.
+    ///
+    /// [`Frontend::parse_with_imports`]: super::Frontend::parse_with_imports
+    #[cfg(feature = "stderr")]
+    pub fn emit_to_stderr_with_sources(&self, sources: &Sources) {
+        let files = crate::error::codespan_files(sources.text(), sources.file_ranges());
+        let config = term::Config::default();
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "termcolor")] {
+                let writer = termcolor::StandardStream::stderr(termcolor::ColorChoice::Auto);
+            } else {
+                let writer = std::io::stderr();
+            }
+        }
+
+        let diagnostic = self.diagnostic(|span| crate::error::locate(sources.file_ranges(), span));
+        term::emit(&mut writer.lock(), &config, &files, &diagnostic).expect("cannot write error");
+    }
+
+    /// Emits a summary of the error to a string, with the labels in the files they refer to.
+    ///
+    /// Use this for errors returned by [`Frontend::parse_with_imports`], whose spans refer
+    /// to `sources`.
+    ///
+    /// [`Frontend::parse_with_imports`]: super::Frontend::parse_with_imports
+    pub fn emit_to_string_with_sources(&self, sources: &Sources) -> String {
+        self.emit_to_string_in_files(sources.text(), sources.file_ranges())
+    }
+
+    /// Emits a summary of the error to a string, with the labels in the `files` of `source`
+    /// they refer to.
+    pub(crate) fn emit_to_string_in_files(
+        &self,
+        source: &str,
+        files: &[(String, Range<usize>)],
+    ) -> String {
+        let codespan_files = crate::error::codespan_files(source, files);
+        let config = term::Config::default();
+
+        let mut writer = crate::error::DiagnosticBuffer::new();
+        let diagnostic = self.diagnostic(|span| crate::error::locate(files, span));
+        term::emit(writer.inner_mut(), &config, &codespan_files, &diagnostic)
             .expect("cannot write error");
         writer.into_string()
     }
@@ -120,6 +174,10 @@ impl ParseError {
     }
 }
 
+fn single_file(span: Span) -> Option<((), Range<usize>)> {
+    span.to_range().map(|range| ((), range))
+}
+
 impl core::fmt::Display for ParseError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.message)
@@ -136,6 +194,8 @@ impl core::error::Error for ParseError {
 pub enum ExpectedToken<'a> {
     Token(Token<'a>),
     Identifier,
+    /// Expected: the format string of `debugPrintf`
+    StringLiteral,
     AfterIdentListComma,
     AfterIdentListArg,
     /// Expected: constant, parenthesized expression, identifier
@@ -156,6 +216,8 @@ pub enum ExpectedToken<'a> {
     Function,
     /// The `diagnostic` identifier of the `@diagnostic(…)` attribute.
     DiagnosticAttribute,
+    /// A define, `true`, `false`, `!` or `(`, in the condition of an `@if(…)` attribute.
+    Condition,
 }
 
 #[derive(Clone, Copy, Debug, Error, PartialEq)]
@@ -201,6 +263,7 @@ pub(crate) enum Error<'a> {
     InvalidConstructorComponentType(Span, i32),
     InvalidIdentifierUnderscore(Span),
     ReservedIdentifierPrefix(Span),
+    InvalidStringEscape(Span),
     UnknownAddressSpace(Span),
     RepeatedAttribute(Span),
     UnknownAttribute(Span),
@@ -375,6 +438,13 @@ pub(crate) enum Error<'a> {
     DirectiveAfterFirstGlobalDecl {
         directive_span: Span,
     },
+    /// An `import` directive in a module parsed without a way to load the imported files.
+    UnresolvedImport(Span),
+    /// The file named by an `import` directive couldn't be loaded.
+    ImportFailed {
+        span: Span,
+        reason: String,
+    },
     EnableExtensionNotYetImplemented {
         kind: UnimplementedEnableExtension,
         span: Span,
@@ -467,6 +537,7 @@ impl<'a> Error<'a> {
                         Token::Attribute => "@".to_string(),
                         Token::Number(_) => "number".to_string(),
                         Token::Word(s) => s.to_string(),
+                        Token::String(s) => format!("string literal (\"{s}\")"),
                         Token::Operation(c) => format!("operation (`{c}`)"),
                         Token::LogicalOperation(c) => format!("logical operation (`{c}`)"),
                         Token::ShiftOperation(c) => format!("bitshift (`{c}{c}`)"),
@@ -484,6 +555,7 @@ impl<'a> Error<'a> {
                         Token::End => "end".to_string(),
                     },
                     ExpectedToken::Identifier => "identifier".to_string(),
+                    ExpectedToken::StringLiteral => "string literal".to_string(),
                     ExpectedToken::PrimaryExpression => "expression".to_string(),
                     ExpectedToken::Assignment => "assignment or increment/decrement".to_string(),
                     ExpectedToken::SwitchItem => concat!(
@@ -512,6 +584,9 @@ impl<'a> Error<'a> {
                     ExpectedToken::DiagnosticAttribute => {
                         "the `diagnostic` attribute identifier".to_string()
                     }
+                    ExpectedToken::Condition => {
+                        "a define, `true`, `false`, `!` or `(`".to_string()
+                    }
                 };
                 ParseError {
                     message: format!(
@@ -649,6 +724,17 @@ impl<'a> Error<'a> {
                 labels: vec![(bad_span, "invalid identifier".into())],
                 notes: vec![],
             },
+            Error::InvalidStringEscape(bad_span) => ParseError {
+                message: format!(
+                    "invalid escape sequence in string literal: `{}`",
+                    &source[bad_span]
+                ),
+                labels: vec![(bad_span, "invalid escape sequence".into())],
+                notes: vec![
+                    "the supported escape sequences are `\\\"`, `\\\\`, `\\n` and `\\t`"
+                        .to_string(),
+                ],
+            },
             Error::UnknownAddressSpace(bad_span) => ParseError {
                 message: format!("unknown address space: `{}`", &source[bad_span]),
                 labels: vec![(bad_span, "unknown address space".into())],
@@ -1174,6 +1260,19 @@ impl<'a> Error<'a> {
                 )
                 .into()],
             },
+            Error::UnresolvedImport(span) => ParseError {
+                message: "`import` directives can't be resolved here".into(),
+                labels: vec![(span, "imported file can't be loaded".into())],
+                notes: vec![
+                    "parse the module with `Frontend::parse_with_imports`, which loads the imported files"
+                        .into(),
+                ],
+            },
+            Error::ImportFailed { span, ref reason } => ParseError {
+                message: format!("failed to import {}", &source[span]),
+                labels: vec![(span, reason.clone().into())],
+                notes: vec![],
+            },
             Error::EnableExtensionNotYetImplemented { kind, span } => ParseError {
                 message: format!(
                     "the `{}` enable-extension is not yet supported",
@@ -0,0 +1,205 @@
+//! Loading the files named by `import` directives.
+//!
+//! A module with imports is parsed as if its files were a single file: their declarations
+//! share one namespace, so a name declared in two files is a redefinition. The files are
+//! stored one after another in a [`Sources`], and spans are offsets in all of them.
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, ops::Range};
+
+use crate::front::wgsl::error::Error;
+use crate::front::wgsl::parse::directive::DirectiveKind;
+use crate::front::wgsl::parse::lexer::{Lexer, Token};
+use crate::{SourceLocation, Span};
+
+/// A file loaded for an `import` directive, returned by the callback of
+/// [`Frontend::parse_with_imports`].
+///
+/// [`Frontend::parse_with_imports`]: super::Frontend::parse_with_imports
+#[derive(Clone, Debug)]
+pub struct ImportedFile {
+    /// The path of the file, after resolving it relative to the importing file.
+    ///
+    /// Each path is only loaded once, so files which are imported several times, or
+    /// which import each other, are only parsed once. It is passed back to the callback
+    /// to resolve the imports of this file, and used to name the file in error messages.
+    pub path: String,
+    /// The WGSL source code of the file.
+    pub source: String,
+}
+
+/// The WGSL source files of a module, loaded by [`Frontend::parse_with_imports`] or added
+/// with [`Sources::push`].
+///
+/// The spans of the parsed module and of its [`ParseError`]s are offsets in
+/// [`Sources::text`], where the files are stored one after another. Use
+/// [`Sources::location`] to find the file and the position in it of a span, or
+/// [`ParseError::emit_to_string_with_sources`] to show an error in the files.
+///
+/// [`Frontend::parse_with_imports`]: super::Frontend::parse_with_imports
+/// [`ParseError`]: super::ParseError
+/// [`ParseError::emit_to_string_with_sources`]: super::ParseError::emit_to_string_with_sources
+#[derive(Clone, Debug, Default)]
+pub struct Sources {
+    text: String,
+    files: Vec<(String, Range<usize>)>,
+}
+
+impl Sources {
+    /// Creates an empty `Sources`, to be filled by [`Frontend::parse_with_imports`] or
+    /// [`Sources::push`].
+    ///
+    /// [`Frontend::parse_with_imports`]: super::Frontend::parse_with_imports
+    pub const fn new() -> Self {
+        Self {
+            text: String::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Adds the file at `path`, to be parsed with the other files by
+    /// [`Frontend::parse_sources`].
+    ///
+    /// `path` names the file in error messages, and in the `import` directives of the other
+    /// files.
+    ///
+    /// [`Frontend::parse_sources`]: super::Frontend::parse_sources
+    pub fn push(&mut self, path: impl Into<String>, source: &str) {
+        if !self.text.is_empty() {
+            // Keep the files apart, so that spans at the end of a file are still in it.
+            self.text.push('\n');
+        }
+        let start = self.text.len();
+        self.text.push_str(source);
+        self.files.push((path.into(), start..self.text.len()));
+    }
+
+    /// The source code of all the files, one after another, which spans are offsets in.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The paths and source code of the files, starting with the file which was parsed
+    /// and followed by the files it imports, in the order they were loaded.
+    pub fn files(&self) -> impl ExactSizeIterator<Item = (&str, &str)> + '_ {
+        self.files
+            .iter()
+            .map(|&(ref path, ref range)| (path.as_str(), &self.text[range.clone()]))
+    }
+
+    /// The paths of the files and their ranges in [`Sources::text`], as in
+    /// [`ShaderError::files`].
+    ///
+    /// [`ShaderError::files`]: crate::error::ShaderError::files
+    pub fn file_ranges(&self) -> &[(String, Range<usize>)] {
+        &self.files
+    }
+
+    /// Returns the path of the file `span` is in, and the span relative to the start of
+    /// that file, or `None` if `span` is undefined.
+    pub fn file(&self, span: Span) -> Option<(&str, Span)> {
+        let (index, range) = crate::error::locate(&self.files, span)?;
+        Some((self.files[index].0.as_str(), range.into()))
+    }
+
+    /// Returns the path of the file `span` is in, and the [`SourceLocation`] of the span in
+    /// that file, or `None` if `span` is undefined.
+    pub fn location(&self, span: Span) -> Option<(&str, SourceLocation)> {
+        let (index, range) = crate::error::locate(&self.files, span)?;
+        let (ref path, ref file_range) = self.files[index];
+        let location = Span::from(range).location(&self.text[file_range.clone()]);
+        Some((path.as_str(), location))
+    }
+
+    pub(super) fn ranges(&self) -> Vec<Range<usize>> {
+        self.files
+            .iter()
+            .map(|&(_, ref range)| range.clone())
+            .collect()
+    }
+
+    /// Replaces the files with the file at `path`, and the files it imports, which are
+    /// loaded by `import`.
+    ///
+    /// `import` is called with the path of the importing file and the path written in the
+    /// `import` directive. If it fails, the error is returned with the span of the path in
+    /// the directive.
+    pub(super) fn load<'a, E: fmt::Display>(
+        &mut self,
+        path: &str,
+        source: &str,
+        mut import: impl FnMut(&str, &str) -> Result<ImportedFile, E>,
+    ) -> Result<(), Box<Error<'a>>> {
+        self.text.clear();
+        self.files.clear();
+        self.push(path, source);
+
+        let mut next = 0;
+        while let Some((importer, range)) = self.files.get(next).cloned() {
+            for (imported, span) in imports(&self.text, range) {
+                let file = import(&importer, &imported).map_err(|error| {
+                    Box::new(Error::ImportFailed {
+                        span,
+                        reason: error.to_string(),
+                    })
+                })?;
+                if self.files.iter().all(|&(ref path, _)| *path != file.path) {
+                    self.push(file.path, &file.source);
+                }
+            }
+            next += 1;
+        }
+        Ok(())
+    }
+
+    /// Checks that the `import` directives of the files name files in `self`.
+    ///
+    /// The files are parsed together, so the imported files need not be loaded.
+    pub(super) fn check_imports<'a>(&self) -> Result<(), Box<Error<'a>>> {
+        for &(_, ref range) in self.files.iter() {
+            for (imported, span) in imports(&self.text, range.clone()) {
+                if self.files.iter().all(|&(ref path, _)| *path != imported) {
+                    return Err(Box::new(Error::ImportFailed {
+                        span,
+                        reason: format!("there is no source named `{imported}`"),
+                    }));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the paths in the `import` directives of the file in `range` of `source`, with
+/// their spans.
+///
+/// This only looks at the directives at the start of the file, and stops at the first
+/// syntax error, which is reported when the file is parsed.
+fn imports(source: &str, range: Range<usize>) -> Vec<(String, Span)> {
+    let mut lexer = Lexer::new_in_range(source, range, true);
+    let mut imports = Vec::new();
+    while let (Token::Word(word), _) = lexer.next() {
+        match DirectiveKind::from_ident(word) {
+            Some(DirectiveKind::Import) => match (lexer.next(), lexer.next().0) {
+                ((Token::String(path), span), Token::Separator(';')) => {
+                    imports.push((path.to_string(), span));
+                }
+                _ => break,
+            },
+            Some(_) => loop {
+                match lexer.next().0 {
+                    Token::Separator(';') => break,
+                    Token::End => return imports,
+                    _ => {}
+                }
+            },
+            None => break,
+        }
+    }
+    imports
+}
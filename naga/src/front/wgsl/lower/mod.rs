@@ -2056,6 +2056,24 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     .insert(value, ("phony".to_string(), stmt.span));
                 return Ok(());
             }
+            ast::StatementKind::DebugPrintf {
+                ref format,
+                ref arguments,
+            } => {
+                let mut emitter = proc::Emitter::default();
+                emitter.start(&ctx.function.expressions);
+
+                let arguments = arguments
+                    .iter()
+                    .map(|&arg| self.expression(arg, &mut ctx.as_expression(block, &mut emitter)))
+                    .collect::<Result<Vec<_>>>()?;
+                block.extend(emitter.finish(&ctx.function.expressions));
+
+                ir::Statement::DebugPrintf {
+                    format: format.clone(),
+                    arguments,
+                }
+            }
         };
 
         block.push(out, stmt.span);
@@ -2850,13 +2868,21 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 })
                                 .transpose()?;
 
-                            let level = class
-                                .is_mipmapped()
-                                .then(|| {
-                                    args.min_args += 1;
-                                    self.expression(args.next()?, ctx)
-                                })
-                                .transpose()?;
+                            let level = match class {
+                                // External textures have a single level, which
+                                // WGSL doesn't let you name.
+                                ir::ImageClass::External => Some(ctx.interrupt_emitter(
+                                    ir::Expression::Literal(ir::Literal::U32(0)),
+                                    span,
+                                )?),
+                                _ => class
+                                    .is_mipmapped()
+                                    .then(|| {
+                                        args.min_args += 1;
+                                        self.expression(args.next()?, ctx)
+                                    })
+                                    .transpose()?,
+                            };
 
                             let sample = class
                                 .is_multisampled()
@@ -2918,6 +2944,46 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 query: ir::ImageQuery::NumSamples,
                             }
                         }
+                        "traceRay" => {
+                            let mut args = ctx.prepare_args(arguments, 6, span);
+                            let acceleration_structure = self.expression(args.next()?, ctx)?;
+                            let descriptor = self.expression(args.next()?, ctx)?;
+                            let sbt_record_offset = self.expression(args.next()?, ctx)?;
+                            let sbt_record_stride = self.expression(args.next()?, ctx)?;
+                            let miss_index = self.expression(args.next()?, ctx)?;
+                            let payload = self.expression(args.next()?, ctx)?;
+                            args.finish()?;
+
+                            let _ = ctx.module.generate_ray_desc_type();
+                            let fun = ir::RayPipelineFunction::TraceRay {
+                                acceleration_structure,
+                                descriptor,
+                                sbt_record_offset,
+                                sbt_record_stride,
+                                miss_index,
+                                payload,
+                            };
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .extend(rctx.emitter.finish(&rctx.function.expressions));
+                            rctx.emitter.start(&rctx.function.expressions);
+                            rctx.block
+                                .push(ir::Statement::RayPipelineFunction(fun), span);
+                            return Ok(None);
+                        }
+                        "ignoreIntersection" | "terminateRay" => {
+                            ctx.prepare_args(arguments, 0, span).finish()?;
+
+                            let fun = match function.name {
+                                "ignoreIntersection" => ir::RayPipelineFunction::IgnoreIntersection,
+                                _ => ir::RayPipelineFunction::TerminateRay,
+                            };
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .push(ir::Statement::RayPipelineFunction(fun), span);
+                            return Ok(None);
+                        }
                         "rayQueryInitialize" => {
                             let mut args = ctx.prepare_args(arguments, 3, span);
                             let query = self.ray_query_pointer(args.next()?, ctx)?;
@@ -3590,9 +3656,12 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                         self.expression_with_leaf_scalar(args.next()?, ir::Scalar::F32, ctx)?
                     }
 
-                    // Sampling `Storage` textures isn't allowed at all. Let the
-                    // validator report the error.
-                    ir::ImageClass::Storage { .. } => self.expression(args.next()?, ctx)?,
+                    // Sampling `Storage` textures isn't allowed at all, and
+                    // `External` textures can only be sampled at level zero. Let
+                    // the validator report the error.
+                    ir::ImageClass::Storage { .. } | ir::ImageClass::External => {
+                        self.expression(args.next()?, ctx)?
+                    }
                 };
                 level = ir::SampleLevel::Exact(exact);
                 depth_ref = None;
@@ -4021,6 +4090,11 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 binding.apply_default_interpolation(&ctx.module.types[ty].inner);
                 Some(binding)
             }
+            Some(ast::Binding::Color(location)) => {
+                Some(ir::Binding::BuiltIn(ir::BuiltIn::FramebufferFetch {
+                    location: self.const_u32(location, &mut ctx.as_const())?.0,
+                }))
+            }
             None => None,
         })
     }
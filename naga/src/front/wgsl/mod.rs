@@ -5,6 +5,7 @@ Frontend for [WGSL][wgsl] (WebGPU Shading Language).
 */
 
 mod error;
+mod import;
 mod index;
 mod lower;
 mod parse;
@@ -12,6 +13,7 @@ mod parse;
 mod tests;
 
 pub use crate::front::wgsl::error::ParseError;
+pub use crate::front::wgsl::import::{ImportedFile, Sources};
 pub use crate::front::wgsl::parse::Options;
 pub use crate::front::wgsl::parse::directive::language_extension::{
     ImplementedLanguageExtension, LanguageExtension, UnimplementedLanguageExtension,
@@ -53,6 +55,61 @@ impl Frontend {
         self.inner(source).map_err(|x| x.as_parse_error(source))
     }
 
+    /// Parses the file at `path`, and the files it imports, as a single module.
+    ///
+    /// Files import other files with `import` directives, which are written before the
+    /// declarations of the file, like `enable` directives:
+    ///
+    /// ```wgsl
+    /// import "lighting.wgsl";
+    ///
+    /// @fragment
+    /// fn main(@location(0) normal: vec3f) -> @location(0) vec4f {
+    ///     return shade(normal);
+    /// }
+    /// ```
+    ///
+    /// The files are loaded by `import`, which is called with the path of the importing
+    /// file and the path written in the directive, and usually resolves the second path
+    /// relative to the first and reads the file. Each file is only parsed once, even if it
+    /// is imported several times. The declarations of all the files share one namespace, so
+    /// a name declared in two files is an error, and the enable extensions and diagnostic
+    /// directives of each file apply to the whole module.
+    ///
+    /// The files are stored in `sources`, which the spans of the module and of the error
+    /// refer to. Use [`ParseError::emit_to_string_with_sources`] to show errors.
+    pub fn parse_with_imports<E: core::fmt::Display>(
+        &mut self,
+        sources: &mut Sources,
+        path: &str,
+        source: &str,
+        import: impl FnMut(&str, &str) -> core::result::Result<ImportedFile, E>,
+    ) -> core::result::Result<crate::Module, ParseError> {
+        if let Err(error) = sources.load(path, source, import) {
+            return Err(error.as_parse_error(sources.text()));
+        }
+        self.inner_files(sources.text(), &sources.ranges())
+            .map_err(|x| x.as_parse_error(sources.text()))
+    }
+
+    /// Parses the files of `sources` as a single module.
+    ///
+    /// This is like [`Frontend::parse_with_imports`], except that the files are given
+    /// up front, with [`Sources::push`]. The `import` directives of the files must name
+    /// other files of `sources`, and have no other effect, since all the files are parsed.
+    ///
+    /// The spans of the module and of the error refer to `sources`. Use
+    /// [`ParseError::emit_to_string_with_sources`] to show errors.
+    pub fn parse_sources(
+        &mut self,
+        sources: &Sources,
+    ) -> core::result::Result<crate::Module, ParseError> {
+        sources
+            .check_imports()
+            .and_then(|()| self.inner_files(sources.text(), &sources.ranges()))
+            .map_err(|x| x.as_parse_error(sources.text()))
+    }
+
     fn inner<'a>(&mut self, source: &'a str) -> Result<'a, crate::Module> {
         let tu = self.parser.parse(source, &self.options)?;
         let index = index::Index::generate(&tu)?;
@@ -60,6 +117,20 @@ impl Frontend {
 
         Ok(module)
     }
+
+    fn inner_files<'a>(
+        &mut self,
+        source: &'a str,
+        files: &[core::ops::Range<usize>],
+    ) -> Result<'a, crate::Module> {
+        let tu = self
+            .parser
+            .parse_files(source, files, true, &self.options)?;
+        let index = index::Index::generate(&tu)?;
+        let module = Lowerer::new(&index).lower(tu)?;
+
+        Ok(module)
+    }
 }
 
 /// <div class="warning">
@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use core::hash::Hash;
 
 use crate::diagnostic_filter::DiagnosticFilterNode;
@@ -153,6 +153,8 @@ pub enum Binding<'a> {
         sampling: Option<crate::Sampling>,
         blend_src: Option<Handle<Expression<'a>>>,
     },
+    /// A fragment input with the `color` attribute, read with framebuffer fetch.
+    Color(Handle<Expression<'a>>),
 }
 
 #[derive(Debug)]
@@ -317,6 +319,10 @@ pub enum StatementKind<'a> {
     Decrement(Handle<Expression<'a>>),
     Phony(Handle<Expression<'a>>),
     ConstAssert(Handle<Expression<'a>>),
+    DebugPrintf {
+        format: String,
+        arguments: Vec<Handle<Expression<'a>>>,
+    },
 }
 
 #[derive(Debug)]
@@ -4,10 +4,14 @@ use crate::front::wgsl::parse::directive::enable_extension::{
 };
 use crate::front::wgsl::{Error, Result, Scalar};
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String};
 
-pub fn map_address_space(word: &str, span: Span) -> Result<'_, crate::AddressSpace> {
-    match word {
+pub fn map_address_space<'a>(
+    enable_extensions: &EnableExtensions,
+    word: &str,
+    span: Span,
+) -> Result<'a, crate::AddressSpace> {
+    let space = match word {
         "private" => Ok(crate::AddressSpace::Private),
         "workgroup" => Ok(crate::AddressSpace::WorkGroup),
         "uniform" => Ok(crate::AddressSpace::Uniform),
@@ -16,8 +20,21 @@ pub fn map_address_space(word: &str, span: Span) -> Result<'_, crate::AddressSpa
         }),
         "push_constant" => Ok(crate::AddressSpace::PushConstant),
         "function" => Ok(crate::AddressSpace::Function),
+        "ray_payload" => Ok(crate::AddressSpace::RayPayload),
+        "incoming_ray_payload" => Ok(crate::AddressSpace::IncomingRayPayload),
         _ => Err(Box::new(Error::UnknownAddressSpace(span))),
+    }?;
+    if matches!(
+        space,
+        crate::AddressSpace::RayPayload | crate::AddressSpace::IncomingRayPayload
+    ) && !enable_extensions.contains(ImplementedEnableExtension::RayTracingPipeline)
+    {
+        return Err(Box::new(Error::EnableExtensionNotEnabled {
+            span,
+            kind: ImplementedEnableExtension::RayTracingPipeline.into(),
+        }));
     }
+    Ok(space)
 }
 
 pub fn map_built_in(
@@ -32,12 +49,15 @@ pub fn map_built_in(
         "instance_index" => crate::BuiltIn::InstanceIndex,
         "view_index" => crate::BuiltIn::ViewIndex,
         "clip_distances" => crate::BuiltIn::ClipDistance,
+        "viewport_index" => crate::BuiltIn::ViewportIndex,
+        "layer" => crate::BuiltIn::Layer,
         // fragment
         "front_facing" => crate::BuiltIn::FrontFacing,
         "frag_depth" => crate::BuiltIn::FragDepth,
         "primitive_index" => crate::BuiltIn::PrimitiveIndex,
         "sample_index" => crate::BuiltIn::SampleIndex,
         "sample_mask" => crate::BuiltIn::SampleMask,
+        "fully_covered" => crate::BuiltIn::FullyCovered,
         // compute
         "global_invocation_id" => crate::BuiltIn::GlobalInvocationId,
         "local_invocation_id" => crate::BuiltIn::LocalInvocationId,
@@ -49,6 +69,22 @@ pub fn map_built_in(
         "subgroup_id" => crate::BuiltIn::SubgroupId,
         "subgroup_size" => crate::BuiltIn::SubgroupSize,
         "subgroup_invocation_id" => crate::BuiltIn::SubgroupInvocationId,
+        // ray tracing pipeline
+        "ray_launch_id" => crate::BuiltIn::LaunchId,
+        "ray_launch_size" => crate::BuiltIn::LaunchSize,
+        "world_ray_origin" => crate::BuiltIn::WorldRayOrigin,
+        "world_ray_direction" => crate::BuiltIn::WorldRayDirection,
+        "object_ray_origin" => crate::BuiltIn::ObjectRayOrigin,
+        "object_ray_direction" => crate::BuiltIn::ObjectRayDirection,
+        "ray_t_min" => crate::BuiltIn::RayTMin,
+        "ray_t_current_max" => crate::BuiltIn::RayTCurrentMax,
+        "incoming_ray_flags" => crate::BuiltIn::IncomingRayFlags,
+        "instance_custom_data" => crate::BuiltIn::InstanceCustomData,
+        "geometry_index" => crate::BuiltIn::GeometryIndex,
+        "object_to_world" => crate::BuiltIn::ObjectToWorld,
+        "world_to_object" => crate::BuiltIn::WorldToObject,
+        "hit_kind" => crate::BuiltIn::HitKind,
+        "barycentrics" => crate::BuiltIn::Barycentrics,
         _ => return Err(Box::new(Error::UnknownBuiltin(span))),
     };
     match built_in {
@@ -60,6 +96,28 @@ pub fn map_built_in(
                 }));
             }
         }
+        crate::BuiltIn::LaunchId
+        | crate::BuiltIn::LaunchSize
+        | crate::BuiltIn::WorldRayOrigin
+        | crate::BuiltIn::WorldRayDirection
+        | crate::BuiltIn::ObjectRayOrigin
+        | crate::BuiltIn::ObjectRayDirection
+        | crate::BuiltIn::RayTMin
+        | crate::BuiltIn::RayTCurrentMax
+        | crate::BuiltIn::IncomingRayFlags
+        | crate::BuiltIn::InstanceCustomData
+        | crate::BuiltIn::GeometryIndex
+        | crate::BuiltIn::ObjectToWorld
+        | crate::BuiltIn::WorldToObject
+        | crate::BuiltIn::HitKind
+        | crate::BuiltIn::Barycentrics => {
+            if !enable_extensions.contains(ImplementedEnableExtension::RayTracingPipeline) {
+                return Err(Box::new(Error::EnableExtensionNotEnabled {
+                    span,
+                    kind: ImplementedEnableExtension::RayTracingPipeline.into(),
+                }));
+            }
+        }
         _ => {}
     }
     Ok(built_in)
@@ -168,6 +226,10 @@ pub fn get_scalar_type(
             kind: Sk::Uint,
             width: 8,
         }),
+        "i8" => Some(Scalar::I8),
+        "u8" => Some(Scalar::U8),
+        "i16" => Some(Scalar::I16),
+        "u16" => Some(Scalar::U16),
         "bool" => Some(Scalar {
             kind: Sk::Bool,
             width: crate::BOOL_WIDTH,
@@ -336,3 +398,28 @@ pub fn map_subgroup_operation(
         _ => return None,
     })
 }
+
+/// Decodes the escape sequences in the body of a string literal spanning `span`.
+pub fn map_string_literal(body: &str, span: Span) -> Result<'_, String> {
+    let mut string = String::with_capacity(body.len());
+    let mut chars = body.char_indices();
+    while let Some((backslash, c)) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+        string.push(match chars.next() {
+            Some((_, '"')) => '"',
+            Some((_, '\\')) => '\\',
+            Some((_, 'n')) => '\n',
+            Some((_, 't')) => '\t',
+            other => {
+                // `body` starts after the opening quote of the literal.
+                let start = span.to_range().unwrap_or_default().start + 1 + backslash;
+                let end = start + 1 + other.map_or(0, |(_, c)| c.len_utf8());
+                return Err(Box::new(Error::InvalidStringEscape(Span::from(start..end))));
+            }
+        });
+    }
+    Ok(string)
+}
@@ -17,12 +17,16 @@ pub(crate) enum DirectiveKind {
     Enable,
     /// A [`language_extension`].
     Requires,
+    /// An import of another file, which is loaded by
+    /// [`Frontend::parse_with_imports`](crate::front::wgsl::Frontend::parse_with_imports).
+    Import,
 }
 
 impl DirectiveKind {
     const DIAGNOSTIC: &'static str = "diagnostic";
     const ENABLE: &'static str = "enable";
     const REQUIRES: &'static str = "requires";
+    const IMPORT: &'static str = "import";
 
     /// Convert from a sentinel word in WGSL into its associated [`DirectiveKind`], if possible.
     pub fn from_ident(s: &str) -> Option<Self> {
@@ -30,6 +34,7 @@ impl DirectiveKind {
             Self::DIAGNOSTIC => Self::Diagnostic,
             Self::ENABLE => Self::Enable,
             Self::REQUIRES => Self::Requires,
+            Self::IMPORT => Self::Import,
             _ => return None,
         })
     }
@@ -101,6 +106,19 @@ error: expected global declaration, but found a global directive
   │
   = note: global directives are only allowed before global declarations; maybe hoist this closer to the top of the shader module?
 
+";
+                }
+                DirectiveKind::Import => {
+                    directive = "import \"common.wgsl\"";
+                    expected_msg = "\
+error: expected global declaration, but found a global directive
+  ┌─ wgsl:2:1
+  │
+2 │ import \"common.wgsl\";
+  │ ^^^^^^ written after first global declaration
+  │
+  = note: global directives are only allowed before global declarations; maybe hoist this closer to the top of the shader module?
+
 ";
                 }
             }
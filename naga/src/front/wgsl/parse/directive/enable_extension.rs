@@ -14,6 +14,9 @@ pub struct EnableExtensions {
     /// Whether `enable f16;` was written earlier in the shader module.
     f16: bool,
     clip_distances: bool,
+    framebuffer_fetch: bool,
+    debug_printf: bool,
+    ray_tracing_pipeline: bool,
 }
 
 impl EnableExtensions {
@@ -22,6 +25,9 @@ impl EnableExtensions {
             f16: false,
             dual_source_blending: false,
             clip_distances: false,
+            framebuffer_fetch: false,
+            debug_printf: false,
+            ray_tracing_pipeline: false,
         }
     }
 
@@ -31,6 +37,9 @@ impl EnableExtensions {
             ImplementedEnableExtension::DualSourceBlending => &mut self.dual_source_blending,
             ImplementedEnableExtension::F16 => &mut self.f16,
             ImplementedEnableExtension::ClipDistances => &mut self.clip_distances,
+            ImplementedEnableExtension::FramebufferFetch => &mut self.framebuffer_fetch,
+            ImplementedEnableExtension::DebugPrintf => &mut self.debug_printf,
+            ImplementedEnableExtension::RayTracingPipeline => &mut self.ray_tracing_pipeline,
         };
         *field = true;
     }
@@ -41,6 +50,9 @@ impl EnableExtensions {
             ImplementedEnableExtension::DualSourceBlending => self.dual_source_blending,
             ImplementedEnableExtension::F16 => self.f16,
             ImplementedEnableExtension::ClipDistances => self.clip_distances,
+            ImplementedEnableExtension::FramebufferFetch => self.framebuffer_fetch,
+            ImplementedEnableExtension::DebugPrintf => self.debug_printf,
+            ImplementedEnableExtension::RayTracingPipeline => self.ray_tracing_pipeline,
         }
     }
 }
@@ -71,6 +83,9 @@ impl EnableExtension {
     const CLIP_DISTANCES: &'static str = "clip_distances";
     const DUAL_SOURCE_BLENDING: &'static str = "dual_source_blending";
     const SUBGROUPS: &'static str = "subgroups";
+    const WGPU_FRAMEBUFFER_FETCH: &'static str = "wgpu_framebuffer_fetch";
+    const WGPU_DEBUG_PRINTF: &'static str = "wgpu_debug_printf";
+    const WGPU_RAY_TRACING_PIPELINE: &'static str = "wgpu_ray_tracing_pipeline";
 
     /// Convert from a sentinel word in WGSL into its associated [`EnableExtension`], if possible.
     pub(crate) fn from_ident(word: &str, span: Span) -> Result<'_, Self> {
@@ -81,6 +96,13 @@ impl EnableExtension {
                 Self::Implemented(ImplementedEnableExtension::DualSourceBlending)
             }
             Self::SUBGROUPS => Self::Unimplemented(UnimplementedEnableExtension::Subgroups),
+            Self::WGPU_FRAMEBUFFER_FETCH => {
+                Self::Implemented(ImplementedEnableExtension::FramebufferFetch)
+            }
+            Self::WGPU_DEBUG_PRINTF => Self::Implemented(ImplementedEnableExtension::DebugPrintf),
+            Self::WGPU_RAY_TRACING_PIPELINE => {
+                Self::Implemented(ImplementedEnableExtension::RayTracingPipeline)
+            }
             _ => return Err(Box::new(Error::UnknownEnableExtension(span, word))),
         })
     }
@@ -92,6 +114,9 @@ impl EnableExtension {
                 ImplementedEnableExtension::DualSourceBlending => Self::DUAL_SOURCE_BLENDING,
                 ImplementedEnableExtension::F16 => Self::F16,
                 ImplementedEnableExtension::ClipDistances => Self::CLIP_DISTANCES,
+                ImplementedEnableExtension::FramebufferFetch => Self::WGPU_FRAMEBUFFER_FETCH,
+                ImplementedEnableExtension::DebugPrintf => Self::WGPU_DEBUG_PRINTF,
+                ImplementedEnableExtension::RayTracingPipeline => Self::WGPU_RAY_TRACING_PIPELINE,
             },
             Self::Unimplemented(kind) => match kind {
                 UnimplementedEnableExtension::Subgroups => Self::SUBGROUPS,
@@ -121,6 +146,24 @@ pub enum ImplementedEnableExtension {
     ///
     /// [`enable clip_distances;`]: https://www.w3.org/TR/WGSL/#extension-clip_distances
     ClipDistances,
+    /// Enables the `color` attribute on fragment inputs in WGSL, which reads the
+    /// current value of a color attachment.
+    ///
+    /// This is not part of the WGSL standard, and corresponds to
+    /// `enable wgpu_framebuffer_fetch;`.
+    FramebufferFetch,
+    /// Enables the `debugPrintf` statement in WGSL, which prints a formatted message
+    /// through the graphics API's debugging tools.
+    ///
+    /// This is not part of the WGSL standard, and corresponds to
+    /// `enable wgpu_debug_printf;`.
+    DebugPrintf,
+    /// Enables the ray generation, miss, closest hit and any hit shader stages
+    /// in WGSL, along with the ray payload address spaces and built-ins they use.
+    ///
+    /// This is not part of the WGSL standard, and corresponds to
+    /// `enable wgpu_ray_tracing_pipeline;`.
+    RayTracingPipeline,
 }
 
 /// A variant of [`EnableExtension::Unimplemented`].
@@ -6,6 +6,7 @@ use crate::front::wgsl::parse::directive::enable_extension::EnableExtensions;
 use crate::front::wgsl::parse::{Number, conv};
 
 use alloc::{boxed::Box, vec::Vec};
+use core::ops::Range;
 
 type TokenSpan<'a> = (Token<'a>, Span);
 
@@ -16,6 +17,8 @@ pub enum Token<'a> {
     Attribute,
     Number(core::result::Result<Number, NumberError>),
     Word(&'a str),
+    /// The contents of a string literal, between the quotes and with escapes undecoded.
+    String(&'a str),
     Operation(char),
     LogicalOperation(char),
     ShiftOperation(char),
@@ -205,6 +208,22 @@ fn consume_token(input: &str, generic: bool, ignore_doc_comments: bool) -> (Toke
             let (word, rest) = consume_any(input, is_word_part);
             (Token::Word(word), rest)
         }
+        '"' => {
+            // String literals aren't part of WGSL; they are only used as the format
+            // of `debugPrintf`. They can't span lines, and `\` escapes the next char.
+            let body = chars.as_str();
+            let mut escaped = false;
+            for (i, c) in body.char_indices() {
+                match c {
+                    '\n' | '\r' => break,
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => return (Token::String(&body[..i]), &body[i + 1..]),
+                    _ => {}
+                }
+            }
+            (Token::Unknown(cur), body)
+        }
         _ => (Token::Unknown(cur), chars.as_str()),
     }
 }
@@ -280,6 +299,25 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Creates a lexer for the part `range` of `source`, whose spans are offsets in all
+    /// of `source`.
+    ///
+    /// This is used to lex the files of a module with imports, which are stored one after
+    /// another in the same string.
+    pub(in crate::front::wgsl) fn new_in_range(
+        source: &'a str,
+        range: Range<usize>,
+        ignore_doc_comments: bool,
+    ) -> Self {
+        Lexer {
+            input: &source[range.clone()],
+            source: &source[..range.end],
+            last_end_offset: range.start,
+            enable_extensions: EnableExtensions::empty(),
+            ignore_doc_comments,
+        }
+    }
+
     /// Calls the function with a lexer and returns the result of the function as well as the span for everything the function parsed
     ///
     /// # Examples
@@ -468,6 +506,17 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Parses a string literal, returning its contents with escapes undecoded.
+    pub(in crate::front::wgsl) fn next_string(&mut self) -> Result<'a, (&'a str, Span)> {
+        match self.next() {
+            (Token::String(string), span) => Ok((string, span)),
+            other => Err(Box::new(Error::Unexpected(
+                other.1,
+                ExpectedToken::StringLiteral,
+            ))),
+        }
+    }
+
     pub(in crate::front::wgsl) fn peek_ident_with_span(&mut self) -> Result<'a, (&'a str, Span)> {
         match self.peek() {
             (Token::Word(word), span) => Self::word_as_ident_with_span(word, span),
@@ -876,7 +925,27 @@ fn test_tokens() {
             Token::Number(Ok(Number::AbstractFloat(1.125))),
             Token::Word("lf"),
         ],
-    )
+    );
+
+    // String literals end at an unescaped quote, and can't span lines.
+    sub_test(
+        r#""a %d" "\"\\" "b"#,
+        &[
+            Token::String("a %d"),
+            Token::String(r#"\"\\"#),
+            Token::Unknown('"'),
+            Token::Word("b"),
+        ],
+    );
+    sub_test(
+        "\"a\nb\"",
+        &[
+            Token::Unknown('"'),
+            Token::Word("a"),
+            Token::Word("b"),
+            Token::Unknown('"'),
+        ],
+    );
 }
 
 #[test]
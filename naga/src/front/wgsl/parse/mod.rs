@@ -1,4 +1,5 @@
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::ops::Range;
 use directive::enable_extension::ImplementedEnableExtension;
 
 use crate::diagnostic_filter::{
@@ -178,6 +179,7 @@ struct BindingParser<'a> {
     sampling: ParsedAttribute<crate::Sampling>,
     invariant: ParsedAttribute<bool>,
     blend_src: ParsedAttribute<Handle<ast::Expression<'a>>>,
+    color: ParsedAttribute<Handle<ast::Expression<'a>>>,
 }
 
 impl<'a> BindingParser<'a> {
@@ -237,12 +239,41 @@ impl<'a> BindingParser<'a> {
                     .set(parser.general_expression(lexer, ctx)?, name_span)?;
                 lexer.expect(Token::Paren(')'))?;
             }
+            "color" => {
+                if !lexer
+                    .enable_extensions
+                    .contains(ImplementedEnableExtension::FramebufferFetch)
+                {
+                    return Err(Box::new(Error::EnableExtensionNotEnabled {
+                        span: name_span,
+                        kind: ImplementedEnableExtension::FramebufferFetch.into(),
+                    }));
+                }
+
+                lexer.expect(Token::Paren('('))?;
+                self.color
+                    .set(parser.general_expression(lexer, ctx)?, name_span)?;
+                lexer.expect(Token::Paren(')'))?;
+            }
             _ => return Err(Box::new(Error::UnknownAttribute(name_span))),
         }
         Ok(())
     }
 
     fn finish(self, span: Span) -> Result<'a, Option<ast::Binding<'a>>> {
+        if let Some(color) = self.color.value {
+            return match (
+                self.location.value,
+                self.built_in.value,
+                self.interpolation.value,
+                self.sampling.value,
+                self.invariant.value.unwrap_or_default(),
+                self.blend_src.value,
+            ) {
+                (None, None, None, None, false, None) => Ok(Some(ast::Binding::Color(color))),
+                _ => Err(Box::new(Error::InconsistentBinding(span))),
+            };
+        }
         match (
             self.location.value,
             self.built_in.value,
@@ -278,16 +309,34 @@ impl<'a> BindingParser<'a> {
 }
 
 /// Configuration for the whole parser run.
+#[derive(Clone, Debug, Default)]
 pub struct Options {
     /// Controls whether the parser should parse doc comments.
     pub parse_doc_comments: bool,
+    /// The defines which are `true` in the conditions of `@if(…)` attributes. Any other
+    /// name is `false`.
+    ///
+    /// Declarations, struct members and compound statements with an `@if(…)` attribute
+    /// whose condition is `false` are parsed, but left out of the module, like this
+    /// function unless `"debug"` is defined:
+    ///
+    /// ```wgsl
+    /// @if(debug && !mobile)
+    /// fn check(value: f32) -> f32 {
+    ///     return select(value, 0.0, value != value);
+    /// }
+    /// ```
+    ///
+    /// Conditions are made of defines, `true`, `false`, `!`, `&&`, `||` and parentheses.
+    pub defines: Vec<String>,
 }
 
 impl Options {
-    /// Creates a new [`Options`] without doc comments parsing.
+    /// Creates a new [`Options`] without doc comments parsing or defines.
     pub const fn new() -> Self {
         Options {
             parse_doc_comments: false,
+            defines: Vec::new(),
         }
     }
 }
@@ -295,6 +344,8 @@ impl Options {
 pub struct Parser {
     rules: Vec<(Rule, usize)>,
     recursion_depth: u32,
+    /// The [`Options::defines`] of the current parse.
+    defines: Vec<String>,
 }
 
 impl Parser {
@@ -302,6 +353,7 @@ impl Parser {
         Parser {
             rules: Vec::new(),
             recursion_depth: 0,
+            defines: Vec::new(),
         }
     }
 
@@ -680,7 +732,8 @@ impl Parser {
             | "texture_storage_1d_array"
             | "texture_storage_2d"
             | "texture_storage_2d_array"
-            | "texture_storage_3d" => return Err(Box::new(Error::TypeNotConstructible(span))),
+            | "texture_storage_3d"
+            | "texture_external" => return Err(Box::new(Error::TypeNotConstructible(span))),
             _ => return Ok(None),
         };
 
@@ -948,6 +1001,18 @@ impl Parser {
                     ast::Expression::Ident(ident)
                 }
             }
+            (Token::String(_), span)
+                if !lexer
+                    .enable_extensions
+                    .contains(ImplementedEnableExtension::DebugPrintf) =>
+            {
+                // String literals are only allowed as the format of `debugPrintf`; point
+                // at the missing extension if that's what this was meant to be.
+                return Err(Box::new(Error::EnableExtensionNotEnabled {
+                    span,
+                    kind: ImplementedEnableExtension::DebugPrintf.into(),
+                }));
+            }
             other => {
                 return Err(Box::new(Error::Unexpected(
                     other.1,
@@ -1316,7 +1381,7 @@ impl Parser {
                     };
                     crate::AddressSpace::Storage { access }
                 }
-                _ => conv::map_address_space(class_str, span)?,
+                _ => conv::map_address_space(&lexer.enable_extensions, class_str, span)?,
             };
             lexer.expect(Token::Paren('>'))?;
         }
@@ -1366,12 +1431,17 @@ impl Parser {
             }
 
             let doc_comments = lexer.accumulate_doc_comments();
+            let unresolved = ctx.unresolved.len();
 
             let (mut size, mut align) = (ParsedAttribute::default(), ParsedAttribute::default());
             self.push_rule_span(Rule::Attribute, lexer);
             let mut bind_parser = BindingParser::default();
+            let mut enabled = ParsedAttribute::default();
             while lexer.skip(Token::Attribute) {
                 match lexer.next_ident_with_span()? {
+                    ("if", name_span) => {
+                        enabled.set(self.condition(lexer)?, name_span)?;
+                    }
                     ("size", name_span) => {
                         lexer.expect(Token::Paren('('))?;
                         let expr = self.general_expression(lexer, ctx)?;
@@ -1396,6 +1466,11 @@ impl Parser {
             let ty = self.type_decl(lexer, ctx)?;
             ready = lexer.skip(Token::Separator(','));
 
+            if enabled.value == Some(false) {
+                ctx.unresolved.truncate(unresolved);
+                continue;
+            }
+
             members.push(ast::StructMember {
                 name,
                 ty,
@@ -1689,7 +1764,7 @@ impl Parser {
             "ptr" => {
                 lexer.expect_generic_paren('<')?;
                 let (ident, span) = lexer.next_ident_with_span()?;
-                let mut space = conv::map_address_space(ident, span)?;
+                let mut space = conv::map_address_space(&lexer.enable_extensions, ident, span)?;
                 lexer.expect(Token::Separator(','))?;
                 let base = self.type_decl(lexer, ctx)?;
                 if let crate::AddressSpace::Storage { ref mut access } = space {
@@ -1907,6 +1982,11 @@ impl Parser {
                     class: crate::ImageClass::Storage { format, access },
                 }
             }
+            "texture_external" => ast::Type::Image {
+                dim: crate::ImageDimension::D2,
+                arrayed: false,
+                class: crate::ImageClass::External,
+            },
             "acceleration_structure" => {
                 let vertex_return = lexer.next_acceleration_structure_flags()?;
                 ast::Type::AccelerationStructure { vertex_return }
@@ -2471,6 +2551,22 @@ impl Parser {
                             lexer.expect(Token::Separator(';'))?;
                             ast::StatementKind::ConstAssert(condition)
                         }
+                        "debugPrintf"
+                            if lexer
+                                .enable_extensions
+                                .contains(ImplementedEnableExtension::DebugPrintf) =>
+                        {
+                            let _ = lexer.next();
+                            lexer.open_arguments()?;
+                            let (format, format_span) = lexer.next_string()?;
+                            let format = conv::map_string_literal(format, format_span)?;
+                            let mut arguments = Vec::new();
+                            while lexer.next_argument()? {
+                                arguments.push(this.general_expression(lexer, ctx)?);
+                            }
+                            lexer.expect(Token::Separator(';'))?;
+                            ast::StatementKind::DebugPrintf { format, arguments }
+                        }
                         // assignment or a function call
                         _ => {
                             this.function_call_or_assignment_statement(lexer, ctx, block)?;
@@ -2584,6 +2680,8 @@ impl Parser {
         ctx.local_table.push_scope();
 
         let mut diagnostic_filters = DiagnosticFilterMap::new();
+        let mut enabled = ParsedAttribute::default();
+        let unresolved = ctx.unresolved.len();
 
         self.push_rule_span(Rule::Attribute, lexer);
         while lexer.skip(Token::Attribute) {
@@ -2594,6 +2692,8 @@ impl Parser {
                 diagnostic_filters
                     .add(filter, span, ShouldConflictOnFullDuplicate::Yes)
                     .map_err(|e| Box::new(e.into()))?;
+            } else if name == "if" {
+                enabled.set(self.condition(lexer)?, name_span)?;
             } else {
                 return Err(Box::new(Error::Unexpected(
                     name_span,
@@ -2621,6 +2721,12 @@ impl Parser {
 
         ctx.local_table.pop_scope();
 
+        // A block whose condition doesn't hold is left empty.
+        if enabled.value == Some(false) {
+            block = ast::Block::default();
+            ctx.unresolved.truncate(unresolved);
+        }
+
         let span = self.pop_rule_span(lexer);
         Ok((block, span))
     }
@@ -2786,6 +2892,7 @@ impl Parser {
         let mut id = ParsedAttribute::default();
 
         let mut must_use: ParsedAttribute<Span> = ParsedAttribute::default();
+        let mut enabled = ParsedAttribute::default();
 
         let mut dependencies = FastIndexSet::default();
         let mut ctx = ExpressionContext {
@@ -2844,6 +2951,24 @@ impl Parser {
                     stage.set(ShaderStage::Compute, name_span)?;
                     compute_span = name_span;
                 }
+                "ray_generation" | "miss" | "closest_hit" | "any_hit" => {
+                    if !lexer
+                        .enable_extensions
+                        .contains(ImplementedEnableExtension::RayTracingPipeline)
+                    {
+                        return Err(Box::new(Error::EnableExtensionNotEnabled {
+                            span: name_span,
+                            kind: ImplementedEnableExtension::RayTracingPipeline.into(),
+                        }));
+                    }
+                    let value = match name {
+                        "ray_generation" => ShaderStage::RayGeneration,
+                        "miss" => ShaderStage::Miss,
+                        "closest_hit" => ShaderStage::ClosestHit,
+                        _ => ShaderStage::AnyHit,
+                    };
+                    stage.set(value, name_span)?;
+                }
                 "workgroup_size" => {
                     lexer.expect(Token::Paren('('))?;
                     let mut new_workgroup_size = [None; 3];
@@ -2878,6 +3003,9 @@ impl Parser {
                 "must_use" => {
                     must_use.set(name_span, name_span)?;
                 }
+                "if" => {
+                    enabled.set(self.condition(lexer)?, name_span)?;
+                }
                 _ => return Err(Box::new(Error::UnknownAttribute(name_span))),
             }
         }
@@ -3046,7 +3174,8 @@ impl Parser {
             }
         };
 
-        if let Some(kind) = kind {
+        // Declarations whose condition doesn't hold are left out, with their dependencies.
+        if let Some(kind) = kind.filter(|_| enabled.value.unwrap_or(true)) {
             out.decls.append(
                 ast::GlobalDecl { kind, dependencies },
                 lexer.span_from(start),
@@ -3071,89 +3200,126 @@ impl Parser {
         &mut self,
         source: &'a str,
         options: &Options,
+    ) -> Result<'a, ast::TranslationUnit<'a>> {
+        let file = 0..source.len();
+        self.parse_files(source, core::slice::from_ref(&file), false, options)
+    }
+
+    /// Parses the `files`, ranges of `source`, as a single module.
+    ///
+    /// The first file's module doc comments document the module. The enable extensions
+    /// and diagnostic directives of every file apply to the whole module. `import`
+    /// directives are skipped if `imports_resolved`, since the imported files are among the
+    /// `files`, or rejected otherwise.
+    pub fn parse_files<'a>(
+        &mut self,
+        source: &'a str,
+        files: &[Range<usize>],
+        imports_resolved: bool,
+        options: &Options,
     ) -> Result<'a, ast::TranslationUnit<'a>> {
         self.reset();
+        self.defines.clone_from(&options.defines);
 
-        let mut lexer = Lexer::new(source, !options.parse_doc_comments);
         let mut tu = ast::TranslationUnit::default();
         let mut enable_extensions = EnableExtensions::empty();
         let mut diagnostic_filters = DiagnosticFilterMap::new();
 
-        // Parse module doc comments.
-        tu.doc_comments = lexer.accumulate_module_doc_comments();
-
-        // Parse directives.
-        while let Ok((ident, _directive_ident_span)) = lexer.peek_ident_with_span() {
-            if let Some(kind) = DirectiveKind::from_ident(ident) {
-                self.push_rule_span(Rule::Directive, &mut lexer);
-                let _ = lexer.next_ident_with_span().unwrap();
-                match kind {
-                    DirectiveKind::Diagnostic => {
-                        let diagnostic_filter = self.diagnostic_filter(&mut lexer)?;
-                        let span = self.peek_rule_span(&lexer);
-                        diagnostic_filters
-                            .add(diagnostic_filter, span, ShouldConflictOnFullDuplicate::No)
-                            .map_err(|e| Box::new(e.into()))?;
-                        lexer.expect(Token::Separator(';'))?;
-                    }
-                    DirectiveKind::Enable => {
-                        self.directive_ident_list(&mut lexer, |ident, span| {
-                            let kind = EnableExtension::from_ident(ident, span)?;
-                            let extension = match kind {
-                                EnableExtension::Implemented(kind) => kind,
-                                EnableExtension::Unimplemented(kind) => {
-                                    return Err(Box::new(
-                                        Error::EnableExtensionNotYetImplemented { kind, span },
-                                    ));
-                                }
-                            };
-                            enable_extensions.add(extension);
-                            Ok(())
-                        })?;
-                    }
-                    DirectiveKind::Requires => {
-                        self.directive_ident_list(&mut lexer, |ident, span| {
-                            match LanguageExtension::from_ident(ident) {
-                                Some(LanguageExtension::Implemented(_kind)) => {
-                                    // NOTE: No further validation is needed for an extension, so
-                                    // just throw parsed information away. If we ever want to apply
-                                    // what we've parsed to diagnostics, maybe we'll want to refer
-                                    // to enabled extensions later?
-                                    Ok(())
-                                }
-                                Some(LanguageExtension::Unimplemented(kind)) => {
-                                    Err(Box::new(Error::LanguageExtensionNotYetImplemented {
-                                        kind,
-                                        span,
-                                    }))
+        for (index, range) in files.iter().enumerate() {
+            let mut lexer = Lexer::new_in_range(source, range.clone(), !options.parse_doc_comments);
+            let mut file_enable_extensions = EnableExtensions::empty();
+
+            // Parse module doc comments.
+            let doc_comments = lexer.accumulate_module_doc_comments();
+            if index == 0 {
+                tu.doc_comments = doc_comments;
+            }
+
+            // Parse directives.
+            while let Ok((ident, _directive_ident_span)) = lexer.peek_ident_with_span() {
+                if let Some(kind) = DirectiveKind::from_ident(ident) {
+                    self.push_rule_span(Rule::Directive, &mut lexer);
+                    let _ = lexer.next_ident_with_span().unwrap();
+                    match kind {
+                        DirectiveKind::Diagnostic => {
+                            let diagnostic_filter = self.diagnostic_filter(&mut lexer)?;
+                            let span = self.peek_rule_span(&lexer);
+                            diagnostic_filters
+                                .add(diagnostic_filter, span, ShouldConflictOnFullDuplicate::No)
+                                .map_err(|e| Box::new(e.into()))?;
+                            lexer.expect(Token::Separator(';'))?;
+                        }
+                        DirectiveKind::Enable => {
+                            self.directive_ident_list(&mut lexer, |ident, span| {
+                                let kind = EnableExtension::from_ident(ident, span)?;
+                                let extension = match kind {
+                                    EnableExtension::Implemented(kind) => kind,
+                                    EnableExtension::Unimplemented(kind) => {
+                                        return Err(Box::new(
+                                            Error::EnableExtensionNotYetImplemented { kind, span },
+                                        ));
+                                    }
+                                };
+                                file_enable_extensions.add(extension);
+                                enable_extensions.add(extension);
+                                Ok(())
+                            })?;
+                        }
+                        DirectiveKind::Requires => {
+                            self.directive_ident_list(&mut lexer, |ident, span| {
+                                match LanguageExtension::from_ident(ident) {
+                                    Some(LanguageExtension::Implemented(_kind)) => {
+                                        // NOTE: No further validation is needed for an extension, so
+                                        // just throw parsed information away. If we ever want to apply
+                                        // what we've parsed to diagnostics, maybe we'll want to refer
+                                        // to enabled extensions later?
+                                        Ok(())
+                                    }
+                                    Some(LanguageExtension::Unimplemented(kind)) => {
+                                        Err(Box::new(Error::LanguageExtensionNotYetImplemented {
+                                            kind,
+                                            span,
+                                        }))
+                                    }
+                                    None => {
+                                        Err(Box::new(Error::UnknownLanguageExtension(span, ident)))
+                                    }
                                 }
-                                None => Err(Box::new(Error::UnknownLanguageExtension(span, ident))),
+                            })?;
+                        }
+                        DirectiveKind::Import => {
+                            let (_path, span) = lexer.next_string()?;
+                            if !imports_resolved {
+                                return Err(Box::new(Error::UnresolvedImport(span)));
                             }
-                        })?;
+                            lexer.expect(Token::Separator(';'))?;
+                        }
                     }
+                    self.pop_rule_span(&lexer);
+                } else {
+                    break;
                 }
-                self.pop_rule_span(&lexer);
-            } else {
-                break;
             }
-        }
 
-        lexer.enable_extensions = enable_extensions.clone();
-        tu.enable_extensions = enable_extensions;
-        tu.diagnostic_filter_leaf =
-            Self::write_diagnostic_filters(&mut tu.diagnostic_filters, diagnostic_filters, None);
+            // Each file must enable the extensions it uses itself.
+            lexer.enable_extensions = file_enable_extensions;
 
-        loop {
-            match self.global_decl(&mut lexer, &mut tu) {
-                Err(error) => return Err(error),
-                Ok(()) => {
-                    if lexer.peek().0 == Token::End {
-                        break;
+            loop {
+                match self.global_decl(&mut lexer, &mut tu) {
+                    Err(error) => return Err(error),
+                    Ok(()) => {
+                        if lexer.peek().0 == Token::End {
+                            break;
+                        }
                     }
                 }
             }
         }
 
+        tu.enable_extensions = enable_extensions;
+        tu.diagnostic_filter_leaf =
+            Self::write_diagnostic_filters(&mut tu.diagnostic_filters, diagnostic_filters, None);
+
         Ok(tu)
     }
 
@@ -3175,6 +3341,48 @@ impl Parser {
         Ok(brace_nesting_level + 1)
     }
 
+    /// Parses the parenthesized condition of an `@if(…)` attribute, and returns whether it
+    /// holds with the [`Options::defines`].
+    fn condition<'a>(&mut self, lexer: &mut Lexer<'a>) -> Result<'a, bool> {
+        lexer.expect(Token::Paren('('))?;
+        let value = self.condition_or(lexer)?;
+        lexer.skip(Token::Separator(','));
+        lexer.expect(Token::Paren(')'))?;
+        Ok(value)
+    }
+
+    fn condition_or<'a>(&mut self, lexer: &mut Lexer<'a>) -> Result<'a, bool> {
+        let mut value = self.condition_and(lexer)?;
+        while lexer.skip(Token::LogicalOperation('|')) {
+            // Both sides are parsed, so the operators don't short-circuit.
+            value |= self.condition_and(lexer)?;
+        }
+        Ok(value)
+    }
+
+    fn condition_and<'a>(&mut self, lexer: &mut Lexer<'a>) -> Result<'a, bool> {
+        let mut value = self.condition_unary(lexer)?;
+        while lexer.skip(Token::LogicalOperation('&')) {
+            value &= self.condition_unary(lexer)?;
+        }
+        Ok(value)
+    }
+
+    fn condition_unary<'a>(&mut self, lexer: &mut Lexer<'a>) -> Result<'a, bool> {
+        self.track_recursion(|this| match lexer.next() {
+            (Token::Operation('!'), _) => Ok(!this.condition_unary(lexer)?),
+            (Token::Paren('('), _) => {
+                let value = this.condition_or(lexer)?;
+                lexer.expect(Token::Paren(')'))?;
+                Ok(value)
+            }
+            (Token::Word("true"), _) => Ok(true),
+            (Token::Word("false"), _) => Ok(false),
+            (Token::Word(word), _) => Ok(this.defines.iter().any(|define| define == word)),
+            (_, span) => Err(Box::new(Error::Unexpected(span, ExpectedToken::Condition))),
+        })
+    }
+
     fn diagnostic_filter<'a>(&self, lexer: &mut Lexer<'a>) -> Result<'a, DiagnosticFilter> {
         lexer.expect(Token::Paren('('))?;
 
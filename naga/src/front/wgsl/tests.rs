@@ -26,6 +26,7 @@ fn parse_types() {
     parse_str("var t: texture_multisampled_2d<u32>;").unwrap();
     parse_str("var t: texture_storage_1d<rgba8uint,write>;").unwrap();
     parse_str("var t: texture_storage_3d<r32float,read>;").unwrap();
+    parse_str("var t: texture_external;").unwrap();
 }
 
 #[test]
@@ -871,3 +872,312 @@ error: found conflicting `diagnostic(…)` rule(s)
         }
     }
 }
+
+mod imports {
+    use alloc::{string::String, vec::Vec};
+
+    use crate::front::wgsl::{Frontend, ImportedFile, ParseError, Sources, parse_str};
+
+    const MAIN: &str = r#"
+import "lighting.wgsl";
+import "math.wgsl";
+
+@fragment
+fn main(@location(0) normal: vec3f) -> @location(0) vec4f {
+    return vec4f(shade(normal) * square(2.0));
+}
+"#;
+
+    const LIGHTING: &str = r#"
+import "math.wgsl";
+
+const LIGHT: vec3f = vec3f(0.0, 1.0, 0.0);
+
+fn shade(normal: vec3f) -> f32 {
+    return square(dot(normal, LIGHT));
+}
+"#;
+
+    const MATH: &str = r#"
+// Imports of files which are already loaded are skipped.
+import "lighting.wgsl";
+
+fn square(x: f32) -> f32 {
+    return x * x;
+}
+"#;
+
+    /// Parses `MAIN` and `files`, which are looked up by the paths in `import` directives,
+    /// and returns the imports the callback was called for.
+    fn parse(
+        sources: &mut Sources,
+        files: &[(&str, &str)],
+    ) -> (Result<crate::Module, ParseError>, Vec<(String, String)>) {
+        let mut imports = Vec::new();
+        let result =
+            Frontend::new().parse_with_imports(sources, "main.wgsl", MAIN, |importer, path| {
+                imports.push((importer.into(), path.into()));
+                files
+                    .iter()
+                    .find(|&&(name, _)| name == path)
+                    .map(|&(name, source)| ImportedFile {
+                        path: name.into(),
+                        source: source.into(),
+                    })
+                    .ok_or("no such file")
+            });
+        (result, imports)
+    }
+
+    #[test]
+    fn imported_declarations() {
+        let mut sources = Sources::new();
+        let (result, imports) = parse(
+            &mut sources,
+            &[("lighting.wgsl", LIGHTING), ("math.wgsl", MATH)],
+        );
+        let module = result.unwrap();
+
+        let mut functions = module
+            .functions
+            .iter()
+            .map(|(_, function)| function.name.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        functions.sort();
+        assert_eq!(functions, ["shade", "square"]);
+        assert_eq!(module.entry_points[0].name, "main");
+
+        assert_eq!(
+            imports,
+            [
+                ("main.wgsl", "lighting.wgsl"),
+                ("main.wgsl", "math.wgsl"),
+                ("lighting.wgsl", "math.wgsl"),
+                ("math.wgsl", "lighting.wgsl"),
+            ]
+            .map(|(importer, path)| (String::from(importer), String::from(path)))
+        );
+        assert_eq!(
+            sources.files().map(|(path, _)| path).collect::<Vec<_>>(),
+            ["main.wgsl", "lighting.wgsl", "math.wgsl"]
+        );
+    }
+
+    #[test]
+    fn redefinition_in_another_file() {
+        let lighting = "fn square(x: f32) -> f32 { return x * x; }";
+        let mut sources = Sources::new();
+        let (result, _) = parse(
+            &mut sources,
+            &[("lighting.wgsl", lighting), ("math.wgsl", MATH)],
+        );
+        let error = result.unwrap_err();
+
+        assert_eq!(error.message(), "redefinition of `square`");
+        let locations = error
+            .labels()
+            .map(|(span, _)| {
+                let (path, location) = sources.location(span).unwrap();
+                (path, location.line_number, location.line_position)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(locations, [("math.wgsl", 5, 4), ("lighting.wgsl", 1, 4)]);
+    }
+
+    #[test]
+    fn missing_file() {
+        let mut sources = Sources::new();
+        let (result, _) = parse(&mut sources, &[("lighting.wgsl", LIGHTING)]);
+        let error = result.unwrap_err();
+
+        assert_eq!(
+            error.emit_to_string_with_sources(&sources),
+            "\
+error: failed to import \"math.wgsl\"
+  ┌─ main.wgsl:3:8
+  │
+3 │ import \"math.wgsl\";
+  │        ^^^^^^^^^^^ no such file
+
+"
+        );
+    }
+
+    #[test]
+    fn unresolved_import() {
+        let error = parse_str(MAIN).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "`import` directives can't be resolved here"
+        );
+    }
+
+    #[test]
+    fn named_sources() {
+        let mut sources = Sources::new();
+        sources.push("math.wgsl", "fn square(x: f32) -> f32 { return x * x; }");
+        sources.push("main.wgsl", "fn nine() -> f32 { return square(3.0); }");
+        let module = Frontend::new().parse_sources(&sources).unwrap();
+        assert_eq!(module.functions.len(), 2);
+
+        // Imports of pushed files are allowed, and other imports are errors.
+        let mut sources = Sources::new();
+        sources.push("main.wgsl", MAIN);
+        sources.push("lighting.wgsl", LIGHTING);
+        let error = Frontend::new().parse_sources(&sources).unwrap_err();
+        assert_eq!(
+            error.emit_to_string_with_sources(&sources),
+            "\
+error: failed to import \"math.wgsl\"
+  ┌─ main.wgsl:3:8
+  │
+3 │ import \"math.wgsl\";
+  │        ^^^^^^^^^^^ there is no source named `math.wgsl`
+
+"
+        );
+
+        sources.push("math.wgsl", MATH);
+        Frontend::new().parse_sources(&sources).unwrap();
+    }
+
+    #[test]
+    fn shader_error_in_named_sources() {
+        let mut sources = Sources::new();
+        sources.push("a.wgsl", "const A: f32 = 1.0;");
+        sources.push("b.wgsl", "\nconst B: f32 = A + C;");
+        let error = Frontend::new().parse_sources(&sources).unwrap_err();
+
+        let error = crate::error::ShaderError {
+            source: sources.text().into(),
+            files: sources.file_ranges().to_vec(),
+            label: Some("shader".into()),
+            inner: alloc::boxed::Box::new(error),
+        };
+        let (span, _) = error.inner.labels().next().unwrap();
+        let (file, location) = error.location(span).unwrap();
+        assert_eq!(
+            (file, location.line_number, location.line_position),
+            (Some("b.wgsl"), 2, 20)
+        );
+        assert_eq!(
+            alloc::format!("{error}"),
+            "
+Shader 'shader' parsing error: no definition in scope for identifier: `C`
+  ┌─ b.wgsl:2:20
+  │
+2 │ const B: f32 = A + C;
+  │                    ^ unknown identifier
+
+"
+        );
+    }
+}
+
+mod conditional_compilation {
+    use alloc::{string::String, vec::Vec};
+
+    use crate::front::wgsl::{Frontend, Options};
+
+    const SHADER: &str = "
+        struct Light {
+            position: vec3f,
+            @if(shadows) shadow_map: u32,
+        }
+
+        @if(shadows && !mobile)
+        const SHADOW_SAMPLES: u32 = 16;
+        @if(shadows && mobile)
+        const SHADOW_SAMPLES: u32 = 4;
+
+        @if(!shadows)
+        fn shadow(light: Light) -> f32 {
+            return 1.0;
+        }
+        @if(shadows)
+        fn shadow(light: Light) -> f32 {
+            var visible = 0.0;
+            for (var i = 0u; i < SHADOW_SAMPLES; i++) {
+                visible += f32(light.shadow_map & 1u);
+            }
+            @if(debug || (false)) {
+                visible = undefined_in_release;
+            }
+            return visible;
+        }
+    ";
+
+    fn parse(defines: &[&str]) -> crate::Module {
+        let options = Options {
+            defines: defines.iter().map(|&define| String::from(define)).collect(),
+            ..Options::new()
+        };
+        Frontend::new_with_options(options).parse(SHADER).unwrap()
+    }
+
+    fn members(module: &crate::Module) -> Vec<&str> {
+        let (_, ty) = module
+            .types
+            .iter()
+            .find(|&(_, ty)| ty.name.as_deref() == Some("Light"))
+            .unwrap();
+        let crate::TypeInner::Struct { ref members, .. } = ty.inner else {
+            unreachable!();
+        };
+        members
+            .iter()
+            .map(|member| member.name.as_deref().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn defines() {
+        let module = parse(&[]);
+        assert_eq!(members(&module), ["position"]);
+        assert!(module.constants.is_empty());
+        assert_eq!(module.functions.len(), 1);
+
+        let module = parse(&["shadows", "mobile"]);
+        assert_eq!(members(&module), ["position", "shadow_map"]);
+        let (_, samples) = module.constants.iter().next().unwrap();
+        assert_eq!(samples.name.as_deref(), Some("SHADOW_SAMPLES"));
+        assert!(matches!(
+            module.global_expressions[samples.init],
+            crate::Expression::Literal(crate::Literal::U32(4))
+        ));
+    }
+
+    #[test]
+    fn disabled_block_dependencies() {
+        // The disabled block refers to an undefined name, which is only an error if the
+        // block is enabled.
+        parse(&["shadows"]);
+        let error = Frontend::new_with_options(Options {
+            defines: ["shadows", "debug"].map(String::from).into(),
+            ..Options::new()
+        })
+        .parse(SHADER)
+        .unwrap_err();
+        assert_eq!(
+            error.message(),
+            "no definition in scope for identifier: `undefined_in_release`"
+        );
+    }
+
+    #[test]
+    fn invalid_condition() {
+        let parse_err = |shader| crate::front::wgsl::parse_str(shader).unwrap_err();
+        assert_eq!(
+            parse_err("@if(a + b) fn f() {}").message(),
+            "expected `)`, found \"+\""
+        );
+        assert_eq!(
+            parse_err("@if(+a) fn f() {}").message(),
+            "expected a define, `true`, `false`, `!` or `(`, found \"+\""
+        );
+        assert_eq!(
+            parse_err("@if(a) @if(b) fn f() {}").message(),
+            "repeated attribute: `if`"
+        );
+    }
+}
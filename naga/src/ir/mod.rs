@@ -327,6 +327,20 @@ pub enum ShaderStage {
     Compute,
     Task,
     Mesh,
+    RayGeneration,
+    Miss,
+    ClosestHit,
+    AnyHit,
+}
+
+impl ShaderStage {
+    /// Whether this is a stage of a ray tracing pipeline.
+    pub const fn is_ray_tracing(self) -> bool {
+        matches!(
+            self,
+            Self::RayGeneration | Self::Miss | Self::ClosestHit | Self::AnyHit
+        )
+    }
 }
 
 /// Addressing space of variables.
@@ -349,6 +363,19 @@ pub enum AddressSpace {
     Handle,
     /// Push constants.
     PushConstant,
+    /// The payload of rays traced by this invocation, per invocation, mutable.
+    ///
+    /// A variable in this address space is passed to [`TraceRay`], and holds what
+    /// the invoked miss and hit stages wrote to their [`IncomingRayPayload`] once
+    /// it returns.
+    ///
+    /// [`TraceRay`]: RayPipelineFunction::TraceRay
+    /// [`IncomingRayPayload`]: AddressSpace::IncomingRayPayload
+    RayPayload,
+    /// The payload of the ray that invoked this miss or hit stage, mutable.
+    ///
+    /// An entry point may use at most one variable in this address space.
+    IncomingRayPayload,
 }
 
 /// Built-in inputs and outputs.
@@ -357,7 +384,9 @@ pub enum AddressSpace {
 #[cfg_attr(feature = "deserialize", derive(Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum BuiltIn {
-    Position { invariant: bool },
+    Position {
+        invariant: bool,
+    },
     ViewIndex,
     // vertex
     BaseInstance,
@@ -368,6 +397,20 @@ pub enum BuiltIn {
     PointSize,
     VertexIndex,
     DrawID,
+    /// The viewport that the primitive is rasterized into, written by the
+    /// vertex shader.
+    ///
+    /// This requires [`Capabilities::MULTI_VIEWPORT`].
+    ///
+    /// [`Capabilities::MULTI_VIEWPORT`]: crate::valid::Capabilities::MULTI_VIEWPORT
+    ViewportIndex,
+    /// The array layer of the attachments that the primitive is rasterized
+    /// into, written by the vertex shader.
+    ///
+    /// This requires [`Capabilities::MULTI_VIEWPORT`].
+    ///
+    /// [`Capabilities::MULTI_VIEWPORT`]: crate::valid::Capabilities::MULTI_VIEWPORT
+    Layer,
     // fragment
     FragDepth,
     PointCoord,
@@ -375,6 +418,23 @@ pub enum BuiltIn {
     PrimitiveIndex,
     SampleIndex,
     SampleMask,
+    /// The current value of the color attachment at `location`, read at the
+    /// current fragment.
+    ///
+    /// This requires [`Capabilities::FRAMEBUFFER_FETCH`]. Only the MSL and GLSL
+    /// backends can write it.
+    ///
+    /// [`Capabilities::FRAMEBUFFER_FETCH`]: crate::valid::Capabilities::FRAMEBUFFER_FETCH
+    FramebufferFetch {
+        location: u32,
+    },
+    /// Whether the fragment's area is fully covered by the primitive being
+    /// rasterized, when conservative rasterization is enabled.
+    ///
+    /// This requires [`Capabilities::FRAGMENT_FULLY_COVERED`].
+    ///
+    /// [`Capabilities::FRAGMENT_FULLY_COVERED`]: crate::valid::Capabilities::FRAGMENT_FULLY_COVERED
+    FullyCovered,
     // compute
     GlobalInvocationId,
     LocalInvocationId,
@@ -387,6 +447,41 @@ pub enum BuiltIn {
     SubgroupId,
     SubgroupSize,
     SubgroupInvocationId,
+    // ray tracing
+    /// The coordinates of the invocation within the ray tracing launch.
+    LaunchId,
+    /// The dimensions of the ray tracing launch.
+    LaunchSize,
+    /// The origin of the traced ray, in world space.
+    WorldRayOrigin,
+    /// The direction of the traced ray, in world space.
+    WorldRayDirection,
+    /// The origin of the traced ray, in the object space of the hit instance.
+    ObjectRayOrigin,
+    /// The direction of the traced ray, in the object space of the hit instance.
+    ObjectRayDirection,
+    /// The minimum distance of intersections along the traced ray.
+    RayTMin,
+    /// The distance of the closest intersection found so far along the traced ray,
+    /// or its maximum distance in miss stages.
+    RayTCurrentMax,
+    /// The [`RayFlag`]s of the traced ray.
+    ///
+    /// [`RayFlag`]: crate::RayFlag
+    IncomingRayFlags,
+    /// The custom data of the hit instance of the top level acceleration structure.
+    InstanceCustomData,
+    /// The index of the geometry hit within its bottom level acceleration structure.
+    GeometryIndex,
+    /// The matrix transforming the object space of the hit instance to world space.
+    ObjectToWorld,
+    /// The matrix transforming world space to the object space of the hit instance.
+    WorldToObject,
+    /// The kind of the hit, such as whether a triangle was hit on its front face.
+    HitKind,
+    /// The barycentric coordinates of the hit within the triangle, relative to its
+    /// second and third vertices.
+    Barycentrics,
 }
 
 /// Number of bytes per scalar.
@@ -645,6 +740,22 @@ pub enum ImageClass {
         format: StorageFormat,
         access: StorageAccess,
     },
+    /// External texture, such as a video frame.
+    ///
+    /// External textures are always two-dimensional, non-arrayed, and hold
+    /// floating-point values. They can only be loaded from, sampled with
+    /// [`clamp_to_edge`] at level zero, and have their size queried.
+    ///
+    /// They have a single mip level. WGSL doesn't let loads name it, but in the IR
+    /// [`ImageLoad`] expressions still give it, and it must be zero.
+    ///
+    /// Corresponds to WGSL's `texture_external`. Other backends treat them like
+    /// `Sampled { kind: ScalarKind::Float, multi: false }` images.
+    ///
+    /// [`ImageLoad`]: Expression::ImageLoad
+    ///
+    /// [`clamp_to_edge`]: Expression::ImageSample::clamp_to_edge
+    External,
 }
 
 /// A data type declared in the module.
@@ -1786,6 +1897,66 @@ pub enum RayQueryFunction {
     Terminate,
 }
 
+/// An operation of a ray tracing pipeline, the operand of a
+/// [`RayPipelineFunction` statement].
+///
+/// [`RayPipelineFunction` statement]: Statement::RayPipelineFunction
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum RayPipelineFunction {
+    /// Trace a ray, invoking the miss stage or the hit stages it finds, and wait
+    /// for them to return.
+    ///
+    /// This is only available in ray generation, miss and closest hit stages.
+    TraceRay {
+        /// The acceleration structure within which to trace the ray.
+        ///
+        /// The expression must be an [`AccelerationStructure`].
+        ///
+        /// [`AccelerationStructure`]: TypeInner::AccelerationStructure
+        acceleration_structure: Handle<Expression>,
+
+        #[allow(rustdoc::private_intra_doc_links)]
+        /// The ray to trace.
+        ///
+        /// This expression should have the struct type given in
+        /// [`SpecialTypes::ray_desc`]. This is available in the WGSL
+        /// front end as the `RayDesc` type.
+        descriptor: Handle<Expression>,
+
+        /// The offset added to the hit group index of the shader binding table
+        /// record used for hits, as a `u32`.
+        sbt_record_offset: Handle<Expression>,
+
+        /// The stride by which the geometry index of a hit is multiplied in the
+        /// hit group index of the shader binding table record used for hits, as
+        /// a `u32`.
+        sbt_record_stride: Handle<Expression>,
+
+        /// The index of the miss record to use in the shader binding table, as a
+        /// `u32`.
+        miss_index: Handle<Expression>,
+
+        /// A pointer to the [`RayPayload`] global variable holding the payload of
+        /// the ray.
+        ///
+        /// [`RayPayload`]: AddressSpace::RayPayload
+        payload: Handle<Expression>,
+    },
+
+    /// Ignore the current intersection, and carry on tracing the ray.
+    ///
+    /// This ends the invocation, and is only available in any hit stages.
+    IgnoreIntersection,
+
+    /// Accept the current intersection, and stop tracing the ray.
+    ///
+    /// This ends the invocation, and is only available in any hit stages.
+    TerminateRay,
+}
+
 //TODO: consider removing `Clone`. It's not valid to clone `Statement::Emit` anyway.
 /// Instructions which make up an executable block.
 ///
@@ -2146,6 +2317,35 @@ pub enum Statement {
         /// [`SubgroupOperationResult`]: Expression::SubgroupOperationResult
         result: Handle<Expression>,
     },
+    /// Print a formatted message, for debugging shaders.
+    ///
+    /// Each conversion specifier in `format` formats the next of `arguments`, in
+    /// order. The specifiers are those of [`NonSemantic.DebugPrintf`]: `%d` or `%i`
+    /// for signed integers, `%u` for unsigned integers, `%x` for hexadecimal, `%f`,
+    /// `%e`, `%g` or `%a` for floats, and `%%` for a literal `%`. A vector is
+    /// formatted with a size prefix, as in `%v4f`.
+    ///
+    /// Every argument must be a 32-bit scalar or vector of integers or floats.
+    ///
+    /// Messages are printed once per invocation that executes this statement,
+    /// which can be a lot: guard this statement with conditions where possible.
+    ///
+    /// This requires [`Capabilities::DEBUG_PRINTF`].
+    ///
+    /// [`NonSemantic.DebugPrintf`]: https://github.com/KhronosGroup/Vulkan-ValidationLayers/blob/main/docs/debug_printf.md
+    /// [`Capabilities::DEBUG_PRINTF`]: crate::valid::Capabilities::DEBUG_PRINTF
+    DebugPrintf {
+        /// The format string.
+        format: String,
+        /// The values formatted by the conversion specifiers of `format`.
+        arguments: Vec<Handle<Expression>>,
+    },
+    /// Operate on the ray being traced by a ray tracing pipeline.
+    ///
+    /// This requires [`Capabilities::RAY_TRACING_PIPELINE`].
+    ///
+    /// [`Capabilities::RAY_TRACING_PIPELINE`]: crate::valid::Capabilities::RAY_TRACING_PIPELINE
+    RayPipelineFunction(RayPipelineFunction),
 }
 
 /// A function argument.
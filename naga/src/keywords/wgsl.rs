@@ -47,6 +47,7 @@ pub const RESERVED: &[&str] = &[
     "texture_depth_cube",
     "texture_depth_cube_array",
     "texture_depth_multisampled_2d",
+    "texture_external",
     "u32",
     "u64",
     "vec2",
@@ -107,6 +107,8 @@ extern crate alloc;
 
 mod arena;
 pub mod back;
+#[cfg(feature = "ir-cache")]
+pub mod cache;
 pub mod common;
 pub mod compact;
 pub mod diagnostic_filter;
@@ -1754,6 +1754,36 @@ impl<'a> ConstantEvaluator<'a> {
         }
     }
 
+    /// Convert the scalar components of `expr` to the 8-bit or 16-bit integer `target`.
+    ///
+    /// There are no literals of these types, so outside of runtime contexts this is an
+    /// error. Otherwise, `expr` is cast to the 32-bit integer type of the same kind, and
+    /// a runtime conversion from that to `target` is appended.
+    fn cast_to_small_int(
+        &mut self,
+        expr: Handle<Expression>,
+        target: crate::Scalar,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        if !self.behavior.has_runtime_restrictions() {
+            return Err(ConstantEvaluatorError::NotImplemented(format!(
+                "{}-bit integer constants",
+                target.width * 8
+            )));
+        }
+        let wide = crate::Scalar {
+            kind: target.kind,
+            width: 4,
+        };
+        let expr = self.cast(expr, wide, span)?;
+        let expr = Expression::As {
+            expr,
+            kind: target.kind,
+            convert: Some(target.width),
+        };
+        Ok(self.append_expr(expr, span, ExpressionKind::Runtime))
+    }
+
     /// Convert the scalar components of `expr` to `target`.
     ///
     /// Treat `span` as the location of the resulting expression.
@@ -1765,6 +1795,10 @@ impl<'a> ConstantEvaluator<'a> {
     ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
         use crate::Scalar as Sc;
 
+        if matches!(target.kind, ScalarKind::Sint | ScalarKind::Uint) && target.width < 4 {
+            return self.cast_to_small_int(expr, target, span);
+        }
+
         let expr = self.eval_zero_value(expr, span)?;
 
         let make_error = || -> Result<_, ConstantEvaluatorError> {
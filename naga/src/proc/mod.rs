@@ -7,6 +7,7 @@ mod emitter;
 pub mod index;
 mod layouter;
 mod namer;
+mod optimize;
 mod overloads;
 mod terminator;
 mod type_methods;
@@ -19,6 +20,9 @@ pub use emitter::Emitter;
 pub use index::{BoundsCheckPolicies, BoundsCheckPolicy, IndexableLength, IndexableLengthError};
 pub use layouter::{Alignment, LayoutError, LayoutErrorInner, Layouter, TypeLayout};
 pub use namer::{EntryPointIndex, NameKey, Namer};
+pub use optimize::{OptimizationLevel, optimize};
+#[cfg(any(hlsl_out, msl_out, spv_out, glsl_out))]
+pub(crate) use optimize::{adjust_expr, fold_function};
 pub use overloads::{Conclusion, MissingSpecialType, OverloadSet, Rule};
 pub use terminator::ensure_block_returns;
 use thiserror::Error;
@@ -172,7 +176,9 @@ impl super::AddressSpace {
         match self {
             crate::AddressSpace::Function
             | crate::AddressSpace::Private
-            | crate::AddressSpace::WorkGroup => Sa::LOAD | Sa::STORE,
+            | crate::AddressSpace::WorkGroup
+            | crate::AddressSpace::RayPayload
+            | crate::AddressSpace::IncomingRayPayload => Sa::LOAD | Sa::STORE,
             crate::AddressSpace::Uniform => Sa::LOAD,
             crate::AddressSpace::Storage { access } => access,
             crate::AddressSpace::Handle => Sa::LOAD,
@@ -382,13 +388,14 @@ impl super::ImageClass {
     pub const fn is_multisampled(self) -> bool {
         match self {
             crate::ImageClass::Sampled { multi, .. } | crate::ImageClass::Depth { multi } => multi,
-            crate::ImageClass::Storage { .. } => false,
+            crate::ImageClass::Storage { .. } | crate::ImageClass::External => false,
         }
     }
 
     pub const fn is_mipmapped(self) -> bool {
         match self {
             crate::ImageClass::Sampled { multi, .. } | crate::ImageClass::Depth { multi } => !multi,
+            crate::ImageClass::External => true,
             crate::ImageClass::Storage { .. } => false,
         }
     }
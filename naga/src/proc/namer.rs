@@ -220,6 +220,11 @@ impl Namer {
                         crate::ShaderStage::Fragment => "FragmentOutput",
                         crate::ShaderStage::Compute => "ComputeOutput",
                         crate::ShaderStage::Task | crate::ShaderStage::Mesh => unreachable!(),
+                        // Ray tracing stages can't return values.
+                        crate::ShaderStage::RayGeneration
+                        | crate::ShaderStage::Miss
+                        | crate::ShaderStage::ClosestHit
+                        | crate::ShaderStage::AnyHit => unreachable!(),
                     };
                     entrypoint_type_fallbacks.insert(result.ty, label);
                 }
@@ -0,0 +1,1118 @@
+/*!
+Simplifying modules before they are written by a backend.
+
+Front ends translate shaders statement by statement, so the modules they build often
+contain code which a backend writes out although it has no effect: expressions whose
+value is known before the shader runs, branches which are never taken, loops which never
+loop, and functions and globals which no entry point uses. Shader compilers usually
+remove all of it, but only after parsing it, which makes large shaders slow to compile.
+[`optimize`] removes it from the module instead.
+*/
+
+use core::mem;
+
+use crate::{
+    Arena, Block, Expression, Function, Handle, Literal, Module, Range, Scalar, Statement,
+    TypeInner,
+    arena::HandleVec,
+    compact::{KeepUnused, compact},
+    proc::{ConstantEvaluator, ConstantEvaluatorError, Emitter, ExpressionKindTracker, Layouter},
+};
+
+/// How much [`optimize`] simplifies a module.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub enum OptimizationLevel {
+    /// Leave the module unchanged.
+    #[default]
+    None,
+    /// Evaluate the expressions whose operands are constant, and remove the
+    /// expressions, functions, global variables, constants and types which no entry
+    /// point uses.
+    Basic,
+    /// Also remove the statements which can't be reached, replace `if` statements with
+    /// a constant condition by the branch which is taken, and replace loops which run
+    /// at most once by their body.
+    Full,
+}
+
+/// Simplify `module`, which must be valid, to make the code backends write for it
+/// smaller and faster to compile.
+///
+/// The entry points of the simplified module behave like those of `module`, but the
+/// module must be validated again to get its [`ModuleInfo`]. See [`OptimizationLevel`]
+/// for the simplifications which are made.
+///
+/// If an expression with constant operands fails to evaluate, for example because it
+/// divides by zero, the error is returned and `module` is left unchanged.
+///
+/// # Panics
+///
+/// If `module` would not pass validation, this may panic.
+///
+/// [`ModuleInfo`]: crate::valid::ModuleInfo
+pub fn optimize(
+    module: &mut Module,
+    level: OptimizationLevel,
+) -> Result<(), ConstantEvaluatorError> {
+    if level == OptimizationLevel::None {
+        return Ok(());
+    }
+
+    let mut optimized = module.clone();
+    let mut layouter = Layouter::default();
+
+    let mut functions = mem::take(&mut optimized.functions);
+    for (_, function) in functions.iter_mut() {
+        optimize_function(&mut optimized, &mut layouter, function, level)?;
+    }
+    optimized.functions = functions;
+
+    let mut entry_points = mem::take(&mut optimized.entry_points);
+    for ep in entry_points.iter_mut() {
+        optimize_function(&mut optimized, &mut layouter, &mut ep.function, level)?;
+    }
+    optimized.entry_points = entry_points;
+
+    compact(&mut optimized, KeepUnused::No);
+    *module = optimized;
+    Ok(())
+}
+
+fn optimize_function(
+    module: &mut Module,
+    layouter: &mut Layouter,
+    function: &mut Function,
+    level: OptimizationLevel,
+) -> Result<(), ConstantEvaluatorError> {
+    fold_function(module, layouter, function, |expr| expr)?;
+
+    if level == OptimizationLevel::Full {
+        let mut simplifier = Simplifier {
+            module,
+            expressions: &function.expressions,
+            named_expressions: &mut function.named_expressions,
+        };
+        simplifier.simplify_block(&mut function.body);
+    }
+    Ok(())
+}
+
+/// Evaluate the expressions of `function` whose values are known.
+///
+/// Rebuild `function`'s expression arena, passing each expression to `replace`
+/// first, and replacing it by its fully evaluated form if its operands are
+/// constant. The statements of `function` are adjusted to refer to the new arena.
+pub(crate) fn fold_function(
+    module: &mut Module,
+    layouter: &mut Layouter,
+    function: &mut Function,
+    mut replace: impl FnMut(Expression) -> Expression,
+) -> Result<(), ConstantEvaluatorError> {
+    // A map from original local expression handles to
+    // handles in the new, local expression arena.
+    let mut adjusted_local_expressions = HandleVec::with_capacity(function.expressions.len());
+
+    let mut local_expression_kind_tracker = ExpressionKindTracker::new();
+
+    let mut expressions = mem::take(&mut function.expressions);
+
+    // Dummy `emitter` and `block` for the constant evaluator.
+    // We can ignore the concept of emitting expressions here since
+    // expressions have already been covered by a `Statement::Emit`
+    // in the frontend.
+    // The only thing we might have to do is remove some expressions
+    // that have been covered by a `Statement::Emit`. See the docs of
+    // `filter_emits_in_block` for the reasoning.
+    let mut emitter = Emitter::default();
+    let mut block = Block::new();
+
+    let mut evaluator = ConstantEvaluator::for_wgsl_function(
+        module,
+        &mut function.expressions,
+        &mut local_expression_kind_tracker,
+        layouter,
+        &mut emitter,
+        &mut block,
+        false,
+    );
+
+    for (old_h, expr, span) in expressions.drain() {
+        let mut expr = replace(expr);
+        adjust_expr(&adjusted_local_expressions, &mut expr);
+        let h = evaluator.try_eval_and_append(expr, span)?;
+        adjusted_local_expressions.insert(old_h, h);
+    }
+
+    adjust_block(&adjusted_local_expressions, &mut function.body);
+
+    filter_emits_in_block(&mut function.body, &function.expressions);
+
+    // Update local expression initializers.
+    for (_, local) in function.local_variables.iter_mut() {
+        if let &mut Some(ref mut init) = &mut local.init {
+            *init = adjusted_local_expressions[*init];
+        }
+    }
+
+    // We've changed the keys of `function.named_expression`, so we have to
+    // rebuild it from scratch.
+    let named_expressions = mem::take(&mut function.named_expressions);
+    for (expr_h, name) in named_expressions {
+        function
+            .named_expressions
+            .insert(adjusted_local_expressions[expr_h], name);
+    }
+
+    Ok(())
+}
+
+/// Removes unreachable statements and simplifies control flow in a function whose
+/// expressions have been folded by [`fold_function`].
+struct Simplifier<'a> {
+    module: &'a Module,
+    expressions: &'a Arena<Expression>,
+    named_expressions: &'a mut crate::NamedExpressions,
+}
+
+impl Simplifier<'_> {
+    /// Simplify the statements of `block`.
+    ///
+    /// Return true if the block always ends with a `return`, `break` or `continue`,
+    /// which makes the statements after it unreachable.
+    fn simplify_block(&mut self, block: &mut Block) -> bool {
+        let original = mem::replace(block, Block::with_capacity(block.len()));
+        let mut statements = original.span_into_iter();
+        let mut diverges = false;
+        for (stmt, span) in statements.by_ref() {
+            let (stmt, stmt_diverges) = self.simplify_statement(stmt);
+            if let Some(stmt) = stmt {
+                block.push(stmt, span);
+            }
+            if stmt_diverges {
+                diverges = true;
+                break;
+            }
+        }
+        for (stmt, _) in statements {
+            self.forget_statement(&stmt);
+        }
+        diverges
+    }
+
+    /// Simplify `stmt`, returning the statement to replace it with, if any, and
+    /// whether it always ends with a `return`, `break` or `continue`.
+    fn simplify_statement(&mut self, stmt: Statement) -> (Option<Statement>, bool) {
+        match stmt {
+            Statement::Return { .. } | Statement::Break | Statement::Continue => (Some(stmt), true),
+            Statement::Block(mut block) => {
+                let diverges = self.simplify_block(&mut block);
+                (non_empty(block), diverges)
+            }
+            Statement::If {
+                condition,
+                mut accept,
+                mut reject,
+            } => {
+                if let Some(condition) = self.constant_bool(condition) {
+                    let (mut taken, not_taken) = if condition {
+                        (accept, reject)
+                    } else {
+                        (reject, accept)
+                    };
+                    self.forget_block(&not_taken);
+                    let diverges = self.simplify_block(&mut taken);
+                    return (non_empty(taken), diverges);
+                }
+                let accept_diverges = self.simplify_block(&mut accept);
+                let reject_diverges = self.simplify_block(&mut reject);
+                let stmt = Statement::If {
+                    condition,
+                    accept,
+                    reject,
+                };
+                (Some(stmt), accept_diverges && reject_diverges)
+            }
+            Statement::Switch {
+                selector,
+                mut cases,
+            } => {
+                for case in cases.iter_mut() {
+                    self.simplify_block(&mut case.body);
+                }
+                (Some(Statement::Switch { selector, cases }), false)
+            }
+            Statement::Loop {
+                mut body,
+                mut continuing,
+                break_if,
+            } => {
+                let body_diverges = self.simplify_block(&mut body);
+                self.simplify_block(&mut continuing);
+                let break_if =
+                    break_if.filter(|&break_if| self.constant_bool(break_if) != Some(false));
+
+                // A loop whose body ends with its only `break` runs once, without
+                // its continuing block.
+                if ends_with_break(&body) && loop_exits(&body, false) == 1 {
+                    remove_final_break(&mut body);
+                    self.forget_block(&continuing);
+                    return (non_empty(body), false);
+                }
+
+                // A loop whose body always returns, or which always breaks after its
+                // continuing block, runs once if the body doesn't leave it otherwise.
+                let always_breaks = break_if.and_then(|break_if| self.constant_bool(break_if));
+                if (body_diverges || always_breaks == Some(true)) && loop_exits(&body, false) == 0 {
+                    if body_diverges {
+                        self.forget_block(&continuing);
+                    } else {
+                        body.append(&mut continuing);
+                    }
+                    return (non_empty(body), body_diverges);
+                }
+
+                let stmt = Statement::Loop {
+                    body,
+                    continuing,
+                    break_if,
+                };
+                (Some(stmt), false)
+            }
+            stmt => (Some(stmt), false),
+        }
+    }
+
+    /// Return the value of `expr` if it is a constant `bool`.
+    fn constant_bool(&self, expr: Handle<Expression>) -> Option<bool> {
+        let expr = match self.expressions[expr] {
+            Expression::Constant(c) => {
+                &self.module.global_expressions[self.module.constants[c].init]
+            }
+            ref expr => expr,
+        };
+        match *expr {
+            Expression::Literal(Literal::Bool(value)) => Some(value),
+            Expression::ZeroValue(ty) => {
+                (self.module.types[ty].inner == TypeInner::Scalar(Scalar::BOOL)).then_some(false)
+            }
+            _ => None,
+        }
+    }
+
+    /// Drop the names of the expressions emitted by the statements of `block`, which
+    /// are being removed, so that compaction removes the expressions.
+    ///
+    /// The results of other statements, like calls, keep their names, which is
+    /// harmless.
+    fn forget_block(&mut self, block: &Block) {
+        for stmt in block.iter() {
+            self.forget_statement(stmt);
+        }
+    }
+
+    fn forget_statement(&mut self, stmt: &Statement) {
+        match *stmt {
+            Statement::Emit(ref range) => {
+                for handle in range.clone() {
+                    self.named_expressions.shift_remove(&handle);
+                }
+            }
+            Statement::Block(ref block) => self.forget_block(block),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => {
+                self.forget_block(accept);
+                self.forget_block(reject);
+            }
+            Statement::Switch { ref cases, .. } => {
+                for case in cases.iter() {
+                    self.forget_block(&case.body);
+                }
+            }
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => {
+                self.forget_block(body);
+                self.forget_block(continuing);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn non_empty(block: Block) -> Option<Statement> {
+    (!block.is_empty()).then_some(Statement::Block(block))
+}
+
+/// Return true if the last statement of `block` is a `break`, possibly nested in
+/// blocks.
+fn ends_with_break(block: &Block) -> bool {
+    match block.last() {
+        Some(&Statement::Break) => true,
+        Some(&Statement::Block(ref block)) => ends_with_break(block),
+        _ => false,
+    }
+}
+
+/// Remove the final `break` found by [`ends_with_break`], and the blocks it leaves
+/// empty.
+fn remove_final_break(block: &mut Block) {
+    let len = block.len();
+    if let Some(&mut Statement::Block(ref mut inner)) = block.last_mut() {
+        remove_final_break(inner);
+        if !inner.is_empty() {
+            return;
+        }
+    }
+    block.cull(len - 1..);
+}
+
+/// Count the `break` and `continue` statements in `block` which leave the loop whose
+/// body it is in.
+///
+/// A `break` in a `switch` leaves the `switch`, but a `continue` in it continues the
+/// loop.
+fn loop_exits(block: &Block, in_switch: bool) -> usize {
+    block
+        .iter()
+        .map(|stmt| match *stmt {
+            Statement::Break if !in_switch => 1,
+            Statement::Continue => 1,
+            Statement::Block(ref block) => loop_exits(block, in_switch),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => loop_exits(accept, in_switch) + loop_exits(reject, in_switch),
+            Statement::Switch { ref cases, .. } => {
+                cases.iter().map(|case| loop_exits(&case.body, true)).sum()
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Replace every expression handle in `expr` with its counterpart
+/// given by `new_pos`.
+pub(crate) fn adjust_expr(
+    new_pos: &HandleVec<Expression, Handle<Expression>>,
+    expr: &mut Expression,
+) {
+    let adjust = |expr: &mut Handle<Expression>| {
+        *expr = new_pos[*expr];
+    };
+    match *expr {
+        Expression::Compose {
+            ref mut components,
+            ty: _,
+        } => {
+            for c in components.iter_mut() {
+                adjust(c);
+            }
+        }
+        Expression::Access {
+            ref mut base,
+            ref mut index,
+        } => {
+            adjust(base);
+            adjust(index);
+        }
+        Expression::AccessIndex {
+            ref mut base,
+            index: _,
+        } => {
+            adjust(base);
+        }
+        Expression::Splat {
+            ref mut value,
+            size: _,
+        } => {
+            adjust(value);
+        }
+        Expression::Swizzle {
+            ref mut vector,
+            size: _,
+            pattern: _,
+        } => {
+            adjust(vector);
+        }
+        Expression::Load { ref mut pointer } => {
+            adjust(pointer);
+        }
+        Expression::ImageSample {
+            ref mut image,
+            ref mut sampler,
+            ref mut coordinate,
+            ref mut array_index,
+            ref mut offset,
+            ref mut level,
+            ref mut depth_ref,
+            gather: _,
+            clamp_to_edge: _,
+        } => {
+            adjust(image);
+            adjust(sampler);
+            adjust(coordinate);
+            if let Some(e) = array_index.as_mut() {
+                adjust(e);
+            }
+            if let Some(e) = offset.as_mut() {
+                adjust(e);
+            }
+            match *level {
+                crate::SampleLevel::Exact(ref mut expr)
+                | crate::SampleLevel::Bias(ref mut expr) => {
+                    adjust(expr);
+                }
+                crate::SampleLevel::Gradient {
+                    ref mut x,
+                    ref mut y,
+                } => {
+                    adjust(x);
+                    adjust(y);
+                }
+                _ => {}
+            }
+            if let Some(e) = depth_ref.as_mut() {
+                adjust(e);
+            }
+        }
+        Expression::ImageLoad {
+            ref mut image,
+            ref mut coordinate,
+            ref mut array_index,
+            ref mut sample,
+            ref mut level,
+        } => {
+            adjust(image);
+            adjust(coordinate);
+            if let Some(e) = array_index.as_mut() {
+                adjust(e);
+            }
+            if let Some(e) = sample.as_mut() {
+                adjust(e);
+            }
+            if let Some(e) = level.as_mut() {
+                adjust(e);
+            }
+        }
+        Expression::ImageQuery {
+            ref mut image,
+            ref mut query,
+        } => {
+            adjust(image);
+            match *query {
+                crate::ImageQuery::Size { ref mut level } => {
+                    if let Some(e) = level.as_mut() {
+                        adjust(e);
+                    }
+                }
+                crate::ImageQuery::NumLevels
+                | crate::ImageQuery::NumLayers
+                | crate::ImageQuery::NumSamples => {}
+            }
+        }
+        Expression::Unary {
+            ref mut expr,
+            op: _,
+        } => {
+            adjust(expr);
+        }
+        Expression::Binary {
+            ref mut left,
+            ref mut right,
+            op: _,
+        } => {
+            adjust(left);
+            adjust(right);
+        }
+        Expression::Select {
+            ref mut condition,
+            ref mut accept,
+            ref mut reject,
+        } => {
+            adjust(condition);
+            adjust(accept);
+            adjust(reject);
+        }
+        Expression::Derivative {
+            ref mut expr,
+            axis: _,
+            ctrl: _,
+        } => {
+            adjust(expr);
+        }
+        Expression::Relational {
+            ref mut argument,
+            fun: _,
+        } => {
+            adjust(argument);
+        }
+        Expression::Math {
+            ref mut arg,
+            ref mut arg1,
+            ref mut arg2,
+            ref mut arg3,
+            fun: _,
+        } => {
+            adjust(arg);
+            if let Some(e) = arg1.as_mut() {
+                adjust(e);
+            }
+            if let Some(e) = arg2.as_mut() {
+                adjust(e);
+            }
+            if let Some(e) = arg3.as_mut() {
+                adjust(e);
+            }
+        }
+        Expression::As {
+            ref mut expr,
+            kind: _,
+            convert: _,
+        } => {
+            adjust(expr);
+        }
+        Expression::ArrayLength(ref mut expr) => {
+            adjust(expr);
+        }
+        Expression::RayQueryGetIntersection {
+            ref mut query,
+            committed: _,
+        } => {
+            adjust(query);
+        }
+        Expression::Literal(_)
+        | Expression::FunctionArgument(_)
+        | Expression::GlobalVariable(_)
+        | Expression::LocalVariable(_)
+        | Expression::CallResult(_)
+        | Expression::RayQueryProceedResult
+        | Expression::Constant(_)
+        | Expression::Override(_)
+        | Expression::ZeroValue(_)
+        | Expression::AtomicResult {
+            ty: _,
+            comparison: _,
+        }
+        | Expression::WorkGroupUniformLoadResult { ty: _ }
+        | Expression::SubgroupBallotResult
+        | Expression::SubgroupOperationResult { .. } => {}
+        Expression::RayQueryVertexPositions {
+            ref mut query,
+            committed: _,
+        } => {
+            adjust(query);
+        }
+    }
+}
+
+/// Replace every expression handle in `block` with its counterpart
+/// given by `new_pos`.
+fn adjust_block(new_pos: &HandleVec<Expression, Handle<Expression>>, block: &mut Block) {
+    for stmt in block.iter_mut() {
+        adjust_stmt(new_pos, stmt);
+    }
+}
+
+/// Replace every expression handle in `stmt` with its counterpart
+/// given by `new_pos`.
+fn adjust_stmt(new_pos: &HandleVec<Expression, Handle<Expression>>, stmt: &mut Statement) {
+    let adjust = |expr: &mut Handle<Expression>| {
+        *expr = new_pos[*expr];
+    };
+    match *stmt {
+        Statement::Emit(ref mut range) => {
+            if let Some((mut first, mut last)) = range.first_and_last() {
+                adjust(&mut first);
+                adjust(&mut last);
+                *range = Range::new_from_bounds(first, last);
+            }
+        }
+        Statement::Block(ref mut block) => {
+            adjust_block(new_pos, block);
+        }
+        Statement::If {
+            ref mut condition,
+            ref mut accept,
+            ref mut reject,
+        } => {
+            adjust(condition);
+            adjust_block(new_pos, accept);
+            adjust_block(new_pos, reject);
+        }
+        Statement::Switch {
+            ref mut selector,
+            ref mut cases,
+        } => {
+            adjust(selector);
+            for case in cases.iter_mut() {
+                adjust_block(new_pos, &mut case.body);
+            }
+        }
+        Statement::Loop {
+            ref mut body,
+            ref mut continuing,
+            ref mut break_if,
+        } => {
+            adjust_block(new_pos, body);
+            adjust_block(new_pos, continuing);
+            if let Some(e) = break_if.as_mut() {
+                adjust(e);
+            }
+        }
+        Statement::Return { ref mut value } => {
+            if let Some(e) = value.as_mut() {
+                adjust(e);
+            }
+        }
+        Statement::Store {
+            ref mut pointer,
+            ref mut value,
+        } => {
+            adjust(pointer);
+            adjust(value);
+        }
+        Statement::ImageStore {
+            ref mut image,
+            ref mut coordinate,
+            ref mut array_index,
+            ref mut value,
+        } => {
+            adjust(image);
+            adjust(coordinate);
+            if let Some(e) = array_index.as_mut() {
+                adjust(e);
+            }
+            adjust(value);
+        }
+        Statement::Atomic {
+            ref mut pointer,
+            ref mut value,
+            ref mut result,
+            ref mut fun,
+        } => {
+            adjust(pointer);
+            adjust(value);
+            if let Some(ref mut result) = *result {
+                adjust(result);
+            }
+            match *fun {
+                crate::AtomicFunction::Exchange {
+                    compare: Some(ref mut compare),
+                } => {
+                    adjust(compare);
+                }
+                crate::AtomicFunction::Add
+                | crate::AtomicFunction::Subtract
+                | crate::AtomicFunction::And
+                | crate::AtomicFunction::ExclusiveOr
+                | crate::AtomicFunction::InclusiveOr
+                | crate::AtomicFunction::Min
+                | crate::AtomicFunction::Max
+                | crate::AtomicFunction::Exchange { compare: None } => {}
+            }
+        }
+        Statement::ImageAtomic {
+            ref mut image,
+            ref mut coordinate,
+            ref mut array_index,
+            fun: _,
+            ref mut value,
+        } => {
+            adjust(image);
+            adjust(coordinate);
+            if let Some(ref mut array_index) = *array_index {
+                adjust(array_index);
+            }
+            adjust(value);
+        }
+        Statement::WorkGroupUniformLoad {
+            ref mut pointer,
+            ref mut result,
+        } => {
+            adjust(pointer);
+            adjust(result);
+        }
+        Statement::SubgroupBallot {
+            ref mut result,
+            ref mut predicate,
+        } => {
+            if let Some(ref mut predicate) = *predicate {
+                adjust(predicate);
+            }
+            adjust(result);
+        }
+        Statement::SubgroupCollectiveOperation {
+            ref mut argument,
+            ref mut result,
+            ..
+        } => {
+            adjust(argument);
+            adjust(result);
+        }
+        Statement::SubgroupGather {
+            ref mut mode,
+            ref mut argument,
+            ref mut result,
+        } => {
+            match *mode {
+                crate::GatherMode::BroadcastFirst => {}
+                crate::GatherMode::Broadcast(ref mut index)
+                | crate::GatherMode::Shuffle(ref mut index)
+                | crate::GatherMode::ShuffleDown(ref mut index)
+                | crate::GatherMode::ShuffleUp(ref mut index)
+                | crate::GatherMode::ShuffleXor(ref mut index)
+                | crate::GatherMode::QuadBroadcast(ref mut index) => {
+                    adjust(index);
+                }
+                crate::GatherMode::QuadSwap(_) => {}
+            }
+            adjust(argument);
+            adjust(result)
+        }
+        Statement::Call {
+            ref mut arguments,
+            ref mut result,
+            function: _,
+        } => {
+            for argument in arguments.iter_mut() {
+                adjust(argument);
+            }
+            if let Some(e) = result.as_mut() {
+                adjust(e);
+            }
+        }
+        Statement::RayQuery {
+            ref mut query,
+            ref mut fun,
+        } => {
+            adjust(query);
+            match *fun {
+                crate::RayQueryFunction::Initialize {
+                    ref mut acceleration_structure,
+                    ref mut descriptor,
+                } => {
+                    adjust(acceleration_structure);
+                    adjust(descriptor);
+                }
+                crate::RayQueryFunction::Proceed { ref mut result } => {
+                    adjust(result);
+                }
+                crate::RayQueryFunction::GenerateIntersection { ref mut hit_t } => {
+                    adjust(hit_t);
+                }
+                crate::RayQueryFunction::ConfirmIntersection => {}
+                crate::RayQueryFunction::Terminate => {}
+            }
+        }
+        Statement::DebugPrintf {
+            ref mut arguments,
+            format: _,
+        } => {
+            for argument in arguments.iter_mut() {
+                adjust(argument);
+            }
+        }
+        Statement::RayPipelineFunction(ref mut fun) => match *fun {
+            crate::RayPipelineFunction::TraceRay {
+                ref mut acceleration_structure,
+                ref mut descriptor,
+                ref mut sbt_record_offset,
+                ref mut sbt_record_stride,
+                ref mut miss_index,
+                ref mut payload,
+            } => {
+                adjust(acceleration_structure);
+                adjust(descriptor);
+                adjust(sbt_record_offset);
+                adjust(sbt_record_stride);
+                adjust(miss_index);
+                adjust(payload);
+            }
+            crate::RayPipelineFunction::IgnoreIntersection
+            | crate::RayPipelineFunction::TerminateRay => {}
+        },
+        Statement::Break
+        | Statement::Continue
+        | Statement::Kill
+        | Statement::ControlBarrier(_)
+        | Statement::MemoryBarrier(_) => {}
+    }
+}
+
+/// Adjust [`Emit`] statements in `block` to skip [`needs_pre_emit`] expressions we have introduced.
+///
+/// According to validation, [`Emit`] statements must not cover any expressions
+/// for which [`Expression::needs_pre_emit`] returns true. All expressions built
+/// by successful constant evaluation fall into that category, meaning that
+/// [`fold_function`] will usually rewrite [`Override`] expressions and those
+/// that use their values into pre-emitted expressions, leaving any [`Emit`]
+/// statements that cover them invalid.
+///
+/// This function rewrites all [`Emit`] statements into zero or more new
+/// [`Emit`] statements covering only those expressions in the original range
+/// that are not pre-emitted.
+///
+/// [`Emit`]: Statement::Emit
+/// [`needs_pre_emit`]: Expression::needs_pre_emit
+/// [`Override`]: Expression::Override
+fn filter_emits_in_block(block: &mut Block, expressions: &Arena<Expression>) {
+    let original = mem::replace(block, Block::with_capacity(block.len()));
+    for (stmt, span) in original.span_into_iter() {
+        match stmt {
+            Statement::Emit(range) => {
+                let mut current = None;
+                for expr_h in range {
+                    if expressions[expr_h].needs_pre_emit() {
+                        if let Some((first, last)) = current {
+                            block.push(Statement::Emit(Range::new_from_bounds(first, last)), span);
+                        }
+
+                        current = None;
+                    } else if let Some((_, ref mut last)) = current {
+                        *last = expr_h;
+                    } else {
+                        current = Some((expr_h, expr_h));
+                    }
+                }
+                if let Some((first, last)) = current {
+                    block.push(Statement::Emit(Range::new_from_bounds(first, last)), span);
+                }
+            }
+            Statement::Block(mut child) => {
+                filter_emits_in_block(&mut child, expressions);
+                block.push(Statement::Block(child), span);
+            }
+            Statement::If {
+                condition,
+                mut accept,
+                mut reject,
+            } => {
+                filter_emits_in_block(&mut accept, expressions);
+                filter_emits_in_block(&mut reject, expressions);
+                block.push(
+                    Statement::If {
+                        condition,
+                        accept,
+                        reject,
+                    },
+                    span,
+                );
+            }
+            Statement::Switch {
+                selector,
+                mut cases,
+            } => {
+                for case in &mut cases {
+                    filter_emits_in_block(&mut case.body, expressions);
+                }
+                block.push(Statement::Switch { selector, cases }, span);
+            }
+            Statement::Loop {
+                mut body,
+                mut continuing,
+                break_if,
+            } => {
+                filter_emits_in_block(&mut body, expressions);
+                filter_emits_in_block(&mut continuing, expressions);
+                block.push(
+                    Statement::Loop {
+                        body,
+                        continuing,
+                        break_if,
+                    },
+                    span,
+                );
+            }
+            stmt => block.push(stmt.clone(), span),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OptimizationLevel, optimize};
+    use crate::valid::{Capabilities, ValidationFlags, Validator};
+    use crate::{Block, Expression, Literal, Module, Span, Statement};
+
+    fn validate(module: &Module) {
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(module)
+            .unwrap();
+    }
+
+    /// Count the statements in `block` and its nested blocks for which `f` is true.
+    fn count(block: &Block, f: &impl Fn(&Statement) -> bool) -> usize {
+        block
+            .iter()
+            .map(|stmt| {
+                let nested = match *stmt {
+                    Statement::Block(ref block) => count(block, f),
+                    Statement::If {
+                        ref accept,
+                        ref reject,
+                        ..
+                    } => count(accept, f) + count(reject, f),
+                    Statement::Switch { ref cases, .. } => {
+                        cases.iter().map(|case| count(&case.body, f)).sum()
+                    }
+                    Statement::Loop {
+                        ref body,
+                        ref continuing,
+                        ..
+                    } => count(body, f) + count(continuing, f),
+                    _ => 0,
+                };
+                nested + usize::from(f(stmt))
+            })
+            .sum()
+    }
+
+    #[test]
+    fn fold_constants() {
+        let mut module = Module::default();
+        let ty_i32 = module.types.insert(
+            crate::Type {
+                name: None,
+                inner: crate::TypeInner::Scalar(crate::Scalar::I32),
+            },
+            Span::UNDEFINED,
+        );
+
+        let mut function = crate::Function::default();
+        let x = function.local_variables.append(
+            crate::LocalVariable {
+                name: Some("x".into()),
+                ty: ty_i32,
+                init: None,
+            },
+            Span::UNDEFINED,
+        );
+        let pointer = function
+            .expressions
+            .append(Expression::LocalVariable(x), Span::UNDEFINED);
+        let one = function
+            .expressions
+            .append(Expression::Literal(Literal::I32(1)), Span::UNDEFINED);
+        let two = function
+            .expressions
+            .append(Expression::Literal(Literal::I32(2)), Span::UNDEFINED);
+        let sum = function.expressions.append(
+            Expression::Binary {
+                op: crate::BinaryOperator::Add,
+                left: one,
+                right: two,
+            },
+            Span::UNDEFINED,
+        );
+        function.body.push(
+            Statement::Emit(crate::Range::new_from_bounds(sum, sum)),
+            Span::UNDEFINED,
+        );
+        function.body.push(
+            Statement::Store {
+                pointer,
+                value: sum,
+            },
+            Span::UNDEFINED,
+        );
+        module.entry_points.push(crate::EntryPoint {
+            name: "main".into(),
+            stage: crate::ShaderStage::Compute,
+            early_depth_test: None,
+            workgroup_size: [1, 1, 1],
+            workgroup_size_overrides: None,
+            function,
+        });
+        validate(&module);
+
+        optimize(&mut module, OptimizationLevel::Basic).unwrap();
+        validate(&module);
+
+        let function = &module.entry_points[0].function;
+        let Some(&Statement::Store { value, .. }) = function.body.last() else {
+            panic!("expected a store: {:?}", function.body);
+        };
+        assert_eq!(
+            function.expressions[value],
+            Expression::Literal(Literal::I32(3))
+        );
+        assert_eq!(
+            count(&function.body, &|stmt| matches!(*stmt, Statement::Emit(_))),
+            0
+        );
+    }
+
+    #[cfg(feature = "wgsl-in")]
+    #[test]
+    fn simplify_control_flow() {
+        let source = "
+            const DEBUG: bool = false;
+
+            @group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+            fn unused() -> f32 {
+                return 1.0;
+            }
+
+            @compute @workgroup_size(64)
+            fn main(@builtin(global_invocation_id) id: vec3u) {
+                var x = data[id.x];
+                if DEBUG {
+                    x = 2.0;
+                }
+                loop {
+                    x += 1.0;
+                    break;
+                }
+                while false {
+                    x += 3.0;
+                }
+                for (var i = 0u; i < id.y; i++) {
+                    if x > 10.0 {
+                        continue;
+                    }
+                    x *= 2.0;
+                }
+                data[id.x] = x;
+                return;
+                x = 4.0;
+            }
+        ";
+        let module = crate::front::wgsl::parse_str(source).unwrap();
+        validate(&module);
+        let stores = |module: &Module| {
+            count(&module.entry_points[0].function.body, &|stmt| {
+                matches!(*stmt, Statement::Store { .. })
+            })
+        };
+        let ifs_and_loops = |module: &Module| {
+            count(&module.entry_points[0].function.body, &|stmt| {
+                matches!(*stmt, Statement::If { .. } | Statement::Loop { .. })
+            })
+        };
+
+        let mut basic = module.clone();
+        optimize(&mut basic, OptimizationLevel::Basic).unwrap();
+        validate(&basic);
+        assert!(basic.functions.is_empty());
+        assert_eq!(stores(&basic), stores(&module));
+        assert_eq!(ifs_and_loops(&basic), ifs_and_loops(&module));
+
+        let mut full = module.clone();
+        optimize(&mut full, OptimizationLevel::Full).unwrap();
+        validate(&full);
+        assert!(full.functions.is_empty());
+        // `x = 2.0`, `x += 3.0` and `x = 4.0` are removed.
+        assert_eq!(stores(&full), stores(&module) - 3);
+        // Only the `for` loop and the `if` statements in it are left.
+        assert_eq!(ifs_and_loops(&full), 3);
+    }
+}
@@ -29,6 +29,10 @@ pub fn ensure_block_returns(block: &mut crate::Block) {
             }
         }
         Some(&mut (S::Break | S::Continue | S::Return { .. } | S::Kill)) => (),
+        Some(&mut S::RayPipelineFunction(
+            crate::RayPipelineFunction::IgnoreIntersection
+            | crate::RayPipelineFunction::TerminateRay,
+        )) => (),
         Some(
             &mut (S::Emit(_)
             | S::Loop { .. }
@@ -43,7 +47,9 @@ pub fn ensure_block_returns(block: &mut crate::Block) {
             | S::SubgroupCollectiveOperation { .. }
             | S::SubgroupGather { .. }
             | S::ControlBarrier(_)
-            | S::MemoryBarrier(_)),
+            | S::MemoryBarrier(_)
+            | S::DebugPrintf { .. }
+            | S::RayPipelineFunction(crate::RayPipelineFunction::TraceRay { .. })),
         )
         | None => block.push(S::Return { value: None }, Default::default()),
     }
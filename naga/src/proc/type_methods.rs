@@ -50,6 +50,22 @@ impl crate::Scalar {
         kind: crate::ScalarKind::Uint,
         width: 8,
     };
+    pub const I8: Self = Self {
+        kind: crate::ScalarKind::Sint,
+        width: 1,
+    };
+    pub const U8: Self = Self {
+        kind: crate::ScalarKind::Uint,
+        width: 1,
+    };
+    pub const I16: Self = Self {
+        kind: crate::ScalarKind::Sint,
+        width: 2,
+    };
+    pub const U16: Self = Self {
+        kind: crate::ScalarKind::Uint,
+        width: 2,
+    };
     pub const BOOL: Self = Self {
         kind: crate::ScalarKind::Bool,
         width: crate::BOOL_WIDTH,
@@ -512,6 +512,10 @@ impl<'a> ResolveContext<'a> {
                         scalar: format.into(),
                         size: crate::VectorSize::Quad,
                     },
+                    crate::ImageClass::External => Ti::Vector {
+                        scalar: crate::Scalar::F32,
+                        size: crate::VectorSize::Quad,
+                    },
                 }),
                 ref other => {
                     log::error!("Image type {:?}", other);
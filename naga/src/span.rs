@@ -247,6 +247,17 @@ impl<E> WithSpan<E> {
     }
 
     pub(crate) fn diagnostic(&self) -> codespan_reporting::diagnostic::Diagnostic<()>
+    where
+        E: Error,
+    {
+        self.diagnostic_in_files(|span| span.to_range().map(|range| ((), range)))
+    }
+
+    /// Builds the diagnostic, with `locate` mapping each span to a file and a range in it.
+    pub(crate) fn diagnostic_in_files<F>(
+        &self,
+        locate: impl Fn(Span) -> Option<(F, Range<usize>)>,
+    ) -> codespan_reporting::diagnostic::Diagnostic<F>
     where
         E: Error,
     {
@@ -255,8 +266,9 @@ impl<E> WithSpan<E> {
             .with_message(self.inner.to_string())
             .with_labels(
                 self.spans()
-                    .map(|&(span, ref desc)| {
-                        Label::primary((), span.to_range().unwrap()).with_message(desc.to_owned())
+                    .filter_map(|&(span, ref desc)| {
+                        let (file, range) = locate(span)?;
+                        Some(Label::primary(file, range).with_message(desc.to_owned()))
                     })
                     .collect(),
             )
@@ -633,7 +633,7 @@ impl FunctionInfo {
                 let var = &resolve_context.global_vars[gh];
                 let uniform = match var.space {
                     // local data is non-uniform
-                    As::Function | As::Private => false,
+                    As::Function | As::Private | As::RayPayload | As::IncomingRayPayload => false,
                     // workgroup memory is exclusively accessed by the group
                     As::WorkGroup => true,
                     // uniform data
@@ -895,7 +895,11 @@ impl FunctionInfo {
                     }
                 }
                 S::Break | S::Continue => FunctionUniformity::new(),
-                S::Kill => FunctionUniformity {
+                S::Kill
+                | S::RayPipelineFunction(
+                    crate::RayPipelineFunction::IgnoreIntersection
+                    | crate::RayPipelineFunction::TerminateRay,
+                ) => FunctionUniformity {
                     result: Uniformity::new(),
                     exit: if disruptor.is_some() {
                         ExitFlags::MAY_KILL
@@ -1066,6 +1070,12 @@ impl FunctionInfo {
                     //Note: the result is validated by the Validator, not here
                     self.process_call(info, arguments, expression_arena)?
                 }
+                S::DebugPrintf { ref arguments, .. } => {
+                    for &argument in arguments {
+                        let _ = self.add_ref(argument);
+                    }
+                    FunctionUniformity::new()
+                }
                 S::Atomic {
                     pointer,
                     ref fun,
@@ -1113,6 +1123,22 @@ impl FunctionInfo {
                     }
                     FunctionUniformity::new()
                 }
+                S::RayPipelineFunction(crate::RayPipelineFunction::TraceRay {
+                    acceleration_structure,
+                    descriptor,
+                    sbt_record_offset,
+                    sbt_record_stride,
+                    miss_index,
+                    payload,
+                }) => {
+                    let _ = self.add_ref(acceleration_structure);
+                    let _ = self.add_ref(descriptor);
+                    let _ = self.add_ref(sbt_record_offset);
+                    let _ = self.add_ref(sbt_record_stride);
+                    let _ = self.add_ref(miss_index);
+                    let _ = self.add_ref_impl(payload, GlobalUse::READ | GlobalUse::WRITE);
+                    FunctionUniformity::new()
+                }
                 S::SubgroupBallot {
                     result: _,
                     predicate,
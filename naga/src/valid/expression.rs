@@ -133,6 +133,10 @@ pub enum ExpressionError {
     InvalidSampleClampCoordinateToEdge(alloc::string::String),
     #[error("Unable to cast")]
     InvalidCastArgument,
+    #[error(
+        "Floats can't be converted to 8-bit or 16-bit integers directly, convert them to 32-bit integers first"
+    )]
+    FloatToSmallIntCast,
     #[error("Invalid argument count for {0:?}")]
     WrongArgumentCount(crate::MathFunction),
     #[error("Argument [{1}] to {0:?} as expression {2:?} has an invalid type.")]
@@ -469,6 +473,8 @@ impl super::Validator {
                         multi: false,
                     } if gather.is_some() => false,
                     crate::ImageClass::Depth { multi: false } => true,
+                    // External textures can only be sampled by clamping to the edge
+                    crate::ImageClass::External if clamp_to_edge => false,
                     _ => return Err(ExpressionError::InvalidImageClass(class)),
                 };
                 if comparison != depth_ref.is_some() || (comparison && !image_depth) {
@@ -559,7 +565,7 @@ impl super::Validator {
                         crate::ImageClass::Sampled {
                             kind: crate::ScalarKind::Float,
                             multi: false
-                        }
+                        } | crate::ImageClass::External
                     ) {
                         return Err(ExpressionError::InvalidSampleClampCoordinateToEdge(
                             alloc::format!("image class `{class:?}`"),
@@ -1133,6 +1139,7 @@ impl super::Validator {
                     crate::TypeInner::Matrix { scalar, .. } => scalar,
                     _ => return Err(ExpressionError::InvalidCastArgument),
                 };
+                let from_float = base_scalar.kind == crate::ScalarKind::Float;
                 base_scalar.kind = kind;
                 if let Some(width) = convert {
                     base_scalar.width = width;
@@ -1140,6 +1147,15 @@ impl super::Validator {
                 if self.check_width(base_scalar).is_err() {
                     return Err(ExpressionError::InvalidCastArgument);
                 }
+                // Backends clamp floats converted to integers to the range of the integer
+                // type, which they only implement for 32-bit and 64-bit integers.
+                if from_float
+                    && convert.is_some()
+                    && matches!(kind, crate::ScalarKind::Sint | crate::ScalarKind::Uint)
+                    && base_scalar.width < 4
+                {
+                    return Err(ExpressionError::FloatToSmallIntCast);
+                }
                 ShaderStages::all()
             }
             E::CallResult(function) => mod_info.functions[function.index()].available_stages,
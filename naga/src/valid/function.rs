@@ -1,4 +1,4 @@
-use alloc::{format, string::String};
+use alloc::{format, string::String, vec::Vec};
 
 use super::validate_atomic_compare_exchange_struct;
 use super::{
@@ -77,6 +77,24 @@ pub enum SubgroupError {
     InvalidInvocationIdExprType(Handle<crate::Expression>),
 }
 
+#[derive(Clone, Debug, thiserror::Error)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum DebugPrintfError {
+    #[error("The conversion specifier at byte {0} of the format string is incomplete")]
+    IncompleteSpecifier(usize),
+    #[error("The conversion specifier `{0}` is not supported")]
+    UnsupportedSpecifier(String),
+    #[error(
+        "The format string has {specifiers} conversion specifiers, but {arguments} arguments are given"
+    )]
+    ArgumentCount { specifiers: usize, arguments: usize },
+    #[error("Argument {argument:?} doesn't match the conversion specifier `{specifier}`")]
+    MismatchedArgument {
+        argument: Handle<crate::Expression>,
+        specifier: String,
+    },
+}
+
 #[derive(Clone, Debug, thiserror::Error)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum LocalVariableError {
@@ -193,6 +211,10 @@ pub enum FunctionError {
     InvalidRayQueryType(Handle<crate::Type>),
     #[error("Hit distance {0:?} must be an f32")]
     InvalidHitDistanceType(Handle<crate::Expression>),
+    #[error("Shader binding table operand {0:?} must be a u32")]
+    InvalidShaderBindingTableOperand(Handle<crate::Expression>),
+    #[error("Ray payload {0:?} is not a ray payload global variable")]
+    InvalidRayPayload(Handle<crate::Expression>),
     #[error("Shader requires capability {0:?}")]
     MissingCapability(super::Capabilities),
     #[error(
@@ -223,6 +245,8 @@ pub enum FunctionError {
     WorkgroupUniformLoadInvalidPointer(Handle<crate::Expression>),
     #[error("Subgroup operation is invalid")]
     InvalidSubgroup(#[from] SubgroupError),
+    #[error("Debug printf is invalid")]
+    InvalidDebugPrintf(#[from] DebugPrintfError),
     #[error("Emit statement should not cover \"result\" expressions like {0:?}")]
     EmitResult(Handle<crate::Expression>),
     #[error("Expression not visited by the appropriate statement")]
@@ -704,6 +728,49 @@ impl super::Validator {
         }
         Ok(())
     }
+
+    fn validate_debug_printf(
+        &self,
+        format: &str,
+        arguments: &[Handle<crate::Expression>],
+        span: crate::Span,
+        context: &BlockContext,
+    ) -> Result<(), WithSpan<FunctionError>> {
+        let specifiers = debug_printf_specifiers(format)
+            .map_err(|error| FunctionError::from(error).with_span_static(span, "invalid format"))?;
+        if specifiers.len() != arguments.len() {
+            return Err(FunctionError::from(DebugPrintfError::ArgumentCount {
+                specifiers: specifiers.len(),
+                arguments: arguments.len(),
+            })
+            .with_span_static(span, "wrong number of arguments"));
+        }
+        for (&(vector_size, kind, specifier), &argument) in specifiers.iter().zip(arguments) {
+            let ty = context.resolve_type_inner(argument, &self.valid_expression_set)?;
+            let (size, scalar) = match *ty {
+                crate::TypeInner::Scalar(scalar) => (None, scalar),
+                crate::TypeInner::Vector { size, scalar } => (Some(size), scalar),
+                _ => (None, crate::Scalar::BOOL),
+            };
+            let kind_matches = match kind {
+                crate::ScalarKind::Float => scalar.kind == crate::ScalarKind::Float,
+                _ => matches!(
+                    scalar.kind,
+                    crate::ScalarKind::Sint | crate::ScalarKind::Uint
+                ),
+            };
+            if size != vector_size || !kind_matches || scalar.width != 4 {
+                return Err(DebugPrintfError::MismatchedArgument {
+                    argument,
+                    specifier: specifier.into(),
+                }
+                .with_span_handle(argument, context.expressions)
+                .into_other());
+            }
+        }
+        Ok(())
+    }
+
     fn validate_subgroup_gather(
         &mut self,
         mode: &crate::GatherMode,
@@ -1550,6 +1617,81 @@ impl super::Validator {
                         crate::RayQueryFunction::Terminate => {}
                     }
                 }
+                S::RayPipelineFunction(ref fun) => {
+                    if !self
+                        .capabilities
+                        .contains(super::Capabilities::RAY_TRACING_PIPELINE)
+                    {
+                        return Err(FunctionError::MissingCapability(
+                            super::Capabilities::RAY_TRACING_PIPELINE,
+                        )
+                        .with_span_static(span, "missing capability for this operation"));
+                    }
+                    match *fun {
+                        crate::RayPipelineFunction::TraceRay {
+                            acceleration_structure,
+                            descriptor,
+                            sbt_record_offset,
+                            sbt_record_stride,
+                            miss_index,
+                            payload,
+                        } => {
+                            stages &= super::ShaderStages::RAY_GENERATION
+                                | super::ShaderStages::MISS
+                                | super::ShaderStages::CLOSEST_HIT;
+                            match *context.resolve_type_inner(
+                                acceleration_structure,
+                                &self.valid_expression_set,
+                            )? {
+                                Ti::AccelerationStructure { .. } => {}
+                                _ => {
+                                    return Err(FunctionError::InvalidAccelerationStructure(
+                                        acceleration_structure,
+                                    )
+                                    .with_span_static(span, "invalid acceleration structure"));
+                                }
+                            }
+                            let desc_ty_given = context
+                                .resolve_type_inner(descriptor, &self.valid_expression_set)?;
+                            let desc_ty_expected = context
+                                .special_types
+                                .ray_desc
+                                .map(|handle| &context.types[handle].inner);
+                            if Some(desc_ty_given) != desc_ty_expected {
+                                return Err(FunctionError::InvalidRayDescriptor(descriptor)
+                                    .with_span_static(span, "invalid ray descriptor"));
+                            }
+                            for operand in [sbt_record_offset, sbt_record_stride, miss_index] {
+                                match *context
+                                    .resolve_type_inner(operand, &self.valid_expression_set)?
+                                {
+                                    Ti::Scalar(crate::Scalar::U32) => {}
+                                    _ => {
+                                        return Err(
+                                            FunctionError::InvalidShaderBindingTableOperand(
+                                                operand,
+                                            )
+                                            .with_span_handle(operand, context.expressions),
+                                        );
+                                    }
+                                }
+                            }
+                            match *context.get_expression(payload) {
+                                crate::Expression::GlobalVariable(var)
+                                    if context.global_vars[var].space
+                                        == AddressSpace::RayPayload => {}
+                                _ => {
+                                    return Err(FunctionError::InvalidRayPayload(payload)
+                                        .with_span_handle(payload, context.expressions));
+                                }
+                            }
+                        }
+                        crate::RayPipelineFunction::IgnoreIntersection
+                        | crate::RayPipelineFunction::TerminateRay => {
+                            stages &= super::ShaderStages::ANY_HIT;
+                        }
+                    }
+                }
                 S::SubgroupBallot { result, predicate } => {
                     stages &= self.subgroup_stages;
                     if !self.capabilities.contains(super::Capabilities::SUBGROUP) {
@@ -1630,6 +1772,21 @@ impl super::Validator {
                     }
                     self.validate_subgroup_gather(mode, argument, result, context)?;
                 }
+                S::DebugPrintf {
+                    ref format,
+                    ref arguments,
+                } => {
+                    if !self
+                        .capabilities
+                        .contains(super::Capabilities::DEBUG_PRINTF)
+                    {
+                        return Err(FunctionError::MissingCapability(
+                            super::Capabilities::DEBUG_PRINTF,
+                        )
+                        .with_span_static(span, "missing capability for this operation"));
+                    }
+                    self.validate_debug_printf(format, arguments, span, context)?;
+                }
             }
         }
         Ok(BlockInfo { stages })
@@ -1801,3 +1958,77 @@ impl super::Validator {
         Ok(info)
     }
 }
+
+/// The vector size, scalar kind and text of a conversion specifier in the format
+/// string of a [`DebugPrintf`] statement.
+///
+/// [`DebugPrintf`]: crate::Statement::DebugPrintf
+type DebugPrintfSpecifier<'a> = (Option<crate::VectorSize>, crate::ScalarKind, &'a str);
+
+/// Returns the conversion specifiers in the format string of a [`DebugPrintf`]
+/// statement.
+///
+/// Integer specifiers are reported as [`ScalarKind::Sint`]; they accept unsigned
+/// integers too.
+///
+/// [`DebugPrintf`]: crate::Statement::DebugPrintf
+/// [`ScalarKind::Sint`]: crate::ScalarKind::Sint
+fn debug_printf_specifiers(
+    format: &str,
+) -> Result<Vec<DebugPrintfSpecifier<'_>>, DebugPrintfError> {
+    let mut specifiers = Vec::new();
+    let bytes = format.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        if bytes.get(i) == Some(&b'%') {
+            i += 1;
+            continue;
+        }
+        // Flags, width and precision.
+        while i < bytes.len() && b"-+ #0".contains(&bytes[i]) {
+            i += 1;
+        }
+        while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+            i += 1;
+        }
+        let mut vector_size = None;
+        if bytes.get(i) == Some(&b'v') {
+            vector_size = Some(match bytes.get(i + 1).copied() {
+                Some(b'2') => crate::VectorSize::Bi,
+                Some(b'3') => crate::VectorSize::Tri,
+                Some(b'4') => crate::VectorSize::Quad,
+                Some(_) => {
+                    let end = i + 1 + format[i + 1..].chars().next().map_or(0, char::len_utf8);
+                    return Err(DebugPrintfError::UnsupportedSpecifier(
+                        format[start..end].into(),
+                    ));
+                }
+                None => return Err(DebugPrintfError::IncompleteSpecifier(start)),
+            });
+            i += 2;
+        }
+        let kind = match bytes.get(i).copied() {
+            Some(b'd' | b'i' | b'o' | b'u' | b'x' | b'X') => crate::ScalarKind::Sint,
+            Some(b'a' | b'A' | b'e' | b'E' | b'f' | b'F' | b'g' | b'G') => crate::ScalarKind::Float,
+            Some(_) => {
+                let end = format[i..]
+                    .chars()
+                    .next()
+                    .map_or(format.len(), |c| i + c.len_utf8());
+                return Err(DebugPrintfError::UnsupportedSpecifier(
+                    format[start..end].into(),
+                ));
+            }
+            None => return Err(DebugPrintfError::IncompleteSpecifier(start)),
+        };
+        i += 1;
+        specifiers.push((vector_size, kind, &format[start..i]));
+    }
+    Ok(specifiers)
+}
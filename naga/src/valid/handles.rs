@@ -801,6 +801,28 @@ impl super::Validator {
                 }
                 Ok(())
             }
+            crate::Statement::RayPipelineFunction(ref fun) => {
+                match *fun {
+                    crate::RayPipelineFunction::TraceRay {
+                        acceleration_structure,
+                        descriptor,
+                        sbt_record_offset,
+                        sbt_record_stride,
+                        miss_index,
+                        payload,
+                    } => {
+                        validate_expr(acceleration_structure)?;
+                        validate_expr(descriptor)?;
+                        validate_expr(sbt_record_offset)?;
+                        validate_expr(sbt_record_stride)?;
+                        validate_expr(miss_index)?;
+                        validate_expr(payload)?;
+                    }
+                    crate::RayPipelineFunction::IgnoreIntersection
+                    | crate::RayPipelineFunction::TerminateRay => {}
+                }
+                Ok(())
+            }
             crate::Statement::SubgroupBallot { result, predicate } => {
                 validate_expr_opt(predicate)?;
                 validate_expr(result)?;
@@ -835,6 +857,12 @@ impl super::Validator {
                 validate_expr(result)?;
                 Ok(())
             }
+            crate::Statement::DebugPrintf { ref arguments, .. } => {
+                for arg in arguments.iter().copied() {
+                    validate_expr(arg)?;
+                }
+                Ok(())
+            }
             crate::Statement::Break
             | crate::Statement::Continue
             | crate::Statement::Kill
@@ -121,6 +121,10 @@ pub enum EntryPointError {
     InvalidGlobalUsage(Handle<crate::GlobalVariable>, GlobalUse),
     #[error("More than 1 push constant variable is used")]
     MoreThanOnePushConstantUsed,
+    #[error("More than 1 incoming ray payload variable is used")]
+    MoreThanOneIncomingRayPayloadUsed,
+    #[error("Ray tracing shaders can't return a value")]
+    UnexpectedRayTracingResult,
     #[error("Bindings for {0:?} conflict with other resource")]
     BindingCollision(Handle<crate::GlobalVariable>),
     #[error("Argument {0} varying error")]
@@ -187,13 +191,34 @@ impl VaryingContext<'_> {
                 let required = match built_in {
                     Bi::ClipDistance => Capabilities::CLIP_DISTANCE,
                     Bi::CullDistance => Capabilities::CULL_DISTANCE,
+                    Bi::PrimitiveIndex if self.stage.is_ray_tracing() => {
+                        Capabilities::RAY_TRACING_PIPELINE
+                    }
                     Bi::PrimitiveIndex => Capabilities::PRIMITIVE_INDEX,
                     Bi::ViewIndex => Capabilities::MULTIVIEW,
                     Bi::SampleIndex => Capabilities::MULTISAMPLED_SHADING,
+                    Bi::FramebufferFetch { .. } => Capabilities::FRAMEBUFFER_FETCH,
+                    Bi::FullyCovered => Capabilities::FRAGMENT_FULLY_COVERED,
+                    Bi::ViewportIndex | Bi::Layer => Capabilities::MULTI_VIEWPORT,
                     Bi::NumSubgroups
                     | Bi::SubgroupId
                     | Bi::SubgroupSize
                     | Bi::SubgroupInvocationId => Capabilities::SUBGROUP,
+                    Bi::LaunchId
+                    | Bi::LaunchSize
+                    | Bi::WorldRayOrigin
+                    | Bi::WorldRayDirection
+                    | Bi::ObjectRayOrigin
+                    | Bi::ObjectRayDirection
+                    | Bi::RayTMin
+                    | Bi::RayTCurrentMax
+                    | Bi::IncomingRayFlags
+                    | Bi::InstanceCustomData
+                    | Bi::GeometryIndex
+                    | Bi::ObjectToWorld
+                    | Bi::WorldToObject
+                    | Bi::HitKind
+                    | Bi::Barycentrics => Capabilities::RAY_TRACING_PIPELINE,
                     _ => Capabilities::empty(),
                 };
                 if !self.capabilities.contains(required) {
@@ -208,12 +233,13 @@ impl VaryingContext<'_> {
                     return Err(VaryingError::InvalidMultiDimensionalSubgroupBuiltIn);
                 }
 
+                let is_hit_stage = matches!(self.stage, St::ClosestHit | St::AnyHit);
                 let (visible, type_good) = match built_in {
-                    Bi::BaseInstance
-                    | Bi::BaseVertex
-                    | Bi::InstanceIndex
-                    | Bi::VertexIndex
-                    | Bi::DrawID => (
+                    Bi::InstanceIndex => (
+                        (self.stage == St::Vertex || is_hit_stage) && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::BaseInstance | Bi::BaseVertex | Bi::VertexIndex | Bi::DrawID => (
                         self.stage == St::Vertex && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
@@ -230,6 +256,10 @@ impl VaryingContext<'_> {
                             _ => false,
                         },
                     ),
+                    Bi::ViewportIndex | Bi::Layer => (
+                        self.stage == St::Vertex && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
                     Bi::PointSize => (
                         self.stage == St::Vertex && self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::F32),
@@ -248,6 +278,7 @@ impl VaryingContext<'_> {
                             St::Fragment => !self.output,
                             St::Compute => false,
                             St::Task | St::Mesh => unreachable!(),
+                            St::RayGeneration | St::Miss | St::ClosestHit | St::AnyHit => false,
                         },
                         *ty_inner
                             == Ti::Vector {
@@ -260,6 +291,7 @@ impl VaryingContext<'_> {
                             St::Vertex | St::Fragment => !self.output,
                             St::Compute => false,
                             St::Task | St::Mesh => unreachable!(),
+                            St::RayGeneration | St::Miss | St::ClosestHit | St::AnyHit => false,
                         },
                         *ty_inner == Ti::Scalar(crate::Scalar::I32),
                     ),
@@ -267,12 +299,12 @@ impl VaryingContext<'_> {
                         self.stage == St::Fragment && self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::F32),
                     ),
-                    Bi::FrontFacing => (
+                    Bi::FrontFacing | Bi::FullyCovered => (
                         self.stage == St::Fragment && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::BOOL),
                     ),
                     Bi::PrimitiveIndex => (
-                        self.stage == St::Fragment && !self.output,
+                        (self.stage == St::Fragment || is_hit_stage) && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::SampleIndex => (
@@ -283,6 +315,21 @@ impl VaryingContext<'_> {
                         self.stage == St::Fragment,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
+                    Bi::FramebufferFetch { .. } => (
+                        self.stage == St::Fragment && !self.output,
+                        match *ty_inner {
+                            Ti::Scalar(scalar) | Ti::Vector { scalar, .. } => {
+                                scalar.width == 4
+                                    && matches!(
+                                        scalar.kind,
+                                        crate::ScalarKind::Float
+                                            | crate::ScalarKind::Sint
+                                            | crate::ScalarKind::Uint
+                                    )
+                            }
+                            _ => false,
+                        },
+                    ),
                     Bi::LocalInvocationIndex => (
                         self.stage == St::Compute && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
@@ -308,9 +355,63 @@ impl VaryingContext<'_> {
                             St::Compute | St::Fragment => !self.output,
                             St::Vertex => false,
                             St::Task | St::Mesh => unreachable!(),
+                            St::RayGeneration | St::Miss | St::ClosestHit | St::AnyHit => false,
                         },
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
+                    Bi::LaunchId | Bi::LaunchSize => (
+                        self.stage.is_ray_tracing() && !self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::WorldRayOrigin | Bi::WorldRayDirection => (
+                        (self.stage == St::Miss || is_hit_stage) && !self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::F32,
+                            },
+                    ),
+                    Bi::ObjectRayOrigin | Bi::ObjectRayDirection => (
+                        is_hit_stage && !self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::F32,
+                            },
+                    ),
+                    Bi::RayTMin | Bi::RayTCurrentMax => (
+                        (self.stage == St::Miss || is_hit_stage) && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::F32),
+                    ),
+                    Bi::IncomingRayFlags => (
+                        (self.stage == St::Miss || is_hit_stage) && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::InstanceCustomData | Bi::GeometryIndex | Bi::HitKind => (
+                        is_hit_stage && !self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::ObjectToWorld | Bi::WorldToObject => (
+                        is_hit_stage && !self.output,
+                        *ty_inner
+                            == Ti::Matrix {
+                                columns: Vs::Quad,
+                                rows: Vs::Tri,
+                                scalar: crate::Scalar::F32,
+                            },
+                    ),
+                    Bi::Barycentrics => (
+                        is_hit_stage && !self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Bi,
+                                scalar: crate::Scalar::F32,
+                            },
+                    ),
                 };
 
                 if !visible {
@@ -327,6 +428,12 @@ impl VaryingContext<'_> {
                 sampling,
                 blend_src,
             } => {
+                if self.stage.is_ray_tracing() {
+                    return Err(VaryingError::InvalidAttributeInStage(
+                        "location", self.stage,
+                    ));
+                }
+
                 // Only IO-shareable types may be stored in locations.
                 if !self.type_info[ty.index()]
                     .flags
@@ -403,6 +510,10 @@ impl VaryingContext<'_> {
                     crate::ShaderStage::Fragment => !self.output,
                     crate::ShaderStage::Compute => false,
                     crate::ShaderStage::Task | crate::ShaderStage::Mesh => unreachable!(),
+                    crate::ShaderStage::RayGeneration
+                    | crate::ShaderStage::Miss
+                    | crate::ShaderStage::ClosestHit
+                    | crate::ShaderStage::AnyHit => unreachable!(),
                 };
 
                 // It doesn't make sense to specify a sampling when `interpolation` is `Flat`, but
@@ -621,6 +732,23 @@ impl super::Validator {
                     false,
                 )
             }
+            crate::AddressSpace::RayPayload | crate::AddressSpace::IncomingRayPayload => {
+                if !self
+                    .capabilities
+                    .contains(Capabilities::RAY_TRACING_PIPELINE)
+                {
+                    return Err(GlobalVariableError::UnsupportedCapability(
+                        Capabilities::RAY_TRACING_PIPELINE,
+                    ));
+                }
+                if !matches!(gctx.types[var.ty].inner, crate::TypeInner::Struct { .. }) {
+                    return Err(GlobalVariableError::InvalidType(var.space));
+                }
+                (
+                    TypeFlags::CONSTRUCTIBLE | TypeFlags::CREATION_RESOLVED,
+                    false,
+                )
+            }
         };
 
         if !type_info.flags.contains(required_type_flags) {
@@ -665,6 +793,20 @@ impl super::Validator {
         module: &crate::Module,
         mod_info: &ModuleInfo,
     ) -> Result<FunctionInfo, WithSpan<EntryPointError>> {
+        if ep.stage.is_ray_tracing() {
+            let required = Capabilities::RAY_TRACING_PIPELINE;
+            if !self.capabilities.contains(required) {
+                return Err(
+                    EntryPointError::Result(VaryingError::UnsupportedCapability(required))
+                        .with_span(),
+                );
+            }
+
+            if ep.function.result.is_some() {
+                return Err(EntryPointError::UnexpectedRayTracingResult.with_span());
+            }
+        }
+
         if ep.early_depth_test.is_some() {
             let required = Capabilities::EARLY_DEPTH_TEST;
             if !self.capabilities.contains(required) {
@@ -703,6 +845,10 @@ impl super::Validator {
                 crate::ShaderStage::Fragment => ShaderStages::FRAGMENT,
                 crate::ShaderStage::Compute => ShaderStages::COMPUTE,
                 crate::ShaderStage::Task | crate::ShaderStage::Mesh => unreachable!(),
+                crate::ShaderStage::RayGeneration => ShaderStages::RAY_GENERATION,
+                crate::ShaderStage::Miss => ShaderStages::MISS,
+                crate::ShaderStage::ClosestHit => ShaderStages::CLOSEST_HIT,
+                crate::ShaderStage::AnyHit => ShaderStages::ANY_HIT,
             };
 
             if !info.available_stages.contains(stage_bit) {
@@ -772,6 +918,19 @@ impl super::Validator {
             }
         }
 
+        {
+            let mut used_incoming_ray_payloads = module
+                .global_variables
+                .iter()
+                .filter(|&(_, var)| var.space == crate::AddressSpace::IncomingRayPayload)
+                .map(|(handle, _)| handle)
+                .filter(|&handle| !info[handle].is_empty());
+            if let Some(handle) = used_incoming_ray_payloads.nth(1) {
+                return Err(EntryPointError::MoreThanOneIncomingRayPayloadUsed
+                    .with_span_handle(handle, &module.global_variables));
+            }
+        }
+
         self.ep_resource_bindings.clear();
         for (var_handle, var) in module.global_variables.iter() {
             let usage = info[var_handle];
@@ -801,6 +960,18 @@ impl super::Validator {
                     GlobalUse::READ | GlobalUse::WRITE | GlobalUse::QUERY
                 }
                 crate::AddressSpace::PushConstant => GlobalUse::READ,
+                crate::AddressSpace::RayPayload => match ep.stage {
+                    crate::ShaderStage::RayGeneration
+                    | crate::ShaderStage::Miss
+                    | crate::ShaderStage::ClosestHit => GlobalUse::READ | GlobalUse::WRITE,
+                    _ => GlobalUse::empty(),
+                },
+                crate::AddressSpace::IncomingRayPayload => match ep.stage {
+                    crate::ShaderStage::Miss
+                    | crate::ShaderStage::ClosestHit
+                    | crate::ShaderStage::AnyHit => GlobalUse::READ | GlobalUse::WRITE,
+                    _ => GlobalUse::empty(),
+                },
             };
             if !allowed_usage.contains(usage) {
                 log::warn!("\tUsage error for: {:?}", var);
@@ -29,7 +29,7 @@ pub use analyzer::{ExpressionInfo, FunctionInfo, GlobalUse, Uniformity, Uniformi
 pub use compose::ComposeError;
 pub use expression::{ConstExpressionError, ExpressionError};
 pub use expression::{LiteralError, check_literal_value};
-pub use function::{CallError, FunctionError, LocalVariableError, SubgroupError};
+pub use function::{CallError, DebugPrintfError, FunctionError, LocalVariableError, SubgroupError};
 pub use interface::{EntryPointError, GlobalVariableError, VaryingError};
 pub use r#type::{Disalignment, PushConstantError, TypeError, TypeFlags, WidthError};
 
@@ -80,7 +80,7 @@ bitflags::bitflags! {
     #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
     #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-    pub struct Capabilities: u32 {
+    pub struct Capabilities: u64 {
         /// Support for [`AddressSpace::PushConstant`][1].
         ///
         /// [1]: crate::AddressSpace::PushConstant
@@ -165,6 +165,35 @@ bitflags::bitflags! {
         const RAY_HIT_VERTEX_POSITION = 1 << 25;
         /// Support for 16-bit floating-point types.
         const SHADER_FLOAT16 = 1 << 26;
+        /// Support for [`BuiltIn::FramebufferFetch`].
+        ///
+        /// [`BuiltIn::FramebufferFetch`]: crate::BuiltIn::FramebufferFetch
+        const FRAMEBUFFER_FETCH = 1 << 27;
+        /// Support for [`BuiltIn::FullyCovered`].
+        ///
+        /// [`BuiltIn::FullyCovered`]: crate::BuiltIn::FullyCovered
+        const FRAGMENT_FULLY_COVERED = 1 << 28;
+        /// Support for [`BuiltIn::ViewportIndex`] and [`BuiltIn::Layer`].
+        ///
+        /// [`BuiltIn::ViewportIndex`]: crate::BuiltIn::ViewportIndex
+        /// [`BuiltIn::Layer`]: crate::BuiltIn::Layer
+        const MULTI_VIEWPORT = 1 << 29;
+        /// Support for [`Statement::DebugPrintf`].
+        ///
+        /// [`Statement::DebugPrintf`]: crate::Statement::DebugPrintf
+        const DEBUG_PRINTF = 1 << 30;
+        /// Support for 8-bit integer types: `i8` and `u8`.
+        const SHADER_INT8 = 1 << 31;
+        /// Support for 16-bit integer types: `i16` and `u16`.
+        const SHADER_INT16 = 1 << 32;
+        /// Support for ray tracing pipeline stages, the [`RayPayload`] and
+        /// [`IncomingRayPayload`] address spaces, their built-ins, and
+        /// [`Statement::RayPipelineFunction`].
+        ///
+        /// [`RayPayload`]: crate::AddressSpace::RayPayload
+        /// [`IncomingRayPayload`]: crate::AddressSpace::IncomingRayPayload
+        /// [`Statement::RayPipelineFunction`]: crate::Statement::RayPipelineFunction
+        const RAY_TRACING_PIPELINE = 1 << 33;
     }
 }
 
@@ -235,6 +264,10 @@ bitflags::bitflags! {
         const VERTEX = 0x1;
         const FRAGMENT = 0x2;
         const COMPUTE = 0x4;
+        const RAY_GENERATION = 0x8;
+        const MISS = 0x10;
+        const CLOSEST_HIT = 0x20;
+        const ANY_HIT = 0x40;
     }
 }
 
@@ -220,9 +220,13 @@ const fn ptr_space_argument_flag(space: crate::AddressSpace) -> TypeFlags {
     use crate::AddressSpace as As;
     match space {
         As::Function | As::Private => TypeFlags::ARGUMENT,
-        As::Uniform | As::Storage { .. } | As::Handle | As::PushConstant | As::WorkGroup => {
-            TypeFlags::empty()
-        }
+        As::Uniform
+        | As::Storage { .. }
+        | As::Handle
+        | As::PushConstant
+        | As::WorkGroup
+        | As::RayPayload
+        | As::IncomingRayPayload => TypeFlags::empty(),
     }
 }
 
@@ -254,6 +258,19 @@ impl TypeInfo {
     }
 }
 
+/// Returns the [`TypeFlags`] saying where scalars and vectors of `scalar` can be shared.
+///
+/// 8-bit and 16-bit integers can be stored in buffers, but not passed between stages.
+const fn shareable_flags(scalar: crate::Scalar) -> TypeFlags {
+    match scalar.kind {
+        crate::ScalarKind::Sint | crate::ScalarKind::Uint if scalar.width < 4 => {
+            TypeFlags::HOST_SHAREABLE
+        }
+        _ if scalar.kind.is_numeric() => TypeFlags::IO_SHAREABLE.union(TypeFlags::HOST_SHAREABLE),
+        _ => TypeFlags::empty(),
+    }
+}
+
 impl super::Validator {
     const fn require_type_capability(&self, capability: Capabilities) -> Result<(), TypeError> {
         if self.capabilities.contains(capability) {
@@ -294,8 +311,8 @@ impl super::Validator {
                 }
                 _ => scalar.width == 4,
             },
-            crate::ScalarKind::Sint => {
-                if scalar.width == 8 {
+            crate::ScalarKind::Sint => match scalar.width {
+                8 => {
                     if !self.capabilities.contains(Capabilities::SHADER_INT64) {
                         return Err(WidthError::MissingCapability {
                             name: "i64",
@@ -303,12 +320,31 @@ impl super::Validator {
                         });
                     }
                     true
-                } else {
-                    scalar.width == 4
                 }
-            }
-            crate::ScalarKind::Uint => {
-                if scalar.width == 8 {
+                2 => {
+                    if !self.capabilities.contains(Capabilities::SHADER_INT16) {
+                        return Err(WidthError::MissingCapability {
+                            name: "i16",
+                            flag: "SHADER_INT16",
+                        });
+                    }
+                    push_constant_compatibility = Err(PushConstantError::InvalidScalar(scalar));
+                    true
+                }
+                1 => {
+                    if !self.capabilities.contains(Capabilities::SHADER_INT8) {
+                        return Err(WidthError::MissingCapability {
+                            name: "i8",
+                            flag: "SHADER_INT8",
+                        });
+                    }
+                    push_constant_compatibility = Err(PushConstantError::InvalidScalar(scalar));
+                    true
+                }
+                _ => scalar.width == 4,
+            },
+            crate::ScalarKind::Uint => match scalar.width {
+                8 => {
                     if !self.capabilities.contains(Capabilities::SHADER_INT64) {
                         return Err(WidthError::MissingCapability {
                             name: "u64",
@@ -316,10 +352,29 @@ impl super::Validator {
                         });
                     }
                     true
-                } else {
-                    scalar.width == 4
                 }
-            }
+                2 => {
+                    if !self.capabilities.contains(Capabilities::SHADER_INT16) {
+                        return Err(WidthError::MissingCapability {
+                            name: "u16",
+                            flag: "SHADER_INT16",
+                        });
+                    }
+                    push_constant_compatibility = Err(PushConstantError::InvalidScalar(scalar));
+                    true
+                }
+                1 => {
+                    if !self.capabilities.contains(Capabilities::SHADER_INT8) {
+                        return Err(WidthError::MissingCapability {
+                            name: "u8",
+                            flag: "SHADER_INT8",
+                        });
+                    }
+                    push_constant_compatibility = Err(PushConstantError::InvalidScalar(scalar));
+                    true
+                }
+                _ => scalar.width == 4,
+            },
             crate::ScalarKind::AbstractInt | crate::ScalarKind::AbstractFloat => {
                 return Err(WidthError::Abstract);
             }
@@ -346,11 +401,7 @@ impl super::Validator {
         Ok(match gctx.types[handle].inner {
             Ti::Scalar(scalar) => {
                 let push_constant_compatibility = self.check_width(scalar)?;
-                let shareable = if scalar.kind.is_numeric() {
-                    TypeFlags::IO_SHAREABLE | TypeFlags::HOST_SHAREABLE
-                } else {
-                    TypeFlags::empty()
-                };
+                let shareable = shareable_flags(scalar);
                 let mut type_info = TypeInfo::new(
                     TypeFlags::DATA
                         | TypeFlags::SIZED
@@ -366,11 +417,7 @@ impl super::Validator {
             }
             Ti::Vector { size, scalar } => {
                 let push_constant_compatibility = self.check_width(scalar)?;
-                let shareable = if scalar.kind.is_numeric() {
-                    TypeFlags::IO_SHAREABLE | TypeFlags::HOST_SHAREABLE
-                } else {
-                    TypeFlags::empty()
-                };
+                let shareable = shareable_flags(scalar);
                 let mut type_info = TypeInfo::new(
                     TypeFlags::DATA
                         | TypeFlags::SIZED
@@ -724,7 +771,10 @@ impl super::Validator {
                 arrayed,
                 class,
             } => {
-                if arrayed && matches!(dim, crate::ImageDimension::D3) {
+                let external = matches!(class, crate::ImageClass::External);
+                if (arrayed && matches!(dim, crate::ImageDimension::D3))
+                    || (external && (arrayed || dim != crate::ImageDimension::D2))
+                {
                     return Err(TypeError::UnsupportedImageType {
                         dim,
                         arrayed,
@@ -744,7 +794,14 @@ impl super::Validator {
                 Alignment::ONE,
             ),
             Ti::AccelerationStructure { vertex_return } => {
-                self.require_type_capability(Capabilities::RAY_QUERY)?;
+                // Ray tracing pipelines trace rays through acceleration
+                // structures without ray queries.
+                if !self
+                    .capabilities
+                    .contains(Capabilities::RAY_TRACING_PIPELINE)
+                {
+                    self.require_type_capability(Capabilities::RAY_QUERY)?;
+                }
                 if vertex_return {
                     self.require_type_capability(Capabilities::RAY_HIT_VERTEX_POSITION)?;
                 }
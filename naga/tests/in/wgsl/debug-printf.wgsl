@@ -0,0 +1,17 @@
+enable wgpu_debug_printf;
+
+@group(0) @binding(0)
+var<storage, read_write> values: array<f32>;
+
+fn check(index: u32) {
+    if values[index] < 0.0 {
+        debugPrintf("values[%u] = %f is \"negative\"\n", index, values[index]);
+    }
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    debugPrintf("invocation %u", id.x);
+    check(id.x);
+    debugPrintf("id = %v3u, 100%% done", id);
+}
@@ -0,0 +1,17 @@
+enable wgpu_framebuffer_fetch;
+
+struct FragmentInput {
+    @builtin(position) position: vec4<f32>,
+    @color(1) normal: vec4<f32>,
+}
+
+struct FragmentOutput {
+    @location(0) color: vec4<f32>,
+    @location(1) normal: vec4<f32>,
+}
+
+@fragment
+fn main(input: FragmentInput, @color(0) current: vec4<f32>) -> FragmentOutput {
+    let light = max(dot(input.normal.xyz, vec3<f32>(0.0, 0.0, 1.0)), 0.0);
+    return FragmentOutput(current * light, input.normal);
+}
@@ -0,0 +1,27 @@
+// An `if` statement whose condition is known before the shader runs is
+// replaced by the branch which is taken.
+
+const DEBUG: bool = false;
+const LIGHTING: bool = true;
+
+@group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    var x = data[id.x];
+    if DEBUG {
+        x = 0.0;
+    }
+    if LIGHTING {
+        x *= 2.0;
+    } else {
+        x = 1.0;
+    }
+    if !DEBUG && LIGHTING {
+        x += 1.0;
+    }
+    if x > 4.0 {
+        x = 4.0;
+    }
+    data[id.x] = x;
+}
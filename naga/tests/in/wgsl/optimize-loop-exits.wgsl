@@ -0,0 +1,39 @@
+// A `break` in a `switch` leaves the `switch`, but a `continue` in it continues
+// the loop.
+
+@group(0) @binding(0) var<storage, read_write> data: array<i32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    var x = data[id.x];
+
+    // Runs once: the `break` in the `switch` doesn't leave the loop.
+    loop {
+        switch x {
+            case 0: {
+                x = 1;
+                break;
+            }
+            default: {
+                x += 1;
+            }
+        }
+        break;
+    }
+
+    // Kept: the `continue` in the `switch` runs the loop again.
+    loop {
+        switch x {
+            case 0: {
+                x = 1;
+                continue;
+            }
+            default: {
+                x += 1;
+            }
+        }
+        break;
+    }
+
+    data[id.x] = x;
+}
@@ -0,0 +1,50 @@
+// Loops which run at most once are replaced by their body.
+
+@group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+fn first(id: u32) -> f32 {
+    // The body always returns, so neither the continuing block nor the
+    // statements after the loop ever run.
+    loop {
+        if data[id] > 0.0 {
+            return data[id];
+        }
+        return 0.0;
+
+        continuing {
+            data[id] = 1.0;
+        }
+    }
+    return -1.0;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    var x = first(id.x);
+
+    // The body ends with its only `break`.
+    loop {
+        x += 1.0;
+        break;
+    }
+
+    // The loop always breaks after its continuing block.
+    loop {
+        x *= 2.0;
+
+        continuing {
+            x -= 1.0;
+            break if true;
+        }
+    }
+
+    // Kept: the loop can run more than once.
+    loop {
+        x += 1.0;
+        if x > 10.0 {
+            break;
+        }
+    }
+
+    data[id.x] = x;
+}
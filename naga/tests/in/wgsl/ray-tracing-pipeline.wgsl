@@ -0,0 +1,57 @@
+enable wgpu_ray_tracing_pipeline;
+
+struct Payload {
+    color: vec3<f32>,
+    hit: u32,
+}
+
+@group(0) @binding(0)
+var acc_struct: acceleration_structure;
+
+@group(0) @binding(1)
+var output: texture_storage_2d<rgba8unorm, write>;
+
+var<ray_payload> payload: Payload;
+
+var<incoming_ray_payload> incoming: Payload;
+
+@ray_generation
+fn ray_gen(@builtin(ray_launch_id) id: vec3<u32>, @builtin(ray_launch_size) size: vec3<u32>) {
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size.xy);
+    let origin = vec3<f32>(uv * 2.0 - 1.0, -1.0);
+    let desc = RayDesc(RAY_FLAG_FORCE_OPAQUE, 0xffu, 0.001, 100.0, origin, vec3<f32>(0.0, 0.0, 1.0));
+    traceRay(acc_struct, desc, 0u, 1u, 0u, &payload);
+    textureStore(output, id.xy, vec4<f32>(payload.color, 1.0));
+}
+
+@miss
+fn miss(@builtin(world_ray_direction) direction: vec3<f32>) {
+    incoming.color = direction * 0.5 + 0.5;
+    incoming.hit = 0u;
+}
+
+struct HitInput {
+    @builtin(barycentrics) barycentrics: vec2<f32>,
+    @builtin(ray_t_current_max) t: f32,
+    @builtin(instance_custom_data) custom_data: u32,
+    @builtin(primitive_index) primitive_index: u32,
+    @builtin(object_to_world) object_to_world: mat4x3<f32>,
+}
+
+@closest_hit
+fn closest_hit(input: HitInput, @builtin(hit_kind) hit_kind: u32) {
+    let position = input.object_to_world * vec4<f32>(input.barycentrics, input.t, 1.0);
+    incoming.color = vec3<f32>(input.barycentrics, 1.0 - input.barycentrics.x - input.barycentrics.y) + position * 0.0;
+    incoming.hit = input.custom_data + input.primitive_index + hit_kind;
+}
+
+@any_hit
+fn any_hit(@builtin(barycentrics) barycentrics: vec2<f32>, @builtin(geometry_index) geometry_index: u32) {
+    if geometry_index == 1u {
+        ignoreIntersection();
+    }
+    if barycentrics.x > 0.9 {
+        incoming.hit = 1u;
+        terminateRay();
+    }
+}
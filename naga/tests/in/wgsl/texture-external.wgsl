@@ -0,0 +1,12 @@
+@group(0) @binding(0)
+var tex: texture_external;
+@group(0) @binding(1)
+var samp: sampler;
+
+@fragment
+fn fragment_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = textureDimensions(tex);
+    let sampled = textureSampleBaseClampToEdge(tex, samp, position.xy / vec2<f32>(size));
+    let loaded = textureLoad(tex, vec2<u32>(position.xy));
+    return sampled + loaded;
+}
@@ -0,0 +1,94 @@
+/*!
+Test the `#line` directives written by the HLSL and MSL backends.
+*/
+
+#![cfg(all(feature = "wgsl-in", any(hlsl_out, msl_out)))]
+
+use naga::back::DebugSource;
+
+const SOURCE: &str = "\
+@group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    let value = data[id.x];
+    data[id.x] = value * 2.0;
+}
+";
+
+fn parse() -> (naga::Module, naga::valid::ModuleInfo) {
+    let module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+    let info = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .unwrap();
+    (module, info)
+}
+
+fn debug_source() -> Option<DebugSource> {
+    Some(DebugSource {
+        file_name: "shaders/\"doubler\".wgsl".to_string(),
+        source_code: SOURCE.to_string(),
+    })
+}
+
+/// Checks that the statements of line 6 of [`SOURCE`] are attributed to it.
+#[track_caller]
+fn check_directives(output: &str) {
+    let directive = r#"#line 6 "shaders/\"doubler\".wgsl""#;
+    let lines: Vec<_> = output.lines().map(str::trim).collect();
+    let index = lines
+        .iter()
+        .rposition(|&line| line == directive)
+        .unwrap_or_else(|| panic!("no `{directive}` in:\n{output}"));
+    let next = lines[index + 1];
+    assert!(next.contains("2.0"), "`{next}` follows `{directive}`");
+    assert!(output.contains(r#"#line 5 "shaders/\"doubler\".wgsl""#));
+}
+
+#[cfg(hlsl_out)]
+fn write_hlsl(debug_source: Option<DebugSource>) -> String {
+    use naga::back::hlsl;
+
+    let (module, info) = parse();
+    let options = hlsl::Options::default();
+    let pipeline_options = hlsl::PipelineOptions {
+        entry_point: None,
+        debug_source,
+    };
+    let mut output = String::new();
+    hlsl::Writer::new(&mut output, &options, &pipeline_options)
+        .write(&module, &info, None)
+        .unwrap();
+    output
+}
+
+#[cfg(msl_out)]
+fn write_msl(debug_source: Option<DebugSource>) -> String {
+    use naga::back::msl;
+
+    let (module, info) = parse();
+    let pipeline_options = msl::PipelineOptions {
+        debug_source,
+        ..Default::default()
+    };
+    msl::write_string(&module, &info, &msl::Options::default(), &pipeline_options)
+        .unwrap()
+        .0
+}
+
+#[cfg(hlsl_out)]
+#[test]
+fn hlsl_line_directives() {
+    check_directives(&write_hlsl(debug_source()));
+    assert!(!write_hlsl(None).contains("#line"));
+}
+
+#[cfg(msl_out)]
+#[test]
+fn msl_line_directives() {
+    check_directives(&write_msl(debug_source()));
+    assert!(!write_msl(None).contains("#line"));
+}
@@ -169,6 +169,10 @@ struct Parameters {
     #[cfg(feature = "deserialize")]
     bounds_check_policies: naga::proc::BoundsCheckPolicies,
 
+    // Simplify the module with `naga::proc::optimize` before writing it out.
+    #[cfg(feature = "deserialize")]
+    optimization: naga::proc::OptimizationLevel,
+
     #[cfg(all(feature = "deserialize", any(hlsl_out, msl_out, spv_out, glsl_out)))]
     pipeline_constants: naga::back::PipelineConstants,
 }
@@ -429,6 +433,15 @@ fn check_targets(input: &Input, module: &mut naga::Module, source_code: Option<&
             );
         });
 
+    #[cfg(feature = "deserialize")]
+    naga::proc::optimize(module, params.optimization).unwrap_or_else(|err| {
+        panic!(
+            "Naga module optimization failed on test `{}`:\n{:?}",
+            name.display(),
+            err
+        );
+    });
+
     let info = {
         // Our backends often generate temporary names based on handle indices,
         // which means that adding or removing unused arena entries can affect
@@ -784,6 +797,10 @@ fn write_output_hlsl(
             naga::ShaderStage::Vertex => &mut config.vertex,
             naga::ShaderStage::Fragment => &mut config.fragment,
             naga::ShaderStage::Compute => &mut config.compute,
+            naga::ShaderStage::RayGeneration
+            | naga::ShaderStage::Miss
+            | naga::ShaderStage::ClosestHit
+            | naga::ShaderStage::AnyHit => &mut config.library,
             naga::ShaderStage::Task | naga::ShaderStage::Mesh => unreachable!(),
         }
         .push(hlsl_snapshots::ConfigItem {
@@ -831,7 +848,10 @@ fn convert_snapshots_wgsl() {
         let params = input.read_parameters();
         let WgslInParameters { parse_doc_comments } = params.wgsl_in;
 
-        let options = naga::front::wgsl::Options { parse_doc_comments };
+        let options = naga::front::wgsl::Options {
+            parse_doc_comments,
+            ..naga::front::wgsl::Options::new()
+        };
         let mut frontend = naga::front::wgsl::Frontend::new_with_options(options);
         match frontend.parse(&source) {
             Ok(mut module) => check_targets(&input, &mut module, Some(&source)),
@@ -213,3 +213,39 @@ fn int64() {
 fn float16() {
     require(&[Ca::Float16], "enable f16; fn f(x: f16) { }");
 }
+
+#[test]
+fn int8() {
+    require(
+        &[
+            Ca::Int8,
+            Ca::StorageBuffer8BitAccess,
+            Ca::UniformAndStorageBuffer8BitAccess,
+        ],
+        r#"
+            @group(0) @binding(0) var<storage, read_write> data: array<u8>;
+
+            fn f(i: u32) {
+                data[i] = data[i] + u8(1);
+            }
+        "#,
+    );
+}
+
+#[test]
+fn int16() {
+    require(
+        &[
+            Ca::Int16,
+            Ca::StorageBuffer16BitAccess,
+            Ca::UniformAndStorageBuffer16BitAccess,
+        ],
+        r#"
+            @group(0) @binding(0) var<storage, read_write> data: array<vec2<i16>>;
+
+            fn f(i: u32) {
+                data[i] = vec2<i16>(vec2(i32(i), -1));
+            }
+        "#,
+    );
+}
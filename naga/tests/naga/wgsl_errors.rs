@@ -1139,6 +1139,67 @@ fn float16_in_atomic() {
     }
 }
 
+#[test]
+fn small_int_capability() {
+    check_validation! {
+        "var<storage> input: array<u8>;",
+        "var<storage> input: array<vec4<i8>>;":
+        Err(naga::valid::ValidationError::Type {
+            source: naga::valid::TypeError::WidthError(naga::valid::WidthError::MissingCapability {flag: "SHADER_INT8",..}),
+            ..
+        })
+    }
+
+    check_validation! {
+        "var<storage> input: array<u16>;",
+        "var<storage> input: array<vec2<i16>>;":
+        Err(naga::valid::ValidationError::Type {
+            source: naga::valid::TypeError::WidthError(naga::valid::WidthError::MissingCapability {flag: "SHADER_INT16",..}),
+            ..
+        })
+    }
+}
+
+#[test]
+fn small_int_in_push_constant() {
+    check_validation! {
+        "var<push_constant> input: u8;",
+        "struct S { a: u32, b: u8 }; var<push_constant> input: S;":
+        Err(naga::valid::ValidationError::GlobalVariable {
+            source: naga::valid::GlobalVariableError::InvalidPushConstantType(
+                naga::valid::PushConstantError::InvalidScalar(
+                    naga::Scalar::U8
+                )
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::SHADER_INT8 | naga::valid::Capabilities::PUSH_CONSTANT
+    }
+}
+
+#[test]
+fn float_to_small_int_cast() {
+    check_validation! {
+        "fn f(x: f32) -> u16 { return u16(x); }",
+        "fn f(x: vec2<f32>) -> vec2<i16> { return vec2<i16>(x); }":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::Expression {
+                source: naga::valid::ExpressionError::FloatToSmallIntCast,
+                ..
+            },
+            ..
+        }),
+        naga::valid::Capabilities::SHADER_INT16
+    }
+
+    check_validation! {
+        "fn f(x: f32) -> u16 { return u16(u32(x)); }",
+        "fn f(x: i32) -> vec2<i16> { return vec2<i16>(vec2(x, 1)); }":
+        Ok(_),
+        naga::valid::Capabilities::SHADER_INT16
+    }
+}
+
 #[test]
 fn invalid_arrays() {
     check_validation! {
@@ -1886,6 +1947,419 @@ fn invalid_blend_src() {
     }
 }
 
+#[test]
+fn invalid_framebuffer_fetch() {
+    // Missing capability.
+    check_validation! {
+        "
+        enable wgpu_framebuffer_fetch;
+        @fragment
+        fn main(@color(0) current: vec4<f32>) -> @location(0) vec4<f32> { return current; }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::UnsupportedCapability(Capabilities::FRAMEBUFFER_FETCH),
+                ),
+                ..
+            },
+        )
+    }
+
+    // Missing enable directive.
+    check(
+        "
+        @fragment
+        fn main(@color(0) current: vec4<f32>) -> @location(0) vec4<f32> { return current; }
+        ",
+        r###"error: the `wgpu_framebuffer_fetch` enable extension is not enabled
+  ┌─ wgsl:3:18
+  │
+3 │         fn main(@color(0) current: vec4<f32>) -> @location(0) vec4<f32> { return current; }
+  │                  ^^^^^ the `wgpu_framebuffer_fetch` "Enable Extension" is needed for this functionality, but it is not currently enabled.
+  │
+  = note: You can enable this extension by adding `enable wgpu_framebuffer_fetch;` at the top of the shader, before any other items.
+
+"###,
+    );
+
+    // Reading a color target with a type that isn't a 32-bit scalar or vector.
+    check_validation! {
+        "
+        enable wgpu_framebuffer_fetch;
+        @fragment
+        fn main(@color(0) current: mat2x2<f32>) -> @location(0) vec4<f32> { return vec4(0.0); }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Fragment,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::InvalidBuiltInType(
+                        naga::BuiltIn::FramebufferFetch { location: 0 },
+                    ),
+                ),
+                ..
+            },
+        ),
+        Capabilities::FRAMEBUFFER_FETCH
+    }
+
+    // Reading a color target outside of a fragment shader.
+    check_validation! {
+        "
+        enable wgpu_framebuffer_fetch;
+        @vertex
+        fn main(@color(0) current: vec4<f32>) -> @builtin(position) vec4<f32> { return current; }
+        ":
+        Err(
+            naga::valid::ValidationError::EntryPoint {
+                stage: naga::ShaderStage::Vertex,
+                source: naga::valid::EntryPointError::Argument(
+                    0,
+                    naga::valid::VaryingError::InvalidBuiltInStage(
+                        naga::BuiltIn::FramebufferFetch { location: 0 },
+                    ),
+                ),
+                ..
+            },
+        ),
+        Capabilities::FRAMEBUFFER_FETCH
+    }
+
+    // `@color` can't be combined with other binding attributes.
+    check(
+        "
+        enable wgpu_framebuffer_fetch;
+        @fragment
+        fn main(@location(0) @color(0) current: vec4<f32>) -> @location(0) vec4<f32> { return current; }
+        ",
+        r###"error: input/output binding is not consistent
+  ┌─ wgsl:4:17
+  │
+4 │         fn main(@location(0) @color(0) current: vec4<f32>) -> @location(0) vec4<f32> { return current; }
+  │                 ^^^^^^^^^^^^^^^^^^^^^^ input/output binding is not consistent
+
+"###,
+    );
+}
+
+#[test]
+fn invalid_debug_printf() {
+    // Missing capability.
+    check_validation! {
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("%d", 1);
+        }
+        "#:
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::MissingCapability(Capabilities::DEBUG_PRINTF),
+            ..
+        })
+    }
+
+    // Unsupported conversion specifier.
+    check_validation! {
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("%s", 1);
+        }
+        "#:
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidDebugPrintf(
+                naga::valid::DebugPrintfError::UnsupportedSpecifier(specifier),
+            ),
+            ..
+        })
+        if specifier == "%s",
+        Capabilities::DEBUG_PRINTF
+    }
+
+    // Incomplete conversion specifier.
+    check_validation! {
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("%v3", vec3(1));
+        }
+        "#:
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidDebugPrintf(
+                naga::valid::DebugPrintfError::IncompleteSpecifier(0),
+            ),
+            ..
+        }),
+        Capabilities::DEBUG_PRINTF
+    }
+
+    // Too few arguments.
+    check_validation! {
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("%d and %f", 1);
+        }
+        "#:
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidDebugPrintf(
+                naga::valid::DebugPrintfError::ArgumentCount {
+                    specifiers: 2,
+                    arguments: 1,
+                },
+            ),
+            ..
+        }),
+        Capabilities::DEBUG_PRINTF
+    }
+
+    // Arguments that don't match their specifier.
+    check_validation! {
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("%d", 1.0);
+        }
+        "#,
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("%f", vec2(1.0));
+        }
+        "#,
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("%v2d", vec3(1));
+        }
+        "#:
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidDebugPrintf(
+                naga::valid::DebugPrintfError::MismatchedArgument { .. },
+            ),
+            ..
+        }),
+        Capabilities::DEBUG_PRINTF
+    }
+
+    // Missing enable directive.
+    check(
+        r#"
+        fn main() {
+            debugPrintf("%d", 1);
+        }
+        "#,
+        r###"error: the `wgpu_debug_printf` enable extension is not enabled
+  ┌─ wgsl:3:25
+  │
+3 │             debugPrintf("%d", 1);
+  │                         ^^^^ the `wgpu_debug_printf` "Enable Extension" is needed for this functionality, but it is not currently enabled.
+  │
+  = note: You can enable this extension by adding `enable wgpu_debug_printf;` at the top of the shader, before any other items.
+
+"###,
+    );
+
+    // Unknown escape sequence in the format string.
+    check(
+        r#"
+        enable wgpu_debug_printf;
+        fn main() {
+            debugPrintf("\q");
+        }
+        "#,
+        r###"error: invalid escape sequence in string literal: `\q`
+  ┌─ wgsl:4:26
+  │
+4 │             debugPrintf("\q");
+  │                          ^^ invalid escape sequence
+  │
+  = note: the supported escape sequences are `\"`, `\\`, `\n` and `\t`
+
+"###,
+    );
+}
+
+#[test]
+fn invalid_ray_tracing_pipeline() {
+    // Missing capability.
+    check_validation! {
+        r#"
+        enable wgpu_ray_tracing_pipeline;
+        @ray_generation
+        fn main() {}
+        "#:
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Result(
+                naga::valid::VaryingError::UnsupportedCapability(
+                    Capabilities::RAY_TRACING_PIPELINE,
+                ),
+            ),
+            ..
+        })
+    }
+
+    // Ray tracing stages can't return values.
+    check_validation! {
+        r#"
+        enable wgpu_ray_tracing_pipeline;
+        @miss
+        fn main() -> @builtin(position) vec4f {
+            return vec4f();
+        }
+        "#:
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::UnexpectedRayTracingResult,
+            ..
+        }),
+        Capabilities::RAY_TRACING_PIPELINE
+    }
+
+    // Payloads must be structs.
+    check_validation! {
+        r#"
+        enable wgpu_ray_tracing_pipeline;
+        var<ray_payload> payload: vec4f;
+        "#:
+        Err(naga::valid::ValidationError::GlobalVariable {
+            source: naga::valid::GlobalVariableError::InvalidType(
+                naga::AddressSpace::RayPayload,
+            ),
+            ..
+        }),
+        Capabilities::RAY_TRACING_PIPELINE
+    }
+
+    // Built-ins that aren't visible in the stage.
+    check_validation! {
+        r#"
+        enable wgpu_ray_tracing_pipeline;
+        @ray_generation
+        fn main(@builtin(hit_kind) hit_kind: u32) {}
+        "#:
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Argument(
+                0,
+                naga::valid::VaryingError::InvalidBuiltInStage(naga::BuiltIn::HitKind),
+            ),
+            ..
+        }),
+        Capabilities::RAY_TRACING_PIPELINE
+    }
+
+    // Intersections can only be ignored in any hit shaders, and rays can't be
+    // traced from them.
+    check_validation! {
+        r#"
+        enable wgpu_ray_tracing_pipeline;
+        @closest_hit
+        fn main() {
+            ignoreIntersection();
+        }
+        "#,
+        r#"
+        enable wgpu_ray_tracing_pipeline;
+        struct Payload { hit: u32 }
+        @group(0) @binding(0) var acc_struct: acceleration_structure;
+        var<ray_payload> payload: Payload;
+        @any_hit
+        fn main() {
+            traceRay(acc_struct, RayDesc(), 0u, 1u, 0u, &payload);
+        }
+        "#:
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::ForbiddenStageOperations,
+            ..
+        }),
+        Capabilities::RAY_TRACING_PIPELINE
+    }
+
+    // Rays can only be traced with outgoing payloads.
+    check_validation! {
+        r#"
+        enable wgpu_ray_tracing_pipeline;
+        struct Payload { hit: u32 }
+        @group(0) @binding(0) var acc_struct: acceleration_structure;
+        var<incoming_ray_payload> payload: Payload;
+        @closest_hit
+        fn main() {
+            traceRay(acc_struct, RayDesc(), 0u, 1u, 0u, &payload);
+        }
+        "#:
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::InvalidRayPayload(_),
+            ),
+            ..
+        }),
+        Capabilities::RAY_TRACING_PIPELINE
+    }
+
+    // Missing enable directive.
+    check(
+        r#"
+        @ray_generation
+        fn main() {}
+        "#,
+        r###"error: the `wgpu_ray_tracing_pipeline` enable extension is not enabled
+  ┌─ wgsl:2:10
+  │
+2 │         @ray_generation
+  │          ^^^^^^^^^^^^^^ the `wgpu_ray_tracing_pipeline` "Enable Extension" is needed for this functionality, but it is not currently enabled.
+  │
+  = note: You can enable this extension by adding `enable wgpu_ray_tracing_pipeline;` at the top of the shader, before any other items.
+
+"###,
+    );
+}
+
+#[test]
+fn external_texture_sampling() {
+    check_validation! {
+        r#"
+            @group(0) @binding(0) var tex: texture_external;
+            @group(0) @binding(1) var samp: sampler;
+
+            fn main() -> vec4<f32> {
+                return textureSample(tex, samp, vec2<f32>(0.0));
+            }
+        "#,
+        r#"
+            @group(0) @binding(0) var tex: texture_external;
+            @group(0) @binding(1) var samp: sampler;
+
+            fn main() -> vec4<f32> {
+                return textureSampleLevel(tex, samp, vec2<f32>(0.0), 0.0);
+            }
+        "#:
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::Expression {
+                source: naga::valid::ExpressionError::InvalidImageClass(naga::ImageClass::External),
+                ..
+            },
+            ..
+        })
+    }
+
+    check_validation! {
+        r#"
+            @group(0) @binding(0) var tex: texture_external;
+            @group(0) @binding(1) var samp: sampler;
+
+            fn main() -> vec4<f32> {
+                let size = vec2<f32>(textureDimensions(tex));
+                return textureSampleBaseClampToEdge(tex, samp, vec2<f32>(0.5) / size)
+                    + textureLoad(tex, vec2<i32>(0));
+            }
+        "#:
+        Ok(_)
+    }
+}
+
 #[test]
 fn invalid_access() {
     check_validation! {
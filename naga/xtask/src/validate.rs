@@ -327,8 +327,9 @@ fn push_job_for_each_hlsl_config_item(
         vertex,
         fragment,
         compute,
+        library,
     } = hlsl_snapshots::Config::from_path(path.with_extension("ron"))?;
-    for shader in [vertex, fragment, compute].into_iter().flatten() {
+    for shader in [vertex, fragment, compute, library].into_iter().flatten() {
         // Let each job closure stand on its own.
         let mut validator = validator.clone();
         let path = path.to_owned();
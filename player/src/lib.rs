@@ -54,6 +54,9 @@ impl GlobalPlay for wgc::global::Global {
                 trace::Command::CopyTextureToTexture { src, dst, size } => self
                     .command_encoder_copy_texture_to_texture(encoder, &src, &dst, &size)
                     .unwrap(),
+                trace::Command::ResolveTexture { src, dst, regions } => self
+                    .command_encoder_resolve_texture(encoder, src, dst, &regions)
+                    .unwrap(),
                 trace::Command::ClearBuffer { dst, offset, size } => self
                     .command_encoder_clear_buffer(encoder, dst, offset, size)
                     .unwrap(),
@@ -97,11 +100,13 @@ impl GlobalPlay for wgc::global::Global {
                 trace::Command::RunComputePass {
                     base,
                     timestamp_writes,
+                    pipeline_statistics_query,
                 } => {
                     self.compute_pass_end_with_unresolved_commands(
                         encoder,
                         base,
                         timestamp_writes.as_ref(),
+                        pipeline_statistics_query.as_ref(),
                     );
                 }
                 trace::Command::RunRenderPass {
@@ -110,6 +115,9 @@ impl GlobalPlay for wgc::global::Global {
                     target_depth_stencil,
                     timestamp_writes,
                     occlusion_query_set_id,
+                    pipeline_statistics_query,
+                    flags,
+                    multiview,
                 } => {
                     self.render_pass_end_with_unresolved_commands(
                         encoder,
@@ -118,6 +126,9 @@ impl GlobalPlay for wgc::global::Global {
                         target_depth_stencil.as_ref(),
                         timestamp_writes.as_ref(),
                         occlusion_query_set_id,
+                        pipeline_statistics_query.as_ref(),
+                        flags,
+                        multiview,
                     );
                 }
                 trace::Command::BuildAccelerationStructures { blas, tlas } => {
@@ -172,8 +183,13 @@ impl GlobalPlay for wgc::global::Global {
                 }
             }
         }
-        let (cmd_buf, error) =
-            self.command_encoder_finish(encoder, &wgt::CommandBufferDescriptor { label: None });
+        let (cmd_buf, error) = self.command_encoder_finish(
+            encoder,
+            &wgt::CommandBufferDescriptor {
+                label: None,
+                reusable: false,
+            },
+        );
         if let Some(e) = error {
             panic!("{e}");
         }
@@ -283,7 +299,10 @@ impl GlobalPlay for wgc::global::Global {
                 log::debug!("Creating shader from {}", data);
                 let code = fs::read_to_string(dir.join(&data)).unwrap();
                 let source = if data.ends_with(".wgsl") {
-                    wgc::pipeline::ShaderModuleSource::Wgsl(Cow::Owned(code.clone()))
+                    wgc::pipeline::ShaderModuleSource::Wgsl(
+                        Cow::Owned(code.clone()),
+                        wgc::naga::front::wgsl::Options::new(),
+                    )
                 } else if data.ends_with(".ron") {
                     let module = ron::de::from_str(&code).unwrap();
                     wgc::pipeline::ShaderModuleSource::Naga(module)
@@ -319,6 +338,15 @@ impl GlobalPlay for wgc::global::Global {
             Action::DestroyComputePipeline(id) => {
                 self.compute_pipeline_drop(id);
             }
+            Action::CreateRayTracingPipeline { id, desc } => {
+                let (_, error) = self.device_create_ray_tracing_pipeline(device, &desc, Some(id));
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyRayTracingPipeline(id) => {
+                self.ray_tracing_pipeline_drop(id);
+            }
             Action::CreateRenderPipeline {
                 id,
                 desc,
@@ -337,6 +365,24 @@ impl GlobalPlay for wgc::global::Global {
                     panic!("{e}");
                 }
             }
+            Action::CreateMeshPipeline {
+                id,
+                desc,
+                implicit_context,
+            } => {
+                let implicit_ids =
+                    implicit_context
+                        .as_ref()
+                        .map(|ic| wgc::device::ImplicitPipelineIds {
+                            root_id: ic.root_id,
+                            group_ids: &ic.group_ids,
+                        });
+                let (_, error) =
+                    self.device_create_mesh_pipeline(device, &desc, Some(id), implicit_ids);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
             Action::DestroyRenderPipeline(id) => {
                 self.render_pipeline_drop(id);
             }
@@ -426,3 +472,34 @@ impl GlobalPlay for wgc::global::Global {
         }
     }
 }
+
+/// Read the actions of the [`trace::TraceFile`] recorded in the directory `dir`.
+///
+/// A trace which wasn't finished, because the traced application crashed for example,
+/// is read up to its last recorded action.
+pub fn load_trace(dir: &Path) -> Result<Vec<trace::Action<'static>>, String> {
+    #[derive(serde::Deserialize)]
+    struct Header {
+        version: u32,
+    }
+
+    let path = dir.join(trace::FILE_NAME);
+    let mut string = fs::read_to_string(&path).map_err(|e| format!("{path:?}: {e}"))?;
+    if !string.trim_end().ends_with(')') {
+        string.push_str(trace::FILE_END);
+    }
+    let parse_error =
+        |e: ron::error::SpannedError| format!("{path:?}:{} {}", e.span.start.line, e.code);
+
+    // Check the version first, as the actions of other versions may not parse.
+    let header: Header = ron::de::from_str(&string).map_err(parse_error)?;
+    if header.version != trace::VERSION {
+        return Err(format!(
+            "{path:?} was recorded with version {} of the trace format, but this player reads version {}",
+            header.version,
+            trace::VERSION,
+        ));
+    }
+    let file: trace::TraceFile = ron::de::from_str(&string).map_err(parse_error)?;
+    Ok(file.actions)
+}
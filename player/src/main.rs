@@ -1,5 +1,6 @@
-//! Tester for WebGPU
-//!  It enumerates the available backends on the system,
+//! Player for WebGPU traces, and tester for WebGPU
+//!  Given a trace directory, it replays the trace on the first adapter found.
+//!  Otherwise, it enumerates the available backends on the system,
 //!  and run the tests through them.
 //!
 //!  Test requirements:
@@ -20,6 +21,7 @@ use std::{
     path::{Path, PathBuf},
     slice,
 };
+use wgc::device::trace;
 
 #[derive(serde::Deserialize)]
 struct RawId {
@@ -76,7 +78,7 @@ impl Test<'_> {
         };
         let string = read_to_string(&path).unwrap().replace("Noop", backend_name);
         ron::de::from_str(&string)
-            .unwrap_or_else(|e| panic!("{path:?}:{} {}", e.position.line, e.code))
+            .unwrap_or_else(|e| panic!("{path:?}:{} {}", e.span.start.line, e.code))
     }
 
     fn run(
@@ -96,6 +98,7 @@ impl Test<'_> {
                 required_limits: wgt::Limits::default(),
                 memory_hints: wgt::MemoryHints::default(),
                 trace: wgt::Trace::Off,
+                toggles: wgt::TogglesDescriptor::default(),
             },
             Some(device_id),
             Some(queue_id),
@@ -242,6 +245,59 @@ impl Corpus {
     }
 }
 
+/// Replay the trace recorded in `dir` on the first adapter of the backends selected
+/// by `WGPU_BACKEND`, whichever backend it was recorded on.
+fn replay(dir: &Path) {
+    println!("Trace {dir:?}");
+    let mut actions = player::load_trace(dir)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .into_iter();
+    let desc = match actions.next() {
+        Some(trace::Action::Init { desc, backend }) => {
+            println!("\tRecorded on {backend:?}");
+            desc
+        }
+        _ => panic!("The trace doesn't start with an `Init` action"),
+    };
+
+    let instance_desc = wgt::InstanceDescriptor::from_env_or_default();
+    let global = wgc::global::Global::new("player", &instance_desc);
+    let adapter = global
+        .request_adapter(
+            &wgc::instance::RequestAdapterOptions {
+                power_preference: wgt::PowerPreference::from_env().unwrap_or_default(),
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            },
+            instance_desc.backends,
+            Some(wgc::id::Id::zip(0, 1)),
+        )
+        .unwrap_or_else(|e| panic!("{e}"));
+    let info = global.adapter_get_info(adapter);
+    println!("\tReplaying on {} ({:?})", info.name, info.backend);
+
+    let device_id = wgc::id::Id::zip(0, 1);
+    let queue_id = wgc::id::Id::zip(0, 1);
+    if let Err(e) = global.adapter_request_device(adapter, &desc, Some(device_id), Some(queue_id)) {
+        panic!("{e:?}");
+    }
+
+    let mut command_buffer_id_manager = wgc::identity::IdentityManager::new();
+    for action in actions {
+        global.process(
+            device_id,
+            queue_id,
+            action,
+            dir,
+            &mut command_buffer_id_manager,
+        );
+    }
+    global
+        .device_poll(device_id, wgt::PollType::wait())
+        .unwrap();
+    println!("\tDone");
+}
+
 fn main() {
     env_logger::init();
 
@@ -252,7 +308,11 @@ fn main() {
         PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/data/all.ron")
     };
 
-    Corpus::run_from(path);
+    if path.join(trace::FILE_NAME).is_file() {
+        replay(&path);
+    } else {
+        Corpus::run_from(path);
+    }
 }
 
 #[test]
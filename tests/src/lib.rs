@@ -10,6 +10,7 @@ mod isolation;
 pub mod native;
 mod params;
 mod poll;
+mod render_target;
 mod report;
 mod run;
 
@@ -23,6 +24,10 @@ pub use ctor;
 pub use expectations::{FailureApplicationReasons, FailureBehavior, FailureCase, FailureReason};
 pub use init::{initialize_adapter, initialize_device, initialize_instance};
 pub use params::TestParameters;
+pub use render_target::{
+    RenderTarget, color_attachment, create_layered_render_target, create_render_target,
+    run_render_pass, run_render_pass_with,
+};
 pub use run::{TestingContext, execute_test};
 pub use wgpu_macros::gpu_test;
 
@@ -0,0 +1,128 @@
+//! Render targets and render passes for validation tests.
+
+/// A 2D texture to render to.
+///
+/// The default is a single 64x64 [`wgpu::TextureFormat::Rgba8Unorm`] layer without
+/// multisampling.
+#[derive(Clone, Copy, Debug)]
+pub struct RenderTarget {
+    pub format: wgpu::TextureFormat,
+    pub size: u32,
+    pub layers: u32,
+    pub sample_count: u32,
+    /// Usages of the texture in addition to [`wgpu::TextureUsages::RENDER_ATTACHMENT`].
+    pub usage: wgpu::TextureUsages,
+}
+
+impl Default for RenderTarget {
+    fn default() -> Self {
+        Self::new(wgpu::TextureFormat::Rgba8Unorm, 1)
+    }
+}
+
+impl RenderTarget {
+    /// Returns a single 64x64 layer of `format` with `sample_count` samples.
+    pub fn new(format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        Self {
+            format,
+            size: 64,
+            layers: 1,
+            sample_count,
+            usage: wgpu::TextureUsages::empty(),
+        }
+    }
+
+    /// Creates the texture and returns a view of all of it.
+    ///
+    /// Textures with more than one array layer are viewed as a
+    /// [`wgpu::TextureViewDimension::D2Array`].
+    pub fn create_view(&self, device: &wgpu::Device) -> wgpu::TextureView {
+        let dimension = (self.layers > 1).then_some(wgpu::TextureViewDimension::D2Array);
+        self.create_texture(device)
+            .create_view(&wgpu::TextureViewDescriptor {
+                dimension,
+                ..Default::default()
+            })
+    }
+
+    /// Creates the texture and returns a [`wgpu::TextureViewDimension::D2Array`] view of it,
+    /// even if it only has one array layer.
+    pub fn create_array_view(&self, device: &wgpu::Device) -> wgpu::TextureView {
+        self.create_texture(device)
+            .create_view(&wgpu::TextureViewDescriptor {
+                dimension: Some(wgpu::TextureViewDimension::D2Array),
+                ..Default::default()
+            })
+    }
+
+    fn create_texture(&self, device: &wgpu::Device) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("target"),
+            size: wgpu::Extent3d {
+                width: self.size,
+                height: self.size,
+                depth_or_array_layers: self.layers,
+            },
+            mip_level_count: 1,
+            sample_count: self.sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: self.usage | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+    }
+}
+
+/// Creates a view of the default [`RenderTarget`].
+pub fn create_render_target(device: &wgpu::Device) -> wgpu::TextureView {
+    RenderTarget::default().create_view(device)
+}
+
+/// Creates a [`wgpu::TextureViewDimension::D2Array`] view of the default [`RenderTarget`]
+/// with `layers` array layers.
+pub fn create_layered_render_target(device: &wgpu::Device, layers: u32) -> wgpu::TextureView {
+    RenderTarget {
+        layers,
+        ..Default::default()
+    }
+    .create_array_view(device)
+}
+
+/// Returns a color attachment of `view` with the default operations.
+pub fn color_attachment(view: &wgpu::TextureView) -> Option<wgpu::RenderPassColorAttachment<'_>> {
+    Some(wgpu::RenderPassColorAttachment {
+        view,
+        depth_slice: None,
+        resolve_target: None,
+        ops: wgpu::Operations::default(),
+    })
+}
+
+/// Records a render pass to `view`, calling `f` to encode its commands.
+pub fn run_render_pass(
+    device: &wgpu::Device,
+    view: &wgpu::TextureView,
+    f: impl FnOnce(&mut wgpu::RenderPass<'_>),
+) -> wgpu::CommandBuffer {
+    run_render_pass_with(
+        device,
+        &wgpu::RenderPassDescriptor {
+            color_attachments: &[color_attachment(view)],
+            ..Default::default()
+        },
+        f,
+    )
+}
+
+/// Records a render pass described by `desc`, calling `f` to encode its commands.
+pub fn run_render_pass_with(
+    device: &wgpu::Device,
+    desc: &wgpu::RenderPassDescriptor<'_>,
+    f: impl FnOnce(&mut wgpu::RenderPass<'_>),
+) -> wgpu::CommandBuffer {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    let mut pass = encoder.begin_render_pass(desc);
+    f(&mut pass);
+    drop(pass);
+    encoder.finish()
+}
@@ -109,6 +109,7 @@ static BGRA8_UNORM_STORAGE: GpuTestConfiguration = GpuTestConfiguration::new()
             let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: None,
                 timestamp_writes: None,
+                pipeline_statistics_query: None,
             });
 
             pass.set_bind_group(0, &bg, &[]);
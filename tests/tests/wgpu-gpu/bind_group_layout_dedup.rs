@@ -90,6 +90,7 @@ async fn bgl_dedupe(ctx: TestingContext) {
     let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     pass.set_bind_group(0, &bg_1b, &[]);
@@ -177,6 +178,7 @@ fn bgl_dedupe_with_dropped_user_handle(ctx: TestingContext) {
     let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     pass.set_bind_group(0, &bg, &[]);
@@ -246,6 +248,7 @@ fn get_derived_bgl(ctx: TestingContext) {
     let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     pass.set_pipeline(&pipeline);
@@ -308,6 +311,7 @@ fn separate_pipelines_have_incompatible_derived_bgls(ctx: TestingContext) {
     let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     pass.set_pipeline(&pipeline1);
@@ -381,6 +385,7 @@ fn derived_bgls_incompatible_with_regular_bgls(ctx: TestingContext) {
     let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     pass.set_pipeline(&pipeline);
@@ -474,6 +479,7 @@ fn bgl_dedupe_derived(ctx: TestingContext) {
     let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
     pass.set_pipeline(&pipeline);
     pass.set_bind_group(0, &bind_group_0, &[]);
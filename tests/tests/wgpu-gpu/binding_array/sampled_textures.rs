@@ -192,6 +192,7 @@ async fn binding_array_sampled_textures(ctx: TestingContext, partially_bound: bo
                     format: TextureFormat::Rgba8UnormSrgb,
                     blend: None,
                     write_mask: ColorWrites::ALL,
+                    logic_op: None,
                 })],
                 compilation_options: PipelineCompilationOptions::default(),
             }),
@@ -220,6 +221,9 @@ async fn binding_array_sampled_textures(ctx: TestingContext, partially_bound: bo
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: RenderPassFlags::empty(),
+            multiview: None,
         });
         render_pass.set_pipeline(&pipeline);
         render_pass.set_bind_group(0, &bind_group, &[]);
@@ -188,6 +188,7 @@ async fn binding_array_storage_textures(ctx: TestingContext, partially_bound: bo
         let mut render_pass = encoder.begin_compute_pass(&ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         render_pass.set_pipeline(&pipeline);
         render_pass.set_bind_group(0, &bind_group, &[]);
@@ -316,6 +316,7 @@ static MINIMUM_BUFFER_BINDING_SIZE_DISPATCH: GpuTestConfiguration = GpuTestConfi
                 let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                     label: None,
                     timestamp_writes: None,
+                    pipeline_statistics_query: None,
                 });
 
                 pass.set_bind_group(0, &bind_group, &[]);
@@ -385,3 +386,26 @@ static CLEAR_OFFSET_PLUS_SIZE_OUTSIDE_U64_BOUNDS: GpuTestConfiguration =
                 )),
             );
         });
+
+#[gpu_test]
+static DOWNLOAD_BUFFER_READ_ASYNC: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let data: Vec<u8> = (0..64).collect();
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: data.len() as u64,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        ctx.queue.write_buffer(&buffer, 0, &data);
+
+        let download = wgpu::util::DownloadBuffer::read_buffer_async(
+            &ctx.device,
+            &ctx.queue,
+            &buffer.slice(16..),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(&*download, &data[16..]);
+    });
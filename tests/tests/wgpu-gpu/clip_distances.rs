@@ -35,6 +35,7 @@ async fn clip_distances(ctx: TestingContext) {
                     format: wgpu::TextureFormat::R8Unorm,
                     blend: None,
                     write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
                 })],
             }),
             multiview: None,
@@ -81,6 +82,9 @@ async fn clip_distances(ctx: TestingContext) {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
         rpass.set_pipeline(&pipeline);
         rpass.draw(0..3, 0..1);
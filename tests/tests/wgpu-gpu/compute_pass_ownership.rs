@@ -61,11 +61,10 @@ async fn compute_pass_resource_ownership(ctx: TestingContext) {
 #[gpu_test]
 static COMPUTE_PASS_QUERY_SET_OWNERSHIP_PIPELINE_STATISTICS: GpuTestConfiguration =
     GpuTestConfiguration::new()
-        .parameters(
-            TestParameters::default()
-                .test_features_limits()
-                .features(wgpu::Features::PIPELINE_STATISTICS_QUERY),
-        )
+        .parameters(TestParameters::default().test_features_limits().features(
+            wgpu::Features::PIPELINE_STATISTICS_QUERY
+                | wgpu::Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES,
+        ))
         .run_async(compute_pass_query_set_ownership_pipeline_statistics);
 
 async fn compute_pass_query_set_ownership_pipeline_statistics(ctx: TestingContext) {
@@ -147,6 +146,7 @@ async fn compute_pass_query_set_ownership_timestamps(ctx: TestingContext) {
                 beginning_of_pass_write_index: Some(0),
                 end_of_pass_write_index: Some(1),
             }),
+            pipeline_statistics_query: None,
         });
         cpass.set_pipeline(&pipeline);
         cpass.set_bind_group(0, &bind_group, &[]);
@@ -184,6 +184,7 @@ async fn compute_pass_keep_encoder_alive(ctx: TestingContext) {
     let cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: Some("compute_pass"),
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     // Now drop the encoder - it is kept alive by the compute pass.
@@ -301,6 +301,7 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
                 beginning_of_pass_write_index: None,
                 end_of_pass_write_index: None,
             }),
+            pipeline_statistics_query: None,
         });
         drop(pass);
         ctx.queue.submit([encoder_for_compute_pass.finish()]);
@@ -316,6 +317,9 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
         drop(pass);
         ctx.queue.submit([encoder_for_render_pass.finish()]);
@@ -572,6 +576,7 @@ static DIFFERENT_BGL_ORDER_BW_SHADER_AND_API: GpuTestConfiguration = GpuTestConf
                         format: wgpu::TextureFormat::Bgra8Unorm,
                         blend: None,
                         write_mask: wgpu::ColorWrites::ALL,
+                        logic_op: None,
                     })],
                 }),
                 layout: None,
@@ -179,6 +179,7 @@ async fn run_test(ctx: TestingContext, test_data: TestData, expect_noop: bool) {
                 format: wgpu::TextureFormat::R8Unorm,
                 blend: None,
                 write_mask: wgpu::ColorWrites::ALL,
+                logic_op: None,
             })],
         }),
         multiview: None,
@@ -246,6 +247,9 @@ async fn run_test(ctx: TestingContext, test_data: TestData, expect_noop: bool) {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
 
         rpass.set_pipeline(&pipeline);
@@ -661,6 +665,7 @@ async fn indirect_buffer_offsets(ctx: TestingContext) {
                 format: wgpu::TextureFormat::R8Unorm,
                 blend: None,
                 write_mask: wgpu::ColorWrites::ALL,
+                logic_op: None,
             })],
         }),
         multiview: None,
@@ -713,6 +718,9 @@ async fn indirect_buffer_offsets(ctx: TestingContext) {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
 
         rpass.set_pipeline(&pipeline);
@@ -758,3 +766,166 @@ async fn indirect_buffer_offsets(ctx: TestingContext) {
         data[..half].iter().all(|b| *b == u8::MAX) && data[half..].iter().all(|b| *b == 0);
     assert!(succeeded);
 }
+
+#[gpu_test]
+static MULTI_DRAW_INDIRECT_COUNT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION)
+            .features(wgpu::Features::MULTI_DRAW_INDIRECT_COUNT)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(multi_draw_indirect_count);
+
+/// Tests that only the draws below the count are issued,
+/// regardless of whether the backend supports indirect count draws natively.
+async fn multi_draw_indirect_count(ctx: TestingContext) {
+    let indirect_args = [
+        // 1st draw: left half
+        6, 1, 0, 0, //
+        // 2nd draw: right half, past the count
+        6, 1, 6, 0,
+    ];
+
+    let shader_src = "
+        @vertex
+        fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+            var positions = array<vec2f, 6>(
+                vec2f(-1.0, -1.0), vec2f(0.0, -1.0), vec2f(0.0, 1.0),
+                vec2f(-1.0, -1.0), vec2f(0.0, 1.0), vec2f(-1.0, 1.0),
+            );
+            let offset = select(0.0, 1.0, index >= 6u);
+            return vec4f(positions[index % 6u] + vec2f(offset, 0.0), 0.0, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4f {
+            return vec4f(1.0);
+        }
+    ";
+
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(shader_src.into()),
+        });
+
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[],
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::TextureFormat::R8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+    let out_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 256,
+            height: 256,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let out_texture_view = out_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256 * 256,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let indirect_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice::<u32, u8>(&indirect_args),
+        usage: wgpu::BufferUsages::INDIRECT,
+    });
+    let count_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice::<u32, u8>(&[1]),
+        usage: wgpu::BufferUsages::INDIRECT,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations::default(),
+                resolve_target: None,
+                view: &out_texture_view,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
+        });
+
+        rpass.set_pipeline(&pipeline);
+        rpass.multi_draw_indirect_count(&indirect_buffer, 0, &count_buffer, 0, 2);
+    }
+
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &out_texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(256),
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            width: 256,
+            height: 256,
+            depth_or_array_layers: 1,
+        },
+    );
+
+    ctx.queue.submit([encoder.finish()]);
+
+    let slice = readback_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+
+    let data = slice.get_mapped_range();
+    let succeeded = data
+        .chunks(256)
+        .all(|row| row[..128].iter().all(|b| *b == u8::MAX) && row[128..].iter().all(|b| *b == 0));
+    assert!(succeeded);
+}
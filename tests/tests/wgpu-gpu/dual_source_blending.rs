@@ -75,6 +75,7 @@ async fn dual_source_blending_disabled(ctx: TestingContext) {
                             format: wgpu::TextureFormat::Rgba8Unorm,
                             blend: Some(blend_state_with_dual_source_blending()),
                             write_mask: wgpu::ColorWrites::all(),
+                            logic_op: None,
                         })],
                         compilation_options: Default::default(),
                     }),
@@ -92,7 +93,7 @@ async fn dual_source_blending_disabled(ctx: TestingContext) {
                 });
         },
         Some(
-            "Features Features { features_wgpu: FeaturesWGPU(0x0), features_webgpu: FeaturesWebGPU(DUAL_SOURCE_BLENDING) } are required but not enabled on the device",
+            "Features Features { features_wgpu: FeaturesWGPU(0x0), features_webgpu: FeaturesWebGPU(DUAL_SOURCE_BLENDING), features_wgpu2: FeaturesWGPU2(0x0) } are required but not enabled on the device",
         ),
     );
 
@@ -165,6 +166,7 @@ async fn dual_source_blending_enabled(ctx: TestingContext) {
                     format: wgpu::TextureFormat::Rgba8Unorm,
                     blend: Some(blend_state_with_dual_source_blending()),
                     write_mask: wgpu::ColorWrites::all(),
+                    logic_op: None,
                 })],
                 compilation_options: Default::default(),
             }),
@@ -187,6 +189,7 @@ async fn dual_source_blending_enabled(ctx: TestingContext) {
                             format: wgpu::TextureFormat::Rgba8Unorm,
                             blend: None,
                             write_mask: wgpu::ColorWrites::all(),
+                            logic_op: None,
                         })],
                         compilation_options: Default::default(),
                     }),
@@ -212,6 +215,7 @@ async fn dual_source_blending_enabled(ctx: TestingContext) {
                             format: wgpu::TextureFormat::Rgba8Unorm,
                             blend: Some(blend_state_with_dual_source_blending()),
                             write_mask: wgpu::ColorWrites::all(),
+                            logic_op: None,
                         })],
                         compilation_options: Default::default(),
                     }),
@@ -221,3 +225,139 @@ async fn dual_source_blending_enabled(ctx: TestingContext) {
         Some("Pipeline expects the shader entry point to make use of dual-source blending."),
     );
 }
+
+const SHADER_DUAL_SOURCE_BLENDING_OUTPUT: &str = r#"
+enable dual_source_blending;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4f {
+    var positions = array(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0),
+    );
+    return vec4f(positions[vertex_index], 0.0, 1.0);
+}
+
+struct FragmentOutput {
+    @location(0) @blend_src(0) color: vec4f,
+    @location(0) @blend_src(1) blend: vec4f,
+}
+
+@fragment
+fn fs_main() -> FragmentOutput {
+    return FragmentOutput(vec4f(1.0, 1.0, 0.0, 1.0), vec4f(1.0, 0.0, 0.0, 0.0));
+}
+"#;
+
+#[gpu_test]
+static DUAL_SOURCE_BLENDING_OUTPUT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::DUAL_SOURCE_BLENDING))
+    .run_async(dual_source_blending_output);
+
+/// Draws over a cleared target, using the second blend source as the factor of the
+/// first one and one minus it as the factor of the target.
+async fn dual_source_blending_output(ctx: TestingContext) {
+    const SIZE: u32 = 4;
+
+    let shader = ctx.device.create_shader_module(ShaderModuleDescriptor {
+        label: Some("shader"),
+        source: ShaderSource::Wgsl(SHADER_DUAL_SOURCE_BLENDING_OUTPUT.into()),
+    });
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("render_pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: None,
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: None,
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    blend: Some(wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::Src1,
+                            dst_factor: wgpu::BlendFactor::OneMinusSrc1,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::Src1Alpha,
+                            dst_factor: wgpu::BlendFactor::OneMinusSrc1Alpha,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                    }),
+                    write_mask: wgpu::ColorWrites::all(),
+                    logic_op: None,
+                })],
+                compilation_options: Default::default(),
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+    let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("target"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let readback_buffers = wgpu_test::image::ReadbackBuffers::new(&ctx.device, &texture);
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: 0.0,
+                        g: 0.0,
+                        b: 1.0,
+                        a: 1.0,
+                    }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
+        });
+        rpass.set_pipeline(&pipeline);
+        rpass.draw(0..3, 0..1);
+    }
+    readback_buffers.copy_from(&ctx.device, &mut encoder, &texture);
+    ctx.queue.submit([encoder.finish()]);
+
+    // Red comes from the first blend source, and the other channels from the target. Using
+    // the first blend source as the factor would make every channel 0xFF.
+    let expected = [0xFF, 0x00, 0xFF, 0xFF].repeat((SIZE * SIZE) as usize);
+    readback_buffers
+        .assert_buffer_contents(&ctx, &expected)
+        .await;
+}
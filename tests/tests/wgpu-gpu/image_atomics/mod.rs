@@ -137,6 +137,7 @@ async fn test_format(
     let mut rpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: None,
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
     rpass.set_pipeline(&pipeline);
     rpass.set_bind_group(0, Some(&bind_group), &[]);
@@ -0,0 +1,86 @@
+use wgpu_test::{GpuTestConfiguration, gpu_test};
+
+#[gpu_test]
+static IMAGE_COPIER_FLIP_AND_PREMULTIPLY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        // A 3x2 image, of which the right 2x2 texels are copied.
+        #[rustfmt::skip]
+        let image: [u8; 24] = [
+            0, 0, 0, 0,    255, 0, 0, 255,   0, 255, 0, 128,
+            0, 0, 0, 0,    0, 0, 255, 0,     255, 255, 255, 64,
+        ];
+        let size = wgpu::Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+
+        let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        let copier = wgpu::util::ImageCopier::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm);
+        copier.copy_image_to_texture(
+            &ctx.device,
+            &ctx.queue,
+            &wgpu::util::CopyImageSourceInfo {
+                data: &image,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(12),
+                    rows_per_image: None,
+                },
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                color_space: wgpu::PredefinedColorSpace::Srgb,
+                premultiplied_alpha: false,
+                origin: wgpu::Origin2d { x: 1, y: 0 },
+                flip_y: true,
+            },
+            wgpu::CopyExternalImageDestInfo {
+                texture: &target,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x: 1, y: 1, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+                color_space: wgpu::PredefinedColorSpace::Srgb,
+                premultiplied_alpha: true,
+            },
+            size,
+        );
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        wgpu::util::read_texture_to_vec(
+            &ctx.device,
+            &ctx.queue,
+            &target,
+            0,
+            wgpu::Origin3d { x: 1, y: 1, z: 0 },
+            size,
+            move |result| sender.send(result).unwrap(),
+        );
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let result = receiver.recv().unwrap().unwrap();
+
+        // The rows are swapped, and the colors multiplied by alpha.
+        #[rustfmt::skip]
+        let expected: [u8; 16] = [
+            0, 0, 0, 0,      64, 64, 64, 64,
+            255, 0, 0, 255,  0, 128, 0, 128,
+        ];
+        for (i, (&texel, &expected)) in result.iter().zip(&expected).enumerate() {
+            assert!(
+                texel.abs_diff(expected) <= 1,
+                "byte {i} is {texel}, expected {expected}"
+            );
+        }
+    });
@@ -0,0 +1,166 @@
+use wgpu::util::DeviceExt as _;
+use wgpu_test::{GpuTestConfiguration, TestParameters, TestingContext, gpu_test};
+
+const SIZE: u32 = 64;
+
+#[gpu_test]
+static INDEX_FORMAT_UINT8: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::INDEX_FORMAT_UINT8))
+    .run_async(index_format_uint8);
+
+/// Draws a strip of two triangles in the bottom corners, split by a restart index, from
+/// an 8 bit index buffer bound at an odd offset. Without the restart, the strip would
+/// also cover the middle of the target.
+async fn index_format_uint8(ctx: TestingContext) {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+        });
+    let indices: [u8; 8] = [0xAA, 0, 1, 2, 0xFF, 3, 4, 5];
+    let index_buffer = ctx
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &indices,
+            usage: wgpu::BufferUsages::INDEX,
+        });
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[],
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                strip_index_format: Some(wgpu::IndexFormat::Uint8),
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::R8Unorm,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
+                })],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+    let render_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Render Texture"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                resolve_target: None,
+                view: &render_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
+        });
+        rpass.set_pipeline(&pipeline);
+        rpass.set_index_buffer(index_buffer.slice(1..), wgpu::IndexFormat::Uint8);
+        rpass.draw_indexed(0..7, 0, 0..1);
+    }
+
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256 * SIZE as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &render_texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(256),
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+    );
+    ctx.queue.submit([encoder.finish()]);
+    let slice = readback_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+    let data: &[u8] = &slice.get_mapped_range();
+    let texel = |x: usize, y: usize| data[x + y * 256];
+
+    assert_eq!(texel(2, 61), 0xFF);
+    assert_eq!(texel(61, 61), 0xFF);
+    assert_eq!(texel(32, 32), 0x00);
+}
+
+const SHADER_SRC: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4f {
+    var positions = array(
+        vec2f(-1.0, -1.0),
+        vec2f(-0.5, -1.0),
+        vec2f(-1.0, -0.5),
+        vec2f(1.0, -1.0),
+        vec2f(0.5, -1.0),
+        vec2f(1.0, -0.5),
+    );
+    // Vertex 255 is only drawn if the strip doesn't restart.
+    var position = vec2f(0.0, 1.0);
+    if vertex_index < 6u {
+        position = positions[vertex_index];
+    }
+    return vec4f(position, 0.5, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4f {
+    return vec4f(1.0);
+}
+";
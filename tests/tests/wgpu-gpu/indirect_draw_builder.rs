@@ -0,0 +1,103 @@
+use wgpu::util::{BufferInitDescriptor, DeviceExt, DrawIndexedIndirectArgs, IndirectDrawCommand};
+use wgpu_test::{GpuTestConfiguration, TestParameters, TestingContext, gpu_test};
+
+const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+fn command(
+    first_index: u32,
+    instance_count: u32,
+    center: [f32; 3],
+    radius: f32,
+) -> IndirectDrawCommand {
+    IndirectDrawCommand {
+        args: DrawIndexedIndirectArgs {
+            index_count: 3,
+            instance_count,
+            first_index,
+            base_vertex: 0,
+            first_instance: 0,
+        },
+        center,
+        radius,
+    }
+}
+
+async fn build_draws(ctx: TestingContext) {
+    let commands = [
+        // Inside the frustum.
+        command(0, 1, [0.0, 0.0, 0.5], 0.1),
+        // Outside the frustum.
+        command(3, 1, [5.0, 0.0, 0.5], 0.1),
+        // Outside the frustum, but never culled.
+        command(6, 1, [5.0, 0.0, 0.5], -1.0),
+        // Intersecting the frustum.
+        command(9, 2, [1.05, 0.0, 0.5], 0.1),
+        // Inside the frustum, but without instances.
+        command(12, 0, [0.0, 0.0, 0.5], 0.1),
+    ];
+    let commands_buffer = ctx.device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice(&commands),
+        usage: wgpu::BufferUsages::STORAGE,
+    });
+
+    let builder = wgpu::util::IndirectDrawBuilder::new(&ctx.device);
+    let draws = wgpu::util::IndirectDraws::new(&ctx.device, commands.len() as u32);
+
+    let args_size = draws.args_buffer().size();
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4 + args_size,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    builder.build(
+        &ctx.device,
+        &mut encoder,
+        &commands_buffer,
+        commands.len() as u32,
+        IDENTITY,
+        &draws,
+    );
+    encoder.copy_buffer_to_buffer(draws.count_buffer(), 0, &readback_buffer, 0, 4);
+    encoder.copy_buffer_to_buffer(draws.args_buffer(), 0, &readback_buffer, 4, args_size);
+    ctx.queue.submit([encoder.finish()]);
+
+    let slice = readback_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+
+    let data = slice.get_mapped_range();
+    let words: &[u32] = bytemuck::cast_slice(&data);
+    let count = words[0] as usize;
+    assert_eq!(count, 3);
+
+    // The order of the draws isn't preserved.
+    let mut drawn = words[1..]
+        .chunks(5)
+        .take(count)
+        .map(|args| (args[2], args[1]))
+        .collect::<Vec<_>>();
+    drawn.sort();
+    assert_eq!(drawn, [(0, 1), (6, 1), (9, 2)]);
+}
+
+#[gpu_test]
+static INDIRECT_DRAW_BUILDER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(
+                wgpu::DownlevelFlags::COMPUTE_SHADERS | wgpu::DownlevelFlags::INDIRECT_EXECUTION,
+            )
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(build_draws);
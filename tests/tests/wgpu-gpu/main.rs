@@ -31,9 +31,13 @@ mod encoder;
 mod external_texture;
 mod float32_filterable;
 mod image_atomics;
+mod image_copy;
+mod index_format_uint8;
+mod indirect_draw_builder;
 mod instance;
 mod life_cycle;
 mod mem_leaks;
+mod multi_viewport;
 mod nv12_texture;
 mod occlusion_query;
 mod oob_indexing;
@@ -46,6 +50,7 @@ mod query_set;
 mod queue_transfer;
 mod ray_tracing;
 mod render_pass_ownership;
+mod render_pass_suspend;
 mod render_target;
 mod resource_descriptor_accessor;
 mod resource_error;
@@ -65,7 +70,10 @@ mod transfer;
 mod transition_resources;
 mod vertex_formats;
 mod vertex_indices;
+mod wide_lines;
 mod write_texture;
+mod yuv_converter;
+mod yuv_sampling;
 mod zero_init_texture_after_discard;
 
 wgpu_test::gpu_test_main!();
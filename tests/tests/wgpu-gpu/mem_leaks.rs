@@ -110,6 +110,7 @@ async fn draw_test_with_reports(
                     format: wgpu::TextureFormat::Rgba8Unorm,
                     blend: None,
                     write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
                 })],
             }),
             multiview: None,
@@ -193,6 +194,9 @@ async fn draw_test_with_reports(
         depth_stencil_attachment: None,
         timestamp_writes: None,
         occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        flags: wgpu::RenderPassFlags::empty(),
+        multiview: None,
     });
 
     rpass.set_pipeline(&pipeline);
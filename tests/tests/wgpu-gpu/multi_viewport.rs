@@ -0,0 +1,161 @@
+use wgpu_test::{GpuTestConfiguration, TestParameters, TestingContext, gpu_test};
+
+const SIZE: u32 = 64;
+const LAYERS: u32 = 2;
+
+#[gpu_test]
+static MULTI_VIEWPORT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::MULTI_VIEWPORT))
+    .run_async(multi_viewport);
+
+/// Draws a full screen triangle that the vertex shader sends to the second viewport,
+/// covering the right half of the target, and to the second array layer.
+async fn multi_viewport(ctx: TestingContext) {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+        });
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[],
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::TextureFormat::R8Unorm.into())],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+    let render_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Render Texture"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: LAYERS,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let render_view = render_texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(wgpu::TextureViewDimension::D2Array),
+        ..Default::default()
+    });
+
+    let half = SIZE as f32 / 2.0;
+    let viewports = [0.0, half].map(|x| wgpu::Viewport {
+        x,
+        y: 0.0,
+        width: half,
+        height: SIZE as f32,
+        min_depth: 0.0,
+        max_depth: 1.0,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                resolve_target: None,
+                view: &render_view,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
+        });
+        rpass.set_pipeline(&pipeline);
+        rpass.set_viewports(&viewports);
+        rpass.draw(0..3, 0..1);
+    }
+
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256 * (SIZE * LAYERS) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &render_texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(256),
+                rows_per_image: Some(SIZE),
+            },
+        },
+        wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: LAYERS,
+        },
+    );
+    ctx.queue.submit([encoder.finish()]);
+    let slice = readback_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+    let data: &[u8] = &slice.get_mapped_range();
+    let texel = |layer: u32, x: u32, y: u32| data[(x + (y + layer * SIZE) * 256) as usize];
+
+    assert_eq!(texel(0, 16, 32), 0x00);
+    assert_eq!(texel(0, 48, 32), 0x00);
+    assert_eq!(texel(1, 16, 32), 0x00);
+    assert_eq!(texel(1, 48, 32), 0xFF);
+}
+
+const SHADER_SRC: &str = "
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @builtin(viewport_index) viewport: u32,
+    @builtin(layer) layer: u32,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var positions = array(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0),
+    );
+    return VertexOutput(vec4f(positions[vertex_index], 0.5, 1.0), 1u, 1u);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4f {
+    return vec4f(1.0);
+}
+";
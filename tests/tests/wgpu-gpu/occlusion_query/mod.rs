@@ -77,9 +77,13 @@ static OCCLUSION_QUERY: GpuTestConfiguration = GpuTestConfiguration::new()
                         store: wgpu::StoreOp::Store,
                     }),
                     stencil_ops: None,
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: Some(&query_set),
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             render_pass.set_pipeline(&pipeline);
 
@@ -162,6 +162,7 @@ static RENDER_PIPELINE_DEFAULT_LAYOUT_BAD_BGL_INDEX: GpuTestConfiguration =
                                         format: wgpu::TextureFormat::Rgba8Unorm,
                                         blend: None,
                                         write_mask: wgpu::ColorWrites::ALL,
+                                        logic_op: None,
                                     })],
                                 }),
                                 multiview: None,
@@ -165,6 +165,7 @@ async fn validate_pipeline(
         let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: Some("compute_pass"),
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         cpass.set_pipeline(&pipeline);
         cpass.set_bind_group(0, Some(bind_group), &[]);
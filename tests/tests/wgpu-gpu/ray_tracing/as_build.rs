@@ -167,6 +167,7 @@ fn blas_compaction(ctx: TestingContext) {
     recv.recv().unwrap();
     // This should return true because the callback has been called, and we haven't rebuilt the BLAS
     assert!(as_ctx.blas.ready_for_compaction());
+    assert!(as_ctx.blas.compacted_size().is_some());
 
     let compacted = ctx.queue.compact_blas(&as_ctx.blas);
 
@@ -360,6 +361,7 @@ fn out_of_order_as_build_use(ctx: TestingContext) {
         let mut pass = encoder_compute.begin_compute_pass(&ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         pass.set_pipeline(&compute_pipeline);
         pass.set_bind_group(0, Some(&bind_group), &[]);
@@ -434,6 +436,7 @@ fn out_of_order_as_build_use(ctx: TestingContext) {
         let mut pass = encoder_compute.begin_compute_pass(&ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         pass.set_pipeline(&compute_pipeline);
         pass.set_bind_group(0, Some(&bind_group), &[]);
@@ -669,6 +672,7 @@ fn only_blas_vertex_return(ctx: TestingContext) {
             let mut pass = encoder_compute.begin_compute_pass(&ComputePassDescriptor {
                 label: None,
                 timestamp_writes: None,
+                pipeline_statistics_query: None,
             });
             pass.set_pipeline(&compute_pipeline);
             pass.set_bind_group(0, Some(&bind_group), &[]);
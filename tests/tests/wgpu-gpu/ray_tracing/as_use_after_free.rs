@@ -131,6 +131,7 @@ fn acceleration_structure_use_after_free(ctx: TestingContext) {
         let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         pass.set_pipeline(&compute_pipeline);
         pass.set_bind_group(0, Some(&bind_group), &[]);
@@ -90,6 +90,7 @@ fn access_all_struct_members(ctx: TestingContext) {
         let mut pass = encoder_compute.begin_compute_pass(&ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         pass.set_pipeline(&compute_pipeline);
         pass.set_bind_group(0, Some(&bind_group), &[]);
@@ -113,6 +113,7 @@ async fn multi_stage_data_binding_test(ctx: TestingContext) {
                     format: wgpu::TextureFormat::Rgba8Unorm,
                     blend: None,
                     write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
                 })],
             }),
             primitive: wgpu::PrimitiveState::default(),
@@ -161,6 +162,9 @@ async fn multi_stage_data_binding_test(ctx: TestingContext) {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
 
         rpass.set_pipeline(&pipeline);
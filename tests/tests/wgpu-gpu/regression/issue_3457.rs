@@ -77,6 +77,7 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
                         format: TextureFormat::Rgba8Unorm,
                         blend: None,
                         write_mask: ColorWrites::all(),
+                        logic_op: None,
                     })],
                 }),
                 multiview: None,
@@ -109,6 +110,7 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
                         format: TextureFormat::Rgba8Unorm,
                         blend: None,
                         write_mask: ColorWrites::all(),
+                        logic_op: None,
                     })],
                 }),
                 multiview: None,
@@ -151,6 +153,9 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: RenderPassFlags::empty(),
+            multiview: None,
         });
 
         double_rpass.set_pipeline(&double_pipeline);
@@ -187,6 +192,9 @@ static PASS_RESET_VERTEX_BUFFER: GpuTestConfiguration =
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: RenderPassFlags::empty(),
+            multiview: None,
         });
 
         single_rpass.set_pipeline(&single_pipeline);
@@ -60,6 +60,7 @@ async fn test_impl(ctx: &TestingContext) {
                     format: wgpu::TextureFormat::Rgba8Unorm,
                     blend: None,
                     write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
                 })],
             }),
             multiview: None,
@@ -92,6 +93,9 @@ async fn test_impl(ctx: &TestingContext) {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             render_pass.set_pipeline(&pipeline);
             render_pass.draw(0..3, 0..1);
@@ -46,6 +46,7 @@ static ALLOW_INPUT_NOT_CONSUMED: GpuTestConfiguration =
                         format: TextureFormat::Rgba8Unorm,
                         blend: None,
                         write_mask: ColorWrites::all(),
+                        logic_op: None,
                     })],
                 }),
                 multiview: None,
@@ -56,6 +56,7 @@ static ZERO_WORKGROUP_COUNT: GpuTestConfiguration = GpuTestConfiguration::new()
             let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: None,
                 timestamp_writes: None,
+                pipeline_statistics_query: None,
             });
             cpass.set_pipeline(&compute_pipeline);
             let bind_group_layout = compute_pipeline.get_bind_group_layout(0);
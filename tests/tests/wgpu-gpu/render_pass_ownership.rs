@@ -77,9 +77,13 @@ async fn render_pass_resource_ownership(ctx: TestingContext) {
                     store: wgpu::StoreOp::Store,
                 }),
                 stencil_ops: None,
+                resolve: None,
             }),
             timestamp_writes: None,
             occlusion_query_set: Some(&occlusion_query_set),
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
 
         // Drop render pass attachments right away.
@@ -111,11 +115,10 @@ async fn render_pass_resource_ownership(ctx: TestingContext) {
 #[gpu_test]
 static RENDER_PASS_QUERY_SET_OWNERSHIP_PIPELINE_STATISTICS: GpuTestConfiguration =
     GpuTestConfiguration::new()
-        .parameters(
-            TestParameters::default()
-                .test_features_limits()
-                .features(wgpu::Features::PIPELINE_STATISTICS_QUERY),
-        )
+        .parameters(TestParameters::default().test_features_limits().features(
+            wgpu::Features::PIPELINE_STATISTICS_QUERY
+                | wgpu::Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES,
+        ))
         .run_async(render_pass_query_set_ownership_pipeline_statistics);
 
 async fn render_pass_query_set_ownership_pipeline_statistics(ctx: TestingContext) {
@@ -159,6 +162,7 @@ async fn render_pass_query_set_ownership_pipeline_statistics(ctx: TestingContext
                     store: wgpu::StoreOp::Store,
                 }),
                 stencil_ops: None,
+                resolve: None,
             }),
             ..Default::default()
         });
@@ -230,6 +234,7 @@ async fn render_pass_query_set_ownership_timestamps(ctx: TestingContext) {
                     store: wgpu::StoreOp::Store,
                 }),
                 stencil_ops: None,
+                resolve: None,
             }),
             timestamp_writes: Some(wgpu::RenderPassTimestampWrites {
                 query_set: &query_set_timestamp_writes,
@@ -288,6 +293,7 @@ async fn render_pass_keep_encoder_alive(ctx: TestingContext) {
                 store: wgpu::StoreOp::Store,
             }),
             stencil_ops: None,
+            resolve: None,
         }),
         ..Default::default()
     });
@@ -0,0 +1,187 @@
+use wgpu_test::{GpuTestConfiguration, TestParameters, TestingContext, gpu_test};
+
+const SIZE: u32 = 64;
+
+#[gpu_test]
+static SUSPEND_RESUME_SAME_ENCODER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_async(|ctx| suspend_resume(ctx, false));
+
+#[gpu_test]
+static SUSPEND_RESUME_ACROSS_ENCODERS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_async(|ctx| suspend_resume(ctx, true));
+
+/// Splits a render pass in three parts: the first clears the target, the second draws
+/// to its left half and the third to its bottom half. The clear has to apply only to the
+/// first part and the stores only to the last one.
+async fn suspend_resume(ctx: TestingContext, across_encoders: bool) {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+        });
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[],
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::R8Unorm,
+                    blend: Some(wgpu::BlendState {
+                        color: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::One,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                        alpha: wgpu::BlendComponent::REPLACE,
+                    }),
+                    write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
+                })],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+    let render_texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Render Texture"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let render_view = render_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let half = SIZE / 2;
+    let parts = [
+        (wgpu::RenderPassFlags::SUSPENDING, None),
+        (
+            wgpu::RenderPassFlags::SUSPENDING | wgpu::RenderPassFlags::RESUMING,
+            Some((0, 0, half, SIZE)),
+        ),
+        (wgpu::RenderPassFlags::RESUMING, Some((0, half, SIZE, half))),
+    ];
+
+    let mut encoders = Vec::new();
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    for (flags, scissor) in parts {
+        if across_encoders && !flags.contains(wgpu::RenderPassFlags::SUSPENDING) {
+            encoders.push(encoder);
+            encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        }
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: 0.2,
+                        g: 0.0,
+                        b: 0.0,
+                        a: 1.0,
+                    }),
+                    store: wgpu::StoreOp::Store,
+                },
+                resolve_target: None,
+                view: &render_view,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags,
+            multiview: None,
+        });
+        if let Some((x, y, width, height)) = scissor {
+            rpass.set_pipeline(&pipeline);
+            rpass.set_scissor_rect(x, y, width, height);
+            rpass.draw(0..3, 0..1);
+        }
+    }
+
+    let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256 * SIZE as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture: &render_texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback_buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(256),
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+    );
+    encoders.push(encoder);
+    ctx.queue
+        .submit(encoders.into_iter().map(|encoder| encoder.finish()));
+    let slice = readback_buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+    let data: &[u8] = &slice.get_mapped_range();
+    let texel = |x: u32, y: u32| data[(x + y * 256) as usize];
+
+    // Each draw adds 0.2 on top of the cleared 0.2.
+    let expected = |draws: u8| 51 * (1 + draws);
+    assert_eq!(texel(48, 16), expected(0));
+    assert_eq!(texel(16, 16), expected(1));
+    assert_eq!(texel(48, 48), expected(1));
+    assert_eq!(texel(16, 48), expected(2));
+}
+
+const SHADER_SRC: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4f {
+    var positions = array(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0),
+    );
+    return vec4f(positions[vertex_index], 0.5, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4f {
+    return vec4f(0.2);
+}
+";
@@ -92,6 +92,7 @@ async fn run_test(
                 format: wgpu::TextureFormat::R8Unorm,
                 blend: None,
                 write_mask: wgpu::ColorWrites::ALL,
+                logic_op: None,
             })],
         }),
         multiview: None,
@@ -191,6 +192,9 @@ async fn run_test(
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             rpass.set_pipeline(&pipeline);
             rpass.set_vertex_buffer(0, vertex_buffer.slice(..));
@@ -302,6 +306,7 @@ async fn run_test_3d(ctx: TestingContext) {
                 format: wgpu::TextureFormat::R8Unorm,
                 blend: None,
                 write_mask: wgpu::ColorWrites::ALL,
+                logic_op: None,
             })],
         }),
         multiview: None,
@@ -372,6 +377,9 @@ async fn run_test_3d(ctx: TestingContext) {
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             rpass.set_pipeline(&pipeline);
             rpass.set_vertex_buffer(0, vertex_buffer.slice(..));
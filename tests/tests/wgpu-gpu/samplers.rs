@@ -511,6 +511,7 @@ fn sampler_bind_group(ctx: TestingContext, group_type: GroupType) {
         let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         cpass.set_pipeline(&pipeline);
         for (i, &bg) in bg_references.iter().enumerate() {
@@ -58,6 +58,7 @@ async fn scissor_test_impl(
                     format: wgpu::TextureFormat::Rgba8Unorm,
                     blend: None,
                     write_mask: wgpu::ColorWrites::ALL,
+                    logic_op: None,
                 })],
             }),
             multiview: None,
@@ -89,6 +90,9 @@ async fn scissor_test_impl(
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgpu::RenderPassFlags::empty(),
+                multiview: None,
             });
             render_pass.set_pipeline(&pipeline);
             render_pass.set_scissor_rect(
@@ -121,6 +121,7 @@ async fn array_size_overrides(
         let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         });
         cpass.set_pipeline(&compute_pipeline);
         let bind_group_layout = compute_pipeline.get_bind_group_layout(0);
@@ -147,6 +147,9 @@ async fn reinterpret(
         depth_stencil_attachment: None,
         timestamp_writes: None,
         occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        flags: wgpu::RenderPassFlags::empty(),
+        multiview: None,
     });
     rpass.set_pipeline(&pipeline);
     rpass.set_bind_group(0, &bind_group, &[]);
@@ -102,3 +102,206 @@ static TEXTURE_BLIT_WITH_NEAREST_FILTER_TEST: GpuTestConfiguration = GpuTestConf
             &target.create_view(&wgpu::TextureViewDescriptor::default()),
         );
     });
+
+#[gpu_test]
+static TEXTURE_BLIT_GENERATE_MIPMAPS_TEST: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        const LAYER_COLORS: [[u8; 4]; 2] = [[255, 0, 0, 255], [0, 255, 0, 255]];
+        const SIZE: u32 = 16;
+        const MIP_LEVELS: u32 = 5;
+
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: LAYER_COLORS.len() as u32,
+            },
+            mip_level_count: MIP_LEVELS,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        for (layer, color) in LAYER_COLORS.iter().enumerate() {
+            let data = color.repeat((SIZE * SIZE) as usize);
+            ctx.queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: layer as u32,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &data,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE * 4),
+                    rows_per_image: None,
+                },
+                wgpu::Extent3d {
+                    width: SIZE,
+                    height: SIZE,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        let blitter = wgpu::util::TextureBlitterBuilder::new(
+            &ctx.device,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+        )
+        .sample_type(wgpu::FilterMode::Linear)
+        .build();
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        blitter.generate_mipmaps(&ctx.device, &mut encoder, &texture);
+
+        // The smallest level is a single texel per layer.
+        let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64 * LAYER_COLORS.len() as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: MIP_LEVELS - 1,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                    rows_per_image: Some(1),
+                },
+            },
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: LAYER_COLORS.len() as u32,
+            },
+        );
+        ctx.queue.submit([encoder.finish()]);
+
+        let slice = readback_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let data: &[u8] = &slice.get_mapped_range();
+
+        for (layer, color) in LAYER_COLORS.iter().enumerate() {
+            let offset = layer * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+            assert_eq!(&data[offset..offset + 4], color, "layer {layer}");
+        }
+    });
+
+#[gpu_test]
+static TEXTURE_BLIT_CUBE_LAYERS_TEST: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        const FACE_COLORS: [[u8; 4]; 6] = [
+            [255, 0, 0, 255],
+            [0, 255, 0, 255],
+            [0, 0, 255, 255],
+            [255, 255, 0, 255],
+            [0, 255, 255, 255],
+            [255, 0, 255, 255],
+        ];
+        const SIZE: u32 = 4;
+
+        let cube_descriptor = |usage| wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: FACE_COLORS.len() as u32,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage,
+            view_formats: &[],
+        };
+        let source = ctx.device.create_texture(&cube_descriptor(
+            wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        ));
+        let target = ctx.device.create_texture(&cube_descriptor(
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        ));
+
+        for (face, color) in FACE_COLORS.iter().enumerate() {
+            ctx.queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &source,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: face as u32,
+                    },
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &color.repeat((SIZE * SIZE) as usize),
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE * 4),
+                    rows_per_image: None,
+                },
+                wgpu::Extent3d {
+                    width: SIZE,
+                    height: SIZE,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        let blitter = wgpu::util::TextureBlitter::new(&ctx.device, wgpu::TextureFormat::Rgba8Unorm);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        blitter.copy_layers(&ctx.device, &mut encoder, &source, &target);
+
+        let layer_stride = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT * SIZE;
+        let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: layer_stride as u64 * FACE_COLORS.len() as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            target.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                    rows_per_image: Some(SIZE),
+                },
+            },
+            target.size(),
+        );
+        ctx.queue.submit([encoder.finish()]);
+
+        let slice = readback_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let data: &[u8] = &slice.get_mapped_range();
+
+        for (face, color) in FACE_COLORS.iter().enumerate() {
+            let offset = face * layer_stride as usize;
+            assert_eq!(&data[offset..offset + 4], color, "face {face}");
+        }
+    });
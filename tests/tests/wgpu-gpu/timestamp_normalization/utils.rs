@@ -264,6 +264,7 @@ fn process_shader(ctx: TestingContext, inputs: &[u8], entry_point_src: &str) ->
     let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
         label: Some("Compute Pass"),
         timestamp_writes: None,
+        pipeline_statistics_query: None,
     });
 
     cpass.set_pipeline(&pipeline);
@@ -69,6 +69,7 @@ fn timestamp_query(ctx: TestingContext) {
                 beginning_of_pass_write_index: Some(base_index),
                 end_of_pass_write_index: Some(base_index + 1),
             }),
+            pipeline_statistics_query: None,
         });
         compute_pass.set_pipeline(&pipeline);
 
@@ -253,6 +253,7 @@ async fn vertex_index_common(ctx: TestingContext) {
                 format: wgpu::TextureFormat::Rgba8Unorm,
                 blend: None,
                 write_mask: wgpu::ColorWrites::ALL,
+                logic_op: None,
             })],
         }),
         multiview: None,
@@ -362,6 +363,9 @@ async fn vertex_index_common(ctx: TestingContext) {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu::RenderPassFlags::empty(),
+            multiview: None,
         });
 
         {
@@ -377,6 +381,7 @@ async fn vertex_index_common(ctx: TestingContext) {
                         depth_stencil: None,
                         sample_count: 1,
                         multiview: None,
+                        inheritance: wgpu::RenderBundleInheritance::empty(),
                     },
                 )),
             };
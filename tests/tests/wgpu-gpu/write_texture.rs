@@ -228,3 +228,117 @@ static WRITE_TEXTURE_NO_OOB: GpuTestConfiguration =
             },
         );
     });
+
+#[gpu_test]
+static READ_TEXTURE_TO_VEC: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        // A row of this texture is 20 bytes, so copies out of it need padding.
+        let size = wgpu::Extent3d {
+            width: 5,
+            height: 4,
+            depth_or_array_layers: 2,
+        };
+
+        let tex = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            dimension: wgpu::TextureDimension::D2,
+            size,
+            format: wgpu::TextureFormat::Rgba8Uint,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            mip_level_count: 1,
+            sample_count: 1,
+            view_formats: &[],
+        });
+        let data: Vec<u8> = (0..size.width * size.height * size.depth_or_array_layers * 4)
+            .map(|i| i as u8)
+            .collect();
+        ctx.queue.write_texture(
+            tex.as_image_copy(),
+            &data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(size.width * 4),
+                rows_per_image: Some(size.height),
+            },
+            size,
+        );
+
+        // Read back a 3x2 region of both layers, starting at (1, 1).
+        let (sender, receiver) = std::sync::mpsc::channel();
+        wgpu::util::read_texture_to_vec(
+            &ctx.device,
+            &ctx.queue,
+            &tex,
+            0,
+            wgpu::Origin3d { x: 1, y: 1, z: 0 },
+            wgpu::Extent3d {
+                width: 3,
+                height: 2,
+                depth_or_array_layers: 2,
+            },
+            move |result| sender.send(result).unwrap(),
+        );
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let result = receiver.recv().unwrap().unwrap();
+
+        let mut expected = Vec::new();
+        for layer in 0..2 {
+            for y in 1..3 {
+                let row_start = ((layer * size.height + y) * size.width + 1) as usize * 4;
+                expected.extend_from_slice(&data[row_start..row_start + 3 * 4]);
+            }
+        }
+        assert_eq!(result, expected);
+    });
+
+#[gpu_test]
+static WRITE_TEXTURE_LARGE_UNALIGNED_ROWS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        // A row of this texture is 1200 bytes, which isn't a multiple of
+        // `COPY_BYTES_PER_ROW_ALIGNMENT`, and the upload is large enough to be
+        // repacked on the GPU on backends that need it.
+        let size = wgpu::Extent3d {
+            width: 300,
+            height: 512,
+            depth_or_array_layers: 2,
+        };
+
+        let tex = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            dimension: wgpu::TextureDimension::D2,
+            size,
+            format: wgpu::TextureFormat::Rgba8Uint,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            mip_level_count: 1,
+            sample_count: 1,
+            view_formats: &[],
+        });
+        let data: Vec<u8> = (0..size.width * size.height * size.depth_or_array_layers * 4)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        ctx.queue.write_texture(
+            tex.as_image_copy(),
+            &data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(size.width * 4),
+                rows_per_image: Some(size.height),
+            },
+            size,
+        );
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        wgpu::util::read_texture_to_vec(
+            &ctx.device,
+            &ctx.queue,
+            &tex,
+            0,
+            wgpu::Origin3d::ZERO,
+            size,
+            move |result| sender.send(result).unwrap(),
+        );
+        ctx.async_poll(wgpu::PollType::wait()).await.unwrap();
+        let result = receiver.recv().unwrap().unwrap();
+
+        assert_eq!(result, data);
+    });
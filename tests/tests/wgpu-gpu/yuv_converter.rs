@@ -0,0 +1,65 @@
+use wgpu_test::{GpuTestConfiguration, TestParameters, TestingContext, gpu_test};
+
+fn convert(ctx: TestingContext, source_format: wgpu::TextureFormat) {
+    let size = wgpu::Extent3d {
+        width: 64,
+        height: 64,
+        depth_or_array_layers: 1,
+    };
+    let source = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: source_format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+
+    let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+
+    let converter = wgpu::util::YuvConverterBuilder::new(
+        &ctx.device,
+        source_format,
+        wgpu::TextureFormat::Rgba8Unorm,
+    )
+    .matrix(wgpu::YuvMatrix::Bt601)
+    .range(wgpu::YuvRange::Full)
+    .build();
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+    converter.convert(
+        &ctx.device,
+        &mut encoder,
+        &source,
+        &target.create_view(&wgpu::TextureViewDescriptor::default()),
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+}
+
+#[gpu_test]
+static YUV_CONVERTER_NV12_TEST: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_FORMAT_NV12))
+    .run_sync(|ctx| convert(ctx, wgpu::TextureFormat::NV12));
+
+#[gpu_test]
+static YUV_CONVERTER_P010_TEST: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_FORMAT_P010))
+    .run_sync(|ctx| convert(ctx, wgpu::TextureFormat::P010));
+
+#[gpu_test]
+static YUV_CONVERTER_I420_TEST: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_FORMAT_I420))
+    .run_sync(|ctx| convert(ctx, wgpu::TextureFormat::I420));
@@ -161,9 +161,13 @@ impl<'ctx> TestCase<'ctx> {
                         load: LoadOp::Clear(0xFFFFFFFF),
                         store: StoreOp::Store,
                     }),
+                    resolve: None,
                 }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: RenderPassFlags::empty(),
+                multiview: None,
             });
             ctx.queue.submit([encoder.finish()]);
         } else {
@@ -246,10 +250,14 @@ impl<'ctx> TestCase<'ctx> {
                             load: LoadOp::Load,
                             store: StoreOp::Discard,
                         }),
+                        resolve: None,
                     },
                 ),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: RenderPassFlags::empty(),
+                multiview: None,
             });
     }
 
@@ -271,10 +279,14 @@ impl<'ctx> TestCase<'ctx> {
                             load: LoadOp::Clear(0),
                             store: StoreOp::Store,
                         }),
+                        resolve: None,
                     },
                 ),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: RenderPassFlags::empty(),
+                multiview: None,
             });
     }
 
@@ -296,10 +308,14 @@ impl<'ctx> TestCase<'ctx> {
                             load: LoadOp::Load,
                             store: StoreOp::Discard,
                         }),
+                        resolve: None,
                     },
                 ),
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: RenderPassFlags::empty(),
+                multiview: None,
             });
     }
 
@@ -0,0 +1,219 @@
+//! Tests of acceleration structure refits and serialization.
+
+use std::sync::{Arc, Mutex};
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const IDENTITY: [f32; 12] = [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+
+fn ray_query_device() -> (Device, Queue) {
+    device_with_features(Features::EXPERIMENTAL_RAY_QUERY)
+}
+
+fn serialization_device() -> (Device, Queue) {
+    device_with_features(
+        Features::EXPERIMENTAL_RAY_QUERY
+            | Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION,
+    )
+}
+
+fn device_with_features(required_features: Features) -> (Device, Queue) {
+    Device::noop(&DeviceDescriptor {
+        required_features,
+        required_limits: Limits::default().using_minimum_supported_acceleration_structure_values(),
+        ..DeviceDescriptor::default()
+    })
+}
+
+fn triangle_size(vertex_count: u32) -> BlasTriangleGeometrySizeDescriptor {
+    BlasTriangleGeometrySizeDescriptor {
+        vertex_format: VertexFormat::Float32x3,
+        vertex_count,
+        index_format: None,
+        index_count: None,
+        flags: AccelerationStructureGeometryFlags::empty(),
+    }
+}
+
+fn create_blas(device: &Device, flags: AccelerationStructureFlags) -> Blas {
+    device.create_blas(
+        &CreateBlasDescriptor {
+            label: None,
+            flags,
+            update_mode: AccelerationStructureUpdateMode::Refit,
+        },
+        BlasGeometrySizeDescriptors::Triangles {
+            descriptors: vec![triangle_size(6)],
+        },
+    )
+}
+
+fn create_tlas(device: &Device, flags: AccelerationStructureFlags) -> Tlas {
+    device.create_tlas(&CreateTlasDescriptor {
+        label: None,
+        max_instances: 2,
+        flags,
+        update_mode: AccelerationStructureUpdateMode::Refit,
+    })
+}
+
+/// Builds `blas` from its first `vertex_count` vertices, and `tlas` if given.
+fn build(device: &Device, queue: &Queue, blas: &Blas, vertex_count: u32, tlas: Option<&Tlas>) {
+    let vertices = device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: &[0; 6 * size_of::<[f32; 3]>()],
+        usage: BufferUsages::BLAS_INPUT,
+    });
+    let size = triangle_size(vertex_count);
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.build_acceleration_structures(
+        [&BlasBuildEntry {
+            blas,
+            geometry: BlasGeometries::TriangleGeometries(vec![BlasTriangleGeometry {
+                size: &size,
+                vertex_buffer: &vertices,
+                first_vertex: 0,
+                vertex_stride: size_of::<[f32; 3]>() as BufferAddress,
+                index_buffer: None,
+                first_index: None,
+                transform_buffer: None,
+                transform_buffer_offset: None,
+            }]),
+        }],
+        tlas,
+    );
+    queue.submit([encoder.finish()]);
+}
+
+#[test]
+fn refit_requires_allow_update() {
+    let (device, _queue) = ray_query_device();
+
+    fail(
+        &device,
+        || create_blas(&device, AccelerationStructureFlags::empty()),
+        Some("requires the flag AccelerationStructureFlags::ALLOW_UPDATE"),
+    );
+    fail(
+        &device,
+        || create_tlas(&device, AccelerationStructureFlags::empty()),
+        Some("requires the flag AccelerationStructureFlags::ALLOW_UPDATE"),
+    );
+}
+
+#[test]
+fn blas_refit() {
+    let (device, queue) = ray_query_device();
+    let blas = create_blas(&device, AccelerationStructureFlags::ALLOW_UPDATE);
+
+    // The first build is a full one, every later one refits it.
+    valid(&device, || build(&device, &queue, &blas, 6, None));
+    valid(&device, || build(&device, &queue, &blas, 6, None));
+
+    fail(
+        &device,
+        || build(&device, &queue, &blas, 3, None),
+        Some("refit with different vertex or index counts"),
+    );
+}
+
+#[test]
+fn tlas_refit() {
+    let (device, queue) = ray_query_device();
+    let blas = create_blas(&device, AccelerationStructureFlags::ALLOW_UPDATE);
+    let mut tlas = create_tlas(&device, AccelerationStructureFlags::ALLOW_UPDATE);
+    tlas[0] = Some(TlasInstance::new(&blas, IDENTITY, 0, 0xff));
+
+    valid(&device, || build(&device, &queue, &blas, 6, Some(&tlas)));
+    valid(&device, || build(&device, &queue, &blas, 6, Some(&tlas)));
+
+    tlas[1] = Some(TlasInstance::new(&blas, IDENTITY, 0, 0xff));
+    fail(
+        &device,
+        || build(&device, &queue, &blas, 6, Some(&tlas)),
+        Some("refit with a different instance count"),
+    );
+}
+
+/// Serializes `blas`, returning what its callback was called with.
+fn serialize(
+    device: &Device,
+    queue: &Queue,
+    blas: &Blas,
+) -> Option<Result<Vec<u8>, BlasAsyncError>> {
+    let result = Arc::new(Mutex::new(None));
+    let callback_result = result.clone();
+    blas.serialize(move |data| *callback_result.lock().unwrap() = Some(data));
+
+    // One submission for reading back the serialized size, one for the data.
+    for _ in 0..2 {
+        queue.submit([]);
+        device.poll(PollType::Wait).unwrap();
+    }
+    result.lock().unwrap().take()
+}
+
+#[test]
+fn serialize_requires_feature() {
+    let (device, queue) = ray_query_device();
+    let blas = create_blas(&device, AccelerationStructureFlags::ALLOW_UPDATE);
+    build(&device, &queue, &blas, 6, None);
+
+    let mut result = None;
+    fail(
+        &device,
+        || result = serialize(&device, &queue, &blas),
+        Some("EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION"),
+    );
+    assert_eq!(result, Some(Err(BlasAsyncError)));
+}
+
+#[test]
+fn serialize_unbuilt_blas() {
+    let (device, queue) = serialization_device();
+    let blas = create_blas(&device, AccelerationStructureFlags::ALLOW_UPDATE);
+
+    fail(
+        &device,
+        || serialize(&device, &queue, &blas),
+        Some("BLAS is not yet built"),
+    );
+}
+
+#[test]
+fn blas_serialization_roundtrip() {
+    let (device, queue) = serialization_device();
+    let blas = create_blas(&device, AccelerationStructureFlags::ALLOW_UPDATE);
+    build(&device, &queue, &blas, 6, None);
+
+    let data = valid(&device, || serialize(&device, &queue, &blas))
+        .unwrap()
+        .unwrap();
+    assert!(device.is_serialized_blas_compatible(&data));
+
+    let deserialized = valid(&device, || {
+        device.create_blas_from_serialized(
+            &CreateBlasDescriptor {
+                label: None,
+                flags: AccelerationStructureFlags::ALLOW_UPDATE,
+                update_mode: AccelerationStructureUpdateMode::Refit,
+            },
+            BlasGeometrySizeDescriptors::Triangles {
+                descriptors: vec![triangle_size(6)],
+            },
+            &data,
+        )
+    });
+
+    // The deserialized BLAS counts as built, so a TLAS can be built from it right away.
+    let mut tlas = create_tlas(&device, AccelerationStructureFlags::ALLOW_UPDATE);
+    tlas[0] = Some(TlasInstance::new(&deserialized, IDENTITY, 0, 0xff));
+    valid(&device, || {
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.build_acceleration_structures([], [&tlas]);
+        queue.submit([encoder.finish()]);
+    });
+}
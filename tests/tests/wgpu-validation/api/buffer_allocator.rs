@@ -0,0 +1,66 @@
+//! Tests of [`wgpu::util::BufferAllocator`].
+
+use wgpu::util::BufferAllocator;
+
+fn size(size: u64) -> wgpu::BufferSize {
+    wgpu::BufferSize::new(size).unwrap()
+}
+
+#[test]
+fn allocations_are_aligned_and_disjoint() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let alignment = u64::from(device.limits().min_uniform_buffer_offset_alignment);
+    let mut allocator = BufferAllocator::new(&device, wgpu::BufferUsages::UNIFORM, 4 * alignment);
+
+    let allocations: Vec<_> = (0..6).map(|_| allocator.allocate(size(16))).collect();
+    for (i, allocation) in allocations.iter().enumerate() {
+        assert_eq!(allocation.offset() % alignment, 0);
+        assert_eq!(allocation.size(), size(16));
+        for other in &allocations[..i] {
+            assert!(allocation.buffer() != other.buffer() || allocation.offset() != other.offset());
+        }
+    }
+
+    let stats = allocator.stats();
+    assert_eq!(stats.block_count, 2);
+    assert_eq!(stats.resident_bytes, 8 * alignment);
+    assert_eq!(stats.allocated_bytes, 6 * alignment);
+}
+
+#[test]
+fn dropped_ranges_are_reused_after_recall() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let mut allocator = BufferAllocator::new(&device, wgpu::BufferUsages::VERTEX, 1024);
+
+    let first = allocator.allocate(size(512));
+    let second = allocator.allocate(size(512));
+    let (first_buffer, first_offset) = (first.buffer().clone(), first.offset());
+    drop(first);
+    drop(second);
+
+    // Not reused before `recall`, so a new block is needed.
+    let third = allocator.allocate(size(1024));
+    assert_ne!(*third.buffer(), first_buffer);
+    drop(third);
+
+    allocator.recall(&queue);
+    device.poll(wgpu::PollType::wait()).unwrap();
+
+    // Both halves were merged, so a whole block can be allocated again.
+    let fourth = allocator.allocate(size(1024));
+    assert_eq!(*fourth.buffer(), first_buffer);
+    assert_eq!(fourth.offset(), first_offset);
+    assert_eq!(allocator.stats().block_count, 2);
+    assert_eq!(allocator.stats().allocated_bytes, 1024);
+}
+
+#[test]
+fn large_allocations_get_dedicated_buffers() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let mut allocator = BufferAllocator::new(&device, wgpu::BufferUsages::STORAGE, 1024);
+
+    let allocation = allocator.allocate(size(4096));
+    assert_eq!(allocation.offset(), 0);
+    assert_eq!(allocation.buffer().size(), 4096);
+    assert_eq!(allocator.stats().block_count, 0);
+}
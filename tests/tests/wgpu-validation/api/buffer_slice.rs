@@ -67,3 +67,22 @@ fn into_buffer_binding() {
     assert_eq!(b, &buffer);
     assert_eq!(size, NonZero::new(30).unwrap());
 }
+
+#[test]
+fn map_async_await() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mapped = buffer.slice(..).map_async_await(wgpu::MapMode::Read);
+    let work_done = queue.on_submitted_work_done_async();
+    device.poll(wgpu::PollType::Wait).unwrap();
+
+    pollster::block_on(mapped).unwrap();
+    pollster::block_on(work_done);
+    assert_eq!(&*buffer.slice(..).get_mapped_range(), &[0; 16]);
+}
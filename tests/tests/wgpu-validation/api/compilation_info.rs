@@ -0,0 +1,57 @@
+//! Tests of [`wgpu::ShaderModule::get_compilation_info`].
+
+use wgpu::*;
+use wgpu_test::fail;
+
+#[test]
+fn compilation_info_error_location() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    // The non-ASCII characters make the UTF-8 and UTF-16 positions differ.
+    let source = "// Ünïcödé 😀\nconst X: f32 = 1.0;\nconst Y: f32 = /* ü */ X + Z;\n";
+    let module = fail(
+        &device,
+        || {
+            device.create_shader_module(ShaderModuleDescriptor {
+                label: None,
+                source: ShaderSource::Wgsl(source.into()),
+            })
+        },
+        Some("no definition in scope for identifier: `Z`"),
+    );
+
+    let compilation_info = pollster::block_on(module.get_compilation_info());
+    let [message] = &compilation_info.messages[..] else {
+        panic!("expected one message, got {:?}", compilation_info.messages);
+    };
+    assert_eq!(message.message_type, CompilationMessageType::Error);
+    assert_eq!(
+        message.message,
+        "no definition in scope for identifier: `Z`"
+    );
+    assert_eq!(message.file_name, None);
+    assert_eq!(
+        message.location,
+        Some(SourceLocation {
+            line_number: 3,
+            line_position: 29,
+            offset: 68,
+            length: 1,
+            utf16_line_position: 28,
+            utf16_offset: 61,
+            utf16_length: 1,
+        })
+    );
+}
+
+#[test]
+fn compilation_info_success() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl("const X: f32 = 1.0;".into()),
+    });
+    let compilation_info = pollster::block_on(module.get_compilation_info());
+    assert!(compilation_info.messages.is_empty());
+}
@@ -0,0 +1,127 @@
+//! Tests of conditional blocks in render passes.
+
+use wgpu::*;
+use wgpu_test::{create_render_target, fail, run_render_pass, valid};
+
+fn create_predicate_buffer(device: &Device, usage: BufferUsages) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some("predicate"),
+        size: 16,
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+#[test]
+fn conditional_block() {
+    for required_features in [Features::empty(), Features::CONDITIONAL_RENDERING] {
+        let (device, _queue) = Device::noop(&DeviceDescriptor {
+            required_features,
+            ..Default::default()
+        });
+        let view = create_render_target(&device);
+        let buffer = create_predicate_buffer(&device, BufferUsages::INDIRECT);
+
+        valid(&device, || {
+            run_render_pass(&device, &view, |pass| {
+                pass.begin_conditional_block(&buffer, 0);
+                pass.end_conditional_block();
+                pass.begin_conditional_block(&buffer, 8);
+                pass.end_conditional_block();
+            })
+        });
+    }
+}
+
+#[test]
+fn conditional_block_requires_indirect_usage() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::CONDITIONAL_RENDERING,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let buffer = create_predicate_buffer(&device, BufferUsages::QUERY_RESOLVE);
+
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.begin_conditional_block(&buffer, 0);
+                pass.end_conditional_block();
+            })
+        },
+        Some("INDIRECT"),
+    );
+}
+
+#[test]
+fn conditional_block_predicate_bounds() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::CONDITIONAL_RENDERING,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let buffer = create_predicate_buffer(&device, BufferUsages::INDIRECT);
+
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.begin_conditional_block(&buffer, 4);
+                pass.end_conditional_block();
+            })
+        },
+        Some("is not a multiple of 8"),
+    );
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.begin_conditional_block(&buffer, 16);
+                pass.end_conditional_block();
+            })
+        },
+        Some("overruns predicate buffer of size 16"),
+    );
+}
+
+#[test]
+fn conditional_block_nesting() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::CONDITIONAL_RENDERING,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let buffer = create_predicate_buffer(&device, BufferUsages::INDIRECT);
+
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.begin_conditional_block(&buffer, 0);
+                pass.begin_conditional_block(&buffer, 8);
+                pass.end_conditional_block();
+                pass.end_conditional_block();
+            })
+        },
+        Some("while another conditional block was active"),
+    );
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.end_conditional_block();
+            })
+        },
+        Some("no active conditional block"),
+    );
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.begin_conditional_block(&buffer, 0);
+            })
+        },
+        Some("not ended before the render pass"),
+    );
+}
@@ -0,0 +1,81 @@
+//! Tests of [`wgpu::Features::CONSERVATIVE_RASTERIZATION_FULLY_COVERED`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main() -> @builtin(position) vec4f {
+        return vec4f(0.0);
+    }
+
+    @fragment
+    fn fs_main(@builtin(fully_covered) covered: bool) -> @location(0) vec4f {
+        return select(vec4f(0.5), vec4f(1.0), covered);
+    }
+";
+
+fn create_module(device: &Device) -> ShaderModule {
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    })
+}
+
+fn create_pipeline(device: &Device, module: &ShaderModule, conservative: bool) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: PrimitiveState {
+            conservative,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[test]
+fn fully_covered_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::CONSERVATIVE_RASTERIZATION,
+        ..Default::default()
+    });
+
+    fail(
+        &device,
+        || create_module(&device),
+        Some("FRAGMENT_FULLY_COVERED"),
+    );
+}
+
+#[test]
+fn fully_covered_requires_conservative_pipeline() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::CONSERVATIVE_RASTERIZATION
+            | Features::CONSERVATIVE_RASTERIZATION_FULLY_COVERED,
+        ..Default::default()
+    });
+    let module = valid(&device, || create_module(&device));
+
+    valid(&device, || create_pipeline(&device, &module, true));
+    fail(
+        &device,
+        || create_pipeline(&device, &module, false),
+        Some("doesn't use conservative rasterization"),
+    );
+}
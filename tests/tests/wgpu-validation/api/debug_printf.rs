@@ -0,0 +1,58 @@
+//! Tests of [`wgpu::Features::SHADER_DEBUG_PRINTF`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = r#"
+    enable wgpu_debug_printf;
+
+    @compute @workgroup_size(1)
+    fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+        debugPrintf("invocation %v3u, %f%% done", id, 50.0);
+    }
+"#;
+
+fn create_shader_module(device: &Device, source: &str) -> ShaderModule {
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(source.into()),
+    })
+}
+
+#[test]
+fn debug_printf_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || create_shader_module(&device, SHADER),
+        Some("DEBUG_PRINTF"),
+    );
+}
+
+#[test]
+fn debug_printf() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::SHADER_DEBUG_PRINTF,
+        ..Default::default()
+    });
+
+    valid(&device, || create_shader_module(&device, SHADER));
+    fail(
+        &device,
+        || {
+            create_shader_module(
+                &device,
+                r#"
+                enable wgpu_debug_printf;
+
+                @compute @workgroup_size(1)
+                fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+                    debugPrintf("invocation %u", id);
+                }
+                "#,
+            )
+        },
+        Some("doesn't match the conversion specifier `%u`"),
+    );
+}
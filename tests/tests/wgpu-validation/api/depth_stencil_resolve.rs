@@ -0,0 +1,183 @@
+//! Tests of [`wgpu::Features::DEPTH_STENCIL_RESOLVE`].
+
+use wgpu_test::{RenderTarget, fail, run_render_pass_with, valid};
+
+fn resolve_device() -> (wgpu::Device, wgpu::Queue) {
+    wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::DEPTH_STENCIL_RESOLVE,
+        ..Default::default()
+    })
+}
+
+fn run_pass(
+    device: &wgpu::Device,
+    view: &wgpu::TextureView,
+    resolve: wgpu::RenderPassDepthStencilResolve<'_>,
+) -> wgpu::CommandBuffer {
+    let format = view.texture().format();
+    let desc = wgpu::RenderPassDescriptor {
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: format.has_depth_aspect().then_some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: wgpu::StoreOp::Discard,
+            }),
+            stencil_ops: format.has_stencil_aspect().then_some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(0),
+                store: wgpu::StoreOp::Discard,
+            }),
+            resolve: Some(resolve),
+        }),
+        ..Default::default()
+    };
+    run_render_pass_with(device, &desc, |_| {})
+}
+
+#[test]
+fn requires_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let msaa = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 4).create_view(&device);
+    let target = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 1).create_view(&device);
+
+    fail(
+        &device,
+        || {
+            run_pass(
+                &device,
+                &msaa,
+                wgpu::RenderPassDepthStencilResolve::new(&target, wgpu::ResolveMode::SampleZero),
+            )
+        },
+        Some("DEPTH_STENCIL_RESOLVE"),
+    );
+}
+
+#[test]
+fn resolve_modes() {
+    let (device, _queue) = resolve_device();
+
+    for format in [
+        wgpu::TextureFormat::Depth32Float,
+        wgpu::TextureFormat::Depth24PlusStencil8,
+    ] {
+        let msaa = RenderTarget::new(format, 4).create_view(&device);
+        let target = RenderTarget::new(format, 1).create_view(&device);
+        for mode in [
+            wgpu::ResolveMode::SampleZero,
+            wgpu::ResolveMode::Min,
+            wgpu::ResolveMode::Max,
+        ] {
+            valid(&device, || {
+                run_pass(
+                    &device,
+                    &msaa,
+                    wgpu::RenderPassDepthStencilResolve::new(&target, mode),
+                )
+            });
+        }
+    }
+
+    let msaa = RenderTarget::new(wgpu::TextureFormat::Stencil8, 4).create_view(&device);
+    let target = RenderTarget::new(wgpu::TextureFormat::Stencil8, 1).create_view(&device);
+    valid(&device, || {
+        run_pass(
+            &device,
+            &msaa,
+            wgpu::RenderPassDepthStencilResolve {
+                target: &target,
+                depth_mode: None,
+                resolve_stencil: true,
+            },
+        )
+    });
+}
+
+#[test]
+fn resolved_aspects() {
+    let (device, _queue) = resolve_device();
+
+    let msaa = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 4).create_view(&device);
+    let target = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 1).create_view(&device);
+    fail(
+        &device,
+        || {
+            run_pass(
+                &device,
+                &msaa,
+                wgpu::RenderPassDepthStencilResolve {
+                    target: &target,
+                    depth_mode: None,
+                    resolve_stencil: true,
+                },
+            )
+        },
+        Some("must resolve at least one of the depth and stencil aspects"),
+    );
+
+    let msaa = RenderTarget::new(wgpu::TextureFormat::Stencil8, 4).create_view(&device);
+    let target = RenderTarget::new(wgpu::TextureFormat::Stencil8, 1).create_view(&device);
+    fail(
+        &device,
+        || {
+            run_pass(
+                &device,
+                &msaa,
+                wgpu::RenderPassDepthStencilResolve::new(&target, wgpu::ResolveMode::Max),
+            )
+        },
+        Some("has no depth aspect"),
+    );
+}
+
+#[test]
+fn mismatched_target() {
+    let (device, _queue) = resolve_device();
+    let msaa = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 4).create_view(&device);
+
+    let resolve_into = |target: &wgpu::TextureView| {
+        run_pass(
+            &device,
+            &msaa,
+            wgpu::RenderPassDepthStencilResolve::new(target, wgpu::ResolveMode::SampleZero),
+        )
+    };
+
+    let target = RenderTarget {
+        size: 32,
+        ..RenderTarget::new(wgpu::TextureFormat::Depth32Float, 1)
+    }
+    .create_view(&device);
+    fail(
+        &device,
+        || resolve_into(&target),
+        Some("Attachments have differing sizes"),
+    );
+
+    let target = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 4).create_view(&device);
+    fail(
+        &device,
+        || resolve_into(&target),
+        Some("while the resolve destination must not be multisampled"),
+    );
+
+    let target = RenderTarget::new(wgpu::TextureFormat::Depth24Plus, 1).create_view(&device);
+    fail(
+        &device,
+        || resolve_into(&target),
+        Some("must match the resolve destination format"),
+    );
+
+    let single = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 1).create_view(&device);
+    let target = RenderTarget::new(wgpu::TextureFormat::Depth32Float, 1).create_view(&device);
+    fail(
+        &device,
+        || {
+            run_pass(
+                &device,
+                &single,
+                wgpu::RenderPassDepthStencilResolve::new(&target, wgpu::ResolveMode::SampleZero),
+            )
+        },
+        Some("must be multi-sampled"),
+    );
+}
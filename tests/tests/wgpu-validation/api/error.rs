@@ -0,0 +1,57 @@
+use wgpu::{ErrorCause, wgc::binding_model::CreateBindGroupError};
+
+#[test]
+fn typed_validation_error() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[],
+    });
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::UNIFORM,
+        mapped_at_creation: false,
+    });
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    });
+    let error = pollster::block_on(device.pop_error_scope()).unwrap();
+
+    assert_eq!(error.operation(), Some("Device::create_bind_group"));
+    assert!(matches!(
+        error.cause(),
+        Some(ErrorCause::CreateBindGroup(
+            CreateBindGroupError::BindingsNumMismatch {
+                actual: 1,
+                expected: 0
+            }
+        ))
+    ));
+}
+
+#[test]
+fn pop_error_scope_blocking() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+    let _buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::MAP_WRITE,
+        mapped_at_creation: false,
+    });
+
+    // The validation error skips the out-of-memory scope.
+    assert!(device.pop_error_scope_blocking().is_none());
+    let error = device.pop_error_scope_blocking().unwrap();
+    assert!(matches!(error, wgpu::Error::Validation { .. }));
+}
@@ -0,0 +1,130 @@
+//! Tests of [`wgpu::Features::FRAMEBUFFER_FETCH`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = "
+    enable wgpu_framebuffer_fetch;
+
+    @vertex
+    fn vs_main() -> @builtin(position) vec4f {
+        return vec4f(0.0);
+    }
+
+    @fragment
+    fn fs_blend(@color(0) current: vec4f) -> @location(0) vec4f {
+        return current * 0.5;
+    }
+
+    @fragment
+    fn fs_second_target(@color(1) second: vec4<u32>) -> @location(0) vec4f {
+        return vec4f(second);
+    }
+";
+
+fn create_pipeline(
+    device: &Device,
+    module: &ShaderModule,
+    fragment_entry_point: &str,
+    targets: &[Option<ColorTargetState>],
+) -> RenderPipeline {
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module,
+            entry_point: Some(fragment_entry_point),
+            compilation_options: Default::default(),
+            targets,
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[test]
+fn framebuffer_fetch_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || {
+            device.create_shader_module(ShaderModuleDescriptor {
+                label: None,
+                source: ShaderSource::Wgsl(SHADER.into()),
+            })
+        },
+        Some("FRAMEBUFFER_FETCH"),
+    );
+}
+
+#[test]
+fn framebuffer_fetch_targets() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::FRAMEBUFFER_FETCH,
+        ..Default::default()
+    });
+    let module = valid(&device, || {
+        device.create_shader_module(ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Wgsl(SHADER.into()),
+        })
+    });
+
+    valid(&device, || {
+        create_pipeline(
+            &device,
+            &module,
+            "fs_blend",
+            &[Some(TextureFormat::Rgba8Unorm.into())],
+        )
+    });
+    valid(&device, || {
+        create_pipeline(
+            &device,
+            &module,
+            "fs_second_target",
+            &[
+                Some(TextureFormat::Rgba8Unorm.into()),
+                Some(TextureFormat::Rgba8Uint.into()),
+            ],
+        )
+    });
+
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                &module,
+                "fs_second_target",
+                &[Some(TextureFormat::Rgba8Unorm.into())],
+            )
+        },
+        Some("has no color target at that index"),
+    );
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                &module,
+                "fs_second_target",
+                &[
+                    Some(TextureFormat::Rgba8Unorm.into()),
+                    Some(TextureFormat::Rgba8Unorm.into()),
+                ],
+            )
+        },
+        Some("is incompatible with the shader"),
+    );
+}
@@ -0,0 +1,74 @@
+//! Tests of [`wgpu::Instance::create_headless_surface`].
+
+fn noop_instance() -> wgpu::Instance {
+    wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[test]
+fn presented_frames() {
+    let instance = noop_instance();
+    let surface = instance
+        .create_headless_surface(64, 32, wgpu::TextureFormat::Rgba8Unorm)
+        .unwrap();
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        compatible_surface: Some(&surface),
+        ..Default::default()
+    }))
+    .unwrap();
+    let (device, _queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+
+    let config = surface.get_default_config(&adapter, 64, 32).unwrap();
+    assert_eq!(config.format, wgpu::TextureFormat::Rgba8Unorm);
+    surface.configure(&device, &config);
+
+    for _ in 0..2 {
+        let frame = surface.get_current_texture().unwrap();
+        assert_eq!((frame.texture.width(), frame.texture.height()), (64, 32));
+        frame.present();
+    }
+
+    // Discarded frames are not presented.
+    drop(surface.get_current_texture().unwrap());
+
+    let frames = surface.take_presented_frames().unwrap();
+    assert_eq!(frames.len(), 2);
+    for frame in &frames {
+        assert_eq!(frame.format(), wgpu::TextureFormat::Rgba8Unorm);
+        assert!(frame.usage().contains(wgpu::TextureUsages::COPY_SRC));
+    }
+    assert!(surface.take_presented_frames().unwrap().is_empty());
+}
+
+#[test]
+fn presented_frames_are_bounded() {
+    let instance = noop_instance();
+    let surface = instance
+        .create_headless_surface(8, 8, wgpu::TextureFormat::Bgra8Unorm)
+        .unwrap();
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .unwrap();
+    let (device, _queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+    surface.configure(
+        &device,
+        &surface.get_default_config(&adapter, 8, 8).unwrap(),
+    );
+
+    for _ in 0..20 {
+        surface.get_current_texture().unwrap().present();
+    }
+
+    assert_eq!(surface.take_presented_frames().unwrap().len(), 16);
+}
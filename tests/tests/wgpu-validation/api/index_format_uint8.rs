@@ -0,0 +1,90 @@
+//! Tests of [`wgpu::Features::INDEX_FORMAT_UINT8`].
+
+use wgpu::*;
+use wgpu_test::{create_render_target, fail, run_render_pass, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main() -> @builtin(position) vec4f {
+        return vec4f(0.0);
+    }
+";
+
+fn create_index_buffer(device: &Device) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some("indices"),
+        size: 16,
+        usage: BufferUsages::INDEX,
+        mapped_at_creation: false,
+    })
+}
+
+fn run_pass(device: &Device, buffer: &Buffer, offset: BufferAddress) -> CommandBuffer {
+    let view = create_render_target(device);
+    run_render_pass(device, &view, |pass| {
+        pass.set_index_buffer(buffer.slice(offset..), IndexFormat::Uint8);
+    })
+}
+
+fn create_strip_pipeline(device: &Device) -> RenderPipeline {
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: PrimitiveState {
+            topology: PrimitiveTopology::TriangleStrip,
+            strip_index_format: Some(IndexFormat::Uint8),
+            ..Default::default()
+        },
+        depth_stencil: Some(DepthStencilState {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: true,
+            depth_compare: CompareFunction::Less,
+            stencil: StencilState::default(),
+            bias: DepthBiasState::default(),
+        }),
+        multisample: MultisampleState::default(),
+        fragment: None,
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[test]
+fn index_format_uint8_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let buffer = create_index_buffer(&device);
+
+    fail(
+        &device,
+        || run_pass(&device, &buffer, 0),
+        Some("INDEX_FORMAT_UINT8"),
+    );
+    fail(
+        &device,
+        || create_strip_pipeline(&device),
+        Some("INDEX_FORMAT_UINT8"),
+    );
+}
+
+#[test]
+fn index_format_uint8() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::INDEX_FORMAT_UINT8,
+        ..Default::default()
+    });
+    let buffer = create_index_buffer(&device);
+
+    // 8 bit indices don't need any alignment.
+    valid(&device, || run_pass(&device, &buffer, 3));
+    valid(&device, || create_strip_pipeline(&device));
+}
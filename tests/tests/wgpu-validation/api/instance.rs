@@ -41,6 +41,7 @@ mod request_adapter_error {
             flags: wgpu::InstanceFlags::default(),
             memory_budget_thresholds: wgpu::MemoryBudgetThresholds::default(),
             backend_options: wgpu::BackendOptions::default(),
+            toggles: wgpu::TogglesDescriptor::default(),
         }
     }
 
@@ -65,7 +66,8 @@ mod request_adapter_error {
             metal not requested, \
             dx12 not requested, \
             gl not requested, \
-            webgpu not requested"
+            webgpu not requested, \
+            cpu not requested"
         );
     }
 
@@ -81,7 +83,8 @@ mod request_adapter_error {
             metal not requested, \
             dx12 not requested, \
             gl not requested, \
-            webgpu not requested"
+            webgpu not requested, \
+            cpu not requested"
         );
     }
 
@@ -99,7 +102,8 @@ mod request_adapter_error {
             metal support not compiled in, \
             dx12 not requested, \
             gl not requested, \
-            webgpu not requested"
+            webgpu not requested, \
+            cpu not requested"
         );
 
         #[cfg(not(target_family = "wasm"))]
@@ -111,7 +115,8 @@ mod request_adapter_error {
             metal not requested, \
             dx12 not requested, \
             gl not requested, \
-            webgpu support not compiled in"
+            webgpu support not compiled in, \
+            cpu not requested"
         );
     }
 }
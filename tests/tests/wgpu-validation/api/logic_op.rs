@@ -0,0 +1,154 @@
+//! Tests of [`wgpu::Features::LOGIC_OP`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main() -> @builtin(position) vec4f {
+        return vec4f(0.0);
+    }
+
+    @fragment
+    fn fs_uint() -> @location(0) vec4<u32> {
+        return vec4<u32>(0xffu);
+    }
+
+    @fragment
+    fn fs_float() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+
+    struct Outputs {
+        @location(0) first: vec4<u32>,
+        @location(1) second: vec4<u32>,
+    }
+
+    @fragment
+    fn fs_two_targets() -> Outputs {
+        return Outputs(vec4<u32>(0xffu), vec4<u32>(0xffu));
+    }
+";
+
+fn logic_op_target(format: TextureFormat, logic_op: LogicOp) -> Option<ColorTargetState> {
+    Some(ColorTargetState {
+        logic_op: Some(logic_op),
+        ..format.into()
+    })
+}
+
+fn create_pipeline(
+    device: &Device,
+    fragment_entry_point: &str,
+    targets: &[Option<ColorTargetState>],
+) -> RenderPipeline {
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module: &module,
+            entry_point: Some(fragment_entry_point),
+            compilation_options: Default::default(),
+            targets,
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[test]
+fn logic_op_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                "fs_uint",
+                &[logic_op_target(TextureFormat::Rgba8Uint, LogicOp::Xor)],
+            )
+        },
+        Some("LOGIC_OP"),
+    );
+}
+
+#[test]
+fn logic_op_targets() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::LOGIC_OP,
+        ..Default::default()
+    });
+
+    valid(&device, || {
+        create_pipeline(
+            &device,
+            "fs_uint",
+            &[logic_op_target(TextureFormat::Rgba8Uint, LogicOp::Xor)],
+        )
+    });
+    valid(&device, || {
+        create_pipeline(
+            &device,
+            "fs_two_targets",
+            &[
+                logic_op_target(TextureFormat::Rgba8Uint, LogicOp::Or),
+                logic_op_target(TextureFormat::R32Uint, LogicOp::Or),
+            ],
+        )
+    });
+
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                "fs_float",
+                &[logic_op_target(TextureFormat::Rgba8Unorm, LogicOp::Xor)],
+            )
+        },
+        Some("doesn't support logic operations"),
+    );
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                "fs_float",
+                &[Some(ColorTargetState {
+                    blend: Some(BlendState::REPLACE),
+                    logic_op: Some(LogicOp::Xor),
+                    ..TextureFormat::Rgba8Unorm.into()
+                })],
+            )
+        },
+        Some("can't be combined with blending"),
+    );
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                "fs_two_targets",
+                &[
+                    logic_op_target(TextureFormat::Rgba8Uint, LogicOp::Xor),
+                    Some(TextureFormat::Rgba8Uint.into()),
+                ],
+            )
+        },
+        Some("must use the same logic operation"),
+    );
+}
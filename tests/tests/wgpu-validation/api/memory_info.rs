@@ -0,0 +1,62 @@
+//! Tests of [`wgpu::Adapter::memory_info`] and [`wgpu::Device::on_memory_budget_change`].
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+fn noop_adapter() -> wgpu::Adapter {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    pollster::block_on(instance.request_adapter(&Default::default())).unwrap()
+}
+
+/// The noop backend can't report memory info, so the callback is never called.
+#[test]
+fn unsupported_on_noop() {
+    let adapter = noop_adapter();
+    assert_eq!(adapter.memory_info(), None);
+
+    let (device, _queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    device.on_memory_budget_change({
+        let calls = Arc::clone(&calls);
+        move |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    device.poll(wgpu::PollType::Wait).unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn budget_differs_ignores_usage() {
+    let heap = |usage, budget| wgpu::MemoryHeapInfo {
+        usage,
+        budget,
+        device_local: true,
+    };
+    let info = wgpu::MemoryInfo {
+        heaps: vec![heap(10, 100)],
+    };
+
+    assert!(!info.budget_differs(&wgpu::MemoryInfo {
+        heaps: vec![heap(50, 100)],
+    }));
+    assert!(info.budget_differs(&wgpu::MemoryInfo {
+        heaps: vec![heap(10, 80)],
+    }));
+    assert!(info.budget_differs(&wgpu::MemoryInfo {
+        heaps: vec![heap(10, 100), heap(0, 100)],
+    }));
+}
@@ -0,0 +1,109 @@
+//! Tests of [`wgpu::Device::generate_memory_report`].
+
+use wgpu::{MemoryReportHeap, MemoryReportResourceType, ResourceTypeMemoryReport};
+
+fn create_buffer(
+    device: &wgpu::Device,
+    label: &str,
+    size: u64,
+    usage: wgpu::BufferUsages,
+) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+#[test]
+fn reports_live_resources() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    let _vertices = create_buffer(&device, "vertices", 1024, wgpu::BufferUsages::VERTEX);
+    let _readback = create_buffer(
+        &device,
+        "readback",
+        256,
+        wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+    );
+    let destroyed = create_buffer(&device, "destroyed", 4096, wgpu::BufferUsages::VERTEX);
+    destroyed.destroy();
+    let dropped = create_buffer(&device, "dropped", 4096, wgpu::BufferUsages::VERTEX);
+    drop(dropped);
+
+    let _texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("texture"),
+        size: wgpu::Extent3d {
+            width: 16,
+            height: 16,
+            depth_or_array_layers: 2,
+        },
+        mip_level_count: 2,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    // Two layers of 16x16 and 8x8 texels, 4 bytes each.
+    let texture_size = (16 * 16 + 8 * 8) * 2 * 4;
+
+    device.poll(wgpu::PollType::Wait).unwrap();
+
+    let summary = device.generate_memory_report(false).unwrap();
+    assert_eq!(
+        summary.buffers,
+        ResourceTypeMemoryReport {
+            count: 2,
+            bytes: 1024 + 256,
+        }
+    );
+    assert_eq!(
+        summary.textures,
+        ResourceTypeMemoryReport {
+            count: 1,
+            bytes: texture_size,
+        }
+    );
+    assert!(summary.resources.is_empty());
+
+    let report = device.generate_memory_report(true).unwrap();
+    assert_eq!(report.buffers, summary.buffers);
+    assert_eq!(report.textures, summary.textures);
+    let resources: Vec<_> = report
+        .resources
+        .iter()
+        .map(|resource| {
+            (
+                resource.resource_type,
+                resource.label.as_str(),
+                resource.size,
+                resource.heap,
+            )
+        })
+        .collect();
+    assert_eq!(
+        resources,
+        [
+            (
+                MemoryReportResourceType::Texture,
+                "texture",
+                texture_size,
+                MemoryReportHeap::DeviceLocal
+            ),
+            (
+                MemoryReportResourceType::Buffer,
+                "vertices",
+                1024,
+                MemoryReportHeap::DeviceLocal
+            ),
+            (
+                MemoryReportResourceType::Buffer,
+                "readback",
+                256,
+                MemoryReportHeap::HostVisible
+            ),
+        ]
+    );
+}
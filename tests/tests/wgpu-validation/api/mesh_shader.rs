@@ -0,0 +1,222 @@
+//! Tests of mesh shader pipelines and the draws that use them.
+
+use std::borrow::Cow;
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main() -> @builtin(position) vec4f {
+        return vec4f(0.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+";
+
+const TARGETS: &[Option<ColorTargetState>] = &[Some(ColorTargetState {
+    format: TextureFormat::Rgba8Unorm,
+    blend: None,
+    write_mask: ColorWrites::ALL,
+    logic_op: None,
+})];
+
+fn mesh_device() -> (Device, Queue) {
+    Device::noop(&DeviceDescriptor {
+        required_features: Features::EXPERIMENTAL_MESH_SHADER | Features::SPIRV_SHADER_PASSTHROUGH,
+        ..DeviceDescriptor::default()
+    })
+}
+
+/// A module standing in for one with a mesh entry point. The noop backend doesn't look
+/// at the SPIR-V, so only the magic number is provided.
+fn mesh_module(device: &Device) -> ShaderModule {
+    unsafe {
+        device.create_shader_module_passthrough(ShaderModuleDescriptorPassthrough::SpirV(
+            ShaderModuleDescriptorSpirV {
+                label: None,
+                source: Cow::Borrowed(&[0x0723_0203]),
+            },
+        ))
+    }
+}
+
+/// Mesh pipelines built from passthrough modules can't derive a layout, so they are
+/// given an empty one.
+fn mesh_pipeline_descriptor<'a>(
+    layout: &'a PipelineLayout,
+    mesh: &'a ShaderModule,
+    fragment: &'a ShaderModule,
+) -> MeshPipelineDescriptor<'a> {
+    MeshPipelineDescriptor {
+        label: None,
+        layout: Some(layout),
+        task: None,
+        mesh: MeshState {
+            module: mesh,
+            entry_point: Some("ms_main"),
+            compilation_options: Default::default(),
+        },
+        primitive: PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module: fragment,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: TARGETS,
+        }),
+        multiview: None,
+        cache: None,
+    }
+}
+
+fn draw_in_pass(device: &Device, pipeline: &RenderPipeline, draw: impl FnOnce(&mut RenderPass)) {
+    let target = device.create_texture(&TextureDescriptor {
+        label: None,
+        size: Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let view = target.create_view(&TextureViewDescriptor::default());
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    {
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            ..RenderPassDescriptor::default()
+        });
+        pass.set_pipeline(pipeline);
+        draw(&mut pass);
+    }
+    encoder.finish();
+}
+
+#[test]
+fn mesh_pipeline_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::SPIRV_SHADER_PASSTHROUGH,
+        ..DeviceDescriptor::default()
+    });
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor::default());
+    let mesh = mesh_module(&device);
+    let fragment = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+
+    fail(
+        &device,
+        || device.create_mesh_pipeline(&mesh_pipeline_descriptor(&layout, &mesh, &fragment)),
+        Some("EXPERIMENTAL_MESH_SHADER"),
+    );
+}
+
+#[test]
+fn draw_mesh_tasks() {
+    let (device, _queue) = mesh_device();
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor::default());
+    let mesh = mesh_module(&device);
+    let fragment = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    let pipeline = valid(&device, || {
+        device.create_mesh_pipeline(&mesh_pipeline_descriptor(&layout, &mesh, &fragment))
+    });
+
+    valid(&device, || {
+        draw_in_pass(&device, &pipeline, |pass| pass.draw_mesh_tasks(1, 1, 1))
+    });
+    fail(
+        &device,
+        || draw_in_pass(&device, &pipeline, |pass| pass.draw(0..3, 0..1)),
+        Some("is a mesh pipeline"),
+    );
+}
+
+#[test]
+fn draw_mesh_tasks_requires_mesh_pipeline() {
+    let (device, _queue) = mesh_device();
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module: &module,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: None,
+        cache: None,
+    });
+
+    fail(
+        &device,
+        || draw_in_pass(&device, &pipeline, |pass| pass.draw_mesh_tasks(1, 1, 1)),
+        Some("is not a mesh pipeline"),
+    );
+}
+
+#[test]
+fn draw_mesh_tasks_indirect_overrun() {
+    let (device, _queue) = mesh_device();
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor::default());
+    let mesh = mesh_module(&device);
+    let fragment = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    let pipeline =
+        device.create_mesh_pipeline(&mesh_pipeline_descriptor(&layout, &mesh, &fragment));
+    let indirect = device.create_buffer(&BufferDescriptor {
+        label: None,
+        size: 12,
+        usage: BufferUsages::INDIRECT,
+        mapped_at_creation: false,
+    });
+
+    valid(&device, || {
+        draw_in_pass(&device, &pipeline, |pass| {
+            pass.draw_mesh_tasks_indirect(&indirect, 0)
+        })
+    });
+    fail(
+        &device,
+        || {
+            draw_in_pass(&device, &pipeline, |pass| {
+                pass.draw_mesh_tasks_indirect(&indirect, 4)
+            })
+        },
+        Some("indirect"),
+    );
+}
@@ -1,6 +1,53 @@
+mod acceleration_structure;
 mod binding_arrays;
 mod buffer;
+mod buffer_allocator;
 mod buffer_slice;
+mod compilation_info;
+mod conditional_rendering;
+mod conservative_rasterization;
+mod debug_printf;
+mod depth_stencil_resolve;
+mod error;
 mod external_texture;
+mod framebuffer_fetch;
+mod headless_surface;
+mod index_format_uint8;
 mod instance;
+mod logic_op;
+mod memory_info;
+mod memory_report;
+mod mesh_shader;
+mod multi_queue;
+mod multi_viewport;
+mod multiview;
+mod naga_ir_cache;
+mod parallel_render_encoder;
+mod pipeline_statistics;
+mod portability;
+mod primitive_restart;
+mod ray_tracing_pipeline;
+mod render_bundle_inheritance;
+mod render_pass_suspend;
+mod reusable_command_buffer;
+mod shader_defines;
+mod shader_f64;
+mod shader_files;
+mod shader_optimization;
+mod shader_type;
+mod shared_fence;
+mod sparse_binding;
+mod submission_index;
 mod texture;
+mod texture_aliasing;
+mod texture_atomic;
+mod texture_resolve;
+mod timestamp_calibration;
+mod toggles;
+mod transient_attachment;
+mod trim_memory;
+mod variable_rate_shading;
+mod vertex_instance_step_rate;
+mod video;
+mod wide_lines;
+mod workgroup_memory;
@@ -0,0 +1,175 @@
+//! Tests of [`wgpu::Features::EXPERIMENTAL_MULTI_QUEUE`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+fn noop_adapter() -> Adapter {
+    let instance = Instance::new(&InstanceDescriptor {
+        backends: Backends::NOOP,
+        backend_options: BackendOptions {
+            noop: NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    pollster::block_on(instance.request_adapter(&RequestAdapterOptions::default())).unwrap()
+}
+
+/// Returns the device, its main queue, and one additional queue of each type in `queue_types`.
+fn multi_queue_device(queue_types: &[QueueType]) -> (Device, Queue, Vec<Queue>) {
+    let queues = queue_types
+        .iter()
+        .map(|&queue_type| QueueDescriptor {
+            label: None,
+            queue_type,
+        })
+        .collect::<Vec<_>>();
+    pollster::block_on(noop_adapter().request_device_with_queues(
+        &DeviceDescriptor {
+            required_features: Features::EXPERIMENTAL_MULTI_QUEUE,
+            ..Default::default()
+        },
+        &queues,
+    ))
+    .unwrap()
+}
+
+fn create_buffer(device: &Device, usage: BufferUsages) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: None,
+        size: 16,
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+#[test]
+fn additional_queues_require_feature() {
+    let adapter = noop_adapter();
+    pollster::block_on(adapter.request_device_with_queues(
+        &DeviceDescriptor::default(),
+        &[QueueDescriptor {
+            label: None,
+            queue_type: QueueType::Compute,
+        }],
+    ))
+    .unwrap_err();
+}
+
+#[test]
+fn one_queue_per_descriptor() {
+    let (_device, _queue, additional_queues) =
+        multi_queue_device(&[QueueType::Compute, QueueType::Transfer, QueueType::Graphics]);
+    assert_eq!(additional_queues.len(), 3);
+}
+
+#[test]
+fn command_buffer_submitted_to_other_queue() {
+    let (device, queue, additional_queues) = multi_queue_device(&[QueueType::Compute]);
+    let compute_queue = &additional_queues[0];
+
+    let encoder = device.create_command_encoder_for_queue(compute_queue, &Default::default());
+    let command_buffer = encoder.finish();
+    fail(
+        &device,
+        || queue.submit([command_buffer]),
+        Some("was not encoded for this queue"),
+    );
+
+    let encoder = device.create_command_encoder(&Default::default());
+    let command_buffer = encoder.finish();
+    fail(
+        &device,
+        || compute_queue.submit([command_buffer]),
+        Some("was not encoded for this queue"),
+    );
+
+    let encoder = device.create_command_encoder_for_queue(compute_queue, &Default::default());
+    let command_buffer = encoder.finish();
+    valid(&device, || compute_queue.submit([command_buffer]));
+}
+
+#[test]
+fn passes_on_unsupported_queue() {
+    let (device, _queue, additional_queues) =
+        multi_queue_device(&[QueueType::Compute, QueueType::Transfer]);
+    let (compute_queue, transfer_queue) = (&additional_queues[0], &additional_queues[1]);
+
+    let mut encoder = device.create_command_encoder_for_queue(compute_queue, &Default::default());
+    encoder.begin_compute_pass(&Default::default());
+    valid(&device, || encoder.finish());
+
+    let texture = device.create_texture(&TextureDescriptor {
+        label: None,
+        size: Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&Default::default());
+    let mut encoder = device.create_command_encoder_for_queue(compute_queue, &Default::default());
+    encoder.begin_render_pass(&RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &view,
+            depth_slice: None,
+            resolve_target: None,
+            ops: Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        flags: RenderPassFlags::empty(),
+        multiview: None,
+    });
+    fail(
+        &device,
+        || encoder.finish(),
+        Some("render passes can't be encoded for a compute queue"),
+    );
+
+    let mut encoder = device.create_command_encoder_for_queue(transfer_queue, &Default::default());
+    encoder.begin_compute_pass(&Default::default());
+    fail(
+        &device,
+        || encoder.finish(),
+        Some("compute passes can't be encoded for a transfer queue"),
+    );
+}
+
+#[test]
+fn copy_across_queues() {
+    let (device, queue, additional_queues) = multi_queue_device(&[QueueType::Transfer]);
+    let transfer_queue = &additional_queues[0];
+
+    let data = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+    let upload = create_buffer(&device, BufferUsages::COPY_SRC | BufferUsages::COPY_DST);
+    let readback = create_buffer(&device, BufferUsages::COPY_DST | BufferUsages::MAP_READ);
+
+    transfer_queue.write_buffer(&upload, 0, &data);
+    let upload_index = transfer_queue.submit([]);
+
+    queue.wait_for_submission(&upload_index);
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_buffer_to_buffer(&upload, 0, &readback, 0, 16);
+    let index = queue.submit([encoder.finish()]);
+
+    readback
+        .slice(..)
+        .map_async(MapMode::Read, |result| result.unwrap());
+    device
+        .poll(PollType::WaitForSubmissionIndex(index))
+        .unwrap();
+    assert_eq!(*readback.slice(..).get_mapped_range(), data);
+}
@@ -0,0 +1,120 @@
+//! Tests of [`wgpu::Features::MULTI_VIEWPORT`].
+
+use wgpu::*;
+use wgpu_test::{
+    color_attachment, create_layered_render_target, fail, run_render_pass_with, valid,
+};
+
+const SHADER: &str = "
+    struct VertexOutput {
+        @builtin(position) position: vec4f,
+        @builtin(viewport_index) viewport: u32,
+        @builtin(layer) layer: u32,
+    }
+
+    @vertex
+    fn vs_main(@builtin(instance_index) instance: u32) -> VertexOutput {
+        return VertexOutput(vec4f(0.0), instance % 2u, instance);
+    }
+";
+
+fn run_pass(device: &Device, views: &[&TextureView], viewports: &[Viewport]) -> CommandBuffer {
+    let color_attachments = views
+        .iter()
+        .map(|&view| color_attachment(view))
+        .collect::<Vec<_>>();
+    let desc = RenderPassDescriptor {
+        color_attachments: &color_attachments,
+        ..Default::default()
+    };
+    run_render_pass_with(device, &desc, |pass| {
+        if !viewports.is_empty() {
+            pass.set_viewports(viewports);
+        }
+    })
+}
+
+fn viewports(count: usize) -> Vec<Viewport> {
+    (0..count)
+        .map(|i| Viewport {
+            x: i as f32,
+            y: 0.0,
+            width: 32.0,
+            height: 32.0,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })
+        .collect()
+}
+
+#[test]
+fn multi_viewport_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let view = create_layered_render_target(&device, 1);
+
+    fail(
+        &device,
+        || run_pass(&device, &[&view], &viewports(2)),
+        Some("MULTI_VIEWPORT"),
+    );
+    fail(
+        &device,
+        || {
+            device.create_shader_module(ShaderModuleDescriptor {
+                label: None,
+                source: ShaderSource::Wgsl(SHADER.into()),
+            })
+        },
+        Some("MULTI_VIEWPORT"),
+    );
+
+    let cube = create_layered_render_target(&device, 6);
+    fail(
+        &device,
+        || run_pass(&device, &[&cube], &[]),
+        Some("is not renderable"),
+    );
+}
+
+#[test]
+fn set_viewports() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::MULTI_VIEWPORT,
+        ..Default::default()
+    });
+    let view = create_layered_render_target(&device, 1);
+
+    valid(&device, || {
+        run_pass(&device, &[&view], &viewports(MAX_VIEWPORTS as usize))
+    });
+    fail(
+        &device,
+        || run_pass(&device, &[&view], &viewports(MAX_VIEWPORTS as usize + 1)),
+        Some("Viewport index 16 is not less than the maximum of 16 viewports"),
+    );
+
+    valid(&device, || {
+        device.create_shader_module(ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Wgsl(SHADER.into()),
+        })
+    });
+}
+
+#[test]
+fn layered_attachments() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::MULTI_VIEWPORT,
+        ..Default::default()
+    });
+    let cube = create_layered_render_target(&device, 6);
+    let other_cube = create_layered_render_target(&device, 6);
+    let single = create_layered_render_target(&device, 1);
+
+    valid(&device, || run_pass(&device, &[&cube, &other_cube], &[]));
+    fail(
+        &device,
+        || run_pass(&device, &[&cube, &single], &[]),
+        Some("has 1 array layers, but the first attachment of the render pass has 6"),
+    );
+}
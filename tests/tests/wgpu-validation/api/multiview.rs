@@ -0,0 +1,123 @@
+//! Tests of [`wgpu::RenderPassDescriptor::multiview`].
+
+use core::num::NonZeroU32;
+
+use wgpu_test::{
+    color_attachment, create_layered_render_target, fail, run_render_pass_with, valid,
+};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main(@builtin(view_index) view: i32) -> @builtin(position) vec4f {
+        return vec4f(f32(view), 0.0, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+";
+
+fn run_pass(
+    device: &wgpu::Device,
+    view: &wgpu::TextureView,
+    multiview: Option<NonZeroU32>,
+    pipeline: Option<&wgpu::RenderPipeline>,
+) -> wgpu::CommandBuffer {
+    let desc = wgpu::RenderPassDescriptor {
+        color_attachments: &[color_attachment(view)],
+        multiview,
+        ..Default::default()
+    };
+    run_render_pass_with(device, &desc, |pass| {
+        if let Some(pipeline) = pipeline {
+            pass.set_pipeline(pipeline);
+        }
+    })
+}
+
+#[test]
+fn multiview_requires_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let view = create_layered_render_target(&device, 1);
+
+    fail(
+        &device,
+        || run_pass(&device, &view, NonZeroU32::new(2), None),
+        Some("MULTIVIEW"),
+    );
+}
+
+#[test]
+fn multiview_attachment_layers() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::MULTIVIEW,
+        ..Default::default()
+    });
+    let single = create_layered_render_target(&device, 1);
+    let double = create_layered_render_target(&device, 2);
+
+    valid(&device, || run_pass(&device, &single, None, None));
+    valid(&device, || {
+        run_pass(&device, &double, NonZeroU32::new(2), None)
+    });
+
+    // Without multiview, array attachments are used for layered rendering.
+    valid(&device, || run_pass(&device, &double, None, None));
+
+    fail(
+        &device,
+        || run_pass(&device, &single, NonZeroU32::new(2), None),
+        Some("has 1 array layers, but the render pass has a view count of 2"),
+    );
+    fail(
+        &device,
+        || run_pass(&device, &double, NonZeroU32::new(3), None),
+        Some("has 2 array layers, but the render pass has a view count of 3"),
+    );
+}
+
+#[test]
+fn multiview_pipeline() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::MULTIVIEW,
+        ..Default::default()
+    });
+    let view = create_layered_render_target(&device, 2);
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+    });
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: NonZeroU32::new(2),
+        cache: None,
+    });
+
+    valid(&device, || {
+        run_pass(&device, &view, NonZeroU32::new(2), Some(&pipeline))
+    });
+
+    let single = create_layered_render_target(&device, 1);
+    fail(
+        &device,
+        || run_pass(&device, &single, None, Some(&pipeline)),
+        Some("Incompatible multiview setting"),
+    );
+}
@@ -0,0 +1,70 @@
+//! Tests of [`wgpu::Device::create_shader_module_from_naga_ir`].
+
+use wgpu::naga::{cache, front::wgsl, valid};
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+fn cached_module(source: &str) -> Vec<u8> {
+    let module = wgsl::parse_str(source).unwrap();
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .unwrap();
+    cache::to_bytes(&module, &info)
+}
+
+#[test]
+fn create_shader_module_from_naga_ir() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    let ir = cached_module(
+        "
+        @group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+        @compute @workgroup_size(64)
+        fn main(@builtin(global_invocation_id) id: vec3u) {
+            data[id.x] *= 2.0;
+        }
+        ",
+    );
+    let module = valid(&device, || {
+        device
+            .create_shader_module_from_naga_ir(Some("cached"), &ir)
+            .unwrap()
+    });
+
+    valid(&device, || {
+        device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        })
+    });
+}
+
+#[test]
+fn create_shader_module_from_invalid_naga_ir() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    let error = device
+        .create_shader_module_from_naga_ir(None, b"@compute fn main() {}")
+        .err()
+        .unwrap();
+    assert!(matches!(error, cache::CacheError::NotACachedModule));
+}
+
+#[test]
+fn create_shader_module_from_naga_ir_unsupported_by_device() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    // The module is valid for Naga, but uses a feature the device doesn't have, so it is
+    // still rejected by wgpu's validation.
+    let ir = cached_module("enable f16; const X: f16 = 1.0h;");
+    fail(
+        &device,
+        || device.create_shader_module_from_naga_ir(None, &ir).unwrap(),
+        None,
+    );
+}
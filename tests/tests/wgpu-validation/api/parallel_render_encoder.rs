@@ -0,0 +1,208 @@
+//! Tests of [`wgpu::RenderPass::create_parallel_encoder`].
+
+use wgpu::*;
+use wgpu_test::{create_render_target, fail, run_render_pass, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+        return vec4f(f32(index), 0.0, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+";
+
+fn device_descriptor() -> DeviceDescriptor<'static> {
+    DeviceDescriptor {
+        required_features: Features::PARALLEL_RENDER_ENCODING,
+        ..Default::default()
+    }
+}
+
+fn create_pipeline(device: &Device) -> RenderPipeline {
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: Some("pipeline"),
+        layout: None,
+        vertex: VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive: PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module: &module,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[test]
+fn encode_on_threads() {
+    let (device, queue) = Device::noop_recording(&device_descriptor());
+    let view = create_render_target(&device);
+    let pipeline = create_pipeline(&device);
+
+    let command_buffer = valid(&device, || {
+        run_render_pass(&device, &view, |pass| {
+            pass.set_pipeline(&pipeline);
+            pass.draw(0..3, 0..1);
+            let encoders = [
+                pass.create_parallel_encoder(),
+                pass.create_parallel_encoder(),
+            ];
+            pass.set_pipeline(&pipeline);
+            pass.draw(0..3, 0..4);
+
+            std::thread::scope(|scope| {
+                for (instances, mut encoder) in (2..).zip(encoders) {
+                    let pipeline = &pipeline;
+                    scope.spawn(move || {
+                        encoder.set_pipeline(pipeline);
+                        encoder.draw(0..3, 0..instances);
+                    });
+                }
+            });
+        })
+    });
+    queue.submit([command_buffer]);
+
+    let recorded = queue.take_recorded_command_buffers();
+    let parts = recorded
+        .iter()
+        .flat_map(|cmd_buf| &cmd_buf.commands)
+        .find_map(|command| match command {
+            RecordedCommand::ExecuteParallelEncoders(parts) => Some(parts),
+            _ => None,
+        })
+        .expect("the pass executes parallel encoders");
+    // The commands before the parallel encoders and after them are parts too.
+    let draws: Vec<_> = parts
+        .iter()
+        .map(|part| {
+            assert!(part.contains(&RecordedCommand::SetRenderPipeline {
+                pipeline: Some("pipeline".into()),
+            }));
+            part.iter()
+                .filter_map(|command| match command {
+                    RecordedCommand::Draw { instances, .. } => Some(instances.end),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(draws, [[1], [2], [3], [4]]);
+}
+
+#[test]
+fn state_is_not_inherited() {
+    let (device, _queue) = Device::noop(&device_descriptor());
+    let view = create_render_target(&device);
+    let pipeline = create_pipeline(&device);
+
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.set_pipeline(&pipeline);
+                pass.create_parallel_encoder().draw(0..3, 0..1);
+            })
+        },
+        Some("Pipeline must be set"),
+    );
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.set_pipeline(&pipeline);
+                drop(pass.create_parallel_encoder());
+                pass.draw(0..3, 0..1);
+            })
+        },
+        Some("Pipeline must be set"),
+    );
+}
+
+#[test]
+fn requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let view = create_render_target(&device);
+
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                drop(pass.create_parallel_encoder());
+            })
+        },
+        Some("PARALLEL_RENDER_ENCODING"),
+    );
+}
+
+#[test]
+fn unended_encoder() {
+    let (device, _queue) = Device::noop(&device_descriptor());
+    let view = create_render_target(&device);
+
+    let mut encoder = None;
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                encoder = Some(pass.create_parallel_encoder());
+            })
+        },
+        Some("A parallel encoder of the render pass was not ended before the pass"),
+    );
+}
+
+#[test]
+fn nested_encoder() {
+    let (device, _queue) = Device::noop(&device_descriptor());
+    let view = create_render_target(&device);
+
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.create_parallel_encoder().create_parallel_encoder();
+            })
+        },
+        Some("A parallel encoder can't create parallel encoders of its own"),
+    );
+}
+
+#[test]
+fn suspended_pass() {
+    let (device, _queue) = Device::noop(&device_descriptor());
+    let view = create_render_target(&device);
+
+    fail(
+        &device,
+        || {
+            let mut encoder = device.create_command_encoder(&Default::default());
+            let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                color_attachments: &[wgpu_test::color_attachment(&view)],
+                flags: RenderPassFlags::SUSPENDING,
+                ..Default::default()
+            });
+            drop(pass.create_parallel_encoder());
+            drop(pass);
+            encoder.finish()
+        },
+        Some("can't create parallel encoders"),
+    );
+}
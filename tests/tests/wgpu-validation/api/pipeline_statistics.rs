@@ -0,0 +1,201 @@
+//! Tests of pass-level and in-pass pipeline statistics queries.
+
+use wgpu::*;
+use wgpu_test::{
+    color_attachment, create_render_target, fail, run_render_pass, run_render_pass_with, valid,
+};
+
+fn create_query_set(device: &Device) -> QuerySet {
+    device.create_query_set(&QuerySetDescriptor {
+        label: None,
+        ty: QueryType::PipelineStatistics(
+            PipelineStatisticsTypes::VERTEX_SHADER_INVOCATIONS
+                | PipelineStatisticsTypes::FRAGMENT_SHADER_INVOCATIONS,
+        ),
+        count: 2,
+    })
+}
+
+fn run_compute_pass(
+    device: &Device,
+    pipeline_statistics_query: Option<PassPipelineStatisticsQuery<'_>>,
+    f: impl FnOnce(&mut ComputePass<'_>),
+) -> CommandBuffer {
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+        pipeline_statistics_query,
+        ..Default::default()
+    });
+    f(&mut pass);
+    drop(pass);
+    encoder.finish()
+}
+
+#[test]
+fn pass_query_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::TIMESTAMP_QUERY,
+        ..Default::default()
+    });
+    // A query set of another type can be created without the feature.
+    let query_set = device.create_query_set(&QuerySetDescriptor {
+        label: None,
+        ty: QueryType::Timestamp,
+        count: 2,
+    });
+
+    fail(
+        &device,
+        || {
+            run_compute_pass(
+                &device,
+                Some(PassPipelineStatisticsQuery {
+                    query_set: &query_set,
+                    query_index: 0,
+                }),
+                |_| {},
+            )
+        },
+        Some("PIPELINE_STATISTICS_QUERY"),
+    );
+}
+
+#[test]
+fn pass_queries() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::PIPELINE_STATISTICS_QUERY,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let query_set = create_query_set(&device);
+    let query = |query_index| {
+        Some(PassPipelineStatisticsQuery {
+            query_set: &query_set,
+            query_index,
+        })
+    };
+
+    valid(&device, || {
+        run_render_pass_with(
+            &device,
+            &RenderPassDescriptor {
+                color_attachments: &[color_attachment(&view)],
+                pipeline_statistics_query: query(0),
+                ..Default::default()
+            },
+            |_| {},
+        )
+    });
+    valid(&device, || run_compute_pass(&device, query(1), |_| {}));
+    fail(
+        &device,
+        || run_compute_pass(&device, query(2), |_| {}),
+        Some("out of bounds"),
+    );
+}
+
+#[test]
+fn inside_passes_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::PIPELINE_STATISTICS_QUERY,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let query_set = create_query_set(&device);
+
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.begin_pipeline_statistics_query(&query_set, 0);
+                pass.end_pipeline_statistics_query();
+            })
+        },
+        Some("PIPELINE_STATISTICS_QUERY_INSIDE_PASSES"),
+    );
+    fail(
+        &device,
+        || {
+            run_compute_pass(&device, None, |pass| {
+                pass.begin_pipeline_statistics_query(&query_set, 0);
+                pass.end_pipeline_statistics_query();
+            })
+        },
+        Some("PIPELINE_STATISTICS_QUERY_INSIDE_PASSES"),
+    );
+}
+
+#[test]
+#[should_panic = "PIPELINE_STATISTICS_QUERY_INSIDE_PASSES"]
+fn inside_bundles_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::PIPELINE_STATISTICS_QUERY,
+        ..Default::default()
+    });
+    let query_set = create_query_set(&device);
+
+    let mut encoder = device.create_render_bundle_encoder(&RenderBundleEncoderDescriptor {
+        color_formats: &[Some(TextureFormat::Rgba8Unorm)],
+        sample_count: 1,
+        ..Default::default()
+    });
+    encoder.begin_pipeline_statistics_query(&query_set, 0);
+    encoder.end_pipeline_statistics_query();
+    encoder.finish(&RenderBundleDescriptor::default());
+}
+
+#[test]
+fn inside_pass_query() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::PIPELINE_STATISTICS_QUERY
+            | Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let query_set = create_query_set(&device);
+
+    valid(&device, || {
+        run_render_pass(&device, &view, |pass| {
+            pass.begin_pipeline_statistics_query(&query_set, 0);
+            pass.end_pipeline_statistics_query();
+        })
+    });
+    fail(
+        &device,
+        || {
+            run_render_pass_with(
+                &device,
+                &RenderPassDescriptor {
+                    color_attachments: &[color_attachment(&view)],
+                    pipeline_statistics_query: Some(PassPipelineStatisticsQuery {
+                        query_set: &query_set,
+                        query_index: 0,
+                    }),
+                    ..Default::default()
+                },
+                |pass| {
+                    pass.begin_pipeline_statistics_query(&query_set, 1);
+                    pass.end_pipeline_statistics_query();
+                },
+            )
+        },
+        Some("was started in a pass whose statistics are recorded by query 0"),
+    );
+    fail(
+        &device,
+        || {
+            run_compute_pass(
+                &device,
+                Some(PassPipelineStatisticsQuery {
+                    query_set: &query_set,
+                    query_index: 0,
+                }),
+                |pass| {
+                    pass.begin_pipeline_statistics_query(&query_set, 1);
+                    pass.end_pipeline_statistics_query();
+                },
+            )
+        },
+        Some("was started in a pass whose statistics are recorded by query 0"),
+    );
+}
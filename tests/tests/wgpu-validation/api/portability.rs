@@ -0,0 +1,50 @@
+//! Tests of [`wgpu::InstanceFlags::STRICT_WEBGPU_PORTABILITY`].
+
+fn strict_adapter() -> wgpu::Adapter {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        flags: wgpu::InstanceFlags::STRICT_WEBGPU_PORTABILITY,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default())).unwrap()
+}
+
+#[test]
+fn webgpu_device_allowed() {
+    let adapter = strict_adapter();
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::TIMESTAMP_QUERY,
+        ..Default::default()
+    }))
+    .unwrap();
+}
+
+#[test]
+fn native_only_feature_rejected() {
+    let adapter = strict_adapter();
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::PUSH_CONSTANTS,
+        ..Default::default()
+    }))
+    .unwrap_err();
+}
+
+#[test]
+fn native_only_limit_rejected() {
+    let adapter = strict_adapter();
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        required_limits: wgpu::Limits {
+            max_push_constant_size: 128,
+            ..Default::default()
+        },
+        ..Default::default()
+    }))
+    .unwrap_err();
+}
@@ -0,0 +1,151 @@
+//! Tests of [`wgpu::PrimitiveState::primitive_restart`].
+
+use wgpu::*;
+use wgpu_test::{create_render_target, fail, run_render_pass, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+        return vec4f(f32(index), 0.0, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+";
+
+fn create_pipeline(device: &Device, primitive: PrimitiveState) -> RenderPipeline {
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive,
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module: &module,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn strip(strip_index_format: Option<IndexFormat>, primitive_restart: bool) -> PrimitiveState {
+    PrimitiveState {
+        topology: PrimitiveTopology::TriangleStrip,
+        strip_index_format,
+        primitive_restart,
+        ..Default::default()
+    }
+}
+
+fn draw(device: &Device, pipeline: &RenderPipeline, indexed: bool) -> CommandBuffer {
+    let view = create_render_target(device);
+    let index_buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("indices"),
+        size: 16,
+        usage: BufferUsages::INDEX,
+        mapped_at_creation: false,
+    });
+
+    run_render_pass(device, &view, |pass| {
+        pass.set_pipeline(pipeline);
+        if indexed {
+            pass.set_index_buffer(index_buffer.slice(..), IndexFormat::Uint16);
+            pass.draw_indexed(0..4, 0, 0..1);
+        } else {
+            pass.draw(0..4, 0..1);
+        }
+    })
+}
+
+#[test]
+fn primitive_restart_requires_strip_index_format() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    let restart = valid(&device, || create_pipeline(&device, strip(None, true)));
+    valid(&device, || draw(&device, &restart, false));
+    fail(
+        &device,
+        || draw(&device, &restart, true),
+        Some("needs a `strip_index_format` to be used with indexed draws"),
+    );
+
+    let restart_uint16 = valid(&device, || {
+        create_pipeline(&device, strip(Some(IndexFormat::Uint16), true))
+    });
+    valid(&device, || draw(&device, &restart_uint16, true));
+}
+
+#[test]
+#[should_panic = "needs a `strip_index_format` to be used with indexed draws"]
+fn primitive_restart_requires_strip_index_format_in_bundles() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let restart = create_pipeline(&device, strip(None, true));
+    let index_buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("indices"),
+        size: 16,
+        usage: BufferUsages::INDEX,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_render_bundle_encoder(&RenderBundleEncoderDescriptor {
+        color_formats: &[Some(TextureFormat::Rgba8Unorm)],
+        sample_count: 1,
+        ..Default::default()
+    });
+    encoder.set_pipeline(&restart);
+    encoder.set_index_buffer(index_buffer.slice(..), IndexFormat::Uint16);
+    encoder.draw_indexed(0..4, 0, 0..1);
+    encoder.finish(&RenderBundleDescriptor::default());
+}
+
+#[test]
+fn disabling_primitive_restart_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || create_pipeline(&device, strip(None, false)),
+        Some("PRIMITIVE_RESTART_CONTROL"),
+    );
+    // Primitive restart has no effect on list topologies.
+    valid(&device, || {
+        create_pipeline(
+            &device,
+            PrimitiveState {
+                primitive_restart: false,
+                ..Default::default()
+            },
+        )
+    });
+}
+
+#[test]
+fn disable_primitive_restart() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::PRIMITIVE_RESTART_CONTROL,
+        ..Default::default()
+    });
+
+    let no_restart = valid(&device, || create_pipeline(&device, strip(None, false)));
+    valid(&device, || draw(&device, &no_restart, true));
+
+    let no_restart_uint16 = valid(&device, || {
+        create_pipeline(&device, strip(Some(IndexFormat::Uint16), false))
+    });
+    valid(&device, || draw(&device, &no_restart_uint16, true));
+}
@@ -0,0 +1,321 @@
+//! Tests of ray tracing pipelines, shader binding tables and `trace_rays`.
+
+use std::borrow::Cow;
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const STAGES: &[(ShaderStages, &str)] = &[
+    (ShaderStages::RAY_GENERATION, "rgen"),
+    (ShaderStages::MISS, "miss"),
+    (ShaderStages::CLOSEST_HIT, "chit"),
+];
+
+const GROUPS: &[RayTracingShaderGroup] = &[
+    RayTracingShaderGroup::General { stage: 0 },
+    RayTracingShaderGroup::General { stage: 1 },
+    RayTracingShaderGroup::TrianglesHit {
+        closest_hit: Some(2),
+        any_hit: None,
+    },
+];
+
+fn ray_tracing_device() -> (Device, Queue) {
+    Device::noop(&DeviceDescriptor {
+        required_features: Features::EXPERIMENTAL_RAY_TRACING_PIPELINE
+            | Features::SPIRV_SHADER_PASSTHROUGH,
+        required_limits: Limits::default().using_minimum_supported_ray_tracing_pipeline_values(),
+        ..DeviceDescriptor::default()
+    })
+}
+
+/// A module standing in for one with ray tracing entry points. The noop backend doesn't
+/// look at the SPIR-V, so only the magic number is provided.
+fn ray_tracing_module(device: &Device) -> ShaderModule {
+    unsafe {
+        device.create_shader_module_passthrough(ShaderModuleDescriptorPassthrough::SpirV(
+            ShaderModuleDescriptorSpirV {
+                label: None,
+                source: Cow::Borrowed(&[0x0723_0203]),
+            },
+        ))
+    }
+}
+
+fn create_pipeline(
+    device: &Device,
+    groups: &[RayTracingShaderGroup],
+    max_recursion_depth: u32,
+) -> RayTracingPipeline {
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor::default());
+    create_pipeline_with_layout(device, &layout, groups, max_recursion_depth)
+}
+
+fn create_pipeline_with_layout(
+    device: &Device,
+    layout: &PipelineLayout,
+    groups: &[RayTracingShaderGroup],
+    max_recursion_depth: u32,
+) -> RayTracingPipeline {
+    let module = ray_tracing_module(device);
+    let stages = STAGES
+        .iter()
+        .map(|&(stage, entry_point)| RayTracingStage {
+            stage,
+            module: &module,
+            entry_point,
+        })
+        .collect::<Vec<_>>();
+
+    device.create_ray_tracing_pipeline(&RayTracingPipelineDescriptor {
+        label: None,
+        layout,
+        stages: &stages,
+        groups,
+        max_recursion_depth,
+        cache: None,
+    })
+}
+
+fn create_table(device: &Device, pipeline: &RayTracingPipeline) -> ShaderBindingTable {
+    device.create_shader_binding_table(&ShaderBindingTableDescriptor {
+        label: None,
+        pipeline,
+        ray_generation: 0,
+        miss: &[1],
+        hit: &[2],
+        callable: &[],
+    })
+}
+
+fn trace(
+    device: &Device,
+    pipeline: &RayTracingPipeline,
+    bind_groups: &[(&BindGroup, &[DynamicOffset])],
+    table: &ShaderBindingTable,
+) {
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.trace_rays(&TraceRaysDescriptor {
+        label: None,
+        pipeline,
+        bind_groups,
+        push_constants: &[],
+        shader_binding_table: table,
+        width: 4,
+        height: 4,
+        depth: 1,
+    });
+    encoder.finish();
+}
+
+#[test]
+fn ray_tracing_pipeline_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::SPIRV_SHADER_PASSTHROUGH,
+        ..DeviceDescriptor::default()
+    });
+
+    fail(
+        &device,
+        || create_pipeline(&device, GROUPS, 1),
+        Some("EXPERIMENTAL_RAY_TRACING_PIPELINE"),
+    );
+}
+
+#[test]
+fn trace_rays() {
+    let (device, _queue) = ray_tracing_device();
+    let pipeline = valid(&device, || create_pipeline(&device, GROUPS, 1));
+    let table = valid(&device, || create_table(&device, &pipeline));
+
+    let layout = table.layout();
+    assert_eq!(layout.ray_generation.size, layout.ray_generation.stride);
+    assert_eq!(layout.miss.size, layout.miss.stride);
+    assert_eq!(layout.callable, ShaderBindingTableRegion::default());
+
+    valid(&device, || trace(&device, &pipeline, &[], &table));
+}
+
+#[test]
+fn trace_rays_binds_bind_groups() {
+    let (device, _queue) = ray_tracing_device();
+    let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::RAY_GENERATION,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: BufferUsages::UNIFORM,
+        mapped_at_creation: false,
+    });
+    let bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: None,
+        layout: &bind_group_layout,
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    });
+    let pipeline = create_pipeline_with_layout(&device, &layout, GROUPS, 1);
+    let table = create_table(&device, &pipeline);
+
+    valid(&device, || {
+        trace(&device, &pipeline, &[(&bind_group, &[])], &table)
+    });
+    fail(
+        &device,
+        || trace(&device, &pipeline, &[], &table),
+        Some("expects a BindGroup to be set at index 0"),
+    );
+}
+
+#[test]
+fn ray_tracing_pipeline_group_stage_mismatch() {
+    let (device, _queue) = ray_tracing_device();
+
+    // Stage 2 is a closest hit stage, which can't make up a general group.
+    fail(
+        &device,
+        || create_pipeline(&device, &[RayTracingShaderGroup::General { stage: 2 }], 1),
+        Some("refers to stage 2"),
+    );
+    fail(
+        &device,
+        || create_pipeline(&device, &[RayTracingShaderGroup::General { stage: 3 }], 1),
+        Some("refers to stage 3"),
+    );
+}
+
+#[test]
+fn ray_tracing_pipeline_too_deep_recursion() {
+    let (device, _queue) = ray_tracing_device();
+
+    fail(
+        &device,
+        || create_pipeline(&device, GROUPS, 2),
+        Some("exceeds the limit 1"),
+    );
+}
+
+#[test]
+fn shader_binding_table_group_kind_mismatch() {
+    let (device, _queue) = ray_tracing_device();
+    let pipeline = create_pipeline(&device, GROUPS, 1);
+
+    fail(
+        &device,
+        || {
+            device.create_shader_binding_table(&ShaderBindingTableDescriptor {
+                label: None,
+                pipeline: &pipeline,
+                ray_generation: 1,
+                miss: &[],
+                hit: &[],
+                callable: &[],
+            })
+        },
+        Some("can't be used as a ray generation record"),
+    );
+}
+
+const WGSL_RAY_TRACING_SHADER: &str = "
+enable wgpu_ray_tracing_pipeline;
+
+@group(0) @binding(0)
+var<storage, read_write> output: array<u32>;
+
+struct Payload {
+    value: u32,
+}
+
+var<ray_payload> payload: Payload;
+
+var<incoming_ray_payload> incoming: Payload;
+
+@ray_generation
+fn rgen(@builtin(ray_launch_id) id: vec3<u32>) {
+    output[id.x] = payload.value;
+}
+
+@miss
+fn miss() {
+    incoming.value = 0u;
+}
+
+@closest_hit
+fn chit(@builtin(hit_kind) hit_kind: u32) {
+    incoming.value = hit_kind;
+}
+";
+
+fn create_wgsl_pipeline(device: &Device, layout: &PipelineLayout) -> RayTracingPipeline {
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(WGSL_RAY_TRACING_SHADER.into()),
+    });
+    let stages = STAGES
+        .iter()
+        .map(|&(stage, entry_point)| RayTracingStage {
+            stage,
+            module: &module,
+            entry_point,
+        })
+        .collect::<Vec<_>>();
+
+    device.create_ray_tracing_pipeline(&RayTracingPipelineDescriptor {
+        label: None,
+        layout,
+        stages: &stages,
+        groups: GROUPS,
+        max_recursion_depth: 1,
+        cache: None,
+    })
+}
+
+#[test]
+fn wgsl_ray_tracing_pipeline() {
+    let (device, _queue) = ray_tracing_device();
+    let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::RAY_GENERATION,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+
+    valid(&device, || create_wgsl_pipeline(&device, &layout));
+
+    // The ray generation stage uses a binding the layout doesn't provide.
+    let empty_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor::default());
+    fail(
+        &device,
+        || create_wgsl_pipeline(&device, &empty_layout),
+        Some("error matching stage 0"),
+    );
+}
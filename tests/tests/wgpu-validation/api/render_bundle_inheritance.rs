@@ -0,0 +1,162 @@
+//! Tests of queries in render bundles and [`wgpu::RenderBundleInheritance`].
+
+use wgpu::*;
+use wgpu_test::{
+    color_attachment, create_render_target, fail, run_render_pass, run_render_pass_with, valid,
+};
+
+fn create_query_set(device: &Device, ty: QueryType) -> QuerySet {
+    device.create_query_set(&QuerySetDescriptor {
+        label: None,
+        ty,
+        count: 2,
+    })
+}
+
+fn create_bundle(
+    device: &Device,
+    inheritance: RenderBundleInheritance,
+    f: impl FnOnce(&mut RenderBundleEncoder<'_>),
+) -> RenderBundle {
+    let mut encoder = device.create_render_bundle_encoder(&RenderBundleEncoderDescriptor {
+        color_formats: &[Some(TextureFormat::Rgba8Unorm)],
+        sample_count: 1,
+        inheritance,
+        ..Default::default()
+    });
+    f(&mut encoder);
+    encoder.finish(&RenderBundleDescriptor::default())
+}
+
+#[test]
+#[should_panic = "TIMESTAMP_QUERY_INSIDE_PASSES"]
+fn timestamp_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::TIMESTAMP_QUERY,
+        ..Default::default()
+    });
+    let query_set = create_query_set(&device, QueryType::Timestamp);
+
+    create_bundle(&device, RenderBundleInheritance::empty(), |bundle| {
+        bundle.write_timestamp(&query_set, 0);
+    });
+}
+
+#[test]
+fn timestamps() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::TIMESTAMP_QUERY | Features::TIMESTAMP_QUERY_INSIDE_PASSES,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let query_set = create_query_set(&device, QueryType::Timestamp);
+
+    let bundle = create_bundle(&device, RenderBundleInheritance::empty(), |bundle| {
+        bundle.write_timestamp(&query_set, 0);
+        bundle.write_timestamp(&query_set, 1);
+    });
+
+    valid(&device, || {
+        run_render_pass(&device, &view, |pass| pass.execute_bundles([&bundle]))
+    });
+    fail(
+        &device,
+        || {
+            run_render_pass(&device, &view, |pass| {
+                pass.execute_bundles([&bundle, &bundle])
+            })
+        },
+        Some("has already been used within the same renderpass"),
+    );
+}
+
+#[test]
+#[should_panic = "require it to inherit the occlusion query set of the render pass"]
+fn occlusion_query_requires_inheritance() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    create_bundle(&device, RenderBundleInheritance::empty(), |bundle| {
+        bundle.begin_occlusion_query(0);
+        bundle.end_occlusion_query();
+    });
+}
+
+#[test]
+#[should_panic = "must be ended in the same render bundle"]
+fn unended_query() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    create_bundle(
+        &device,
+        RenderBundleInheritance::OCCLUSION_QUERY_SET,
+        |bundle| bundle.begin_occlusion_query(0),
+    );
+}
+
+#[test]
+fn occlusion_queries() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let view = create_render_target(&device);
+    let query_set = create_query_set(&device, QueryType::Occlusion);
+    let occlusion_pass = RenderPassDescriptor {
+        color_attachments: &[color_attachment(&view)],
+        occlusion_query_set: Some(&query_set),
+        ..Default::default()
+    };
+
+    let bundle = create_bundle(
+        &device,
+        RenderBundleInheritance::OCCLUSION_QUERY_SET,
+        |bundle| {
+            bundle.begin_occlusion_query(1);
+            bundle.end_occlusion_query();
+        },
+    );
+
+    valid(&device, || {
+        run_render_pass_with(&device, &occlusion_pass, |pass| {
+            pass.begin_occlusion_query(0);
+            pass.end_occlusion_query();
+            pass.execute_bundles([&bundle]);
+        })
+    });
+    fail(
+        &device,
+        || run_render_pass(&device, &view, |pass| pass.execute_bundles([&bundle])),
+        Some("missing occlusion query set"),
+    );
+    fail(
+        &device,
+        || {
+            run_render_pass_with(&device, &occlusion_pass, |pass| {
+                pass.begin_occlusion_query(0);
+                pass.execute_bundles([&bundle]);
+                pass.end_occlusion_query();
+            })
+        },
+        Some("was started while query 0 was already active"),
+    );
+}
+
+#[test]
+#[should_panic = "PUSH_CONSTANTS"]
+fn push_constant_inheritance_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    create_bundle(&device, RenderBundleInheritance::PUSH_CONSTANTS, |_| {});
+}
+
+#[test]
+fn push_constant_inheritance() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::PUSH_CONSTANTS,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+
+    let bundle = create_bundle(&device, RenderBundleInheritance::PUSH_CONSTANTS, |_| {});
+
+    valid(&device, || {
+        run_render_pass(&device, &view, |pass| pass.execute_bundles([&bundle]))
+    });
+}
@@ -0,0 +1,385 @@
+//! Tests of [`wgpu::RenderPassFlags`].
+
+use wgpu_test::{
+    RenderTarget, color_attachment, create_render_target, fail, run_render_pass_with, valid,
+};
+
+const LOAD_STORE: wgpu::Operations<wgpu::Color> = wgpu::Operations {
+    load: wgpu::LoadOp::Load,
+    store: wgpu::StoreOp::Store,
+};
+
+fn run_pass(
+    encoder: &mut wgpu::CommandEncoder,
+    view: &wgpu::TextureView,
+    ops: wgpu::Operations<wgpu::Color>,
+    flags: wgpu::RenderPassFlags,
+) {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            depth_slice: None,
+            resolve_target: None,
+            ops,
+        })],
+        flags,
+        ..Default::default()
+    });
+}
+
+fn single_pass(
+    device: &wgpu::Device,
+    view: &wgpu::TextureView,
+    ops: wgpu::Operations<wgpu::Color>,
+    flags: wgpu::RenderPassFlags,
+) -> wgpu::CommandBuffer {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    run_pass(&mut encoder, view, ops, flags);
+    encoder.finish()
+}
+
+#[test]
+fn suspend_and_resume() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let view = create_render_target(&device);
+    let clear_discard = wgpu::Operations {
+        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+        store: wgpu::StoreOp::Discard,
+    };
+
+    let command_buffer = valid(&device, || {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        run_pass(
+            &mut encoder,
+            &view,
+            clear_discard,
+            wgpu::RenderPassFlags::SUSPENDING,
+        );
+        run_pass(
+            &mut encoder,
+            &view,
+            clear_discard,
+            wgpu::RenderPassFlags::RESUMING | wgpu::RenderPassFlags::SUSPENDING,
+        );
+        run_pass(
+            &mut encoder,
+            &view,
+            clear_discard,
+            wgpu::RenderPassFlags::RESUMING,
+        );
+        encoder.finish()
+    });
+    valid(&device, || queue.submit([command_buffer]));
+
+    // Only the last part resolves.
+    let msaa = RenderTarget::new(wgpu::TextureFormat::Rgba8Unorm, 4).create_view(&device);
+    let command_buffer = valid(&device, || {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        for flags in [
+            wgpu::RenderPassFlags::SUSPENDING,
+            wgpu::RenderPassFlags::RESUMING,
+        ] {
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &msaa,
+                    depth_slice: None,
+                    resolve_target: Some(&view),
+                    ops: clear_discard,
+                })],
+                flags,
+                ..Default::default()
+            });
+        }
+        encoder.finish()
+    });
+    valid(&device, || queue.submit([command_buffer]));
+}
+
+#[test]
+fn suspend_across_command_buffers() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let view = create_render_target(&device);
+    let other_view = create_render_target(&device);
+
+    let suspend = || {
+        single_pass(
+            &device,
+            &view,
+            LOAD_STORE,
+            wgpu::RenderPassFlags::SUSPENDING,
+        )
+    };
+    let resume = |view: &wgpu::TextureView| {
+        single_pass(&device, view, LOAD_STORE, wgpu::RenderPassFlags::RESUMING)
+    };
+
+    let command_buffers = valid(&device, || [suspend(), resume(&view)]);
+    valid(&device, || queue.submit(command_buffers));
+
+    let command_buffer = suspend();
+    fail(
+        &device,
+        || queue.submit([command_buffer]),
+        Some("ends with a suspended render pass"),
+    );
+
+    let command_buffer = resume(&view);
+    fail(
+        &device,
+        || queue.submit([command_buffer]),
+        Some("didn't suspend with the same attachments"),
+    );
+
+    let command_buffers = [suspend(), resume(&other_view)];
+    fail(
+        &device,
+        || queue.submit(command_buffers),
+        Some("didn't suspend with the same attachments"),
+    );
+
+    // The transitions of the resuming command buffer are done before the
+    // suspended part, so they can't change the resources it uses.
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDEX,
+        mapped_at_creation: false,
+    });
+    let run = |flags, f: &dyn Fn(&mut wgpu::RenderPass<'_>)| {
+        run_render_pass_with(
+            &device,
+            &wgpu::RenderPassDescriptor {
+                color_attachments: &[color_attachment(&view)],
+                flags,
+                ..Default::default()
+            },
+            f,
+        )
+    };
+    let set_vertex_buffer = |pass: &mut wgpu::RenderPass<'_>| {
+        pass.set_vertex_buffer(0, buffer.slice(..));
+    };
+    let set_index_buffer = |pass: &mut wgpu::RenderPass<'_>| {
+        pass.set_index_buffer(buffer.slice(..), wgpu::IndexFormat::Uint16);
+    };
+
+    let command_buffers = [
+        run(wgpu::RenderPassFlags::SUSPENDING, &set_vertex_buffer),
+        run(wgpu::RenderPassFlags::RESUMING, &set_vertex_buffer),
+    ];
+    valid(&device, || queue.submit(command_buffers));
+
+    let command_buffers = [
+        run(wgpu::RenderPassFlags::SUSPENDING, &set_vertex_buffer),
+        run(wgpu::RenderPassFlags::RESUMING, &set_index_buffer),
+    ];
+    fail(
+        &device,
+        || queue.submit(command_buffers),
+        Some("needs barriers on the resources used by the parts of it before"),
+    );
+}
+
+#[test]
+fn suspend_resume_limits() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::TIMESTAMP_QUERY
+            | wgpu::Features::PIPELINE_STATISTICS_QUERY,
+        ..Default::default()
+    });
+    let view = create_render_target(&device);
+    let timestamps = device.create_query_set(&wgpu::QuerySetDescriptor {
+        label: None,
+        ty: wgpu::QueryType::Timestamp,
+        count: 2,
+    });
+    let statistics = device.create_query_set(&wgpu::QuerySetDescriptor {
+        label: None,
+        ty: wgpu::QueryType::PipelineStatistics(
+            wgpu::PipelineStatisticsTypes::VERTEX_SHADER_INVOCATIONS,
+        ),
+        count: 1,
+    });
+    let run = |desc: wgpu::RenderPassDescriptor<'_>| {
+        run_render_pass_with(
+            &device,
+            &wgpu::RenderPassDescriptor {
+                color_attachments: &[color_attachment(&view)],
+                ..desc
+            },
+            |_| {},
+        )
+    };
+    let timestamp_writes = |beginning, end| {
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: &timestamps,
+            beginning_of_pass_write_index: beginning,
+            end_of_pass_write_index: end,
+        })
+    };
+
+    valid(&device, || {
+        run(wgpu::RenderPassDescriptor {
+            timestamp_writes: timestamp_writes(Some(0), None),
+            flags: wgpu::RenderPassFlags::SUSPENDING,
+            ..Default::default()
+        })
+    });
+    fail(
+        &device,
+        || {
+            run(wgpu::RenderPassDescriptor {
+                timestamp_writes: timestamp_writes(None, Some(1)),
+                flags: wgpu::RenderPassFlags::SUSPENDING,
+                ..Default::default()
+            })
+        },
+        Some("A suspending render pass can't write a timestamp at its end"),
+    );
+    fail(
+        &device,
+        || {
+            run(wgpu::RenderPassDescriptor {
+                timestamp_writes: timestamp_writes(Some(0), None),
+                flags: wgpu::RenderPassFlags::RESUMING,
+                ..Default::default()
+            })
+        },
+        Some("A resuming render pass can't write a timestamp at its beginning"),
+    );
+    fail(
+        &device,
+        || {
+            run(wgpu::RenderPassDescriptor {
+                pipeline_statistics_query: Some(wgpu::PassPipelineStatisticsQuery {
+                    query_set: &statistics,
+                    query_index: 0,
+                }),
+                flags: wgpu::RenderPassFlags::SUSPENDING,
+                ..Default::default()
+            })
+        },
+        Some("can't have a pipeline statistics query"),
+    );
+
+    let transient = RenderTarget {
+        usage: wgpu::TextureUsages::TRANSIENT,
+        ..Default::default()
+    }
+    .create_view(&device);
+    fail(
+        &device,
+        || {
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            run_pass(
+                &mut encoder,
+                &transient,
+                wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Discard,
+                },
+                wgpu::RenderPassFlags::SUSPENDING,
+            );
+            encoder.finish()
+        },
+        Some("can't render to the transient"),
+    );
+}
+
+#[test]
+fn commands_between_passes() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let view = create_render_target(&device);
+    let other_view = create_render_target(&device);
+
+    let with_encoder = |f: &dyn Fn(&mut wgpu::CommandEncoder)| {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        f(&mut encoder);
+        encoder.finish()
+    };
+
+    fail(
+        &device,
+        || {
+            with_encoder(&|encoder| {
+                run_pass(
+                    encoder,
+                    &view,
+                    LOAD_STORE,
+                    wgpu::RenderPassFlags::SUSPENDING,
+                );
+                encoder.insert_debug_marker("between");
+            })
+        },
+        Some("Encoder has a suspended render pass"),
+    );
+    fail(
+        &device,
+        || {
+            with_encoder(&|encoder| {
+                run_pass(
+                    encoder,
+                    &view,
+                    LOAD_STORE,
+                    wgpu::RenderPassFlags::SUSPENDING,
+                );
+                run_pass(encoder, &view, LOAD_STORE, wgpu::RenderPassFlags::empty());
+            })
+        },
+        Some("Encoder has a suspended render pass"),
+    );
+    fail(
+        &device,
+        || {
+            with_encoder(&|encoder| {
+                run_pass(
+                    encoder,
+                    &view,
+                    LOAD_STORE,
+                    wgpu::RenderPassFlags::SUSPENDING,
+                );
+                run_pass(
+                    encoder,
+                    &other_view,
+                    LOAD_STORE,
+                    wgpu::RenderPassFlags::RESUMING,
+                );
+            })
+        },
+        Some("resumes a suspended render pass with different attachments or operations"),
+    );
+    fail(
+        &device,
+        || {
+            with_encoder(&|encoder| {
+                run_pass(
+                    encoder,
+                    &view,
+                    LOAD_STORE,
+                    wgpu::RenderPassFlags::SUSPENDING,
+                );
+                run_pass(
+                    encoder,
+                    &view,
+                    wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Discard,
+                    },
+                    wgpu::RenderPassFlags::RESUMING,
+                );
+            })
+        },
+        Some("resumes a suspended render pass with different attachments or operations"),
+    );
+    fail(
+        &device,
+        || {
+            with_encoder(&|encoder| {
+                run_pass(encoder, &view, LOAD_STORE, wgpu::RenderPassFlags::empty());
+                run_pass(encoder, &view, LOAD_STORE, wgpu::RenderPassFlags::RESUMING);
+            })
+        },
+        Some("There is no suspended render pass to resume"),
+    );
+}
@@ -0,0 +1,129 @@
+//! Tests of [`wgpu::Features::REUSABLE_COMMAND_BUFFERS`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+fn create_buffer(device: &Device, usage: BufferUsages) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some("buffer"),
+        size: 256,
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+fn copy(device: &Device, src: &Buffer, dst: &Buffer) -> CommandEncoder {
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.copy_buffer_to_buffer(src, 0, dst, 0, 256);
+    encoder
+}
+
+#[test]
+fn reusable_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let src = create_buffer(&device, BufferUsages::COPY_SRC);
+    let dst = create_buffer(&device, BufferUsages::COPY_DST | BufferUsages::MAP_READ);
+
+    fail(
+        &device,
+        || copy(&device, &src, &dst).finish_reusable(),
+        Some("REUSABLE_COMMAND_BUFFERS"),
+    );
+}
+
+#[test]
+fn submit_many_times() {
+    let (device, queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::REUSABLE_COMMAND_BUFFERS,
+        ..Default::default()
+    });
+    let src = create_buffer(&device, BufferUsages::COPY_SRC);
+    let dst = create_buffer(&device, BufferUsages::COPY_DST | BufferUsages::MAP_READ);
+
+    let command_buffer = valid(&device, || copy(&device, &src, &dst).finish_reusable());
+    valid(&device, || queue.submit([command_buffer.clone()]));
+    valid(&device, || {
+        queue.submit([command_buffer.clone(), command_buffer.clone()])
+    });
+
+    let command_buffer = valid(&device, || copy(&device, &src, &dst).finish());
+    valid(&device, || queue.submit([command_buffer.clone()]));
+    fail(
+        &device,
+        || queue.submit([command_buffer]),
+        Some("This command buffer has already been submitted."),
+    );
+}
+
+#[test]
+fn resources_still_alive() {
+    let (device, queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::REUSABLE_COMMAND_BUFFERS,
+        ..Default::default()
+    });
+    let src = create_buffer(&device, BufferUsages::COPY_SRC);
+    let dst = create_buffer(&device, BufferUsages::COPY_DST | BufferUsages::MAP_READ);
+
+    let command_buffer = valid(&device, || copy(&device, &src, &dst).finish_reusable());
+    valid(&device, || queue.submit([command_buffer.clone()]));
+
+    dst.map_async(MapMode::Read, .., |_| {});
+    fail(
+        &device,
+        || queue.submit([command_buffer.clone()]),
+        Some("is still mapped"),
+    );
+    device.poll(PollType::Wait).unwrap();
+    dst.unmap();
+    valid(&device, || queue.submit([command_buffer.clone()]));
+
+    src.destroy();
+    fail(
+        &device,
+        || queue.submit([command_buffer]),
+        Some("has been destroyed"),
+    );
+}
+
+#[test]
+fn reusable_render_pass_suspension() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::REUSABLE_COMMAND_BUFFERS,
+        ..Default::default()
+    });
+    let view = device
+        .create_texture(&TextureDescriptor {
+            label: Some("target"),
+            size: Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&Default::default());
+
+    fail(
+        &device,
+        || {
+            let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+            encoder.begin_render_pass(&RenderPassDescriptor {
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                flags: RenderPassFlags::SUSPENDING,
+                ..Default::default()
+            });
+            encoder.finish_reusable()
+        },
+        Some("A reusable command buffer can't suspend or resume render passes"),
+    );
+}
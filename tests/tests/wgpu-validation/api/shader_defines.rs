@@ -0,0 +1,68 @@
+//! Tests of [`wgpu::ShaderSource::WgslWithDefines`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = r#"
+    @if(hdr) const EXPOSURE: f32 = 2.0;
+    @if(tonemap) const WHITE: f32 = 4.0;
+
+    @group(0) @binding(0) var<storage, read_write> color: vec3f;
+
+    @compute @workgroup_size(1)
+    fn main() {
+        @if(hdr) {
+            color *= EXPOSURE;
+        }
+        @if(hdr && tonemap) {
+            color /= 1.0 + color / WHITE;
+        }
+    }
+"#;
+
+fn create_shader_module(device: &Device, defines: &[&str]) -> ShaderModule {
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::WgslWithDefines {
+            shader: SHADER.into(),
+            defines,
+        },
+    })
+}
+
+#[test]
+fn shader_defines() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    valid(&device, || create_shader_module(&device, &[]));
+    valid(&device, || create_shader_module(&device, &["hdr"]));
+    valid(&device, || {
+        create_shader_module(&device, &["hdr", "tonemap"])
+    });
+    valid(&device, || {
+        device.create_shader_module(ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Wgsl(SHADER.into()),
+        })
+    });
+}
+
+#[test]
+fn shader_defines_missing_declaration() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    // `WHITE` is only declared if `tonemap` is defined.
+    fail(
+        &device,
+        || {
+            device.create_shader_module(ShaderModuleDescriptor {
+                label: None,
+                source: ShaderSource::WgslWithDefines {
+                    shader: SHADER.replace("@if(tonemap) ", "@if(false) ").into(),
+                    defines: &["hdr", "tonemap"],
+                },
+            })
+        },
+        Some("no definition in scope for identifier: `WHITE`"),
+    );
+}
@@ -0,0 +1,51 @@
+//! Tests of [`wgpu::Features::SHADER_F64`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = "
+    @group(0) @binding(0) var<storage, read_write> data: array<f64>;
+
+    @compute @workgroup_size(64)
+    fn main(@builtin(global_invocation_id) id: vec3u) {
+        data[id.x] = fma(data[id.x], 0.5lf, f64(id.x));
+    }
+";
+
+fn create_shader_module(device: &Device) -> ShaderModule {
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    })
+}
+
+#[test]
+fn shader_f64_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || create_shader_module(&device),
+        Some("Using `f64` values requires the `naga::valid::Capabilities::FLOAT64` flag"),
+    );
+}
+
+#[test]
+fn shader_f64() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::SHADER_F64,
+        ..Default::default()
+    });
+
+    let module = valid(&device, || create_shader_module(&device));
+    valid(&device, || {
+        device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            cache: None,
+        })
+    });
+}
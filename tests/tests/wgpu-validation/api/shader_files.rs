@@ -0,0 +1,67 @@
+//! Tests of [`wgpu::ShaderSource::WgslFiles`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const MATH: &str = "fn square(x: f32) -> f32 {
+    return x * x;
+}
+";
+
+const LIGHTING: &str = "import \"math.wgsl\";
+
+@group(0) @binding(0) var<storage, read_write> light: f32;
+
+@compute @workgroup_size(1)
+fn main() {
+    light = square(light);
+}
+";
+
+fn create_shader_module(device: &Device, files: &[(&str, &str)]) -> ShaderModule {
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::WgslFiles(files),
+    })
+}
+
+#[test]
+fn shader_files() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    valid(&device, || {
+        create_shader_module(&device, &[("math.wgsl", MATH), ("lighting.wgsl", LIGHTING)])
+    });
+}
+
+#[test]
+fn shader_files_error_location() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    let lighting = LIGHTING.replace("square(light)", "cube(light)");
+    let files = [("math.wgsl", MATH), ("lighting.wgsl", lighting.as_str())];
+    let module = fail(
+        &device,
+        || create_shader_module(&device, &files),
+        Some("┌─ lighting.wgsl:7:13"),
+    );
+
+    let compilation_info = pollster::block_on(module.get_compilation_info());
+    let message = &compilation_info.messages[0];
+    assert_eq!(message.message_type, CompilationMessageType::Error);
+    assert_eq!(message.file_name.as_deref(), Some("lighting.wgsl"));
+    let location = message.location.unwrap();
+    assert_eq!((location.line_number, location.line_position), (7, 13));
+    assert_eq!(&lighting[location.offset as usize..][..4], "cube");
+}
+
+#[test]
+fn shader_files_unknown_import() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || create_shader_module(&device, &[("lighting.wgsl", LIGHTING)]),
+        Some("there is no source named `math.wgsl`"),
+    );
+}
@@ -0,0 +1,65 @@
+//! Tests of [`wgpu::Device::create_shader_module_with_optimization`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SOURCE: &str = "
+@group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    data[id.x] = data[id.x] * 2.0;
+}
+";
+
+fn create_shader_module(
+    device: &Device,
+    source: &str,
+    optimization: ShaderOptimization,
+) -> ShaderModule {
+    device.create_shader_module_with_optimization(
+        ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Wgsl(source.into()),
+        },
+        optimization,
+    )
+}
+
+#[test]
+fn shader_optimization() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    for optimization in [ShaderOptimization::Default, ShaderOptimization::Performance] {
+        let module = valid(&device, || {
+            create_shader_module(&device, SOURCE, optimization)
+        });
+        valid(&device, || {
+            device.create_compute_pipeline(&ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: None,
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        });
+    }
+}
+
+#[test]
+fn shader_optimization_still_validates() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || {
+            create_shader_module(
+                &device,
+                &SOURCE.replace("2.0", "scale"),
+                ShaderOptimization::Performance,
+            )
+        },
+        Some("no definition in scope for identifier: `scale`"),
+    );
+}
@@ -0,0 +1,111 @@
+//! Tests of [`wgpu::ShaderModule::check_layout`] with derived [`wgpu::ShaderType`]s.
+
+const SHADER: &str = "
+    struct Light {
+        position: vec3<f32>,
+        intensity: f32,
+        color: vec4<f32>,
+    }
+
+    struct Camera {
+        view_projection: mat4x4<f32>,
+        normal: mat3x3<f32>,
+        position: vec3<f32>,
+        exposure: f32,
+        light_count: u32,
+    }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+    @group(0) @binding(1) var<storage, read> lights: array<Light>;
+    @group(0) @binding(2) var<storage, read_write> counters: array<atomic<u32>, 4>;
+
+    @compute @workgroup_size(1)
+    fn main() {
+        atomicAdd(&counters[0], camera.light_count + u32(lights[0].intensity));
+    }
+";
+
+#[derive(wgpu::ShaderType)]
+#[shader_type(uniform)]
+#[repr(C, align(16))]
+struct Camera {
+    view_projection: [[f32; 4]; 4],
+    normal: [[f32; 4]; 3],
+    position: [f32; 3],
+    exposure: f32,
+    light_count: u32,
+}
+
+#[derive(wgpu::ShaderType)]
+#[repr(C)]
+struct Light {
+    position: [f32; 3],
+    intensity: f32,
+    color: [f32; 4],
+}
+
+/// `Light` with the intensity before the position, as if the shader had changed.
+#[derive(wgpu::ShaderType)]
+#[repr(C)]
+struct StaleLight {
+    intensity: f32,
+    position: [f32; 3],
+    color: [f32; 4],
+}
+
+#[derive(wgpu::ShaderType)]
+#[repr(C)]
+struct Pair<T>(T, T);
+
+fn module() -> wgpu::ShaderModule {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+    })
+}
+
+#[test]
+fn matching_layouts() {
+    let module = module();
+    module.check_layout::<Camera>("camera").unwrap();
+    module.check_layout::<[Light]>("lights").unwrap();
+    module.check_layout::<[u32; 4]>("counters").unwrap();
+}
+
+#[test]
+fn mismatched_layouts() {
+    let module = module();
+    let mismatch = |path: &str, reason: &str| wgpu::ShaderLayoutError::Mismatch {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    };
+
+    assert_eq!(
+        module.check_layout::<[StaleLight]>("lights"),
+        Err(mismatch(
+            "lights[].position",
+            "the shader has `vec3<f32>`, the layout has `f32`"
+        ))
+    );
+    assert_eq!(
+        module.check_layout::<[u32; 3]>("counters"),
+        Err(mismatch(
+            "counters",
+            "the shader's array has 4 elements, the layout has 3 elements"
+        ))
+    );
+    assert_eq!(
+        module.check_layout::<Pair<u32>>("camera"),
+        Err(mismatch(
+            "camera",
+            "the shader's struct has 5 members, `Pair` has 2"
+        ))
+    );
+    assert_eq!(
+        module.check_layout::<Light>("missing"),
+        Err(wgpu::ShaderLayoutError::NotFound {
+            name: "missing".to_string()
+        })
+    );
+}
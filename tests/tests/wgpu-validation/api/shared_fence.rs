@@ -0,0 +1,99 @@
+//! Tests of [`wgpu::Features::EXPERIMENTAL_SHARED_FENCE`].
+//!
+//! The noop backend has nothing to share fences with, so it never supports the feature.
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+fn noop_device() -> (Device, Queue) {
+    Device::noop(&DeviceDescriptor::default())
+}
+
+#[test]
+fn create_shared_fence_requires_feature() {
+    let (device, _queue) = noop_device();
+    fail(
+        &device,
+        || device.create_shared_fence(&SharedFenceDescriptor::default()),
+        Some("EXPERIMENTAL_SHARED_FENCE"),
+    );
+}
+
+#[test]
+fn import_shared_fence_requires_feature() {
+    let (device, _queue) = noop_device();
+    // The handle is never used, as the missing feature is reported first.
+    fail(
+        &device,
+        || unsafe {
+            device.import_shared_fence(
+                SharedFenceHandle::VulkanOpaqueFd(-1),
+                &SharedFenceDescriptor::default(),
+            )
+        },
+        Some("EXPERIMENTAL_SHARED_FENCE"),
+    );
+}
+
+#[test]
+fn export_invalid_shared_fence() {
+    let (device, _queue) = noop_device();
+    let fence = fail(
+        &device,
+        || device.create_shared_fence(&SharedFenceDescriptor::default()),
+        None,
+    );
+    let handle = fail(&device, || fence.export(), Some("is invalid"));
+    assert_eq!(handle, None);
+}
+
+#[test]
+fn submit_with_invalid_shared_fence() {
+    let (device, queue) = noop_device();
+    let fence = fail(
+        &device,
+        || device.create_shared_fence(&SharedFenceDescriptor::default()),
+        None,
+    );
+
+    let command_buffer = device.create_command_encoder(&Default::default()).finish();
+    fail(
+        &device,
+        || {
+            queue.submit_with_external_sync(
+                [command_buffer],
+                &[SharedFenceValue {
+                    fence: &fence,
+                    value: 1,
+                }],
+                &[],
+            )
+        },
+        Some("is invalid"),
+    );
+
+    let command_buffer = device.create_command_encoder(&Default::default()).finish();
+    fail(
+        &device,
+        || {
+            queue.submit_with_external_sync(
+                [command_buffer],
+                &[],
+                &[SharedFenceValue {
+                    fence: &fence,
+                    value: 1,
+                }],
+            )
+        },
+        Some("is invalid"),
+    );
+}
+
+#[test]
+fn submit_without_external_sync() {
+    let (device, queue) = noop_device();
+    let command_buffer = device.create_command_encoder(&Default::default()).finish();
+    valid(&device, || {
+        queue.submit_with_external_sync([command_buffer], &[], &[])
+    });
+}
@@ -0,0 +1,249 @@
+//! Tests of [`wgpu::Features::SPARSE_BINDING`].
+
+use wgpu_test::{fail, valid};
+
+const PAGE: u64 = wgpu::SPARSE_PAGE_SIZE;
+
+fn sparse_device() -> (wgpu::Device, wgpu::Queue) {
+    wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::SPARSE_BINDING | wgpu::Features::TEXTURE_ALIASING,
+        ..Default::default()
+    })
+}
+
+fn buffer_desc(size: u64, usage: wgpu::BufferUsages) -> wgpu::BufferDescriptor<'static> {
+    wgpu::BufferDescriptor {
+        label: Some("sparse"),
+        size,
+        usage,
+        mapped_at_creation: false,
+    }
+}
+
+fn texture_desc(
+    width: u32,
+    height: u32,
+    dimension: wgpu::TextureDimension,
+    sample_count: u32,
+) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("sparse"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    }
+}
+
+fn create_heap(device: &wgpu::Device, size: u64) -> wgpu::MemoryHeap {
+    device.create_memory_heap(&wgpu::MemoryHeapDescriptor {
+        label: Some("heap"),
+        size,
+    })
+}
+
+fn bind_buffer(
+    queue: &wgpu::Queue,
+    buffer: &wgpu::Buffer,
+    offset: u64,
+    size: u64,
+    memory: Option<(&wgpu::MemoryHeap, u64)>,
+) {
+    queue.bind_sparse_memory(
+        &[wgpu::SparseBufferBinding {
+            buffer,
+            offset,
+            size,
+            memory: memory.map(|(heap, offset)| wgpu::HeapRegion { heap, offset }),
+        }],
+        &[],
+    );
+}
+
+#[test]
+fn requires_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || device.create_sparse_buffer(&buffer_desc(PAGE, wgpu::BufferUsages::STORAGE)),
+        Some("SPARSE_BINDING"),
+    );
+    fail(
+        &device,
+        || device.create_sparse_texture(&texture_desc(256, 256, wgpu::TextureDimension::D2, 1)),
+        Some("SPARSE_BINDING"),
+    );
+}
+
+#[test]
+fn buffer_creation() {
+    let (device, _queue) = sparse_device();
+
+    let buffer = valid(&device, || {
+        device.create_sparse_buffer(&buffer_desc(PAGE + 1, wgpu::BufferUsages::STORAGE))
+    });
+    assert_eq!(buffer.size(), PAGE + 1);
+
+    fail(
+        &device,
+        || {
+            device.create_sparse_buffer(&buffer_desc(
+                PAGE,
+                wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            ))
+        },
+        Some("Sparse buffers can't be mapped"),
+    );
+    fail(
+        &device,
+        || {
+            device.create_sparse_buffer(&wgpu::BufferDescriptor {
+                mapped_at_creation: true,
+                ..buffer_desc(PAGE, wgpu::BufferUsages::STORAGE)
+            })
+        },
+        Some("Sparse buffers can't be mapped at creation"),
+    );
+}
+
+#[test]
+fn texture_creation() {
+    let (device, _queue) = sparse_device();
+
+    valid(&device, || {
+        device.create_sparse_texture(&texture_desc(256, 256, wgpu::TextureDimension::D2, 1))
+    });
+
+    fail(
+        &device,
+        || device.create_sparse_texture(&texture_desc(256, 256, wgpu::TextureDimension::D3, 1)),
+        Some("Sparse textures must be 2D"),
+    );
+    fail(
+        &device,
+        || device.create_sparse_texture(&texture_desc(256, 256, wgpu::TextureDimension::D2, 4)),
+        Some("Sparse textures can't be multisampled"),
+    );
+    fail(
+        &device,
+        || device.create_sparse_texture(&texture_desc(100, 256, wgpu::TextureDimension::D2, 1)),
+        Some("is not made of whole tiles"),
+    );
+}
+
+#[test]
+fn buffer_binding_validation() {
+    let (device, queue) = sparse_device();
+    let buffer = device.create_sparse_buffer(&buffer_desc(4 * PAGE, wgpu::BufferUsages::STORAGE));
+    let heap = create_heap(&device, 2 * PAGE);
+
+    valid(&device, || {
+        bind_buffer(&queue, &buffer, 0, 2 * PAGE, Some((&heap, 0)))
+    });
+    valid(&device, || {
+        bind_buffer(&queue, &buffer, 2 * PAGE, PAGE, Some((&heap, PAGE)))
+    });
+    valid(&device, || bind_buffer(&queue, &buffer, 0, 4 * PAGE, None));
+
+    fail(
+        &device,
+        || bind_buffer(&queue, &buffer, 1, PAGE, None),
+        Some("is not made of whole sparse pages"),
+    );
+    fail(
+        &device,
+        || bind_buffer(&queue, &buffer, 0, PAGE + 1, None),
+        Some("is not made of whole sparse pages"),
+    );
+    fail(
+        &device,
+        || bind_buffer(&queue, &buffer, 3 * PAGE, 2 * PAGE, None),
+        Some("is out of bounds of"),
+    );
+    fail(
+        &device,
+        || bind_buffer(&queue, &buffer, 0, PAGE, Some((&heap, 1))),
+        Some("is not a multiple of the sparse page size"),
+    );
+    fail(
+        &device,
+        || bind_buffer(&queue, &buffer, 0, 2 * PAGE, Some((&heap, PAGE))),
+        Some("doesn't fit in"),
+    );
+
+    let regular = device.create_buffer(&buffer_desc(PAGE, wgpu::BufferUsages::STORAGE));
+    fail(
+        &device,
+        || bind_buffer(&queue, &regular, 0, PAGE, None),
+        Some("is not a sparse resource"),
+    );
+}
+
+#[test]
+fn texture_binding_validation() {
+    let (device, queue) = sparse_device();
+    let texture =
+        device.create_sparse_texture(&texture_desc(256, 256, wgpu::TextureDimension::D2, 1));
+    let heap = create_heap(&device, 4 * PAGE);
+
+    let bind = |origin: wgpu::Origin3d, width: u32, height: u32, offset: Option<u64>| {
+        queue.bind_sparse_memory(
+            &[],
+            &[wgpu::SparseTextureBinding {
+                texture: &texture,
+                mip_level: 0,
+                array_layer: 0,
+                origin,
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                memory: offset.map(|offset| wgpu::HeapRegion {
+                    heap: &heap,
+                    offset,
+                }),
+            }],
+        )
+    };
+
+    // Rgba8Unorm tiles are 128x128 texels, so the texture is 2x2 tiles.
+    valid(&device, || bind(wgpu::Origin3d::ZERO, 256, 256, Some(0)));
+    valid(&device, || {
+        bind(
+            wgpu::Origin3d {
+                x: 128,
+                y: 128,
+                z: 0,
+            },
+            128,
+            128,
+            Some(PAGE),
+        )
+    });
+    valid(&device, || bind(wgpu::Origin3d::ZERO, 256, 256, None));
+
+    fail(
+        &device,
+        || bind(wgpu::Origin3d { x: 64, y: 0, z: 0 }, 128, 128, None),
+        Some("is not made of whole tiles"),
+    );
+    fail(
+        &device,
+        || bind(wgpu::Origin3d { x: 128, y: 0, z: 0 }, 256, 128, None),
+        Some("is out of bounds of"),
+    );
+    fail(
+        &device,
+        || bind(wgpu::Origin3d::ZERO, 256, 256, Some(PAGE)),
+        Some("doesn't fit in"),
+    );
+}
@@ -0,0 +1,57 @@
+//! Tests of waiting for a [`wgpu::SubmissionIndex`].
+
+use core::time::Duration;
+
+use wgpu::*;
+
+#[test]
+fn completed_submission_index() {
+    let (_device, queue) = Device::noop(&DeviceDescriptor::default());
+
+    let first = queue.submit([]);
+    let second = queue.submit([]);
+    assert!(first < second);
+
+    // Noop submissions finish as soon as they are submitted.
+    assert!(queue.get_completed_submission_index() >= second);
+}
+
+#[test]
+fn wait_with_timeout() {
+    let (_device, queue) = Device::noop(&DeviceDescriptor::default());
+
+    let index = queue.submit([]);
+    assert!(index.wait(Some(Duration::ZERO)).unwrap().wait_finished());
+    assert!(index.wait(None).unwrap().wait_finished());
+}
+
+#[test]
+fn wait_async() {
+    let (device, queue) = Device::noop(&DeviceDescriptor::default());
+
+    let first = queue.submit([]);
+    let second = queue.submit([]);
+
+    let future = first.wait_async();
+    second.wait(None).unwrap();
+    pollster::block_on(future);
+
+    // Waiting for a submission that has already been processed resolves on the next poll.
+    let future = first.wait_async();
+    device.poll(PollType::Poll).unwrap();
+    pollster::block_on(future);
+}
+
+#[test]
+fn device_poll_with_timeout() {
+    let (device, queue) = Device::noop(&DeviceDescriptor::default());
+
+    let index = queue.submit([]);
+    let status = device
+        .poll(PollType::WaitForSubmissionWithTimeout(
+            index,
+            Duration::from_millis(100),
+        ))
+        .unwrap();
+    assert!(status.wait_finished());
+}
@@ -0,0 +1,212 @@
+//! Tests of [`wgpu::Features::TEXTURE_ALIASING`].
+
+use wgpu_test::{fail, valid};
+
+fn aliasing_device() -> (wgpu::Device, wgpu::Queue) {
+    wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::TEXTURE_ALIASING,
+        ..Default::default()
+    })
+}
+
+fn texture_desc(usage: wgpu::TextureUsages) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("aliased"),
+        size: wgpu::Extent3d {
+            width: 64,
+            height: 64,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage,
+        view_formats: &[],
+    }
+}
+
+fn create_heap(device: &wgpu::Device, size: u64) -> wgpu::MemoryHeap {
+    device.create_memory_heap(&wgpu::MemoryHeapDescriptor {
+        label: Some("heap"),
+        size,
+    })
+}
+
+fn place_texture(
+    device: &wgpu::Device,
+    heap: &wgpu::MemoryHeap,
+    offset: u64,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    device.create_texture_aliased(&wgpu::HeapRegion { heap, offset }, &texture_desc(usage))
+}
+
+fn encode_color_pass(encoder: &mut wgpu::CommandEncoder, textures: &[&wgpu::Texture]) {
+    let views = textures
+        .iter()
+        .map(|texture| texture.create_view(&Default::default()))
+        .collect::<Vec<_>>();
+    let color_attachments = views
+        .iter()
+        .map(|view| {
+            Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        color_attachments: &color_attachments,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn requires_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || create_heap(&device, 1 << 20),
+        Some("TEXTURE_ALIASING"),
+    );
+    fail(
+        &device,
+        || {
+            device
+                .texture_memory_requirements(&texture_desc(wgpu::TextureUsages::RENDER_ATTACHMENT))
+        },
+        Some("TEXTURE_ALIASING"),
+    );
+}
+
+#[test]
+fn placement_validation() {
+    let (device, _queue) = aliasing_device();
+    let usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+
+    let requirements = valid(&device, || {
+        device.texture_memory_requirements(&texture_desc(usage))
+    })
+    .unwrap();
+    assert_ne!(requirements.size, 0);
+    assert!(requirements.alignment.is_power_of_two());
+
+    fail(&device, || create_heap(&device, 0), Some("can't be empty"));
+
+    let heap = create_heap(&device, 2 * requirements.size);
+    valid(&device, || place_texture(&device, &heap, 0, usage));
+    valid(&device, || {
+        place_texture(&device, &heap, requirements.size, usage)
+    });
+
+    if requirements.alignment > 1 {
+        fail(
+            &device,
+            || place_texture(&device, &heap, 1, usage),
+            Some("is not a multiple of the texture's alignment"),
+        );
+    }
+    fail(
+        &device,
+        || place_texture(&device, &heap, 2 * requirements.size, usage),
+        Some("doesn't fit in"),
+    );
+    fail(
+        &device,
+        || place_texture(&device, &heap, 0, usage | wgpu::TextureUsages::TRANSIENT),
+        Some("Transient textures can't be placed in a memory heap"),
+    );
+}
+
+#[test]
+fn overlapping_textures_in_one_pass() {
+    let (device, queue) = aliasing_device();
+    let usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+    let size = device
+        .texture_memory_requirements(&texture_desc(usage))
+        .unwrap()
+        .size;
+
+    let heap = create_heap(&device, 2 * size);
+    let first = place_texture(&device, &heap, 0, usage);
+    let overlapping = place_texture(&device, &heap, 0, usage);
+    let disjoint = place_texture(&device, &heap, size, usage);
+
+    fail(
+        &device,
+        || {
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encode_color_pass(&mut encoder, &[&first, &overlapping]);
+            encoder.finish()
+        },
+        Some("share memory and can't be used at the same time"),
+    );
+
+    let command_buffer = valid(&device, || {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encode_color_pass(&mut encoder, &[&first, &disjoint]);
+        encode_color_pass(&mut encoder, &[&overlapping, &disjoint]);
+        encode_color_pass(&mut encoder, &[&first]);
+        encoder.finish()
+    });
+    queue.submit([command_buffer]);
+}
+
+#[test]
+fn copy_between_aliases() {
+    let (device, _queue) = aliasing_device();
+    let usage = wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST;
+    let size = device
+        .texture_memory_requirements(&texture_desc(usage))
+        .unwrap()
+        .size;
+
+    let heap = create_heap(&device, size);
+    let src = place_texture(&device, &heap, 0, usage);
+    let dst = place_texture(&device, &heap, 0, usage);
+
+    fail(
+        &device,
+        || {
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.copy_texture_to_texture(src.as_image_copy(), dst.as_image_copy(), src.size());
+            encoder.finish()
+        },
+        Some("share memory and can't be used at the same time"),
+    );
+}
+
+#[test]
+fn queue_write_to_aliased_texture() {
+    let (device, queue) = aliasing_device();
+    let usage = wgpu::TextureUsages::COPY_DST;
+    let size = device
+        .texture_memory_requirements(&texture_desc(usage))
+        .unwrap()
+        .size;
+
+    let heap = create_heap(&device, size);
+    let texture = place_texture(&device, &heap, 0, usage);
+
+    fail(
+        &device,
+        || {
+            queue.write_texture(
+                texture.as_image_copy(),
+                &[0; 4],
+                wgpu::TexelCopyBufferLayout::default(),
+                wgpu::Extent3d::default(),
+            )
+        },
+        Some("can't be written to from the queue"),
+    );
+}
@@ -0,0 +1,78 @@
+//! Tests of [`wgpu::Features::TEXTURE_ATOMIC`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = "
+    @group(0) @binding(0) var image: texture_storage_2d<r32uint, atomic>;
+
+    @compute @workgroup_size(8, 8)
+    fn main(@builtin(global_invocation_id) id: vec3u) {
+        textureAtomicMax(image, id.xy, id.x);
+    }
+";
+
+fn create_bind_group_layout(device: &Device) -> BindGroupLayout {
+    device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::StorageTexture {
+                access: StorageTextureAccess::Atomic,
+                format: TextureFormat::R32Uint,
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: None,
+        }],
+    })
+}
+
+fn create_shader_module(device: &Device) -> ShaderModule {
+    device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    })
+}
+
+#[test]
+fn texture_atomic_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || create_bind_group_layout(&device),
+        Some("require the native only feature TEXTURE_ATOMIC"),
+    );
+    fail(
+        &device,
+        || create_shader_module(&device),
+        Some("TEXTURE_ATOMIC"),
+    );
+}
+
+#[test]
+fn texture_atomic() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::TEXTURE_ATOMIC,
+        ..Default::default()
+    });
+
+    let bind_group_layout = valid(&device, || create_bind_group_layout(&device));
+    let module = valid(&device, || create_shader_module(&device));
+    let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    valid(&device, || {
+        device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: None,
+            layout: Some(&layout),
+            module: &module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            cache: None,
+        })
+    });
+}
@@ -0,0 +1,123 @@
+//! Tests of [`wgpu::CommandEncoder::resolve_texture`].
+
+use wgpu_test::{RenderTarget, fail, valid};
+
+fn create_view(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    usage: wgpu::TextureUsages,
+) -> wgpu::TextureView {
+    RenderTarget {
+        usage,
+        ..RenderTarget::new(format, sample_count)
+    }
+    .create_view(device)
+}
+
+fn resolve(
+    device: &wgpu::Device,
+    source: &wgpu::TextureView,
+    destination: &wgpu::TextureView,
+    regions: &[wgpu::Rect],
+) -> wgpu::CommandBuffer {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.resolve_texture(source, destination, regions);
+    encoder.finish()
+}
+
+const REGION: wgpu::Rect = wgpu::Rect {
+    x: 16,
+    y: 8,
+    width: 32,
+    height: 16,
+};
+
+#[test]
+fn resolve_regions() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let msaa = create_view(&device, format, 4, wgpu::TextureUsages::COPY_SRC);
+    let target = create_view(&device, format, 1, wgpu::TextureUsages::COPY_DST);
+
+    let command_buffer = valid(&device, || {
+        resolve(
+            &device,
+            &msaa,
+            &target,
+            &[
+                REGION,
+                wgpu::Rect {
+                    x: 0,
+                    y: 0,
+                    width: 64,
+                    height: 64,
+                },
+            ],
+        )
+    });
+    queue.submit([command_buffer]);
+    valid(&device, || resolve(&device, &msaa, &target, &[]));
+
+    fail(
+        &device,
+        || {
+            resolve(
+                &device,
+                &msaa,
+                &target,
+                &[wgpu::Rect {
+                    x: 48,
+                    y: 0,
+                    width: 32,
+                    height: 16,
+                }],
+            )
+        },
+        Some("is out of the bounds of the 64x64 views"),
+    );
+}
+
+#[test]
+fn resolve_validation() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let msaa = create_view(&device, format, 4, wgpu::TextureUsages::COPY_SRC);
+    let target = create_view(&device, format, 1, wgpu::TextureUsages::COPY_DST);
+
+    fail(
+        &device,
+        || resolve(&device, &target, &target, &[REGION]),
+        Some("The resolve source must be multisampled"),
+    );
+    fail(
+        &device,
+        || resolve(&device, &msaa, &msaa, &[REGION]),
+        Some("The resolve source must be multisampled"),
+    );
+
+    let bgra_target = create_view(
+        &device,
+        wgpu::TextureFormat::Bgra8Unorm,
+        1,
+        wgpu::TextureUsages::COPY_DST,
+    );
+    fail(
+        &device,
+        || resolve(&device, &msaa, &bgra_target, &[REGION]),
+        Some("must match the resolve destination format"),
+    );
+
+    let no_copy_src = create_view(&device, format, 4, wgpu::TextureUsages::empty());
+    fail(
+        &device,
+        || resolve(&device, &no_copy_src, &target, &[REGION]),
+        Some("COPY_SRC"),
+    );
+    let no_copy_dst = create_view(&device, format, 1, wgpu::TextureUsages::empty());
+    fail(
+        &device,
+        || resolve(&device, &msaa, &no_copy_dst, &[REGION]),
+        Some("COPY_DST"),
+    );
+}
@@ -0,0 +1,33 @@
+//! Tests of [`wgpu::Queue::get_timestamp_calibration`].
+
+use wgpu::*;
+
+#[test]
+fn noop_has_no_calibration() {
+    let (_device, queue) = Device::noop(&DeviceDescriptor::default());
+
+    assert_eq!(queue.get_timestamp_calibration(), None);
+}
+
+#[test]
+fn gpu_to_cpu_timestamp() {
+    let calibration = TimestampCalibration {
+        gpu_timestamp: 1_000,
+        cpu_timestamp: 50_000,
+        timestamp_period: 2.5,
+    };
+
+    assert_eq!(calibration.gpu_to_cpu_timestamp(1_000), 50_000);
+    assert_eq!(calibration.gpu_to_cpu_timestamp(1_400), 51_000);
+    assert_eq!(calibration.gpu_to_cpu_timestamp(600), 49_000);
+    assert_eq!(calibration.gpu_to_cpu_timestamp(0), 47_500);
+    // Timestamps before the start of the CPU clock saturate.
+    assert_eq!(
+        TimestampCalibration {
+            cpu_timestamp: 100,
+            ..calibration
+        }
+        .gpu_to_cpu_timestamp(0),
+        0
+    );
+}
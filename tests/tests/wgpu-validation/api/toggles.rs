@@ -0,0 +1,76 @@
+//! Tests of [`wgpu::TogglesDescriptor`] and [`wgpu::Adapter::applied_workarounds`].
+
+fn noop_adapter(toggles: wgpu::TogglesDescriptor) -> wgpu::Adapter {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        toggles,
+        ..Default::default()
+    });
+    pollster::block_on(instance.request_adapter(&Default::default())).unwrap()
+}
+
+/// The noop backend has no workarounds, so forcing one on has no effect.
+#[test]
+fn no_workarounds_on_noop() {
+    let adapter = noop_adapter(wgpu::TogglesDescriptor {
+        force_enabled: wgpu::Toggles::VULKAN_SEPARATE_ENTRY_POINTS,
+        force_disabled: wgpu::Toggles::empty(),
+    });
+    assert_eq!(adapter.applied_workarounds(), wgpu::Toggles::empty());
+}
+
+#[test]
+fn device_toggles() {
+    let adapter = noop_adapter(wgpu::TogglesDescriptor::default());
+    let (device, _queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        toggles: wgpu::TogglesDescriptor {
+            force_enabled: wgpu::Toggles::empty(),
+            force_disabled: wgpu::Toggles::DEVICE,
+        },
+        ..Default::default()
+    }))
+    .unwrap();
+
+    // Resources and shader modules are created as usual, without zero
+    // initialization or bounds checks.
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    buffer.map_async(wgpu::MapMode::Read, .., |result| result.unwrap());
+    device.poll(wgpu::PollType::Wait).unwrap();
+    let _module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(
+            "@group(0) @binding(0) var<storage> data: array<u32>;
+            @compute @workgroup_size(1) fn main() { _ = data[0]; }"
+                .into(),
+        ),
+    });
+}
+
+#[test]
+fn adapter_toggles_on_device() {
+    let adapter = noop_adapter(wgpu::TogglesDescriptor::default());
+    let error = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        toggles: wgpu::TogglesDescriptor {
+            force_enabled: wgpu::Toggles::LAZY_ZERO_INITIALIZATION,
+            force_disabled: wgpu::Toggles::VULKAN_SEPARATE_ENTRY_POINTS,
+        },
+        ..Default::default()
+    }))
+    .unwrap_err();
+    assert!(
+        error.to_string().contains("VULKAN_SEPARATE_ENTRY_POINTS"),
+        "{error}"
+    );
+}
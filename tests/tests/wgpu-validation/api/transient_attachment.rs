@@ -0,0 +1,219 @@
+//! Tests of [`wgpu::TextureUsages::TRANSIENT`].
+
+use wgpu_test::{fail, valid};
+
+fn create_texture(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("attachment"),
+        size: wgpu::Extent3d {
+            width: 64,
+            height: 64,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    })
+}
+
+fn transient_usage() -> wgpu::TextureUsages {
+    wgpu::TextureUsages::TRANSIENT | wgpu::TextureUsages::RENDER_ATTACHMENT
+}
+
+fn run_color_pass(
+    device: &wgpu::Device,
+    view: &wgpu::TextureView,
+    resolve_target: Option<&wgpu::TextureView>,
+    ops: wgpu::Operations<wgpu::Color>,
+) -> wgpu::CommandBuffer {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            depth_slice: None,
+            resolve_target,
+            ops,
+        })],
+        ..Default::default()
+    });
+    encoder.finish()
+}
+
+#[test]
+fn requires_render_attachment_only() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    for usage in [
+        wgpu::TextureUsages::TRANSIENT,
+        transient_usage() | wgpu::TextureUsages::TEXTURE_BINDING,
+        transient_usage() | wgpu::TextureUsages::COPY_SRC,
+    ] {
+        fail(
+            &device,
+            || create_texture(&device, wgpu::TextureFormat::Rgba8Unorm, 1, usage),
+            Some("usage TRANSIENT must be combined with RENDER_ATTACHMENT"),
+        );
+    }
+
+    valid(&device, || {
+        create_texture(
+            &device,
+            wgpu::TextureFormat::Rgba8Unorm,
+            4,
+            transient_usage(),
+        )
+    });
+}
+
+#[test]
+fn msaa_color_attachment() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    let msaa = create_texture(
+        &device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        4,
+        transient_usage(),
+    );
+    let msaa_view = msaa.create_view(&Default::default());
+    let resolve = create_texture(
+        &device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        1,
+        wgpu::TextureUsages::RENDER_ATTACHMENT,
+    );
+    let resolve_view = resolve.create_view(&Default::default());
+
+    let clear_and_discard = wgpu::Operations {
+        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+        store: wgpu::StoreOp::Discard,
+    };
+    let command_buffer = valid(&device, || {
+        run_color_pass(&device, &msaa_view, Some(&resolve_view), clear_and_discard)
+    });
+    queue.submit([command_buffer]);
+
+    fail(
+        &device,
+        || {
+            run_color_pass(
+                &device,
+                &msaa_view,
+                Some(&resolve_view),
+                wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Discard,
+                },
+            )
+        },
+        Some("its LoadOp must be `Clear`"),
+    );
+    fail(
+        &device,
+        || {
+            run_color_pass(
+                &device,
+                &msaa_view,
+                Some(&resolve_view),
+                wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            )
+        },
+        Some("its StoreOp must be `Discard`"),
+    );
+}
+
+#[test]
+fn cannot_be_resolve_target() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    let msaa = create_texture(
+        &device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        4,
+        wgpu::TextureUsages::RENDER_ATTACHMENT,
+    );
+    let msaa_view = msaa.create_view(&Default::default());
+    let resolve = create_texture(
+        &device,
+        wgpu::TextureFormat::Rgba8Unorm,
+        1,
+        transient_usage(),
+    );
+    let resolve_view = resolve.create_view(&Default::default());
+
+    fail(
+        &device,
+        || {
+            run_color_pass(
+                &device,
+                &msaa_view,
+                Some(&resolve_view),
+                wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Discard,
+                },
+            )
+        },
+        Some("can't be a transient texture"),
+    );
+}
+
+#[test]
+fn depth_stencil_attachment() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    let depth = create_texture(
+        &device,
+        wgpu::TextureFormat::Depth24PlusStencil8,
+        1,
+        transient_usage(),
+    );
+    let depth_view = depth.create_view(&Default::default());
+
+    let run_pass = |stencil_ops| {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Discard,
+                }),
+                stencil_ops,
+                resolve: None,
+            }),
+            ..Default::default()
+        });
+        encoder.finish()
+    };
+
+    let command_buffer = valid(&device, || {
+        run_pass(Some(wgpu::Operations {
+            load: wgpu::LoadOp::Clear(0),
+            store: wgpu::StoreOp::Discard,
+        }))
+    });
+    queue.submit([command_buffer]);
+
+    fail(
+        &device,
+        || {
+            run_pass(Some(wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Discard,
+            }))
+        },
+        Some("its LoadOp must be `Clear`"),
+    );
+}
@@ -0,0 +1,22 @@
+//! Tests of [`wgpu::Device::trim_memory`].
+
+#[test]
+fn releases_pooled_command_encoders() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    let encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    queue.submit([encoder.finish()]);
+    device.poll(wgpu::PollType::Wait).unwrap();
+
+    let report = device.trim_memory();
+    assert!(report.freed_command_encoders > 0);
+    assert_eq!(report.freed_memory_blocks, 0);
+
+    // Everything was released by the first call.
+    assert_eq!(device.trim_memory(), wgpu::TrimMemoryReport::default());
+
+    // The device keeps working after being trimmed.
+    let encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    queue.submit([encoder.finish()]);
+    device.poll(wgpu::PollType::Wait).unwrap();
+}
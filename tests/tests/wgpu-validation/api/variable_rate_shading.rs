@@ -0,0 +1,46 @@
+//! Tests of [`wgpu::RenderPass::set_shading_rate`].
+
+use wgpu_test::{create_render_target, fail, run_render_pass, valid};
+
+fn set_shading_rates(device: &wgpu::Device, rates: &[wgpu::ShadingRate]) -> wgpu::CommandBuffer {
+    let view = create_render_target(device);
+    run_render_pass(device, &view, |pass| {
+        for &rate in rates {
+            pass.set_shading_rate(rate);
+        }
+    })
+}
+
+#[test]
+fn shading_rate_requires_feature() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+
+    fail(
+        &device,
+        || set_shading_rates(&device, &[wgpu::ShadingRate::TwoByTwo]),
+        Some("VARIABLE_RATE_SHADING"),
+    );
+}
+
+#[test]
+fn all_shading_rates() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::VARIABLE_RATE_SHADING,
+        ..Default::default()
+    });
+
+    valid(&device, || {
+        set_shading_rates(
+            &device,
+            &[
+                wgpu::ShadingRate::OneByTwo,
+                wgpu::ShadingRate::TwoByOne,
+                wgpu::ShadingRate::TwoByTwo,
+                wgpu::ShadingRate::TwoByFour,
+                wgpu::ShadingRate::FourByTwo,
+                wgpu::ShadingRate::FourByFour,
+                wgpu::ShadingRate::OneByOne,
+            ],
+        )
+    });
+}
@@ -0,0 +1,116 @@
+//! Tests of [`wgpu::Features::VERTEX_INSTANCE_STEP_RATE`].
+
+use core::ops::Range;
+
+use wgpu::*;
+use wgpu_test::{create_render_target, fail, run_render_pass, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main(@location(0) offset: vec4f) -> @builtin(position) vec4f {
+        return offset;
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+";
+
+fn create_pipeline(device: &Device, step_mode: VertexStepMode) -> RenderPipeline {
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[VertexBufferLayout {
+                array_stride: 16,
+                step_mode,
+                attributes: &vertex_attr_array![0 => Float32x4],
+            }],
+        },
+        primitive: PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: MultisampleState::default(),
+        fragment: Some(FragmentState {
+            module: &module,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn draw(
+    device: &Device,
+    pipeline: &RenderPipeline,
+    buffer: &Buffer,
+    instances: Range<u32>,
+) -> CommandBuffer {
+    let view = create_render_target(device);
+    run_render_pass(device, &view, |pass| {
+        pass.set_pipeline(pipeline);
+        pass.set_vertex_buffer(0, buffer.slice(..));
+        pass.draw(0..3, instances);
+    })
+}
+
+#[test]
+fn instance_step_rate_requires_feature() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    valid(&device, || {
+        create_pipeline(&device, VertexStepMode::InstanceStepRate { rate: 1 })
+    });
+    fail(
+        &device,
+        || create_pipeline(&device, VertexStepMode::InstanceStepRate { rate: 4 }),
+        Some("VERTEX_INSTANCE_STEP_RATE"),
+    );
+}
+
+#[test]
+fn instance_step_rate() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::VERTEX_INSTANCE_STEP_RATE,
+        ..Default::default()
+    });
+
+    fail(
+        &device,
+        || create_pipeline(&device, VertexStepMode::InstanceStepRate { rate: 0 }),
+        Some("has an instance step rate of 0"),
+    );
+
+    let pipeline = valid(&device, || {
+        create_pipeline(&device, VertexStepMode::InstanceStepRate { rate: 4 })
+    });
+    // Two elements, each shared by four instances.
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("instances"),
+        size: 32,
+        usage: BufferUsages::VERTEX,
+        mapped_at_creation: false,
+    });
+
+    valid(&device, || draw(&device, &pipeline, &buffer, 0..8));
+    valid(&device, || draw(&device, &pipeline, &buffer, 1..5));
+    fail(
+        &device,
+        || draw(&device, &pipeline, &buffer, 0..9),
+        Some("Instance 3 extends beyond limit 2"),
+    );
+    fail(
+        &device,
+        || draw(&device, &pipeline, &buffer, 1..6),
+        Some("Instance 3 extends beyond limit 2"),
+    );
+}
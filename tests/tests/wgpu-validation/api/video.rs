@@ -0,0 +1,16 @@
+//! Tests of [`wgpu::video`].
+//!
+//! Video decoding is only implemented on Metal, so the noop backend can't decode.
+
+use wgpu::video::{VideoCodec, VideoDecodeError, VideoDecoder};
+
+#[test]
+fn video_decoder_unsupported_on_noop() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    for codec in [VideoCodec::H264, VideoCodec::Hevc] {
+        assert_eq!(
+            VideoDecoder::new(&device, codec).unwrap_err(),
+            VideoDecodeError::Unsupported
+        );
+    }
+}
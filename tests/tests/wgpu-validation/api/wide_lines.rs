@@ -0,0 +1,145 @@
+//! Tests of [`wgpu::Features::WIDE_LINES`] and [`wgpu::Features::LINE_STIPPLE`].
+
+use wgpu::*;
+use wgpu_test::{fail, valid};
+
+const SHADER: &str = "
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+        return vec4f(f32(index), 0.0, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main() -> @location(0) vec4f {
+        return vec4f(1.0);
+    }
+";
+
+fn create_pipeline(
+    device: &Device,
+    primitive: PrimitiveState,
+    multisample: MultisampleState,
+) -> RenderPipeline {
+    let module = device.create_shader_module(ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    });
+    device.create_render_pipeline(&RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            compilation_options: Default::default(),
+            buffers: &[],
+        },
+        primitive,
+        depth_stencil: None,
+        multisample,
+        fragment: Some(FragmentState {
+            module: &module,
+            entry_point: Some("fs_main"),
+            compilation_options: Default::default(),
+            targets: &[Some(TextureFormat::Rgba8Unorm.into())],
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn lines(line_width: f32, line_stipple: Option<LineStipple>) -> PrimitiveState {
+    PrimitiveState {
+        topology: PrimitiveTopology::LineList,
+        line_width,
+        line_stipple,
+        ..Default::default()
+    }
+}
+
+const DASHED: LineStipple = LineStipple {
+    factor: 4,
+    pattern: 0x00ff,
+};
+
+#[test]
+fn line_features_required() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+
+    valid(&device, || {
+        create_pipeline(&device, lines(1.0, None), MultisampleState::default())
+    });
+    fail(
+        &device,
+        || create_pipeline(&device, lines(4.0, None), MultisampleState::default()),
+        Some("WIDE_LINES"),
+    );
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                lines(1.0, Some(DASHED)),
+                MultisampleState::default(),
+            )
+        },
+        Some("LINE_STIPPLE"),
+    );
+}
+
+#[test]
+fn line_width_and_stipple() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor {
+        required_features: Features::WIDE_LINES | Features::LINE_STIPPLE,
+        ..Default::default()
+    });
+
+    valid(&device, || {
+        create_pipeline(
+            &device,
+            lines(4.0, Some(DASHED)),
+            MultisampleState::default(),
+        )
+    });
+    fail(
+        &device,
+        || create_pipeline(&device, lines(0.0, None), MultisampleState::default()),
+        Some("must be a finite number greater than zero"),
+    );
+    fail(
+        &device,
+        || create_pipeline(&device, lines(f32::NAN, None), MultisampleState::default()),
+        Some("must be a finite number greater than zero"),
+    );
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                lines(
+                    1.0,
+                    Some(LineStipple {
+                        factor: 0,
+                        pattern: 0xffff,
+                    }),
+                ),
+                MultisampleState::default(),
+            )
+        },
+        Some("must be between 1 and 256"),
+    );
+    fail(
+        &device,
+        || {
+            create_pipeline(
+                &device,
+                lines(1.0, Some(DASHED)),
+                MultisampleState {
+                    count: 4,
+                    alpha_to_coverage_enabled: true,
+                    ..Default::default()
+                },
+            )
+        },
+        Some("can't be drawn with alpha to coverage"),
+    );
+}
@@ -0,0 +1,80 @@
+//! Tests of [`wgpu::ShaderModule::workgroup_memory`] and
+//! [`wgpu::ShaderRuntimeChecks::zero_initialize_workgroup_memory`].
+
+use wgpu::*;
+use wgpu_test::valid;
+
+const SHADER: &str = "
+    var<workgroup> tile: array<u32, 64>;
+    var<workgroup> unused: array<f32, 16>;
+
+    @compute @workgroup_size(64)
+    fn main(@builtin(local_invocation_index) index: u32) {
+        tile[index] = index;
+    }
+
+    @compute @workgroup_size(1)
+    fn no_workgroup_memory() {}
+";
+
+fn descriptor() -> ShaderModuleDescriptor<'static> {
+    ShaderModuleDescriptor {
+        label: None,
+        source: ShaderSource::Wgsl(SHADER.into()),
+    }
+}
+
+#[test]
+fn workgroup_memory_is_zero_initialized_by_default() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let module = device.create_shader_module(descriptor());
+
+    assert_eq!(
+        module.workgroup_memory("main"),
+        Some(WorkgroupMemoryInfo {
+            size: 256,
+            zero_initialized: true,
+        })
+    );
+    assert_eq!(
+        module.workgroup_memory("no_workgroup_memory"),
+        Some(WorkgroupMemoryInfo {
+            size: 0,
+            zero_initialized: false,
+        })
+    );
+    assert_eq!(module.workgroup_memory("missing"), None);
+}
+
+#[test]
+fn workgroup_memory_zero_initialization_can_be_disabled() {
+    let (device, _queue) = Device::noop(&DeviceDescriptor::default());
+    let module = unsafe {
+        device.create_shader_module_trusted(
+            descriptor(),
+            ShaderRuntimeChecks {
+                zero_initialize_workgroup_memory: false,
+                ..ShaderRuntimeChecks::checked()
+            },
+        )
+    };
+
+    assert_eq!(
+        module.workgroup_memory("main"),
+        Some(WorkgroupMemoryInfo {
+            size: 256,
+            zero_initialized: false,
+        })
+    );
+
+    valid(&device, || {
+        device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        })
+    });
+}
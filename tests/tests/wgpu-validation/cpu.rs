@@ -0,0 +1,284 @@
+//! Tests of [`wgpu::Backend::Cpu`], which check the results of real workloads.
+
+use wgpu::util::DeviceExt as _;
+
+fn device() -> (wgpu::Device, wgpu::Queue) {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::CPU,
+        ..Default::default()
+    });
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .expect("CPU backend adapter absent when it should be");
+    assert_eq!(adapter.get_info().device_type, wgpu::DeviceType::Cpu);
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        required_limits: wgpu::Limits::downlevel_defaults(),
+        ..Default::default()
+    }))
+    .unwrap()
+}
+
+/// Copies `buffer` into a mappable buffer and returns its contents.
+fn read_buffer(device: &wgpu::Device, queue: &wgpu::Queue, buffer: &wgpu::Buffer) -> Vec<u8> {
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: buffer.size(),
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+    queue.submit([encoder.finish()]);
+    staging
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, |result| result.unwrap());
+    device.poll(wgpu::PollType::Wait).unwrap();
+    staging.slice(..).get_mapped_range().to_vec()
+}
+
+#[test]
+fn compute() {
+    let (device, queue) = device();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(
+            "
+            override factor: u32 = 2u;
+            var<workgroup> partial: array<u32, 64>;
+            @group(0) @binding(0) var<storage, read_write> values: array<u32>;
+            @group(0) @binding(1) var<storage, read_write> sums: array<atomic<u32>, 1>;
+
+            @compute @workgroup_size(64)
+            fn main(@builtin(global_invocation_id) id: vec3u, @builtin(local_invocation_index) i: u32) {
+                values[id.x] = values[id.x] * factor + 1u;
+                partial[i] = values[id.x];
+                workgroupBarrier();
+                if i == 0u {
+                    var sum = 0u;
+                    for (var j = 0u; j < 64u; j++) {
+                        sum += partial[j];
+                    }
+                    atomicAdd(&sums[0], sum);
+                }
+            }
+            "
+            .into(),
+        ),
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: None,
+        layout: None,
+        module: &module,
+        entry_point: None,
+        compilation_options: wgpu::PipelineCompilationOptions {
+            constants: &[("factor", 3.0)],
+            ..Default::default()
+        },
+        cache: None,
+    });
+
+    let input: Vec<u32> = (0..256).collect();
+    let values = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice(&input),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+    });
+    let sums = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &[0; 4],
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: values.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: sums.as_entire_binding(),
+            },
+        ],
+    });
+
+    let mut encoder = device.create_command_encoder(&Default::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&Default::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(4, 1, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    let expected: Vec<u32> = input.iter().map(|x| x * 3 + 1).collect();
+    let values = read_buffer(&device, &queue, &values);
+    assert_eq!(bytemuck::cast_slice::<u8, u32>(&values), expected);
+    let sums = read_buffer(&device, &queue, &sums);
+    assert_eq!(
+        bytemuck::cast_slice::<u8, u32>(&sums),
+        [expected.iter().sum::<u32>()]
+    );
+}
+
+#[test]
+fn render() {
+    let (device, queue) = device();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(
+            "
+            struct Varyings {
+                @builtin(position) position: vec4f,
+                @location(0) color: vec4f,
+            }
+
+            @vertex
+            fn vs_main(@location(0) position: vec2f, @location(1) color: vec4f) -> Varyings {
+                return Varyings(vec4f(position, 0.5, 1.0), color);
+            }
+
+            @fragment
+            fn fs_main(in: Varyings) -> @location(0) vec4f {
+                return in.color;
+            }
+            "
+            .into(),
+        ),
+    });
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: None,
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: 24,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4],
+            }],
+        },
+        primitive: Default::default(),
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth32Float,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: Default::default(),
+            bias: Default::default(),
+        }),
+        multisample: Default::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: None,
+            compilation_options: Default::default(),
+            targets: &[Some(format.into())],
+        }),
+        multiview: None,
+        cache: None,
+    });
+
+    // A red triangle covering the left half of the target, drawn over a green one covering
+    // the bottom half, which fails the depth test where they overlap.
+    #[rustfmt::skip]
+    let vertices: [f32; 36] = [
+        -1.0, -1.0, 1.0, 0.0, 0.0, 1.0,
+         0.0, -1.0, 1.0, 0.0, 0.0, 1.0,
+        -1.0,  3.0, 1.0, 0.0, 0.0, 1.0,
+        -1.0, -1.0, 0.0, 1.0, 0.0, 1.0,
+         3.0, -1.0, 0.0, 1.0, 0.0, 1.0,
+        -1.0,  0.0, 0.0, 1.0, 0.0, 1.0,
+    ];
+    let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice(&vertices),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+
+    let size = wgpu::Extent3d {
+        width: 4,
+        height: 4,
+        depth_or_array_layers: 1,
+    };
+    let target = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let depth = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Depth32Float,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256 * 4,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&Default::default());
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &target.create_view(&Default::default()),
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLUE),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth.create_view(&Default::default()),
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+                resolve: None,
+            }),
+            ..Default::default()
+        });
+        pass.set_pipeline(&pipeline);
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.draw(0..6, 0..1);
+    }
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(256),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    queue.submit([encoder.finish()]);
+
+    let data = read_buffer(&device, &queue, &readback);
+    let pixel = |x: usize, y: usize| &data[y * 256 + x * 4..][..4];
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    assert_eq!(pixel(0, 0), RED);
+    assert_eq!(pixel(1, 3), RED);
+    assert_eq!(pixel(3, 3), GREEN);
+    assert_eq!(pixel(3, 0), BLUE);
+}
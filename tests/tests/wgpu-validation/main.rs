@@ -1,4 +1,7 @@
 //! Tests of the [`wgpu`] library API that are not run against a particular GPU.
 
 mod api;
+mod cpu;
 mod noop;
+mod remote;
+mod util;
@@ -19,7 +19,10 @@ fn device_is_available_when_requested() {
     let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
         backends: wgpu::Backends::NOOP,
         backend_options: wgpu::BackendOptions {
-            noop: wgpu::NoopBackendOptions { enable: true },
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
             ..Default::default()
         },
         ..Default::default()
@@ -56,3 +59,186 @@ fn device_and_buffers() {
     device.poll(wgpu::PollType::Wait).unwrap();
     assert!(done2.load(Relaxed));
 }
+
+#[test]
+fn no_graphics_debugger_capture() {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // There is no device to capture yet.
+    assert!(!unsafe { instance.start_graphics_debugger_capture() });
+
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .unwrap();
+    let (_device, _queue) =
+        pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).unwrap();
+
+    // The noop backend can't be captured by any graphics debugger.
+    assert!(!unsafe { instance.start_graphics_debugger_capture() });
+    unsafe { instance.stop_graphics_debugger_capture() };
+}
+
+#[test]
+fn barrier_report() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let buffer_desc = |label| wgpu::BufferDescriptor {
+        label: Some(label),
+        size: 256,
+        usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    };
+    let src = device.create_buffer(&buffer_desc("src"));
+    let dst = device.create_buffer(&buffer_desc("dst"));
+
+    let record = || {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("copy"),
+        });
+        encoder.copy_buffer_to_buffer(&src, 0, &dst, 0, 256);
+        encoder.transition_resources(
+            [wgpu::BufferTransition {
+                buffer: &dst,
+                state: wgpu::BufferUses::COPY_SRC,
+            }]
+            .into_iter(),
+            [].into_iter(),
+        );
+        encoder.finish()
+    };
+
+    // Nothing is reported while logging is disabled.
+    queue.submit([record()]);
+    assert_eq!(
+        device.take_barrier_report(),
+        Some(wgpu::BarrierReport::default())
+    );
+
+    device.set_barrier_logging(true);
+    queue.submit([record()]);
+    let report = device.take_barrier_report().unwrap();
+    assert_eq!(
+        report.passes[0],
+        wgpu::PassBarrierReport {
+            origin: "transition_resources".into(),
+            buffer_barriers: 1,
+            texture_barriers: 0,
+        }
+    );
+    assert!(report.passes[1..].iter().any(|pass| pass.origin
+        == "submission of CommandBuffer with 'copy' label"
+        && pass.buffer_barriers > 0));
+
+    // The report is drained.
+    assert_eq!(
+        device.take_barrier_report(),
+        Some(wgpu::BarrierReport::default())
+    );
+}
+
+#[test]
+fn recorded_commands() {
+    let (device, queue) = wgpu::Device::noop_recording(&wgpu::DeviceDescriptor::default());
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(
+            "
+            @group(0) @binding(0) var<storage, read_write> values: array<u32>;
+
+            @compute @workgroup_size(64)
+            fn main(@builtin(global_invocation_id) id: vec3u) {
+                values[id.x] += 1u;
+            }
+            "
+            .into(),
+        ),
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("pipeline"),
+        layout: None,
+        module: &module,
+        entry_point: None,
+        compilation_options: Default::default(),
+        cache: None,
+    });
+    let values = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("values"),
+        size: 256,
+        usage: wgpu::BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("bind group"),
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: values.as_entire_binding(),
+        }],
+    });
+
+    let mut encoder = device.create_command_encoder(&Default::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("pass"),
+            timestamp_writes: None,
+            pipeline_statistics_query: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(1, 1, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    let recorded = queue.take_recorded_command_buffers();
+    let commands: Vec<_> = recorded
+        .iter()
+        .flat_map(|cmd_buf| &cmd_buf.commands)
+        .collect();
+
+    // wgpu-core may insert barriers into the pass, which are checked below.
+    let pass_commands: Vec<_> = commands
+        .iter()
+        .copied()
+        .skip_while(|command| !matches!(command, wgpu::RecordedCommand::BeginComputePass { .. }))
+        .filter(|command| !matches!(command, wgpu::RecordedCommand::BufferBarrier { .. }))
+        .collect();
+    assert_eq!(
+        pass_commands,
+        [
+            &wgpu::RecordedCommand::BeginComputePass {
+                label: Some("pass".into()),
+            },
+            &wgpu::RecordedCommand::SetComputePipeline {
+                pipeline: Some("pipeline".into()),
+            },
+            &wgpu::RecordedCommand::SetBindGroup {
+                index: 0,
+                bind_group: Some("bind group".into()),
+                dynamic_offsets: Vec::new(),
+            },
+            &wgpu::RecordedCommand::Dispatch {
+                group_count: [1, 1, 1],
+            },
+            &wgpu::RecordedCommand::EndComputePass,
+        ]
+    );
+
+    // The buffer is transitioned for its use in the pass.
+    assert!(commands.iter().any(
+        |command| matches!(command, wgpu::RecordedCommand::BufferBarrier { buffer, to, .. }
+            if buffer.as_deref() == Some("values") && to.contains(wgpu::BufferUses::STORAGE_READ_WRITE))
+    ));
+
+    // Recordings are only returned once.
+    assert!(queue.take_recorded_command_buffers().is_empty());
+}
@@ -0,0 +1,261 @@
+//! Tests of `wgpu-remote`, with a server running [`wgpu::Backend::Cpu`] on another thread.
+
+use std::{
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use wgpu::util::DeviceExt as _;
+
+fn device() -> (wgpu::Device, wgpu::Queue) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let server = wgpu_remote::Server::new(wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::CPU,
+            ..Default::default()
+        }));
+        let (stream, _) = listener.accept().unwrap();
+        server.serve(stream).unwrap();
+    });
+
+    let instance = wgpu_remote::connect(TcpStream::connect(address).unwrap()).unwrap();
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+            .expect("the server has no adapter");
+    assert_eq!(adapter.get_info().device_type, wgpu::DeviceType::Cpu);
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        required_limits: wgpu::Limits::downlevel_defaults(),
+        ..Default::default()
+    }))
+    .unwrap()
+}
+
+#[test]
+fn compute() {
+    let (device, queue) = device();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(
+            "
+            override factor: u32 = 2u;
+            @group(0) @binding(0) var<storage, read_write> values: array<u32>;
+
+            @compute @workgroup_size(64)
+            fn main(@builtin(global_invocation_id) id: vec3u) {
+                values[id.x] = values[id.x] * factor + 1u;
+            }
+            "
+            .into(),
+        ),
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: None,
+        layout: None,
+        module: &module,
+        entry_point: None,
+        compilation_options: wgpu::PipelineCompilationOptions {
+            constants: &[("factor", 3.0)],
+            ..Default::default()
+        },
+        cache: None,
+    });
+
+    let input: Vec<u32> = (0..256).collect();
+    let values = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::cast_slice(&input),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+    });
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: values.size(),
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: values.as_entire_binding(),
+        }],
+    });
+
+    let mut encoder = device.create_command_encoder(&Default::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&Default::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(4, 1, 1);
+    }
+    encoder.copy_buffer_to_buffer(&values, 0, &staging, 0, values.size());
+    let submission = queue.submit([encoder.finish()]);
+
+    staging
+        .slice(..)
+        .map_async(wgpu::MapMode::Read, |result| result.unwrap());
+    device
+        .poll(wgpu::PollType::WaitForSubmissionIndex(submission))
+        .unwrap();
+    let expected: Vec<u32> = input.iter().map(|x| x * 3 + 1).collect();
+    assert_eq!(
+        bytemuck::cast_slice::<u8, u32>(&staging.slice(..).get_mapped_range()),
+        expected
+    );
+}
+
+#[test]
+fn error_scope() {
+    let (device, _queue) = device();
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::MAP_WRITE,
+        mapped_at_creation: false,
+    });
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(matches!(error, Some(wgpu::Error::Validation { .. })));
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    assert!(pollster::block_on(device.pop_error_scope()).is_none());
+}
+
+#[test]
+fn unsupported_shader_source() {
+    let (device, _queue) = device();
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(Default::default())),
+    });
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(
+        matches!(error, Some(wgpu::Error::Validation { ref description, .. })
+            if description.contains("Only WGSL shaders"))
+    );
+
+    let info = pollster::block_on(module.get_compilation_info());
+    assert_eq!(info.messages.len(), 1);
+    assert_eq!(
+        info.messages[0].message_type,
+        wgpu::CompilationMessageType::Error
+    );
+}
+
+#[test]
+fn reported_error_keeps_scope_order() {
+    let (device, _queue) = device();
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::MAP_WRITE,
+        mapped_at_creation: false,
+    });
+    let _module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(Default::default())),
+    });
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(
+        matches!(error, Some(wgpu::Error::Validation { ref description, .. })
+            if !description.contains("Only WGSL shaders"))
+    );
+}
+
+#[test]
+fn reusable_command_buffer_without_feature() {
+    let (device, _queue) = device();
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _command_buffer = device
+        .create_command_encoder(&Default::default())
+        .finish_reusable();
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(matches!(error, Some(wgpu::Error::Validation { .. })));
+}
+
+#[test]
+fn unsupported_resources_are_invalid() {
+    let (device, _queue) = device();
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let sparse = device.create_sparse_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4,
+        usage: wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(
+        matches!(error, Some(wgpu::Error::Validation { ref description, .. })
+            if description.contains("Sparse resources"))
+    );
+
+    // Using the invalid buffer is an error rather than a panic.
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4,
+        usage: wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_buffer_to_buffer(&buffer, 0, &sparse, 0, 4);
+    let _command_buffer = encoder.finish();
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(matches!(error, Some(wgpu::Error::Validation { .. })));
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _tlas = device.create_tlas(&wgpu::CreateTlasDescriptor {
+        label: None,
+        max_instances: 1,
+        flags: wgpu::AccelerationStructureFlags::empty(),
+        update_mode: wgpu::AccelerationStructureUpdateMode::Build,
+    });
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(
+        matches!(error, Some(wgpu::Error::Validation { ref description, .. })
+            if description.contains("Ray tracing"))
+    );
+}
+
+#[test]
+fn unsupported_command_is_reported_on_finish() {
+    let (device, _queue) = device();
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.build_acceleration_structures([], []);
+    let _command_buffer = encoder.finish();
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(
+        matches!(error, Some(wgpu::Error::Validation { ref description, .. })
+            if description.contains("Ray tracing"))
+    );
+}
+
+#[test]
+fn parallel_render_encoder_without_feature() {
+    let (device, _queue) = device();
+
+    // The CPU backend of the server can't encode parallel render encoders, whose error
+    // comes back from it.
+    let view = wgpu_test::create_render_target(&device);
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _command_buffer = wgpu_test::run_render_pass(&device, &view, |pass| {
+        let mut parallel_encoder = pass.create_parallel_encoder();
+        parallel_encoder.set_blend_constant(wgpu::Color::WHITE);
+    });
+    let error = pollster::block_on(device.pop_error_scope());
+    assert!(
+        matches!(error, Some(wgpu::Error::Validation { ref description, .. })
+            if description.contains("PARALLEL_RENDER_ENCODING"))
+    );
+}
@@ -0,0 +1,509 @@
+//! Tests of [`wgpu::util`] helpers, run against the noop backend.
+
+use wgpu::util::{
+    BindGroupBuilder, BindGroupCache, ComputePipelineBuilder, DeviceExt as _, DeviceRecreator,
+    DownloadBuffer, GpuProfiler, RenderPipelineBuilder, StagingBelt, UploadStream,
+};
+
+fn read_back(device: &wgpu::Device, queue: &wgpu::Queue, buffer: &wgpu::Buffer) -> Vec<u8> {
+    let download = DownloadBuffer::read_buffer_async(device, queue, &buffer.slice(..));
+    device.poll(wgpu::PollType::Wait).unwrap();
+    pollster::block_on(download).unwrap().to_vec()
+}
+
+#[test]
+fn upload_stream_from_iter() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+
+    let data: Vec<u8> = (0..40).collect();
+    let mut stream = UploadStream::new(wgpu::BufferSize::new(16).unwrap());
+    // Chunk boundaries that don't line up with the stream's chunk size.
+    let written = stream.write_from_iter(&device, &queue, &buffer, 8, data.chunks(7));
+    assert_eq!(written, 40);
+
+    let contents = read_back(&device, &queue, &buffer);
+    assert_eq!(&contents[..8], &[0; 8]);
+    assert_eq!(&contents[8..48], &data[..]);
+    assert_eq!(&contents[48..], &[0; 16]);
+}
+
+#[test]
+fn upload_stream_from_reader_pads_tail() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 32,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    queue.write_buffer(&buffer, 0, &[0xFF; 32]);
+
+    let data: Vec<u8> = (1..=21).collect();
+    let mut stream = UploadStream::new(wgpu::BufferSize::new(8).unwrap());
+    let written = stream
+        .write_from_reader(&device, &queue, &buffer, 0, std::io::Cursor::new(&data))
+        .unwrap();
+    assert_eq!(written, 21);
+
+    let contents = read_back(&device, &queue, &buffer);
+    assert_eq!(&contents[..21], &data[..]);
+    assert_eq!(&contents[21..24], &[0; 3]);
+    assert_eq!(&contents[24..], &[0xFF; 8]);
+}
+
+#[test]
+fn staging_belt_stats_and_trim() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256,
+        usage: wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut belt = StagingBelt::new(64);
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    // Two writes which fit in one chunk, and one which needs a chunk of its own.
+    for (offset, size) in [(0, 32), (32, 32), (64, 128)] {
+        belt.write_buffer(
+            &mut encoder,
+            &buffer,
+            offset,
+            wgpu::BufferSize::new(size).unwrap(),
+            &device,
+        )
+        .fill(1);
+    }
+    belt.finish();
+    queue.submit(Some(encoder.finish()));
+    belt.recall();
+
+    let stats = belt.stats();
+    assert_eq!(stats.chunk_count, 2);
+    assert_eq!(stats.resident_bytes, 64 + 128);
+    assert_eq!(stats.peak_resident_bytes, 64 + 128);
+
+    device.poll(wgpu::PollType::Wait).unwrap();
+    // The oversized chunk goes first.
+    belt.trim(100);
+    let stats = belt.stats();
+    assert_eq!(stats.chunk_count, 1);
+    assert_eq!(stats.resident_bytes, 64);
+
+    belt.trim(0);
+    let stats = belt.stats();
+    assert_eq!(stats.chunk_count, 0);
+    assert_eq!(stats.resident_bytes, 0);
+    assert_eq!(stats.peak_resident_bytes, 64 + 128);
+}
+
+#[test]
+fn download_buffer_read_async_polls_the_device() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &[1, 2, 3, 4, 5, 6, 7, 8],
+        usage: wgpu::BufferUsages::COPY_SRC,
+    });
+
+    // Without a call to `Device::poll`.
+    let download = DownloadBuffer::read_buffer_async(&device, &queue, &buffer.slice(4..));
+    let download = pollster::block_on(download).unwrap();
+
+    assert_eq!(&*download, &[5, 6, 7, 8]);
+}
+
+#[test]
+fn download_buffer_as_slice_of() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let data = [1.0f32, -2.5, 0.125, 1e10];
+    queue.write_buffer(&buffer, 0, bytemuck::cast_slice(&data));
+
+    let download = DownloadBuffer::read_buffer_async(&device, &queue, &buffer.slice(..));
+    device.poll(wgpu::PollType::Wait).unwrap();
+    let download = pollster::block_on(download).unwrap();
+
+    assert_eq!(download.as_slice_of::<f32>(), &data);
+    assert_eq!(download.try_as_slice_of::<u32>().unwrap().len(), 4);
+    assert_eq!(
+        download.try_as_slice_of::<[u8; 3]>(),
+        Err(bytemuck::PodCastError::OutputSliceWouldHaveSlop)
+    );
+}
+
+#[test]
+fn read_texture_to_vec_empty_extent() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    for extent in [
+        wgpu::Extent3d {
+            width: 0,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        wgpu::Extent3d {
+            width: 4,
+            height: 0,
+            depth_or_array_layers: 1,
+        },
+        wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 0,
+        },
+    ] {
+        let result = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let sender = result.clone();
+        wgpu::util::read_texture_to_vec(
+            &device,
+            &queue,
+            &texture,
+            0,
+            wgpu::Origin3d::ZERO,
+            extent,
+            move |data| *sender.lock().unwrap() = Some(data.unwrap()),
+        );
+        // The callback runs without waiting for the device to be polled.
+        assert_eq!(result.lock().unwrap().take(), Some(Vec::new()));
+    }
+}
+
+#[test]
+fn gpu_profiler_scopes() {
+    let (device, queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor {
+        required_features: wgpu::Features::TIMESTAMP_QUERY
+            | wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS
+            | wgpu::Features::TIMESTAMP_QUERY_INSIDE_PASSES,
+        ..Default::default()
+    });
+    let mut profiler = GpuProfiler::new(&device, &queue, 3);
+
+    for _ in 0..2 {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        profiler.begin_scope("frame", &mut encoder);
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: profiler.compute_pass_timestamp_writes("pass"),
+                pipeline_statistics_query: None,
+            });
+            profiler.begin_scope("inside pass", &mut pass);
+            // Beyond the limit of 3 scopes per frame, so not recorded.
+            profiler.begin_scope("dropped", &mut pass);
+            profiler.end_scope(&mut pass);
+            profiler.end_scope(&mut pass);
+        }
+        profiler.end_scope(&mut encoder);
+        profiler.resolve_queries(&mut encoder);
+        queue.submit(Some(encoder.finish()));
+        profiler.end_frame();
+    }
+
+    device.poll(wgpu::PollType::Wait).unwrap();
+    for _ in 0..2 {
+        let scopes = profiler.process_finished_frame().unwrap();
+        let scopes: Vec<_> = scopes
+            .iter()
+            .map(|scope| (scope.label.as_str(), scope.depth))
+            .collect();
+        assert_eq!(scopes, [("frame", 0), ("pass", 1), ("inside pass", 1)]);
+    }
+    assert!(profiler.process_finished_frame().is_none());
+}
+
+#[test]
+fn bind_group_cache_dedups() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let buffers: Vec<_> = (0..2)
+        .map(|_| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 512,
+                usage: wgpu::BufferUsages::UNIFORM,
+                mapped_at_creation: false,
+            })
+        })
+        .collect();
+
+    let mut cache = BindGroupCache::new();
+    let a = BindGroupBuilder::new(&layout)
+        .buffer(0, &buffers[0])
+        .build_cached(&device, &mut cache);
+    // Labels aren't part of the key.
+    let b = BindGroupBuilder::new(&layout)
+        .label("same resources")
+        .buffer(0, &buffers[0])
+        .build_cached(&device, &mut cache);
+    let c = BindGroupBuilder::new(&layout)
+        .buffer(0, &buffers[1])
+        .build_cached(&device, &mut cache);
+    let d = BindGroupBuilder::new(&layout)
+        .buffer_range(0, &buffers[0], 256, None)
+        .build_cached(&device, &mut cache);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_ne!(a, d);
+    assert_eq!(cache.len(), 3);
+
+    cache.evict_unused();
+    assert_eq!(cache.len(), 3);
+    let e = BindGroupBuilder::new(&layout)
+        .buffer(0, &buffers[1])
+        .build_cached(&device, &mut cache);
+    assert_eq!(c, e);
+    cache.evict_unused();
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn bind_group_by_name() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(
+            "
+            @group(0) @binding(3) var<uniform> params: vec4<f32>;
+            @group(1) @binding(2) var<storage, read_write> output: array<f32>;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                output[0] = params.x;
+            }
+            "
+            .into(),
+        ),
+    });
+    assert_eq!(module.binding("params"), Some((0, 3)));
+    assert_eq!(module.binding("output"), Some((1, 2)));
+    assert_eq!(module.binding("missing"), None);
+
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 2,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+    // Creating the bind group validates the binding index against the layout.
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _bind_group = device
+        .create_bind_group_by_name(
+            None,
+            &layout,
+            &module,
+            &[("output", buffer.as_entire_binding())],
+        )
+        .unwrap();
+    assert!(pollster::block_on(device.pop_error_scope()).is_none());
+
+    assert_eq!(
+        device
+            .create_bind_group_by_name(
+                None,
+                &layout,
+                &module,
+                &[("missing", buffer.as_entire_binding())],
+            )
+            .unwrap_err(),
+        wgpu::util::NamedBindingError::NotFound {
+            name: "missing".to_string()
+        }
+    );
+    assert_eq!(
+        device
+            .create_bind_group_by_name(
+                None,
+                &layout,
+                &module,
+                &[
+                    ("output", buffer.as_entire_binding()),
+                    ("params", buffer.as_entire_binding()),
+                ],
+            )
+            .unwrap_err(),
+        wgpu::util::NamedBindingError::GroupMismatch {
+            name: "params".to_string(),
+            group: 0,
+            expected: 1,
+        }
+    );
+}
+
+#[test]
+fn device_recreator_recovers_lost_device() {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::NOOP,
+        backend_options: wgpu::BackendOptions {
+            noop: wgpu::NoopBackendOptions {
+                enable: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let recreator = pollster::block_on(DeviceRecreator::new(
+        instance,
+        &wgpu::RequestAdapterOptions::default(),
+        wgpu::DeviceDescriptor::default(),
+    ))
+    .unwrap();
+    assert_eq!(recreator.lost_reason(), None);
+
+    let recreated = Arc::new(AtomicUsize::new(0));
+    let removed = recreator.on_recreate(|_, _, _| panic!("removed callback was called"));
+    recreator.on_recreate({
+        let recreated = Arc::clone(&recreated);
+        move |_, device, _| {
+            let _buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 16,
+                usage: wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            recreated.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    assert!(recreator.remove_on_recreate(removed));
+    assert!(!recreator.remove_on_recreate(removed));
+
+    let old_device = recreator.device();
+    old_device.destroy();
+    let _ = old_device.poll(wgpu::PollType::Wait);
+    assert_eq!(
+        recreator.lost_reason(),
+        Some(wgpu::DeviceLostReason::Destroyed)
+    );
+
+    pollster::block_on(recreator.recreate(None)).unwrap();
+    assert_eq!(recreated.load(Ordering::Relaxed), 1);
+    assert_eq!(recreator.lost_reason(), None);
+    assert_ne!(recreator.device(), old_device);
+}
+
+#[test]
+fn pipeline_builders() {
+    let (device, _queue) = wgpu::Device::noop(&wgpu::DeviceDescriptor::default());
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(
+            "
+            @vertex
+            fn vs_main(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+                return vec4(position, 1.0);
+            }
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {
+                return vec4(1.0);
+            }
+
+            @compute @workgroup_size(1)
+            fn cs_main() {}
+            "
+            .into(),
+        ),
+    });
+    let attributes = wgpu::vertex_attr_array![0 => Float32x3];
+
+    let builder = RenderPipelineBuilder::new(None, &module, &module)
+        .label("mesh")
+        .vertex_entry_point("vs_main")
+        .fragment_entry_point("fs_main")
+        .vertex_buffer(wgpu::VertexBufferLayout {
+            array_stride: 12,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &attributes,
+        })
+        .color_target(wgpu::TextureFormat::Rgba8Unorm)
+        .depth(
+            wgpu::TextureFormat::Depth32Float,
+            wgpu::CompareFunction::Less,
+        )
+        .cull_mode(wgpu::Face::Back);
+    let desc = builder.descriptor();
+    assert_eq!(desc.vertex.buffers.len(), 1);
+    assert_eq!(desc.primitive.cull_mode, Some(wgpu::Face::Back));
+    assert_eq!(
+        desc.fragment.unwrap().targets,
+        [Some(wgpu::ColorTargetState::from(
+            wgpu::TextureFormat::Rgba8Unorm
+        ))]
+    );
+    assert!(desc.depth_stencil.unwrap().depth_write_enabled);
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    builder.build(&device);
+    RenderPipelineBuilder::new_vertex_only(None, &module)
+        .vertex_entry_point("vs_main")
+        .vertex_buffer(wgpu::VertexBufferLayout {
+            array_stride: 12,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &attributes,
+        })
+        .depth(
+            wgpu::TextureFormat::Depth32Float,
+            wgpu::CompareFunction::Less,
+        )
+        .build(&device);
+    ComputePipelineBuilder::new(None, &module)
+        .entry_point("cs_main")
+        .build(&device);
+    assert!(pollster::block_on(device.pop_error_scope()).is_none());
+}
@@ -41,7 +41,7 @@ pub enum BindGroupLayoutEntryError {
     #[error("Cube dimension is not expected for texture storage")]
     StorageTextureCube,
     #[error(
-        "Atomic storage textures are not allowed by baseline webgpu, they require the native only feature TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES"
+        "Atomic storage textures are not allowed by baseline webgpu, they require the native only feature TEXTURE_ATOMIC"
     )]
     StorageTextureAtomic,
     #[error("Arrays of bindings unsupported for this type of binding")]
@@ -52,6 +52,8 @@ pub enum BindGroupLayoutEntryError {
     SampleTypeFloatFilterableBindingMultisampled,
     #[error("Multisampled texture binding view dimension must be 2d, got {0:?}")]
     Non2DMultisampled(wgt::TextureViewDimension),
+    #[error("Y'CbCr conversion format {0:?} is not a multi-planar format")]
+    YcbcrConversionFormat(wgt::TextureFormat),
     #[error(transparent)]
     MissingFeatures(#[from] MissingFeatures),
     #[error(transparent)]
@@ -248,6 +250,15 @@ pub enum CreateBindGroupError {
         binding: u32,
         format: wgt::TextureFormat,
     },
+    #[error(
+        "Y'CbCr texture binding {binding} expects a view of format {layout_format:?} with all of its planes, but given a view with format = {view_format:?} and aspect = {view_aspect:?}"
+    )]
+    InvalidYcbcrTextureView {
+        binding: u32,
+        layout_format: wgt::TextureFormat,
+        view_format: wgt::TextureFormat,
+        view_aspect: wgt::TextureAspect,
+    },
     #[error(
         "Sampler binding {binding} expects comparison = {layout_cmp}, but given a sampler with comparison = {sampler_cmp}"
     )]
@@ -320,7 +331,8 @@ impl WebGpuError for CreateBindGroupError {
             | Self::StorageAtomicNotSupported(_)
             | Self::MissingTLASVertexReturn { .. }
             | Self::InvalidExternalTextureMipLevelCount { .. }
-            | Self::InvalidExternalTextureFormat { .. } => return ErrorType::Validation,
+            | Self::InvalidExternalTextureFormat { .. }
+            | Self::InvalidYcbcrTextureView { .. } => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -528,6 +540,11 @@ impl BindingTypeMaxCountValidator {
                     self.samplers.add(binding.visibility, count);
                     self.uniform_buffers.add(binding.visibility, count);
                 }
+                wgt::BindingType::YcbcrTexture { .. } => {
+                    // Bound as a texture with an immutable sampler.
+                    self.sampled_textures.add(binding.visibility, count);
+                    self.samplers.add(binding.visibility, count);
+                }
             }
         }
     }
@@ -49,4 +49,10 @@ impl CommandAllocator {
         let mut free_encoders = self.free_encoders.lock();
         free_encoders.push(encoder);
     }
+
+    /// Destroy all encoders in the free pool, returning how many there were.
+    pub(crate) fn trim(&self) -> usize {
+        let free_encoders = core::mem::take(&mut *self.free_encoders.lock());
+        free_encoders.len()
+    }
 }
@@ -35,6 +35,22 @@ bundle writes to push constant storage. Setting a pipeline initializes any push
 constant storage it could access to zero, and this initialization may also be
 visible after bundle execution.
 
+A bundle can opt out of parts of its isolation with the `inheritance` field of
+its [`RenderBundleEncoderDescriptor`]:
+
+- With [`RenderBundleInheritance::OCCLUSION_QUERY_SET`], the bundle may begin
+  and end occlusion queries, which use the occlusion query set of the pass.
+
+- With [`RenderBundleInheritance::PUSH_CONSTANTS`], setting a pipeline in the
+  bundle sets its push constants to the values last set on the pass, rather
+  than to zero.
+
+Timestamp and pipeline statistics queries name their query set, so they need
+no inheritance. Every query a bundle begins must also end in that bundle.
+
+[`RenderBundleInheritance::OCCLUSION_QUERY_SET`]: wgt::RenderBundleInheritance::OCCLUSION_QUERY_SET
+[`RenderBundleInheritance::PUSH_CONSTANTS`]: wgt::RenderBundleInheritance::PUSH_CONSTANTS
+
 ## Render Bundle Lifecycle
 
 To create a render bundle:
@@ -100,12 +116,13 @@ use crate::{
     Label, LabelHelpers,
     binding_model::{BindError, BindGroup, PipelineLayout},
     command::{
-        BasePass, BindGroupStateChange, ColorAttachmentError, DrawError, MapPassErr,
-        PassErrorScope, RenderCommandError, StateChange,
+        BasePass, BindGroupStateChange, ColorAttachmentError, CommandBuffer, DrawError, MapPassErr,
+        PassErrorScope, QueryResetMap, QueryUseError, RenderCommandError, SimplifiedQueryType,
+        StateChange,
     },
     device::{
-        AttachmentData, Device, DeviceError, MissingDownlevelFlags, RenderPassContext,
-        SHADER_STAGE_COUNT,
+        AttachmentData, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
+        RenderPassContext, SHADER_STAGE_COUNT,
     },
     hub::Hub,
     id,
@@ -113,11 +130,11 @@ use crate::{
     pipeline::{PipelineFlags, RenderPipeline, VertexStep},
     resource::{
         Buffer, DestroyedResourceError, Fallible, InvalidResourceError, Labeled, ParentDevice,
-        RawResourceAccess, TrackingData,
+        QuerySet, RawResourceAccess, TrackingData,
     },
     resource_log,
     snatch::SnatchGuard,
-    track::RenderBundleScope,
+    track::{RenderBundleScope, StatelessTracker},
 };
 
 use super::{
@@ -152,6 +169,11 @@ pub struct RenderBundleEncoderDescriptor<'a> {
     /// If this render bundle will rendering to multiple array layers in the
     /// attachments at the same time.
     pub multiview: Option<NonZeroU32>,
+    /// State of the render pass that this render bundle inherits.
+    ///
+    /// See [Render Bundle Isolation](self#render-bundle-isolation).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inheritance: wgt::RenderBundleInheritance,
 }
 
 #[derive(Debug)]
@@ -162,6 +184,7 @@ pub struct RenderBundleEncoder {
     pub(crate) context: RenderPassContext,
     pub(crate) is_depth_read_only: bool,
     pub(crate) is_stencil_read_only: bool,
+    pub(crate) inheritance: wgt::RenderBundleInheritance,
 
     // Resource binding dedupe state.
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -225,6 +248,7 @@ impl RenderBundleEncoder {
 
             is_depth_read_only,
             is_stencil_read_only,
+            inheritance: desc.inheritance,
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
         })
@@ -245,6 +269,7 @@ impl RenderBundleEncoder {
             },
             is_depth_read_only: false,
             is_stencil_read_only: false,
+            inheritance: wgt::RenderBundleInheritance::empty(),
 
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
@@ -280,9 +305,19 @@ impl RenderBundleEncoder {
 
         device.check_is_valid().map_pass_err(scope)?;
 
+        if self
+            .inheritance
+            .contains(wgt::RenderBundleInheritance::PUSH_CONSTANTS)
+        {
+            device
+                .require_features(wgt::Features::PUSH_CONSTANTS)
+                .map_pass_err(scope)?;
+        }
+
         let bind_group_guard = hub.bind_groups.read();
         let pipeline_guard = hub.render_pipelines.read();
         let buffer_guard = hub.buffers.read();
+        let query_set_guard = hub.query_sets.read();
 
         let mut state = State {
             trackers: RenderBundleScope::new(),
@@ -296,6 +331,9 @@ impl RenderBundleEncoder {
             buffer_memory_init_actions: Vec::new(),
             texture_memory_init_actions: Vec::new(),
             next_dynamic_offset: 0,
+            inheritance: self.inheritance,
+            active_occlusion_query: None,
+            active_pipeline_statistics_query: None,
         };
 
         let indices = &state.device.tracker_indices;
@@ -433,25 +471,87 @@ impl RenderBundleEncoder {
                     )
                     .map_pass_err(scope)?;
                 }
+                RenderCommand::DrawMeshTasks { .. }
+                | RenderCommand::DrawMeshTasksIndirect { .. }
+                | RenderCommand::MultiDrawMeshTasksIndirectCount { .. } => {
+                    let kind = match command {
+                        RenderCommand::DrawMeshTasksIndirect { count: 1, .. } => {
+                            DrawKind::DrawMeshTasksIndirect
+                        }
+                        RenderCommand::DrawMeshTasksIndirect { .. } => {
+                            DrawKind::MultiDrawMeshTasksIndirect
+                        }
+                        RenderCommand::MultiDrawMeshTasksIndirectCount { .. } => {
+                            DrawKind::MultiDrawMeshTasksIndirectCount
+                        }
+                        _ => DrawKind::DrawMeshTasks,
+                    };
+                    let scope = PassErrorScope::Draw {
+                        kind,
+                        indexed: false,
+                    };
+                    // Mesh pipelines can't be set in a render bundle either.
+                    return Err(RenderCommandError::Unimplemented(
+                        "mesh shaders in render bundles",
+                    ))
+                    .map_pass_err(scope);
+                }
                 RenderCommand::DrawIndirect { .. }
                 | RenderCommand::MultiDrawIndirectCount { .. } => unimplemented!(),
                 RenderCommand::PushDebugGroup { color: _, len: _ } => unimplemented!(),
                 RenderCommand::InsertDebugMarker { color: _, len: _ } => unimplemented!(),
                 RenderCommand::PopDebugGroup => unimplemented!(),
-                // Must check the TIMESTAMP_QUERY_INSIDE_PASSES feature
-                RenderCommand::WriteTimestamp { .. }
-                | RenderCommand::BeginOcclusionQuery { .. }
-                | RenderCommand::EndOcclusionQuery
-                | RenderCommand::BeginPipelineStatisticsQuery { .. }
-                | RenderCommand::EndPipelineStatisticsQuery => unimplemented!(),
+                RenderCommand::WriteTimestamp {
+                    query_set_id,
+                    query_index,
+                } => {
+                    let scope = PassErrorScope::WriteTimestamp;
+                    write_timestamp(&mut state, &query_set_guard, query_set_id, query_index)
+                        .map_pass_err(scope)?;
+                }
+                RenderCommand::BeginOcclusionQuery { query_index } => {
+                    let scope = PassErrorScope::BeginOcclusionQuery;
+                    begin_occlusion_query(&mut state, query_index).map_pass_err(scope)?;
+                }
+                RenderCommand::EndOcclusionQuery => {
+                    let scope = PassErrorScope::EndOcclusionQuery;
+                    end_occlusion_query(&mut state).map_pass_err(scope)?;
+                }
+                RenderCommand::BeginPipelineStatisticsQuery {
+                    query_set_id,
+                    query_index,
+                } => {
+                    let scope = PassErrorScope::BeginPipelineStatisticsQuery;
+                    begin_pipeline_statistics_query(
+                        &mut state,
+                        &query_set_guard,
+                        query_set_id,
+                        query_index,
+                    )
+                    .map_pass_err(scope)?;
+                }
+                RenderCommand::EndPipelineStatisticsQuery => {
+                    let scope = PassErrorScope::EndPipelineStatisticsQuery;
+                    end_pipeline_statistics_query(&mut state).map_pass_err(scope)?;
+                }
                 RenderCommand::ExecuteBundle(_)
                 | RenderCommand::SetBlendConstant(_)
                 | RenderCommand::SetStencilReference(_)
+                | RenderCommand::SetShadingRate(_)
+                | RenderCommand::BeginConditionalBlock { .. }
+                | RenderCommand::EndConditionalBlock
                 | RenderCommand::SetViewport { .. }
+                | RenderCommand::SetIndexedViewport { .. }
                 | RenderCommand::SetScissor(_) => unreachable!("not supported by a render bundle"),
             }
         }
 
+        if state.active_occlusion_query.is_some()
+            || state.active_pipeline_statistics_query.is_some()
+        {
+            return Err(RenderBundleErrorInner::UnendedQuery).map_pass_err(scope);
+        }
+
         let State {
             trackers,
             flat_dynamic_offsets,
@@ -478,6 +578,7 @@ impl RenderBundleEncoder {
             },
             is_depth_read_only: self.is_depth_read_only,
             is_stencil_read_only: self.is_stencil_read_only,
+            inheritance: self.inheritance,
             device: device.clone(),
             used: trackers,
             buffer_memory_init_actions,
@@ -511,7 +612,7 @@ impl RenderBundleEncoder {
 
 fn set_bind_group(
     state: &mut State,
-    bind_group_guard: &crate::storage::Storage<Fallible<BindGroup>>,
+    bind_group_guard: &crate::storage::StorageReadGuard<'_, Fallible<BindGroup>>,
     dynamic_offsets: &[u32],
     index: u32,
     num_dynamic_offsets: usize,
@@ -563,7 +664,7 @@ fn set_bind_group(
 
 fn set_pipeline(
     state: &mut State,
-    pipeline_guard: &crate::storage::Storage<Fallible<RenderPipeline>>,
+    pipeline_guard: &crate::storage::StorageReadGuard<'_, Fallible<RenderPipeline>>,
     context: &RenderPassContext,
     is_depth_read_only: bool,
     is_stencil_read_only: bool,
@@ -573,6 +674,16 @@ fn set_pipeline(
 
     pipeline.same_device(&state.device)?;
 
+    if pipeline.is_mesh {
+        return Err(RenderCommandError::Unimplemented("mesh pipelines in render bundles").into());
+    }
+    if pipeline.wide_lines.is_some() {
+        return Err(RenderCommandError::Unimplemented(
+            "pipelines with emulated wide lines in render bundles",
+        )
+        .into());
+    }
+
     context
         .check_compatible(&pipeline.pass_context, pipeline.as_ref())
         .map_err(RenderCommandError::IncompatiblePipelineTargets)?;
@@ -590,9 +701,15 @@ fn set_pipeline(
         .commands
         .push(ArcRenderCommand::SetPipeline(pipeline.clone()));
 
-    // If this pipeline uses push constants, zero out their values.
-    if let Some(iter) = pipeline_state.zero_push_constants() {
-        state.commands.extend(iter)
+    // If this pipeline uses push constants, zero out their values, unless
+    // `RenderBundle::execute` sets them to the values inherited from the pass.
+    if !state
+        .inheritance
+        .contains(wgt::RenderBundleInheritance::PUSH_CONSTANTS)
+    {
+        if let Some(iter) = pipeline_state.zero_push_constants() {
+            state.commands.extend(iter)
+        }
     }
 
     state.invalidate_bind_groups(&pipeline_state, &pipeline.layout);
@@ -605,7 +722,7 @@ fn set_pipeline(
 // This function is duplicative of `render::set_index_buffer`.
 fn set_index_buffer(
     state: &mut State,
-    buffer_guard: &crate::storage::Storage<Fallible<Buffer>>,
+    buffer_guard: &crate::storage::StorageReadGuard<'_, Fallible<Buffer>>,
     buffer_id: id::Id<id::markers::Buffer>,
     index_format: wgt::IndexFormat,
     offset: u64,
@@ -621,6 +738,18 @@ fn set_index_buffer(
     buffer.same_device(&state.device)?;
     buffer.check_usage(wgt::BufferUsages::INDEX)?;
 
+    if index_format == wgt::IndexFormat::Uint8 {
+        state
+            .device
+            .require_features(wgt::Features::INDEX_FORMAT_UINT8)?;
+        if state.device.index_expansion.is_some() {
+            return Err(RenderCommandError::Unimplemented(
+                "emulated 8 bit index buffers in render bundles",
+            )
+            .into());
+        }
+    }
+
     if offset % u64::try_from(index_format.byte_size()).unwrap() != 0 {
         return Err(RenderCommandError::UnalignedIndexBuffer {
             offset,
@@ -644,7 +773,7 @@ fn set_index_buffer(
 // This function is duplicative of `render::set_vertex_buffer`.
 fn set_vertex_buffer(
     state: &mut State,
-    buffer_guard: &crate::storage::Storage<Fallible<Buffer>>,
+    buffer_guard: &crate::storage::StorageReadGuard<'_, Fallible<Buffer>>,
     slot: u32,
     buffer_id: id::Id<id::markers::Buffer>,
     offset: u64,
@@ -749,6 +878,7 @@ fn draw_indexed(
 ) -> Result<(), RenderBundleErrorInner> {
     let pipeline = state.pipeline()?;
     let used_bind_groups = pipeline.used_bind_groups;
+    pipeline.pipeline.check_strip_index_format()?;
     let index = match state.index {
         Some(ref index) => index,
         None => return Err(DrawError::MissingIndexBuffer.into()),
@@ -785,7 +915,7 @@ fn draw_indexed(
 fn multi_draw_indirect(
     state: &mut State,
     dynamic_offsets: &[u32],
-    buffer_guard: &crate::storage::Storage<Fallible<Buffer>>,
+    buffer_guard: &crate::storage::StorageReadGuard<'_, Fallible<Buffer>>,
     buffer_id: id::Id<id::markers::Buffer>,
     offset: u64,
     indexed: bool,
@@ -796,6 +926,9 @@ fn multi_draw_indirect(
 
     let pipeline = state.pipeline()?;
     let used_bind_groups = pipeline.used_bind_groups;
+    if indexed {
+        pipeline.pipeline.check_strip_index_format()?;
+    }
 
     let buffer = buffer_guard.get(buffer_id).get()?;
 
@@ -823,7 +956,7 @@ fn multi_draw_indirect(
     } else {
         vertex_limits.vertex_limit
     };
-    let instance_limit = vertex_limits.instance_limit;
+    let instance_limit = vertex_limits.indirect_instance_limit();
 
     let buffer_uses = if state.device.indirect_validation.is_some() {
         wgt::BufferUses::STORAGE_READ_ONLY
@@ -847,6 +980,107 @@ fn multi_draw_indirect(
     Ok(())
 }
 
+fn write_timestamp(
+    state: &mut State,
+    query_set_guard: &crate::storage::StorageReadGuard<'_, Fallible<QuerySet>>,
+    query_set_id: id::Id<id::markers::QuerySet>,
+    query_index: u32,
+) -> Result<(), RenderBundleErrorInner> {
+    state
+        .device
+        .require_features(wgt::Features::TIMESTAMP_QUERY_INSIDE_PASSES)?;
+
+    let query_set = query_set_guard.get(query_set_id).get()?;
+
+    query_set.same_device(&state.device)?;
+    query_set.validate_query(SimplifiedQueryType::Timestamp, query_index, None)?;
+
+    state.commands.push(ArcRenderCommand::WriteTimestamp {
+        query_set,
+        query_index,
+    });
+    Ok(())
+}
+
+fn begin_occlusion_query(
+    state: &mut State,
+    query_index: u32,
+) -> Result<(), RenderBundleErrorInner> {
+    if !state
+        .inheritance
+        .contains(wgt::RenderBundleInheritance::OCCLUSION_QUERY_SET)
+    {
+        return Err(RenderBundleErrorInner::MissingOcclusionQuerySetInheritance);
+    }
+
+    // The query set is only known when the bundle is executed, so the index
+    // and the type are validated then.
+    if let Some(active_query_index) = state.active_occlusion_query.replace(query_index) {
+        return Err(QueryUseError::AlreadyStarted {
+            active_query_index,
+            new_query_index: query_index,
+        }
+        .into());
+    }
+
+    state
+        .commands
+        .push(ArcRenderCommand::BeginOcclusionQuery { query_index });
+    Ok(())
+}
+
+fn end_occlusion_query(state: &mut State) -> Result<(), RenderBundleErrorInner> {
+    if state.active_occlusion_query.take().is_none() {
+        return Err(QueryUseError::AlreadyStopped.into());
+    }
+
+    state.commands.push(ArcRenderCommand::EndOcclusionQuery);
+    Ok(())
+}
+
+fn begin_pipeline_statistics_query(
+    state: &mut State,
+    query_set_guard: &crate::storage::StorageReadGuard<'_, Fallible<QuerySet>>,
+    query_set_id: id::Id<id::markers::QuerySet>,
+    query_index: u32,
+) -> Result<(), RenderBundleErrorInner> {
+    state
+        .device
+        .require_features(wgt::Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES)?;
+
+    let query_set = query_set_guard.get(query_set_id).get()?;
+
+    query_set.same_device(&state.device)?;
+    query_set.validate_query(SimplifiedQueryType::PipelineStatistics, query_index, None)?;
+
+    if let Some(active_query_index) = state.active_pipeline_statistics_query.replace(query_index) {
+        return Err(QueryUseError::AlreadyStarted {
+            active_query_index,
+            new_query_index: query_index,
+        }
+        .into());
+    }
+
+    state
+        .commands
+        .push(ArcRenderCommand::BeginPipelineStatisticsQuery {
+            query_set,
+            query_index,
+        });
+    Ok(())
+}
+
+fn end_pipeline_statistics_query(state: &mut State) -> Result<(), RenderBundleErrorInner> {
+    if state.active_pipeline_statistics_query.take().is_none() {
+        return Err(QueryUseError::AlreadyStopped.into());
+    }
+
+    state
+        .commands
+        .push(ArcRenderCommand::EndPipelineStatisticsQuery);
+    Ok(())
+}
+
 /// Error type returned from `RenderBundleEncoder::new` if the sample count is invalid.
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
@@ -876,6 +1110,12 @@ pub enum ExecutionError {
     DestroyedResource(#[from] DestroyedResourceError),
     #[error("Using {0} in a render bundle is not implemented")]
     Unimplemented(&'static str),
+    #[error(transparent)]
+    Query(#[from] QueryUseError),
+    #[error(
+        "The render bundle begins an occlusion query, but the render pass has no occlusion query set"
+    )]
+    MissingOcclusionQuerySet,
 }
 
 pub type RenderBundleDescriptor<'a> = wgt::RenderBundleDescriptor<Label<'a>>;
@@ -890,6 +1130,7 @@ pub struct RenderBundle {
     base: BasePass<ArcRenderCommand, Infallible>,
     pub(super) is_depth_read_only: bool,
     pub(super) is_stencil_read_only: bool,
+    pub(super) inheritance: wgt::RenderBundleInheritance,
     pub(crate) device: Arc<Device>,
     pub(crate) used: RenderBundleScope,
     pub(super) buffer_memory_init_actions: Vec<BufferInitTrackerAction>,
@@ -907,6 +1148,21 @@ impl Drop for RenderBundle {
     }
 }
 
+/// The state of the render pass that [`RenderBundle::execute`] uses for the
+/// bundle's queries and inherited push constants.
+pub(super) struct BundlePassState<'a> {
+    pub cmd_buf: &'a CommandBuffer,
+    pub occlusion_query_set: Option<&'a Arc<QuerySet>>,
+    pub query_sets: &'a mut StatelessTracker<QuerySet>,
+    pub pending_query_resets: &'a mut QueryResetMap,
+    pub active_occlusion_query: &'a mut Option<(Arc<QuerySet>, u32)>,
+    pub active_pipeline_statistics_query: &'a mut Option<(Arc<QuerySet>, u32)>,
+    /// The index of the pipeline statistics query recording the whole pass, if any.
+    pub pass_pipeline_statistics_query: Option<u32>,
+    /// The push constant values last set on the pass, in words.
+    pub push_constants: &'a [u32],
+}
+
 #[cfg(send_sync)]
 unsafe impl Send for RenderBundle {}
 #[cfg(send_sync)]
@@ -921,12 +1177,14 @@ impl RenderBundle {
     ///
     /// Note that the function isn't expected to fail, generally.
     /// All the validation has already been done by this point.
-    /// The only failure condition is if some of the used buffers are destroyed.
+    /// The only failure conditions are if some of the used buffers are
+    /// destroyed, or if the bundle's queries conflict with the pass's.
     pub(super) unsafe fn execute(
         &self,
         raw: &mut dyn hal::DynCommandEncoder,
         indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
         indirect_draw_validation_batcher: &mut crate::indirect_validation::DrawBatcher,
+        pass_state: BundlePassState<'_>,
         snatch_guard: &SnatchGuard,
     ) -> Result<(), ExecutionError> {
         let mut offsets = self.base.dynamic_offsets.as_slice();
@@ -964,6 +1222,27 @@ impl RenderBundle {
                 Cmd::SetPipeline(pipeline) => {
                     unsafe { raw.set_render_pipeline(pipeline.raw()) };
 
+                    if self
+                        .inheritance
+                        .contains(wgt::RenderBundleInheritance::PUSH_CONSTANTS)
+                    {
+                        let layout = &pipeline.layout;
+                        for range in
+                            super::bind::compute_nonoverlapping_ranges(&layout.push_constant_ranges)
+                        {
+                            let start = (range.range.start / wgt::PUSH_CONSTANT_ALIGNMENT) as usize;
+                            let end = (range.range.end / wgt::PUSH_CONSTANT_ALIGNMENT) as usize;
+                            unsafe {
+                                raw.set_push_constants(
+                                    layout.raw(),
+                                    range.stages,
+                                    range.range.start,
+                                    &pass_state.push_constants[start..end],
+                                )
+                            };
+                        }
+                    }
+
                     pipeline_layout = Some(pipeline.layout.clone());
                 }
                 Cmd::SetIndexBuffer {
@@ -1079,6 +1358,7 @@ impl RenderBundle {
                             *indexed,
                             *vertex_or_index_limit,
                             *instance_limit,
+                            None,
                         )?;
 
                         let dst_buffer =
@@ -1096,20 +1376,70 @@ impl RenderBundle {
                 Cmd::DrawIndirect { .. } | Cmd::MultiDrawIndirectCount { .. } => {
                     return Err(ExecutionError::Unimplemented("multi-draw-indirect"));
                 }
+                Cmd::DrawMeshTasks { .. }
+                | Cmd::DrawMeshTasksIndirect { .. }
+                | Cmd::MultiDrawMeshTasksIndirectCount { .. } => {
+                    return Err(ExecutionError::Unimplemented("mesh shaders"));
+                }
                 Cmd::PushDebugGroup { .. } | Cmd::InsertDebugMarker { .. } | Cmd::PopDebugGroup => {
                     return Err(ExecutionError::Unimplemented("debug-markers"));
                 }
-                Cmd::WriteTimestamp { .. }
-                | Cmd::BeginOcclusionQuery { .. }
-                | Cmd::EndOcclusionQuery
-                | Cmd::BeginPipelineStatisticsQuery { .. }
-                | Cmd::EndPipelineStatisticsQuery => {
-                    return Err(ExecutionError::Unimplemented("queries"));
+                Cmd::WriteTimestamp {
+                    query_set,
+                    query_index,
+                } => {
+                    let query_set = pass_state.query_sets.insert_single(query_set.clone());
+                    query_set.validate_and_write_timestamp(
+                        raw,
+                        *query_index,
+                        Some(&mut *pass_state.pending_query_resets),
+                    )?;
+                }
+                Cmd::BeginOcclusionQuery { query_index } => {
+                    let query_set = pass_state
+                        .occlusion_query_set
+                        .ok_or(ExecutionError::MissingOcclusionQuerySet)?;
+                    super::validate_and_begin_occlusion_query(
+                        query_set.clone(),
+                        raw,
+                        pass_state.query_sets,
+                        *query_index,
+                        Some(&mut *pass_state.pending_query_resets),
+                        pass_state.active_occlusion_query,
+                    )?;
+                }
+                Cmd::EndOcclusionQuery => {
+                    super::end_occlusion_query(raw, pass_state.active_occlusion_query)?;
+                }
+                Cmd::BeginPipelineStatisticsQuery {
+                    query_set,
+                    query_index,
+                } => {
+                    super::validate_and_begin_pipeline_statistics_query(
+                        query_set.clone(),
+                        raw,
+                        pass_state.query_sets,
+                        pass_state.cmd_buf,
+                        *query_index,
+                        Some(&mut *pass_state.pending_query_resets),
+                        pass_state.active_pipeline_statistics_query,
+                        pass_state.pass_pipeline_statistics_query,
+                    )?;
+                }
+                Cmd::EndPipelineStatisticsQuery => {
+                    super::end_pipeline_statistics_query(
+                        raw,
+                        pass_state.active_pipeline_statistics_query,
+                    )?;
                 }
                 Cmd::ExecuteBundle(_)
                 | Cmd::SetBlendConstant(_)
                 | Cmd::SetStencilReference(_)
+                | Cmd::SetShadingRate(_)
+                | Cmd::BeginConditionalBlock { .. }
+                | Cmd::EndConditionalBlock
                 | Cmd::SetViewport { .. }
+                | Cmd::SetIndexedViewport { .. }
                 | Cmd::SetScissor(_) => unreachable!(),
             }
         }
@@ -1347,6 +1677,16 @@ struct State {
     buffer_memory_init_actions: Vec<BufferInitTrackerAction>,
     texture_memory_init_actions: Vec<TextureInitTrackerAction>,
     next_dynamic_offset: usize,
+
+    /// The state of the render pass that the bundle inherits.
+    inheritance: wgt::RenderBundleInheritance,
+
+    /// The index of the occlusion query begun in the bundle, if any. Queries
+    /// must be ended in the bundle that begins them.
+    active_occlusion_query: Option<u32>,
+
+    /// The index of the pipeline statistics query begun in the bundle, if any.
+    active_pipeline_statistics_query: Option<u32>,
 }
 
 impl State {
@@ -1531,9 +1871,19 @@ pub(super) enum RenderBundleErrorInner {
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
     #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
     Bind(#[from] BindError),
     #[error(transparent)]
     InvalidResource(#[from] InvalidResourceError),
+    #[error(transparent)]
+    Query(#[from] QueryUseError),
+    #[error(
+        "Occlusion queries in a render bundle require it to inherit the occlusion query set of the render pass"
+    )]
+    MissingOcclusionQuerySetInheritance,
+    #[error("A query begun in a render bundle must be ended in the same render bundle")]
+    UnendedQuery,
 }
 
 impl<T> From<T> for RenderBundleErrorInner
@@ -1562,8 +1912,12 @@ impl WebGpuError for RenderBundleError {
             RenderBundleErrorInner::RenderCommand(e) => e,
             RenderBundleErrorInner::Draw(e) => e,
             RenderBundleErrorInner::MissingDownlevelFlags(e) => e,
+            RenderBundleErrorInner::MissingFeatures(e) => e,
             RenderBundleErrorInner::Bind(e) => e,
             RenderBundleErrorInner::InvalidResource(e) => e,
+            RenderBundleErrorInner::Query(e) => e,
+            RenderBundleErrorInner::MissingOcclusionQuerySetInheritance
+            | RenderBundleErrorInner::UnendedQuery => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -1789,4 +2143,50 @@ pub mod bundle_ffi {
     ) {
         //TODO
     }
+
+    pub fn wgpu_render_bundle_write_timestamp(
+        bundle: &mut RenderBundleEncoder,
+        query_set_id: id::QuerySetId,
+        query_index: u32,
+    ) {
+        bundle.base.commands.push(RenderCommand::WriteTimestamp {
+            query_set_id,
+            query_index,
+        });
+    }
+
+    pub fn wgpu_render_bundle_begin_occlusion_query(
+        bundle: &mut RenderBundleEncoder,
+        query_index: u32,
+    ) {
+        bundle
+            .base
+            .commands
+            .push(RenderCommand::BeginOcclusionQuery { query_index });
+    }
+
+    pub fn wgpu_render_bundle_end_occlusion_query(bundle: &mut RenderBundleEncoder) {
+        bundle.base.commands.push(RenderCommand::EndOcclusionQuery);
+    }
+
+    pub fn wgpu_render_bundle_begin_pipeline_statistics_query(
+        bundle: &mut RenderBundleEncoder,
+        query_set_id: id::QuerySetId,
+        query_index: u32,
+    ) {
+        bundle
+            .base
+            .commands
+            .push(RenderCommand::BeginPipelineStatisticsQuery {
+                query_set_id,
+                query_index,
+            });
+    }
+
+    pub fn wgpu_render_bundle_end_pipeline_statistics_query(bundle: &mut RenderBundleEncoder) {
+        bundle
+            .base
+            .commands
+            .push(RenderCommand::EndPipelineStatisticsQuery);
+    }
 }
@@ -5,15 +5,16 @@ use core::ops::Range;
 use crate::device::trace::Command as TraceCommand;
 use crate::{
     api_log,
-    command::EncoderStateError,
+    command::{EncoderStateError, memory_init::reset_aliased_textures},
     device::DeviceError,
     get_lowest_common_denom,
     global::Global,
     id::{BufferId, CommandEncoderId, TextureId},
     init_tracker::{MemoryInitKind, TextureInitRange},
     resource::{
-        DestroyedResourceError, InvalidResourceError, Labeled, MissingBufferUsageError,
-        ParentDevice, RawResourceAccess, ResourceErrorIdent, Texture, TextureClearMode,
+        AliasedTextureConflictError, DestroyedResourceError, InvalidResourceError, Labeled,
+        MissingBufferUsageError, ParentDevice, RawResourceAccess, ResourceErrorIdent, Texture,
+        TextureClearMode,
     },
     snatch::SnatchGuard,
     track::TextureTrackerSetSingle,
@@ -34,6 +35,8 @@ pub enum ClearError {
     MissingClearTextureFeature,
     #[error(transparent)]
     DestroyedResource(#[from] DestroyedResourceError),
+    #[error(transparent)]
+    AliasedTextureConflict(#[from] AliasedTextureConflictError),
     #[error("{0} can not be cleared")]
     NoValidTextureClearMode(ResourceErrorIdent),
     #[error("Buffer clear size {0:?} is not a multiple of `COPY_BUFFER_ALIGNMENT`")]
@@ -94,6 +97,7 @@ impl WebGpuError for ClearError {
     fn webgpu_error_type(&self) -> ErrorType {
         let e: &dyn WebGpuError = match self {
             Self::DestroyedResource(e) => e,
+            Self::AliasedTextureConflict(e) => e,
             Self::MissingBufferUsage(e) => e,
             Self::Device(e) => e,
             Self::EncoderState(e) => e,
@@ -270,17 +274,31 @@ impl Global {
                 });
             }
 
+            let range = TextureInitRange {
+                mip_range: subresource_mip_range,
+                layer_range: subresource_layer_range,
+            };
+
+            // Clearing an aliased texture hands it the memory it shares with
+            // other textures.
+            let alias_resets = if dst_texture.aliasing.is_some() {
+                cmd_buf_data
+                    .texture_memory_actions
+                    .register_implicit_init(&dst_texture, range.clone());
+                cmd_buf_data.texture_memory_actions.take_alias_resets()?
+            } else {
+                Vec::new()
+            };
+
             let device = &cmd_buf.device;
             device.check_is_valid()?;
             let (encoder, tracker) = cmd_buf_data.open_encoder_and_tracker()?;
 
             let snatch_guard = device.snatchable_lock.read();
+            reset_aliased_textures(alias_resets, encoder, &mut tracker.textures, &snatch_guard)?;
             clear_texture(
                 &dst_texture,
-                TextureInitRange {
-                    mip_range: subresource_mip_range,
-                    layer_range: subresource_layer_range,
-                },
+                range,
                 encoder,
                 &mut tracker.textures,
                 &device.alignments,
@@ -384,8 +402,8 @@ fn clear_texture_via_buffer_copies(
 ) {
     assert!(!texture_desc.format.is_depth_stencil_format());
 
-    if texture_desc.format == wgt::TextureFormat::NV12 {
-        // TODO: Currently COPY_DST for NV12 textures is unsupported.
+    if texture_desc.format.is_multi_planar_format() {
+        // TODO: Currently COPY_DST for multi-planar textures is unsupported.
         return;
     }
 
@@ -521,6 +539,7 @@ fn clear_texture_via_render_passes(
                         depth_ops: hal::AttachmentOps::STORE,
                         stencil_ops: hal::AttachmentOps::STORE,
                         clear_value: (0.0, 0),
+                        resolve: None,
                     }),
                 )
             };
@@ -535,6 +554,9 @@ fn clear_texture_via_render_passes(
                         multiview: None,
                         timestamp_writes: None,
                         occlusion_query_set: None,
+                        pipeline_statistics_query: None,
+                        flags: wgt::RenderPassFlags::empty(),
+                        parallel: false,
                     })
                     .map_err(|e| dst_texture.device.handle_hal_error(e))?;
                 encoder.end_render_pass();
@@ -4,32 +4,33 @@ use wgt::{
     error::{ErrorType, WebGpuError},
 };
 
-use alloc::{borrow::Cow, boxed::Box, sync::Arc, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, format, sync::Arc, vec::Vec};
 use core::{fmt, str};
 
 use crate::command::{EncoderStateError, PassStateError, TimestampWritesError, pass};
-use crate::resource::DestroyedResourceError;
+use crate::resource::{AliasedTextureConflictError, DestroyedResourceError};
 use crate::{
     Label,
     binding_model::{LateMinBufferBindingSizeMismatch, PushConstantUploadError},
     command::{
-        ArcPassTimestampWrites, BasePass, BindGroupStateChange, CommandBuffer, CommandEncoderError,
-        MapPassErr, PassErrorScope, PassTimestampWrites, QueryUseError, StateChange,
+        ArcPassPipelineStatisticsQuery, ArcPassTimestampWrites, BasePass, BindGroupStateChange,
+        CommandBuffer, CommandEncoderError, MapPassErr, PassErrorScope,
+        PassPipelineStatisticsQuery, PassTimestampWrites, QueryUseError, StateChange,
         bind::{Binder, BinderError},
         compute_command::ArcComputeCommand,
         end_pipeline_statistics_query,
-        memory_init::{SurfacesInDiscardState, fixup_discarded_surfaces},
+        memory_init::{SurfacesInDiscardState, fixup_discarded_surfaces, reset_aliased_textures},
         pass_base, pass_try, validate_and_begin_pipeline_statistics_query,
     },
     device::{DeviceError, MissingDownlevelFlags, MissingFeatures},
     global::Global,
     hal_label, id,
     init_tracker::MemoryInitKind,
-    pipeline::ComputePipeline,
+    pipeline::{ComputePipeline, RayTracingPipeline},
     resource::{
         self, Buffer, InvalidResourceError, Labeled, MissingBufferUsageError, ParentDevice,
     },
-    track::{ResourceUsageCompatibilityError, Tracker, TrackerIndex},
+    track::{BarrierLog, ResourceUsageCompatibilityError, Tracker, TrackerIndex},
 };
 use crate::{binding_model::BindError, resource::RawResourceAccess};
 
@@ -54,6 +55,7 @@ pub struct ComputePass {
     parent: Option<Arc<CommandBuffer>>,
 
     timestamp_writes: Option<ArcPassTimestampWrites>,
+    pipeline_statistics_query: Option<ArcPassPipelineStatisticsQuery>,
 
     // Resource binding dedupe state.
     current_bind_groups: BindGroupStateChange,
@@ -66,12 +68,14 @@ impl ComputePass {
         let ArcComputePassDescriptor {
             label,
             timestamp_writes,
+            pipeline_statistics_query,
         } = desc;
 
         Self {
             base: BasePass::new(&label),
             parent: Some(parent),
             timestamp_writes,
+            pipeline_statistics_query,
 
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
@@ -83,6 +87,7 @@ impl ComputePass {
             base: BasePass::new_invalid(label, err),
             parent: Some(parent),
             timestamp_writes: None,
+            pipeline_statistics_query: None,
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
         }
@@ -104,14 +109,35 @@ impl fmt::Debug for ComputePass {
 }
 
 #[derive(Clone, Debug, Default)]
-pub struct ComputePassDescriptor<'a, PTW = PassTimestampWrites> {
+pub struct ComputePassDescriptor<'a, PTW = PassTimestampWrites, PPSQ = PassPipelineStatisticsQuery>
+{
     pub label: Label<'a>,
     /// Defines where and when timestamp values will be written for this pass.
     pub timestamp_writes: Option<PTW>,
+    /// Defines where the pipeline statistics of this pass will be written.
+    pub pipeline_statistics_query: Option<PPSQ>,
 }
 
 /// cbindgen:ignore
-type ArcComputePassDescriptor<'a> = ComputePassDescriptor<'a, ArcPassTimestampWrites>;
+type ArcComputePassDescriptor<'a> =
+    ComputePassDescriptor<'a, ArcPassTimestampWrites, ArcPassPipelineStatisticsQuery>;
+
+/// Describes a [`Global::command_encoder_trace_rays`] call.
+#[derive(Clone, Debug)]
+pub struct TraceRaysDescriptor<'a> {
+    pub label: Label<'a>,
+    pub pipeline: id::RayTracingPipelineId,
+    /// The bind groups used by the traced shaders, bound at the index of their position,
+    /// with their dynamic offsets.
+    pub bind_groups: &'a [(id::BindGroupId, &'a [DynamicOffset])],
+    /// Push constant data for the ray tracing stages, written at offset 0.
+    pub push_constants: &'a [u8],
+    /// The buffer holding the shader binding table.
+    pub shader_binding_table: id::BufferId,
+    /// Where the records of each kind of shader group are in `shader_binding_table`.
+    pub layout: wgt::ShaderBindingTableLayout,
+    pub size: [u32; 3],
+}
 
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
@@ -134,6 +160,46 @@ impl WebGpuError for DispatchError {
     }
 }
 
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum TraceRaysError {
+    #[error("Ray tracing pipeline must be set")]
+    MissingPipeline,
+    #[error(transparent)]
+    IncompatibleBindGroup(#[from] Box<BinderError>),
+    #[error(
+        "The ray generation region has size {size} and stride {stride}, but must hold exactly one record"
+    )]
+    InvalidRayGenerationRegion {
+        size: BufferAddress,
+        stride: BufferAddress,
+    },
+    #[error("Shader binding table region offset {offset} is not a multiple of {alignment}")]
+    UnalignedRegionOffset {
+        offset: BufferAddress,
+        alignment: BufferAddress,
+    },
+    #[error("Shader binding table region stride {stride} is not a multiple of {alignment}")]
+    UnalignedRegionStride {
+        stride: BufferAddress,
+        alignment: BufferAddress,
+    },
+    #[error(
+        "Shader binding table region uses bytes {offset}..{end_offset} which overruns the buffer of size {buffer_size}"
+    )]
+    RegionOverrun {
+        offset: BufferAddress,
+        end_offset: BufferAddress,
+        buffer_size: BufferAddress,
+    },
+}
+
+impl WebGpuError for TraceRaysError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        ErrorType::Validation
+    }
+}
+
 /// Error encountered when performing a compute pass.
 #[derive(Clone, Debug, Error)]
 pub enum ComputePassErrorInner {
@@ -147,6 +213,8 @@ pub enum ComputePassErrorInner {
     BindGroupIndexOutOfRange(#[from] pass::BindGroupIndexOutOfRange),
     #[error(transparent)]
     DestroyedResource(#[from] DestroyedResourceError),
+    #[error(transparent)]
+    AliasedTextureConflict(#[from] AliasedTextureConflictError),
     #[error("Indirect buffer offset {0:?} is not a multiple of 4")]
     UnalignedIndirectBufferOffset(BufferAddress),
     #[error(
@@ -166,6 +234,8 @@ pub enum ComputePassErrorInner {
     #[error(transparent)]
     Dispatch(#[from] DispatchError),
     #[error(transparent)]
+    TraceRays(#[from] TraceRaysError),
+    #[error(transparent)]
     Bind(#[from] BindError),
     #[error(transparent)]
     PushConstants(#[from] PushConstantUploadError),
@@ -183,6 +253,8 @@ pub enum ComputePassErrorInner {
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
     #[error("The compute pass has already been ended and no further commands can be recorded")]
     PassEnded,
+    #[error("Compute passes can't be encoded for a {0:?} queue")]
+    UnsupportedQueueType(wgt::QueueType),
     #[error(transparent)]
     InvalidResource(#[from] InvalidResourceError),
     #[error(transparent)]
@@ -202,6 +274,13 @@ pub struct ComputePassError {
     pub(super) inner: ComputePassErrorInner,
 }
 
+impl ComputePassError {
+    /// The cause of the error.
+    pub fn inner(&self) -> &ComputePassErrorInner {
+        &self.inner
+    }
+}
+
 impl From<pass::MissingPipeline> for ComputePassErrorInner {
     fn from(value: pass::MissingPipeline) -> Self {
         Self::Dispatch(DispatchError::MissingPipeline(value))
@@ -227,9 +306,11 @@ impl WebGpuError for ComputePassError {
             ComputePassErrorInner::Device(e) => e,
             ComputePassErrorInner::EncoderState(e) => e,
             ComputePassErrorInner::DestroyedResource(e) => e,
+            ComputePassErrorInner::AliasedTextureConflict(e) => e,
             ComputePassErrorInner::ResourceUsageCompatibility(e) => e,
             ComputePassErrorInner::MissingBufferUsage(e) => e,
             ComputePassErrorInner::Dispatch(e) => e,
+            ComputePassErrorInner::TraceRays(e) => e,
             ComputePassErrorInner::Bind(e) => e,
             ComputePassErrorInner::PushConstants(e) => e,
             ComputePassErrorInner::QueryUse(e) => e,
@@ -247,7 +328,8 @@ impl WebGpuError for ComputePassError {
             | ComputePassErrorInner::PushConstantOffsetAlignment
             | ComputePassErrorInner::PushConstantSizeAlignment
             | ComputePassErrorInner::PushConstantOutOfMemory
-            | ComputePassErrorInner::PassEnded => return ErrorType::Validation,
+            | ComputePassErrorInner::PassEnded
+            | ComputePassErrorInner::UnsupportedQueueType(_) => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -256,13 +338,22 @@ impl WebGpuError for ComputePassError {
 struct State<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder> {
     pipeline: Option<Arc<ComputePipeline>>,
 
+    /// Set instead of `pipeline` while a ray tracing pipeline is bound.
+    ray_tracing_pipeline: Option<Arc<RayTracingPipeline>>,
+
     general: pass::BaseState<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>,
 
     active_query: Option<(Arc<resource::QuerySet>, u32)>,
 
+    /// The index of the pipeline statistics query recording the whole pass, if any.
+    pass_pipeline_statistics_query: Option<u32>,
+
     push_constants: Vec<u32>,
 
     intermediate_trackers: Tracker,
+
+    /// Logs the barriers of the pass, if barrier logging is enabled on the device.
+    barrier_log: Option<BarrierLog>,
 }
 
 impl<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
@@ -278,6 +369,35 @@ impl<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
         }
     }
 
+    /// The stages push constants are written for by `set_push_constants`.
+    fn push_constant_stages(&self) -> wgt::ShaderStages {
+        match self.ray_tracing_pipeline {
+            Some(ref pipeline) => pipeline.push_constant_stages(),
+            None => wgt::ShaderStages::COMPUTE,
+        }
+    }
+
+    /// Binds all valid bind groups again, after switching between compute and
+    /// ray tracing pipelines. Backends keep separate bindings for the two.
+    fn rebind_all_groups(
+        &mut self,
+        pipeline_layout: &crate::binding_model::PipelineLayout,
+    ) -> Result<(), DestroyedResourceError> {
+        for (i, e) in self.general.binder.list_valid() {
+            let group = e.group.as_ref().unwrap();
+            let raw_bg = group.try_raw(self.general.snatch_guard)?;
+            unsafe {
+                self.general.raw_encoder.set_bind_group(
+                    pipeline_layout.raw(),
+                    i as u32,
+                    Some(raw_bg),
+                    &e.dynamic_offsets,
+                );
+            }
+        }
+        Ok(())
+    }
+
     // `extra_buffer` is there to represent the indirect buffer that is also
     // part of the usage scope.
     fn flush_states(
@@ -314,6 +434,7 @@ impl<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
             self.general.raw_encoder,
             &mut self.intermediate_trackers,
             self.general.snatch_guard,
+            self.barrier_log.as_mut(),
         );
         Ok(())
     }
@@ -357,22 +478,46 @@ impl Global {
                     );
                 }
 
+                if cmd_buf.queue_type == wgt::QueueType::Transfer {
+                    let err = ComputePassErrorInner::UnsupportedQueueType(cmd_buf.queue_type);
+                    return (
+                        ComputePass::new_invalid(cmd_buf, &label, err.map_pass_err(scope)),
+                        None,
+                    );
+                }
+
+                let query_sets = hub.query_sets.read();
                 match desc
                     .timestamp_writes
                     .as_ref()
                     .map(|tw| {
                         Self::validate_pass_timestamp_writes::<ComputePassErrorInner>(
                             &cmd_buf.device,
-                            &hub.query_sets.read(),
+                            &query_sets,
                             tw,
                         )
                     })
                     .transpose()
-                {
-                    Ok(timestamp_writes) => {
+                    .and_then(|timestamp_writes| {
+                        let pipeline_statistics_query =
+                            desc.pipeline_statistics_query
+                                .as_ref()
+                                .map(|psq| {
+                                    Self::validate_pass_pipeline_statistics_query::<
+                                        ComputePassErrorInner,
+                                    >(
+                                        &cmd_buf.device, &query_sets, psq
+                                    )
+                                })
+                                .transpose()?;
+                        Ok((timestamp_writes, pipeline_statistics_query))
+                    }) {
+                    Ok((timestamp_writes, pipeline_statistics_query)) => {
+                        drop(query_sets);
                         let arc_desc = ArcComputePassDescriptor {
                             label,
                             timestamp_writes,
+                            pipeline_statistics_query,
                         };
                         (ComputePass::new(cmd_buf, arc_desc), None)
                     }
@@ -414,8 +559,10 @@ impl Global {
                     None,
                 )
             }
-            Err(SErr::Unlocked) => {
-                unreachable!("lock_encoder cannot fail due to the encoder being unlocked")
+            Err(SErr::Unlocked | SErr::Suspended) => {
+                unreachable!(
+                    "lock_encoder cannot fail due to the encoder being unlocked or suspended"
+                )
             }
         }
     }
@@ -432,6 +579,7 @@ impl Global {
         encoder_id: id::CommandEncoderId,
         base: BasePass<super::ComputeCommand, core::convert::Infallible>,
         timestamp_writes: Option<&PassTimestampWrites>,
+        pipeline_statistics_query: Option<&PassPipelineStatisticsQuery>,
     ) {
         #[cfg(feature = "trace")]
         {
@@ -453,6 +601,7 @@ impl Global {
                         push_constant_data: base.push_constant_data.clone(),
                     },
                     timestamp_writes: timestamp_writes.cloned(),
+                    pipeline_statistics_query: pipeline_statistics_query.cloned(),
                 });
             }
         }
@@ -471,6 +620,7 @@ impl Global {
             &ComputePassDescriptor {
                 label: label.as_deref().map(Cow::Borrowed),
                 timestamp_writes: timestamp_writes.cloned(),
+                pipeline_statistics_query: pipeline_statistics_query.cloned(),
             },
         );
         if let Some(err) = encoder_error {
@@ -526,6 +676,10 @@ impl Global {
             let device = &cmd_buf.device;
             device.check_is_valid().map_pass_err(pass_scope)?;
 
+            if cmd_buf_data.suspended_render_pass.is_some() {
+                return Err(EncoderStateError::Suspended).map_pass_err(pass_scope);
+            }
+
             let base = &mut pass.base;
 
             let encoder = &mut cmd_buf_data.encoder;
@@ -542,6 +696,7 @@ impl Global {
 
             let mut state = State {
                 pipeline: None,
+                ray_tracing_pipeline: None,
 
                 general: pass::BaseState {
                     device,
@@ -563,10 +718,18 @@ impl Global {
                     string_offset: 0,
                 },
                 active_query: None,
+                pass_pipeline_statistics_query: None,
 
                 push_constants: Vec::new(),
 
                 intermediate_trackers: Tracker::new(),
+
+                barrier_log: BarrierLog::new(device, || {
+                    format!(
+                        "compute pass {:?}",
+                        base.label.as_deref().unwrap_or_default()
+                    )
+                }),
             };
 
             let indices = &state.general.device.tracker_indices;
@@ -581,6 +744,35 @@ impl Global {
                 .textures
                 .set_size(indices.textures.size());
 
+            let pipeline_statistics_query = pass.pipeline_statistics_query.take();
+            let pipeline_statistics_query_hal =
+                if let Some(psq) = pipeline_statistics_query.as_ref() {
+                    psq.query_set
+                        .same_device_as(cmd_buf.as_ref())
+                        .map_pass_err(pass_scope)?;
+
+                    state
+                        .general
+                        .tracker
+                        .query_sets
+                        .insert_single(psq.query_set.clone());
+                    state.pass_pipeline_statistics_query = Some(psq.query_index);
+
+                    unsafe {
+                        state.general.raw_encoder.reset_queries(
+                            psq.query_set.raw(),
+                            psq.query_index..psq.query_index + 1,
+                        );
+                    }
+
+                    Some(hal::PassPipelineStatisticsQuery {
+                        query_set: psq.query_set.raw(),
+                        query_index: psq.query_index,
+                    })
+                } else {
+                    None
+                };
+
             let timestamp_writes: Option<hal::PassTimestampWrites<'_, dyn hal::DynQuerySet>> =
                 if let Some(tw) = pass.timestamp_writes.take() {
                     tw.query_set
@@ -623,6 +815,7 @@ impl Global {
             let hal_desc = hal::ComputePassDescriptor {
                 label: hal_label(base.label.as_deref(), device.instance_flags),
                 timestamp_writes,
+                pipeline_statistics_query: pipeline_statistics_query_hal,
             };
 
             unsafe {
@@ -652,16 +845,22 @@ impl Global {
                         let scope = PassErrorScope::SetPipelineCompute;
                         set_pipeline(&mut state, cmd_buf.as_ref(), pipeline).map_pass_err(scope)?;
                     }
+                    ArcComputeCommand::SetRayTracingPipeline(pipeline) => {
+                        let scope = PassErrorScope::SetPipelineRayTracing;
+                        set_ray_tracing_pipeline(&mut state, cmd_buf.as_ref(), pipeline)
+                            .map_pass_err(scope)?;
+                    }
                     ArcComputeCommand::SetPushConstant {
                         offset,
                         size_bytes,
                         values_offset,
                     } => {
                         let scope = PassErrorScope::SetPushConstant;
+                        let stages = state.push_constant_stages();
                         pass::set_push_constant::<ComputePassErrorInner, _>(
                             &mut state.general,
                             &base.push_constant_data,
-                            wgt::ShaderStages::COMPUTE,
+                            stages,
                             offset,
                             size_bytes,
                             Some(values_offset),
@@ -685,6 +884,15 @@ impl Global {
                         dispatch_indirect(&mut state, cmd_buf.as_ref(), buffer, offset)
                             .map_pass_err(scope)?;
                     }
+                    ArcComputeCommand::TraceRays {
+                        buffer,
+                        layout,
+                        size,
+                    } => {
+                        let scope = PassErrorScope::TraceRays;
+                        trace_rays(&mut state, cmd_buf.as_ref(), buffer, layout, size)
+                            .map_pass_err(scope)?;
+                    }
                     ArcComputeCommand::PushDebugGroup { color: _, len } => {
                         pass::push_debug_group(&mut state.general, &base.string_data, len);
                     }
@@ -715,6 +923,13 @@ impl Global {
                         query_index,
                     } => {
                         let scope = PassErrorScope::BeginPipelineStatisticsQuery;
+                        state
+                            .general
+                            .device
+                            .require_features(
+                                wgt::Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES,
+                            )
+                            .map_pass_err(scope)?;
                         validate_and_begin_pipeline_statistics_query(
                             query_set,
                             state.general.raw_encoder,
@@ -723,6 +938,7 @@ impl Global {
                             query_index,
                             None,
                             &mut state.active_query,
+                            state.pass_pipeline_statistics_query,
                         )
                         .map_pass_err(scope)?;
                     }
@@ -745,13 +961,19 @@ impl Global {
                 general:
                     pass::BaseState {
                         tracker,
+                        texture_memory_actions,
                         pending_discard_init_fixups,
                         ..
                     },
                 intermediate_trackers,
+                mut barrier_log,
                 ..
             } = state;
 
+            let alias_resets = texture_memory_actions
+                .take_alias_resets()
+                .map_pass_err(pass_scope)?;
+
             // Stop the current command buffer.
             encoder.close().map_pass_err(pass_scope)?;
 
@@ -761,6 +983,8 @@ impl Global {
             let transit = encoder
                 .open_pass(Some("(wgpu internal) Pre Pass"))
                 .map_pass_err(pass_scope)?;
+            reset_aliased_textures(alias_resets, transit, &mut tracker.textures, &snatch_guard)
+                .map_pass_err(pass_scope)?;
             fixup_discarded_surfaces(
                 pending_discard_init_fixups.into_iter(),
                 transit,
@@ -773,6 +997,7 @@ impl Global {
                 tracker,
                 &intermediate_trackers,
                 &snatch_guard,
+                barrier_log.as_mut(),
             );
             // Close the command buffer, and swap it with the previous.
             encoder.close_and_swap().map_pass_err(pass_scope)?;
@@ -790,6 +1015,7 @@ fn set_pipeline(
     pipeline.same_device_as(cmd_buf)?;
 
     state.pipeline = Some(pipeline.clone());
+    let switched = state.ray_tracing_pipeline.take().is_some();
 
     let pipeline = state
         .general
@@ -827,7 +1053,167 @@ fn set_pipeline(
                 state.push_constants.extend(core::iter::repeat_n(0, len));
             }
         },
-    )
+    )?;
+
+    if switched {
+        state.rebind_all_groups(&pipeline.layout)?;
+    }
+    Ok(())
+}
+
+fn set_ray_tracing_pipeline(
+    state: &mut State,
+    cmd_buf: &CommandBuffer,
+    pipeline: Arc<RayTracingPipeline>,
+) -> Result<(), ComputePassErrorInner> {
+    pipeline.same_device_as(cmd_buf)?;
+
+    state.ray_tracing_pipeline = Some(pipeline.clone());
+    let switched = state.pipeline.take().is_some();
+
+    let pipeline = state
+        .general
+        .tracker
+        .ray_tracing_pipelines
+        .insert_single(pipeline)
+        .clone();
+
+    unsafe {
+        state
+            .general
+            .raw_encoder
+            .set_ray_tracing_pipeline(pipeline.raw());
+    }
+
+    // Ray tracing stages are never reflected, so there are no late sized buffers.
+    pass::rebind_resources::<ComputePassErrorInner, _>(
+        &mut state.general,
+        &pipeline.layout,
+        &[],
+        || {
+            state.push_constants.clear();
+            let len = pipeline
+                .layout
+                .push_constant_ranges
+                .iter()
+                .filter(|pcr| pcr.stages.intersects(wgt::ShaderStages::RAY_TRACING))
+                .map(|pcr| pcr.range.end / wgt::PUSH_CONSTANT_ALIGNMENT)
+                .max()
+                .unwrap_or(0);
+            state
+                .push_constants
+                .extend(core::iter::repeat_n(0, len as usize));
+        },
+    )?;
+
+    if switched {
+        state.rebind_all_groups(&pipeline.layout)?;
+    }
+    Ok(())
+}
+
+fn trace_rays(
+    state: &mut State,
+    cmd_buf: &CommandBuffer,
+    buffer: Arc<Buffer>,
+    layout: wgt::ShaderBindingTableLayout,
+    size: [u32; 3],
+) -> Result<(), ComputePassErrorInner> {
+    buffer.same_device_as(cmd_buf)?;
+
+    let pipeline = state
+        .ray_tracing_pipeline
+        .as_ref()
+        .ok_or(TraceRaysError::MissingPipeline)?;
+    state
+        .general
+        .binder
+        .check_compatibility(pipeline.as_ref())
+        .map_err(TraceRaysError::from)?;
+
+    buffer.check_usage(wgt::BufferUsages::SHADER_BINDING_TABLE)?;
+    buffer.check_destroyed(state.general.snatch_guard)?;
+
+    let ray_generation = layout.ray_generation;
+    if ray_generation.size == 0 || ray_generation.size != ray_generation.stride {
+        return Err(TraceRaysError::InvalidRayGenerationRegion {
+            size: ray_generation.size,
+            stride: ray_generation.stride,
+        }
+        .into());
+    }
+
+    let alignments = &state.general.device.alignments;
+    let base_alignment = alignments.ray_tracing_shader_group_base_alignment as BufferAddress;
+    let handle_alignment = alignments.ray_tracing_shader_group_handle_alignment as BufferAddress;
+    let regions = [
+        layout.ray_generation,
+        layout.miss,
+        layout.hit,
+        layout.callable,
+    ];
+    for region in regions.iter().filter(|region| region.size != 0) {
+        if region.offset % base_alignment != 0 {
+            return Err(TraceRaysError::UnalignedRegionOffset {
+                offset: region.offset,
+                alignment: base_alignment,
+            }
+            .into());
+        }
+        if region.stride % handle_alignment != 0 {
+            return Err(TraceRaysError::UnalignedRegionStride {
+                stride: region.stride,
+                alignment: handle_alignment,
+            }
+            .into());
+        }
+        let end_offset = region.offset + region.size;
+        if end_offset > buffer.size {
+            return Err(TraceRaysError::RegionOverrun {
+                offset: region.offset,
+                end_offset,
+                buffer_size: buffer.size,
+            }
+            .into());
+        }
+        state.general.buffer_memory_init_actions.extend(
+            buffer.initialization_status.read().create_action(
+                &buffer,
+                region.offset..end_offset,
+                MemoryInitKind::NeedsInitializedMemory,
+            ),
+        );
+    }
+
+    state
+        .general
+        .scope
+        .buffers
+        .merge_single(&buffer, wgt::BufferUses::SHADER_BINDING_TABLE)?;
+
+    use crate::resource::Trackable;
+    state.flush_states(Some(buffer.tracker_index()))?;
+
+    let buf_raw = buffer.try_raw(state.general.snatch_guard)?;
+    let table = |region: wgt::ShaderBindingTableRegion| {
+        (region.size != 0).then_some(hal::ShaderBindingTable {
+            buffer: buf_raw,
+            region,
+        })
+    };
+    let tables = hal::ShaderBindingTables {
+        ray_generation: hal::ShaderBindingTable {
+            buffer: buf_raw,
+            region: layout.ray_generation,
+        },
+        miss: table(layout.miss),
+        hit: table(layout.hit),
+        callable: table(layout.callable),
+    };
+    unsafe {
+        state.general.raw_encoder.trace_rays(&tables, size);
+    }
+    Ok(())
 }
 
 fn dispatch(state: &mut State, groups: [u32; 3]) -> Result<(), ComputePassErrorInner> {
@@ -1132,6 +1518,31 @@ impl Global {
         Ok(())
     }
 
+    fn compute_pass_set_ray_tracing_pipeline(
+        &self,
+        pass: &mut ComputePass,
+        pipeline_id: id::RayTracingPipelineId,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::SetPipelineRayTracing;
+        let base = pass_base!(pass, scope);
+
+        // Binding a ray tracing pipeline replaces the compute pipeline, so the
+        // next `set_pipeline` must not be skipped as redundant.
+        pass.current_pipeline.reset();
+
+        let hub = &self.hub;
+        let pipeline = pass_try!(
+            base,
+            scope,
+            hub.ray_tracing_pipelines.get(pipeline_id).get()
+        );
+
+        base.commands
+            .push(ArcComputeCommand::SetRayTracingPipeline(pipeline));
+
+        Ok(())
+    }
+
     pub fn compute_pass_set_push_constants(
         &self,
         pass: &mut ComputePass,
@@ -1213,6 +1624,67 @@ impl Global {
         Ok(())
     }
 
+    fn compute_pass_trace_rays(
+        &self,
+        pass: &mut ComputePass,
+        buffer_id: id::BufferId,
+        layout: wgt::ShaderBindingTableLayout,
+        size: [u32; 3],
+    ) -> Result<(), PassStateError> {
+        let hub = &self.hub;
+        let scope = PassErrorScope::TraceRays;
+        let base = pass_base!(pass, scope);
+
+        let buffer = pass_try!(base, scope, hub.buffers.get(buffer_id).get());
+
+        base.commands.push(ArcComputeCommand::TraceRays {
+            buffer,
+            layout,
+            size,
+        });
+
+        Ok(())
+    }
+
+    /// Traces rays with a ray tracing pipeline.
+    ///
+    /// Ray tracing pipelines are bound to the same bind point as compute pipelines, so the
+    /// trace is encoded as a compute pass which binds the pipeline, its bind groups and
+    /// push constants, and traces the rays. Errors are reported when the encoder is
+    /// finished, like those of passes.
+    pub fn command_encoder_trace_rays(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        desc: &TraceRaysDescriptor<'_>,
+    ) -> Result<(), CommandEncoderError> {
+        let (mut pass, err) = self.command_encoder_begin_compute_pass(
+            encoder_id,
+            &ComputePassDescriptor {
+                label: desc.label.clone(),
+                timestamp_writes: None,
+                pipeline_statistics_query: None,
+            },
+        );
+        if let Some(err) = err {
+            return Err(err);
+        }
+
+        self.compute_pass_set_ray_tracing_pipeline(&mut pass, desc.pipeline)
+            .map_err(|e| e.inner)?;
+        for (index, &(bind_group, offsets)) in desc.bind_groups.iter().enumerate() {
+            self.compute_pass_set_bind_group(&mut pass, index as u32, Some(bind_group), offsets)
+                .map_err(|e| e.inner)?;
+        }
+        if !desc.push_constants.is_empty() {
+            self.compute_pass_set_push_constants(&mut pass, 0, desc.push_constants)
+                .map_err(|e| e.inner)?;
+        }
+        self.compute_pass_trace_rays(&mut pass, desc.shader_binding_table, desc.layout, desc.size)
+            .map_err(|e| e.inner)?;
+        self.compute_pass_end(&mut pass)?;
+        Ok(())
+    }
+
     pub fn compute_pass_push_debug_group(
         &self,
         pass: &mut ComputePass,
@@ -3,7 +3,7 @@ use alloc::sync::Arc;
 use crate::{
     binding_model::BindGroup,
     id,
-    pipeline::ComputePipeline,
+    pipeline::{ComputePipeline, RayTracingPipeline},
     resource::{Buffer, QuerySet},
 };
 
@@ -18,6 +18,8 @@ pub enum ComputeCommand {
 
     SetPipeline(id::ComputePipelineId),
 
+    SetRayTracingPipeline(id::RayTracingPipelineId),
+
     /// Set a range of push constants to values stored in `push_constant_data`.
     SetPushConstant {
         /// The byte offset within the push constant storage to write to. This
@@ -42,6 +44,13 @@ pub enum ComputeCommand {
         offset: wgt::BufferAddress,
     },
 
+    TraceRays {
+        /// The buffer holding the shader binding table.
+        buffer_id: id::BufferId,
+        layout: wgt::ShaderBindingTableLayout,
+        size: [u32; 3],
+    },
+
     PushDebugGroup {
         color: u32,
         len: usize,
@@ -81,6 +90,7 @@ impl ComputeCommand {
         let bind_group_guard = hub.bind_groups.read();
         let query_set_guard = hub.query_sets.read();
         let pipelines_guard = hub.compute_pipelines.read();
+        let ray_tracing_pipelines_guard = hub.ray_tracing_pipelines.read();
 
         let resolved_commands: Vec<ArcComputeCommand> = commands
             .iter()
@@ -123,6 +133,16 @@ impl ComputeCommand {
                             })?,
                     ),
 
+                    ComputeCommand::SetRayTracingPipeline(pipeline_id) => {
+                        let pipeline = ray_tracing_pipelines_guard.get(pipeline_id).get();
+                        ArcComputeCommand::SetRayTracingPipeline(pipeline.map_err(|e| {
+                            ComputePassError {
+                                scope: PassErrorScope::SetPipelineRayTracing,
+                                inner: e.into(),
+                            }
+                        })?)
+                    }
+
                     ComputeCommand::SetPushConstant {
                         offset,
                         size_bytes,
@@ -147,6 +167,21 @@ impl ComputeCommand {
                         }
                     }
 
+                    ComputeCommand::TraceRays {
+                        buffer_id,
+                        layout,
+                        size,
+                    } => ArcComputeCommand::TraceRays {
+                        buffer: buffers_guard.get(buffer_id).get().map_err(|e| {
+                            ComputePassError {
+                                scope: PassErrorScope::TraceRays,
+                                inner: e.into(),
+                            }
+                        })?,
+                        layout,
+                        size,
+                    },
+
                     ComputeCommand::PushDebugGroup { color, len } => {
                         ArcComputeCommand::PushDebugGroup { color, len }
                     }
@@ -204,6 +239,8 @@ pub enum ArcComputeCommand {
 
     SetPipeline(Arc<ComputePipeline>),
 
+    SetRayTracingPipeline(Arc<RayTracingPipeline>),
+
     /// Set a range of push constants to values stored in `push_constant_data`.
     SetPushConstant {
         /// The byte offset within the push constant storage to write to. This
@@ -228,6 +265,12 @@ pub enum ArcComputeCommand {
         offset: wgt::BufferAddress,
     },
 
+    TraceRays {
+        buffer: Arc<Buffer>,
+        layout: wgt::ShaderBindingTableLayout,
+        size: [u32; 3],
+    },
+
     PushDebugGroup {
         #[cfg_attr(not(any(feature = "serde", feature = "replay")), allow(dead_code))]
         color: u32,
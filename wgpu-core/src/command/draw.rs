@@ -60,8 +60,22 @@ pub enum DrawError {
         pipeline_format: wgt::IndexFormat,
         buffer_format: wgt::IndexFormat,
     },
+    #[error(
+        "{pipeline} restarts strips, so it needs a `strip_index_format` to be used with indexed draws"
+    )]
+    MissingStripIndexFormat { pipeline: ResourceErrorIdent },
     #[error(transparent)]
     BindingSizeTooSmall(#[from] LateMinBufferBindingSizeMismatch),
+    #[error("{pipeline} is a mesh pipeline and can only be used with mesh task draws")]
+    UnexpectedMeshPipeline { pipeline: ResourceErrorIdent },
+    #[error("{pipeline} is not a mesh pipeline and can't be used with mesh task draws")]
+    ExpectedMeshPipeline { pipeline: ResourceErrorIdent },
+    #[error("{pipeline} emulates wide lines and can only be used with non-indexed, direct draws")]
+    EmulatedWideLines { pipeline: ResourceErrorIdent },
+    #[error(
+        "8 bit indices are emulated on this backend by widening them to 16 bit, which needs a storage buffer binding of {size} bytes, more than the limit of {limit} bytes"
+    )]
+    Uint8IndicesTooLarge { size: u64, limit: u64 },
 }
 
 impl WebGpuError for DrawError {
@@ -115,6 +129,8 @@ pub enum RenderCommandError {
     InvalidViewportRectPosition { rect: Rect<f32>, min: f32, max: f32 },
     #[error("Viewport minDepth {0} and/or maxDepth {1} are not in [0, 1]")]
     InvalidViewportDepth(f32, f32),
+    #[error("Viewport index {index} is not less than the maximum of {max} viewports")]
+    ViewportIndexOutOfRange { index: u32, max: u32 },
     #[error("Scissor {0:?} is not contained in the render target {1:?}")]
     InvalidScissorRect(Rect<u32>, wgt::Extent3d),
     #[error("Support for {0} is not implemented yet")]
@@ -141,6 +157,7 @@ impl WebGpuError for RenderCommandError {
             | Self::InvalidViewportRectSize { .. }
             | Self::InvalidViewportRectPosition { .. }
             | Self::InvalidViewportDepth(..)
+            | Self::ViewportIndexOutOfRange { .. }
             | Self::InvalidScissorRect(..)
             | Self::Unimplemented(..) => return ErrorType::Validation,
         };
@@ -10,7 +10,10 @@ use crate::{
     FastHashMap,
     device::Device,
     init_tracker::*,
-    resource::{DestroyedResourceError, ParentDevice, RawResourceAccess, Texture, Trackable},
+    resource::{
+        AliasedTextureConflictError, DestroyedResourceError, Labeled, ParentDevice,
+        RawResourceAccess, Texture, Trackable,
+    },
     snatch::SnatchGuard,
     track::{DeviceTracker, TextureTracker},
 };
@@ -37,9 +40,62 @@ pub(crate) struct CommandBufferTextureMemoryActions {
     /// command buffer i.e. everything in this list resets the texture init
     /// state *after* the command buffer execution
     discards: Vec<TextureSurfaceDiscard>,
+    /// Aliased textures that currently own their memory within the command
+    /// buffer, with the epoch they were last used in.
+    active_aliases: Vec<(Arc<Texture>, u32)>,
+    /// Aliased textures that took over their memory and still need their
+    /// tracked state reset, see [`Self::take_alias_resets`].
+    pending_alias_resets: Vec<Arc<Texture>>,
+    /// Advanced every time pending resets are taken, i.e. after every pass or
+    /// copy. Aliased textures used in the same epoch are used at the same time.
+    alias_epoch: u32,
+    alias_conflict: Option<AliasedTextureConflictError>,
+    /// Whether actions are only gathered, to be registered with the actions of
+    /// another command buffer, see [`Self::new_deferred`].
+    deferred: bool,
 }
 
 impl CommandBufferTextureMemoryActions {
+    /// Returns the actions to execute for another submission of the same
+    /// command buffer.
+    ///
+    /// Only the init actions and discards are needed at submission; the
+    /// aliasing state is only used while recording.
+    pub(crate) fn clone_for_resubmission(&self) -> Self {
+        Self {
+            init_actions: self.init_actions.clone(),
+            discards: self.discards.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns actions that only gather the init actions registered with them,
+    /// for a part of a render pass recorded by a parallel encoder.
+    ///
+    /// Whether the actions need surfaces to be initialized depends on what the
+    /// command buffer did before, so they are only checked once the pass ends
+    /// and they are registered with [`Self::register_deferred`].
+    pub(crate) fn new_deferred() -> Self {
+        Self {
+            deferred: true,
+            ..Default::default()
+        }
+    }
+
+    /// Registers the actions gathered by actions created with
+    /// [`Self::new_deferred`].
+    ///
+    /// Returns previously discarded surfaces that need to be initialized
+    /// *immediately* now.
+    #[must_use]
+    pub(crate) fn register_deferred(&mut self, deferred: Self) -> SurfacesInDiscardState {
+        let mut immediately_necessary_clears = SurfacesInDiscardState::new();
+        for action in deferred.init_actions {
+            immediately_necessary_clears.extend(self.register_init_action(&action));
+        }
+        immediately_necessary_clears
+    }
+
     pub(crate) fn drain_init_actions<'a>(&'a mut self) -> Drain<'a, TextureInitTrackerAction> {
         self.init_actions.drain(..)
     }
@@ -58,6 +114,15 @@ impl CommandBufferTextureMemoryActions {
     ) -> SurfacesInDiscardState {
         let mut immediately_necessary_clears = SurfacesInDiscardState::new();
 
+        if self.deferred {
+            self.init_actions.push(action.clone());
+            return immediately_necessary_clears;
+        }
+
+        if action.texture.aliasing.is_some() {
+            self.activate_alias(&action.texture);
+        }
+
         // Note that within a command buffer we may stack arbitrary memory init
         // actions on the same texture Since we react to them in sequence, they
         // are going to be dropped again at queue submit
@@ -110,6 +175,73 @@ impl CommandBufferTextureMemoryActions {
         immediately_necessary_clears
     }
 
+    // Gives an aliased texture the memory it shares with other textures, unless
+    // it already has it. The previous contents of that memory are lost, so the
+    // whole texture is treated as discarded.
+    fn activate_alias(&mut self, texture: &Arc<Texture>) {
+        let epoch = self.alias_epoch;
+        if let Some((_, last_use)) = self
+            .active_aliases
+            .iter_mut()
+            .find(|(active, _)| active.is_equal(texture))
+        {
+            *last_use = epoch;
+            return;
+        }
+
+        let conflict = &mut self.alias_conflict;
+        self.active_aliases.retain(|(active, last_use)| {
+            if !active.aliases(texture) {
+                return true;
+            }
+            if *last_use == epoch && conflict.is_none() {
+                *conflict = Some(AliasedTextureConflictError {
+                    first: active.error_ident(),
+                    second: texture.error_ident(),
+                });
+            }
+            false
+        });
+        self.active_aliases.push((texture.clone(), epoch));
+
+        self.init_actions.push(TextureInitTrackerAction {
+            texture: texture.clone(),
+            range: TextureInitRange {
+                mip_range: texture.full_range.mips.clone(),
+                layer_range: texture.full_range.layers.clone(),
+            },
+            kind: MemoryInitKind::ImplicitlyInitialized,
+        });
+        self.discards
+            .retain(|discarded_surface| !discarded_surface.texture.is_equal(texture));
+        for mip_level in texture.full_range.mips.clone() {
+            for layer in texture.full_range.layers.clone() {
+                self.discards.push(TextureSurfaceDiscard {
+                    texture: texture.clone(),
+                    mip_level,
+                    layer,
+                });
+            }
+        }
+
+        self.pending_alias_resets.push(texture.clone());
+    }
+
+    /// Returns the aliased textures that took over their memory since the last
+    /// call. Their tracked state has to be reset with [`reset_aliased_textures`]
+    /// before any barriers for their new uses are recorded.
+    ///
+    /// Fails if overlapping aliased textures were used since the last call.
+    pub(crate) fn take_alias_resets(
+        &mut self,
+    ) -> Result<Vec<Arc<Texture>>, AliasedTextureConflictError> {
+        self.alias_epoch = self.alias_epoch.wrapping_add(1);
+        if let Some(conflict) = self.alias_conflict.take() {
+            return Err(conflict);
+        }
+        Ok(core::mem::take(&mut self.pending_alias_resets))
+    }
+
     // Shortcut for register_init_action when it is known that the action is an
     // implicit init, not requiring any immediate resource init.
     pub(crate) fn register_implicit_init(
@@ -154,6 +286,33 @@ pub(crate) fn fixup_discarded_surfaces<InitIter: Iterator<Item = TextureSurfaceD
     }
 }
 
+/// Moves aliased textures returned by
+/// [`CommandBufferTextureMemoryActions::take_alias_resets`] back to
+/// `UNINITIALIZED`, so that the barriers for their next uses wait for the
+/// textures that used their memory before.
+pub(crate) fn reset_aliased_textures(
+    textures: Vec<Arc<Texture>>,
+    encoder: &mut dyn hal::DynCommandEncoder,
+    texture_tracker: &mut TextureTracker,
+    snatch_guard: &SnatchGuard<'_>,
+) -> Result<(), DestroyedResourceError> {
+    for texture in textures {
+        let raw = texture.try_raw(snatch_guard)?;
+        let barriers = texture_tracker
+            .set_single(
+                &texture,
+                texture.full_range.clone(),
+                wgt::TextureUses::UNINITIALIZED,
+            )
+            .map(|pending| pending.into_hal(raw))
+            .collect::<Vec<_>>();
+        unsafe {
+            encoder.transition_textures(&barriers);
+        }
+    }
+    Ok(())
+}
+
 impl BakedCommands {
     // inserts all buffer initializations that are going to be needed for
     // executing the commands and updates resource init states accordingly
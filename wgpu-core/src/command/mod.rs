@@ -33,7 +33,7 @@ use self::memory_init::CommandBufferTextureMemoryActions;
 
 use crate::binding_model::BindingError;
 use crate::command::transition_resources::TransitionResourcesError;
-use crate::device::queue::TempResource;
+use crate::device::queue::{Queue, TempResource};
 use crate::device::{Device, DeviceError, MissingFeatures};
 use crate::lock::{Mutex, rank};
 use crate::snatch::SnatchGuard;
@@ -43,8 +43,10 @@ use crate::ray_tracing::{AsAction, BuildAccelerationStructureError};
 use crate::resource::{
     DestroyedResourceError, Fallible, InvalidResourceError, Labeled, ParentDevice as _, QuerySet,
 };
-use crate::storage::Storage;
-use crate::track::{DeviceTracker, ResourceUsageCompatibilityError, Tracker, UsageScope};
+use crate::storage::StorageReadGuard;
+use crate::track::{
+    BarrierLog, DeviceTracker, ResourceUsageCompatibilityError, Tracker, UsageScope,
+};
 use crate::{Label, api_log, global::Global, id, resource_log};
 use crate::{LabelHelpers, hal_label};
 
@@ -98,6 +100,15 @@ pub(crate) enum CommandEncoderStatus {
     /// See <https://www.w3.org/TR/webgpu/#encoder-state-ended>
     Finished(CommandBufferMutable),
 
+    /// Command recording is complete, and the buffer can be submitted any
+    /// number of times.
+    ///
+    /// [`Global::command_encoder_finish`] transitions a `CommandBuffer` from
+    /// the `Recording` state into this state if
+    /// [`wgt::CommandBufferDescriptor::reusable`] is set. Unlike
+    /// [`Self::Finished`], submitting the buffer leaves it in this state.
+    Reusable(Arc<CommandBufferMutable>),
+
     /// The command encoder is invalid.
     ///
     /// The error that caused the invalidation is stored here, and will
@@ -132,6 +143,13 @@ impl CommandEncoderStatus {
         f: F,
     ) -> Result<(), EncoderStateError> {
         match self {
+            Self::Recording(inner) if inner.suspended_render_pass.is_some() => {
+                // Nothing may come between a suspended render pass and the
+                // pass resuming it. Like a locked encoder, this invalidates
+                // the encoder without an immediate validation error.
+                self.invalidate(EncoderStateError::Suspended);
+                Ok(())
+            }
             Self::Recording(_) => {
                 RecordingGuard { inner: self }.record(f);
                 Ok(())
@@ -144,7 +162,7 @@ impl CommandEncoderStatus {
             }
             // Encoder is ended. Invalidate the encoder, do not record anything,
             // and return an immediate validation error.
-            Self::Finished(_) => Err(self.invalidate(EncoderStateError::Ended)),
+            Self::Finished(_) | Self::Reusable(_) => Err(self.invalidate(EncoderStateError::Ended)),
             // Encoder is already invalid. Do not record anything, but do not
             // return an immediate validation error.
             Self::Error(_) => Ok(()),
@@ -164,12 +182,16 @@ impl CommandEncoderStatus {
         f: F,
     ) -> T {
         match self {
+            Self::Recording(inner) if inner.suspended_render_pass.is_some() => {
+                self.invalidate(EncoderStateError::Suspended);
+                f(None)
+            }
             Self::Recording(_) => RecordingGuard { inner: self }.record_as_hal_mut(f),
             Self::Locked(_) => {
                 self.invalidate(EncoderStateError::Locked);
                 f(None)
             }
-            Self::Finished(_) => {
+            Self::Finished(_) | Self::Reusable(_) => {
                 self.invalidate(EncoderStateError::Ended);
                 f(None)
             }
@@ -187,6 +209,22 @@ impl CommandEncoderStatus {
             // implement serialization for all the error types, we don't support
             // storing the errors in a trace.
             Self::Error(_) => unreachable!("passes in a trace do not store errors"),
+            // Traces record the commands of every submission of a reusable
+            // command buffer, and replay them with non-reusable ones.
+            Self::Reusable(_) => unreachable!("passes in a trace are not reusable"),
+            Self::Transitioning => unreachable!(),
+        }
+    }
+
+    /// Returns the [`CommandBufferMutable`] of an encoder in the
+    /// [`Self::Locked`] state, for the pass that locked it to record into
+    /// before it ends.
+    fn locked_inner(&mut self) -> Result<&mut CommandBufferMutable, EncoderStateError> {
+        match self {
+            Self::Locked(inner) => Ok(inner),
+            Self::Recording(_) => Err(EncoderStateError::Unlocked),
+            Self::Finished(_) | Self::Reusable(_) => Err(EncoderStateError::Ended),
+            Self::Error(_) => Err(EncoderStateError::Invalid),
             Self::Transitioning => unreachable!(),
         }
     }
@@ -202,7 +240,7 @@ impl CommandEncoderStatus {
                 *self = Self::Locked(inner);
                 Ok(())
             }
-            st @ Self::Finished(_) => {
+            st @ (Self::Finished(_) | Self::Reusable(_)) => {
                 // Attempting to open a pass on a finished encoder raises a
                 // validation error but does not invalidate the encoder. This is
                 // related to https://github.com/gpuweb/gpuweb/issues/5207.
@@ -246,7 +284,7 @@ impl CommandEncoderStatus {
                 RecordingGuard { inner: self }.record(f);
                 Ok(())
             }
-            st @ Self::Finished(_) => {
+            st @ (Self::Finished(_) | Self::Reusable(_)) => {
                 *self = st;
                 Err(EncoderStateError::Ended)
             }
@@ -264,19 +302,36 @@ impl CommandEncoderStatus {
         }
     }
 
-    fn finish(&mut self) -> Result<(), CommandEncoderError> {
+    fn finish(&mut self, reusable: bool) -> Result<(), CommandEncoderError> {
         match mem::replace(self, Self::Transitioning) {
             Self::Recording(mut inner) => {
                 if let Err(e) = inner.encoder.close_if_open() {
                     Err(self.invalidate(e.into()))
-                } else {
+                } else if let Err(e) = inner.record_suspended_pre_pass() {
+                    Err(self.invalidate(e))
+                } else if !reusable {
                     *self = Self::Finished(inner);
                     // Note: if we want to stop tracking the swapchain texture view,
                     // this is the place to do it.
                     Ok(())
+                } else if let Err(e) = inner
+                    .encoder
+                    .device
+                    .require_features(wgt::Features::REUSABLE_COMMAND_BUFFERS)
+                {
+                    Err(self.invalidate(e.into()))
+                } else if inner.suspended_render_pass.is_some()
+                    || inner.resumed_render_pass.is_some()
+                {
+                    Err(self.invalidate(CommandEncoderError::ReusableRenderPassSuspension))
+                } else {
+                    *self = Self::Reusable(Arc::new(inner));
+                    Ok(())
                 }
             }
-            Self::Finished(_) => Err(self.invalidate(EncoderStateError::Ended.into())),
+            Self::Finished(_) | Self::Reusable(_) => {
+                Err(self.invalidate(EncoderStateError::Ended.into()))
+            }
             Self::Locked(_) => Err(self.invalidate(EncoderStateError::Locked.into())),
             Self::Error(err) => Err(self.invalidate(err)),
             Self::Transitioning => unreachable!(),
@@ -417,6 +472,12 @@ pub(crate) struct CommandEncoder {
 
     pub(crate) device: Arc<Device>,
 
+    /// The [`CommandAllocator`] `raw` came from, and returns to.
+    ///
+    /// Each queue has its own allocator, as raw encoders can only record
+    /// command buffers for the queue they were created for.
+    pub(crate) allocator: Arc<CommandAllocator>,
+
     /// True if `raw` is in the "recording" state.
     ///
     /// See the documentation for [`wgpu_hal::CommandEncoder`] for
@@ -575,7 +636,7 @@ impl Drop for CommandEncoder {
         }
         // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
         let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
-        self.device.command_allocator.release_encoder(raw);
+        self.allocator.release_encoder(raw);
     }
 }
 
@@ -617,6 +678,23 @@ pub struct CommandBufferMutable {
 
     indirect_draw_validation_resources: crate::indirect_validation::DrawResources,
 
+    /// The render pass ended with [`wgt::RenderPassFlags::SUSPENDING`] that
+    /// has not been resumed yet. No commands but a resuming render pass can be
+    /// recorded while this is set.
+    pub(crate) suspended_render_pass: Option<SuspendedRenderPass>,
+
+    /// The render pass suspended by a previous command buffer, which the
+    /// first render pass of this one resumes.
+    pub(crate) resumed_render_pass: Option<SuspendedRenderPass>,
+
+    /// The work to record before the parts of [`Self::suspended_render_pass`]
+    /// recorded into this command buffer.
+    suspended_pre_pass: Option<SuspendedPrePass>,
+
+    /// The resources used by the parts of [`Self::suspended_render_pass`]
+    /// recorded into this command buffer, once it is finished.
+    pub(crate) suspended_resources: SuspendedResources,
+
     #[cfg(feature = "trace")]
     pub(crate) commands: Option<Vec<TraceCommand>>,
 }
@@ -631,6 +709,58 @@ impl CommandBufferMutable {
         Ok((encoder, tracker))
     }
 
+    /// Records the work needed before a render pass that is still suspended
+    /// at the end of the command buffer, see [`SuspendedPrePass`].
+    fn record_suspended_pre_pass(&mut self) -> Result<(), CommandEncoderError> {
+        let Some(pre_pass) = self.suspended_pre_pass.take() else {
+            return Ok(());
+        };
+        self.suspended_resources = pre_pass.resources();
+        let device = self.encoder.device.clone();
+        let snatch_guard = device.snatchable_lock.read();
+        pre_pass
+            .record(&device, self, &snatch_guard)
+            .map_pass_err(PassErrorScope::Pass)?;
+        Ok(())
+    }
+
+    /// Returns [`BakedCommands`] preparing the resources for another
+    /// submission of the commands of a reusable command buffer.
+    ///
+    /// The commands recorded in `self` are left as they are. Instead, the
+    /// returned [`BakedCommands`] has an encoder of its own, in which the
+    /// transitions and memory initialization that precede them are recorded.
+    /// Which memory needs to be initialized is only known at submission, so the
+    /// memory init actions are copied.
+    pub(crate) fn reusable_baked_commands(
+        &self,
+        queue: &Queue,
+    ) -> Result<BakedCommands, DeviceError> {
+        let device = &self.encoder.device;
+        let raw = self
+            .encoder
+            .allocator
+            .acquire_encoder(device.raw(), queue.raw())
+            .map_err(|e| device.handle_hal_error(e))?;
+        Ok(BakedCommands {
+            encoder: CommandEncoder {
+                raw: ManuallyDrop::new(raw),
+                list: Vec::new(),
+                device: device.clone(),
+                allocator: self.encoder.allocator.clone(),
+                is_open: false,
+                hal_label: self.encoder.hal_label.clone(),
+            },
+            trackers: Tracker::new(),
+            temp_resources: Vec::new(),
+            indirect_draw_validation_resources: crate::indirect_validation::DrawResources::new(
+                device.clone(),
+            ),
+            buffer_memory_init_actions: self.buffer_memory_init_actions.clone(),
+            texture_memory_actions: self.texture_memory_actions.clone_for_resubmission(),
+        })
+    }
+
     pub(crate) fn into_baked_commands(self) -> BakedCommands {
         BakedCommands {
             encoder: self.encoder,
@@ -663,6 +793,8 @@ impl CommandBufferMutable {
 ///   whose contents eventually become the property of the submission queue.
 pub struct CommandBuffer {
     pub(crate) device: Arc<Device>,
+    /// The type of the queue this command buffer can be submitted to.
+    pub(crate) queue_type: wgt::QueueType,
     support_clear_texture: bool,
     /// The `label` from the descriptor used to create the resource.
     label: String,
@@ -680,11 +812,13 @@ impl Drop for CommandBuffer {
 impl CommandBuffer {
     pub(crate) fn new(
         encoder: Box<dyn hal::DynCommandEncoder>,
-        device: &Arc<Device>,
+        queue: &Queue,
         label: &Label,
     ) -> Self {
+        let device = &queue.device;
         CommandBuffer {
             device: device.clone(),
+            queue_type: queue.queue_type,
             support_clear_texture: device.features.contains(wgt::Features::CLEAR_TEXTURE),
             label: label.to_string(),
             data: Mutex::new(
@@ -694,6 +828,7 @@ impl CommandBuffer {
                         raw: ManuallyDrop::new(encoder),
                         list: Vec::new(),
                         device: device.clone(),
+                        allocator: queue.command_allocator().clone(),
                         is_open: false,
                         hal_label: label.to_hal(device.instance_flags).map(str::to_owned),
                     },
@@ -705,6 +840,10 @@ impl CommandBuffer {
                     temp_resources: Default::default(),
                     indirect_draw_validation_resources:
                         crate::indirect_validation::DrawResources::new(device.clone()),
+                    suspended_render_pass: None,
+                    resumed_render_pass: None,
+                    suspended_pre_pass: None,
+                    suspended_resources: SuspendedResources::default(),
                     #[cfg(feature = "trace")]
                     commands: if device.trace.lock().is_some() {
                         Some(Vec::new())
@@ -723,6 +862,7 @@ impl CommandBuffer {
     ) -> Self {
         CommandBuffer {
             device: device.clone(),
+            queue_type: wgt::QueueType::Graphics,
             support_clear_texture: device.features.contains(wgt::Features::CLEAR_TEXTURE),
             label: label.to_string(),
             data: Mutex::new(rank::COMMAND_BUFFER_DATA, CommandEncoderStatus::Error(err)),
@@ -734,13 +874,14 @@ impl CommandBuffer {
         base: &mut Tracker,
         head: &Tracker,
         snatch_guard: &SnatchGuard,
+        barrier_log: Option<&mut BarrierLog>,
     ) {
         profiling::scope!("insert_barriers");
 
         base.buffers.set_from_tracker(&head.buffers);
         base.textures.set_from_tracker(&head.textures);
 
-        Self::drain_barriers(raw, base, snatch_guard);
+        Self::drain_barriers(raw, base, snatch_guard, barrier_log);
     }
 
     pub(crate) fn insert_barriers_from_scope(
@@ -748,22 +889,33 @@ impl CommandBuffer {
         base: &mut Tracker,
         head: &UsageScope,
         snatch_guard: &SnatchGuard,
+        barrier_log: Option<&mut BarrierLog>,
     ) {
         profiling::scope!("insert_barriers");
 
         base.buffers.set_from_usage_scope(&head.buffers);
         base.textures.set_from_usage_scope(&head.textures);
 
-        Self::drain_barriers(raw, base, snatch_guard);
+        Self::drain_barriers(raw, base, snatch_guard, barrier_log);
     }
 
     pub(crate) fn drain_barriers(
         raw: &mut dyn hal::DynCommandEncoder,
         base: &mut Tracker,
         snatch_guard: &SnatchGuard,
+        barrier_log: Option<&mut BarrierLog>,
     ) {
         profiling::scope!("drain_barriers");
 
+        if let Some(barrier_log) = barrier_log {
+            for (buffer, pending) in base.buffers.pending_transitions() {
+                barrier_log.buffer(buffer, pending);
+            }
+            for (texture, pending) in base.textures.pending_transitions() {
+                barrier_log.texture(texture, pending);
+            }
+        }
+
         let buffer_barriers = base
             .buffers
             .drain_transitions(snatch_guard)
@@ -786,17 +938,22 @@ impl CommandBuffer {
         base: &mut DeviceTracker,
         head: &Tracker,
         snatch_guard: &SnatchGuard,
+        mut barrier_log: Option<&mut BarrierLog>,
     ) {
         profiling::scope!("insert_barriers_from_device_tracker");
 
         let buffer_barriers = base
             .buffers
-            .set_from_tracker_and_drain_transitions(&head.buffers, snatch_guard)
+            .set_from_tracker_and_drain_transitions(
+                &head.buffers,
+                snatch_guard,
+                barrier_log.as_deref_mut(),
+            )
             .collect::<Vec<_>>();
 
         let texture_barriers = base
             .textures
-            .set_from_tracker_and_drain_transitions(&head.textures, snatch_guard)
+            .set_from_tracker_and_drain_transitions(&head.textures, snatch_guard, barrier_log)
             .collect::<Vec<_>>();
 
         unsafe {
@@ -806,14 +963,42 @@ impl CommandBuffer {
     }
 }
 
+/// The commands of a finished [`CommandBuffer`], taken for submission.
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum FinishedCommands {
+    /// The commands of a command buffer that can only be submitted once.
+    Once(CommandBufferMutable),
+    /// The commands of a reusable command buffer, which are shared by all its
+    /// submissions.
+    Reusable(Arc<CommandBufferMutable>),
+}
+
+impl FinishedCommands {
+    /// Returns the commands to record in the trace for this submission.
+    #[cfg(feature = "trace")]
+    pub(crate) fn take_trace_commands(&mut self) -> Option<Vec<TraceCommand>> {
+        match self {
+            Self::Once(cmd_buf_data) => cmd_buf_data.commands.take(),
+            Self::Reusable(cmd_buf_data) => cmd_buf_data.commands.clone(),
+        }
+    }
+}
+
 impl CommandBuffer {
-    pub fn take_finished(&self) -> Result<CommandBufferMutable, CommandEncoderError> {
+    pub(crate) fn take_finished(&self) -> Result<FinishedCommands, CommandEncoderError> {
         use CommandEncoderStatus as St;
+        let mut data = self.data.lock();
+        if let St::Reusable(ref command_buffer_mutable) = *data {
+            return Ok(FinishedCommands::Reusable(command_buffer_mutable.clone()));
+        }
         match mem::replace(
-            &mut *self.data.lock(),
+            &mut *data,
             CommandEncoderStatus::Error(EncoderStateError::Submitted.into()),
         ) {
-            St::Finished(command_buffer_mutable) => Ok(command_buffer_mutable),
+            St::Finished(command_buffer_mutable) => {
+                Ok(FinishedCommands::Once(command_buffer_mutable))
+            }
+            St::Reusable(_) => unreachable!(),
             St::Error(err) => Err(err),
             St::Recording(_) | St::Locked(_) => {
                 Err(InvalidResourceError(self.error_ident()).into())
@@ -1003,6 +1188,13 @@ pub enum EncoderStateError {
     /// objects, we use `CommandEncoderStatus` for both.
     #[error("This command buffer has already been submitted.")]
     Submitted,
+
+    /// Stored in the encoder when a command other than a resuming render pass
+    /// is recorded after a suspended render pass.
+    #[error(
+        "Encoder has a suspended render pass. No commands can be recorded until a render pass resumes it."
+    )]
+    Suspended,
 }
 
 impl WebGpuError for EncoderStateError {
@@ -1012,7 +1204,8 @@ impl WebGpuError for EncoderStateError {
             | EncoderStateError::Ended
             | EncoderStateError::Locked
             | EncoderStateError::Unlocked
-            | EncoderStateError::Submitted => ErrorType::Validation,
+            | EncoderStateError::Submitted
+            | EncoderStateError::Suspended => ErrorType::Validation,
         }
     }
 }
@@ -1046,6 +1239,10 @@ pub enum CommandEncoderError {
     ComputePass(#[from] ComputePassError),
     #[error(transparent)]
     RenderPass(#[from] RenderPassError),
+    #[error(
+        "A reusable command buffer can't suspend or resume render passes across command buffers"
+    )]
+    ReusableRenderPassSuspension,
 }
 
 impl CommandEncoderError {
@@ -1095,6 +1292,7 @@ impl WebGpuError for CommandEncoderError {
             Self::ResourceUsage(e) => e,
             Self::ComputePass(e) => e,
             Self::RenderPass(e) => e,
+            Self::ReusableRenderPassSuspension => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -1125,7 +1323,7 @@ impl Global {
     pub fn command_encoder_finish(
         &self,
         encoder_id: id::CommandEncoderId,
-        _desc: &wgt::CommandBufferDescriptor<Label>,
+        desc: &wgt::CommandBufferDescriptor<Label>,
     ) -> (id::CommandBufferId, Option<CommandEncoderError>) {
         profiling::scope!("CommandEncoder::finish");
 
@@ -1135,7 +1333,7 @@ impl Global {
 
         // Errors related to destroyed resources are not reported until the
         // command buffer is submitted.
-        let error = match cmd_buf.data.lock().finish() {
+        let error = match cmd_buf.data.lock().finish(desc.reusable) {
             Err(e) if !e.is_destroyed_error() => Some(e),
             _ => None,
         };
@@ -1249,7 +1447,7 @@ impl Global {
 
     fn validate_pass_timestamp_writes<E>(
         device: &Device,
-        query_sets: &Storage<Fallible<QuerySet>>,
+        query_sets: &StorageReadGuard<'_, Fallible<QuerySet>>,
         timestamp_writes: &PassTimestampWrites,
     ) -> Result<ArcPassTimestampWrites, E>
     where
@@ -1297,6 +1495,35 @@ impl Global {
             end_of_pass_write_index,
         })
     }
+
+    fn validate_pass_pipeline_statistics_query<E>(
+        device: &Device,
+        query_sets: &StorageReadGuard<'_, Fallible<QuerySet>>,
+        pipeline_statistics_query: &PassPipelineStatisticsQuery,
+    ) -> Result<ArcPassPipelineStatisticsQuery, E>
+    where
+        E: From<QueryUseError>
+            + From<DeviceError>
+            + From<MissingFeatures>
+            + From<InvalidResourceError>,
+    {
+        let &PassPipelineStatisticsQuery {
+            query_set,
+            query_index,
+        } = pipeline_statistics_query;
+
+        device.require_features(wgt::Features::PIPELINE_STATISTICS_QUERY)?;
+
+        let query_set = query_sets.get(query_set).get()?;
+
+        query_set.same_device(device)?;
+        query_set.validate_query(SimplifiedQueryType::PipelineStatistics, query_index, None)?;
+
+        Ok(ArcPassPipelineStatisticsQuery {
+            query_set,
+            query_index,
+        })
+    }
 }
 
 fn push_constant_clear<PushFn>(offset: u32, size_bytes: u32, mut push_fn: PushFn)
@@ -1421,6 +1648,10 @@ pub enum DrawKind {
     DrawIndirect,
     MultiDrawIndirect,
     MultiDrawIndirectCount,
+    DrawMeshTasks,
+    DrawMeshTasksIndirect,
+    MultiDrawMeshTasksIndirect,
+    MultiDrawMeshTasksIndirectCount,
 }
 
 /// A command that can be recorded in a pass or bundle.
@@ -1446,6 +1677,8 @@ pub enum PassErrorScope {
     SetPipelineRender,
     #[error("In a set_pipeline command")]
     SetPipelineCompute,
+    #[error("In a set_ray_tracing_pipeline command")]
+    SetPipelineRayTracing,
     #[error("In a set_push_constant command")]
     SetPushConstant,
     #[error("In a set_vertex_buffer command")]
@@ -1456,8 +1689,12 @@ pub enum PassErrorScope {
     SetBlendConstant,
     #[error("In a set_stencil_reference command")]
     SetStencilReference,
+    #[error("In a set_shading_rate command")]
+    SetShadingRate,
     #[error("In a set_viewport command")]
     SetViewport,
+    #[error("In a set_viewports command")]
+    SetViewports,
     #[error("In a set_scissor_rect command")]
     SetScissorRect,
     #[error("In a draw command, kind: {kind:?}")]
@@ -1472,10 +1709,18 @@ pub enum PassErrorScope {
     BeginPipelineStatisticsQuery,
     #[error("In a end_pipeline_statistics_query command")]
     EndPipelineStatisticsQuery,
+    #[error("In a begin_conditional_block command")]
+    BeginConditionalBlock,
+    #[error("In a end_conditional_block command")]
+    EndConditionalBlock,
     #[error("In a execute_bundle command")]
     ExecuteBundle,
+    #[error("In a create_parallel_encoder command")]
+    CreateParallelEncoder,
     #[error("In a dispatch command, indirect:{indirect}")]
     Dispatch { indirect: bool },
+    #[error("In a trace_rays command")]
+    TraceRays,
     #[error("In a push_debug_group command")]
     PushDebugGroup,
     #[error("In a pop_debug_group command")]
@@ -47,6 +47,19 @@ impl QueryResetMap {
         mem::replace(&mut vec_pair.0[query as usize], true)
     }
 
+    /// Adds the queries used by `other`, which were used in the same render
+    /// pass as those of `self`.
+    pub fn merge(&mut self, other: Self) -> Result<(), QueryUseError> {
+        for (_, (state, query_set)) in other.map {
+            for (query_index, used) in (0..).zip(state) {
+                if used && self.use_query_set(&query_set, query_index) {
+                    return Err(QueryUseError::UsedTwiceInsideRenderpass { query_index });
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn reset_queries(&mut self, raw_encoder: &mut dyn hal::DynCommandEncoder) {
         for (_, (state, query_set)) in self.map.drain() {
             debug_assert_eq!(state.len(), query_set.desc.count as usize);
@@ -92,6 +105,19 @@ impl From<wgt::QueryType> for SimplifiedQueryType {
     }
 }
 
+/// Describes a pipeline statistics query recording the work of a whole render or compute pass.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PassPipelineStatisticsQuery<QS = id::QuerySetId> {
+    /// The query set to write the statistics to.
+    pub query_set: QS,
+    /// The index of the query in the query set.
+    pub query_index: u32,
+}
+
+/// cbindgen:ignore
+pub(crate) type ArcPassPipelineStatisticsQuery = PassPipelineStatisticsQuery<Arc<QuerySet>>;
+
 /// Error encountered when dealing with queries
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
@@ -149,6 +175,13 @@ pub enum QueryUseError {
         active_query_index: u32,
         new_query_index: u32,
     },
+    #[error(
+        "Pipeline statistics query {query_index} was started in a pass whose statistics are recorded by query {pass_query_index}"
+    )]
+    PassQueryActive {
+        pass_query_index: u32,
+        query_index: u32,
+    },
     #[error("Query was stopped while there was no active query")]
     AlreadyStopped,
     #[error("A query of type {query_type:?} was started using a query set of type {set_type:?}")]
@@ -165,6 +198,7 @@ impl WebGpuError for QueryUseError {
             Self::OutOfBounds { .. }
             | Self::UsedTwiceInsideRenderpass { .. }
             | Self::AlreadyStarted { .. }
+            | Self::PassQueryActive { .. }
             | Self::AlreadyStopped
             | Self::IncompatibleType { .. } => ErrorType::Validation,
         }
@@ -319,9 +353,18 @@ pub(super) fn validate_and_begin_pipeline_statistics_query(
     query_index: u32,
     reset_state: Option<&mut QueryResetMap>,
     active_query: &mut Option<(Arc<QuerySet>, u32)>,
+    pass_query_index: Option<u32>,
 ) -> Result<(), QueryUseError> {
     query_set.same_device_as(cmd_buf)?;
 
+    // A query of the same type can't be active twice.
+    if let Some(pass_query_index) = pass_query_index {
+        return Err(QueryUseError::PassQueryActive {
+            pass_query_index,
+            query_index,
+        });
+    }
+
     let needs_reset = reset_state.is_none();
     query_set.validate_query(
         SimplifiedQueryType::PipelineStatistics,
@@ -7,7 +7,7 @@ use core::{
 
 use wgt::{BufferUsages, BufferUses, Features, math::align_to};
 
-use crate::ray_tracing::{AsAction, AsBuild, TlasBuild, ValidateAsActionsError};
+use crate::ray_tracing::{AsAction, AsBuild, BlasBuild, TlasBuild, ValidateAsActionsError};
 use crate::{
     command::CommandBufferMutable,
     device::queue::TempResource,
@@ -37,18 +37,21 @@ struct TriangleBufferStore<'a> {
     transform_buffer_transition: Option<(Arc<Buffer>, Option<PendingTransition<BufferUses>>)>,
     geometry: BlasTriangleGeometry<'a>,
     ending_blas: Option<Arc<Blas>>,
+    ending_blas_update: bool,
 }
 
 struct BlasStore<'a> {
     blas: Arc<Blas>,
     entries: hal::AccelerationStructureEntries<'a, dyn hal::DynBuffer>,
     scratch_buffer_offset: u64,
+    update: bool,
 }
 
 struct UnsafeTlasStore<'a> {
     tlas: Arc<Tlas>,
     entries: hal::AccelerationStructureEntries<'a, dyn hal::DynBuffer>,
     scratch_buffer_offset: u64,
+    update: bool,
 }
 
 struct TlasStore<'a> {
@@ -82,7 +85,11 @@ impl Global {
 
                 for blas in blas_ids {
                     let blas = hub.blas_s.get(*blas).get()?;
-                    build_command.blas_s_built.push(blas);
+                    build_command.blas_s_built.push(BlasBuild {
+                        blas,
+                        update: false,
+                        counts: None,
+                    });
                 }
 
                 for tlas in tlas_ids {
@@ -90,6 +97,8 @@ impl Global {
                     build_command.tlas_s_built.push(TlasBuild {
                         tlas,
                         dependencies: Vec::new(),
+                        update: false,
+                        instance_count: None,
                     });
                 }
 
@@ -255,12 +264,6 @@ impl Global {
             for (package, tlas) in &mut tlas_lock_store {
                 let package = package.take().unwrap();
 
-                let scratch_buffer_offset = scratch_buffer_tlas_size;
-                scratch_buffer_tlas_size += align_to(
-                    tlas.size_info.build_scratch_size as u32,
-                    device.alignments.ray_tracing_scratch_buffer_alignment,
-                ) as u64;
-
                 let first_byte_index = instance_buffer_staging_source.len();
 
                 let mut dependencies = Vec::new();
@@ -303,9 +306,24 @@ impl Global {
                     dependencies.push(blas.clone());
                 }
 
+                let update = is_update_build(
+                    tlas.update_mode,
+                    tlas.flags,
+                    tlas.built_index.read().is_some(),
+                    || *tlas.built_instance_count.read() == Some(instance_count),
+                );
+
+                let scratch_buffer_offset = scratch_buffer_tlas_size;
+                scratch_buffer_tlas_size += align_to(
+                    scratch_size(&tlas.size_info, update) as u32,
+                    device.alignments.ray_tracing_scratch_buffer_alignment,
+                ) as u64;
+
                 build_command.tlas_s_built.push(TlasBuild {
                     tlas: tlas.clone(),
                     dependencies,
+                    update,
+                    instance_count: Some(instance_count),
                 });
 
                 if instance_count > tlas.max_instance_count {
@@ -327,17 +345,22 @@ impl Global {
                             },
                         ),
                         scratch_buffer_offset,
+                        update,
                     },
                     range: first_byte_index..instance_buffer_staging_source.len(),
                 });
             }
 
-            let Some(scratch_size) =
-                wgt::BufferSize::new(max(scratch_buffer_blas_size, scratch_buffer_tlas_size))
-            else {
-                // if the size is zero there is nothing to build
+            if blas_storage.is_empty() && tlas_storage.is_empty() {
+                // there is nothing to build
                 return Ok(());
-            };
+            }
+
+            // Updates may not need any scratch space, but the buffer can't be empty.
+            let scratch_size = wgt::BufferSize::new(
+                max(scratch_buffer_blas_size, scratch_buffer_tlas_size).max(1),
+            )
+            .unwrap();
 
             let scratch_buffer = ScratchBuffer::new(device, scratch_size)?;
 
@@ -357,19 +380,18 @@ impl Global {
                         ref tlas,
                         ref entries,
                         ref scratch_buffer_offset,
+                        update,
                     },
                 ..
             } in &tlas_storage
             {
-                if tlas.update_mode == wgt::AccelerationStructureUpdateMode::PreferUpdate {
-                    log::info!("only rebuild implemented")
-                }
+                let raw = tlas.try_raw(&snatch_guard)?;
                 tlas_descriptors.push(hal::BuildAccelerationStructureDescriptor {
                     entries,
-                    mode: hal::AccelerationStructureBuildMode::Build,
+                    mode: build_mode(update),
                     flags: tlas.flags,
-                    source_acceleration_structure: None,
-                    destination_acceleration_structure: tlas.try_raw(&snatch_guard)?,
+                    source_acceleration_structure: update.then_some(raw),
+                    destination_acceleration_structure: raw,
                     scratch_buffer: scratch_buffer.raw(),
                     scratch_buffer_offset: *scratch_buffer_offset,
                 })
@@ -520,7 +542,23 @@ impl CommandBufferMutable {
                     .unwrap();
 
                     command_index_guard.next_acceleration_structure_build_command_index += 1;
-                    for blas in build.blas_s_built.iter() {
+                    for BlasBuild {
+                        blas,
+                        update,
+                        counts,
+                    } in build.blas_s_built.iter()
+                    {
+                        if let (true, Some(built), Some(counts)) =
+                            (*update, blas.built_counts.read().as_ref(), counts)
+                        {
+                            if built != counts {
+                                return Err(ValidateAsActionsError::IncompatibleBlasRefit(
+                                    blas.error_ident(),
+                                    built.clone(),
+                                    counts.clone(),
+                                ));
+                            }
+                        }
                         let mut state_lock = blas.compacted_state.lock();
                         *state_lock = match *state_lock {
                             BlasCompactState::Compacted => {
@@ -531,9 +569,24 @@ impl CommandBufferMutable {
                             _ => BlasCompactState::Idle,
                         };
                         *blas.built_index.write() = Some(build_command_index);
+                        blas.built_counts.write().clone_from(counts);
                     }
 
                     for tlas_build in build.tlas_s_built.iter() {
+                        let tlas = &tlas_build.tlas;
+                        if let (true, Some(built), Some(count)) = (
+                            tlas_build.update,
+                            *tlas.built_instance_count.read(),
+                            tlas_build.instance_count,
+                        ) {
+                            if built != count {
+                                return Err(ValidateAsActionsError::IncompatibleTlasRefit(
+                                    tlas.error_ident(),
+                                    built,
+                                    count,
+                                ));
+                            }
+                        }
                         for blas in &tlas_build.dependencies {
                             if blas.built_index.read().is_none() {
                                 return Err(ValidateAsActionsError::UsedUnbuiltBlas(
@@ -542,7 +595,8 @@ impl CommandBufferMutable {
                                 ));
                             }
                         }
-                        *tlas_build.tlas.built_index.write() = Some(build_command_index);
+                        *tlas.built_index.write() = Some(build_command_index);
+                        *tlas.built_instance_count.write() = tlas_build.instance_count;
                         tlas_build
                             .tlas
                             .dependencies
@@ -593,7 +647,7 @@ fn iter_blas<'a>(
         let blas = hub.blas_s.get(entry.blas_id).get()?;
         cmd_buf_data.trackers.blas_s.insert_single(blas.clone());
 
-        build_command.blas_s_built.push(blas.clone());
+        let mut counts = Vec::new();
 
         match entry.geometries {
             BlasGeometries::TriangleGeometries(triangle_geometries) => {
@@ -750,6 +804,8 @@ fn iter_blas<'a>(
                         }
                         None
                     };
+                    counts.push(mesh.size.vertex_count);
+                    counts.push(mesh.size.index_count.unwrap_or(0));
                     temp_buffer.push(TriangleBufferStore {
                         vertex_buffer,
                         vertex_transition: vertex_pending,
@@ -757,19 +813,72 @@ fn iter_blas<'a>(
                         transform_buffer_transition: transform_data,
                         geometry: mesh,
                         ending_blas: None,
+                        ending_blas_update: false,
                     });
                 }
-
-                if let Some(last) = temp_buffer.last_mut() {
-                    last.ending_blas = Some(blas);
-                    buf_storage.append(&mut temp_buffer);
-                }
             }
         }
+
+        let update = is_update_build(
+            blas.update_mode,
+            blas.flags,
+            blas.built_index.read().is_some(),
+            || blas.built_counts.read().as_ref() == Some(&counts),
+        );
+
+        if let Some(last) = temp_buffer.last_mut() {
+            last.ending_blas = Some(blas.clone());
+            last.ending_blas_update = update;
+            buf_storage.append(&mut temp_buffer);
+        }
+
+        build_command.blas_s_built.push(BlasBuild {
+            blas,
+            update,
+            counts: Some(counts),
+        });
     }
     Ok(())
 }
 
+/// Whether a build of an acceleration structure updates its previous build, rather than
+/// building it anew. `built` tells whether a build of it was submitted already, and
+/// `same_counts_as_built` whether the last one had the same geometry counts as this one.
+///
+/// Refits with different counts are caught when the build is submitted.
+fn is_update_build(
+    update_mode: wgt::AccelerationStructureUpdateMode,
+    flags: wgt::AccelerationStructureFlags,
+    built: bool,
+    same_counts_as_built: impl FnOnce() -> bool,
+) -> bool {
+    match update_mode {
+        wgt::AccelerationStructureUpdateMode::Build => false,
+        wgt::AccelerationStructureUpdateMode::PreferUpdate => {
+            flags.contains(wgt::AccelerationStructureFlags::ALLOW_UPDATE)
+                && built
+                && same_counts_as_built()
+        }
+        wgt::AccelerationStructureUpdateMode::Refit => built,
+    }
+}
+
+fn scratch_size(size_info: &hal::AccelerationStructureBuildSizes, update: bool) -> u64 {
+    if update {
+        size_info.update_scratch_size
+    } else {
+        size_info.build_scratch_size
+    }
+}
+
+fn build_mode(update: bool) -> hal::AccelerationStructureBuildMode {
+    if update {
+        hal::AccelerationStructureBuildMode::Update
+    } else {
+        hal::AccelerationStructureBuildMode::Build
+    }
+}
+
 /// Iterates over the buffers generated in [iter_blas], convert the barriers into hal barriers, and the triangles into [hal::AccelerationStructureEntries] (and also some validation).
 fn iter_buffers<'a, 'b>(
     buf_storage: &'a mut Vec<TriangleBufferStore<'b>>,
@@ -930,9 +1039,10 @@ fn iter_buffers<'a, 'b>(
         };
         triangle_entries.push(triangles);
         if let Some(blas) = buf.ending_blas.take() {
+            let update = buf.ending_blas_update;
             let scratch_buffer_offset = *scratch_buffer_blas_size;
             *scratch_buffer_blas_size += align_to(
-                blas.size_info.build_scratch_size as u32,
+                scratch_size(&blas.size_info, update) as u32,
                 ray_tracing_scratch_buffer_alignment,
             ) as u64;
 
@@ -940,6 +1050,7 @@ fn iter_buffers<'a, 'b>(
                 blas,
                 entries: hal::AccelerationStructureEntries::Triangles(triangle_entries),
                 scratch_buffer_offset,
+                update,
             });
             triangle_entries = Vec::new();
         }
@@ -967,10 +1078,8 @@ fn map_blas<'a>(
         blas,
         entries,
         scratch_buffer_offset,
+        update,
     } = storage;
-    if blas.update_mode == wgt::AccelerationStructureUpdateMode::PreferUpdate {
-        log::info!("only rebuild implemented")
-    }
     let raw = blas.try_raw(snatch_guard)?;
 
     let state_lock = blas.compacted_state.lock();
@@ -988,9 +1097,9 @@ fn map_blas<'a>(
     }
     Ok(hal::BuildAccelerationStructureDescriptor {
         entries,
-        mode: hal::AccelerationStructureBuildMode::Build,
+        mode: build_mode(*update),
         flags: blas.flags,
-        source_acceleration_structure: None,
+        source_acceleration_structure: update.then_some(raw),
         destination_acceleration_structure: raw,
         scratch_buffer,
         scratch_buffer_offset: *scratch_buffer_offset,
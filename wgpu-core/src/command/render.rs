@@ -1,5 +1,5 @@
-use alloc::{borrow::Cow, sync::Arc, vec::Vec};
-use core::{fmt, num::NonZeroU32, ops::Range, str};
+use alloc::{borrow::Cow, boxed::Box, format, string::String, sync::Arc, vec, vec::Vec};
+use core::{fmt, mem, num::NonZeroU32, ops::Range, str};
 
 use arrayvec::ArrayVec;
 use thiserror::Error;
@@ -13,19 +13,23 @@ use crate::command::{
     EncoderStateError, PassStateError, TimestampWritesError, pass, pass_base, pass_try,
     validate_and_begin_occlusion_query, validate_and_begin_pipeline_statistics_query,
 };
+use crate::init_tracker::BufferInitTrackerAction;
+use crate::lock::{Mutex, rank};
 use crate::pipeline::{RenderPipeline, VertexStep};
+use crate::ray_tracing::AsAction;
 use crate::resource::RawResourceAccess;
 use crate::resource::{InvalidResourceError, ResourceErrorIdent};
 use crate::snatch::SnatchGuard;
 use crate::{
     Label, api_log,
     command::{
-        ArcPassTimestampWrites, BasePass, BindGroupStateChange, CommandBuffer, CommandEncoderError,
-        DrawError, ExecutionError, MapPassErr, PassErrorScope, PassTimestampWrites, QueryUseError,
-        RenderCommandError, StateChange,
+        ArcPassPipelineStatisticsQuery, ArcPassTimestampWrites, BasePass, BindGroupStateChange,
+        BundlePassState, CommandBuffer, CommandBufferMutable, CommandEncoderError, DrawError,
+        ExecutionError, MapPassErr, PassErrorScope, PassPipelineStatisticsQuery,
+        PassTimestampWrites, QueryUseError, RenderCommandError, StateChange,
         bind::Binder,
         end_occlusion_query, end_pipeline_statistics_query,
-        memory_init::{SurfacesInDiscardState, fixup_discarded_surfaces},
+        memory_init::{SurfacesInDiscardState, fixup_discarded_surfaces, reset_aliased_textures},
     },
     device::{
         AttachmentData, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
@@ -36,10 +40,14 @@ use crate::{
     init_tracker::{MemoryInitKind, TextureInitRange, TextureInitTrackerAction},
     pipeline::PipelineFlags,
     resource::{
-        DestroyedResourceError, Labeled, MissingBufferUsageError, MissingTextureUsageError,
-        ParentDevice, QuerySet, Texture, TextureView, TextureViewNotRenderableReason,
+        AliasedTextureConflictError, DestroyedResourceError, Labeled, MissingBufferUsageError,
+        MissingTextureUsageError, ParentDevice, QuerySet, Texture, TextureView,
+        TextureViewNotRenderableReason,
+    },
+    track::{
+        BarrierLog, BufferUsageScope, DeviceTracker, ResourceUsageCompatibilityError,
+        TextureUsageScope, Tracker, UsageScope,
     },
-    track::{ResourceUsageCompatibilityError, Tracker, UsageScope},
 };
 
 #[cfg(feature = "serde")]
@@ -71,6 +79,163 @@ fn store_hal_ops(store: StoreOp) -> hal::AttachmentOps {
     }
 }
 
+/// Transient textures have no backing storage outside of the render pass,
+/// so their contents can be neither loaded nor stored.
+fn check_transient_ops<V>(
+    view: &TextureView,
+    load: LoadOp<V>,
+    store: StoreOp,
+) -> Result<(), AttachmentError> {
+    if !view.parent.desc.usage.contains(TextureUsages::TRANSIENT) {
+        return Ok(());
+    }
+    if !matches!(load, LoadOp::Clear(_)) {
+        return Err(AttachmentError::TransientWithLoad(view.error_ident()));
+    }
+    if store != StoreOp::Discard {
+        return Err(AttachmentError::TransientWithStore(view.error_ident()));
+    }
+    Ok(())
+}
+
+/// Returns the operations a part of a render pass split up with
+/// [`wgt::RenderPassFlags`] performs on an attachment. Only the first part
+/// loads it, and only the last part stores it.
+fn effective_ops<V>(
+    flags: wgt::RenderPassFlags,
+    load_op: LoadOp<V>,
+    store_op: StoreOp,
+) -> (LoadOp<V>, StoreOp) {
+    let load_op = if flags.contains(wgt::RenderPassFlags::RESUMING) {
+        LoadOp::Load
+    } else {
+        load_op
+    };
+    let store_op = if flags.contains(wgt::RenderPassFlags::SUSPENDING) {
+        StoreOp::Store
+    } else {
+        store_op
+    };
+    (load_op, store_op)
+}
+
+/// Checks what a part of a render pass split up with [`wgt::RenderPassFlags`]
+/// can't do, as the render pass isn't ended between the parts on all backends.
+fn validate_suspend_resume(desc: &ArcRenderPassDescriptor) -> Result<(), RenderPassErrorInner> {
+    let suspending = desc.flags.contains(wgt::RenderPassFlags::SUSPENDING);
+    let resuming = desc.flags.contains(wgt::RenderPassFlags::RESUMING);
+    if !suspending && !resuming {
+        return Ok(());
+    }
+
+    if let Some(tw) = desc.timestamp_writes.as_ref() {
+        if suspending && tw.end_of_pass_write_index.is_some() {
+            return Err(RenderPassErrorInner::SuspendingPassEndTimestamp);
+        }
+        if resuming && tw.beginning_of_pass_write_index.is_some() {
+            return Err(RenderPassErrorInner::ResumingPassBeginningTimestamp);
+        }
+    }
+    if desc.pipeline_statistics_query.is_some() {
+        return Err(RenderPassErrorInner::SuspendResumePipelineStatisticsQuery);
+    }
+
+    // Transient attachments have no memory to store to between the parts.
+    let views = desc
+        .color_attachments
+        .iter()
+        .flatten()
+        .map(|at| &at.view)
+        .chain(desc.depth_stencil_attachment.as_ref().map(|at| &at.view));
+    for view in views {
+        if view.parent.desc.usage.contains(TextureUsages::TRANSIENT) {
+            return Err(RenderPassErrorInner::SuspendResumeTransientAttachment(
+                view.error_ident(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// A render pass ended with [`wgt::RenderPassFlags::SUSPENDING`], which the
+/// next render pass must resume.
+#[derive(Clone, Debug)]
+pub(crate) struct SuspendedRenderPass {
+    color_attachments:
+        ArrayVec<Option<ArcRenderPassColorAttachment>, { hal::MAX_COLOR_ATTACHMENTS }>,
+    depth_stencil_attachment: Option<ArcRenderPassDepthStencilAttachment>,
+    occlusion_query_set: Option<Arc<QuerySet>>,
+    multiview: Option<NonZeroU32>,
+}
+
+impl SuspendedRenderPass {
+    fn new(pass: &RenderPass) -> Self {
+        Self {
+            color_attachments: pass.color_attachments.clone(),
+            depth_stencil_attachment: pass.depth_stencil_attachment.clone(),
+            occlusion_query_set: pass.occlusion_query_set.clone(),
+            multiview: pass.multiview,
+        }
+    }
+
+    /// Returns true if `other` renders to exactly the same attachments, with
+    /// the same operations.
+    pub(crate) fn matches(&self, other: &Self) -> bool {
+        fn same<T>(a: Option<&Arc<T>>, b: Option<&Arc<T>>) -> bool {
+            match (a, b) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+        }
+
+        let same_color = self.color_attachments.len() == other.color_attachments.len()
+            && self
+                .color_attachments
+                .iter()
+                .zip(other.color_attachments.iter())
+                .all(|pair| match pair {
+                    (None, None) => true,
+                    (Some(a), Some(b)) => {
+                        Arc::ptr_eq(&a.view, &b.view)
+                            && a.depth_slice == b.depth_slice
+                            && same(a.resolve_target.as_ref(), b.resolve_target.as_ref())
+                            && a.load_op == b.load_op
+                            && a.store_op == b.store_op
+                    }
+                    _ => false,
+                });
+        let same_depth_stencil = match (
+            &self.depth_stencil_attachment,
+            &other.depth_stencil_attachment,
+        ) {
+            (None, None) => true,
+            (Some(a), Some(b)) => {
+                Arc::ptr_eq(&a.view, &b.view)
+                    && a.depth == b.depth
+                    && a.stencil == b.stencil
+                    && match (&a.resolve, &b.resolve) {
+                        (None, None) => true,
+                        (Some(a), Some(b)) => {
+                            Arc::ptr_eq(&a.target, &b.target)
+                                && a.depth_mode == b.depth_mode
+                                && a.resolve_stencil == b.resolve_stencil
+                        }
+                        _ => false,
+                    }
+            }
+            _ => false,
+        };
+        same_color
+            && same_depth_stencil
+            && same(
+                self.occlusion_query_set.as_ref(),
+                other.occlusion_query_set.as_ref(),
+            )
+            && self.multiview == other.multiview
+    }
+}
+
 /// Describes an individual channel within a render pass, such as color, depth, or stencil.
 #[repr(C)]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -115,7 +280,7 @@ impl<V: Copy + Default> PassChannel<Option<V>> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ResolvedPassChannel<V> {
     ReadOnly,
     Operational(wgt::Operations<V>),
@@ -202,10 +367,13 @@ pub struct RenderPassDepthStencilAttachment {
     pub depth: PassChannel<Option<f32>>,
     /// What operations will be performed on the stencil part of the attachment.
     pub stencil: PassChannel<Option<u32>>,
+    /// Single sampled view to resolve the attachment into at the end of the pass.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub resolve: Option<wgt::DepthStencilResolve<id::TextureViewId>>,
 }
 
 /// Describes a depth/stencil attachment to a render pass.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ArcRenderPassDepthStencilAttachment {
     /// The view to use as an attachment.
     pub view: Arc<TextureView>,
@@ -213,6 +381,8 @@ pub struct ArcRenderPassDepthStencilAttachment {
     pub depth: ResolvedPassChannel<f32>,
     /// What operations will be performed on the stencil part of the attachment.
     pub stencil: ResolvedPassChannel<u32>,
+    /// Single sampled view to resolve the attachment into at the end of the pass.
+    pub resolve: Option<wgt::DepthStencilResolve<Arc<TextureView>>>,
 }
 
 /// Describes the attachments of a render pass.
@@ -227,6 +397,13 @@ pub struct RenderPassDescriptor<'a> {
     pub timestamp_writes: Option<&'a PassTimestampWrites>,
     /// Defines where the occlusion query results will be stored for this pass.
     pub occlusion_query_set: Option<id::QuerySetId>,
+    /// Defines where the pipeline statistics of this pass will be written.
+    pub pipeline_statistics_query: Option<&'a PassPipelineStatisticsQuery>,
+    /// Whether the pass is suspended or resumes a suspended pass.
+    pub flags: wgt::RenderPassFlags,
+    /// The number of array layers of each attachment rendered at once, if this is a
+    /// multiview pass.
+    pub multiview: Option<NonZeroU32>,
 }
 
 /// Describes the attachments of a render pass.
@@ -241,6 +418,13 @@ struct ArcRenderPassDescriptor<'a> {
     pub timestamp_writes: Option<ArcPassTimestampWrites>,
     /// Defines where the occlusion query results will be stored for this pass.
     pub occlusion_query_set: Option<Arc<QuerySet>>,
+    /// Defines where the pipeline statistics of this pass will be written.
+    pub pipeline_statistics_query: Option<ArcPassPipelineStatisticsQuery>,
+    /// Whether the pass is suspended or resumes a suspended pass.
+    pub flags: wgt::RenderPassFlags,
+    /// The number of array layers of each attachment rendered at once, if this is a
+    /// multiview pass.
+    pub multiview: Option<NonZeroU32>,
 }
 
 pub type RenderBasePass = BasePass<ArcRenderCommand, RenderPassError>;
@@ -268,10 +452,19 @@ pub struct RenderPass {
     depth_stencil_attachment: Option<ArcRenderPassDepthStencilAttachment>,
     timestamp_writes: Option<ArcPassTimestampWrites>,
     occlusion_query_set: Option<Arc<QuerySet>>,
+    pipeline_statistics_query: Option<ArcPassPipelineStatisticsQuery>,
+    flags: wgt::RenderPassFlags,
+    multiview: Option<NonZeroU32>,
 
     // Resource binding dedupe state.
     current_bind_groups: BindGroupStateChange,
     current_pipeline: StateChange<id::RenderPipelineId>,
+
+    /// The parts of this pass recorded by parallel encoders, or the part this
+    /// pass records if it is a parallel encoder.
+    ///
+    /// See [`Global::render_pass_create_parallel_encoder`].
+    parallel: Option<Parallel>,
 }
 
 impl RenderPass {
@@ -283,6 +476,9 @@ impl RenderPass {
             color_attachments,
             depth_stencil_attachment,
             occlusion_query_set,
+            pipeline_statistics_query,
+            flags,
+            multiview,
         } = desc;
 
         Self {
@@ -292,9 +488,13 @@ impl RenderPass {
             depth_stencil_attachment,
             timestamp_writes,
             occlusion_query_set,
+            pipeline_statistics_query,
+            flags,
+            multiview,
 
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
+            parallel: None,
         }
     }
 
@@ -306,8 +506,33 @@ impl RenderPass {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgt::RenderPassFlags::empty(),
+            multiview: None,
+            current_bind_groups: BindGroupStateChange::new(),
+            current_pipeline: StateChange::new(),
+            parallel: None,
+        }
+    }
+
+    fn new_parallel_encoder(
+        parent: Option<Arc<CommandBuffer>>,
+        base: RenderBasePass,
+        encoder: Option<ParallelEncoder>,
+    ) -> Self {
+        Self {
+            base,
+            parent,
+            color_attachments: ArrayVec::new(),
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgt::RenderPassFlags::empty(),
+            multiview: None,
             current_bind_groups: BindGroupStateChange::new(),
             current_pipeline: StateChange::new(),
+            parallel: Some(Parallel::Encoder(encoder)),
         }
     }
 
@@ -315,6 +540,386 @@ impl RenderPass {
     pub fn label(&self) -> Option<&str> {
         self.base.label.as_deref()
     }
+
+    /// Creates a parallel encoder of this pass, beginning the hal render pass
+    /// if it is the first one.
+    fn create_parallel_encoder(
+        &mut self,
+        cmd_buf: &Arc<CommandBuffer>,
+    ) -> Result<ParallelEncoder, RenderPassErrorInner> {
+        let device = &cmd_buf.device;
+        device.check_is_valid()?;
+
+        let mut cmd_buf_data = cmd_buf.data.lock();
+        let cmd_buf_data = cmd_buf_data.locked_inner()?;
+        let snatch_guard = &device.snatchable_lock.read();
+
+        if self.parallel.is_none() {
+            let parallel = self.begin_parallel(device, cmd_buf_data, snatch_guard)?;
+            self.parallel = Some(Parallel::Pass(Box::new(parallel)));
+        }
+        let parallel = match self.parallel {
+            Some(Parallel::Pass(ref mut parallel)) => parallel,
+            Some(Parallel::Encoder(_)) => {
+                return Err(RenderPassErrorInner::NestedParallelEncoder);
+            }
+            None => unreachable!(),
+        };
+
+        // The commands the pass recorded since its last parallel encoder are a
+        // part of their own, executed before the new encoder's.
+        if !self.base.commands.is_empty() {
+            let encoder = parallel.add_part(device, &mut cmd_buf_data.encoder)?;
+            let label = self.base.label.clone().map(Cow::Owned);
+            let base = mem::replace(&mut self.base, BasePass::new(&label));
+            encoder.end(cmd_buf, base, snatch_guard);
+        }
+
+        Ok(parallel.add_part(device, &mut cmd_buf_data.encoder)?)
+    }
+
+    /// Begins the hal render pass of a pass whose commands are recorded into
+    /// parallel encoders.
+    fn begin_parallel(
+        &mut self,
+        device: &Arc<Device>,
+        cmd_buf_data: &mut CommandBufferMutable,
+        snatch_guard: &SnatchGuard,
+    ) -> Result<ParallelPass, RenderPassErrorInner> {
+        device.require_features(wgt::Features::PARALLEL_RENDER_ENCODING)?;
+        if !self.flags.is_empty() || self.pipeline_statistics_query.is_some() {
+            return Err(RenderPassErrorInner::UnsupportedParallelPass);
+        }
+        if cmd_buf_data.suspended_render_pass.is_some() {
+            return Err(EncoderStateError::Suspended.into());
+        }
+
+        let encoder = &mut cmd_buf_data.encoder;
+        encoder.close_if_open()?;
+        encoder.open_pass(self.base.label.as_deref())?;
+
+        let mut pending_discard_init_fixups = SurfacesInDiscardState::new();
+        let info = RenderPassInfo::start(
+            device,
+            hal_label(self.base.label.as_deref(), device.instance_flags),
+            self.color_attachments.take(),
+            self.depth_stencil_attachment.take(),
+            self.timestamp_writes.take(),
+            self.occlusion_query_set.clone(),
+            None,
+            self.flags,
+            self.multiview,
+            true,
+            encoder,
+            &mut cmd_buf_data.trackers,
+            &mut cmd_buf_data.texture_memory_actions,
+            &mut cmd_buf_data.pending_query_resets,
+            &mut pending_discard_init_fixups,
+            snatch_guard,
+        )?;
+
+        Ok(ParallelPass {
+            context: Arc::new(ParallelContext {
+                info: info.part_info(),
+                occlusion_query_set: self.occlusion_query_set.clone(),
+            }),
+            info,
+            pending_discard_init_fixups,
+            parts: Vec::new(),
+        })
+    }
+}
+
+/// How a render pass is split into parts recorded by parallel encoders.
+///
+/// Each part is validated and encoded into a hal command encoder of its own,
+/// on the thread that ends it, and the pass executes the parts in order when
+/// it ends.
+enum Parallel {
+    /// The pass has created parallel encoders.
+    Pass(Box<ParallelPass>),
+    /// The pass is a parallel encoder, or `None` if it could not be created.
+    /// Its commands are then discarded, and the error is reported by its pass.
+    Encoder(Option<ParallelEncoder>),
+}
+
+/// A render pass that has created parallel encoders.
+struct ParallelPass {
+    /// The hal render pass, begun when the first parallel encoder was created.
+    info: RenderPassInfo,
+    context: Arc<ParallelContext>,
+    pending_discard_init_fixups: SurfacesInDiscardState,
+    /// The parts of the pass, in the order they are executed in.
+    parts: Vec<Arc<PartSlot>>,
+}
+
+/// What the parts of a render pass need to encode their commands.
+struct ParallelContext {
+    /// The render pass, without its attachments.
+    info: RenderPassInfo,
+    occlusion_query_set: Option<Arc<QuerySet>>,
+}
+
+/// Where a part of a render pass is handed over to the pass when it is ended.
+type PartSlot = Mutex<Option<Result<EncodedPart, RenderPassError>>>;
+
+/// A parallel encoder, with the hal command encoder its part is encoded into.
+struct ParallelEncoder {
+    context: Arc<ParallelContext>,
+    raw: Box<dyn hal::DynCommandEncoder>,
+    part: Arc<PartSlot>,
+}
+
+/// A part of a render pass, encoded into a hal command encoder of its own.
+///
+/// Everything the part did to the command buffer is gathered here, to be added
+/// to the command buffer when the pass ends.
+struct EncodedPart {
+    raw: Box<dyn hal::DynCommandEncoder>,
+    tracker: Tracker,
+    buffer_memory_init_actions: Vec<BufferInitTrackerAction>,
+    texture_memory_actions: CommandBufferTextureMemoryActions,
+    as_actions: Vec<AsAction>,
+    buffers: BufferUsageScope,
+    textures: TextureUsageScope,
+    pending_query_resets: QueryResetMap,
+    indirect_draw_validation_resources: crate::indirect_validation::DrawResources,
+    indirect_draw_validation_batcher: crate::indirect_validation::DrawBatcher,
+    index_expansions: Vec<crate::index_expansion::Expansion>,
+}
+
+// All fields are `Send` and `Sync`, but proving it for `RenderPass` otherwise overflows
+// the recursion limit of crates using it.
+#[cfg(send_sync)]
+unsafe impl Send for EncodedPart {}
+#[cfg(send_sync)]
+unsafe impl Sync for EncodedPart {}
+
+impl ParallelPass {
+    /// Creates the hal command encoder of a new part of the pass.
+    fn add_part(
+        &mut self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+    ) -> Result<ParallelEncoder, DeviceError> {
+        let raw = unsafe { encoder.raw.create_parallel_render_encoder() }
+            .map_err(|e| device.handle_hal_error(e))?;
+        let part = Arc::new(Mutex::new(rank::PARALLEL_RENDER_ENCODER_PART, None));
+        self.parts.push(part.clone());
+        Ok(ParallelEncoder {
+            context: self.context.clone(),
+            raw,
+            part,
+        })
+    }
+
+    /// Executes the parts of the pass and ends it.
+    fn end(
+        self,
+        label: Option<String>,
+        device: &Arc<Device>,
+        cmd_buf_data: &mut CommandBufferMutable,
+        snatch_guard: &SnatchGuard,
+    ) -> Result<(), RenderPassError> {
+        let Self {
+            info,
+            context: _,
+            mut pending_discard_init_fixups,
+            parts,
+        } = self;
+
+        let mut scope = device.new_usage_scope();
+        let mut raws = Vec::with_capacity(parts.len());
+        let mut index_expansions = Vec::new();
+        let mut parts_indirect_draw_validation = Vec::new();
+        for part in parts {
+            let part = part
+                .lock()
+                .take()
+                .ok_or(RenderPassErrorInner::UnendedParallelEncoder)
+                .map_pass_err(PassErrorScope::Pass)??;
+
+            raws.push(part.raw);
+            cmd_buf_data.trackers.extend_stateless(part.tracker);
+            cmd_buf_data
+                .buffer_memory_init_actions
+                .extend(part.buffer_memory_init_actions);
+            pending_discard_init_fixups.extend(
+                cmd_buf_data
+                    .texture_memory_actions
+                    .register_deferred(part.texture_memory_actions),
+            );
+            cmd_buf_data.as_actions.extend(part.as_actions);
+            scope
+                .buffers
+                .merge_usage_scope(&part.buffers)
+                .map_pass_err(PassErrorScope::Pass)?;
+            scope
+                .textures
+                .merge_usage_scope(&part.textures)
+                .map_pass_err(PassErrorScope::Pass)?;
+            cmd_buf_data
+                .pending_query_resets
+                .merge(part.pending_query_resets)
+                .map_pass_err(PassErrorScope::Pass)?;
+            index_expansions.extend(part.index_expansions);
+            parts_indirect_draw_validation.push((
+                part.indirect_draw_validation_resources,
+                part.indirect_draw_validation_batcher,
+            ));
+        }
+
+        let raw = cmd_buf_data.encoder.raw.as_mut();
+        unsafe { raw.execute_parallel_render_encoders(raws) }
+            .map_err(|e| device.handle_hal_error(e))
+            .map_pass_err(PassErrorScope::Pass)?;
+        info.finish(device, raw, snatch_guard, &mut scope)
+            .map_pass_err(PassErrorScope::Pass)?;
+
+        let pre_pass = SuspendedPrePass {
+            label,
+            buffers: mem::take(&mut scope.buffers),
+            textures: mem::take(&mut scope.textures),
+            pending_discard_init_fixups,
+            index_expansions,
+            indirect_draw_validation_batcher: crate::indirect_validation::DrawBatcher::new(),
+            parts_indirect_draw_validation,
+        };
+        cmd_buf_data
+            .encoder
+            .close()
+            .map_pass_err(PassErrorScope::Pass)?;
+        pre_pass
+            .record(device, cmd_buf_data, snatch_guard)
+            .map_pass_err(PassErrorScope::Pass)
+    }
+}
+
+impl ParallelEncoder {
+    /// Validates and encodes the commands of the part, and hands it over to
+    /// the pass.
+    fn end(
+        self,
+        cmd_buf: &Arc<CommandBuffer>,
+        mut base: RenderBasePass,
+        snatch_guard: &SnatchGuard,
+    ) {
+        let Self { context, raw, part } = self;
+        let encoded = match base.error.take() {
+            Some(err) => Err(err),
+            None => encode_part(cmd_buf, &context, raw, &mut base, snatch_guard),
+        };
+        *part.lock() = Some(encoded);
+    }
+}
+
+/// Validates and encodes the commands of a part of a render pass into `raw`.
+///
+/// Each part starts like a render pass of its own, without a pipeline, bind
+/// groups or buffers, and with the default viewport, scissor rectangle, blend
+/// constant and stencil reference.
+fn encode_part(
+    cmd_buf: &Arc<CommandBuffer>,
+    context: &ParallelContext,
+    mut raw: Box<dyn hal::DynCommandEncoder>,
+    base: &mut RenderBasePass,
+    snatch_guard: &SnatchGuard,
+) -> Result<EncodedPart, RenderPassError> {
+    let device = &cmd_buf.device;
+
+    let mut tracker = Tracker::new();
+    let mut buffer_memory_init_actions = Vec::new();
+    let mut texture_memory_actions = CommandBufferTextureMemoryActions::new_deferred();
+    let mut as_actions = Vec::new();
+    let mut pending_query_resets = QueryResetMap::new();
+    let mut indirect_draw_validation_resources =
+        crate::indirect_validation::DrawResources::new(device.clone());
+    let mut indirect_draw_validation_batcher = crate::indirect_validation::DrawBatcher::new();
+
+    if device
+        .features
+        .contains(wgt::Features::VARIABLE_RATE_SHADING)
+    {
+        unsafe { raw.set_shading_rate(wgt::ShadingRate::OneByOne) };
+    }
+
+    let (buffers, textures, index_expansions) = {
+        let info = context.info.part_info();
+        let mut state = State {
+            pipeline_flags: PipelineFlags::empty(),
+            blend_constant: OptionalState::Unused,
+            stencil_reference: 0,
+            pipeline: None,
+            index: IndexState::default(),
+            vertex: VertexState::default(),
+            viewport_size: [info.extent.width as f32, info.extent.height as f32],
+            index_expansions: Vec::new(),
+
+            info,
+
+            general: pass::BaseState {
+                device,
+                raw_encoder: raw.as_mut(),
+                tracker: &mut tracker,
+                buffer_memory_init_actions: &mut buffer_memory_init_actions,
+                texture_memory_actions: &mut texture_memory_actions,
+                as_actions: &mut as_actions,
+                pending_discard_init_fixups: SurfacesInDiscardState::new(),
+                scope: device.new_usage_scope(),
+                binder: Binder::new(),
+
+                snatch_guard,
+
+                temp_offsets: Vec::new(),
+                dynamic_offset_count: 0,
+
+                debug_scope_depth: 0,
+                string_offset: 0,
+            },
+
+            active_occlusion_query: None,
+            active_pipeline_statistics_query: None,
+            pass_pipeline_statistics_query: None,
+            active_conditional_block: false,
+
+            push_constants: vec![
+                0;
+                (device.limits.max_push_constant_size / wgt::PUSH_CONSTANT_ALIGNMENT)
+                    as usize
+            ],
+        };
+
+        encode_commands(
+            &mut state,
+            base,
+            cmd_buf,
+            context.occlusion_query_set.as_ref(),
+            &mut pending_query_resets,
+            &mut indirect_draw_validation_resources,
+            &mut indirect_draw_validation_batcher,
+        )?;
+
+        let mut scope = state.general.scope;
+        (
+            mem::take(&mut scope.buffers),
+            mem::take(&mut scope.textures),
+            state.index_expansions,
+        )
+    };
+
+    Ok(EncodedPart {
+        raw,
+        tracker,
+        buffer_memory_init_actions,
+        texture_memory_actions,
+        as_actions,
+        buffers,
+        textures,
+        pending_query_resets,
+        indirect_draw_validation_resources,
+        indirect_draw_validation_batcher,
+        index_expansions,
+    })
 }
 
 impl fmt::Debug for RenderPass {
@@ -352,12 +957,15 @@ impl OptionalState {
 struct IndexState {
     buffer_format: Option<IndexFormat>,
     limit: u64,
+    /// The bound 8 bit index buffer, when they are emulated.
+    emulated: Option<EmulatedIndexBuffer>,
 }
 
 impl IndexState {
     fn update_buffer(&mut self, range: Range<BufferAddress>, format: IndexFormat) {
         self.buffer_format = Some(format);
         let shift = match format {
+            IndexFormat::Uint8 => 0,
             IndexFormat::Uint16 => 1,
             IndexFormat::Uint32 => 2,
         };
@@ -367,9 +975,24 @@ impl IndexState {
     fn reset(&mut self) {
         self.buffer_format = None;
         self.limit = 0;
+        self.emulated = None;
     }
 }
 
+/// An 8 bit index buffer, which is drawn with its expansions to 16 bit indices.
+///
+/// See [`crate::index_expansion`] for how the indices are widened.
+#[derive(Debug)]
+struct EmulatedIndexBuffer {
+    buffer: Arc<crate::resource::Buffer>,
+    range: Range<BufferAddress>,
+    /// Indices into `State::index_expansions` of the expansions for pipelines that
+    /// don't and do restart strips.
+    expansions: [Option<usize>; 2],
+    /// Whether the expansion bound to the encoder restarts strips.
+    bound: Option<bool>,
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct VertexLimits {
     /// Length of the shortest vertex rate vertex buffer
@@ -380,6 +1003,19 @@ pub(crate) struct VertexLimits {
     pub(crate) instance_limit: u64,
     /// Buffer slot which the shortest instance rate vertex buffer is bound to
     instance_limit_slot: u32,
+    /// Instance rate vertex buffers that step every few instances, which can't be
+    /// folded into `instance_limit`
+    stepped_instance_limits: ArrayVec<SteppedInstanceLimit, { hal::MAX_VERTEX_BUFFERS }>,
+}
+
+#[derive(Debug)]
+struct SteppedInstanceLimit {
+    /// Number of elements in the vertex buffer
+    limit: u64,
+    /// Number of instances that use each element
+    rate: u32,
+    /// Buffer slot which the vertex buffer is bound to
+    slot: u32,
 }
 
 impl VertexLimits {
@@ -397,6 +1033,7 @@ impl VertexLimits {
         let mut vertex_limit_slot = 0;
         let mut instance_limit = u64::MAX;
         let mut instance_limit_slot = 0;
+        let mut stepped_instance_limits = ArrayVec::new();
 
         for (idx, (buffer_size, step)) in buffer_sizes.zip(pipeline_steps).enumerate() {
             let Some(buffer_size) = buffer_size else {
@@ -426,12 +1063,19 @@ impl VertexLimits {
                         vertex_limit_slot = idx as _;
                     }
                 }
-                VertexStepMode::Instance => {
+                VertexStepMode::Instance | VertexStepMode::InstanceStepRate { rate: 1 } => {
                     if limit < instance_limit {
                         instance_limit = limit;
                         instance_limit_slot = idx as _;
                     }
                 }
+                VertexStepMode::InstanceStepRate { rate } => {
+                    stepped_instance_limits.push(SteppedInstanceLimit {
+                        limit,
+                        rate,
+                        slot: idx as _,
+                    });
+                }
             }
         }
 
@@ -440,9 +1084,21 @@ impl VertexLimits {
             vertex_limit_slot,
             instance_limit,
             instance_limit_slot,
+            stepped_instance_limits,
         }
     }
 
+    /// Returns an instance limit that holds for every instance rate vertex buffer.
+    ///
+    /// Indirect draw validation doesn't know about step rates, so buffers that step
+    /// every few instances are assumed to be read once per instance.
+    pub(crate) fn indirect_instance_limit(&self) -> u64 {
+        self.stepped_instance_limits
+            .iter()
+            .map(|stepped| stepped.limit)
+            .fold(self.instance_limit, u64::min)
+    }
+
     pub(crate) fn validate_vertex_limit(
         &self,
         first_vertex: u32,
@@ -476,6 +1132,18 @@ impl VertexLimits {
             });
         }
 
+        for stepped in self.stepped_instance_limits.iter() {
+            let last_instance =
+                first_instance as u64 + (instance_count as u64).div_ceil(stepped.rate as u64);
+            if last_instance > stepped.limit {
+                return Err(DrawError::InstanceBeyondLimit {
+                    last_instance,
+                    instance_limit: stepped.limit,
+                    slot: stepped.slot,
+                });
+            }
+        }
+
         Ok(())
     }
 }
@@ -483,6 +1151,9 @@ impl VertexLimits {
 #[derive(Debug, Default)]
 struct VertexState {
     buffer_sizes: [Option<BufferAddress>; hal::MAX_VERTEX_BUFFERS],
+    /// The buffer and offset bound to each slot, which are rebound for each segment
+    /// when wide lines are emulated.
+    buffers: [Option<(Arc<crate::resource::Buffer>, BufferAddress)>; hal::MAX_VERTEX_BUFFERS],
     limits: VertexLimits,
 }
 
@@ -492,6 +1163,24 @@ impl VertexState {
     }
 }
 
+/// The kind of draw command whose state is checked by [`State::is_ready`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DrawCommandFamily {
+    Draw,
+    DrawIndexed,
+    DrawMeshTasks,
+}
+
+impl DrawCommandFamily {
+    fn vertex(indexed: bool) -> Self {
+        if indexed {
+            Self::DrawIndexed
+        } else {
+            Self::Draw
+        }
+    }
+}
+
 struct State<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder> {
     pipeline_flags: PipelineFlags,
     blend_constant: OptionalState,
@@ -499,6 +1188,10 @@ struct State<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder> {
     pipeline: Option<Arc<RenderPipeline>>,
     index: IndexState,
     vertex: VertexState,
+    /// The size of the first viewport, across which emulated wide lines are measured.
+    viewport_size: [f32; 2],
+    /// The 8 bit index buffers to widen before the pass.
+    index_expansions: Vec<crate::index_expansion::Expansion>,
 
     info: RenderPassInfo,
 
@@ -506,13 +1199,37 @@ struct State<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder> {
 
     active_occlusion_query: Option<(Arc<QuerySet>, u32)>,
     active_pipeline_statistics_query: Option<(Arc<QuerySet>, u32)>,
+
+    /// The index of the pipeline statistics query recording the whole pass, if any.
+    pass_pipeline_statistics_query: Option<u32>,
+
+    /// Whether a conditional block is active.
+    active_conditional_block: bool,
+
+    /// The push constant values set on the pass, in words, for render bundles
+    /// that inherit them.
+    push_constants: Vec<u32>,
 }
 
 impl<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
     State<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
 {
-    fn is_ready(&self, indexed: bool) -> Result<(), DrawError> {
+    fn is_ready(&self, family: DrawCommandFamily) -> Result<(), DrawError> {
         if let Some(pipeline) = self.pipeline.as_ref() {
+            match (family, pipeline.is_mesh) {
+                (DrawCommandFamily::DrawMeshTasks, false) => {
+                    return Err(DrawError::ExpectedMeshPipeline {
+                        pipeline: pipeline.error_ident(),
+                    });
+                }
+                (DrawCommandFamily::Draw | DrawCommandFamily::DrawIndexed, true) => {
+                    return Err(DrawError::UnexpectedMeshPipeline {
+                        pipeline: pipeline.error_ident(),
+                    });
+                }
+                _ => {}
+            }
+
             self.general.binder.check_compatibility(pipeline.as_ref())?;
             self.general.binder.check_late_buffer_bindings()?;
 
@@ -535,7 +1252,9 @@ impl<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
                 });
             }
 
-            if indexed {
+            if family == DrawCommandFamily::DrawIndexed {
+                pipeline.check_strip_index_format()?;
+
                 // Pipeline expects an index buffer
                 if let Some(pipeline_index_format) = pipeline.strip_index_format {
                     // We have a buffer bound
@@ -560,6 +1279,19 @@ impl<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
         }
     }
 
+    /// Checks that the pipeline doesn't emulate wide lines, which only non-indexed direct
+    /// draws support.
+    fn check_native_lines(&self) -> Result<(), DrawError> {
+        match self.pipeline {
+            Some(ref pipeline) if pipeline.wide_lines.is_some() => {
+                Err(DrawError::EmulatedWideLines {
+                    pipeline: pipeline.error_ident(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Reset the `RenderBundle`-related states.
     fn reset_bundle(&mut self) {
         self.general.binder.reset();
@@ -576,6 +1308,7 @@ impl<'scope, 'snatch_guard, 'cmd_buf, 'raw_encoder>
 pub enum AttachmentErrorLocation {
     Color { index: usize, resolve: bool },
     Depth,
+    DepthResolve,
 }
 
 impl fmt::Display for AttachmentErrorLocation {
@@ -593,6 +1326,9 @@ impl fmt::Display for AttachmentErrorLocation {
                 "color attachment at index {index}'s resolve texture view"
             ),
             AttachmentErrorLocation::Depth => write!(f, "depth attachment's texture view"),
+            AttachmentErrorLocation::DepthResolve => {
+                write!(f, "depth attachment's resolve texture view")
+            }
         }
     }
 }
@@ -614,6 +1350,8 @@ pub enum ColorAttachmentError {
     MissingDepthSlice,
     #[error("Depth slice was provided but the color attachment's view is not 3D")]
     UnneededDepthSlice,
+    #[error("Resolve target {0} can't be a transient texture")]
+    TransientResolveTarget(ResourceErrorIdent),
     #[error(
         "{view}'s subresource at mip {mip_level} and depth/array layer {depth_or_array_layer} is already attached to this render pass"
     )]
@@ -647,6 +1385,16 @@ pub enum AttachmentError {
     NoClearValue,
     #[error("Clear value ({0}) must be between 0.0 and 1.0, inclusive")]
     ClearValueOutOfRange(f32),
+    #[error("Attachment {0} is a transient texture, so its LoadOp must be `Clear`")]
+    TransientWithLoad(ResourceErrorIdent),
+    #[error("Attachment {0} is a transient texture, so its StoreOp must be `Discard`")]
+    TransientWithStore(ResourceErrorIdent),
+    #[error("Depth/stencil resolve target {0} can't be a transient texture")]
+    TransientDepthStencilResolveTarget(ResourceErrorIdent),
+    #[error("Depth/stencil resolve must resolve at least one of the depth and stencil aspects")]
+    EmptyDepthStencilResolve,
+    #[error("Depth/stencil resolve has a depth mode, but {0:?} has no depth aspect")]
+    MissingDepthResolveAspect(wgt::TextureFormat),
 }
 
 impl WebGpuError for AttachmentError {
@@ -668,6 +1416,8 @@ pub enum RenderPassErrorInner {
     EncoderState(#[from] EncoderStateError),
     #[error("Parent encoder is invalid")]
     InvalidParentEncoder,
+    #[error("Render passes can't be encoded for a {0:?} queue")]
+    UnsupportedQueueType(wgt::QueueType),
     #[error("The format of the {location} ({format:?}) is not resolvable")]
     UnsupportedResolveTargetFormat {
         location: AttachmentErrorLocation,
@@ -778,22 +1528,72 @@ pub enum RenderPassErrorInner {
     PushConstantOutOfMemory,
     #[error(transparent)]
     QueryUse(#[from] QueryUseError),
-    #[error("Multiview layer count must match")]
-    MultiViewMismatch,
+    #[error(
+        "The {location} has {layers} array layers, but the render pass has a view count of {views}"
+    )]
+    MultiViewMismatch {
+        location: AttachmentErrorLocation,
+        layers: u32,
+        views: u32,
+    },
     #[error(
         "Multiview pass texture views with more than one array layer must have D2Array dimension"
     )]
     MultiViewDimensionMismatch,
+    #[error(
+        "The {location} has {layers} array layers, but the first attachment of the render pass has {expected}"
+    )]
+    AttachmentLayerCountMismatch {
+        location: AttachmentErrorLocation,
+        layers: u32,
+        expected: u32,
+    },
     #[error("missing occlusion query set")]
     MissingOcclusionQuerySet,
     #[error(transparent)]
     DestroyedResource(#[from] DestroyedResourceError),
+    #[error(transparent)]
+    AliasedTextureConflict(#[from] AliasedTextureConflictError),
     #[error("The compute pass has already been ended and no further commands can be recorded")]
     PassEnded,
     #[error(transparent)]
     InvalidResource(#[from] InvalidResourceError),
     #[error(transparent)]
     TimestampWrites(#[from] TimestampWritesError),
+    #[error("A suspending render pass can't write a timestamp at its end")]
+    SuspendingPassEndTimestamp,
+    #[error("A resuming render pass can't write a timestamp at its beginning")]
+    ResumingPassBeginningTimestamp,
+    #[error("A suspending or resuming render pass can't have a pipeline statistics query")]
+    SuspendResumePipelineStatisticsQuery,
+    #[error("A suspending or resuming render pass can't render to the transient {0}")]
+    SuspendResumeTransientAttachment(ResourceErrorIdent),
+    #[error("There is no suspended render pass to resume")]
+    NoSuspendedRenderPass,
+    #[error(
+        "The render pass resumes a suspended render pass with different attachments or operations"
+    )]
+    MismatchedResumedAttachments,
+    #[error("A conditional block was begun while another conditional block was active")]
+    ConditionalBlockAlreadyActive,
+    #[error("There is no active conditional block to end")]
+    NoActiveConditionalBlock,
+    #[error("A conditional block was not ended before the render pass")]
+    UnendedConditionalBlock,
+    #[error("Conditional block predicate offset {0:?} is not a multiple of 8")]
+    UnalignedConditionalBlockOffset(BufferAddress),
+    #[error(
+        "Conditional block predicate at offset {offset} overruns predicate buffer of size {buffer_size}"
+    )]
+    ConditionalBlockBufferOverrun { offset: u64, buffer_size: u64 },
+    #[error("A parallel encoder of the render pass was not ended before the pass")]
+    UnendedParallelEncoder,
+    #[error("A parallel encoder can't create parallel encoders of its own")]
+    NestedParallelEncoder,
+    #[error(
+        "A render pass that is suspended, resumes a suspended pass or has a pipeline statistics query can't create parallel encoders"
+    )]
+    UnsupportedParallelPass,
 }
 
 impl From<MissingBufferUsageError> for RenderPassErrorInner {
@@ -835,6 +1635,13 @@ pub struct RenderPassError {
     pub(super) inner: RenderPassErrorInner,
 }
 
+impl RenderPassError {
+    /// The cause of the error.
+    pub fn inner(&self) -> &RenderPassErrorInner {
+        &self.inner
+    }
+}
+
 impl<E: Into<RenderPassErrorInner>> MapPassErr<RenderPassError> for E {
     fn map_pass_err(self, scope: PassErrorScope) -> RenderPassError {
         RenderPassError {
@@ -858,6 +1665,7 @@ impl WebGpuError for RenderPassError {
             RenderPassErrorInner::Bind(e) => e,
             RenderPassErrorInner::QueryUse(e) => e,
             RenderPassErrorInner::DestroyedResource(e) => e,
+            RenderPassErrorInner::AliasedTextureConflict(e) => e,
             RenderPassErrorInner::InvalidResource(e) => e,
             RenderPassErrorInner::IncompatibleBundleTargets(e) => e,
             RenderPassErrorInner::InvalidAttachment(e) => e,
@@ -866,6 +1674,7 @@ impl WebGpuError for RenderPassError {
             RenderPassErrorInner::InvalidPopDebugGroup(e) => e,
 
             RenderPassErrorInner::InvalidParentEncoder
+            | RenderPassErrorInner::UnsupportedQueueType(_)
             | RenderPassErrorInner::UnsupportedResolveTargetFormat { .. }
             | RenderPassErrorInner::MissingAttachments
             | RenderPassErrorInner::TextureViewIsNotRenderable { .. }
@@ -883,9 +1692,24 @@ impl WebGpuError for RenderPassError {
             | RenderPassErrorInner::PushConstantOffsetAlignment
             | RenderPassErrorInner::PushConstantSizeAlignment
             | RenderPassErrorInner::PushConstantOutOfMemory
-            | RenderPassErrorInner::MultiViewMismatch
+            | RenderPassErrorInner::MultiViewMismatch { .. }
             | RenderPassErrorInner::MultiViewDimensionMismatch
+            | RenderPassErrorInner::AttachmentLayerCountMismatch { .. }
             | RenderPassErrorInner::MissingOcclusionQuerySet
+            | RenderPassErrorInner::SuspendingPassEndTimestamp
+            | RenderPassErrorInner::ResumingPassBeginningTimestamp
+            | RenderPassErrorInner::SuspendResumePipelineStatisticsQuery
+            | RenderPassErrorInner::SuspendResumeTransientAttachment(_)
+            | RenderPassErrorInner::NoSuspendedRenderPass
+            | RenderPassErrorInner::MismatchedResumedAttachments
+            | RenderPassErrorInner::ConditionalBlockAlreadyActive
+            | RenderPassErrorInner::NoActiveConditionalBlock
+            | RenderPassErrorInner::UnendedConditionalBlock
+            | RenderPassErrorInner::UnalignedConditionalBlockOffset(..)
+            | RenderPassErrorInner::ConditionalBlockBufferOverrun { .. }
+            | RenderPassErrorInner::UnendedParallelEncoder
+            | RenderPassErrorInner::NestedParallelEncoder
+            | RenderPassErrorInner::UnsupportedParallelPass
             | RenderPassErrorInner::PassEnded => return ErrorType::Validation,
         };
         e.webgpu_error_type()
@@ -908,7 +1732,7 @@ impl TextureView {
     }
 }
 
-const MAX_TOTAL_ATTACHMENTS: usize = hal::MAX_COLOR_ATTACHMENTS + hal::MAX_COLOR_ATTACHMENTS + 1;
+const MAX_TOTAL_ATTACHMENTS: usize = hal::MAX_COLOR_ATTACHMENTS + hal::MAX_COLOR_ATTACHMENTS + 2;
 type AttachmentDataVec<T> = ArrayVec<T, MAX_TOTAL_ATTACHMENTS>;
 
 struct RenderPassInfo {
@@ -969,6 +1793,10 @@ impl RenderPassInfo {
         mut depth_stencil_attachment: Option<ArcRenderPassDepthStencilAttachment>,
         mut timestamp_writes: Option<ArcPassTimestampWrites>,
         mut occlusion_query_set: Option<Arc<QuerySet>>,
+        mut pipeline_statistics_query: Option<ArcPassPipelineStatisticsQuery>,
+        flags: wgt::RenderPassFlags,
+        multiview: Option<NonZeroU32>,
+        parallel: bool,
         encoder: &mut CommandEncoder,
         trackers: &mut Tracker,
         texture_memory_actions: &mut CommandBufferTextureMemoryActions,
@@ -995,37 +1823,43 @@ impl RenderPassInfo {
         let mut extent = None;
         let mut sample_count = 0;
 
-        let mut detected_multiview: Option<Option<NonZeroU32>> = None;
-
-        let mut check_multiview = |view: &TextureView| {
-            // Get the multiview configuration for this texture view
+        // Every attachment must have one array layer per view. Without multiview,
+        // layered rendering takes its layer count from the first attachment.
+        let views = match multiview {
+            Some(views) => views.get(),
+            None => color_attachments
+                .iter()
+                .flatten()
+                .map(|at| &at.view)
+                .chain(depth_stencil_attachment.as_ref().map(|at| &at.view))
+                .next()
+                .map_or(1, |view| {
+                    view.selector.layers.end - view.selector.layers.start
+                }),
+        };
+        let check_multiview = |view: &TextureView, location| {
             let layers = view.selector.layers.end - view.selector.layers.start;
-            let this_multiview = if layers >= 2 {
-                // Trivially proven by the if above
-                Some(unsafe { NonZeroU32::new_unchecked(layers) })
-            } else {
-                None
-            };
+            if layers != views {
+                return Err(if multiview.is_some() {
+                    RenderPassErrorInner::MultiViewMismatch {
+                        location,
+                        layers,
+                        views,
+                    }
+                } else {
+                    RenderPassErrorInner::AttachmentLayerCountMismatch {
+                        location,
+                        layers,
+                        expected: views,
+                    }
+                });
+            }
 
             // Make sure that if this view is a multiview, it is set to be an array
-            if this_multiview.is_some() && view.desc.dimension != TextureViewDimension::D2Array {
+            if multiview.is_some() && view.desc.dimension != TextureViewDimension::D2Array {
                 return Err(RenderPassErrorInner::MultiViewDimensionMismatch);
             }
 
-            // Validate matching first, or store the first one
-            if let Some(multiview) = detected_multiview {
-                if multiview != this_multiview {
-                    return Err(RenderPassErrorInner::MultiViewMismatch);
-                }
-            } else {
-                // Multiview is only supported if the feature is enabled
-                if this_multiview.is_some() {
-                    device.require_features(wgt::Features::MULTIVIEW)?;
-                }
-
-                detected_multiview = Some(this_multiview);
-            }
-
             Ok(())
         };
         let mut add_view = |view: &TextureView, location| {
@@ -1062,26 +1896,37 @@ impl RenderPassInfo {
 
         if let Some(at) = depth_stencil_attachment.as_ref() {
             let view = &at.view;
-            check_multiview(view)?;
+            check_multiview(view, AttachmentErrorLocation::Depth)?;
             add_view(view, AttachmentErrorLocation::Depth)?;
 
             let ds_aspects = view.desc.aspects();
 
+            if ds_aspects.contains(hal::FormatAspects::DEPTH) {
+                check_transient_ops(view, at.depth.load_op(), at.depth.store_op())?;
+            }
+            if ds_aspects.contains(hal::FormatAspects::STENCIL) {
+                check_transient_ops(view, at.stencil.load_op(), at.stencil.store_op())?;
+            }
+
+            let (depth_load_op, depth_store_op) =
+                effective_ops(flags, at.depth.load_op(), at.depth.store_op());
+            let (stencil_load_op, stencil_store_op) =
+                effective_ops(flags, at.stencil.load_op(), at.stencil.store_op());
+
             if !ds_aspects.contains(hal::FormatAspects::STENCIL)
-                || (at.stencil.load_op().eq_variant(at.depth.load_op())
-                    && at.stencil.store_op() == at.depth.store_op())
+                || (stencil_load_op.eq_variant(depth_load_op) && stencil_store_op == depth_store_op)
             {
                 Self::add_pass_texture_init_actions(
-                    at.depth.load_op(),
-                    at.depth.store_op(),
+                    depth_load_op,
+                    depth_store_op,
                     texture_memory_actions,
                     view,
                     pending_discard_init_fixups,
                 );
             } else if !ds_aspects.contains(hal::FormatAspects::DEPTH) {
                 Self::add_pass_texture_init_actions(
-                    at.stencil.load_op(),
-                    at.stencil.store_op(),
+                    stencil_load_op,
+                    stencil_store_op,
                     texture_memory_actions,
                     view,
                     pending_discard_init_fixups,
@@ -1109,7 +1954,7 @@ impl RenderPassInfo {
                 // NeedsInitializedMemory should know that it doesn't need to
                 // clear the aspect that was set to C)
                 let need_init_beforehand =
-                    at.depth.load_op() == LoadOp::Load || at.stencil.load_op() == LoadOp::Load;
+                    depth_load_op == LoadOp::Load || stencil_load_op == LoadOp::Load;
                 if need_init_beforehand {
                     pending_discard_init_fixups.extend(
                         texture_memory_actions.register_init_action(&TextureInitTrackerAction {
@@ -1128,7 +1973,7 @@ impl RenderPassInfo {
                 // it isn't already set to NeedsInitializedMemory).
                 //
                 // (possible optimization: Delay and potentially drop this zeroing)
-                if at.depth.store_op() != at.stencil.store_op() {
+                if depth_store_op != stencil_store_op {
                     if !need_init_beforehand {
                         texture_memory_actions.register_implicit_init(
                             &view.parent,
@@ -1136,14 +1981,14 @@ impl RenderPassInfo {
                         );
                     }
                     divergent_discarded_depth_stencil_aspect = Some((
-                        if at.depth.store_op() == StoreOp::Discard {
+                        if depth_store_op == StoreOp::Discard {
                             wgt::TextureAspect::DepthOnly
                         } else {
                             wgt::TextureAspect::StencilOnly
                         },
                         view.clone(),
                     ));
-                } else if at.depth.store_op() == StoreOp::Discard {
+                } else if depth_store_op == StoreOp::Discard {
                     // Both are discarded using the regular path.
                     discarded_surfaces.push(TextureSurfaceDiscard {
                         texture: view.parent.clone(),
@@ -1169,31 +2014,128 @@ impl RenderPassInfo {
             };
             render_attachments.push(view.to_render_attachment(usage));
 
-            depth_stencil = Some(hal::DepthStencilAttachment {
-                target: hal::Attachment {
-                    view: view.try_raw(snatch_guard)?,
-                    usage,
-                },
-                depth_ops: at.depth.hal_ops(),
-                stencil_ops: at.stencil.hal_ops(),
-                clear_value: (at.depth.clear_value(), at.stencil.clear_value()),
-            });
-        }
+            let mut hal_resolve = None;
+            if let Some(resolve) = &at.resolve {
+                let resolve_view = &resolve.target;
+                resolve_view.same_device(device)?;
+                check_multiview(resolve_view, AttachmentErrorLocation::DepthResolve)?;
 
-        let mut attachment_set = crate::FastHashSet::default();
+                if resolve_view
+                    .parent
+                    .desc
+                    .usage
+                    .contains(TextureUsages::TRANSIENT)
+                {
+                    return Err(AttachmentError::TransientDepthStencilResolveTarget(
+                        resolve_view.error_ident(),
+                    )
+                    .into());
+                }
+                // Stencil is only resolved if the format has it, so that
+                // `DepthStencilResolve::new` works for depth-only formats.
+                let resolve_stencil =
+                    resolve.resolve_stencil && ds_aspects.contains(hal::FormatAspects::STENCIL);
+                if resolve.depth_mode.is_some() && !ds_aspects.contains(hal::FormatAspects::DEPTH) {
+                    return Err(AttachmentError::MissingDepthResolveAspect(view.desc.format).into());
+                }
+                if resolve.depth_mode.is_none() && !resolve_stencil {
+                    return Err(AttachmentError::EmptyDepthStencilResolve.into());
+                }
 
-        let mut color_attachments_hal =
-            ArrayVec::<Option<hal::ColorAttachment<_>>, { hal::MAX_COLOR_ATTACHMENTS }>::new();
-        for (index, attachment) in color_attachments.iter().enumerate() {
-            let at = if let Some(attachment) = attachment.as_ref() {
-                attachment
-            } else {
-                color_attachments_hal.push(None);
+                let resolve_location = AttachmentErrorLocation::DepthResolve;
+                let render_extent = resolve_view.render_extent.map_err(|reason| {
+                    RenderPassErrorInner::TextureViewIsNotRenderable {
+                        location: resolve_location,
+                        reason,
+                    }
+                })?;
+                if view.render_extent.unwrap() != render_extent {
+                    return Err(RenderPassErrorInner::AttachmentsDimensionMismatch {
+                        expected_location: AttachmentErrorLocation::Depth,
+                        expected_extent: view.render_extent.unwrap(),
+                        actual_location: resolve_location,
+                        actual_extent: render_extent,
+                    });
+                }
+                if view.samples == 1 || resolve_view.samples != 1 {
+                    return Err(RenderPassErrorInner::InvalidResolveSampleCounts {
+                        location: resolve_location,
+                        src: view.samples,
+                        dst: resolve_view.samples,
+                    });
+                }
+                if view.desc.format != resolve_view.desc.format {
+                    return Err(RenderPassErrorInner::MismatchedResolveTextureFormat {
+                        location: resolve_location,
+                        src: view.desc.format,
+                        dst: resolve_view.desc.format,
+                    });
+                }
+
+                // Aspects that aren't resolved keep their contents, so the
+                // target only counts as initialized if both are written.
+                let resolves_all_aspects = (resolve.depth_mode.is_some()
+                    || !ds_aspects.contains(hal::FormatAspects::DEPTH))
+                    && (resolve_stencil || !ds_aspects.contains(hal::FormatAspects::STENCIL));
+                if resolves_all_aspects {
+                    texture_memory_actions.register_implicit_init(
+                        &resolve_view.parent,
+                        TextureInitRange::from(resolve_view.selector.clone()),
+                    );
+                } else {
+                    pending_discard_init_fixups.extend(
+                        texture_memory_actions.register_init_action(&TextureInitTrackerAction {
+                            texture: resolve_view.parent.clone(),
+                            range: TextureInitRange::from(resolve_view.selector.clone()),
+                            kind: MemoryInitKind::NeedsInitializedMemory,
+                        }),
+                    );
+                }
+                render_attachments
+                    .push(resolve_view.to_render_attachment(wgt::TextureUses::DEPTH_STENCIL_WRITE));
+
+                hal_resolve = Some(hal::DepthStencilResolve {
+                    target: hal::Attachment {
+                        view: resolve_view.try_raw(snatch_guard)?,
+                        usage: wgt::TextureUses::DEPTH_STENCIL_WRITE,
+                    },
+                    depth_mode: resolve.depth_mode,
+                    resolve_stencil,
+                });
+            }
+
+            depth_stencil = Some(hal::DepthStencilAttachment {
+                target: hal::Attachment {
+                    view: view.try_raw(snatch_guard)?,
+                    usage,
+                },
+                depth_ops: at.depth.hal_ops(),
+                stencil_ops: at.stencil.hal_ops(),
+                clear_value: (at.depth.clear_value(), at.stencil.clear_value()),
+                resolve: hal_resolve,
+            });
+        }
+
+        let mut attachment_set = crate::FastHashSet::default();
+
+        let mut color_attachments_hal =
+            ArrayVec::<Option<hal::ColorAttachment<_>>, { hal::MAX_COLOR_ATTACHMENTS }>::new();
+        for (index, attachment) in color_attachments.iter().enumerate() {
+            let at = if let Some(attachment) = attachment.as_ref() {
+                attachment
+            } else {
+                color_attachments_hal.push(None);
                 continue;
             };
             let color_view: &TextureView = &at.view;
             color_view.same_device(device)?;
-            check_multiview(color_view)?;
+            check_multiview(
+                color_view,
+                AttachmentErrorLocation::Color {
+                    index,
+                    resolve: false,
+                },
+            )?;
             add_view(
                 color_view,
                 AttachmentErrorLocation::Color {
@@ -1276,10 +2218,12 @@ impl RenderPassInfo {
             }
 
             check_attachment_overlap(&mut attachment_set, color_view, at.depth_slice)?;
+            check_transient_ops(color_view, at.load_op, at.store_op)?;
 
+            let (load_op, store_op) = effective_ops(flags, at.load_op, at.store_op);
             Self::add_pass_texture_init_actions(
-                at.load_op,
-                at.store_op,
+                load_op,
+                store_op,
                 texture_memory_actions,
                 color_view,
                 pending_discard_init_fixups,
@@ -1290,9 +2234,25 @@ impl RenderPassInfo {
             let mut hal_resolve_target = None;
             if let Some(resolve_view) = &at.resolve_target {
                 resolve_view.same_device(device)?;
-                check_multiview(resolve_view)?;
+                check_multiview(
+                    resolve_view,
+                    AttachmentErrorLocation::Color {
+                        index,
+                        resolve: true,
+                    },
+                )?;
 
                 check_attachment_overlap(&mut attachment_set, resolve_view, None)?;
+                if resolve_view
+                    .parent
+                    .desc
+                    .usage
+                    .contains(TextureUsages::TRANSIENT)
+                {
+                    return Err(RenderPassErrorInner::ColorAttachment(
+                        ColorAttachmentError::TransientResolveTarget(resolve_view.error_ident()),
+                    ));
+                }
 
                 let resolve_location = AttachmentErrorLocation::Color {
                     index,
@@ -1363,8 +2323,10 @@ impl RenderPassInfo {
             }));
         }
 
-        let extent = extent.ok_or(RenderPassErrorInner::MissingAttachments)?;
-        let multiview = detected_multiview.expect("Multiview was not detected, no attachments");
+        let mut extent = extent.ok_or(RenderPassErrorInner::MissingAttachments)?;
+        if multiview.is_none() {
+            extent.depth_or_array_layers = views;
+        }
 
         let attachment_formats = AttachmentData {
             colors: color_attachments
@@ -1419,6 +2381,20 @@ impl RenderPassInfo {
             None
         };
 
+        let pipeline_statistics_query_hal = if let Some(psq) = pipeline_statistics_query.as_ref() {
+            let query_set = &psq.query_set;
+            query_set.same_device(device)?;
+
+            pending_query_resets.use_query_set(query_set, psq.query_index);
+
+            Some(hal::PassPipelineStatisticsQuery {
+                query_set: query_set.raw(),
+                query_index: psq.query_index,
+            })
+        } else {
+            None
+        };
+
         let hal_desc = hal::RenderPassDescriptor {
             label: hal_label,
             extent,
@@ -1428,12 +2404,25 @@ impl RenderPassInfo {
             multiview,
             timestamp_writes: timestamp_writes_hal,
             occlusion_query_set: occlusion_query_set_hal,
+            pipeline_statistics_query: pipeline_statistics_query_hal,
+            flags,
+            parallel,
         };
         unsafe {
             encoder
                 .raw
                 .begin_render_pass(&hal_desc)
                 .map_err(|e| device.handle_hal_error(e))?;
+            // The shading rate isn't part of the pass, so it would otherwise carry over
+            // from the previous pass on the same command buffer. The parts of a
+            // parallel pass set it themselves.
+            if !parallel
+                && device
+                    .features
+                    .contains(wgt::Features::VARIABLE_RATE_SHADING)
+            {
+                encoder.raw.set_shading_rate(wgt::ShadingRate::OneByOne);
+            }
         };
         drop(color_attachments_hal); // Drop, so we can consume `color_attachments` for the tracker.
 
@@ -1444,8 +2433,14 @@ impl RenderPassInfo {
         if let Some(occlusion_query_set) = occlusion_query_set.take() {
             trackers.query_sets.insert_single(occlusion_query_set);
         };
+        if let Some(psq) = pipeline_statistics_query.take() {
+            trackers.query_sets.insert_single(psq.query_set);
+        };
         if let Some(at) = depth_stencil_attachment.take() {
             trackers.views.insert_single(at.view.clone());
+            if let Some(resolve) = at.resolve {
+                trackers.views.insert_single(resolve.target);
+            }
         }
         for at in color_attachments.into_iter().flatten() {
             trackers.views.insert_single(at.view.clone());
@@ -1465,6 +2460,20 @@ impl RenderPassInfo {
         })
     }
 
+    /// Returns what the parts of a parallel pass need to know about the pass,
+    /// without the attachments, which only the pass itself uses.
+    fn part_info(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+            render_attachments: AttachmentDataVec::new(),
+            is_depth_read_only: self.is_depth_read_only,
+            is_stencil_read_only: self.is_stencil_read_only,
+            extent: self.extent,
+            divergent_discarded_depth_stencil_aspect: None,
+            multiview: self.multiview,
+        }
+    }
+
     fn finish(
         self,
         device: &Device,
@@ -1523,10 +2532,14 @@ impl RenderPassInfo {
                     depth_ops,
                     stencil_ops,
                     clear_value: (0.0, 0),
+                    resolve: None,
                 }),
                 multiview: self.multiview,
                 timestamp_writes: None,
                 occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: wgt::RenderPassFlags::empty(),
+                parallel: false,
             };
             unsafe {
                 raw.begin_render_pass(&desc)
@@ -1562,9 +2575,14 @@ impl Global {
             desc: &RenderPassDescriptor<'_>,
             arc_desc: &mut ArcRenderPassDescriptor,
             device: &Device,
+            queue_type: wgt::QueueType,
         ) -> Result<(), RenderPassErrorInner> {
             device.check_is_valid()?;
 
+            if queue_type != wgt::QueueType::Graphics {
+                return Err(RenderPassErrorInner::UnsupportedQueueType(queue_type));
+            }
+
             let query_sets = hub.query_sets.read();
             let texture_views = hub.texture_views.read();
 
@@ -1647,6 +2665,16 @@ impl Global {
                         } else {
                             ResolvedPassChannel::ReadOnly
                         },
+                        resolve: if let Some(resolve) = depth_stencil_attachment.resolve {
+                            device.require_features(wgt::Features::DEPTH_STENCIL_RESOLVE)?;
+                            Some(wgt::DepthStencilResolve {
+                                target: texture_views.get(resolve.target).get()?,
+                                depth_mode: resolve.depth_mode,
+                                resolve_stencil: resolve.resolve_stencil,
+                            })
+                        } else {
+                            None
+                        },
                     })
                 } else {
                     None
@@ -1673,6 +2701,24 @@ impl Global {
                     None
                 };
 
+            arc_desc.pipeline_statistics_query = desc
+                .pipeline_statistics_query
+                .map(|psq| {
+                    Global::validate_pass_pipeline_statistics_query::<RenderPassErrorInner>(
+                        device,
+                        &query_sets,
+                        psq,
+                    )
+                })
+                .transpose()?;
+
+            // Multiview is only supported if the feature is enabled
+            if arc_desc.multiview.is_some() {
+                device.require_features(wgt::Features::MULTIVIEW)?;
+            }
+
+            validate_suspend_resume(arc_desc)?;
+
             Ok(())
         }
 
@@ -1691,8 +2737,17 @@ impl Global {
                     color_attachments: ArrayVec::new(),
                     depth_stencil_attachment: None,
                     occlusion_query_set: None,
+                    pipeline_statistics_query: None,
+                    flags: desc.flags,
+                    multiview: desc.multiview,
                 };
-                match fill_arc_desc(hub, desc, &mut arc_desc, &cmd_buf.device) {
+                match fill_arc_desc(
+                    hub,
+                    desc,
+                    &mut arc_desc,
+                    &cmd_buf.device,
+                    cmd_buf.queue_type,
+                ) {
                     Ok(()) => (RenderPass::new(cmd_buf, arc_desc), None),
                     Err(err) => (
                         RenderPass::new_invalid(cmd_buf, &desc.label, err.map_pass_err(scope)),
@@ -1732,8 +2787,10 @@ impl Global {
                     None,
                 )
             }
-            Err(SErr::Unlocked) => {
-                unreachable!("lock_encoder cannot fail due to the encoder being unlocked")
+            Err(SErr::Unlocked | SErr::Suspended) => {
+                unreachable!(
+                    "lock_encoder cannot fail due to the encoder being unlocked or suspended"
+                )
             }
         }
     }
@@ -1750,6 +2807,9 @@ impl Global {
         depth_stencil_attachment: Option<&RenderPassDepthStencilAttachment>,
         timestamp_writes: Option<&PassTimestampWrites>,
         occlusion_query_set: Option<id::QuerySetId>,
+        pipeline_statistics_query: Option<&PassPipelineStatisticsQuery>,
+        flags: wgt::RenderPassFlags,
+        multiview: Option<NonZeroU32>,
     ) {
         #[cfg(feature = "trace")]
         {
@@ -1774,6 +2834,9 @@ impl Global {
                     target_depth_stencil: depth_stencil_attachment.cloned(),
                     timestamp_writes: timestamp_writes.cloned(),
                     occlusion_query_set_id: occlusion_query_set,
+                    pipeline_statistics_query: pipeline_statistics_query.cloned(),
+                    flags,
+                    multiview,
                 });
             }
         }
@@ -1795,6 +2858,9 @@ impl Global {
                 depth_stencil_attachment,
                 timestamp_writes,
                 occlusion_query_set,
+                pipeline_statistics_query,
+                flags,
+                multiview,
             },
         );
         if let Some(err) = encoder_error {
@@ -1821,6 +2887,21 @@ impl Global {
             pass.base.label.as_deref().unwrap_or("")
         );
 
+        if let Some(Parallel::Encoder(encoder)) = pass
+            .parallel
+            .take_if(|parallel| matches!(parallel, Parallel::Encoder(_)))
+        {
+            // A parallel encoder encodes its part on this thread, and hands it
+            // over to its pass, which executes it when it ends.
+            if let (Some(cmd_buf), Some(encoder)) = (pass.parent.take(), encoder) {
+                let label = pass.base.label.clone().map(Cow::Owned);
+                let base = mem::replace(&mut pass.base, BasePass::new(&label));
+                let snatch_guard = cmd_buf.device.snatchable_lock.read();
+                encoder.end(&cmd_buf, base, &snatch_guard);
+            }
+            return Ok(());
+        }
+
         let cmd_buf = pass.parent.take().ok_or(EncoderStateError::Ended)?;
         let mut cmd_buf_data = cmd_buf.data.lock();
 
@@ -1849,14 +2930,70 @@ impl Global {
         cmd_buf_data.unlock_and_record(|cmd_buf_data| -> Result<(), RenderPassError> {
             let device = &cmd_buf.device;
             device.check_is_valid().map_pass_err(pass_scope)?;
+
+            if let Some(Parallel::Pass(mut parallel)) = pass.parallel.take() {
+                let snatch_guard = &device.snatchable_lock.read();
+                let label = pass.base.label.take();
+                // The commands the pass recorded after its last parallel
+                // encoder are its last part.
+                if !pass.base.commands.is_empty() {
+                    let encoder = parallel
+                        .add_part(device, &mut cmd_buf_data.encoder)
+                        .map_pass_err(pass_scope)?;
+                    let base = mem::replace(&mut pass.base, BasePass::new(&None));
+                    encoder.end(&cmd_buf, base, snatch_guard);
+                }
+                return parallel.end(label, device, cmd_buf_data, snatch_guard);
+            }
+
+            let attachments = SuspendedRenderPass::new(pass);
+            let resuming = pass.flags.contains(wgt::RenderPassFlags::RESUMING);
+            match cmd_buf_data.suspended_render_pass.take() {
+                Some(_) if !resuming => {
+                    return Err(EncoderStateError::Suspended).map_pass_err(pass_scope);
+                }
+                Some(suspended) if !suspended.matches(&attachments) => {
+                    return Err(RenderPassErrorInner::MismatchedResumedAttachments)
+                        .map_pass_err(pass_scope);
+                }
+                Some(_) => {}
+                None if resuming => {
+                    // Only the first command of a command buffer may resume a
+                    // pass suspended by another command buffer. Whether that
+                    // pass matches is checked at submission.
+                    let encoder = &cmd_buf_data.encoder;
+                    if encoder.is_open || !encoder.list.is_empty() {
+                        return Err(RenderPassErrorInner::NoSuspendedRenderPass)
+                            .map_pass_err(pass_scope);
+                    }
+                    cmd_buf_data.resumed_render_pass = Some(attachments.clone());
+                }
+                None => {}
+            }
+            if pass.flags.contains(wgt::RenderPassFlags::SUSPENDING) {
+                cmd_buf_data.suspended_render_pass = Some(attachments);
+            }
+
             let snatch_guard = &device.snatchable_lock.read();
 
             let base = &mut pass.base;
 
-            let mut indirect_draw_validation_batcher =
-                crate::indirect_validation::DrawBatcher::new();
+            // A pass resuming a pass suspended in this command buffer continues
+            // the hal command buffer it was recorded into, and adds to the
+            // work recorded before it.
+            let suspended_pre_pass = cmd_buf_data.suspended_pre_pass.take();
+            let continued = suspended_pre_pass.is_some();
+            let SuspendedPrePass {
+                label: pre_pass_label,
+                buffers: pre_pass_buffers,
+                textures: pre_pass_textures,
+                pending_discard_init_fixups,
+                index_expansions,
+                mut indirect_draw_validation_batcher,
+                parts_indirect_draw_validation,
+            } = suspended_pre_pass.unwrap_or_else(|| SuspendedPrePass::new(base.label.clone()));
 
-            let (scope, pending_discard_init_fixups) = {
+            let pre_pass = {
                 let encoder = &mut cmd_buf_data.encoder;
                 let tracker = &mut cmd_buf_data.trackers;
                 let buffer_memory_init_actions = &mut cmd_buf_data.buffer_memory_init_actions;
@@ -1865,15 +3002,22 @@ impl Global {
                 let indirect_draw_validation_resources =
                     &mut cmd_buf_data.indirect_draw_validation_resources;
 
-                // We automatically keep extending command buffers over time, and because
-                // we want to insert a command buffer _before_ what we're about to record,
-                // we need to make sure to close the previous one.
-                encoder.close_if_open().map_pass_err(pass_scope)?;
-                encoder
-                    .open_pass(base.label.as_deref())
-                    .map_pass_err(pass_scope)?;
+                if !continued {
+                    // We automatically keep extending command buffers over time, and because
+                    // we want to insert a command buffer _before_ what we're about to record,
+                    // we need to make sure to close the previous one.
+                    encoder.close_if_open().map_pass_err(pass_scope)?;
+                    encoder
+                        .open_pass(base.label.as_deref())
+                        .map_pass_err(pass_scope)?;
+                }
+
+                let mut pending_discard_init_fixups = pending_discard_init_fixups;
 
-                let mut pending_discard_init_fixups = SurfacesInDiscardState::new();
+                let pass_pipeline_statistics_query = pass
+                    .pipeline_statistics_query
+                    .as_ref()
+                    .map(|psq| psq.query_index);
 
                 let info = RenderPassInfo::start(
                     device,
@@ -1884,6 +3028,10 @@ impl Global {
                     // Still needed down the line.
                     // TODO(wumpf): by restructuring the code, we could get rid of some of this Arc clone.
                     pass.occlusion_query_set.clone(),
+                    pass.pipeline_statistics_query.take(),
+                    pass.flags,
+                    pass.multiview,
+                    false,
                     encoder,
                     tracker,
                     texture_memory_actions,
@@ -1897,6 +3045,14 @@ impl Global {
                 tracker.buffers.set_size(indices.buffers.size());
                 tracker.textures.set_size(indices.textures.size());
 
+                let mut scope = device.new_usage_scope();
+                if continued {
+                    scope.buffers = pre_pass_buffers;
+                    scope.textures = pre_pass_textures;
+                    scope.buffers.set_size(indices.buffers.size());
+                    scope.textures.set_size(indices.textures.size());
+                }
+
                 let mut state = State {
                     pipeline_flags: PipelineFlags::empty(),
                     blend_constant: OptionalState::Unused,
@@ -1904,6 +3060,8 @@ impl Global {
                     pipeline: None,
                     index: IndexState::default(),
                     vertex: VertexState::default(),
+                    viewport_size: [info.extent.width as f32, info.extent.height as f32],
+                    index_expansions,
 
                     info,
 
@@ -1915,7 +3073,7 @@ impl Global {
                         texture_memory_actions,
                         as_actions: &mut cmd_buf_data.as_actions,
                         pending_discard_init_fixups,
-                        scope: device.new_usage_scope(),
+                        scope,
                         binder: Binder::new(),
 
                         snatch_guard,
@@ -1929,287 +3087,25 @@ impl Global {
 
                     active_occlusion_query: None,
                     active_pipeline_statistics_query: None,
+                    pass_pipeline_statistics_query,
+                    active_conditional_block: false,
+
+                    push_constants: vec![
+                        0;
+                        (device.limits.max_push_constant_size / wgt::PUSH_CONSTANT_ALIGNMENT)
+                            as usize
+                    ],
                 };
 
-                for command in base.commands.drain(..) {
-                    match command {
-                        ArcRenderCommand::SetBindGroup {
-                            index,
-                            num_dynamic_offsets,
-                            bind_group,
-                        } => {
-                            let scope = PassErrorScope::SetBindGroup;
-                            pass::set_bind_group::<RenderPassErrorInner>(
-                                &mut state.general,
-                                cmd_buf.as_ref(),
-                                &base.dynamic_offsets,
-                                index,
-                                num_dynamic_offsets,
-                                bind_group,
-                                true,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::SetPipeline(pipeline) => {
-                            let scope = PassErrorScope::SetPipelineRender;
-                            set_pipeline(&mut state, &cmd_buf, pipeline).map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::SetIndexBuffer {
-                            buffer,
-                            index_format,
-                            offset,
-                            size,
-                        } => {
-                            let scope = PassErrorScope::SetIndexBuffer;
-                            set_index_buffer(
-                                &mut state,
-                                &cmd_buf,
-                                buffer,
-                                index_format,
-                                offset,
-                                size,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::SetVertexBuffer {
-                            slot,
-                            buffer,
-                            offset,
-                            size,
-                        } => {
-                            let scope = PassErrorScope::SetVertexBuffer;
-                            set_vertex_buffer(&mut state, &cmd_buf, slot, buffer, offset, size)
-                                .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::SetBlendConstant(ref color) => {
-                            set_blend_constant(&mut state, color);
-                        }
-                        ArcRenderCommand::SetStencilReference(value) => {
-                            set_stencil_reference(&mut state, value);
-                        }
-                        ArcRenderCommand::SetViewport {
-                            rect,
-                            depth_min,
-                            depth_max,
-                        } => {
-                            let scope = PassErrorScope::SetViewport;
-                            set_viewport(&mut state, rect, depth_min, depth_max)
-                                .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::SetPushConstant {
-                            stages,
-                            offset,
-                            size_bytes,
-                            values_offset,
-                        } => {
-                            let scope = PassErrorScope::SetPushConstant;
-                            pass::set_push_constant::<RenderPassErrorInner, _>(
-                                &mut state.general,
-                                &base.push_constant_data,
-                                stages,
-                                offset,
-                                size_bytes,
-                                values_offset,
-                                |_| {},
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::SetScissor(rect) => {
-                            let scope = PassErrorScope::SetScissorRect;
-                            set_scissor(&mut state, rect).map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::Draw {
-                            vertex_count,
-                            instance_count,
-                            first_vertex,
-                            first_instance,
-                        } => {
-                            let scope = PassErrorScope::Draw {
-                                kind: DrawKind::Draw,
-                                indexed: false,
-                            };
-                            draw(
-                                &mut state,
-                                vertex_count,
-                                instance_count,
-                                first_vertex,
-                                first_instance,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::DrawIndexed {
-                            index_count,
-                            instance_count,
-                            first_index,
-                            base_vertex,
-                            first_instance,
-                        } => {
-                            let scope = PassErrorScope::Draw {
-                                kind: DrawKind::Draw,
-                                indexed: true,
-                            };
-                            draw_indexed(
-                                &mut state,
-                                index_count,
-                                instance_count,
-                                first_index,
-                                base_vertex,
-                                first_instance,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::DrawIndirect {
-                            buffer,
-                            offset,
-                            count,
-                            indexed,
-
-                            vertex_or_index_limit: _,
-                            instance_limit: _,
-                        } => {
-                            let scope = PassErrorScope::Draw {
-                                kind: if count != 1 {
-                                    DrawKind::MultiDrawIndirect
-                                } else {
-                                    DrawKind::DrawIndirect
-                                },
-                                indexed,
-                            };
-                            multi_draw_indirect(
-                                &mut state,
-                                indirect_draw_validation_resources,
-                                &mut indirect_draw_validation_batcher,
-                                &cmd_buf,
-                                buffer,
-                                offset,
-                                count,
-                                indexed,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::MultiDrawIndirectCount {
-                            buffer,
-                            offset,
-                            count_buffer,
-                            count_buffer_offset,
-                            max_count,
-                            indexed,
-                        } => {
-                            let scope = PassErrorScope::Draw {
-                                kind: DrawKind::MultiDrawIndirectCount,
-                                indexed,
-                            };
-                            multi_draw_indirect_count(
-                                &mut state,
-                                &cmd_buf,
-                                buffer,
-                                offset,
-                                count_buffer,
-                                count_buffer_offset,
-                                max_count,
-                                indexed,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::PushDebugGroup { color: _, len } => {
-                            pass::push_debug_group(&mut state.general, &base.string_data, len);
-                        }
-                        ArcRenderCommand::PopDebugGroup => {
-                            let scope = PassErrorScope::PopDebugGroup;
-                            pass::pop_debug_group::<RenderPassErrorInner>(&mut state.general)
-                                .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::InsertDebugMarker { color: _, len } => {
-                            pass::insert_debug_marker(&mut state.general, &base.string_data, len);
-                        }
-                        ArcRenderCommand::WriteTimestamp {
-                            query_set,
-                            query_index,
-                        } => {
-                            let scope = PassErrorScope::WriteTimestamp;
-                            pass::write_timestamp::<RenderPassErrorInner>(
-                                &mut state.general,
-                                cmd_buf.as_ref(),
-                                Some(&mut cmd_buf_data.pending_query_resets),
-                                query_set,
-                                query_index,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::BeginOcclusionQuery { query_index } => {
-                            api_log!("RenderPass::begin_occlusion_query {query_index}");
-                            let scope = PassErrorScope::BeginOcclusionQuery;
-
-                            let query_set = pass
-                                .occlusion_query_set
-                                .clone()
-                                .ok_or(RenderPassErrorInner::MissingOcclusionQuerySet)
-                                .map_pass_err(scope)?;
-
-                            validate_and_begin_occlusion_query(
-                                query_set,
-                                state.general.raw_encoder,
-                                &mut state.general.tracker.query_sets,
-                                query_index,
-                                Some(&mut cmd_buf_data.pending_query_resets),
-                                &mut state.active_occlusion_query,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::EndOcclusionQuery => {
-                            api_log!("RenderPass::end_occlusion_query");
-                            let scope = PassErrorScope::EndOcclusionQuery;
-
-                            end_occlusion_query(
-                                state.general.raw_encoder,
-                                &mut state.active_occlusion_query,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::BeginPipelineStatisticsQuery {
-                            query_set,
-                            query_index,
-                        } => {
-                            api_log!(
-                                "RenderPass::begin_pipeline_statistics_query {query_index} {}",
-                                query_set.error_ident()
-                            );
-                            let scope = PassErrorScope::BeginPipelineStatisticsQuery;
-
-                            validate_and_begin_pipeline_statistics_query(
-                                query_set,
-                                state.general.raw_encoder,
-                                &mut state.general.tracker.query_sets,
-                                cmd_buf.as_ref(),
-                                query_index,
-                                Some(&mut cmd_buf_data.pending_query_resets),
-                                &mut state.active_pipeline_statistics_query,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::EndPipelineStatisticsQuery => {
-                            api_log!("RenderPass::end_pipeline_statistics_query");
-                            let scope = PassErrorScope::EndPipelineStatisticsQuery;
-
-                            end_pipeline_statistics_query(
-                                state.general.raw_encoder,
-                                &mut state.active_pipeline_statistics_query,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                        ArcRenderCommand::ExecuteBundle(bundle) => {
-                            let scope = PassErrorScope::ExecuteBundle;
-                            execute_bundle(
-                                &mut state,
-                                indirect_draw_validation_resources,
-                                &mut indirect_draw_validation_batcher,
-                                &cmd_buf,
-                                bundle,
-                            )
-                            .map_pass_err(scope)?;
-                        }
-                    }
-                }
+                encode_commands(
+                    &mut state,
+                    base,
+                    &cmd_buf,
+                    pass.occlusion_query_set.as_ref(),
+                    &mut cmd_buf_data.pending_query_resets,
+                    indirect_draw_validation_resources,
+                    &mut indirect_draw_validation_batcher,
+                )?;
 
                 state
                     .info
@@ -2221,143 +3117,661 @@ impl Global {
                     )
                     .map_pass_err(pass_scope)?;
 
-                let trackers = state.general.scope;
-
-                let pending_discard_init_fixups = state.general.pending_discard_init_fixups;
-
-                encoder.close().map_pass_err(pass_scope)?;
-                (trackers, pending_discard_init_fixups)
+                let mut scope = state.general.scope;
+                SuspendedPrePass {
+                    label: pre_pass_label,
+                    buffers: mem::take(&mut scope.buffers),
+                    textures: mem::take(&mut scope.textures),
+                    pending_discard_init_fixups: state.general.pending_discard_init_fixups,
+                    index_expansions: state.index_expansions,
+                    indirect_draw_validation_batcher,
+                    parts_indirect_draw_validation,
+                }
             };
 
-            let encoder = &mut cmd_buf_data.encoder;
-            let tracker = &mut cmd_buf_data.trackers;
-
-            {
-                let transit = encoder
-                    .open_pass(Some("(wgpu internal) Pre Pass"))
-                    .map_pass_err(pass_scope)?;
-
-                fixup_discarded_surfaces(
-                    pending_discard_init_fixups.into_iter(),
-                    transit,
-                    &mut tracker.textures,
-                    &cmd_buf.device,
-                    snatch_guard,
-                );
-
-                cmd_buf_data.pending_query_resets.reset_queries(transit);
-
-                CommandBuffer::insert_barriers_from_scope(transit, tracker, &scope, snatch_guard);
-
-                if let Some(ref indirect_validation) = device.indirect_validation {
-                    indirect_validation
-                        .draw
-                        .inject_validation_pass(
-                            device,
-                            snatch_guard,
-                            &mut cmd_buf_data.indirect_draw_validation_resources,
-                            &mut cmd_buf_data.temp_resources,
-                            transit,
-                            indirect_draw_validation_batcher,
-                        )
-                        .map_pass_err(pass_scope)?;
-                }
+            if pass.flags.contains(wgt::RenderPassFlags::SUSPENDING) {
+                cmd_buf_data.suspended_pre_pass = Some(pre_pass);
+                return Ok(());
             }
 
-            encoder.close_and_swap().map_pass_err(pass_scope)?;
-
-            Ok(())
+            cmd_buf_data.encoder.close().map_pass_err(pass_scope)?;
+            pre_pass
+                .record(device, cmd_buf_data, snatch_guard)
+                .map_pass_err(pass_scope)
         })
     }
 }
 
-fn set_pipeline(
-    state: &mut State,
-    cmd_buf: &Arc<CommandBuffer>,
-    pipeline: Arc<RenderPipeline>,
-) -> Result<(), RenderPassErrorInner> {
-    api_log!("RenderPass::set_pipeline {}", pipeline.error_ident());
-
-    state.pipeline = Some(pipeline.clone());
-
-    let pipeline = state
-        .general
-        .tracker
-        .render_pipelines
-        .insert_single(pipeline)
-        .clone();
-
-    pipeline.same_device_as(cmd_buf.as_ref())?;
-
-    state
-        .info
-        .context
-        .check_compatible(&pipeline.pass_context, pipeline.as_ref())
-        .map_err(RenderCommandError::IncompatiblePipelineTargets)?;
-
-    state.pipeline_flags = pipeline.flags;
+/// The buffers and textures used by the parts of a render pass suspended at
+/// the end of a command buffer, by tracker index.
+#[derive(Debug, Default)]
+pub(crate) struct SuspendedResources {
+    buffers: Vec<usize>,
+    textures: Vec<usize>,
+}
 
-    if pipeline.flags.contains(PipelineFlags::WRITES_DEPTH) && state.info.is_depth_read_only {
-        return Err(RenderCommandError::IncompatibleDepthAccess(pipeline.error_ident()).into());
+impl SuspendedResources {
+    pub(crate) fn extend(&mut self, other: Self) {
+        self.buffers.extend(other.buffers);
+        self.textures.extend(other.textures);
     }
-    if pipeline.flags.contains(PipelineFlags::WRITES_STENCIL) && state.info.is_stencil_read_only {
-        return Err(RenderCommandError::IncompatibleStencilAccess(pipeline.error_ident()).into());
+
+    /// Returns true if getting the resources of `tracker` into the states
+    /// they start in needs a barrier on any of these resources.
+    pub(crate) fn need_barriers(&self, device_tracker: &DeviceTracker, tracker: &Tracker) -> bool {
+        self.buffers.iter().any(|&index| {
+            device_tracker
+                .buffers
+                .needs_barrier(&tracker.buffers, index)
+        }) || self.textures.iter().any(|&index| {
+            device_tracker
+                .textures
+                .needs_barrier(&tracker.textures, index)
+        })
     }
+}
 
-    state
-        .blend_constant
-        .require(pipeline.flags.contains(PipelineFlags::BLEND_CONSTANT));
+/// The work recorded before a render pass, in a hal command buffer of its own.
+///
+/// When a render pass is split up with [`wgt::RenderPassFlags`], the parts
+/// recorded into the same command buffer go into the same hal command buffer.
+/// Nothing may come between them, so what each part needs done before it is
+/// gathered here and recorded before the first part once the last part ends,
+/// or the command buffer is finished.
+pub(crate) struct SuspendedPrePass {
+    label: Option<String>,
+    buffers: BufferUsageScope,
+    textures: TextureUsageScope,
+    pending_discard_init_fixups: SurfacesInDiscardState,
+    index_expansions: Vec<crate::index_expansion::Expansion>,
+    indirect_draw_validation_batcher: crate::indirect_validation::DrawBatcher,
+    /// The indirect draws of the parts of a pass recorded by parallel
+    /// encoders, with the resources they were validated with.
+    parts_indirect_draw_validation: Vec<(
+        crate::indirect_validation::DrawResources,
+        crate::indirect_validation::DrawBatcher,
+    )>,
+}
 
-    unsafe {
-        state
-            .general
-            .raw_encoder
-            .set_render_pipeline(pipeline.raw());
+impl SuspendedPrePass {
+    fn new(label: Option<String>) -> Self {
+        Self {
+            label,
+            buffers: BufferUsageScope::default(),
+            textures: TextureUsageScope::default(),
+            pending_discard_init_fixups: SurfacesInDiscardState::new(),
+            index_expansions: Vec::new(),
+            indirect_draw_validation_batcher: crate::indirect_validation::DrawBatcher::new(),
+            parts_indirect_draw_validation: Vec::new(),
+        }
     }
 
-    if pipeline.flags.contains(PipelineFlags::STENCIL_REFERENCE) {
-        unsafe {
-            state
-                .general
-                .raw_encoder
-                .set_stencil_reference(state.stencil_reference);
+    /// Returns the resources used by the parts of the render pass.
+    pub(crate) fn resources(&self) -> SuspendedResources {
+        SuspendedResources {
+            buffers: self.buffers.tracker_indices().collect(),
+            textures: self.textures.tracker_indices().collect(),
         }
     }
 
-    // Rebind resource
-    pass::rebind_resources::<RenderPassErrorInner, _>(
-        &mut state.general,
-        &pipeline.layout,
-        &pipeline.late_sized_buffer_groups,
-        || {},
-    )?;
+    /// Records the work into a new hal command buffer, and puts it before the
+    /// last one, which the render pass has been recorded into.
+    pub(crate) fn record(
+        self,
+        device: &Arc<Device>,
+        cmd_buf_data: &mut CommandBufferMutable,
+        snatch_guard: &SnatchGuard,
+    ) -> Result<(), RenderPassErrorInner> {
+        let alias_resets = cmd_buf_data.texture_memory_actions.take_alias_resets()?;
 
-    // Update vertex buffer limits.
-    state.vertex.update_limits(&pipeline.vertex_steps);
-    Ok(())
-}
+        let encoder = &mut cmd_buf_data.encoder;
+        let tracker = &mut cmd_buf_data.trackers;
 
-// This function is duplicative of `bundle::set_index_buffer`.
-fn set_index_buffer(
-    state: &mut State,
-    cmd_buf: &Arc<CommandBuffer>,
-    buffer: Arc<crate::resource::Buffer>,
-    index_format: IndexFormat,
-    offset: u64,
-    size: Option<BufferSize>,
-) -> Result<(), RenderPassErrorInner> {
-    api_log!("RenderPass::set_index_buffer {}", buffer.error_ident());
+        let scope = UsageScope {
+            pool: &device.usage_scopes,
+            buffers: self.buffers,
+            textures: self.textures,
+        };
 
-    state
-        .general
-        .scope
-        .buffers
-        .merge_single(&buffer, wgt::BufferUses::INDEX)?;
+        {
+            let transit = encoder.open_pass(Some("(wgpu internal) Pre Pass"))?;
 
-    buffer.same_device_as(cmd_buf.as_ref())?;
+            reset_aliased_textures(alias_resets, transit, &mut tracker.textures, snatch_guard)?;
+
+            fixup_discarded_surfaces(
+                self.pending_discard_init_fixups.into_iter(),
+                transit,
+                &mut tracker.textures,
+                device,
+                snatch_guard,
+            );
+
+            cmd_buf_data.pending_query_resets.reset_queries(transit);
+
+            let mut barrier_log = BarrierLog::new(device, || {
+                format!(
+                    "render pass {:?}",
+                    self.label.as_deref().unwrap_or_default()
+                )
+            });
+            CommandBuffer::insert_barriers_from_scope(
+                transit,
+                tracker,
+                &scope,
+                snatch_guard,
+                barrier_log.as_mut(),
+            );
+
+            if let Some(ref indirect_validation) = device.indirect_validation {
+                indirect_validation.draw.inject_validation_pass(
+                    device,
+                    snatch_guard,
+                    &mut cmd_buf_data.indirect_draw_validation_resources,
+                    &mut cmd_buf_data.temp_resources,
+                    transit,
+                    self.indirect_draw_validation_batcher,
+                )?;
+                for (mut resources, batcher) in self.parts_indirect_draw_validation {
+                    indirect_validation.draw.inject_validation_pass(
+                        device,
+                        snatch_guard,
+                        &mut resources,
+                        &mut cmd_buf_data.temp_resources,
+                        transit,
+                        batcher,
+                    )?;
+                    cmd_buf_data
+                        .indirect_draw_validation_resources
+                        .extend(resources);
+                }
+            }
+
+            if let Some(ref index_expansion) = device.index_expansion {
+                index_expansion.inject_expansion_pass(
+                    device,
+                    transit,
+                    self.index_expansions,
+                    &mut cmd_buf_data.temp_resources,
+                );
+            }
+        }
+
+        encoder.close_and_swap()?;
+
+        Ok(())
+    }
+}
+
+/// Validates and encodes the commands of `base` into `state`, for the whole
+/// render pass or a part of it.
+fn encode_commands(
+    state: &mut State,
+    base: &mut RenderBasePass,
+    cmd_buf: &Arc<CommandBuffer>,
+    occlusion_query_set: Option<&Arc<QuerySet>>,
+    pending_query_resets: &mut QueryResetMap,
+    indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
+    indirect_draw_validation_batcher: &mut crate::indirect_validation::DrawBatcher,
+) -> Result<(), RenderPassError> {
+    for command in base.commands.drain(..) {
+        match command {
+            ArcRenderCommand::SetBindGroup {
+                index,
+                num_dynamic_offsets,
+                bind_group,
+            } => {
+                let scope = PassErrorScope::SetBindGroup;
+                pass::set_bind_group::<RenderPassErrorInner>(
+                    &mut state.general,
+                    cmd_buf.as_ref(),
+                    &base.dynamic_offsets,
+                    index,
+                    num_dynamic_offsets,
+                    bind_group,
+                    true,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetPipeline(pipeline) => {
+                let scope = PassErrorScope::SetPipelineRender;
+                set_pipeline(state, cmd_buf, pipeline).map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetIndexBuffer {
+                buffer,
+                index_format,
+                offset,
+                size,
+            } => {
+                let scope = PassErrorScope::SetIndexBuffer;
+                set_index_buffer(state, cmd_buf, buffer, index_format, offset, size)
+                    .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetVertexBuffer {
+                slot,
+                buffer,
+                offset,
+                size,
+            } => {
+                let scope = PassErrorScope::SetVertexBuffer;
+                set_vertex_buffer(state, cmd_buf, slot, buffer, offset, size)
+                    .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetBlendConstant(ref color) => {
+                set_blend_constant(state, color);
+            }
+            ArcRenderCommand::SetStencilReference(value) => {
+                set_stencil_reference(state, value);
+            }
+            ArcRenderCommand::SetShadingRate(rate) => {
+                let scope = PassErrorScope::SetShadingRate;
+                set_shading_rate(state, rate).map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetViewport {
+                rect,
+                depth_min,
+                depth_max,
+            } => {
+                let scope = PassErrorScope::SetViewport;
+                set_viewport(state, None, rect, depth_min, depth_max).map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetIndexedViewport {
+                index,
+                rect,
+                depth_min,
+                depth_max,
+            } => {
+                let scope = PassErrorScope::SetViewports;
+                set_viewport(state, Some(index), rect, depth_min, depth_max).map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetPushConstant {
+                stages,
+                offset,
+                size_bytes,
+                values_offset,
+            } => {
+                let scope = PassErrorScope::SetPushConstant;
+                pass::set_push_constant::<RenderPassErrorInner, _>(
+                    &mut state.general,
+                    &base.push_constant_data,
+                    stages,
+                    offset,
+                    size_bytes,
+                    values_offset,
+                    |data_slice| {
+                        let offset_in_elements = (offset / wgt::PUSH_CONSTANT_ALIGNMENT) as usize;
+                        state.push_constants[offset_in_elements..][..data_slice.len()]
+                            .copy_from_slice(data_slice);
+                    },
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::SetScissor(rect) => {
+                let scope = PassErrorScope::SetScissorRect;
+                set_scissor(state, rect).map_pass_err(scope)?;
+            }
+            ArcRenderCommand::Draw {
+                vertex_count,
+                instance_count,
+                first_vertex,
+                first_instance,
+            } => {
+                let scope = PassErrorScope::Draw {
+                    kind: DrawKind::Draw,
+                    indexed: false,
+                };
+                draw(
+                    state,
+                    vertex_count,
+                    instance_count,
+                    first_vertex,
+                    first_instance,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::DrawIndexed {
+                index_count,
+                instance_count,
+                first_index,
+                base_vertex,
+                first_instance,
+            } => {
+                let scope = PassErrorScope::Draw {
+                    kind: DrawKind::Draw,
+                    indexed: true,
+                };
+                draw_indexed(
+                    state,
+                    index_count,
+                    instance_count,
+                    first_index,
+                    base_vertex,
+                    first_instance,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::DrawIndirect {
+                buffer,
+                offset,
+                count,
+                indexed,
+
+                vertex_or_index_limit: _,
+                instance_limit: _,
+            } => {
+                let scope = PassErrorScope::Draw {
+                    kind: if count != 1 {
+                        DrawKind::MultiDrawIndirect
+                    } else {
+                        DrawKind::DrawIndirect
+                    },
+                    indexed,
+                };
+                multi_draw_indirect(
+                    state,
+                    indirect_draw_validation_resources,
+                    indirect_draw_validation_batcher,
+                    cmd_buf,
+                    buffer,
+                    offset,
+                    count,
+                    indexed,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::MultiDrawIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_count,
+                indexed,
+            } => {
+                let scope = PassErrorScope::Draw {
+                    kind: DrawKind::MultiDrawIndirectCount,
+                    indexed,
+                };
+                multi_draw_indirect_count(
+                    state,
+                    indirect_draw_validation_resources,
+                    indirect_draw_validation_batcher,
+                    cmd_buf,
+                    buffer,
+                    offset,
+                    count_buffer,
+                    count_buffer_offset,
+                    max_count,
+                    indexed,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::DrawMeshTasks {
+                group_count_x,
+                group_count_y,
+                group_count_z,
+            } => {
+                let scope = PassErrorScope::Draw {
+                    kind: DrawKind::DrawMeshTasks,
+                    indexed: false,
+                };
+                draw_mesh_tasks(state, group_count_x, group_count_y, group_count_z)
+                    .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::DrawMeshTasksIndirect {
+                buffer,
+                offset,
+                count,
+            } => {
+                let scope = PassErrorScope::Draw {
+                    kind: if count != 1 {
+                        DrawKind::MultiDrawMeshTasksIndirect
+                    } else {
+                        DrawKind::DrawMeshTasksIndirect
+                    },
+                    indexed: false,
+                };
+                multi_draw_mesh_tasks_indirect(state, cmd_buf, buffer, offset, count)
+                    .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::MultiDrawMeshTasksIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_buffer_offset,
+                max_count,
+            } => {
+                let scope = PassErrorScope::Draw {
+                    kind: DrawKind::MultiDrawMeshTasksIndirectCount,
+                    indexed: false,
+                };
+                multi_draw_mesh_tasks_indirect_count(
+                    state,
+                    cmd_buf,
+                    buffer,
+                    offset,
+                    count_buffer,
+                    count_buffer_offset,
+                    max_count,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::PushDebugGroup { color: _, len } => {
+                pass::push_debug_group(&mut state.general, &base.string_data, len);
+            }
+            ArcRenderCommand::PopDebugGroup => {
+                let scope = PassErrorScope::PopDebugGroup;
+                pass::pop_debug_group::<RenderPassErrorInner>(&mut state.general)
+                    .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::InsertDebugMarker { color: _, len } => {
+                pass::insert_debug_marker(&mut state.general, &base.string_data, len);
+            }
+            ArcRenderCommand::WriteTimestamp {
+                query_set,
+                query_index,
+            } => {
+                let scope = PassErrorScope::WriteTimestamp;
+                pass::write_timestamp::<RenderPassErrorInner>(
+                    &mut state.general,
+                    cmd_buf.as_ref(),
+                    Some(&mut *pending_query_resets),
+                    query_set,
+                    query_index,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::BeginOcclusionQuery { query_index } => {
+                api_log!("RenderPass::begin_occlusion_query {query_index}");
+                let scope = PassErrorScope::BeginOcclusionQuery;
+
+                let query_set = occlusion_query_set
+                    .cloned()
+                    .ok_or(RenderPassErrorInner::MissingOcclusionQuerySet)
+                    .map_pass_err(scope)?;
+
+                validate_and_begin_occlusion_query(
+                    query_set,
+                    state.general.raw_encoder,
+                    &mut state.general.tracker.query_sets,
+                    query_index,
+                    Some(&mut *pending_query_resets),
+                    &mut state.active_occlusion_query,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::EndOcclusionQuery => {
+                api_log!("RenderPass::end_occlusion_query");
+                let scope = PassErrorScope::EndOcclusionQuery;
+
+                end_occlusion_query(state.general.raw_encoder, &mut state.active_occlusion_query)
+                    .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::BeginPipelineStatisticsQuery {
+                query_set,
+                query_index,
+            } => {
+                api_log!(
+                    "RenderPass::begin_pipeline_statistics_query {query_index} {}",
+                    query_set.error_ident()
+                );
+                let scope = PassErrorScope::BeginPipelineStatisticsQuery;
+
+                state
+                    .general
+                    .device
+                    .require_features(wgt::Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES)
+                    .map_pass_err(scope)?;
+                validate_and_begin_pipeline_statistics_query(
+                    query_set,
+                    state.general.raw_encoder,
+                    &mut state.general.tracker.query_sets,
+                    cmd_buf.as_ref(),
+                    query_index,
+                    Some(&mut *pending_query_resets),
+                    &mut state.active_pipeline_statistics_query,
+                    state.pass_pipeline_statistics_query,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::EndPipelineStatisticsQuery => {
+                api_log!("RenderPass::end_pipeline_statistics_query");
+                let scope = PassErrorScope::EndPipelineStatisticsQuery;
+
+                end_pipeline_statistics_query(
+                    state.general.raw_encoder,
+                    &mut state.active_pipeline_statistics_query,
+                )
+                .map_pass_err(scope)?;
+            }
+            ArcRenderCommand::BeginConditionalBlock { buffer, offset } => {
+                let scope = PassErrorScope::BeginConditionalBlock;
+                begin_conditional_block(state, cmd_buf, buffer, offset).map_pass_err(scope)?;
+            }
+            ArcRenderCommand::EndConditionalBlock => {
+                let scope = PassErrorScope::EndConditionalBlock;
+                end_conditional_block(state).map_pass_err(scope)?;
+            }
+            ArcRenderCommand::ExecuteBundle(bundle) => {
+                let scope = PassErrorScope::ExecuteBundle;
+                execute_bundle(
+                    state,
+                    indirect_draw_validation_resources,
+                    indirect_draw_validation_batcher,
+                    cmd_buf,
+                    occlusion_query_set,
+                    pending_query_resets,
+                    bundle,
+                )
+                .map_pass_err(scope)?;
+            }
+        }
+    }
+
+    if state.active_conditional_block {
+        return Err(RenderPassErrorInner::UnendedConditionalBlock)
+            .map_pass_err(PassErrorScope::Pass);
+    }
+    Ok(())
+}
+
+fn set_pipeline(
+    state: &mut State,
+    cmd_buf: &Arc<CommandBuffer>,
+    pipeline: Arc<RenderPipeline>,
+) -> Result<(), RenderPassErrorInner> {
+    api_log!("RenderPass::set_pipeline {}", pipeline.error_ident());
+
+    state.pipeline = Some(pipeline.clone());
+
+    let pipeline = state
+        .general
+        .tracker
+        .render_pipelines
+        .insert_single(pipeline)
+        .clone();
+
+    pipeline.same_device_as(cmd_buf.as_ref())?;
+
+    state
+        .info
+        .context
+        .check_compatible(&pipeline.pass_context, pipeline.as_ref())
+        .map_err(RenderCommandError::IncompatiblePipelineTargets)?;
+
+    state.pipeline_flags = pipeline.flags;
+
+    if pipeline.flags.contains(PipelineFlags::WRITES_DEPTH) && state.info.is_depth_read_only {
+        return Err(RenderCommandError::IncompatibleDepthAccess(pipeline.error_ident()).into());
+    }
+    if pipeline.flags.contains(PipelineFlags::WRITES_STENCIL) && state.info.is_stencil_read_only {
+        return Err(RenderCommandError::IncompatibleStencilAccess(pipeline.error_ident()).into());
+    }
+
+    state
+        .blend_constant
+        .require(pipeline.flags.contains(PipelineFlags::BLEND_CONSTANT));
+
+    unsafe {
+        state
+            .general
+            .raw_encoder
+            .set_render_pipeline(pipeline.raw());
+    }
+
+    if pipeline.flags.contains(PipelineFlags::STENCIL_REFERENCE) {
+        unsafe {
+            state
+                .general
+                .raw_encoder
+                .set_stencil_reference(state.stencil_reference);
+        }
+    }
+
+    // Rebind resource
+    pass::rebind_resources::<RenderPassErrorInner, _>(
+        &mut state.general,
+        &pipeline.layout,
+        &pipeline.late_sized_buffer_groups,
+        // The push constants are cleared along with the pipeline layout change.
+        || state.push_constants.fill(0),
+    )?;
+
+    // Update vertex buffer limits.
+    state.vertex.update_limits(&pipeline.vertex_steps);
+    Ok(())
+}
+
+// This function is duplicative of `bundle::set_index_buffer`.
+fn set_index_buffer(
+    state: &mut State,
+    cmd_buf: &Arc<CommandBuffer>,
+    buffer: Arc<crate::resource::Buffer>,
+    index_format: IndexFormat,
+    offset: u64,
+    size: Option<BufferSize>,
+) -> Result<(), RenderPassErrorInner> {
+    api_log!("RenderPass::set_index_buffer {}", buffer.error_ident());
+
+    // Emulated 8 bit index buffers are only read by the compute pass that widens them.
+    let emulated =
+        index_format == IndexFormat::Uint8 && state.general.device.index_expansion.is_some();
+    let usage = if emulated {
+        wgt::BufferUses::STORAGE_READ_ONLY
+    } else {
+        wgt::BufferUses::INDEX
+    };
+    state.general.scope.buffers.merge_single(&buffer, usage)?;
+
+    buffer.same_device_as(cmd_buf.as_ref())?;
 
     buffer.check_usage(BufferUsages::INDEX)?;
 
+    if index_format == IndexFormat::Uint8 {
+        state
+            .general
+            .device
+            .require_features(wgt::Features::INDEX_FORMAT_UINT8)?;
+    }
+
     if offset % u64::try_from(index_format.byte_size()).unwrap() != 0 {
         return Err(RenderCommandError::UnalignedIndexBuffer {
             offset,
@@ -2379,12 +3793,62 @@ fn set_index_buffer(
         ),
     );
 
+    if emulated {
+        state.index.emulated = Some(EmulatedIndexBuffer {
+            buffer,
+            range: offset..end,
+            expansions: [None; 2],
+            bound: None,
+        });
+        return Ok(());
+    }
+    state.index.emulated = None;
+
     unsafe {
         hal::DynCommandEncoder::set_index_buffer(state.general.raw_encoder, binding, index_format);
     }
     Ok(())
 }
 
+/// Binds the expansion of the emulated 8 bit index buffer for the current pipeline,
+/// widening it first if no draw needed it yet.
+fn bind_expanded_index_buffer(state: &mut State) -> Result<(), RenderPassErrorInner> {
+    let Some(ref mut emulated) = state.index.emulated else {
+        return Ok(());
+    };
+    let restart = state.pipeline.as_ref().unwrap().strip_restart;
+    if emulated.bound == Some(restart) || emulated.range.is_empty() {
+        return Ok(());
+    }
+
+    let index = match emulated.expansions[usize::from(restart)] {
+        Some(index) => index,
+        None => {
+            let index_expansion = state.general.device.index_expansion.as_ref().unwrap();
+            let expansion = index_expansion.expand(
+                state.general.device,
+                &emulated.buffer,
+                emulated.range.clone(),
+                restart,
+                state.general.snatch_guard,
+            )?;
+            state.index_expansions.push(expansion);
+            let index = state.index_expansions.len() - 1;
+            emulated.expansions[usize::from(restart)] = Some(index);
+            index
+        }
+    };
+    unsafe {
+        hal::DynCommandEncoder::set_index_buffer(
+            state.general.raw_encoder,
+            state.index_expansions[index].index_buffer(),
+            IndexFormat::Uint16,
+        );
+    }
+    emulated.bound = Some(restart);
+    Ok(())
+}
+
 // This function is duplicative of `render::set_vertex_buffer`.
 fn set_vertex_buffer(
     state: &mut State,
@@ -2425,6 +3889,7 @@ fn set_vertex_buffer(
         .binding(offset, size, state.general.snatch_guard)
         .map_err(RenderCommandError::from)?;
     state.vertex.buffer_sizes[slot as usize] = Some(buffer_size);
+    state.vertex.buffers[slot as usize] = Some((buffer.clone(), offset));
 
     state.general.buffer_memory_init_actions.extend(
         buffer.initialization_status.read().create_action(
@@ -2472,13 +3937,42 @@ fn set_stencil_reference(state: &mut State, value: u32) {
     }
 }
 
+fn set_shading_rate(state: &mut State, rate: wgt::ShadingRate) -> Result<(), RenderPassErrorInner> {
+    api_log!("RenderPass::set_shading_rate {rate:?}");
+
+    state
+        .general
+        .device
+        .require_features(wgt::Features::VARIABLE_RATE_SHADING)?;
+
+    unsafe {
+        state.general.raw_encoder.set_shading_rate(rate);
+    }
+    Ok(())
+}
+
 fn set_viewport(
     state: &mut State,
+    index: Option<u32>,
     rect: Rect<f32>,
     depth_min: f32,
     depth_max: f32,
 ) -> Result<(), RenderPassErrorInner> {
-    api_log!("RenderPass::set_viewport {rect:?}");
+    api_log!("RenderPass::set_viewport {index:?} {rect:?}");
+
+    if let Some(index) = index {
+        state
+            .general
+            .device
+            .require_features(wgt::Features::MULTI_VIEWPORT)?;
+        if index >= wgt::MAX_VIEWPORTS {
+            return Err(RenderCommandError::ViewportIndexOutOfRange {
+                index,
+                max: wgt::MAX_VIEWPORTS,
+            }
+            .into());
+        }
+    }
 
     if rect.w < 0.0
         || rect.h < 0.0
@@ -2510,6 +4004,9 @@ fn set_viewport(
     if !(0.0..=1.0).contains(&depth_min) || !(0.0..=1.0).contains(&depth_max) {
         return Err(RenderCommandError::InvalidViewportDepth(depth_min, depth_max).into());
     }
+    if index.unwrap_or(0) == 0 {
+        state.viewport_size = [rect.w, rect.h];
+    }
     let r = hal::Rect {
         x: rect.x,
         y: rect.y,
@@ -2517,10 +4014,18 @@ fn set_viewport(
         h: rect.h,
     };
     unsafe {
-        state
-            .general
-            .raw_encoder
-            .set_viewport(&r, depth_min..depth_max);
+        match index {
+            Some(index) => {
+                state
+                    .general
+                    .raw_encoder
+                    .set_viewport_indexed(index, &r, depth_min..depth_max)
+            }
+            None => state
+                .general
+                .raw_encoder
+                .set_viewport(&r, depth_min..depth_max),
+        }
     }
     Ok(())
 }
@@ -2551,10 +4056,10 @@ fn draw(
     instance_count: u32,
     first_vertex: u32,
     first_instance: u32,
-) -> Result<(), DrawError> {
+) -> Result<(), RenderPassErrorInner> {
     api_log!("RenderPass::draw {vertex_count} {instance_count} {first_vertex} {first_instance}");
 
-    state.is_ready(false)?;
+    state.is_ready(DrawCommandFamily::Draw)?;
 
     state
         .vertex
@@ -2565,16 +4070,110 @@ fn draw(
         .limits
         .validate_instance_limit(first_instance, instance_count)?;
 
+    if instance_count == 0 || vertex_count == 0 {
+        return Ok(());
+    }
+
+    let pipeline = state.pipeline.as_ref().unwrap();
+    if pipeline.wide_lines.is_some() {
+        let pipeline = pipeline.clone();
+        return draw_wide_lines(
+            state,
+            &pipeline,
+            vertex_count,
+            instance_count,
+            first_vertex,
+            first_instance,
+        );
+    }
+
     unsafe {
-        if instance_count > 0 && vertex_count > 0 {
+        state
+            .general
+            .raw_encoder
+            .draw(first_vertex, vertex_count, first_instance, instance_count);
+    }
+    Ok(())
+}
+
+/// Draws each segment of the lines as a quad, for a pipeline that emulates wide lines.
+///
+/// See [`crate::wide_lines`] for how the segments are expanded.
+fn draw_wide_lines(
+    state: &mut State,
+    pipeline: &RenderPipeline,
+    vertex_count: u32,
+    instance_count: u32,
+    first_vertex: u32,
+    first_instance: u32,
+) -> Result<(), RenderPassErrorInner> {
+    let wide_lines = pipeline.wide_lines.as_ref().unwrap();
+    let segment_count = wide_lines.segment_count(vertex_count);
+    if segment_count == 0 {
+        return Ok(());
+    }
+
+    // Bind each buffer at the first vertex of the first segment for its first end,
+    // and one vertex later for its second end.
+    let buffer_count = pipeline.vertex_steps.len();
+    for (slot, step) in pipeline.vertex_steps.iter().enumerate() {
+        let (ref buffer, offset) = *state.vertex.buffers[slot].as_ref().unwrap();
+        let size = state.vertex.buffer_sizes[slot].unwrap();
+        let raw = buffer.try_raw(state.general.snatch_guard)?;
+        for (end, end_slot) in [(0, slot), (1, slot + buffer_count)] {
+            let start = (first_vertex as u64 + end) * step.stride;
+            // The vertex limits were validated, so this only skips buffers without
+            // attributes.
+            let Some(end_size) = BufferSize::new(size.saturating_sub(start)) else {
+                continue;
+            };
+            let binding = hal::BufferBinding::new_unchecked(raw, offset + start, end_size);
+            unsafe {
+                state
+                    .general
+                    .raw_encoder
+                    .set_vertex_buffer(end_slot as u32, binding);
+            }
+        }
+    }
+
+    for instance_index in (0..instance_count).map(|i| first_instance.wrapping_add(i)) {
+        let push_constants = crate::wide_lines::WideLines::push_constants(
+            state.viewport_size,
+            first_vertex,
+            instance_index,
+        );
+        unsafe {
+            state.general.raw_encoder.set_push_constants(
+                pipeline.layout.raw(),
+                ShaderStages::VERTEX,
+                0,
+                &push_constants,
+            );
             state.general.raw_encoder.draw(
-                first_vertex,
-                vertex_count,
-                first_instance,
-                instance_count,
+                0,
+                crate::wide_lines::QUAD_VERTEX_COUNT,
+                0,
+                segment_count,
             );
         }
     }
+
+    // Restore the bindings of the pass for the following draws.
+    for slot in 0..buffer_count * 2 {
+        let Some((ref buffer, offset)) = state.vertex.buffers[slot] else {
+            continue;
+        };
+        let raw = buffer.try_raw(state.general.snatch_guard)?;
+        let size = state.vertex.buffer_sizes[slot].and_then(BufferSize::new);
+        let binding = hal::BufferBinding::new_unchecked(raw, offset, size);
+        unsafe {
+            state
+                .general
+                .raw_encoder
+                .set_vertex_buffer(slot as u32, binding);
+        }
+    }
     Ok(())
 }
 
@@ -2585,12 +4184,13 @@ fn draw_indexed(
     first_index: u32,
     base_vertex: i32,
     first_instance: u32,
-) -> Result<(), DrawError> {
+) -> Result<(), RenderPassErrorInner> {
     api_log!(
         "RenderPass::draw_indexed {index_count} {instance_count} {first_index} {base_vertex} {first_instance}"
     );
 
-    state.is_ready(true)?;
+    state.is_ready(DrawCommandFamily::DrawIndexed)?;
+    state.check_native_lines()?;
 
     let last_index = first_index as u64 + index_count as u64;
     let index_limit = state.index.limit;
@@ -2598,27 +4198,153 @@ fn draw_indexed(
         return Err(DrawError::IndexBeyondLimit {
             last_index,
             index_limit,
-        });
+        }
+        .into());
     }
     state
         .vertex
         .limits
         .validate_instance_limit(first_instance, instance_count)?;
 
+    if instance_count == 0 || index_count == 0 {
+        return Ok(());
+    }
+
+    bind_expanded_index_buffer(state)?;
+
     unsafe {
-        if instance_count > 0 && index_count > 0 {
-            state.general.raw_encoder.draw_indexed(
-                first_index,
-                index_count,
-                base_vertex,
-                first_instance,
-                instance_count,
-            );
-        }
+        state.general.raw_encoder.draw_indexed(
+            first_index,
+            index_count,
+            base_vertex,
+            first_instance,
+            instance_count,
+        );
     }
     Ok(())
 }
 
+fn draw_indirect(
+    raw_encoder: &mut dyn hal::DynCommandEncoder,
+    indexed: bool,
+    indirect_buffer: &dyn hal::DynBuffer,
+    offset: u64,
+    count: u32,
+) {
+    match indexed {
+        false => unsafe {
+            raw_encoder.draw_indirect(indirect_buffer, offset, count);
+        },
+        true => unsafe {
+            raw_encoder.draw_indexed_indirect(indirect_buffer, offset, count);
+        },
+    }
+}
+
+struct IndirectDrawData {
+    buffer_index: usize,
+    offset: u64,
+    count: u32,
+}
+
+/// Issues indirect draws whose arguments are validated by the
+/// indirect validation pass injected before the render pass.
+struct IndirectDrawContext<'a> {
+    raw_encoder: &'a mut dyn hal::DynCommandEncoder,
+    device: &'a Device,
+
+    indirect_draw_validation_resources: &'a mut crate::indirect_validation::DrawResources,
+    indirect_draw_validation_batcher: &'a mut crate::indirect_validation::DrawBatcher,
+
+    indirect_buffer: Arc<crate::resource::Buffer>,
+    indexed: bool,
+    vertex_or_index_limit: u64,
+    instance_limit: u64,
+    count: Option<crate::indirect_validation::DrawCount>,
+}
+
+impl<'a> IndirectDrawContext<'a> {
+    fn new(
+        state: &'a mut State,
+        indirect_draw_validation_resources: &'a mut crate::indirect_validation::DrawResources,
+        indirect_draw_validation_batcher: &'a mut crate::indirect_validation::DrawBatcher,
+        indirect_buffer: Arc<crate::resource::Buffer>,
+        indexed: bool,
+        count: Option<crate::indirect_validation::DrawCount>,
+    ) -> Self {
+        Self {
+            raw_encoder: state.general.raw_encoder,
+            device: state.general.device,
+            indirect_draw_validation_resources,
+            indirect_draw_validation_batcher,
+            indirect_buffer,
+            indexed,
+            vertex_or_index_limit: if indexed {
+                state.index.limit
+            } else {
+                state.vertex.limits.vertex_limit
+            },
+            instance_limit: state.vertex.limits.indirect_instance_limit(),
+            count,
+        }
+    }
+
+    fn add(&mut self, offset: u64, draw_index: u32) -> Result<IndirectDrawData, DeviceError> {
+        let (dst_resource_index, dst_offset) = self.indirect_draw_validation_batcher.add(
+            self.indirect_draw_validation_resources,
+            self.device,
+            &self.indirect_buffer,
+            offset,
+            self.indexed,
+            self.vertex_or_index_limit,
+            self.instance_limit,
+            self.count.as_ref().map(|count| (count, draw_index)),
+        )?;
+        Ok(IndirectDrawData {
+            buffer_index: dst_resource_index,
+            offset: dst_offset,
+            count: 1,
+        })
+    }
+
+    fn draw(&mut self, draw_data: IndirectDrawData) {
+        let dst_buffer = self
+            .indirect_draw_validation_resources
+            .get_dst_buffer(draw_data.buffer_index);
+        draw_indirect(
+            self.raw_encoder,
+            self.indexed,
+            dst_buffer,
+            draw_data.offset,
+            draw_data.count,
+        );
+    }
+
+    /// Validates `count` consecutive draws starting at `offset`,
+    /// merging the ones that end up next to each other into a single multi-draw.
+    fn draw_validated(&mut self, offset: u64, stride: u64, count: u32) -> Result<(), DeviceError> {
+        let mut current_draw_data = self.add(offset, 0)?;
+
+        for i in 1..count {
+            let draw_data = self.add(offset + stride * i as u64, i)?;
+
+            if draw_data.buffer_index == current_draw_data.buffer_index {
+                debug_assert_eq!(
+                    draw_data.offset,
+                    current_draw_data.offset + stride * current_draw_data.count as u64
+                );
+                current_draw_data.count += 1;
+            } else {
+                self.draw(current_draw_data);
+                current_draw_data = draw_data;
+            }
+        }
+
+        self.draw(current_draw_data);
+        Ok(())
+    }
+}
+
 fn multi_draw_indirect(
     state: &mut State,
     indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
@@ -2634,7 +4360,11 @@ fn multi_draw_indirect(
         indirect_buffer.error_ident()
     );
 
-    state.is_ready(indexed)?;
+    state.is_ready(DrawCommandFamily::vertex(indexed))?;
+    state.check_native_lines()?;
+    if indexed {
+        bind_expanded_index_buffer(state)?;
+    }
 
     if count != 1 {
         state
@@ -2676,23 +4406,6 @@ fn multi_draw_indirect(
         ),
     );
 
-    fn draw(
-        raw_encoder: &mut dyn hal::DynCommandEncoder,
-        indexed: bool,
-        indirect_buffer: &dyn hal::DynBuffer,
-        offset: u64,
-        count: u32,
-    ) {
-        match indexed {
-            false => unsafe {
-                raw_encoder.draw_indirect(indirect_buffer, offset, count);
-            },
-            true => unsafe {
-                raw_encoder.draw_indexed_indirect(indirect_buffer, offset, count);
-            },
-        }
-    }
-
     if state.general.device.indirect_validation.is_some() {
         state
             .general
@@ -2700,109 +4413,274 @@ fn multi_draw_indirect(
             .buffers
             .merge_single(&indirect_buffer, wgt::BufferUses::STORAGE_READ_ONLY)?;
 
-        struct DrawData {
-            buffer_index: usize,
-            offset: u64,
-            count: u32,
-        }
+        let mut draw_ctx = IndirectDrawContext::new(
+            state,
+            indirect_draw_validation_resources,
+            indirect_draw_validation_batcher,
+            indirect_buffer,
+            indexed,
+            None,
+        );
+        draw_ctx.draw_validated(offset, stride, count)?;
+    } else {
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&indirect_buffer, wgt::BufferUses::INDIRECT)?;
 
-        struct DrawContext<'a> {
-            raw_encoder: &'a mut dyn hal::DynCommandEncoder,
-            device: &'a Device,
+        draw_indirect(
+            state.general.raw_encoder,
+            indexed,
+            indirect_buffer.try_raw(state.general.snatch_guard)?,
+            offset,
+            count,
+        );
+    };
 
-            indirect_draw_validation_resources: &'a mut crate::indirect_validation::DrawResources,
-            indirect_draw_validation_batcher: &'a mut crate::indirect_validation::DrawBatcher,
+    Ok(())
+}
 
-            indirect_buffer: Arc<crate::resource::Buffer>,
-            indexed: bool,
-            vertex_or_index_limit: u64,
-            instance_limit: u64,
-        }
+fn multi_draw_indirect_count(
+    state: &mut State,
+    indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
+    indirect_draw_validation_batcher: &mut crate::indirect_validation::DrawBatcher,
+    cmd_buf: &Arc<CommandBuffer>,
+    indirect_buffer: Arc<crate::resource::Buffer>,
+    offset: u64,
+    count_buffer: Arc<crate::resource::Buffer>,
+    count_buffer_offset: u64,
+    max_count: u32,
+    indexed: bool,
+) -> Result<(), RenderPassErrorInner> {
+    api_log!(
+        "RenderPass::multi_draw_indirect_count (indexed:{indexed}) {} {offset} {} {count_buffer_offset:?} {max_count:?}",
+        indirect_buffer.error_ident(),
+        count_buffer.error_ident()
+    );
 
-        impl<'a> DrawContext<'a> {
-            fn add(&mut self, offset: u64) -> Result<DrawData, DeviceError> {
-                let (dst_resource_index, dst_offset) = self.indirect_draw_validation_batcher.add(
-                    self.indirect_draw_validation_resources,
-                    self.device,
-                    &self.indirect_buffer,
-                    offset,
-                    self.indexed,
-                    self.vertex_or_index_limit,
-                    self.instance_limit,
-                )?;
-                Ok(DrawData {
-                    buffer_index: dst_resource_index,
-                    offset: dst_offset,
-                    count: 1,
-                })
-            }
-            fn draw(&mut self, draw_data: DrawData) {
-                let dst_buffer = self
-                    .indirect_draw_validation_resources
-                    .get_dst_buffer(draw_data.buffer_index);
-                draw(
-                    self.raw_encoder,
-                    self.indexed,
-                    dst_buffer,
-                    draw_data.offset,
-                    draw_data.count,
-                );
-            }
+    state.is_ready(DrawCommandFamily::vertex(indexed))?;
+    state.check_native_lines()?;
+    if indexed {
+        bind_expanded_index_buffer(state)?;
+    }
+
+    let stride = get_stride_of_indirect_args(indexed);
+
+    state
+        .general
+        .device
+        .require_features(wgt::Features::MULTI_DRAW_INDIRECT_COUNT)?;
+    state
+        .general
+        .device
+        .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)?;
+
+    indirect_buffer.same_device_as(cmd_buf.as_ref())?;
+    count_buffer.same_device_as(cmd_buf.as_ref())?;
+
+    indirect_buffer.check_usage(BufferUsages::INDIRECT)?;
+    indirect_buffer.check_destroyed(state.general.snatch_guard)?;
+    count_buffer.check_usage(BufferUsages::INDIRECT)?;
+    count_buffer.check_destroyed(state.general.snatch_guard)?;
+
+    if offset % 4 != 0 {
+        return Err(RenderPassErrorInner::UnalignedIndirectBufferOffset(offset));
+    }
+    if count_buffer_offset % 4 != 0 {
+        return Err(RenderPassErrorInner::UnalignedIndirectBufferOffset(
+            count_buffer_offset,
+        ));
+    }
+
+    let end_offset = offset + stride * max_count as u64;
+    if end_offset > indirect_buffer.size {
+        return Err(RenderPassErrorInner::IndirectBufferOverrun {
+            count: 1,
+            offset,
+            end_offset,
+            buffer_size: indirect_buffer.size,
+        });
+    }
+    state.general.buffer_memory_init_actions.extend(
+        indirect_buffer.initialization_status.read().create_action(
+            &indirect_buffer,
+            offset..end_offset,
+            MemoryInitKind::NeedsInitializedMemory,
+        ),
+    );
+
+    let begin_count_offset = count_buffer_offset;
+    let end_count_offset = count_buffer_offset + 4;
+    if end_count_offset > count_buffer.size {
+        return Err(RenderPassErrorInner::IndirectCountBufferOverrun {
+            begin_count_offset,
+            end_count_offset,
+            count_buffer_size: count_buffer.size,
+        });
+    }
+    state.general.buffer_memory_init_actions.extend(
+        count_buffer.initialization_status.read().create_action(
+            &count_buffer,
+            count_buffer_offset..end_count_offset,
+            MemoryInitKind::NeedsInitializedMemory,
+        ),
+    );
+
+    if state.general.device.indirect_validation.is_some() {
+        // The validation pass zeroes out every draw at or past the count, so we can
+        // issue all `max_count` draws. This is also how the feature is emulated on
+        // backends without native support for it.
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&indirect_buffer, wgt::BufferUses::STORAGE_READ_ONLY)?;
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&count_buffer, wgt::BufferUses::STORAGE_READ_ONLY)?;
+
+        if max_count == 0 {
+            return Ok(());
         }
 
-        let mut draw_ctx = DrawContext {
-            raw_encoder: state.general.raw_encoder,
-            device: state.general.device,
+        let count =
+            indirect_draw_validation_batcher.begin_count(&count_buffer, count_buffer_offset);
+        let mut draw_ctx = IndirectDrawContext::new(
+            state,
             indirect_draw_validation_resources,
             indirect_draw_validation_batcher,
             indirect_buffer,
             indexed,
-            vertex_or_index_limit: if indexed {
-                state.index.limit
-            } else {
-                state.vertex.limits.vertex_limit
+            Some(count),
+        );
+        draw_ctx.draw_validated(offset, stride, max_count)?;
+    } else {
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&indirect_buffer, wgt::BufferUses::INDIRECT)?;
+        state
+            .general
+            .scope
+            .buffers
+            .merge_single(&count_buffer, wgt::BufferUses::INDIRECT)?;
+
+        let indirect_raw = indirect_buffer.try_raw(state.general.snatch_guard)?;
+        let count_raw = count_buffer.try_raw(state.general.snatch_guard)?;
+
+        match indexed {
+            false => unsafe {
+                state.general.raw_encoder.draw_indirect_count(
+                    indirect_raw,
+                    offset,
+                    count_raw,
+                    count_buffer_offset,
+                    max_count,
+                );
             },
-            instance_limit: state.vertex.limits.instance_limit,
-        };
+            true => unsafe {
+                state.general.raw_encoder.draw_indexed_indirect_count(
+                    indirect_raw,
+                    offset,
+                    count_raw,
+                    count_buffer_offset,
+                    max_count,
+                );
+            },
+        }
+    }
+    Ok(())
+}
 
-        let mut current_draw_data = draw_ctx.add(offset)?;
+fn draw_mesh_tasks(
+    state: &mut State,
+    group_count_x: u32,
+    group_count_y: u32,
+    group_count_z: u32,
+) -> Result<(), DrawError> {
+    api_log!("RenderPass::draw_mesh_tasks {group_count_x} {group_count_y} {group_count_z}");
 
-        for i in 1..count {
-            let draw_data = draw_ctx.add(offset + stride * i as u64)?;
+    state.is_ready(DrawCommandFamily::DrawMeshTasks)?;
 
-            if draw_data.buffer_index == current_draw_data.buffer_index {
-                debug_assert_eq!(
-                    draw_data.offset,
-                    current_draw_data.offset + stride * current_draw_data.count as u64
-                );
-                current_draw_data.count += 1;
-            } else {
-                draw_ctx.draw(current_draw_data);
-                current_draw_data = draw_data;
-            }
+    unsafe {
+        if group_count_x > 0 && group_count_y > 0 && group_count_z > 0 {
+            state
+                .general
+                .raw_encoder
+                .draw_mesh_tasks(group_count_x, group_count_y, group_count_z);
         }
+    }
+    Ok(())
+}
+
+fn multi_draw_mesh_tasks_indirect(
+    state: &mut State,
+    cmd_buf: &Arc<CommandBuffer>,
+    indirect_buffer: Arc<crate::resource::Buffer>,
+    offset: u64,
+    count: u32,
+) -> Result<(), RenderPassErrorInner> {
+    api_log!(
+        "RenderPass::draw_mesh_tasks_indirect {} {offset} {count:?}",
+        indirect_buffer.error_ident()
+    );
+
+    state.is_ready(DrawCommandFamily::DrawMeshTasks)?;
+
+    if count != 1 {
+        state
+            .general
+            .device
+            .require_features(wgt::Features::MULTI_DRAW_INDIRECT)?;
+    }
+
+    indirect_buffer.same_device_as(cmd_buf.as_ref())?;
+    indirect_buffer.check_usage(BufferUsages::INDIRECT)?;
+
+    state
+        .general
+        .scope
+        .buffers
+        .merge_single(&indirect_buffer, wgt::BufferUses::INDIRECT)?;
+    let indirect_raw = indirect_buffer.try_raw(state.general.snatch_guard)?;
+
+    if offset % 4 != 0 {
+        return Err(RenderPassErrorInner::UnalignedIndirectBufferOffset(offset));
+    }
+
+    let stride = MESH_TASKS_INDIRECT_ARGS_STRIDE;
+    let end_offset = offset + stride * count as u64;
+    if end_offset > indirect_buffer.size {
+        return Err(RenderPassErrorInner::IndirectBufferOverrun {
+            count,
+            offset,
+            end_offset,
+            buffer_size: indirect_buffer.size,
+        });
+    }
 
-        draw_ctx.draw(current_draw_data);
-    } else {
+    state.general.buffer_memory_init_actions.extend(
+        indirect_buffer.initialization_status.read().create_action(
+            &indirect_buffer,
+            offset..end_offset,
+            MemoryInitKind::NeedsInitializedMemory,
+        ),
+    );
+
+    unsafe {
         state
             .general
-            .scope
-            .buffers
-            .merge_single(&indirect_buffer, wgt::BufferUses::INDIRECT)?;
-
-        draw(
-            state.general.raw_encoder,
-            indexed,
-            indirect_buffer.try_raw(state.general.snatch_guard)?,
-            offset,
-            count,
-        );
-    };
-
+            .raw_encoder
+            .draw_mesh_tasks_indirect(indirect_raw, offset, count);
+    }
     Ok(())
 }
 
-fn multi_draw_indirect_count(
+fn multi_draw_mesh_tasks_indirect_count(
     state: &mut State,
     cmd_buf: &Arc<CommandBuffer>,
     indirect_buffer: Arc<crate::resource::Buffer>,
@@ -2810,26 +4688,19 @@ fn multi_draw_indirect_count(
     count_buffer: Arc<crate::resource::Buffer>,
     count_buffer_offset: u64,
     max_count: u32,
-    indexed: bool,
 ) -> Result<(), RenderPassErrorInner> {
     api_log!(
-        "RenderPass::multi_draw_indirect_count (indexed:{indexed}) {} {offset} {} {count_buffer_offset:?} {max_count:?}",
+        "RenderPass::multi_draw_mesh_tasks_indirect_count {} {offset} {} {count_buffer_offset:?} {max_count:?}",
         indirect_buffer.error_ident(),
         count_buffer.error_ident()
     );
 
-    state.is_ready(indexed)?;
-
-    let stride = get_stride_of_indirect_args(indexed);
+    state.is_ready(DrawCommandFamily::DrawMeshTasks)?;
 
     state
         .general
         .device
         .require_features(wgt::Features::MULTI_DRAW_INDIRECT_COUNT)?;
-    state
-        .general
-        .device
-        .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)?;
 
     indirect_buffer.same_device_as(cmd_buf.as_ref())?;
     count_buffer.same_device_as(cmd_buf.as_ref())?;
@@ -2856,6 +4727,7 @@ fn multi_draw_indirect_count(
         return Err(RenderPassErrorInner::UnalignedIndirectBufferOffset(offset));
     }
 
+    let stride = MESH_TASKS_INDIRECT_ARGS_STRIDE;
     let end_offset = offset + stride * max_count as u64;
     if end_offset > indirect_buffer.size {
         return Err(RenderPassErrorInner::IndirectBufferOverrun {
@@ -2890,25 +4762,102 @@ fn multi_draw_indirect_count(
         ),
     );
 
-    match indexed {
-        false => unsafe {
-            state.general.raw_encoder.draw_indirect_count(
-                indirect_raw,
-                offset,
-                count_raw,
-                count_buffer_offset,
-                max_count,
-            );
-        },
-        true => unsafe {
-            state.general.raw_encoder.draw_indexed_indirect_count(
-                indirect_raw,
-                offset,
-                count_raw,
-                count_buffer_offset,
-                max_count,
-            );
-        },
+    unsafe {
+        state.general.raw_encoder.draw_mesh_tasks_indirect_count(
+            indirect_raw,
+            offset,
+            count_raw,
+            count_buffer_offset,
+            max_count,
+        );
+    }
+    Ok(())
+}
+
+fn begin_conditional_block(
+    state: &mut State,
+    cmd_buf: &Arc<CommandBuffer>,
+    buffer: Arc<crate::resource::Buffer>,
+    offset: u64,
+) -> Result<(), RenderPassErrorInner> {
+    api_log!(
+        "RenderPass::begin_conditional_block {} {offset}",
+        buffer.error_ident()
+    );
+
+    if state.active_conditional_block {
+        return Err(RenderPassErrorInner::ConditionalBlockAlreadyActive);
+    }
+
+    buffer.same_device_as(cmd_buf.as_ref())?;
+    buffer.check_usage(BufferUsages::INDIRECT)?;
+    buffer.check_destroyed(state.general.snatch_guard)?;
+
+    if offset % 8 != 0 {
+        return Err(RenderPassErrorInner::UnalignedConditionalBlockOffset(
+            offset,
+        ));
+    }
+
+    let end_offset = offset + 8;
+    if end_offset > buffer.size {
+        return Err(RenderPassErrorInner::ConditionalBlockBufferOverrun {
+            offset,
+            buffer_size: buffer.size,
+        });
+    }
+
+    state.general.buffer_memory_init_actions.extend(
+        buffer.initialization_status.read().create_action(
+            &buffer,
+            offset..end_offset,
+            MemoryInitKind::NeedsInitializedMemory,
+        ),
+    );
+
+    state
+        .general
+        .scope
+        .buffers
+        .merge_single(&buffer, wgt::BufferUses::INDIRECT)?;
+
+    state.active_conditional_block = true;
+
+    // Without the feature, the block is validated but its draws always run.
+    if state
+        .general
+        .device
+        .features
+        .contains(wgt::Features::CONDITIONAL_RENDERING)
+    {
+        let raw = buffer.try_raw(state.general.snatch_guard)?;
+        unsafe {
+            state
+                .general
+                .raw_encoder
+                .begin_conditional_rendering(raw, offset);
+        }
+    }
+    Ok(())
+}
+
+fn end_conditional_block(state: &mut State) -> Result<(), RenderPassErrorInner> {
+    api_log!("RenderPass::end_conditional_block");
+
+    if !state.active_conditional_block {
+        return Err(RenderPassErrorInner::NoActiveConditionalBlock);
+    }
+    state.active_conditional_block = false;
+
+    if state
+        .general
+        .device
+        .features
+        .contains(wgt::Features::CONDITIONAL_RENDERING)
+    {
+        unsafe {
+            state.general.raw_encoder.end_conditional_rendering();
+        }
     }
     Ok(())
 }
@@ -2918,6 +4867,8 @@ fn execute_bundle(
     indirect_draw_validation_resources: &mut crate::indirect_validation::DrawResources,
     indirect_draw_validation_batcher: &mut crate::indirect_validation::DrawBatcher,
     cmd_buf: &Arc<CommandBuffer>,
+    occlusion_query_set: Option<&Arc<QuerySet>>,
+    pending_query_resets: &mut QueryResetMap,
     bundle: Arc<super::RenderBundle>,
 ) -> Result<(), RenderPassErrorInner> {
     api_log!("RenderPass::execute_bundle {}", bundle.error_ident());
@@ -2971,6 +4922,16 @@ fn execute_bundle(
             state.general.raw_encoder,
             indirect_draw_validation_resources,
             indirect_draw_validation_batcher,
+            BundlePassState {
+                cmd_buf,
+                occlusion_query_set,
+                query_sets: &mut state.general.tracker.query_sets,
+                pending_query_resets,
+                active_occlusion_query: &mut state.active_occlusion_query,
+                active_pipeline_statistics_query: &mut state.active_pipeline_statistics_query,
+                pass_pipeline_statistics_query: state.pass_pipeline_statistics_query,
+                push_constants: &state.push_constants,
+            },
             state.general.snatch_guard,
         )
     }
@@ -2982,6 +4943,8 @@ fn execute_bundle(
         ExecutionError::Unimplemented(what) => {
             RenderPassErrorInner::RenderCommand(RenderCommandError::Unimplemented(what))
         }
+        ExecutionError::Query(e) => RenderPassErrorInner::QueryUse(e),
+        ExecutionError::MissingOcclusionQuerySet => RenderPassErrorInner::MissingOcclusionQuerySet,
     })?;
 
     unsafe {
@@ -3163,6 +5126,19 @@ impl Global {
         Ok(())
     }
 
+    pub fn render_pass_set_shading_rate(
+        &self,
+        pass: &mut RenderPass,
+        rate: wgt::ShadingRate,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::SetShadingRate;
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::SetShadingRate(rate));
+
+        Ok(())
+    }
+
     pub fn render_pass_set_viewport(
         &self,
         pass: &mut RenderPass,
@@ -3185,6 +5161,31 @@ impl Global {
         Ok(())
     }
 
+    pub fn render_pass_set_viewports(
+        &self,
+        pass: &mut RenderPass,
+        viewports: &[wgt::Viewport],
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::SetViewports;
+        let base = pass_base!(pass, scope);
+
+        for (index, viewport) in viewports.iter().enumerate() {
+            base.commands.push(ArcRenderCommand::SetIndexedViewport {
+                index: index as u32,
+                rect: Rect {
+                    x: viewport.x,
+                    y: viewport.y,
+                    w: viewport.width,
+                    h: viewport.height,
+                },
+                depth_min: viewport.min_depth,
+                depth_max: viewport.max_depth,
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn render_pass_set_scissor_rect(
         &self,
         pass: &mut RenderPass,
@@ -3467,6 +5468,102 @@ impl Global {
         Ok(())
     }
 
+    pub fn render_pass_draw_mesh_tasks(
+        &self,
+        pass: &mut RenderPass,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::Draw {
+            kind: DrawKind::DrawMeshTasks,
+            indexed: false,
+        };
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::DrawMeshTasks {
+            group_count_x,
+            group_count_y,
+            group_count_z,
+        });
+
+        Ok(())
+    }
+
+    pub fn render_pass_draw_mesh_tasks_indirect(
+        &self,
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::Draw {
+            kind: DrawKind::DrawMeshTasksIndirect,
+            indexed: false,
+        };
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::DrawMeshTasksIndirect {
+            buffer: pass_try!(base, scope, self.resolve_render_pass_buffer_id(buffer_id)),
+            offset,
+            count: 1,
+        });
+
+        Ok(())
+    }
+
+    pub fn render_pass_multi_draw_mesh_tasks_indirect(
+        &self,
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count: u32,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::Draw {
+            kind: DrawKind::MultiDrawMeshTasksIndirect,
+            indexed: false,
+        };
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::DrawMeshTasksIndirect {
+            buffer: pass_try!(base, scope, self.resolve_render_pass_buffer_id(buffer_id)),
+            offset,
+            count,
+        });
+
+        Ok(())
+    }
+
+    pub fn render_pass_multi_draw_mesh_tasks_indirect_count(
+        &self,
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::Draw {
+            kind: DrawKind::MultiDrawMeshTasksIndirectCount,
+            indexed: false,
+        };
+        let base = pass_base!(pass, scope);
+
+        base.commands
+            .push(ArcRenderCommand::MultiDrawMeshTasksIndirectCount {
+                buffer: pass_try!(base, scope, self.resolve_render_pass_buffer_id(buffer_id)),
+                offset,
+                count_buffer: pass_try!(
+                    base,
+                    scope,
+                    self.resolve_render_pass_buffer_id(count_buffer_id)
+                ),
+                count_buffer_offset,
+                max_count,
+            });
+
+        Ok(())
+    }
+
     pub fn render_pass_push_debug_group(
         &self,
         pass: &mut RenderPass,
@@ -3595,6 +5692,35 @@ impl Global {
         Ok(())
     }
 
+    pub fn render_pass_begin_conditional_block(
+        &self,
+        pass: &mut RenderPass,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::BeginConditionalBlock;
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::BeginConditionalBlock {
+            buffer: pass_try!(base, scope, self.resolve_render_pass_buffer_id(buffer_id)),
+            offset,
+        });
+
+        Ok(())
+    }
+
+    pub fn render_pass_end_conditional_block(
+        &self,
+        pass: &mut RenderPass,
+    ) -> Result<(), PassStateError> {
+        let scope = PassErrorScope::EndConditionalBlock;
+        let base = pass_base!(pass, scope);
+
+        base.commands.push(ArcRenderCommand::EndConditionalBlock);
+
+        Ok(())
+    }
+
     pub fn render_pass_execute_bundles(
         &self,
         pass: &mut RenderPass,
@@ -3616,6 +5742,61 @@ impl Global {
 
         Ok(())
     }
+
+    /// Creates a parallel encoder of `pass`: a render pass without attachments
+    /// of its own, whose commands are executed at this point of `pass`.
+    ///
+    /// The encoder can record commands on another thread while `pass` keeps
+    /// recording, and must be ended with [`Global::render_pass_end`] before
+    /// `pass` is. Its commands are validated and encoded into a hal command
+    /// encoder of its own when it is ended, on the thread that ends it. The
+    /// commands `pass` records before and after its parallel encoders are
+    /// parts of their own.
+    ///
+    /// Each part starts without a pipeline, bind groups or buffers, and with
+    /// the default viewport, scissor rectangle, blend constant and stencil
+    /// reference.
+    ///
+    /// If the encoder can't be created, the error is stored in `pass`, and the
+    /// returned encoder is invalid.
+    pub fn render_pass_create_parallel_encoder(
+        &self,
+        pass: &mut RenderPass,
+    ) -> (RenderPass, Option<PassStateError>) {
+        let scope = PassErrorScope::CreateParallelEncoder;
+        api_log!("RenderPass::create_parallel_encoder");
+
+        let label = pass.base.label.clone().map(Cow::Owned);
+        let Some(cmd_buf) = pass.parent.clone() else {
+            return (
+                RenderPass::new_parallel_encoder(None, BasePass::new(&label), None),
+                Some(EncoderStateError::Ended.map_pass_err(scope)),
+            );
+        };
+
+        let encoder = if pass.base.error.is_none() {
+            match pass.create_parallel_encoder(&cmd_buf).map_pass_err(scope) {
+                Ok(encoder) => Some(encoder),
+                Err(err) => {
+                    pass.base.error = Some(err);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        pass.current_pipeline.reset();
+        pass.current_bind_groups.reset();
+
+        let base = match pass.base.error {
+            Some(ref err) => BasePass::new_invalid(&label, err.clone()),
+            None => BasePass::new(&label),
+        };
+        (
+            RenderPass::new_parallel_encoder(Some(cmd_buf), base, encoder),
+            None,
+        )
+    }
 }
 
 pub(crate) const fn get_stride_of_indirect_args(indexed: bool) -> u64 {
@@ -3624,3 +5805,7 @@ pub(crate) const fn get_stride_of_indirect_args(indexed: bool) -> u64 {
         true => size_of::<wgt::DrawIndexedIndirectArgs>() as u64,
     }
 }
+
+/// Indirect mesh task draws take a workgroup count, laid out like the arguments
+/// of an indirect dispatch.
+const MESH_TASKS_INDIRECT_ARGS_STRIDE: u64 = size_of::<wgt::DispatchIndirectArgs>() as u64;
@@ -34,12 +34,21 @@ pub enum RenderCommand {
     },
     SetBlendConstant(Color),
     SetStencilReference(u32),
+    SetShadingRate(wgt::ShadingRate),
     SetViewport {
         rect: Rect<f32>,
         //TODO: use half-float to reduce the size?
         depth_min: f32,
         depth_max: f32,
     },
+    /// Set the viewport at `index`. Recorded once per viewport by
+    /// [`wgpu::RenderPass::set_viewports`].
+    SetIndexedViewport {
+        index: u32,
+        rect: Rect<f32>,
+        depth_min: f32,
+        depth_max: f32,
+    },
     SetScissor(Rect<u32>),
 
     /// Set a range of push constants to values stored in [`BasePass::push_constant_data`].
@@ -96,6 +105,23 @@ pub enum RenderCommand {
         max_count: u32,
         indexed: bool,
     },
+    DrawMeshTasks {
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    },
+    DrawMeshTasksIndirect {
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count: u32,
+    },
+    MultiDrawMeshTasksIndirectCount {
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        count_buffer_id: id::BufferId,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    },
     PushDebugGroup {
         color: u32,
         len: usize,
@@ -118,6 +144,11 @@ pub enum RenderCommand {
         query_index: u32,
     },
     EndPipelineStatisticsQuery,
+    BeginConditionalBlock {
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    },
+    EndConditionalBlock,
     ExecuteBundle(id::RenderBundleId),
 }
 
@@ -231,6 +262,20 @@ impl RenderCommand {
                             ArcRenderCommand::EndPipelineStatisticsQuery
                         }
 
+                        RenderCommand::BeginConditionalBlock { buffer_id, offset } => {
+                            ArcRenderCommand::BeginConditionalBlock {
+                                buffer: buffers_guard.get(buffer_id).get().map_err(|e| {
+                                    RenderPassError {
+                                        scope: PassErrorScope::BeginConditionalBlock,
+                                        inner: e.into(),
+                                    }
+                                })?,
+                                offset,
+                            }
+                        }
+
+                        RenderCommand::EndConditionalBlock => ArcRenderCommand::EndConditionalBlock,
+
                         RenderCommand::SetIndexBuffer {
                             buffer_id,
                             index_format,
@@ -273,6 +318,10 @@ impl RenderCommand {
                             ArcRenderCommand::SetStencilReference(reference)
                         }
 
+                        RenderCommand::SetShadingRate(shading_rate) => {
+                            ArcRenderCommand::SetShadingRate(shading_rate)
+                        }
+
                         RenderCommand::SetViewport {
                             rect,
                             depth_min,
@@ -283,6 +332,18 @@ impl RenderCommand {
                             depth_max,
                         },
 
+                        RenderCommand::SetIndexedViewport {
+                            index,
+                            rect,
+                            depth_min,
+                            depth_max,
+                        } => ArcRenderCommand::SetIndexedViewport {
+                            index,
+                            rect,
+                            depth_min,
+                            depth_max,
+                        },
+
                         RenderCommand::SetScissor(scissor) => ArcRenderCommand::SetScissor(scissor),
 
                         RenderCommand::Draw {
@@ -370,6 +431,68 @@ impl RenderCommand {
                             }
                         }
 
+                        RenderCommand::DrawMeshTasks {
+                            group_count_x,
+                            group_count_y,
+                            group_count_z,
+                        } => ArcRenderCommand::DrawMeshTasks {
+                            group_count_x,
+                            group_count_y,
+                            group_count_z,
+                        },
+
+                        RenderCommand::DrawMeshTasksIndirect {
+                            buffer_id,
+                            offset,
+                            count,
+                        } => ArcRenderCommand::DrawMeshTasksIndirect {
+                            buffer: buffers_guard.get(buffer_id).get().map_err(|e| {
+                                RenderPassError {
+                                    scope: PassErrorScope::Draw {
+                                        kind: if count != 1 {
+                                            DrawKind::MultiDrawMeshTasksIndirect
+                                        } else {
+                                            DrawKind::DrawMeshTasksIndirect
+                                        },
+                                        indexed: false,
+                                    },
+                                    inner: e.into(),
+                                }
+                            })?,
+                            offset,
+                            count,
+                        },
+
+                        RenderCommand::MultiDrawMeshTasksIndirectCount {
+                            buffer_id,
+                            offset,
+                            count_buffer_id,
+                            count_buffer_offset,
+                            max_count,
+                        } => {
+                            let scope = PassErrorScope::Draw {
+                                kind: DrawKind::MultiDrawMeshTasksIndirectCount,
+                                indexed: false,
+                            };
+                            ArcRenderCommand::MultiDrawMeshTasksIndirectCount {
+                                buffer: buffers_guard.get(buffer_id).get().map_err(|e| {
+                                    RenderPassError {
+                                        scope,
+                                        inner: e.into(),
+                                    }
+                                })?,
+                                offset,
+                                count_buffer: buffers_guard.get(count_buffer_id).get().map_err(
+                                    |e| RenderPassError {
+                                        scope,
+                                        inner: e.into(),
+                                    },
+                                )?,
+                                count_buffer_offset,
+                                max_count,
+                            }
+                        }
+
                         RenderCommand::BeginOcclusionQuery { query_index } => {
                             ArcRenderCommand::BeginOcclusionQuery { query_index }
                         }
@@ -426,11 +549,18 @@ pub enum ArcRenderCommand {
     },
     SetBlendConstant(Color),
     SetStencilReference(u32),
+    SetShadingRate(wgt::ShadingRate),
     SetViewport {
         rect: Rect<f32>,
         depth_min: f32,
         depth_max: f32,
     },
+    SetIndexedViewport {
+        index: u32,
+        rect: Rect<f32>,
+        depth_min: f32,
+        depth_max: f32,
+    },
     SetScissor(Rect<u32>),
 
     /// Set a range of push constants to values stored in [`BasePass::push_constant_data`].
@@ -492,6 +622,23 @@ pub enum ArcRenderCommand {
         max_count: u32,
         indexed: bool,
     },
+    DrawMeshTasks {
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
+    },
+    DrawMeshTasksIndirect {
+        buffer: Arc<Buffer>,
+        offset: BufferAddress,
+        count: u32,
+    },
+    MultiDrawMeshTasksIndirectCount {
+        buffer: Arc<Buffer>,
+        offset: BufferAddress,
+        count_buffer: Arc<Buffer>,
+        count_buffer_offset: BufferAddress,
+        max_count: u32,
+    },
     PushDebugGroup {
         #[cfg_attr(not(any(feature = "serde", feature = "replay")), allow(dead_code))]
         color: u32,
@@ -516,5 +663,10 @@ pub enum ArcRenderCommand {
         query_index: u32,
     },
     EndPipelineStatisticsQuery,
+    BeginConditionalBlock {
+        buffer: Arc<Buffer>,
+        offset: BufferAddress,
+    },
+    EndConditionalBlock,
     ExecuteBundle(Arc<RenderBundle>),
 }
@@ -16,19 +16,20 @@ use crate::{
     conv,
     device::{Device, MissingDownlevelFlags},
     global::Global,
-    id::{BufferId, CommandEncoderId, TextureId},
+    id::{BufferId, CommandEncoderId, TextureId, TextureViewId},
     init_tracker::{
         MemoryInitKind, TextureInitRange, TextureInitTrackerAction,
         has_copy_partial_init_tracker_coverage,
     },
     resource::{
-        MissingBufferUsageError, MissingTextureUsageError, ParentDevice, RawResourceAccess,
-        Texture, TextureErrorDimension,
+        AliasedTextureConflictError, Labeled, MissingBufferUsageError, MissingTextureUsageError,
+        ParentDevice, RawResourceAccess, ResourceErrorIdent, Texture, TextureErrorDimension,
+        TextureView,
     },
     snatch::SnatchGuard,
 };
 
-use super::{ClearError, CommandBufferMutable};
+use super::{ClearError, CommandBufferMutable, memory_init::reset_aliased_textures};
 
 pub type TexelCopyBufferInfo = wgt::TexelCopyBufferInfo<BufferId>;
 pub type TexelCopyTextureInfo = wgt::TexelCopyTextureInfo<TextureId>;
@@ -50,6 +51,10 @@ pub enum TransferError {
     MissingBufferUsage(#[from] MissingBufferUsageError),
     #[error(transparent)]
     MissingTextureUsage(#[from] MissingTextureUsageError),
+    #[error(transparent)]
+    AliasedTextureConflict(#[from] AliasedTextureConflictError),
+    #[error("{0} shares memory with other textures and can't be written to from the queue")]
+    QueueWriteToAliasedTexture(ResourceErrorIdent),
     #[error(
         "Copy of {start_offset}..{end_offset} would end up overrunning the bounds of the {side:?} buffer of size {buffer_size}"
     )]
@@ -144,6 +149,29 @@ pub enum TransferError {
     },
     #[error("Requested mip level {requested} does no exist (count: {count})")]
     InvalidMipLevel { requested: u32, count: u32 },
+    #[error(
+        "The resolve source must be multisampled (has {src} samples) while the resolve destination must not be (has {dst} samples)"
+    )]
+    InvalidResolveSampleCounts { src: u32, dst: u32 },
+    #[error("Resolve source format ({src:?}) must match the resolve destination format ({dst:?})")]
+    MismatchedResolveFormats {
+        src: wgt::TextureFormat,
+        dst: wgt::TextureFormat,
+    },
+    #[error("Format {0:?} can't be resolved")]
+    UnsupportedResolveFormat(wgt::TextureFormat),
+    #[error("Resolve views must have a single mip level, but {0} has {1}")]
+    InvalidResolveMipLevelCount(ResourceErrorIdent, u32),
+    #[error(
+        "Resolve source has {src} array layers, but the resolve destination has {dst} array layers"
+    )]
+    ResolveLayerCountMismatch { src: u32, dst: u32 },
+    #[error("Resolve region {region:?} is out of the bounds of the {width}x{height} views")]
+    ResolveRegionOutOfBounds {
+        region: wgt::Rect,
+        width: u32,
+        height: u32,
+    },
 }
 
 impl WebGpuError for TransferError {
@@ -151,6 +179,7 @@ impl WebGpuError for TransferError {
         let e: &dyn WebGpuError = match self {
             Self::MissingBufferUsage(e) => e,
             Self::MissingTextureUsage(e) => e,
+            Self::AliasedTextureConflict(e) => e,
             Self::MemoryInitFailure(e) => e,
 
             Self::BufferOverrun { .. }
@@ -181,6 +210,13 @@ impl WebGpuError for TransferError {
             | Self::InvalidSampleCount { .. }
             | Self::SampleCountNotEqual { .. }
             | Self::InvalidMipLevel { .. }
+            | Self::InvalidResolveSampleCounts { .. }
+            | Self::MismatchedResolveFormats { .. }
+            | Self::UnsupportedResolveFormat(..)
+            | Self::InvalidResolveMipLevelCount(..)
+            | Self::ResolveLayerCountMismatch { .. }
+            | Self::ResolveRegionOutOfBounds { .. }
+            | Self::QueueWriteToAliasedTexture(..)
             | Self::SameSourceDestinationBuffer => return ErrorType::Validation,
         };
         e.webgpu_error_type()
@@ -433,22 +469,25 @@ pub(crate) fn validate_texture_copy_range<T>(
     Ok((copy_extent, array_layer_count))
 }
 
+fn copy_init_range(copy_texture: &TexelCopyTextureInfo, copy_size: &Extent3d) -> TextureInitRange {
+    TextureInitRange {
+        mip_range: copy_texture.mip_level..copy_texture.mip_level + 1,
+        layer_range: copy_texture.origin.z
+            ..(copy_texture.origin.z + copy_size.depth_or_array_layers),
+    }
+}
+
 fn handle_texture_init(
     init_kind: MemoryInitKind,
     cmd_buf_data: &mut CommandBufferMutable,
     device: &Device,
-    copy_texture: &TexelCopyTextureInfo,
-    copy_size: &Extent3d,
+    range: TextureInitRange,
     texture: &Arc<Texture>,
     snatch_guard: &SnatchGuard<'_>,
 ) -> Result<(), ClearError> {
     let init_action = TextureInitTrackerAction {
         texture: texture.clone(),
-        range: TextureInitRange {
-            mip_range: copy_texture.mip_level..copy_texture.mip_level + 1,
-            layer_range: copy_texture.origin.z
-                ..(copy_texture.origin.z + copy_size.depth_or_array_layers),
-        },
+        range,
         kind: init_kind,
     };
 
@@ -456,6 +495,16 @@ fn handle_texture_init(
     let immediate_inits = cmd_buf_data
         .texture_memory_actions
         .register_init_action(&{ init_action });
+    let alias_resets = cmd_buf_data.texture_memory_actions.take_alias_resets()?;
+    if !alias_resets.is_empty() {
+        let cmd_buf_raw = cmd_buf_data.encoder.open()?;
+        reset_aliased_textures(
+            alias_resets,
+            cmd_buf_raw,
+            &mut cmd_buf_data.trackers.textures,
+            snatch_guard,
+        )?;
+    }
 
     // In rare cases we may need to insert an init operation immediately onto the command buffer.
     if !immediate_inits.is_empty() {
@@ -495,8 +544,7 @@ fn handle_src_texture_init(
         MemoryInitKind::NeedsInitializedMemory,
         cmd_buf_data,
         device,
-        source,
-        copy_size,
+        copy_init_range(source, copy_size),
         texture,
         snatch_guard,
     )?;
@@ -533,8 +581,7 @@ fn handle_dst_texture_init(
         dst_init_kind,
         cmd_buf_data,
         device,
-        destination,
-        copy_size,
+        copy_init_range(destination, copy_size),
         texture,
         snatch_guard,
     )?;
@@ -1092,6 +1139,16 @@ impl Global {
             src_texture.same_device_as(cmd_buf.as_ref())?;
             dst_texture.same_device_as(cmd_buf.as_ref())?;
 
+            if src_texture.aliases(&dst_texture) && !src_texture.is_equal(&dst_texture) {
+                return Err(
+                    TransferError::AliasedTextureConflict(AliasedTextureConflictError {
+                        first: src_texture.error_ident(),
+                        second: dst_texture.error_ident(),
+                    })
+                    .into(),
+                );
+            }
+
             // src and dst texture format must be copy-compatible
             // https://gpuweb.github.io/gpuweb/#copy-compatible
             if src_texture.desc.format.remove_srgb_suffix()
@@ -1218,4 +1275,224 @@ impl Global {
             Ok(())
         })
     }
+
+    /// Resolves `regions` of the multisampled `source` view into the `destination` view.
+    pub fn command_encoder_resolve_texture(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        source: TextureViewId,
+        destination: TextureViewId,
+        regions: &[wgt::Rect],
+    ) -> Result<(), EncoderStateError> {
+        profiling::scope!("CommandEncoder::resolve_texture");
+        api_log!("CommandEncoder::resolve_texture {source:?} -> {destination:?} {regions:?}");
+
+        let hub = &self.hub;
+
+        let cmd_buf = hub
+            .command_buffers
+            .get(command_encoder_id.into_command_buffer_id());
+        let mut cmd_buf_data = cmd_buf.data.lock();
+        cmd_buf_data.record_with(|cmd_buf_data| -> Result<(), CommandEncoderError> {
+            let device = &cmd_buf.device;
+            device.check_is_valid()?;
+
+            let snatch_guard = device.snatchable_lock.read();
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut list) = cmd_buf_data.commands {
+                list.push(TraceCommand::ResolveTexture {
+                    src: source,
+                    dst: destination,
+                    regions: regions.to_vec(),
+                });
+            }
+
+            let src_view = hub.texture_views.get(source).get()?;
+            let dst_view = hub.texture_views.get(destination).get()?;
+
+            src_view.same_device_as(cmd_buf.as_ref())?;
+            dst_view.same_device_as(cmd_buf.as_ref())?;
+
+            let src_texture = &src_view.parent;
+            let dst_texture = &dst_view.parent;
+
+            if src_texture.aliases(dst_texture) && !src_texture.is_equal(dst_texture) {
+                return Err(
+                    TransferError::AliasedTextureConflict(AliasedTextureConflictError {
+                        first: src_texture.error_ident(),
+                        second: dst_texture.error_ident(),
+                    })
+                    .into(),
+                );
+            }
+
+            if src_texture.desc.sample_count == 1 || dst_texture.desc.sample_count != 1 {
+                return Err(TransferError::InvalidResolveSampleCounts {
+                    src: src_texture.desc.sample_count,
+                    dst: dst_texture.desc.sample_count,
+                }
+                .into());
+            }
+            if src_texture.desc.format != dst_texture.desc.format {
+                return Err(TransferError::MismatchedResolveFormats {
+                    src: src_texture.desc.format,
+                    dst: dst_texture.desc.format,
+                }
+                .into());
+            }
+            if !dst_texture
+                .format_features
+                .flags
+                .contains(wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE)
+            {
+                return Err(
+                    TransferError::UnsupportedResolveFormat(dst_texture.desc.format).into(),
+                );
+            }
+            for view in [&src_view, &dst_view] {
+                let mip_level_count = view.selector.mips.end - view.selector.mips.start;
+                if mip_level_count != 1 {
+                    return Err(TransferError::InvalidResolveMipLevelCount(
+                        view.error_ident(),
+                        mip_level_count,
+                    )
+                    .into());
+                }
+            }
+            let layer_count = src_view.selector.layers.end - src_view.selector.layers.start;
+            let dst_layer_count = dst_view.selector.layers.end - dst_view.selector.layers.start;
+            if layer_count != dst_layer_count {
+                return Err(TransferError::ResolveLayerCountMismatch {
+                    src: layer_count,
+                    dst: dst_layer_count,
+                }
+                .into());
+            }
+
+            let src_size = src_texture
+                .desc
+                .mip_level_size(src_view.selector.mips.start)
+                .unwrap();
+            let dst_size = dst_texture
+                .desc
+                .mip_level_size(dst_view.selector.mips.start)
+                .unwrap();
+            let (width, height) = (
+                src_size.width.min(dst_size.width),
+                src_size.height.min(dst_size.height),
+            );
+            let mut covers_destination = false;
+            for &region in regions {
+                if region
+                    .x
+                    .checked_add(region.width)
+                    .is_none_or(|end| end > width)
+                    || region
+                        .y
+                        .checked_add(region.height)
+                        .is_none_or(|end| end > height)
+                {
+                    return Err(TransferError::ResolveRegionOutOfBounds {
+                        region,
+                        width,
+                        height,
+                    }
+                    .into());
+                }
+                covers_destination |=
+                    region.width == dst_size.width && region.height == dst_size.height;
+            }
+
+            let regions = regions
+                .iter()
+                .filter(|region| region.width != 0 && region.height != 0)
+                .collect::<Vec<_>>();
+            if regions.is_empty() {
+                log::trace!("Ignoring resolve_texture without regions");
+                return Ok(());
+            }
+
+            // Partially resolved subresources have to be initialized first,
+            // since the init tracker doesn't track regions.
+            handle_texture_init(
+                MemoryInitKind::NeedsInitializedMemory,
+                cmd_buf_data,
+                device,
+                TextureInitRange::from(src_view.selector.clone()),
+                src_texture,
+                &snatch_guard,
+            )?;
+            handle_texture_init(
+                if covers_destination {
+                    MemoryInitKind::ImplicitlyInitialized
+                } else {
+                    MemoryInitKind::NeedsInitializedMemory
+                },
+                cmd_buf_data,
+                device,
+                TextureInitRange::from(dst_view.selector.clone()),
+                dst_texture,
+                &snatch_guard,
+            )?;
+
+            let src_pending = cmd_buf_data.trackers.textures.set_single(
+                src_texture,
+                src_view.selector.clone(),
+                wgt::TextureUses::COPY_SRC,
+            );
+            let src_raw = src_texture.try_raw(&snatch_guard)?;
+            src_texture
+                .check_usage(TextureUsages::COPY_SRC)
+                .map_err(TransferError::MissingTextureUsage)?;
+
+            let mut barriers: ArrayVec<_, 2> = src_pending
+                .map(|pending| pending.into_hal(src_raw))
+                .collect();
+
+            let dst_pending = cmd_buf_data.trackers.textures.set_single(
+                dst_texture,
+                dst_view.selector.clone(),
+                wgt::TextureUses::COPY_DST,
+            );
+            let dst_raw = dst_texture.try_raw(&snatch_guard)?;
+            dst_texture
+                .check_usage(TextureUsages::COPY_DST)
+                .map_err(TransferError::MissingTextureUsage)?;
+
+            barriers.extend(dst_pending.map(|pending| pending.into_hal(dst_raw)));
+
+            let mut hal_regions = Vec::with_capacity(layer_count as usize * regions.len());
+            for rel_array_layer in 0..layer_count {
+                for region in regions.iter() {
+                    let base = |view: &TextureView| hal::TextureCopyBase {
+                        mip_level: view.selector.mips.start,
+                        array_layer: view.selector.layers.start + rel_array_layer,
+                        origin: wgt::Origin3d {
+                            x: region.x,
+                            y: region.y,
+                            z: 0,
+                        },
+                        aspect: hal::FormatAspects::COLOR,
+                    };
+                    hal_regions.push(hal::TextureCopy {
+                        src_base: base(&src_view),
+                        dst_base: base(&dst_view),
+                        size: hal::CopyExtent {
+                            width: region.width,
+                            height: region.height,
+                            depth: 1,
+                        },
+                    });
+                }
+            }
+            let cmd_buf_raw = cmd_buf_data.encoder.open()?;
+            unsafe {
+                cmd_buf_raw.transition_textures(&barriers);
+                cmd_buf_raw.resolve_texture(src_raw, dst_raw, &hal_regions);
+            }
+
+            Ok(())
+        })
+    }
 }
@@ -7,7 +7,7 @@ use crate::{
     global::Global,
     id::{BufferId, CommandEncoderId, TextureId},
     resource::{InvalidResourceError, ParentDevice},
-    track::ResourceUsageCompatibilityError,
+    track::{BarrierLog, ResourceUsageCompatibilityError},
 };
 
 impl Global {
@@ -68,6 +68,7 @@ impl Global {
                 &mut cmd_buf_data.trackers,
                 &usage_scope,
                 snatch_guard,
+                BarrierLog::new(device, || "transition_resources".into()).as_mut(),
             );
             Ok(())
         })
@@ -11,21 +11,24 @@ use crate::{
     },
     command::{self, CommandBuffer},
     conv,
-    device::{DeviceError, DeviceLostClosure, bgl, life::WaitIdleError},
+    device::{
+        DeviceError, DeviceLostClosure, GpuValidationCallback, MemoryBudgetCallback, bgl,
+        life::WaitIdleError,
+    },
     global::Global,
     hal_api::HalApi,
     id::{self, AdapterId, DeviceId, QueueId, SurfaceId},
     instance::{self, Adapter, Surface},
     pipeline::{
         self, ResolvedComputePipelineDescriptor, ResolvedFragmentState,
-        ResolvedProgrammableStageDescriptor, ResolvedRenderPipelineDescriptor, ResolvedVertexState,
+        ResolvedProgrammableStageDescriptor, ResolvedVertexState,
     },
     present,
     resource::{
         self, BufferAccessError, BufferAccessResult, BufferMapOperation, CreateBufferError,
         Fallible,
     },
-    storage::Storage,
+    storage::StorageReadGuard,
 };
 
 use wgt::{BufferAddress, TextureFormat};
@@ -61,6 +64,7 @@ impl Global {
                 present_modes: hal_caps.present_modes,
                 alpha_modes: hal_caps.composite_alpha_modes,
                 usages,
+                color_spaces: hal_caps.color_spaces,
             })
         })
     }
@@ -336,6 +340,138 @@ impl Global {
         (id, Some(error))
     }
 
+    // Memory heaps and the textures placed in them aren't traced, as a replay
+    // can't reproduce the placement on another adapter.
+    pub fn device_create_memory_heap(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::MemoryHeapDescriptor,
+        id_in: Option<id::MemoryHeapId>,
+    ) -> (id::MemoryHeapId, Option<resource::CreateMemoryHeapError>) {
+        profiling::scope!("Device::create_memory_heap");
+
+        let hub = &self.hub;
+        let fid = hub.memory_heaps.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+        let error = match device.create_memory_heap(desc) {
+            Ok(heap) => {
+                let id = fid.assign(Fallible::Valid(heap));
+                api_log!("Device::create_memory_heap({desc:?}) -> {id:?}");
+                return (id, None);
+            }
+            Err(err) => err,
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+        (id, Some(error))
+    }
+
+    pub fn memory_heap_drop(&self, heap_id: id::MemoryHeapId) {
+        profiling::scope!("MemoryHeap::drop");
+        api_log!("MemoryHeap::drop {heap_id:?}");
+
+        let _heap = self.hub.memory_heaps.remove(heap_id);
+    }
+
+    pub fn device_texture_memory_requirements(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, resource::CreateAliasedTextureError> {
+        let device = self.hub.devices.get(device_id);
+        device.texture_memory_requirements(desc)
+    }
+
+    pub fn device_create_texture_aliased(
+        &self,
+        device_id: DeviceId,
+        heap_id: id::MemoryHeapId,
+        offset: BufferAddress,
+        desc: &resource::TextureDescriptor,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateAliasedTextureError>) {
+        profiling::scope!("Device::create_texture_aliased");
+
+        let hub = &self.hub;
+
+        let fid = hub.textures.prepare(id_in);
+
+        let error = 'error: {
+            let device = self.hub.devices.get(device_id);
+
+            let heap = match hub.memory_heaps.get(heap_id).get() {
+                Ok(heap) => heap,
+                Err(error) => break 'error error.into(),
+            };
+
+            let texture = match device.create_texture_aliased(&heap, offset, desc) {
+                Ok(texture) => texture,
+                Err(error) => break 'error error,
+            };
+
+            let id = fid.assign(Fallible::Valid(texture));
+            api_log!("Device::create_texture_aliased({heap_id:?}, {offset}, {desc:?}) -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+        (id, Some(error))
+    }
+
+    // Sparse resources aren't traced either, as the memory bound to them lives
+    // in memory heaps.
+    pub fn device_create_sparse_buffer(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<resource::CreateSparseBufferError>) {
+        profiling::scope!("Device::create_sparse_buffer");
+
+        let hub = &self.hub;
+        let fid = hub.buffers.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+        let error = match device.create_sparse_buffer(desc) {
+            Ok(buffer) => {
+                let id = fid.assign(Fallible::Valid(buffer));
+                api_log!("Device::create_sparse_buffer({desc:?}) -> {id:?}");
+                return (id, None);
+            }
+            Err(err) => err,
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+        (id, Some(error))
+    }
+
+    pub fn device_create_sparse_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateSparseTextureError>) {
+        profiling::scope!("Device::create_sparse_texture");
+
+        let hub = &self.hub;
+        let fid = hub.textures.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+        let error = match device.create_sparse_texture(desc) {
+            Ok(texture) => {
+                let id = fid.assign(Fallible::Valid(texture));
+                api_log!("Device::create_sparse_texture({desc:?}) -> {id:?}");
+                return (id, None);
+            }
+            Err(err) => err,
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+        (id, Some(error))
+    }
+
     /// # Safety
     ///
     /// - `hal_texture` must be created from `device_id` corresponding raw handle.
@@ -379,6 +515,53 @@ impl Global {
         (id, Some(error))
     }
 
+    /// # Safety
+    ///
+    /// - `io_surface` must be a valid `IOSurfaceRef`.
+    /// - `desc.format` must be compatible with the pixel format of `plane`.
+    #[cfg(metal)]
+    pub unsafe fn device_create_texture_from_io_surface(
+        &self,
+        device_id: DeviceId,
+        io_surface: *mut core::ffi::c_void,
+        plane: u32,
+        desc: &resource::TextureDescriptor,
+        id_in: Option<id::TextureId>,
+    ) -> (
+        id::TextureId,
+        Option<resource::CreateTextureFromIoSurfaceError>,
+    ) {
+        profiling::scope!("Device::create_texture_from_io_surface");
+
+        let hub = &self.hub;
+
+        let fid = hub.textures.prepare(id_in);
+
+        let error = 'error: {
+            let device = self.hub.devices.get(device_id);
+
+            // NB: The surface's contents will not be recorded in the replay
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture =
+                match unsafe { device.create_texture_from_io_surface(io_surface, plane, desc) } {
+                    Ok(texture) => texture,
+                    Err(error) => break 'error error,
+                };
+
+            let id = fid.assign(Fallible::Valid(texture));
+            api_log!("Device::create_texture_from_io_surface({plane}, {desc:?}) -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+        (id, Some(error))
+    }
+
     /// # Safety
     ///
     /// - `hal_buffer` must be created from `device_id` corresponding raw handle.
@@ -739,10 +922,10 @@ impl Global {
 
             fn resolve_entry<'a>(
                 e: &BindGroupEntry<'a>,
-                buffer_storage: &Storage<Fallible<resource::Buffer>>,
-                sampler_storage: &Storage<Fallible<resource::Sampler>>,
-                texture_view_storage: &Storage<Fallible<resource::TextureView>>,
-                tlas_storage: &Storage<Fallible<resource::Tlas>>,
+                buffer_storage: &StorageReadGuard<'_, Fallible<resource::Buffer>>,
+                sampler_storage: &StorageReadGuard<'_, Fallible<resource::Sampler>>,
+                texture_view_storage: &StorageReadGuard<'_, Fallible<resource::TextureView>>,
+                tlas_storage: &StorageReadGuard<'_, Fallible<resource::Tlas>>,
             ) -> Result<ResolvedBindGroupEntry<'a>, binding_model::CreateBindGroupError>
             {
                 let resolve_buffer = |bb: &BufferBinding| {
@@ -912,9 +1095,13 @@ impl Global {
             if let Some(ref mut trace) = *device.trace.lock() {
                 let data = match source {
                     #[cfg(feature = "wgsl")]
-                    pipeline::ShaderModuleSource::Wgsl(ref code) => {
+                    pipeline::ShaderModuleSource::Wgsl(ref code, _) => {
                         trace.make_binary("wgsl", code.as_bytes())
                     }
+                    #[cfg(feature = "wgsl")]
+                    pipeline::ShaderModuleSource::WgslFiles(ref sources, _) => {
+                        trace.make_binary("wgsl", sources.text().as_bytes())
+                    }
                     #[cfg(feature = "glsl")]
                     pipeline::ShaderModuleSource::Glsl(ref code, _) => {
                         trace.make_binary("glsl", code.as_bytes())
@@ -986,24 +1173,28 @@ impl Global {
                             pipeline::ShaderModuleDescriptor {
                                 label: inner.label.clone(),
                                 runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+                                optimization: wgt::ShaderOptimization::Default,
                             }
                         }
                         pipeline::ShaderModuleDescriptorPassthrough::Msl(inner) => {
                             pipeline::ShaderModuleDescriptor {
                                 label: inner.label.clone(),
                                 runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+                                optimization: wgt::ShaderOptimization::Default,
                             }
                         }
                         pipeline::ShaderModuleDescriptorPassthrough::Dxil(inner) => {
                             pipeline::ShaderModuleDescriptor {
                                 label: inner.label.clone(),
                                 runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+                                optimization: wgt::ShaderOptimization::Default,
                             }
                         }
                         pipeline::ShaderModuleDescriptorPassthrough::Hlsl(inner) => {
                             pipeline::ShaderModuleDescriptor {
                                 label: inner.label.clone(),
                                 runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+                                optimization: wgt::ShaderOptimization::Default,
                             }
                         }
                     },
@@ -1026,6 +1217,61 @@ impl Global {
         (id, Some(error))
     }
 
+    /// Returns the binding of the resource variable named `name` in the shader module.
+    ///
+    /// Returns `None` if the module doesn't declare such a variable, or if the module
+    /// is invalid or was created from passthrough shader code, which isn't reflected.
+    pub fn shader_module_resource_binding(
+        &self,
+        shader_module_id: id::ShaderModuleId,
+        name: &str,
+    ) -> Option<naga::ResourceBinding> {
+        let shader_module = self.hub.shader_modules.get(shader_module_id).get().ok()?;
+        shader_module.interface.as_ref()?.resource_binding(name)
+    }
+
+    /// Returns the workgroup memory used by the compute, task or mesh entry point
+    /// named `entry_point` in the shader module.
+    ///
+    /// Returns `None` if the module doesn't declare such an entry point, or if the module
+    /// is invalid or was created from passthrough shader code, which isn't reflected.
+    pub fn shader_module_workgroup_memory(
+        &self,
+        shader_module_id: id::ShaderModuleId,
+        entry_point: &str,
+    ) -> Option<wgt::WorkgroupMemoryInfo> {
+        let shader_module = self.hub.shader_modules.get(shader_module_id).get().ok()?;
+        let size = shader_module
+            .interface
+            .as_ref()?
+            .workgroup_memory_size(entry_point)?;
+        Some(wgt::WorkgroupMemoryInfo {
+            size,
+            zero_initialized: size > 0 && shader_module.zero_initialize_workgroup_memory,
+        })
+    }
+
+    /// Checks that the uniform or storage buffer variable named `name` in the shader module
+    /// has the memory layout described by `layout`.
+    pub fn shader_module_check_buffer_layout(
+        &self,
+        shader_module_id: id::ShaderModuleId,
+        name: &str,
+        layout: &wgt::ShaderTypeLayout,
+    ) -> Result<(), wgt::ShaderLayoutError> {
+        let shader_module = self
+            .hub
+            .shader_modules
+            .get(shader_module_id)
+            .get()
+            .map_err(|_| wgt::ShaderLayoutError::NotReflected)?;
+        shader_module
+            .interface
+            .as_ref()
+            .ok_or(wgt::ShaderLayoutError::NotReflected)?
+            .check_buffer_layout(name, layout)
+    }
+
     pub fn shader_module_drop(&self, shader_module_id: id::ShaderModuleId) {
         profiling::scope!("ShaderModule::drop");
         api_log!("ShaderModule::drop {shader_module_id:?}");
@@ -1076,6 +1322,45 @@ impl Global {
         (id.into_command_encoder_id(), Some(error))
     }
 
+    /// Create a command encoder whose command buffers are submitted to
+    /// `queue_id`, which must be a queue of `device_id`.
+    pub fn device_create_command_encoder_for_queue(
+        &self,
+        device_id: DeviceId,
+        queue_id: QueueId,
+        desc: &wgt::CommandEncoderDescriptor<Label>,
+        id_in: Option<id::CommandEncoderId>,
+    ) -> (id::CommandEncoderId, Option<DeviceError>) {
+        profiling::scope!("Device::create_command_encoder_for_queue");
+
+        let hub = &self.hub;
+        let fid = hub
+            .command_buffers
+            .prepare(id_in.map(|id| id.into_command_buffer_id()));
+
+        let device = self.hub.devices.get(device_id);
+        let queue = self.hub.queues.get(queue_id);
+
+        let error = 'error: {
+            let command_buffer = match device.create_command_encoder_for_queue(&queue, &desc.label)
+            {
+                Ok(command_buffer) => command_buffer,
+                Err(e) => break 'error e,
+            };
+
+            let id = fid.assign(command_buffer);
+            api_log!("Device::create_command_encoder_for_queue -> {id:?}");
+            return (id.into_command_encoder_id(), None);
+        };
+
+        let id = fid.assign(Arc::new(CommandBuffer::new_invalid(
+            &device,
+            &desc.label,
+            error.clone().into(),
+        )));
+        (id.into_command_encoder_id(), Some(error))
+    }
+
     pub fn command_encoder_drop(&self, command_encoder_id: id::CommandEncoderId) {
         profiling::scope!("CommandEncoder::drop");
         api_log!("CommandEncoder::drop {command_encoder_id:?}");
@@ -1134,6 +1419,7 @@ impl Global {
                         &bundle_encoder.context,
                         bundle_encoder.is_depth_read_only,
                         bundle_encoder.is_stencil_read_only,
+                        bundle_encoder.inheritance,
                     ),
                     base: bundle_encoder.to_base_pass(),
                 });
@@ -1223,6 +1509,77 @@ impl Global {
         }
     }
 
+    // Shared fences aren't traced, as a replay has nothing to share them with.
+    pub fn device_create_shared_fence(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::SharedFenceDescriptor,
+        id_in: Option<id::SharedFenceId>,
+    ) -> (id::SharedFenceId, Option<resource::CreateSharedFenceError>) {
+        profiling::scope!("Device::create_shared_fence");
+
+        let hub = &self.hub;
+        let fid = hub.shared_fences.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+        let error = match device.create_shared_fence(desc) {
+            Ok(fence) => {
+                let id = fid.assign(Fallible::Valid(fence));
+                api_log!("Device::create_shared_fence -> {id:?}");
+                return (id, None);
+            }
+            Err(err) => err,
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+        (id, Some(error))
+    }
+
+    /// # Safety
+    ///
+    /// - `handle` must be a valid handle to a fence that can be used by the
+    ///   device, as described by [`wgt::SharedFenceHandle`].
+    pub unsafe fn device_import_shared_fence(
+        &self,
+        device_id: DeviceId,
+        handle: wgt::SharedFenceHandle,
+        desc: &resource::SharedFenceDescriptor,
+        id_in: Option<id::SharedFenceId>,
+    ) -> (id::SharedFenceId, Option<resource::CreateSharedFenceError>) {
+        profiling::scope!("Device::import_shared_fence");
+
+        let hub = &self.hub;
+        let fid = hub.shared_fences.prepare(id_in);
+
+        let device = self.hub.devices.get(device_id);
+        let error = match unsafe { device.import_shared_fence(handle, desc) } {
+            Ok(fence) => {
+                let id = fid.assign(Fallible::Valid(fence));
+                api_log!("Device::import_shared_fence -> {id:?}");
+                return (id, None);
+            }
+            Err(err) => err,
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+        (id, Some(error))
+    }
+
+    pub fn shared_fence_export(
+        &self,
+        fence_id: id::SharedFenceId,
+    ) -> Result<wgt::SharedFenceHandle, resource::ExportSharedFenceError> {
+        let fence = self.hub.shared_fences.get(fence_id).get()?;
+        fence.export()
+    }
+
+    pub fn shared_fence_drop(&self, fence_id: id::SharedFenceId) {
+        profiling::scope!("SharedFence::drop");
+        api_log!("SharedFence::drop {fence_id:?}");
+
+        let _fence = self.hub.shared_fences.remove(fence_id);
+    }
+
     pub fn device_create_render_pipeline(
         &self,
         device_id: DeviceId,
@@ -1234,7 +1591,43 @@ impl Global {
         Option<pipeline::CreateRenderPipelineError>,
     ) {
         profiling::scope!("Device::create_render_pipeline");
+        self.device_create_general_render_pipeline(
+            device_id,
+            desc.clone().into(),
+            id_in,
+            implicit_pipeline_ids,
+        )
+    }
 
+    pub fn device_create_mesh_pipeline(
+        &self,
+        device_id: DeviceId,
+        desc: &pipeline::MeshPipelineDescriptor,
+        id_in: Option<id::RenderPipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<'_>>,
+    ) -> (
+        id::RenderPipelineId,
+        Option<pipeline::CreateRenderPipelineError>,
+    ) {
+        profiling::scope!("Device::create_mesh_pipeline");
+        self.device_create_general_render_pipeline(
+            device_id,
+            desc.clone().into(),
+            id_in,
+            implicit_pipeline_ids,
+        )
+    }
+
+    fn device_create_general_render_pipeline(
+        &self,
+        device_id: DeviceId,
+        desc: pipeline::GeneralRenderPipelineDescriptor,
+        id_in: Option<id::RenderPipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<'_>>,
+    ) -> (
+        id::RenderPipelineId,
+        Option<pipeline::CreateRenderPipelineError>,
+    ) {
         let hub = &self.hub;
 
         let missing_implicit_pipeline_ids =
@@ -1253,10 +1646,44 @@ impl Global {
 
             #[cfg(feature = "trace")]
             if let Some(ref mut trace) = *device.trace.lock() {
-                trace.add(trace::Action::CreateRenderPipeline {
-                    id: fid.id(),
-                    desc: desc.clone(),
-                    implicit_context: implicit_context.clone(),
+                let id = fid.id();
+                let implicit_context = implicit_context.clone();
+                trace.add(match desc.vertex.clone() {
+                    pipeline::RenderPipelineVertexProcessor::Vertex(vertex) => {
+                        trace::Action::CreateRenderPipeline {
+                            id,
+                            desc: pipeline::RenderPipelineDescriptor {
+                                label: desc.label.clone(),
+                                layout: desc.layout,
+                                vertex,
+                                primitive: desc.primitive,
+                                depth_stencil: desc.depth_stencil.clone(),
+                                multisample: desc.multisample,
+                                fragment: desc.fragment.clone(),
+                                multiview: desc.multiview,
+                                cache: desc.cache,
+                            },
+                            implicit_context,
+                        }
+                    }
+                    pipeline::RenderPipelineVertexProcessor::Mesh(task, mesh) => {
+                        trace::Action::CreateMeshPipeline {
+                            id,
+                            desc: pipeline::MeshPipelineDescriptor {
+                                label: desc.label.clone(),
+                                layout: desc.layout,
+                                task,
+                                mesh,
+                                primitive: desc.primitive,
+                                depth_stencil: desc.depth_stencil.clone(),
+                                multisample: desc.multisample,
+                                fragment: desc.fragment.clone(),
+                                multiview: desc.multiview,
+                                cache: desc.cache,
+                            },
+                            implicit_context,
+                        }
+                    }
                 });
             }
 
@@ -1282,56 +1709,61 @@ impl Global {
                 Err(e) => break 'error e.into(),
             };
 
-            let vertex = {
+            fn resolve_stage<'a>(
+                hub: &crate::hub::Hub,
+                stage_desc: &pipeline::ProgrammableStageDescriptor<'a>,
+                stage: wgt::ShaderStages,
+            ) -> Result<ResolvedProgrammableStageDescriptor<'a>, pipeline::CreateRenderPipelineError>
+            {
                 let module = hub
                     .shader_modules
-                    .get(desc.vertex.stage.module)
+                    .get(stage_desc.module)
                     .get()
                     .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
-                        stage: wgt::ShaderStages::VERTEX,
+                        stage,
                         error: e.into(),
-                    });
-                let module = match module {
-                    Ok(module) => module,
-                    Err(e) => break 'error e,
-                };
-                let stage = ResolvedProgrammableStageDescriptor {
+                    })?;
+                Ok(ResolvedProgrammableStageDescriptor {
                     module,
-                    entry_point: desc.vertex.stage.entry_point.clone(),
-                    constants: desc.vertex.stage.constants.clone(),
-                    zero_initialize_workgroup_memory: desc
-                        .vertex
-                        .stage
-                        .zero_initialize_workgroup_memory,
-                };
-                ResolvedVertexState {
-                    stage,
-                    buffers: desc.vertex.buffers.clone(),
+                    entry_point: stage_desc.entry_point.clone(),
+                    constants: stage_desc.constants.clone(),
+                    zero_initialize_workgroup_memory: stage_desc.zero_initialize_workgroup_memory,
+                })
+            }
+
+            let vertex = match desc.vertex {
+                pipeline::RenderPipelineVertexProcessor::Vertex(ref vertex) => {
+                    let stage = match resolve_stage(hub, &vertex.stage, wgt::ShaderStages::VERTEX) {
+                        Ok(stage) => stage,
+                        Err(e) => break 'error e,
+                    };
+                    pipeline::RenderPipelineVertexProcessor::Vertex(ResolvedVertexState {
+                        stage,
+                        buffers: vertex.buffers.clone(),
+                    })
+                }
+                pipeline::RenderPipelineVertexProcessor::Mesh(ref task, ref mesh) => {
+                    let task = match task
+                        .as_ref()
+                        .map(|task| resolve_stage(hub, &task.stage, wgt::ShaderStages::TASK))
+                        .transpose()
+                    {
+                        Ok(stage) => stage.map(|stage| pipeline::ResolvedTaskState { stage }),
+                        Err(e) => break 'error e,
+                    };
+                    let mesh = match resolve_stage(hub, &mesh.stage, wgt::ShaderStages::MESH) {
+                        Ok(stage) => pipeline::ResolvedMeshState { stage },
+                        Err(e) => break 'error e,
+                    };
+                    pipeline::RenderPipelineVertexProcessor::Mesh(task, mesh)
                 }
             };
 
             let fragment = if let Some(ref state) = desc.fragment {
-                let module = hub
-                    .shader_modules
-                    .get(state.stage.module)
-                    .get()
-                    .map_err(|e| pipeline::CreateRenderPipelineError::Stage {
-                        stage: wgt::ShaderStages::FRAGMENT,
-                        error: e.into(),
-                    });
-                let module = match module {
-                    Ok(module) => module,
+                let stage = match resolve_stage(hub, &state.stage, wgt::ShaderStages::FRAGMENT) {
+                    Ok(stage) => stage,
                     Err(e) => break 'error e,
                 };
-                let stage = ResolvedProgrammableStageDescriptor {
-                    module,
-                    entry_point: state.stage.entry_point.clone(),
-                    constants: state.stage.constants.clone(),
-                    zero_initialize_workgroup_memory: desc
-                        .vertex
-                        .stage
-                        .zero_initialize_workgroup_memory,
-                };
                 Some(ResolvedFragmentState {
                     stage,
                     targets: state.targets.clone(),
@@ -1340,7 +1772,7 @@ impl Global {
                 None
             };
 
-            let desc = ResolvedRenderPipelineDescriptor {
+            let desc = pipeline::ResolvedGeneralRenderPipelineDescriptor {
                 label: desc.label.clone(),
                 layout,
                 vertex,
@@ -1370,9 +1802,8 @@ impl Global {
                         .into();
                 }
 
-                let mut pipeline_layout_guard = hub.pipeline_layouts.write();
-                let mut bgl_guard = hub.bind_group_layouts.write();
-                pipeline_layout_guard.insert(ids.root_id, Fallible::Valid(pipeline.layout.clone()));
+                hub.pipeline_layouts
+                    .insert(ids.root_id, Fallible::Valid(pipeline.layout.clone()));
                 let mut group_ids = ids.group_ids.iter();
                 // NOTE: If the first iterator is longer than the second, the `.zip()` impl will still advance the
                 // the first iterator before realizing that the second iterator has finished.
@@ -1384,10 +1815,12 @@ impl Global {
                     .iter()
                     .zip(&mut group_ids)
                 {
-                    bgl_guard.insert(*bgl_id, Fallible::Valid(bgl.clone()));
+                    hub.bind_group_layouts
+                        .insert(*bgl_id, Fallible::Valid(bgl.clone()));
                 }
                 for bgl_id in group_ids {
-                    bgl_guard.insert(*bgl_id, Fallible::Invalid(Arc::new(String::new())));
+                    hub.bind_group_layouts
+                        .insert(*bgl_id, Fallible::Invalid(Arc::new(String::new())));
                 }
             }
 
@@ -1402,11 +1835,11 @@ impl Global {
         // We also need to assign errors to the implicit pipeline layout and the
         // implicit bind group layouts.
         if let Some(ids) = implicit_context {
-            let mut pipeline_layout_guard = hub.pipeline_layouts.write();
-            let mut bgl_guard = hub.bind_group_layouts.write();
-            pipeline_layout_guard.insert(ids.root_id, Fallible::Invalid(Arc::new(String::new())));
+            hub.pipeline_layouts
+                .insert(ids.root_id, Fallible::Invalid(Arc::new(String::new())));
             for bgl_id in ids.group_ids {
-                bgl_guard.insert(bgl_id, Fallible::Invalid(Arc::new(String::new())));
+                hub.bind_group_layouts
+                    .insert(bgl_id, Fallible::Invalid(Arc::new(String::new())));
             }
         }
 
@@ -1558,9 +1991,8 @@ impl Global {
                         .into();
                 }
 
-                let mut pipeline_layout_guard = hub.pipeline_layouts.write();
-                let mut bgl_guard = hub.bind_group_layouts.write();
-                pipeline_layout_guard.insert(ids.root_id, Fallible::Valid(pipeline.layout.clone()));
+                hub.pipeline_layouts
+                    .insert(ids.root_id, Fallible::Valid(pipeline.layout.clone()));
                 let mut group_ids = ids.group_ids.iter();
                 // NOTE: If the first iterator is longer than the second, the `.zip()` impl will still advance the
                 // the first iterator before realizing that the second iterator has finished.
@@ -1572,10 +2004,12 @@ impl Global {
                     .iter()
                     .zip(&mut group_ids)
                 {
-                    bgl_guard.insert(*bgl_id, Fallible::Valid(bgl.clone()));
+                    hub.bind_group_layouts
+                        .insert(*bgl_id, Fallible::Valid(bgl.clone()));
                 }
                 for bgl_id in group_ids {
-                    bgl_guard.insert(*bgl_id, Fallible::Invalid(Arc::new(String::new())));
+                    hub.bind_group_layouts
+                        .insert(*bgl_id, Fallible::Invalid(Arc::new(String::new())));
                 }
             }
 
@@ -1590,11 +2024,11 @@ impl Global {
         // We also need to assign errors to the implicit pipeline layout and the
         // implicit bind group layouts.
         if let Some(ids) = implicit_context {
-            let mut pipeline_layout_guard = hub.pipeline_layouts.write();
-            let mut bgl_guard = hub.bind_group_layouts.write();
-            pipeline_layout_guard.insert(ids.root_id, Fallible::Invalid(Arc::new(String::new())));
+            hub.pipeline_layouts
+                .insert(ids.root_id, Fallible::Invalid(Arc::new(String::new())));
             for bgl_id in ids.group_ids {
-                bgl_guard.insert(bgl_id, Fallible::Invalid(Arc::new(String::new())));
+                hub.bind_group_layouts
+                    .insert(bgl_id, Fallible::Invalid(Arc::new(String::new())));
             }
         }
 
@@ -1652,6 +2086,124 @@ impl Global {
         }
     }
 
+    pub fn device_create_ray_tracing_pipeline(
+        &self,
+        device_id: DeviceId,
+        desc: &pipeline::RayTracingPipelineDescriptor,
+        id_in: Option<id::RayTracingPipelineId>,
+    ) -> (
+        id::RayTracingPipelineId,
+        Option<pipeline::CreateRayTracingPipelineError>,
+    ) {
+        profiling::scope!("Device::create_ray_tracing_pipeline");
+
+        let hub = &self.hub;
+
+        let fid = hub.ray_tracing_pipelines.prepare(id_in);
+
+        let error = 'error: {
+            let device = self.hub.devices.get(device_id);
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateRayTracingPipeline {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            if let Err(e) = device.check_is_valid() {
+                break 'error e.into();
+            }
+
+            let layout = match hub.pipeline_layouts.get(desc.layout).get() {
+                Ok(layout) => layout,
+                Err(e) => break 'error e.into(),
+            };
+
+            let cache = desc
+                .cache
+                .map(|cache| hub.pipeline_caches.get(cache).get())
+                .transpose();
+            let cache = match cache {
+                Ok(cache) => cache,
+                Err(e) => break 'error e.into(),
+            };
+
+            let mut stages = Vec::with_capacity(desc.stages.len());
+            for stage in desc.stages.iter() {
+                let module = match hub.shader_modules.get(stage.module).get() {
+                    Ok(module) => module,
+                    Err(e) => break 'error e.into(),
+                };
+                stages.push(pipeline::RayTracingStageDescriptor {
+                    stage: stage.stage,
+                    module,
+                    entry_point: stage.entry_point.clone(),
+                });
+            }
+
+            let desc = pipeline::ResolvedRayTracingPipelineDescriptor {
+                label: desc.label.clone(),
+                layout,
+                stages: Cow::Owned(stages),
+                groups: desc.groups.clone(),
+                max_recursion_depth: desc.max_recursion_depth,
+                cache,
+            };
+
+            let pipeline = match device.create_ray_tracing_pipeline(desc) {
+                Ok(pipeline) => pipeline,
+                Err(e) => break 'error e,
+            };
+
+            let id = fid.assign(Fallible::Valid(pipeline));
+            api_log!("Device::create_ray_tracing_pipeline -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(desc.label.to_string())));
+
+        (id, Some(error))
+    }
+
+    /// Returns the contents of a shader binding table holding the handles of the
+    /// shader groups selected by `desc`, and the layout of its regions.
+    ///
+    /// The caller is expected to upload the contents to a buffer created with
+    /// [`wgt::BufferUsages::SHADER_BINDING_TABLE`].
+    pub fn ray_tracing_pipeline_shader_binding_table(
+        &self,
+        pipeline_id: id::RayTracingPipelineId,
+        desc: &pipeline::ShaderBindingTableDescriptor,
+    ) -> Result<(Vec<u8>, wgt::ShaderBindingTableLayout), pipeline::CreateShaderBindingTableError>
+    {
+        profiling::scope!("RayTracingPipeline::shader_binding_table");
+
+        let pipeline = self.hub.ray_tracing_pipelines.get(pipeline_id).get()?;
+        pipeline.device.check_is_valid()?;
+        pipeline.shader_binding_table(desc)
+    }
+
+    pub fn ray_tracing_pipeline_drop(&self, ray_tracing_pipeline_id: id::RayTracingPipelineId) {
+        profiling::scope!("RayTracingPipeline::drop");
+        api_log!("RayTracingPipeline::drop {ray_tracing_pipeline_id:?}");
+
+        let hub = &self.hub;
+
+        let _pipeline = hub.ray_tracing_pipelines.remove(ray_tracing_pipeline_id);
+
+        #[cfg(feature = "trace")]
+        if let Ok(pipeline) = _pipeline.get() {
+            if let Some(t) = pipeline.device.trace.lock().as_mut() {
+                t.add(trace::Action::DestroyRayTracingPipeline(
+                    ray_tracing_pipeline_id,
+                ));
+            }
+        }
+    }
+
     /// # Safety
     /// The `data` argument of `desc` must have been returned by
     /// [Self::pipeline_cache_get_data] for the same adapter
@@ -1821,6 +2373,22 @@ impl Global {
                 );
                 config.composite_alpha_mode = new_alpha_mode;
             }
+            if config.color_space == wgt::SurfaceColorSpace::Auto {
+                let extended = wgt::SurfaceColorSpace::ExtendedSrgbLinear;
+                config.color_space = if config.format == TextureFormat::Rgba16Float
+                    && caps.color_spaces.contains(&extended)
+                {
+                    extended
+                } else {
+                    wgt::SurfaceColorSpace::Srgb
+                };
+            }
+            if !caps.color_spaces.contains(&config.color_space) {
+                return Err(E::UnsupportedColorSpace {
+                    requested: config.color_space,
+                    available: caps.color_spaces.clone(),
+                });
+            }
             if !caps.usage.contains(config.usage) {
                 return Err(E::UnsupportedUsage {
                     requested: config.usage,
@@ -1890,6 +2458,9 @@ impl Global {
                     maximum_frame_latency,
                     present_mode: config.present_mode,
                     composite_alpha_mode: config.alpha_mode,
+                    color_space: config.color_space,
+                    hdr_metadata: config.hdr_metadata,
+                    exclusive_fullscreen: config.exclusive_fullscreen,
                     format: config.format,
                     extent: wgt::Extent3d {
                         width: config.width,
@@ -1956,22 +2527,25 @@ impl Global {
                 //
                 // https://github.com/gfx-rs/wgpu/issues/4105
 
-                let surface_raw = surface.raw(device.backend()).unwrap();
-                match unsafe { surface_raw.configure(device.raw(), &hal_config) } {
-                    Ok(()) => (),
-                    Err(error) => {
-                        break 'error match error {
-                            hal::SurfaceError::Outdated | hal::SurfaceError::Lost => {
-                                E::InvalidSurface
-                            }
-                            hal::SurfaceError::Device(error) => {
-                                E::Device(device.handle_hal_error(error))
-                            }
-                            hal::SurfaceError::Other(message) => {
-                                log::error!("surface configuration failed: {}", message);
-                                E::InvalidSurface
-                            }
-                        };
+                // Headless surfaces have no hal surface to configure.
+                if surface.headless.is_none() {
+                    let surface_raw = surface.raw(device.backend()).unwrap();
+                    match unsafe { surface_raw.configure(device.raw(), &hal_config) } {
+                        Ok(()) => (),
+                        Err(error) => {
+                            break 'error match error {
+                                hal::SurfaceError::Outdated | hal::SurfaceError::Lost => {
+                                    E::InvalidSurface
+                                }
+                                hal::SurfaceError::Device(error) => {
+                                    E::Device(device.handle_hal_error(error))
+                                }
+                                hal::SurfaceError::Other(message) => {
+                                    log::error!("surface configuration failed: {}", message);
+                                    E::InvalidSurface
+                                }
+                            };
+                        }
                     }
                 }
 
@@ -2009,21 +2583,27 @@ impl Global {
         result
     }
 
-    fn poll_single_device(
+    pub(crate) fn poll_single_device(
         device: &crate::device::Device,
         poll_type: wgt::PollType<crate::SubmissionIndex>,
     ) -> (UserClosures, Result<wgt::PollStatus, WaitIdleError>) {
         let snatch_guard = device.snatchable_lock.read();
         let fence = device.fence.read();
-        let maintain_result = device.maintain(fence, poll_type, snatch_guard);
+        let (mut closures, result) = device.maintain(fence, poll_type, snatch_guard);
 
         device.lose_if_oom();
+        closures
+            .memory_budget_invocations
+            .extend(device.check_memory_budget());
+        closures
+            .gpu_validation_invocations
+            .extend(device.check_gpu_validation());
 
         // Some deferred destroys are scheduled in maintain so run this right after
         // to avoid holding on to them until the next device poll.
         device.deferred_resource_destruction();
 
-        maintain_result
+        (closures, result)
     }
 
     /// Poll all devices belonging to the specified backend.
@@ -2112,6 +2692,54 @@ impl Global {
         unsafe { device.raw().stop_graphics_debugger_capture() };
     }
 
+    /// Start a capture in the attached graphics debugger of the first device it can
+    /// capture, returning `false` if there is none or a capture was already started.
+    ///
+    /// # Safety
+    ///
+    /// - See [wgpu::Instance::start_graphics_debugger_capture][api] for details the safety.
+    ///
+    /// [api]: ../../wgpu/struct.Instance.html#method.start_graphics_debugger_capture
+    pub unsafe fn instance_start_graphics_debugger_capture(&self) -> bool {
+        api_log!("Instance::start_graphics_debugger_capture");
+
+        let devices = self
+            .hub
+            .devices
+            .read()
+            .iter()
+            .map(|(_id, device)| Arc::clone(device))
+            .collect::<Vec<_>>();
+
+        let mut capture = self.graphics_debugger_capture.lock();
+        if capture.is_some() {
+            return false;
+        }
+        for device in devices {
+            if device.is_valid() && unsafe { device.raw().start_graphics_debugger_capture() } {
+                *capture = Some(device);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Stop the capture started by [`Global::instance_start_graphics_debugger_capture`].
+    ///
+    /// # Safety
+    ///
+    /// - See [wgpu::Instance::stop_graphics_debugger_capture][api] for details the safety.
+    ///
+    /// [api]: ../../wgpu/struct.Instance.html#method.stop_graphics_debugger_capture
+    pub unsafe fn instance_stop_graphics_debugger_capture(&self) {
+        api_log!("Instance::stop_graphics_debugger_capture");
+
+        let Some(device) = self.graphics_debugger_capture.lock().take() else {
+            return;
+        };
+        unsafe { device.raw().stop_graphics_debugger_capture() };
+    }
+
     pub fn pipeline_cache_get_data(&self, id: id::PipelineCacheId) -> Option<Vec<u8>> {
         use crate::pipeline_cache;
         api_log!("PipelineCache::get_data");
@@ -2162,6 +2790,66 @@ impl Global {
             .replace(device_lost_closure);
     }
 
+    /// Set a callback to be called from [`Global::device_poll`] and
+    /// [`Global::poll_all_devices`] when the memory budget reported by the device's
+    /// adapter changes, replacing any previous callback.
+    ///
+    /// Nothing is called if the adapter can't report its memory budget.
+    pub fn device_set_memory_budget_callback(
+        &self,
+        device_id: DeviceId,
+        callback: MemoryBudgetCallback,
+    ) {
+        let device = self.hub.devices.get(device_id);
+        let info = device.adapter.memory_info().unwrap_or_default();
+        device
+            .memory_budget_callback
+            .lock()
+            .replace((callback, info));
+    }
+
+    /// Set a callback to be called from [`Global::device_poll`] and
+    /// [`Global::poll_all_devices`] with each error reported by GPU-based
+    /// validation, replacing any previous callback.
+    ///
+    /// Nothing is called unless [`wgt::InstanceFlags::GPU_BASED_VALIDATION`] is set
+    /// and the backend supports it.
+    pub fn device_set_gpu_validation_callback(
+        &self,
+        device_id: DeviceId,
+        callback: GpuValidationCallback,
+    ) {
+        let device = self.hub.devices.get(device_id);
+        device.gpu_validation_callback.lock().replace(callback);
+    }
+
+    /// Start recording the API calls made on the device into a trace in the
+    /// directory `path`, replacing the trace in progress, if any.
+    ///
+    /// The trace can be replayed with `wgpu-player`. Resources created before the
+    /// trace started aren't recorded in it, so it should be started before creating
+    /// any for the trace to be replayable.
+    #[cfg(feature = "trace")]
+    pub fn device_start_trace(
+        &self,
+        device_id: DeviceId,
+        path: std::path::PathBuf,
+    ) -> Result<(), std::io::Error> {
+        api_log!("Device::start_trace {device_id:?} {path:?}");
+
+        let device = self.hub.devices.get(device_id);
+        device.start_trace(path)
+    }
+
+    /// Finish the trace started on the device, if any.
+    #[cfg(feature = "trace")]
+    pub fn device_stop_trace(&self, device_id: DeviceId) {
+        api_log!("Device::stop_trace {device_id:?}");
+
+        let device = self.hub.devices.get(device_id);
+        device.trace.lock().take();
+    }
+
     pub fn device_destroy(&self, device_id: DeviceId) {
         api_log!("Device::destroy {device_id:?}");
 
@@ -2202,6 +2890,36 @@ impl Global {
         device.generate_allocator_report()
     }
 
+    pub fn device_trim_memory(&self, device_id: DeviceId) -> wgt::TrimMemoryReport {
+        api_log!("Device::trim_memory {device_id:?}");
+
+        let device = self.hub.devices.get(device_id);
+        device.trim_memory()
+    }
+
+    pub fn device_generate_memory_report(
+        &self,
+        device_id: DeviceId,
+        include_resources: bool,
+    ) -> wgt::MemoryReport {
+        let device = self.hub.devices.get(device_id);
+        device.generate_memory_report(include_resources)
+    }
+
+    pub fn device_set_barrier_logging(&self, device_id: DeviceId, enabled: bool) {
+        api_log!("Device::set_barrier_logging {device_id:?} {enabled}");
+
+        let device = self.hub.devices.get(device_id);
+        device.barrier_logging.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn device_take_barrier_report(&self, device_id: DeviceId) -> wgt::BarrierReport {
+        let device = self.hub.devices.get(device_id);
+        wgt::BarrierReport {
+            passes: core::mem::take(&mut *device.barrier_report.lock()),
+        }
+    }
+
     pub fn queue_drop(&self, queue_id: QueueId) {
         profiling::scope!("Queue::drop");
         api_log!("Queue::drop {queue_id:?}");
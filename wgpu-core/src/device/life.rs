@@ -10,7 +10,7 @@ use crate::{
         queue::{EncoderInFlight, SubmittedWorkDoneClosure, TempResource},
     },
     ray_tracing::BlasCompactReadyPendingClosure,
-    resource::{Blas, Buffer, Texture, Trackable},
+    resource::{Blas, BlasPendingSerialization, Buffer, SharedFence, Texture, Trackable},
     snatch::SnatchGuard,
 };
 
@@ -50,6 +50,16 @@ struct ActiveSubmission {
     /// [`wgpu_hal::Queue::submit`]: hal::Queue::submit
     encoders: Vec<EncoderInFlight>,
 
+    /// Shared fences that this submission waits on or signals.
+    ///
+    /// [`wgpu_hal::Queue::wait_for_fence`] and [`wgpu_hal::Queue::signal_fence`]
+    /// require the fences to remain alive until the submission has completed.
+    ///
+    /// [`wgpu_hal::Queue::wait_for_fence`]: hal::Queue::wait_for_fence
+    /// [`wgpu_hal::Queue::signal_fence`]: hal::Queue::signal_fence
+    #[expect(dead_code)]
+    shared_fences: Vec<Arc<SharedFence>>,
+
     /// List of queue "on_submitted_work_done" closures to be called once this
     /// submission has completed.
     work_done_closures: SmallVec<SubmittedWorkDoneClosure, 1>,
@@ -66,7 +76,10 @@ impl ActiveSubmission {
             // in the pending buffer list. If it came from a user command encoder,
             // it is in the tracker.
 
-            if encoder.trackers.buffers.contains(buffer) {
+            if encoder
+                .used_trackers()
+                .any(|trackers| trackers.buffers.contains(buffer))
+            {
                 return true;
             }
 
@@ -91,7 +104,10 @@ impl ActiveSubmission {
             // in the pending buffer list. If it came from a user command encoder,
             // it is in the tracker.
 
-            if encoder.trackers.textures.contains(texture) {
+            if encoder
+                .used_trackers()
+                .any(|trackers| trackers.textures.contains(texture))
+            {
                 return true;
             }
 
@@ -111,7 +127,10 @@ impl ActiveSubmission {
     /// This only uses constant-time operations.
     pub fn contains_blas(&self, blas: &Blas) -> bool {
         for encoder in &self.encoders {
-            if encoder.trackers.blas_s.contains(blas) {
+            if encoder
+                .used_trackers()
+                .any(|trackers| trackers.blas_s.contains(blas))
+            {
                 return true;
             }
 
@@ -186,6 +205,9 @@ pub(crate) struct LifetimeTracker {
     /// queue submission still in flight.
     ready_to_compact: Vec<Arc<Blas>>,
 
+    /// BLAS serializations whose last step was recorded in a completed submission.
+    ready_to_serialize: Vec<BlasPendingSerialization>,
+
     /// Queue "on_submitted_work_done" closures that were initiated for while there is no
     /// currently pending submissions. These cannot be immediately invoked as they
     /// must happen _after_ all mapped buffer callbacks are mapped, so we defer them
@@ -199,6 +221,7 @@ impl LifetimeTracker {
             active: Vec::new(),
             ready_to_map: Vec::new(),
             ready_to_compact: Vec::new(),
+            ready_to_serialize: Vec::new(),
             work_done_closures: SmallVec::new(),
         }
     }
@@ -209,12 +232,18 @@ impl LifetimeTracker {
     }
 
     /// Start tracking resources associated with a new queue submission.
-    pub fn track_submission(&mut self, index: SubmissionIndex, encoders: Vec<EncoderInFlight>) {
+    pub fn track_submission(
+        &mut self,
+        index: SubmissionIndex,
+        encoders: Vec<EncoderInFlight>,
+        shared_fences: Vec<Arc<SharedFence>>,
+    ) {
         self.active.push(ActiveSubmission {
             index,
             mapped: Vec::new(),
             compact_read_back: Vec::new(),
             encoders,
+            shared_fences,
             work_done_closures: SmallVec::new(),
         });
     }
@@ -314,7 +343,9 @@ impl LifetimeTracker {
         for a in self.active.drain(..done_count) {
             self.ready_to_map.extend(a.mapped);
             self.ready_to_compact.extend(a.compact_read_back);
-            for encoder in a.encoders {
+            for mut encoder in a.encoders {
+                self.ready_to_serialize
+                    .append(&mut encoder.blas_serializations);
                 // This involves actually decrementing the ref count of all command buffer
                 // resources, so can be _very_ expensive.
                 profiling::scope!("drop command buffer trackers");
@@ -363,6 +394,30 @@ impl LifetimeTracker {
         }
     }
 
+    /// Like [`Self::add_work_done_closure`], but calls `closure` once the
+    /// submission `submission_index` is done, rather than the last one.
+    pub fn add_submission_done_closure(
+        &mut self,
+        submission_index: SubmissionIndex,
+        closure: SubmittedWorkDoneClosure,
+    ) -> Option<SubmissionIndex> {
+        match self
+            .active
+            .iter_mut()
+            .find(|active| active.index >= submission_index)
+        {
+            Some(active) => {
+                active.work_done_closures.push(closure);
+                Some(active.index)
+            }
+            // The submission is already done.
+            None => {
+                self.work_done_closures.push(closure);
+                None
+            }
+        }
+    }
+
     /// Map the buffers in `self.ready_to_map`.
     ///
     /// Return a list of mapping notifications to send.
@@ -408,4 +463,11 @@ impl LifetimeTracker {
         }
         pending_callbacks
     }
+
+    /// Take the BLAS serializations in `self.ready_to_serialize`, to be advanced by
+    /// [`Queue::maintain`](super::queue::Queue::maintain).
+    #[must_use]
+    pub(crate) fn take_ready_serializations(&mut self) -> Vec<BlasPendingSerialization> {
+        core::mem::take(&mut self.ready_to_serialize)
+    }
 }
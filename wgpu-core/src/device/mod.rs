@@ -1,11 +1,11 @@
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::{fmt, num::NonZeroU32};
 
 use crate::{
     DOWNLEVEL_ERROR_MESSAGE, Label, binding_model,
     hub::Hub,
     id::{BindGroupLayoutId, PipelineLayoutId},
-    ray_tracing::BlasCompactReadyPendingClosure,
+    ray_tracing::{BlasCompactReadyPendingClosure, BlasSerializeReadyPendingClosure},
     resource::{
         Buffer, BufferAccessError, BufferAccessResult, BufferMapOperation, Labeled,
         RawResourceAccess, ResourceErrorIdent,
@@ -168,17 +168,25 @@ pub type BufferMapPendingClosure = (BufferMapOperation, BufferAccessResult);
 pub struct UserClosures {
     pub mappings: Vec<BufferMapPendingClosure>,
     pub blas_compact_ready: Vec<BlasCompactReadyPendingClosure>,
+    pub blas_serialized: Vec<BlasSerializeReadyPendingClosure>,
     pub submissions: SmallVec<queue::SubmittedWorkDoneClosure, 1>,
     pub device_lost_invocations: SmallVec<DeviceLostInvocation, 1>,
+    pub memory_budget_invocations: SmallVec<MemoryBudgetInvocation, 1>,
+    pub gpu_validation_invocations: SmallVec<GpuValidationInvocation, 1>,
 }
 
 impl UserClosures {
     fn extend(&mut self, other: Self) {
         self.mappings.extend(other.mappings);
         self.blas_compact_ready.extend(other.blas_compact_ready);
+        self.blas_serialized.extend(other.blas_serialized);
         self.submissions.extend(other.submissions);
         self.device_lost_invocations
             .extend(other.device_lost_invocations);
+        self.memory_budget_invocations
+            .extend(other.memory_budget_invocations);
+        self.gpu_validation_invocations
+            .extend(other.gpu_validation_invocations);
     }
 
     fn fire(self) {
@@ -197,9 +205,20 @@ impl UserClosures {
                 callback(status);
             }
         }
+        for (callback, result) in self.blas_serialized {
+            callback(result);
+        }
         for closure in self.submissions {
             closure();
         }
+        for invocation in self.memory_budget_invocations {
+            (invocation.callback)(invocation.info);
+        }
+        for invocation in self.gpu_validation_invocations {
+            for message in invocation.messages {
+                (invocation.callback)(message);
+            }
+        }
         for invocation in self.device_lost_invocations {
             (invocation.closure)(invocation.reason, invocation.message);
         }
@@ -217,6 +236,26 @@ pub struct DeviceLostInvocation {
     message: String,
 }
 
+#[cfg(send_sync)]
+pub type MemoryBudgetCallback = Arc<dyn Fn(wgt::MemoryInfo) + Send + Sync + 'static>;
+#[cfg(not(send_sync))]
+pub type MemoryBudgetCallback = Arc<dyn Fn(wgt::MemoryInfo) + 'static>;
+
+pub struct MemoryBudgetInvocation {
+    pub(crate) callback: MemoryBudgetCallback,
+    pub(crate) info: wgt::MemoryInfo,
+}
+
+#[cfg(send_sync)]
+pub type GpuValidationCallback = Arc<dyn Fn(String) + Send + Sync + 'static>;
+#[cfg(not(send_sync))]
+pub type GpuValidationCallback = Arc<dyn Fn(String) + 'static>;
+
+pub struct GpuValidationInvocation {
+    pub(crate) callback: GpuValidationCallback,
+    pub(crate) messages: Vec<String>,
+}
+
 pub(crate) fn map_buffer(
     buffer: &Buffer,
     offset: BufferAddress,
@@ -428,6 +467,14 @@ pub fn create_validator(
         Caps::SHADER_FLOAT16,
         features.contains(wgt::Features::SHADER_F16),
     );
+    caps.set(
+        Caps::SHADER_INT8,
+        features.contains(wgt::Features::SHADER_I8),
+    );
+    caps.set(
+        Caps::SHADER_INT16,
+        features.contains(wgt::Features::SHADER_I16),
+    );
     caps.set(
         Caps::PRIMITIVE_INDEX,
         features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX),
@@ -498,6 +545,22 @@ pub fn create_validator(
         Caps::CLIP_DISTANCE,
         features.contains(wgt::Features::CLIP_DISTANCES),
     );
+    caps.set(
+        Caps::FRAMEBUFFER_FETCH,
+        features.contains(wgt::Features::FRAMEBUFFER_FETCH),
+    );
+    caps.set(
+        Caps::FRAGMENT_FULLY_COVERED,
+        features.contains(wgt::Features::CONSERVATIVE_RASTERIZATION_FULLY_COVERED),
+    );
+    caps.set(
+        Caps::MULTI_VIEWPORT,
+        features.contains(wgt::Features::MULTI_VIEWPORT),
+    );
+    caps.set(
+        Caps::DEBUG_PRINTF,
+        features.contains(wgt::Features::SHADER_DEBUG_PRINTF),
+    );
     caps.set(
         Caps::CUBE_ARRAY_TEXTURES,
         downlevel.contains(wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES),
@@ -514,6 +577,10 @@ pub fn create_validator(
         Caps::RAY_QUERY,
         features.intersects(wgt::Features::EXPERIMENTAL_RAY_QUERY),
     );
+    caps.set(
+        Caps::RAY_TRACING_PIPELINE,
+        features.contains(wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE),
+    );
     caps.set(
         Caps::SUBGROUP_VERTEX_STAGE,
         features.contains(wgt::Features::SUBGROUP_VERTEX),
@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, string::ToString, sync::Arc, vec, vec::Vec};
+use alloc::{boxed::Box, format, string::ToString, sync::Arc, vec, vec::Vec};
 use core::{
     iter,
     mem::{self, ManuallyDrop},
@@ -19,39 +19,68 @@ use crate::device::trace::Action;
 use crate::{
     FastHashMap, SubmissionIndex, api_log,
     command::{
-        ClearError, CommandAllocator, CommandBuffer, CommandEncoderError, CopySide,
-        TexelCopyTextureInfo, TransferError, extract_texture_selector,
-        validate_linear_texture_data, validate_texture_copy_range,
+        ClearError, CommandAllocator, CommandBuffer, CommandBufferMutable, CommandEncoderError,
+        CopySide, FinishedCommands, SuspendedRenderPass, SuspendedResources, TexelCopyTextureInfo,
+        TransferError, extract_texture_selector, validate_linear_texture_data,
+        validate_texture_copy_range,
     },
     conv,
-    device::{DeviceError, WaitIdleError},
+    device::{DeviceError, MissingFeatures, WaitIdleError},
     get_lowest_common_denom,
     global::Global,
     id::{self, BlasId, QueueId},
     init_tracker::{TextureInitRange, has_copy_partial_init_tracker_coverage},
     lock::{Mutex, MutexGuard, RwLock, RwLockWriteGuard, rank},
-    ray_tracing::{BlasCompactReadyPendingClosure, CompactBlasError},
+    ray_tracing::{
+        BlasCompactReadyPendingClosure, BlasSerializeError, BlasSerializeReadyPendingClosure,
+        CompactBlasError,
+    },
     resource::{
-        Blas, BlasCompactState, Buffer, BufferAccessError, BufferMapState, DestroyedBuffer,
-        DestroyedResourceError, DestroyedTexture, Fallible, FlushedStagingBuffer,
-        InvalidResourceError, Labeled, ParentDevice, ResourceErrorIdent, StagingBuffer, Texture,
-        TextureInner, Trackable, TrackingData,
+        Blas, BlasCompactState, BlasPendingSerialization, BlasSerializeCallback, Buffer,
+        BufferAccessError, BufferMapState, DestroyedBuffer, DestroyedResourceError,
+        DestroyedTexture, Fallible, FlushedStagingBuffer, InvalidResourceError, Labeled,
+        MemoryHeap, ParentDevice, ResourceErrorIdent, SerializedBlasBuffer, SharedFence,
+        StagingBuffer, Texture, TextureInner, Trackable, TrackingData,
     },
     resource_log,
     scratch::ScratchBuffer,
     snatch::{SnatchGuard, Snatchable},
-    track::{self, Tracker, TrackerIndex},
+    track::{self, BarrierLog, Tracker, TrackerIndex},
+};
+use crate::{
+    device::resource::{CommandIndices, QueueFence},
+    resource::RawResourceAccess,
 };
-use crate::{device::resource::CommandIndices, resource::RawResourceAccess};
+
+/// Minimum size of a [`Queue::write_texture`] upload, in bytes, for which rows
+/// that don't meet the backend's alignment are repacked on the GPU rather than
+/// on the CPU.
+const GPU_REPACK_MIN_SIZE: wgt::BufferAddress = 1 << 20;
 
 pub struct Queue {
     raw: Box<dyn hal::DynQueue>,
+    pub(crate) queue_type: wgt::QueueType,
+    /// Set if this is one of the additional queues requested with
+    /// [`wgt::Features::EXPERIMENTAL_MULTI_QUEUE`].
+    additional: Option<AdditionalQueue>,
     pub(crate) pending_writes: Mutex<PendingWrites>,
     life_tracker: Mutex<LifetimeTracker>,
     // The device needs to be dropped last (`Device.zero_buffer` might be referenced by the encoder in pending writes).
     pub(crate) device: Arc<Device>,
 }
 
+/// The state specific to a device's additional queues.
+struct AdditionalQueue {
+    /// The device's main queue.
+    ///
+    /// The submissions to all of a device's queues are tracked by the main
+    /// queue's `life_tracker`, in submission index order.
+    main: Arc<Queue>,
+    /// The index of this queue's fence in [`Device::additional_queue_fences`].
+    fence_index: usize,
+    command_allocator: Arc<CommandAllocator>,
+}
+
 impl Queue {
     pub(crate) fn new(
         device: Arc<Device>,
@@ -99,19 +128,77 @@ impl Queue {
 
         Ok(Queue {
             raw,
+            queue_type: wgt::QueueType::Graphics,
+            additional: None,
             device,
             pending_writes: Mutex::new(rank::QUEUE_PENDING_WRITES, pending_writes),
             life_tracker: Mutex::new(rank::QUEUE_LIFE_TRACKER, LifetimeTracker::new()),
         })
     }
 
+    /// Create one of the additional queues requested with
+    /// [`wgt::Features::EXPERIMENTAL_MULTI_QUEUE`], next to the device's
+    /// `main` queue.
+    ///
+    /// The queue can't use `Device::zero_buffer` before the main queue's
+    /// first submission, which initializes it, has completed.
+    pub(crate) fn new_additional(
+        main: &Arc<Queue>,
+        raw: Box<dyn hal::DynQueue>,
+        queue_type: wgt::QueueType,
+    ) -> Result<Self, DeviceError> {
+        let device = &main.device;
+
+        let command_allocator = Arc::new(CommandAllocator::new());
+        let pending_encoder = command_allocator
+            .acquire_encoder(device.raw(), raw.as_ref())
+            .map_err(DeviceError::from_hal)?;
+
+        let fence = unsafe { device.raw().create_fence() }.map_err(DeviceError::from_hal)?;
+        let mut additional_queue_fences = device.additional_queue_fences.lock();
+        let fence_index = additional_queue_fences.len();
+        additional_queue_fences.push(QueueFence {
+            raw: fence,
+            last_submission_index: 0,
+            command_allocator: Arc::clone(&command_allocator),
+        });
+        drop(additional_queue_fences);
+
+        Ok(Queue {
+            raw,
+            queue_type,
+            additional: Some(AdditionalQueue {
+                main: main.clone(),
+                fence_index,
+                command_allocator,
+            }),
+            device: device.clone(),
+            pending_writes: Mutex::new(
+                rank::QUEUE_PENDING_WRITES,
+                PendingWrites::new(pending_encoder),
+            ),
+            life_tracker: Mutex::new(rank::QUEUE_LIFE_TRACKER, LifetimeTracker::new()),
+        })
+    }
+
     pub(crate) fn raw(&self) -> &dyn hal::DynQueue {
         self.raw.as_ref()
     }
 
+    /// The allocator of the raw command encoders used with this queue.
+    pub(crate) fn command_allocator(&self) -> &Arc<CommandAllocator> {
+        match self.additional {
+            Some(ref additional) => &additional.command_allocator,
+            None => &self.device.command_allocator,
+        }
+    }
+
     #[track_caller]
     pub(crate) fn lock_life<'a>(&'a self) -> MutexGuard<'a, LifetimeTracker> {
-        self.life_tracker.lock()
+        match self.additional {
+            Some(ref additional) => additional.main.lock_life(),
+            None => self.life_tracker.lock(),
+        }
     }
 
     pub(crate) fn maintain(
@@ -122,6 +209,7 @@ impl Queue {
         SmallVec<SubmittedWorkDoneClosure, 1>,
         Vec<super::BufferMapPendingClosure>,
         Vec<BlasCompactReadyPendingClosure>,
+        Vec<BlasSerializeReadyPendingClosure>,
         bool,
     ) {
         let mut life_tracker = self.lock_life();
@@ -129,13 +217,23 @@ impl Queue {
 
         let mapping_closures = life_tracker.handle_mapping(snatch_guard);
         let blas_closures = life_tracker.handle_compact_read_back();
+        let ready_serializations = life_tracker.take_ready_serializations();
 
         let queue_empty = life_tracker.queue_empty();
+        drop(life_tracker);
+
+        // Advancing a serialization records commands into `pending_writes`, which must be
+        // locked before the life tracker.
+        let serialize_closures = ready_serializations
+            .into_iter()
+            .filter_map(|pending| self.advance_blas_serialization(pending, snatch_guard))
+            .collect();
 
         (
             submission_closures,
             mapping_closures,
             blas_closures,
+            serialize_closures,
             queue_empty,
         )
     }
@@ -175,15 +273,34 @@ impl Drop for Queue {
                 timeout_ms
             );
 
-            let wait_res = unsafe {
-                self.device.raw().wait(
-                    fence.as_ref(),
-                    last_successful_submission_index,
-                    #[cfg(not(target_arch = "wasm32"))]
-                    timeout_ms,
-                    #[cfg(target_arch = "wasm32")]
-                    0, // WebKit and Chromium don't support a non-0 timeout
-                )
+            // WebKit and Chromium don't support a non-0 timeout
+            let timeout_ms = if cfg!(target_arch = "wasm32") {
+                0
+            } else {
+                timeout_ms
+            };
+
+            let wait_res = match self.additional {
+                // The main queue keeps tracking the lifetime of an additional
+                // queue's submissions, so only its own work needs to be done.
+                Some(ref additional) => {
+                    let additional_queue_fences = self.device.additional_queue_fences.lock();
+                    let queue_fence = &additional_queue_fences[additional.fence_index];
+                    unsafe {
+                        self.device.raw().wait(
+                            queue_fence.raw.as_ref(),
+                            queue_fence.last_submission_index,
+                            timeout_ms,
+                        )
+                    }
+                }
+                None => unsafe {
+                    self.device.wait_for_submission_index(
+                        fence.as_ref(),
+                        last_successful_submission_index,
+                        timeout_ms,
+                    )
+                },
             };
             // Note: If we don't panic below we are in UB land (destroying resources while they are still in use by the GPU).
             match wait_res {
@@ -230,9 +347,18 @@ impl Drop for Queue {
         }
         drop(fence);
 
+        if self.additional.is_some() {
+            return;
+        }
+
         let snatch_guard = self.device.snatchable_lock.read();
-        let (submission_closures, mapping_closures, blas_compact_ready_closures, queue_empty) =
-            self.maintain(last_successful_submission_index, &snatch_guard);
+        let (
+            submission_closures,
+            mapping_closures,
+            blas_compact_ready_closures,
+            blas_serialized_closures,
+            queue_empty,
+        ) = self.maintain(last_successful_submission_index, &snatch_guard);
         drop(snatch_guard);
 
         assert!(queue_empty);
@@ -240,8 +366,11 @@ impl Drop for Queue {
         let closures = crate::device::UserClosures {
             mappings: mapping_closures,
             blas_compact_ready: blas_compact_ready_closures,
+            blas_serialized: blas_serialized_closures,
             submissions: submission_closures,
             device_lost_invocations: SmallVec::new(),
+            memory_budget_invocations: SmallVec::new(),
+            gpu_validation_invocations: SmallVec::new(),
         };
 
         closures.fire();
@@ -267,8 +396,10 @@ pub type SubmittedWorkDoneClosure = Box<dyn FnOnce() + 'static>;
 pub enum TempResource {
     StagingBuffer(FlushedStagingBuffer),
     ScratchBuffer(ScratchBuffer),
+    SerializedBlasBuffer(SerializedBlasBuffer),
     DestroyedBuffer(DestroyedBuffer),
     DestroyedTexture(DestroyedTexture),
+    ExpandedIndices(crate::index_expansion::ExpandedIndices),
 }
 
 /// A series of raw [`CommandBuffer`]s that have been submitted to a
@@ -282,6 +413,10 @@ pub(crate) struct EncoderInFlight {
     pub(crate) temp_resources: Vec<TempResource>,
     /// We only need to keep these resources alive.
     _indirect_draw_validation_resources: crate::indirect_validation::DrawResources,
+    /// The commands of a reusable command buffer, executed after the ones of
+    /// `inner`. Holding them keeps their raw command buffers alive until the
+    /// submission has completed.
+    reusable: Option<Arc<CommandBufferMutable>>,
 
     /// These are the buffers that have been tracked by `PendingWrites`.
     pub(crate) pending_buffers: FastHashMap<TrackerIndex, Arc<Buffer>>,
@@ -289,6 +424,28 @@ pub(crate) struct EncoderInFlight {
     pub(crate) pending_textures: FastHashMap<TrackerIndex, Arc<Texture>>,
     /// These are the BLASes that have been tracked by `PendingWrites`.
     pub(crate) pending_blas_s: FastHashMap<TrackerIndex, Arc<Blas>>,
+    /// BLAS serializations whose next step was recorded by `PendingWrites`.
+    pub(crate) blas_serializations: Vec<BlasPendingSerialization>,
+}
+
+impl EncoderInFlight {
+    /// Returns the trackers of all the resources used by this encoder's commands.
+    pub(crate) fn used_trackers(&self) -> impl Iterator<Item = &Tracker> {
+        iter::once(&self.trackers).chain(
+            self.reusable
+                .as_deref()
+                .map(|cmd_buf_data| &cmd_buf_data.trackers),
+        )
+    }
+
+    /// Returns the raw command buffers to submit, in order.
+    fn raw_command_buffers(&self) -> impl Iterator<Item = &dyn hal::DynCommandBuffer> {
+        let reusable = self
+            .reusable
+            .iter()
+            .flat_map(|cmd_buf_data| &cmd_buf_data.encoder.list);
+        self.inner.list.iter().chain(reusable).map(|b| b.as_ref())
+    }
 }
 
 /// A private command encoder for writes made directly on the device
@@ -327,6 +484,7 @@ pub(crate) struct PendingWrites {
     dst_buffers: FastHashMap<TrackerIndex, Arc<Buffer>>,
     dst_textures: FastHashMap<TrackerIndex, Arc<Texture>>,
     copied_blas_s: FastHashMap<TrackerIndex, Arc<Blas>>,
+    blas_serializations: Vec<BlasPendingSerialization>,
 }
 
 impl PendingWrites {
@@ -338,6 +496,7 @@ impl PendingWrites {
             dst_buffers: FastHashMap::default(),
             dst_textures: FastHashMap::default(),
             copied_blas_s: FastHashMap::default(),
+            blas_serializations: Vec::new(),
         }
     }
 
@@ -375,7 +534,7 @@ impl PendingWrites {
 
     fn pre_submit(
         &mut self,
-        command_allocator: &CommandAllocator,
+        command_allocator: &Arc<CommandAllocator>,
         device: &Arc<Device>,
         queue: &Queue,
     ) -> Result<Option<EncoderInFlight>, DeviceError> {
@@ -383,6 +542,7 @@ impl PendingWrites {
             let pending_buffers = mem::take(&mut self.dst_buffers);
             let pending_textures = mem::take(&mut self.dst_textures);
             let pending_blas_s = mem::take(&mut self.copied_blas_s);
+            let blas_serializations = mem::take(&mut self.blas_serializations);
 
             let cmd_buf = unsafe { self.command_encoder.end_encoding() }
                 .map_err(|e| device.handle_hal_error(e))?;
@@ -397,6 +557,7 @@ impl PendingWrites {
                     raw: ManuallyDrop::new(mem::replace(&mut self.command_encoder, new_encoder)),
                     list: vec![cmd_buf],
                     device: device.clone(),
+                    allocator: command_allocator.clone(),
                     is_open: false,
                     hal_label: None,
                 },
@@ -405,9 +566,11 @@ impl PendingWrites {
                 _indirect_draw_validation_resources: crate::indirect_validation::DrawResources::new(
                     device.clone(),
                 ),
+                reusable: None,
                 pending_buffers,
                 pending_textures,
                 pending_blas_s,
+                blas_serializations,
             };
             Ok(Some(encoder))
         } else {
@@ -486,6 +649,24 @@ pub enum QueueSubmitError {
     CommandEncoder(#[from] CommandEncoderError),
     #[error(transparent)]
     ValidateAsActionsError(#[from] crate::ray_tracing::ValidateAsActionsError),
+    #[error("{0} was not encoded for this queue")]
+    WrongQueue(ResourceErrorIdent),
+    #[error("{0} can only be used on the device's main queue")]
+    SurfaceTextureOnAdditionalQueue(ResourceErrorIdent),
+    #[error("{0} can't be used by a reusable command buffer")]
+    SurfaceTextureInReusableCommandBuffer(ResourceErrorIdent),
+    #[error(
+        "{0} ends with a suspended render pass, which the next command buffer of the submission must resume"
+    )]
+    UnresumedRenderPass(ResourceErrorIdent),
+    #[error(
+        "{0} resumes a render pass that the previous command buffer of the submission didn't suspend with the same attachments and operations"
+    )]
+    InvalidRenderPassResume(ResourceErrorIdent),
+    #[error(
+        "{0} resumes a render pass, but needs barriers on the resources used by the parts of it before"
+    )]
+    ResumedRenderPassBarriers(ResourceErrorIdent),
 }
 
 impl WebGpuError for QueueSubmitError {
@@ -496,7 +677,14 @@ impl WebGpuError for QueueSubmitError {
             Self::CommandEncoder(e) => e,
             Self::ValidateAsActionsError(e) => e,
             Self::InvalidResource(e) => e,
-            Self::DestroyedResource(_) | Self::BufferStillMapped(_) => {
+            Self::DestroyedResource(_)
+            | Self::BufferStillMapped(_)
+            | Self::WrongQueue(_)
+            | Self::SurfaceTextureOnAdditionalQueue(_)
+            | Self::SurfaceTextureInReusableCommandBuffer(_)
+            | Self::UnresumedRenderPass(_)
+            | Self::InvalidRenderPassResume(_)
+            | Self::ResumedRenderPassBarriers(_) => {
                 return ErrorType::Validation;
             }
         };
@@ -504,6 +692,96 @@ impl WebGpuError for QueueSubmitError {
     }
 }
 
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum QueueBindSparseError {
+    #[error(transparent)]
+    Queue(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    DestroyedResource(#[from] DestroyedResourceError),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+    #[error("Sparse memory can only be bound on the device's main queue")]
+    AdditionalQueue,
+    #[error("{0} is not a sparse resource")]
+    NotSparse(ResourceErrorIdent),
+    #[error(
+        "Range of {size} bytes at offset {offset} in {buffer} is not made of whole sparse pages"
+    )]
+    UnalignedBufferRange {
+        buffer: ResourceErrorIdent,
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+    },
+    #[error(
+        "Range of {size} bytes at offset {offset} is out of bounds of {buffer} of {buffer_size} bytes"
+    )]
+    BufferRangeOutOfBounds {
+        buffer: ResourceErrorIdent,
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+        buffer_size: wgt::BufferAddress,
+    },
+    #[error(
+        "Region of {size:?} at {origin:?} in mip level {mip_level} of layer {array_layer} of {texture} is not made of whole tiles of {tile_size:?} texels"
+    )]
+    UnalignedTextureRegion {
+        texture: ResourceErrorIdent,
+        mip_level: u32,
+        array_layer: u32,
+        origin: wgt::Origin3d,
+        size: wgt::Extent3d,
+        tile_size: wgt::Extent3d,
+    },
+    #[error(
+        "Region of {size:?} at {origin:?} in mip level {mip_level} of layer {array_layer} is out of bounds of {texture}"
+    )]
+    TextureRegionOutOfBounds {
+        texture: ResourceErrorIdent,
+        mip_level: u32,
+        array_layer: u32,
+        origin: wgt::Origin3d,
+        size: wgt::Extent3d,
+    },
+    #[error("Offset {offset} in {heap} is not a multiple of the sparse page size")]
+    UnalignedHeapOffset {
+        heap: ResourceErrorIdent,
+        offset: wgt::BufferAddress,
+    },
+    #[error(
+        "Binding needs {size} bytes at offset {offset}, which doesn't fit in {heap} of {heap_size} bytes"
+    )]
+    HeapRangeOutOfBounds {
+        heap: ResourceErrorIdent,
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+        heap_size: wgt::BufferAddress,
+    },
+}
+
+impl WebGpuError for QueueBindSparseError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Queue(e) => e,
+            Self::MissingFeatures(e) => e,
+            Self::DestroyedResource(e) => e,
+            Self::InvalidResource(e) => e,
+
+            Self::AdditionalQueue
+            | Self::NotSparse(_)
+            | Self::UnalignedBufferRange { .. }
+            | Self::BufferRangeOutOfBounds { .. }
+            | Self::UnalignedTextureRegion { .. }
+            | Self::TextureRegionOutOfBounds { .. }
+            | Self::UnalignedHeapOffset { .. }
+            | Self::HeapRangeOutOfBounds { .. } => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
 //TODO: move out common parts of write_xxx.
 
 impl Queue {
@@ -733,6 +1011,9 @@ impl Queue {
 
         dst.check_usage(wgt::TextureUsages::COPY_DST)
             .map_err(TransferError::MissingTextureUsage)?;
+        if dst.aliasing.is_some() {
+            return Err(TransferError::QueueWriteToAliasedTexture(dst.error_ident()).into());
+        }
 
         // Note: Doing the copy range validation early is important because ensures that the
         // dimensions are not going to cause overflow in other parts of the validation.
@@ -840,12 +1121,31 @@ impl Queue {
         );
         let stage_bytes_per_row = wgt::math::align_to(bytes_in_last_row, bytes_per_row_alignment);
 
+        let block_rows_in_copy =
+            (size.depth_or_array_layers - 1) * rows_per_image + height_in_blocks;
+        let aligned_size =
+            wgt::BufferSize::new(stage_bytes_per_row as u64 * block_rows_in_copy as u64).unwrap();
+
+        // Large uploads whose rows are tighter than the backend allows are
+        // staged as they are, and their rows are moved to aligned offsets on
+        // the GPU instead of one by one on the CPU.
+        let repack_on_gpu = bytes_per_row < stage_bytes_per_row
+            && required_bytes_in_copy >= GPU_REPACK_MIN_SIZE
+            && bytes_per_row as u64 % wgt::COPY_BUFFER_ALIGNMENT == 0
+            && bytes_in_last_row as u64 % wgt::COPY_BUFFER_ALIGNMENT == 0;
+        let repack_buffer = if repack_on_gpu {
+            Some(ScratchBuffer::new_copy(&self.device, aligned_size)?)
+        } else {
+            None
+        };
+
         // Platform validation requires that the staging buffer always be
         // freed, even if an error occurs. All paths from here must call
         // `device.pending_writes.consume`.
-        let staging_buffer = if stage_bytes_per_row == bytes_per_row {
+        let staging_buffer = if stage_bytes_per_row == bytes_per_row || repack_on_gpu {
             profiling::scope!("copy aligned");
-            // Fast path if the data is already being aligned optimally.
+            // Fast path if the data is already being aligned optimally, or
+            // will be aligned on the GPU.
             let stage_size = wgt::BufferSize::new(required_bytes_in_copy).unwrap();
             let mut staging_buffer = StagingBuffer::new(&self.device, stage_size)?;
             staging_buffer.write(&data[data_layout.offset as usize..]);
@@ -853,12 +1153,7 @@ impl Queue {
         } else {
             profiling::scope!("copy chunked");
             // Copy row by row into the optimal alignment.
-            let block_rows_in_copy =
-                (size.depth_or_array_layers - 1) * rows_per_image + height_in_blocks;
-            let stage_size =
-                wgt::BufferSize::new(stage_bytes_per_row as u64 * block_rows_in_copy as u64)
-                    .unwrap();
-            let mut staging_buffer = StagingBuffer::new(&self.device, stage_size)?;
+            let mut staging_buffer = StagingBuffer::new(&self.device, aligned_size)?;
             let copy_bytes_per_row = stage_bytes_per_row.min(bytes_per_row) as usize;
             for layer in 0..size.depth_or_array_layers {
                 let rows_offset = layer * rows_per_image;
@@ -919,11 +1214,56 @@ impl Queue {
             unsafe {
                 encoder.transition_textures(&texture_barriers);
                 encoder.transition_buffers(&[buffer_barrier]);
-                encoder.copy_buffer_to_texture(staging_buffer.raw(), dst_raw, &regions);
+            }
+
+            let copy_src = if let Some(repack_buffer) = repack_buffer.as_ref() {
+                let row_size = wgt::BufferSize::new(bytes_in_last_row as u64).unwrap();
+                let row_copies = (0..size.depth_or_array_layers)
+                    .flat_map(|layer| {
+                        let rows_offset = layer * rows_per_image;
+                        rows_offset..rows_offset + height_in_blocks
+                    })
+                    .map(|row| hal::BufferCopy {
+                        src_offset: row as u64 * bytes_per_row as u64,
+                        dst_offset: row as u64 * stage_bytes_per_row as u64,
+                        size: row_size,
+                    })
+                    .collect::<Vec<_>>();
+                unsafe {
+                    encoder.transition_buffers(&[hal::BufferBarrier {
+                        buffer: repack_buffer.raw(),
+                        usage: hal::StateTransition {
+                            from: wgt::BufferUses::empty(),
+                            to: wgt::BufferUses::COPY_DST,
+                        },
+                    }]);
+                    encoder.copy_buffer_to_buffer(
+                        staging_buffer.raw(),
+                        repack_buffer.raw(),
+                        &row_copies,
+                    );
+                    encoder.transition_buffers(&[hal::BufferBarrier {
+                        buffer: repack_buffer.raw(),
+                        usage: hal::StateTransition {
+                            from: wgt::BufferUses::COPY_DST,
+                            to: wgt::BufferUses::COPY_SRC,
+                        },
+                    }]);
+                }
+                repack_buffer.raw()
+            } else {
+                staging_buffer.raw()
+            };
+
+            unsafe {
+                encoder.copy_buffer_to_texture(copy_src, dst_raw, &regions);
             }
         }
 
         pending_writes.consume(staging_buffer);
+        if let Some(repack_buffer) = repack_buffer {
+            pending_writes.consume_temp(TempResource::ScratchBuffer(repack_buffer));
+        }
         pending_writes.insert_texture(&dst);
 
         Ok(())
@@ -983,6 +1323,9 @@ impl Queue {
         }
         dst.check_usage(wgt::TextureUsages::COPY_DST | wgt::TextureUsages::RENDER_ATTACHMENT)
             .map_err(TransferError::MissingTextureUsage)?;
+        if dst.aliasing.is_some() {
+            return Err(TransferError::QueueWriteToAliasedTexture(dst.error_ident()).into());
+        }
         if dst.desc.sample_count != 1 {
             return Err(TransferError::InvalidSampleCount {
                 sample_count: dst.desc.sample_count,
@@ -1128,6 +1471,18 @@ impl Queue {
     pub fn submit(
         &self,
         command_buffers: &[Arc<CommandBuffer>],
+    ) -> Result<SubmissionIndex, (SubmissionIndex, QueueSubmitError)> {
+        self.submit_with_external_sync(command_buffers, &[], &[])
+    }
+
+    /// Submit `command_buffers`, after the shared fences in `wait` have reached
+    /// their values. Once the submission completes, the shared fences in
+    /// `signal` are set to their values.
+    pub fn submit_with_external_sync(
+        &self,
+        command_buffers: &[Arc<CommandBuffer>],
+        wait: &[wgt::SharedFenceValue<Fallible<SharedFence>>],
+        signal: &[wgt::SharedFenceValue<Fallible<SharedFence>>],
     ) -> Result<SubmissionIndex, (SubmissionIndex, QueueSubmitError)> {
         profiling::scope!("Queue::submit");
         api_log!("Queue::submit");
@@ -1148,7 +1503,19 @@ impl Queue {
                 break 'error Err(e.into());
             }
 
+            let (wait, signal) = match (
+                self.get_shared_fence_values(wait),
+                self.get_shared_fence_values(signal),
+            ) {
+                (Ok(wait), Ok(signal)) => (wait, signal),
+                (Err(e), _) | (_, Err(e)) => break 'error Err(e),
+            };
+
             let mut active_executions = Vec::new();
+            // The index in `active_executions` of each command buffer resuming
+            // a render pass suspended by an earlier one, with where the first
+            // part of that render pass is.
+            let mut resumed_render_passes = Vec::new();
 
             let mut used_surface_textures = track::TextureUsageScope::default();
 
@@ -1162,6 +1529,10 @@ impl Queue {
 
                     let mut first_error = None;
 
+                    // The last render pass suspended by the command buffers
+                    // so far.
+                    let mut suspended_render_pass = None;
+
                     //TODO: if multiple command buffers are submitted, we can re-use the last
                     // native command buffer of the previous chain instead of always creating
                     // a temporary one, since the chains are not finished.
@@ -1184,7 +1555,7 @@ impl Queue {
                             if let Ok(ref mut cmd_buf_data) = cmd_buf_data {
                                 trace.add(Action::Submit(
                                     submit_index,
-                                    cmd_buf_data.commands.take().unwrap(),
+                                    cmd_buf_data.take_trace_commands().unwrap(),
                                 ));
                             }
                         }
@@ -1193,12 +1564,13 @@ impl Queue {
                             continue;
                         }
 
-                        let mut baked = match cmd_buf_data {
-                            Ok(cmd_buf_data) => {
+                        let (mut baked, reusable, resumed, suspended) = match cmd_buf_data {
+                            Ok(FinishedCommands::Once(mut cmd_buf_data)) => {
                                 let res = validate_command_buffer(
                                     command_buffer,
                                     self,
                                     &cmd_buf_data,
+                                    false,
                                     &snatch_guard,
                                     &mut submit_surface_textures_owned,
                                     &mut used_surface_textures,
@@ -1208,7 +1580,52 @@ impl Queue {
                                     first_error.get_or_insert(err);
                                     continue;
                                 }
-                                cmd_buf_data.into_baked_commands()
+                                let resumed = match resume_render_pass(
+                                    command_buffer,
+                                    cmd_buf_data.resumed_render_pass.take(),
+                                    &mut suspended_render_pass,
+                                ) {
+                                    Ok(resumed) => resumed,
+                                    Err(err) => {
+                                        first_error.get_or_insert(err);
+                                        continue;
+                                    }
+                                };
+                                let suspended =
+                                    cmd_buf_data.suspended_render_pass.take().map(|pass| {
+                                        (pass, mem::take(&mut cmd_buf_data.suspended_resources))
+                                    });
+                                (cmd_buf_data.into_baked_commands(), None, resumed, suspended)
+                            }
+                            Ok(FinishedCommands::Reusable(cmd_buf_data)) => {
+                                let res = validate_command_buffer(
+                                    command_buffer,
+                                    self,
+                                    &cmd_buf_data,
+                                    true,
+                                    &snatch_guard,
+                                    &mut submit_surface_textures_owned,
+                                    &mut used_surface_textures,
+                                    &mut command_index_guard,
+                                );
+                                if let Err(err) = res {
+                                    first_error.get_or_insert(err);
+                                    continue;
+                                }
+                                // Reusable command buffers never suspend or
+                                // resume render passes.
+                                if let Err(err) = resume_render_pass(
+                                    command_buffer,
+                                    None,
+                                    &mut suspended_render_pass,
+                                ) {
+                                    first_error.get_or_insert(err);
+                                    continue;
+                                }
+                                match cmd_buf_data.reusable_baked_commands(self) {
+                                    Ok(baked) => (baked, Some(cmd_buf_data), None, None),
+                                    Err(e) => break 'error Err(e.into()),
+                                }
                             }
                             Err(err) => {
                                 first_error.get_or_insert(err.into());
@@ -1223,6 +1640,21 @@ impl Queue {
 
                         //Note: locking the trackers has to be done after the storages
                         let mut trackers = self.device.trackers.lock();
+
+                        // The transitions are moved before the first part of
+                        // the resumed render pass, so they can't change the
+                        // resources it uses.
+                        if let Some(ref resumed) = resumed {
+                            if resumed.resources.need_barriers(&trackers, &baked.trackers) {
+                                first_error.get_or_insert(
+                                    QueueSubmitError::ResumedRenderPassBarriers(
+                                        command_buffer.error_ident(),
+                                    ),
+                                );
+                                continue;
+                            }
+                        }
+
                         if let Err(e) = baked.initialize_buffer_memory(&mut trackers, &snatch_guard)
                         {
                             break 'error Err(e.into());
@@ -1237,12 +1669,19 @@ impl Queue {
 
                         //Note: stateless trackers are not merged:
                         // device already knows these resources exist.
+                        let mut barrier_log = BarrierLog::new(&self.device, || {
+                            format!("submission of {}", command_buffer.error_ident())
+                        });
                         CommandBuffer::insert_barriers_from_device_tracker(
                             baked.encoder.raw.as_mut(),
                             &mut trackers,
-                            &baked.trackers,
+                            reusable
+                                .as_deref()
+                                .map_or(&baked.trackers, |cmd_buf_data| &cmd_buf_data.trackers),
                             &snatch_guard,
+                            barrier_log.as_mut(),
                         );
+                        drop(barrier_log);
 
                         if let Err(e) = baked.encoder.close_and_push_front() {
                             break 'error Err(e.into());
@@ -1251,7 +1690,9 @@ impl Queue {
                         // Transition surface textures into `Present` state.
                         // Note: we could technically do it after all of the command buffers,
                         // but here we have a command encoder by hand, so it's easier to use it.
-                        if !used_surface_textures.is_empty() {
+                        // Nothing may come after a suspended render pass, so this is left to
+                        // the command buffer resuming it.
+                        if !used_surface_textures.is_empty() && suspended.is_none() {
                             if let Err(e) = baked.encoder.open_pass(Some("(wgpu internal) Present"))
                             {
                                 break 'error Err(e.into());
@@ -1272,6 +1713,30 @@ impl Queue {
                             used_surface_textures = track::TextureUsageScope::default();
                         }
 
+                        let index = active_executions.len();
+                        if let Some(ref resumed) = resumed {
+                            resumed_render_passes.push((index, resumed.start));
+                        }
+                        if let Some((pass, resources)) = suspended {
+                            // If the render pass is suspended in the raw
+                            // command buffer it was resumed in, it goes on
+                            // from where it was first started.
+                            let last = baked.encoder.list.len() - 1;
+                            let (start, resources) = match resumed {
+                                Some(mut chain) if last == RESUMED_RENDER_PASS_PRELUDE => {
+                                    chain.resources.extend(resources);
+                                    (chain.start, chain.resources)
+                                }
+                                _ => ((index, last), resources),
+                            };
+                            suspended_render_pass = Some(SuspendedChain {
+                                command_buffer: command_buffer.error_ident(),
+                                pass,
+                                start,
+                                resources,
+                            });
+                        }
+
                         // done
                         active_executions.push(EncoderInFlight {
                             inner: baked.encoder,
@@ -1279,12 +1744,20 @@ impl Queue {
                             temp_resources: baked.temp_resources,
                             _indirect_draw_validation_resources: baked
                                 .indirect_draw_validation_resources,
+                            reusable,
                             pending_buffers: FastHashMap::default(),
                             pending_textures: FastHashMap::default(),
                             pending_blas_s: FastHashMap::default(),
+                            blas_serializations: Vec::new(),
                         });
                     }
 
+                    if let Some(suspended) = suspended_render_pass {
+                        first_error.get_or_insert(QueueSubmitError::UnresumedRenderPass(
+                            suspended.command_buffer,
+                        ));
+                    }
+
                     if let Some(first_error) = first_error {
                         break 'error Err(first_error);
                     }
@@ -1298,6 +1771,11 @@ impl Queue {
                 for texture in pending_writes.dst_textures.values() {
                     match texture.try_inner(&snatch_guard) {
                         Ok(TextureInner::Native { .. }) => {}
+                        Ok(TextureInner::Surface { .. }) if self.additional.is_some() => {
+                            break 'error Err(QueueSubmitError::SurfaceTextureOnAdditionalQueue(
+                                texture.error_ident(),
+                            ));
+                        }
                         Ok(TextureInner::Surface { .. }) => {
                             // Compare the Arcs by pointer as Textures don't implement Eq
                             submit_surface_textures_owned
@@ -1335,17 +1813,37 @@ impl Queue {
                 }
             }
 
-            match pending_writes.pre_submit(&self.device.command_allocator, &self.device, self) {
+            let mut first_execution = 0;
+            match pending_writes.pre_submit(self.command_allocator(), &self.device, self) {
                 Ok(Some(pending_execution)) => {
                     active_executions.insert(0, pending_execution);
+                    first_execution = 1;
                 }
                 Ok(None) => {}
                 Err(e) => break 'error Err(e.into()),
             }
-            let hal_command_buffers = active_executions
-                .iter()
-                .flat_map(|e| e.inner.list.iter().map(|b| b.as_ref()))
-                .collect::<Vec<_>>();
+            let mut hal_command_buffers = Vec::new();
+            let mut execution_starts = Vec::with_capacity(active_executions.len());
+            for execution in active_executions.iter() {
+                execution_starts.push(hal_command_buffers.len());
+                hal_command_buffers.extend(execution.raw_command_buffers());
+            }
+
+            // Nothing may come between the parts of a render pass suspended by
+            // one command buffer and resumed by the next, so the transitions
+            // and the work recorded before the resuming command buffer's
+            // render pass are moved before the first part.
+            let mut hoisted = (None, 0);
+            for (index, (start_index, start)) in resumed_render_passes {
+                if hoisted.0 != Some((start_index, start)) {
+                    hoisted = (Some((start_index, start)), 0);
+                }
+                let to = execution_starts[first_execution + start_index] + start + hoisted.1;
+                let from = execution_starts[first_execution + index];
+                hal_command_buffers[to..from + RESUMED_RENDER_PASS_PRELUDE]
+                    .rotate_right(RESUMED_RENDER_PASS_PRELUDE);
+                hoisted.1 += RESUMED_RENDER_PASS_PRELUDE;
+            }
 
             {
                 let mut submit_surface_textures =
@@ -1361,11 +1859,18 @@ impl Queue {
                     submit_surface_textures.push(raw);
                 }
 
+                if let Err(e) = unsafe { self.sync_with_shared_fences(&wait, &signal) }
+                    .map_err(|e| self.device.handle_hal_error(e))
+                {
+                    break 'error Err(e.into());
+                }
+
                 if let Err(e) = unsafe {
-                    self.raw().submit(
+                    self.raw_submit(
+                        fence.as_mut(),
                         &hal_command_buffers,
                         &submit_surface_textures,
-                        (fence.as_mut(), submit_index),
+                        submit_index,
                     )
                 }
                 .map_err(|e| self.device.handle_hal_error(e))
@@ -1384,8 +1889,9 @@ impl Queue {
             profiling::scope!("cleanup");
 
             // this will register the new submission to the life time tracker
+            let shared_fences = wait.into_iter().chain(signal).map(|v| v.fence).collect();
             self.lock_life()
-                .track_submission(submit_index, active_executions);
+                .track_submission(submit_index, active_executions, shared_fences);
             drop(pending_writes);
 
             // This will schedule destruction of all resources that are no longer needed
@@ -1426,10 +1932,350 @@ impl Queue {
         Ok(submit_index)
     }
 
+    fn get_shared_fence_values(
+        &self,
+        values: &[wgt::SharedFenceValue<Fallible<SharedFence>>],
+    ) -> Result<Vec<wgt::SharedFenceValue<Arc<SharedFence>>>, QueueSubmitError> {
+        let mut resolved = Vec::with_capacity(values.len());
+        for value in values {
+            let fence = value.fence.clone().get()?;
+            self.same_device_as(fence.as_ref())?;
+            resolved.push(wgt::SharedFenceValue {
+                fence,
+                value: value.value,
+            });
+        }
+        Ok(resolved)
+    }
+
+    /// Make the next raw submission wait for the fences in `wait`, and signal
+    /// the fences in `signal`.
+    ///
+    /// # Safety
+    ///
+    /// - The fences must be kept alive until the next submission completes.
+    unsafe fn sync_with_shared_fences(
+        &self,
+        wait: &[wgt::SharedFenceValue<Arc<SharedFence>>],
+        signal: &[wgt::SharedFenceValue<Arc<SharedFence>>],
+    ) -> Result<(), hal::DeviceError> {
+        for wait in wait {
+            unsafe { self.raw().wait_for_fence(wait.fence.raw(), wait.value) }?;
+        }
+        for signal in signal {
+            unsafe { self.raw().signal_fence(signal.fence.raw(), signal.value) }?;
+        }
+        Ok(())
+    }
+
+    /// Submit `command_buffers` to the raw queue, signalling this queue's fence
+    /// with `submit_index` on completion.
+    ///
+    /// `fence` is the main queue's fence, which the caller must have locked
+    /// for writing.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`hal::Queue::submit`].
+    unsafe fn raw_submit(
+        &self,
+        fence: &mut dyn hal::DynFence,
+        command_buffers: &[&dyn hal::DynCommandBuffer],
+        surface_textures: &[&dyn hal::DynSurfaceTexture],
+        submit_index: SubmissionIndex,
+    ) -> Result<(), hal::DeviceError> {
+        match self.additional {
+            Some(ref additional) => {
+                let mut additional_queue_fences = self.device.additional_queue_fences.lock();
+                let queue_fence = &mut additional_queue_fences[additional.fence_index];
+                unsafe {
+                    self.raw().submit(
+                        command_buffers,
+                        surface_textures,
+                        (queue_fence.raw.as_mut(), submit_index),
+                    )
+                }?;
+                queue_fence.last_submission_index = submit_index;
+            }
+            None => {
+                unsafe {
+                    self.raw()
+                        .submit(command_buffers, surface_textures, (fence, submit_index))
+                }?;
+                self.device
+                    .last_main_queue_submission_index
+                    .store(submit_index, Ordering::Release);
+            }
+        }
+        Ok(())
+    }
+
+    /// Make this queue's future submissions wait for the submission
+    /// `submission_index`, and every submission before it, to complete on the
+    /// device's other queues.
+    pub fn wait_for_submission(
+        &self,
+        submission_index: SubmissionIndex,
+    ) -> Result<(), WaitIdleError> {
+        api_log!("Queue::wait_for_submission {submission_index}");
+
+        let fence = self.device.fence.read();
+
+        let last_successful_submission_index = self
+            .device
+            .last_successful_submission_index
+            .load(Ordering::Acquire);
+        if submission_index > last_successful_submission_index {
+            return Err(WaitIdleError::WrongSubmissionIndex(
+                submission_index,
+                last_successful_submission_index,
+            ));
+        }
+
+        let additional_queue_fences = self.device.additional_queue_fences.lock();
+
+        // A queue's fence never goes past the index of its own last submission.
+        let main_fence = self.additional.as_ref().map(|_| {
+            let last_submission_index = self
+                .device
+                .last_main_queue_submission_index
+                .load(Ordering::Acquire);
+            (fence.as_ref(), last_submission_index)
+        });
+        let own_fence_index = self
+            .additional
+            .as_ref()
+            .map(|additional| additional.fence_index);
+        let other_fences = additional_queue_fences
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| Some(index) != own_fence_index)
+            .map(|(_, queue_fence)| (queue_fence.raw.as_ref(), queue_fence.last_submission_index));
+
+        for (other_fence, last_submission_index) in main_fence.into_iter().chain(other_fences) {
+            let value = submission_index.min(last_submission_index);
+            let completed = unsafe { self.device.raw().get_fence_value(other_fence) }
+                .map_err(|e| self.device.handle_hal_error(e))?;
+            if completed >= value {
+                continue;
+            }
+            // SAFETY: the submission signalling `value` was made while holding
+            // the fence lock, which we hold now.
+            unsafe { self.raw().wait_for_fence(other_fence, value) }
+                .map_err(|e| self.device.handle_hal_error(e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Binds memory to, or unbinds memory from, regions of sparse buffers and
+    /// textures, after all previously submitted work.
+    pub fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[wgt::SparseBufferBinding<Fallible<Buffer>, Fallible<MemoryHeap>>],
+        texture_bindings: &[wgt::SparseTextureBinding<Fallible<Texture>, Fallible<MemoryHeap>>],
+    ) -> Result<(), QueueBindSparseError> {
+        use QueueBindSparseError as Error;
+
+        profiling::scope!("Queue::bind_sparse_memory");
+        api_log!("Queue::bind_sparse_memory");
+
+        self.device.check_is_valid()?;
+        self.device
+            .require_features(wgt::Features::SPARSE_BINDING)?;
+        if self.additional.is_some() {
+            return Err(Error::AdditionalQueue);
+        }
+
+        let get_memory = |memory: &Option<wgt::HeapRegion<Fallible<MemoryHeap>>>,
+                          size: wgt::BufferAddress|
+         -> Result<_, Error> {
+            let Some(region) = memory else {
+                return Ok(None);
+            };
+            let heap = region.heap.clone().get()?;
+            self.same_device_as(heap.as_ref())?;
+            if region.offset % wgt::SPARSE_PAGE_SIZE != 0 {
+                return Err(Error::UnalignedHeapOffset {
+                    heap: heap.error_ident(),
+                    offset: region.offset,
+                });
+            }
+            let end = region.offset.checked_add(size);
+            if end.is_none_or(|end| end > heap.size) {
+                return Err(Error::HeapRangeOutOfBounds {
+                    heap: heap.error_ident(),
+                    offset: region.offset,
+                    size,
+                    heap_size: heap.size,
+                });
+            }
+            Ok(Some((heap, region.offset)))
+        };
+
+        let mut buffers = Vec::with_capacity(buffer_bindings.len());
+        for binding in buffer_bindings {
+            let buffer = binding.buffer.clone().get()?;
+            self.same_device_as(buffer.as_ref())?;
+            if buffer.sparse_heaps.is_none() {
+                return Err(Error::NotSparse(buffer.error_ident()));
+            }
+            if binding.offset % wgt::SPARSE_PAGE_SIZE != 0
+                || binding.size % wgt::SPARSE_PAGE_SIZE != 0
+            {
+                return Err(Error::UnalignedBufferRange {
+                    buffer: buffer.error_ident(),
+                    offset: binding.offset,
+                    size: binding.size,
+                });
+            }
+            // The buffer was created with a whole number of pages.
+            let buffer_size = buffer.size.next_multiple_of(wgt::SPARSE_PAGE_SIZE);
+            let end = binding.offset.checked_add(binding.size);
+            if end.is_none_or(|end| end > buffer_size) {
+                return Err(Error::BufferRangeOutOfBounds {
+                    buffer: buffer.error_ident(),
+                    offset: binding.offset,
+                    size: binding.size,
+                    buffer_size,
+                });
+            }
+            let memory = get_memory(&binding.memory, binding.size)?;
+            if binding.size != 0 {
+                buffers.push((
+                    buffer,
+                    binding.offset..binding.offset + binding.size,
+                    memory,
+                ));
+            }
+        }
+
+        let mut textures = Vec::with_capacity(texture_bindings.len());
+        for binding in texture_bindings {
+            let texture = binding.texture.clone().get()?;
+            self.same_device_as(texture.as_ref())?;
+            if texture.sparse_heaps.is_none() {
+                return Err(Error::NotSparse(texture.error_ident()));
+            }
+            let out_of_bounds = || Error::TextureRegionOutOfBounds {
+                texture: texture.error_ident(),
+                mip_level: binding.mip_level,
+                array_layer: binding.array_layer,
+                origin: binding.origin,
+                size: binding.size,
+            };
+            if binding.array_layer >= texture.desc.array_layer_count() {
+                return Err(out_of_bounds());
+            }
+            let Some(mip_size) = texture.desc.mip_level_size(binding.mip_level) else {
+                return Err(out_of_bounds());
+            };
+            let (origin, size) = (binding.origin, binding.size);
+            let end_x = origin.x.checked_add(size.width);
+            let end_y = origin.y.checked_add(size.height);
+            if end_x.is_none_or(|end| end > mip_size.width)
+                || end_y.is_none_or(|end| end > mip_size.height)
+                || origin.z != 0
+                || size.depth_or_array_layers != 1
+            {
+                return Err(out_of_bounds());
+            }
+            // Regions may end in a partial tile only at the edge of the mip level.
+            let tile_size = texture.desc.format.sparse_tile_size().unwrap();
+            if origin.x % tile_size.width != 0
+                || origin.y % tile_size.height != 0
+                || (size.width % tile_size.width != 0 && end_x != Some(mip_size.width))
+                || (size.height % tile_size.height != 0 && end_y != Some(mip_size.height))
+            {
+                return Err(Error::UnalignedTextureRegion {
+                    texture: texture.error_ident(),
+                    mip_level: binding.mip_level,
+                    array_layer: binding.array_layer,
+                    origin,
+                    size,
+                    tile_size,
+                });
+            }
+            let tile_count = u64::from(size.width.div_ceil(tile_size.width))
+                * u64::from(size.height.div_ceil(tile_size.height));
+            let memory = get_memory(&binding.memory, tile_count * wgt::SPARSE_PAGE_SIZE)?;
+            if tile_count != 0 {
+                textures.push((texture, binding, memory));
+            }
+        }
+
+        let snatch_guard = self.device.snatchable_lock.read();
+        // Bindings are ordered with submissions on the raw queue, so they can't
+        // happen concurrently with one.
+        let fence = self.device.fence.write();
+
+        let hal_buffer_bindings = buffers
+            .iter()
+            .map(|(buffer, range, memory)| {
+                Ok(hal::SparseBufferBinding {
+                    buffer: buffer.try_raw(&snatch_guard)?,
+                    range: range.clone(),
+                    memory: memory.as_ref().map(|(heap, offset)| (heap.raw(), *offset)),
+                })
+            })
+            .collect::<Result<Vec<_>, DestroyedResourceError>>()?;
+        let hal_texture_bindings = textures
+            .iter()
+            .map(|(texture, binding, memory)| {
+                Ok(hal::SparseTextureBinding {
+                    texture: texture.try_raw(&snatch_guard)?,
+                    mip_level: binding.mip_level,
+                    array_layer: binding.array_layer,
+                    origin: binding.origin,
+                    size: binding.size,
+                    memory: memory.as_ref().map(|(heap, offset)| (heap.raw(), *offset)),
+                })
+            })
+            .collect::<Result<Vec<_>, DestroyedResourceError>>()?;
+
+        unsafe {
+            self.raw()
+                .bind_sparse_memory(&hal_buffer_bindings, &hal_texture_bindings)
+        }
+        .map_err(|e| self.device.handle_hal_error(e))?;
+
+        drop(fence);
+        drop(snatch_guard);
+
+        // The heaps are kept alive for as long as the resources their memory may
+        // still be bound to.
+        fn keep_heap(heaps: &Mutex<Vec<Arc<MemoryHeap>>>, heap: &Arc<MemoryHeap>) {
+            let mut heaps = heaps.lock();
+            if !heaps.iter().any(|other| Arc::ptr_eq(other, heap)) {
+                heaps.push(heap.clone());
+            }
+        }
+        for (buffer, range, memory) in buffers {
+            if let Some((heap, _)) = memory {
+                keep_heap(buffer.sparse_heaps.as_ref().unwrap(), &heap);
+            }
+            // Newly bound memory holds anything, so it needs to be zeroed before it's read.
+            buffer.initialization_status.write().discard_range(range);
+        }
+        // Texture contents are tracked per subresource, so newly bound tiles are left
+        // undefined rather than having to clear the tiles around them as well.
+        for (texture, _, memory) in textures {
+            if let Some((heap, _)) = memory {
+                keep_heap(texture.sparse_heaps.as_ref().unwrap(), &heap);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn get_timestamp_period(&self) -> f32 {
         unsafe { self.raw().get_timestamp_period() }
     }
 
+    pub fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        unsafe { self.raw().get_timestamp_calibration() }
+    }
+
     /// `closure` is guaranteed to be called.
     pub fn on_submitted_work_done(
         &self,
@@ -1440,6 +2286,25 @@ impl Queue {
         self.lock_life().add_work_done_closure(closure)
     }
 
+    pub fn on_submission_done(
+        &self,
+        submission_index: SubmissionIndex,
+        closure: SubmittedWorkDoneClosure,
+    ) -> Option<SubmissionIndex> {
+        api_log!("Queue::on_submission_done {submission_index}");
+        self.lock_life()
+            .add_submission_done_closure(submission_index, closure)
+    }
+
+    /// Returns the index of the last submission that has completed, along with
+    /// every submission before it.
+    pub fn get_completed_submission_index(&self) -> Result<SubmissionIndex, DeviceError> {
+        let fence = self.device.fence.read();
+        self.device
+            .completed_submission_index(fence.as_ref())
+            .map_err(|e| self.device.handle_hal_error(e))
+    }
+
     pub fn compact_blas(&self, blas: &Arc<Blas>) -> Result<Arc<Blas>, CompactBlasError> {
         profiling::scope!("Queue::compact_blas");
         api_log!("Queue::compact_blas");
@@ -1469,6 +2334,9 @@ impl Queue {
                     size: size_info.acceleration_structure_size,
                     format: hal::AccelerationStructureFormat::BottomLevel,
                     allow_compaction: false,
+                    allow_serialization: device
+                        .features
+                        .contains(wgt::Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION),
                 })
         }
         .map_err(DeviceError::from_hal)?;
@@ -1506,6 +2374,7 @@ impl Queue {
             update_mode: blas.update_mode,
             // Bypass the submit checks which update this because we don't submit this normally.
             built_index: RwLock::new(rank::BLAS_BUILT_INDEX, Some(built_index)),
+            built_counts: RwLock::new(rank::BLAS_BUILT_COUNTS, blas.built_counts.read().clone()),
             handle,
             label: blas.label.clone() + " compacted",
             tracking_data: TrackingData::new(blas.device.tracker_indices.blas_s.clone()),
@@ -1518,6 +2387,140 @@ impl Queue {
 
         Ok(new_blas)
     }
+
+    /// Starts serializing `blas`, calling `callback` once the serialized data has been
+    /// read back.
+    ///
+    /// Each step of the serialization is recorded into the pending writes, so it only
+    /// progresses with queue submissions.
+    pub(crate) fn serialize_blas(
+        &self,
+        blas: &Arc<Blas>,
+        callback: BlasSerializeCallback,
+    ) -> Result<(), (BlasSerializeCallback, BlasSerializeError)> {
+        profiling::scope!("Queue::serialize_blas");
+        api_log!("Queue::serialize_blas");
+
+        if let Err(e) = self.device.check_is_valid() {
+            return Err((callback, e.into()));
+        }
+        if let Err(e) = self
+            .device
+            .require_features(wgt::Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION)
+        {
+            return Err((callback, e.into()));
+        }
+        if blas.built_index.read().is_none() {
+            return Err((callback, BlasSerializeError::NotBuilt));
+        }
+
+        let snatch_guard = self.device.snatchable_lock.read();
+        let mut pending_writes = self.pending_writes.lock();
+        match self.record_blas_serialization(blas, None, &mut pending_writes, &snatch_guard) {
+            Ok(buffer) => {
+                pending_writes
+                    .blas_serializations
+                    .push(BlasPendingSerialization {
+                        blas: blas.clone(),
+                        buffer,
+                        reading_size: true,
+                        callback,
+                    });
+                Ok(())
+            }
+            Err(e) => Err((callback, e)),
+        }
+    }
+
+    /// Records reading back the serialized size of `blas` if `size` is `None`, or else
+    /// serializing it into a buffer of `size` bytes.
+    fn record_blas_serialization(
+        &self,
+        blas: &Arc<Blas>,
+        size: Option<wgt::BufferAddress>,
+        pending_writes: &mut PendingWrites,
+        snatch_guard: &SnatchGuard,
+    ) -> Result<SerializedBlasBuffer, BlasSerializeError> {
+        let raw = blas.try_raw(snatch_guard)?;
+
+        // The serialized size is followed by other information on some backends.
+        let buffer_size = size.unwrap_or(2 * size_of::<wgt::BufferAddress>() as wgt::BufferAddress);
+        let buffer = SerializedBlasBuffer::new(
+            &self.device,
+            buffer_size,
+            wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY | wgt::BufferUses::MAP_READ,
+        )?;
+
+        let cmd_buf_raw = pending_writes.activate();
+        unsafe {
+            cmd_buf_raw.place_acceleration_structure_barrier(hal::AccelerationStructureBarrier {
+                usage: hal::StateTransition {
+                    from: hal::AccelerationStructureUses::BUILD_OUTPUT,
+                    to: hal::AccelerationStructureUses::QUERY_INPUT
+                        | hal::AccelerationStructureUses::COPY_SRC,
+                },
+            });
+            cmd_buf_raw.transition_buffers(&[hal::BufferBarrier {
+                buffer: buffer.raw(),
+                usage: hal::StateTransition {
+                    from: wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY,
+                    to: wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY,
+                },
+            }]);
+            match size {
+                None => cmd_buf_raw.read_acceleration_structure_serialized_size(raw, buffer.raw()),
+                Some(_) => cmd_buf_raw.copy_acceleration_structure_to_buffer(raw, buffer.raw(), 0),
+            }
+        }
+        pending_writes.insert_blas(blas);
+
+        Ok(buffer)
+    }
+
+    /// Takes a serialization whose last step has completed on the GPU to its next step.
+    ///
+    /// Returns the callback to fire if the serialization is finished.
+    fn advance_blas_serialization(
+        &self,
+        pending: BlasPendingSerialization,
+        snatch_guard: &SnatchGuard,
+    ) -> Option<BlasSerializeReadyPendingClosure> {
+        let BlasPendingSerialization {
+            blas,
+            buffer,
+            reading_size,
+            callback,
+        } = pending;
+
+        let data = match buffer.read() {
+            Ok(data) => data,
+            Err(e) => return Some((callback, Err(e.into()))),
+        };
+        if !reading_size {
+            return Some((callback, Ok(data)));
+        }
+
+        let size = u64::from_ne_bytes(data[..size_of::<u64>()].try_into().unwrap());
+        if size == 0 {
+            return Some((callback, Ok(Vec::new())));
+        }
+
+        let mut pending_writes = self.pending_writes.lock();
+        match self.record_blas_serialization(&blas, Some(size), &mut pending_writes, snatch_guard) {
+            Ok(buffer) => {
+                pending_writes
+                    .blas_serializations
+                    .push(BlasPendingSerialization {
+                        blas,
+                        buffer,
+                        reading_size: false,
+                        callback,
+                    });
+                None
+            }
+            Err(e) => Some((callback, Err(e))),
+        }
+    }
 }
 
 impl Global {
@@ -1650,6 +2653,72 @@ impl Global {
         queue.submit(&command_buffers)
     }
 
+    pub fn queue_submit_with_external_sync(
+        &self,
+        queue_id: QueueId,
+        command_buffer_ids: &[id::CommandBufferId],
+        wait: &[wgt::SharedFenceValue<id::SharedFenceId>],
+        signal: &[wgt::SharedFenceValue<id::SharedFenceId>],
+    ) -> Result<SubmissionIndex, (SubmissionIndex, QueueSubmitError)> {
+        let queue = self.hub.queues.get(queue_id);
+        let command_buffer_guard = self.hub.command_buffers.read();
+        let command_buffers = command_buffer_ids
+            .iter()
+            .map(|id| command_buffer_guard.get(*id))
+            .collect::<Vec<_>>();
+        drop(command_buffer_guard);
+        let shared_fence_guard = self.hub.shared_fences.read();
+        let get_values = |values: &[wgt::SharedFenceValue<id::SharedFenceId>]| {
+            values
+                .iter()
+                .map(|value| wgt::SharedFenceValue {
+                    fence: shared_fence_guard.get(value.fence),
+                    value: value.value,
+                })
+                .collect::<Vec<_>>()
+        };
+        let wait = get_values(wait);
+        let signal = get_values(signal);
+        drop(shared_fence_guard);
+        queue.submit_with_external_sync(&command_buffers, &wait, &signal)
+    }
+
+    pub fn queue_bind_sparse_memory(
+        &self,
+        queue_id: QueueId,
+        buffer_bindings: &[wgt::SparseBufferBinding<id::BufferId, id::MemoryHeapId>],
+        texture_bindings: &[wgt::SparseTextureBinding<id::TextureId, id::MemoryHeapId>],
+    ) -> Result<(), QueueBindSparseError> {
+        let queue = self.hub.queues.get(queue_id);
+        let get_memory = |memory: &Option<wgt::HeapRegion<id::MemoryHeapId>>| {
+            memory.map(|region| wgt::HeapRegion {
+                heap: self.hub.memory_heaps.get(region.heap),
+                offset: region.offset,
+            })
+        };
+        let buffer_bindings = buffer_bindings
+            .iter()
+            .map(|binding| wgt::SparseBufferBinding {
+                buffer: self.hub.buffers.get(binding.buffer),
+                offset: binding.offset,
+                size: binding.size,
+                memory: get_memory(&binding.memory),
+            })
+            .collect::<Vec<_>>();
+        let texture_bindings = texture_bindings
+            .iter()
+            .map(|binding| wgt::SparseTextureBinding {
+                texture: self.hub.textures.get(binding.texture),
+                mip_level: binding.mip_level,
+                array_layer: binding.array_layer,
+                origin: binding.origin,
+                size: binding.size,
+                memory: get_memory(&binding.memory),
+            })
+            .collect::<Vec<_>>();
+        queue.bind_sparse_memory(&buffer_bindings, &texture_bindings)
+    }
+
     pub fn queue_get_timestamp_period(&self, queue_id: QueueId) -> f32 {
         let queue = self.hub.queues.get(queue_id);
 
@@ -1660,6 +2729,37 @@ impl Global {
         queue.get_timestamp_period()
     }
 
+    pub fn queue_get_timestamp_calibration(
+        &self,
+        queue_id: QueueId,
+    ) -> Option<wgt::TimestampCalibration> {
+        let queue = self.hub.queues.get(queue_id);
+        let calibration = queue.get_timestamp_calibration()?;
+
+        // Normalized timestamp queries are in nanoseconds, so the GPU timestamp has to be too.
+        if queue.device.timestamp_normalizer.get().unwrap().enabled() {
+            return Some(wgt::TimestampCalibration {
+                gpu_timestamp: (calibration.gpu_timestamp as f64
+                    * calibration.timestamp_period as f64) as u64,
+                timestamp_period: 1.0,
+                ..calibration
+            });
+        }
+
+        Some(calibration)
+    }
+
+    pub fn queue_wait_for_submission(
+        &self,
+        queue_id: QueueId,
+        submission_index: SubmissionIndex,
+    ) -> Result<(), WaitIdleError> {
+        api_log!("Queue::wait_for_submission {queue_id:?} {submission_index}");
+
+        let queue = self.hub.queues.get(queue_id);
+        queue.wait_for_submission(submission_index)
+    }
+
     pub fn queue_on_submitted_work_done(
         &self,
         queue_id: QueueId,
@@ -1673,6 +2773,43 @@ impl Global {
         result.unwrap_or(0) // '0' means no wait is necessary
     }
 
+    pub fn queue_on_submission_done(
+        &self,
+        queue_id: QueueId,
+        submission_index: SubmissionIndex,
+        closure: SubmittedWorkDoneClosure,
+    ) -> SubmissionIndex {
+        api_log!("Queue::on_submission_done {queue_id:?} {submission_index}");
+
+        let queue = self.hub.queues.get(queue_id);
+        let result = queue.on_submission_done(submission_index, closure);
+        result.unwrap_or(0) // '0' means no wait is necessary
+    }
+
+    pub fn queue_get_completed_submission_index(
+        &self,
+        queue_id: QueueId,
+    ) -> Result<SubmissionIndex, DeviceError> {
+        let queue = self.hub.queues.get(queue_id);
+        queue.get_completed_submission_index()
+    }
+
+    /// Like [`Global::device_poll`], for the device of `queue_id`.
+    pub fn queue_poll(
+        &self,
+        queue_id: QueueId,
+        poll_type: wgt::PollType<SubmissionIndex>,
+    ) -> Result<wgt::PollStatus, WaitIdleError> {
+        api_log!("Queue::poll {queue_id:?} {poll_type:?}");
+
+        let queue = self.hub.queues.get(queue_id);
+        let (closures, result) = Self::poll_single_device(&queue.device, poll_type);
+
+        closures.fire();
+
+        result
+    }
+
     pub fn queue_compact_blas(
         &self,
         queue_id: QueueId,
@@ -1728,7 +2865,8 @@ impl Global {
 fn validate_command_buffer(
     command_buffer: &CommandBuffer,
     queue: &Queue,
-    cmd_buf_data: &crate::command::CommandBufferMutable,
+    cmd_buf_data: &CommandBufferMutable,
+    reusable: bool,
     snatch_guard: &SnatchGuard,
     submit_surface_textures_owned: &mut FastHashMap<*const Texture, Arc<Texture>>,
     used_surface_textures: &mut track::TextureUsageScope,
@@ -1736,6 +2874,10 @@ fn validate_command_buffer(
 ) -> Result<(), QueueSubmitError> {
     command_buffer.same_device_as(queue)?;
 
+    if !Arc::ptr_eq(&cmd_buf_data.encoder.allocator, queue.command_allocator()) {
+        return Err(QueueSubmitError::WrongQueue(command_buffer.error_ident()));
+    }
+
     {
         profiling::scope!("check resource state");
 
@@ -1755,6 +2897,16 @@ fn validate_command_buffer(
             for texture in cmd_buf_data.trackers.textures.used_resources() {
                 let should_extend = match texture.try_inner(snatch_guard)? {
                     TextureInner::Native { .. } => false,
+                    TextureInner::Surface { .. } if queue.additional.is_some() => {
+                        return Err(QueueSubmitError::SurfaceTextureOnAdditionalQueue(
+                            texture.error_ident(),
+                        ));
+                    }
+                    TextureInner::Surface { .. } if reusable => {
+                        return Err(QueueSubmitError::SurfaceTextureInReusableCommandBuffer(
+                            texture.error_ident(),
+                        ));
+                    }
                     TextureInner::Surface { .. } => {
                         // Compare the Arcs by pointer as Textures don't implement Eq.
                         submit_surface_textures_owned.insert(Arc::as_ptr(texture), texture.clone());
@@ -1780,3 +2932,41 @@ fn validate_command_buffer(
     }
     Ok(())
 }
+
+/// The number of raw command buffers a command buffer resuming a render pass
+/// suspended by another one has before it: the transitions, and the work
+/// recorded before the render pass.
+const RESUMED_RENDER_PASS_PRELUDE: usize = 2;
+
+/// A render pass suspended by a command buffer of a submission.
+struct SuspendedChain {
+    /// The command buffer that suspended the render pass.
+    command_buffer: ResourceErrorIdent,
+    pass: SuspendedRenderPass,
+    /// The index in `active_executions` of the command buffer the first part
+    /// of the render pass was recorded into, and the index of the raw command
+    /// buffer in it.
+    start: (usize, usize),
+    /// The resources used by the parts of the render pass so far.
+    resources: SuspendedResources,
+}
+
+/// Checks that a render pass suspended by the previous command buffer of the
+/// submission is resumed by `resumed`, the first render pass of
+/// `command_buffer`, and returns the suspended render pass if so.
+fn resume_render_pass(
+    command_buffer: &CommandBuffer,
+    resumed: Option<SuspendedRenderPass>,
+    suspended_render_pass: &mut Option<SuspendedChain>,
+) -> Result<Option<SuspendedChain>, QueueSubmitError> {
+    match (suspended_render_pass.take(), resumed) {
+        (None, None) => Ok(None),
+        (Some(suspended), Some(resumed)) if suspended.pass.matches(&resumed) => Ok(Some(suspended)),
+        (Some(suspended), None) => Err(QueueSubmitError::UnresumedRenderPass(
+            suspended.command_buffer,
+        )),
+        (_, Some(_)) => Err(QueueSubmitError::InvalidRenderPassResume(
+            command_buffer.error_ident(),
+        )),
+    }
+}
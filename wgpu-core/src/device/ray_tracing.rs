@@ -1,21 +1,25 @@
 use alloc::{string::ToString as _, sync::Arc, vec::Vec};
-use core::mem::{ManuallyDrop, size_of};
+use core::{
+    mem::{ManuallyDrop, size_of},
+    num::NonZeroU64,
+};
 
 use crate::device::DeviceError;
 #[cfg(feature = "trace")]
 use crate::device::trace;
 use crate::{
     LabelHelpers, api_log,
-    device::Device,
+    device::{Device, queue::TempResource},
     global::Global,
     id::{self, BlasId, TlasId},
     lock::RwLock,
     lock::{Mutex, rank},
-    ray_tracing::BlasPrepareCompactError,
+    ray_tracing::{BlasPrepareCompactError, BlasSerializeError},
     ray_tracing::{CreateBlasError, CreateTlasError},
     resource,
     resource::{
-        BlasCompactCallback, BlasCompactState, Fallible, InvalidResourceError, TrackingData,
+        BlasCompactCallback, BlasCompactState, BlasSerializeCallback, Fallible,
+        InvalidResourceError, RawResourceAccess, TrackingData,
     },
     snatch::Snatchable,
 };
@@ -23,10 +27,13 @@ use hal::AccelerationStructureTriangleIndices;
 use wgt::Features;
 
 impl Device {
+    /// Creates a BLAS for geometry of `sizes`, large enough to also hold an acceleration
+    /// structure of `deserialized_size` bytes if given.
     fn create_blas(
         self: &Arc<Self>,
         blas_desc: &resource::BlasDescriptor,
         sizes: wgt::BlasGeometrySizeDescriptors,
+        deserialized_size: Option<wgt::BufferAddress>,
     ) -> Result<Arc<resource::Blas>, CreateBlasError> {
         self.check_is_valid()?;
         self.require_features(Features::EXPERIMENTAL_RAY_QUERY)?;
@@ -38,6 +45,14 @@ impl Device {
             self.require_features(Features::EXPERIMENTAL_RAY_HIT_VERTEX_RETURN)?;
         }
 
+        if blas_desc.update_mode == wgt::AccelerationStructureUpdateMode::Refit
+            && !blas_desc
+                .flags
+                .contains(wgt::AccelerationStructureFlags::ALLOW_UPDATE)
+        {
+            return Err(CreateBlasError::RefitWithoutAllowUpdate);
+        }
+
         let size_info = match &sizes {
             wgt::BlasGeometrySizeDescriptors::Triangles { descriptors } => {
                 if descriptors.len() as u32 > self.limits.max_blas_geometry_count {
@@ -117,6 +132,12 @@ impl Device {
             }
         };
 
+        let mut size_info = size_info;
+        if let Some(deserialized_size) = deserialized_size {
+            size_info.acceleration_structure_size =
+                size_info.acceleration_structure_size.max(deserialized_size);
+        }
+
         let raw = unsafe {
             self.raw()
                 .create_acceleration_structure(&hal::AccelerationStructureDescriptor {
@@ -126,6 +147,9 @@ impl Device {
                     allow_compaction: blas_desc
                         .flags
                         .contains(wgpu_types::AccelerationStructureFlags::ALLOW_COMPACTION),
+                    allow_serialization: self
+                        .features
+                        .contains(Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION),
                 })
         }
         .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
@@ -164,6 +188,7 @@ impl Device {
             handle,
             label: blas_desc.label.to_string(),
             built_index: RwLock::new(rank::BLAS_BUILT_INDEX, None),
+            built_counts: RwLock::new(rank::BLAS_BUILT_COUNTS, None),
             tracking_data: TrackingData::new(self.tracker_indices.blas_s.clone()),
             compaction_buffer,
             compacted_state: Mutex::new(rank::BLAS_COMPACTION_STATE, BlasCompactState::Idle),
@@ -200,6 +225,14 @@ impl Device {
             self.require_features(Features::EXPERIMENTAL_RAY_HIT_VERTEX_RETURN)?;
         }
 
+        if desc.update_mode == wgt::AccelerationStructureUpdateMode::Refit
+            && !desc
+                .flags
+                .contains(wgt::AccelerationStructureFlags::ALLOW_UPDATE)
+        {
+            return Err(CreateTlasError::RefitWithoutAllowUpdate);
+        }
+
         let size_info = unsafe {
             self.raw().get_acceleration_structure_build_sizes(
                 &hal::GetAccelerationStructureBuildSizesDescriptor {
@@ -222,6 +255,7 @@ impl Device {
                     size: size_info.acceleration_structure_size,
                     format: hal::AccelerationStructureFormat::TopLevel,
                     allow_compaction: false,
+                    allow_serialization: false,
                 })
         }
         .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
@@ -246,6 +280,7 @@ impl Device {
             flags: desc.flags,
             update_mode: desc.update_mode,
             built_index: RwLock::new(rank::TLAS_BUILT_INDEX, None),
+            built_instance_count: RwLock::new(rank::TLAS_BUILT_INSTANCE_COUNT, None),
             dependencies: RwLock::new(rank::TLAS_DEPENDENCIES, Vec::new()),
             instance_buffer: ManuallyDrop::new(instance_buffer),
             label: desc.label.to_string(),
@@ -253,6 +288,73 @@ impl Device {
             tracking_data: TrackingData::new(self.tracker_indices.tlas_s.clone()),
         }))
     }
+
+    fn create_blas_from_serialized(
+        self: &Arc<Self>,
+        blas_desc: &resource::BlasDescriptor,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+        data: &[u8],
+    ) -> Result<Arc<resource::Blas>, CreateBlasError> {
+        self.check_is_valid()?;
+        self.require_features(Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION)?;
+
+        let deserialized_size = unsafe {
+            self.raw()
+                .get_deserialized_acceleration_structure_size(data)
+        }
+        .ok_or(CreateBlasError::IncompatibleSerializedData)?;
+
+        // A deserialized BLAS has no compacted size to read back, a compacted BLAS should
+        // be serialized instead.
+        let blas_desc = resource::BlasDescriptor {
+            flags: blas_desc.flags & !wgt::AccelerationStructureFlags::ALLOW_COMPACTION,
+            ..blas_desc.clone()
+        };
+        let blas = self.create_blas(&blas_desc, sizes, Some(deserialized_size))?;
+
+        let Some(queue) = self.get_queue() else {
+            return Err(DeviceError::Lost.into());
+        };
+
+        if !data.is_empty() {
+            let staging_buffer = resource::SerializedBlasBuffer::new(
+                self,
+                data.len() as wgt::BufferAddress,
+                wgt::BufferUses::MAP_WRITE
+                    | wgt::BufferUses::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT,
+            )?;
+            staging_buffer.write(data)?;
+
+            let snatch_guard = self.snatchable_lock.read();
+            let raw = blas.raw(&snatch_guard).unwrap();
+
+            let mut pending_writes = queue.pending_writes.lock();
+            let cmd_buf_raw = pending_writes.activate();
+            unsafe {
+                cmd_buf_raw.copy_buffer_to_acceleration_structure(staging_buffer.raw(), 0, raw);
+                cmd_buf_raw.place_acceleration_structure_barrier(
+                    hal::AccelerationStructureBarrier {
+                        usage: hal::StateTransition {
+                            from: hal::AccelerationStructureUses::COPY_DST,
+                            to: hal::AccelerationStructureUses::BUILD_INPUT
+                                | hal::AccelerationStructureUses::BUILD_OUTPUT
+                                | hal::AccelerationStructureUses::COPY_SRC,
+                        },
+                    },
+                );
+            }
+            pending_writes.consume_temp(TempResource::SerializedBlasBuffer(staging_buffer));
+            pending_writes.insert_blas(&blas);
+        }
+
+        // Bypass the submit checks which update this, like compaction does.
+        let mut command_indices = self.command_indices.write();
+        command_indices.next_acceleration_structure_build_command_index += 1;
+        *blas.built_index.write() =
+            NonZeroU64::new(command_indices.next_acceleration_structure_build_command_index);
+
+        Ok(blas)
+    }
 }
 
 impl Global {
@@ -279,7 +381,7 @@ impl Global {
                 });
             }
 
-            let blas = match device.create_blas(desc, sizes) {
+            let blas = match device.create_blas(desc, sizes, None) {
                 Ok(blas) => blas,
                 Err(e) => break 'error e,
             };
@@ -397,4 +499,102 @@ impl Global {
 
         Ok(matches!(*lock, BlasCompactState::Ready { .. }))
     }
+
+    pub fn blas_compacted_size(
+        &self,
+        blas_id: BlasId,
+    ) -> Result<Option<u64>, InvalidResourceError> {
+        profiling::scope!("Blas::compacted_size");
+        api_log!("Blas::compacted_size {blas_id:?}");
+
+        let hub = &self.hub;
+
+        let blas = hub.blas_s.get(blas_id).get()?;
+
+        let lock = blas.compacted_state.lock();
+
+        Ok(match *lock {
+            BlasCompactState::Ready { size } => Some(size),
+            _ => None,
+        })
+    }
+
+    /// Starts serializing the BLAS, calling `callback` with the serialized data.
+    ///
+    /// Serialization is recorded into the queue's pending writes, so it only makes progress
+    /// as the queue is submitted to and the device polled.
+    pub fn blas_serialize_async(
+        &self,
+        blas_id: BlasId,
+        callback: BlasSerializeCallback,
+    ) -> Result<(), BlasSerializeError> {
+        profiling::scope!("Blas::serialize_async");
+        api_log!("Blas::serialize_async {blas_id:?}");
+
+        let hub = &self.hub;
+
+        let serialize_result = match hub.blas_s.get(blas_id).get() {
+            Ok(blas) => match blas.device.get_queue() {
+                Some(queue) => queue.serialize_blas(&blas, callback),
+                None => Err((callback, DeviceError::Lost.into())),
+            },
+            Err(e) => Err((callback, e.into())),
+        };
+
+        serialize_result.map_err(|(callback, err)| {
+            callback(Err(err.clone()));
+            err
+        })
+    }
+
+    pub fn device_create_blas_from_serialized(
+        &self,
+        device_id: id::DeviceId,
+        desc: &resource::BlasDescriptor,
+        sizes: wgt::BlasGeometrySizeDescriptors,
+        data: &[u8],
+        id_in: Option<BlasId>,
+    ) -> (BlasId, Option<u64>, Option<CreateBlasError>) {
+        profiling::scope!("Device::create_blas_from_serialized");
+
+        let fid = self.hub.blas_s.prepare(id_in);
+
+        // TODO: Tracing
+
+        let error = 'error: {
+            let device = self.hub.devices.get(device_id);
+
+            let blas = match device.create_blas_from_serialized(desc, sizes, data) {
+                Ok(blas) => blas,
+                Err(e) => break 'error e,
+            };
+            let handle = blas.handle;
+
+            let id = fid.assign(Fallible::Valid(blas));
+            api_log!("Device::create_blas_from_serialized -> {id:?}");
+
+            return (id, Some(handle), None);
+        };
+
+        let id = fid.assign(Fallible::Invalid(Arc::new(error.to_string())));
+        (id, None, Some(error))
+    }
+
+    /// Returns whether a BLAS serialized into `data` can be deserialized on the device.
+    pub fn device_is_serialized_blas_compatible(
+        &self,
+        device_id: id::DeviceId,
+        data: &[u8],
+    ) -> bool {
+        let device = self.hub.devices.get(device_id);
+        device
+            .features
+            .contains(Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION)
+            && unsafe {
+                device
+                    .raw()
+                    .get_deserialized_acceleration_structure_size(data)
+            }
+            .is_some()
+    }
 }
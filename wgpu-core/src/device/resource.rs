@@ -1,6 +1,7 @@
 use alloc::{
     borrow::Cow,
     boxed::Box,
+    format,
     string::{String, ToString as _},
     sync::{Arc, Weak},
     vec::Vec,
@@ -53,8 +54,9 @@ use crate::{
 };
 
 use super::{
-    DeviceDescriptor, DeviceError, DeviceLostClosure, ENTRYPOINT_FAILURE_ERROR, UserClosures,
-    ZERO_BUFFER_SIZE, queue::Queue,
+    DeviceDescriptor, DeviceError, DeviceLostClosure, ENTRYPOINT_FAILURE_ERROR,
+    GpuValidationCallback, GpuValidationInvocation, MemoryBudgetCallback, MemoryBudgetInvocation,
+    UserClosures, ZERO_BUFFER_SIZE, queue::Queue,
 };
 
 #[cfg(supports_64bit_atomics)]
@@ -84,7 +86,7 @@ pub struct Device {
     /// The `label` from the descriptor used to create the resource.
     label: String,
 
-    pub(crate) command_allocator: command::CommandAllocator,
+    pub(crate) command_allocator: Arc<command::CommandAllocator>,
 
     pub(crate) command_indices: RwLock<CommandIndices>,
 
@@ -104,6 +106,24 @@ pub struct Device {
     pub(crate) fence: RwLock<ManuallyDrop<Box<dyn hal::DynFence>>>,
     pub(crate) snatchable_lock: SnatchLock,
 
+    /// The fences signalled by the additional queues requested with
+    /// [`wgt::Features::EXPERIMENTAL_MULTI_QUEUE`].
+    ///
+    /// When this is non-empty, `fence` is only signalled by the main queue,
+    /// and submissions may complete out of order. Use
+    /// [`Device::completed_submission_index`] rather than reading `fence`.
+    ///
+    /// If both are needed, `fence` must be locked first.
+    pub(crate) additional_queue_fences: Mutex<Vec<QueueFence>>,
+
+    /// The index of the last successful submission to the main queue.
+    ///
+    /// This only differs from [`last_successful_submission_index`] when
+    /// there are additional queues.
+    ///
+    /// [`last_successful_submission_index`]: Device::last_successful_submission_index
+    pub(crate) last_main_queue_submission_index: hal::AtomicFenceValue,
+
     /// Is this device valid? Valid is closely associated with "lose the device",
     /// which can be triggered by various methods, including at the end of device
     /// destroy, and by any GPU errors that cause us to no longer trust the state
@@ -122,9 +142,22 @@ pub struct Device {
     /// has been destroyed and its queues are empty.
     pub(crate) device_lost_closure: Mutex<Option<DeviceLostClosure>>,
 
+    /// Callback to be called when the adapter's memory budget changes, along with
+    /// the memory info it was last called with. Checked on every device poll.
+    pub(crate) memory_budget_callback: Mutex<Option<(MemoryBudgetCallback, wgt::MemoryInfo)>>,
+
+    /// Callback to be called with the errors reported by GPU-based validation.
+    /// Checked on every device poll.
+    pub(crate) gpu_validation_callback: Mutex<Option<GpuValidationCallback>>,
+
     /// Stores the state of buffers and textures.
     pub(crate) trackers: Mutex<DeviceTracker>,
     pub(crate) tracker_indices: TrackerIndexAllocators,
+    /// Whether the barriers inserted by the trackers are logged and counted in
+    /// [`Self::barrier_report`].
+    pub(crate) barrier_logging: AtomicBool,
+    /// Barrier counts of the passes recorded while `barrier_logging` was set.
+    pub(crate) barrier_report: Mutex<Vec<wgt::PassBarrierReport>>,
     /// Pool of bind group layouts, allowing deduplication.
     pub(crate) bgl_pool: ResourcePool<bgl::EntryMap, BindGroupLayout>,
     pub(crate) alignments: hal::Alignments,
@@ -132,9 +165,14 @@ pub struct Device {
     pub(crate) features: wgt::Features,
     pub(crate) downlevel: wgt::DownlevelCapabilities,
     pub(crate) instance_flags: wgt::InstanceFlags,
+    /// The device toggles which apply to this device.
+    pub(crate) toggles: wgt::Toggles,
     pub(crate) deferred_destroy: Mutex<Vec<DeferredDestroy>>,
     pub(crate) usage_scopes: UsageScopePool,
     pub(crate) indirect_validation: Option<crate::indirect_validation::IndirectValidation>,
+    /// Widens 8 bit index buffers, when [`wgt::Features::INDEX_FORMAT_UINT8`] is
+    /// emulated.
+    pub(crate) index_expansion: Option<crate::index_expansion::IndexExpansion>,
     // Optional so that we can late-initialize this after the queue is created.
     pub(crate) timestamp_normalizer:
         OnceCellOrLock<crate::timestamp_normalization::TimestampNormalizer>,
@@ -143,11 +181,73 @@ pub struct Device {
     pub(crate) trace: Mutex<Option<trace::Trace>>,
 }
 
+/// A fence signalled by an additional queue's submissions.
+pub(crate) struct QueueFence {
+    pub(crate) raw: Box<dyn hal::DynFence>,
+    /// The index of the last successful submission to the queue.
+    pub(crate) last_submission_index: hal::FenceValue,
+    /// The queue's pool of command encoders, kept here so that
+    /// [`Device::trim_memory`] can reach it.
+    pub(crate) command_allocator: Arc<command::CommandAllocator>,
+}
+
+/// Converts `duration` to milliseconds, rounding up so that short non-zero
+/// timeouts don't become polls.
+fn duration_to_ms_ceil(duration: core::time::Duration) -> u32 {
+    duration
+        .as_nanos()
+        .div_ceil(1_000_000)
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+/// The time left of a wait spanning several fences.
+///
+/// Without a clock, the first wait gets the whole timeout and the following
+/// ones only poll, which keeps the total wait bounded by the timeout.
+struct Deadline {
+    timeout_ms: u32,
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    start: std::time::Instant,
+}
+
+impl Deadline {
+    fn new(timeout_ms: u32) -> Self {
+        Self {
+            timeout_ms,
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Returns the number of milliseconds left until the deadline.
+    fn remaining_ms(&mut self) -> u32 {
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        {
+            let timeout = core::time::Duration::from_millis(self.timeout_ms.into());
+            duration_to_ms_ceil(timeout.saturating_sub(self.start.elapsed()))
+        }
+        #[cfg(not(all(feature = "std", not(target_arch = "wasm32"))))]
+        {
+            mem::take(&mut self.timeout_ms)
+        }
+    }
+}
+
 pub(crate) enum DeferredDestroy {
     TextureViews(WeakVec<TextureView>),
     BindGroups(WeakVec<BindGroup>),
 }
 
+/// The HAL stages producing primitives for a render pipeline being created.
+enum HalVertexProcessor<'a> {
+    Vertex(hal::ProgrammableStage<'a, dyn hal::DynShaderModule>),
+    Mesh(
+        Option<hal::ProgrammableStage<'a, dyn hal::DynShaderModule>>,
+        hal::ProgrammableStage<'a, dyn hal::DynShaderModule>,
+    ),
+}
+
 impl fmt::Debug for Device {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Device")
@@ -167,15 +267,22 @@ impl Drop for Device {
         let zero_buffer = unsafe { ManuallyDrop::take(&mut self.zero_buffer) };
         // SAFETY: We are in the Drop impl and we don't use self.fence anymore after this point.
         let fence = unsafe { ManuallyDrop::take(&mut self.fence.write()) };
+        let additional_queue_fences = mem::take(&mut *self.additional_queue_fences.lock());
         if let Some(indirect_validation) = self.indirect_validation.take() {
             indirect_validation.dispose(self.raw.as_ref());
         }
+        if let Some(index_expansion) = self.index_expansion.take() {
+            index_expansion.dispose(self.raw.as_ref());
+        }
         if let Some(timestamp_normalizer) = self.timestamp_normalizer.take() {
             timestamp_normalizer.dispose(self.raw.as_ref());
         }
         unsafe {
             self.raw.destroy_buffer(zero_buffer);
             self.raw.destroy_fence(fence);
+            for queue_fence in additional_queue_fences {
+                self.raw.destroy_fence(queue_fence.raw);
+            }
         }
     }
 }
@@ -232,7 +339,7 @@ impl Device {
 
         let fence = unsafe { raw_device.create_fence() }.map_err(DeviceError::from_hal)?;
 
-        let command_allocator = command::CommandAllocator::new();
+        let command_allocator = Arc::new(command::CommandAllocator::new());
 
         let rt_uses = if desc
             .required_features
@@ -257,8 +364,13 @@ impl Device {
         let alignments = adapter.raw.capabilities.alignments.clone();
         let downlevel = adapter.raw.capabilities.downlevel.clone();
 
-        let enable_indirect_validation = instance_flags
+        // `MULTI_DRAW_INDIRECT_COUNT` is emulated by the indirect validation pass,
+        // so requesting it turns it on regardless of the instance flags.
+        let enable_indirect_validation = (instance_flags
             .contains(wgt::InstanceFlags::VALIDATION_INDIRECT_CALL)
+            || desc
+                .required_features
+                .intersects(adapter.emulated_features & wgt::Features::MULTI_DRAW_INDIRECT_COUNT))
             && downlevel
                 .flags
                 .contains(wgt::DownlevelFlags::INDIRECT_EXECUTION);
@@ -274,6 +386,17 @@ impl Device {
             None
         };
 
+        let index_expansion = if desc
+            .required_features
+            .intersects(adapter.emulated_features & wgt::Features::INDEX_FORMAT_UINT8)
+        {
+            Some(crate::index_expansion::IndexExpansion::new(
+                raw_device.as_ref(),
+            )?)
+        } else {
+            None
+        };
+
         Ok(Self {
             raw: raw_device,
             adapter: adapter.clone(),
@@ -292,10 +415,16 @@ impl Device {
             last_successful_submission_index: AtomicU64::new(0),
             fence: RwLock::new(rank::DEVICE_FENCE, ManuallyDrop::new(fence)),
             snatchable_lock: unsafe { SnatchLock::new(rank::DEVICE_SNATCHABLE_LOCK) },
+            additional_queue_fences: Mutex::new(rank::DEVICE_ADDITIONAL_QUEUE_FENCES, Vec::new()),
+            last_main_queue_submission_index: AtomicU64::new(0),
             valid: AtomicBool::new(true),
             device_lost_closure: Mutex::new(rank::DEVICE_LOST_CLOSURE, None),
+            memory_budget_callback: Mutex::new(rank::DEVICE_MEMORY_BUDGET_CALLBACK, None),
+            gpu_validation_callback: Mutex::new(rank::DEVICE_GPU_VALIDATION_CALLBACK, None),
             trackers: Mutex::new(rank::DEVICE_TRACKERS, DeviceTracker::new()),
             tracker_indices: TrackerIndexAllocators::new(),
+            barrier_logging: AtomicBool::new(false),
+            barrier_report: Mutex::new(rank::DEVICE_BARRIER_REPORT, Vec::new()),
             bgl_pool: ResourcePool::new(),
             #[cfg(feature = "trace")]
             trace: Mutex::new(
@@ -322,10 +451,12 @@ impl Device {
             features: desc.required_features,
             downlevel,
             instance_flags,
+            toggles: desc.toggles.apply(wgt::Toggles::DEVICE),
             deferred_destroy: Mutex::new(rank::DEVICE_DEFERRED_DESTROY, Vec::new()),
             usage_scopes: Mutex::new(rank::DEVICE_USAGE_SCOPES, Default::default()),
             timestamp_normalizer: OnceCellOrLock::new(),
             indirect_validation,
+            index_expansion,
         })
     }
 
@@ -349,6 +480,32 @@ impl Device {
         self.adapter.backend()
     }
 
+    /// Start recording the API calls made on this device into a trace in the
+    /// directory `path`, replacing the trace in progress, if any.
+    ///
+    /// The trace starts with an [`trace::Action::Init`] describing this device, but
+    /// not the resources created before it started.
+    #[cfg(feature = "trace")]
+    pub(crate) fn start_trace(&self, path: std::path::PathBuf) -> Result<(), std::io::Error> {
+        let mut trace = trace::Trace::new(path)?;
+        trace.add(trace::Action::Init {
+            desc: wgt::DeviceDescriptor {
+                label: (!self.label.is_empty()).then(|| Cow::Borrowed(self.label.as_str())),
+                required_features: self.features,
+                required_limits: self.limits.clone(),
+                memory_hints: wgt::MemoryHints::default(),
+                trace: wgt::Trace::Off,
+                toggles: wgt::TogglesDescriptor {
+                    force_enabled: self.toggles,
+                    force_disabled: wgt::Toggles::DEVICE - self.toggles,
+                },
+            },
+            backend: self.backend(),
+        });
+        *self.trace.lock() = Some(trace);
+        Ok(())
+    }
+
     pub fn is_valid(&self) -> bool {
         self.valid.load(Ordering::Acquire)
     }
@@ -374,14 +531,48 @@ impl Device {
             .map_err(|e| self.handle_hal_error(e));
     }
 
+    /// Returns an invocation of the memory budget callback if the adapter's memory
+    /// budget changed since it was last called.
+    pub(crate) fn check_memory_budget(&self) -> Option<MemoryBudgetInvocation> {
+        let mut memory_budget_callback = self.memory_budget_callback.lock();
+        let (callback, last_info) = memory_budget_callback.as_mut()?;
+        let info = self.adapter.memory_info()?;
+        if !info.budget_differs(last_info) {
+            return None;
+        }
+        *last_info = info.clone();
+        Some(MemoryBudgetInvocation {
+            callback: callback.clone(),
+            info,
+        })
+    }
+
+    /// Returns an invocation of the GPU-based validation callback with the errors
+    /// reported since the last check, if there are any.
+    ///
+    /// Errors are dropped if no callback is set; wgpu-hal logs them either way.
+    pub(crate) fn check_gpu_validation(&self) -> Option<GpuValidationInvocation> {
+        let messages = self.raw().take_validation_messages();
+        if messages.is_empty() {
+            return None;
+        }
+        let callback = self.gpu_validation_callback.lock().clone()?;
+        Some(GpuValidationInvocation { callback, messages })
+    }
+
     pub fn handle_hal_error(&self, error: hal::DeviceError) -> DeviceError {
-        match error {
-            hal::DeviceError::OutOfMemory
-            | hal::DeviceError::Lost
-            | hal::DeviceError::Unexpected => {
-                self.lose(&error.to_string());
+        let reason = match error {
+            hal::DeviceError::OutOfMemory => DeviceLostReason::OutOfMemory,
+            hal::DeviceError::Lost => self.raw().lost_reason(),
+            hal::DeviceError::Unexpected => DeviceLostReason::Unknown,
+        };
+        let mut message = error.to_string();
+        if let hal::DeviceError::Lost = error {
+            if let Some(fault_info) = self.raw().fault_info() {
+                message = format!("{message}\n{fault_info}");
             }
         }
+        self.lose(reason, &message);
         DeviceError::from_hal(error)
     }
 
@@ -442,6 +633,87 @@ impl Device {
         }
     }
 
+    /// Return the index of the last submission that has completed, along with
+    /// every submission before it, on all of this device's queues.
+    ///
+    /// `fence` is the main queue's fence, which the caller must have locked.
+    pub(crate) fn completed_submission_index(
+        &self,
+        fence: &dyn hal::DynFence,
+    ) -> Result<hal::FenceValue, hal::DeviceError> {
+        let main_fence_value = unsafe { self.raw().get_fence_value(fence) }?;
+
+        let additional_queue_fences = self.additional_queue_fences.lock();
+        if additional_queue_fences.is_empty() {
+            return Ok(main_fence_value);
+        }
+
+        // A queue's fence holds the index of its last completed submission, and
+        // any of its submissions still in flight have a greater index. Queues
+        // with no work in flight don't hold back the others.
+        let mut completed = self
+            .last_successful_submission_index
+            .load(Ordering::Acquire);
+        let queue_fence_values = additional_queue_fences
+            .iter()
+            .map(|queue_fence| {
+                let value = unsafe { self.raw().get_fence_value(queue_fence.raw.as_ref()) }?;
+                Ok((value, queue_fence.last_submission_index))
+            })
+            .collect::<Result<Vec<_>, hal::DeviceError>>()?;
+        let main_last_submission_index = self
+            .last_main_queue_submission_index
+            .load(Ordering::Acquire);
+        for (value, last_submission_index) in queue_fence_values.into_iter().chain(
+            core::iter::once((main_fence_value, main_last_submission_index)),
+        ) {
+            if value < last_submission_index {
+                completed = completed.min(value);
+            }
+        }
+        Ok(completed)
+    }
+
+    /// Wait for the submission `submission_index`, and every submission before
+    /// it, to complete on all of this device's queues.
+    ///
+    /// `fence` is the main queue's fence, which the caller must have locked.
+    /// `timeout_ms` bounds the whole wait, across all queues. Returns
+    /// `Ok(false)` on timeout.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`hal::Device::wait`].
+    pub(crate) unsafe fn wait_for_submission_index(
+        &self,
+        fence: &dyn hal::DynFence,
+        submission_index: crate::SubmissionIndex,
+        timeout_ms: u32,
+    ) -> Result<bool, hal::DeviceError> {
+        let additional_queue_fences = self.additional_queue_fences.lock();
+        if additional_queue_fences.is_empty() {
+            return unsafe { self.raw().wait(fence, submission_index, timeout_ms) };
+        }
+
+        // A queue's fence never goes past the index of its own last submission.
+        let main_last_submission_index = self
+            .last_main_queue_submission_index
+            .load(Ordering::Acquire);
+        let fences = core::iter::once((fence, main_last_submission_index)).chain(
+            additional_queue_fences
+                .iter()
+                .map(|queue_fence| (queue_fence.raw.as_ref(), queue_fence.last_submission_index)),
+        );
+        let mut deadline = Deadline::new(timeout_ms);
+        for (fence, last_submission_index) in fences {
+            let value = submission_index.min(last_submission_index);
+            if !unsafe { self.raw().wait(fence, value, deadline.remaining_ms()) }? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     pub fn get_queue(&self) -> Option<Arc<Queue>> {
         self.queue.get().as_ref()?.upgrade()
     }
@@ -477,38 +749,45 @@ impl Device {
 
         let mut user_closures = UserClosures::default();
 
-        // If a wait was requested, determine which submission index to wait for.
-        let wait_submission_index = match poll_type {
+        // If a wait was requested, determine which submission index to wait for,
+        // and for how long.
+        let (wait_submission_index, timeout_ms) = match poll_type {
             wgt::PollType::WaitForSubmissionIndex(submission_index) => {
-                let last_successful_submission_index = self
-                    .last_successful_submission_index
-                    .load(Ordering::Acquire);
-
-                if submission_index > last_successful_submission_index {
-                    let result = Err(WaitIdleError::WrongSubmissionIndex(
-                        submission_index,
-                        last_successful_submission_index,
-                    ));
-
-                    return (user_closures, result);
-                }
-
-                Some(submission_index)
+                (Some(submission_index), CLEANUP_WAIT_MS)
             }
-            wgt::PollType::Wait => Some(
-                self.last_successful_submission_index
-                    .load(Ordering::Acquire),
+            wgt::PollType::WaitForSubmissionWithTimeout(submission_index, timeout) => {
+                (Some(submission_index), duration_to_ms_ceil(timeout))
+            }
+            wgt::PollType::Wait => (
+                Some(
+                    self.last_successful_submission_index
+                        .load(Ordering::Acquire),
+                ),
+                CLEANUP_WAIT_MS,
             ),
-            wgt::PollType::Poll => None,
+            wgt::PollType::Poll => (None, 0),
         };
+        if let Some(submission_index) = wait_submission_index {
+            let last_successful_submission_index = self
+                .last_successful_submission_index
+                .load(Ordering::Acquire);
+
+            if submission_index > last_successful_submission_index {
+                let result = Err(WaitIdleError::WrongSubmissionIndex(
+                    submission_index,
+                    last_successful_submission_index,
+                ));
+
+                return (user_closures, result);
+            }
+        }
 
         // Wait for the submission index if requested.
         if let Some(target_submission_index) = wait_submission_index {
             log::trace!("Device::maintain: waiting for submission index {target_submission_index}");
 
             let wait_result = unsafe {
-                self.raw()
-                    .wait(fence.as_ref(), target_submission_index, CLEANUP_WAIT_MS)
+                self.wait_for_submission_index(fence.as_ref(), target_submission_index, timeout_ms)
             };
 
             // This error match is only about `DeviceErrors`. At this stage we do not care if
@@ -521,7 +800,7 @@ impl Device {
 
         // Get the currently finished submission index. This may be higher than the requested
         // wait, or it may be less than the requested wait if the wait failed.
-        let fence_value_result = unsafe { self.raw().get_fence_value(fence.as_ref()) };
+        let fence_value_result = self.completed_submission_index(fence.as_ref());
         let current_finished_submission = match fence_value_result {
             Ok(fence_value) => fence_value,
             Err(e) => {
@@ -532,6 +811,10 @@ impl Device {
 
         // Maintain all finished submissions on the queue, updating the relevant user closures and collecting if the queue is empty.
         //
+        // The main queue's lifetime tracker also tracks the submissions to the
+        // device's additional queues, which keep the main queue alive, so this
+        // maintains every queue of the device.
+        //
         // We don't use the result of the wait here, as we want to progress forward as far as possible
         // and the wait could have been for submissions that finished long ago.
         let mut queue_empty = false;
@@ -541,6 +824,7 @@ impl Device {
                 user_closures.submissions,
                 user_closures.mappings,
                 user_closures.blas_compact_ready,
+                user_closures.blas_serialized,
                 queue_empty,
             ) = queue_result
         };
@@ -614,6 +898,98 @@ impl Device {
     ) -> Result<Arc<Buffer>, resource::CreateBufferError> {
         self.check_is_valid()?;
 
+        let hal_desc = self.map_buffer_descriptor(desc)?;
+        let buffer = unsafe { self.raw().create_buffer(&hal_desc) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        let buffer = self.create_buffer_from_raw(buffer, desc, hal_desc.size, None)?;
+
+        let buffer_use = if !desc.mapped_at_creation {
+            wgt::BufferUses::empty()
+        } else if desc.usage.contains(wgt::BufferUsages::MAP_WRITE) {
+            // buffer is mappable, so we are just doing that at start
+            let map_size = buffer.size;
+            let mapping = if map_size == 0 {
+                hal::BufferMapping {
+                    ptr: core::ptr::NonNull::dangling(),
+                    is_coherent: true,
+                }
+            } else {
+                let snatch_guard: SnatchGuard = self.snatchable_lock.read();
+                map_buffer(&buffer, 0, map_size, HostMap::Write, &snatch_guard)?
+            };
+            *buffer.map_state.lock() = resource::BufferMapState::Active {
+                mapping,
+                range: 0..map_size,
+                host: HostMap::Write,
+            };
+            wgt::BufferUses::MAP_WRITE
+        } else {
+            let mut staging_buffer =
+                StagingBuffer::new(self, wgt::BufferSize::new(hal_desc.size).unwrap())?;
+
+            // Zero initialize memory and then mark the buffer as initialized
+            // (it's guaranteed that this is the case by the time the buffer is usable)
+            staging_buffer.write_zeros();
+            buffer.initialization_status.write().drain(0..hal_desc.size);
+
+            *buffer.map_state.lock() = resource::BufferMapState::Init { staging_buffer };
+            wgt::BufferUses::COPY_DST
+        };
+
+        self.trackers
+            .lock()
+            .buffers
+            .insert_single(&buffer, buffer_use);
+
+        Ok(buffer)
+    }
+
+    pub(crate) fn create_sparse_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+    ) -> Result<Arc<Buffer>, resource::CreateSparseBufferError> {
+        use resource::CreateSparseBufferError as Error;
+
+        self.check_is_valid()?;
+        self.require_features(wgt::Features::SPARSE_BINDING)?;
+
+        if desc
+            .usage
+            .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+        {
+            return Err(Error::MapUsage(desc.usage));
+        }
+        if desc.mapped_at_creation {
+            return Err(Error::MappedAtCreation);
+        }
+
+        let mut hal_desc = self.map_buffer_descriptor(desc)?;
+        // Memory is bound in whole pages, so the buffer spans a whole number of them.
+        hal_desc.size = hal_desc.size.next_multiple_of(wgt::SPARSE_PAGE_SIZE);
+
+        let buffer = unsafe { self.raw().create_sparse_buffer(&hal_desc) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        // No memory is bound yet, so the buffer starts out uninitialized, and is
+        // zeroed on first use like any other buffer.
+        let sparse_heaps = Mutex::new(rank::BUFFER_SPARSE_HEAPS, Vec::new());
+        let buffer =
+            self.create_buffer_from_raw(buffer, desc, hal_desc.size, Some(sparse_heaps))?;
+
+        self.trackers
+            .lock()
+            .buffers
+            .insert_single(&buffer, wgt::BufferUses::empty());
+
+        Ok(buffer)
+    }
+
+    /// Validates `desc` and maps it to the descriptor hal creates the buffer from.
+    fn map_buffer_descriptor<'a>(
+        &self,
+        desc: &'a resource::BufferDescriptor,
+    ) -> Result<hal::BufferDescriptor<'a>, resource::CreateBufferError> {
         if desc.size > self.limits.max_buffer_size {
             return Err(resource::CreateBufferError::MaxBufferSize {
                 requested: desc.size,
@@ -693,15 +1069,21 @@ impl Device {
             actual_size
         };
 
-        let hal_desc = hal::BufferDescriptor {
+        Ok(hal::BufferDescriptor {
             label: desc.label.to_hal(self.instance_flags),
             size: aligned_size,
             usage,
             memory_flags: hal::MemoryFlags::empty(),
-        };
-        let buffer = unsafe { self.raw().create_buffer(&hal_desc) }
-            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+        })
+    }
 
+    fn create_buffer_from_raw(
+        self: &Arc<Self>,
+        buffer: Box<dyn hal::DynBuffer>,
+        desc: &resource::BufferDescriptor,
+        aligned_size: wgt::BufferAddress,
+        sparse_heaps: Option<Mutex<Vec<Arc<resource::MemoryHeap>>>>,
+    ) -> Result<Arc<Buffer>, resource::CreateBufferError> {
         let timestamp_normalization_bind_group = Snatchable::new(unsafe {
             // SAFETY: The size passed here must not overflow the buffer.
             self.timestamp_normalizer
@@ -711,7 +1093,7 @@ impl Device {
                     self,
                     &*buffer,
                     desc.label.as_deref(),
-                    wgt::BufferSize::new(hal_desc.size).unwrap(),
+                    wgt::BufferSize::new(aligned_size).unwrap(),
                     desc.usage,
                 )
         }?);
@@ -726,7 +1108,14 @@ impl Device {
             size: desc.size,
             initialization_status: RwLock::new(
                 rank::BUFFER_INITIALIZATION_STATUS,
-                BufferInitTracker::new(aligned_size),
+                if self
+                    .toggles
+                    .contains(wgt::Toggles::LAZY_ZERO_INITIALIZATION)
+                {
+                    BufferInitTracker::new(aligned_size)
+                } else {
+                    BufferInitTracker::new(0)
+                },
             ),
             map_state: Mutex::new(rank::BUFFER_MAP_STATE, resource::BufferMapState::Idle),
             label: desc.label.to_string(),
@@ -734,49 +1123,10 @@ impl Device {
             bind_groups: Mutex::new(rank::BUFFER_BIND_GROUPS, WeakVec::new()),
             timestamp_normalization_bind_group,
             indirect_validation_bind_groups,
+            sparse_heaps,
         };
 
-        let buffer = Arc::new(buffer);
-
-        let buffer_use = if !desc.mapped_at_creation {
-            wgt::BufferUses::empty()
-        } else if desc.usage.contains(wgt::BufferUsages::MAP_WRITE) {
-            // buffer is mappable, so we are just doing that at start
-            let map_size = buffer.size;
-            let mapping = if map_size == 0 {
-                hal::BufferMapping {
-                    ptr: core::ptr::NonNull::dangling(),
-                    is_coherent: true,
-                }
-            } else {
-                let snatch_guard: SnatchGuard = self.snatchable_lock.read();
-                map_buffer(&buffer, 0, map_size, HostMap::Write, &snatch_guard)?
-            };
-            *buffer.map_state.lock() = resource::BufferMapState::Active {
-                mapping,
-                range: 0..map_size,
-                host: HostMap::Write,
-            };
-            wgt::BufferUses::MAP_WRITE
-        } else {
-            let mut staging_buffer =
-                StagingBuffer::new(self, wgt::BufferSize::new(aligned_size).unwrap())?;
-
-            // Zero initialize memory and then mark the buffer as initialized
-            // (it's guaranteed that this is the case by the time the buffer is usable)
-            staging_buffer.write_zeros();
-            buffer.initialization_status.write().drain(0..aligned_size);
-
-            *buffer.map_state.lock() = resource::BufferMapState::Init { staging_buffer };
-            wgt::BufferUses::COPY_DST
-        };
-
-        self.trackers
-            .lock()
-            .buffers
-            .insert_single(&buffer, buffer_use);
-
-        Ok(buffer)
+        Ok(Arc::new(buffer))
     }
 
     pub(crate) fn create_texture_from_hal(
@@ -810,6 +1160,96 @@ impl Device {
         Ok(texture)
     }
 
+    /// # Safety
+    ///
+    /// - `io_surface` must be a valid `IOSurfaceRef`.
+    /// - `desc.format` must be compatible with the pixel format of `plane`.
+    #[cfg(metal)]
+    pub(crate) unsafe fn create_texture_from_io_surface(
+        self: &Arc<Self>,
+        io_surface: *mut core::ffi::c_void,
+        plane: u32,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<Arc<Texture>, resource::CreateTextureFromIoSurfaceError> {
+        use resource::CreateTextureFromIoSurfaceError as Error;
+
+        self.check_is_valid()
+            .map_err(resource::CreateTextureError::from)?;
+
+        let Some(raw_device) = self.raw().as_any().downcast_ref::<hal::metal::Device>() else {
+            return Err(Error::UnsupportedBackend(self.backend()));
+        };
+
+        if desc.dimension != wgt::TextureDimension::D2
+            || desc.mip_level_count != 1
+            || desc.size.depth_or_array_layers != 1
+            || desc.sample_count != 1
+        {
+            return Err(Error::InvalidDescriptor {
+                dimension: desc.dimension,
+                mip_level_count: desc.mip_level_count,
+                depth_or_array_layers: desc.size.depth_or_array_layers,
+                sample_count: desc.sample_count,
+            });
+        }
+
+        let (format_features, hal_view_formats) = self.validate_texture_descriptor(desc)?;
+
+        let plane_size = unsafe { hal::metal::io_surface_plane_size(io_surface, plane) }
+            .ok_or(Error::InvalidPlane(plane))?;
+        let texture_size = (desc.size.width, desc.size.height);
+        if texture_size != plane_size {
+            return Err(Error::SizeMismatch {
+                plane,
+                texture_size,
+                plane_size,
+            });
+        }
+
+        let hal_usage = conv::map_texture_usage_for_texture(desc, &format_features);
+
+        let hal_desc = hal::TextureDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            dimension: desc.dimension,
+            format: desc.format,
+            usage: hal_usage,
+            memory_flags: hal::MemoryFlags::empty(),
+            view_formats: hal_view_formats,
+        };
+
+        let raw_texture =
+            unsafe { raw_device.texture_from_io_surface(io_surface, plane, &hal_desc) }
+                .map_err(|e| resource::CreateTextureError::from(self.handle_hal_error(e)))?;
+        let raw_texture: Box<dyn hal::DynTexture> = Box::new(raw_texture);
+
+        let clear_mode = self
+            .create_texture_clear_mode(raw_texture.as_ref(), desc, hal_usage)
+            .map_err(resource::CreateTextureError::from)?;
+
+        // The surface's contents come from outside of wgpu, so it's already initialized.
+        let texture = Texture::new(
+            self,
+            resource::TextureInner::Native { raw: raw_texture },
+            hal_usage,
+            desc,
+            format_features,
+            clear_mode,
+            false,
+        );
+
+        let texture = Arc::new(texture);
+
+        self.trackers
+            .lock()
+            .textures
+            .insert_single(&texture, wgt::TextureUses::UNINITIALIZED);
+
+        Ok(texture)
+    }
+
     /// # Safety
     ///
     /// - `hal_buffer` must have been created on this device.
@@ -869,6 +1309,7 @@ impl Device {
             bind_groups: Mutex::new(rank::BUFFER_BIND_GROUPS, WeakVec::new()),
             timestamp_normalization_bind_group,
             indirect_validation_bind_groups,
+            sparse_heaps: None,
         };
 
         let buffer = Arc::new(buffer);
@@ -915,14 +1356,262 @@ impl Device {
         self: &Arc<Self>,
         desc: &resource::TextureDescriptor,
     ) -> Result<Arc<Texture>, resource::CreateTextureError> {
-        use resource::{CreateTextureError, TextureDimensionError};
+        self.check_is_valid()?;
+
+        let (hal_desc, format_features) = self.map_texture_descriptor(desc)?;
+
+        let raw_texture = unsafe { self.raw().create_texture(&hal_desc) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        self.create_texture_from_raw(
+            raw_texture,
+            desc,
+            hal_desc.usage,
+            format_features,
+            None,
+            None,
+        )
+    }
+
+    pub(crate) fn create_memory_heap(
+        self: &Arc<Self>,
+        desc: &resource::MemoryHeapDescriptor,
+    ) -> Result<Arc<resource::MemoryHeap>, resource::CreateMemoryHeapError> {
+        self.check_is_valid()?;
+        self.require_features(wgt::Features::TEXTURE_ALIASING)?;
+
+        if desc.size == 0 {
+            return Err(resource::CreateMemoryHeapError::ZeroSize);
+        }
+
+        let hal_desc = hal::MemoryHeapDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            size: desc.size,
+        };
+        let raw = unsafe { self.raw().create_memory_heap(&hal_desc) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        Ok(Arc::new(resource::MemoryHeap {
+            raw: ManuallyDrop::new(raw),
+            device: self.clone(),
+            size: desc.size,
+            label: desc.label.to_string(),
+        }))
+    }
+
+    pub(crate) fn texture_memory_requirements(
+        &self,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, resource::CreateAliasedTextureError> {
+        self.check_is_valid()?;
+        self.require_features(wgt::Features::TEXTURE_ALIASING)?;
+
+        if desc.usage.contains(wgt::TextureUsages::TRANSIENT) {
+            return Err(resource::CreateAliasedTextureError::TransientUsage);
+        }
+
+        let (hal_desc, _) = self.map_texture_descriptor(desc)?;
+
+        unsafe { self.raw().texture_memory_requirements(&hal_desc) }
+            .map_err(|e| self.handle_hal_error(e).into())
+    }
+
+    pub(crate) fn create_texture_aliased(
+        self: &Arc<Self>,
+        heap: &Arc<resource::MemoryHeap>,
+        offset: wgt::BufferAddress,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<Arc<Texture>, resource::CreateAliasedTextureError> {
+        use resource::CreateAliasedTextureError as Error;
+
+        self.check_is_valid()?;
+        self.require_features(wgt::Features::TEXTURE_ALIASING)?;
+        heap.same_device(self)?;
+
+        if desc.usage.contains(wgt::TextureUsages::TRANSIENT) {
+            return Err(Error::TransientUsage);
+        }
+
+        let (hal_desc, format_features) = self.map_texture_descriptor(desc)?;
+
+        let requirements = unsafe { self.raw().texture_memory_requirements(&hal_desc) }
+            .map_err(|e| self.handle_hal_error(e))?;
+        if offset % requirements.alignment != 0 {
+            return Err(Error::UnalignedOffset {
+                heap: heap.error_ident(),
+                offset,
+                alignment: requirements.alignment,
+            });
+        }
+        let end = offset.checked_add(requirements.size);
+        if end.is_none_or(|end| end > heap.size) {
+            return Err(Error::OutOfBounds {
+                heap: heap.error_ident(),
+                offset,
+                size: requirements.size,
+                heap_size: heap.size,
+            });
+        }
+
+        let raw_texture = unsafe {
+            self.raw()
+                .create_placed_texture(heap.raw(), offset, &hal_desc)
+        }
+        .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        let aliasing = resource::TextureAliasing {
+            heap: heap.clone(),
+            range: offset..offset + requirements.size,
+        };
+        Ok(self.create_texture_from_raw(
+            raw_texture,
+            desc,
+            hal_desc.usage,
+            format_features,
+            Some(aliasing),
+            None,
+        )?)
+    }
+
+    pub(crate) fn create_sparse_texture(
+        self: &Arc<Self>,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<Arc<Texture>, resource::CreateSparseTextureError> {
+        use resource::CreateSparseTextureError as Error;
 
         self.check_is_valid()?;
+        self.require_features(wgt::Features::SPARSE_BINDING)?;
+
+        if desc.usage.contains(wgt::TextureUsages::TRANSIENT) {
+            return Err(Error::TransientUsage);
+        }
+        if desc.dimension != wgt::TextureDimension::D2 {
+            return Err(Error::InvalidDimension(desc.dimension));
+        }
+        if desc.sample_count != 1 {
+            return Err(Error::InvalidSampleCount(desc.sample_count));
+        }
+        let Some(tile_size) = desc.format.sparse_tile_size() else {
+            return Err(Error::UnsupportedFormat(desc.format));
+        };
+
+        let (hal_desc, format_features) = self.map_texture_descriptor(desc)?;
+
+        // Memory is bound in whole tiles, and there is no way to bind the smaller
+        // mip levels a partial tile would be shared by.
+        for mip_level in 0..desc.mip_level_count {
+            let size = desc.mip_level_size(mip_level).unwrap();
+            if size.width % tile_size.width != 0 || size.height % tile_size.height != 0 {
+                return Err(Error::PartialTiles {
+                    mip_level,
+                    size,
+                    tile_size,
+                });
+            }
+        }
+
+        let raw_texture = unsafe { self.raw().create_sparse_texture(&hal_desc) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        let sparse_heaps = Mutex::new(rank::TEXTURE_SPARSE_HEAPS, Vec::new());
+        Ok(self.create_texture_from_raw(
+            raw_texture,
+            desc,
+            hal_desc.usage,
+            format_features,
+            None,
+            Some(sparse_heaps),
+        )?)
+    }
+
+    /// Validates `desc` and maps it to the descriptor hal creates the texture from.
+    fn map_texture_descriptor<'a>(
+        &self,
+        desc: &'a resource::TextureDescriptor,
+    ) -> Result<
+        (hal::TextureDescriptor<'a>, wgt::TextureFormatFeatures),
+        resource::CreateTextureError,
+    > {
+        let (format_features, hal_view_formats) = self.validate_texture_descriptor(desc)?;
+
+        let hal_usage = conv::map_texture_usage_for_texture(desc, &format_features);
+
+        let mut memory_flags = hal::MemoryFlags::empty();
+        memory_flags.set(
+            hal::MemoryFlags::TRANSIENT_ATTACHMENT,
+            desc.usage.contains(wgt::TextureUsages::TRANSIENT),
+        );
+
+        let hal_desc = hal::TextureDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            dimension: desc.dimension,
+            format: desc.format,
+            usage: hal_usage,
+            memory_flags,
+            view_formats: hal_view_formats,
+        };
+
+        Ok((hal_desc, format_features))
+    }
+
+    fn create_texture_from_raw(
+        self: &Arc<Self>,
+        raw_texture: Box<dyn hal::DynTexture>,
+        desc: &resource::TextureDescriptor,
+        hal_usage: wgt::TextureUses,
+        format_features: wgt::TextureFormatFeatures,
+        aliasing: Option<resource::TextureAliasing>,
+        sparse_heaps: Option<Mutex<Vec<Arc<resource::MemoryHeap>>>>,
+    ) -> Result<Arc<Texture>, resource::CreateTextureError> {
+        let clear_mode = self.create_texture_clear_mode(raw_texture.as_ref(), desc, hal_usage)?;
+
+        let mut texture = Texture::new(
+            self,
+            resource::TextureInner::Native { raw: raw_texture },
+            hal_usage,
+            desc,
+            format_features,
+            clear_mode,
+            self.toggles
+                .contains(wgt::Toggles::LAZY_ZERO_INITIALIZATION),
+        );
+        texture.aliasing = aliasing;
+        texture.sparse_heaps = sparse_heaps;
+
+        let texture = Arc::new(texture);
+
+        self.trackers
+            .lock()
+            .textures
+            .insert_single(&texture, wgt::TextureUses::UNINITIALIZED);
+
+        Ok(texture)
+    }
+
+    /// Validates `desc` against the device's limits and features.
+    ///
+    /// Returns the features of `desc.format` and the view formats that need to be
+    /// passed on to hal.
+    fn validate_texture_descriptor(
+        &self,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<(wgt::TextureFormatFeatures, Vec<TextureFormat>), resource::CreateTextureError>
+    {
+        use resource::{CreateTextureError, TextureDimensionError};
 
         if desc.usage.is_empty() || desc.usage.contains_unknown_bits() {
             return Err(CreateTextureError::InvalidUsage(desc.usage));
         }
 
+        if desc.usage.contains(wgt::TextureUsages::TRANSIENT)
+            && desc.usage != wgt::TextureUsages::TRANSIENT | wgt::TextureUsages::RENDER_ATTACHMENT
+        {
+            return Err(CreateTextureError::InvalidTransientUsage(desc.usage));
+        }
+
         conv::check_texture_dimension_size(
             desc.dimension,
             desc.size,
@@ -1089,7 +1778,8 @@ impl Device {
             });
         }
 
-        let missing_allowed_usages = desc.usage - format_features.allowed_usages;
+        let missing_allowed_usages =
+            desc.usage - wgt::TextureUsages::TRANSIENT - format_features.allowed_usages;
         if !missing_allowed_usages.is_empty() {
             // detect downlevel incompatibilities
             let wgpu_allowed_usages = desc
@@ -1118,23 +1808,17 @@ impl Device {
             self.require_downlevel_flags(wgt::DownlevelFlags::VIEW_FORMATS)?;
         }
 
-        let hal_usage = conv::map_texture_usage_for_texture(desc, &format_features);
-
-        let hal_desc = hal::TextureDescriptor {
-            label: desc.label.to_hal(self.instance_flags),
-            size: desc.size,
-            mip_level_count: desc.mip_level_count,
-            sample_count: desc.sample_count,
-            dimension: desc.dimension,
-            format: desc.format,
-            usage: hal_usage,
-            memory_flags: hal::MemoryFlags::empty(),
-            view_formats: hal_view_formats,
-        };
-
-        let raw_texture = unsafe { self.raw().create_texture(&hal_desc) }
-            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+        Ok((format_features, hal_view_formats))
+    }
 
+    /// Picks how `raw_texture` will be cleared, creating the clear views if it is
+    /// cleared with render passes.
+    fn create_texture_clear_mode(
+        &self,
+        raw_texture: &dyn hal::DynTexture,
+        desc: &resource::TextureDescriptor,
+        hal_usage: wgt::TextureUses,
+    ) -> Result<resource::TextureClearMode, DeviceError> {
         let clear_mode = if hal_usage
             .intersects(wgt::TextureUses::DEPTH_STENCIL_WRITE | wgt::TextureUses::COLOR_TARGET)
             && desc.dimension == wgt::TextureDimension::D2
@@ -1169,10 +1853,8 @@ impl Device {
                                 },
                             };
                             clear_views.push(ManuallyDrop::new(
-                                unsafe {
-                                    self.raw().create_texture_view(raw_texture.as_ref(), &desc)
-                                }
-                                .map_err(|e| self.handle_hal_error(e))?,
+                                unsafe { self.raw().create_texture_view(raw_texture, &desc) }
+                                    .map_err(|e| self.handle_hal_error(e))?,
                             ));
                         };
                     }
@@ -1196,24 +1878,7 @@ impl Device {
             resource::TextureClearMode::BufferCopy
         };
 
-        let texture = Texture::new(
-            self,
-            resource::TextureInner::Native { raw: raw_texture },
-            hal_usage,
-            desc,
-            format_features,
-            clear_mode,
-            true,
-        );
-
-        let texture = Arc::new(texture);
-
-        self.trackers
-            .lock()
-            .textures
-            .insert_single(&texture, wgt::TextureUses::UNINITIALIZED);
-
-        Ok(texture)
+        Ok(clear_mode)
     }
 
     pub(crate) fn create_texture_view(
@@ -1447,7 +2112,9 @@ impl Device {
             }
 
             if resolved_array_layer_count != 1
-                && !(self.features.contains(wgt::Features::MULTIVIEW))
+                && !(self
+                    .features
+                    .intersects(wgt::Features::MULTIVIEW | wgt::Features::MULTI_VIEWPORT))
             {
                 break 'error Err(TextureViewNotRenderableReason::ArrayLayerCount(
                     resolved_array_layer_count,
@@ -1669,18 +2336,59 @@ impl Device {
     ) -> Result<Arc<pipeline::ShaderModule>, pipeline::CreateShaderModuleError> {
         self.check_is_valid()?;
 
-        let (module, source) = match source {
+        if self
+            .instance_flags
+            .contains(wgt::InstanceFlags::STRICT_WEBGPU_PORTABILITY)
+        {
+            #[cfg(feature = "wgsl")]
+            let is_wgsl = matches!(
+                source,
+                pipeline::ShaderModuleSource::Wgsl(..)
+                    | pipeline::ShaderModuleSource::WgslFiles(..)
+            );
+            #[cfg(not(feature = "wgsl"))]
+            let is_wgsl = false;
+            if !is_wgsl {
+                log::warn!(
+                    "Shader module {:?} is not created from WGSL, which is the only shader \
+                    language supported by WebGPU",
+                    desc.label.as_deref().unwrap_or_default()
+                );
+            }
+        }
+
+        let (module, source, files) = match source {
             #[cfg(feature = "wgsl")]
-            pipeline::ShaderModuleSource::Wgsl(code) => {
-                profiling::scope!("naga::front::wgsl::parse_str");
-                let module = naga::front::wgsl::parse_str(&code).map_err(|inner| {
+            pipeline::ShaderModuleSource::Wgsl(code, options) => {
+                let mut parser = naga::front::wgsl::Frontend::new_with_options(options);
+                profiling::scope!("naga::front::wgsl::Frontend.parse");
+                let module = parser.parse(&code).map_err(|inner| {
                     pipeline::CreateShaderModuleError::Parsing(naga::error::ShaderError {
                         source: code.to_string(),
+                        files: Vec::new(),
                         label: desc.label.as_ref().map(|l| l.to_string()),
                         inner: Box::new(inner),
                     })
                 })?;
-                (Cow::Owned(module), code.into_owned())
+                (Cow::Owned(module), code.into_owned(), Vec::new())
+            }
+            #[cfg(feature = "wgsl")]
+            pipeline::ShaderModuleSource::WgslFiles(sources, options) => {
+                let mut parser = naga::front::wgsl::Frontend::new_with_options(options);
+                profiling::scope!("naga::front::wgsl::Frontend.parse_sources");
+                let module = parser.parse_sources(&sources).map_err(|inner| {
+                    pipeline::CreateShaderModuleError::Parsing(naga::error::ShaderError {
+                        source: sources.text().to_string(),
+                        files: sources.file_ranges().to_vec(),
+                        label: desc.label.as_ref().map(|l| l.to_string()),
+                        inner: Box::new(inner),
+                    })
+                })?;
+                (
+                    Cow::Owned(module),
+                    sources.text().to_string(),
+                    sources.file_ranges().to_vec(),
+                )
             }
             #[cfg(feature = "spirv")]
             pipeline::ShaderModuleSource::SpirV(spv, options) => {
@@ -1689,11 +2397,12 @@ impl Device {
                 let module = parser.parse().map_err(|inner| {
                     pipeline::CreateShaderModuleError::ParsingSpirV(naga::error::ShaderError {
                         source: String::new(),
+                        files: Vec::new(),
                         label: desc.label.as_ref().map(|l| l.to_string()),
                         inner: Box::new(inner),
                     })
                 })?;
-                (Cow::Owned(module), String::new())
+                (Cow::Owned(module), String::new(), Vec::new())
             }
             #[cfg(feature = "glsl")]
             pipeline::ShaderModuleSource::Glsl(code, options) => {
@@ -1702,13 +2411,14 @@ impl Device {
                 let module = parser.parse(&options, &code).map_err(|inner| {
                     pipeline::CreateShaderModuleError::ParsingGlsl(naga::error::ShaderError {
                         source: code.to_string(),
+                        files: Vec::new(),
                         label: desc.label.as_ref().map(|l| l.to_string()),
                         inner: Box::new(inner),
                     })
                 })?;
-                (Cow::Owned(module), code.into_owned())
+                (Cow::Owned(module), code.into_owned(), Vec::new())
             }
-            pipeline::ShaderModuleSource::Naga(module) => (module, String::new()),
+            pipeline::ShaderModuleSource::Naga(module) => (module, String::new(), Vec::new()),
             pipeline::ShaderModuleSource::Dummy(_) => panic!("found `ShaderModuleSource::Dummy`"),
         };
         for (_, var) in module.global_variables.iter() {
@@ -1748,20 +2458,46 @@ impl Device {
         .map_err(|inner| {
             pipeline::CreateShaderModuleError::Validation(naga::error::ShaderError {
                 source,
+                files,
                 label: desc.label.as_ref().map(|l| l.to_string()),
                 inner: Box::new(inner),
             })
         })?;
 
         let interface = validation::Interface::new(&module, &info, self.limits.clone());
+        let wide_lines_module = (self.features.contains(wgt::Features::WIDE_LINES)
+            && self
+                .adapter
+                .emulated_features
+                .contains(wgt::Features::WIDE_LINES))
+        .then(|| (*module).clone());
+        // The interface is reflected from the module as written, since optimizing it
+        // removes the resources which no entry point uses.
+        let (module, info) = match desc.optimization {
+            wgt::ShaderOptimization::Default => (module, info),
+            wgt::ShaderOptimization::Performance => self.optimize_shader_module(module, info),
+        };
         let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
             module,
             info,
             debug_source,
         });
+        let runtime_checks = if self.toggles.contains(wgt::Toggles::SHADER_BOUNDS_CHECKS) {
+            desc.runtime_checks
+        } else {
+            // The toggle only controls bounds checks; workgroup memory
+            // zero-initialization is still up to the caller.
+            wgt::ShaderRuntimeChecks {
+                zero_initialize_workgroup_memory: desc
+                    .runtime_checks
+                    .zero_initialize_workgroup_memory,
+                ..wgt::ShaderRuntimeChecks::unchecked()
+            }
+        };
         let hal_desc = hal::ShaderModuleDescriptor {
             label: desc.label.to_hal(self.instance_flags),
-            runtime_checks: desc.runtime_checks,
+            runtime_checks,
+            optimization: desc.optimization,
         };
         let raw = match unsafe { self.raw().create_shader_module(&hal_desc, hal_shader) } {
             Ok(raw) => raw,
@@ -1782,6 +2518,12 @@ impl Device {
             raw: ManuallyDrop::new(raw),
             device: self.clone(),
             interface: Some(interface),
+            zero_initialize_workgroup_memory: runtime_checks.zero_initialize_workgroup_memory,
+            wide_lines_source: wide_lines_module.map(|module| crate::wide_lines::ShaderSource {
+                module,
+                runtime_checks,
+                optimization: desc.optimization,
+            }),
             label: desc.label.to_string(),
         };
 
@@ -1790,6 +2532,38 @@ impl Device {
         Ok(module)
     }
 
+    /// Simplifies a validated `module` with [`naga::proc::optimize`].
+    ///
+    /// Optimization is only a hint, so if it fails, `module` is returned as is.
+    fn optimize_shader_module<'a>(
+        &self,
+        module: Cow<'a, naga::Module>,
+        info: naga::valid::ModuleInfo,
+    ) -> (Cow<'a, naga::Module>, naga::valid::ModuleInfo) {
+        profiling::scope!("naga::proc::optimize");
+        let mut optimized = module.as_ref().clone();
+        if let Err(error) =
+            naga::proc::optimize(&mut optimized, naga::proc::OptimizationLevel::Full)
+        {
+            log::warn!("Shader module could not be optimized: {error}");
+            return (module, info);
+        }
+
+        match create_validator(
+            self.features,
+            self.downlevel.flags,
+            naga::valid::ValidationFlags::all(),
+        )
+        .validate(&optimized)
+        {
+            Ok(optimized_info) => (Cow::Owned(optimized), optimized_info),
+            Err(error) => {
+                log::error!("Optimized shader module is invalid: {error}");
+                (module, info)
+            }
+        }
+    }
+
     #[allow(unused_unsafe)]
     pub(crate) unsafe fn create_shader_module_passthrough<'a>(
         self: &Arc<Self>,
@@ -1830,6 +2604,7 @@ impl Device {
         let hal_desc = hal::ShaderModuleDescriptor {
             label: descriptor.label().to_hal(self.instance_flags),
             runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+            optimization: wgt::ShaderOptimization::Default,
         };
 
         let raw = match unsafe { self.raw().create_shader_module(&hal_desc, hal_shader) } {
@@ -1851,6 +2626,8 @@ impl Device {
             raw: ManuallyDrop::new(raw),
             device: self.clone(),
             interface: None,
+            zero_initialize_workgroup_memory: true,
+            wide_lines_source: None,
             label: descriptor.label().to_string(),
         };
 
@@ -1865,12 +2642,25 @@ impl Device {
 
         let queue = self.get_queue().unwrap();
 
-        let encoder = self
-            .command_allocator
+        self.create_command_encoder_for_queue(&queue, label)
+    }
+
+    /// Create a command encoder whose command buffers can only be submitted
+    /// to `queue`.
+    pub(crate) fn create_command_encoder_for_queue(
+        self: &Arc<Self>,
+        queue: &Queue,
+        label: &crate::Label,
+    ) -> Result<Arc<command::CommandBuffer>, DeviceError> {
+        self.check_is_valid()?;
+        queue.same_device(self)?;
+
+        let allocator = queue.command_allocator();
+        let encoder = allocator
             .acquire_encoder(self.raw(), queue.raw())
             .map_err(|e| self.handle_hal_error(e))?;
 
-        let command_buffer = command::CommandBuffer::new(encoder, self, label);
+        let command_buffer = command::CommandBuffer::new(encoder, queue, label);
 
         let command_buffer = Arc::new(command_buffer);
 
@@ -2043,6 +2833,25 @@ impl Device {
                         })?;
                     (None, WritableStorage::No)
                 }
+                Bt::YcbcrTexture { conversion } => {
+                    if !conversion.format.is_multi_planar_format() {
+                        return Err(binding_model::CreateBindGroupLayoutError::Entry {
+                            binding: entry.binding,
+                            error: BindGroupLayoutEntryError::YcbcrConversionFormat(
+                                conversion.format,
+                            ),
+                        });
+                    }
+                    let features =
+                        wgt::Features::YUV_SAMPLING | conversion.format.required_features();
+                    self.require_features(features).map_err(|e| {
+                        binding_model::CreateBindGroupLayoutError::Entry {
+                            binding: entry.binding,
+                            error: e.into(),
+                        }
+                    })?;
+                    (None, WritableStorage::No)
+                }
             };
 
             // Validate the count parameter
@@ -2802,6 +3611,35 @@ impl Device {
                 view.check_usage(wgt::TextureUsages::TEXTURE_BINDING)?;
                 Ok(wgt::TextureUses::RESOURCE)
             }
+            wgt::BindingType::YcbcrTexture { conversion } => {
+                if view.desc.format != conversion.format
+                    || view.desc.range.aspect != wgt::TextureAspect::All
+                {
+                    return Err(Error::InvalidYcbcrTextureView {
+                        binding,
+                        layout_format: conversion.format,
+                        view_format: view.desc.format,
+                        view_aspect: view.desc.range.aspect,
+                    });
+                }
+                if view.desc.dimension != TextureViewDimension::D2 {
+                    return Err(Error::InvalidTextureDimension {
+                        binding,
+                        layout_dimension: TextureViewDimension::D2,
+                        view_dimension: view.desc.dimension,
+                    });
+                }
+                if view.samples != 1 {
+                    return Err(Error::InvalidTextureMultisample {
+                        binding,
+                        layout_multisampled: false,
+                        view_samples: view.samples,
+                    });
+                }
+
+                view.check_usage(wgt::TextureUsages::TEXTURE_BINDING)?;
+                Ok(wgt::TextureUses::RESOURCE)
+            }
             _ => Err(Error::WrongBindingType {
                 binding,
                 actual: decl.ty,
@@ -2882,8 +3720,22 @@ impl Device {
             .cloned()
             .collect::<ArrayVec<_, { hal::MAX_BIND_GROUPS }>>();
 
-        let raw_bind_group_layouts = desc
-            .bind_group_layouts
+        Ok(self.create_pipeline_layout_unchecked(
+            &desc.label,
+            bind_group_layouts,
+            &desc.push_constant_ranges,
+        )?)
+    }
+
+    /// Creates a pipeline layout without validating it against the device's limits
+    /// and features.
+    pub(crate) fn create_pipeline_layout_unchecked(
+        self: &Arc<Self>,
+        label: &crate::Label,
+        bind_group_layouts: ArrayVec<Arc<BindGroupLayout>, { hal::MAX_BIND_GROUPS }>,
+        push_constant_ranges: &[wgt::PushConstantRange],
+    ) -> Result<Arc<binding_model::PipelineLayout>, DeviceError> {
+        let raw_bind_group_layouts = bind_group_layouts
             .iter()
             .map(|bgl| bgl.raw())
             .collect::<ArrayVec<_, { hal::MAX_BIND_GROUPS }>>();
@@ -2895,12 +3747,12 @@ impl Device {
         };
 
         let hal_desc = hal::PipelineLayoutDescriptor {
-            label: desc.label.to_hal(self.instance_flags),
+            label: label.to_hal(self.instance_flags),
             flags: hal::PipelineLayoutFlags::FIRST_VERTEX_INSTANCE
                 | hal::PipelineLayoutFlags::NUM_WORK_GROUPS
                 | additional_flags,
             bind_group_layouts: &raw_bind_group_layouts,
-            push_constant_ranges: desc.push_constant_ranges.as_ref(),
+            push_constant_ranges,
         };
 
         let raw = unsafe { self.raw().create_pipeline_layout(&hal_desc) }
@@ -2911,9 +3763,9 @@ impl Device {
         let layout = binding_model::PipelineLayout {
             raw: ManuallyDrop::new(raw),
             device: self.clone(),
-            label: desc.label.to_string(),
+            label: label.to_string(),
             bind_group_layouts,
-            push_constant_ranges: desc.push_constant_ranges.iter().cloned().collect(),
+            push_constant_ranges: push_constant_ranges.iter().cloned().collect(),
         };
 
         let layout = Arc::new(layout);
@@ -3049,7 +3901,8 @@ impl Device {
                 module: shader_module.raw(),
                 entry_point: final_entry_point_name.as_ref(),
                 constants: &desc.stage.constants,
-                zero_initialize_workgroup_memory: desc.stage.zero_initialize_workgroup_memory,
+                zero_initialize_workgroup_memory: desc.stage.zero_initialize_workgroup_memory
+                    && shader_module.zero_initialize_workgroup_memory,
             },
             cache: cache.as_ref().map(|it| it.raw()),
         };
@@ -3100,14 +3953,188 @@ impl Device {
         Ok(pipeline)
     }
 
+    pub(crate) fn create_ray_tracing_pipeline(
+        self: &Arc<Self>,
+        desc: pipeline::ResolvedRayTracingPipelineDescriptor,
+    ) -> Result<Arc<pipeline::RayTracingPipeline>, pipeline::CreateRayTracingPipelineError> {
+        use pipeline::CreateRayTracingPipelineError as Error;
+
+        self.check_is_valid()?;
+
+        self.require_features(wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE)?;
+
+        desc.layout.same_device(self)?;
+
+        let mut binding_layout_source =
+            validation::BindingLayoutSource::Provided(desc.layout.get_binding_maps());
+        let mut shader_binding_sizes = FastHashMap::default();
+        let mut stages = Vec::with_capacity(desc.stages.len());
+        for (index, stage) in desc.stages.iter().enumerate() {
+            stage.module.same_device(self)?;
+            if stage.stage.bits().count_ones() != 1
+                || !wgt::ShaderStages::RAY_TRACING.contains(stage.stage)
+            {
+                return Err(Error::InvalidStage {
+                    index,
+                    stage: stage.stage,
+                });
+            }
+            // WGSL has no intersection or callable stages, so those can only
+            // come from passthrough modules, which carry no interface.
+            if let Some(ref interface) = stage.module.interface {
+                if stage
+                    .stage
+                    .intersects(wgt::ShaderStages::INTERSECTION | wgt::ShaderStages::CALLABLE)
+                {
+                    return Err(Error::NotPassthrough { index });
+                }
+                let _ = interface
+                    .check_stage(
+                        &mut binding_layout_source,
+                        &mut shader_binding_sizes,
+                        &stage.entry_point,
+                        stage.stage,
+                        validation::StageIo::default(),
+                        None,
+                    )
+                    .map_err(|error| Error::Stage { index, error })?;
+            }
+            stages.push(stage.stage);
+        }
+        drop(binding_layout_source);
+
+        if !stages.contains(&wgt::ShaderStages::RAY_GENERATION) {
+            return Err(Error::MissingRayGenerationStage);
+        }
+
+        for (group_index, group) in desc.groups.iter().enumerate() {
+            let check = |stage: u32, expected: wgt::ShaderStages| {
+                if stages
+                    .get(stage as usize)
+                    .is_some_and(|&s| expected.contains(s))
+                {
+                    Ok(())
+                } else {
+                    Err(Error::InvalidGroupStage {
+                        group: group_index,
+                        stage,
+                    })
+                }
+            };
+            let check_hit = |closest_hit: Option<u32>, any_hit: Option<u32>| -> Result<(), Error> {
+                if let Some(stage) = closest_hit {
+                    check(stage, wgt::ShaderStages::CLOSEST_HIT)?;
+                }
+                if let Some(stage) = any_hit {
+                    check(stage, wgt::ShaderStages::ANY_HIT)?;
+                }
+                Ok(())
+            };
+            match *group {
+                wgt::RayTracingShaderGroup::General { stage } => check(
+                    stage,
+                    wgt::ShaderStages::RAY_GENERATION
+                        | wgt::ShaderStages::MISS
+                        | wgt::ShaderStages::CALLABLE,
+                )?,
+                wgt::RayTracingShaderGroup::TrianglesHit {
+                    closest_hit,
+                    any_hit,
+                } => check_hit(closest_hit, any_hit)?,
+                wgt::RayTracingShaderGroup::ProceduralHit {
+                    closest_hit,
+                    any_hit,
+                    intersection,
+                } => {
+                    check_hit(closest_hit, any_hit)?;
+                    check(intersection, wgt::ShaderStages::INTERSECTION)?;
+                }
+            }
+        }
+
+        if desc.max_recursion_depth > self.limits.max_ray_recursion_depth {
+            return Err(Error::TooDeepRecursion {
+                given: desc.max_recursion_depth,
+                limit: self.limits.max_ray_recursion_depth,
+            });
+        }
+
+        let cache = match desc.cache {
+            Some(cache) => {
+                cache.same_device(self)?;
+                Some(cache)
+            }
+            None => None,
+        };
+
+        let constants = Default::default();
+        let hal_stages = desc
+            .stages
+            .iter()
+            .map(|stage| hal::RayTracingStage {
+                stage: stage.stage,
+                programmable: hal::ProgrammableStage {
+                    module: stage.module.raw(),
+                    entry_point: stage.entry_point.as_ref(),
+                    constants: &constants,
+                    zero_initialize_workgroup_memory: false,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let pipeline_desc = hal::RayTracingPipelineDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            layout: desc.layout.raw(),
+            stages: &hal_stages,
+            groups: &desc.groups,
+            max_recursion_depth: desc.max_recursion_depth,
+            cache: cache.as_ref().map(|it| it.raw()),
+        };
+
+        let raw =
+            unsafe { self.raw().create_ray_tracing_pipeline(&pipeline_desc) }.map_err(|err| {
+                match err {
+                    hal::PipelineError::Device(error) => {
+                        Error::Device(self.handle_hal_error(error))
+                    }
+                    hal::PipelineError::Linkage(_stages, msg)
+                    | hal::PipelineError::PipelineConstants(_stages, msg) => Error::Internal(msg),
+                    hal::PipelineError::EntryPoint(_stage) => {
+                        Error::Internal(ENTRYPOINT_FAILURE_ERROR.to_string())
+                    }
+                }
+            })?;
+
+        let pipeline = pipeline::RayTracingPipeline {
+            raw: ManuallyDrop::new(raw),
+            layout: desc.layout,
+            device: self.clone(),
+            _shader_modules: desc.stages.iter().map(|s| s.module.clone()).collect(),
+            stages: stages.into_boxed_slice(),
+            groups: desc.groups.iter().cloned().collect(),
+            label: desc.label.to_string(),
+            tracking_data: TrackingData::new(self.tracker_indices.ray_tracing_pipelines.clone()),
+        };
+
+        Ok(Arc::new(pipeline))
+    }
+
     pub(crate) fn create_render_pipeline(
         self: &Arc<Self>,
-        desc: pipeline::ResolvedRenderPipelineDescriptor,
+        desc: pipeline::ResolvedGeneralRenderPipelineDescriptor,
     ) -> Result<Arc<pipeline::RenderPipeline>, pipeline::CreateRenderPipelineError> {
         use wgt::TextureFormatFeatureFlags as Tfff;
 
         self.check_is_valid()?;
 
+        let is_mesh = matches!(
+            desc.vertex,
+            pipeline::RenderPipelineVertexProcessor::Mesh(..)
+        );
+        if is_mesh {
+            self.require_features(wgt::Features::EXPERIMENTAL_MESH_SHADER)?;
+        }
+
         let mut shader_binding_sizes = FastHashMap::default();
 
         let num_attachments = desc.fragment.as_ref().map(|f| f.targets.len()).unwrap_or(0);
@@ -3143,12 +4170,16 @@ impl Device {
         let mut io = validation::StageIo::default();
         let mut validated_stages = wgt::ShaderStages::empty();
 
-        let mut vertex_steps = Vec::with_capacity(desc.vertex.buffers.len());
-        let mut vertex_buffers = Vec::with_capacity(desc.vertex.buffers.len());
+        let vertex_buffer_layouts: &[pipeline::VertexBufferLayout] = match desc.vertex {
+            pipeline::RenderPipelineVertexProcessor::Vertex(ref vertex) => &vertex.buffers,
+            pipeline::RenderPipelineVertexProcessor::Mesh(..) => &[],
+        };
+        let mut vertex_steps = Vec::with_capacity(vertex_buffer_layouts.len());
+        let mut vertex_buffers = Vec::with_capacity(vertex_buffer_layouts.len());
         let mut total_attributes = 0;
         let mut shader_expects_dual_source_blending = false;
         let mut pipeline_expects_dual_source_blending = false;
-        for (i, vb_state) in desc.vertex.buffers.iter().enumerate() {
+        for (i, vb_state) in vertex_buffer_layouts.iter().enumerate() {
             // https://gpuweb.github.io/gpuweb/#abstract-opdef-validating-gpuvertexbufferlayout
 
             if vb_state.array_stride > self.limits.max_vertex_buffer_array_stride as u64 {
@@ -3164,6 +4195,19 @@ impl Device {
                     stride: vb_state.array_stride,
                 });
             }
+            match vb_state.step_mode {
+                wgt::VertexStepMode::Vertex
+                | wgt::VertexStepMode::Instance
+                | wgt::VertexStepMode::InstanceStepRate { rate: 1 } => {}
+                wgt::VertexStepMode::InstanceStepRate { rate: 0 } => {
+                    return Err(pipeline::CreateRenderPipelineError::ZeroInstanceStepRate {
+                        index: i as u32,
+                    });
+                }
+                wgt::VertexStepMode::InstanceStepRate { .. } => {
+                    self.require_features(wgt::Features::VERTEX_INSTANCE_STEP_RATE)?;
+                }
+            }
 
             let max_stride = if vb_state.array_stride == 0 {
                 self.limits.max_vertex_buffer_array_stride as u64
@@ -3274,6 +4318,14 @@ impl Device {
             );
         }
 
+        if desc.primitive.topology.is_strip() && !desc.primitive.primitive_restart {
+            self.require_features(wgt::Features::PRIMITIVE_RESTART_CONTROL)?;
+        }
+
+        if desc.primitive.strip_index_format == Some(wgt::IndexFormat::Uint8) {
+            self.require_features(wgt::Features::INDEX_FORMAT_UINT8)?;
+        }
+
         if desc.primitive.unclipped_depth {
             self.require_features(wgt::Features::DEPTH_CLIP_CONTROL)?;
         }
@@ -3295,6 +4347,29 @@ impl Device {
             );
         }
 
+        let line_width = desc.primitive.line_width;
+        if !line_width.is_finite() || line_width <= 0.0 {
+            return Err(pipeline::CreateRenderPipelineError::InvalidLineWidth(
+                line_width,
+            ));
+        }
+        if line_width != 1.0 {
+            self.require_features(wgt::Features::WIDE_LINES)?;
+        }
+        let emulate_wide_lines = crate::wide_lines::is_emulated(self, &desc.primitive, is_mesh)?;
+
+        if let Some(stipple) = desc.primitive.line_stipple {
+            self.require_features(wgt::Features::LINE_STIPPLE)?;
+            if !(1..=256).contains(&stipple.factor) {
+                return Err(
+                    pipeline::CreateRenderPipelineError::InvalidLineStippleFactor(stipple.factor),
+                );
+            }
+            if desc.multisample.alpha_to_coverage_enabled {
+                return Err(pipeline::CreateRenderPipelineError::LineStippleWithAlphaToCoverage);
+            }
+        }
+
         let mut target_specified = false;
 
         for (i, cs) in color_targets.iter().enumerate() {
@@ -3333,6 +4408,17 @@ impl Device {
                     if !hal::FormatAspects::from(cs.format).contains(hal::FormatAspects::COLOR) {
                         break 'error Some(pipeline::ColorStateError::FormatNotColor(cs.format));
                     }
+                    if cs.logic_op.is_some() {
+                        self.require_features(wgt::Features::LOGIC_OP)?;
+                        if cs.blend.is_some() {
+                            break 'error Some(pipeline::ColorStateError::LogicOpWithBlend);
+                        }
+                        if cs.format.sample_type(None, None) != Some(TextureSampleType::Uint) {
+                            break 'error Some(pipeline::ColorStateError::FormatNotLogicOp(
+                                cs.format,
+                            ));
+                        }
+                    }
 
                     if desc.multisample.count > 1
                         && !format_features
@@ -3381,6 +4467,14 @@ impl Device {
             }
         }
 
+        // Vulkan and GL have a single logic operation for the whole pipeline.
+        let mut logic_ops = color_targets.iter().flatten().map(|cs| cs.logic_op);
+        if let Some(first) = logic_ops.next() {
+            if logic_ops.any(|logic_op| logic_op != first) {
+                return Err(pipeline::CreateRenderPipelineError::MismatchedLogicOps);
+            }
+        }
+
         let limit = self.limits.max_color_attachment_bytes_per_sample;
         let formats = color_targets
             .iter()
@@ -3473,46 +4567,88 @@ impl Device {
             sc
         };
 
-        let vertex_entry_point_name;
-        let vertex_stage = {
-            let stage_desc = &desc.vertex.stage;
-            let stage = wgt::ShaderStages::VERTEX;
-
-            let vertex_shader_module = &stage_desc.module;
-            vertex_shader_module.same_device(self)?;
+        let depth_compare = desc.depth_stencil.as_ref().map(|d| d.depth_compare);
 
-            let stage_err = |error| pipeline::CreateRenderPipelineError::Stage { stage, error };
-
-            vertex_entry_point_name = vertex_shader_module
-                .finalize_entry_point_name(
-                    stage,
-                    stage_desc.entry_point.as_ref().map(|ep| ep.as_ref()),
+        let vertex_entry_point_name;
+        let task_entry_point_name;
+        let mesh_entry_point_name;
+        let (last_primitive_stage, vertex_processor) = match desc.vertex {
+            pipeline::RenderPipelineVertexProcessor::Vertex(ref vertex) => {
+                vertex_entry_point_name = self.check_render_pipeline_stage(
+                    &vertex.stage,
+                    wgt::ShaderStages::VERTEX,
+                    &mut binding_layout_source,
+                    &mut shader_binding_sizes,
+                    &mut io,
+                    &mut validated_stages,
+                    depth_compare,
+                )?;
+                (
+                    wgt::ShaderStages::VERTEX,
+                    HalVertexProcessor::Vertex(hal::ProgrammableStage {
+                        module: vertex.stage.module.raw(),
+                        entry_point: &vertex_entry_point_name,
+                        constants: &vertex.stage.constants,
+                        zero_initialize_workgroup_memory: vertex
+                            .stage
+                            .zero_initialize_workgroup_memory,
+                    }),
                 )
-                .map_err(stage_err)?;
-
-            if let Some(ref interface) = vertex_shader_module.interface {
-                io = interface
-                    .check_stage(
-                        &mut binding_layout_source,
-                        &mut shader_binding_sizes,
-                        &vertex_entry_point_name,
-                        stage,
-                        io,
-                        desc.depth_stencil.as_ref().map(|d| d.depth_compare),
-                    )
-                    .map_err(stage_err)?;
-                validated_stages |= stage;
             }
-
-            hal::ProgrammableStage {
-                module: vertex_shader_module.raw(),
-                entry_point: &vertex_entry_point_name,
-                constants: &stage_desc.constants,
-                zero_initialize_workgroup_memory: stage_desc.zero_initialize_workgroup_memory,
+            pipeline::RenderPipelineVertexProcessor::Mesh(ref task, ref mesh) => {
+                let task_stage = match task {
+                    Some(task) => {
+                        task_entry_point_name = self.check_render_pipeline_stage(
+                            &task.stage,
+                            wgt::ShaderStages::TASK,
+                            &mut binding_layout_source,
+                            &mut shader_binding_sizes,
+                            &mut io,
+                            &mut validated_stages,
+                            depth_compare,
+                        )?;
+                        Some(hal::ProgrammableStage {
+                            module: task.stage.module.raw(),
+                            entry_point: &task_entry_point_name,
+                            constants: &task.stage.constants,
+                            zero_initialize_workgroup_memory: task
+                                .stage
+                                .zero_initialize_workgroup_memory
+                                && task.stage.module.zero_initialize_workgroup_memory,
+                        })
+                    }
+                    None => None,
+                };
+                mesh_entry_point_name = self.check_render_pipeline_stage(
+                    &mesh.stage,
+                    wgt::ShaderStages::MESH,
+                    &mut binding_layout_source,
+                    &mut shader_binding_sizes,
+                    &mut io,
+                    &mut validated_stages,
+                    depth_compare,
+                )?;
+                (
+                    wgt::ShaderStages::MESH,
+                    HalVertexProcessor::Mesh(
+                        task_stage,
+                        hal::ProgrammableStage {
+                            module: mesh.stage.module.raw(),
+                            entry_point: &mesh_entry_point_name,
+                            constants: &mesh.stage.constants,
+                            zero_initialize_workgroup_memory: mesh
+                                .stage
+                                .zero_initialize_workgroup_memory
+                                && mesh.stage.module.zero_initialize_workgroup_memory,
+                        },
+                    ),
+                )
             }
         };
 
         let fragment_entry_point_name;
+        let mut framebuffer_fetches = Vec::new();
+        let mut shader_reads_fully_covered = false;
         let fragment_stage = match desc.fragment {
             Some(ref fragment_state) => {
                 let stage = wgt::ShaderStages::FRAGMENT;
@@ -3533,7 +4669,7 @@ impl Device {
                     )
                     .map_err(stage_err)?;
 
-                if validated_stages == wgt::ShaderStages::VERTEX {
+                if validated_stages.contains(last_primitive_stage) {
                     if let Some(ref interface) = shader_module.interface {
                         io = interface
                             .check_stage(
@@ -3556,6 +4692,12 @@ impl Device {
                             stage,
                             error,
                         })?;
+                    framebuffer_fetches = interface
+                        .fragment_framebuffer_fetches(&fragment_entry_point_name)
+                        .map_err(stage_err)?;
+                    shader_reads_fully_covered = interface
+                        .fragment_reads_fully_covered(&fragment_entry_point_name)
+                        .map_err(stage_err)?;
                 }
 
                 Some(hal::ProgrammableStage {
@@ -3580,6 +4722,11 @@ impl Device {
                 pipeline::CreateRenderPipelineError::PipelineExpectsShaderToUseDualSourceBlending,
             );
         }
+        if shader_reads_fully_covered && !desc.primitive.conservative {
+            return Err(
+                pipeline::CreateRenderPipelineError::FullyCoveredWithoutConservativeRasterization,
+            );
+        }
 
         if validated_stages.contains(wgt::ShaderStages::FRAGMENT) {
             for (i, output) in io.iter() {
@@ -3609,9 +4756,25 @@ impl Device {
                 }
             }
         }
+        for (location, ty) in framebuffer_fetches {
+            let Some(Some(state)) = color_targets.get(location as usize) else {
+                return Err(
+                    pipeline::CreateRenderPipelineError::FramebufferFetchMissingTarget(location),
+                );
+            };
+            validation::check_texture_format(state.format, &ty).map_err(|pipeline| {
+                pipeline::CreateRenderPipelineError::ColorState(
+                    location as u8,
+                    pipeline::ColorStateError::IncompatibleFormat {
+                        pipeline,
+                        shader: ty,
+                    },
+                )
+            })?;
+        }
         let last_stage = match desc.fragment {
             Some(_) => wgt::ShaderStages::FRAGMENT,
-            None => wgt::ShaderStages::VERTEX,
+            None => last_primitive_stage,
         };
         if is_auto_layout && !validated_stages.contains(last_stage) {
             return Err(pipeline::ImplicitLayoutError::ReflectionError(last_stage).into());
@@ -3630,6 +4793,9 @@ impl Device {
         // Multiview is only supported if the feature is enabled
         if desc.multiview.is_some() {
             self.require_features(wgt::Features::MULTIVIEW)?;
+            if is_mesh {
+                self.require_features(wgt::Features::EXPERIMENTAL_MESH_SHADER_MULTIVIEW)?;
+            }
         }
 
         if !self
@@ -3651,6 +4817,18 @@ impl Device {
         let late_sized_buffer_groups =
             Device::make_late_sized_buffer_groups(&shader_binding_sizes, &pipeline_layout);
 
+        let mut expanded_pipeline = match desc.vertex {
+            pipeline::RenderPipelineVertexProcessor::Vertex(ref vertex) if emulate_wide_lines => {
+                Some(crate::wide_lines::ExpandedPipeline::new(
+                    self,
+                    vertex,
+                    &desc.primitive,
+                    &pipeline_layout,
+                )?)
+            }
+            _ => None,
+        };
+
         let cache = match desc.cache {
             Some(cache) => {
                 cache.same_device(self)?;
@@ -3659,39 +4837,89 @@ impl Device {
             None => None,
         };
 
-        let pipeline_desc = hal::RenderPipelineDescriptor {
-            label: desc.label.to_hal(self.instance_flags),
-            layout: pipeline_layout.raw(),
-            vertex_buffers: &vertex_buffers,
-            vertex_stage,
-            primitive: desc.primitive,
-            depth_stencil: desc.depth_stencil.clone(),
-            multisample: desc.multisample,
-            fragment_stage,
-            color_targets,
-            multiview: desc.multiview,
-            cache: cache.as_ref().map(|it| it.raw()),
-        };
-        let raw =
-            unsafe { self.raw().create_render_pipeline(&pipeline_desc) }.map_err(
-                |err| match err {
-                    hal::PipelineError::Device(error) => {
-                        pipeline::CreateRenderPipelineError::Device(self.handle_hal_error(error))
-                    }
-                    hal::PipelineError::Linkage(stage, msg) => {
-                        pipeline::CreateRenderPipelineError::Internal { stage, error: msg }
-                    }
-                    hal::PipelineError::EntryPoint(stage) => {
-                        pipeline::CreateRenderPipelineError::Internal {
-                            stage: hal::auxil::map_naga_stage(stage),
-                            error: ENTRYPOINT_FAILURE_ERROR.to_string(),
-                        }
+        // Emulated 8 bit index buffers are drawn as 16 bit ones.
+        let mut primitive = desc.primitive;
+        if self.index_expansion.is_some()
+            && primitive.strip_index_format == Some(wgt::IndexFormat::Uint8)
+        {
+            primitive.strip_index_format = Some(wgt::IndexFormat::Uint16);
+        }
+
+        let raw = match vertex_processor {
+            HalVertexProcessor::Vertex(mut vertex_stage) => {
+                let expanded_vertex_buffers;
+                let (layout, vertex_buffers, primitive) = match expanded_pipeline {
+                    Some(ref expanded) => {
+                        vertex_stage.module = expanded.module();
+                        expanded_vertex_buffers = expanded.vertex_buffers();
+                        (
+                            expanded.layout.raw(),
+                            &expanded_vertex_buffers,
+                            crate::wide_lines::ExpandedPipeline::primitive(desc.primitive),
+                        )
                     }
-                    hal::PipelineError::PipelineConstants(stage, error) => {
-                        pipeline::CreateRenderPipelineError::PipelineConstants { stage, error }
+                    None => (pipeline_layout.raw(), &vertex_buffers, primitive),
+                };
+                let pipeline_desc = hal::RenderPipelineDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    layout,
+                    vertex_buffers,
+                    vertex_stage,
+                    primitive,
+                    depth_stencil: desc.depth_stencil.clone(),
+                    multisample: desc.multisample,
+                    fragment_stage,
+                    color_targets,
+                    multiview: desc.multiview,
+                    cache: cache.as_ref().map(|it| it.raw()),
+                };
+                unsafe { self.raw().create_render_pipeline(&pipeline_desc) }
+            }
+            HalVertexProcessor::Mesh(task_stage, mesh_stage) => {
+                let pipeline_desc = hal::MeshPipelineDescriptor {
+                    label: desc.label.to_hal(self.instance_flags),
+                    layout: pipeline_layout.raw(),
+                    task_stage,
+                    mesh_stage,
+                    primitive,
+                    depth_stencil: desc.depth_stencil.clone(),
+                    multisample: desc.multisample,
+                    fragment_stage,
+                    color_targets,
+                    multiview: desc.multiview,
+                    cache: cache.as_ref().map(|it| it.raw()),
+                };
+                unsafe { self.raw().create_mesh_pipeline(&pipeline_desc) }
+            }
+        }
+        .map_err(|err| {
+            if let Some(expanded) = expanded_pipeline.take() {
+                // SAFETY: The pipeline using the module wasn't created.
+                unsafe { expanded.wide_lines.destroy(self.raw()) };
+            }
+            match err {
+                hal::PipelineError::Device(error) => {
+                    pipeline::CreateRenderPipelineError::Device(self.handle_hal_error(error))
+                }
+                hal::PipelineError::Linkage(stage, msg) => {
+                    pipeline::CreateRenderPipelineError::Internal { stage, error: msg }
+                }
+                hal::PipelineError::EntryPoint(stage) => {
+                    pipeline::CreateRenderPipelineError::Internal {
+                        stage: hal::auxil::map_naga_stage(stage),
+                        error: ENTRYPOINT_FAILURE_ERROR.to_string(),
                     }
-                },
-            )?;
+                }
+                hal::PipelineError::PipelineConstants(stage, error) => {
+                    pipeline::CreateRenderPipelineError::PipelineConstants { stage, error }
+                }
+            }
+        })?;
+
+        let (pipeline_layout, wide_lines) = match expanded_pipeline {
+            Some(expanded) => (expanded.layout, Some(expanded.wide_lines)),
+            None => (pipeline_layout, None),
+        };
 
         let pass_context = RenderPassContext {
             attachments: AttachmentData {
@@ -3728,7 +4956,15 @@ impl Device {
 
         let shader_modules = {
             let mut shader_modules = ArrayVec::new();
-            shader_modules.push(desc.vertex.stage.module);
+            match desc.vertex {
+                pipeline::RenderPipelineVertexProcessor::Vertex(vertex) => {
+                    shader_modules.push(vertex.stage.module);
+                }
+                pipeline::RenderPipelineVertexProcessor::Mesh(task, mesh) => {
+                    shader_modules.extend(task.map(|t| t.stage.module));
+                    shader_modules.push(mesh.stage.module);
+                }
+            }
             shader_modules.extend(desc.fragment.map(|f| f.stage.module));
             shader_modules
         };
@@ -3741,8 +4977,11 @@ impl Device {
             _shader_modules: shader_modules,
             flags,
             strip_index_format: desc.primitive.strip_index_format,
+            strip_restart: desc.primitive.topology.is_strip() && desc.primitive.primitive_restart,
             vertex_steps,
             late_sized_buffer_groups,
+            is_mesh,
+            wide_lines,
             label: desc.label.to_string(),
             tracking_data: TrackingData::new(self.tracker_indices.render_pipelines.clone()),
         };
@@ -3763,6 +5002,46 @@ impl Device {
         Ok(pipeline)
     }
 
+    /// Resolve the entry point of a stage preceding the fragment stage of a render
+    /// pipeline, and check it against the pipeline layout and the outputs of the
+    /// previous stage if the module can be reflected.
+    #[allow(clippy::too_many_arguments)]
+    fn check_render_pipeline_stage(
+        &self,
+        stage_desc: &pipeline::ResolvedProgrammableStageDescriptor,
+        stage: wgt::ShaderStages,
+        binding_layout_source: &mut validation::BindingLayoutSource,
+        shader_binding_sizes: &mut FastHashMap<naga::ResourceBinding, wgt::BufferSize>,
+        io: &mut validation::StageIo,
+        validated_stages: &mut wgt::ShaderStages,
+        depth_compare: Option<wgt::CompareFunction>,
+    ) -> Result<String, pipeline::CreateRenderPipelineError> {
+        let shader_module = &stage_desc.module;
+        shader_module.same_device(self)?;
+
+        let stage_err = |error| pipeline::CreateRenderPipelineError::Stage { stage, error };
+
+        let entry_point_name = shader_module
+            .finalize_entry_point_name(stage, stage_desc.entry_point.as_ref().map(|ep| ep.as_ref()))
+            .map_err(stage_err)?;
+
+        if let Some(ref interface) = shader_module.interface {
+            *io = interface
+                .check_stage(
+                    binding_layout_source,
+                    shader_binding_sizes,
+                    &entry_point_name,
+                    stage,
+                    mem::take(io),
+                    depth_compare,
+                )
+                .map_err(stage_err)?;
+            *validated_stages |= stage;
+        }
+
+        Ok(entry_point_name)
+    }
+
     /// # Safety
     /// The `data` field on `desc` must have previously been returned from [`crate::global::Global::pipeline_cache_get_data`]
     pub unsafe fn create_pipeline_cache(
@@ -3858,10 +5137,11 @@ impl Device {
         submission_index: crate::SubmissionIndex,
     ) -> Result<(), DeviceError> {
         let fence = self.fence.read();
-        let last_done_index = unsafe { self.raw().get_fence_value(fence.as_ref()) }
+        let last_done_index = self
+            .completed_submission_index(fence.as_ref())
             .map_err(|e| self.handle_hal_error(e))?;
         if last_done_index < submission_index {
-            unsafe { self.raw().wait(fence.as_ref(), submission_index, !0) }
+            unsafe { self.wait_for_submission_index(fence.as_ref(), submission_index, !0) }
                 .map_err(|e| self.handle_hal_error(e))?;
             drop(fence);
             if let Some(queue) = self.get_queue() {
@@ -3922,7 +5202,67 @@ impl Device {
         Ok(query_set)
     }
 
-    fn lose(&self, message: &str) {
+    pub(crate) fn create_shared_fence(
+        self: &Arc<Self>,
+        desc: &resource::SharedFenceDescriptor,
+    ) -> Result<Arc<resource::SharedFence>, resource::CreateSharedFenceError> {
+        self.check_is_valid()?;
+        self.require_features(wgt::Features::EXPERIMENTAL_SHARED_FENCE)?;
+
+        let raw = unsafe { self.raw().create_shared_fence() }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        Ok(self.create_shared_fence_from_hal(raw, desc))
+    }
+
+    /// # Safety
+    ///
+    /// - `handle` must be a valid handle to a fence that can be used by this
+    ///   device.
+    pub(crate) unsafe fn import_shared_fence(
+        self: &Arc<Self>,
+        handle: wgt::SharedFenceHandle,
+        desc: &resource::SharedFenceDescriptor,
+    ) -> Result<Arc<resource::SharedFence>, resource::CreateSharedFenceError> {
+        self.check_is_valid()?;
+        self.require_features(wgt::Features::EXPERIMENTAL_SHARED_FENCE)?;
+
+        let supported = match handle {
+            wgt::SharedFenceHandle::VulkanOpaqueFd(_) => {
+                self.backend() == wgt::Backend::Vulkan && cfg!(not(windows))
+            }
+            wgt::SharedFenceHandle::VulkanOpaqueWin32(_) => {
+                self.backend() == wgt::Backend::Vulkan && cfg!(windows)
+            }
+            wgt::SharedFenceHandle::D3D12Fence(_) => self.backend() == wgt::Backend::Dx12,
+            wgt::SharedFenceHandle::MetalSharedEvent(_) => self.backend() == wgt::Backend::Metal,
+            _ => false,
+        };
+        if !supported {
+            return Err(resource::CreateSharedFenceError::UnsupportedHandle(
+                self.backend(),
+            ));
+        }
+
+        let raw = unsafe { self.raw().import_fence(handle) }
+            .map_err(|e| self.handle_hal_error_with_nonfatal_oom(e))?;
+
+        Ok(self.create_shared_fence_from_hal(raw, desc))
+    }
+
+    fn create_shared_fence_from_hal(
+        self: &Arc<Self>,
+        raw: Box<dyn hal::DynFence>,
+        desc: &resource::SharedFenceDescriptor,
+    ) -> Arc<resource::SharedFence> {
+        Arc::new(resource::SharedFence {
+            raw: ManuallyDrop::new(raw),
+            device: self.clone(),
+            label: desc.label.to_string(),
+        })
+    }
+
+    fn lose(&self, reason: DeviceLostReason, message: &str) {
         // Follow the steps at https://gpuweb.github.io/gpuweb/#lose-the-device.
 
         // Mark the device explicitly as invalid. This is checked in various
@@ -3931,7 +5271,7 @@ impl Device {
 
         // 1) Resolve the GPUDevice device.lost promise.
         if let Some(device_lost_closure) = self.device_lost_closure.lock().take() {
-            device_lost_closure(DeviceLostReason::Unknown, message.to_string());
+            device_lost_closure(reason, message.to_string());
         }
 
         // 2) Complete any outstanding mapAsync() steps.
@@ -3977,6 +5317,107 @@ impl Device {
     pub fn generate_allocator_report(&self) -> Option<wgt::AllocatorReport> {
         self.raw().generate_allocator_report()
     }
+
+    /// Release memory that is held on to without being used: empty memory blocks of the
+    /// backend's allocator and pooled command encoders.
+    ///
+    /// Live resources are not relocated, so memory blocks that are only partially used
+    /// are kept.
+    pub fn trim_memory(&self) -> wgt::TrimMemoryReport {
+        let command_allocators = core::iter::once(Arc::clone(&self.command_allocator))
+            .chain(
+                self.additional_queue_fences
+                    .lock()
+                    .iter()
+                    .map(|queue_fence| Arc::clone(&queue_fence.command_allocator)),
+            )
+            .collect::<Vec<_>>();
+        let freed_command_encoders = command_allocators
+            .iter()
+            .map(|command_allocator| command_allocator.trim())
+            .sum::<usize>();
+
+        wgt::TrimMemoryReport {
+            freed_memory_blocks: self.raw().trim_memory(),
+            freed_command_encoders: freed_command_encoders as u32,
+        }
+    }
+
+    /// Attribute memory to the live, non-destroyed buffers and textures of this device.
+    ///
+    /// If `include_resources` is true, every such resource is listed in the report.
+    pub fn generate_memory_report(&self, include_resources: bool) -> wgt::MemoryReport {
+        let (buffers, textures) = {
+            let trackers = self.trackers.lock();
+            let buffers: Vec<_> = trackers
+                .buffers
+                .used_resources()
+                .filter_map(Weak::upgrade)
+                .collect();
+            let textures: Vec<_> = trackers
+                .textures
+                .used_resources()
+                .filter_map(Weak::upgrade)
+                .collect();
+            (buffers, textures)
+        };
+
+        // Declared after `buffers` and `textures`, so that it is released before they
+        // are dropped, which may destroy the last reference to a resource.
+        let snatch_guard = self.snatchable_lock.read();
+        let mut report = wgt::MemoryReport::default();
+
+        for buffer in &buffers {
+            if buffer.raw(&snatch_guard).is_none() {
+                continue;
+            }
+            report.buffers.count += 1;
+            report.buffers.bytes += buffer.size;
+            if include_resources {
+                let heap = if buffer
+                    .usage
+                    .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+                {
+                    wgt::MemoryReportHeap::HostVisible
+                } else {
+                    wgt::MemoryReportHeap::DeviceLocal
+                };
+                report.resources.push(wgt::ResourceMemoryReport {
+                    resource_type: wgt::MemoryReportResourceType::Buffer,
+                    label: buffer.label.clone(),
+                    size: buffer.size,
+                    heap,
+                });
+            }
+        }
+
+        for texture in &textures {
+            if texture.raw(&snatch_guard).is_none() {
+                continue;
+            }
+            let desc = &texture.desc;
+            let size = (0..desc.mip_level_count)
+                .filter_map(|level| desc.mip_level_size(level))
+                .map(|extent| desc.format.theoretical_memory_footprint(extent))
+                .sum::<u64>()
+                * u64::from(desc.sample_count);
+            report.textures.count += 1;
+            report.textures.bytes += size;
+            if include_resources {
+                report.resources.push(wgt::ResourceMemoryReport {
+                    resource_type: wgt::MemoryReportResourceType::Texture,
+                    label: texture.label.clone(),
+                    size,
+                    heap: wgt::MemoryReportHeap::DeviceLocal,
+                });
+            }
+        }
+
+        report
+            .resources
+            .sort_by_key(|resource| core::cmp::Reverse(resource.size));
+        report
+    }
 }
 
 crate::impl_resource_type!(Device);
@@ -12,12 +12,36 @@ type FileName = String;
 
 pub const FILE_NAME: &str = "trace.ron";
 
+/// Version of the format of [`FILE_NAME`], increased on every change which makes
+/// traces recorded before it unreadable.
+pub const VERSION: u32 = 1;
+
+/// Contents of [`FILE_NAME`].
+///
+/// The trace directory also holds the files named by actions, like shader sources
+/// and the data of buffer writes, so it can be replayed on its own.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceFile<'a> {
+    /// The [`VERSION`] of the format the trace was recorded with.
+    pub version: u32,
+    /// The recorded actions, starting with [`Action::Init`].
+    pub actions: Vec<Action<'a>>,
+}
+
+/// Text written after the actions of [`FILE_NAME`] once the trace is finished.
+///
+/// Appending it to a trace which wasn't finished, because the traced application
+/// crashed for example, makes it readable.
+pub const FILE_END: &str = "],\n)\n";
+
 #[cfg(feature = "trace")]
 pub(crate) fn new_render_bundle_encoder_descriptor<'a>(
     label: crate::Label<'a>,
     context: &'a super::RenderPassContext,
     depth_read_only: bool,
     stencil_read_only: bool,
+    inheritance: wgt::RenderBundleInheritance,
 ) -> crate::command::RenderBundleEncoderDescriptor<'a> {
     crate::command::RenderBundleEncoderDescriptor {
         label,
@@ -31,6 +55,7 @@ pub(crate) fn new_render_bundle_encoder_descriptor<'a>(
         }),
         sample_count: context.sample_count,
         multiview: context.multiview,
+        inheritance,
     }
 }
 
@@ -94,12 +119,23 @@ pub enum Action<'a> {
         implicit_context: Option<super::ImplicitPipelineContext>,
     },
     DestroyComputePipeline(id::ComputePipelineId),
+    CreateRayTracingPipeline {
+        id: id::RayTracingPipelineId,
+        desc: crate::pipeline::RayTracingPipelineDescriptor<'a>,
+    },
+    DestroyRayTracingPipeline(id::RayTracingPipelineId),
     CreateRenderPipeline {
         id: id::RenderPipelineId,
         desc: crate::pipeline::RenderPipelineDescriptor<'a>,
         #[cfg_attr(feature = "replay", serde(default))]
         implicit_context: Option<super::ImplicitPipelineContext>,
     },
+    CreateMeshPipeline {
+        id: id::RenderPipelineId,
+        desc: crate::pipeline::MeshPipelineDescriptor<'a>,
+        #[cfg_attr(feature = "replay", serde(default))]
+        implicit_context: Option<super::ImplicitPipelineContext>,
+    },
     DestroyRenderPipeline(id::RenderPipelineId),
     CreatePipelineCache {
         id: id::PipelineCacheId,
@@ -143,7 +179,7 @@ pub enum Action<'a> {
     DestroyTlas(id::TlasId),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Command {
     CopyBufferToBuffer {
@@ -168,6 +204,11 @@ pub enum Command {
         dst: crate::command::TexelCopyTextureInfo,
         size: wgt::Extent3d,
     },
+    ResolveTexture {
+        src: id::TextureViewId,
+        dst: id::TextureViewId,
+        regions: Vec<wgt::Rect>,
+    },
     ClearBuffer {
         dst: id::BufferId,
         offset: wgt::BufferAddress,
@@ -194,6 +235,8 @@ pub enum Command {
     RunComputePass {
         base: crate::command::BasePass<crate::command::ComputeCommand, Infallible>,
         timestamp_writes: Option<crate::command::PassTimestampWrites>,
+        #[cfg_attr(feature = "replay", serde(default))]
+        pipeline_statistics_query: Option<crate::command::PassPipelineStatisticsQuery>,
     },
     RunRenderPass {
         base: crate::command::BasePass<crate::command::RenderCommand, Infallible>,
@@ -201,6 +244,12 @@ pub enum Command {
         target_depth_stencil: Option<crate::command::RenderPassDepthStencilAttachment>,
         timestamp_writes: Option<crate::command::PassTimestampWrites>,
         occlusion_query_set_id: Option<id::QuerySetId>,
+        #[cfg_attr(feature = "replay", serde(default))]
+        pipeline_statistics_query: Option<crate::command::PassPipelineStatisticsQuery>,
+        #[cfg_attr(feature = "replay", serde(default))]
+        flags: wgt::RenderPassFlags,
+        #[cfg_attr(feature = "replay", serde(default))]
+        multiview: Option<core::num::NonZeroU32>,
     },
     BuildAccelerationStructures {
         blas: Vec<crate::ray_tracing::TraceBlasBuildEntry>,
@@ -221,8 +270,10 @@ pub struct Trace {
 impl Trace {
     pub fn new(path: std::path::PathBuf) -> Result<Self, std::io::Error> {
         log::info!("Tracing into '{:?}'", path);
+        std::fs::create_dir_all(&path)?;
         let mut file = std::fs::File::create(path.join(FILE_NAME))?;
-        file.write_all(b"[\n")?;
+        // The start of a `TraceFile`, the actions are appended as they happen.
+        writeln!(file, "(\n    version: {VERSION},\n    actions: [")?;
         Ok(Self {
             path,
             file,
@@ -253,6 +304,6 @@ impl Trace {
 #[cfg(feature = "trace")]
 impl Drop for Trace {
     fn drop(&mut self) {
-        let _ = self.file.write_all(b"]");
+        let _ = self.file.write_all(FILE_END.as_bytes());
     }
 }
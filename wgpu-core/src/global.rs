@@ -2,9 +2,11 @@ use alloc::{borrow::ToOwned as _, sync::Arc};
 use core::fmt;
 
 use crate::{
+    device::resource::Device,
     hal_api::HalApi,
     hub::{Hub, HubReport},
     instance::{Instance, Surface},
+    lock::{Mutex, rank},
     registry::{Registry, RegistryReport},
     resource_log,
 };
@@ -27,6 +29,8 @@ impl GlobalReport {
 pub struct Global {
     pub(crate) surfaces: Registry<Arc<Surface>>,
     pub(crate) hub: Hub,
+    /// The device captured by [`Global::instance_start_graphics_debugger_capture`].
+    pub(crate) graphics_debugger_capture: Mutex<Option<Arc<Device>>>,
     // the instance must be dropped last
     pub instance: Instance,
 }
@@ -38,6 +42,7 @@ impl Global {
             instance: Instance::new(name, instance_desc),
             surfaces: Registry::new(),
             hub: Hub::new(),
+            graphics_debugger_capture: Mutex::new(rank::GLOBAL_GRAPHICS_DEBUGGER_CAPTURE, None),
         }
     }
 
@@ -51,6 +56,7 @@ impl Global {
             instance: Instance::from_hal_instance::<A>(name.to_owned(), hal_instance),
             surfaces: Registry::new(),
             hub: Hub::new(),
+            graphics_debugger_capture: Mutex::new(rank::GLOBAL_GRAPHICS_DEBUGGER_CAPTURE, None),
         }
     }
 
@@ -70,6 +76,7 @@ impl Global {
             instance,
             surfaces: Registry::new(),
             hub: Hub::new(),
+            graphics_debugger_capture: Mutex::new(rank::GLOBAL_GRAPHICS_DEBUGGER_CAPTURE, None),
         }
     }
 
@@ -94,8 +101,20 @@ impl Drop for Global {
     }
 }
 
+// Every member of `Global` is `Send` and `Sync`, as checked below. Stating it
+// here keeps crates using `Global` from recursing through every resource type
+// in the hub, which can exceed the default recursion limit, whenever they check
+// whether one of their own types holding a `Global` is `Send` or `Sync`.
+#[cfg(send_sync)]
+unsafe impl Send for Global {}
+#[cfg(send_sync)]
+unsafe impl Sync for Global {}
+
 #[cfg(send_sync)]
 fn _test_send_sync(global: &Global) {
     fn test_internal<T: Send + Sync>(_: T) {}
-    test_internal(global)
+    test_internal(&global.surfaces);
+    test_internal(&global.hub);
+    test_internal(&global.graphics_debugger_capture);
+    test_internal(&global.instance);
 }
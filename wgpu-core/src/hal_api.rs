@@ -8,6 +8,11 @@ impl HalApi for hal::api::Noop {
     const VARIANT: Backend = Backend::Noop;
 }
 
+#[cfg(feature = "cpu")]
+impl HalApi for hal::api::Cpu {
+    const VARIANT: Backend = Backend::Cpu;
+}
+
 #[cfg(vulkan)]
 impl HalApi for hal::api::Vulkan {
     const VARIANT: Backend = Backend::Vulkan;
@@ -108,10 +108,11 @@ use crate::{
     command::{CommandBuffer, RenderBundle},
     device::{Device, queue::Queue},
     instance::Adapter,
-    pipeline::{ComputePipeline, PipelineCache, RenderPipeline, ShaderModule},
+    pipeline::{ComputePipeline, PipelineCache, RayTracingPipeline, RenderPipeline, ShaderModule},
     registry::{Registry, RegistryReport},
     resource::{
-        Blas, Buffer, Fallible, QuerySet, Sampler, StagingBuffer, Texture, TextureView, Tlas,
+        Blas, Buffer, Fallible, MemoryHeap, QuerySet, Sampler, SharedFence, StagingBuffer, Texture,
+        TextureView, Tlas,
     },
 };
 
@@ -128,8 +129,11 @@ pub struct HubReport {
     pub render_bundles: RegistryReport,
     pub render_pipelines: RegistryReport,
     pub compute_pipelines: RegistryReport,
+    pub ray_tracing_pipelines: RegistryReport,
     pub pipeline_caches: RegistryReport,
     pub query_sets: RegistryReport,
+    pub shared_fences: RegistryReport,
+    pub memory_heaps: RegistryReport,
     pub buffers: RegistryReport,
     pub textures: RegistryReport,
     pub texture_views: RegistryReport,
@@ -148,21 +152,21 @@ impl HubReport {
 /// ## Locking
 ///
 /// Each field in `Hub` is a [`Registry`] holding all the values of a
-/// particular type of resource, all protected by a single RwLock.
-/// So for example, to access any [`Buffer`], you must acquire a read
-/// lock on the `Hub`s entire buffers registry. The lock guard
-/// gives you access to the `Registry`'s [`Storage`], which you can
-/// then index with the buffer's id. (Yes, this design causes
-/// contention; see [#2272].)
+/// particular type of resource in a [`Storage`]. The storage is split into
+/// shards by id, each protected by its own RwLock, so resources of the
+/// same type can be registered and looked up from several threads without
+/// contending on a single lock (see [#2272]). Looking up a single value,
+/// for example with [`Registry::get`], only locks the shard holding it.
 ///
-/// But most `wgpu` operations require access to several different
-/// kinds of resource, so you often need to hold locks on several
-/// different fields of your [`Hub`] simultaneously.
+/// Some `wgpu` operations look up many values of several different kinds
+/// of resource; they hold read locks on all shards of several different
+/// fields of your [`Hub`] simultaneously, via [`Registry::read`].
 ///
 /// Inside the `Registry` there are `Arc<T>` where `T` is a Resource
 /// Lock of `Registry` happens only when accessing to get the specific resource
 ///
 /// [`Storage`]: crate::storage::Storage
+/// [#2272]: https://github.com/gfx-rs/wgpu/issues/2272
 pub struct Hub {
     pub(crate) adapters: Registry<Arc<Adapter>>,
     pub(crate) devices: Registry<Arc<Device>>,
@@ -175,8 +179,11 @@ pub struct Hub {
     pub(crate) render_bundles: Registry<Fallible<RenderBundle>>,
     pub(crate) render_pipelines: Registry<Fallible<RenderPipeline>>,
     pub(crate) compute_pipelines: Registry<Fallible<ComputePipeline>>,
+    pub(crate) ray_tracing_pipelines: Registry<Fallible<RayTracingPipeline>>,
     pub(crate) pipeline_caches: Registry<Fallible<PipelineCache>>,
     pub(crate) query_sets: Registry<Fallible<QuerySet>>,
+    pub(crate) shared_fences: Registry<Fallible<SharedFence>>,
+    pub(crate) memory_heaps: Registry<Fallible<MemoryHeap>>,
     pub(crate) buffers: Registry<Fallible<Buffer>>,
     pub(crate) staging_buffers: Registry<StagingBuffer>,
     pub(crate) textures: Registry<Fallible<Texture>>,
@@ -200,8 +207,11 @@ impl Hub {
             render_bundles: Registry::new(),
             render_pipelines: Registry::new(),
             compute_pipelines: Registry::new(),
+            ray_tracing_pipelines: Registry::new(),
             pipeline_caches: Registry::new(),
             query_sets: Registry::new(),
+            shared_fences: Registry::new(),
+            memory_heaps: Registry::new(),
             buffers: Registry::new(),
             staging_buffers: Registry::new(),
             textures: Registry::new(),
@@ -225,8 +235,11 @@ impl Hub {
             render_bundles: self.render_bundles.generate_report(),
             render_pipelines: self.render_pipelines.generate_report(),
             compute_pipelines: self.compute_pipelines.generate_report(),
+            ray_tracing_pipelines: self.ray_tracing_pipelines.generate_report(),
             pipeline_caches: self.pipeline_caches.generate_report(),
             query_sets: self.query_sets.generate_report(),
+            shared_fences: self.shared_fences.generate_report(),
+            memory_heaps: self.memory_heaps.generate_report(),
             buffers: self.buffers.generate_report(),
             textures: self.textures.generate_report(),
             texture_views: self.texture_views.generate_report(),
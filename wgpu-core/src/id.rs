@@ -252,6 +252,7 @@ ids! {
     pub type ShaderModuleId ShaderModule;
     pub type RenderPipelineId RenderPipeline;
     pub type ComputePipelineId ComputePipeline;
+    pub type RayTracingPipelineId RayTracingPipeline;
     pub type PipelineCacheId PipelineCache;
     pub type CommandEncoderId CommandEncoder;
     pub type CommandBufferId CommandBuffer;
@@ -260,6 +261,8 @@ ids! {
     pub type RenderBundleEncoderId RenderBundleEncoder;
     pub type RenderBundleId RenderBundle;
     pub type QuerySetId QuerySet;
+    pub type SharedFenceId SharedFence;
+    pub type MemoryHeapId MemoryHeap;
     pub type BlasId Blas;
     pub type TlasId Tlas;
 }
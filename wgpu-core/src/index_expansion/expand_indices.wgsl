@@ -0,0 +1,43 @@
+// Widens 8 bit indices into 16 bit indices, two at a time.
+//
+// For an explanation of how the expanded indices are used, see the `mod.rs` file
+// in this folder.
+
+struct Params {
+    // offset in bytes of the first index in `src`
+    src_offset: u32,
+    // number of indices to expand
+    index_count: u32,
+    // whether `0xFF` restarts strips, and must become `0xFFFF`
+    restart: u32,
+    // number of invocations in a row of workgroups
+    row_size: u32,
+}
+var<push_constant> params: Params;
+
+@group(0) @binding(0)
+var<storage, read> src: array<u32>;
+@group(0) @binding(1)
+var<storage, read_write> dst: array<u32>;
+
+fn load_index(index: u32) -> u32 {
+    if index >= params.index_count {
+        return 0u;
+    }
+    let byte = params.src_offset + index;
+    let value = (src[byte / 4u] >> ((byte % 4u) * 8u)) & 0xFFu;
+    if params.restart != 0u && value == 0xFFu {
+        return 0xFFFFu;
+    }
+    return value;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3u) {
+    let pair = id.x + id.y * params.row_size;
+    if pair * 2u >= params.index_count {
+        return;
+    }
+
+    dst[pair] = load_index(pair * 2u) | (load_index(pair * 2u + 1u) << 16u);
+}
@@ -0,0 +1,393 @@
+//! Emulation of [`wgt::IndexFormat::Uint8`] on backends that can only draw with 16 and
+//! 32 bit indices.
+//!
+//! ## Algorithm
+//!
+//! The 8 bit index buffer bound in a render pass isn't bound as an index buffer at all.
+//! The first indexed draw with it creates a 16 bit index buffer that the bound range
+//! is widened into, and binds that in its place. Indices keep their position, so the
+//! first index and base vertex of direct and indirect draws apply unchanged. The 8 bit
+//! buffer is used as a read-only storage buffer by the pass instead.
+//!
+//! Before the render pass, a compute pass runs `expand_indices.wgsl` over each of
+//! these ranges. Each invocation writes two indices, since storage buffers can only be
+//! written 32 bits at a time.
+//!
+//! Pipelines that restart strips are created with a 16 bit strip index format, so
+//! `0xFF` is widened to `0xFFFF` for them. Other pipelines can draw vertex 255, so a
+//! range is expanded once for each kind of pipeline that draws with it.
+
+use alloc::{boxed::Box, string::ToString as _, sync::Arc, vec::Vec};
+use core::{mem::ManuallyDrop, num::NonZeroU64, ops::Range};
+
+use wgt::BufferAddress;
+
+use crate::{
+    command::{DrawError, RenderPassErrorInner},
+    device::{Device, DeviceError, queue::TempResource},
+    pipeline::CreateShaderModuleError,
+    resource::{Buffer, RawResourceAccess as _},
+    resource_log,
+    snatch::SnatchGuard,
+};
+
+/// The size of the invocations of `expand_indices.wgsl`.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Holds the device-level resources that expand 8 bit index buffers.
+pub(crate) struct IndexExpansion {
+    module: Box<dyn hal::DynShaderModule>,
+    bind_group_layout: Box<dyn hal::DynBindGroupLayout>,
+    pipeline_layout: Box<dyn hal::DynPipelineLayout>,
+    pipeline: Box<dyn hal::DynComputePipeline>,
+}
+
+impl IndexExpansion {
+    pub(crate) fn new(device: &dyn hal::DynDevice) -> Result<Self, DeviceError> {
+        let module = match create_expansion_module(device) {
+            Ok(module) => module,
+            Err(e) => {
+                log::error!("index-expansion error: {e:?}");
+                return Err(DeviceError::Lost);
+            }
+        };
+
+        let storage_entry = |binding, read_only| wgt::BindGroupLayoutEntry {
+            binding,
+            visibility: wgt::ShaderStages::COMPUTE,
+            ty: wgt::BindingType::Buffer {
+                ty: wgt::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: Some(NonZeroU64::new(4).unwrap()),
+            },
+            count: None,
+        };
+        let bind_group_layout = unsafe {
+            device
+                .create_bind_group_layout(&hal::BindGroupLayoutDescriptor {
+                    label: None,
+                    flags: hal::BindGroupLayoutFlags::empty(),
+                    entries: &[storage_entry(0, true), storage_entry(1, false)],
+                })
+                .map_err(DeviceError::from_hal)?
+        };
+
+        let pipeline_layout = unsafe {
+            device
+                .create_pipeline_layout(&hal::PipelineLayoutDescriptor {
+                    label: None,
+                    flags: hal::PipelineLayoutFlags::empty(),
+                    bind_group_layouts: &[bind_group_layout.as_ref()],
+                    push_constant_ranges: &[wgt::PushConstantRange {
+                        stages: wgt::ShaderStages::COMPUTE,
+                        range: 0..16,
+                    }],
+                })
+                .map_err(DeviceError::from_hal)?
+        };
+
+        let pipeline_desc = hal::ComputePipelineDescriptor {
+            label: None,
+            layout: pipeline_layout.as_ref(),
+            stage: hal::ProgrammableStage {
+                module: module.as_ref(),
+                entry_point: "main",
+                constants: &Default::default(),
+                zero_initialize_workgroup_memory: false,
+            },
+            cache: None,
+        };
+        let pipeline = match unsafe { device.create_compute_pipeline(&pipeline_desc) } {
+            Ok(pipeline) => pipeline,
+            Err(hal::PipelineError::Device(error)) => return Err(DeviceError::from_hal(error)),
+            Err(e) => {
+                log::error!("index-expansion error: {e:?}");
+                return Err(DeviceError::Lost);
+            }
+        };
+
+        Ok(Self {
+            module,
+            bind_group_layout,
+            pipeline_layout,
+            pipeline,
+        })
+    }
+
+    /// Creates the 16 bit index buffer that `range` of `buffer` is widened into before
+    /// the pass, for pipelines that restart strips or not.
+    pub(crate) fn expand(
+        &self,
+        device: &Arc<Device>,
+        buffer: &Buffer,
+        range: Range<BufferAddress>,
+        restart: bool,
+        snatch_guard: &SnatchGuard,
+    ) -> Result<Expansion, RenderPassErrorInner> {
+        let limits = &device.adapter.raw.capabilities.limits;
+        let alignment = u64::from(limits.min_storage_buffer_offset_alignment);
+        let limit = u64::from(limits.max_storage_buffer_binding_size);
+
+        // The raw buffer is padded to a multiple of 4 bytes, so the binding can read
+        // the last word of the range.
+        let src_start = range.start - range.start % alignment;
+        let src_size = range.end.next_multiple_of(4) - src_start;
+        let index_count = range.end - range.start;
+        let dst_size = (index_count * 2).next_multiple_of(4);
+        let size = src_size.max(dst_size);
+        if size > limit {
+            return Err(DrawError::Uint8IndicesTooLarge { size, limit }.into());
+        }
+        // Draws with an empty range are rejected before they get here.
+        let src_size = wgt::BufferSize::new(src_size).unwrap();
+        let dst_size = wgt::BufferSize::new(dst_size).unwrap();
+
+        let src = buffer.try_raw(snatch_guard)?;
+        let dst = unsafe {
+            device.raw().create_buffer(&hal::BufferDescriptor {
+                label: crate::hal_label(
+                    Some("(wgpu internal) expanded index buffer"),
+                    device.instance_flags,
+                ),
+                size: dst_size.get(),
+                usage: wgt::BufferUses::INDEX | wgt::BufferUses::STORAGE_READ_WRITE,
+                memory_flags: hal::MemoryFlags::empty(),
+            })
+        }
+        .map_err(|e| device.handle_hal_error(e))?;
+
+        let bind_group_desc = hal::BindGroupDescriptor {
+            label: None,
+            layout: self.bind_group_layout.as_ref(),
+            entries: &[
+                hal::BindGroupEntry {
+                    binding: 0,
+                    resource_index: 0,
+                    count: 1,
+                },
+                hal::BindGroupEntry {
+                    binding: 1,
+                    resource_index: 1,
+                    count: 1,
+                },
+            ],
+            // SAFETY: Both bindings were sized to fit within their buffers.
+            buffers: &[
+                hal::BufferBinding::new_unchecked(src, src_start, src_size),
+                hal::BufferBinding::new_unchecked(dst.as_ref(), 0, dst_size),
+            ],
+            samplers: &[],
+            textures: &[],
+            acceleration_structures: &[],
+        };
+        let bind_group = match unsafe { device.raw().create_bind_group(&bind_group_desc) } {
+            Ok(bind_group) => bind_group,
+            Err(e) => {
+                unsafe { device.raw().destroy_buffer(dst) };
+                return Err(device.handle_hal_error(e).into());
+            }
+        };
+
+        Ok(Expansion {
+            indices: ExpandedIndices {
+                buffer: ManuallyDrop::new(dst),
+                size: dst_size,
+                bind_group: ManuallyDrop::new(bind_group),
+                device: device.clone(),
+            },
+            src_offset: (range.start - src_start) as u32,
+            index_count: index_count as u32,
+            restart,
+        })
+    }
+
+    /// Injects a compute pass that widens the index buffers of the render pass that is
+    /// about to run.
+    ///
+    /// The expanded index buffers are kept alive in `temp_resources`.
+    pub(crate) fn inject_expansion_pass(
+        &self,
+        device: &Device,
+        encoder: &mut dyn hal::DynCommandEncoder,
+        expansions: Vec<Expansion>,
+        temp_resources: &mut Vec<TempResource>,
+    ) {
+        if expansions.is_empty() {
+            return;
+        }
+
+        let barriers = |from, to| {
+            expansions
+                .iter()
+                .map(|expansion| hal::BufferBarrier {
+                    buffer: expansion.indices.buffer.as_ref(),
+                    usage: hal::StateTransition { from, to },
+                })
+                .collect::<Vec<_>>()
+        };
+        unsafe {
+            encoder.transition_buffers(&barriers(
+                wgt::BufferUses::empty(),
+                wgt::BufferUses::STORAGE_READ_WRITE,
+            ));
+            encoder.begin_compute_pass(&hal::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+                pipeline_statistics_query: None,
+            });
+            encoder.set_compute_pipeline(self.pipeline.as_ref());
+        }
+
+        let max_workgroups = device.limits.max_compute_workgroups_per_dimension;
+        for expansion in expansions.iter() {
+            // Spread the workgroups over rows if there are too many for one dimension.
+            let workgroups = expansion.index_count.div_ceil(2 * WORKGROUP_SIZE);
+            let row_workgroups = workgroups.min(max_workgroups);
+            let rows = workgroups.div_ceil(row_workgroups);
+            unsafe {
+                encoder.set_bind_group(
+                    self.pipeline_layout.as_ref(),
+                    0,
+                    Some(expansion.indices.bind_group.as_ref()),
+                    &[],
+                );
+                encoder.set_push_constants(
+                    self.pipeline_layout.as_ref(),
+                    wgt::ShaderStages::COMPUTE,
+                    0,
+                    &[
+                        expansion.src_offset,
+                        expansion.index_count,
+                        expansion.restart.into(),
+                        row_workgroups * WORKGROUP_SIZE,
+                    ],
+                );
+                encoder.dispatch([row_workgroups, rows, 1]);
+            }
+        }
+
+        unsafe {
+            encoder.end_compute_pass();
+            encoder.transition_buffers(&barriers(
+                wgt::BufferUses::STORAGE_READ_WRITE,
+                wgt::BufferUses::INDEX,
+            ));
+        }
+
+        temp_resources.extend(
+            expansions
+                .into_iter()
+                .map(|expansion| TempResource::ExpandedIndices(expansion.indices)),
+        );
+    }
+
+    pub(crate) fn dispose(self, device: &dyn hal::DynDevice) {
+        let Self {
+            module,
+            bind_group_layout,
+            pipeline_layout,
+            pipeline,
+        } = self;
+
+        unsafe {
+            device.destroy_compute_pipeline(pipeline);
+            device.destroy_pipeline_layout(pipeline_layout);
+            device.destroy_bind_group_layout(bind_group_layout);
+            device.destroy_shader_module(module);
+        }
+    }
+}
+
+/// A range of an 8 bit index buffer, widened into 16 bit indices before a render pass.
+#[derive(Debug)]
+pub(crate) struct Expansion {
+    indices: ExpandedIndices,
+    /// Offset in bytes of the range from the start of the source binding.
+    src_offset: u32,
+    index_count: u32,
+    restart: bool,
+}
+
+impl Expansion {
+    /// The binding of the 16 bit index buffer to draw with.
+    pub(crate) fn index_buffer(&self) -> hal::BufferBinding<'_, dyn hal::DynBuffer> {
+        // SAFETY: The binding spans the whole buffer.
+        hal::BufferBinding::new_unchecked(self.indices.buffer.as_ref(), 0, self.indices.size)
+    }
+}
+
+/// The 16 bit index buffer of an [`Expansion`], and the bind group it is written
+/// through.
+#[derive(Debug)]
+pub struct ExpandedIndices {
+    buffer: ManuallyDrop<Box<dyn hal::DynBuffer>>,
+    size: wgt::BufferSize,
+    bind_group: ManuallyDrop<Box<dyn hal::DynBindGroup>>,
+    device: Arc<Device>,
+}
+
+impl Drop for ExpandedIndices {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw ExpandedIndices");
+        // SAFETY: We are in the Drop impl and we don't use these anymore after this point.
+        let bind_group = unsafe { ManuallyDrop::take(&mut self.bind_group) };
+        let buffer = unsafe { ManuallyDrop::take(&mut self.buffer) };
+        unsafe {
+            self.device.raw().destroy_bind_group(bind_group);
+            self.device.raw().destroy_buffer(buffer);
+        }
+    }
+}
+
+fn create_expansion_module(
+    device: &dyn hal::DynDevice,
+) -> Result<Box<dyn hal::DynShaderModule>, CreateShaderModuleError> {
+    let src = include_str!("./expand_indices.wgsl");
+
+    #[cfg(feature = "wgsl")]
+    let module = naga::front::wgsl::parse_str(src).map_err(|inner| {
+        CreateShaderModuleError::Parsing(naga::error::ShaderError {
+            source: src.to_string(),
+            files: Vec::new(),
+            label: None,
+            inner: Box::new(inner),
+        })
+    })?;
+    #[cfg(not(feature = "wgsl"))]
+    #[allow(clippy::diverging_sub_expression)]
+    let module = panic!("Index expansion requires the wgsl feature flag to be enabled!");
+
+    let info = crate::device::create_validator(
+        wgt::Features::PUSH_CONSTANTS,
+        wgt::DownlevelFlags::empty(),
+        naga::valid::ValidationFlags::all(),
+    )
+    .validate(&module)
+    .map_err(|inner| {
+        CreateShaderModuleError::Validation(naga::error::ShaderError {
+            source: src.to_string(),
+            files: Vec::new(),
+            label: None,
+            inner: Box::new(inner),
+        })
+    })?;
+    let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
+        module: alloc::borrow::Cow::Owned(module),
+        info,
+        debug_source: None,
+    });
+    let hal_desc = hal::ShaderModuleDescriptor {
+        label: None,
+        runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+        optimization: wgt::ShaderOptimization::Default,
+    };
+    unsafe { device.create_shader_module(&hal_desc, hal_shader) }.map_err(|error| match error {
+        hal::ShaderError::Device(error) => {
+            CreateShaderModuleError::Device(DeviceError::from_hal(error))
+        }
+        hal::ShaderError::Compilation(ref msg) => {
+            log::error!("Shader error: {}", msg);
+            CreateShaderModuleError::Generation
+        }
+    })
+}
@@ -3,7 +3,7 @@ use crate::{
     device::DeviceError,
     pipeline::{CreateComputePipelineError, CreateShaderModuleError},
 };
-use alloc::{boxed::Box, format, string::ToString as _};
+use alloc::{boxed::Box, format, string::ToString as _, vec::Vec};
 use core::num::NonZeroU64;
 
 /// This machinery requires the following limits:
@@ -87,6 +87,7 @@ impl Dispatch {
         let module = naga::front::wgsl::parse_str(&src).map_err(|inner| {
             CreateShaderModuleError::Parsing(naga::error::ShaderError {
                 source: src.clone(),
+                files: Vec::new(),
                 label: None,
                 inner: Box::new(inner),
             })
@@ -104,6 +105,7 @@ impl Dispatch {
         .map_err(|inner| {
             CreateShaderModuleError::Validation(naga::error::ShaderError {
                 source: src,
+                files: Vec::new(),
                 label: None,
                 inner: Box::new(inner),
             })
@@ -116,6 +118,7 @@ impl Dispatch {
         let hal_desc = hal::ShaderModuleDescriptor {
             label: None,
             runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+            optimization: wgt::ShaderOptimization::Default,
         };
         let module =
             unsafe { device.create_shader_module(&hal_desc, hal_shader) }.map_err(|error| {
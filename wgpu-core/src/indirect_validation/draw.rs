@@ -38,10 +38,10 @@ const BUFFER_SIZE: wgt::BufferSize = unsafe { wgt::BufferSize::new_unchecked(1_0
 ///
 /// This machinery requires the following limits:
 ///
-/// - max_bind_groups: 3,
-/// - max_dynamic_storage_buffers_per_pipeline_layout: 1,
-/// - max_storage_buffers_per_shader_stage: 3,
-/// - max_push_constant_size: 8,
+/// - max_bind_groups: 4,
+/// - max_dynamic_storage_buffers_per_pipeline_layout: 2,
+/// - max_storage_buffers_per_shader_stage: 4,
+/// - max_push_constant_size: 16,
 ///
 /// These are all indirectly satisfied by `DownlevelFlags::INDIRECT_EXECUTION`, which is also
 /// required for this module's functionality to work.
@@ -79,10 +79,12 @@ impl Draw {
                 metadata_bind_group_layout.as_ref(),
                 src_bind_group_layout.as_ref(),
                 dst_bind_group_layout.as_ref(),
+                // the count buffer is bound the same way as the source buffer
+                src_bind_group_layout.as_ref(),
             ],
             push_constant_ranges: &[wgt::PushConstantRange {
                 stages: wgt::ShaderStages::COMPUTE,
-                range: 0..8,
+                range: 0..16,
             }],
         };
         let pipeline_layout = unsafe {
@@ -348,6 +350,7 @@ impl Draw {
         let desc = hal::ComputePassDescriptor {
             label: None,
             timestamp_writes: None,
+            pipeline_statistics_query: None,
         };
         unsafe {
             encoder.begin_compute_pass(&desc);
@@ -362,12 +365,21 @@ impl Draw {
             let metadata_start =
                 (batch.metadata_buffer_offset / size_of::<MetadataEntry>() as u64) as u32;
             let metadata_count = batch.entries.len() as u32;
+            let (count_offset, first_draw_index) = match batch.count {
+                Some(ref count) => (count.offset | (1 << 31), count.first_draw_index),
+                None => (0, 0),
+            };
             unsafe {
                 encoder.set_push_constants(
                     pipeline_layout,
                     wgt::ShaderStages::COMPUTE,
                     0,
-                    &[metadata_start, metadata_count],
+                    &[
+                        metadata_start,
+                        metadata_count,
+                        count_offset,
+                        first_draw_index,
+                    ],
                 );
             }
 
@@ -398,6 +410,30 @@ impl Draw {
                 encoder.set_bind_group(pipeline_layout, 2, Some(dst_bind_group), &[]);
             }
 
+            // Draws that aren't limited by a count buffer still need something bound,
+            // the shader won't read from it.
+            let (count_bind_group, count_dynamic_offset) = match batch.count {
+                Some(ref count) => (
+                    count
+                        .buffer
+                        .indirect_validation_bind_groups
+                        .get(snatch_guard)
+                        .unwrap()
+                        .draw
+                        .as_ref(),
+                    count.dynamic_offset,
+                ),
+                None => (src_bind_group, batch.src_dynamic_offset),
+            };
+            unsafe {
+                encoder.set_bind_group(
+                    pipeline_layout,
+                    3,
+                    Some(count_bind_group),
+                    &[count_dynamic_offset as u32],
+                );
+            }
+
             unsafe {
                 encoder.dispatch([(batch.entries.len() as u32).div_ceil(64), 1, 1]);
             }
@@ -473,6 +509,7 @@ fn create_validation_module(
     let module = naga::front::wgsl::parse_str(src).map_err(|inner| {
         CreateShaderModuleError::Parsing(naga::error::ShaderError {
             source: src.to_string(),
+            files: Vec::new(),
             label: None,
             inner: Box::new(inner),
         })
@@ -490,6 +527,7 @@ fn create_validation_module(
     .map_err(|inner| {
         CreateShaderModuleError::Validation(naga::error::ShaderError {
             source: src.to_string(),
+            files: Vec::new(),
             label: None,
             inner: Box::new(inner),
         })
@@ -502,6 +540,7 @@ fn create_validation_module(
     let hal_desc = hal::ShaderModuleDescriptor {
         label: None,
         runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+        optimization: wgt::ShaderOptimization::Default,
     };
     let module = unsafe { device.create_shader_module(&hal_desc, hal_shader) }.map_err(
         |error| match error {
@@ -727,6 +766,15 @@ impl DrawResources {
         }
     }
 
+    /// Keeps the entries of `other` until `self` is dropped.
+    ///
+    /// The entries are put after those of `self`, so batches must be done
+    /// with them.
+    pub(crate) fn extend(&mut self, mut other: Self) {
+        self.dst_entries.append(&mut other.dst_entries);
+        self.metadata_entries.append(&mut other.metadata_entries);
+    }
+
     pub(crate) fn get_dst_buffer(&self, index: usize) -> &dyn hal::DynBuffer {
         self.dst_entries.get(index).unwrap().buffer.as_ref()
     }
@@ -869,10 +917,21 @@ impl MetadataEntry {
     }
 }
 
+/// The count buffer limiting the draws of a [`DrawIndirectValidationBatch`].
+struct BatchCount {
+    buffer: Arc<crate::resource::Buffer>,
+    dynamic_offset: u64,
+    /// Offset of the count in u32's, relative to `dynamic_offset`.
+    offset: u32,
+    /// Index of the batch's first draw within its `multi_draw_indirect_count` call.
+    first_draw_index: u32,
+}
+
 struct DrawIndirectValidationBatch {
     src_buffer: Arc<crate::resource::Buffer>,
     src_dynamic_offset: u64,
     dst_resource_index: usize,
+    count: Option<BatchCount>,
     entries: Vec<MetadataEntry>,
 
     staging_buffer_index: usize,
@@ -893,10 +952,22 @@ impl DrawIndirectValidationBatch {
     }
 }
 
+/// The count buffer of a `multi_draw_indirect_count` call.
+///
+/// Draws added with a [`DrawCount`] are zeroed out by the validation pass
+/// if their index is not less than the value in the count buffer,
+/// which is how we emulate indirect count draws.
+pub(crate) struct DrawCount {
+    buffer: Arc<crate::resource::Buffer>,
+    offset: u64,
+    call_index: u32,
+}
+
 /// Accumulates all needed data needed to validate indirect draws.
 pub(crate) struct DrawBatcher {
-    batches: FastHashMap<(TrackerIndex, u64, usize), DrawIndirectValidationBatch>,
+    batches: FastHashMap<(TrackerIndex, u64, usize, Option<u32>), DrawIndirectValidationBatch>,
     current_dst_entry: Option<CurrentEntry>,
+    count_calls: u32,
 }
 
 impl DrawBatcher {
@@ -904,11 +975,31 @@ impl DrawBatcher {
         Self {
             batches: FastHashMap::default(),
             current_dst_entry: None,
+            count_calls: 0,
+        }
+    }
+
+    /// Start a `multi_draw_indirect_count` call whose draws will be limited
+    /// by the u32 at `offset` in `buffer`.
+    pub(crate) fn begin_count(
+        &mut self,
+        buffer: &Arc<crate::resource::Buffer>,
+        offset: u64,
+    ) -> DrawCount {
+        let call_index = self.count_calls;
+        self.count_calls += 1;
+        DrawCount {
+            buffer: buffer.clone(),
+            offset,
+            call_index,
         }
     }
 
     /// Add an indirect draw to be validated.
     ///
+    /// If `count` is given, `draw_index` is the index of the draw within the
+    /// `multi_draw_indirect_count` call. Draws of the same call must be added in order.
+    ///
     /// Returns the index of the indirect buffer in `indirect_draw_validation_resources`
     /// and the offset to be used for the draw.
     pub(crate) fn add<'a>(
@@ -920,6 +1011,7 @@ impl DrawBatcher {
         indexed: bool,
         vertex_or_index_limit: u64,
         instance_limit: u64,
+        count: Option<(&DrawCount, u32)>,
     ) -> Result<(usize, u64), DeviceError> {
         // space for D3D12 special constants
         let extra = if device.backend() == wgt::Backend::Dx12 {
@@ -950,15 +1042,27 @@ impl DrawBatcher {
             src_buffer_tracker_index,
             src_dynamic_offset,
             dst_resource_index,
+            count.map(|(count, _)| count.call_index),
         )) {
             hashbrown::hash_map::Entry::Occupied(mut occupied_entry) => {
                 occupied_entry.get_mut().entries.push(entry)
             }
             hashbrown::hash_map::Entry::Vacant(vacant_entry) => {
+                let count = count.map(|(count, draw_index)| {
+                    let (dynamic_offset, offset) =
+                        calculate_src_offsets(count.buffer.size, &limits, count.offset);
+                    BatchCount {
+                        buffer: count.buffer.clone(),
+                        dynamic_offset,
+                        offset: offset as u32 / 4, // translate byte offset to offset in u32's
+                        first_draw_index: draw_index,
+                    }
+                });
                 vacant_entry.insert(DrawIndirectValidationBatch {
                     src_buffer: src_buffer.clone(),
                     src_dynamic_offset,
                     dst_resource_index,
+                    count,
                     entries: vec![entry],
 
                     // these will be initialized once we accumulated all entries for the batch
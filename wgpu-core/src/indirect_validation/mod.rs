@@ -10,7 +10,7 @@ mod draw;
 mod utils;
 
 pub(crate) use dispatch::Dispatch;
-pub(crate) use draw::{Draw, DrawBatcher, DrawResources};
+pub(crate) use draw::{Draw, DrawBatcher, DrawCount, DrawResources};
 
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
@@ -13,11 +13,16 @@ struct MetadataEntry {
     instance_limit: u32,
 }
 
-struct MetadataRange {
-    start: u32,
-    count: u32,
+struct Params {
+    metadata_start: u32,
+    metadata_count: u32,
+    // bits 0..30 are an offset into `count`
+    // bit 31 signifies that the draws are limited by `count`
+    count_offset: u32,
+    // index of the first draw within its `multi_draw_indirect_count` call
+    first_draw_index: u32,
 }
-var<push_constant> metadata_range: MetadataRange;
+var<push_constant> params: Params;
 
 @group(0) @binding(0)
 var<storage, read> metadata: array<MetadataEntry>;
@@ -25,6 +30,8 @@ var<storage, read> metadata: array<MetadataEntry>;
 var<storage, read> src: array<u32>;
 @group(2) @binding(0)
 var<storage, read_write> dst: array<u32>;
+@group(3) @binding(0)
+var<storage, read> count: array<u32>;
 
 fn is_bit_set(data: u32, index: u32) -> bool {
     return ((data >> index) & 1u) == 1u;
@@ -32,11 +39,18 @@ fn is_bit_set(data: u32, index: u32) -> bool {
 
 @compute @workgroup_size(64)
 fn main(@builtin(global_invocation_id) global_invocation_id: vec3u) {
-    if global_invocation_id.x >= metadata_range.count { return; }
+    if global_invocation_id.x >= params.metadata_count { return; }
 
-    let metadata = metadata[metadata_range.start + global_invocation_id.x];
+    let metadata = metadata[params.metadata_start + global_invocation_id.x];
     var failed = false;
 
+    // Draws past the count are emulated by zeroing them out.
+    if is_bit_set(params.count_offset, 31) {
+        let count_offset = ((params.count_offset << 1) >> 1);
+        let draw_index = params.first_draw_index + global_invocation_id.x;
+        failed |= draw_index >= count[count_offset];
+    }
+
     let is_indexed = is_bit_set(metadata.src_offset, 31);
     let src_base_offset = ((metadata.src_offset << 2) >> 2);
     let dst_base_offset = ((metadata.dst_offset << 2) >> 2);
@@ -247,6 +247,27 @@ where
             next_index: index,
         }
     }
+
+    // Makes a range uninitialized, merging it with the uninitialized ranges it touches.
+    pub(crate) fn discard_range(&mut self, range: Range<Idx>) {
+        if range.start >= range.end {
+            return;
+        }
+        // Ranges from `first` up to `last` overlap or are adjacent to `range`.
+        let first = self
+            .uninitialized_ranges
+            .partition_point(|r| r.end < range.start);
+        let last = self
+            .uninitialized_ranges
+            .partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.uninitialized_ranges[first].start);
+            merged.end = merged.end.max(self.uninitialized_ranges[last - 1].end);
+            self.uninitialized_ranges.drain(first..last);
+        }
+        self.uninitialized_ranges.insert(first, merged);
+    }
 }
 
 impl InitTracker<u32> {
@@ -427,4 +448,21 @@ mod test {
         assert_eq!(tracker.uninitialized_ranges.len(), 1);
         assert_eq!(tracker.uninitialized_ranges[0], 0..10);
     }
+
+    #[test]
+    fn discard_range_merges_touching_ranges() {
+        let mut tracker = Tracker::new(20);
+        tracker.drain(0..20);
+        tracker.discard_range(2..4);
+        tracker.discard_range(8..10);
+        tracker.discard_range(14..16);
+        tracker.discard_range(4..9);
+        assert_eq!(tracker.uninitialized_ranges.as_slice(), &[2..10, 14..16]);
+        tracker.discard_range(0..1);
+        tracker.discard_range(12..18);
+        assert_eq!(
+            tracker.uninitialized_ranges.as_slice(),
+            &[0..1, 2..10, 12..18]
+        );
+    }
 }
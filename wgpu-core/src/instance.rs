@@ -13,12 +13,16 @@ use wgt::error::{ErrorType, WebGpuError};
 
 use crate::{
     DOWNLEVEL_WARNING_MESSAGE, api_log, api_log_debug,
-    device::{DeviceDescriptor, DeviceError, queue::Queue, resource::Device},
+    device::{
+        DeviceDescriptor, DeviceError, MissingFeatures, WaitIdleError,
+        queue::{Queue, QueueSubmitError},
+        resource::Device,
+    },
     global::Global,
     hal_api::HalApi,
     id::{AdapterId, DeviceId, QueueId, SurfaceId, markers},
     lock::{Mutex, rank},
-    present::Presentation,
+    present::{HeadlessSurface, Presentation},
     resource::ResourceType,
     resource_log,
     timestamp_normalization::TimestampNormalizerInitError,
@@ -57,6 +61,27 @@ fn check_limits(requested: &wgt::Limits, allowed: &wgt::Limits) -> Vec<FailedLim
     failed
 }
 
+/// The highest limits a device may require under
+/// [`wgt::InstanceFlags::STRICT_WEBGPU_PORTABILITY`]: limits which are not part of WebGPU
+/// may not exceed their defaults, the others are only limited by the adapter.
+fn webgpu_portable_limits(allowed: &wgt::Limits) -> wgt::Limits {
+    let defaults = wgt::Limits::default();
+    wgt::Limits {
+        max_binding_array_elements_per_shader_stage: defaults
+            .max_binding_array_elements_per_shader_stage,
+        max_binding_array_sampler_elements_per_shader_stage: defaults
+            .max_binding_array_sampler_elements_per_shader_stage,
+        max_push_constant_size: defaults.max_push_constant_size,
+        max_blas_primitive_count: defaults.max_blas_primitive_count,
+        max_blas_geometry_count: defaults.max_blas_geometry_count,
+        max_tlas_instance_count: defaults.max_tlas_instance_count,
+        max_acceleration_structures_per_shader_stage: defaults
+            .max_acceleration_structures_per_shader_stage,
+        max_ray_recursion_depth: defaults.max_ray_recursion_depth,
+        ..allowed.clone()
+    }
+}
+
 #[test]
 fn downlevel_default_limits_less_than_default_limits() {
     let res = check_limits(&wgt::Limits::downlevel_defaults(), &wgt::Limits::default());
@@ -111,6 +136,8 @@ impl Instance {
         this.try_add_hal(hal::api::Gles, instance_desc);
         #[cfg(feature = "noop")]
         this.try_add_hal(hal::api::Noop, instance_desc);
+        #[cfg(feature = "cpu")]
+        this.try_add_hal(hal::api::Cpu, instance_desc);
 
         this
     }
@@ -131,6 +158,7 @@ impl Instance {
             flags: self.flags,
             memory_budget_thresholds: instance_desc.memory_budget_thresholds,
             backend_options: instance_desc.backend_options.clone(),
+            toggles: instance_desc.toggles,
         };
 
         use hal::Instance as _;
@@ -237,6 +265,7 @@ impl Instance {
             let surface = Surface {
                 presentation: Mutex::new(rank::SURFACE_PRESENTATION, None),
                 surface_per_backend,
+                headless: None,
             };
 
             Ok(surface)
@@ -303,6 +332,93 @@ impl Instance {
             let surface = Surface {
                 presentation: Mutex::new(rank::SURFACE_PRESENTATION, None),
                 surface_per_backend,
+                headless: None,
+            };
+
+            Ok(surface)
+        }
+    }
+
+    /// Enumerates the physical displays that can be presented to directly,
+    /// without a windowing system.
+    ///
+    /// # Platform Support
+    ///
+    /// This function is only available on non-apple Unix-like platforms (Linux, FreeBSD) and
+    /// currently only works with the Vulkan backend. It returns an empty list otherwise.
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    pub fn enumerate_displays(&self) -> Vec<Display> {
+        profiling::scope!("Instance::enumerate_displays");
+        api_log!("Instance::enumerate_displays");
+
+        #[cfg_attr(not(vulkan), expect(unused_mut))]
+        let mut displays = Vec::new();
+
+        #[cfg(vulkan)]
+        if let Some(instance) = unsafe { self.as_hal::<hal::api::Vulkan>() } {
+            displays.extend(
+                instance
+                    .enumerate_displays()
+                    .into_iter()
+                    .map(|raw| Display {
+                        info: raw.info().clone(),
+                        raw,
+                    }),
+            );
+        }
+
+        displays
+    }
+
+    /// Creates a new surface presenting directly to `display`, driving it with `mode`.
+    ///
+    /// # Safety
+    ///
+    /// - `display` must have been returned by [`Self::enumerate_displays`] on this instance.
+    /// - The display must not be in use by a windowing system or another surface.
+    ///
+    /// # Platform Support
+    ///
+    /// This function is only available on non-apple Unix-like platforms (Linux, FreeBSD) and
+    /// currently only works with the Vulkan backend.
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    #[cfg_attr(not(vulkan), expect(unused_variables))]
+    pub unsafe fn create_surface_from_display(
+        &self,
+        display: &Display,
+        mode: &wgt::DisplayMode,
+    ) -> Result<Surface, CreateSurfaceError> {
+        profiling::scope!("Instance::create_surface_from_display");
+
+        let mut errors = HashMap::default();
+        let mut surface_per_backend: HashMap<Backend, Box<dyn hal::DynSurface>> =
+            HashMap::default();
+
+        #[cfg(vulkan)]
+        {
+            let instance = unsafe { self.as_hal::<hal::api::Vulkan>() }
+                .ok_or(CreateSurfaceError::BackendNotEnabled(Backend::Vulkan))?;
+
+            // Safety must be upheld by the caller
+            match unsafe { instance.create_surface_from_display(&display.raw, mode) } {
+                Ok(surface) => {
+                    surface_per_backend.insert(Backend::Vulkan, Box::new(surface));
+                }
+                Err(err) => {
+                    errors.insert(Backend::Vulkan, err);
+                }
+            }
+        }
+
+        if surface_per_backend.is_empty() {
+            Err(CreateSurfaceError::FailedToCreateSurfaceForAnyBackend(
+                errors,
+            ))
+        } else {
+            let surface = Surface {
+                presentation: Mutex::new(rank::SURFACE_PRESENTATION, None),
+                surface_per_backend,
+                headless: None,
             };
 
             Ok(surface)
@@ -341,6 +457,7 @@ impl Instance {
         let surface = Surface {
             presentation: Mutex::new(rank::SURFACE_PRESENTATION, None),
             surface_per_backend: core::iter::once((Backend::Metal, raw_surface)).collect(),
+            headless: None,
         };
 
         Ok(surface)
@@ -358,6 +475,7 @@ impl Instance {
         let surface = Surface {
             presentation: Mutex::new(rank::SURFACE_PRESENTATION, None),
             surface_per_backend: core::iter::once((Backend::Dx12, surface)).collect(),
+            headless: None,
         };
 
         Ok(surface)
@@ -403,6 +521,27 @@ impl Instance {
         })
     }
 
+    /// Creates a surface that isn't connected to any window, and presents to
+    /// offscreen textures instead.
+    ///
+    /// `width` and `height` are the size of the surface, as reported by its
+    /// capabilities, and `format` is the only format it supports. Headless
+    /// surfaces are compatible with all adapters.
+    pub fn create_headless_surface(
+        &self,
+        width: u32,
+        height: u32,
+        format: wgt::TextureFormat,
+    ) -> Surface {
+        profiling::scope!("Instance::create_headless_surface");
+
+        Surface {
+            presentation: Mutex::new(rank::SURFACE_PRESENTATION, None),
+            surface_per_backend: HashMap::default(),
+            headless: Some(HeadlessSurface::new(width, height, format)),
+        }
+    }
+
     pub fn enumerate_adapters(&self, backends: Backends) -> Vec<Adapter> {
         profiling::scope!("Instance::enumerate_adapters");
         api_log!("Instance::enumerate_adapters");
@@ -569,9 +708,30 @@ impl Instance {
     }
 }
 
+/// A physical display that can be presented to directly.
+///
+/// Returned by [`Instance::enumerate_displays`].
+#[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+#[derive(Debug)]
+pub struct Display {
+    info: wgt::DisplayInfo,
+    #[cfg(vulkan)]
+    raw: hal::vulkan::Display,
+}
+
+#[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+impl Display {
+    pub fn info(&self) -> &wgt::DisplayInfo {
+        &self.info
+    }
+}
+
 pub struct Surface {
     pub(crate) presentation: Mutex<Option<Presentation>>,
     pub surface_per_backend: HashMap<Backend, Box<dyn hal::DynSurface>>,
+    /// Set for surfaces created with [`Instance::create_headless_surface`],
+    /// which have no hal surface and present to offscreen textures.
+    pub(crate) headless: Option<HeadlessSurface>,
 }
 
 impl ResourceType for Surface {
@@ -593,6 +753,10 @@ impl Surface {
         &self,
         adapter: &hal::DynExposedAdapter,
     ) -> Result<hal::SurfaceCapabilities, GetSurfaceSupportError> {
+        if let Some(ref headless) = self.headless {
+            return Ok(headless.capabilities());
+        }
+
         let backend = adapter.backend();
         let suf = self
             .raw(backend)
@@ -624,6 +788,8 @@ impl Drop for Surface {
 
 pub struct Adapter {
     pub(crate) raw: hal::DynExposedAdapter,
+    /// Features exposed by wgpu-core on top of the ones the backend supports natively.
+    pub(crate) emulated_features: wgt::Features,
 }
 
 impl Adapter {
@@ -640,7 +806,35 @@ impl Adapter {
             .min_storage_buffer_offset_alignment
             .max(MIN_BUFFER_OFFSET_ALIGNMENT_LOWER_BOUND);
 
-        Self { raw }
+        // `MULTI_DRAW_INDIRECT_COUNT` is emulated by the indirect validation pass,
+        // which zeroes out the draws past the count.
+        let mut emulated_features = wgt::Features::empty();
+        if cfg!(feature = "wgsl")
+            && raw.capabilities.downlevel.flags.contains(
+                wgt::DownlevelFlags::INDIRECT_EXECUTION | wgt::DownlevelFlags::COMPUTE_SHADERS,
+            )
+        {
+            emulated_features |= wgt::Features::MULTI_DRAW_INDIRECT_COUNT - raw.features;
+        }
+        // `INDEX_FORMAT_UINT8` is emulated by widening the indices to 16 bit before
+        // each render pass.
+        if cfg!(feature = "wgsl")
+            && raw
+                .capabilities
+                .downlevel
+                .flags
+                .contains(wgt::DownlevelFlags::COMPUTE_SHADERS)
+        {
+            emulated_features |= wgt::Features::INDEX_FORMAT_UINT8 - raw.features;
+        }
+        // `WIDE_LINES` is emulated by drawing each segment of a line as a quad.
+        emulated_features |= wgt::Features::WIDE_LINES - raw.features;
+        raw.features |= emulated_features;
+
+        Self {
+            raw,
+            emulated_features,
+        }
     }
 
     /// Returns the backend this adapter is using.
@@ -664,6 +858,10 @@ impl Adapter {
         self.raw.features
     }
 
+    pub fn applied_workarounds(&self) -> wgt::Toggles {
+        self.raw.toggles
+    }
+
     pub fn limits(&self) -> wgt::Limits {
         self.raw.capabilities.limits.clone()
     }
@@ -676,6 +874,10 @@ impl Adapter {
         unsafe { self.raw.adapter.get_presentation_timestamp() }
     }
 
+    pub fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        self.raw.adapter.memory_info()
+    }
+
     pub fn get_texture_format_features(
         &self,
         format: wgt::TextureFormat,
@@ -773,6 +975,22 @@ impl Adapter {
         desc: &DeviceDescriptor,
         instance_flags: wgt::InstanceFlags,
     ) -> Result<(Arc<Device>, Arc<Queue>), RequestDeviceError> {
+        // Additional queues of `hal_device` aren't exposed.
+        let (device, queue, _) =
+            self.create_device_and_queues_from_hal(hal_device, desc, &[], instance_flags)?;
+        Ok((device, queue))
+    }
+
+    /// Create a device, its main queue, and one additional queue of each of
+    /// the types in `additional_queues` from the queues of `hal_device`.
+    #[allow(clippy::type_complexity)]
+    fn create_device_and_queues_from_hal(
+        self: &Arc<Self>,
+        hal_device: hal::DynOpenDevice,
+        desc: &DeviceDescriptor,
+        additional_queues: &[wgt::QueueType],
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<(Arc<Device>, Arc<Queue>, Vec<Arc<Queue>>), RequestDeviceError> {
         api_log!("Adapter::create_device");
 
         let device = Device::new(hal_device.device, self, desc, instance_flags)?;
@@ -784,7 +1002,32 @@ impl Adapter {
         device.set_queue(&queue);
         device.late_init_resources_with_queue()?;
 
-        Ok((device, queue))
+        let additional_queues = hal_device
+            .additional_queues
+            .into_iter()
+            .zip(additional_queues)
+            .map(|(raw, &queue_type)| Queue::new_additional(&queue, raw, queue_type).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !additional_queues.is_empty() {
+            // The main queue's first submission initializes the zero buffer,
+            // which the additional queues must wait on before using it.
+            let submission_index = match queue.submit(&[]) {
+                Ok(submission_index) => submission_index,
+                Err((_, QueueSubmitError::Queue(e))) => return Err(e.into()),
+                Err((_, e)) => unreachable!("Unexpected error on an empty submission: {e}"),
+            };
+            for additional_queue in &additional_queues {
+                additional_queue
+                    .wait_for_submission(submission_index)
+                    .map_err(|e| match e {
+                        WaitIdleError::Device(e) => e,
+                        e => unreachable!("Unexpected error waiting for a submission: {e}"),
+                    })?;
+            }
+        }
+
+        Ok((device, queue, additional_queues))
     }
 
     pub fn create_device_and_queue(
@@ -792,6 +1035,21 @@ impl Adapter {
         desc: &DeviceDescriptor,
         instance_flags: wgt::InstanceFlags,
     ) -> Result<(Arc<Device>, Arc<Queue>), RequestDeviceError> {
+        let (device, queue, _) = self.create_device_and_queues(desc, &[], instance_flags)?;
+        Ok((device, queue))
+    }
+
+    /// Like [`Self::create_device_and_queue`], but also creates one additional
+    /// queue of each of the types in `additional_queues`.
+    ///
+    /// Additional queues require [`wgt::Features::EXPERIMENTAL_MULTI_QUEUE`].
+    #[allow(clippy::type_complexity)]
+    pub fn create_device_and_queues(
+        self: &Arc<Self>,
+        desc: &DeviceDescriptor,
+        additional_queues: &[wgt::QueueType],
+        instance_flags: wgt::InstanceFlags,
+    ) -> Result<(Arc<Device>, Arc<Queue>, Vec<Arc<Queue>>), RequestDeviceError> {
         // Verify all features were exposed by the adapter
         if !self.raw.features.contains(desc.required_features) {
             return Err(RequestDeviceError::UnsupportedFeature(
@@ -799,6 +1057,27 @@ impl Adapter {
             ));
         }
 
+        if !additional_queues.is_empty()
+            && !desc
+                .required_features
+                .contains(wgt::Features::EXPERIMENTAL_MULTI_QUEUE)
+        {
+            return Err(MissingFeatures(wgt::Features::EXPERIMENTAL_MULTI_QUEUE).into());
+        }
+
+        let adapter_toggles =
+            (desc.toggles.force_enabled | desc.toggles.force_disabled) - wgt::Toggles::DEVICE;
+        if !adapter_toggles.is_empty() {
+            return Err(RequestDeviceError::AdapterToggles(adapter_toggles));
+        }
+
+        let strict_portability =
+            instance_flags.contains(wgt::InstanceFlags::STRICT_WEBGPU_PORTABILITY);
+        let native_only_features = desc.required_features - wgt::Features::all_webgpu_mask();
+        if strict_portability && !native_only_features.is_empty() {
+            return Err(RequestDeviceError::NonPortableFeature(native_only_features));
+        }
+
         let caps = &self.raw.capabilities;
         if Backends::PRIMARY.contains(Backends::from(self.backend()))
             && !caps.downlevel.is_webgpu_compliant()
@@ -827,17 +1106,24 @@ impl Adapter {
         if let Some(failed) = check_limits(&desc.required_limits, &caps.limits).pop() {
             return Err(RequestDeviceError::LimitsExceeded(failed));
         }
+        if strict_portability {
+            let portable_limits = webgpu_portable_limits(&caps.limits);
+            if let Some(failed) = check_limits(&desc.required_limits, &portable_limits).pop() {
+                return Err(RequestDeviceError::NonPortableLimit(failed));
+            }
+        }
 
         let open = unsafe {
             self.raw.adapter.open(
-                desc.required_features,
+                desc.required_features - self.emulated_features,
                 &desc.required_limits,
                 &desc.memory_hints,
+                additional_queues,
             )
         }
         .map_err(DeviceError::from_hal)?;
 
-        self.create_device_and_queue_from_hal(open, desc, instance_flags)
+        self.create_device_and_queues_from_hal(open, desc, additional_queues, instance_flags)
     }
 }
 
@@ -865,6 +1151,16 @@ pub enum RequestDeviceError {
     TimestampNormalizerInitFailed(#[from] TimestampNormalizerInitError),
     #[error("Unsupported features were requested: {0:?}")]
     UnsupportedFeature(wgt::Features),
+    #[error(
+        "Features which are not part of WebGPU were requested in strict WebGPU portability mode: {0:?}"
+    )]
+    NonPortableFeature(wgt::Features),
+    #[error("A native-only limit was requested in strict WebGPU portability mode: {0}")]
+    NonPortableLimit(FailedLimit),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Adapter toggles can only be set on the instance, not on a device: {0:?}")]
+    AdapterToggles(wgt::Toggles),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -942,6 +1238,40 @@ impl Global {
         Ok(id)
     }
 
+    /// Enumerates the physical displays that can be presented to directly,
+    /// without a windowing system.
+    ///
+    /// # Platform Support
+    ///
+    /// This function is only available on non-apple Unix-like platforms (Linux, FreeBSD) and
+    /// currently only works with the Vulkan backend.
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    pub fn instance_enumerate_displays(&self) -> Vec<Display> {
+        self.instance.enumerate_displays()
+    }
+
+    /// # Safety
+    ///
+    /// - `display` must have been returned by [`Self::instance_enumerate_displays`].
+    /// - The display must not be in use by a windowing system or another surface.
+    ///
+    /// # Platform Support
+    ///
+    /// This function is only available on non-apple Unix-like platforms (Linux, FreeBSD) and
+    /// currently only works with the Vulkan backend.
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    pub unsafe fn instance_create_surface_from_display(
+        &self,
+        display: &Display,
+        mode: &wgt::DisplayMode,
+        id_in: Option<SurfaceId>,
+    ) -> Result<SurfaceId, CreateSurfaceError> {
+        let surface = unsafe { self.instance.create_surface_from_display(display, mode) }?;
+        let id = self.surfaces.prepare(id_in).assign(Arc::new(surface));
+
+        Ok(id)
+    }
+
     /// # Safety
     ///
     /// `layer` must be a valid pointer.
@@ -1012,6 +1342,17 @@ impl Global {
         self.surfaces.remove(id);
     }
 
+    pub fn instance_create_headless_surface(
+        &self,
+        width: u32,
+        height: u32,
+        format: wgt::TextureFormat,
+        id_in: Option<SurfaceId>,
+    ) -> SurfaceId {
+        let surface = self.instance.create_headless_surface(width, height, format);
+        self.surfaces.prepare(id_in).assign(Arc::new(surface))
+    }
+
     pub fn enumerate_adapters(&self, backends: Backends) -> Vec<AdapterId> {
         let adapters = self.instance.enumerate_adapters(backends);
         adapters
@@ -1073,6 +1414,11 @@ impl Global {
         adapter.features()
     }
 
+    pub fn adapter_applied_workarounds(&self, adapter_id: AdapterId) -> wgt::Toggles {
+        let adapter = self.hub.adapters.get(adapter_id);
+        adapter.applied_workarounds()
+    }
+
     pub fn adapter_limits(&self, adapter_id: AdapterId) -> wgt::Limits {
         let adapter = self.hub.adapters.get(adapter_id);
         adapter.limits()
@@ -1094,6 +1440,11 @@ impl Global {
         adapter.get_presentation_timestamp()
     }
 
+    pub fn adapter_memory_info(&self, adapter_id: AdapterId) -> Option<wgt::MemoryInfo> {
+        let adapter = self.hub.adapters.get(adapter_id);
+        adapter.memory_info()
+    }
+
     pub fn adapter_drop(&self, adapter_id: AdapterId) {
         profiling::scope!("Adapter::drop");
         api_log!("Adapter::drop {adapter_id:?}");
@@ -1110,6 +1461,33 @@ impl Global {
         device_id_in: Option<DeviceId>,
         queue_id_in: Option<QueueId>,
     ) -> Result<(DeviceId, QueueId), RequestDeviceError> {
+        let (device_id, queue_id, _) = self.adapter_request_device_with_queues(
+            adapter_id,
+            desc,
+            &[],
+            device_id_in,
+            queue_id_in,
+            None,
+        )?;
+        Ok((device_id, queue_id))
+    }
+
+    /// Like [`Self::adapter_request_device`], but also creates one additional
+    /// queue of each of the types in `additional_queues`, in order.
+    ///
+    /// Additional queues require [`wgt::Features::EXPERIMENTAL_MULTI_QUEUE`].
+    ///
+    /// `additional_queue_ids_in`, if `Some`, holds the ids to assign to the
+    /// additional queues, and must be as long as `additional_queues`.
+    pub fn adapter_request_device_with_queues(
+        &self,
+        adapter_id: AdapterId,
+        desc: &DeviceDescriptor,
+        additional_queues: &[wgt::QueueType],
+        device_id_in: Option<DeviceId>,
+        queue_id_in: Option<QueueId>,
+        additional_queue_ids_in: Option<&[QueueId]>,
+    ) -> Result<(DeviceId, QueueId, Vec<QueueId>), RequestDeviceError> {
         profiling::scope!("Adapter::request_device");
         api_log!("Adapter::request_device");
 
@@ -1117,7 +1495,8 @@ impl Global {
         let queue_fid = self.hub.queues.prepare(queue_id_in);
 
         let adapter = self.hub.adapters.get(adapter_id);
-        let (device, queue) = adapter.create_device_and_queue(desc, self.instance.flags)?;
+        let (device, queue, additional_queues) =
+            adapter.create_device_and_queues(desc, additional_queues, self.instance.flags)?;
 
         let device_id = device_fid.assign(device);
         resource_log!("Created Device {:?}", device_id);
@@ -1125,7 +1504,18 @@ impl Global {
         let queue_id = queue_fid.assign(queue);
         resource_log!("Created Queue {:?}", queue_id);
 
-        Ok((device_id, queue_id))
+        let additional_queue_ids = additional_queues
+            .into_iter()
+            .enumerate()
+            .map(|(index, queue)| {
+                let id_in = additional_queue_ids_in.map(|ids| ids[index]);
+                let queue_id = self.hub.queues.prepare(id_in).assign(queue);
+                resource_log!("Created Queue {:?}", queue_id);
+                queue_id
+            })
+            .collect();
+
+        Ok((device_id, queue_id, additional_queue_ids))
     }
 
     /// # Safety
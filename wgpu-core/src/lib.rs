@@ -77,6 +77,7 @@ mod hash_utils;
 pub mod hub;
 pub mod id;
 pub mod identity;
+mod index_expansion;
 mod indirect_validation;
 mod init_tracker;
 pub mod instance;
@@ -93,6 +94,7 @@ pub mod storage;
 mod timestamp_normalization;
 mod track;
 mod weak_vec;
+mod wide_lines;
 // This is public for users who pre-compile shaders while still wanting to
 // preserve all run-time checks that `wgpu-core` does.
 // See <https://github.com/gfx-rs/wgpu/issues/3103>, after which this can be
@@ -102,11 +102,15 @@ define_lock_ranks! {
         DEVICE_USAGE_SCOPES,
         SHARED_TRACKER_INDEX_ALLOCATOR_INNER,
         BUFFER_MAP_STATE,
+        DEVICE_BARRIER_REPORT,
+        PARALLEL_RENDER_ENCODER_PART,
     }
     rank DEVICE_SNATCHABLE_LOCK "Device::snatchable_lock" followed by {
         SHARED_TRACKER_INDEX_ALLOCATOR_INNER,
         DEVICE_TRACE,
         BUFFER_MAP_STATE,
+        DEVICE_BARRIER_REPORT,
+        PARALLEL_RENDER_ENCODER_PART,
         // Uncomment this to see an interesting cycle.
         // COMMAND_BUFFER_DATA,
     }
@@ -130,28 +134,45 @@ define_lock_ranks! {
 
     rank BUFFER_BIND_GROUPS "Buffer::bind_groups" followed by { }
     rank BUFFER_INITIALIZATION_STATUS "Buffer::initialization_status" followed by { }
+    rank BUFFER_SPARSE_HEAPS "Buffer::sparse_heaps" followed by { }
     rank DEVICE_COMMAND_INDICES "Device::command_indices" followed by {}
     rank DEVICE_DEFERRED_DESTROY "Device::deferred_destroy" followed by {}
-    rank DEVICE_FENCE "Device::fence" followed by { }
+    rank DEVICE_FENCE "Device::fence" followed by {
+        DEVICE_ADDITIONAL_QUEUE_FENCES,
+    }
+    rank DEVICE_ADDITIONAL_QUEUE_FENCES "Device::additional_queue_fences" followed by { }
     #[allow(dead_code)]
     rank DEVICE_TRACE "Device::trace" followed by { }
-    rank DEVICE_TRACKERS "Device::trackers" followed by { }
+    rank DEVICE_TRACKERS "Device::trackers" followed by {
+        DEVICE_BARRIER_REPORT,
+    }
+    rank DEVICE_BARRIER_REPORT "Device::barrier_report" followed by { }
     rank DEVICE_LOST_CLOSURE "Device::device_lost_closure" followed by { }
+    rank DEVICE_MEMORY_BUDGET_CALLBACK "Device::memory_budget_callback" followed by { }
+    rank DEVICE_GPU_VALIDATION_CALLBACK "Device::gpu_validation_callback" followed by { }
+    rank GLOBAL_GRAPHICS_DEBUGGER_CAPTURE "Global::graphics_debugger_capture" followed by { }
     rank DEVICE_USAGE_SCOPES "Device::usage_scopes" followed by { }
     rank IDENTITY_MANAGER_VALUES "IdentityManager::values" followed by { }
     rank REGISTRY_STORAGE "Registry::storage" followed by { }
     rank RESOURCE_POOL_INNER "ResourcePool::inner" followed by { }
     rank SHARED_TRACKER_INDEX_ALLOCATOR_INNER "SharedTrackerIndexAllocator::inner" followed by { }
-    rank SURFACE_PRESENTATION "Surface::presentation" followed by { }
+    rank SURFACE_PRESENTATION "Surface::presentation" followed by {
+        HEADLESS_SURFACE_PRESENTED_FRAMES,
+    }
+    rank HEADLESS_SURFACE_PRESENTED_FRAMES "HeadlessSurface::presented_frames" followed by { }
     rank TEXTURE_BIND_GROUPS "Texture::bind_groups" followed by { }
     rank TEXTURE_INITIALIZATION_STATUS "Texture::initialization_status" followed by { }
     rank TEXTURE_CLEAR_MODE "Texture::clear_mode" followed by { }
     rank TEXTURE_VIEWS "Texture::views" followed by { }
+    rank TEXTURE_SPARSE_HEAPS "Texture::sparse_heaps" followed by { }
     rank BLAS_BUILT_INDEX "Blas::built_index" followed by { }
+    rank BLAS_BUILT_COUNTS "Blas::built_counts" followed by { }
     rank BLAS_COMPACTION_STATE "Blas::compaction_size" followed by { }
     rank TLAS_BUILT_INDEX "Tlas::built_index" followed by { }
+    rank TLAS_BUILT_INSTANCE_COUNT "Tlas::built_instance_count" followed by { }
     rank TLAS_DEPENDENCIES "Tlas::dependencies" followed by { }
     rank BUFFER_POOL "BufferPool::buffers" followed by { }
+    rank PARALLEL_RENDER_ENCODER_PART "ParallelEncoder::part" followed by { }
 
     #[cfg(test)]
     rank PAWN "pawn" followed by { ROOK, BISHOP }
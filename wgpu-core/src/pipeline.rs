@@ -1,5 +1,5 @@
 use alloc::{
-    borrow::Cow,
+    borrow::{Cow, ToOwned},
     boxed::Box,
     string::{String, ToString as _},
     sync::Arc,
@@ -16,7 +16,7 @@ pub use crate::pipeline_cache::PipelineCacheValidationError;
 use crate::{
     Label,
     binding_model::{CreateBindGroupLayoutError, CreatePipelineLayoutError, PipelineLayout},
-    command::ColorAttachmentError,
+    command::{ColorAttachmentError, DrawError},
     device::{Device, DeviceError, MissingDownlevelFlags, MissingFeatures, RenderPassContext},
     id::{PipelineCacheId, PipelineLayoutId, ShaderModuleId},
     resource::{InvalidResourceError, Labeled, TrackingData},
@@ -35,7 +35,10 @@ pub(crate) struct LateSizedBufferGroup {
 #[allow(clippy::large_enum_variant)]
 pub enum ShaderModuleSource<'a> {
     #[cfg(feature = "wgsl")]
-    Wgsl(Cow<'a, str>),
+    Wgsl(Cow<'a, str>, naga::front::wgsl::Options),
+    /// Several named WGSL files, compiled as one module.
+    #[cfg(feature = "wgsl")]
+    WgslFiles(naga::front::wgsl::Sources, naga::front::wgsl::Options),
     #[cfg(feature = "glsl")]
     Glsl(Cow<'a, str>, naga::front::glsl::Options),
     #[cfg(feature = "spirv")]
@@ -53,6 +56,8 @@ pub struct ShaderModuleDescriptor<'a> {
     pub label: Label<'a>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub runtime_checks: wgt::ShaderRuntimeChecks,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub optimization: wgt::ShaderOptimization,
 }
 
 pub type ShaderModuleDescriptorPassthrough<'a> =
@@ -63,6 +68,14 @@ pub struct ShaderModule {
     pub(crate) raw: ManuallyDrop<Box<dyn hal::DynShaderModule>>,
     pub(crate) device: Arc<Device>,
     pub(crate) interface: Option<validation::Interface>,
+    /// Whether pipelines using this module may zero-initialize workgroup memory.
+    ///
+    /// This is `false` when the module was created with
+    /// [`wgt::ShaderRuntimeChecks::zero_initialize_workgroup_memory`] disabled,
+    /// and overrides the per-pipeline option.
+    pub(crate) zero_initialize_workgroup_memory: bool,
+    /// The IR of the module, kept when the device emulates wide lines.
+    pub(crate) wide_lines_source: Option<crate::wide_lines::ShaderSource>,
     /// The `label` from the descriptor used to create the resource.
     pub(crate) label: String,
 }
@@ -307,6 +320,294 @@ impl ComputePipeline {
     }
 }
 
+/// Describes a shader stage of a ray tracing pipeline.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayTracingStageDescriptor<'a, SM = ShaderModuleId> {
+    /// Which ray tracing stage this is. Exactly one bit of
+    /// [`wgt::ShaderStages::RAY_TRACING`] must be set.
+    pub stage: wgt::ShaderStages,
+    /// The compiled shader module for this stage. Intersection and callable
+    /// stages must use passthrough modules.
+    pub module: SM,
+    /// The name of the entry point in the compiled shader.
+    pub entry_point: Cow<'a, str>,
+}
+
+/// Describes a ray tracing pipeline.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayTracingPipelineDescriptor<
+    'a,
+    PLL = PipelineLayoutId,
+    SM = ShaderModuleId,
+    PLC = PipelineCacheId,
+> where
+    [RayTracingStageDescriptor<'a, SM>]: ToOwned,
+    <[RayTracingStageDescriptor<'a, SM>] as ToOwned>::Owned: core::fmt::Debug,
+{
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    ///
+    /// Ray tracing stages can't be reflected, so the layout can't be derived.
+    pub layout: PLL,
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound(
+            deserialize = "<[RayTracingStageDescriptor<'a, SM>] as ToOwned>::Owned: serde::Deserialize<'de>"
+        ))
+    )]
+    /// The shader stages of the pipeline, indexed by [`Self::groups`].
+    pub stages: Cow<'a, [RayTracingStageDescriptor<'a, SM>]>,
+    /// The shader groups of the pipeline.
+    pub groups: Cow<'a, [wgt::RayTracingShaderGroup]>,
+    /// The maximum depth of `traceRay` calls from within the pipeline.
+    pub max_recursion_depth: u32,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<PLC>,
+}
+
+/// cbindgen:ignore
+pub type ResolvedRayTracingPipelineDescriptor<'a> =
+    RayTracingPipelineDescriptor<'a, Arc<PipelineLayout>, Arc<ShaderModule>, Arc<PipelineCache>>;
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateRayTracingPipelineError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(
+        "Stage {index} has stage flags {stage:?}, but exactly one ray tracing stage must be set"
+    )]
+    InvalidStage {
+        index: usize,
+        stage: wgt::ShaderStages,
+    },
+    #[error(
+        "Stage {index} is an intersection or callable stage, which requires a passthrough shader module"
+    )]
+    NotPassthrough { index: usize },
+    #[error("Error matching stage {index} against the pipeline layout")]
+    Stage {
+        index: usize,
+        #[source]
+        error: validation::StageError,
+    },
+    #[error("The pipeline has no ray generation stage")]
+    MissingRayGenerationStage,
+    #[error(
+        "Shader group {group} refers to stage {stage}, which is out of range or of the wrong kind"
+    )]
+    InvalidGroupStage { group: usize, stage: u32 },
+    #[error("Maximum recursion depth {given} exceeds the limit {limit}")]
+    TooDeepRecursion { given: u32, limit: u32 },
+    #[error("Internal error: {0}")]
+    Internal(String),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+}
+
+impl WebGpuError for CreateRayTracingPipelineError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::MissingFeatures(e) => e,
+            Self::InvalidResource(e) => e,
+            Self::Stage { error, .. } => error,
+            Self::Internal(_) => return ErrorType::Internal,
+            Self::InvalidStage { .. }
+            | Self::NotPassthrough { .. }
+            | Self::MissingRayGenerationStage
+            | Self::InvalidGroupStage { .. }
+            | Self::TooDeepRecursion { .. } => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+#[derive(Debug)]
+pub struct RayTracingPipeline {
+    pub(crate) raw: ManuallyDrop<Box<dyn hal::DynRayTracingPipeline>>,
+    pub(crate) layout: Arc<PipelineLayout>,
+    pub(crate) device: Arc<Device>,
+    pub(crate) _shader_modules: Vec<Arc<ShaderModule>>,
+    /// The stage of each entry in the descriptor's `stages`.
+    pub(crate) stages: Box<[wgt::ShaderStages]>,
+    pub(crate) groups: Box<[wgt::RayTracingShaderGroup]>,
+    /// The `label` from the descriptor used to create the resource.
+    pub(crate) label: String,
+    pub(crate) tracking_data: TrackingData,
+}
+
+impl Drop for RayTracingPipeline {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw {}", self.error_ident());
+        // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
+        let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
+        unsafe {
+            self.device.raw().destroy_ray_tracing_pipeline(raw);
+        }
+    }
+}
+
+crate::impl_resource_type!(RayTracingPipeline);
+crate::impl_labeled!(RayTracingPipeline);
+crate::impl_parent_device!(RayTracingPipeline);
+crate::impl_storage_item!(RayTracingPipeline);
+crate::impl_trackable!(RayTracingPipeline);
+
+impl RayTracingPipeline {
+    pub(crate) fn raw(&self) -> &dyn hal::DynRayTracingPipeline {
+        self.raw.as_ref()
+    }
+
+    /// The stages that push constants are written for while this pipeline is bound.
+    pub(crate) fn push_constant_stages(&self) -> wgt::ShaderStages {
+        self.layout
+            .push_constant_ranges
+            .iter()
+            .map(|pcr| pcr.stages & wgt::ShaderStages::RAY_TRACING)
+            .fold(wgt::ShaderStages::NONE, |acc, stages| acc | stages)
+    }
+
+    fn general_stage(&self, group: u32) -> Option<wgt::ShaderStages> {
+        match self.groups.get(group as usize)? {
+            &wgt::RayTracingShaderGroup::General { stage } => Some(self.stages[stage as usize]),
+            _ => None,
+        }
+    }
+
+    /// Gather the handles of the groups selected by `desc` into the contents of a
+    /// shader binding table, along with where each kind of record ended up.
+    pub(crate) fn shader_binding_table(
+        &self,
+        desc: &ShaderBindingTableDescriptor,
+    ) -> Result<(Vec<u8>, wgt::ShaderBindingTableLayout), CreateShaderBindingTableError> {
+        let group_count = self.groups.len() as u32;
+        let check = |group: u32, region: &'static str, valid: bool| {
+            if group >= group_count {
+                Err(CreateShaderBindingTableError::GroupOutOfRange {
+                    group,
+                    count: group_count,
+                })
+            } else if !valid {
+                Err(CreateShaderBindingTableError::WrongGroupKind { group, region })
+            } else {
+                Ok(())
+            }
+        };
+        let is_general =
+            |group: u32, stage: wgt::ShaderStages| self.general_stage(group) == Some(stage);
+        check(
+            desc.ray_generation,
+            "ray generation",
+            is_general(desc.ray_generation, wgt::ShaderStages::RAY_GENERATION),
+        )?;
+        for &group in desc.miss.iter() {
+            check(group, "miss", is_general(group, wgt::ShaderStages::MISS))?;
+        }
+        for &group in desc.hit.iter() {
+            let is_hit = self
+                .groups
+                .get(group as usize)
+                .is_some_and(|group| !matches!(group, wgt::RayTracingShaderGroup::General { .. }));
+            check(group, "hit", is_hit)?;
+        }
+        for &group in desc.callable.iter() {
+            check(
+                group,
+                "callable",
+                is_general(group, wgt::ShaderStages::CALLABLE),
+            )?;
+        }
+
+        let handles = unsafe {
+            self.device
+                .raw()
+                .get_ray_tracing_shader_group_handles(self.raw(), 0, group_count)
+        }
+        .map_err(|e| self.device.handle_hal_error(e))?;
+
+        let alignments = &self.device.alignments;
+        let handle_size = alignments.ray_tracing_shader_group_handle_size as usize;
+        let stride = wgt::math::align_to(
+            alignments.ray_tracing_shader_group_handle_size,
+            alignments.ray_tracing_shader_group_handle_alignment,
+        ) as wgt::BufferAddress;
+        let base_alignment =
+            alignments.ray_tracing_shader_group_base_alignment as wgt::BufferAddress;
+
+        let mut data = Vec::new();
+        let mut push_region = |groups: &[u32]| {
+            if groups.is_empty() {
+                return wgt::ShaderBindingTableRegion::default();
+            }
+            let offset = wgt::math::align_to(data.len() as wgt::BufferAddress, base_alignment);
+            data.resize(offset as usize, 0);
+            for &group in groups {
+                let start = group as usize * handle_size;
+                data.extend_from_slice(&handles[start..start + handle_size]);
+                data.resize(data.len() + stride as usize - handle_size, 0);
+            }
+            wgt::ShaderBindingTableRegion {
+                offset,
+                stride,
+                size: stride * groups.len() as wgt::BufferAddress,
+            }
+        };
+
+        let layout = wgt::ShaderBindingTableLayout {
+            ray_generation: push_region(&[desc.ray_generation]),
+            miss: push_region(&desc.miss),
+            hit: push_region(&desc.hit),
+            callable: push_region(&desc.callable),
+        };
+        Ok((data, layout))
+    }
+}
+
+/// Selects the shader groups of a [`RayTracingPipeline`] that make up a shader
+/// binding table.
+#[derive(Clone, Debug)]
+pub struct ShaderBindingTableDescriptor<'a> {
+    /// The group launched for each ray generation invocation.
+    pub ray_generation: u32,
+    /// The groups of the miss records, in record order.
+    pub miss: Cow<'a, [u32]>,
+    /// The groups of the hit records, in record order.
+    pub hit: Cow<'a, [u32]>,
+    /// The groups of the callable records, in record order.
+    pub callable: Cow<'a, [u32]>,
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateShaderBindingTableError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Shader group {group} is out of range for a pipeline with {count} groups")]
+    GroupOutOfRange { group: u32, count: u32 },
+    #[error("Shader group {group} can't be used as a {region} record")]
+    WrongGroupKind { group: u32, region: &'static str },
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+}
+
+impl WebGpuError for CreateShaderBindingTableError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::InvalidResource(e) => e,
+            Self::GroupOutOfRange { .. } | Self::WrongGroupKind { .. } => {
+                return ErrorType::Validation;
+            }
+        };
+        e.webgpu_error_type()
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum CreatePipelineCacheError {
@@ -445,6 +746,133 @@ pub struct RenderPipelineDescriptor<
 pub type ResolvedRenderPipelineDescriptor<'a> =
     RenderPipelineDescriptor<'a, Arc<PipelineLayout>, Arc<ShaderModule>, Arc<PipelineCache>>;
 
+/// Describes the task stage of a mesh pipeline.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaskState<'a, SM = ShaderModuleId> {
+    /// The compiled task stage and its entry point.
+    pub stage: ProgrammableStageDescriptor<'a, SM>,
+}
+
+/// cbindgen:ignore
+pub type ResolvedTaskState<'a> = TaskState<'a, Arc<ShaderModule>>;
+
+/// Describes the mesh stage of a mesh pipeline.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshState<'a, SM = ShaderModuleId> {
+    /// The compiled mesh stage and its entry point.
+    pub stage: ProgrammableStageDescriptor<'a, SM>,
+}
+
+/// cbindgen:ignore
+pub type ResolvedMeshState<'a> = MeshState<'a, Arc<ShaderModule>>;
+
+/// Describes a mesh shader pipeline.
+///
+/// This is a render pipeline whose primitives are produced by a mesh stage,
+/// optionally preceded by a task stage, instead of by vertex fetch and a
+/// vertex stage.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshPipelineDescriptor<
+    'a,
+    PLL = PipelineLayoutId,
+    SM = ShaderModuleId,
+    PLC = PipelineCacheId,
+> {
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    pub layout: Option<PLL>,
+    /// The task processing state for this pipeline, if any.
+    pub task: Option<TaskState<'a, SM>>,
+    /// The mesh processing state for this pipeline.
+    pub mesh: MeshState<'a, SM>,
+    /// The properties of the pipeline at the primitive assembly and rasterization level.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub primitive: wgt::PrimitiveState,
+    /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub depth_stencil: Option<wgt::DepthStencilState>,
+    /// The multi-sampling properties of the pipeline.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub multisample: wgt::MultisampleState,
+    /// The fragment processing state for this pipeline.
+    pub fragment: Option<FragmentState<'a, SM>>,
+    /// If the pipeline will be used with a multiview render pass, this indicates how many array
+    /// layers the attachments will have.
+    pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<PLC>,
+}
+
+/// The stages of a render pipeline that produce primitives for rasterization.
+#[derive(Clone, Debug)]
+pub(crate) enum RenderPipelineVertexProcessor<'a, SM = ShaderModuleId> {
+    Vertex(VertexState<'a, SM>),
+    Mesh(Option<TaskState<'a, SM>>, MeshState<'a, SM>),
+}
+
+/// Common form of [`RenderPipelineDescriptor`] and [`MeshPipelineDescriptor`],
+/// which lets both kinds of pipeline share their creation path.
+#[derive(Clone, Debug)]
+pub(crate) struct GeneralRenderPipelineDescriptor<
+    'a,
+    PLL = PipelineLayoutId,
+    SM = ShaderModuleId,
+    PLC = PipelineCacheId,
+> {
+    pub label: Label<'a>,
+    pub layout: Option<PLL>,
+    pub vertex: RenderPipelineVertexProcessor<'a, SM>,
+    pub primitive: wgt::PrimitiveState,
+    pub depth_stencil: Option<wgt::DepthStencilState>,
+    pub multisample: wgt::MultisampleState,
+    pub fragment: Option<FragmentState<'a, SM>>,
+    pub multiview: Option<NonZeroU32>,
+    pub cache: Option<PLC>,
+}
+
+impl<'a, PLL, SM, PLC> From<RenderPipelineDescriptor<'a, PLL, SM, PLC>>
+    for GeneralRenderPipelineDescriptor<'a, PLL, SM, PLC>
+{
+    fn from(desc: RenderPipelineDescriptor<'a, PLL, SM, PLC>) -> Self {
+        Self {
+            label: desc.label,
+            layout: desc.layout,
+            vertex: RenderPipelineVertexProcessor::Vertex(desc.vertex),
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil,
+            multisample: desc.multisample,
+            fragment: desc.fragment,
+            multiview: desc.multiview,
+            cache: desc.cache,
+        }
+    }
+}
+
+impl<'a, PLL, SM, PLC> From<MeshPipelineDescriptor<'a, PLL, SM, PLC>>
+    for GeneralRenderPipelineDescriptor<'a, PLL, SM, PLC>
+{
+    fn from(desc: MeshPipelineDescriptor<'a, PLL, SM, PLC>) -> Self {
+        Self {
+            label: desc.label,
+            layout: desc.layout,
+            vertex: RenderPipelineVertexProcessor::Mesh(desc.task, desc.mesh),
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil,
+            multisample: desc.multisample,
+            fragment: desc.fragment,
+            multiview: desc.multiview,
+            cache: desc.cache,
+        }
+    }
+}
+
+/// cbindgen:ignore
+pub(crate) type ResolvedGeneralRenderPipelineDescriptor<'a> =
+    GeneralRenderPipelineDescriptor<'a, Arc<PipelineLayout>, Arc<ShaderModule>, Arc<PipelineCache>>;
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PipelineCacheDescriptor<'a> {
@@ -473,6 +901,10 @@ pub enum ColorStateError {
     },
     #[error("Invalid write mask {0:?}")]
     InvalidWriteMask(wgt::ColorWrites),
+    #[error("Logic operations can't be combined with blending")]
+    LogicOpWithBlend,
+    #[error("Format {0:?} doesn't support logic operations, which need a `Uint` format")]
+    FormatNotLogicOp(wgt::TextureFormat),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -490,6 +922,44 @@ pub enum DepthStencilStateError {
     InvalidSampleCount(u32, wgt::TextureFormat, Vec<u32>, Vec<u32>),
 }
 
+/// Describes why a render pipeline can't draw wide lines on a backend that emulates them.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum WideLinesError {
+    #[error("Lines drawn with `PolygonMode::Line` can't be wide")]
+    PolygonModeLine,
+    #[error("Mesh pipelines can't draw wide lines")]
+    MeshPipeline,
+    #[error("Stippled lines can't be wide")]
+    LineStipple,
+    #[error(
+        "The pipeline layout can't have push constant ranges, which are used to draw the lines"
+    )]
+    PushConstantRanges,
+    #[error("Vertex buffer {index} steps per instance, which isn't supported")]
+    InstanceStepMode { index: u32 },
+    #[error("Each of the {count} vertex buffers is bound twice, which exceeds the limit {limit}")]
+    TooManyVertexBuffers { count: u32, limit: u32 },
+    #[error(
+        "Each vertex attribute up to location {location} is read twice, which exceeds the limit {limit}"
+    )]
+    TooManyVertexAttributes { location: u32, limit: u32 },
+    #[error(
+        "Vertex buffer {index} stride {stride} is read every other vertex, which exceeds the limit {limit}"
+    )]
+    VertexStrideTooLarge {
+        index: u32,
+        stride: wgt::BufferAddress,
+        limit: u32,
+    },
+    #[error("The vertex shader module has no IR to expand")]
+    MissingShaderSource,
+    #[error("The vertex shader reads the {0:?} built-in, which isn't supported")]
+    UnsupportedBuiltIn(naga::BuiltIn),
+    #[error("The vertex shader doesn't write a position")]
+    MissingPosition,
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum CreateRenderPipelineError {
@@ -527,6 +997,8 @@ pub enum CreateRenderPipelineError {
         location: wgt::ShaderLocation,
         offset: wgt::BufferAddress,
     },
+    #[error("Vertex buffer {index} has an instance step rate of 0")]
+    ZeroInstanceStepRate { index: u32 },
     #[error("Two or more vertex attributes were assigned to the same location in the shader: {0}")]
     ShaderLocationClash(u32),
     #[error(
@@ -538,6 +1010,16 @@ pub enum CreateRenderPipelineError {
     },
     #[error("Conservative Rasterization is only supported for wgt::PolygonMode::Fill")]
     ConservativeRasterizationNonFillPolygonMode,
+    #[error("Line width {0} must be a finite number greater than zero")]
+    InvalidLineWidth(f32),
+    #[error("Line stipple factor {0} must be between 1 and 256")]
+    InvalidLineStippleFactor(u32),
+    #[error("Stippled lines can't be drawn with alpha to coverage")]
+    LineStippleWithAlphaToCoverage,
+    #[error("All color targets must use the same logic operation")]
+    MismatchedLogicOps,
+    #[error("Wide lines are emulated on this backend, and can't be drawn by this pipeline")]
+    WideLines(#[from] WideLinesError),
     #[error(transparent)]
     MissingFeatures(#[from] MissingFeatures),
     #[error(transparent)]
@@ -573,6 +1055,14 @@ pub enum CreateRenderPipelineError {
     PipelineExpectsShaderToUseDualSourceBlending,
     #[error("Shader entry point expects the pipeline to make use of dual-source blending.")]
     ShaderExpectsPipelineToUseDualSourceBlending,
+    #[error(
+        "The fragment shader reads color target {0} with framebuffer fetch, but the pipeline has no color target at that index"
+    )]
+    FramebufferFetchMissingTarget(u32),
+    #[error(
+        "The fragment shader reads the `fully_covered` built-in, but the pipeline doesn't use conservative rasterization"
+    )]
+    FullyCoveredWithoutConservativeRasterization,
     #[error("{}", concat!(
         "At least one color attachment or depth-stencil attachment was expected, ",
         "but no render target for the pipeline was specified."
@@ -602,14 +1092,22 @@ impl WebGpuError for CreateRenderPipelineError {
             | Self::VertexStrideTooLarge { .. }
             | Self::UnalignedVertexStride { .. }
             | Self::InvalidVertexAttributeOffset { .. }
+            | Self::ZeroInstanceStepRate { .. }
             | Self::ShaderLocationClash(_)
             | Self::StripIndexFormatForNonStripTopology { .. }
             | Self::ConservativeRasterizationNonFillPolygonMode
+            | Self::InvalidLineWidth(_)
+            | Self::InvalidLineStippleFactor(_)
+            | Self::LineStippleWithAlphaToCoverage
+            | Self::MismatchedLogicOps
+            | Self::WideLines(_)
             | Self::Stage { .. }
             | Self::UnalignedShader { .. }
             | Self::BlendFactorOnUnsupportedTarget { .. }
             | Self::PipelineExpectsShaderToUseDualSourceBlending
             | Self::ShaderExpectsPipelineToUseDualSourceBlending
+            | Self::FramebufferFetchMissingTarget(_)
+            | Self::FullyCoveredWithoutConservativeRasterization
             | Self::NoTargetSpecified
             | Self::PipelineConstants { .. }
             | Self::VertexAttributeStrideTooLarge { .. } => return ErrorType::Validation,
@@ -661,8 +1159,14 @@ pub struct RenderPipeline {
     pub(crate) pass_context: RenderPassContext,
     pub(crate) flags: PipelineFlags,
     pub(crate) strip_index_format: Option<wgt::IndexFormat>,
+    /// Whether indexed draws restart strips, which needs `strip_index_format` to be set.
+    pub(crate) strip_restart: bool,
     pub(crate) vertex_steps: Vec<VertexStep>,
     pub(crate) late_sized_buffer_groups: ArrayVec<LateSizedBufferGroup, { hal::MAX_BIND_GROUPS }>,
+    /// Whether primitives are produced by a mesh stage instead of a vertex stage.
+    pub(crate) is_mesh: bool,
+    /// How lines are widened, if the backend can't rasterize wide lines.
+    pub(crate) wide_lines: Option<crate::wide_lines::WideLines>,
     /// The `label` from the descriptor used to create the resource.
     pub(crate) label: String,
     pub(crate) tracking_data: TrackingData,
@@ -676,6 +1180,10 @@ impl Drop for RenderPipeline {
         unsafe {
             self.device.raw().destroy_render_pipeline(raw);
         }
+        if let Some(wide_lines) = self.wide_lines.take() {
+            // SAFETY: The pipeline using the module was destroyed above.
+            unsafe { wide_lines.destroy(self.device.raw()) };
+        }
     }
 }
 
@@ -689,4 +1197,15 @@ impl RenderPipeline {
     pub(crate) fn raw(&self) -> &dyn hal::DynRenderPipeline {
         self.raw.as_ref()
     }
+
+    /// Checks that every backend knows which index value restarts strips when
+    /// this pipeline is used with indexed draws.
+    pub(crate) fn check_strip_index_format(&self) -> Result<(), DrawError> {
+        if self.strip_restart && self.strip_index_format.is_none() {
+            return Err(DrawError::MissingStripIndexFormat {
+                pipeline: self.error_ident(),
+            });
+        }
+        Ok(())
+    }
 }
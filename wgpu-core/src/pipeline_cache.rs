@@ -223,7 +223,9 @@ impl PipelineCacheHeader {
 
 fn adapter_key(adapter: &AdapterInfo) -> Result<[u8; 15], PipelineCacheValidationError> {
     match adapter.backend {
-        wgt::Backend::Vulkan => {
+        // The backend itself is recorded separately in the header. Metal reports
+        // zero for both IDs, so its validation key identifies the device instead.
+        wgt::Backend::Vulkan | wgt::Backend::Dx12 | wgt::Backend::Metal => {
             // If these change size, the header format needs to change
             // We set the type explicitly so this won't compile in that case
             let v: [u8; 4] = adapter.vendor.to_be_bytes();
@@ -534,4 +536,20 @@ mod tests {
         let validation_result = super::validate_pipeline_cache(&cache, &ADAPTER, VALIDATION_KEY);
         assert_eq!(validation_result, Err(E::Corrupted));
     }
+    #[test]
+    fn other_backends() {
+        for backend in [wgt::Backend::Dx12, wgt::Backend::Metal] {
+            let adapter = AdapterInfo { backend, ..ADAPTER };
+            let mut cache = [0; HEADER_LENGTH];
+            super::add_cache_header(&mut cache, &[], &adapter, VALIDATION_KEY);
+            let expected: &[u8] = &[];
+            let validation_result =
+                super::validate_pipeline_cache(&cache, &adapter, VALIDATION_KEY);
+            assert_eq!(validation_result, Ok(expected));
+            // A cache from another backend is rejected
+            let validation_result =
+                super::validate_pipeline_cache(&cache, &ADAPTER, VALIDATION_KEY);
+            assert_eq!(validation_result, Err(E::DeviceMismatch));
+        }
+    }
 }
@@ -9,17 +9,18 @@ When this texture is presented, we remove it from the device tracker as well as
 extract it from the hub.
 !*/
 
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{collections::VecDeque, sync::Arc, vec, vec::Vec};
 use core::mem::ManuallyDrop;
 
 #[cfg(feature = "trace")]
 use crate::device::trace::Action;
 use crate::{
     conv,
-    device::{Device, DeviceError, MissingDownlevelFlags, WaitIdleError},
+    device::{Device, DeviceError, MissingDownlevelFlags, MissingFeatures, WaitIdleError},
     global::Global,
     hal_label, id,
     instance::Surface,
+    lock::{Mutex, rank},
     resource,
 };
 
@@ -51,14 +52,23 @@ pub enum SurfaceError {
     AlreadyAcquired,
     #[error("Texture has been destroyed")]
     TextureDestroyed,
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    CreateTexture(#[from] resource::CreateTextureError),
+    #[error("Surface is not headless")]
+    NotHeadless,
 }
 
 impl WebGpuError for SurfaceError {
     fn webgpu_error_type(&self) -> ErrorType {
         let e: &dyn WebGpuError = match self {
             Self::Device(e) => e,
+            Self::MissingFeatures(e) => e,
+            Self::CreateTexture(e) => e,
             Self::Invalid
             | Self::NotConfigured
+            | Self::NotHeadless
             | Self::AlreadyAcquired
             | Self::TextureDestroyed => return ErrorType::Validation,
         };
@@ -116,6 +126,13 @@ pub enum ConfigureSurfaceError {
         requested: wgt::CompositeAlphaMode,
         available: Vec<wgt::CompositeAlphaMode>,
     },
+    #[error(
+        "Requested color space {requested:?} is not in the list of supported color spaces: {available:?}"
+    )]
+    UnsupportedColorSpace {
+        requested: wgt::SurfaceColorSpace,
+        available: Vec<wgt::SurfaceColorSpace>,
+    },
     #[error("Requested usage {requested:?} is not in the list of supported usages: {available:?}")]
     UnsupportedUsage {
         requested: wgt::TextureUses,
@@ -148,6 +165,7 @@ impl WebGpuError for ConfigureSurfaceError {
             | Self::UnsupportedFormat { .. }
             | Self::UnsupportedPresentMode { .. }
             | Self::UnsupportedAlphaMode { .. }
+            | Self::UnsupportedColorSpace { .. }
             | Self::UnsupportedUsage { .. } => return ErrorType::Validation,
         };
         e.webgpu_error_type()
@@ -156,6 +174,63 @@ impl WebGpuError for ConfigureSurfaceError {
 
 pub type ResolvedSurfaceOutput = SurfaceOutput<Arc<resource::Texture>>;
 
+/// A frame presented to a headless surface.
+#[derive(Debug)]
+pub struct PresentedFrame {
+    pub texture: id::TextureId,
+    pub desc: wgt::TextureDescriptor<(), ()>,
+}
+
+/// The most frames a [`HeadlessSurface`] keeps until they are taken, dropping
+/// the oldest ones first.
+const MAX_HEADLESS_PRESENTED_FRAMES: usize = 16;
+
+/// State of a surface that isn't connected to any window.
+///
+/// Instead of acquiring textures from a swap chain, it creates regular textures,
+/// and keeps the presented ones around for [`Global::surface_take_presented_frames`].
+#[derive(Debug)]
+pub(crate) struct HeadlessSurface {
+    extent: wgt::Extent3d,
+    format: wgt::TextureFormat,
+    presented_frames: Mutex<VecDeque<Arc<resource::Texture>>>,
+}
+
+impl HeadlessSurface {
+    pub(crate) fn new(width: u32, height: u32, format: wgt::TextureFormat) -> Self {
+        Self {
+            extent: wgt::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            format,
+            presented_frames: Mutex::new(rank::HEADLESS_SURFACE_PRESENTED_FRAMES, VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn capabilities(&self) -> hal::SurfaceCapabilities {
+        hal::SurfaceCapabilities {
+            formats: vec![self.format],
+            maximum_frame_latency: 1..=15,
+            current_extent: Some(self.extent),
+            usage: wgt::TextureUses::COLOR_TARGET
+                | wgt::TextureUses::COPY_SRC
+                | wgt::TextureUses::COPY_DST
+                | wgt::TextureUses::RESOURCE,
+            // Presenting never waits, so all modes behave the same.
+            present_modes: vec![
+                wgt::PresentMode::Fifo,
+                wgt::PresentMode::FifoRelaxed,
+                wgt::PresentMode::Immediate,
+                wgt::PresentMode::Mailbox,
+            ],
+            composite_alpha_modes: vec![wgt::CompositeAlphaMode::Opaque],
+            color_spaces: vec![wgt::SurfaceColorSpace::Srgb],
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct SurfaceOutput<T = id::TextureId> {
@@ -165,6 +240,19 @@ pub struct SurfaceOutput<T = id::TextureId> {
 
 impl Surface {
     pub fn get_current_texture(&self) -> Result<ResolvedSurfaceOutput, SurfaceError> {
+        self.get_current_texture_with_timeout(core::time::Duration::from_millis(
+            FRAME_TIMEOUT_MS as u64,
+        ))
+    }
+
+    /// Acquires the next texture, waiting at most `timeout` for one to become
+    /// available. A zero `timeout` doesn't wait at all.
+    ///
+    /// If no texture became available in time, the status is [`Status::Timeout`].
+    pub fn get_current_texture_with_timeout(
+        &self,
+        timeout: core::time::Duration,
+    ) -> Result<ResolvedSurfaceOutput, SurfaceError> {
         profiling::scope!("Surface::get_current_texture");
 
         let (device, config) = if let Some(ref present) = *self.presentation.lock() {
@@ -174,15 +262,15 @@ impl Surface {
             return Err(SurfaceError::NotConfigured);
         };
 
+        if self.headless.is_some() {
+            return self.acquire_headless_texture(&device, config);
+        }
+
         let fence = device.fence.read();
 
         let suf = self.raw(device.backend()).unwrap();
-        let (texture, status) = match unsafe {
-            suf.acquire_texture(
-                Some(core::time::Duration::from_millis(FRAME_TIMEOUT_MS as u64)),
-                fence.as_ref(),
-            )
-        } {
+        let (texture, status) = match unsafe { suf.acquire_texture(Some(timeout), fence.as_ref()) }
+        {
             Ok(Some(ast)) => {
                 drop(fence);
 
@@ -281,7 +369,46 @@ impl Surface {
         Ok(ResolvedSurfaceOutput { status, texture })
     }
 
-    pub fn present(&self) -> Result<Status, SurfaceError> {
+    fn acquire_headless_texture(
+        &self,
+        device: &Arc<Device>,
+        config: wgt::SurfaceConfiguration<Vec<wgt::TextureFormat>>,
+    ) -> Result<ResolvedSurfaceOutput, SurfaceError> {
+        let desc = resource::TextureDescriptor {
+            label: Some(alloc::borrow::Cow::Borrowed("<Headless Surface Texture>")),
+            size: wgt::Extent3d {
+                width: config.width,
+                height: config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgt::TextureDimension::D2,
+            format: config.format,
+            // Presented frames are read back by copying from them.
+            usage: config.usage | wgt::TextureUsages::COPY_SRC,
+            view_formats: config.view_formats,
+        };
+        let texture = device.create_texture(&desc)?;
+
+        let mut presentation = self.presentation.lock();
+        let present = presentation.as_mut().ok_or(SurfaceError::NotConfigured)?;
+        if present.acquired_texture.is_some() {
+            return Err(SurfaceError::AlreadyAcquired);
+        }
+        present.acquired_texture = Some(texture.clone());
+
+        Ok(ResolvedSurfaceOutput {
+            status: Status::Good,
+            texture: Some(texture),
+        })
+    }
+
+    /// Presents the acquired texture.
+    ///
+    /// `damage` lists the regions that changed since the previous present. It is
+    /// clipped to the texture's extent, and an empty list means the whole texture.
+    pub fn present(&self, damage: &[wgt::Rect]) -> Result<Status, SurfaceError> {
         profiling::scope!("Surface::present");
 
         let mut presentation = self.presentation.lock();
@@ -300,12 +427,38 @@ impl Surface {
             .take()
             .ok_or(SurfaceError::AlreadyAcquired)?;
 
+        if let Some(ref headless) = self.headless {
+            let mut presented_frames = headless.presented_frames.lock();
+            if presented_frames.len() == MAX_HEADLESS_PRESENTED_FRAMES {
+                presented_frames.pop_front();
+            }
+            presented_frames.push_back(texture);
+            return Ok(Status::Good);
+        }
+
+        let extent = texture.desc.size;
+        let damage = damage
+            .iter()
+            .filter_map(|rect| {
+                let x = rect.x.min(extent.width);
+                let y = rect.y.min(extent.height);
+                let width = rect.width.min(extent.width - x);
+                let height = rect.height.min(extent.height - y);
+                (width != 0 && height != 0).then_some(wgt::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                })
+            })
+            .collect::<Vec<_>>();
+
         let result = match texture.inner.snatch(&mut device.snatchable_lock.write()) {
             None => return Err(SurfaceError::TextureDestroyed),
             Some(resource::TextureInner::Surface { raw }) => {
                 let raw_surface = self.raw(device.backend()).unwrap();
                 let raw_queue = queue.raw();
-                unsafe { raw_queue.present(raw_surface, raw) }
+                unsafe { raw_queue.present(raw_surface, raw, &damage) }
             }
             _ => unreachable!(),
         };
@@ -326,6 +479,76 @@ impl Surface {
         }
     }
 
+    /// Requests that the next present isn't displayed before `time`.
+    pub fn set_target_present_time(
+        &self,
+        time: wgt::PresentationTimestamp,
+    ) -> Result<(), SurfaceError> {
+        let presentation = self.presentation.lock();
+        let present = presentation.as_ref().ok_or(SurfaceError::NotConfigured)?;
+        let device = &present.device;
+
+        device.check_is_valid()?;
+        device.require_features(wgt::Features::PRESENTATION_TIMING)?;
+
+        // Headless surfaces present immediately.
+        let Some(raw_surface) = self.raw(device.backend()) else {
+            return Ok(());
+        };
+        unsafe { raw_surface.set_target_present_time(time) };
+        Ok(())
+    }
+
+    pub fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, SurfaceError> {
+        profiling::scope!("Surface::get_presentation_statistics");
+
+        let presentation = self.presentation.lock();
+        let present = presentation.as_ref().ok_or(SurfaceError::NotConfigured)?;
+        let device = &present.device;
+
+        device.check_is_valid()?;
+        device.require_features(wgt::Features::PRESENTATION_TIMING)?;
+
+        let Some(raw_surface) = self.raw(device.backend()) else {
+            return Ok(Vec::new());
+        };
+        match unsafe { raw_surface.get_presentation_statistics() } {
+            Ok(statistics) => Ok(statistics),
+            // The next acquire reports these.
+            Err(hal::SurfaceError::Lost | hal::SurfaceError::Outdated) => Ok(Vec::new()),
+            Err(hal::SurfaceError::Device(err)) => {
+                Err(SurfaceError::from(device.handle_hal_error(err)))
+            }
+            Err(hal::SurfaceError::Other(msg)) => {
+                log::error!("presentation statistics error: {}", msg);
+                Err(SurfaceError::Invalid)
+            }
+        }
+    }
+
+    /// Returns how frames presented to the surface currently reach the display.
+    pub fn presentation_path(&self) -> Result<wgt::PresentationPath, SurfaceError> {
+        let presentation = self.presentation.lock();
+        let present = presentation.as_ref().ok_or(SurfaceError::NotConfigured)?;
+        let device = &present.device;
+
+        device.check_is_valid()?;
+
+        let Some(raw_surface) = self.raw(device.backend()) else {
+            return Ok(wgt::PresentationPath::Unknown);
+        };
+        Ok(unsafe { raw_surface.presentation_path() })
+    }
+
+    /// Takes the frames presented to a headless surface since the previous call,
+    /// oldest first.
+    pub fn take_presented_frames(&self) -> Result<Vec<Arc<resource::Texture>>, SurfaceError> {
+        let headless = self.headless.as_ref().ok_or(SurfaceError::NotHeadless)?;
+        Ok(headless.presented_frames.lock().drain(..).collect())
+    }
+
     pub fn discard(&self) -> Result<(), SurfaceError> {
         profiling::scope!("Surface::discard");
 
@@ -344,6 +567,10 @@ impl Surface {
             .take()
             .ok_or(SurfaceError::AlreadyAcquired)?;
 
+        if self.headless.is_some() {
+            return Ok(());
+        }
+
         match texture.inner.snatch(&mut device.snatchable_lock.write()) {
             None => return Err(SurfaceError::TextureDestroyed),
             Some(resource::TextureInner::Surface { raw }) => {
@@ -362,6 +589,21 @@ impl Global {
         &self,
         surface_id: id::SurfaceId,
         texture_id_in: Option<id::TextureId>,
+    ) -> Result<SurfaceOutput, SurfaceError> {
+        self.surface_get_current_texture_with_timeout(
+            surface_id,
+            core::time::Duration::from_millis(FRAME_TIMEOUT_MS as u64),
+            texture_id_in,
+        )
+    }
+
+    /// Acquires the next texture of the surface, waiting at most `timeout` for
+    /// one to become available. A zero `timeout` doesn't wait at all.
+    pub fn surface_get_current_texture_with_timeout(
+        &self,
+        surface_id: id::SurfaceId,
+        timeout: core::time::Duration,
+        texture_id_in: Option<id::TextureId>,
     ) -> Result<SurfaceOutput, SurfaceError> {
         let surface = self.surfaces.get(surface_id);
 
@@ -377,7 +619,7 @@ impl Global {
             }
         }
 
-        let output = surface.get_current_texture()?;
+        let output = surface.get_current_texture_with_timeout(timeout)?;
 
         let status = output.status;
         let texture_id = output
@@ -391,6 +633,16 @@ impl Global {
     }
 
     pub fn surface_present(&self, surface_id: id::SurfaceId) -> Result<Status, SurfaceError> {
+        self.surface_present_with_damage(surface_id, &[])
+    }
+
+    /// Presents the surface's texture, hinting that only the `damage` regions
+    /// changed since the previous present.
+    pub fn surface_present_with_damage(
+        &self,
+        surface_id: id::SurfaceId,
+        damage: &[wgt::Rect],
+    ) -> Result<Status, SurfaceError> {
         let surface = self.surfaces.get(surface_id);
 
         #[cfg(feature = "trace")]
@@ -400,7 +652,57 @@ impl Global {
             }
         }
 
-        surface.present()
+        surface.present(damage)
+    }
+
+    /// Requests that the next present on the surface isn't displayed before `time`.
+    pub fn surface_set_target_present_time(
+        &self,
+        surface_id: id::SurfaceId,
+        time: wgt::PresentationTimestamp,
+    ) -> Result<(), SurfaceError> {
+        let surface = self.surfaces.get(surface_id);
+        surface.set_target_present_time(time)
+    }
+
+    /// Returns feedback about the frames presented on the surface since the previous call.
+    pub fn surface_get_presentation_statistics(
+        &self,
+        surface_id: id::SurfaceId,
+    ) -> Result<Vec<wgt::PresentationStatistics>, SurfaceError> {
+        let surface = self.surfaces.get(surface_id);
+        surface.get_presentation_statistics()
+    }
+
+    /// Returns how frames presented to the surface currently reach the display.
+    pub fn surface_presentation_path(
+        &self,
+        surface_id: id::SurfaceId,
+    ) -> Result<wgt::PresentationPath, SurfaceError> {
+        let surface = self.surfaces.get(surface_id);
+        surface.presentation_path()
+    }
+
+    /// Takes the frames presented to a headless surface since the previous call,
+    /// oldest first, and registers them as textures.
+    pub fn surface_take_presented_frames(
+        &self,
+        surface_id: id::SurfaceId,
+    ) -> Result<Vec<PresentedFrame>, SurfaceError> {
+        let surface = self.surfaces.get(surface_id);
+        let frames = surface.take_presented_frames()?;
+        Ok(frames
+            .into_iter()
+            .map(|texture| {
+                let desc = texture.desc.map_label_and_view_formats(|_| (), |_| ());
+                let texture = self
+                    .hub
+                    .textures
+                    .prepare(None)
+                    .assign(resource::Fallible::Valid(texture));
+                PresentedFrame { texture, desc }
+            })
+            .collect())
     }
 
     pub fn surface_texture_discard(&self, surface_id: id::SurfaceId) -> Result<(), SurfaceError> {
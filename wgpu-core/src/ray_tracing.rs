@@ -1,7 +1,6 @@
 // Ray tracing
 // Major missing optimizations (no api surface changes needed):
 // - use custom tracker to track build state
-// - no forced rebuilt (build mode deduction)
 // - lazy instance buffer allocation
 // - maybe share scratch and instance staging buffer allocation
 // - partial instance buffer uploads (api surface already designed with this in mind)
@@ -21,8 +20,9 @@ use crate::{
     device::{DeviceError, MissingFeatures},
     id::{BlasId, BufferId, TlasId},
     resource::{
-        Blas, BlasCompactCallback, BlasPrepareCompactResult, DestroyedResourceError,
-        InvalidResourceError, MissingBufferUsageError, ResourceErrorIdent, Tlas,
+        Blas, BlasCompactCallback, BlasPrepareCompactResult, BlasSerializeCallback,
+        BlasSerializeResult, DestroyedResourceError, InvalidResourceError, MissingBufferUsageError,
+        ResourceErrorIdent, Tlas,
     },
 };
 
@@ -46,6 +46,10 @@ pub enum CreateBlasError {
         "Limit `max_blas_primitive_count` is {0}, but the BLAS had a maximum of {1} primitives"
     )]
     TooManyPrimitives(u32, u32),
+    #[error("Update mode `Refit` requires the flag AccelerationStructureFlags::ALLOW_UPDATE")]
+    RefitWithoutAllowUpdate,
+    #[error("Serialized BLAS data is not compatible with this device")]
+    IncompatibleSerializedData,
 }
 
 impl WebGpuError for CreateBlasError {
@@ -56,7 +60,9 @@ impl WebGpuError for CreateBlasError {
             Self::MissingIndexData
             | Self::InvalidVertexFormat(..)
             | Self::TooManyGeometries(..)
-            | Self::TooManyPrimitives(..) => return ErrorType::Validation,
+            | Self::TooManyPrimitives(..)
+            | Self::RefitWithoutAllowUpdate
+            | Self::IncompatibleSerializedData => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -72,6 +78,8 @@ pub enum CreateTlasError {
     DisallowedFlag(wgt::AccelerationStructureFlags),
     #[error("Limit `max_tlas_instance_count` is {0}, but the TLAS had a maximum of {1} instances")]
     TooManyInstances(u32, u32),
+    #[error("Update mode `Refit` requires the flag AccelerationStructureFlags::ALLOW_UPDATE")]
+    RefitWithoutAllowUpdate,
 }
 
 impl WebGpuError for CreateTlasError {
@@ -79,7 +87,9 @@ impl WebGpuError for CreateTlasError {
         let e: &dyn WebGpuError = match self {
             Self::Device(e) => e,
             Self::MissingFeatures(e) => e,
-            Self::DisallowedFlag(..) | Self::TooManyInstances(..) => return ErrorType::Validation,
+            Self::DisallowedFlag(..)
+            | Self::TooManyInstances(..)
+            | Self::RefitWithoutAllowUpdate => return ErrorType::Validation,
         };
         e.webgpu_error_type()
     }
@@ -233,13 +243,27 @@ pub enum ValidateAsActionsError {
 
     #[error("Blas {0:?} is newer than the containing Tlas {1:?}")]
     BlasNewerThenTlas(ResourceErrorIdent, ResourceErrorIdent),
+
+    #[error(
+        "Blas {0:?} is refit with different vertex or index counts than its previous build (previous: {1:?}, refit: {2:?})"
+    )]
+    IncompatibleBlasRefit(ResourceErrorIdent, Vec<u32>, Vec<u32>),
+
+    #[error(
+        "Tlas {0:?} is refit with a different instance count than its previous build (previous: {1}, refit: {2})"
+    )]
+    IncompatibleTlasRefit(ResourceErrorIdent, u32, u32),
 }
 
 impl WebGpuError for ValidateAsActionsError {
     fn webgpu_error_type(&self) -> ErrorType {
         let e: &dyn WebGpuError = match self {
             Self::DestroyedResource(e) => e,
-            Self::UsedUnbuiltTlas(..) | Self::UsedUnbuiltBlas(..) | Self::BlasNewerThenTlas(..) => {
+            Self::UsedUnbuiltTlas(..)
+            | Self::UsedUnbuiltBlas(..)
+            | Self::BlasNewerThenTlas(..)
+            | Self::IncompatibleBlasRefit(..)
+            | Self::IncompatibleTlasRefit(..) => {
                 return ErrorType::Validation;
             }
         };
@@ -290,15 +314,29 @@ pub struct TlasPackage<'a> {
     pub lowest_unmodified: u32,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct BlasBuild {
+    pub blas: Arc<Blas>,
+    /// Whether the BLAS is updated from its previous build instead of being built anew.
+    pub update: bool,
+    /// The vertex and index count of each geometry, `None` if the BLAS was built outside of
+    /// wgpu.
+    pub counts: Option<Vec<u32>>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct TlasBuild {
     pub tlas: Arc<Tlas>,
     pub dependencies: Vec<Arc<Blas>>,
+    /// Whether the TLAS is updated from its previous build instead of being built anew.
+    pub update: bool,
+    /// The number of instances, `None` if the TLAS was built outside of wgpu.
+    pub instance_count: Option<u32>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct AsBuild {
-    pub blas_s_built: Vec<Arc<Blas>>,
+    pub blas_s_built: Vec<BlasBuild>,
     pub tlas_s_built: Vec<TlasBuild>,
 }
 
@@ -419,3 +457,32 @@ impl WebGpuError for CompactBlasError {
 }
 
 pub type BlasCompactReadyPendingClosure = (Option<BlasCompactCallback>, BlasPrepareCompactResult);
+
+#[derive(Clone, Debug, Error)]
+pub enum BlasSerializeError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+    #[error(transparent)]
+    DestroyedResource(#[from] DestroyedResourceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("BLAS is not yet built")]
+    NotBuilt,
+}
+
+impl WebGpuError for BlasSerializeError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::InvalidResource(e) => e,
+            Self::DestroyedResource(e) => e,
+            Self::MissingFeatures(e) => e,
+            Self::NotBuilt => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+pub type BlasSerializeReadyPendingClosure = (BlasSerializeCallback, BlasSerializeResult);
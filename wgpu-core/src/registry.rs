@@ -3,8 +3,7 @@ use alloc::sync::Arc;
 use crate::{
     id::Id,
     identity::IdentityManager,
-    lock::{RwLock, RwLockReadGuard, RwLockWriteGuard, rank},
-    storage::{Element, Storage, StorageItem},
+    storage::{Storage, StorageItem, StorageReadGuard},
 };
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
@@ -36,14 +35,14 @@ impl RegistryReport {
 pub(crate) struct Registry<T: StorageItem> {
     // Must only contain an id which has either never been used or has been released from `storage`
     identity: Arc<IdentityManager<T::Marker>>,
-    storage: RwLock<Storage<T>>,
+    storage: Storage<T>,
 }
 
 impl<T: StorageItem> Registry<T> {
     pub(crate) fn new() -> Self {
         Self {
             identity: Arc::new(IdentityManager::new()),
-            storage: RwLock::new(rank::REGISTRY_STORAGE, Storage::new()),
+            storage: Storage::new(),
         }
     }
 }
@@ -51,7 +50,7 @@ impl<T: StorageItem> Registry<T> {
 #[must_use]
 pub(crate) struct FutureId<'a, T: StorageItem> {
     id: Id<T::Marker>,
-    data: &'a RwLock<Storage<T>>,
+    data: &'a Storage<T>,
 }
 
 impl<T: StorageItem> FutureId<'_, T> {
@@ -63,8 +62,7 @@ impl<T: StorageItem> FutureId<'_, T> {
     ///
     /// Registers it with the registry.
     pub fn assign(self, value: T) -> Id<T::Marker> {
-        let mut data = self.data.write();
-        data.insert(self.id, value);
+        self.data.insert(self.id, value);
         self.id
     }
 }
@@ -84,15 +82,16 @@ impl<T: StorageItem> Registry<T> {
     }
 
     #[track_caller]
-    pub(crate) fn read<'a>(&'a self) -> RwLockReadGuard<'a, Storage<T>> {
+    pub(crate) fn read<'a>(&'a self) -> StorageReadGuard<'a, T> {
         self.storage.read()
     }
-    #[track_caller]
-    pub(crate) fn write<'a>(&'a self) -> RwLockWriteGuard<'a, Storage<T>> {
-        self.storage.write()
+    /// Registers `value` under an id that was already marked as used, such
+    /// as the ids of implicit pipeline layouts.
+    pub(crate) fn insert(&self, id: Id<T::Marker>, value: T) {
+        self.storage.insert(id, value);
     }
     pub(crate) fn remove(&self, id: Id<T::Marker>) -> T {
-        let value = self.storage.write().remove(id);
+        let value = self.storage.remove(id);
         // This needs to happen *after* removing it from the storage, to maintain the
         // invariant that `self.identity` only contains ids which are actually available
         // See https://github.com/gfx-rs/wgpu/issues/5372
@@ -102,25 +101,19 @@ impl<T: StorageItem> Registry<T> {
     }
 
     pub(crate) fn generate_report(&self) -> RegistryReport {
-        let storage = self.storage.read();
-        let mut report = RegistryReport {
+        let (num_kept_from_user, num_released_from_user) = self.storage.count();
+        RegistryReport {
+            num_allocated: self.identity.values.lock().count(),
+            num_kept_from_user,
+            num_released_from_user,
             element_size: size_of::<T>(),
-            ..Default::default()
-        };
-        report.num_allocated = self.identity.values.lock().count();
-        for element in storage.map.iter() {
-            match *element {
-                Element::Occupied(..) => report.num_kept_from_user += 1,
-                Element::Vacant => report.num_released_from_user += 1,
-            }
         }
-        report
     }
 }
 
 impl<T: StorageItem + Clone> Registry<T> {
     pub(crate) fn get(&self, id: Id<T::Marker>) -> T {
-        self.read().get(id)
+        self.storage.get(id)
     }
 }
 
@@ -128,7 +121,7 @@ impl<T: StorageItem + Clone> Registry<T> {
 mod tests {
     use super::Registry;
     use crate::{id::Marker, resource::ResourceType, storage::StorageItem};
-    use alloc::sync::Arc;
+    use alloc::{sync::Arc, vec::Vec};
 
     struct TestData;
     struct TestDataId;
@@ -157,4 +150,30 @@ mod tests {
             }
         })
     }
+
+    #[test]
+    fn read_sees_all_shards() {
+        let registry = Registry::new();
+        let ids: Vec<_> = (0..100)
+            .map(|_| registry.prepare(None).assign(Arc::new(TestData)))
+            .collect();
+        for &id in ids.iter().step_by(3) {
+            registry.remove(id);
+        }
+
+        let mut live: Vec<_> = registry.read().iter().map(|(id, _)| id).collect();
+        live.sort_by_key(|id| id.unzip());
+        let expected: Vec<_> = ids
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i % 3 != 0)
+            .map(|(_, &id)| id)
+            .collect();
+        assert_eq!(live, expected);
+
+        let guard = registry.read();
+        for &id in &expected {
+            assert!(Arc::ptr_eq(&guard.get(id), &registry.get(id)));
+        }
+    }
 }
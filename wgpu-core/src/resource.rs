@@ -17,7 +17,7 @@ use wgt::{
 #[cfg(feature = "trace")]
 use crate::device::trace;
 use crate::{
-    Label, LabelHelpers, SubmissionIndex,
+    Label, LabelHelpers, SubmissionIndex, api_log,
     binding_model::{BindGroup, BindingError},
     device::{
         BufferMapPendingClosure, Device, DeviceError, DeviceMismatch, HostMap,
@@ -25,7 +25,7 @@ use crate::{
     },
     init_tracker::{BufferInitTracker, TextureInitTracker},
     lock::{Mutex, RwLock, rank},
-    ray_tracing::{BlasCompactReadyPendingClosure, BlasPrepareCompactError},
+    ray_tracing::{BlasCompactReadyPendingClosure, BlasPrepareCompactError, BlasSerializeError},
     resource_log,
     snatch::{SnatchGuard, Snatchable},
     timestamp_normalization::TimestampNormalizationBindGroup,
@@ -355,6 +355,19 @@ impl WebGpuError for MissingTextureUsageError {
     }
 }
 
+#[derive(Clone, Debug, Error)]
+#[error("{first} and {second} share memory and can't be used at the same time")]
+pub struct AliasedTextureConflictError {
+    pub(crate) first: ResourceErrorIdent,
+    pub(crate) second: ResourceErrorIdent,
+}
+
+impl WebGpuError for AliasedTextureConflictError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        ErrorType::Validation
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[error("{0} has been destroyed")]
@@ -437,6 +450,8 @@ pub struct Buffer {
     pub(crate) bind_groups: Mutex<WeakVec<BindGroup>>,
     pub(crate) timestamp_normalization_bind_group: Snatchable<TimestampNormalizationBindGroup>,
     pub(crate) indirect_validation_bind_groups: Snatchable<crate::indirect_validation::BindGroups>,
+    /// The heaps memory has been bound from, if this is a sparse buffer.
+    pub(crate) sparse_heaps: Option<Mutex<Vec<Arc<MemoryHeap>>>>,
 }
 
 impl Drop for Buffer {
@@ -876,6 +891,11 @@ impl Buffer {
                 bind_groups,
                 timestamp_normalization_bind_group,
                 indirect_validation_bind_groups,
+                _sparse_heaps: self
+                    .sparse_heaps
+                    .as_ref()
+                    .map(|heaps| heaps.lock().clone())
+                    .unwrap_or_default(),
             })
         };
 
@@ -947,6 +967,8 @@ pub struct DestroyedBuffer {
     bind_groups: WeakVec<BindGroup>,
     timestamp_normalization_bind_group: Option<TimestampNormalizationBindGroup>,
     indirect_validation_bind_groups: Option<crate::indirect_validation::BindGroups>,
+    /// Keeps the memory bound to a sparse buffer alive until the buffer is gone.
+    _sparse_heaps: Vec<Arc<MemoryHeap>>,
 }
 
 impl DestroyedBuffer {
@@ -1183,6 +1205,10 @@ pub struct Texture {
     pub(crate) clear_mode: RwLock<TextureClearMode>,
     pub(crate) views: Mutex<WeakVec<TextureView>>,
     pub(crate) bind_groups: Mutex<WeakVec<BindGroup>>,
+    /// Where the texture lives, if it was placed in a [`MemoryHeap`].
+    pub(crate) aliasing: Option<TextureAliasing>,
+    /// The heaps memory has been bound from, if this is a sparse texture.
+    pub(crate) sparse_heaps: Option<Mutex<Vec<Arc<MemoryHeap>>>>,
 }
 
 impl Texture {
@@ -1218,6 +1244,20 @@ impl Texture {
             clear_mode: RwLock::new(rank::TEXTURE_CLEAR_MODE, clear_mode),
             views: Mutex::new(rank::TEXTURE_VIEWS, WeakVec::new()),
             bind_groups: Mutex::new(rank::TEXTURE_BIND_GROUPS, WeakVec::new()),
+            aliasing: None,
+            sparse_heaps: None,
+        }
+    }
+
+    /// Returns `true` if `self` and `other` are placed in overlapping memory.
+    pub(crate) fn aliases(&self, other: &Texture) -> bool {
+        match (&self.aliasing, &other.aliasing) {
+            (Some(a), Some(b)) => {
+                Arc::ptr_eq(&a.heap, &b.heap)
+                    && a.range.start < b.range.end
+                    && b.range.start < a.range.end
+            }
+            _ => false,
         }
     }
 
@@ -1362,6 +1402,12 @@ impl Texture {
                 views,
                 clear_mode: mem::replace(&mut *self.clear_mode.write(), TextureClearMode::None),
                 bind_groups,
+                _heap: self.aliasing.as_ref().map(|aliasing| aliasing.heap.clone()),
+                _sparse_heaps: self
+                    .sparse_heaps
+                    .as_ref()
+                    .map(|heaps| heaps.lock().clone())
+                    .unwrap_or_default(),
                 device: Arc::clone(&self.device),
                 label: self.label().to_owned(),
             })
@@ -1389,6 +1435,10 @@ pub struct DestroyedTexture {
     views: WeakVec<TextureView>,
     clear_mode: TextureClearMode,
     bind_groups: WeakVec<BindGroup>,
+    /// Keeps the memory of a placed texture alive until the texture is gone.
+    _heap: Option<Arc<MemoryHeap>>,
+    /// Keeps the memory bound to a sparse texture alive until the texture is gone.
+    _sparse_heaps: Vec<Arc<MemoryHeap>>,
     device: Arc<Device>,
     label: String,
 }
@@ -1433,6 +1483,200 @@ impl Drop for DestroyedTexture {
     }
 }
 
+pub type MemoryHeapDescriptor<'a> = wgt::MemoryHeapDescriptor<Label<'a>>;
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateMemoryHeapError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Memory heaps can't be empty")]
+    ZeroSize,
+}
+
+impl WebGpuError for CreateMemoryHeapError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::MissingFeatures(e) => e,
+
+            Self::ZeroSize => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+/// A block of device memory that textures can be placed in.
+///
+/// Textures placed in overlapping parts of a heap alias each other. See
+/// [`wgt::Features::TEXTURE_ALIASING`].
+#[derive(Debug)]
+pub struct MemoryHeap {
+    pub(crate) raw: ManuallyDrop<Box<dyn hal::DynMemoryHeap>>,
+    pub(crate) device: Arc<Device>,
+    pub(crate) size: wgt::BufferAddress,
+    /// The `label` from the descriptor used to create the resource.
+    pub(crate) label: String,
+}
+
+impl Drop for MemoryHeap {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw {}", self.error_ident());
+        // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
+        let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
+        unsafe {
+            self.device.raw().destroy_memory_heap(raw);
+        }
+    }
+}
+
+crate::impl_resource_type!(MemoryHeap);
+crate::impl_labeled!(MemoryHeap);
+crate::impl_parent_device!(MemoryHeap);
+crate::impl_storage_item!(MemoryHeap);
+
+impl MemoryHeap {
+    pub(crate) fn raw(&self) -> &dyn hal::DynMemoryHeap {
+        self.raw.as_ref()
+    }
+}
+
+/// The part of a [`MemoryHeap`] a texture was placed in.
+#[derive(Debug)]
+pub(crate) struct TextureAliasing {
+    pub(crate) heap: Arc<MemoryHeap>,
+    pub(crate) range: Range<wgt::BufferAddress>,
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateAliasedTextureError {
+    #[error(transparent)]
+    CreateTexture(#[from] CreateTextureError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+    #[error("Transient textures can't be placed in a memory heap")]
+    TransientUsage,
+    #[error("Offset {offset} in {heap} is not a multiple of the texture's alignment {alignment}")]
+    UnalignedOffset {
+        heap: ResourceErrorIdent,
+        offset: wgt::BufferAddress,
+        alignment: wgt::BufferAddress,
+    },
+    #[error(
+        "Texture needs {size} bytes at offset {offset}, which doesn't fit in {heap} of {heap_size} bytes"
+    )]
+    OutOfBounds {
+        heap: ResourceErrorIdent,
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+        heap_size: wgt::BufferAddress,
+    },
+}
+
+impl From<DeviceError> for CreateAliasedTextureError {
+    fn from(error: DeviceError) -> Self {
+        Self::CreateTexture(error.into())
+    }
+}
+
+impl WebGpuError for CreateAliasedTextureError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::CreateTexture(e) => e,
+            Self::MissingFeatures(e) => e,
+            Self::InvalidResource(e) => e,
+
+            Self::TransientUsage | Self::UnalignedOffset { .. } | Self::OutOfBounds { .. } => {
+                return ErrorType::Validation;
+            }
+        };
+        e.webgpu_error_type()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateSparseBufferError {
+    #[error(transparent)]
+    CreateBuffer(#[from] CreateBufferError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Sparse buffers can't be mapped, but usage {0:?} contains map usages")]
+    MapUsage(wgt::BufferUsages),
+    #[error("Sparse buffers can't be mapped at creation")]
+    MappedAtCreation,
+}
+
+impl From<DeviceError> for CreateSparseBufferError {
+    fn from(error: DeviceError) -> Self {
+        Self::CreateBuffer(error.into())
+    }
+}
+
+impl WebGpuError for CreateSparseBufferError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::CreateBuffer(e) => e,
+            Self::MissingFeatures(e) => e,
+
+            Self::MapUsage(_) | Self::MappedAtCreation => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateSparseTextureError {
+    #[error(transparent)]
+    CreateTexture(#[from] CreateTextureError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Transient textures can't be sparse")]
+    TransientUsage,
+    #[error("Sparse textures must be 2D, not {0:?}")]
+    InvalidDimension(wgt::TextureDimension),
+    #[error("Sparse textures can't be multisampled, but the sample count is {0}")]
+    InvalidSampleCount(u32),
+    #[error("Format {0:?} can't be used for sparse textures")]
+    UnsupportedFormat(wgt::TextureFormat),
+    #[error(
+        "Mip level {mip_level} of size {size:?} is not made of whole tiles of {tile_size:?} texels"
+    )]
+    PartialTiles {
+        mip_level: u32,
+        size: wgt::Extent3d,
+        tile_size: wgt::Extent3d,
+    },
+}
+
+impl From<DeviceError> for CreateSparseTextureError {
+    fn from(error: DeviceError) -> Self {
+        Self::CreateTexture(error.into())
+    }
+}
+
+impl WebGpuError for CreateSparseTextureError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::CreateTexture(e) => e,
+            Self::MissingFeatures(e) => e,
+
+            Self::TransientUsage
+            | Self::InvalidDimension(_)
+            | Self::InvalidSampleCount(_)
+            | Self::UnsupportedFormat(_)
+            | Self::PartialTiles { .. } => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum TextureErrorDimension {
     X,
@@ -1500,6 +1744,10 @@ pub enum CreateTextureError {
     CreateTextureView(#[from] CreateTextureViewError),
     #[error("Invalid usage flags {0:?}")]
     InvalidUsage(wgt::TextureUsages),
+    #[error(
+        "Texture usage TRANSIENT must be combined with RENDER_ATTACHMENT and no other usage, but the usage is {0:?}"
+    )]
+    InvalidTransientUsage(wgt::TextureUsages),
     #[error(transparent)]
     InvalidDimension(#[from] TextureDimensionError),
     #[error("Depth texture ({1:?}) can't be created as {0:?}")]
@@ -1559,6 +1807,7 @@ impl WebGpuError for CreateTextureError {
             Self::MissingDownlevelFlags(e) => e,
 
             Self::InvalidUsage(_)
+            | Self::InvalidTransientUsage(_)
             | Self::InvalidDepthDimension(_, _)
             | Self::InvalidCompressedDimension(_, _)
             | Self::InvalidMipLevelCount { .. }
@@ -1574,6 +1823,48 @@ impl WebGpuError for CreateTextureError {
     }
 }
 
+#[cfg(metal)]
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateTextureFromIoSurfaceError {
+    #[error(transparent)]
+    Texture(#[from] CreateTextureError),
+    #[error("Textures can only be created from IOSurfaces on Metal devices, not {0:?}")]
+    UnsupportedBackend(wgt::Backend),
+    #[error(
+        "Textures created from IOSurfaces must be 2D with a single mip level, array layer and sample, got {dimension:?} with {mip_level_count} mip levels, {depth_or_array_layers} array layers and {sample_count} samples"
+    )]
+    InvalidDescriptor {
+        dimension: wgt::TextureDimension,
+        mip_level_count: u32,
+        depth_or_array_layers: u32,
+        sample_count: u32,
+    },
+    #[error("IOSurface has no plane {0}")]
+    InvalidPlane(u32),
+    #[error(
+        "Texture size {texture_size:?} doesn't match the size {plane_size:?} of IOSurface plane {plane}"
+    )]
+    SizeMismatch {
+        plane: u32,
+        texture_size: (u32, u32),
+        plane_size: (u32, u32),
+    },
+}
+
+#[cfg(metal)]
+impl WebGpuError for CreateTextureFromIoSurfaceError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        match self {
+            Self::Texture(e) => e.webgpu_error_type(),
+            Self::UnsupportedBackend(_)
+            | Self::InvalidDescriptor { .. }
+            | Self::InvalidPlane(_)
+            | Self::SizeMismatch { .. } => ErrorType::Validation,
+        }
+    }
+}
+
 /// Describes a [`TextureView`].
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1993,6 +2284,96 @@ impl QuerySet {
     }
 }
 
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateSharedFenceError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("{0:?} devices can't import this kind of shared fence handle")]
+    UnsupportedHandle(wgt::Backend),
+}
+
+impl WebGpuError for CreateSharedFenceError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::MissingFeatures(e) => e,
+
+            Self::UnsupportedHandle(_) => return ErrorType::Validation,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum ExportSharedFenceError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    InvalidResource(#[from] InvalidResourceError),
+}
+
+impl WebGpuError for ExportSharedFenceError {
+    fn webgpu_error_type(&self) -> ErrorType {
+        let e: &dyn WebGpuError = match self {
+            Self::Device(e) => e,
+            Self::InvalidResource(e) => e,
+        };
+        e.webgpu_error_type()
+    }
+}
+
+pub type SharedFenceDescriptor<'a> = wgt::SharedFenceDescriptor<Label<'a>>;
+
+/// A fence that can be shared with other APIs or processes.
+///
+/// Submissions can wait for a shared fence to reach a value, and signal it
+/// with a value once they complete.
+#[derive(Debug)]
+pub struct SharedFence {
+    pub(crate) raw: ManuallyDrop<Box<dyn hal::DynFence>>,
+    pub(crate) device: Arc<Device>,
+    /// The `label` from the descriptor used to create the resource.
+    pub(crate) label: String,
+}
+
+impl Drop for SharedFence {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw {}", self.error_ident());
+        // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
+        let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
+        unsafe {
+            self.device.raw().destroy_fence(raw);
+        }
+    }
+}
+
+crate::impl_resource_type!(SharedFence);
+crate::impl_labeled!(SharedFence);
+crate::impl_parent_device!(SharedFence);
+crate::impl_storage_item!(SharedFence);
+
+impl SharedFence {
+    pub(crate) fn raw(&self) -> &dyn hal::DynFence {
+        self.raw.as_ref()
+    }
+
+    /// Export a platform handle to this fence.
+    ///
+    /// See [`wgt::SharedFenceHandle`] for who owns the returned handle.
+    pub fn export(&self) -> Result<wgt::SharedFenceHandle, ExportSharedFenceError> {
+        api_log!("SharedFence::export {}", self.error_ident());
+
+        self.device.check_is_valid()?;
+
+        unsafe { self.device.raw().export_fence(self.raw()) }
+            .map_err(|e| self.device.handle_hal_error_with_nonfatal_oom(e).into())
+    }
+}
+
 pub type BlasDescriptor<'a> = wgt::CreateBlasDescriptor<Label<'a>>;
 pub type TlasDescriptor<'a> = wgt::CreateTlasDescriptor<Label<'a>>;
 
@@ -2018,6 +2399,126 @@ impl fmt::Debug for BlasPendingCompact {
     }
 }
 
+pub type BlasSerializeResult = Result<Vec<u8>, BlasSerializeError>;
+
+#[cfg(send_sync)]
+pub type BlasSerializeCallback = Box<dyn FnOnce(BlasSerializeResult) + Send + 'static>;
+#[cfg(not(send_sync))]
+pub type BlasSerializeCallback = Box<dyn FnOnce(BlasSerializeResult) + 'static>;
+
+/// A buffer holding a serialized BLAS, or its serialized size, on its way to or from the
+/// host.
+#[derive(Debug)]
+pub struct SerializedBlasBuffer {
+    raw: ManuallyDrop<Box<dyn hal::DynBuffer>>,
+    device: Arc<Device>,
+    size: wgt::BufferAddress,
+}
+
+impl SerializedBlasBuffer {
+    pub(crate) fn new(
+        device: &Arc<Device>,
+        size: wgt::BufferAddress,
+        usage: wgt::BufferUses,
+    ) -> Result<Self, DeviceError> {
+        let raw = unsafe {
+            device
+                .raw()
+                .create_buffer(&hal::BufferDescriptor {
+                    label: Some("(wgpu internal) serialized BLAS buffer"),
+                    size,
+                    usage,
+                    memory_flags: hal::MemoryFlags::PREFER_COHERENT,
+                })
+                .map_err(DeviceError::from_hal)?
+        };
+        Ok(Self {
+            raw: ManuallyDrop::new(raw),
+            device: device.clone(),
+            size,
+        })
+    }
+
+    pub(crate) fn raw(&self) -> &dyn hal::DynBuffer {
+        self.raw.as_ref()
+    }
+
+    /// Reads the contents of the buffer, which must have been created with
+    /// [`wgt::BufferUses::MAP_READ`].
+    pub(crate) fn read(&self) -> Result<Vec<u8>, DeviceError> {
+        let raw = self.raw();
+        unsafe {
+            let mapping = self
+                .device
+                .raw()
+                .map_buffer(raw, 0..self.size)
+                .map_err(DeviceError::from_hal)?;
+            if !mapping.is_coherent {
+                #[expect(clippy::single_range_in_vec_init)]
+                self.device
+                    .raw()
+                    .invalidate_mapped_ranges(raw, &[0..self.size]);
+            }
+            let data =
+                core::slice::from_raw_parts(mapping.ptr.as_ptr(), self.size as usize).to_vec();
+            self.device.raw().unmap_buffer(raw);
+            Ok(data)
+        }
+    }
+
+    /// Writes `data` to the start of the buffer, which must have been created with
+    /// [`wgt::BufferUses::MAP_WRITE`].
+    pub(crate) fn write(&self, data: &[u8]) -> Result<(), DeviceError> {
+        let raw = self.raw();
+        unsafe {
+            let mapping = self
+                .device
+                .raw()
+                .map_buffer(raw, 0..self.size)
+                .map_err(DeviceError::from_hal)?;
+            core::ptr::copy_nonoverlapping(data.as_ptr(), mapping.ptr.as_ptr(), data.len());
+            if !mapping.is_coherent {
+                #[expect(clippy::single_range_in_vec_init)]
+                self.device.raw().flush_mapped_ranges(raw, &[0..self.size]);
+            }
+            self.device.raw().unmap_buffer(raw);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SerializedBlasBuffer {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw SerializedBlasBuffer");
+        // SAFETY: We are in the Drop impl and we don't use self.raw anymore after this point.
+        let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
+        unsafe { self.device.raw().destroy_buffer(raw) };
+    }
+}
+
+/// A BLAS being serialized, waiting for the submission that writes `buffer` to complete.
+///
+/// Serialization takes two steps: the serialized size is read back first, so that a
+/// buffer large enough for the serialized data can be made for the second.
+pub(crate) struct BlasPendingSerialization {
+    pub(crate) blas: Arc<Blas>,
+    pub(crate) buffer: SerializedBlasBuffer,
+    /// Whether `buffer` will hold the serialized size, rather than the serialized data.
+    pub(crate) reading_size: bool,
+    pub(crate) callback: BlasSerializeCallback,
+}
+
+impl fmt::Debug for BlasPendingSerialization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlasPendingSerialization")
+            .field("blas", &self.blas)
+            .field("buffer", &self.buffer)
+            .field("reading_size", &self.reading_size)
+            .field("callback", &())
+            .finish()
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum BlasCompactState {
     /// Created from a compact operation.
@@ -2044,6 +2545,9 @@ pub struct Blas {
     pub(crate) flags: wgt::AccelerationStructureFlags,
     pub(crate) update_mode: wgt::AccelerationStructureUpdateMode,
     pub(crate) built_index: RwLock<Option<NonZeroU64>>,
+    /// The vertex and index count of each geometry in the build that set `built_index`,
+    /// which a refit has to match. `None` if unknown.
+    pub(crate) built_counts: RwLock<Option<Vec<u32>>>,
     pub(crate) handle: u64,
     /// The `label` from the descriptor used to create the resource.
     pub(crate) label: String,
@@ -2195,6 +2699,9 @@ pub struct Tlas {
     pub(crate) flags: wgt::AccelerationStructureFlags,
     pub(crate) update_mode: wgt::AccelerationStructureUpdateMode,
     pub(crate) built_index: RwLock<Option<NonZeroU64>>,
+    /// The instance count of the build that set `built_index`, which a refit has to match.
+    /// `None` if unknown.
+    pub(crate) built_instance_count: RwLock<Option<u32>>,
     pub(crate) dependencies: RwLock<Vec<Arc<Blas>>>,
     pub(crate) instance_buffer: ManuallyDrop<Box<dyn hal::DynBuffer>>,
     /// The `label` from the descriptor used to create the resource.
@@ -14,13 +14,41 @@ pub struct ScratchBuffer {
 
 impl ScratchBuffer {
     pub(crate) fn new(device: &Arc<Device>, size: wgt::BufferSize) -> Result<Self, DeviceError> {
+        Self::with_usage(
+            device,
+            "(wgpu) scratch buffer",
+            size,
+            BufferUses::ACCELERATION_STRUCTURE_SCRATCH,
+        )
+    }
+
+    /// Creates a device-local buffer that staged data can be copied through,
+    /// e.g. to repack rows of a texture upload.
+    pub(crate) fn new_copy(
+        device: &Arc<Device>,
+        size: wgt::BufferSize,
+    ) -> Result<Self, DeviceError> {
+        Self::with_usage(
+            device,
+            "(wgpu) copy scratch buffer",
+            size,
+            BufferUses::COPY_SRC | BufferUses::COPY_DST,
+        )
+    }
+
+    fn with_usage(
+        device: &Arc<Device>,
+        label: &str,
+        size: wgt::BufferSize,
+        usage: BufferUses,
+    ) -> Result<Self, DeviceError> {
         let raw = unsafe {
             device
                 .raw()
                 .create_buffer(&hal::BufferDescriptor {
-                    label: Some("(wgpu) scratch buffer"),
+                    label: Some(label),
                     size: size.get(),
-                    usage: BufferUses::ACCELERATION_STRUCTURE_SCRATCH,
+                    usage,
                     memory_flags: hal::MemoryFlags::empty(),
                 })
                 .map_err(DeviceError::from_hal)?
@@ -1,13 +1,24 @@
 use alloc::{sync::Arc, vec::Vec};
 use core::mem;
 
+use arrayvec::ArrayVec;
+
 use crate::id::{Id, Marker};
+use crate::lock::{RwLock, RwLockReadGuard, rank};
 use crate::resource::ResourceType;
 use crate::{Epoch, Index};
 
+/// The number of shards a [`Storage`] is split into.
+///
+/// Ids are assigned to shards by their index, round-robin. Since
+/// `IdentityManager` hands out consecutive indices, resources created
+/// concurrently usually land in different shards and don't contend on the
+/// same lock.
+const SHARD_COUNT: usize = 16;
+
 /// An entry in a `Storage::map` table.
 #[derive(Debug)]
-pub(crate) enum Element<T>
+enum Element<T>
 where
     T: StorageItem,
 {
@@ -42,44 +53,162 @@ macro_rules! impl_storage_item {
 
 /// A table of `T` values indexed by the id type `I`.
 ///
-/// `Storage` implements [`core::ops::Index`], accepting `Id` values as
-/// indices.
-///
-/// The table is represented as a vector indexed by the ids' index
-/// values, so you should use an id allocator like `IdentityManager`
-/// that keeps the index values dense and close to zero.
+/// The table is split into [`SHARD_COUNT`] shards, each behind its own
+/// lock, so that inserting and removing values from multiple threads
+/// scales with the number of threads. Each shard is represented as a
+/// vector indexed by the ids' index values, so you should use an id
+/// allocator like `IdentityManager` that keeps the index values dense and
+/// close to zero.
 #[derive(Debug)]
 pub(crate) struct Storage<T>
 where
     T: StorageItem,
 {
-    pub(crate) map: Vec<Element<T>>,
-    kind: &'static str,
+    shards: [RwLock<Shard<T>>; SHARD_COUNT],
 }
 
+// These are the bounds the auto traits would derive through `RwLock`. Stating
+// them directly keeps the compiler from recursing through every resource type
+// reachable from `T` when checking whether `Global` is `Send` or `Sync`.
+unsafe impl<T: StorageItem + Send + Sync> Send for Storage<T> {}
+unsafe impl<T: StorageItem + Send + Sync> Sync for Storage<T> {}
+
 impl<T> Storage<T>
 where
     T: StorageItem,
 {
     pub(crate) fn new() -> Self {
         Self {
-            map: Vec::new(),
-            kind: T::TYPE,
+            shards: core::array::from_fn(|shard| {
+                RwLock::new(
+                    rank::REGISTRY_STORAGE,
+                    Shard {
+                        map: Vec::new(),
+                        shard: shard as Index,
+                        kind: T::TYPE,
+                    },
+                )
+            }),
+        }
+    }
+
+    fn shard(&self, id: Id<T::Marker>) -> &RwLock<Shard<T>> {
+        let (index, _) = id.unzip();
+        &self.shards[index as usize % SHARD_COUNT]
+    }
+
+    pub(crate) fn insert(&self, id: Id<T::Marker>, value: T) {
+        self.shard(id).write().insert(id, value)
+    }
+
+    pub(crate) fn remove(&self, id: Id<T::Marker>) -> T {
+        self.shard(id).write().remove(id)
+    }
+
+    /// Locks all shards for reading.
+    ///
+    /// Prefer [`Storage::get`] when looking up a single value, which only
+    /// locks the shard containing it.
+    #[track_caller]
+    pub(crate) fn read(&self) -> StorageReadGuard<'_, T> {
+        StorageReadGuard {
+            shards: self.shards.iter().map(|shard| shard.read()).collect(),
         }
     }
+
+    /// Returns the number of occupied and vacant entries.
+    pub(crate) fn count(&self) -> (usize, usize) {
+        let mut occupied = 0;
+        let mut vacant = 0;
+        for shard in &self.shards {
+            for element in shard.read().map.iter() {
+                match *element {
+                    Element::Occupied(..) => occupied += 1,
+                    Element::Vacant => vacant += 1,
+                }
+            }
+        }
+        (occupied, vacant)
+    }
 }
 
 impl<T> Storage<T>
+where
+    T: StorageItem + Clone,
+{
+    /// Get an owned reference to an item.
+    /// Panics if there is an epoch mismatch, the entry is empty or in error.
+    pub(crate) fn get(&self, id: Id<T::Marker>) -> T {
+        self.shard(id).read().get(id)
+    }
+}
+
+/// Read access to all shards of a [`Storage`].
+///
+/// Holding this blocks insertions into, and removals from, the storage, so
+/// only hold it while looking up several values at once.
+pub(crate) struct StorageReadGuard<'a, T>
+where
+    T: StorageItem,
+{
+    shards: ArrayVec<RwLockReadGuard<'a, Shard<T>>, SHARD_COUNT>,
+}
+
+impl<T> StorageReadGuard<'_, T>
 where
     T: StorageItem,
 {
-    pub(crate) fn insert(&mut self, id: Id<T::Marker>, value: T) {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Id<T::Marker>, &T)> {
+        self.shards.iter().flat_map(|shard| shard.iter())
+    }
+}
+
+impl<T> StorageReadGuard<'_, T>
+where
+    T: StorageItem + Clone,
+{
+    /// Get an owned reference to an item.
+    /// Panics if there is an epoch mismatch, the entry is empty or in error.
+    pub(crate) fn get(&self, id: Id<T::Marker>) -> T {
+        let (index, _) = id.unzip();
+        self.shards[index as usize % SHARD_COUNT].get(id)
+    }
+}
+
+impl<T> Drop for StorageReadGuard<'_, T>
+where
+    T: StorageItem,
+{
+    fn drop(&mut self) {
+        // Release the locks in the reverse order of acquisition.
+        while self.shards.pop().is_some() {}
+    }
+}
+
+/// One shard of a [`Storage`], holding the ids whose index is congruent to
+/// `shard` modulo [`SHARD_COUNT`].
+#[derive(Debug)]
+struct Shard<T>
+where
+    T: StorageItem,
+{
+    /// Indexed by the ids' index divided by [`SHARD_COUNT`].
+    map: Vec<Element<T>>,
+    shard: Index,
+    kind: &'static str,
+}
+
+impl<T> Shard<T>
+where
+    T: StorageItem,
+{
+    fn insert(&mut self, id: Id<T::Marker>, value: T) {
         let (index, epoch) = id.unzip();
-        let index = index as usize;
-        if index >= self.map.len() {
-            self.map.resize_with(index + 1, || Element::Vacant);
+        let local = index as usize / SHARD_COUNT;
+        if local >= self.map.len() {
+            self.map.resize_with(local + 1, || Element::Vacant);
         }
-        match mem::replace(&mut self.map[index], Element::Occupied(value, epoch)) {
+        match mem::replace(&mut self.map[local], Element::Occupied(value, epoch)) {
             Element::Vacant => {}
             Element::Occupied(_, storage_epoch) => {
                 assert_ne!(
@@ -92,9 +221,10 @@ where
         }
     }
 
-    pub(crate) fn remove(&mut self, id: Id<T::Marker>) -> T {
+    fn remove(&mut self, id: Id<T::Marker>) -> T {
         let (index, epoch) = id.unzip();
-        match mem::replace(&mut self.map[index as usize], Element::Vacant) {
+        let local = index as usize / SHARD_COUNT;
+        match mem::replace(&mut self.map[local], Element::Vacant) {
             Element::Occupied(value, storage_epoch) => {
                 assert_eq!(epoch, storage_epoch, "id epoch mismatch");
                 value
@@ -103,28 +233,27 @@ where
         }
     }
 
-    pub(crate) fn iter(&self) -> impl Iterator<Item = (Id<T::Marker>, &T)> {
+    fn iter(&self) -> impl Iterator<Item = (Id<T::Marker>, &T)> {
         self.map
             .iter()
             .enumerate()
-            .filter_map(move |(index, x)| match *x {
+            .filter_map(move |(local, x)| match *x {
                 Element::Occupied(ref value, storage_epoch) => {
-                    Some((Id::zip(index as Index, storage_epoch), value))
+                    let index = local as Index * SHARD_COUNT as Index + self.shard;
+                    Some((Id::zip(index, storage_epoch), value))
                 }
                 _ => None,
             })
     }
 }
 
-impl<T> Storage<T>
+impl<T> Shard<T>
 where
     T: StorageItem + Clone,
 {
-    /// Get an owned reference to an item.
-    /// Panics if there is an epoch mismatch, the entry is empty or in error.
-    pub(crate) fn get(&self, id: Id<T::Marker>) -> T {
+    fn get(&self, id: Id<T::Marker>) -> T {
         let (index, epoch) = id.unzip();
-        let (result, storage_epoch) = match self.map.get(index as usize) {
+        let (result, storage_epoch) = match self.map.get(index as usize / SHARD_COUNT) {
             Some(&Element::Occupied(ref v, epoch)) => (v.clone(), epoch),
             None | Some(&Element::Vacant) => panic!("{}[{:?}] does not exist", self.kind, id),
         };
@@ -29,7 +29,7 @@
 
 use core::num::NonZeroU64;
 
-use alloc::{boxed::Box, string::String, string::ToString, sync::Arc};
+use alloc::{boxed::Box, string::String, string::ToString, sync::Arc, vec::Vec};
 
 use hashbrown::HashMap;
 use wgt::PushConstantRange;
@@ -140,6 +140,7 @@ impl TimestampNormalizer {
             let module = naga::front::wgsl::parse_str(&preprocessed_src).map_err(|inner| {
                 TimestampNormalizerInitError::ParseWgsl(naga::error::ShaderError {
                     source: preprocessed_src.clone(),
+                    files: Vec::new(),
                     label: None,
                     inner: Box::new(inner),
                 })
@@ -158,6 +159,7 @@ impl TimestampNormalizer {
             .map_err(|inner| {
                 TimestampNormalizerInitError::ValidateWgsl(naga::error::ShaderError {
                     source: preprocessed_src.clone(),
+                    files: Vec::new(),
                     label: None,
                     inner: Box::new(inner),
                 })
@@ -170,6 +172,7 @@ impl TimestampNormalizer {
             let hal_desc = hal::ShaderModuleDescriptor {
                 label: None,
                 runtime_checks: wgt::ShaderRuntimeChecks::unchecked(),
+                optimization: wgt::ShaderOptimization::Default,
             };
             let module = device
                 .raw()
@@ -338,6 +341,7 @@ impl TimestampNormalizer {
             encoder.begin_compute_pass(&hal::ComputePassDescriptor {
                 label: Some("Timestamp normalization pass"),
                 timestamp_writes: None,
+                pipeline_statistics_query: None,
             });
             encoder.set_compute_pipeline(&*state.pipeline);
             encoder.set_bind_group(&*state.pipeline_layout, 0, Some(bind_group), &[]);
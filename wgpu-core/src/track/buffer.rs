@@ -12,7 +12,7 @@ use alloc::{
 use hal::BufferBarrier;
 use wgt::{BufferUses, strict_assert, strict_assert_eq};
 
-use super::{PendingTransition, TrackerIndex};
+use super::{BarrierLog, PendingTransition, TrackerIndex};
 use crate::{
     resource::{Buffer, Trackable},
     snatch::SnatchGuard,
@@ -102,6 +102,11 @@ impl BufferUsageScope {
         self.metadata.clear();
     }
 
+    /// Returns the indices of all the buffers in the scope.
+    pub fn tracker_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.metadata.owned_indices()
+    }
+
     /// Sets the size of all the vectors inside the tracker.
     ///
     /// Must be called with the highest possible Buffer ID before
@@ -319,6 +324,17 @@ impl BufferTracker {
         self.metadata.owned_resources()
     }
 
+    /// Returns the currently pending transitions with their buffers, without
+    /// draining them.
+    pub fn pending_transitions(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<Buffer>, &PendingTransition<BufferUses>)> {
+        self.temp.iter().map(|pending| {
+            let buf = unsafe { self.metadata.get_resource_unchecked(pending.id as _) };
+            (buf, pending)
+        })
+    }
+
     /// Drains all currently pending transitions.
     pub fn drain_transitions<'a, 'b: 'a>(
         &'b mut self,
@@ -622,6 +638,18 @@ impl DeviceBufferTracker {
         self.temp.pop()
     }
 
+    /// Returns true if getting the buffer with the given index into the state
+    /// it starts in in `tracker` needs a barrier.
+    pub fn needs_barrier(&self, tracker: &BufferTracker, index: usize) -> bool {
+        if !tracker.metadata.contains(index) {
+            return false;
+        }
+        let start = tracker.start[index];
+        self.current_states
+            .get(index)
+            .is_none_or(|&current| !skip_barrier(current, start))
+    }
+
     /// Sets the given state for all buffers in the given tracker.
     ///
     /// If a transition is needed to get the buffers into the needed state,
@@ -630,6 +658,7 @@ impl DeviceBufferTracker {
         &'a mut self,
         tracker: &'a BufferTracker,
         snatch_guard: &'b SnatchGuard<'b>,
+        barrier_log: Option<&mut BarrierLog>,
     ) -> impl Iterator<Item = BufferBarrier<'a, dyn hal::DynBuffer>> {
         for index in tracker.metadata.owned_indices() {
             self.tracker_assert_in_bounds(index);
@@ -651,6 +680,13 @@ impl DeviceBufferTracker {
             unsafe { update(&mut self.current_states, index, end_state_provider) };
         }
 
+        if let Some(barrier_log) = barrier_log {
+            for pending in &self.temp {
+                let buf = unsafe { tracker.metadata.get_resource_unchecked(pending.id as _) };
+                barrier_log.buffer(buf, pending);
+            }
+        }
+
         self.temp.drain(..).map(|pending| {
             let buf = unsafe { tracker.metadata.get_resource_unchecked(pending.id as _) };
             pending.into_hal(buf, snatch_guard)
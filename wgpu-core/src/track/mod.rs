@@ -111,7 +111,7 @@ use crate::{
     track::blas::BlasTracker,
 };
 
-use alloc::{sync::Arc, vec::Vec};
+use alloc::{string::String, sync::Arc, vec::Vec};
 use core::{fmt, mem, ops};
 
 use thiserror::Error;
@@ -228,6 +228,7 @@ pub(crate) struct TrackerIndexAllocators {
     pub samplers: Arc<SharedTrackerIndexAllocator>,
     pub bind_groups: Arc<SharedTrackerIndexAllocator>,
     pub compute_pipelines: Arc<SharedTrackerIndexAllocator>,
+    pub ray_tracing_pipelines: Arc<SharedTrackerIndexAllocator>,
     pub render_pipelines: Arc<SharedTrackerIndexAllocator>,
     pub bundles: Arc<SharedTrackerIndexAllocator>,
     pub query_sets: Arc<SharedTrackerIndexAllocator>,
@@ -244,6 +245,7 @@ impl TrackerIndexAllocators {
             samplers: Arc::new(SharedTrackerIndexAllocator::new()),
             bind_groups: Arc::new(SharedTrackerIndexAllocator::new()),
             compute_pipelines: Arc::new(SharedTrackerIndexAllocator::new()),
+            ray_tracing_pipelines: Arc::new(SharedTrackerIndexAllocator::new()),
             render_pipelines: Arc::new(SharedTrackerIndexAllocator::new()),
             bundles: Arc::new(SharedTrackerIndexAllocator::new()),
             query_sets: Arc::new(SharedTrackerIndexAllocator::new()),
@@ -309,6 +311,85 @@ impl PendingTransition<wgt::TextureUses> {
     }
 }
 
+/// Logs the barriers inserted from trackers for one pass or command while barrier
+/// logging is enabled on the device, and adds their counts to the device's barrier
+/// report when dropped.
+///
+/// See [`Device::barrier_logging`](crate::device::Device::barrier_logging).
+pub(crate) struct BarrierLog {
+    device: Arc<crate::device::Device>,
+    origin: String,
+    buffer_barriers: u32,
+    texture_barriers: u32,
+}
+
+impl BarrierLog {
+    /// Returns a log for the barriers inserted for `origin`, if barrier logging is
+    /// enabled on `device`.
+    pub fn new(
+        device: &Arc<crate::device::Device>,
+        origin: impl FnOnce() -> String,
+    ) -> Option<Self> {
+        device
+            .barrier_logging
+            .load(core::sync::atomic::Ordering::Relaxed)
+            .then(|| Self {
+                device: device.clone(),
+                origin: origin(),
+                buffer_barriers: 0,
+                texture_barriers: 0,
+            })
+    }
+
+    pub fn buffer(
+        &mut self,
+        buffer: &resource::Buffer,
+        pending: &PendingTransition<wgt::BufferUses>,
+    ) {
+        self.buffer_barriers += 1;
+        log::info!(
+            "{}: barrier on {}: {:?} -> {:?}",
+            self.origin,
+            buffer.error_ident(),
+            pending.usage.from,
+            pending.usage.to,
+        );
+    }
+
+    pub fn texture(
+        &mut self,
+        texture: &resource::Texture,
+        pending: &PendingTransition<wgt::TextureUses>,
+    ) {
+        self.texture_barriers += 1;
+        log::info!(
+            "{}: barrier on {} (mips {:?}, layers {:?}): {:?} -> {:?}",
+            self.origin,
+            texture.error_ident(),
+            pending.selector.mips,
+            pending.selector.layers,
+            pending.usage.from,
+            pending.usage.to,
+        );
+    }
+}
+
+impl Drop for BarrierLog {
+    fn drop(&mut self) {
+        if self.buffer_barriers == 0 && self.texture_barriers == 0 {
+            return;
+        }
+        self.device
+            .barrier_report
+            .lock()
+            .push(wgt::PassBarrierReport {
+                origin: mem::take(&mut self.origin),
+                buffer_barriers: self.buffer_barriers,
+                texture_barriers: self.texture_barriers,
+            });
+    }
+}
+
 /// The uses that a resource or subresource can be in.
 pub(crate) trait ResourceUses:
     fmt::Debug + ops::BitAnd<Output = Self> + ops::BitOr<Output = Self> + PartialEq + Sized + Copy
@@ -617,6 +698,7 @@ pub(crate) struct Tracker {
     pub views: StatelessTracker<resource::TextureView>,
     pub bind_groups: StatelessTracker<binding_model::BindGroup>,
     pub compute_pipelines: StatelessTracker<pipeline::ComputePipeline>,
+    pub ray_tracing_pipelines: StatelessTracker<pipeline::RayTracingPipeline>,
     pub render_pipelines: StatelessTracker<pipeline::RenderPipeline>,
     pub bundles: StatelessTracker<command::RenderBundle>,
     pub query_sets: StatelessTracker<resource::QuerySet>,
@@ -632,6 +714,7 @@ impl Tracker {
             views: StatelessTracker::new(),
             bind_groups: StatelessTracker::new(),
             compute_pipelines: StatelessTracker::new(),
+            ray_tracing_pipelines: StatelessTracker::new(),
             render_pipelines: StatelessTracker::new(),
             bundles: StatelessTracker::new(),
             query_sets: StatelessTracker::new(),
@@ -676,4 +759,20 @@ impl Tracker {
                 .set_and_remove_from_usage_scope_sparse(&mut scope.textures, &bind_group.views)
         };
     }
+
+    /// Adds the resources `other` keeps alive, without any state.
+    ///
+    /// Used for the parts of a render pass, which only keep resources alive
+    /// and leave their state to the usage scope of the pass.
+    pub fn extend_stateless(&mut self, other: Self) {
+        self.tlas_s.extend(other.tlas_s);
+        self.views.extend(other.views);
+        self.bind_groups.extend(other.bind_groups);
+        self.compute_pipelines.extend(other.compute_pipelines);
+        self.ray_tracing_pipelines
+            .extend(other.ray_tracing_pipelines);
+        self.render_pipelines.extend(other.render_pipelines);
+        self.bundles.extend(other.bundles);
+        self.query_sets.extend(other.query_sets);
+    }
 }
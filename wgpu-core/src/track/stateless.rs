@@ -24,6 +24,11 @@ impl<T> StatelessTracker<T> {
         self.resources.push(resource);
         unsafe { self.resources.last().unwrap_unchecked() }
     }
+
+    /// Inserts all resources of `other` into the resource tracker.
+    pub fn extend(&mut self, other: Self) {
+        self.resources.extend(other.resources);
+    }
 }
 
 impl<'a, T> IntoIterator for &'a StatelessTracker<T> {
@@ -18,7 +18,7 @@
 //!   is known to be in some undefined state. Any transition away from UNINITIALIZED
 //!   will treat the contents as junk.
 
-use super::{PendingTransition, PendingTransitionList, range::RangedStates};
+use super::{BarrierLog, PendingTransition, PendingTransitionList, range::RangedStates};
 use crate::{
     resource::{RawResourceAccess, Texture, TextureInner, TextureView, Trackable},
     snatch::SnatchGuard,
@@ -294,6 +294,11 @@ impl TextureUsageScope {
         self.metadata.set_size(size);
     }
 
+    /// Returns the indices of all the textures in the scope.
+    pub fn tracker_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.metadata.owned_indices()
+    }
+
     /// Returns true if the tracker owns no resources.
     ///
     /// This is a O(n) operation.
@@ -466,6 +471,17 @@ impl TextureTracker {
     pub fn used_resources(&self) -> impl Iterator<Item = &Arc<Texture>> + '_ {
         self.metadata.owned_resources()
     }
+    /// Returns the currently pending transitions with their textures, without
+    /// draining them.
+    pub fn pending_transitions(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<Texture>, &PendingTransition<TextureUses>)> {
+        self.temp.iter().map(|pending| {
+            let tex = unsafe { self.metadata.get_resource_unchecked(pending.id as _) };
+            (tex, pending)
+        })
+    }
+
     /// Drain all currently pending transitions.
     pub fn drain_transitions<'a>(
         &'a mut self,
@@ -768,6 +784,31 @@ impl DeviceTextureTracker {
         self.temp.drain(..)
     }
 
+    /// Returns true if getting the texture with the given index into the state
+    /// it starts in in `tracker` needs a barrier.
+    pub fn needs_barrier(&self, tracker: &TextureTracker, index: usize) -> bool {
+        if !tracker.metadata.contains(index) {
+            return false;
+        }
+        if index >= self.current_state_set.size() {
+            return true;
+        }
+        let mut barriers = Vec::new();
+        unsafe {
+            let texture_selector = &tracker.metadata.get_resource_unchecked(index).full_range;
+            barrier(
+                texture_selector,
+                &self.current_state_set,
+                index,
+                TextureStateProvider::TextureSet {
+                    set: &tracker.start_set,
+                },
+                &mut barriers,
+            );
+        }
+        !barriers.is_empty()
+    }
+
     /// Sets the given state for all texture in the given tracker.
     ///
     /// If a transition is needed to get the texture into the needed state,
@@ -776,6 +817,7 @@ impl DeviceTextureTracker {
         &'a mut self,
         tracker: &'a TextureTracker,
         snatch_guard: &'b SnatchGuard<'b>,
+        barrier_log: Option<&mut BarrierLog>,
     ) -> impl Iterator<Item = TextureBarrier<'a, dyn hal::DynTexture>> {
         for index in tracker.metadata.owned_indices() {
             self.tracker_assert_in_bounds(index);
@@ -805,6 +847,13 @@ impl DeviceTextureTracker {
             }
         }
 
+        if let Some(barrier_log) = barrier_log {
+            for pending in &self.temp {
+                let tex = unsafe { tracker.metadata.get_resource_unchecked(pending.id as _) };
+                barrier_log.texture(tex, pending);
+            }
+        }
+
         self.temp.drain(..).map(|pending| {
             let tex = unsafe { tracker.metadata.get_resource_unchecked(pending.id as _) };
             let tex = tex.try_raw(snatch_guard).unwrap();
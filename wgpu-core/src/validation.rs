@@ -1,5 +1,6 @@
 use alloc::{
     boxed::Box,
+    format,
     string::{String, ToString as _},
     vec::Vec,
 };
@@ -19,6 +20,8 @@ use crate::{FastHashMap, FastHashSet, device::bgl, resource::InvalidResourceErro
 enum ResourceType {
     Buffer {
         size: wgt::BufferSize,
+        /// The type of the buffer's contents, in [`Interface::types`].
+        ty: naga::Handle<naga::Type>,
     },
     Texture {
         dim: naga::ImageDimension,
@@ -62,13 +65,13 @@ impl From<&BindingType> for BindingTypeName {
             BindingType::Sampler { .. } => BindingTypeName::Sampler,
             BindingType::AccelerationStructure { .. } => BindingTypeName::AccelerationStructure,
             BindingType::ExternalTexture => BindingTypeName::ExternalTexture,
+            BindingType::YcbcrTexture { .. } => BindingTypeName::Texture,
         }
     }
 }
 
 #[derive(Debug)]
 struct Resource {
-    #[allow(unused)]
     name: Option<String>,
     bind: naga::ResourceBinding,
     ty: ResourceType,
@@ -149,7 +152,15 @@ impl fmt::Display for InterfaceVar {
 
 #[derive(Debug)]
 enum Varying {
-    Local { location: u32, iv: InterfaceVar },
+    Local {
+        location: u32,
+        iv: InterfaceVar,
+    },
+    /// A fragment input reading color target `location` with framebuffer fetch.
+    FramebufferFetch {
+        location: u32,
+        ty: NumericType,
+    },
     BuiltIn(naga::BuiltIn),
 }
 
@@ -169,12 +180,16 @@ struct EntryPoint {
     spec_constants: Vec<SpecializationConstant>,
     sampling_pairs: FastHashSet<(naga::Handle<Resource>, naga::Handle<Resource>)>,
     workgroup_size: [u32; 3],
+    /// The total size of the workgroup variables used by the entry point, in bytes.
+    workgroup_memory_size: u32,
     dual_source_blending: bool,
 }
 
 #[derive(Debug)]
 pub struct Interface {
     limits: wgt::Limits,
+    /// The types of the module, to compare the types of buffers with [`wgt::ShaderType`]s.
+    types: naga::UniqueArena<naga::Type>,
     resources: naga::Arena<Resource>,
     entry_points: FastHashMap<(naga::ShaderStage, String), EntryPoint>,
 }
@@ -453,7 +468,7 @@ pub fn map_storage_format_from_naga(format: naga::StorageFormat) -> wgt::Texture
 impl Resource {
     fn check_binding_use(&self, entry: &BindGroupLayoutEntry) -> Result<(), BindingError> {
         match self.ty {
-            ResourceType::Buffer { size } => {
+            ResourceType::Buffer { size, .. } => {
                 let min_size = match entry.ty {
                     BindingType::Buffer {
                         ty,
@@ -516,7 +531,9 @@ impl Resource {
                 let view_dimension = match entry.ty {
                     BindingType::Texture { view_dimension, .. }
                     | BindingType::StorageTexture { view_dimension, .. } => view_dimension,
-                    BindingType::ExternalTexture => wgt::TextureViewDimension::D2,
+                    BindingType::ExternalTexture | BindingType::YcbcrTexture { .. } => {
+                        wgt::TextureViewDimension::D2
+                    }
                     _ => {
                         return Err(BindingError::WrongTextureViewDimension {
                             dim,
@@ -596,6 +613,11 @@ impl Resource {
                             access: naga_access,
                         }
                     }
+                    BindingType::ExternalTexture => naga::ImageClass::External,
+                    BindingType::YcbcrTexture { .. } => naga::ImageClass::Sampled {
+                        kind: naga::ScalarKind::Float,
+                        multi: false,
+                    },
                     _ => {
                         return Err(BindingError::WrongType {
                             binding: (&entry.ty).into(),
@@ -631,7 +653,7 @@ impl Resource {
         is_reffed_by_sampler_in_entrypoint: bool,
     ) -> Result<BindingType, BindingError> {
         Ok(match self.ty {
-            ResourceType::Buffer { size } => BindingType::Buffer {
+            ResourceType::Buffer { size, .. } => BindingType::Buffer {
                 ty: match self.class {
                     naga::AddressSpace::Uniform => wgt::BufferBindingType::Uniform,
                     naga::AddressSpace::Storage { access } => wgt::BufferBindingType::Storage {
@@ -680,6 +702,7 @@ impl Resource {
                         view_dimension,
                         multisampled: multi,
                     },
+                    naga::ImageClass::External => BindingType::ExternalTexture,
                     naga::ImageClass::Storage { format, access } => BindingType::StorageTexture {
                         access: {
                             const LOAD_STORE: naga::StorageAccess =
@@ -813,6 +836,8 @@ impl NumericType {
                 panic!("Unexpected depth format")
             }
             Tf::NV12 => panic!("Unexpected nv12 format"),
+            Tf::P010 => panic!("Unexpected p010 format"),
+            Tf::I420 => panic!("Unexpected i420 format"),
             Tf::Rgb9e5Ufloat => (NumericDimension::Vector(Vs::Tri), Scalar::F32),
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
@@ -958,6 +983,12 @@ impl Interface {
                     sampling,
                 },
             },
+            Some(&naga::Binding::BuiltIn(naga::BuiltIn::FramebufferFetch { location })) => {
+                Varying::FramebufferFetch {
+                    location,
+                    ty: numeric_ty,
+                }
+            }
             Some(&naga::Binding::BuiltIn(built_in)) => Varying::BuiltIn(built_in),
             None => {
                 log::error!("Missing binding for a varying");
@@ -977,10 +1008,11 @@ impl Interface {
             };
             let naga_ty = &module.types[var.ty].inner;
 
-            let inner_ty = match *naga_ty {
-                naga::TypeInner::BindingArray { base, .. } => &module.types[base].inner,
-                ref ty => ty,
+            let data_ty = match *naga_ty {
+                naga::TypeInner::BindingArray { base, .. } => base,
+                _ => var.ty,
             };
+            let inner_ty = &module.types[data_ty].inner;
 
             let ty = match *inner_ty {
                 naga::TypeInner::Image {
@@ -998,6 +1030,7 @@ impl Interface {
                 }
                 ref other => ResourceType::Buffer {
                     size: wgt::BufferSize::new(other.size(module.to_ctx()) as u64).unwrap(),
+                    ty: data_ty,
                 },
             };
             let handle = resources.append(
@@ -1034,6 +1067,9 @@ impl Interface {
                 if !usage.is_empty() && var.binding.is_some() {
                     ep.resources.push(resource_mapping[&var_handle]);
                 }
+                if !usage.is_empty() && var.space == naga::AddressSpace::WorkGroup {
+                    ep.workgroup_memory_size += module.types[var.ty].inner.size(module.to_ctx());
+                }
             }
 
             for key in info.sampling_set.iter() {
@@ -1048,11 +1084,42 @@ impl Interface {
 
         Self {
             limits,
+            types: module.types.clone(),
             resources,
             entry_points,
         }
     }
 
+    /// Returns the binding of the resource variable named `name`, if the module
+    /// declares one.
+    pub fn resource_binding(&self, name: &str) -> Option<naga::ResourceBinding> {
+        self.resources
+            .iter()
+            .find(|(_, res)| res.name.as_deref() == Some(name))
+            .map(|(_, res)| res.bind)
+    }
+
+    /// Checks that the type of the uniform or storage buffer variable named `name` has the
+    /// memory layout described by `layout`.
+    pub fn check_buffer_layout(
+        &self,
+        name: &str,
+        layout: &wgt::ShaderTypeLayout,
+    ) -> Result<(), wgt::ShaderLayoutError> {
+        let ty = self
+            .resources
+            .iter()
+            .find_map(|(_, res)| match res.ty {
+                ResourceType::Buffer { ty, .. } if res.name.as_deref() == Some(name) => Some(ty),
+                _ => None,
+            })
+            .ok_or_else(|| wgt::ShaderLayoutError::NotFound {
+                name: name.to_string(),
+            })?;
+        let mut path = name.to_string();
+        check_layout(&self.types, ty, layout, &mut path)
+    }
+
     pub fn finalize_entry_point_name(
         &self,
         stage_bit: wgt::ShaderStages,
@@ -1080,6 +1147,12 @@ impl Interface {
             wgt::ShaderStages::VERTEX => naga::ShaderStage::Vertex,
             wgt::ShaderStages::FRAGMENT => naga::ShaderStage::Fragment,
             wgt::ShaderStages::COMPUTE => naga::ShaderStage::Compute,
+            wgt::ShaderStages::TASK => naga::ShaderStage::Task,
+            wgt::ShaderStages::MESH => naga::ShaderStage::Mesh,
+            wgt::ShaderStages::RAY_GENERATION => naga::ShaderStage::RayGeneration,
+            wgt::ShaderStages::MISS => naga::ShaderStage::Miss,
+            wgt::ShaderStages::CLOSEST_HIT => naga::ShaderStage::ClosestHit,
+            wgt::ShaderStages::ANY_HIT => naga::ShaderStage::AnyHit,
             _ => unreachable!(),
         }
     }
@@ -1110,7 +1183,7 @@ impl Interface {
                 match layouts {
                     BindingLayoutSource::Provided(layouts) => {
                         // update the required binding size for this buffer
-                        if let ResourceType::Buffer { size } = res.ty {
+                        if let ResourceType::Buffer { size, .. } = res.ty {
                             match shader_binding_sizes.entry(res.bind) {
                                 Entry::Occupied(e) => {
                                     *e.into_mut() = size.max(*e.get());
@@ -1198,7 +1271,7 @@ impl Interface {
                 );
                 let texture_sample_type = match texture_layout.ty {
                     BindingType::Texture { sample_type, .. } => sample_type,
-                    BindingType::ExternalTexture => {
+                    BindingType::ExternalTexture | BindingType::YcbcrTexture { .. } => {
                         wgt::TextureSampleType::Float { filterable: true }
                     }
                     _ => unreachable!(),
@@ -1284,7 +1357,12 @@ impl Interface {
                                         )
                                     }
                                     naga::ShaderStage::Compute => (false, 0),
-                                    naga::ShaderStage::Task | naga::ShaderStage::Mesh => {
+                                    naga::ShaderStage::Task
+                                    | naga::ShaderStage::Mesh
+                                    | naga::ShaderStage::RayGeneration
+                                    | naga::ShaderStage::Miss
+                                    | naga::ShaderStage::ClosestHit
+                                    | naga::ShaderStage::AnyHit => {
                                         unreachable!()
                                     }
                                 };
@@ -1307,7 +1385,7 @@ impl Interface {
                         }
                     }
                 }
-                Varying::BuiltIn(_) => {}
+                Varying::FramebufferFetch { .. } | Varying::BuiltIn(_) => {}
             }
         }
 
@@ -1316,7 +1394,7 @@ impl Interface {
                 //TODO: count builtins towards the limit?
                 inter_stage_components += match *output {
                     Varying::Local { ref iv, .. } => iv.ty.dim.num_components(),
-                    Varying::BuiltIn(_) => 0,
+                    Varying::FramebufferFetch { .. } | Varying::BuiltIn(_) => 0,
                 };
 
                 if let Some(
@@ -1348,12 +1426,62 @@ impl Interface {
             .iter()
             .filter_map(|output| match *output {
                 Varying::Local { location, ref iv } => Some((location, iv.clone())),
-                Varying::BuiltIn(_) => None,
+                Varying::FramebufferFetch { .. } | Varying::BuiltIn(_) => None,
             })
             .collect();
         Ok(outputs)
     }
 
+    /// Returns the color targets a fragment entry point reads with framebuffer fetch,
+    /// and the types it reads them as.
+    pub fn fragment_framebuffer_fetches(
+        &self,
+        entry_point_name: &str,
+    ) -> Result<Vec<(u32, NumericType)>, StageError> {
+        let pair = (naga::ShaderStage::Fragment, entry_point_name.to_string());
+        let ep = self
+            .entry_points
+            .get(&pair)
+            .ok_or(StageError::MissingEntryPoint(pair.1))?;
+        Ok(ep
+            .inputs
+            .iter()
+            .filter_map(|input| match *input {
+                Varying::FramebufferFetch { location, ty } => Some((location, ty)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Returns whether a fragment entry point reads the `fully_covered` built-in.
+    pub fn fragment_reads_fully_covered(&self, entry_point_name: &str) -> Result<bool, StageError> {
+        let pair = (naga::ShaderStage::Fragment, entry_point_name.to_string());
+        self.entry_points
+            .get(&pair)
+            .ok_or(StageError::MissingEntryPoint(pair.1))
+            .map(|ep| {
+                ep.inputs
+                    .iter()
+                    .any(|input| matches!(*input, Varying::BuiltIn(naga::BuiltIn::FullyCovered)))
+            })
+    }
+
+    /// Returns the number of bytes of workgroup memory used by the compute, task
+    /// or mesh entry point named `entry_point_name`, if the module declares one.
+    pub fn workgroup_memory_size(&self, entry_point_name: &str) -> Option<u32> {
+        [
+            naga::ShaderStage::Compute,
+            naga::ShaderStage::Task,
+            naga::ShaderStage::Mesh,
+        ]
+        .into_iter()
+        .find_map(|stage| {
+            self.entry_points
+                .get(&(stage, entry_point_name.to_string()))
+                .map(|ep| ep.workgroup_memory_size)
+        })
+    }
+
     pub fn fragment_uses_dual_source_blending(
         &self,
         entry_point_name: &str,
@@ -1367,6 +1495,186 @@ impl Interface {
 }
 
 // https://gpuweb.github.io/gpuweb/#abstract-opdef-calculating-color-attachment-bytes-per-sample
+/// Checks that `ty` has the memory layout described by `layout`, for
+/// [`Interface::check_buffer_layout`].
+///
+/// `path` is the path to `ty` from the buffer variable, for the error.
+fn check_layout(
+    types: &naga::UniqueArena<naga::Type>,
+    ty: naga::Handle<naga::Type>,
+    layout: &wgt::ShaderTypeLayout,
+    path: &mut String,
+) -> Result<(), wgt::ShaderLayoutError> {
+    use naga::common::wgsl::TypeContext as _;
+    use wgt::ShaderTypeLayout as L;
+
+    let mismatch = |path: &String, reason: String| wgt::ShaderLayoutError::Mismatch {
+        path: path.clone(),
+        reason,
+    };
+    let matches = match (&types[ty].inner, *layout) {
+        (&(naga::TypeInner::Scalar(scalar) | naga::TypeInner::Atomic(scalar)), L::Scalar(host)) => {
+            scalar_matches(scalar, host)
+        }
+        (
+            &naga::TypeInner::Vector { size, scalar },
+            L::Array {
+                element: &L::Scalar(host),
+                length: Some(length),
+                stride,
+            },
+        ) => {
+            length == size as u32
+                && scalar_matches(scalar, host)
+                && stride == u32::from(scalar.width)
+        }
+        (
+            &naga::TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            },
+            L::Array {
+                element:
+                    &L::Array {
+                        element: &L::Scalar(host),
+                        length: Some(column_length),
+                        stride: scalar_stride,
+                    },
+                length: Some(length),
+                stride,
+            },
+        ) => {
+            let width = u32::from(scalar.width);
+            let column_stride = match rows {
+                naga::VectorSize::Bi => 2 * width,
+                naga::VectorSize::Tri | naga::VectorSize::Quad => 4 * width,
+            };
+            length == columns as u32
+                && scalar_matches(scalar, host)
+                && scalar_stride == width
+                && stride == column_stride
+                // Padded columns, like `[f32; 4]` for the columns of `mat3x3<f32>`, are fine.
+                && (column_length == rows as u32 || column_length * width == column_stride)
+        }
+        (
+            &naga::TypeInner::Array { base, size, stride },
+            L::Array {
+                element,
+                length,
+                stride: host_stride,
+            },
+        ) => {
+            let shader_length = match size {
+                naga::ArraySize::Constant(length) => Some(length.get()),
+                naga::ArraySize::Dynamic => None,
+                naga::ArraySize::Pending(_) => {
+                    return Err(mismatch(
+                        path,
+                        "the length of the shader's array is an override".to_string(),
+                    ));
+                }
+            };
+            if shader_length != length {
+                let describe = |length: Option<u32>| match length {
+                    Some(length) => format!("{length} elements"),
+                    None => "a runtime-sized length".to_string(),
+                };
+                return Err(mismatch(
+                    path,
+                    format!(
+                        "the shader's array has {}, the layout has {}",
+                        describe(shader_length),
+                        describe(length)
+                    ),
+                ));
+            }
+            if stride != host_stride {
+                return Err(mismatch(
+                    path,
+                    format!(
+                        "the shader's array has a stride of {stride} bytes, the layout has \
+                         {host_stride}"
+                    ),
+                ));
+            }
+            let len = path.len();
+            path.push_str("[]");
+            check_layout(types, base, element, path)?;
+            path.truncate(len);
+            return Ok(());
+        }
+        (
+            &naga::TypeInner::Struct { ref members, span },
+            L::Struct {
+                name,
+                members: host_members,
+                size,
+            },
+        ) => {
+            if members.len() != host_members.len() {
+                return Err(mismatch(
+                    path,
+                    format!(
+                        "the shader's struct has {} members, `{name}` has {}",
+                        members.len(),
+                        host_members.len()
+                    ),
+                ));
+            }
+            for (member, host_member) in members.iter().zip(host_members) {
+                let len = path.len();
+                path.push('.');
+                path.push_str(member.name.as_deref().unwrap_or(host_member.name));
+                if member.offset != host_member.offset {
+                    return Err(mismatch(
+                        path,
+                        format!(
+                            "the shader's member is at offset {}, `{name}::{}` is at offset {}",
+                            member.offset, host_member.name, host_member.offset
+                        ),
+                    ));
+                }
+                check_layout(types, member.ty, host_member.ty, path)?;
+                path.truncate(len);
+            }
+            if span != size {
+                return Err(mismatch(
+                    path,
+                    format!("the shader's struct has a size of {span} bytes, `{name}` has {size}"),
+                ));
+            }
+            return Ok(());
+        }
+        _ => false,
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err(mismatch(
+            path,
+            format!(
+                "the shader has `{}`, the layout has `{layout}`",
+                types.type_to_string(ty)
+            ),
+        ))
+    }
+}
+
+fn scalar_matches(scalar: naga::Scalar, host: wgt::ShaderScalar) -> bool {
+    use wgt::ShaderScalar as S;
+
+    scalar
+        == match host {
+            S::F32 => naga::Scalar::F32,
+            S::F64 => naga::Scalar::F64,
+            S::I32 => naga::Scalar::I32,
+            S::U32 => naga::Scalar::U32,
+            S::I64 => naga::Scalar::I64,
+            S::U64 => naga::Scalar::U64,
+        }
+}
+
 pub fn validate_color_attachment_bytes_per_sample(
     attachment_formats: impl Iterator<Item = Option<wgt::TextureFormat>>,
     limit: u32,
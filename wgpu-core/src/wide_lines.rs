@@ -0,0 +1,847 @@
+//! Emulation of [`wgt::Features::WIDE_LINES`] on backends that can't rasterize wide lines.
+//!
+//! ## Algorithm
+//!
+//! Each segment of a line list or line strip is drawn as a quad made of two triangles.
+//! The vertex entry point of the pipeline is replaced by one that calls the original
+//! entry point for both ends of the segment, and moves the end the current corner of
+//! the quad belongs to sideways, by half the line width in screen space.
+//!
+//! The vertex buffers are bound twice, once for each end of the segment, and step once
+//! per instance of the expanded draw. A draw of `n` segments is then a draw of
+//! [`QUAD_VERTEX_COUNT`] vertices and `n` instances, which is repeated for each instance
+//! of the original draw.
+//!
+//! The values the expanded entry point can't derive from its own inputs, the viewport
+//! size, the first vertex and the instance index of the original draw, are passed in
+//! push constants.
+
+use alloc::{borrow::Cow, borrow::ToOwned as _, boxed::Box, string::ToString as _, sync::Arc};
+use alloc::{vec, vec::Vec};
+use core::mem::{self, ManuallyDrop};
+
+use crate::{
+    binding_model::PipelineLayout,
+    device::{Device, create_validator},
+    pipeline::{CreateRenderPipelineError, ResolvedVertexState, WideLinesError},
+};
+
+/// The number of vertices drawn for each segment.
+pub(crate) const QUAD_VERTEX_COUNT: u32 = 6;
+
+/// The size of the push constants of the expanded entry point, in bytes.
+const PUSH_CONSTANT_SIZE: u32 = 16;
+
+/// The corners of the quad, by vertex index, that are at the second end of the segment.
+const SECOND_END_CORNERS: u32 = 0b110010;
+
+/// The corners of the quad, by vertex index, that are on the left of the segment.
+const LEFT_CORNERS: u32 = 0b101100;
+
+/// The IR of a shader module, kept to expand the vertex stages of pipelines that draw
+/// wide lines.
+#[derive(Debug)]
+pub(crate) struct ShaderSource {
+    pub(crate) module: naga::Module,
+    pub(crate) runtime_checks: wgt::ShaderRuntimeChecks,
+    pub(crate) optimization: wgt::ShaderOptimization,
+}
+
+/// Returns whether a render pipeline with the `primitive` state draws its lines as quads.
+pub(crate) fn is_emulated(
+    device: &Device,
+    primitive: &wgt::PrimitiveState,
+    is_mesh: bool,
+) -> Result<bool, WideLinesError> {
+    if primitive.line_width == 1.0
+        || !device
+            .adapter
+            .emulated_features
+            .contains(wgt::Features::WIDE_LINES)
+    {
+        return Ok(false);
+    }
+    match primitive.topology {
+        wgt::PrimitiveTopology::LineList | wgt::PrimitiveTopology::LineStrip => {}
+        _ if primitive.polygon_mode == wgt::PolygonMode::Line => {
+            return Err(WideLinesError::PolygonModeLine);
+        }
+        // The line width doesn't apply to points and filled triangles.
+        _ => return Ok(false),
+    }
+    if is_mesh {
+        return Err(WideLinesError::MeshPipeline);
+    }
+    if primitive.line_stipple.is_some() {
+        return Err(WideLinesError::LineStipple);
+    }
+    Ok(true)
+}
+
+/// The state of a render pipeline that draws its lines as quads.
+#[derive(Debug)]
+pub(crate) struct WideLines {
+    /// The shader module with the expanded vertex entry point.
+    module: ManuallyDrop<Box<dyn hal::DynShaderModule>>,
+    /// The number of vertices between the first vertices of two segments.
+    step: u32,
+}
+
+impl WideLines {
+    /// Returns the number of segments drawn by `vertex_count` vertices.
+    pub(crate) fn segment_count(&self, vertex_count: u32) -> u32 {
+        match vertex_count {
+            0 | 1 => 0,
+            _ => (vertex_count - 2) / self.step + 1,
+        }
+    }
+
+    /// Returns the push constants of the expanded entry point.
+    pub(crate) fn push_constants(
+        viewport_size: [f32; 2],
+        first_vertex: u32,
+        instance_index: u32,
+    ) -> [u32; (PUSH_CONSTANT_SIZE / wgt::PUSH_CONSTANT_ALIGNMENT) as usize] {
+        [
+            viewport_size[0].to_bits(),
+            viewport_size[1].to_bits(),
+            first_vertex,
+            instance_index,
+        ]
+    }
+
+    /// # Safety
+    ///
+    /// The render pipeline using the module must have been destroyed.
+    pub(crate) unsafe fn destroy(mut self, device: &dyn hal::DynDevice) {
+        // SAFETY: We don't use self.module anymore after this point.
+        let module = unsafe { ManuallyDrop::take(&mut self.module) };
+        unsafe { device.destroy_shader_module(module) };
+    }
+}
+
+/// The parts of a render pipeline that differ when it draws its lines as quads.
+pub(crate) struct ExpandedPipeline {
+    pub(crate) wide_lines: WideLines,
+    /// The pipeline layout, with the push constant range of the expanded entry point.
+    pub(crate) layout: Arc<PipelineLayout>,
+    /// The stride and attributes of each vertex buffer slot.
+    vertex_buffers: Vec<(wgt::BufferAddress, Vec<wgt::VertexAttribute>)>,
+}
+
+impl ExpandedPipeline {
+    pub(crate) fn new(
+        device: &Arc<Device>,
+        vertex: &ResolvedVertexState,
+        primitive: &wgt::PrimitiveState,
+        layout: &Arc<PipelineLayout>,
+    ) -> Result<Self, CreateRenderPipelineError> {
+        let limits = &device.adapter.raw.capabilities.limits;
+
+        if !layout.push_constant_ranges.is_empty() {
+            return Err(WideLinesError::PushConstantRanges.into());
+        }
+
+        let count = vertex.buffers.len() as u32;
+        if count * 2 > limits.max_vertex_buffers {
+            return Err(WideLinesError::TooManyVertexBuffers {
+                count,
+                limit: limits.max_vertex_buffers,
+            }
+            .into());
+        }
+
+        let location_offset = vertex
+            .buffers
+            .iter()
+            .flat_map(|buffer| buffer.attributes.iter())
+            .map(|attribute| attribute.shader_location + 1)
+            .max()
+            .unwrap_or(0);
+        if location_offset * 2 > limits.max_vertex_attributes {
+            return Err(WideLinesError::TooManyVertexAttributes {
+                location: location_offset - 1,
+                limit: limits.max_vertex_attributes,
+            }
+            .into());
+        }
+
+        let step = match primitive.topology {
+            wgt::PrimitiveTopology::LineList => 2,
+            _ => 1,
+        };
+
+        let mut vertex_buffers = Vec::with_capacity(vertex.buffers.len() * 2);
+        for (index, buffer) in vertex.buffers.iter().enumerate() {
+            if buffer.step_mode != wgt::VertexStepMode::Vertex {
+                return Err(WideLinesError::InstanceStepMode {
+                    index: index as u32,
+                }
+                .into());
+            }
+            let stride = buffer.array_stride * step as u64;
+            if stride > limits.max_vertex_buffer_array_stride as u64 {
+                return Err(WideLinesError::VertexStrideTooLarge {
+                    index: index as u32,
+                    stride: buffer.array_stride,
+                    limit: limits.max_vertex_buffer_array_stride,
+                }
+                .into());
+            }
+            vertex_buffers.push((stride, buffer.attributes.to_vec()));
+        }
+        let second_ends = vertex_buffers
+            .iter()
+            .map(|(stride, attributes)| {
+                let attributes = attributes
+                    .iter()
+                    .map(|attribute| wgt::VertexAttribute {
+                        shader_location: attribute.shader_location + location_offset,
+                        ..*attribute
+                    })
+                    .collect();
+                (*stride, attributes)
+            })
+            .collect::<Vec<_>>();
+        vertex_buffers.extend(second_ends);
+
+        let shader_module = &vertex.stage.module;
+        let source = shader_module
+            .wide_lines_source
+            .as_ref()
+            .ok_or(WideLinesError::MissingShaderSource)?;
+        let entry_point = shader_module
+            .finalize_entry_point_name(
+                wgt::ShaderStages::VERTEX,
+                vertex.stage.entry_point.as_ref().map(|ep| ep.as_ref()),
+            )
+            .map_err(|error| CreateRenderPipelineError::Stage {
+                stage: wgt::ShaderStages::VERTEX,
+                error,
+            })?;
+
+        let mut module = source.module.clone();
+        expand_entry_point(
+            &mut module,
+            &entry_point,
+            primitive.line_width,
+            step,
+            location_offset,
+        )?;
+        let info = create_validator(
+            device.features | wgt::Features::PUSH_CONSTANTS,
+            device.downlevel.flags,
+            naga::valid::ValidationFlags::all(),
+        )
+        .validate(&module)
+        .map_err(|error| CreateRenderPipelineError::Internal {
+            stage: wgt::ShaderStages::VERTEX,
+            error: error.into_inner().to_string(),
+        })?;
+
+        let label = (!layout.label.is_empty()).then(|| Cow::Borrowed(layout.label.as_str()));
+        let layout = device.create_pipeline_layout_unchecked(
+            &label,
+            layout.bind_group_layouts.clone(),
+            &[wgt::PushConstantRange {
+                stages: wgt::ShaderStages::VERTEX,
+                range: 0..PUSH_CONSTANT_SIZE,
+            }],
+        )?;
+
+        let hal_desc = hal::ShaderModuleDescriptor {
+            label: crate::hal_label(
+                Some("(wgpu internal) wide lines vertex shader"),
+                device.instance_flags,
+            ),
+            runtime_checks: source.runtime_checks,
+            optimization: source.optimization,
+        };
+        let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
+            module: Cow::Owned(module),
+            info,
+            debug_source: None,
+        });
+        let raw = unsafe { device.raw().create_shader_module(&hal_desc, hal_shader) }.map_err(
+            |error| match error {
+                hal::ShaderError::Device(error) => {
+                    CreateRenderPipelineError::Device(device.handle_hal_error(error))
+                }
+                hal::ShaderError::Compilation(error) => CreateRenderPipelineError::Internal {
+                    stage: wgt::ShaderStages::VERTEX,
+                    error,
+                },
+            },
+        )?;
+
+        Ok(Self {
+            wide_lines: WideLines {
+                module: ManuallyDrop::new(raw),
+                step,
+            },
+            layout,
+            vertex_buffers,
+        })
+    }
+
+    /// Returns the shader module with the expanded vertex entry point.
+    pub(crate) fn module(&self) -> &dyn hal::DynShaderModule {
+        self.wide_lines.module.as_ref()
+    }
+
+    /// Returns the layouts of the vertex buffers, which are bound once for the first
+    /// end of each segment and once more, after all of them, for the second end.
+    pub(crate) fn vertex_buffers(&self) -> Vec<hal::VertexBufferLayout<'_>> {
+        self.vertex_buffers
+            .iter()
+            .map(|(stride, attributes)| hal::VertexBufferLayout {
+                array_stride: *stride,
+                step_mode: wgt::VertexStepMode::Instance,
+                attributes,
+            })
+            .collect()
+    }
+
+    /// Returns the primitive state of the expanded pipeline.
+    pub(crate) fn primitive(primitive: wgt::PrimitiveState) -> wgt::PrimitiveState {
+        wgt::PrimitiveState {
+            topology: wgt::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            cull_mode: None,
+            polygon_mode: wgt::PolygonMode::Fill,
+            line_width: 1.0,
+            ..primitive
+        }
+    }
+}
+
+/// Replaces the vertex entry point `entry_point` of `module` with one that draws each
+/// segment of a line as a quad `width` pixels wide.
+///
+/// The first vertex of each segment is `step` vertices after the first vertex of the
+/// previous one, and the inputs of the second vertex of a segment are read at their
+/// location plus `location_offset`.
+fn expand_entry_point(
+    module: &mut naga::Module,
+    entry_point: &str,
+    width: f32,
+    step: u32,
+    location_offset: u32,
+) -> Result<(), WideLinesError> {
+    use naga::{BinaryOperator as Bo, Expression as Ex, Literal};
+
+    let index = module
+        .entry_points
+        .iter()
+        .position(|ep| ep.stage == naga::ShaderStage::Vertex && ep.name == entry_point)
+        .unwrap();
+    let mut inner = mem::take(&mut module.entry_points[index].function);
+
+    let result = inner
+        .result
+        .clone()
+        .ok_or(WideLinesError::MissingPosition)?;
+    let position_member = match result.binding {
+        Some(naga::Binding::BuiltIn(naga::BuiltIn::Position { .. })) => None,
+        _ => match module.types[result.ty].inner {
+            naga::TypeInner::Struct { ref members, .. } => Some(
+                members
+                    .iter()
+                    .position(|member| {
+                        matches!(
+                            member.binding,
+                            Some(naga::Binding::BuiltIn(naga::BuiltIn::Position { .. }))
+                        )
+                    })
+                    .ok_or(WideLinesError::MissingPosition)? as u32,
+            ),
+            _ => return Err(WideLinesError::MissingPosition),
+        },
+    };
+
+    // The original entry point becomes a regular function.
+    let arguments = inner.arguments.clone();
+    for argument in inner.arguments.iter_mut() {
+        argument.binding = None;
+    }
+    if let Some(ref mut result) = inner.result {
+        result.binding = None;
+    }
+    inner.name = Some(entry_point.to_owned());
+    let inner = module.functions.append(inner, naga::Span::UNDEFINED);
+
+    let mut insert_type = |inner| {
+        module
+            .types
+            .insert(naga::Type { name: None, inner }, naga::Span::UNDEFINED)
+    };
+    let u32_ty = insert_type(naga::TypeInner::Scalar(naga::Scalar::U32));
+    let vec2_ty = insert_type(naga::TypeInner::Vector {
+        size: naga::VectorSize::Bi,
+        scalar: naga::Scalar::F32,
+    });
+    let vec4_ty = insert_type(naga::TypeInner::Vector {
+        size: naga::VectorSize::Quad,
+        scalar: naga::Scalar::F32,
+    });
+    let push_constants_ty = module.types.insert(
+        naga::Type {
+            name: Some("WideLines".to_owned()),
+            inner: naga::TypeInner::Struct {
+                members: vec![
+                    naga::StructMember {
+                        name: Some("viewport_size".to_owned()),
+                        ty: vec2_ty,
+                        binding: None,
+                        offset: 0,
+                    },
+                    naga::StructMember {
+                        name: Some("first_vertex".to_owned()),
+                        ty: u32_ty,
+                        binding: None,
+                        offset: 8,
+                    },
+                    naga::StructMember {
+                        name: Some("instance_index".to_owned()),
+                        ty: u32_ty,
+                        binding: None,
+                        offset: 12,
+                    },
+                ],
+                span: PUSH_CONSTANT_SIZE,
+            },
+        },
+        naga::Span::UNDEFINED,
+    );
+    let push_constants = module.global_variables.append(
+        naga::GlobalVariable {
+            name: Some("wide_lines".to_owned()),
+            space: naga::AddressSpace::PushConstant,
+            binding: None,
+            ty: push_constants_ty,
+            init: None,
+        },
+        naga::Span::UNDEFINED,
+    );
+
+    let mut wrapper = FunctionBuilder::new(naga::Function {
+        name: Some(entry_point.to_owned()),
+        result: Some(result.clone()),
+        ..Default::default()
+    });
+
+    let corner = wrapper.argument(
+        Some("corner"),
+        u32_ty,
+        naga::Binding::BuiltIn(naga::BuiltIn::VertexIndex),
+    );
+    let segment = wrapper.argument(
+        Some("segment"),
+        u32_ty,
+        naga::Binding::BuiltIn(naga::BuiltIn::InstanceIndex),
+    );
+    let push_constants = wrapper.append(Ex::GlobalVariable(push_constants));
+    let viewport_size = wrapper.load_member(push_constants, 0);
+    let first_vertex = wrapper.load_member(push_constants, 1);
+    let instance_index = wrapper.load_member(push_constants, 2);
+
+    let zero = wrapper.append(Ex::Literal(Literal::U32(0)));
+    let one = wrapper.append(Ex::Literal(Literal::U32(1)));
+    let step = wrapper.append(Ex::Literal(Literal::U32(step)));
+    let segment_offset = wrapper.binary(Bo::Multiply, segment, step);
+    let first_index = wrapper.binary(Bo::Add, first_vertex, segment_offset);
+    let second_index = wrapper.binary(Bo::Add, first_index, one);
+
+    // Read the inputs of both ends of the segment.
+    let inputs = Inputs {
+        vertex_index: [first_index, second_index],
+        instance_index,
+        location_offset,
+    };
+    let mut first_arguments = Vec::with_capacity(arguments.len());
+    let mut second_arguments = Vec::with_capacity(arguments.len());
+    for argument in arguments.iter() {
+        let [first, second] = match argument.binding {
+            Some(ref binding) => inputs.read(&mut wrapper, binding, argument.ty)?,
+            None => {
+                let naga::TypeInner::Struct { ref members, .. } = module.types[argument.ty].inner
+                else {
+                    unreachable!("entry point arguments without bindings are structs");
+                };
+                let mut components = [Vec::new(), Vec::new()];
+                for member in members.iter() {
+                    let binding = member.binding.as_ref().unwrap();
+                    let [first, second] = inputs.read(&mut wrapper, binding, member.ty)?;
+                    components[0].push(first);
+                    components[1].push(second);
+                }
+                components.map(|components| {
+                    wrapper.append(Ex::Compose {
+                        ty: argument.ty,
+                        components,
+                    })
+                })
+            }
+        };
+        first_arguments.push(first);
+        second_arguments.push(second);
+    }
+
+    let first_end = wrapper.call(inner, first_arguments);
+    let second_end = wrapper.call(inner, second_arguments);
+    let [first_position, second_position] =
+        [first_end, second_end].map(|end| match position_member {
+            Some(index) => wrapper.append(Ex::AccessIndex { base: end, index }),
+            None => end,
+        });
+
+    let is_second_end = wrapper.corner_in(corner, SECOND_END_CORNERS, zero, one);
+    let is_left = wrapper.corner_in(corner, LEFT_CORNERS, zero, one);
+
+    // The segment is perpendicular to its direction in pixels.
+    let float_one = wrapper.append(Ex::Literal(Literal::F32(1.0)));
+    let [first_ndc, second_ndc] =
+        [first_position, second_position].map(|position| wrapper.ndc(position, float_one));
+    let delta = wrapper.binary(Bo::Subtract, second_ndc, first_ndc);
+    let delta = wrapper.binary(Bo::Multiply, delta, viewport_size);
+    let direction = wrapper.append(Ex::Math {
+        fun: naga::MathFunction::Normalize,
+        arg: delta,
+        arg1: None,
+        arg2: None,
+        arg3: None,
+    });
+    let direction_x = wrapper.append(Ex::AccessIndex {
+        base: direction,
+        index: 0,
+    });
+    let direction_y = wrapper.append(Ex::AccessIndex {
+        base: direction,
+        index: 1,
+    });
+    let normal_x = wrapper.append(Ex::Unary {
+        op: naga::UnaryOperator::Negate,
+        expr: direction_y,
+    });
+    let normal = wrapper.append(Ex::Compose {
+        ty: vec2_ty,
+        components: vec![normal_x, direction_x],
+    });
+
+    // Half the width in pixels is the full width in normalized device coordinates,
+    // which span two units across the viewport.
+    let left_width = wrapper.append(Ex::Literal(Literal::F32(width)));
+    let right_width = wrapper.append(Ex::Literal(Literal::F32(-width)));
+    let signed_width = wrapper.append(Ex::Select {
+        condition: is_left,
+        accept: left_width,
+        reject: right_width,
+    });
+    let offset = wrapper.binary(Bo::Multiply, normal, signed_width);
+    let offset = wrapper.binary(Bo::Divide, offset, viewport_size);
+
+    let position = wrapper.append(Ex::Select {
+        condition: is_second_end,
+        accept: second_position,
+        reject: first_position,
+    });
+    let w = wrapper.append(Ex::AccessIndex {
+        base: position,
+        index: 3,
+    });
+    let offset = wrapper.binary(Bo::Multiply, offset, w);
+    let float_zero = wrapper.append(Ex::Literal(Literal::F32(0.0)));
+    let offset = wrapper.append(Ex::Compose {
+        ty: vec4_ty,
+        components: vec![offset, float_zero, float_zero],
+    });
+    let position = wrapper.binary(Bo::Add, position, offset);
+
+    let value = match position_member {
+        None => position,
+        Some(index) => {
+            let output = wrapper.function.local_variables.append(
+                naga::LocalVariable {
+                    name: Some("output".to_owned()),
+                    ty: result.ty,
+                    init: None,
+                },
+                naga::Span::UNDEFINED,
+            );
+            let output = wrapper.append(Ex::LocalVariable(output));
+            let store = |value| {
+                naga::Block::from_vec(vec![naga::Statement::Store {
+                    pointer: output,
+                    value,
+                }])
+            };
+            wrapper.push(naga::Statement::If {
+                condition: is_second_end,
+                accept: store(second_end),
+                reject: store(first_end),
+            });
+            let output_position = wrapper.append(Ex::AccessIndex {
+                base: output,
+                index,
+            });
+            wrapper.push(naga::Statement::Store {
+                pointer: output_position,
+                value: position,
+            });
+            wrapper.append(Ex::Load { pointer: output })
+        }
+    };
+    wrapper.push(naga::Statement::Return { value: Some(value) });
+
+    module.entry_points[index].function = wrapper.function;
+    Ok(())
+}
+
+/// The values the inputs of both ends of a segment are read from.
+struct Inputs {
+    vertex_index: [naga::Handle<naga::Expression>; 2],
+    instance_index: naga::Handle<naga::Expression>,
+    location_offset: u32,
+}
+
+impl Inputs {
+    /// Returns the value of the input with `binding` at both ends of the segment.
+    fn read(
+        &self,
+        wrapper: &mut FunctionBuilder,
+        binding: &naga::Binding,
+        ty: naga::Handle<naga::Type>,
+    ) -> Result<[naga::Handle<naga::Expression>; 2], WideLinesError> {
+        match *binding {
+            naga::Binding::Location { location, .. } => {
+                let mut second_binding = binding.clone();
+                if let naga::Binding::Location {
+                    location: ref mut second_location,
+                    ..
+                } = second_binding
+                {
+                    *second_location = location + self.location_offset;
+                }
+                Ok([
+                    wrapper.argument(None, ty, binding.clone()),
+                    wrapper.argument(None, ty, second_binding),
+                ])
+            }
+            naga::Binding::BuiltIn(naga::BuiltIn::VertexIndex) => Ok(self.vertex_index),
+            naga::Binding::BuiltIn(naga::BuiltIn::InstanceIndex) => Ok([self.instance_index; 2]),
+            naga::Binding::BuiltIn(naga::BuiltIn::ViewIndex) => {
+                let view_index = wrapper.argument(None, ty, binding.clone());
+                Ok([view_index; 2])
+            }
+            naga::Binding::BuiltIn(built_in) => Err(WideLinesError::UnsupportedBuiltIn(built_in)),
+        }
+    }
+}
+
+/// Appends expressions and statements to a function, emitting the expressions that
+/// need it.
+struct FunctionBuilder {
+    function: naga::Function,
+    emitter: naga::proc::Emitter,
+}
+
+impl FunctionBuilder {
+    fn new(function: naga::Function) -> Self {
+        let mut emitter = naga::proc::Emitter::default();
+        emitter.start(&function.expressions);
+        Self { function, emitter }
+    }
+
+    /// Runs `f` outside of the current range of emitted expressions.
+    fn interrupt<T>(&mut self, f: impl FnOnce(&mut naga::Function) -> T) -> T {
+        if let Some((statement, span)) = self.emitter.finish(&self.function.expressions) {
+            self.function.body.push(statement, span);
+        }
+        let value = f(&mut self.function);
+        self.emitter.start(&self.function.expressions);
+        value
+    }
+
+    fn append(&mut self, expression: naga::Expression) -> naga::Handle<naga::Expression> {
+        if expression.needs_pre_emit() {
+            self.interrupt(|function| {
+                function
+                    .expressions
+                    .append(expression, naga::Span::UNDEFINED)
+            })
+        } else {
+            self.function
+                .expressions
+                .append(expression, naga::Span::UNDEFINED)
+        }
+    }
+
+    fn push(&mut self, statement: naga::Statement) {
+        self.interrupt(|function| function.body.push(statement, naga::Span::UNDEFINED));
+    }
+
+    fn argument(
+        &mut self,
+        name: Option<&str>,
+        ty: naga::Handle<naga::Type>,
+        binding: naga::Binding,
+    ) -> naga::Handle<naga::Expression> {
+        let index = self.function.arguments.len() as u32;
+        self.function.arguments.push(naga::FunctionArgument {
+            name: name.map(str::to_owned),
+            ty,
+            binding: Some(binding),
+        });
+        self.append(naga::Expression::FunctionArgument(index))
+    }
+
+    fn call(
+        &mut self,
+        function: naga::Handle<naga::Function>,
+        arguments: Vec<naga::Handle<naga::Expression>>,
+    ) -> naga::Handle<naga::Expression> {
+        self.interrupt(|caller| {
+            let result = caller.expressions.append(
+                naga::Expression::CallResult(function),
+                naga::Span::UNDEFINED,
+            );
+            caller.body.push(
+                naga::Statement::Call {
+                    function,
+                    arguments,
+                    result: Some(result),
+                },
+                naga::Span::UNDEFINED,
+            );
+            result
+        })
+    }
+
+    fn binary(
+        &mut self,
+        op: naga::BinaryOperator,
+        left: naga::Handle<naga::Expression>,
+        right: naga::Handle<naga::Expression>,
+    ) -> naga::Handle<naga::Expression> {
+        self.append(naga::Expression::Binary { op, left, right })
+    }
+
+    /// Loads member `index` of the struct behind `pointer`.
+    fn load_member(
+        &mut self,
+        pointer: naga::Handle<naga::Expression>,
+        index: u32,
+    ) -> naga::Handle<naga::Expression> {
+        let member = self.append(naga::Expression::AccessIndex {
+            base: pointer,
+            index,
+        });
+        self.append(naga::Expression::Load { pointer: member })
+    }
+
+    /// Returns whether bit `corner` of `corners` is set.
+    fn corner_in(
+        &mut self,
+        corner: naga::Handle<naga::Expression>,
+        corners: u32,
+        zero: naga::Handle<naga::Expression>,
+        one: naga::Handle<naga::Expression>,
+    ) -> naga::Handle<naga::Expression> {
+        let corners = self.append(naga::Expression::Literal(naga::Literal::U32(corners)));
+        let shifted = self.binary(naga::BinaryOperator::ShiftRight, corners, corner);
+        let bit = self.binary(naga::BinaryOperator::And, shifted, one);
+        self.binary(naga::BinaryOperator::NotEqual, bit, zero)
+    }
+
+    /// Returns the `xy` normalized device coordinates of the clip space `position`.
+    fn ndc(
+        &mut self,
+        position: naga::Handle<naga::Expression>,
+        one: naga::Handle<naga::Expression>,
+    ) -> naga::Handle<naga::Expression> {
+        use naga::SwizzleComponent as Sc;
+
+        let xy = self.append(naga::Expression::Swizzle {
+            size: naga::VectorSize::Bi,
+            vector: position,
+            pattern: [Sc::X, Sc::Y, Sc::X, Sc::X],
+        });
+        let w = self.append(naga::Expression::AccessIndex {
+            base: position,
+            index: 3,
+        });
+        let inverse_w = self.binary(naga::BinaryOperator::Divide, one, w);
+        self.binary(naga::BinaryOperator::Multiply, xy, inverse_w)
+    }
+}
+
+#[cfg(all(test, feature = "wgsl"))]
+mod tests {
+    use super::*;
+
+    fn expand(source: &str, step: u32) -> Result<naga::Module, WideLinesError> {
+        let mut module = naga::front::wgsl::parse_str(source).unwrap();
+        expand_entry_point(&mut module, "vs_main", 4.0, step, 2)?;
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::PUSH_CONSTANT,
+        )
+        .validate(&module)
+        .unwrap();
+        Ok(module)
+    }
+
+    #[test]
+    fn expands_position_output() {
+        let module = expand(
+            "
+            @vertex
+            fn vs_main(@location(0) position: vec2f) -> @builtin(position) vec4f {
+                return vec4f(position, 0.0, 1.0);
+            }
+            ",
+            2,
+        )
+        .unwrap();
+
+        let function = &module.entry_points[0].function;
+        let locations = function
+            .arguments
+            .iter()
+            .filter_map(|argument| match argument.binding {
+                Some(naga::Binding::Location { location, .. }) => Some(location),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(locations, [0, 2]);
+    }
+
+    #[test]
+    fn expands_struct_io() {
+        expand(
+            "
+            struct VertexInput {
+                @location(0) position: vec2f,
+                @location(1) color: vec4f,
+            }
+
+            struct VertexOutput {
+                @location(0) color: vec4f,
+                @builtin(position) position: vec4f,
+            }
+
+            @vertex
+            fn vs_main(
+                input: VertexInput,
+                @builtin(vertex_index) vertex_index: u32,
+                @builtin(instance_index) instance_index: u32,
+            ) -> VertexOutput {
+                let y = f32(instance_index) + f32(vertex_index) * 0.0;
+                return VertexOutput(input.color, vec4f(input.position.x, y, 0.0, 1.0));
+            }
+            ",
+            1,
+        )
+        .unwrap();
+    }
+}
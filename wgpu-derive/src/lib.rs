@@ -0,0 +1,125 @@
+//! Derive macros for wgpu, re-exported by the `derive` feature of `wgpu`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens as _, quote};
+use syn::{Data, DeriveInput, Error, Fields, parse_macro_input, spanned::Spanned as _};
+
+/// Derives `wgpu::ShaderType` for a `#[repr(C)]` struct whose fields all implement it.
+///
+/// See the documentation of the re-export, `wgpu::ShaderType`.
+#[proc_macro_derive(ShaderType, attributes(shader_type))]
+pub fn derive_shader_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    shader_type(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn shader_type(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "`ShaderType` can only be derived for structs",
+        ));
+    };
+
+    let mut repr_c = false;
+    let mut uniform = false;
+    for attr in &input.attrs {
+        if attr.path().is_ident("repr") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("C") {
+                    repr_c = true;
+                } else if meta.input.peek(syn::token::Paren) {
+                    // Skip the arguments of `align(N)` and similar.
+                    let arguments;
+                    syn::parenthesized!(arguments in meta.input);
+                    arguments.parse::<proc_macro2::TokenStream>()?;
+                }
+                Ok(())
+            })?;
+        } else if attr.path().is_ident("shader_type") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("uniform") {
+                    uniform = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `uniform`"))
+                }
+            })?;
+        }
+    }
+    if !repr_c {
+        return Err(Error::new(
+            input.ident.span(),
+            "`ShaderType` can only be derived for `#[repr(C)]` structs, since the layout of \
+             other structs can change",
+        ));
+    }
+    if uniform && !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`#[shader_type(uniform)]` can't check generic structs",
+        ));
+    }
+    if data.fields.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "`ShaderType` can't be derived for structs without fields, which WGSL doesn't have",
+        ));
+    }
+
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let mut generics = input.generics.clone();
+    let generic = !generics.params.is_empty();
+    let where_clause = generics.make_where_clause();
+    let mut members = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let ty = &field.ty;
+        // The fields of other structs are checked without bounds, which could expose
+        // private types in the bounds of a public impl.
+        if generic {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: ::wgpu::ShaderType));
+        }
+        let (member, member_name) = match (&data.fields, &field.ident) {
+            (Fields::Named(_), Some(ident)) => (ident.to_token_stream(), ident.to_string()),
+            _ => (syn::Index::from(index).to_token_stream(), index.to_string()),
+        };
+        members.push(quote! {
+            ::wgpu::ShaderMemberLayout {
+                name: #member_name,
+                offset: ::core::mem::offset_of!(Self, #member) as u32,
+                ty: &<#ty as ::wgpu::ShaderType>::LAYOUT,
+            }
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let uniform_check = uniform.then(|| {
+        quote! {
+            const _: () = {
+                if let ::core::option::Option::Some(error) =
+                    <#ident as ::wgpu::ShaderType>::LAYOUT.uniform_layout_error()
+                {
+                    ::core::panic!("{}", error);
+                }
+            };
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::wgpu::ShaderType for #ident #ty_generics #where_clause {
+            const LAYOUT: ::wgpu::ShaderTypeLayout = ::wgpu::ShaderTypeLayout::Struct {
+                name: #name,
+                members: &[#(#members),*],
+                size: ::core::mem::size_of::<Self>() as u32,
+            };
+        }
+
+        #uniform_check
+    })
+}
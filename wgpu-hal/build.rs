@@ -7,6 +7,7 @@ fn main() {
         ) },
         webgl: { all(target_arch = "wasm32", not(target_os = "emscripten"), gles) },
         Emscripten: { all(target_os = "emscripten", gles) },
+        cpu: { feature = "cpu" },
         dx12: { all(target_os = "windows", feature = "dx12") },
         gles: { all(feature = "gles") },
         // Within the GL ES backend, use `std` and be Send + Sync only if we are using a target
@@ -102,6 +102,7 @@ impl<A: hal::Api> Example<A> {
             memory_budget_thresholds: wgpu_types::MemoryBudgetThresholds::default(),
             // Can't rely on having DXC available, so use FXC instead
             backend_options: wgpu_types::BackendOptions::default(),
+            toggles: wgpu_types::TogglesDescriptor::default(),
         };
         let instance = unsafe { A::Instance::init(&instance_desc)? };
         let surface = {
@@ -128,12 +129,13 @@ impl<A: hal::Api> Example<A> {
             .ok_or("failed to get surface capabilities")?;
         log::info!("Surface caps: {:#?}", surface_caps);
 
-        let hal::OpenDevice { device, queue } = unsafe {
+        let hal::OpenDevice { device, queue, .. } = unsafe {
             adapter
                 .open(
                     wgpu_types::Features::empty(),
                     &wgpu_types::Limits::default(),
                     &wgpu_types::MemoryHints::default(),
+                    &[],
                 )
                 .unwrap()
         };
@@ -146,6 +148,9 @@ impl<A: hal::Api> Example<A> {
             ),
             present_mode: wgpu_types::PresentMode::Fifo,
             composite_alpha_mode: wgpu_types::CompositeAlphaMode::Opaque,
+            color_space: wgpu_types::SurfaceColorSpace::Srgb,
+            hdr_metadata: None,
+            exclusive_fullscreen: false,
             format: wgpu_types::TextureFormat::Bgra8UnormSrgb,
             extent: wgpu_types::Extent3d {
                 width: window_size.0,
@@ -181,6 +186,7 @@ impl<A: hal::Api> Example<A> {
         let shader_desc = hal::ShaderModuleDescriptor {
             label: None,
             runtime_checks: wgpu_types::ShaderRuntimeChecks::checked(),
+            optimization: wgpu_types::ShaderOptimization::Default,
         };
         let shader = unsafe {
             device
@@ -280,6 +286,7 @@ impl<A: hal::Api> Example<A> {
                 format: surface_config.format,
                 blend: Some(wgpu_types::BlendState::ALPHA_BLENDING),
                 write_mask: wgpu_types::ColorWrites::default(),
+                logic_op: None,
             })],
             multiview: None,
             cache: None,
@@ -729,6 +736,9 @@ impl<A: hal::Api> Example<A> {
             multiview: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: wgpu_types::RenderPassFlags::empty(),
+            parallel: false,
         };
         unsafe {
             ctx.encoder.begin_render_pass(&pass_desc).unwrap();
@@ -771,7 +781,7 @@ impl<A: hal::Api> Example<A> {
                     (&mut ctx.fence, ctx.fence_value),
                 )
                 .unwrap();
-            self.queue.present(&self.surface, surface_tex).unwrap();
+            self.queue.present(&self.surface, surface_tex, &[]).unwrap();
             ctx.used_cmd_bufs.push(cmd_buf);
             ctx.used_views.push(surface_tex_view);
         };
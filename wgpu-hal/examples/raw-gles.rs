@@ -305,6 +305,7 @@ fn fill_screen(exposed: &hal::ExposedAdapter<hal::api::Gles>, width: u32, height
             wgpu_types::Features::empty(),
             &wgpu_types::Limits::downlevel_defaults(),
             &wgpu_types::MemoryHints::default(),
+            &[],
         )
     }
     .unwrap();
@@ -358,6 +359,9 @@ fn fill_screen(exposed: &hal::ExposedAdapter<hal::api::Gles>, width: u32, height
         multiview: None,
         timestamp_writes: None,
         occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        flags: wgpu_types::RenderPassFlags::empty(),
+        parallel: false,
     };
     unsafe {
         encoder.begin_encoding(None).unwrap();
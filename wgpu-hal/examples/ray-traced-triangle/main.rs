@@ -250,6 +250,7 @@ impl<A: hal::Api> Example<A> {
                 },
                 ..Default::default()
             },
+            toggles: wgpu_types::TogglesDescriptor::default(),
         };
         let instance = unsafe { A::Instance::init(&instance_desc)? };
         let surface = {
@@ -276,12 +277,13 @@ impl<A: hal::Api> Example<A> {
             .expect("Surface doesn't support presentation");
         log::info!("Surface caps: {:#?}", surface_caps);
 
-        let hal::OpenDevice { device, queue } = unsafe {
+        let hal::OpenDevice { device, queue, .. } = unsafe {
             adapter
                 .open(
                     features,
                     &wgpu_types::Limits::default(),
                     &wgpu_types::MemoryHints::Performance,
+                    &[],
                 )
                 .unwrap()
         };
@@ -302,6 +304,9 @@ impl<A: hal::Api> Example<A> {
                 .min(*surface_caps.maximum_frame_latency.end()),
             present_mode: wgpu_types::PresentMode::Fifo,
             composite_alpha_mode: wgpu_types::CompositeAlphaMode::Opaque,
+            color_space: wgpu_types::SurfaceColorSpace::Srgb,
+            hdr_metadata: None,
+            exclusive_fullscreen: false,
             format: surface_format,
             extent: wgpu_types::Extent3d {
                 width: window_size.0,
@@ -380,6 +385,7 @@ impl<A: hal::Api> Example<A> {
         let shader_desc = hal::ShaderModuleDescriptor {
             label: None,
             runtime_checks: wgpu_types::ShaderRuntimeChecks::checked(),
+            optimization: wgpu_types::ShaderOptimization::Default,
         };
         let shader_module = unsafe {
             device
@@ -532,6 +538,7 @@ impl<A: hal::Api> Example<A> {
                 size: blas_sizes.acceleration_structure_size,
                 format: hal::AccelerationStructureFormat::BottomLevel,
                 allow_compaction: false,
+                allow_serialization: false,
             })
         }
         .unwrap();
@@ -542,6 +549,7 @@ impl<A: hal::Api> Example<A> {
                 size: tlas_sizes.acceleration_structure_size,
                 format: hal::AccelerationStructureFormat::TopLevel,
                 allow_compaction: false,
+                allow_serialization: false,
             })
         }
         .unwrap();
@@ -974,6 +982,7 @@ impl<A: hal::Api> Example<A> {
             ctx.encoder.begin_compute_pass(&hal::ComputePassDescriptor {
                 label: None,
                 timestamp_writes: None,
+                pipeline_statistics_query: None,
             });
             ctx.encoder.set_compute_pipeline(&self.pipeline);
             ctx.encoder
@@ -1047,7 +1056,7 @@ impl<A: hal::Api> Example<A> {
                     (&mut ctx.fence, ctx.fence_value),
                 )
                 .unwrap();
-            self.queue.present(&self.surface, surface_tex).unwrap();
+            self.queue.present(&self.surface, surface_tex, &[]).unwrap();
             ctx.used_cmd_bufs.push(cmd_buf);
             ctx.used_views.push(surface_tex_view);
         };
@@ -0,0 +1,524 @@
+//! Host side of the `debugPrintf` emulation of backends without native support.
+//!
+//! Naga lowers [`DebugPrintf`] statements to writes into a storage buffer, laid
+//! out as described in [`DEBUG_PRINTF_HEADER_WORDS`]. The backend binds that buffer
+//! to every pipeline, and this module formats and logs the messages it contains
+//! once the submissions writing them are done.
+//!
+//! [`DebugPrintf`]: naga::Statement::DebugPrintf
+
+use alloc::{format, string::String, vec::Vec};
+use core::ptr::NonNull;
+
+use naga::back::DEBUG_PRINTF_HEADER_WORDS;
+use parking_lot::{Mutex, MutexGuard};
+
+/// The capacity of the ring, in words.
+///
+/// This must be a power of two, for positions to stay consistent as the
+/// counter of reserved words wraps around.
+const CAPACITY: u32 = 1 << 16;
+
+/// The size of the buffer shaders write messages to, in bytes.
+pub const BUFFER_SIZE: u64 = (DEBUG_PRINTF_HEADER_WORDS + CAPACITY) as u64 * 4;
+
+/// Reads the messages written by shaders, see the [module documentation](self).
+pub struct DebugPrintf {
+    /// The mapped contents of the buffer.
+    words: NonNull<u32>,
+    /// The format strings of all shaders translated so far.
+    formats: Mutex<Vec<String>>,
+    /// The number of words read so far, which wraps around like the number of
+    /// words reserved by shaders.
+    read: Mutex<u32>,
+}
+
+unsafe impl Send for DebugPrintf {}
+unsafe impl Sync for DebugPrintf {}
+
+impl DebugPrintf {
+    /// Clears the buffer at `words`, and starts reading messages from it.
+    ///
+    /// # Safety
+    ///
+    /// - `words` must point to [`BUFFER_SIZE`] bytes of host coherent memory,
+    ///   that stay mapped as long as the returned value lives.
+    /// - The GPU must not be using the buffer yet.
+    pub unsafe fn new(words: NonNull<u32>) -> Self {
+        unsafe {
+            words
+                .as_ptr()
+                .write_bytes(0, (DEBUG_PRINTF_HEADER_WORDS + CAPACITY) as usize);
+            words.as_ptr().add(1).write_volatile(CAPACITY);
+        }
+        Self {
+            words,
+            formats: Mutex::new(Vec::new()),
+            read: Mutex::new(0),
+        }
+    }
+
+    /// Locks the table of format strings.
+    ///
+    /// Shader translation appends the formats of a module to it, starting at
+    /// its current length, and keeps it locked meanwhile so that concurrent
+    /// translations don't use the same indices.
+    pub fn formats(&self) -> MutexGuard<'_, Vec<String>> {
+        self.formats.lock()
+    }
+
+    /// Logs the messages written since the last call.
+    ///
+    /// Call this once submissions are known to be done, as messages from
+    /// submissions still running are either skipped until the next call or
+    /// incomplete.
+    pub fn poll(&self) {
+        for message in self.read_messages() {
+            log::info!("{message}");
+        }
+    }
+
+    fn read_messages(&self) -> Vec<String> {
+        let ring = |position: u32| unsafe {
+            self.words
+                .as_ptr()
+                .add((DEBUG_PRINTF_HEADER_WORDS + position % CAPACITY) as usize)
+        };
+        let mut read = self.read.lock();
+        let reserved = unsafe { self.words.as_ptr().read_volatile() };
+        let mut messages = Vec::new();
+
+        while *read != reserved {
+            let pending = reserved.wrapping_sub(*read);
+            let length = unsafe { ring(*read).read_volatile() };
+            if pending > CAPACITY || (length != 0 && !(2..=pending).contains(&length)) {
+                // Shaders wrote more than the ring holds, overwriting messages.
+                log::warn!("Dropped debug printf messages, as shaders printed too much at once");
+                for position in 0..CAPACITY {
+                    unsafe { ring(position).write_volatile(0) };
+                }
+                *read = reserved;
+                break;
+            }
+            if length == 0 {
+                // The message is still being written.
+                break;
+            }
+
+            let record = (0..length)
+                .map(|i| unsafe {
+                    let word = ring(read.wrapping_add(i));
+                    let value = word.read_volatile();
+                    word.write_volatile(0);
+                    value
+                })
+                .collect::<Vec<_>>();
+            *read = read.wrapping_add(length);
+
+            match self.formats.lock().get(record[1] as usize) {
+                Some(format) => messages.push(format_message(format, &record[2..])),
+                None => log::warn!("Debug printf message has unknown format {}", record[1]),
+            }
+        }
+
+        messages
+    }
+}
+
+/// Formats `arguments` with the conversion specifiers of `format`, like C's `printf`.
+///
+/// Each argument is the bit pattern of a 32-bit value, which the conversion
+/// interprets. A vector conversion such as `%v3f` consumes as many arguments,
+/// formatting them separated by commas.
+fn format_message(format: &str, arguments: &[u32]) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut arguments = arguments.iter().copied();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        if chars.next_if_eq(&'%').is_some() {
+            out.push('%');
+            continue;
+        }
+
+        let mut spec = Spec::default();
+        while let Some(flag) = chars.next_if(|c| "-+ #0".contains(*c)) {
+            match flag {
+                '-' => spec.left = true,
+                '+' => spec.plus = true,
+                ' ' => spec.space = true,
+                '#' => spec.alternate = true,
+                _ => spec.zero = true,
+            }
+        }
+        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+            spec.width = spec.width * 10 + digit as usize - '0' as usize;
+        }
+        if chars.next_if_eq(&'.').is_some() {
+            let mut precision = 0;
+            while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                precision = precision * 10 + digit as usize - '0' as usize;
+            }
+            spec.precision = Some(precision);
+        }
+        let mut components = 1;
+        if chars.next_if_eq(&'v').is_some() {
+            components = chars.next().and_then(|c| c.to_digit(10)).unwrap_or(1);
+        }
+        let Some(conversion) = chars.next() else {
+            break;
+        };
+        spec.conversion = conversion;
+
+        for component in 0..components {
+            if component != 0 {
+                out.push_str(", ");
+            }
+            spec.write(&mut out, arguments.next().unwrap_or(0));
+        }
+    }
+
+    out
+}
+
+/// A conversion specifier.
+#[derive(Default)]
+struct Spec {
+    left: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    conversion: char,
+}
+
+impl Spec {
+    fn write(&self, out: &mut String, word: u32) {
+        let upper = self.conversion.is_ascii_uppercase();
+        let mut negative = false;
+        let mut prefix = "";
+        let mut zero_pad = self.zero;
+        let body = match self.conversion {
+            'd' | 'i' => {
+                negative = (word as i32) < 0;
+                self.integer((word as i32).unsigned_abs(), 10)
+            }
+            'u' => self.integer(word, 10),
+            'o' => {
+                let digits = self.integer(word, 8);
+                if self.alternate && !digits.starts_with('0') {
+                    format!("0{digits}")
+                } else {
+                    digits
+                }
+            }
+            'x' | 'X' => {
+                if self.alternate && word != 0 {
+                    prefix = if upper { "0X" } else { "0x" };
+                }
+                self.integer(word, 16)
+            }
+            _ => {
+                let value = f64::from(f32::from_bits(word));
+                negative = value.is_sign_negative();
+                let value = if negative { -value } else { value };
+                if !value.is_finite() {
+                    zero_pad = false;
+                    String::from(if value.is_nan() { "nan" } else { "inf" })
+                } else {
+                    match self.conversion.to_ascii_lowercase() {
+                        'e' => self.exponential(value, self.precision.unwrap_or(6)),
+                        'g' => self.general(value),
+                        'a' => {
+                            prefix = if upper { "0X" } else { "0x" };
+                            self.hexadecimal(value)
+                        }
+                        _ => self.decimal(value, self.precision.unwrap_or(6)),
+                    }
+                }
+            }
+        };
+        let body = if upper {
+            body.to_ascii_uppercase()
+        } else {
+            body
+        };
+        let sign = match (negative, self.conversion) {
+            (true, _) => "-",
+            (false, 'u' | 'o' | 'x' | 'X') => "",
+            (false, _) if self.plus => "+",
+            (false, _) if self.space => " ",
+            (false, _) => "",
+        };
+
+        // The precision of integer conversions overrides zero padding.
+        if "diouxX".contains(self.conversion) && self.precision.is_some() {
+            zero_pad = false;
+        }
+        let padding = self
+            .width
+            .saturating_sub(sign.len() + prefix.len() + body.len());
+        if self.left {
+            out.extend([sign, prefix, body.as_str()]);
+            out.extend(core::iter::repeat_n(' ', padding));
+        } else if zero_pad {
+            out.extend([sign, prefix]);
+            out.extend(core::iter::repeat_n('0', padding));
+            out.push_str(&body);
+        } else {
+            out.extend(core::iter::repeat_n(' ', padding));
+            out.extend([sign, prefix, body.as_str()]);
+        }
+    }
+
+    fn integer(&self, value: u32, radix: u32) -> String {
+        let digits = match (value, radix) {
+            (0, _) if self.precision == Some(0) => String::new(),
+            (_, 8) => format!("{value:o}"),
+            (_, 16) => format!("{value:x}"),
+            _ => format!("{value}"),
+        };
+        let precision = self.precision.unwrap_or(0);
+        format!("{digits:0>precision$}")
+    }
+
+    fn decimal(&self, value: f64, precision: usize) -> String {
+        let mut body = format!("{value:.precision$}");
+        if self.alternate && precision == 0 {
+            body.push('.');
+        }
+        body
+    }
+
+    fn exponential(&self, value: f64, precision: usize) -> String {
+        // Rust writes `1.5e-7` where C writes `1.5e-07`.
+        let body = format!("{value:.precision$e}");
+        let (mantissa, exponent) = body.split_once('e').unwrap();
+        let exponent = exponent.parse::<i32>().unwrap();
+        let point = if self.alternate && precision == 0 {
+            "."
+        } else {
+            ""
+        };
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{mantissa}{point}e{sign}{:02}", exponent.unsigned_abs())
+    }
+
+    fn general(&self, value: f64) -> String {
+        let precision = self.precision.unwrap_or(6).max(1);
+        let exponential = self.exponential(value, precision - 1);
+        let exponent = exponential
+            .split_once('e')
+            .unwrap()
+            .1
+            .parse::<i32>()
+            .unwrap();
+        let mut body = if -4 <= exponent && exponent < precision as i32 {
+            self.decimal(value, (precision as i32 - 1 - exponent) as usize)
+        } else {
+            exponential
+        };
+        if !self.alternate {
+            // Remove trailing zeros from the fractional part.
+            let end = body.find('e').unwrap_or(body.len());
+            if body[..end].contains('.') {
+                let trimmed = body[..end].trim_end_matches('0').trim_end_matches('.');
+                body = format!("{trimmed}{}", &body[end..]);
+            }
+        }
+        body
+    }
+
+    fn hexadecimal(&self, value: f64) -> String {
+        const FRACTION_DIGITS: usize = 13;
+        let bits = value.to_bits();
+        let (mut leading, mut fraction, exponent) = if value == 0.0 {
+            (0, 0, 0)
+        } else {
+            // Values converted from `f32` are never subnormal as `f64`.
+            (
+                1,
+                bits & ((1 << 52) - 1),
+                ((bits >> 52) & 0x7ff) as i32 - 1023,
+            )
+        };
+
+        let digits = match self.precision {
+            Some(precision) if precision < FRACTION_DIGITS => {
+                // Round to nearest, ties to even.
+                let shift = 4 * (FRACTION_DIGITS - precision);
+                let remainder = fraction & ((1 << shift) - 1);
+                let half = 1 << (shift - 1);
+                fraction >>= shift;
+                if remainder > half || (remainder == half && fraction & 1 == 1) {
+                    fraction += 1;
+                    if fraction >> (4 * precision) != 0 {
+                        leading += 1;
+                        fraction = 0;
+                    }
+                }
+                if precision == 0 {
+                    String::new()
+                } else {
+                    format!("{fraction:0precision$x}")
+                }
+            }
+            Some(precision) => format!("{fraction:013x}{:0<1$}", "", precision - FRACTION_DIGITS),
+            None => format!("{fraction:013x}").trim_end_matches('0').into(),
+        };
+
+        let point = if digits.is_empty() && !self.alternate {
+            ""
+        } else {
+            "."
+        };
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{leading}{point}{digits}p{sign}{}", exponent.unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec, vec::Vec};
+    use core::ptr::NonNull;
+
+    use super::{BUFFER_SIZE, CAPACITY, DEBUG_PRINTF_HEADER_WORDS, DebugPrintf, format_message};
+
+    /// Writes a record like a shader does, completing it unless `complete` is false.
+    ///
+    /// Returns the position of the record.
+    fn write_record(words: NonNull<u32>, record: &[u32], complete: bool) -> u32 {
+        let length = record.len() as u32 + 1;
+        unsafe {
+            let position = words.as_ptr().read();
+            words.as_ptr().write(position.wrapping_add(length));
+            for (i, &word) in record.iter().enumerate() {
+                write_word(words, position.wrapping_add(i as u32 + 1), word);
+            }
+            if complete {
+                write_word(words, position, length);
+            }
+            position
+        }
+    }
+
+    unsafe fn write_word(words: NonNull<u32>, position: u32, word: u32) {
+        let index = DEBUG_PRINTF_HEADER_WORDS + position % CAPACITY;
+        unsafe { words.as_ptr().add(index as usize).write(word) };
+    }
+
+    #[test]
+    fn ring() {
+        let mut buffer = vec![0u32; (BUFFER_SIZE / 4) as usize];
+        let words = NonNull::new(buffer.as_mut_ptr()).unwrap();
+        let debug_printf = unsafe { DebugPrintf::new(words) };
+        debug_printf
+            .formats()
+            .extend(["a %u".into(), "b %d %d".into()]);
+
+        write_record(words, &[0, 5], true);
+        let incomplete = write_record(words, &[1, 1, -2i32 as u32], false);
+        assert_eq!(debug_printf.read_messages(), ["a 5"]);
+        assert_eq!(debug_printf.read_messages(), Vec::<&str>::new());
+        unsafe { write_word(words, incomplete, 4) };
+        assert_eq!(debug_printf.read_messages(), ["b 1 -2"]);
+
+        // Wrap around the end of the ring.
+        for i in 0..CAPACITY / 2 {
+            write_record(words, &[0, i], true);
+            write_record(words, &[1, i, i], true);
+            assert_eq!(
+                debug_printf.read_messages(),
+                [format!("a {i}"), format!("b {i} {i}")]
+            );
+        }
+
+        // Drop messages that were overwritten.
+        for i in 0..CAPACITY / 3 + 1 {
+            write_record(words, &[0, i], true);
+        }
+        assert_eq!(debug_printf.read_messages(), Vec::<&str>::new());
+        write_record(words, &[0, 1], true);
+        assert_eq!(debug_printf.read_messages(), ["a 1"]);
+    }
+
+    fn float(value: f32) -> u32 {
+        value.to_bits()
+    }
+
+    #[test]
+    fn integers() {
+        let check = |format, argument: u32, expected| {
+            assert_eq!(format_message(format, &[argument]), expected, "{format}");
+        };
+        check("%d", -5i32 as u32, "-5");
+        check("%i", 42, "42");
+        check("%+d", 3, "+3");
+        check("% d", 3, " 3");
+        check("%u", u32::MAX, "4294967295");
+        check("%x", 0xabc, "abc");
+        check("%X", 0xabc, "ABC");
+        check("%#x", 0xabc, "0xabc");
+        check("%#x", 0, "0");
+        check("%#o", 8, "010");
+        check("%5d", -42i32 as u32, "  -42");
+        check("%-5d|", 42, "42   |");
+        check("%05d", -42i32 as u32, "-0042");
+        check("%.3d", 7, "007");
+        check("%08.3d", 7, "     007");
+        check("%.0d", 0, "");
+    }
+
+    #[test]
+    fn floats() {
+        let check = |format, argument: f32, expected| {
+            assert_eq!(
+                format_message(format, &[float(argument)]),
+                expected,
+                "{format}"
+            );
+        };
+        check("%f", 1.5, "1.500000");
+        check("%5.2f", 1.23456, " 1.23");
+        check("%.0f", 2.75, "3");
+        check("%#.0f", 3.0, "3.");
+        check("%+.1f", 2.0, "+2.0");
+        check("%e", 1234.75, "1.234750e+03");
+        check("%010.2e", 1234.75, "001.23e+03");
+        check("%E", -0.00125, "-1.250000E-03");
+        check("%g", 1.5, "1.5");
+        check("%g", 0.0001, "0.0001");
+        check("%g", 0.00001, "1e-05");
+        check("%g", 100000.0, "100000");
+        check("%g", 1000000.0, "1e+06");
+        check("%G", 0.00001, "1E-05");
+        check("%#g", 1.5, "1.50000");
+        check("%a", 1.0, "0x1p+0");
+        check("%a", -0.5, "-0x1p-1");
+        check("%A", 3.0, "0X1.8P+1");
+        check("%.1a", 1.0, "0x1.0p+0");
+        check("%.0a", 1.75, "0x2p+0");
+        check("%a", 0.0, "0x0p+0");
+        check("%f", f32::INFINITY, "inf");
+        check("%5.1F", f32::NEG_INFINITY, " -INF");
+        check("%05f", f32::NAN, "  nan");
+    }
+
+    #[test]
+    fn messages() {
+        assert_eq!(
+            format_message("id = %v3u, 100%% done", &[1, 2, 3]),
+            "id = 1, 2, 3, 100% done"
+        );
+        assert_eq!(
+            format_message("%d: %.1v2f\n", &[7, float(0.5), float(-1.3)]),
+            "7: 0.5, -1.3\n"
+        );
+    }
+}
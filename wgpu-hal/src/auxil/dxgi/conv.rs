@@ -68,6 +68,7 @@ pub fn map_texture_format_failable(
         Tf::Depth32Float => DXGI_FORMAT_D32_FLOAT,
         Tf::Depth32FloatStencil8 => DXGI_FORMAT_D32_FLOAT_S8X24_UINT,
         Tf::NV12 => DXGI_FORMAT_NV12,
+        Tf::P010 => DXGI_FORMAT_P010,
         Tf::Bc1RgbaUnorm => DXGI_FORMAT_BC1_UNORM,
         Tf::Bc1RgbaUnormSrgb => DXGI_FORMAT_BC1_UNORM_SRGB,
         Tf::Bc2RgbaUnorm => DXGI_FORMAT_BC2_UNORM,
@@ -92,6 +93,7 @@ pub fn map_texture_format_failable(
         | Tf::EacR11Snorm
         | Tf::EacRg11Unorm
         | Tf::EacRg11Snorm
+        | Tf::I420
         | Tf::Astc {
             block: _,
             channel: _,
@@ -227,6 +229,8 @@ pub fn map_texture_format_for_resource(
 
 pub fn map_index_format(format: wgt::IndexFormat) -> Dxgi::Common::DXGI_FORMAT {
     match format {
+        // D3D12 only accepts 16 and 32 bit index buffers
+        wgt::IndexFormat::Uint8 => unreachable!(),
         wgt::IndexFormat::Uint16 => Dxgi::Common::DXGI_FORMAT_R16_UINT,
         wgt::IndexFormat::Uint32 => Dxgi::Common::DXGI_FORMAT_R32_UINT,
     }
@@ -292,3 +296,14 @@ pub fn map_acomposite_alpha_mode(mode: wgt::CompositeAlphaMode) -> Dxgi::Common:
         }
     }
 }
+
+pub fn map_color_space(color_space: wgt::SurfaceColorSpace) -> Dxgi::Common::DXGI_COLOR_SPACE_TYPE {
+    match color_space {
+        wgt::SurfaceColorSpace::Srgb => Dxgi::Common::DXGI_COLOR_SPACE_RGB_FULL_G22_NONE_P709,
+        wgt::SurfaceColorSpace::ExtendedSrgbLinear => {
+            Dxgi::Common::DXGI_COLOR_SPACE_RGB_FULL_G10_NONE_P709
+        }
+        wgt::SurfaceColorSpace::Hdr10 => Dxgi::Common::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020,
+        wgt::SurfaceColorSpace::ExtendedSrgb | wgt::SurfaceColorSpace::Auto => unreachable!(),
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::{borrow::Cow, string::String};
+use alloc::{borrow::Cow, string::String, vec::Vec};
 
 use parking_lot::Mutex;
 use windows::Win32::{Foundation, System::Diagnostics::Debug};
@@ -19,6 +19,18 @@ pub fn register_exception_handler() {
     *count_guard += 1;
 }
 
+/// Errors reported by GPU-based validation that no device has taken yet.
+///
+/// The debug layer reports messages through `OutputDebugString`, which isn't tied
+/// to a device, so every device with GPU-based validation enabled takes from the
+/// same queue.
+static GPU_BASED_VALIDATION_MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Returns the errors reported by GPU-based validation since the last call.
+pub fn take_gpu_based_validation_messages() -> Vec<String> {
+    core::mem::take(&mut *GPU_BASED_VALIDATION_MESSAGES.lock())
+}
+
 pub fn unregister_exception_handler() {
     let mut count_guard = EXCEPTION_HANDLER_COUNT.lock();
     if *count_guard == 1 {
@@ -83,6 +95,12 @@ unsafe extern "system" fn output_debug_string_handler(
         log::log!(level, "{}", message);
     });
 
+    if level == log::Level::Error && message.contains("GPU-BASED VALIDATION") {
+        GPU_BASED_VALIDATION_MESSAGES
+            .lock()
+            .push(String::from(message));
+    }
+
     #[cfg(feature = "validation_canary")]
     if cfg!(debug_assertions) && level == log::Level::Error {
         use alloc::string::ToString as _;
@@ -12,7 +12,9 @@ impl<T> HResult<T> for windows::core::Result<T> {
 
             match err.code() {
                 Foundation::E_OUTOFMEMORY => crate::DeviceError::OutOfMemory,
-                Dxgi::DXGI_ERROR_DEVICE_RESET | Dxgi::DXGI_ERROR_DEVICE_REMOVED => {
+                Dxgi::DXGI_ERROR_DEVICE_RESET
+                | Dxgi::DXGI_ERROR_DEVICE_REMOVED
+                | Dxgi::DXGI_ERROR_DEVICE_HUNG => {
                     #[cfg(feature = "device_lost_panic")]
                     panic!("{description} failed: Device lost ({err})");
                     crate::DeviceError::Lost
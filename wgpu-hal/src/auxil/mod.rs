@@ -1,3 +1,6 @@
+#[cfg(any(dx12, metal))]
+pub(super) mod debug_printf;
+
 #[cfg(dx12)]
 pub(super) mod dxgi;
 
@@ -55,6 +58,39 @@ pub mod db {
 /// offset at some intermediate point, internally, as i32.
 pub const MAX_I32_BINDING_SIZE: u32 = (1 << 31) - 1;
 
+/// Size of the header Vulkan and DX12 both put at the start of serialized acceleration
+/// structures: a 32-byte driver identifier, followed by the serialized size, the
+/// deserialized size and the number of bottom level handles, each a `u64`.
+#[cfg(any(vulkan, dx12))]
+pub const SERIALIZED_ACCELERATION_STRUCTURE_HEADER_SIZE: usize = 56;
+
+/// Splits the header of serialized acceleration structure `data` into its driver
+/// identifier and the size the acceleration structure needs once deserialized.
+///
+/// Returns `None` if `data` is too short to hold a header.
+#[cfg(any(vulkan, dx12))]
+pub fn parse_serialized_acceleration_structure_header(
+    data: &[u8],
+) -> Option<(&[u8; 32], wgt::BufferAddress)> {
+    let header = data.get(..SERIALIZED_ACCELERATION_STRUCTURE_HEADER_SIZE)?;
+    let identifier = header[..32].try_into().unwrap();
+    let deserialized_size = u64::from_le_bytes(header[40..48].try_into().unwrap());
+    Some((identifier, deserialized_size))
+}
+
+/// Chromaticity coordinates of the red, green and blue primaries and the
+/// white point of `color_space`, as passed along with HDR metadata.
+#[cfg(any(vulkan, dx12))]
+pub fn color_space_primaries(color_space: wgt::SurfaceColorSpace) -> [[f32; 2]; 4] {
+    const D65: [f32; 2] = [0.3127, 0.3290];
+    match color_space {
+        // BT.2020
+        wgt::SurfaceColorSpace::Hdr10 => [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046], D65],
+        // BT.709, shared by the sRGB color spaces
+        _ => [[0.640, 0.330], [0.300, 0.600], [0.150, 0.060], D65],
+    }
+}
+
 pub fn map_naga_stage(stage: naga::ShaderStage) -> wgt::ShaderStages {
     match stage {
         naga::ShaderStage::Vertex => wgt::ShaderStages::VERTEX,
@@ -62,6 +98,10 @@ pub fn map_naga_stage(stage: naga::ShaderStage) -> wgt::ShaderStages {
         naga::ShaderStage::Compute => wgt::ShaderStages::COMPUTE,
         naga::ShaderStage::Task => wgt::ShaderStages::TASK,
         naga::ShaderStage::Mesh => wgt::ShaderStages::MESH,
+        naga::ShaderStage::RayGeneration => wgt::ShaderStages::RAY_GENERATION,
+        naga::ShaderStage::Miss => wgt::ShaderStages::MISS,
+        naga::ShaderStage::ClosestHit => wgt::ShaderStages::CLOSEST_HIT,
+        naga::ShaderStage::AnyHit => wgt::ShaderStages::ANY_HIT,
     }
 }
 
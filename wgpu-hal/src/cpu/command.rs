@@ -0,0 +1,1166 @@
+use alloc::vec::Vec;
+use core::{mem, ops::Range};
+
+use super::{
+    Api, BindGroup, Buffer, ComputePipeline, DeviceResult, QuerySet, RenderPipeline, Resource,
+    Texture, TextureView,
+    interpreter::{self, Resources},
+    raster,
+};
+use crate::FormatAspects;
+
+/// Command encoder, which records the commands to execute them on submission.
+#[derive(Debug)]
+pub struct CommandEncoder {
+    commands: Vec<Command>,
+}
+
+#[derive(Debug)]
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+}
+
+#[derive(Debug)]
+struct ColorAttachment {
+    view: TextureView,
+    depth_slice: u32,
+    clear_value: Option<wgt::Color>,
+}
+
+#[derive(Debug)]
+struct DepthStencilAttachment {
+    view: TextureView,
+    depth_clear_value: Option<f32>,
+    stencil_clear_value: Option<u32>,
+}
+
+#[derive(Debug)]
+enum Command {
+    ClearBuffer {
+        buffer: Buffer,
+        range: crate::MemoryRange,
+    },
+    CopyBufferToBuffer {
+        src: Buffer,
+        dst: Buffer,
+        regions: Vec<crate::BufferCopy>,
+    },
+    CopyBufferToTexture {
+        src: Buffer,
+        dst: Texture,
+        regions: Vec<crate::BufferTextureCopy>,
+    },
+    CopyTextureToBuffer {
+        src: Texture,
+        dst: Buffer,
+        regions: Vec<crate::BufferTextureCopy>,
+    },
+    CopyTextureToTexture {
+        src: Texture,
+        dst: Texture,
+        regions: Vec<crate::TextureCopy>,
+    },
+    ResetQueries {
+        set: QuerySet,
+        range: Range<u32>,
+    },
+    BeginOcclusionQuery {
+        set: QuerySet,
+        index: u32,
+    },
+    EndOcclusionQuery,
+    /// Writes zero to a query, for timestamps and pipeline statistics, which are not
+    /// measured.
+    WriteZero {
+        set: QuerySet,
+        index: u32,
+    },
+    CopyQueryResults {
+        set: QuerySet,
+        range: Range<u32>,
+        buffer: Buffer,
+        offset: wgt::BufferAddress,
+        stride: wgt::BufferAddress,
+    },
+    BeginRenderPass {
+        extent: wgt::Extent3d,
+        color_attachments: Vec<Option<ColorAttachment>>,
+        depth_stencil_attachment: Option<DepthStencilAttachment>,
+    },
+    EndRenderPass,
+    SetBindGroup {
+        index: u32,
+        group: BindGroup,
+    },
+    SetPushConstants {
+        offset_bytes: u32,
+        data: Vec<u32>,
+    },
+    SetRenderPipeline(RenderPipeline),
+    SetComputePipeline(ComputePipeline),
+    SetIndexBuffer {
+        buffer: Buffer,
+        offset: wgt::BufferAddress,
+        format: wgt::IndexFormat,
+    },
+    SetVertexBuffer {
+        index: u32,
+        buffer: Buffer,
+        offset: wgt::BufferAddress,
+    },
+    SetViewport {
+        rect: crate::Rect<f32>,
+        depth_range: Range<f32>,
+    },
+    SetScissorRect(crate::Rect<u32>),
+    SetStencilReference(u32),
+    SetBlendConstants([f32; 4]),
+    BeginConditionalRendering {
+        buffer: Buffer,
+        offset: wgt::BufferAddress,
+    },
+    EndConditionalRendering,
+    Draw(raster::Draw),
+    DrawIndirect {
+        buffer: Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+        indexed: bool,
+    },
+    Dispatch([u32; 3]),
+    DispatchIndirect {
+        buffer: Buffer,
+        offset: wgt::BufferAddress,
+    },
+}
+
+/// The attachments of the current render pass.
+struct RenderPass {
+    extent: wgt::Extent3d,
+    color_attachments: Vec<Option<(TextureView, u32)>>,
+    depth_stencil_attachment: Option<TextureView>,
+}
+
+/// The state set by the commands of a command buffer.
+#[derive(Default)]
+struct State {
+    bind_groups: Vec<Option<BindGroup>>,
+    push_constants: Vec<u8>,
+    render_pipeline: Option<RenderPipeline>,
+    compute_pipeline: Option<ComputePipeline>,
+    vertex_buffers: Vec<Option<(Buffer, wgt::BufferAddress)>>,
+    index_buffer: Option<(Buffer, wgt::BufferAddress, wgt::IndexFormat)>,
+    render_pass: Option<RenderPass>,
+    viewport: Option<crate::Rect<f32>>,
+    depth_range: Range<f32>,
+    scissor: Option<crate::Rect<u32>>,
+    stencil_reference: u32,
+    blend_constant: [f32; 4],
+    /// The occlusion query being written, with the number of samples which passed so far.
+    occlusion_query: Option<(QuerySet, u32, u64)>,
+    /// Whether draws are skipped by conditional rendering.
+    skip_draws: bool,
+}
+
+impl CommandEncoder {
+    pub(super) fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+}
+
+impl CommandBuffer {
+    /// # Safety
+    ///
+    /// Must be called with appropriate synchronization for the resources affected by the command,
+    /// such as ensuring that buffers are not accessed by a command while aliasing references exist.
+    pub(super) unsafe fn execute(&self) {
+        let mut state = State::default();
+        for command in &self.commands {
+            unsafe { command.execute(&mut state) };
+        }
+    }
+}
+
+impl crate::CommandEncoder for CommandEncoder {
+    type A = Api;
+
+    unsafe fn begin_encoding(&mut self, _label: crate::Label) -> DeviceResult<()> {
+        assert!(self.commands.is_empty());
+        Ok(())
+    }
+    unsafe fn discard_encoding(&mut self) {
+        self.commands.clear();
+    }
+    unsafe fn end_encoding(&mut self) -> DeviceResult<CommandBuffer> {
+        Ok(CommandBuffer {
+            commands: mem::take(&mut self.commands),
+        })
+    }
+    unsafe fn reset_all<I>(&mut self, _command_buffers: I) {}
+
+    // Commands are executed in order, so barriers are not needed.
+    unsafe fn transition_buffers<'a, T>(&mut self, _barriers: T)
+    where
+        T: Iterator<Item = crate::BufferBarrier<'a, Buffer>>,
+    {
+    }
+
+    unsafe fn transition_textures<'a, T>(&mut self, _barriers: T)
+    where
+        T: Iterator<Item = crate::TextureBarrier<'a, Texture>>,
+    {
+    }
+
+    unsafe fn clear_buffer(&mut self, buffer: &Buffer, range: crate::MemoryRange) {
+        self.commands.push(Command::ClearBuffer {
+            buffer: buffer.clone(),
+            range,
+        })
+    }
+
+    unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &Buffer, dst: &Buffer, regions: T)
+    where
+        T: Iterator<Item = crate::BufferCopy>,
+    {
+        self.commands.push(Command::CopyBufferToBuffer {
+            src: src.clone(),
+            dst: dst.clone(),
+            regions: regions.collect(),
+        });
+    }
+
+    #[cfg(webgl)]
+    unsafe fn copy_external_image_to_texture<T>(
+        &mut self,
+        _src: &wgt::CopyExternalImageSourceInfo,
+        _dst: &Texture,
+        _dst_premultiplication: bool,
+        _regions: T,
+    ) where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        super::unsupported("copies of external images")
+    }
+
+    unsafe fn copy_texture_to_texture<T>(
+        &mut self,
+        src: &Texture,
+        _src_usage: wgt::TextureUses,
+        dst: &Texture,
+        regions: T,
+    ) where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        self.commands.push(Command::CopyTextureToTexture {
+            src: src.clone(),
+            dst: dst.clone(),
+            regions: regions.collect(),
+        });
+    }
+
+    unsafe fn resolve_texture<T>(&mut self, _src: &Texture, _dst: &Texture, _regions: T)
+    where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        // Multisampled textures are not supported.
+    }
+
+    unsafe fn copy_buffer_to_texture<T>(&mut self, src: &Buffer, dst: &Texture, regions: T)
+    where
+        T: Iterator<Item = crate::BufferTextureCopy>,
+    {
+        self.commands.push(Command::CopyBufferToTexture {
+            src: src.clone(),
+            dst: dst.clone(),
+            regions: regions.collect(),
+        });
+    }
+
+    unsafe fn copy_texture_to_buffer<T>(
+        &mut self,
+        src: &Texture,
+        _src_usage: wgt::TextureUses,
+        dst: &Buffer,
+        regions: T,
+    ) where
+        T: Iterator<Item = crate::BufferTextureCopy>,
+    {
+        self.commands.push(Command::CopyTextureToBuffer {
+            src: src.clone(),
+            dst: dst.clone(),
+            regions: regions.collect(),
+        });
+    }
+
+    unsafe fn begin_query(&mut self, set: &QuerySet, index: u32) {
+        self.commands.push(Command::BeginOcclusionQuery {
+            set: set.clone(),
+            index,
+        });
+    }
+    unsafe fn end_query(&mut self, _set: &QuerySet, _index: u32) {
+        self.commands.push(Command::EndOcclusionQuery);
+    }
+    unsafe fn write_timestamp(&mut self, set: &QuerySet, index: u32) {
+        self.commands.push(Command::WriteZero {
+            set: set.clone(),
+            index,
+        });
+    }
+    unsafe fn read_acceleration_structure_compact_size(
+        &mut self,
+        _acceleration_structure: &Resource,
+        _buf: &Buffer,
+    ) {
+        super::unsupported("acceleration structures")
+    }
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        _acceleration_structure: &Resource,
+        _buf: &Buffer,
+    ) {
+        super::unsupported("acceleration structures")
+    }
+    unsafe fn reset_queries(&mut self, set: &QuerySet, range: Range<u32>) {
+        self.commands.push(Command::ResetQueries {
+            set: set.clone(),
+            range,
+        });
+    }
+    unsafe fn copy_query_results(
+        &mut self,
+        set: &QuerySet,
+        range: Range<u32>,
+        buffer: &Buffer,
+        offset: wgt::BufferAddress,
+        stride: wgt::BufferSize,
+    ) {
+        self.commands.push(Command::CopyQueryResults {
+            set: set.clone(),
+            range,
+            buffer: buffer.clone(),
+            offset,
+            stride: stride.get(),
+        });
+    }
+
+    // render
+
+    unsafe fn begin_render_pass(
+        &mut self,
+        desc: &crate::RenderPassDescriptor<QuerySet, TextureView>,
+    ) -> DeviceResult<()> {
+        if let Some(ref timestamp_writes) = desc.timestamp_writes {
+            if let Some(index) = timestamp_writes.beginning_of_pass_write_index {
+                unsafe { self.write_timestamp(timestamp_writes.query_set, index) };
+            }
+        }
+        let color_attachments = desc
+            .color_attachments
+            .iter()
+            .map(|attachment| {
+                attachment.as_ref().map(|attachment| ColorAttachment {
+                    view: attachment.target.view.clone(),
+                    depth_slice: attachment.depth_slice.unwrap_or(0),
+                    clear_value: (!attachment
+                        .ops
+                        .with_pass_flags(desc.flags)
+                        .contains(crate::AttachmentOps::LOAD))
+                    .then_some(attachment.clear_value),
+                })
+            })
+            .collect();
+        let depth_stencil_attachment =
+            desc.depth_stencil_attachment
+                .as_ref()
+                .map(|attachment| DepthStencilAttachment {
+                    view: attachment.target.view.clone(),
+                    depth_clear_value: (!attachment
+                        .depth_ops
+                        .with_pass_flags(desc.flags)
+                        .contains(crate::AttachmentOps::LOAD))
+                    .then_some(attachment.clear_value.0),
+                    stencil_clear_value: (!attachment
+                        .stencil_ops
+                        .with_pass_flags(desc.flags)
+                        .contains(crate::AttachmentOps::LOAD))
+                    .then_some(attachment.clear_value.1),
+                });
+        self.commands.push(Command::BeginRenderPass {
+            extent: desc.extent,
+            color_attachments,
+            depth_stencil_attachment,
+        });
+        if let Some(ref timestamp_writes) = desc.timestamp_writes {
+            if let Some(index) = timestamp_writes.end_of_pass_write_index {
+                // Timestamps are all zero, so the end of the pass can be written right away.
+                unsafe { self.write_timestamp(timestamp_writes.query_set, index) };
+            }
+        }
+        if let Some(ref query) = desc.pipeline_statistics_query {
+            unsafe { self.write_timestamp(query.query_set, query.query_index) };
+        }
+        Ok(())
+    }
+    unsafe fn end_render_pass(&mut self) {
+        self.commands.push(Command::EndRenderPass);
+    }
+
+    unsafe fn create_parallel_render_encoder(&mut self) -> DeviceResult<CommandEncoder> {
+        Err(crate::DeviceError::Unexpected)
+    }
+
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        _encoders: Vec<CommandEncoder>,
+    ) -> DeviceResult<()> {
+        unreachable!("parallel render encoders are not supported on the CPU backend")
+    }
+
+    unsafe fn set_bind_group(
+        &mut self,
+        _layout: &Resource,
+        index: u32,
+        group: &BindGroup,
+        dynamic_offsets: &[wgt::DynamicOffset],
+    ) {
+        self.commands.push(Command::SetBindGroup {
+            index,
+            group: group.with_dynamic_offsets(dynamic_offsets),
+        });
+    }
+    unsafe fn set_push_constants(
+        &mut self,
+        _layout: &Resource,
+        _stages: wgt::ShaderStages,
+        offset_bytes: u32,
+        data: &[u32],
+    ) {
+        self.commands.push(Command::SetPushConstants {
+            offset_bytes,
+            data: data.to_vec(),
+        });
+    }
+
+    unsafe fn insert_debug_marker(&mut self, _label: &str) {}
+    unsafe fn begin_debug_marker(&mut self, _group_label: &str) {}
+    unsafe fn end_debug_marker(&mut self) {}
+
+    unsafe fn set_render_pipeline(&mut self, pipeline: &RenderPipeline) {
+        self.commands
+            .push(Command::SetRenderPipeline(pipeline.clone()));
+    }
+
+    unsafe fn set_index_buffer<'a>(
+        &mut self,
+        binding: crate::BufferBinding<'a, Buffer>,
+        format: wgt::IndexFormat,
+    ) {
+        self.commands.push(Command::SetIndexBuffer {
+            buffer: binding.buffer.clone(),
+            offset: binding.offset,
+            format,
+        });
+    }
+    unsafe fn set_vertex_buffer<'a>(
+        &mut self,
+        index: u32,
+        binding: crate::BufferBinding<'a, Buffer>,
+    ) {
+        self.commands.push(Command::SetVertexBuffer {
+            index,
+            buffer: binding.buffer.clone(),
+            offset: binding.offset,
+        });
+    }
+    unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {
+        self.commands.push(Command::SetViewport {
+            rect: rect.clone(),
+            depth_range,
+        });
+    }
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+        // Only one viewport is supported.
+        if index == 0 {
+            unsafe { self.set_viewport(rect, depth_range) };
+        }
+    }
+    unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
+        self.commands.push(Command::SetScissorRect(rect.clone()));
+    }
+    unsafe fn set_stencil_reference(&mut self, value: u32) {
+        self.commands.push(Command::SetStencilReference(value));
+    }
+    unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
+        self.commands.push(Command::SetBlendConstants(*color));
+    }
+    unsafe fn set_shading_rate(&mut self, _rate: wgt::ShadingRate) {}
+    unsafe fn begin_conditional_rendering(&mut self, buffer: &Buffer, offset: wgt::BufferAddress) {
+        self.commands.push(Command::BeginConditionalRendering {
+            buffer: buffer.clone(),
+            offset,
+        });
+    }
+    unsafe fn end_conditional_rendering(&mut self) {
+        self.commands.push(Command::EndConditionalRendering);
+    }
+
+    unsafe fn draw(
+        &mut self,
+        first_vertex: u32,
+        vertex_count: u32,
+        first_instance: u32,
+        instance_count: u32,
+    ) {
+        self.commands.push(Command::Draw(raster::Draw {
+            vertices: first_vertex..first_vertex + vertex_count,
+            instances: first_instance..first_instance + instance_count,
+            base_vertex: 0,
+            indexed: false,
+        }));
+    }
+    unsafe fn draw_indexed(
+        &mut self,
+        first_index: u32,
+        index_count: u32,
+        base_vertex: i32,
+        first_instance: u32,
+        instance_count: u32,
+    ) {
+        self.commands.push(Command::Draw(raster::Draw {
+            vertices: first_index..first_index + index_count,
+            instances: first_instance..first_instance + instance_count,
+            base_vertex,
+            indexed: true,
+        }));
+    }
+    unsafe fn draw_mesh_tasks(
+        &mut self,
+        _group_count_x: u32,
+        _group_count_y: u32,
+        _group_count_z: u32,
+    ) {
+        super::unsupported("mesh shaders")
+    }
+    unsafe fn draw_indirect(
+        &mut self,
+        buffer: &Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+        self.commands.push(Command::DrawIndirect {
+            buffer: buffer.clone(),
+            offset,
+            draw_count,
+            indexed: false,
+        });
+    }
+    unsafe fn draw_indexed_indirect(
+        &mut self,
+        buffer: &Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
+    ) {
+        self.commands.push(Command::DrawIndirect {
+            buffer: buffer.clone(),
+            offset,
+            draw_count,
+            indexed: true,
+        });
+    }
+    unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        _buffer: &Buffer,
+        _offset: wgt::BufferAddress,
+        _draw_count: u32,
+    ) {
+        super::unsupported("mesh shaders")
+    }
+    unsafe fn draw_indirect_count(
+        &mut self,
+        _buffer: &Buffer,
+        _offset: wgt::BufferAddress,
+        _count_buffer: &Buffer,
+        _count_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        super::unsupported("multi-draw indirect count")
+    }
+    unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        _buffer: &Buffer,
+        _offset: wgt::BufferAddress,
+        _count_buffer: &Buffer,
+        _count_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        super::unsupported("multi-draw indirect count")
+    }
+    unsafe fn draw_mesh_tasks_indirect_count(
+        &mut self,
+        _buffer: &Buffer,
+        _offset: wgt::BufferAddress,
+        _count_buffer: &Buffer,
+        _count_offset: wgt::BufferAddress,
+        _max_count: u32,
+    ) {
+        super::unsupported("mesh shaders")
+    }
+
+    // compute
+
+    unsafe fn begin_compute_pass(&mut self, desc: &crate::ComputePassDescriptor<QuerySet>) {
+        if let Some(ref timestamp_writes) = desc.timestamp_writes {
+            for index in [
+                timestamp_writes.beginning_of_pass_write_index,
+                timestamp_writes.end_of_pass_write_index,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                unsafe { self.write_timestamp(timestamp_writes.query_set, index) };
+            }
+        }
+        if let Some(ref query) = desc.pipeline_statistics_query {
+            unsafe { self.write_timestamp(query.query_set, query.query_index) };
+        }
+    }
+    unsafe fn end_compute_pass(&mut self) {}
+
+    unsafe fn set_compute_pipeline(&mut self, pipeline: &ComputePipeline) {
+        self.commands
+            .push(Command::SetComputePipeline(pipeline.clone()));
+    }
+
+    unsafe fn dispatch(&mut self, count: [u32; 3]) {
+        self.commands.push(Command::Dispatch(count));
+    }
+    unsafe fn dispatch_indirect(&mut self, buffer: &Buffer, offset: wgt::BufferAddress) {
+        self.commands.push(Command::DispatchIndirect {
+            buffer: buffer.clone(),
+            offset,
+        });
+    }
+
+    unsafe fn set_ray_tracing_pipeline(&mut self, _pipeline: &Resource) {
+        super::unsupported("ray tracing pipelines")
+    }
+    unsafe fn trace_rays(
+        &mut self,
+        _tables: &crate::ShaderBindingTables<Buffer>,
+        _count: [u32; 3],
+    ) {
+        super::unsupported("ray tracing pipelines")
+    }
+
+    unsafe fn build_acceleration_structures<'a, T>(
+        &mut self,
+        _descriptor_count: u32,
+        _descriptors: T,
+    ) where
+        Api: 'a,
+        T: IntoIterator<Item = crate::BuildAccelerationStructureDescriptor<'a, Buffer, Resource>>,
+    {
+        super::unsupported("acceleration structures")
+    }
+
+    unsafe fn place_acceleration_structure_barrier(
+        &mut self,
+        _barriers: crate::AccelerationStructureBarrier,
+    ) {
+    }
+
+    unsafe fn copy_acceleration_structure_to_acceleration_structure(
+        &mut self,
+        _src: &Resource,
+        _dst: &Resource,
+        _copy: wgt::AccelerationStructureCopy,
+    ) {
+        super::unsupported("acceleration structures")
+    }
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        _src: &Resource,
+        _dst: &Buffer,
+        _dst_offset: wgt::BufferAddress,
+    ) {
+        super::unsupported("acceleration structures")
+    }
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        _src: &Buffer,
+        _src_offset: wgt::BufferAddress,
+        _dst: &Resource,
+    ) {
+        super::unsupported("acceleration structures")
+    }
+}
+
+/// Reads `N` consecutive `u32`s from `buffer` at `offset`, for indirect arguments.
+///
+/// # Safety
+///
+/// The range must not be written to concurrently.
+unsafe fn read_words<const N: usize>(buffer: &Buffer, offset: wgt::BufferAddress) -> [u32; N] {
+    // SAFETY: The caller is responsible for ensuring this does not alias.
+    let bytes = unsafe { &*buffer.get_slice_ptr(offset..offset + 4 * N as u64) };
+    core::array::from_fn(|i| u32::from_le_bytes(bytes[4 * i..][..4].try_into().unwrap()))
+}
+
+/// Writes a query result to the results of `set`.
+///
+/// # Safety
+///
+/// The results must not be accessed concurrently.
+unsafe fn write_query(set: &QuerySet, index: u32, value: u64) {
+    let offset = index as u64 * 8;
+    // SAFETY: The caller is responsible for ensuring this does not alias.
+    let result = unsafe { &mut *set.results.get_slice_ptr(offset..offset + 8) };
+    result.copy_from_slice(&value.to_le_bytes());
+}
+
+/// Returns the texel of a clear color for the format of `view`.
+fn clear_texel(view: &TextureView, color: wgt::Color) -> super::format::Texel {
+    let color = [color.r, color.g, color.b, color.a];
+    match view.format.sample_type(None, None) {
+        Some(wgt::TextureSampleType::Sint) => super::format::Texel::Sint(color.map(|c| c as i32)),
+        Some(wgt::TextureSampleType::Uint) => super::format::Texel::Uint(color.map(|c| c as u32)),
+        _ => super::format::Texel::Float(color.map(|c| c as f32)),
+    }
+}
+
+/// Calls `f` with the pointer to each row of texels of a copy region of `texture`, and the
+/// offset of the row in the buffer of the copy.
+fn for_each_row(
+    texture: &Texture,
+    base: &crate::TextureCopyBase,
+    size: &crate::CopyExtent,
+    layout: Option<&wgt::TexelCopyBufferLayout>,
+    mut f: impl FnMut(*mut [u8], wgt::BufferAddress),
+) {
+    let Some(plane) = texture.plane(base.aspect) else {
+        return;
+    };
+    let row_size = size.width as u64 * plane.texel_size as u64;
+    let (offset, bytes_per_row, rows_per_image) = match layout {
+        Some(layout) => (
+            layout.offset,
+            layout.bytes_per_row.map_or(row_size, u64::from),
+            layout.rows_per_image.unwrap_or(size.height) as u64,
+        ),
+        None => (0, row_size, size.height as u64),
+    };
+    for z in 0..size.depth {
+        for y in 0..size.height {
+            let origin = [
+                base.origin.x,
+                base.origin.y + y,
+                base.array_layer + base.origin.z + z,
+            ];
+            if let Some(ptr) = texture.texels_ptr(base.aspect, base.mip_level, origin, size.width) {
+                let row_offset = offset + (z as u64 * rows_per_image + y as u64) * bytes_per_row;
+                f(ptr, row_offset);
+            }
+        }
+    }
+}
+
+impl Command {
+    /// # Safety
+    ///
+    /// Must be called with appropriate synchronization for the resources affected by the command,
+    /// such as ensuring that buffers are not accessed by a command while aliasing references exist.
+    unsafe fn execute(&self, state: &mut State) {
+        match *self {
+            Command::ClearBuffer {
+                ref buffer,
+                ref range,
+            } => {
+                // SAFETY:
+                // Caller is responsible for ensuring this does not alias.
+                let buffer_slice: &mut [u8] = unsafe { &mut *buffer.get_slice_ptr(range.clone()) };
+                buffer_slice.fill(0);
+            }
+
+            Command::CopyBufferToBuffer {
+                ref src,
+                ref dst,
+                ref regions,
+            } => {
+                for &crate::BufferCopy {
+                    src_offset,
+                    dst_offset,
+                    size,
+                } in regions
+                {
+                    // SAFETY:
+                    // Caller is responsible for ensuring this does not alias.
+                    let src_region: &[u8] =
+                        unsafe { &*src.get_slice_ptr(src_offset..src_offset + size.get()) };
+                    let dst_region: &mut [u8] =
+                        unsafe { &mut *dst.get_slice_ptr(dst_offset..dst_offset + size.get()) };
+                    dst_region.copy_from_slice(src_region);
+                }
+            }
+
+            Command::CopyBufferToTexture {
+                ref src,
+                ref dst,
+                ref regions,
+            } => {
+                for region in regions {
+                    let layout = Some(&region.buffer_layout);
+                    for_each_row(
+                        dst,
+                        &region.texture_base,
+                        &region.size,
+                        layout,
+                        |row, offset| {
+                            // SAFETY: Caller is responsible for ensuring this does not alias.
+                            let row = unsafe { &mut *row };
+                            let src =
+                                unsafe { &*src.get_slice_ptr(offset..offset + row.len() as u64) };
+                            row.copy_from_slice(src);
+                        },
+                    );
+                }
+            }
+
+            Command::CopyTextureToBuffer {
+                ref src,
+                ref dst,
+                ref regions,
+            } => {
+                for region in regions {
+                    let layout = Some(&region.buffer_layout);
+                    for_each_row(
+                        src,
+                        &region.texture_base,
+                        &region.size,
+                        layout,
+                        |row, offset| {
+                            // SAFETY: Caller is responsible for ensuring this does not alias.
+                            let row = unsafe { &*row };
+                            let dst = unsafe {
+                                &mut *dst.get_slice_ptr(offset..offset + row.len() as u64)
+                            };
+                            dst.copy_from_slice(row);
+                        },
+                    );
+                }
+            }
+
+            Command::CopyTextureToTexture {
+                ref src,
+                ref dst,
+                ref regions,
+            } => {
+                for region in regions {
+                    // The rows go through a staging copy, as the textures may be the same.
+                    let mut staging = Vec::new();
+                    for_each_row(src, &region.src_base, &region.size, None, |row, _| {
+                        // SAFETY: Caller is responsible for ensuring this does not alias.
+                        staging.extend_from_slice(unsafe { &*row });
+                    });
+                    let mut rows = staging.as_slice();
+                    for_each_row(dst, &region.dst_base, &region.size, None, |row, _| {
+                        // SAFETY: Caller is responsible for ensuring this does not alias.
+                        let row = unsafe { &mut *row };
+                        let (src, rest) = rows.split_at(row.len());
+                        row.copy_from_slice(src);
+                        rows = rest;
+                    });
+                }
+            }
+
+            Command::ResetQueries { ref set, ref range } => {
+                for index in range.clone() {
+                    unsafe { write_query(set, index, 0) };
+                }
+            }
+            Command::BeginOcclusionQuery { ref set, index } => {
+                state.occlusion_query = Some((set.clone(), index, 0));
+            }
+            Command::EndOcclusionQuery => {
+                if let Some((set, index, count)) = state.occlusion_query.take() {
+                    unsafe { write_query(&set, index, count) };
+                }
+            }
+            Command::WriteZero { ref set, index } => unsafe { write_query(set, index, 0) },
+            Command::CopyQueryResults {
+                ref set,
+                ref range,
+                ref buffer,
+                offset,
+                stride,
+            } => {
+                for (i, index) in range.clone().enumerate() {
+                    let src_offset = index as u64 * 8;
+                    let dst_offset = offset + i as u64 * stride;
+                    // SAFETY: Caller is responsible for ensuring this does not alias.
+                    let src = unsafe { &*set.results.get_slice_ptr(src_offset..src_offset + 8) };
+                    let dst = unsafe { &mut *buffer.get_slice_ptr(dst_offset..dst_offset + 8) };
+                    dst.copy_from_slice(src);
+                }
+            }
+
+            Command::BeginRenderPass {
+                extent,
+                ref color_attachments,
+                ref depth_stencil_attachment,
+            } => {
+                let texels = |view: &TextureView, depth_slice: u32| {
+                    let [width, height, layers] = view.mip_extent(0);
+                    let layers = match view.dimension {
+                        wgt::TextureViewDimension::D3 => depth_slice..depth_slice + 1,
+                        _ => 0..layers,
+                    };
+                    layers.flat_map(move |z| {
+                        (0..height).flat_map(move |y| (0..width).map(move |x| [x, y, z]))
+                    })
+                };
+                for attachment in color_attachments.iter().flatten() {
+                    if let Some(color) = attachment.clear_value {
+                        let texel = clear_texel(&attachment.view, color);
+                        for coordinates in texels(&attachment.view, attachment.depth_slice) {
+                            // SAFETY: Caller is responsible for ensuring this does not alias.
+                            unsafe { attachment.view.write(0, coordinates, texel) };
+                        }
+                    }
+                }
+                if let Some(ref attachment) = *depth_stencil_attachment {
+                    let view = &attachment.view;
+                    let clears = [
+                        (
+                            FormatAspects::DEPTH,
+                            attachment
+                                .depth_clear_value
+                                .map(|depth| super::format::Texel::Float([depth, 0.0, 0.0, 1.0])),
+                        ),
+                        (
+                            FormatAspects::STENCIL,
+                            attachment
+                                .stencil_clear_value
+                                .map(|stencil| super::format::Texel::Uint([stencil, 0, 0, 1])),
+                        ),
+                    ];
+                    for (aspect, texel) in clears {
+                        let Some(texel) = texel.filter(|_| view.aspects.contains(aspect)) else {
+                            continue;
+                        };
+                        for [x, y, z] in texels(view, 0) {
+                            let coordinates = view.texture_coordinates([x, y, z]);
+                            // SAFETY: Caller is responsible for ensuring this does not alias.
+                            unsafe {
+                                view.texture.write(
+                                    view.format,
+                                    aspect,
+                                    view.mip_levels.start,
+                                    coordinates,
+                                    texel,
+                                )
+                            };
+                        }
+                    }
+                }
+
+                state.render_pass = Some(RenderPass {
+                    extent,
+                    color_attachments: color_attachments
+                        .iter()
+                        .map(|attachment| {
+                            let attachment = attachment.as_ref()?;
+                            Some((attachment.view.clone(), attachment.depth_slice))
+                        })
+                        .collect(),
+                    depth_stencil_attachment: depth_stencil_attachment
+                        .as_ref()
+                        .map(|attachment| attachment.view.clone()),
+                });
+                state.viewport = None;
+                state.depth_range = 0.0..1.0;
+                state.scissor = None;
+                state.stencil_reference = 0;
+                state.blend_constant = [0.0; 4];
+            }
+            Command::EndRenderPass => {
+                state.render_pass = None;
+            }
+
+            Command::SetBindGroup { index, ref group } => {
+                let index = index as usize;
+                if state.bind_groups.len() <= index {
+                    state.bind_groups.resize(index + 1, None);
+                }
+                state.bind_groups[index] = Some(group.clone());
+            }
+            Command::SetPushConstants {
+                offset_bytes,
+                ref data,
+            } => {
+                let start = offset_bytes as usize;
+                let end = start + data.len() * 4;
+                if state.push_constants.len() < end {
+                    state.push_constants.resize(end, 0);
+                }
+                for (bytes, word) in state.push_constants[start..end]
+                    .chunks_exact_mut(4)
+                    .zip(data)
+                {
+                    bytes.copy_from_slice(&word.to_le_bytes());
+                }
+            }
+            Command::SetRenderPipeline(ref pipeline) => {
+                state.render_pipeline = Some(pipeline.clone());
+            }
+            Command::SetComputePipeline(ref pipeline) => {
+                state.compute_pipeline = Some(pipeline.clone());
+            }
+            Command::SetIndexBuffer {
+                ref buffer,
+                offset,
+                format,
+            } => {
+                state.index_buffer = Some((buffer.clone(), offset, format));
+            }
+            Command::SetVertexBuffer {
+                index,
+                ref buffer,
+                offset,
+            } => {
+                let index = index as usize;
+                if state.vertex_buffers.len() <= index {
+                    state.vertex_buffers.resize(index + 1, None);
+                }
+                state.vertex_buffers[index] = Some((buffer.clone(), offset));
+            }
+            Command::SetViewport {
+                ref rect,
+                ref depth_range,
+            } => {
+                state.viewport = Some(rect.clone());
+                state.depth_range = depth_range.clone();
+            }
+            Command::SetScissorRect(ref rect) => state.scissor = Some(rect.clone()),
+            Command::SetStencilReference(value) => state.stencil_reference = value,
+            Command::SetBlendConstants(color) => state.blend_constant = color,
+            Command::BeginConditionalRendering { ref buffer, offset } => {
+                let [condition] = unsafe { read_words::<1>(buffer, offset) };
+                state.skip_draws = condition == 0;
+            }
+            Command::EndConditionalRendering => state.skip_draws = false,
+
+            Command::Draw(ref draw) => unsafe { state.draw(draw) },
+            Command::DrawIndirect {
+                ref buffer,
+                offset,
+                draw_count,
+                indexed,
+            } => {
+                let stride = if indexed { 20 } else { 16 };
+                for i in 0..draw_count as u64 {
+                    let offset = offset + i * stride;
+                    let draw = if indexed {
+                        let [
+                            count,
+                            instance_count,
+                            first_index,
+                            base_vertex,
+                            first_instance,
+                        ] = unsafe { read_words::<5>(buffer, offset) };
+                        raster::Draw {
+                            vertices: first_index..first_index + count,
+                            instances: first_instance..first_instance + instance_count,
+                            base_vertex: base_vertex as i32,
+                            indexed,
+                        }
+                    } else {
+                        let [count, instance_count, first_vertex, first_instance] =
+                            unsafe { read_words::<4>(buffer, offset) };
+                        raster::Draw {
+                            vertices: first_vertex..first_vertex + count,
+                            instances: first_instance..first_instance + instance_count,
+                            base_vertex: 0,
+                            indexed,
+                        }
+                    };
+                    unsafe { state.draw(&draw) };
+                }
+            }
+
+            Command::Dispatch(count) => unsafe { state.dispatch(count) },
+            Command::DispatchIndirect { ref buffer, offset } => {
+                let count = unsafe { read_words::<3>(buffer, offset) };
+                unsafe { state.dispatch(count) };
+            }
+        }
+    }
+}
+
+impl State {
+    fn resources(&self) -> Resources<'_> {
+        Resources {
+            bind_groups: &self.bind_groups,
+            push_constants: &self.push_constants,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// See [`CommandBuffer::execute`].
+    unsafe fn draw(&mut self, draw: &raster::Draw) {
+        if self.skip_draws {
+            return;
+        }
+        let (Some(pipeline), Some(pass)) = (&self.render_pipeline, &self.render_pass) else {
+            return;
+        };
+        let extent = pass.extent;
+        let viewport = self.viewport.clone().unwrap_or(crate::Rect {
+            x: 0.0,
+            y: 0.0,
+            w: extent.width as f32,
+            h: extent.height as f32,
+        });
+        let scissor = self.scissor.clone().unwrap_or(crate::Rect {
+            x: 0,
+            y: 0,
+            w: extent.width,
+            h: extent.height,
+        });
+        let draw_state = raster::DrawState {
+            resources: self.resources(),
+            vertex_buffers: &self.vertex_buffers,
+            index_buffer: self.index_buffer.as_ref(),
+            viewport: &viewport,
+            depth_range: self.depth_range.clone(),
+            scissor: &scissor,
+            stencil_reference: self.stencil_reference,
+            blend_constant: self.blend_constant,
+            color_attachments: &pass.color_attachments,
+            depth_stencil_attachment: pass.depth_stencil_attachment.as_ref(),
+            extent,
+        };
+        let passed = unsafe { pipeline.inner.draw(&draw_state, draw) };
+        if let Some((_, _, ref mut count)) = self.occlusion_query {
+            *count += passed;
+        }
+    }
+
+    /// # Safety
+    ///
+    /// See [`CommandBuffer::execute`].
+    unsafe fn dispatch(&self, count: [u32; 3]) {
+        let Some(ref pipeline) = self.compute_pipeline else {
+            return;
+        };
+        unsafe { interpreter::dispatch(&pipeline.program, self.resources(), count) };
+    }
+}
@@ -0,0 +1,351 @@
+use alloc::{string::String, sync::Arc, vec, vec::Vec};
+use core::{ptr, sync::atomic::Ordering};
+
+use super::{
+    Api, BindGroup, BindGroupEntry, BindGroupLayout, BoundResource, Buffer, CommandEncoder,
+    ComputePipeline, Device, DeviceResult, Fence, QuerySet, RenderPipeline, Resource, Sampler,
+    ShaderModule, Texture, TextureView, interpreter::Program, raster,
+};
+#[cfg(supports_64bit_atomics)]
+use core::sync::atomic::AtomicU64;
+#[cfg(not(supports_64bit_atomics))]
+use portable_atomic::AtomicU64;
+
+impl crate::Device for Device {
+    type A = Api;
+
+    unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Buffer> {
+        Buffer::new(desc)
+    }
+    unsafe fn destroy_buffer(&self, _buffer: Buffer) {}
+    unsafe fn add_raw_buffer(&self, _buffer: &Buffer) {}
+
+    unsafe fn map_buffer(
+        &self,
+        buffer: &Buffer,
+        range: crate::MemoryRange,
+    ) -> DeviceResult<crate::BufferMapping> {
+        // Safety: as on the noop backend, `wgpu-core` prevents aliasing mappings.
+        Ok(crate::BufferMapping {
+            ptr: ptr::NonNull::new(buffer.get_slice_ptr(range).cast::<u8>()).unwrap(),
+            is_coherent: true,
+        })
+    }
+    unsafe fn unmap_buffer(&self, _buffer: &Buffer) {}
+    unsafe fn flush_mapped_ranges<I>(&self, _buffer: &Buffer, _ranges: I) {}
+    unsafe fn invalidate_mapped_ranges<I>(&self, _buffer: &Buffer, _ranges: I) {}
+
+    unsafe fn create_texture(&self, desc: &crate::TextureDescriptor) -> DeviceResult<Texture> {
+        if desc.sample_count > 1 {
+            // Multisampling is not exposed by the format capabilities.
+            return Err(crate::DeviceError::Unexpected);
+        }
+        Texture::new(desc)
+    }
+    unsafe fn destroy_texture(&self, _texture: Texture) {}
+    unsafe fn add_raw_texture(&self, _texture: &Texture) {}
+
+    // Memory heaps, placed and sparse resources are not supported.
+    unsafe fn create_memory_heap(
+        &self,
+        _desc: &crate::MemoryHeapDescriptor,
+    ) -> DeviceResult<Resource> {
+        Err(crate::DeviceError::Unexpected)
+    }
+    unsafe fn destroy_memory_heap(&self, _heap: Resource) {}
+    unsafe fn texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<wgt::TextureMemoryRequirements> {
+        Err(crate::DeviceError::Unexpected)
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        _heap: &Resource,
+        _offset: wgt::BufferAddress,
+        _desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<Texture> {
+        Err(crate::DeviceError::Unexpected)
+    }
+    unsafe fn create_sparse_buffer(&self, _desc: &crate::BufferDescriptor) -> DeviceResult<Buffer> {
+        Err(crate::DeviceError::Unexpected)
+    }
+    unsafe fn create_sparse_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<Texture> {
+        Err(crate::DeviceError::Unexpected)
+    }
+
+    unsafe fn create_texture_view(
+        &self,
+        texture: &Texture,
+        desc: &crate::TextureViewDescriptor,
+    ) -> DeviceResult<TextureView> {
+        let range = &desc.range;
+        let mip_level_count = range
+            .mip_level_count
+            .unwrap_or(texture.mip_level_count - range.base_mip_level);
+        let array_layer_count = match texture.dimension {
+            wgt::TextureDimension::D3 => 1,
+            _ => range
+                .array_layer_count
+                .unwrap_or(texture.size.depth_or_array_layers - range.base_array_layer),
+        };
+        Ok(TextureView {
+            texture: texture.clone(),
+            format: desc.format,
+            dimension: desc.dimension,
+            aspects: crate::FormatAspects::new(texture.format, range.aspect),
+            mip_levels: range.base_mip_level..range.base_mip_level + mip_level_count,
+            array_layers: range.base_array_layer..range.base_array_layer + array_layer_count,
+        })
+    }
+    unsafe fn destroy_texture_view(&self, _view: TextureView) {}
+
+    unsafe fn create_sampler(&self, desc: &crate::SamplerDescriptor) -> DeviceResult<Sampler> {
+        Ok(Sampler {
+            address_modes: desc.address_modes,
+            mag_filter: desc.mag_filter,
+            min_filter: desc.min_filter,
+            mipmap_filter: desc.mipmap_filter,
+            lod_clamp: desc.lod_clamp.clone(),
+            compare: desc.compare,
+            border_color: desc.border_color,
+        })
+    }
+    unsafe fn destroy_sampler(&self, _sampler: Sampler) {}
+
+    unsafe fn create_command_encoder(
+        &self,
+        _desc: &crate::CommandEncoderDescriptor<super::Queue>,
+    ) -> DeviceResult<CommandEncoder> {
+        Ok(CommandEncoder::new())
+    }
+
+    unsafe fn create_bind_group_layout(
+        &self,
+        desc: &crate::BindGroupLayoutDescriptor,
+    ) -> DeviceResult<BindGroupLayout> {
+        Ok(BindGroupLayout {
+            entries: desc.entries.into(),
+        })
+    }
+    unsafe fn destroy_bind_group_layout(&self, _bg_layout: BindGroupLayout) {}
+    unsafe fn create_pipeline_layout(
+        &self,
+        _desc: &crate::PipelineLayoutDescriptor<BindGroupLayout>,
+    ) -> DeviceResult<Resource> {
+        // Shaders access resources by their group and binding directly.
+        Ok(Resource)
+    }
+    unsafe fn destroy_pipeline_layout(&self, _pipeline_layout: Resource) {}
+    unsafe fn create_bind_group(
+        &self,
+        desc: &crate::BindGroupDescriptor<BindGroupLayout, Buffer, Sampler, TextureView, Resource>,
+    ) -> DeviceResult<BindGroup> {
+        let mut entries = desc
+            .entries
+            .iter()
+            .map(|entry| {
+                let layout = desc
+                    .layout
+                    .entries
+                    .iter()
+                    .find(|layout_entry| layout_entry.binding == entry.binding)
+                    .expect("internal error: no layout entry found with binding slot");
+                let index = entry.resource_index as usize;
+                let (resource, has_dynamic_offset) = match layout.ty {
+                    wgt::BindingType::Buffer {
+                        has_dynamic_offset, ..
+                    } => {
+                        let binding = &desc.buffers[index];
+                        let size = match binding.size {
+                            Some(size) => size.get(),
+                            None => binding.buffer.size() - binding.offset,
+                        };
+                        let resource = BoundResource::Buffer {
+                            buffer: binding.buffer.clone(),
+                            offset: binding.offset,
+                            size,
+                        };
+                        (resource, has_dynamic_offset)
+                    }
+                    wgt::BindingType::Sampler(_) => {
+                        (BoundResource::Sampler(desc.samplers[index].clone()), false)
+                    }
+                    wgt::BindingType::Texture { .. } | wgt::BindingType::StorageTexture { .. } => {
+                        let view = desc.textures[index].view.clone();
+                        (BoundResource::TextureView(view), false)
+                    }
+                    wgt::BindingType::AccelerationStructure { .. } => {
+                        super::unsupported("acceleration structures")
+                    }
+                    wgt::BindingType::ExternalTexture => super::unsupported("external textures"),
+                    wgt::BindingType::YcbcrTexture { .. } => super::unsupported("Y'CbCr textures"),
+                };
+                BindGroupEntry {
+                    binding: entry.binding,
+                    has_dynamic_offset,
+                    resource,
+                }
+            })
+            .collect::<Vec<_>>();
+        // Dynamic offsets are given in the order of the bindings.
+        entries.sort_by_key(|entry| entry.binding);
+        Ok(BindGroup {
+            entries: entries.into(),
+        })
+    }
+    unsafe fn destroy_bind_group(&self, _group: BindGroup) {}
+
+    unsafe fn create_shader_module(
+        &self,
+        _desc: &crate::ShaderModuleDescriptor,
+        shader: crate::ShaderInput,
+    ) -> Result<ShaderModule, crate::ShaderError> {
+        match shader {
+            crate::ShaderInput::Naga(naga) => Ok(ShaderModule {
+                naga: Arc::new(naga),
+            }),
+            _ => Err(crate::ShaderError::Compilation(String::from(
+                "the CPU backend only supports naga shaders",
+            ))),
+        }
+    }
+    unsafe fn destroy_shader_module(&self, _module: ShaderModule) {}
+    unsafe fn create_render_pipeline(
+        &self,
+        desc: &crate::RenderPipelineDescriptor<Resource, ShaderModule, Resource>,
+    ) -> Result<RenderPipeline, crate::PipelineError> {
+        Ok(RenderPipeline {
+            inner: Arc::new(raster::Pipeline::new(desc)?),
+        })
+    }
+    unsafe fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<Resource, ShaderModule, Resource>,
+    ) -> Result<RenderPipeline, crate::PipelineError> {
+        super::unsupported("mesh shaders")
+    }
+    unsafe fn destroy_render_pipeline(&self, _pipeline: RenderPipeline) {}
+    unsafe fn create_compute_pipeline(
+        &self,
+        desc: &crate::ComputePipelineDescriptor<Resource, ShaderModule, Resource>,
+    ) -> Result<ComputePipeline, crate::PipelineError> {
+        Ok(ComputePipeline {
+            program: Arc::new(Program::new(&desc.stage, naga::ShaderStage::Compute)?),
+        })
+    }
+    unsafe fn destroy_compute_pipeline(&self, _pipeline: ComputePipeline) {}
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        _desc: &crate::RayTracingPipelineDescriptor<Resource, ShaderModule, Resource>,
+    ) -> Result<Resource, crate::PipelineError> {
+        super::unsupported("ray tracing pipelines")
+    }
+    unsafe fn destroy_ray_tracing_pipeline(&self, _pipeline: Resource) {}
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        _pipeline: &Resource,
+        _first_group: u32,
+        _group_count: u32,
+    ) -> DeviceResult<Vec<u8>> {
+        super::unsupported("ray tracing pipelines")
+    }
+    unsafe fn create_pipeline_cache(
+        &self,
+        _desc: &crate::PipelineCacheDescriptor<'_>,
+    ) -> Result<Resource, crate::PipelineCacheError> {
+        Ok(Resource)
+    }
+    unsafe fn destroy_pipeline_cache(&self, _cache: Resource) {}
+
+    unsafe fn create_query_set(
+        &self,
+        desc: &wgt::QuerySetDescriptor<crate::Label>,
+    ) -> DeviceResult<QuerySet> {
+        let results = Buffer::new(&crate::BufferDescriptor {
+            label: desc.label,
+            size: desc.count as wgt::BufferAddress * 8,
+            usage: wgt::BufferUses::empty(),
+            memory_flags: crate::MemoryFlags::empty(),
+        })?;
+        Ok(QuerySet { results })
+    }
+    unsafe fn destroy_query_set(&self, _set: QuerySet) {}
+    unsafe fn create_fence(&self) -> DeviceResult<Fence> {
+        Ok(Fence {
+            value: AtomicU64::new(0),
+        })
+    }
+    unsafe fn destroy_fence(&self, _fence: Fence) {}
+    unsafe fn get_fence_value(&self, fence: &Fence) -> DeviceResult<crate::FenceValue> {
+        Ok(fence.value.load(Ordering::Acquire))
+    }
+    unsafe fn wait(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+        _timeout_ms: u32,
+    ) -> DeviceResult<bool> {
+        // Submissions are executed synchronously, so there is nothing to wait for.
+        assert!(
+            fence.value.load(Ordering::Acquire) >= value,
+            "submission must have already been done"
+        );
+        Ok(true)
+    }
+    unsafe fn create_shared_fence(&self) -> DeviceResult<Fence> {
+        unsafe { self.create_fence() }
+    }
+    unsafe fn export_fence(&self, _fence: &Fence) -> DeviceResult<wgt::SharedFenceHandle> {
+        Err(crate::DeviceError::Unexpected)
+    }
+    unsafe fn import_fence(&self, _handle: wgt::SharedFenceHandle) -> DeviceResult<Fence> {
+        Err(crate::DeviceError::Unexpected)
+    }
+
+    unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        false
+    }
+    unsafe fn stop_graphics_debugger_capture(&self) {}
+
+    unsafe fn create_acceleration_structure(
+        &self,
+        _desc: &crate::AccelerationStructureDescriptor,
+    ) -> DeviceResult<Resource> {
+        super::unsupported("acceleration structures")
+    }
+    unsafe fn get_acceleration_structure_build_sizes<'a>(
+        &self,
+        _desc: &crate::GetAccelerationStructureBuildSizesDescriptor<'a, Buffer>,
+    ) -> crate::AccelerationStructureBuildSizes {
+        Default::default()
+    }
+    unsafe fn get_acceleration_structure_device_address(
+        &self,
+        _acceleration_structure: &Resource,
+    ) -> wgt::BufferAddress {
+        Default::default()
+    }
+    unsafe fn destroy_acceleration_structure(&self, _acceleration_structure: Resource) {}
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        _data: &[u8],
+    ) -> Option<wgt::BufferAddress> {
+        None
+    }
+
+    fn tlas_instance_to_bytes(&self, _instance: crate::TlasInstance) -> Vec<u8> {
+        vec![]
+    }
+
+    fn get_internal_counters(&self) -> wgt::HalCounters {
+        Default::default()
+    }
+
+    fn check_if_oom(&self) -> DeviceResult<()> {
+        Ok(())
+    }
+}
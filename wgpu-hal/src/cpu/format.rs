@@ -0,0 +1,561 @@
+//! Encoding and decoding of texels and vertex attributes.
+
+use wgt::{TextureFormat as Tf, VertexFormat as Vf};
+
+use crate::{FormatAspects, TextureFormatCapabilities as Tfc};
+
+/// A texel, or a vertex attribute, with missing channels set to `(0, 0, 0, 1)`.
+///
+/// Depth is stored in the first channel of a `Float` texel, and stencil in the first channel
+/// of a `Uint` texel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Texel {
+    Float([f32; 4]),
+    Sint([i32; 4]),
+    Uint([u32; 4]),
+}
+
+impl Texel {
+    pub fn to_f32s(self) -> [f32; 4] {
+        match self {
+            Self::Float(v) => v,
+            Self::Sint(v) => v.map(|x| x as f32),
+            Self::Uint(v) => v.map(|x| x as f32),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Unorm,
+    Snorm,
+    Uint,
+    Sint,
+    Float,
+}
+
+/// How the channels of a texel or attribute are stored.
+#[derive(Clone, Copy, Debug)]
+enum Layout {
+    /// `count` channels of `bytes` bytes each.
+    Plain {
+        kind: Kind,
+        bytes: u32,
+        count: usize,
+        /// Whether the first and third channels are swapped.
+        bgra: bool,
+        srgb: bool,
+    },
+    Rgb10a2(Kind),
+    Rg11b10Ufloat,
+    Depth16Unorm,
+    Depth32Float,
+    Stencil8,
+}
+
+const fn plain(kind: Kind, bytes: u32, count: usize) -> Layout {
+    Layout::Plain {
+        kind,
+        bytes,
+        count,
+        bgra: false,
+        srgb: false,
+    }
+}
+
+/// Returns the layout of the color aspect of `format`, or `None` if it is not supported.
+fn color_layout(format: Tf) -> Option<Layout> {
+    use Kind::*;
+    Some(match format {
+        Tf::R8Unorm => plain(Unorm, 1, 1),
+        Tf::R8Snorm => plain(Snorm, 1, 1),
+        Tf::R8Uint => plain(Uint, 1, 1),
+        Tf::R8Sint => plain(Sint, 1, 1),
+        Tf::R16Uint => plain(Uint, 2, 1),
+        Tf::R16Sint => plain(Sint, 2, 1),
+        Tf::R16Float => plain(Float, 2, 1),
+        Tf::Rg8Unorm => plain(Unorm, 1, 2),
+        Tf::Rg8Snorm => plain(Snorm, 1, 2),
+        Tf::Rg8Uint => plain(Uint, 1, 2),
+        Tf::Rg8Sint => plain(Sint, 1, 2),
+        Tf::R32Uint => plain(Uint, 4, 1),
+        Tf::R32Sint => plain(Sint, 4, 1),
+        Tf::R32Float => plain(Float, 4, 1),
+        Tf::Rg16Uint => plain(Uint, 2, 2),
+        Tf::Rg16Sint => plain(Sint, 2, 2),
+        Tf::Rg16Float => plain(Float, 2, 2),
+        Tf::Rgba8Unorm => plain(Unorm, 1, 4),
+        Tf::Rgba8UnormSrgb => Layout::Plain {
+            kind: Unorm,
+            bytes: 1,
+            count: 4,
+            bgra: false,
+            srgb: true,
+        },
+        Tf::Rgba8Snorm => plain(Snorm, 1, 4),
+        Tf::Rgba8Uint => plain(Uint, 1, 4),
+        Tf::Rgba8Sint => plain(Sint, 1, 4),
+        Tf::Bgra8Unorm | Tf::Bgra8UnormSrgb => Layout::Plain {
+            kind: Unorm,
+            bytes: 1,
+            count: 4,
+            bgra: true,
+            srgb: format == Tf::Bgra8UnormSrgb,
+        },
+        Tf::Rgb10a2Uint => Layout::Rgb10a2(Uint),
+        Tf::Rgb10a2Unorm => Layout::Rgb10a2(Unorm),
+        Tf::Rg11b10Ufloat => Layout::Rg11b10Ufloat,
+        Tf::Rg32Uint => plain(Uint, 4, 2),
+        Tf::Rg32Sint => plain(Sint, 4, 2),
+        Tf::Rg32Float => plain(Float, 4, 2),
+        Tf::Rgba16Uint => plain(Uint, 2, 4),
+        Tf::Rgba16Sint => plain(Sint, 2, 4),
+        Tf::Rgba16Float => plain(Float, 2, 4),
+        Tf::Rgba32Uint => plain(Uint, 4, 4),
+        Tf::Rgba32Sint => plain(Sint, 4, 4),
+        Tf::Rgba32Float => plain(Float, 4, 4),
+        _ => return None,
+    })
+}
+
+/// Returns the aspects of `format` which are stored in separate planes, with the size of
+/// their texels.
+///
+/// Returns an empty slice if `format` is not supported.
+pub(super) fn planes(format: Tf) -> &'static [(FormatAspects, u32)] {
+    match format {
+        Tf::Depth16Unorm => &[(FormatAspects::DEPTH, 2)],
+        Tf::Depth24Plus | Tf::Depth32Float => &[(FormatAspects::DEPTH, 4)],
+        Tf::Depth24PlusStencil8 | Tf::Depth32FloatStencil8 => {
+            &[(FormatAspects::DEPTH, 4), (FormatAspects::STENCIL, 1)]
+        }
+        Tf::Stencil8 => &[(FormatAspects::STENCIL, 1)],
+        _ => match color_layout(format).map(|_| format.block_copy_size(None)) {
+            Some(Some(1)) => &[(FormatAspects::COLOR, 1)],
+            Some(Some(2)) => &[(FormatAspects::COLOR, 2)],
+            Some(Some(4)) => &[(FormatAspects::COLOR, 4)],
+            Some(Some(8)) => &[(FormatAspects::COLOR, 8)],
+            Some(Some(16)) => &[(FormatAspects::COLOR, 16)],
+            _ => &[],
+        },
+    }
+}
+
+pub(super) fn capabilities(format: Tf) -> Tfc {
+    let copy = Tfc::COPY_SRC | Tfc::COPY_DST;
+    let storage = Tfc::STORAGE_READ_ONLY | Tfc::STORAGE_WRITE_ONLY | Tfc::STORAGE_READ_WRITE;
+    let Some(layout) = color_layout(format) else {
+        return if planes(format).is_empty() {
+            Tfc::empty()
+        } else {
+            Tfc::SAMPLED | Tfc::DEPTH_STENCIL_ATTACHMENT | copy
+        };
+    };
+    let kind = match layout {
+        Layout::Plain { kind, .. } | Layout::Rgb10a2(kind) => kind,
+        _ => Kind::Float,
+    };
+    let mut caps = copy | Tfc::SAMPLED;
+    caps |= match kind {
+        Kind::Unorm | Kind::Float => {
+            Tfc::SAMPLED_LINEAR | Tfc::COLOR_ATTACHMENT | Tfc::COLOR_ATTACHMENT_BLEND
+        }
+        Kind::Snorm => Tfc::SAMPLED_LINEAR,
+        Kind::Uint | Kind::Sint => Tfc::COLOR_ATTACHMENT,
+    };
+    if format == Tf::Rg11b10Ufloat {
+        caps -= Tfc::COLOR_ATTACHMENT | Tfc::COLOR_ATTACHMENT_BLEND;
+    }
+    if matches!(
+        format,
+        Tf::R32Uint
+            | Tf::R32Sint
+            | Tf::R32Float
+            | Tf::Rg32Uint
+            | Tf::Rg32Sint
+            | Tf::Rg32Float
+            | Tf::Rgba8Unorm
+            | Tf::Rgba8Snorm
+            | Tf::Rgba8Uint
+            | Tf::Rgba8Sint
+            | Tf::Rgba16Uint
+            | Tf::Rgba16Sint
+            | Tf::Rgba16Float
+            | Tf::Rgba32Uint
+            | Tf::Rgba32Sint
+            | Tf::Rgba32Float
+    ) {
+        caps |= storage;
+    }
+    caps
+}
+
+/// Decodes the texel of `aspect` of `format` stored in `bytes`.
+pub(super) fn decode(format: Tf, aspect: FormatAspects, bytes: &[u8]) -> Texel {
+    match aspect_layout(format, aspect) {
+        Some(layout) => decode_layout(layout, bytes),
+        None => Texel::Float([0.0, 0.0, 0.0, 1.0]),
+    }
+}
+
+/// Encodes `texel` into the texel of `aspect` of `format` stored in `bytes`.
+pub(super) fn encode(format: Tf, aspect: FormatAspects, texel: Texel, bytes: &mut [u8]) {
+    if let Some(layout) = aspect_layout(format, aspect) {
+        encode_layout(layout, texel, bytes);
+    }
+}
+
+fn aspect_layout(format: Tf, aspect: FormatAspects) -> Option<Layout> {
+    if aspect.contains(FormatAspects::DEPTH) {
+        Some(match format {
+            Tf::Depth16Unorm => Layout::Depth16Unorm,
+            _ => Layout::Depth32Float,
+        })
+    } else if aspect.contains(FormatAspects::STENCIL) {
+        Some(Layout::Stencil8)
+    } else {
+        color_layout(format)
+    }
+}
+
+/// Decodes a vertex attribute of `format` stored in `bytes`.
+pub(super) fn decode_vertex(format: Vf, bytes: &[u8]) -> Texel {
+    use Kind::*;
+    let layout = match format {
+        Vf::Uint8 => plain(Uint, 1, 1),
+        Vf::Uint8x2 => plain(Uint, 1, 2),
+        Vf::Uint8x4 => plain(Uint, 1, 4),
+        Vf::Sint8 => plain(Sint, 1, 1),
+        Vf::Sint8x2 => plain(Sint, 1, 2),
+        Vf::Sint8x4 => plain(Sint, 1, 4),
+        Vf::Unorm8 => plain(Unorm, 1, 1),
+        Vf::Unorm8x2 => plain(Unorm, 1, 2),
+        Vf::Unorm8x4 => plain(Unorm, 1, 4),
+        Vf::Snorm8 => plain(Snorm, 1, 1),
+        Vf::Snorm8x2 => plain(Snorm, 1, 2),
+        Vf::Snorm8x4 => plain(Snorm, 1, 4),
+        Vf::Uint16 => plain(Uint, 2, 1),
+        Vf::Uint16x2 => plain(Uint, 2, 2),
+        Vf::Uint16x4 => plain(Uint, 2, 4),
+        Vf::Sint16 => plain(Sint, 2, 1),
+        Vf::Sint16x2 => plain(Sint, 2, 2),
+        Vf::Sint16x4 => plain(Sint, 2, 4),
+        Vf::Unorm16 => plain(Unorm, 2, 1),
+        Vf::Unorm16x2 => plain(Unorm, 2, 2),
+        Vf::Unorm16x4 => plain(Unorm, 2, 4),
+        Vf::Snorm16 => plain(Snorm, 2, 1),
+        Vf::Snorm16x2 => plain(Snorm, 2, 2),
+        Vf::Snorm16x4 => plain(Snorm, 2, 4),
+        Vf::Float16 => plain(Float, 2, 1),
+        Vf::Float16x2 => plain(Float, 2, 2),
+        Vf::Float16x4 => plain(Float, 2, 4),
+        Vf::Float32 => plain(Float, 4, 1),
+        Vf::Float32x2 => plain(Float, 4, 2),
+        Vf::Float32x3 => plain(Float, 4, 3),
+        Vf::Float32x4 => plain(Float, 4, 4),
+        Vf::Uint32 => plain(Uint, 4, 1),
+        Vf::Uint32x2 => plain(Uint, 4, 2),
+        Vf::Uint32x3 => plain(Uint, 4, 3),
+        Vf::Uint32x4 => plain(Uint, 4, 4),
+        Vf::Sint32 => plain(Sint, 4, 1),
+        Vf::Sint32x2 => plain(Sint, 4, 2),
+        Vf::Sint32x3 => plain(Sint, 4, 3),
+        Vf::Sint32x4 => plain(Sint, 4, 4),
+        Vf::Unorm10_10_10_2 => Layout::Rgb10a2(Unorm),
+        Vf::Unorm8x4Bgra => Layout::Plain {
+            kind: Unorm,
+            bytes: 1,
+            count: 4,
+            bgra: true,
+            srgb: false,
+        },
+        Vf::Float64 | Vf::Float64x2 | Vf::Float64x3 | Vf::Float64x4 => {
+            super::unsupported("64-bit vertex attributes")
+        }
+    };
+    decode_layout(layout, bytes)
+}
+
+fn read_bits(bytes: &[u8], size: u32) -> u32 {
+    let mut word = [0; 4];
+    let size = (size as usize).min(bytes.len());
+    word[..size].copy_from_slice(&bytes[..size]);
+    u32::from_le_bytes(word)
+}
+
+fn write_bits(bytes: &mut [u8], size: u32, bits: u32) {
+    let size = (size as usize).min(bytes.len());
+    bytes[..size].copy_from_slice(&bits.to_le_bytes()[..size]);
+}
+
+fn decode_layout(layout: Layout, bytes: &[u8]) -> Texel {
+    match layout {
+        Layout::Plain {
+            kind,
+            bytes: size,
+            count,
+            bgra,
+            srgb,
+        } => {
+            let bits = |i: usize| read_bits(&bytes[i * size as usize..], size);
+            let mut texel = match kind {
+                Kind::Uint => {
+                    let mut v = [0, 0, 0, 1];
+                    for (i, v) in v.iter_mut().enumerate().take(count) {
+                        *v = bits(i);
+                    }
+                    Texel::Uint(v)
+                }
+                Kind::Sint => {
+                    let mut v = [0, 0, 0, 1];
+                    let shift = 32 - 8 * size;
+                    for (i, v) in v.iter_mut().enumerate().take(count) {
+                        *v = ((bits(i) << shift) as i32) >> shift;
+                    }
+                    Texel::Sint(v)
+                }
+                _ => {
+                    let mut v = [0.0, 0.0, 0.0, 1.0];
+                    for (i, v) in v.iter_mut().enumerate().take(count) {
+                        *v = decode_float(kind, size, bits(i));
+                    }
+                    if srgb {
+                        for v in &mut v[..3] {
+                            *v = srgb_to_linear(*v);
+                        }
+                    }
+                    Texel::Float(v)
+                }
+            };
+            if bgra {
+                if let Texel::Float(ref mut v) = texel {
+                    v.swap(0, 2);
+                }
+            }
+            texel
+        }
+        Layout::Rgb10a2(kind) => {
+            let bits = read_bits(bytes, 4);
+            let v = [
+                bits & 0x3ff,
+                (bits >> 10) & 0x3ff,
+                (bits >> 20) & 0x3ff,
+                bits >> 30,
+            ];
+            match kind {
+                Kind::Uint => Texel::Uint(v),
+                _ => Texel::Float([
+                    v[0] as f32 / 1023.0,
+                    v[1] as f32 / 1023.0,
+                    v[2] as f32 / 1023.0,
+                    v[3] as f32 / 3.0,
+                ]),
+            }
+        }
+        Layout::Rg11b10Ufloat => {
+            let bits = read_bits(bytes, 4);
+            Texel::Float([
+                small_float_to_f32(bits & 0x7ff, 6),
+                small_float_to_f32((bits >> 11) & 0x7ff, 6),
+                small_float_to_f32(bits >> 22, 5),
+                1.0,
+            ])
+        }
+        Layout::Depth16Unorm => Texel::Float([read_bits(bytes, 2) as f32 / 65535.0, 0.0, 0.0, 1.0]),
+        Layout::Depth32Float => Texel::Float([f32::from_bits(read_bits(bytes, 4)), 0.0, 0.0, 1.0]),
+        Layout::Stencil8 => Texel::Uint([read_bits(bytes, 1), 0, 0, 1]),
+    }
+}
+
+fn encode_layout(layout: Layout, texel: Texel, bytes: &mut [u8]) {
+    match layout {
+        Layout::Plain {
+            kind,
+            bytes: size,
+            count,
+            bgra,
+            srgb,
+        } => {
+            let mut bits = match (kind, texel) {
+                (Kind::Uint, Texel::Uint(v)) => v,
+                (Kind::Sint, Texel::Sint(v)) => v.map(|x| x as u32),
+                (_, texel) => {
+                    let mut v = texel.to_f32s();
+                    if srgb {
+                        for v in &mut v[..3] {
+                            *v = linear_to_srgb(*v);
+                        }
+                    }
+                    v.map(|x| encode_float(kind, size, x))
+                }
+            };
+            if bgra {
+                bits.swap(0, 2);
+            }
+            for (i, &bits) in bits.iter().enumerate().take(count) {
+                let offset = i * size as usize;
+                if let Some(bytes) = bytes.get_mut(offset..) {
+                    write_bits(bytes, size, bits);
+                }
+            }
+        }
+        Layout::Rgb10a2(kind) => {
+            let v = match (kind, texel) {
+                (Kind::Uint, Texel::Uint(v)) => v.map(|x| x.min(0x3ff)),
+                (_, texel) => {
+                    let v = texel.to_f32s();
+                    let unorm = |x: f32, max: f32| (x.clamp(0.0, 1.0) * max).round() as u32;
+                    [
+                        unorm(v[0], 1023.0),
+                        unorm(v[1], 1023.0),
+                        unorm(v[2], 1023.0),
+                        unorm(v[3], 3.0),
+                    ]
+                }
+            };
+            write_bits(
+                bytes,
+                4,
+                v[0] | (v[1] << 10) | (v[2] << 20) | (v[3].min(3) << 30),
+            );
+        }
+        Layout::Rg11b10Ufloat => {
+            let v = texel.to_f32s();
+            let bits = f32_to_small_float(v[0], 6)
+                | (f32_to_small_float(v[1], 6) << 11)
+                | (f32_to_small_float(v[2], 5) << 22);
+            write_bits(bytes, 4, bits);
+        }
+        Layout::Depth16Unorm => {
+            let depth = texel.to_f32s()[0];
+            write_bits(bytes, 2, encode_float(Kind::Unorm, 2, depth));
+        }
+        Layout::Depth32Float => write_bits(bytes, 4, texel.to_f32s()[0].to_bits()),
+        Layout::Stencil8 => {
+            let stencil = match texel {
+                Texel::Uint(v) => v[0],
+                texel => texel.to_f32s()[0] as u32,
+            };
+            write_bits(bytes, 1, stencil);
+        }
+    }
+}
+
+fn decode_float(kind: Kind, size: u32, bits: u32) -> f32 {
+    match (kind, size) {
+        (Kind::Unorm, _) => bits as f32 / unorm_max(size),
+        (Kind::Snorm, _) => {
+            let shift = 32 - 8 * size;
+            let value = ((bits << shift) as i32) >> shift;
+            (value as f32 / (unorm_max(size) / 2.0).floor()).max(-1.0)
+        }
+        (_, 2) => f16_to_f32(bits as u16),
+        _ => f32::from_bits(bits),
+    }
+}
+
+fn encode_float(kind: Kind, size: u32, value: f32) -> u32 {
+    match (kind, size) {
+        (Kind::Unorm, _) => (value.clamp(0.0, 1.0) * unorm_max(size)).round() as u32,
+        (Kind::Snorm, _) => {
+            let max = (unorm_max(size) / 2.0).floor();
+            (value.clamp(-1.0, 1.0) * max).round() as i32 as u32
+        }
+        (_, 2) => f32_to_f16(value) as u32,
+        _ => value.to_bits(),
+    }
+}
+
+fn unorm_max(size: u32) -> f32 {
+    ((1u64 << (8 * size)) - 1) as f32
+}
+
+fn srgb_to_linear(x: f32) -> f32 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(x: f32) -> f32 {
+    let x = x.clamp(0.0, 1.0);
+    if x <= 0.0031308 {
+        x * 12.92
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Converts a float to the bits of an IEEE half-precision float, rounding to nearest even.
+pub(super) fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent == 0xff {
+        // Infinity or NaN, keeping NaNs quiet.
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+        // Subnormal half.
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - exponent) as u32;
+        let half = mantissa >> shift;
+        let rest = mantissa & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let round = rest > halfway || (rest == halfway && half & 1 != 0);
+        return sign | (half + round as u32) as u16;
+    }
+    let half = ((exponent as u32) << 10) | (mantissa >> 13);
+    let rest = mantissa & 0x1fff;
+    let round = rest > 0x1000 || (rest == 0x1000 && half & 1 != 0);
+    // Rounding may carry into the exponent, up to infinity, which is correct.
+    sign | (half + round as u32) as u16
+}
+
+pub(super) fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+/// Decodes an unsigned float with a 5-bit exponent and `mantissa_bits` bits of mantissa.
+fn small_float_to_f32(bits: u32, mantissa_bits: u32) -> f32 {
+    let exponent = (bits >> mantissa_bits) as i32;
+    let mantissa = (bits & ((1 << mantissa_bits) - 1)) as f32 / (1 << mantissa_bits) as f32;
+    match exponent {
+        0 => mantissa * 2f32.powi(-14),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa) * 2f32.powi(exponent - 15),
+    }
+}
+
+/// Encodes a non-negative float with a 5-bit exponent and `mantissa_bits` bits of mantissa.
+fn f32_to_small_float(value: f32, mantissa_bits: u32) -> u32 {
+    if value.is_nan() {
+        return 0x1f << mantissa_bits | 1;
+    }
+    // Shares the exponent encoding of half-precision floats, with less mantissa.
+    let half = f32_to_f16(value.max(0.0)) as u32;
+    let shift = 10 - mantissa_bits;
+    if half >= 0x7c00 {
+        return 0x1f << mantissa_bits;
+    }
+    (half >> shift).min((0x1f << mantissa_bits) - 1)
+}
@@ -0,0 +1,258 @@
+//! Texture sampling, loads and stores of shaders.
+
+use wgt::{AddressMode, CompareFunction, FilterMode, TextureViewDimension as Tvd};
+
+use super::{Sampler, TextureView, format::Texel};
+
+/// Returns whether `value` passes the comparison `function` against `reference`, which is
+/// the value in the texture or attachment.
+pub(super) fn compare(function: CompareFunction, value: f32, reference: f32) -> bool {
+    match function {
+        CompareFunction::Never => false,
+        CompareFunction::Less => value < reference,
+        CompareFunction::Equal => value == reference,
+        CompareFunction::LessEqual => value <= reference,
+        CompareFunction::Greater => value > reference,
+        CompareFunction::NotEqual => value != reference,
+        CompareFunction::GreaterEqual => value >= reference,
+        CompareFunction::Always => true,
+    }
+}
+
+/// The parameters of a texture sample, other than its coordinates.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct SampleOptions {
+    pub array_layer: u32,
+    pub offset: [i32; 3],
+    /// The level of detail, before clamping by the sampler.
+    pub lod: f32,
+    pub depth_ref: Option<f32>,
+    /// The component to gather, if this is a gather.
+    pub gather: Option<usize>,
+    /// Whether the coordinates are clamped to the centers of the edge texels.
+    pub clamp_to_edge: bool,
+}
+
+impl TextureView {
+    /// Returns the level of detail of a sample, given the derivatives of its coordinates.
+    pub(super) fn lod(&self, dx: &[f32], dy: &[f32]) -> f32 {
+        let [width, height, depth] = self.mip_extent(0);
+        let size = match self.dimension {
+            // Normalized cube directions span about two face widths.
+            Tvd::Cube | Tvd::CubeArray => [width as f32 / 2.0; 3],
+            _ => [width as f32, height as f32, depth as f32],
+        };
+        let length = |d: &[f32]| {
+            d.iter()
+                .zip(size)
+                .map(|(d, size)| (d * size) * (d * size))
+                .sum::<f32>()
+                .sqrt()
+        };
+        length(dx).max(length(dy)).log2()
+    }
+
+    /// Samples the view at normalized `coordinate`.
+    ///
+    /// # Safety
+    ///
+    /// The texture must not be written to concurrently.
+    pub(super) unsafe fn sample(
+        &self,
+        sampler: &Sampler,
+        coordinate: &[f32],
+        options: SampleOptions,
+    ) -> [f32; 4] {
+        let (coordinate, array_layer) = match self.dimension {
+            Tvd::Cube | Tvd::CubeArray => {
+                let (face, u, v) = cube_face(coordinate);
+                ([u, v, 0.0], options.array_layer * 6 + face)
+            }
+            _ => {
+                let mut c = [0.0; 3];
+                c[..coordinate.len()].copy_from_slice(coordinate);
+                (c, options.array_layer)
+            }
+        };
+        let options = SampleOptions {
+            array_layer,
+            ..options
+        };
+
+        if options.gather.is_some() {
+            return unsafe {
+                self.sample_level(sampler, FilterMode::Linear, 0, coordinate, options)
+            };
+        }
+
+        let lod = options
+            .lod
+            .clamp(sampler.lod_clamp.start, sampler.lod_clamp.end);
+        let filter = if lod <= 0.0 {
+            sampler.mag_filter
+        } else {
+            sampler.min_filter
+        };
+        let max_level = self.mip_levels.len() as u32 - 1;
+        let lod = lod.max(0.0);
+        match sampler.mipmap_filter {
+            FilterMode::Nearest => {
+                let level = ((lod + 0.5).ceil() as u32).saturating_sub(1).min(max_level);
+                unsafe { self.sample_level(sampler, filter, level, coordinate, options) }
+            }
+            FilterMode::Linear => {
+                let level = (lod.floor() as u32).min(max_level);
+                let next_level = (level + 1).min(max_level);
+                let weight = lod - lod.floor();
+                let a = unsafe { self.sample_level(sampler, filter, level, coordinate, options) };
+                if weight == 0.0 || next_level == level {
+                    return a;
+                }
+                let b =
+                    unsafe { self.sample_level(sampler, filter, next_level, coordinate, options) };
+                core::array::from_fn(|i| a[i] * (1.0 - weight) + b[i] * weight)
+            }
+        }
+    }
+
+    unsafe fn sample_level(
+        &self,
+        sampler: &Sampler,
+        filter: FilterMode,
+        level: u32,
+        coordinate: [f32; 3],
+        options: SampleOptions,
+    ) -> [f32; 4] {
+        let extent = self.mip_extent(level);
+        let dimensions = match self.dimension {
+            Tvd::D1 => 1,
+            Tvd::D3 => 3,
+            _ => 2,
+        };
+
+        // The texel coordinates of the sample, and the texels to filter with their weights.
+        let mut texels = [([0i32; 3], 1.0f32); 8];
+        let mut count = 1;
+        for d in 0..dimensions {
+            let size = extent[d] as f32;
+            let mut u = coordinate[d] * size;
+            if options.clamp_to_edge {
+                u = u.clamp(0.5, size - 0.5);
+            }
+            match filter {
+                FilterMode::Nearest => {
+                    for (texel, _) in &mut texels[..count] {
+                        texel[d] = u.floor() as i32 + options.offset[d];
+                    }
+                }
+                FilterMode::Linear => {
+                    let u = u - 0.5;
+                    let i = u.floor() as i32 + options.offset[d];
+                    let weight = u - u.floor();
+                    for j in 0..count {
+                        let (mut texel, w) = texels[j];
+                        texel[d] = i;
+                        texels[j] = (texel, w * (1.0 - weight));
+                        texel[d] = i + 1;
+                        texels[j + count] = (texel, w * weight);
+                    }
+                    count *= 2;
+                }
+            }
+        }
+
+        let fetch = |texel: [i32; 3]| -> [f32; 4] {
+            let mut coordinates = [0; 3];
+            for d in 0..dimensions {
+                let mode = sampler.address_modes[d];
+                match address(mode, texel[d], extent[d]) {
+                    Some(i) => coordinates[d] = i,
+                    None => return border(sampler),
+                }
+            }
+            if dimensions < 3 {
+                coordinates[2] = options.array_layer;
+            }
+            let value = unsafe { self.read(level, coordinates) }.map_or([0.0; 4], Texel::to_f32s);
+            match (options.depth_ref, sampler.compare) {
+                (Some(depth_ref), Some(function)) => {
+                    let passed = compare(function, depth_ref, value[0]) as u32 as f32;
+                    [passed, passed, passed, passed]
+                }
+                _ => value,
+            }
+        };
+
+        if let Some(component) = options.gather {
+            // Gathers return the texels in the order (u0, v1), (u1, v1), (u1, v0), (u0, v0).
+            let component = if options.depth_ref.is_some() {
+                0
+            } else {
+                component
+            };
+            return [2, 3, 1, 0].map(|i| fetch(texels[i].0)[component]);
+        }
+
+        let mut result = [0.0; 4];
+        for &(texel, weight) in &texels[..count] {
+            if weight == 0.0 {
+                continue;
+            }
+            let value = fetch(texel);
+            for (r, v) in result.iter_mut().zip(value) {
+                *r += v * weight;
+            }
+        }
+        result
+    }
+}
+
+/// Applies an address mode to a texel coordinate, returning `None` for the border.
+fn address(mode: AddressMode, i: i32, size: u32) -> Option<u32> {
+    let size = size as i32;
+    Some(match mode {
+        AddressMode::ClampToEdge => i.clamp(0, size - 1),
+        AddressMode::Repeat => i.rem_euclid(size),
+        AddressMode::MirrorRepeat => {
+            let i = i.rem_euclid(2 * size);
+            if i >= size { 2 * size - 1 - i } else { i }
+        }
+        AddressMode::ClampToBorder if (0..size).contains(&i) => i,
+        AddressMode::ClampToBorder => return None,
+    } as u32)
+}
+
+fn border(sampler: &Sampler) -> [f32; 4] {
+    match sampler.border_color {
+        Some(wgt::SamplerBorderColor::OpaqueBlack) => [0.0, 0.0, 0.0, 1.0],
+        Some(wgt::SamplerBorderColor::OpaqueWhite) => [1.0; 4],
+        Some(wgt::SamplerBorderColor::TransparentBlack | wgt::SamplerBorderColor::Zero) | None => {
+            [0.0; 4]
+        }
+    }
+}
+
+/// Returns the face of a cube map a direction points to, and the normalized coordinates on
+/// that face.
+fn cube_face(direction: &[f32]) -> (u32, f32, f32) {
+    let [x, y, z] = [direction[0], direction[1], direction[2]];
+    let (face, s, t, major) = if x.abs() >= y.abs() && x.abs() >= z.abs() {
+        if x >= 0.0 {
+            (0, -z, -y, x)
+        } else {
+            (1, z, -y, x)
+        }
+    } else if y.abs() >= z.abs() {
+        if y >= 0.0 {
+            (2, x, z, y)
+        } else {
+            (3, x, -z, y)
+        }
+    } else if z >= 0.0 {
+        (4, x, -y, z)
+    } else {
+        (5, -x, -y, z)
+    };
+    let major = major.abs();
+    (face, (s / major + 1.0) / 2.0, (t / major + 1.0) / 2.0)
+}
@@ -0,0 +1,1329 @@
+//! Interpreter of the [`naga`] IR of shader entry points.
+//!
+//! All invocations of a batch are executed in lockstep: each statement is executed for all
+//! active invocations before moving on to the next one, and each invocation, or lane, has a
+//! [`Flow`] telling whether it is executing the current block. This makes workgroup
+//! barriers trivial, and lets fragment shaders compute derivatives from the other lanes of
+//! their quad.
+
+use alloc::{format, string::ToString as _, sync::Arc, vec, vec::Vec};
+use core::iter;
+
+use naga::{Expression as E, Handle, Statement as S, TypeInner as Ti};
+
+use super::{
+    BindGroup, BoundResource, Sampler, TextureView,
+    format::Texel,
+    image::SampleOptions,
+    math,
+    value::{self, Pointer, Space, Types, Value},
+};
+
+/// An entry point of a shader module, ready to be executed with the pipeline constants it
+/// was created with.
+#[derive(Debug)]
+pub(super) struct Program {
+    shader: Arc<crate::NagaShader>,
+    entry_point: usize,
+    /// The values of the overrides of the module.
+    overrides: Vec<Value>,
+    /// The values of the global expressions of the module.
+    constants: Vec<Value>,
+    workgroup_size: [u32; 3],
+    /// The offset of each private or workgroup variable in the memory of its space.
+    global_offsets: Vec<u64>,
+    /// The private memory of an invocation before it starts, with the initializers of the
+    /// private variables.
+    private_memory: Vec<u8>,
+    workgroup_memory_size: usize,
+}
+
+impl Program {
+    pub fn new(
+        stage: &crate::ProgrammableStage<super::ShaderModule>,
+        naga_stage: naga::ShaderStage,
+    ) -> Result<Self, crate::PipelineError> {
+        let shader = Arc::clone(&stage.module.naga);
+        let module = &shader.module;
+        let entry_point = module
+            .entry_points
+            .iter()
+            .position(|ep| ep.stage == naga_stage && ep.name == stage.entry_point)
+            .ok_or(crate::PipelineError::EntryPoint(naga_stage))?;
+        let constant_error = |message: alloc::string::String| {
+            crate::PipelineError::PipelineConstants(
+                crate::auxil::map_naga_stage(naga_stage),
+                message,
+            )
+        };
+
+        // Overrides are set by the pipeline constants, by their ID if they have one.
+        let no_overrides = Types {
+            arena: &module.types,
+            overrides: &[],
+        };
+        let mut overrides = module
+            .overrides
+            .iter()
+            .map(|(_, o)| {
+                let key = match o.id {
+                    Some(id) => id.to_string(),
+                    None => o.name.clone().unwrap_or_default(),
+                };
+                stage.constants.get(&key).map(|&constant| {
+                    value::concretize(Value::AbstractFloat(constant), no_overrides.inner(o.ty))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Global expressions only refer to earlier ones, and overrides without a value are
+        // initialized by an earlier expression.
+        let mut constants: Vec<Value> = Vec::with_capacity(module.global_expressions.len());
+        for (handle, expression) in module.global_expressions.iter() {
+            let value = match *expression {
+                E::Constant(constant) => constants[module.constants[constant].init.index()].clone(),
+                E::Override(handle) => {
+                    let o = &module.overrides[handle];
+                    let value = match (overrides[handle.index()].clone(), o.init) {
+                        (Some(value), _) => value,
+                        (None, Some(init)) => constants[init.index()].clone(),
+                        (None, None) => {
+                            return Err(constant_error(format!(
+                                "missing value for override {:?}",
+                                o.name
+                            )));
+                        }
+                    };
+                    let value = value::concretize(value, no_overrides.inner(o.ty));
+                    overrides[handle.index()] = Some(value.clone());
+                    value
+                }
+                ref expression => evaluate_pure(
+                    no_overrides,
+                    expression,
+                    &mut |h| constants[h.index()].clone(),
+                    &|h| shader.info[h].inner_with(&module.types),
+                ),
+            };
+            debug_assert_eq!(constants.len(), handle.index());
+            constants.push(value);
+        }
+        let overrides = module
+            .overrides
+            .iter()
+            .zip(overrides)
+            .map(|((_, o), value)| match (value, o.init) {
+                (Some(value), _) => Ok(value),
+                (None, Some(init)) => Ok(constants[init.index()].clone()),
+                (None, None) => Err(constant_error(format!(
+                    "missing value for override {:?}",
+                    o.name
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let ep = &module.entry_points[entry_point];
+        let mut workgroup_size = ep.workgroup_size;
+        if let Some(sizes) = ep.workgroup_size_overrides {
+            for (size, expression) in workgroup_size.iter_mut().zip(sizes) {
+                if let Some(expression) = expression {
+                    *size = constants[expression.index()].as_u32();
+                }
+            }
+        }
+
+        let mut program = Self {
+            shader: Arc::clone(&shader),
+            entry_point,
+            overrides,
+            constants,
+            workgroup_size,
+            global_offsets: Vec::new(),
+            private_memory: Vec::new(),
+            workgroup_memory_size: 0,
+        };
+
+        let types = program.types();
+        let mut private_size = 0;
+        let mut workgroup_size = 0;
+        let mut private_inits = Vec::new();
+        let mut global_offsets = Vec::with_capacity(module.global_variables.len());
+        for (_, var) in module.global_variables.iter() {
+            let size = types.size(types.inner(var.ty)) as u64;
+            let offset = match var.space {
+                naga::AddressSpace::Private => {
+                    let offset = align(private_size);
+                    private_size = offset + size;
+                    if let Some(init) = var.init {
+                        private_inits.push((offset, var.ty, init));
+                    }
+                    offset
+                }
+                naga::AddressSpace::WorkGroup => {
+                    let offset = align(workgroup_size);
+                    workgroup_size = offset + size;
+                    offset
+                }
+                _ => 0,
+            };
+            global_offsets.push(offset);
+        }
+        let mut private_memory = vec![0; private_size as usize];
+        for (offset, ty, init) in private_inits {
+            types.write(
+                types.inner(ty),
+                &program.constants[init.index()],
+                &mut private_memory[offset as usize..],
+            );
+        }
+        program.global_offsets = global_offsets;
+        program.private_memory = private_memory;
+        program.workgroup_memory_size = workgroup_size as usize;
+        Ok(program)
+    }
+
+    pub fn types(&self) -> Types<'_> {
+        Types {
+            arena: &self.shader.module.types,
+            overrides: &self.overrides,
+        }
+    }
+
+    pub fn entry_point(&self) -> &naga::EntryPoint {
+        &self.shader.module.entry_points[self.entry_point]
+    }
+
+    pub fn workgroup_size(&self) -> [u32; 3] {
+        self.workgroup_size
+    }
+
+    /// Splits the result of an invocation into its outputs, with their bindings.
+    pub fn outputs(&self, result: Value) -> Vec<(&naga::Binding, Value)> {
+        let Some(ref result_ty) = self.entry_point().function.result else {
+            return Vec::new();
+        };
+        match result_ty.binding {
+            Some(ref binding) => vec![(binding, result)],
+            None => match *self.types().inner(result_ty.ty) {
+                Ti::Struct { ref members, .. } => members
+                    .iter()
+                    .zip(result.into_components())
+                    .filter_map(|(member, value)| Some((member.binding.as_ref()?, value)))
+                    .collect(),
+                _ => unreachable!("entry point result without binding"),
+            },
+        }
+    }
+}
+
+/// Rounds an offset in the memory of variables up to a multiple of 16 bytes, which is enough
+/// for all types.
+fn align(offset: u64) -> u64 {
+    offset.next_multiple_of(16)
+}
+
+/// Whether a lane executes the current statement, and if not, why.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Flow {
+    Active,
+    /// The lane didn't enter the current block.
+    Inactive,
+    Break,
+    Continue,
+    Return,
+    /// The fragment was discarded.
+    Kill,
+}
+
+impl Flow {
+    /// Returns the flow of a lane in a nested block or function.
+    fn enter(self) -> Self {
+        match self {
+            Self::Active => Self::Active,
+            _ => Self::Inactive,
+        }
+    }
+}
+
+/// The state of a call of a function, for all lanes.
+struct Frame<'a> {
+    function: &'a naga::Function,
+    info: &'a naga::valid::FunctionInfo,
+    /// The arguments of the function, by argument and lane.
+    arguments: Vec<Vec<Value>>,
+    /// The values of the expressions, by expression and lane.
+    values: Vec<Vec<Option<Value>>>,
+    /// The offsets of the local variables on the stacks.
+    locals: Vec<u64>,
+    /// The returned values, by lane.
+    results: Vec<Option<Value>>,
+}
+
+/// The resources bound to a pipeline.
+#[derive(Clone, Copy)]
+pub(super) struct Resources<'a> {
+    pub bind_groups: &'a [Option<BindGroup>],
+    pub push_constants: &'a [u8],
+}
+
+/// A batch of invocations of a [`Program`], executed in lockstep.
+pub(super) struct Invocations<'a> {
+    program: &'a Program,
+    resources: Resources<'a>,
+    lanes: usize,
+    private: Vec<Vec<u8>>,
+    /// The local variables of each lane.
+    stacks: Vec<Vec<u8>>,
+    workgroup: Vec<u8>,
+    /// Lanes which don't have side effects on buffers and textures, such as fragments
+    /// which are only executed to compute the derivatives of their quad.
+    helpers: Vec<bool>,
+    killed: Vec<bool>,
+}
+
+impl<'a> Invocations<'a> {
+    pub fn new(program: &'a Program, resources: Resources<'a>) -> Self {
+        Self {
+            program,
+            resources,
+            lanes: 0,
+            private: Vec::new(),
+            stacks: Vec::new(),
+            workgroup: Vec::new(),
+            helpers: Vec::new(),
+            killed: Vec::new(),
+        }
+    }
+
+    /// Executes `lanes` invocations of the entry point, where `helpers` tells which lanes
+    /// are helper invocations, and `input` returns the inputs of each lane.
+    ///
+    /// Returns the result of each lane, which is an empty composite for entry points
+    /// without a result, or `None` for killed lanes. Workgroup memory is zeroed first.
+    ///
+    /// # Safety
+    ///
+    /// The bound buffers and textures must not be accessed concurrently.
+    pub unsafe fn run(
+        &mut self,
+        lanes: usize,
+        helpers: &[bool],
+        mut input: impl FnMut(usize, &naga::Binding, &Ti) -> Value,
+    ) -> Vec<Option<Value>> {
+        let program = self.program;
+        self.lanes = lanes;
+        self.private.resize_with(lanes, Vec::new);
+        for private in &mut self.private {
+            private.clear();
+            private.extend_from_slice(&program.private_memory);
+        }
+        self.stacks.resize_with(lanes, Vec::new);
+        for stack in &mut self.stacks {
+            stack.clear();
+        }
+        self.workgroup.clear();
+        self.workgroup.resize(program.workgroup_memory_size, 0);
+        self.helpers.clear();
+        self.helpers
+            .extend((0..lanes).map(|lane| helpers.get(lane) == Some(&true)));
+        self.killed.clear();
+        self.killed.resize(lanes, false);
+
+        let ep = program.entry_point();
+        let types = program.types();
+        let arguments = ep
+            .function
+            .arguments
+            .iter()
+            .map(|argument| {
+                (0..lanes)
+                    .map(|lane| match argument.binding {
+                        Some(ref binding) => input(lane, binding, types.inner(argument.ty)),
+                        None => match *types.inner(argument.ty) {
+                            Ti::Struct { ref members, .. } => Value::Composite(
+                                members
+                                    .iter()
+                                    .map(|member| {
+                                        let binding = member.binding.as_ref().unwrap();
+                                        input(lane, binding, types.inner(member.ty))
+                                    })
+                                    .collect(),
+                            ),
+                            _ => unreachable!("entry point argument without binding"),
+                        },
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let info = program.shader.info.get_entry_point(program.entry_point);
+        let flow = vec![Flow::Active; lanes];
+        let (results, _) = self.call(&ep.function, info, arguments, &flow);
+        results
+            .into_iter()
+            .zip(&self.killed)
+            .map(|(result, &killed)| {
+                (!killed).then(|| result.unwrap_or(Value::Composite(Vec::new())))
+            })
+            .collect()
+    }
+
+    fn module(&self) -> &'a naga::Module {
+        let program: &'a Program = self.program;
+        &program.shader.module
+    }
+
+    fn types(&self) -> Types<'a> {
+        let program: &'a Program = self.program;
+        program.types()
+    }
+
+    fn type_of(&self, frame: &Frame<'a>, expression: Handle<naga::Expression>) -> &'a Ti {
+        let info: &'a naga::valid::FunctionInfo = frame.info;
+        info[expression].ty.inner_with(&self.module().types)
+    }
+
+    /// Calls `function` for the lanes which are active in `flow`, returning the result and
+    /// final flow of each lane.
+    fn call(
+        &mut self,
+        function: &'a naga::Function,
+        info: &'a naga::valid::FunctionInfo,
+        arguments: Vec<Vec<Value>>,
+        flow: &[Flow],
+    ) -> (Vec<Option<Value>>, Vec<Flow>) {
+        let types = self.types();
+        let base = self.stacks.first().map_or(0, |stack| stack.len() as u64);
+        let mut size = 0;
+        let locals = function
+            .local_variables
+            .iter()
+            .map(|(_, local)| {
+                let offset = base + align(size);
+                size = offset - base + types.size(types.inner(local.ty)) as u64;
+                offset
+            })
+            .collect();
+        for stack in &mut self.stacks {
+            stack.resize((base + size) as usize, 0);
+        }
+
+        let mut frame = Frame {
+            function,
+            info,
+            arguments,
+            values: vec![vec![None; self.lanes]; function.expressions.len()],
+            locals,
+            results: vec![None; self.lanes],
+        };
+        let mut flow = flow.iter().map(|flow| flow.enter()).collect::<Vec<_>>();
+
+        for (handle, local) in function.local_variables.iter() {
+            let Some(init) = local.init else {
+                continue;
+            };
+            let pointer = Pointer {
+                space: Space::Function,
+                offset: frame.locals[handle.index()],
+            };
+            for (lane, &lane_flow) in flow.iter().enumerate() {
+                if lane_flow == Flow::Active {
+                    let value = self.get(&mut frame, init, lane);
+                    self.store(lane, pointer, types.inner(local.ty), &value);
+                }
+            }
+        }
+
+        self.block(&mut frame, &function.body, &mut flow);
+
+        for stack in &mut self.stacks {
+            stack.truncate(base as usize);
+        }
+        (frame.results, flow)
+    }
+
+    fn block(&mut self, frame: &mut Frame<'a>, block: &'a naga::Block, flow: &mut [Flow]) {
+        for statement in block.iter() {
+            if !flow.contains(&Flow::Active) {
+                return;
+            }
+            self.statement(frame, statement, flow);
+        }
+    }
+
+    fn statement(
+        &mut self,
+        frame: &mut Frame<'a>,
+        statement: &'a naga::Statement,
+        flow: &mut [Flow],
+    ) {
+        let lanes = self.lanes;
+        let active = |flow: &[Flow]| {
+            (0..lanes)
+                .filter(|&lane| flow[lane] == Flow::Active)
+                .collect::<Vec<_>>()
+        };
+        match *statement {
+            S::Emit(ref range) => {
+                let lanes = active(flow);
+                for expression in range.clone() {
+                    for &lane in &lanes {
+                        let value = self.eval(frame, expression, lane);
+                        frame.values[expression.index()][lane] = Some(value);
+                    }
+                }
+            }
+            S::Block(ref block) => self.block(frame, block, flow),
+            S::If {
+                condition,
+                ref accept,
+                ref reject,
+            } => {
+                let mut accept_flow = vec![Flow::Inactive; lanes];
+                let mut reject_flow = vec![Flow::Inactive; lanes];
+                for lane in active(flow) {
+                    if self.get(frame, condition, lane).as_bool() {
+                        accept_flow[lane] = Flow::Active;
+                    } else {
+                        reject_flow[lane] = Flow::Active;
+                    }
+                }
+                self.block(frame, accept, &mut accept_flow);
+                self.block(frame, reject, &mut reject_flow);
+                for lane in active(flow) {
+                    flow[lane] = match accept_flow[lane] {
+                        Flow::Inactive => reject_flow[lane],
+                        accept => accept,
+                    };
+                }
+            }
+            S::Switch {
+                selector,
+                ref cases,
+            } => {
+                let entered = active(flow);
+                let mut starts = vec![None; lanes];
+                for &lane in &entered {
+                    let selector = self.get(frame, selector, lane).as_i64();
+                    starts[lane] = cases
+                        .iter()
+                        .position(|case| match case.value {
+                            naga::SwitchValue::I32(value) => value as i64 == selector,
+                            naga::SwitchValue::U32(value) => value as i64 == selector,
+                            naga::SwitchValue::Default => false,
+                        })
+                        .or_else(|| {
+                            cases
+                                .iter()
+                                .position(|case| case.value == naga::SwitchValue::Default)
+                        });
+                }
+                // Lanes fall through cases until they break out of the switch.
+                let mut case_flow = vec![Flow::Inactive; lanes];
+                for (i, case) in cases.iter().enumerate() {
+                    for &lane in &entered {
+                        if starts[lane] == Some(i) {
+                            case_flow[lane] = Flow::Active;
+                        }
+                    }
+                    self.block(frame, &case.body, &mut case_flow);
+                    if !case.fall_through {
+                        for flow in &mut case_flow {
+                            if *flow == Flow::Active {
+                                *flow = Flow::Break;
+                            }
+                        }
+                    }
+                }
+                for lane in entered {
+                    flow[lane] = match case_flow[lane] {
+                        Flow::Break | Flow::Inactive => Flow::Active,
+                        case => case,
+                    };
+                }
+            }
+            S::Loop {
+                ref body,
+                ref continuing,
+                break_if,
+            } => {
+                let entered = active(flow);
+                let mut loop_flow = flow.iter().map(|flow| flow.enter()).collect::<Vec<_>>();
+                loop {
+                    self.block(frame, body, &mut loop_flow);
+                    for flow in &mut loop_flow {
+                        if *flow == Flow::Continue {
+                            *flow = Flow::Active;
+                        }
+                    }
+                    self.block(frame, continuing, &mut loop_flow);
+                    if let Some(break_if) = break_if {
+                        for lane in active(&loop_flow) {
+                            if self.get(frame, break_if, lane).as_bool() {
+                                loop_flow[lane] = Flow::Break;
+                            }
+                        }
+                    }
+                    if !loop_flow.contains(&Flow::Active) {
+                        break;
+                    }
+                }
+                for lane in entered {
+                    flow[lane] = match loop_flow[lane] {
+                        Flow::Break => Flow::Active,
+                        other => other,
+                    };
+                }
+            }
+            S::Break => {
+                for lane in active(flow) {
+                    flow[lane] = Flow::Break;
+                }
+            }
+            S::Continue => {
+                for lane in active(flow) {
+                    flow[lane] = Flow::Continue;
+                }
+            }
+            S::Return { value } => {
+                for lane in active(flow) {
+                    if let Some(value) = value {
+                        frame.results[lane] = Some(self.get(frame, value, lane));
+                    }
+                    flow[lane] = Flow::Return;
+                }
+            }
+            S::Kill => {
+                for lane in active(flow) {
+                    flow[lane] = Flow::Kill;
+                    self.killed[lane] = true;
+                }
+            }
+            // All invocations of a workgroup are executed in lockstep.
+            S::ControlBarrier(_) | S::MemoryBarrier(_) => {}
+            S::Store { pointer, value } => {
+                let inner = self.type_of(frame, value);
+                for lane in active(flow) {
+                    let pointer = self.get(frame, pointer, lane).as_pointer();
+                    let value = self.get(frame, value, lane);
+                    self.store(lane, pointer, inner, &value);
+                }
+            }
+            S::ImageStore {
+                image,
+                coordinate,
+                array_index,
+                value,
+            } => {
+                for lane in active(flow) {
+                    if self.helpers[lane] {
+                        continue;
+                    }
+                    let view = self.texture(frame, image, lane);
+                    let coordinates = self.texel_coordinates(frame, coordinate, array_index, lane);
+                    let value = self.get(frame, value, lane);
+                    let texel = match value.components()[0] {
+                        Value::I32(_) => {
+                            Texel::Sint(to_array(value.components(), |v| v.as_u32() as i32))
+                        }
+                        Value::U32(_) => Texel::Uint(to_array(value.components(), Value::as_u32)),
+                        _ => Texel::Float(to_array(value.components(), |v| v.as_f64() as f32)),
+                    };
+                    if let Some(coordinates) = coordinates {
+                        // SAFETY: The caller is responsible for ensuring that the texture
+                        // isn't accessed concurrently.
+                        unsafe { view.write(0, coordinates, texel) };
+                    }
+                }
+            }
+            S::Atomic {
+                pointer,
+                fun,
+                value,
+                result,
+            } => {
+                let inner = self.type_of(frame, value);
+                for lane in active(flow) {
+                    let pointer = self.get(frame, pointer, lane).as_pointer();
+                    let value = self.get(frame, value, lane);
+                    let old = self.load(lane, pointer, inner);
+                    let (new, returned) = match fun {
+                        naga::AtomicFunction::Exchange {
+                            compare: Some(compare),
+                        } => {
+                            let exchanged = old == self.get(frame, compare, lane);
+                            let new = exchanged.then_some(value);
+                            (new, Value::Composite(vec![old, Value::Bool(exchanged)]))
+                        }
+                        fun => (Some(value::atomic(fun, &old, &value)), old),
+                    };
+                    if let Some(ref new) = new {
+                        self.store(lane, pointer, inner, new);
+                    }
+                    if let Some(result) = result {
+                        frame.values[result.index()][lane] = Some(returned);
+                    }
+                }
+            }
+            S::WorkGroupUniformLoad { pointer, result } => {
+                let inner = self.type_of(frame, result);
+                for lane in active(flow) {
+                    let pointer = self.get(frame, pointer, lane).as_pointer();
+                    let value = self.load(lane, pointer, inner);
+                    frame.values[result.index()][lane] = Some(value);
+                }
+            }
+            S::Call {
+                function,
+                ref arguments,
+                result,
+            } => {
+                let module = self.module();
+                let entered = active(flow);
+                let arguments = arguments
+                    .iter()
+                    .map(|&argument| {
+                        (0..lanes)
+                            .map(|lane| match flow[lane] {
+                                Flow::Active => self.get(frame, argument, lane),
+                                _ => Value::Bool(false),
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let info: &'a naga::valid::ModuleInfo = &self.program_shader().info;
+                let (results, call_flow) = self.call(
+                    &module.functions[function],
+                    &info[function],
+                    arguments,
+                    flow,
+                );
+                for lane in entered {
+                    if call_flow[lane] == Flow::Kill {
+                        flow[lane] = Flow::Kill;
+                    }
+                    if let Some(result) = result {
+                        frame.values[result.index()][lane] = results[lane].clone();
+                    }
+                }
+            }
+            S::DebugPrintf {
+                ref format,
+                ref arguments,
+            } => {
+                for lane in active(flow) {
+                    let arguments = arguments
+                        .iter()
+                        .map(|&argument| self.get(frame, argument, lane))
+                        .collect::<Vec<_>>();
+                    log::info!("debugPrintf(\"{format}\", {arguments:?})");
+                }
+            }
+            S::ImageAtomic { .. } => super::unsupported("image atomics"),
+            S::RayQuery { .. } => super::unsupported("ray queries"),
+            S::RayPipelineFunction(_) => unreachable!("ray tracing stages are never run"),
+            S::SubgroupBallot { .. }
+            | S::SubgroupGather { .. }
+            | S::SubgroupCollectiveOperation { .. } => super::unsupported("subgroup operations"),
+        }
+    }
+
+    fn program_shader(&self) -> &'a crate::NagaShader {
+        let program: &'a Program = self.program;
+        &program.shader
+    }
+
+    /// Returns the value of `expression` for `lane`, evaluating it if it wasn't emitted.
+    fn get(
+        &mut self,
+        frame: &mut Frame<'a>,
+        expression: Handle<naga::Expression>,
+        lane: usize,
+    ) -> Value {
+        if let Some(ref value) = frame.values[expression.index()][lane] {
+            return value.clone();
+        }
+        let value = self.eval(frame, expression, lane);
+        frame.values[expression.index()][lane] = Some(value.clone());
+        value
+    }
+
+    fn eval(
+        &mut self,
+        frame: &mut Frame<'a>,
+        handle: Handle<naga::Expression>,
+        lane: usize,
+    ) -> Value {
+        let program: &'a Program = self.program;
+        let module = self.module();
+        let function: &'a naga::Function = frame.function;
+        match function.expressions[handle] {
+            E::Constant(constant) => {
+                program.constants[module.constants[constant].init.index()].clone()
+            }
+            E::Override(o) => program.overrides[o.index()].clone(),
+            E::FunctionArgument(index) => frame.arguments[index as usize][lane].clone(),
+            E::GlobalVariable(global) => {
+                let offset = program.global_offsets[global.index()];
+                let space = match module.global_variables[global].space {
+                    naga::AddressSpace::Private => Space::Private,
+                    naga::AddressSpace::WorkGroup => Space::WorkGroup,
+                    naga::AddressSpace::PushConstant => Space::PushConstant,
+                    naga::AddressSpace::Uniform | naga::AddressSpace::Storage { .. } => {
+                        Space::Binding(global)
+                    }
+                    naga::AddressSpace::Handle => return Value::Handle(global),
+                    naga::AddressSpace::Function => unreachable!("global in function space"),
+                    naga::AddressSpace::RayPayload | naga::AddressSpace::IncomingRayPayload => {
+                        unreachable!("ray payloads are only used by ray tracing stages")
+                    }
+                };
+                Value::Pointer(Pointer { space, offset })
+            }
+            E::LocalVariable(local) => Value::Pointer(Pointer {
+                space: Space::Function,
+                offset: frame.locals[local.index()],
+            }),
+            E::Load { pointer } => {
+                let pointer = self.get(frame, pointer, lane).as_pointer();
+                self.load(lane, pointer, self.type_of(frame, handle))
+            }
+            E::Access { base, index } if self.is_pointer(frame, base) => {
+                let pointer = self.get(frame, base, lane).as_pointer();
+                // Negative indices are out of bounds.
+                let index =
+                    u64::try_from(self.get(frame, index, lane).as_i64()).unwrap_or(u64::MAX);
+                Value::Pointer(self.element_pointer(self.type_of(frame, base), pointer, index))
+            }
+            E::AccessIndex { base, index } if self.is_pointer(frame, base) => {
+                let pointer = self.get(frame, base, lane).as_pointer();
+                Value::Pointer(self.element_pointer(
+                    self.type_of(frame, base),
+                    pointer,
+                    index as u64,
+                ))
+            }
+            E::ImageSample {
+                image,
+                sampler,
+                gather,
+                coordinate,
+                array_index,
+                offset,
+                level,
+                depth_ref,
+                clamp_to_edge,
+            } => {
+                let view = self.texture(frame, image, lane);
+                let sampler = self.sampler(frame, sampler, lane);
+                let coordinates = self.get(frame, coordinate, lane).to_f32s();
+                let mut options = SampleOptions {
+                    gather: gather.map(|component| component as usize),
+                    clamp_to_edge,
+                    ..SampleOptions::default()
+                };
+                if let Some(array_index) = array_index {
+                    let layers = view.array_layers.len() as i64;
+                    let layers = match view.dimension {
+                        wgt::TextureViewDimension::CubeArray => layers / 6,
+                        _ => layers,
+                    };
+                    options.array_layer = self
+                        .get(frame, array_index, lane)
+                        .as_i64()
+                        .clamp(0, layers - 1) as u32;
+                }
+                if let Some(offset) = offset {
+                    let offset = self.get(frame, offset, lane).to_i32s();
+                    options.offset[..offset.len()].copy_from_slice(&offset);
+                }
+                if let Some(depth_ref) = depth_ref {
+                    options.depth_ref = Some(self.get(frame, depth_ref, lane).as_f64() as f32);
+                }
+                options.lod = match level {
+                    naga::SampleLevel::Zero => 0.0,
+                    naga::SampleLevel::Exact(lod) => self.get(frame, lod, lane).as_f64() as f32,
+                    naga::SampleLevel::Auto => self.implicit_lod(frame, view, coordinate, lane),
+                    naga::SampleLevel::Bias(bias) => {
+                        self.implicit_lod(frame, view, coordinate, lane)
+                            + self.get(frame, bias, lane).as_f64() as f32
+                    }
+                    naga::SampleLevel::Gradient { x, y } => view.lod(
+                        &self.get(frame, x, lane).to_f32s(),
+                        &self.get(frame, y, lane).to_f32s(),
+                    ),
+                };
+                // SAFETY: The caller is responsible for ensuring that the texture isn't
+                // written to concurrently.
+                let texel = unsafe { view.sample(sampler, &coordinates, options) };
+                match *self.type_of(frame, handle) {
+                    Ti::Scalar(_) => Value::F32(texel[0]),
+                    _ => Value::Composite(texel.iter().map(|&c| Value::F32(c)).collect()),
+                }
+            }
+            E::ImageLoad {
+                image,
+                coordinate,
+                array_index,
+                sample: _,
+                level,
+            } => {
+                let view = self.texture(frame, image, lane);
+                let coordinates = self.texel_coordinates(frame, coordinate, array_index, lane);
+                let level = match level {
+                    Some(level) => u32::try_from(self.get(frame, level, lane).as_i64()).ok(),
+                    None => Some(0),
+                };
+                let texel = match (coordinates, level) {
+                    // SAFETY: The caller is responsible for ensuring that the texture isn't
+                    // written to concurrently.
+                    (Some(coordinates), Some(level)) => unsafe { view.read(level, coordinates) },
+                    _ => None,
+                };
+                texel_value(texel, self.type_of(frame, handle))
+            }
+            E::ImageQuery { image, query } => {
+                let view = self.texture(frame, image, lane);
+                match query {
+                    naga::ImageQuery::Size { level } => {
+                        let level = match level {
+                            Some(level) => self.get(frame, level, lane).as_u32(),
+                            None => 0,
+                        };
+                        let [width, height, depth] = view.mip_extent(level);
+                        let size = match view.dimension {
+                            wgt::TextureViewDimension::D1 => return Value::U32(width),
+                            wgt::TextureViewDimension::D3 => vec![width, height, depth],
+                            _ => vec![width, height],
+                        };
+                        Value::Composite(size.into_iter().map(Value::U32).collect())
+                    }
+                    naga::ImageQuery::NumLevels => Value::U32(view.mip_levels.len() as u32),
+                    naga::ImageQuery::NumLayers => {
+                        let layers = view.array_layers.len() as u32;
+                        Value::U32(match view.dimension {
+                            wgt::TextureViewDimension::CubeArray => layers / 6,
+                            _ => layers,
+                        })
+                    }
+                    naga::ImageQuery::NumSamples => Value::U32(1),
+                }
+            }
+            E::Derivative {
+                axis,
+                ctrl: _,
+                expr,
+            } => {
+                let (dx, dy) = self.derivatives(frame, expr, lane);
+                match axis {
+                    naga::DerivativeAxis::X => dx,
+                    naga::DerivativeAxis::Y => dy,
+                    naga::DerivativeAxis::Width => dx.zip(&dy, &mut |dx, dy| {
+                        dx.with_f64(dx.as_f64().abs() + dy.as_f64().abs())
+                    }),
+                }
+            }
+            E::ArrayLength(pointer) => {
+                let stride = match *self.type_of(frame, pointer) {
+                    Ti::Pointer { base, .. } => match *self.types().inner(base) {
+                        Ti::Array { stride, .. } => stride as u64,
+                        _ => unreachable!("array length of a non-array"),
+                    },
+                    _ => unreachable!("array length of a non-pointer"),
+                };
+                let pointer = self.get(frame, pointer, lane).as_pointer();
+                let Space::Binding(global) = pointer.space else {
+                    unreachable!("array length of {pointer:?}");
+                };
+                let size = match self.resource(global) {
+                    Some(&BoundResource::Buffer { size, .. }) => size,
+                    _ => 0,
+                };
+                Value::U32((size.saturating_sub(pointer.offset) / stride) as u32)
+            }
+            E::CallResult(_)
+            | E::AtomicResult { .. }
+            | E::WorkGroupUniformLoadResult { .. }
+            | E::SubgroupBallotResult
+            | E::SubgroupOperationResult { .. }
+            | E::RayQueryProceedResult => {
+                unreachable!("{handle:?} is evaluated by its statement")
+            }
+            E::RayQueryVertexPositions { .. } | E::RayQueryGetIntersection { .. } => {
+                super::unsupported("ray queries")
+            }
+            ref expression => {
+                let types = self.types();
+                let info: &'a naga::valid::FunctionInfo = frame.info;
+                let arena = &module.types;
+                evaluate_pure(types, expression, &mut |h| self.get(frame, h, lane), &|h| {
+                    info[h].ty.inner_with(arena)
+                })
+            }
+        }
+    }
+
+    fn is_pointer(&self, frame: &Frame<'a>, expression: Handle<naga::Expression>) -> bool {
+        matches!(
+            *self.type_of(frame, expression),
+            Ti::Pointer { .. } | Ti::ValuePointer { .. }
+        )
+    }
+
+    /// Returns a pointer to element `index` of the array, vector, matrix or structure
+    /// `pointer` points to, where `pointer_ty` is the type of `pointer`.
+    ///
+    /// Indices of sized arrays, vectors and matrices are clamped.
+    fn element_pointer(&self, pointer_ty: &Ti, pointer: Pointer, index: u64) -> Pointer {
+        let types = self.types();
+        let pointee = match *pointer_ty {
+            Ti::Pointer { base, .. } => types.inner(base),
+            Ti::ValuePointer {
+                size: Some(size),
+                scalar,
+                ..
+            } => &Ti::Vector { size, scalar },
+            _ => unreachable!("access into {pointer_ty:?}"),
+        };
+        let offset = match *pointee {
+            Ti::Array { size, stride, .. } => {
+                let index = match types.array_length(size) {
+                    Some(length) => index.min(length.max(1) as u64 - 1),
+                    None => index,
+                };
+                index.saturating_mul(stride as u64)
+            }
+            Ti::Vector { size, scalar } => {
+                index.min(size as u64 - 1) * value::scalar_size(scalar) as u64
+            }
+            Ti::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => index.min(columns as u64 - 1) * value::column_stride(rows, scalar) as u64,
+            Ti::Struct { ref members, .. } => members[index as usize].offset as u64,
+            ref other => unreachable!("access into {other:?}"),
+        };
+        Pointer {
+            space: pointer.space,
+            offset: pointer.offset.saturating_add(offset),
+        }
+    }
+
+    /// Returns the buffer bound to `global`.
+    fn resource(&self, global: Handle<naga::GlobalVariable>) -> Option<&'a BoundResource> {
+        let binding = self.module().global_variables[global].binding.as_ref()?;
+        let resources: Resources<'a> = self.resources;
+        resources
+            .bind_groups
+            .get(binding.group as usize)?
+            .as_ref()?
+            .get(binding.binding)
+    }
+
+    /// Calls `f` with the memory of `space` for `lane`, or with an empty slice if there is
+    /// none.
+    fn with_memory<R>(&mut self, lane: usize, space: Space, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        match space {
+            Space::Function => f(&mut self.stacks[lane]),
+            Space::Private => f(&mut self.private[lane]),
+            Space::WorkGroup => f(&mut self.workgroup),
+            Space::PushConstant => f(&mut self.resources.push_constants.to_vec()),
+            Space::Binding(global) => match self.resource(global) {
+                Some(&BoundResource::Buffer {
+                    ref buffer,
+                    offset,
+                    size,
+                }) => {
+                    let ptr = buffer.get_slice_ptr(offset..offset + size);
+                    // SAFETY: The caller of `run` is responsible for ensuring that the buffer
+                    // isn't accessed concurrently, and this is the only reference to it.
+                    f(unsafe { &mut *ptr })
+                }
+                _ => f(&mut []),
+            },
+        }
+    }
+
+    /// Loads a value of type `inner` from memory, reading zero out of bounds.
+    fn load(&mut self, lane: usize, pointer: Pointer, inner: &Ti) -> Value {
+        let types = self.types();
+        let size = types.size(inner) as u64;
+        self.with_memory(lane, pointer.space, |memory| {
+            match memory_range(pointer.offset, size, memory.len()) {
+                Some(range) => types.read(inner, &memory[range]),
+                None => types.zero(inner),
+            }
+        })
+    }
+
+    /// Stores a value of type `inner` to memory, discarding out of bounds stores and stores
+    /// of helper lanes to buffers.
+    fn store(&mut self, lane: usize, pointer: Pointer, inner: &Ti, value: &Value) {
+        if matches!(pointer.space, Space::Binding(_)) && self.helpers[lane] {
+            return;
+        }
+        let types = self.types();
+        let size = types.size(inner) as u64;
+        self.with_memory(lane, pointer.space, |memory| {
+            if let Some(range) = memory_range(pointer.offset, size, memory.len()) {
+                types.write(inner, value, &mut memory[range]);
+            }
+        })
+    }
+
+    fn texture(
+        &mut self,
+        frame: &mut Frame<'a>,
+        image: Handle<naga::Expression>,
+        lane: usize,
+    ) -> &'a TextureView {
+        let global = self.get(frame, image, lane).as_handle();
+        match self.resource(global) {
+            Some(BoundResource::TextureView(view)) => view,
+            _ => unreachable!("no texture bound to {global:?}"),
+        }
+    }
+
+    fn sampler(
+        &mut self,
+        frame: &mut Frame<'a>,
+        sampler: Handle<naga::Expression>,
+        lane: usize,
+    ) -> &'a Sampler {
+        let global = self.get(frame, sampler, lane).as_handle();
+        match self.resource(global) {
+            Some(BoundResource::Sampler(sampler)) => sampler,
+            _ => unreachable!("no sampler bound to {global:?}"),
+        }
+    }
+
+    /// Returns the coordinates of a texel for [`TextureView::read`], or `None` if they are
+    /// negative.
+    fn texel_coordinates(
+        &mut self,
+        frame: &mut Frame<'a>,
+        coordinate: Handle<naga::Expression>,
+        array_index: Option<Handle<naga::Expression>>,
+        lane: usize,
+    ) -> Option<[u32; 3]> {
+        let mut coordinates = [0; 3];
+        let values = self.get(frame, coordinate, lane);
+        for (c, value) in coordinates.iter_mut().zip(values.components()) {
+            *c = u32::try_from(value.as_i64()).ok()?;
+        }
+        if let Some(array_index) = array_index {
+            coordinates[2] = u32::try_from(self.get(frame, array_index, lane).as_i64()).ok()?;
+        }
+        Some(coordinates)
+    }
+
+    /// Returns the derivatives of `expression` along x and y, from the other lanes of the
+    /// quad of `lane`.
+    ///
+    /// Lanes of a quad are ordered from left to right and from top to bottom.
+    fn derivatives(
+        &mut self,
+        frame: &mut Frame<'a>,
+        expression: Handle<naga::Expression>,
+        lane: usize,
+    ) -> (Value, Value) {
+        let own = self.get(frame, expression, lane);
+        let values = &frame.values[expression.index()];
+        let at = |lane: usize| {
+            values
+                .get(lane)
+                .cloned()
+                .flatten()
+                .unwrap_or_else(|| own.clone())
+        };
+        let difference = |a: Value, b: Value| value::binary(naga::BinaryOperator::Subtract, &a, &b);
+        (
+            difference(at(lane | 1), at(lane & !1)),
+            difference(at(lane | 2), at(lane & !2)),
+        )
+    }
+
+    /// Returns the level of detail of an implicit sample, which is zero outside of fragment
+    /// shaders.
+    fn implicit_lod(
+        &mut self,
+        frame: &mut Frame<'a>,
+        view: &TextureView,
+        coordinate: Handle<naga::Expression>,
+        lane: usize,
+    ) -> f32 {
+        if self.program.entry_point().stage != naga::ShaderStage::Fragment {
+            return 0.0;
+        }
+        let (dx, dy) = self.derivatives(frame, coordinate, lane);
+        view.lod(&dx.to_f32s(), &dy.to_f32s())
+    }
+}
+
+/// Returns the range of `size` bytes at `offset` in a memory of `len` bytes, if it is in
+/// bounds.
+fn memory_range(offset: u64, size: u64, len: usize) -> Option<core::ops::Range<usize>> {
+    let end = offset.checked_add(size)?;
+    (end <= len as u64).then_some(offset as usize..end as usize)
+}
+
+fn to_array<T: Copy + Default>(values: &[Value], f: impl Fn(&Value) -> T) -> [T; 4] {
+    let mut array = [T::default(); 4];
+    for (a, value) in array.iter_mut().zip(values) {
+        *a = f(value);
+    }
+    array
+}
+
+/// Converts a texel to the value of an image load of type `load_ty`, which is zero out of
+/// bounds.
+fn texel_value(texel: Option<Texel>, load_ty: &Ti) -> Value {
+    let texel = texel.unwrap_or(Texel::Float([0.0; 4]));
+    let scalar = match *load_ty {
+        // Loads of depth textures return the depth.
+        Ti::Scalar(_) => return Value::F32(texel.to_f32s()[0]),
+        Ti::Vector { scalar, .. } => scalar,
+        _ => unreachable!("image load of type {load_ty:?}"),
+    };
+    let components: [Value; 4] = match (scalar.kind, texel) {
+        (naga::ScalarKind::Sint, Texel::Sint(texel)) => texel.map(Value::I32),
+        (naga::ScalarKind::Uint, Texel::Uint(texel)) => texel.map(Value::U32),
+        (naga::ScalarKind::Sint, texel) => texel.to_f32s().map(|c| Value::I32(c as i32)),
+        (naga::ScalarKind::Uint, texel) => texel.to_f32s().map(|c| Value::U32(c as u32)),
+        (_, texel) => texel.to_f32s().map(Value::F32),
+    };
+    Value::Composite(components.into())
+}
+
+/// Evaluates an expression which doesn't depend on the state of the invocation, given its
+/// operands and their types.
+fn evaluate_pure<'t>(
+    types: Types<'t>,
+    expression: &naga::Expression,
+    operand: &mut dyn FnMut(Handle<naga::Expression>) -> Value,
+    type_of: &dyn Fn(Handle<naga::Expression>) -> &'t Ti,
+) -> Value {
+    match *expression {
+        E::Literal(literal) => Value::from_literal(literal),
+        E::ZeroValue(ty) => types.zero(types.inner(ty)),
+        E::Compose { ty, ref components } => {
+            let components = components.iter().map(|&c| operand(c));
+            match *types.inner(ty) {
+                // Vectors can be composed of smaller vectors.
+                Ti::Vector { .. } => {
+                    Value::Composite(components.flat_map(Value::into_components).collect())
+                }
+                _ => Value::Composite(components.collect()),
+            }
+        }
+        E::Splat { size, value } => Value::Composite(vec![operand(value); size as usize]),
+        E::Swizzle {
+            size,
+            vector,
+            pattern,
+        } => {
+            let vector = operand(vector);
+            let components = vector.components();
+            Value::Composite(
+                pattern[..size as usize]
+                    .iter()
+                    .map(|&c| components[c as usize].clone())
+                    .collect(),
+            )
+        }
+        E::Access { base, index } => {
+            let index = operand(index).as_i64();
+            let mut components = operand(base).into_components();
+            // Indices of values are clamped.
+            let index = index.clamp(0, components.len() as i64 - 1) as usize;
+            components.swap_remove(index)
+        }
+        E::AccessIndex { base, index } => {
+            operand(base).into_components().swap_remove(index as usize)
+        }
+        E::Unary { op, expr } => value::unary(op, &operand(expr)),
+        E::Binary {
+            op: naga::BinaryOperator::Multiply,
+            left,
+            right,
+        } => value::multiply(
+            &operand(left),
+            type_of(left),
+            &operand(right),
+            type_of(right),
+        ),
+        E::Binary { op, left, right } => value::binary(op, &operand(left), &operand(right)),
+        E::Select {
+            condition,
+            accept,
+            reject,
+        } => value::select(&operand(condition), &operand(accept), &operand(reject)),
+        E::Relational { fun, argument } => value::relational(fun, &operand(argument)),
+        E::Math {
+            fun,
+            arg,
+            arg1,
+            arg2,
+            arg3,
+        } => {
+            let args = iter::once(arg)
+                .chain(arg1)
+                .chain(arg2)
+                .chain(arg3)
+                .map(operand)
+                .collect::<Vec<_>>();
+            math::evaluate(fun, &args)
+        }
+        E::As {
+            expr,
+            kind,
+            convert,
+        } => value::cast(&operand(expr), kind, convert),
+        ref other => unreachable!("{other:?} is not a pure expression"),
+    }
+}
+
+/// Executes a compute dispatch of `group_count` workgroups.
+///
+/// # Safety
+///
+/// The bound buffers and textures must not be accessed concurrently.
+pub(super) unsafe fn dispatch(program: &Program, resources: Resources<'_>, group_count: [u32; 3]) {
+    let [x, y, z] = program.workgroup_size();
+    let lanes = (x * y * z) as usize;
+    let local_ids = (0..lanes as u32)
+        .map(|i| [i % x, i / x % y, i / (x * y)])
+        .collect::<Vec<_>>();
+    let vector = |v: [u32; 3]| Value::Composite(v.map(Value::U32).into());
+
+    let mut invocations = Invocations::new(program, resources);
+    for group_z in 0..group_count[2] {
+        for group_y in 0..group_count[1] {
+            for group_x in 0..group_count[0] {
+                let group_id = [group_x, group_y, group_z];
+                let input = |lane: usize, binding: &naga::Binding, _: &Ti| {
+                    let local_id = local_ids[lane];
+                    match *binding {
+                        naga::Binding::BuiltIn(naga::BuiltIn::LocalInvocationId) => {
+                            vector(local_id)
+                        }
+                        naga::Binding::BuiltIn(naga::BuiltIn::LocalInvocationIndex) => {
+                            Value::U32(lane as u32)
+                        }
+                        naga::Binding::BuiltIn(naga::BuiltIn::GlobalInvocationId) => vector(
+                            [0, 1, 2]
+                                .map(|d| group_id[d] * program.workgroup_size[d] + local_id[d]),
+                        ),
+                        naga::Binding::BuiltIn(naga::BuiltIn::WorkGroupId) => vector(group_id),
+                        naga::Binding::BuiltIn(naga::BuiltIn::NumWorkGroups) => vector(group_count),
+                        ref other => unreachable!("compute shader input {other:?}"),
+                    }
+                };
+                // SAFETY: The caller is responsible for ensuring that the resources aren't
+                // accessed concurrently.
+                unsafe { invocations.run(lanes, &[], input) };
+            }
+        }
+    }
+}
@@ -0,0 +1,446 @@
+//! Evaluation of the built-in math functions of shaders.
+//!
+//! Floating-point functions are evaluated in `f64` and rounded to the type of their
+//! arguments, so they are at least as accurate as the WGSL specification requires.
+
+use alloc::{vec, vec::Vec};
+
+use naga::MathFunction as Mf;
+
+use super::{
+    format,
+    value::{self, Value},
+};
+
+/// Evaluates `fun` on its arguments, where `args` has as many values as the function has
+/// arguments.
+pub(super) fn evaluate(fun: Mf, args: &[Value]) -> Value {
+    let arg = &args[0];
+    match fun {
+        Mf::Abs => arg.map(&mut |v| match *v {
+            Value::I32(v) => Value::I32(v.wrapping_abs()),
+            Value::U32(v) => Value::U32(v),
+            Value::AbstractInt(v) => Value::AbstractInt(v.wrapping_abs()),
+            _ => v.with_f64(v.as_f64().abs()),
+        }),
+        Mf::Min => arg.zip(&args[1], &mut min),
+        Mf::Max => arg.zip(&args[1], &mut max),
+        Mf::Clamp => arg.zip3(&args[1], &args[2], &mut |e, low, high| {
+            min(&max(e, low), high)
+        }),
+        Mf::Saturate => float(arg, |x| x.clamp(0.0, 1.0)),
+        Mf::Cos => float(arg, f64::cos),
+        Mf::Cosh => float(arg, f64::cosh),
+        Mf::Sin => float(arg, f64::sin),
+        Mf::Sinh => float(arg, f64::sinh),
+        Mf::Tan => float(arg, f64::tan),
+        Mf::Tanh => float(arg, f64::tanh),
+        Mf::Acos => float(arg, f64::acos),
+        Mf::Asin => float(arg, f64::asin),
+        Mf::Atan => float(arg, f64::atan),
+        Mf::Atan2 => float2(arg, &args[1], f64::atan2),
+        Mf::Asinh => float(arg, f64::asinh),
+        Mf::Acosh => float(arg, f64::acosh),
+        Mf::Atanh => float(arg, f64::atanh),
+        Mf::Radians => float(arg, f64::to_radians),
+        Mf::Degrees => float(arg, f64::to_degrees),
+        Mf::Ceil => float(arg, f64::ceil),
+        Mf::Floor => float(arg, f64::floor),
+        Mf::Round => float(arg, f64::round_ties_even),
+        Mf::Fract => float(arg, |x| x - x.floor()),
+        Mf::Trunc => float(arg, f64::trunc),
+        Mf::Modf => Value::Composite(vec![float(arg, |x| x - x.trunc()), float(arg, f64::trunc)]),
+        Mf::Frexp => Value::Composite(vec![
+            float(arg, |x| frexp(x).0),
+            arg.map(&mut |v| Value::I32(frexp(v.as_f64()).1)),
+        ]),
+        Mf::Ldexp => arg.zip(&args[1], &mut |x, e| {
+            x.with_f64(x.as_f64() * 2f64.powi(e.as_i64().clamp(-1100, 1100) as i32))
+        }),
+        Mf::Exp => float(arg, f64::exp),
+        Mf::Exp2 => float(arg, f64::exp2),
+        Mf::Log => float(arg, f64::ln),
+        Mf::Log2 => float(arg, f64::log2),
+        Mf::Pow => float2(arg, &args[1], f64::powf),
+        Mf::Dot => value::dot(arg, &args[1]),
+        Mf::Dot4I8Packed => {
+            let (a, b) = (arg.as_u32().to_le_bytes(), args[1].as_u32().to_le_bytes());
+            Value::I32(
+                a.iter()
+                    .zip(b)
+                    .map(|(&a, b)| a as i8 as i32 * b as i8 as i32)
+                    .sum(),
+            )
+        }
+        Mf::Dot4U8Packed => {
+            let (a, b) = (arg.as_u32().to_le_bytes(), args[1].as_u32().to_le_bytes());
+            Value::U32(a.iter().zip(b).map(|(&a, b)| a as u32 * b as u32).sum())
+        }
+        Mf::Outer => Value::Composite(
+            args[1]
+                .components()
+                .iter()
+                .map(|b| value::binary(naga::BinaryOperator::Multiply, arg, b))
+                .collect(),
+        ),
+        Mf::Cross => {
+            let [a, b] = [arg, &args[1]].map(floats);
+            let c = [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ];
+            with_floats(arg, &c)
+        }
+        Mf::Distance => {
+            let difference = value::binary(naga::BinaryOperator::Subtract, arg, &args[1]);
+            length(&difference)
+        }
+        Mf::Length => length(arg),
+        Mf::Normalize => {
+            let length = length(arg).as_f64();
+            float(arg, |x| x / length)
+        }
+        Mf::FaceForward => {
+            if value::dot(&args[1], &args[2]).as_f64() < 0.0 {
+                arg.clone()
+            } else {
+                value::unary(naga::UnaryOperator::Negate, arg)
+            }
+        }
+        Mf::Reflect => {
+            let d = value::dot(&args[1], arg).as_f64();
+            arg.zip(&args[1], &mut |e1, e2| {
+                e1.with_f64(e1.as_f64() - 2.0 * d * e2.as_f64())
+            })
+        }
+        Mf::Refract => {
+            let eta = args[2].as_f64();
+            let d = value::dot(&args[1], arg).as_f64();
+            let k = 1.0 - eta * eta * (1.0 - d * d);
+            if k < 0.0 {
+                float(arg, |_| 0.0)
+            } else {
+                arg.zip(&args[1], &mut |e1, e2| {
+                    e1.with_f64(eta * e1.as_f64() - (eta * d + k.sqrt()) * e2.as_f64())
+                })
+            }
+        }
+        Mf::Sign => arg.map(&mut |v| match *v {
+            Value::I32(v) => Value::I32(v.signum()),
+            Value::AbstractInt(v) => Value::AbstractInt(v.signum()),
+            _ => {
+                let x = v.as_f64();
+                v.with_f64(if x == 0.0 { 0.0 } else { x.signum() })
+            }
+        }),
+        Mf::Fma => arg.zip3(&args[1], &args[2], &mut |a, b, c| {
+            a.with_f64(a.as_f64().mul_add(b.as_f64(), c.as_f64()))
+        }),
+        Mf::Mix => arg.zip3(&args[1], &args[2], &mut |a, b, t| {
+            let (a, b, t) = (a.as_f64(), b.as_f64(), t.as_f64());
+            arg_scalar(arg).with_f64(a * (1.0 - t) + b * t)
+        }),
+        Mf::Step => float2(arg, &args[1], |edge, x| if x >= edge { 1.0 } else { 0.0 }),
+        Mf::SmoothStep => arg.zip3(&args[1], &args[2], &mut |low, high, x| {
+            let (low, high, x) = (low.as_f64(), high.as_f64(), x.as_f64());
+            let t = ((x - low) / (high - low)).clamp(0.0, 1.0);
+            arg_scalar(arg).with_f64(t * t * (3.0 - 2.0 * t))
+        }),
+        Mf::Sqrt => float(arg, f64::sqrt),
+        Mf::InverseSqrt => float(arg, |x| 1.0 / x.sqrt()),
+        Mf::Inverse => {
+            let m = matrix(arg);
+            let det = determinant(&m);
+            let n = m.len();
+            let inverse = (0..n)
+                .map(|column| {
+                    (0..n)
+                        .map(|row| {
+                            // The inverse is the transposed cofactor matrix divided by
+                            // the determinant.
+                            let sign = if (row + column) % 2 == 0 { 1.0 } else { -1.0 };
+                            sign * determinant(&minor(&m, column, row)) / det
+                        })
+                        .collect()
+                })
+                .collect::<Vec<Vec<f64>>>();
+            with_matrix(arg, &inverse)
+        }
+        Mf::Transpose => {
+            let m = arg.components();
+            let rows = m[0].components().len();
+            Value::Composite(
+                (0..rows)
+                    .map(|row| {
+                        Value::Composite(m.iter().map(|c| c.components()[row].clone()).collect())
+                    })
+                    .collect(),
+            )
+        }
+        Mf::Determinant => arg_scalar(arg).with_f64(determinant(&matrix(arg))),
+        Mf::QuantizeToF16 => {
+            arg.map(&mut |v| Value::F32(format::f16_to_f32(format::f32_to_f16(v.as_f64() as f32))))
+        }
+        Mf::CountTrailingZeros => integer(arg, |x, bits| x.trailing_zeros().min(bits) as i64),
+        Mf::CountLeadingZeros => integer(arg, |x, bits| (x.leading_zeros() - (32 - bits)) as i64),
+        Mf::CountOneBits => integer(arg, |x, _| x.count_ones() as i64),
+        Mf::ReverseBits => integer(arg, |x, _| x.reverse_bits() as i64),
+        Mf::ExtractBits => {
+            let (offset, count) = (args[1].as_u32(), args[2].as_u32());
+            let offset = offset.min(32);
+            let count = count.min(32 - offset);
+            arg.map(&mut |v| {
+                let bits = mask(count) & v.as_u32().checked_shr(offset).unwrap_or(0);
+                match *v {
+                    // Signed integers are sign-extended from the last extracted bit.
+                    Value::I32(_) if count == 0 => Value::I32(0),
+                    Value::I32(_) => {
+                        let shift = 32 - count;
+                        Value::I32(((bits << shift) as i32) >> shift)
+                    }
+                    _ => Value::U32(bits),
+                }
+            })
+        }
+        Mf::InsertBits => {
+            let (offset, count) = (args[2].as_u32(), args[3].as_u32());
+            let offset = offset.min(32);
+            let count = count.min(32 - offset);
+            let mask = mask(count).checked_shl(offset).unwrap_or(0);
+            arg.zip(&args[1], &mut |e, new_bits| {
+                let new_bits = new_bits.as_u32().checked_shl(offset).unwrap_or(0);
+                let bits = (e.as_u32() & !mask) | (new_bits & mask);
+                match *e {
+                    Value::I32(_) => Value::I32(bits as i32),
+                    _ => Value::U32(bits),
+                }
+            })
+        }
+        Mf::FirstTrailingBit => arg.map(&mut |v| {
+            let x = v.as_u32();
+            let index = if x == 0 { u32::MAX } else { x.trailing_zeros() };
+            match *v {
+                Value::I32(_) => Value::I32(index as i32),
+                _ => Value::U32(index),
+            }
+        }),
+        Mf::FirstLeadingBit => arg.map(&mut |v| match *v {
+            // For negative integers, this is the most significant zero bit.
+            Value::I32(x) => {
+                let x = if x < 0 { !x as u32 } else { x as u32 };
+                Value::I32(if x == 0 {
+                    -1
+                } else {
+                    31 - x.leading_zeros() as i32
+                })
+            }
+            _ => {
+                let x = v.as_u32();
+                Value::U32(if x == 0 {
+                    u32::MAX
+                } else {
+                    31 - x.leading_zeros()
+                })
+            }
+        }),
+        Mf::Pack4x8snorm => Value::U32(pack(arg, 8, |x| {
+            ((x.clamp(-1.0, 1.0) * 127.0).round() as i32) as u32
+        })),
+        Mf::Pack4x8unorm => {
+            Value::U32(pack(arg, 8, |x| (x.clamp(0.0, 1.0) * 255.0).round() as u32))
+        }
+        Mf::Pack2x16snorm => Value::U32(pack(arg, 16, |x| {
+            ((x.clamp(-1.0, 1.0) * 32767.0).round() as i32) as u32
+        })),
+        Mf::Pack2x16unorm => Value::U32(pack(arg, 16, |x| {
+            (x.clamp(0.0, 1.0) * 65535.0).round() as u32
+        })),
+        Mf::Pack2x16float => Value::U32(pack(arg, 16, |x| format::f32_to_f16(x as f32) as u32)),
+        Mf::Pack4xI8 | Mf::Pack4xU8 => Value::U32(
+            arg.components()
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (v.as_u32() & 0xff) << (i * 8))
+                .sum(),
+        ),
+        Mf::Pack4xI8Clamp => Value::U32(
+            arg.components()
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let x = v.as_i64().clamp(-128, 127) as u32;
+                    (x & 0xff) << (i * 8)
+                })
+                .sum(),
+        ),
+        Mf::Pack4xU8Clamp => Value::U32(
+            arg.components()
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (v.as_i64().clamp(0, 255) as u32) << (i * 8))
+                .sum(),
+        ),
+        Mf::Unpack4x8snorm => unpack(arg, 8, |x| (x as u8 as i8 as f64 / 127.0).max(-1.0)),
+        Mf::Unpack4x8unorm => unpack(arg, 8, |x| x as u8 as f64 / 255.0),
+        Mf::Unpack2x16snorm => unpack(arg, 16, |x| (x as u16 as i16 as f64 / 32767.0).max(-1.0)),
+        Mf::Unpack2x16unorm => unpack(arg, 16, |x| x as u16 as f64 / 65535.0),
+        Mf::Unpack2x16float => unpack(arg, 16, |x| format::f16_to_f32(x as u16) as f64),
+        Mf::Unpack4xI8 => {
+            let bytes = arg.as_u32().to_le_bytes();
+            Value::Composite(bytes.iter().map(|&b| Value::I32(b as i8 as i32)).collect())
+        }
+        Mf::Unpack4xU8 => {
+            let bytes = arg.as_u32().to_le_bytes();
+            Value::Composite(bytes.iter().map(|&b| Value::U32(b as u32)).collect())
+        }
+    }
+}
+
+fn min(a: &Value, b: &Value) -> Value {
+    match (a, b) {
+        (&Value::I32(a), &Value::I32(b)) => Value::I32(a.min(b)),
+        (&Value::U32(a), &Value::U32(b)) => Value::U32(a.min(b)),
+        (&Value::AbstractInt(a), &Value::AbstractInt(b)) => Value::AbstractInt(a.min(b)),
+        _ => a.with_f64(a.as_f64().min(b.as_f64())),
+    }
+}
+
+fn max(a: &Value, b: &Value) -> Value {
+    match (a, b) {
+        (&Value::I32(a), &Value::I32(b)) => Value::I32(a.max(b)),
+        (&Value::U32(a), &Value::U32(b)) => Value::U32(a.max(b)),
+        (&Value::AbstractInt(a), &Value::AbstractInt(b)) => Value::AbstractInt(a.max(b)),
+        _ => a.with_f64(a.as_f64().max(b.as_f64())),
+    }
+}
+
+/// Applies `f` to each float of a scalar or vector.
+fn float(value: &Value, f: impl Fn(f64) -> f64) -> Value {
+    value.map(&mut |v| v.with_f64(f(v.as_f64())))
+}
+
+/// Applies `f` to each pair of floats of two scalars or vectors.
+fn float2(a: &Value, b: &Value, f: impl Fn(f64, f64) -> f64) -> Value {
+    a.zip(b, &mut |a, b| a.with_f64(f(a.as_f64(), b.as_f64())))
+}
+
+/// Applies `f` to each integer of a scalar or vector, with the bit width of the integers.
+fn integer(value: &Value, f: impl Fn(u32, u32) -> i64) -> Value {
+    value.map(&mut |v| match *v {
+        Value::I32(x) => Value::I32(f(x as u32, 32) as i32),
+        Value::U32(x) => Value::U32(f(x, 32) as u32),
+        _ => unreachable!("expected an integer, got {v:?}"),
+    })
+}
+
+/// Returns the first scalar of a scalar, vector or matrix, to convert results to its type.
+fn arg_scalar(value: &Value) -> &Value {
+    match *value {
+        Value::Composite(ref components) => arg_scalar(&components[0]),
+        ref scalar => scalar,
+    }
+}
+
+fn floats(value: &Value) -> Vec<f64> {
+    value.components().iter().map(Value::as_f64).collect()
+}
+
+fn with_floats(like: &Value, floats: &[f64]) -> Value {
+    let scalar = arg_scalar(like);
+    Value::Composite(floats.iter().map(|&x| scalar.with_f64(x)).collect())
+}
+
+fn length(value: &Value) -> Value {
+    let sum = value
+        .components()
+        .iter()
+        .map(|v| v.as_f64() * v.as_f64())
+        .sum::<f64>();
+    arg_scalar(value).with_f64(sum.sqrt())
+}
+
+/// Returns the columns of a square matrix.
+fn matrix(value: &Value) -> Vec<Vec<f64>> {
+    value.components().iter().map(floats).collect()
+}
+
+fn with_matrix(like: &Value, columns: &[Vec<f64>]) -> Value {
+    Value::Composite(
+        columns
+            .iter()
+            .map(|column| with_floats(like, column))
+            .collect(),
+    )
+}
+
+/// Returns the matrix without a row and a column.
+fn minor(m: &[Vec<f64>], row: usize, column: usize) -> Vec<Vec<f64>> {
+    m.iter()
+        .enumerate()
+        .filter(|&(c, _)| c != column)
+        .map(|(_, col)| {
+            col.iter()
+                .enumerate()
+                .filter(|&(r, _)| r != row)
+                .map(|(_, &x)| x)
+                .collect()
+        })
+        .collect()
+}
+
+fn determinant(m: &[Vec<f64>]) -> f64 {
+    match m.len() {
+        1 => m[0][0],
+        2 => m[0][0] * m[1][1] - m[1][0] * m[0][1],
+        n => (0..n)
+            .map(|column| {
+                let sign = if column % 2 == 0 { 1.0 } else { -1.0 };
+                sign * m[column][0] * determinant(&minor(m, 0, column))
+            })
+            .sum(),
+    }
+}
+
+/// Splits `x` into a fraction in `[0.5, 1)` and a power of two.
+fn frexp(x: f64) -> (f64, i32) {
+    if x == 0.0 || !x.is_finite() {
+        return (x, 0);
+    }
+    let mut exponent = x.abs().log2().floor() as i32 + 1;
+    let mut fraction = x / 2f64.powi(exponent);
+    // Correct rounding errors of the logarithm.
+    if fraction.abs() >= 1.0 {
+        fraction /= 2.0;
+        exponent += 1;
+    } else if fraction.abs() < 0.5 {
+        fraction *= 2.0;
+        exponent -= 1;
+    }
+    (fraction, exponent)
+}
+
+/// Returns a mask of the `count` least significant bits.
+fn mask(count: u32) -> u32 {
+    1u32.checked_shl(count).map_or(u32::MAX, |bit| bit - 1)
+}
+
+/// Packs the components of a vector into a `u32`, with `bits` bits for each.
+fn pack(value: &Value, bits: u32, f: impl Fn(f64) -> u32) -> u32 {
+    value
+        .components()
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (f(v.as_f64()) & mask(bits)) << (i as u32 * bits))
+        .sum()
+}
+
+/// Unpacks a `u32` into a vector of floats, with `bits` bits for each.
+fn unpack(value: &Value, bits: u32, f: impl Fn(u32) -> f64) -> Value {
+    let x = value.as_u32();
+    Value::Composite(
+        (0..32 / bits)
+            .map(|i| Value::F32(f((x >> (i * bits)) & mask(bits)) as f32))
+            .collect(),
+    )
+}
@@ -0,0 +1,666 @@
+/*!
+# CPU API internals.
+
+Software implementation of the HAL, which runs everything on the CPU so that compute and
+simple render workloads can run on machines without a GPU.
+
+## Execution
+
+Command buffers are recorded as lists of commands, which are executed on the calling thread
+by `Queue::submit`. Every submission has therefore finished by the time `submit` returns, and
+fences are signalled right away.
+
+Shaders are not compiled. The [`naga`] IR of the pipeline's entry points is interpreted by
+the [`interpreter`], which executes all invocations of a workgroup, or of a batch of vertices
+or fragments, in lockstep. This makes barriers trivial and gives fragment shaders derivatives,
+as the fragments of each 2x2 quad are executed together.
+
+Render pipelines are executed by the rasterizer in [`raster`], which supports all primitive
+topologies, depth and stencil tests, and blending, but not multisampling.
+
+## Limitations
+
+Only the downlevel default limits are supported, and only the texture formats which are
+listed in [`format`]. Buffers and textures are plain allocations which are accessed by the
+interpreter through raw pointers, so hazards between commands are resolved by executing the
+commands in order.
+*/
+
+use alloc::{string::String, sync::Arc, vec, vec::Vec};
+use core::{ops::Range, sync::atomic::Ordering, time::Duration};
+
+#[cfg(supports_64bit_atomics)]
+use core::sync::atomic::AtomicU64;
+#[cfg(not(supports_64bit_atomics))]
+use portable_atomic::AtomicU64;
+
+mod command;
+mod device;
+mod format;
+mod image;
+mod interpreter;
+mod math;
+mod raster;
+mod value;
+
+/// Buffers are plain allocations, as on the noop backend.
+pub use crate::noop::Buffer;
+pub use command::{CommandBuffer, CommandEncoder};
+
+#[derive(Clone, Debug)]
+pub struct Api;
+
+#[derive(Debug)]
+pub struct Instance;
+
+/// The CPU backend can't present, so surfaces are never created.
+#[derive(Debug)]
+pub struct Surface;
+
+#[derive(Debug)]
+pub struct Adapter;
+
+#[derive(Debug)]
+pub struct Device;
+
+#[derive(Debug)]
+pub struct Queue;
+
+/// Resources which are not supported, or which don't need any state.
+#[derive(Debug)]
+pub struct Resource;
+
+#[derive(Debug)]
+pub struct Fence {
+    value: AtomicU64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Texture {
+    format: wgt::TextureFormat,
+    dimension: wgt::TextureDimension,
+    size: wgt::Extent3d,
+    mip_level_count: u32,
+    /// The offset of each mip level in `planes`, in texels.
+    mip_offsets: Arc<[u64]>,
+    /// The memory of each aspect of the texture, holding the mip levels one after the other.
+    planes: Vec<Plane>,
+}
+
+#[derive(Clone, Debug)]
+struct Plane {
+    aspect: crate::FormatAspects,
+    texel_size: u32,
+    memory: Buffer,
+}
+
+#[derive(Clone, Debug)]
+pub struct TextureView {
+    texture: Texture,
+    format: wgt::TextureFormat,
+    dimension: wgt::TextureViewDimension,
+    aspects: crate::FormatAspects,
+    mip_levels: Range<u32>,
+    array_layers: Range<u32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Sampler {
+    address_modes: [wgt::AddressMode; 3],
+    mag_filter: wgt::FilterMode,
+    min_filter: wgt::FilterMode,
+    mipmap_filter: wgt::FilterMode,
+    lod_clamp: Range<f32>,
+    compare: Option<wgt::CompareFunction>,
+    border_color: Option<wgt::SamplerBorderColor>,
+}
+
+/// The results of the queries of a query set, as one `u64` per query.
+#[derive(Clone, Debug)]
+pub struct QuerySet {
+    results: Buffer,
+}
+
+#[derive(Debug)]
+pub struct BindGroupLayout {
+    entries: Arc<[wgt::BindGroupLayoutEntry]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BindGroup {
+    /// The bound resources, sorted by binding.
+    entries: Arc<[BindGroupEntry]>,
+}
+
+#[derive(Clone, Debug)]
+struct BindGroupEntry {
+    binding: u32,
+    has_dynamic_offset: bool,
+    resource: BoundResource,
+}
+
+#[derive(Clone, Debug)]
+enum BoundResource {
+    Buffer {
+        buffer: Buffer,
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+    },
+    Sampler(Sampler),
+    TextureView(TextureView),
+}
+
+#[derive(Debug)]
+pub struct ShaderModule {
+    naga: Arc<crate::NagaShader>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ComputePipeline {
+    program: Arc<interpreter::Program>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RenderPipeline {
+    inner: Arc<raster::Pipeline>,
+}
+
+type DeviceResult<T> = Result<T, crate::DeviceError>;
+
+impl crate::Api for Api {
+    type Instance = Instance;
+    type Surface = Surface;
+    type Adapter = Adapter;
+    type Device = Device;
+
+    type Queue = Queue;
+    type CommandEncoder = CommandEncoder;
+    type CommandBuffer = CommandBuffer;
+
+    type Buffer = Buffer;
+    type Texture = Texture;
+    type SurfaceTexture = Texture;
+    type TextureView = TextureView;
+    type Sampler = Sampler;
+    type QuerySet = QuerySet;
+    type Fence = Fence;
+    type MemoryHeap = Resource;
+    type AccelerationStructure = Resource;
+    type PipelineCache = Resource;
+
+    type BindGroupLayout = BindGroupLayout;
+    type BindGroup = BindGroup;
+    type PipelineLayout = Resource;
+    type ShaderModule = ShaderModule;
+    type RenderPipeline = RenderPipeline;
+    type ComputePipeline = ComputePipeline;
+    type RayTracingPipeline = Resource;
+}
+
+crate::impl_dyn_resource!(
+    Adapter,
+    BindGroup,
+    BindGroupLayout,
+    CommandBuffer,
+    CommandEncoder,
+    ComputePipeline,
+    Device,
+    Fence,
+    Instance,
+    QuerySet,
+    Queue,
+    RenderPipeline,
+    Resource,
+    Sampler,
+    ShaderModule,
+    Surface,
+    Texture,
+    TextureView
+);
+
+impl crate::DynAccelerationStructure for Resource {}
+impl crate::DynBindGroup for BindGroup {}
+impl crate::DynBindGroupLayout for BindGroupLayout {}
+impl crate::DynCommandBuffer for CommandBuffer {}
+impl crate::DynComputePipeline for ComputePipeline {}
+impl crate::DynFence for Fence {}
+impl crate::DynMemoryHeap for Resource {}
+impl crate::DynPipelineCache for Resource {}
+impl crate::DynPipelineLayout for Resource {}
+impl crate::DynQuerySet for QuerySet {}
+impl crate::DynRayTracingPipeline for Resource {}
+impl crate::DynRenderPipeline for RenderPipeline {}
+impl crate::DynSampler for Sampler {}
+impl crate::DynShaderModule for ShaderModule {}
+impl crate::DynSurfaceTexture for Texture {}
+impl crate::DynTexture for Texture {}
+impl crate::DynTextureView for TextureView {}
+
+impl core::borrow::Borrow<dyn crate::DynTexture> for Texture {
+    fn borrow(&self) -> &dyn crate::DynTexture {
+        self
+    }
+}
+
+impl Texture {
+    fn new(desc: &crate::TextureDescriptor) -> DeviceResult<Self> {
+        let mut mip_offsets = Vec::with_capacity(desc.mip_level_count as usize);
+        let mut texel_count = 0u64;
+        for mip_level in 0..desc.mip_level_count {
+            mip_offsets.push(texel_count);
+            let extent = desc.size.mip_level_size(mip_level, desc.dimension);
+            texel_count +=
+                extent.width as u64 * extent.height as u64 * extent.depth_or_array_layers as u64;
+        }
+
+        let planes = format::planes(desc.format)
+            .iter()
+            .map(|&(aspect, texel_size)| {
+                let memory = Buffer::new(&crate::BufferDescriptor {
+                    label: desc.label,
+                    size: texel_count * texel_size as u64,
+                    usage: wgt::BufferUses::empty(),
+                    memory_flags: crate::MemoryFlags::empty(),
+                })?;
+                Ok(Plane {
+                    aspect,
+                    texel_size,
+                    memory,
+                })
+            })
+            .collect::<DeviceResult<_>>()?;
+
+        Ok(Self {
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            mip_offsets: mip_offsets.into(),
+            planes,
+        })
+    }
+
+    /// Returns the width, height, and depth or array layer count of a mip level.
+    fn mip_extent(&self, mip_level: u32) -> [u32; 3] {
+        let extent = self.size.mip_level_size(mip_level, self.dimension);
+        [extent.width, extent.height, extent.depth_or_array_layers]
+    }
+
+    fn plane(&self, aspect: crate::FormatAspects) -> Option<&Plane> {
+        self.planes
+            .iter()
+            .find(|plane| plane.aspect.intersects(aspect))
+    }
+
+    /// Returns a pointer to `count` consecutive texels of a row of `aspect`, starting at
+    /// `origin`, where `origin[2]` is the depth slice or array layer.
+    ///
+    /// Returns `None` if any of the texels is out of bounds.
+    fn texels_ptr(
+        &self,
+        aspect: crate::FormatAspects,
+        mip_level: u32,
+        origin: [u32; 3],
+        count: u32,
+    ) -> Option<*mut [u8]> {
+        let plane = self.plane(aspect)?;
+        if mip_level >= self.mip_level_count {
+            return None;
+        }
+        let [width, height, depth] = self.mip_extent(mip_level);
+        let [x, y, z] = origin;
+        if x as u64 + count as u64 > width as u64 || y >= height || z >= depth {
+            return None;
+        }
+        let index = self.mip_offsets[mip_level as usize]
+            + (z as u64 * height as u64 + y as u64) * width as u64
+            + x as u64;
+        let texel_size = plane.texel_size as u64;
+        Some(
+            plane
+                .memory
+                .get_slice_ptr(index * texel_size..(index + count as u64) * texel_size),
+        )
+    }
+
+    /// Reads the texel of `aspect` at `coordinates`, interpreting it as `format`.
+    ///
+    /// # Safety
+    ///
+    /// The texel must not be written to concurrently.
+    unsafe fn read(
+        &self,
+        format: wgt::TextureFormat,
+        aspect: crate::FormatAspects,
+        mip_level: u32,
+        coordinates: [u32; 3],
+    ) -> Option<format::Texel> {
+        let ptr = self.texels_ptr(aspect, mip_level, coordinates, 1)?;
+        // SAFETY: The caller is responsible for ensuring this does not alias.
+        Some(format::decode(format, aspect, unsafe { &*ptr }))
+    }
+
+    /// Writes the texel of `aspect` at `coordinates`, encoding it as `format`.
+    ///
+    /// Out of bounds writes are discarded.
+    ///
+    /// # Safety
+    ///
+    /// The texel must not be accessed concurrently.
+    unsafe fn write(
+        &self,
+        format: wgt::TextureFormat,
+        aspect: crate::FormatAspects,
+        mip_level: u32,
+        coordinates: [u32; 3],
+        texel: format::Texel,
+    ) {
+        if let Some(ptr) = self.texels_ptr(aspect, mip_level, coordinates, 1) {
+            // SAFETY: The caller is responsible for ensuring this does not alias.
+            format::encode(format, aspect, texel, unsafe { &mut *ptr });
+        }
+    }
+}
+
+impl TextureView {
+    /// Returns the width, height, and depth or array layer count of a mip level of the view.
+    fn mip_extent(&self, level: u32) -> [u32; 3] {
+        let [width, height, depth] = self.texture.mip_extent(self.mip_levels.start + level);
+        match self.dimension {
+            wgt::TextureViewDimension::D3 => [width, height, depth],
+            _ => [width, height, self.array_layers.len() as u32],
+        }
+    }
+
+    /// Converts a depth slice or array layer of the view to one of its texture.
+    fn texture_coordinates(&self, [x, y, z]: [u32; 3]) -> [u32; 3] {
+        match self.dimension {
+            wgt::TextureViewDimension::D3 => [x, y, z],
+            _ => [x, y, self.array_layers.start + z],
+        }
+    }
+
+    /// Reads the texel of the view at `coordinates`, where `coordinates[2]` is a depth slice
+    /// or array layer of the view.
+    ///
+    /// # Safety
+    ///
+    /// See [`Texture::read`].
+    unsafe fn read(&self, level: u32, coordinates: [u32; 3]) -> Option<format::Texel> {
+        let [width, height, depth] = self.mip_extent(level);
+        let [x, y, z] = coordinates;
+        if level >= self.mip_levels.len() as u32 || x >= width || y >= height || z >= depth {
+            return None;
+        }
+        unsafe {
+            self.texture.read(
+                self.format,
+                self.aspects,
+                self.mip_levels.start + level,
+                self.texture_coordinates(coordinates),
+            )
+        }
+    }
+
+    /// Writes the texel of the view at `coordinates`, see [`Self::read`].
+    ///
+    /// # Safety
+    ///
+    /// See [`Texture::write`].
+    unsafe fn write(&self, level: u32, coordinates: [u32; 3], texel: format::Texel) {
+        let [width, height, depth] = self.mip_extent(level);
+        let [x, y, z] = coordinates;
+        if level >= self.mip_levels.len() as u32 || x >= width || y >= height || z >= depth {
+            return;
+        }
+        unsafe {
+            self.texture.write(
+                self.format,
+                self.aspects,
+                self.mip_levels.start + level,
+                self.texture_coordinates(coordinates),
+                texel,
+            )
+        }
+    }
+}
+
+impl BindGroup {
+    /// Returns the bound resources with the dynamic offsets added to their offsets.
+    fn with_dynamic_offsets(&self, dynamic_offsets: &[wgt::DynamicOffset]) -> Self {
+        if dynamic_offsets.is_empty() {
+            return self.clone();
+        }
+        let mut dynamic_offsets = dynamic_offsets.iter();
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut entry = entry.clone();
+                if let BoundResource::Buffer { ref mut offset, .. } = entry.resource {
+                    if entry.has_dynamic_offset {
+                        *offset += *dynamic_offsets.next().unwrap() as wgt::BufferAddress;
+                    }
+                }
+                entry
+            })
+            .collect();
+        Self { entries }
+    }
+
+    fn get(&self, binding: u32) -> Option<&BoundResource> {
+        self.entries
+            .binary_search_by_key(&binding, |entry| entry.binding)
+            .ok()
+            .map(|index| &self.entries[index].resource)
+    }
+}
+
+impl crate::Instance for Instance {
+    type A = Api;
+
+    unsafe fn init(_desc: &crate::InstanceDescriptor) -> Result<Self, crate::InstanceError> {
+        Ok(Instance)
+    }
+
+    unsafe fn create_surface(
+        &self,
+        _display_handle: raw_window_handle::RawDisplayHandle,
+        _window_handle: raw_window_handle::RawWindowHandle,
+    ) -> Result<Surface, crate::InstanceError> {
+        Err(crate::InstanceError::new(String::from(
+            "the CPU backend can't present to surfaces",
+        )))
+    }
+
+    unsafe fn enumerate_adapters(
+        &self,
+        _surface_hint: Option<&Surface>,
+    ) -> Vec<crate::ExposedAdapter<Api>> {
+        vec![crate::ExposedAdapter {
+            adapter: Adapter,
+            info: wgt::AdapterInfo {
+                name: String::from("wgpu CPU rasterizer"),
+                vendor: 0,
+                device: 0,
+                device_type: wgt::DeviceType::Cpu,
+                driver: String::from("wgpu"),
+                driver_info: String::new(),
+                backend: wgt::Backend::Cpu,
+            },
+            features: wgt::Features::DEPTH32FLOAT_STENCIL8
+                | wgt::Features::INDIRECT_FIRST_INSTANCE
+                | wgt::Features::FLOAT32_FILTERABLE,
+            capabilities: capabilities(),
+            toggles: wgt::Toggles::empty(),
+        }]
+    }
+}
+
+fn capabilities() -> crate::Capabilities {
+    crate::Capabilities {
+        limits: wgt::Limits::downlevel_defaults(),
+        alignments: crate::Alignments {
+            buffer_copy_offset: wgt::BufferSize::MIN,
+            buffer_copy_pitch: wgt::BufferSize::MIN,
+            uniform_bounds_check_alignment: wgt::BufferSize::MIN,
+            raw_tlas_instance_size: 0,
+            ray_tracing_scratch_buffer_alignment: 1,
+            ray_tracing_shader_group_handle_size: 32,
+            ray_tracing_shader_group_handle_alignment: 32,
+            ray_tracing_shader_group_base_alignment: 64,
+        },
+        downlevel: wgt::DownlevelCapabilities {
+            // Multisampling, anisotropic filtering and the compressed texture formats
+            // are not implemented.
+            flags: wgt::DownlevelFlags::all()
+                - wgt::DownlevelFlags::MULTISAMPLED_SHADING
+                - wgt::DownlevelFlags::ANISOTROPIC_FILTERING
+                - wgt::DownlevelFlags::WEBGPU_TEXTURE_FORMAT_SUPPORT
+                - wgt::DownlevelFlags::SURFACE_VIEW_FORMATS
+                - wgt::DownlevelFlags::UNRESTRICTED_EXTERNAL_TEXTURE_COPIES,
+            limits: wgt::DownlevelLimits {},
+            shader_model: wgt::ShaderModel::Sm5,
+        },
+    }
+}
+
+impl crate::Surface for Surface {
+    type A = Api;
+
+    unsafe fn configure(
+        &self,
+        _device: &Device,
+        _config: &crate::SurfaceConfiguration,
+    ) -> Result<(), crate::SurfaceError> {
+        Err(crate::SurfaceError::Other("the CPU backend can't present"))
+    }
+
+    unsafe fn unconfigure(&self, _device: &Device) {}
+
+    unsafe fn acquire_texture(
+        &self,
+        _timeout: Option<Duration>,
+        _fence: &Fence,
+    ) -> Result<Option<crate::AcquiredSurfaceTexture<Api>>, crate::SurfaceError> {
+        Ok(None)
+    }
+    unsafe fn discard_texture(&self, _texture: Texture) {}
+
+    unsafe fn set_target_present_time(&self, _time: wgt::PresentationTimestamp) {}
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        Ok(Vec::new())
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        wgt::PresentationPath::Unknown
+    }
+}
+
+impl crate::Adapter for Adapter {
+    type A = Api;
+
+    unsafe fn open(
+        &self,
+        _features: wgt::Features,
+        _limits: &wgt::Limits,
+        _memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
+    ) -> DeviceResult<crate::OpenDevice<Api>> {
+        Ok(crate::OpenDevice {
+            device: Device,
+            queue: Queue,
+            additional_queues: additional_queues.iter().map(|_| Queue).collect(),
+        })
+    }
+
+    unsafe fn texture_format_capabilities(
+        &self,
+        format: wgt::TextureFormat,
+    ) -> crate::TextureFormatCapabilities {
+        format::capabilities(format)
+    }
+
+    unsafe fn surface_capabilities(
+        &self,
+        _surface: &Surface,
+    ) -> Option<crate::SurfaceCapabilities> {
+        None
+    }
+
+    unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
+        wgt::PresentationTimestamp::INVALID_TIMESTAMP
+    }
+
+    fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        None
+    }
+}
+
+impl crate::Queue for Queue {
+    type A = Api;
+
+    unsafe fn submit(
+        &self,
+        command_buffers: &[&CommandBuffer],
+        _surface_textures: &[&Texture],
+        (fence, fence_value): (&mut Fence, crate::FenceValue),
+    ) -> DeviceResult<()> {
+        // All commands are executed synchronously.
+        for cb in command_buffers {
+            // SAFETY: Caller is responsible for ensuring synchronization between commands and
+            // other mutations.
+            unsafe {
+                cb.execute();
+            }
+        }
+        fence.value.store(fence_value, Ordering::Release);
+        Ok(())
+    }
+
+    unsafe fn present(
+        &self,
+        _surface: &Surface,
+        _texture: Texture,
+        _damage: &[wgt::Rect],
+    ) -> Result<(), crate::SurfaceError> {
+        Err(crate::SurfaceError::Other("the CPU backend can't present"))
+    }
+
+    unsafe fn get_timestamp_period(&self) -> f32 {
+        1.0
+    }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        _buffer_bindings: &[crate::SparseBufferBinding<Buffer, Resource>],
+        _texture_bindings: &[crate::SparseTextureBinding<Texture, Resource>],
+    ) -> DeviceResult<()> {
+        // Sparse resources are not supported.
+        Err(crate::DeviceError::Unexpected)
+    }
+
+    unsafe fn wait_for_fence(&self, _fence: &Fence, _value: crate::FenceValue) -> DeviceResult<()> {
+        // All commands are executed synchronously, so the fence has already been signalled.
+        Ok(())
+    }
+
+    unsafe fn signal_fence(&self, fence: &Fence, value: crate::FenceValue) -> DeviceResult<()> {
+        fence.value.fetch_max(value, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Panics on a shader construct which requires a feature the CPU backend doesn't expose,
+/// and which therefore can't pass validation.
+#[cold]
+fn unsupported(what: &str) -> ! {
+    crate::hal_internal_error(format_args!("{what} is not supported by the CPU backend"))
+}
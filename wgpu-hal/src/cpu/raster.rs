@@ -0,0 +1,1095 @@
+//! Execution of render pipelines.
+//!
+//! Vertices are shaded in batches, assembled into primitives which are clipped against
+//! the near and far planes, and rasterized one primitive after the other. The fragments of
+//! each primitive are shaded by 2x2 quads, then tested and written to the attachments in
+//! order, so that blending is applied in primitive order.
+
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use naga::TypeInner as Ti;
+
+use super::{
+    Buffer, TextureView,
+    format::{self, Texel},
+    image,
+    interpreter::{Invocations, Program, Resources},
+    value::Value,
+};
+
+/// The maximum number of invocations executed in lockstep, which is a multiple of 4 so that
+/// batches of fragments are made of whole quads.
+const BATCH_SIZE: usize = 64;
+
+#[derive(Debug)]
+pub(super) struct VertexBufferLayout {
+    array_stride: wgt::BufferAddress,
+    step_mode: wgt::VertexStepMode,
+    attributes: Vec<wgt::VertexAttribute>,
+}
+
+/// A user-defined output of the vertex shader, interpolated for the fragment shader.
+#[derive(Debug)]
+struct Varying {
+    location: u32,
+    /// The offset of the varying in [`Vertex::varyings`].
+    offset: usize,
+    kind: naga::ScalarKind,
+    count: usize,
+    interpolation: naga::Interpolation,
+}
+
+#[derive(Debug)]
+pub(super) struct Pipeline {
+    vertex: Program,
+    fragment: Option<Program>,
+    vertex_buffers: Vec<VertexBufferLayout>,
+    varyings: Vec<Varying>,
+    primitive: wgt::PrimitiveState,
+    depth_stencil: Option<wgt::DepthStencilState>,
+    color_targets: Vec<Option<wgt::ColorTargetState>>,
+}
+
+/// The state of a render pass used by draws.
+pub(super) struct DrawState<'a> {
+    pub resources: Resources<'a>,
+    pub vertex_buffers: &'a [Option<(Buffer, wgt::BufferAddress)>],
+    pub index_buffer: Option<&'a (Buffer, wgt::BufferAddress, wgt::IndexFormat)>,
+    pub viewport: &'a crate::Rect<f32>,
+    pub depth_range: Range<f32>,
+    pub scissor: &'a crate::Rect<u32>,
+    pub stencil_reference: u32,
+    pub blend_constant: [f32; 4],
+    /// The color attachments, with their depth slice.
+    pub color_attachments: &'a [Option<(TextureView, u32)>],
+    pub depth_stencil_attachment: Option<&'a TextureView>,
+    /// The size of the attachments.
+    pub extent: wgt::Extent3d,
+}
+
+/// The parameters of a draw call.
+#[derive(Clone, Debug)]
+pub(super) struct Draw {
+    /// The vertices of the draw, or the indices in the index buffer for indexed draws.
+    pub vertices: Range<u32>,
+    pub instances: Range<u32>,
+    pub base_vertex: i32,
+    pub indexed: bool,
+}
+
+/// A shaded vertex.
+#[derive(Clone, Debug)]
+struct Vertex {
+    /// The position in clip space.
+    position: [f32; 4],
+    /// The components of the varyings, where integers are stored as bits.
+    varyings: Vec<f32>,
+}
+
+/// A vertex after the viewport transform.
+#[derive(Clone, Debug)]
+struct ScreenVertex {
+    x: f32,
+    y: f32,
+    z: f32,
+    /// The reciprocal of the clip space `w`.
+    w_inv: f32,
+    varyings: Vec<f32>,
+}
+
+/// A fragment of a quad, which is only executed for derivatives if it isn't covered.
+#[derive(Clone, Debug)]
+struct Fragment {
+    x: u32,
+    y: u32,
+    depth: f32,
+    w_inv: f32,
+    varyings: Vec<f32>,
+    covered: bool,
+}
+
+impl Pipeline {
+    pub fn new(
+        desc: &crate::RenderPipelineDescriptor<
+            super::Resource,
+            super::ShaderModule,
+            super::Resource,
+        >,
+    ) -> Result<Self, crate::PipelineError> {
+        let vertex = Program::new(&desc.vertex_stage, naga::ShaderStage::Vertex)?;
+        let fragment = desc
+            .fragment_stage
+            .as_ref()
+            .map(|stage| Program::new(stage, naga::ShaderStage::Fragment))
+            .transpose()?;
+
+        let mut varyings = Vec::new();
+        let mut offset = 0;
+        for (binding, inner) in output_types(&vertex) {
+            let naga::Binding::Location {
+                location,
+                interpolation,
+                ..
+            } = *binding
+            else {
+                continue;
+            };
+            let (scalar, count) = match *inner {
+                Ti::Scalar(scalar) => (scalar, 1),
+                Ti::Vector { size, scalar } => (scalar, size as usize),
+                _ => unreachable!("vertex output of type {inner:?}"),
+            };
+            varyings.push(Varying {
+                location,
+                offset,
+                kind: scalar.kind,
+                count,
+                interpolation: interpolation.unwrap_or(naga::Interpolation::Perspective),
+            });
+            offset += count;
+        }
+
+        Ok(Self {
+            vertex,
+            fragment,
+            vertex_buffers: desc
+                .vertex_buffers
+                .iter()
+                .map(|layout| VertexBufferLayout {
+                    array_stride: layout.array_stride,
+                    step_mode: layout.step_mode,
+                    attributes: layout.attributes.to_vec(),
+                })
+                .collect(),
+            varyings,
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            color_targets: desc.color_targets.to_vec(),
+        })
+    }
+
+    /// Executes a draw call, returning the number of samples which passed the depth and
+    /// stencil tests, for occlusion queries.
+    ///
+    /// # Safety
+    ///
+    /// The bound buffers and textures, and the attachments, must not be accessed
+    /// concurrently.
+    pub unsafe fn draw(&self, state: &DrawState<'_>, draw: &Draw) -> u64 {
+        let topology = self.primitive.topology;
+        let restart = self.primitive.primitive_restart && draw.indexed && topology.is_strip();
+        let mut passed = 0;
+
+        for instance in draw.instances.clone() {
+            // The vertex index of each vertex of the draw, or `None` for primitive restarts.
+            let indices = draw
+                .vertices
+                .clone()
+                .map(|i| {
+                    if !draw.indexed {
+                        return Some(i);
+                    }
+                    let (ref buffer, offset, format) = *state.index_buffer.unwrap();
+                    let size = format.byte_size() as u64;
+                    let start = offset + i as u64 * size;
+                    let index = if start + size <= buffer.size() {
+                        // SAFETY: The caller is responsible for ensuring that the index buffer
+                        // isn't written to concurrently.
+                        let bytes = unsafe { &*buffer.get_slice_ptr(start..start + size) };
+                        let mut word = [0; 4];
+                        word[..bytes.len()].copy_from_slice(bytes);
+                        u32::from_le_bytes(word)
+                    } else {
+                        0
+                    };
+                    if restart && index == format.primitive_restart_index() {
+                        return None;
+                    }
+                    Some(index.wrapping_add_signed(draw.base_vertex))
+                })
+                .collect::<Vec<_>>();
+
+            // SAFETY: The caller is responsible for ensuring that the resources aren't
+            // accessed concurrently.
+            let vertices = unsafe { self.shade_vertices(state, &indices, instance) };
+
+            for primitive in assemble(topology, &vertices) {
+                // SAFETY: As above.
+                passed += unsafe { self.rasterize(state, &primitive) };
+            }
+        }
+        passed
+    }
+
+    unsafe fn shade_vertices(
+        &self,
+        state: &DrawState<'_>,
+        indices: &[Option<u32>],
+        instance: u32,
+    ) -> Vec<Option<Vertex>> {
+        let shaded = indices.iter().flatten().copied().collect::<Vec<_>>();
+        let mut vertices = Vec::with_capacity(shaded.len());
+        let mut invocations = Invocations::new(&self.vertex, state.resources);
+        for batch in shaded.chunks(BATCH_SIZE) {
+            let input = |lane: usize, binding: &naga::Binding, inner: &Ti| match *binding {
+                naga::Binding::BuiltIn(naga::BuiltIn::VertexIndex) => Value::U32(batch[lane]),
+                naga::Binding::BuiltIn(naga::BuiltIn::InstanceIndex) => Value::U32(instance),
+                naga::Binding::Location { location, .. } => {
+                    // SAFETY: The caller is responsible for ensuring that the vertex buffers
+                    // aren't written to concurrently.
+                    unsafe { self.fetch(state, location, batch[lane], instance, inner) }
+                }
+                ref other => unreachable!("vertex shader input {other:?}"),
+            };
+            // SAFETY: The caller is responsible for ensuring that the resources aren't
+            // accessed concurrently.
+            let results = unsafe { invocations.run(batch.len(), &[], input) };
+            vertices.extend(
+                results
+                    .into_iter()
+                    .map(|result| self.vertex_outputs(result.unwrap())),
+            );
+        }
+
+        let mut vertices = vertices.into_iter();
+        indices
+            .iter()
+            .map(|index| index.and_then(|_| vertices.next()))
+            .collect()
+    }
+
+    /// Reads the vertex attribute at `location`.
+    unsafe fn fetch(
+        &self,
+        state: &DrawState<'_>,
+        location: u32,
+        vertex: u32,
+        instance: u32,
+        inner: &Ti,
+    ) -> Value {
+        let (slot, layout, attribute) = self
+            .vertex_buffers
+            .iter()
+            .enumerate()
+            .find_map(|(slot, layout)| {
+                let attribute = layout
+                    .attributes
+                    .iter()
+                    .find(|attribute| attribute.shader_location == location)?;
+                Some((slot, layout, attribute))
+            })
+            .unwrap();
+        let index = match layout.step_mode {
+            wgt::VertexStepMode::Vertex => vertex,
+            // Step rates other than 1 need `VERTEX_INSTANCE_STEP_RATE`, which isn't exposed.
+            wgt::VertexStepMode::Instance | wgt::VertexStepMode::InstanceStepRate { .. } => {
+                instance
+            }
+        } as u64;
+
+        // Attributes out of the bounds of their buffer are zero.
+        let zero = [0; 16];
+        let texel = match state.vertex_buffers.get(slot) {
+            Some(&Some((ref buffer, offset))) => {
+                let start = offset + index * layout.array_stride + attribute.offset;
+                let end = start + attribute.format.size();
+                if end <= buffer.size() {
+                    // SAFETY: The caller is responsible for ensuring that the buffer isn't
+                    // written to concurrently.
+                    format::decode_vertex(attribute.format, unsafe {
+                        &*buffer.get_slice_ptr(start..end)
+                    })
+                } else {
+                    format::decode_vertex(attribute.format, &zero)
+                }
+            }
+            _ => format::decode_vertex(attribute.format, &zero),
+        };
+
+        let (count, kind) = match *inner {
+            Ti::Scalar(scalar) => (None, scalar.kind),
+            Ti::Vector { size, scalar } => (Some(size as usize), scalar.kind),
+            _ => unreachable!("vertex input of type {inner:?}"),
+        };
+        let components = texel_components(texel, kind);
+        match count {
+            Some(count) => Value::Composite(components[..count].to_vec()),
+            None => components[0].clone(),
+        }
+    }
+
+    fn vertex_outputs(&self, result: Value) -> Vertex {
+        let mut vertex = Vertex {
+            position: [0.0; 4],
+            varyings: vec![0.0; self.varyings.iter().map(|v| v.count).sum()],
+        };
+        for (binding, value) in self.vertex.outputs(result) {
+            match *binding {
+                naga::Binding::BuiltIn(naga::BuiltIn::Position { .. }) => {
+                    let position = value.to_f32s();
+                    vertex.position.copy_from_slice(&position);
+                }
+                naga::Binding::Location { location, .. } => {
+                    let varying = self.varying(location).unwrap();
+                    let components = &mut vertex.varyings[varying.offset..][..varying.count];
+                    for (c, value) in components.iter_mut().zip(value.components()) {
+                        *c = match *value {
+                            Value::I32(v) => f32::from_bits(v as u32),
+                            Value::U32(v) => f32::from_bits(v),
+                            _ => value.as_f64() as f32,
+                        };
+                    }
+                }
+                _ => {}
+            }
+        }
+        vertex
+    }
+
+    fn varying(&self, location: u32) -> Option<&Varying> {
+        self.varyings
+            .iter()
+            .find(|varying| varying.location == location)
+    }
+
+    /// Clips, rasterizes and shades a primitive, returning the number of samples which
+    /// passed the depth and stencil tests.
+    unsafe fn rasterize(&self, state: &DrawState<'_>, primitive: &[&Vertex]) -> u64 {
+        let unclipped = self.primitive.unclipped_depth;
+        let polygon = clip(primitive, unclipped, &self.varyings);
+        if polygon.is_empty() {
+            return 0;
+        }
+        let provoking = &primitive[0].varyings;
+        let screen = polygon
+            .iter()
+            .map(|vertex| {
+                let [x, y, z, w] = vertex.position;
+                let w_inv = 1.0 / w;
+                let viewport = state.viewport;
+                ScreenVertex {
+                    x: viewport.x + (x * w_inv + 1.0) / 2.0 * viewport.w,
+                    y: viewport.y + (1.0 - y * w_inv) / 2.0 * viewport.h,
+                    z: state.depth_range.start
+                        + z * w_inv * (state.depth_range.end - state.depth_range.start),
+                    w_inv,
+                    varyings: vertex.varyings.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let bounds = self.bounds(state);
+
+        let mut passed = 0;
+        match primitive.len() {
+            1 => {
+                let fragment = point(&screen[0], bounds.clone());
+                // SAFETY: The caller is responsible for ensuring that the resources aren't
+                // accessed concurrently.
+                passed += unsafe {
+                    self.shade_fragments(state, fragment.as_slice(), true, provoking, 0.0)
+                };
+            }
+            2 => {
+                let quads = line(&screen[0], &screen[1], bounds);
+                // SAFETY: As above.
+                passed += unsafe { self.shade_fragments(state, &quads, true, provoking, 0.0) };
+            }
+            _ => {
+                for i in 1..screen.len() - 1 {
+                    let triangle = [&screen[0], &screen[i], &screen[i + 1]];
+                    let Some((quads, front_facing, slope)) =
+                        self.triangle(triangle, bounds.clone())
+                    else {
+                        continue;
+                    };
+                    // SAFETY: As above.
+                    passed += unsafe {
+                        self.shade_fragments(state, &quads, front_facing, provoking, slope)
+                    };
+                }
+            }
+        }
+        passed
+    }
+
+    /// Returns the pixels which may be covered by primitives, inside of the scissor rect,
+    /// the viewport and the attachments.
+    fn bounds(&self, state: &DrawState<'_>) -> [Range<u32>; 2] {
+        let viewport = state.viewport;
+        let scissor = state.scissor;
+        let clamp = |start: f32, end: f32, scissor: Range<u32>, extent: u32| {
+            let start = (start.max(0.0) as u32).max(scissor.start);
+            let end = (end.ceil().max(0.0) as u32).min(scissor.end).min(extent);
+            start..end.max(start)
+        };
+        [
+            clamp(
+                viewport.x,
+                viewport.x + viewport.w,
+                scissor.x..scissor.x + scissor.w,
+                state.extent.width,
+            ),
+            clamp(
+                viewport.y,
+                viewport.y + viewport.h,
+                scissor.y..scissor.y + scissor.h,
+                state.extent.height,
+            ),
+        ]
+    }
+
+    /// Returns the quads of the fragments of a triangle, whether it is front-facing, and its
+    /// maximum depth slope, or `None` if it is culled.
+    fn triangle(
+        &self,
+        [a, b, c]: [&ScreenVertex; 3],
+        [x_bounds, y_bounds]: [Range<u32>; 2],
+    ) -> Option<(Vec<[Fragment; 4]>, bool, f32)> {
+        let area = edge(a, b, c.x, c.y);
+        if area == 0.0 || !area.is_finite() {
+            return None;
+        }
+        // The y axis points down in framebuffer coordinates, so the winding is reversed.
+        let counter_clockwise = area < 0.0;
+        let front_facing = (self.primitive.front_face == wgt::FrontFace::Ccw) == counter_clockwise;
+        match self.primitive.cull_mode {
+            Some(wgt::Face::Front) if front_facing => return None,
+            Some(wgt::Face::Back) if !front_facing => return None,
+            _ => {}
+        }
+        let (a, b, c, area) = if area < 0.0 {
+            (a, c, b, -area)
+        } else {
+            (a, b, c, area)
+        };
+        let vertices = [a, b, c];
+
+        // The derivatives of the barycentric coordinates of the vertices.
+        let gradients =
+            [(b, c), (c, a), (a, b)].map(|(j, k)| ((j.y - k.y) / area, (k.x - j.x) / area));
+        let slope = {
+            let dz = |axis: fn((f32, f32)) -> f32| {
+                vertices
+                    .iter()
+                    .zip(gradients)
+                    .map(|(v, g)| v.z * axis(g))
+                    .sum::<f32>()
+                    .abs()
+            };
+            dz(|g| g.0).max(dz(|g| g.1))
+        };
+
+        let min = |f: fn(&ScreenVertex) -> f32| {
+            vertices.iter().map(|v| f(v)).fold(f32::INFINITY, f32::min)
+        };
+        let max = |f: fn(&ScreenVertex) -> f32| {
+            vertices
+                .iter()
+                .map(|v| f(v))
+                .fold(f32::NEG_INFINITY, f32::max)
+        };
+        let x_start = (min(|v| v.x).max(0.0) as u32).max(x_bounds.start) & !1;
+        let x_end = (max(|v| v.x).ceil().max(0.0) as u32).min(x_bounds.end);
+        let y_start = (min(|v| v.y).max(0.0) as u32).max(y_bounds.start) & !1;
+        let y_end = (max(|v| v.y).ceil().max(0.0) as u32).min(y_bounds.end);
+
+        let mut quads = Vec::new();
+        for y in (y_start..y_end).step_by(2) {
+            for x in (x_start..x_end).step_by(2) {
+                let quad: [Fragment; 4] = core::array::from_fn(|lane| {
+                    let (x, y) = (x + (lane as u32 & 1), y + (lane as u32 >> 1));
+                    let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                    let weights = [edge(b, c, px, py), edge(c, a, px, py), edge(a, b, px, py)];
+                    let covered = x_bounds.contains(&x)
+                        && y_bounds.contains(&y)
+                        && [(b, c), (c, a), (a, b)].iter().zip(weights).all(
+                            |(&(from, to), weight)| {
+                                weight > 0.0 || (weight == 0.0 && is_top_left(from, to))
+                            },
+                        );
+                    interpolate(
+                        &vertices,
+                        weights.map(|w| w / area),
+                        x,
+                        y,
+                        covered,
+                        &self.varyings,
+                    )
+                });
+                if quad.iter().any(|fragment| fragment.covered) {
+                    quads.push(quad);
+                }
+            }
+        }
+        Some((quads, front_facing, slope))
+    }
+
+    /// Shades the quads of a primitive, and writes the fragments which pass the tests to the
+    /// attachments, returning their number.
+    unsafe fn shade_fragments(
+        &self,
+        state: &DrawState<'_>,
+        quads: &[[Fragment; 4]],
+        front_facing: bool,
+        provoking: &[f32],
+        slope: f32,
+    ) -> u64 {
+        let mut passed = 0;
+        for batch in quads.chunks(BATCH_SIZE / 4) {
+            let fragments = batch.iter().flatten().collect::<Vec<_>>();
+            let helpers = fragments
+                .iter()
+                .map(|fragment| !fragment.covered)
+                .collect::<Vec<_>>();
+            let input = |lane: usize, binding: &naga::Binding, inner: &Ti| {
+                let fragment = fragments[lane];
+                match *binding {
+                    naga::Binding::BuiltIn(naga::BuiltIn::Position { .. }) => Value::Composite(
+                        [
+                            fragment.x as f32 + 0.5,
+                            fragment.y as f32 + 0.5,
+                            fragment.depth,
+                            fragment.w_inv,
+                        ]
+                        .map(Value::F32)
+                        .into(),
+                    ),
+                    naga::Binding::BuiltIn(naga::BuiltIn::FrontFacing) => Value::Bool(front_facing),
+                    naga::Binding::BuiltIn(naga::BuiltIn::SampleIndex) => Value::U32(0),
+                    naga::Binding::BuiltIn(naga::BuiltIn::SampleMask) => Value::U32(1),
+                    naga::Binding::Location { location, .. } => {
+                        let varying = self.varying(location).unwrap();
+                        let components = match varying.interpolation {
+                            naga::Interpolation::Flat => provoking,
+                            _ => &fragment.varyings[..],
+                        };
+                        varying_value(&components[varying.offset..][..varying.count], inner)
+                    }
+                    ref other => unreachable!("fragment shader input {other:?}"),
+                }
+            };
+            let results = match self.fragment {
+                Some(ref program) => {
+                    let mut invocations = Invocations::new(program, state.resources);
+                    // SAFETY: The caller is responsible for ensuring that the resources
+                    // aren't accessed concurrently.
+                    unsafe { invocations.run(fragments.len(), &helpers, input) }
+                }
+                // Without fragment shader, only depth and stencil are written.
+                None => vec![Some(Value::Composite(Vec::new())); fragments.len()],
+            };
+
+            for (fragment, result) in fragments.iter().zip(results) {
+                let Some(result) = result else {
+                    continue;
+                };
+                if !fragment.covered {
+                    continue;
+                }
+                let mut depth = fragment.depth;
+                let mut colors = Vec::new();
+                let outputs = match self.fragment {
+                    Some(ref program) => program.outputs(result),
+                    None => Vec::new(),
+                };
+                for (binding, value) in outputs {
+                    match *binding {
+                        naga::Binding::BuiltIn(naga::BuiltIn::FragDepth) => {
+                            depth = value.as_f64() as f32;
+                        }
+                        naga::Binding::BuiltIn(naga::BuiltIn::SampleMask)
+                            if value.as_u32() & 1 == 0 =>
+                        {
+                            depth = f32::NAN;
+                        }
+                        naga::Binding::Location { location, .. } => {
+                            colors.push((location, value));
+                        }
+                        _ => {}
+                    }
+                }
+                if depth.is_nan() {
+                    continue;
+                }
+                // SAFETY: The caller is responsible for ensuring that the attachments aren't
+                // accessed concurrently.
+                if unsafe { self.depth_stencil_test(state, fragment, depth, front_facing, slope) } {
+                    passed += 1;
+                    for (location, value) in colors {
+                        // SAFETY: As above.
+                        unsafe { self.write_color(state, fragment, location, &value) };
+                    }
+                }
+            }
+        }
+        passed
+    }
+
+    /// Executes the depth and stencil tests and writes of a fragment, returning whether it
+    /// passed them.
+    unsafe fn depth_stencil_test(
+        &self,
+        state: &DrawState<'_>,
+        fragment: &Fragment,
+        depth: f32,
+        front_facing: bool,
+        slope: f32,
+    ) -> bool {
+        let (Some(ds), Some(view)) = (&self.depth_stencil, state.depth_stencil_attachment) else {
+            return true;
+        };
+        let texture = &view.texture;
+        let level = view.mip_levels.start;
+        let coordinates = view.texture_coordinates([fragment.x, fragment.y, 0]);
+        let read = |aspect| {
+            // SAFETY: The caller is responsible for ensuring that the attachment isn't
+            // accessed concurrently.
+            unsafe { texture.read(view.format, aspect, level, coordinates) }
+        };
+        let write = |aspect, texel| {
+            // SAFETY: As above.
+            unsafe { texture.write(view.format, aspect, level, coordinates, texel) }
+        };
+
+        let bias = &ds.bias;
+        let r = match ds.format {
+            wgt::TextureFormat::Depth16Unorm => 1.0 / 65536.0,
+            wgt::TextureFormat::Depth32Float | wgt::TextureFormat::Depth32FloatStencil8 => {
+                1.0 / 8388608.0
+            }
+            _ => 1.0 / 16777216.0,
+        };
+        let mut offset = bias.constant as f32 * r + bias.slope_scale * slope;
+        if bias.clamp > 0.0 {
+            offset = offset.min(bias.clamp);
+        } else if bias.clamp < 0.0 {
+            offset = offset.max(bias.clamp);
+        }
+        let depth = (depth + offset)
+            .clamp(0.0, 1.0)
+            .clamp(state.depth_range.start, state.depth_range.end);
+
+        let stencil_aspect = crate::FormatAspects::STENCIL;
+        let face = if front_facing {
+            &ds.stencil.front
+        } else {
+            &ds.stencil.back
+        };
+        let stencil = ds.format.has_stencil_aspect().then(|| {
+            let stored = match read(stencil_aspect) {
+                Some(Texel::Uint(v)) => v[0],
+                _ => 0,
+            };
+            let reference = state.stencil_reference;
+            let mask = ds.stencil.read_mask;
+            let passed = image::compare(
+                face.compare,
+                (reference & mask) as f32,
+                (stored & mask) as f32,
+            );
+            (stored, passed)
+        });
+        let update_stencil = |op: wgt::StencilOperation, stored: u32| {
+            let reference = state.stencil_reference;
+            let value = match op {
+                wgt::StencilOperation::Keep => return,
+                wgt::StencilOperation::Zero => 0,
+                wgt::StencilOperation::Replace => reference,
+                wgt::StencilOperation::Invert => !stored,
+                wgt::StencilOperation::IncrementClamp => (stored + 1).min(0xff),
+                wgt::StencilOperation::DecrementClamp => stored.saturating_sub(1),
+                wgt::StencilOperation::IncrementWrap => stored.wrapping_add(1),
+                wgt::StencilOperation::DecrementWrap => stored.wrapping_sub(1),
+            };
+            let mask = ds.stencil.write_mask;
+            let value = ((stored & !mask) | (value & mask)) & 0xff;
+            write(stencil_aspect, Texel::Uint([value, 0, 0, 1]));
+        };
+
+        if let Some((stored, false)) = stencil {
+            update_stencil(face.fail_op, stored);
+            return false;
+        }
+        let depth_aspect = crate::FormatAspects::DEPTH;
+        if ds.format.has_depth_aspect() {
+            let stored = read(depth_aspect).map_or(1.0, |texel| texel.to_f32s()[0]);
+            if !image::compare(ds.depth_compare, depth, stored) {
+                if let Some((stored, _)) = stencil {
+                    update_stencil(face.depth_fail_op, stored);
+                }
+                return false;
+            }
+            if ds.depth_write_enabled {
+                write(depth_aspect, Texel::Float([depth, 0.0, 0.0, 1.0]));
+            }
+        }
+        if let Some((stored, _)) = stencil {
+            update_stencil(face.pass_op, stored);
+        }
+        true
+    }
+
+    unsafe fn write_color(
+        &self,
+        state: &DrawState<'_>,
+        fragment: &Fragment,
+        location: u32,
+        value: &Value,
+    ) {
+        let (Some(Some(target)), Some(Some((view, depth_slice)))) = (
+            self.color_targets.get(location as usize),
+            state.color_attachments.get(location as usize),
+        ) else {
+            return;
+        };
+        let mask = target.write_mask;
+        if mask.is_empty() {
+            return;
+        }
+        let coordinates = [fragment.x, fragment.y, *depth_slice];
+        let components = texel_from_value(value);
+        // SAFETY: The caller is responsible for ensuring that the attachment isn't
+        // accessed concurrently.
+        let dst = unsafe { view.read(0, coordinates) }.unwrap_or(Texel::Float([0.0; 4]));
+        let texel = match (components, dst) {
+            (Texel::Float(src), Texel::Float(dst)) => {
+                let src = match target.blend {
+                    Some(ref blend) => blend_color(blend, src, dst, state.blend_constant),
+                    None => src,
+                };
+                Texel::Float(write_mask(mask, src, dst))
+            }
+            (Texel::Sint(src), Texel::Sint(dst)) => Texel::Sint(write_mask(mask, src, dst)),
+            (Texel::Uint(src), Texel::Uint(dst)) => Texel::Uint(write_mask(mask, src, dst)),
+            (src, _) => src,
+        };
+        // SAFETY: As above.
+        unsafe { view.write(0, coordinates, texel) };
+    }
+}
+
+/// Returns the outputs of the entry point of `program`, with their types.
+fn output_types(program: &Program) -> Vec<(&naga::Binding, &Ti)> {
+    let types = program.types();
+    let Some(ref result) = program.entry_point().function.result else {
+        return Vec::new();
+    };
+    match result.binding {
+        Some(ref binding) => vec![(binding, types.inner(result.ty))],
+        None => match *types.inner(result.ty) {
+            Ti::Struct { ref members, .. } => members
+                .iter()
+                .filter_map(|member| Some((member.binding.as_ref()?, types.inner(member.ty))))
+                .collect(),
+            _ => unreachable!("entry point result without binding"),
+        },
+    }
+}
+
+/// Splits the vertices of a draw into primitives.
+fn assemble(topology: wgt::PrimitiveTopology, vertices: &[Option<Vertex>]) -> Vec<Vec<&Vertex>> {
+    let mut primitives = Vec::new();
+    // Primitive restarts split strips.
+    for strip in vertices.split(Option::is_none) {
+        let strip = strip.iter().flatten().collect::<Vec<_>>();
+        match topology {
+            wgt::PrimitiveTopology::PointList => {
+                primitives.extend(strip.chunks_exact(1).map(<[_]>::to_vec));
+            }
+            wgt::PrimitiveTopology::LineList => {
+                primitives.extend(strip.chunks_exact(2).map(<[_]>::to_vec));
+            }
+            wgt::PrimitiveTopology::LineStrip => {
+                primitives.extend(strip.windows(2).map(<[_]>::to_vec));
+            }
+            wgt::PrimitiveTopology::TriangleList => {
+                primitives.extend(strip.chunks_exact(3).map(<[_]>::to_vec));
+            }
+            wgt::PrimitiveTopology::TriangleStrip => {
+                // Every other triangle is reversed to keep the winding of the strip.
+                primitives.extend(strip.windows(3).enumerate().map(|(i, w)| match i % 2 {
+                    0 => vec![w[0], w[1], w[2]],
+                    _ => vec![w[1], w[0], w[2]],
+                }));
+            }
+        }
+    }
+    primitives
+}
+
+/// Clips a primitive against the near plane, and the far plane unless depth is unclipped,
+/// returning the vertices of the clipped polygon, line or point.
+fn clip(primitive: &[&Vertex], unclipped_depth: bool, varyings: &[Varying]) -> Vec<Vertex> {
+    // The distances of a position to the planes, which are positive inside. Positions are
+    // also kept in front of the eye, to avoid dividing by zero.
+    let mut planes: Vec<fn(&[f32; 4]) -> f32> = vec![|p| p[3] - f32::EPSILON];
+    if !unclipped_depth {
+        planes.push(|p| p[2]);
+        planes.push(|p| p[3] - p[2]);
+    }
+
+    let mut polygon = primitive.iter().map(|&v| v.clone()).collect::<Vec<_>>();
+    let closed = polygon.len() > 2;
+    for plane in planes {
+        if polygon.iter().all(|v| plane(&v.position) >= 0.0) {
+            continue;
+        }
+        if polygon.len() == 1 {
+            return Vec::new();
+        }
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        let edges = if closed {
+            polygon.len()
+        } else {
+            polygon.len() - 1
+        };
+        for i in 0..edges {
+            let a = &polygon[i];
+            let b = &polygon[(i + 1) % polygon.len()];
+            let (da, db) = (plane(&a.position), plane(&b.position));
+            if da >= 0.0 && (closed || i == 0) {
+                clipped.push(a.clone());
+            }
+            if (da >= 0.0) != (db >= 0.0) {
+                let t = da / (da - db);
+                clipped.push(lerp(a, b, t, varyings));
+            }
+            if !closed && db >= 0.0 {
+                clipped.push(b.clone());
+            }
+        }
+        polygon = clipped;
+        if polygon.len() < primitive.len() {
+            return Vec::new();
+        }
+    }
+    polygon
+}
+
+fn lerp(a: &Vertex, b: &Vertex, t: f32, varyings: &[Varying]) -> Vertex {
+    let mut vertex = Vertex {
+        position: core::array::from_fn(|i| a.position[i] + (b.position[i] - a.position[i]) * t),
+        varyings: a.varyings.clone(),
+    };
+    for varying in varyings {
+        if varying.kind == naga::ScalarKind::Float {
+            for i in varying.offset..varying.offset + varying.count {
+                vertex.varyings[i] += (b.varyings[i] - a.varyings[i]) * t;
+            }
+        }
+    }
+    vertex
+}
+
+/// Returns twice the signed area of the triangle `a`, `b`, `p`, which is positive if `p`
+/// is on the right of `a` to `b` in framebuffer coordinates.
+fn edge(a: &ScreenVertex, b: &ScreenVertex, x: f32, y: f32) -> f32 {
+    (b.x - a.x) * (y - a.y) - (b.y - a.y) * (x - a.x)
+}
+
+/// Returns whether pixels exactly on the edge from `from` to `to` are covered, so that
+/// pixels on an edge shared by two triangles are covered by exactly one of them.
+fn is_top_left(from: &ScreenVertex, to: &ScreenVertex) -> bool {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    dy < 0.0 || (dy == 0.0 && dx > 0.0)
+}
+
+/// Returns the fragment at `x`, `y` given the barycentric coordinates of its center.
+fn interpolate(
+    vertices: &[&ScreenVertex],
+    weights: [f32; 3],
+    x: u32,
+    y: u32,
+    covered: bool,
+    varyings: &[Varying],
+) -> Fragment {
+    let weights = &weights[..vertices.len()];
+    let dot = |f: &dyn Fn(&ScreenVertex) -> f32| {
+        vertices
+            .iter()
+            .zip(weights)
+            .map(|(v, w)| f(v) * w)
+            .sum::<f32>()
+    };
+    let w_inv = dot(&|v| v.w_inv);
+    let mut components = vertices[0].varyings.clone();
+    for varying in varyings {
+        for i in varying.offset..varying.offset + varying.count {
+            components[i] = match varying.interpolation {
+                naga::Interpolation::Perspective => dot(&|v| v.varyings[i] * v.w_inv) / w_inv,
+                naga::Interpolation::Linear => dot(&|v| v.varyings[i]),
+                naga::Interpolation::Flat => continue,
+            };
+        }
+    }
+    Fragment {
+        x,
+        y,
+        depth: dot(&|v| v.z),
+        w_inv,
+        varyings: components,
+        covered,
+    }
+}
+
+/// Returns the quad of the fragment of a point.
+fn point(vertex: &ScreenVertex, [x_bounds, y_bounds]: [Range<u32>; 2]) -> Option<[Fragment; 4]> {
+    let (x, y) = (vertex.x.floor(), vertex.y.floor());
+    if x < 0.0 || y < 0.0 || !x_bounds.contains(&(x as u32)) || !y_bounds.contains(&(y as u32)) {
+        return None;
+    }
+    Some(single_fragment_quad(interpolate(
+        &[vertex],
+        [1.0, 0.0, 0.0],
+        x as u32,
+        y as u32,
+        true,
+        &[],
+    )))
+}
+
+/// Returns the quads of the fragments of a line, stepping along its major axis.
+fn line(
+    a: &ScreenVertex,
+    b: &ScreenVertex,
+    [x_bounds, y_bounds]: [Range<u32>; 2],
+) -> Vec<[Fragment; 4]> {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let x_major = dx.abs() >= dy.abs();
+    let (start, end) = if x_major { (a.x, b.x) } else { (a.y, b.y) };
+    let length = end - start;
+    if length == 0.0 {
+        return Vec::new();
+    }
+    let (first, last) = ((start.min(end) - 0.5).ceil(), (start.max(end) - 0.5).ceil());
+    let mut quads = Vec::new();
+    let mut i = first;
+    while i < last {
+        let t = (i + 0.5 - start) / length;
+        let (x, y) = if x_major {
+            (i, (a.y + dy * t).floor())
+        } else {
+            ((a.x + dx * t).floor(), i)
+        };
+        i += 1.0;
+        if x < 0.0 || y < 0.0 || !x_bounds.contains(&(x as u32)) || !y_bounds.contains(&(y as u32))
+        {
+            continue;
+        }
+        let fragment = interpolate(&[a, b], [1.0 - t, t, 0.0], x as u32, y as u32, true, &[]);
+        quads.push(single_fragment_quad(fragment));
+    }
+    quads
+}
+
+/// Returns a quad of a fragment and of helpers at the same position, which therefore has
+/// zero derivatives.
+fn single_fragment_quad(fragment: Fragment) -> [Fragment; 4] {
+    let helper = Fragment {
+        covered: false,
+        ..fragment.clone()
+    };
+    [fragment, helper.clone(), helper.clone(), helper]
+}
+
+/// Returns the components of a texel as values of `kind`.
+fn texel_components(texel: Texel, kind: naga::ScalarKind) -> [Value; 4] {
+    match (kind, texel) {
+        (naga::ScalarKind::Sint, Texel::Sint(texel)) => texel.map(Value::I32),
+        (naga::ScalarKind::Uint, Texel::Uint(texel)) => texel.map(Value::U32),
+        (naga::ScalarKind::Sint, texel) => texel.to_f32s().map(|c| Value::I32(c as i32)),
+        (naga::ScalarKind::Uint, texel) => texel.to_f32s().map(|c| Value::U32(c as u32)),
+        (_, texel) => texel.to_f32s().map(Value::F32),
+    }
+}
+
+/// Converts the components of a varying to a fragment shader input of type `inner`.
+fn varying_value(components: &[f32], inner: &Ti) -> Value {
+    let scalar = |kind, c: f32| match kind {
+        naga::ScalarKind::Sint => Value::I32(c.to_bits() as i32),
+        naga::ScalarKind::Uint => Value::U32(c.to_bits()),
+        _ => Value::F32(c),
+    };
+    match *inner {
+        Ti::Scalar(s) => scalar(s.kind, components[0]),
+        Ti::Vector { scalar: s, .. } => {
+            Value::Composite(components.iter().map(|&c| scalar(s.kind, c)).collect())
+        }
+        _ => unreachable!("fragment input of type {inner:?}"),
+    }
+}
+
+/// Converts a color output of a fragment shader to a texel.
+fn texel_from_value(value: &Value) -> Texel {
+    let components = value.components();
+    let mut texel = match components[0] {
+        Value::I32(_) => Texel::Sint([0, 0, 0, 1]),
+        Value::U32(_) => Texel::Uint([0, 0, 0, 1]),
+        _ => Texel::Float([0.0, 0.0, 0.0, 1.0]),
+    };
+    for (i, component) in components.iter().enumerate().take(4) {
+        match texel {
+            Texel::Sint(ref mut v) => v[i] = component.as_u32() as i32,
+            Texel::Uint(ref mut v) => v[i] = component.as_u32(),
+            Texel::Float(ref mut v) => v[i] = component.as_f64() as f32,
+        }
+    }
+    texel
+}
+
+fn write_mask<T: Copy>(mask: wgt::ColorWrites, src: [T; 4], dst: [T; 4]) -> [T; 4] {
+    let bits = [
+        wgt::ColorWrites::RED,
+        wgt::ColorWrites::GREEN,
+        wgt::ColorWrites::BLUE,
+        wgt::ColorWrites::ALPHA,
+    ];
+    core::array::from_fn(|i| {
+        if mask.contains(bits[i]) {
+            src[i]
+        } else {
+            dst[i]
+        }
+    })
+}
+
+fn blend_color(
+    blend: &wgt::BlendState,
+    src: [f32; 4],
+    dst: [f32; 4],
+    constant: [f32; 4],
+) -> [f32; 4] {
+    use wgt::BlendFactor as Bf;
+    let factor = |factor: Bf, i: usize| match factor {
+        Bf::Zero => 0.0,
+        Bf::One => 1.0,
+        Bf::Src => src[i],
+        Bf::OneMinusSrc => 1.0 - src[i],
+        Bf::SrcAlpha => src[3],
+        Bf::OneMinusSrcAlpha => 1.0 - src[3],
+        Bf::Dst => dst[i],
+        Bf::OneMinusDst => 1.0 - dst[i],
+        Bf::DstAlpha => dst[3],
+        Bf::OneMinusDstAlpha => 1.0 - dst[3],
+        Bf::SrcAlphaSaturated if i == 3 => 1.0,
+        Bf::SrcAlphaSaturated => src[3].min(1.0 - dst[3]),
+        Bf::Constant => constant[i],
+        Bf::OneMinusConstant => 1.0 - constant[i],
+        Bf::Src1 | Bf::OneMinusSrc1 | Bf::Src1Alpha | Bf::OneMinusSrc1Alpha => {
+            super::unsupported("dual-source blending")
+        }
+    };
+    core::array::from_fn(|i| {
+        let component = if i < 3 { &blend.color } else { &blend.alpha };
+        let s = src[i] * factor(component.src_factor, i);
+        let d = dst[i] * factor(component.dst_factor, i);
+        match component.operation {
+            wgt::BlendOperation::Add => s + d,
+            wgt::BlendOperation::Subtract => s - d,
+            wgt::BlendOperation::ReverseSubtract => d - s,
+            wgt::BlendOperation::Min => src[i].min(dst[i]),
+            wgt::BlendOperation::Max => src[i].max(dst[i]),
+        }
+    })
+}
@@ -0,0 +1,680 @@
+//! Values of the shader interpreter, and their layout in memory.
+
+use alloc::{vec, vec::Vec};
+
+use naga::{BinaryOperator as Bo, Handle, ScalarKind as Sk, TypeInner as Ti};
+
+/// A value computed by an expression.
+///
+/// Vectors are composites of scalars, and matrices are composites of column vectors.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Value {
+    Bool(bool),
+    I32(i32),
+    U32(u32),
+    F32(f32),
+    AbstractInt(i64),
+    AbstractFloat(f64),
+    /// A vector, matrix, array or structure.
+    Composite(Vec<Value>),
+    Pointer(Pointer),
+    /// A texture or sampler, identified by its global variable.
+    Handle(Handle<naga::GlobalVariable>),
+}
+
+/// A pointer to the memory of an address space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct Pointer {
+    pub space: Space,
+    /// The offset of the pointee in the memory of `space`, in bytes.
+    pub offset: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Space {
+    /// The stack of local variables of the invocation.
+    Function,
+    /// The private variables of the invocation.
+    Private,
+    /// The workgroup variables, shared by all invocations of a workgroup.
+    WorkGroup,
+    PushConstant,
+    /// The buffer bound to a uniform or storage variable.
+    Binding(Handle<naga::GlobalVariable>),
+}
+
+/// The types of a module, and the values of its overrides, which determine the length
+/// of some arrays.
+#[derive(Clone, Copy)]
+pub(super) struct Types<'a> {
+    pub arena: &'a naga::UniqueArena<naga::Type>,
+    pub overrides: &'a [Value],
+}
+
+impl<'a> Types<'a> {
+    pub fn inner(&self, ty: Handle<naga::Type>) -> &'a naga::TypeInner {
+        &self.arena[ty].inner
+    }
+
+    /// Returns the number of elements of an array, or `None` if it is runtime-sized.
+    ///
+    /// Arrays sized by overrides which are not known yet are empty.
+    pub fn array_length(&self, size: naga::ArraySize) -> Option<u32> {
+        match size {
+            naga::ArraySize::Constant(length) => Some(length.get()),
+            naga::ArraySize::Pending(handle) => {
+                Some(self.overrides.get(handle.index()).map_or(0, Value::as_u32))
+            }
+            naga::ArraySize::Dynamic => None,
+        }
+    }
+
+    /// Returns the size of a value of type `inner` in memory.
+    ///
+    /// Runtime-sized arrays have a size of zero.
+    pub fn size(&self, inner: &naga::TypeInner) -> u32 {
+        match *inner {
+            Ti::Scalar(scalar) | Ti::Atomic(scalar) => scalar_size(scalar),
+            Ti::Vector { size, scalar } => size as u32 * scalar_size(scalar),
+            Ti::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => columns as u32 * column_stride(rows, scalar),
+            Ti::Array { size, stride, .. } => self.array_length(size).unwrap_or(0) * stride,
+            Ti::Struct { span, .. } => span,
+            _ => 0,
+        }
+    }
+
+    /// Returns the zero value of type `inner`.
+    pub fn zero(&self, inner: &naga::TypeInner) -> Value {
+        match *inner {
+            Ti::Scalar(scalar) | Ti::Atomic(scalar) => Value::zero_scalar(scalar),
+            Ti::Vector { size, scalar } => {
+                Value::Composite(vec![Value::zero_scalar(scalar); size as usize])
+            }
+            Ti::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => Value::Composite(vec![
+                Value::Composite(vec![
+                    Value::zero_scalar(scalar);
+                    rows as usize
+                ]);
+                columns as usize
+            ]),
+            Ti::Array { base, size, .. } => Value::Composite(vec![
+                self.zero(self.inner(base));
+                self.array_length(size).unwrap_or(0)
+                    as usize
+            ]),
+            Ti::Struct { ref members, .. } => Value::Composite(
+                members
+                    .iter()
+                    .map(|member| self.zero(self.inner(member.ty)))
+                    .collect(),
+            ),
+            _ => super::unsupported("zero values of this type"),
+        }
+    }
+
+    /// Decodes a value of type `inner` from `bytes`.
+    ///
+    /// Bytes past the end of `bytes` are read as zero.
+    pub fn read(&self, inner: &naga::TypeInner, bytes: &[u8]) -> Value {
+        match *inner {
+            Ti::Scalar(scalar) | Ti::Atomic(scalar) => read_scalar(scalar, bytes),
+            Ti::Vector { size, scalar } => Value::Composite(
+                (0..size as u32)
+                    .map(|i| read_scalar(scalar, tail(bytes, i * scalar_size(scalar))))
+                    .collect(),
+            ),
+            Ti::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => {
+                let column = Ti::Vector { size: rows, scalar };
+                Value::Composite(
+                    (0..columns as u32)
+                        .map(|i| self.read(&column, tail(bytes, i * column_stride(rows, scalar))))
+                        .collect(),
+                )
+            }
+            Ti::Array { base, size, stride } => {
+                let base = self.inner(base);
+                let length = self.array_length(size).unwrap_or(0);
+                Value::Composite(
+                    (0..length)
+                        .map(|i| self.read(base, tail(bytes, i * stride)))
+                        .collect(),
+                )
+            }
+            Ti::Struct { ref members, .. } => Value::Composite(
+                members
+                    .iter()
+                    .map(|member| self.read(self.inner(member.ty), tail(bytes, member.offset)))
+                    .collect(),
+            ),
+            _ => super::unsupported("loading values of this type"),
+        }
+    }
+
+    /// Encodes `value` of type `inner` into `bytes`.
+    ///
+    /// Bytes past the end of `bytes` are discarded, as well as the padding of the type,
+    /// which is left as it was.
+    pub fn write(&self, inner: &naga::TypeInner, value: &Value, bytes: &mut [u8]) {
+        match *inner {
+            Ti::Scalar(_) | Ti::Atomic(_) => write_scalar(value, bytes),
+            Ti::Vector { scalar, .. } => {
+                for (i, component) in value.components().iter().enumerate() {
+                    write_scalar(component, tail_mut(bytes, i as u32 * scalar_size(scalar)));
+                }
+            }
+            Ti::Matrix { rows, scalar, .. } => {
+                let column = Ti::Vector { size: rows, scalar };
+                for (i, component) in value.components().iter().enumerate() {
+                    let offset = i as u32 * column_stride(rows, scalar);
+                    self.write(&column, component, tail_mut(bytes, offset));
+                }
+            }
+            Ti::Array { base, stride, .. } => {
+                let base = self.inner(base);
+                for (i, element) in value.components().iter().enumerate() {
+                    self.write(base, element, tail_mut(bytes, i as u32 * stride));
+                }
+            }
+            Ti::Struct { ref members, .. } => {
+                for (member, field) in members.iter().zip(value.components()) {
+                    self.write(self.inner(member.ty), field, tail_mut(bytes, member.offset));
+                }
+            }
+            _ => super::unsupported("storing values of this type"),
+        }
+    }
+}
+
+/// Returns the size of a scalar in memory, where booleans take 4 bytes.
+pub(super) fn scalar_size(scalar: naga::Scalar) -> u32 {
+    match scalar.kind {
+        Sk::Bool => 4,
+        _ => scalar.width as u32,
+    }
+}
+
+/// Returns the offset between the columns of a matrix.
+pub(super) fn column_stride(rows: naga::VectorSize, scalar: naga::Scalar) -> u32 {
+    match rows {
+        naga::VectorSize::Bi => 2 * scalar.width as u32,
+        naga::VectorSize::Tri | naga::VectorSize::Quad => 4 * scalar.width as u32,
+    }
+}
+
+fn tail(bytes: &[u8], offset: u32) -> &[u8] {
+    bytes.get(offset as usize..).unwrap_or_default()
+}
+
+fn tail_mut(bytes: &mut [u8], offset: u32) -> &mut [u8] {
+    bytes.get_mut(offset as usize..).unwrap_or_default()
+}
+
+fn read_scalar(scalar: naga::Scalar, bytes: &[u8]) -> Value {
+    let mut word = [0; 4];
+    let len = bytes.len().min(4);
+    word[..len].copy_from_slice(&bytes[..len]);
+    let bits = u32::from_le_bytes(word);
+    match (scalar.kind, scalar.width) {
+        (Sk::Bool, _) => Value::Bool(bits != 0),
+        (Sk::Sint, 4) => Value::I32(bits as i32),
+        (Sk::Uint, 4) => Value::U32(bits),
+        (Sk::Float, 4) => Value::F32(f32::from_bits(bits)),
+        _ => super::unsupported("scalars other than 32-bit ones"),
+    }
+}
+
+fn write_scalar(value: &Value, bytes: &mut [u8]) {
+    let bits = match *value {
+        Value::Bool(value) => value as u32,
+        Value::I32(value) => value as u32,
+        Value::U32(value) => value,
+        Value::F32(value) => value.to_bits(),
+        _ => unreachable!("non-constructible value in memory: {value:?}"),
+    };
+    let len = bytes.len().min(4);
+    bytes[..len].copy_from_slice(&bits.to_le_bytes()[..len]);
+}
+
+impl Value {
+    pub fn zero_scalar(scalar: naga::Scalar) -> Self {
+        match scalar.kind {
+            Sk::Bool => Self::Bool(false),
+            Sk::Sint => Self::I32(0),
+            Sk::Uint => Self::U32(0),
+            Sk::Float => Self::F32(0.0),
+            Sk::AbstractInt => Self::AbstractInt(0),
+            Sk::AbstractFloat => Self::AbstractFloat(0.0),
+        }
+    }
+
+    pub fn from_literal(literal: naga::Literal) -> Self {
+        match literal {
+            naga::Literal::Bool(value) => Self::Bool(value),
+            naga::Literal::I32(value) => Self::I32(value),
+            naga::Literal::U32(value) => Self::U32(value),
+            naga::Literal::F32(value) => Self::F32(value),
+            naga::Literal::AbstractInt(value) => Self::AbstractInt(value),
+            naga::Literal::AbstractFloat(value) => Self::AbstractFloat(value),
+            naga::Literal::F16(_)
+            | naga::Literal::F64(_)
+            | naga::Literal::I64(_)
+            | naga::Literal::U64(_) => super::unsupported("scalars other than 32-bit ones"),
+        }
+    }
+
+    pub fn as_bool(&self) -> bool {
+        match *self {
+            Self::Bool(value) => value,
+            _ => unreachable!("expected a boolean, got {self:?}"),
+        }
+    }
+
+    /// Returns an integer scalar as `u32`, reinterpreting signed integers.
+    pub fn as_u32(&self) -> u32 {
+        match *self {
+            Self::U32(value) => value,
+            Self::I32(value) => value as u32,
+            Self::AbstractInt(value) => value as u32,
+            _ => unreachable!("expected an integer, got {self:?}"),
+        }
+    }
+
+    /// Returns an integer scalar as `i64`, so that all values of `i32` and `u32` fit.
+    pub fn as_i64(&self) -> i64 {
+        match *self {
+            Self::U32(value) => value as i64,
+            Self::I32(value) => value as i64,
+            Self::AbstractInt(value) => value,
+            _ => unreachable!("expected an integer, got {self:?}"),
+        }
+    }
+
+    /// Returns a numeric scalar as `f64`.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Self::F32(value) => value as f64,
+            Self::AbstractFloat(value) => value,
+            Self::I32(value) => value as f64,
+            Self::U32(value) => value as f64,
+            Self::AbstractInt(value) => value as f64,
+            _ => unreachable!("expected a number, got {self:?}"),
+        }
+    }
+
+    /// Returns a value of the same type as the float scalar `self`.
+    pub fn with_f64(&self, value: f64) -> Self {
+        match *self {
+            Self::F32(_) => Self::F32(value as f32),
+            Self::AbstractFloat(_) => Self::AbstractFloat(value),
+            _ => unreachable!("expected a float, got {self:?}"),
+        }
+    }
+
+    pub fn as_pointer(&self) -> Pointer {
+        match *self {
+            Self::Pointer(pointer) => pointer,
+            _ => unreachable!("expected a pointer, got {self:?}"),
+        }
+    }
+
+    pub fn as_handle(&self) -> Handle<naga::GlobalVariable> {
+        match *self {
+            Self::Handle(handle) => handle,
+            _ => unreachable!("expected a texture or sampler, got {self:?}"),
+        }
+    }
+
+    pub fn components(&self) -> &[Value] {
+        match *self {
+            Self::Composite(ref components) => components,
+            _ => core::slice::from_ref(self),
+        }
+    }
+
+    pub fn into_components(self) -> Vec<Value> {
+        match self {
+            Self::Composite(components) => components,
+            scalar => vec![scalar],
+        }
+    }
+
+    /// Returns the components of a vector as `f32`, for image coordinates and colors.
+    pub fn to_f32s(&self) -> Vec<f32> {
+        self.components()
+            .iter()
+            .map(|component| component.as_f64() as f32)
+            .collect()
+    }
+
+    /// Returns the components of an integer vector as `i32`, reinterpreting unsigned ones.
+    pub fn to_i32s(&self) -> Vec<i32> {
+        self.components()
+            .iter()
+            .map(|component| component.as_u32() as i32)
+            .collect()
+    }
+
+    /// Applies `f` to each scalar of a scalar, vector or matrix.
+    pub fn map(&self, f: &mut impl FnMut(&Value) -> Value) -> Value {
+        match *self {
+            Self::Composite(ref components) => Self::Composite(
+                components
+                    .iter()
+                    .map(|component| component.map(f))
+                    .collect(),
+            ),
+            ref scalar => f(scalar),
+        }
+    }
+
+    /// Applies `f` to each pair of scalars of `self` and `other`, splatting scalars to
+    /// the shape of the other operand.
+    pub fn zip(&self, other: &Value, f: &mut impl FnMut(&Value, &Value) -> Value) -> Value {
+        match (self, other) {
+            (Self::Composite(a), Self::Composite(b)) => {
+                Self::Composite(a.iter().zip(b).map(|(a, b)| a.zip(b, f)).collect())
+            }
+            (Self::Composite(a), b) => Self::Composite(a.iter().map(|a| a.zip(b, f)).collect()),
+            (a, Self::Composite(b)) => Self::Composite(b.iter().map(|b| a.zip(b, f)).collect()),
+            (a, b) => f(a, b),
+        }
+    }
+
+    /// Like [`Self::zip`], for three operands.
+    pub fn zip3(
+        &self,
+        b: &Value,
+        c: &Value,
+        f: &mut impl FnMut(&Value, &Value, &Value) -> Value,
+    ) -> Value {
+        let len = [self, b, c].into_iter().find_map(|value| match *value {
+            Self::Composite(ref components) => Some(components.len()),
+            _ => None,
+        });
+        match len {
+            Some(len) => Self::Composite(
+                (0..len)
+                    .map(|i| self.component(i).zip3(b.component(i), c.component(i), f))
+                    .collect(),
+            ),
+            None => f(self, b, c),
+        }
+    }
+
+    /// Returns component `i` of a composite, or `self` if it is a scalar.
+    fn component(&self, i: usize) -> &Value {
+        match *self {
+            Self::Composite(ref components) => &components[i],
+            _ => self,
+        }
+    }
+}
+
+pub(super) fn unary(op: naga::UnaryOperator, value: &Value) -> Value {
+    value.map(&mut |value| match (op, value) {
+        (naga::UnaryOperator::Negate, &Value::I32(v)) => Value::I32(v.wrapping_neg()),
+        (naga::UnaryOperator::Negate, &Value::F32(v)) => Value::F32(-v),
+        (naga::UnaryOperator::Negate, &Value::AbstractInt(v)) => {
+            Value::AbstractInt(v.wrapping_neg())
+        }
+        (naga::UnaryOperator::Negate, &Value::AbstractFloat(v)) => Value::AbstractFloat(-v),
+        (naga::UnaryOperator::LogicalNot, &Value::Bool(v)) => Value::Bool(!v),
+        (naga::UnaryOperator::BitwiseNot, &Value::I32(v)) => Value::I32(!v),
+        (naga::UnaryOperator::BitwiseNot, &Value::U32(v)) => Value::U32(!v),
+        (naga::UnaryOperator::BitwiseNot, &Value::AbstractInt(v)) => Value::AbstractInt(!v),
+        _ => unreachable!("invalid operand of {op:?}: {value:?}"),
+    })
+}
+
+/// Evaluates a binary operator on scalars, vectors, or matrices added or subtracted
+/// componentwise.
+///
+/// Products of matrices are evaluated by [`multiply`].
+pub(super) fn binary(op: Bo, left: &Value, right: &Value) -> Value {
+    left.zip(right, &mut |a, b| binary_scalar(op, a, b))
+}
+
+fn binary_scalar(op: Bo, a: &Value, b: &Value) -> Value {
+    macro_rules! compare {
+        ($a:expr, $b:expr) => {
+            match op {
+                Bo::Equal => Some(Value::Bool($a == $b)),
+                Bo::NotEqual => Some(Value::Bool($a != $b)),
+                Bo::Less => Some(Value::Bool($a < $b)),
+                Bo::LessEqual => Some(Value::Bool($a <= $b)),
+                Bo::Greater => Some(Value::Bool($a > $b)),
+                Bo::GreaterEqual => Some(Value::Bool($a >= $b)),
+                _ => None,
+            }
+        };
+    }
+    macro_rules! integer {
+        ($variant:ident, $a:expr, $b:expr) => {{
+            let (a, b) = ($a, $b);
+            let value = match op {
+                Bo::Add => a.wrapping_add(b),
+                Bo::Subtract => a.wrapping_sub(b),
+                Bo::Multiply => a.wrapping_mul(b),
+                // Division by zero returns the dividend, and remainders by zero are zero.
+                Bo::Divide if b == 0 => a,
+                Bo::Divide => a.wrapping_div(b),
+                Bo::Modulo if b == 0 => 0,
+                Bo::Modulo => a.wrapping_rem(b),
+                Bo::And => a & b,
+                Bo::InclusiveOr => a | b,
+                Bo::ExclusiveOr => a ^ b,
+                _ => return compare!(a, b).unwrap(),
+            };
+            Value::$variant(value)
+        }};
+    }
+    macro_rules! float {
+        ($variant:ident, $a:expr, $b:expr) => {{
+            let (a, b) = ($a, $b);
+            let value = match op {
+                Bo::Add => a + b,
+                Bo::Subtract => a - b,
+                Bo::Multiply => a * b,
+                Bo::Divide => a / b,
+                Bo::Modulo => a % b,
+                _ => return compare!(a, b).unwrap(),
+            };
+            Value::$variant(value)
+        }};
+    }
+
+    match (op, a, b) {
+        // The shift amount is always unsigned, and is taken modulo the bit width.
+        (Bo::ShiftLeft, &Value::I32(a), b) => Value::I32(a.wrapping_shl(b.as_u32())),
+        (Bo::ShiftLeft, &Value::U32(a), b) => Value::U32(a.wrapping_shl(b.as_u32())),
+        (Bo::ShiftLeft, &Value::AbstractInt(a), b) => {
+            Value::AbstractInt(a.wrapping_shl(b.as_u32()))
+        }
+        (Bo::ShiftRight, &Value::I32(a), b) => Value::I32(a.wrapping_shr(b.as_u32())),
+        (Bo::ShiftRight, &Value::U32(a), b) => Value::U32(a.wrapping_shr(b.as_u32())),
+        (Bo::ShiftRight, &Value::AbstractInt(a), b) => {
+            Value::AbstractInt(a.wrapping_shr(b.as_u32()))
+        }
+        (_, &Value::Bool(a), &Value::Bool(b)) => match op {
+            Bo::LogicalAnd | Bo::And => Value::Bool(a && b),
+            Bo::LogicalOr | Bo::InclusiveOr => Value::Bool(a || b),
+            Bo::ExclusiveOr => Value::Bool(a ^ b),
+            _ => compare!(a, b).unwrap(),
+        },
+        (_, &Value::I32(a), &Value::I32(b)) => integer!(I32, a, b),
+        (_, &Value::U32(a), &Value::U32(b)) => integer!(U32, a, b),
+        (_, &Value::AbstractInt(a), &Value::AbstractInt(b)) => integer!(AbstractInt, a, b),
+        (_, &Value::F32(a), &Value::F32(b)) => float!(F32, a, b),
+        (_, &Value::AbstractFloat(a), &Value::AbstractFloat(b)) => float!(AbstractFloat, a, b),
+        _ => unreachable!("invalid operands of {op:?}: {a:?}, {b:?}"),
+    }
+}
+
+/// Evaluates a multiplication, given the types of its operands.
+pub(super) fn multiply(
+    left: &Value,
+    left_ty: &naga::TypeInner,
+    right: &Value,
+    right_ty: &naga::TypeInner,
+) -> Value {
+    match (left_ty, right_ty) {
+        // A matrix is a linear combination of its columns.
+        (&Ti::Matrix { .. }, &Ti::Vector { .. }) => combine(left.components(), right.components()),
+        (&Ti::Vector { .. }, &Ti::Matrix { .. }) => Value::Composite(
+            right
+                .components()
+                .iter()
+                .map(|column| dot(left, column))
+                .collect(),
+        ),
+        (&Ti::Matrix { .. }, &Ti::Matrix { .. }) => Value::Composite(
+            right
+                .components()
+                .iter()
+                .map(|column| combine(left.components(), column.components()))
+                .collect(),
+        ),
+        _ => binary(Bo::Multiply, left, right),
+    }
+}
+
+/// Returns the sum of `vectors` weighted by `weights`.
+fn combine(vectors: &[Value], weights: &[Value]) -> Value {
+    vectors
+        .iter()
+        .zip(weights)
+        .map(|(vector, weight)| binary(Bo::Multiply, vector, weight))
+        .reduce(|sum, term| binary(Bo::Add, &sum, &term))
+        .unwrap()
+}
+
+pub(super) fn dot(a: &Value, b: &Value) -> Value {
+    a.components()
+        .iter()
+        .zip(b.components())
+        .map(|(a, b)| binary_scalar(Bo::Multiply, a, b))
+        .reduce(|sum, term| binary_scalar(Bo::Add, &sum, &term))
+        .unwrap()
+}
+
+pub(super) fn select(condition: &Value, accept: &Value, reject: &Value) -> Value {
+    match *condition {
+        Value::Bool(true) => accept.clone(),
+        Value::Bool(false) => reject.clone(),
+        _ => condition.zip3(accept, reject, &mut |condition, accept, reject| {
+            if condition.as_bool() {
+                accept.clone()
+            } else {
+                reject.clone()
+            }
+        }),
+    }
+}
+
+/// Converts or bitcasts each scalar of `value` to `kind`.
+pub(super) fn cast(value: &Value, kind: naga::ScalarKind, convert: Option<naga::Bytes>) -> Value {
+    value.map(&mut |value| match convert {
+        Some(_) => convert_scalar(value, kind),
+        None => {
+            let bits = match *value {
+                Value::I32(v) => v as u32,
+                Value::U32(v) => v,
+                Value::F32(v) => v.to_bits(),
+                _ => unreachable!("invalid bitcast operand: {value:?}"),
+            };
+            match kind {
+                Sk::Sint => Value::I32(bits as i32),
+                Sk::Uint => Value::U32(bits),
+                Sk::Float => Value::F32(f32::from_bits(bits)),
+                _ => unreachable!("invalid bitcast to {kind:?}"),
+            }
+        }
+    })
+}
+
+fn convert_scalar(value: &Value, kind: naga::ScalarKind) -> Value {
+    if let Value::Bool(v) = *value {
+        return match kind {
+            Sk::Bool => Value::Bool(v),
+            Sk::Sint => Value::I32(v as i32),
+            Sk::Uint => Value::U32(v as u32),
+            Sk::Float => Value::F32(v as u32 as f32),
+            Sk::AbstractInt => Value::AbstractInt(v as i64),
+            Sk::AbstractFloat => Value::AbstractFloat(v as u32 as f64),
+        };
+    }
+    let is_float = matches!(*value, Value::F32(_) | Value::AbstractFloat(_));
+    match kind {
+        Sk::Bool if is_float => Value::Bool(value.as_f64() != 0.0),
+        Sk::Bool => Value::Bool(value.as_i64() != 0),
+        // Conversions of floats to integers saturate, and integers wrap around.
+        Sk::Sint if is_float => Value::I32(value.as_f64() as i32),
+        Sk::Sint => Value::I32(value.as_i64() as i32),
+        Sk::Uint if is_float => Value::U32(value.as_f64() as u32),
+        Sk::Uint => Value::U32(value.as_i64() as u32),
+        Sk::AbstractInt if is_float => Value::AbstractInt(value.as_f64() as i64),
+        Sk::AbstractInt => Value::AbstractInt(value.as_i64()),
+        Sk::Float => match *value {
+            Value::F32(v) => Value::F32(v),
+            Value::I32(v) => Value::F32(v as f32),
+            Value::U32(v) => Value::F32(v as f32),
+            _ => Value::F32(value.as_f64() as f32),
+        },
+        Sk::AbstractFloat => Value::AbstractFloat(value.as_f64()),
+    }
+}
+
+/// Converts an abstract scalar, vector or matrix to the concrete type `inner`, as done by
+/// the automatic conversions of constant expressions.
+pub(super) fn concretize(value: Value, inner: &naga::TypeInner) -> Value {
+    match inner.scalar() {
+        Some(scalar) if !matches!(scalar.kind, Sk::AbstractInt | Sk::AbstractFloat) => {
+            value.map(&mut |v| convert_scalar(v, scalar.kind))
+        }
+        _ => value,
+    }
+}
+
+pub(super) fn relational(fun: naga::RelationalFunction, argument: &Value) -> Value {
+    match fun {
+        naga::RelationalFunction::All => {
+            Value::Bool(argument.components().iter().all(Value::as_bool))
+        }
+        naga::RelationalFunction::Any => {
+            Value::Bool(argument.components().iter().any(Value::as_bool))
+        }
+        naga::RelationalFunction::IsNan => argument.map(&mut |v| Value::Bool(v.as_f64().is_nan())),
+        naga::RelationalFunction::IsInf => {
+            argument.map(&mut |v| Value::Bool(v.as_f64().is_infinite()))
+        }
+    }
+}
+
+/// Evaluates an atomic read-modify-write operation, returning the new value.
+pub(super) fn atomic(fun: naga::AtomicFunction, old: &Value, value: &Value) -> Value {
+    match (fun, old, value) {
+        (naga::AtomicFunction::Add, _, _) => binary_scalar(Bo::Add, old, value),
+        (naga::AtomicFunction::Subtract, _, _) => binary_scalar(Bo::Subtract, old, value),
+        (naga::AtomicFunction::And, _, _) => binary_scalar(Bo::And, old, value),
+        (naga::AtomicFunction::InclusiveOr, _, _) => binary_scalar(Bo::InclusiveOr, old, value),
+        (naga::AtomicFunction::ExclusiveOr, _, _) => binary_scalar(Bo::ExclusiveOr, old, value),
+        (naga::AtomicFunction::Min, &Value::I32(a), &Value::I32(b)) => Value::I32(a.min(b)),
+        (naga::AtomicFunction::Min, &Value::U32(a), &Value::U32(b)) => Value::U32(a.min(b)),
+        (naga::AtomicFunction::Max, &Value::I32(a), &Value::I32(b)) => Value::I32(a.max(b)),
+        (naga::AtomicFunction::Max, &Value::U32(a), &Value::U32(b)) => Value::U32(a.max(b)),
+        (naga::AtomicFunction::Exchange { .. }, _, _) => value.clone(),
+        _ => unreachable!("invalid operands of atomic {fun:?}: {old:?}, {value:?}"),
+    }
+}
@@ -11,7 +11,7 @@ use windows::{
     core::Interface as _,
 };
 
-use super::D3D12Lib;
+use super::{D3D12Lib, conv};
 use crate::{
     auxil::{
         self,
@@ -57,6 +57,7 @@ impl super::Adapter {
         library: &Arc<D3D12Lib>,
         instance_flags: wgt::InstanceFlags,
         memory_budget_thresholds: wgt::MemoryBudgetThresholds,
+        toggles: &wgt::TogglesDescriptor,
         compiler_container: Arc<shader_compilation::CompilerContainer>,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         // Create the device so that we can get the capabilities.
@@ -110,6 +111,8 @@ impl super::Adapter {
 
         let mut workarounds = super::Workarounds::default();
 
+        let driver_version = unsafe { adapter.CheckInterfaceSupport(&Dxgi::IDXGIDevice::IID) }.ok();
+
         let info = wgt::AdapterInfo {
             backend: wgt::Backend::Dx12,
             name: device_name,
@@ -126,7 +129,7 @@ impl super::Adapter {
                 wgt::DeviceType::DiscreteGpu
             },
             driver: {
-                if let Ok(i) = unsafe { adapter.CheckInterfaceSupport(&Dxgi::IDXGIDevice::IID) } {
+                if let Some(i) = driver_version {
                     const MASK: i64 = 0xFFFF;
                     format!(
                         "{}.{}.{}.{}",
@@ -142,6 +145,14 @@ impl super::Adapter {
             driver_info: String::new(),
         };
 
+        let toggles = toggles.apply(if workarounds.avoid_cpu_descriptor_overwrites {
+            wgt::Toggles::DX12_AVOID_CPU_DESCRIPTOR_OVERWRITES
+        } else {
+            wgt::Toggles::empty()
+        }) & wgt::Toggles::DX12_AVOID_CPU_DESCRIPTOR_OVERWRITES;
+        workarounds.avoid_cpu_descriptor_overwrites =
+            toggles.contains(wgt::Toggles::DX12_AVOID_CPU_DESCRIPTOR_OVERWRITES);
+
         let mut options = Direct3D12::D3D12_FEATURE_DATA_D3D12_OPTIONS::default();
         unsafe {
             device.CheckFeatureSupport(
@@ -183,20 +194,31 @@ impl super::Adapter {
                 && features3.CastingFullyTypedFormatSupported.as_bool()
         };
 
-        let heap_create_not_zeroed = {
-            // For D3D12_HEAP_FLAG_CREATE_NOT_ZEROED we just need to
-            // make sure that options7 can be queried. See also:
-            // https://devblogs.microsoft.com/directx/coming-to-directx-12-more-control-over-memory-allocation/
-            let mut features7 = Direct3D12::D3D12_FEATURE_DATA_D3D12_OPTIONS7::default();
-            unsafe {
-                device.CheckFeatureSupport(
-                    Direct3D12::D3D12_FEATURE_D3D12_OPTIONS7,
-                    <*mut _>::cast(&mut features7),
-                    size_of_val(&features7) as u32,
-                )
-            }
-            .is_ok()
-        };
+        let mut features7 = Direct3D12::D3D12_FEATURE_DATA_D3D12_OPTIONS7::default();
+        // For D3D12_HEAP_FLAG_CREATE_NOT_ZEROED we just need to
+        // make sure that options7 can be queried. See also:
+        // https://devblogs.microsoft.com/directx/coming-to-directx-12-more-control-over-memory-allocation/
+        let heap_create_not_zeroed = unsafe {
+            device.CheckFeatureSupport(
+                Direct3D12::D3D12_FEATURE_D3D12_OPTIONS7,
+                <*mut _>::cast(&mut features7),
+                size_of_val(&features7) as u32,
+            )
+        }
+        .is_ok();
+
+        let mut features6 = Direct3D12::D3D12_FEATURE_DATA_D3D12_OPTIONS6::default();
+        let has_features6 = unsafe {
+            device.CheckFeatureSupport(
+                Direct3D12::D3D12_FEATURE_D3D12_OPTIONS6,
+                <*mut _>::cast(&mut features6),
+                size_of_val(&features6) as u32,
+            )
+        }
+        .is_ok();
+        let variable_rate_shading_supported = has_features6
+            && features6.VariableShadingRateTier.0
+                >= Direct3D12::D3D12_VARIABLE_SHADING_RATE_TIER_1.0;
 
         let mut max_sampler_descriptor_heap_size =
             Direct3D12::D3D12_MAX_SHADER_VISIBLE_SAMPLER_HEAP_SIZE;
@@ -285,6 +307,33 @@ impl super::Adapter {
         } else {
             naga::back::hlsl::ShaderModel::V5_1
         };
+        let pipeline_library_supported = {
+            let mut shader_cache = Direct3D12::D3D12_FEATURE_DATA_SHADER_CACHE::default();
+            let hr = unsafe {
+                device.CheckFeatureSupport(
+                    Direct3D12::D3D12_FEATURE_SHADER_CACHE,
+                    <*mut _>::cast(&mut shader_cache),
+                    size_of_val(&shader_cache) as u32,
+                )
+            };
+            hr.is_ok()
+                && shader_cache
+                    .SupportFlags
+                    .contains(Direct3D12::D3D12_SHADER_CACHE_SUPPORT_LIBRARY)
+        };
+
+        // Pipeline libraries are only valid for the driver that serialized them,
+        // so key them on the driver version and the exact hardware revision.
+        let pipeline_cache_validation_key = {
+            let d: [u8; 8] = driver_version.unwrap_or(0).to_be_bytes();
+            let s: [u8; 4] = desc.SubSysId.to_be_bytes();
+            let r: [u8; 4] = desc.Revision.to_be_bytes();
+            [
+                d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7], s[0], s[1], s[2], s[3], r[0], r[1],
+                r[2], r[3],
+            ]
+        };
+
         let private_caps = super::PrivateCapabilities {
             instance_flags,
             heterogeneous_resource_heaps: options.ResourceHeapTier
@@ -302,6 +351,10 @@ impl super::Adapter {
             suballocation_supported: !info.name.contains("Iris(R) Xe"),
             shader_model,
             max_sampler_descriptor_heap_size,
+            pipeline_library_supported,
+            pipeline_cache_validation_key,
+            additional_shading_rates_supported: variable_rate_shading_supported
+                && features6.AdditionalShadingRatesSupported.as_bool(),
         };
 
         // Theoretically vram limited, but in practice 2^20 is the limit
@@ -341,6 +394,9 @@ impl super::Adapter {
             | wgt::Features::DEPTH_CLIP_CONTROL
             | wgt::Features::DEPTH32FLOAT_STENCIL8
             | wgt::Features::INDIRECT_FIRST_INSTANCE
+            | wgt::Features::VERTEX_INSTANCE_STEP_RATE
+            | wgt::Features::PRIMITIVE_RESTART_CONTROL
+            | wgt::Features::REUSABLE_COMMAND_BUFFERS
             | wgt::Features::MAPPABLE_PRIMARY_BUFFERS
             | wgt::Features::MULTI_DRAW_INDIRECT
             | wgt::Features::MULTI_DRAW_INDIRECT_COUNT
@@ -360,25 +416,59 @@ impl super::Adapter {
             | wgt::Features::RG11B10UFLOAT_RENDERABLE
             | wgt::Features::DUAL_SOURCE_BLENDING
             | wgt::Features::TEXTURE_FORMAT_NV12
+            | wgt::Features::TEXTURE_FORMAT_P010
             | wgt::Features::FLOAT32_FILTERABLE
-            | wgt::Features::TEXTURE_ATOMIC;
-
-        //TODO: in order to expose this, we need to run a compute shader
-        // that extract the necessary statistics out of the D3D12 result.
-        // Alternatively, we could allocate a buffer for the query set,
-        // write the results there, and issue a bunch of copy commands.
-        //| wgt::Features::PIPELINE_STATISTICS_QUERY
+            | wgt::Features::TEXTURE_ATOMIC
+            | wgt::Features::SHADER_FLOAT32_ATOMIC
+            | wgt::Features::EXPERIMENTAL_MULTI_QUEUE
+            | wgt::Features::EXPERIMENTAL_SHARED_FENCE
+            | wgt::Features::PRESENTATION_TIMING
+            | wgt::Features::PIPELINE_STATISTICS_QUERY
+            | wgt::Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES
+            | wgt::Features::CONDITIONAL_RENDERING
+            | wgt::Features::SHADER_DEBUG_PRINTF;
 
         if max_feature_level.0 >= Direct3D::D3D_FEATURE_LEVEL_11_1.0 {
             features |= wgt::Features::VERTEX_WRITABLE_STORAGE;
         }
 
+        features.set(wgt::Features::PIPELINE_CACHE, pipeline_library_supported);
+
+        // Heaps have to hold any kind of texture, which needs resource heap tier 2.
+        features.set(
+            wgt::Features::TEXTURE_ALIASING,
+            private_caps.heterogeneous_resource_heaps && private_caps.suballocation_supported,
+        );
+
+        // Unmapped tiles must read as zero and drop writes, which needs tiled resources tier 2.
+        features.set(
+            wgt::Features::SPARSE_BINDING,
+            private_caps.heterogeneous_resource_heaps
+                && private_caps.suballocation_supported
+                && options.TiledResourcesTier.0 >= Direct3D12::D3D12_TILED_RESOURCES_TIER_2.0,
+        );
+
+        // Resolved by the shaders of `depth_resolve`, which any feature level can run.
+        features |= wgt::Features::DEPTH_STENCIL_RESOLVE;
+
         features.set(
             wgt::Features::CONSERVATIVE_RASTERIZATION,
             options.ConservativeRasterizationTier
                 != Direct3D12::D3D12_CONSERVATIVE_RASTERIZATION_TIER_NOT_SUPPORTED,
         );
 
+        features.set(
+            wgt::Features::LOGIC_OP,
+            options.OutputMergerLogicOp.as_bool(),
+        );
+
+        features.set(
+            wgt::Features::MULTI_VIEWPORT,
+            options
+                .VPAndRTArrayIndexFromAnyShaderFeedingRasterizerSupportedWithoutGSEmulation
+                .as_bool(),
+        );
+
         features.set(
             wgt::Features::TEXTURE_BINDING_ARRAY
                 | wgt::Features::STORAGE_RESOURCE_BINDING_ARRAY
@@ -428,6 +518,11 @@ impl super::Adapter {
                 && features1.Int64ShaderOps.as_bool(),
         );
 
+        features.set(
+            wgt::Features::SHADER_F64,
+            options.DoublePrecisionFloatShaderOps.as_bool(),
+        );
+
         let float16_supported = {
             let mut features4 = Direct3D12::D3D12_FEATURE_DATA_D3D12_OPTIONS4::default();
             let hr = unsafe {
@@ -441,7 +536,7 @@ impl super::Adapter {
         };
 
         features.set(
-            wgt::Features::SHADER_F16,
+            wgt::Features::SHADER_F16 | wgt::Features::SHADER_I16,
             shader_model >= naga::back::hlsl::ShaderModel::V6_2 && float16_supported,
         );
 
@@ -468,14 +563,15 @@ impl super::Adapter {
         }
         .is_ok();
 
-        // Once ray tracing pipelines are supported they also will go here
         let supports_ray_tracing = features5.RaytracingTier
             == Direct3D12::D3D12_RAYTRACING_TIER_1_1
             && shader_model >= naga::back::hlsl::ShaderModel::V6_5
             && has_features5;
         features.set(
             wgt::Features::EXPERIMENTAL_RAY_QUERY
-                | wgt::Features::EXTENDED_ACCELERATION_STRUCTURE_VERTEX_FORMATS,
+                | wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE
+                | wgt::Features::EXTENDED_ACCELERATION_STRUCTURE_VERTEX_FORMATS
+                | wgt::Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION,
             supports_ray_tracing,
         );
 
@@ -497,6 +593,17 @@ impl super::Adapter {
             atomic_int64_on_typed_resource_supported,
         );
 
+        features.set(
+            wgt::Features::VARIABLE_RATE_SHADING,
+            variable_rate_shading_supported,
+        );
+
+        features.set(
+            wgt::Features::EXPERIMENTAL_MESH_SHADER,
+            features7.MeshShaderTier != Direct3D12::D3D12_MESH_SHADER_TIER_NOT_SUPPORTED
+                && shader_model >= naga::back::hlsl::ShaderModel::V6_5,
+        );
+
         // TODO: Determine if IPresentationManager is supported
         let presentation_timer = auxil::dxgi::time::PresentationTimer::new_dxgi();
 
@@ -633,6 +740,11 @@ impl super::Adapter {
                     } else {
                         0
                     },
+                    max_ray_recursion_depth: if supports_ray_tracing {
+                        Direct3D12::D3D12_RAYTRACING_MAX_DECLARABLE_TRACE_RECURSION_DEPTH
+                    } else {
+                        0
+                    },
                 },
                 alignments: crate::Alignments {
                     buffer_copy_offset: wgt::BufferSize::new(
@@ -649,9 +761,19 @@ impl super::Adapter {
                     raw_tlas_instance_size: size_of::<Direct3D12::D3D12_RAYTRACING_INSTANCE_DESC>(),
                     ray_tracing_scratch_buffer_alignment:
                         Direct3D12::D3D12_RAYTRACING_ACCELERATION_STRUCTURE_BYTE_ALIGNMENT,
+                    ray_tracing_shader_group_handle_size: if supports_ray_tracing {
+                        Direct3D12::D3D12_SHADER_IDENTIFIER_SIZE_IN_BYTES
+                    } else {
+                        0
+                    },
+                    ray_tracing_shader_group_handle_alignment:
+                        Direct3D12::D3D12_RAYTRACING_SHADER_RECORD_BYTE_ALIGNMENT,
+                    ray_tracing_shader_group_base_alignment:
+                        Direct3D12::D3D12_RAYTRACING_SHADER_TABLE_BYTE_ALIGNMENT,
                 },
                 downlevel,
             },
+            toggles,
         })
     }
 }
@@ -664,25 +786,40 @@ impl crate::Adapter for super::Adapter {
         features: wgt::Features,
         limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        let queue: Direct3D12::ID3D12CommandQueue = {
-            profiling::scope!("ID3D12Device::CreateCommandQueue");
-            unsafe {
-                self.device
-                    .CreateCommandQueue(&Direct3D12::D3D12_COMMAND_QUEUE_DESC {
-                        Type: Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT,
-                        Priority: Direct3D12::D3D12_COMMAND_QUEUE_PRIORITY_NORMAL.0,
-                        Flags: Direct3D12::D3D12_COMMAND_QUEUE_FLAG_NONE,
-                        NodeMask: 0,
-                    })
-            }
-            .into_device_result("Queue creation")?
+        let create_queue = |list_type| -> Result<super::Queue, crate::DeviceError> {
+            let raw: Direct3D12::ID3D12CommandQueue = {
+                profiling::scope!("ID3D12Device::CreateCommandQueue");
+                unsafe {
+                    self.device
+                        .CreateCommandQueue(&Direct3D12::D3D12_COMMAND_QUEUE_DESC {
+                            Type: list_type,
+                            Priority: Direct3D12::D3D12_COMMAND_QUEUE_PRIORITY_NORMAL.0,
+                            Flags: Direct3D12::D3D12_COMMAND_QUEUE_FLAG_NONE,
+                            NodeMask: 0,
+                        })
+                }
+                .into_device_result("Queue creation")?
+            };
+            Ok(super::Queue {
+                raw,
+                list_type,
+                temp_lists: Mutex::new(Vec::new()),
+                pending_signals: Mutex::new(Vec::new()),
+            })
         };
 
+        let queue = create_queue(Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT)?;
+        let additional_queues = additional_queues
+            .iter()
+            .map(|&queue_type| create_queue(conv::map_queue_type(queue_type)))
+            .collect::<Result<Vec<_>, _>>()?;
+
         let device = super::Device::new(
             self.raw.clone(),
             self.device.clone(),
-            queue.clone(),
+            queue.raw.clone(),
             features,
             limits,
             memory_hints,
@@ -693,10 +830,8 @@ impl crate::Adapter for super::Adapter {
         )?;
         Ok(crate::OpenDevice {
             device,
-            queue: super::Queue {
-                raw: queue,
-                temp_lists: Mutex::new(Vec::new()),
-            },
+            queue,
+            additional_queues,
         })
     }
 
@@ -933,10 +1068,37 @@ impl crate::Adapter for super::Adapter {
                     wgt::CompositeAlphaMode::PreMultiplied,
                 ],
             },
+            // Whether presenting in a color space is supported is only known once the
+            // swapchain exists, so `configure` checks it.
+            color_spaces: vec![
+                wgt::SurfaceColorSpace::Srgb,
+                wgt::SurfaceColorSpace::ExtendedSrgbLinear,
+                wgt::SurfaceColorSpace::Hdr10,
+            ],
         })
     }
 
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp(self.presentation_timer.get_timestamp_ns())
     }
+    fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        let query = |group, device_local| {
+            let info = self.raw.query_video_memory_info(group).ok()?;
+            Some(wgt::MemoryHeapInfo {
+                usage: info.CurrentUsage,
+                budget: info.Budget,
+                device_local,
+            })
+        };
+
+        let mut heaps = vec![query(Dxgi::DXGI_MEMORY_SEGMENT_GROUP_LOCAL, true)?];
+        if matches!(
+            self.private_caps.memory_architecture,
+            super::MemoryArchitecture::NonUnified
+        ) {
+            heaps.push(query(Dxgi::DXGI_MEMORY_SEGMENT_GROUP_NON_LOCAL, false)?);
+        }
+
+        Some(wgt::MemoryInfo { heaps })
+    }
 }
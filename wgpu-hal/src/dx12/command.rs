@@ -79,6 +79,9 @@ impl Drop for super::CommandEncoder {
             rtv_pool.free_handle(handle);
         }
         drop(rtv_pool);
+        for handle in self.temp_resolve_srvs.drain(..) {
+            self.shared.heap_views.free_slice(handle);
+        }
 
         self.counters.command_encoders.sub(1);
     }
@@ -278,6 +281,25 @@ impl super::CommandEncoder {
                     },
                     Pk::Transfer => (),
                 },
+                super::RootElement::DebugPrintfBuffer => {
+                    let address = unsafe {
+                        self.shared
+                            .debug_printf
+                            .as_ref()
+                            .unwrap()
+                            .resource
+                            .GetGPUVirtualAddress()
+                    };
+                    match self.pass.kind {
+                        Pk::Render => unsafe {
+                            list.SetGraphicsRootUnorderedAccessView(index, address)
+                        },
+                        Pk::Compute => unsafe {
+                            list.SetComputeRootUnorderedAccessView(index, address)
+                        },
+                        Pk::Transfer => (),
+                    }
+                }
             }
         }
     }
@@ -294,6 +316,9 @@ impl super::CommandEncoder {
         if let Some(root_index) = layout.sampler_heap_root_index {
             self.pass.root_elements[root_index as usize] = super::RootElement::SamplerHeap;
         }
+        if let Some(root_index) = layout.debug_printf_root_index {
+            self.pass.root_elements[root_index as usize] = super::RootElement::DebugPrintfBuffer;
+        }
         self.pass.layout = layout.clone();
         self.pass.dirty_root_elements = (1 << layout.total_root_elements) - 1;
     }
@@ -306,12 +331,53 @@ impl super::CommandEncoder {
                     &crate::dx12::QuerySet {
                         raw: query_set_raw,
                         raw_ty: Direct3D12::D3D12_QUERY_TYPE_TIMESTAMP,
+                        statistics: None,
                     },
                     index,
                 );
             }
         }
     }
+
+    fn begin_pass_pipeline_statistics_query(
+        &mut self,
+        query: Option<&crate::PassPipelineStatisticsQuery<'_, super::QuerySet>>,
+    ) {
+        if let Some(query) = query {
+            unsafe {
+                self.list.as_ref().unwrap().BeginQuery(
+                    &query.query_set.raw,
+                    query.query_set.raw_ty,
+                    query.query_index,
+                )
+            };
+            self.pass_pipeline_statistics_query =
+                Some((query.query_set.raw.clone(), query.query_index));
+        }
+    }
+
+    fn end_pass_pipeline_statistics_query_if_requested(&mut self) {
+        if let Some((query_set_raw, index)) = self.pass_pipeline_statistics_query.take() {
+            unsafe {
+                self.list.as_ref().unwrap().EndQuery(
+                    &query_set_raw,
+                    Direct3D12::D3D12_QUERY_TYPE_PIPELINE_STATISTICS,
+                    index,
+                )
+            };
+        }
+    }
+
+    /// Sets the viewports set so far in the pass, each with the pass scissor.
+    fn flush_viewports(&self) {
+        let count = self.pass.viewport_count;
+        let scissors = [self.pass.scissor; wgt::MAX_VIEWPORTS as usize];
+        let list = self.list.as_ref().unwrap();
+        unsafe {
+            list.RSSetViewports(&self.pass.viewports[..count]);
+            list.RSSetScissorRects(&scissors[..count]);
+        }
+    }
 }
 
 impl crate::CommandEncoder for super::CommandEncoder {
@@ -334,12 +400,8 @@ impl crate::CommandEncoder for super::CommandEncoder {
             list
         } else {
             unsafe {
-                self.device.CreateCommandList(
-                    0,
-                    Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT,
-                    &self.allocator,
-                    None,
-                )
+                self.device
+                    .CreateCommandList(0, self.list_type, &self.allocator, None)
             }
             .into_device_result("Create command list")?
         };
@@ -372,6 +434,9 @@ impl crate::CommandEncoder for super::CommandEncoder {
         if let Err(e) = unsafe { self.allocator.Reset() } {
             log::error!("ID3D12CommandAllocator::Reset() failed with {e}");
         }
+        for handle in self.temp_resolve_srvs.drain(..) {
+            self.shared.heap_views.free_slice(handle);
+        }
     }
 
     unsafe fn transition_buffers<'a, T>(&mut self, barriers: T)
@@ -436,6 +501,26 @@ impl crate::CommandEncoder for super::CommandEncoder {
         self.temp.barriers.clear();
 
         for barrier in barriers {
+            if barrier.texture.allocation.is_in_heap()
+                && barrier.usage.from == wgt::TextureUses::UNINITIALIZED
+            {
+                // The memory may have been used by an aliasing texture.
+                self.temp.barriers.push(Direct3D12::D3D12_RESOURCE_BARRIER {
+                    Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_ALIASING,
+                    Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+                    Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
+                        Aliasing: mem::ManuallyDrop::new(
+                            Direct3D12::D3D12_RESOURCE_ALIASING_BARRIER {
+                                pResourceBefore: mem::ManuallyDrop::new(None),
+                                pResourceAfter: unsafe {
+                                    borrow_interface_temporarily(&barrier.texture.resource)
+                                },
+                            },
+                        ),
+                    },
+                });
+            }
+
             let s0 = conv::map_texture_usage_to_state(barrier.usage.from);
             let s1 = conv::map_texture_usage_to_state(barrier.usage.to);
             if s0 != s1 {
@@ -604,6 +689,84 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn resolve_texture<T>(&mut self, src: &super::Texture, dst: &super::Texture, regions: T)
+    where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        let list = self
+            .list
+            .as_ref()
+            .unwrap()
+            .cast::<Direct3D12::ID3D12GraphicsCommandList1>()
+            .unwrap();
+        let format = auxil::dxgi::conv::map_texture_format(src.format);
+
+        for r in regions {
+            let src_subresource = src.calc_subresource_for_copy(&r.src_base);
+            let dst_subresource = dst.calc_subresource_for_copy(&r.dst_base);
+
+            // The textures are in the copy states, but D3D12 has special
+            // source/destination states for the resolves.
+            self.temp.barriers.clear();
+            for (resource, subresource, state_before, state_after) in [
+                (
+                    &src.resource,
+                    src_subresource,
+                    Direct3D12::D3D12_RESOURCE_STATE_COPY_SOURCE,
+                    Direct3D12::D3D12_RESOURCE_STATE_RESOLVE_SOURCE,
+                ),
+                (
+                    &dst.resource,
+                    dst_subresource,
+                    Direct3D12::D3D12_RESOURCE_STATE_COPY_DEST,
+                    Direct3D12::D3D12_RESOURCE_STATE_RESOLVE_DEST,
+                ),
+            ] {
+                self.temp.barriers.push(Direct3D12::D3D12_RESOURCE_BARRIER {
+                    Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+                    Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+                    Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
+                        Transition: mem::ManuallyDrop::new(
+                            Direct3D12::D3D12_RESOURCE_TRANSITION_BARRIER {
+                                pResource: unsafe { borrow_interface_temporarily(resource) },
+                                Subresource: subresource,
+                                StateBefore: state_before,
+                                StateAfter: state_after,
+                            },
+                        ),
+                    },
+                });
+            }
+            unsafe { list.ResourceBarrier(&self.temp.barriers) };
+
+            let src_rect = Foundation::RECT {
+                left: r.src_base.origin.x as i32,
+                top: r.src_base.origin.y as i32,
+                right: (r.src_base.origin.x + r.size.width) as i32,
+                bottom: (r.src_base.origin.y + r.size.height) as i32,
+            };
+            unsafe {
+                list.ResolveSubresourceRegion(
+                    &dst.resource,
+                    dst_subresource,
+                    r.dst_base.origin.x,
+                    r.dst_base.origin.y,
+                    &src.resource,
+                    src_subresource,
+                    Some(&src_rect),
+                    format,
+                    Direct3D12::D3D12_RESOLVE_MODE_AVERAGE,
+                )
+            };
+
+            for barrier in self.temp.barriers.iter_mut() {
+                let transition = unsafe { &mut *barrier.Anonymous.Transition };
+                mem::swap(&mut transition.StateBefore, &mut transition.StateAfter);
+            }
+            unsafe { list.ResourceBarrier(&self.temp.barriers) };
+        }
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -724,6 +887,29 @@ impl crate::CommandEncoder for super::CommandEncoder {
             )
         }
     }
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        acceleration_structure: &super::AccelerationStructure,
+        buf: &super::Buffer,
+    ) {
+        let list = self
+            .list
+            .as_ref()
+            .unwrap()
+            .cast::<Direct3D12::ID3D12GraphicsCommandList4>()
+            .unwrap();
+        unsafe {
+            list.EmitRaytracingAccelerationStructurePostbuildInfo(
+                &Direct3D12::D3D12_RAYTRACING_ACCELERATION_STRUCTURE_POSTBUILD_INFO_DESC {
+                    DestBuffer: buf.resource.GetGPUVirtualAddress(),
+                    InfoType: Direct3D12::D3D12_RAYTRACING_ACCELERATION_STRUCTURE_POSTBUILD_INFO_SERIALIZATION,
+                },
+                &[
+                    acceleration_structure.resource.GetGPUVirtualAddress()
+                ],
+            )
+        }
+    }
     unsafe fn reset_queries(&mut self, _set: &super::QuerySet, _range: Range<u32>) {
         // nothing to do here
     }
@@ -735,15 +921,71 @@ impl crate::CommandEncoder for super::CommandEncoder {
         offset: wgt::BufferAddress,
         _stride: wgt::BufferSize,
     ) {
+        let list = self.list.as_ref().unwrap();
+        let Some(statistics) = set.statistics.as_ref() else {
+            unsafe {
+                list.ResolveQueryData(
+                    &set.raw,
+                    set.raw_ty,
+                    range.start,
+                    range.end - range.start,
+                    &buffer.resource,
+                    offset,
+                )
+            };
+            return;
+        };
+
+        let raw_size = size_of::<Direct3D12::D3D12_QUERY_DATA_PIPELINE_STATISTICS>() as u64;
+        let transition = |before, after| Direct3D12::D3D12_RESOURCE_BARRIER {
+            Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+            Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+            Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
+                Transition: mem::ManuallyDrop::new(Direct3D12::D3D12_RESOURCE_TRANSITION_BARRIER {
+                    pResource: unsafe { borrow_interface_temporarily(&statistics.buffer) },
+                    Subresource: Direct3D12::D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
+                    StateBefore: before,
+                    StateAfter: after,
+                }),
+            },
+        };
+
         unsafe {
-            self.list.as_ref().unwrap().ResolveQueryData(
+            list.ResolveQueryData(
                 &set.raw,
                 set.raw_ty,
                 range.start,
                 range.end - range.start,
-                &buffer.resource,
-                offset,
-            )
+                &statistics.buffer,
+                range.start as u64 * raw_size,
+            );
+            list.ResourceBarrier(&[transition(
+                Direct3D12::D3D12_RESOURCE_STATE_COPY_DEST,
+                Direct3D12::D3D12_RESOURCE_STATE_COPY_SOURCE,
+            )]);
+        }
+
+        let mut dst_offset = offset;
+        for index in range {
+            for &src_offset in statistics.offsets.iter() {
+                unsafe {
+                    list.CopyBufferRegion(
+                        &buffer.resource,
+                        dst_offset,
+                        &statistics.buffer,
+                        index as u64 * raw_size + src_offset,
+                        size_of::<u64>() as u64,
+                    )
+                };
+                dst_offset += size_of::<u64>() as u64;
+            }
+        }
+
+        unsafe {
+            list.ResourceBarrier(&[transition(
+                Direct3D12::D3D12_RESOURCE_STATE_COPY_SOURCE,
+                Direct3D12::D3D12_RESOURCE_STATE_COPY_DEST,
+            )])
         };
     }
 
@@ -767,6 +1009,8 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 .map(|index| (timestamp_writes.query_set.raw.clone(), index));
         }
 
+        self.begin_pass_pipeline_statistics_query(desc.pipeline_statistics_query.as_ref());
+
         let mut color_views =
             [Direct3D12::D3D12_CPU_DESCRIPTOR_HANDLE { ptr: 0 }; crate::MAX_COLOR_ATTACHMENTS];
         let mut rtv_pool = self.rtv_pool.lock();
@@ -825,7 +1069,11 @@ impl crate::CommandEncoder for super::CommandEncoder {
         self.pass.resolves.clear();
         for (rtv, cat) in color_views.iter().zip(desc.color_attachments.iter()) {
             if let Some(cat) = cat.as_ref() {
-                if !cat.ops.contains(crate::AttachmentOps::LOAD) {
+                if !cat
+                    .ops
+                    .with_pass_flags(desc.flags)
+                    .contains(crate::AttachmentOps::LOAD)
+                {
                     let value = [
                         cat.clear_value.r as f32,
                         cat.clear_value.g as f32,
@@ -834,7 +1082,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     ];
                     unsafe { list.ClearRenderTargetView(*rtv, &value, None) };
                 }
-                if let Some(ref target) = cat.resolve_target {
+                if let Some(target) = cat.resolve_target.as_ref().filter(|_| desc.resolves()) {
                     self.pass.resolves.push(super::PassResolve {
                         src: (
                             cat.target.view.texture.clone(),
@@ -850,12 +1098,23 @@ impl crate::CommandEncoder for super::CommandEncoder {
         if let Some(ref ds) = desc.depth_stencil_attachment {
             let mut flags = Direct3D12::D3D12_CLEAR_FLAGS::default();
             let aspects = ds.target.view.aspects;
-            if !ds.depth_ops.contains(crate::AttachmentOps::LOAD)
+            if let Some(resolve) = ds.resolve.as_ref().filter(|_| desc.resolves()) {
+                self.pass.depth_stencil_resolve = Some(
+                    super::depth_resolve::PassDepthStencilResolve::new(ds, resolve, desc.extent),
+                );
+            }
+            if !ds
+                .depth_ops
+                .with_pass_flags(desc.flags)
+                .contains(crate::AttachmentOps::LOAD)
                 && aspects.contains(crate::FormatAspects::DEPTH)
             {
                 flags |= Direct3D12::D3D12_CLEAR_FLAG_DEPTH;
             }
-            if !ds.stencil_ops.contains(crate::AttachmentOps::LOAD)
+            if !ds
+                .stencil_ops
+                .with_pass_flags(desc.flags)
+                .contains(crate::AttachmentOps::LOAD)
                 && aspects.contains(crate::FormatAspects::STENCIL)
             {
                 flags |= Direct3D12::D3D12_CLEAR_FLAG_STENCIL;
@@ -901,13 +1160,17 @@ impl crate::CommandEncoder for super::CommandEncoder {
             right: desc.extent.width as i32,
             bottom: desc.extent.height as i32,
         };
-        unsafe { list.RSSetViewports(core::slice::from_ref(&raw_vp)) };
-        unsafe { list.RSSetScissorRects(core::slice::from_ref(&raw_rect)) };
+        self.pass.viewports = [raw_vp; wgt::MAX_VIEWPORTS as usize];
+        self.pass.viewport_count = 1;
+        self.pass.scissor = raw_rect;
+        self.flush_viewports();
 
         Ok(())
     }
 
     unsafe fn end_render_pass(&mut self) {
+        self.end_pass_pipeline_statistics_query_if_requested();
+
         if !self.pass.resolves.is_empty() {
             let list = self.list.as_ref().unwrap();
             self.temp.barriers.clear();
@@ -980,11 +1243,28 @@ impl crate::CommandEncoder for super::CommandEncoder {
             }
         }
 
+        if let Some(resolve) = self.pass.depth_stencil_resolve.take() {
+            unsafe { self.resolve_depth_stencil(resolve) };
+        }
+
         self.write_pass_end_timestamp_if_requested();
 
         unsafe { self.end_pass() };
     }
 
+    unsafe fn create_parallel_render_encoder(
+        &mut self,
+    ) -> Result<super::CommandEncoder, crate::DeviceError> {
+        Err(crate::DeviceError::Unexpected)
+    }
+
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        _encoders: Vec<super::CommandEncoder>,
+    ) -> Result<(), crate::DeviceError> {
+        unreachable!("parallel render encoders are not supported on DX12")
+    }
+
     unsafe fn set_bind_group(
         &mut self,
         layout: &super::PipelineLayout,
@@ -1154,7 +1434,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
 
     unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {
-        let raw_vp = Direct3D12::D3D12_VIEWPORT {
+        unsafe { self.set_viewport_indexed(0, rect, depth_range) };
+    }
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+        let index = index as usize;
+        self.pass.viewports[index] = Direct3D12::D3D12_VIEWPORT {
             TopLeftX: rect.x,
             TopLeftY: rect.y,
             Width: rect.w,
@@ -1162,26 +1451,17 @@ impl crate::CommandEncoder for super::CommandEncoder {
             MinDepth: depth_range.start,
             MaxDepth: depth_range.end,
         };
-        unsafe {
-            self.list
-                .as_ref()
-                .unwrap()
-                .RSSetViewports(core::slice::from_ref(&raw_vp))
-        }
+        self.pass.viewport_count = self.pass.viewport_count.max(index + 1);
+        self.flush_viewports();
     }
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
-        let raw_rect = Foundation::RECT {
+        self.pass.scissor = Foundation::RECT {
             left: rect.x as i32,
             top: rect.y as i32,
             right: (rect.x + rect.w) as i32,
             bottom: (rect.y + rect.h) as i32,
         };
-        unsafe {
-            self.list
-                .as_ref()
-                .unwrap()
-                .RSSetScissorRects(core::slice::from_ref(&raw_rect))
-        }
+        self.flush_viewports();
     }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
         unsafe { self.list.as_ref().unwrap().OMSetStencilRef(value) }
@@ -1189,6 +1469,45 @@ impl crate::CommandEncoder for super::CommandEncoder {
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
         unsafe { self.list.as_ref().unwrap().OMSetBlendFactor(Some(color)) }
     }
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate) {
+        let list = self
+            .list
+            .as_ref()
+            .unwrap()
+            .cast::<Direct3D12::ID3D12GraphicsCommandList5>()
+            .unwrap();
+        let rate = conv::map_shading_rate(
+            rate,
+            self.shared.private_caps.additional_shading_rates_supported,
+        );
+        unsafe { list.RSSetShadingRate(rate, None) }
+    }
+
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+    ) {
+        // Commands are skipped while predication is enabled, which `EQUAL_ZERO` does
+        // when the predicate is zero.
+        unsafe {
+            self.list.as_ref().unwrap().SetPredication(
+                &buffer.resource,
+                offset,
+                Direct3D12::D3D12_PREDICATION_OP_EQUAL_ZERO,
+            )
+        }
+    }
+
+    unsafe fn end_conditional_rendering(&mut self) {
+        unsafe {
+            self.list.as_ref().unwrap().SetPredication(
+                None,
+                0,
+                Direct3D12::D3D12_PREDICATION_OP_EQUAL_ZERO,
+            )
+        }
+    }
 
     unsafe fn draw(
         &mut self,
@@ -1228,11 +1547,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
     unsafe fn draw_mesh_tasks(
         &mut self,
-        _group_count_x: u32,
-        _group_count_y: u32,
-        _group_count_z: u32,
+        group_count_x: u32,
+        group_count_y: u32,
+        group_count_z: u32,
     ) {
-        unreachable!()
+        self.update_root_elements();
+        let list = self
+            .list
+            .as_ref()
+            .unwrap()
+            .cast::<Direct3D12::ID3D12GraphicsCommandList6>()
+            .unwrap();
+        unsafe { list.DispatchMesh(group_count_x, group_count_y, group_count_z) }
     }
     unsafe fn draw_indirect(
         &mut self,
@@ -1314,11 +1640,21 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
     unsafe fn draw_mesh_tasks_indirect(
         &mut self,
-        _buffer: &<Self::A as crate::Api>::Buffer,
-        _offset: wgt::BufferAddress,
-        _draw_count: u32,
+        buffer: &<Self::A as crate::Api>::Buffer,
+        offset: wgt::BufferAddress,
+        draw_count: u32,
     ) {
-        unreachable!()
+        self.update_root_elements();
+        unsafe {
+            self.list.as_ref().unwrap().ExecuteIndirect(
+                self.shared.cmd_signatures.draw_mesh_tasks.as_ref().unwrap(),
+                draw_count,
+                &buffer.resource,
+                offset,
+                None,
+                0,
+            )
+        }
     }
     unsafe fn draw_indirect_count(
         &mut self,
@@ -1362,13 +1698,23 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
     unsafe fn draw_mesh_tasks_indirect_count(
         &mut self,
-        _buffer: &<Self::A as crate::Api>::Buffer,
-        _offset: wgt::BufferAddress,
-        _count_buffer: &<Self::A as crate::Api>::Buffer,
-        _count_offset: wgt::BufferAddress,
-        _max_count: u32,
+        buffer: &<Self::A as crate::Api>::Buffer,
+        offset: wgt::BufferAddress,
+        count_buffer: &<Self::A as crate::Api>::Buffer,
+        count_offset: wgt::BufferAddress,
+        max_count: u32,
     ) {
-        unreachable!()
+        self.update_root_elements();
+        unsafe {
+            self.list.as_ref().unwrap().ExecuteIndirect(
+                self.shared.cmd_signatures.draw_mesh_tasks.as_ref().unwrap(),
+                max_count,
+                &buffer.resource,
+                offset,
+                &count_buffer.resource,
+                count_offset,
+            )
+        }
     }
 
     // compute
@@ -1389,8 +1735,11 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 .end_of_pass_write_index
                 .map(|index| (timestamp_writes.query_set.raw.clone(), index));
         }
+
+        self.begin_pass_pipeline_statistics_query(desc.pipeline_statistics_query.as_ref());
     }
     unsafe fn end_compute_pass(&mut self) {
+        self.end_pass_pipeline_statistics_query_if_requested();
         self.write_pass_end_timestamp_if_requested();
         unsafe { self.end_pass() };
     }
@@ -1446,6 +1795,63 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn set_ray_tracing_pipeline(&mut self, pipeline: &super::RayTracingPipeline) {
+        let list = self.list.clone().unwrap();
+
+        // Ray tracing pipelines are bound with the compute root signature.
+        if self.pass.layout.signature != pipeline.layout.signature {
+            // D3D12 requires full reset on signature change
+            unsafe { list.SetComputeRootSignature(pipeline.layout.signature.as_ref()) };
+            self.reset_signature(&pipeline.layout);
+        };
+
+        let list = list
+            .cast::<Direct3D12::ID3D12GraphicsCommandList4>()
+            .unwrap();
+        unsafe { list.SetPipelineState1(&pipeline.raw) }
+    }
+
+    unsafe fn trace_rays(
+        &mut self,
+        tables: &crate::ShaderBindingTables<super::Buffer>,
+        [width, height, depth]: [u32; 3],
+    ) {
+        self.update_root_elements();
+
+        let range = |table: Option<&crate::ShaderBindingTable<super::Buffer>>| {
+            let Some(table) = table else {
+                return Direct3D12::D3D12_GPU_VIRTUAL_ADDRESS_RANGE_AND_STRIDE::default();
+            };
+            Direct3D12::D3D12_GPU_VIRTUAL_ADDRESS_RANGE_AND_STRIDE {
+                StartAddress: unsafe { table.buffer.resource.GetGPUVirtualAddress() }
+                    + table.region.offset,
+                SizeInBytes: table.region.size,
+                StrideInBytes: table.region.stride,
+            }
+        };
+        let ray_generation = range(Some(&tables.ray_generation));
+        let desc = Direct3D12::D3D12_DISPATCH_RAYS_DESC {
+            RayGenerationShaderRecord: Direct3D12::D3D12_GPU_VIRTUAL_ADDRESS_RANGE {
+                StartAddress: ray_generation.StartAddress,
+                SizeInBytes: ray_generation.SizeInBytes,
+            },
+            MissShaderTable: range(tables.miss.as_ref()),
+            HitGroupTable: range(tables.hit.as_ref()),
+            CallableShaderTable: range(tables.callable.as_ref()),
+            Width: width,
+            Height: height,
+            Depth: depth,
+        };
+
+        let list = self
+            .list
+            .as_ref()
+            .unwrap()
+            .cast::<Direct3D12::ID3D12GraphicsCommandList4>()
+            .unwrap();
+        unsafe { list.DispatchRays(&desc) }
+    }
+
     unsafe fn build_acceleration_structures<'a, T>(
         &mut self,
         _descriptor_count: u32,
@@ -1668,4 +2074,46 @@ impl crate::CommandEncoder for super::CommandEncoder {
             )
         }
     }
+
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        src: &super::AccelerationStructure,
+        dst: &super::Buffer,
+        dst_offset: wgt::BufferAddress,
+    ) {
+        let list = self
+            .list
+            .as_ref()
+            .unwrap()
+            .cast::<Direct3D12::ID3D12GraphicsCommandList4>()
+            .unwrap();
+        unsafe {
+            list.CopyRaytracingAccelerationStructure(
+                dst.resource.GetGPUVirtualAddress() + dst_offset,
+                src.resource.GetGPUVirtualAddress(),
+                Direct3D12::D3D12_RAYTRACING_ACCELERATION_STRUCTURE_COPY_MODE_SERIALIZE,
+            )
+        }
+    }
+
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        src: &super::Buffer,
+        src_offset: wgt::BufferAddress,
+        dst: &super::AccelerationStructure,
+    ) {
+        let list = self
+            .list
+            .as_ref()
+            .unwrap()
+            .cast::<Direct3D12::ID3D12GraphicsCommandList4>()
+            .unwrap();
+        unsafe {
+            list.CopyRaytracingAccelerationStructure(
+                dst.resource.GetGPUVirtualAddress(),
+                src.resource.GetGPUVirtualAddress() + src_offset,
+                Direct3D12::D3D12_RAYTRACING_ACCELERATION_STRUCTURE_COPY_MODE_DESERIALIZE,
+            )
+        }
+    }
 }
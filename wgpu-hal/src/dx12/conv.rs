@@ -1,3 +1,5 @@
+use core::mem;
+
 use windows::Win32::Graphics::{Direct3D, Direct3D12, Dxgi};
 
 pub fn map_buffer_usage_to_resource_flags(
@@ -32,6 +34,14 @@ pub fn map_buffer_descriptor(
     }
 }
 
+pub fn map_queue_type(queue_type: wgt::QueueType) -> Direct3D12::D3D12_COMMAND_LIST_TYPE {
+    match queue_type {
+        wgt::QueueType::Graphics => Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT,
+        wgt::QueueType::Compute => Direct3D12::D3D12_COMMAND_LIST_TYPE_COMPUTE,
+        wgt::QueueType::Transfer => Direct3D12::D3D12_COMMAND_LIST_TYPE_COPY,
+    }
+}
+
 pub fn map_texture_dimension(dim: wgt::TextureDimension) -> Direct3D12::D3D12_RESOURCE_DIMENSION {
     match dim {
         wgt::TextureDimension::D1 => Direct3D12::D3D12_RESOURCE_DIMENSION_TEXTURE1D,
@@ -99,6 +109,59 @@ pub fn map_comparison(func: wgt::CompareFunction) -> Direct3D12::D3D12_COMPARISO
     }
 }
 
+pub fn map_shading_rate(
+    rate: wgt::ShadingRate,
+    additional_rates: bool,
+) -> Direct3D12::D3D12_SHADING_RATE {
+    use wgt::ShadingRate as Sr;
+    match rate {
+        Sr::OneByOne => Direct3D12::D3D12_SHADING_RATE_1X1,
+        Sr::OneByTwo => Direct3D12::D3D12_SHADING_RATE_1X2,
+        Sr::TwoByOne => Direct3D12::D3D12_SHADING_RATE_2X1,
+        Sr::TwoByTwo => Direct3D12::D3D12_SHADING_RATE_2X2,
+        Sr::TwoByFour | Sr::FourByTwo | Sr::FourByFour if !additional_rates => {
+            Direct3D12::D3D12_SHADING_RATE_2X2
+        }
+        Sr::TwoByFour => Direct3D12::D3D12_SHADING_RATE_2X4,
+        Sr::FourByTwo => Direct3D12::D3D12_SHADING_RATE_4X2,
+        Sr::FourByFour => Direct3D12::D3D12_SHADING_RATE_4X4,
+    }
+}
+
+/// Returns the offsets of the statistics in a `D3D12_QUERY_DATA_PIPELINE_STATISTICS`,
+/// in the order they are resolved in.
+pub fn map_pipeline_statistics(types: wgt::PipelineStatisticsTypes) -> arrayvec::ArrayVec<u64, 5> {
+    use wgt::PipelineStatisticsTypes as Pst;
+    type Data = Direct3D12::D3D12_QUERY_DATA_PIPELINE_STATISTICS;
+
+    [
+        (
+            Pst::VERTEX_SHADER_INVOCATIONS,
+            mem::offset_of!(Data, VSInvocations),
+        ),
+        (
+            Pst::CLIPPER_INVOCATIONS,
+            mem::offset_of!(Data, CInvocations),
+        ),
+        (
+            Pst::CLIPPER_PRIMITIVES_OUT,
+            mem::offset_of!(Data, CPrimitives),
+        ),
+        (
+            Pst::FRAGMENT_SHADER_INVOCATIONS,
+            mem::offset_of!(Data, PSInvocations),
+        ),
+        (
+            Pst::COMPUTE_SHADER_INVOCATIONS,
+            mem::offset_of!(Data, CSInvocations),
+        ),
+    ]
+    .into_iter()
+    .filter(|&(ty, _)| types.contains(ty))
+    .map(|(_, offset)| offset as u64)
+    .collect()
+}
+
 pub fn map_border_color(border_color: Option<wgt::SamplerBorderColor>) -> [f32; 4] {
     use wgt::SamplerBorderColor as Sbc;
     match border_color {
@@ -112,6 +175,8 @@ pub fn map_visibility(visibility: wgt::ShaderStages) -> Direct3D12::D3D12_SHADER
     match visibility {
         wgt::ShaderStages::VERTEX => Direct3D12::D3D12_SHADER_VISIBILITY_VERTEX,
         wgt::ShaderStages::FRAGMENT => Direct3D12::D3D12_SHADER_VISIBILITY_PIXEL,
+        wgt::ShaderStages::TASK => Direct3D12::D3D12_SHADER_VISIBILITY_AMPLIFICATION,
+        wgt::ShaderStages::MESH => Direct3D12::D3D12_SHADER_VISIBILITY_MESH,
         _ => Direct3D12::D3D12_SHADER_VISIBILITY_ALL,
     }
 }
@@ -136,6 +201,7 @@ pub fn map_binding_type(ty: &wgt::BindingType) -> Direct3D12::D3D12_DESCRIPTOR_R
         | Bt::StorageTexture { .. } => Direct3D12::D3D12_DESCRIPTOR_RANGE_TYPE_UAV,
         Bt::AccelerationStructure { .. } => Direct3D12::D3D12_DESCRIPTOR_RANGE_TYPE_SRV,
         Bt::ExternalTexture => unimplemented!(),
+        Bt::YcbcrTexture { .. } => unimplemented!(),
     }
 }
 
@@ -167,6 +233,9 @@ pub fn map_buffer_usage_to_state(usage: wgt::BufferUses) -> Direct3D12::D3D12_RE
     if usage.intersects(Bu::ACCELERATION_STRUCTURE_QUERY) {
         state |= Direct3D12::D3D12_RESOURCE_STATE_UNORDERED_ACCESS;
     }
+    if usage.intersects(Bu::SHADER_BINDING_TABLE) {
+        state |= Direct3D12::D3D12_RESOURCE_STATE_NON_PIXEL_SHADER_RESOURCE;
+    }
     state
 }
 
@@ -298,6 +367,74 @@ fn map_blend_component(
     (raw_op, raw_src, raw_dst)
 }
 
+fn map_logic_op(logic_op: wgt::LogicOp) -> Direct3D12::D3D12_LOGIC_OP {
+    use wgt::LogicOp as Lo;
+    match logic_op {
+        Lo::Clear => Direct3D12::D3D12_LOGIC_OP_CLEAR,
+        Lo::And => Direct3D12::D3D12_LOGIC_OP_AND,
+        Lo::AndReverse => Direct3D12::D3D12_LOGIC_OP_AND_REVERSE,
+        Lo::Copy => Direct3D12::D3D12_LOGIC_OP_COPY,
+        Lo::AndInverted => Direct3D12::D3D12_LOGIC_OP_AND_INVERTED,
+        Lo::NoOp => Direct3D12::D3D12_LOGIC_OP_NOOP,
+        Lo::Xor => Direct3D12::D3D12_LOGIC_OP_XOR,
+        Lo::Or => Direct3D12::D3D12_LOGIC_OP_OR,
+        Lo::Nor => Direct3D12::D3D12_LOGIC_OP_NOR,
+        Lo::Equivalent => Direct3D12::D3D12_LOGIC_OP_EQUIV,
+        Lo::Invert => Direct3D12::D3D12_LOGIC_OP_INVERT,
+        Lo::OrReverse => Direct3D12::D3D12_LOGIC_OP_OR_REVERSE,
+        Lo::CopyInverted => Direct3D12::D3D12_LOGIC_OP_COPY_INVERTED,
+        Lo::OrInverted => Direct3D12::D3D12_LOGIC_OP_OR_INVERTED,
+        Lo::Nand => Direct3D12::D3D12_LOGIC_OP_NAND,
+        Lo::Set => Direct3D12::D3D12_LOGIC_OP_SET,
+    }
+}
+
+pub fn map_rasterizer(
+    primitive: &wgt::PrimitiveState,
+    depth_stencil: Option<&wgt::DepthStencilState>,
+    sample_count: u32,
+) -> Direct3D12::D3D12_RASTERIZER_DESC {
+    let bias = depth_stencil.map(|ds| ds.bias).unwrap_or_default();
+
+    Direct3D12::D3D12_RASTERIZER_DESC {
+        FillMode: map_polygon_mode(primitive.polygon_mode),
+        CullMode: match primitive.cull_mode {
+            None => Direct3D12::D3D12_CULL_MODE_NONE,
+            Some(wgt::Face::Front) => Direct3D12::D3D12_CULL_MODE_FRONT,
+            Some(wgt::Face::Back) => Direct3D12::D3D12_CULL_MODE_BACK,
+        },
+        FrontCounterClockwise: match primitive.front_face {
+            wgt::FrontFace::Cw => false.into(),
+            wgt::FrontFace::Ccw => true.into(),
+        },
+        DepthBias: bias.constant,
+        DepthBiasClamp: bias.clamp,
+        SlopeScaledDepthBias: bias.slope_scale,
+        DepthClipEnable: (!primitive.unclipped_depth).into(),
+        MultisampleEnable: (sample_count > 1).into(),
+        ForcedSampleCount: 0,
+        AntialiasedLineEnable: false.into(),
+        ConservativeRaster: if primitive.conservative {
+            Direct3D12::D3D12_CONSERVATIVE_RASTERIZATION_MODE_ON
+        } else {
+            Direct3D12::D3D12_CONSERVATIVE_RASTERIZATION_MODE_OFF
+        },
+    }
+}
+
+pub fn map_render_target_formats(
+    color_targets: &[Option<wgt::ColorTargetState>],
+) -> [Dxgi::Common::DXGI_FORMAT; Direct3D12::D3D12_SIMULTANEOUS_RENDER_TARGET_COUNT as usize] {
+    let mut rtv_formats = [Dxgi::Common::DXGI_FORMAT_UNKNOWN;
+        Direct3D12::D3D12_SIMULTANEOUS_RENDER_TARGET_COUNT as usize];
+    for (rtv_format, ct) in rtv_formats.iter_mut().zip(color_targets) {
+        if let Some(ct) = ct.as_ref() {
+            *rtv_format = crate::auxil::dxgi::conv::map_texture_format(ct.format);
+        }
+    }
+    rtv_formats
+}
+
 pub fn map_render_targets(
     color_targets: &[Option<wgt::ColorTargetState>],
 ) -> [Direct3D12::D3D12_RENDER_TARGET_BLEND_DESC;
@@ -331,6 +468,10 @@ pub fn map_render_targets(
                 raw.SrcBlendAlpha = alpha_src;
                 raw.DestBlendAlpha = alpha_dst;
             }
+            if let Some(logic_op) = ct.logic_op {
+                raw.LogicOpEnable = true.into();
+                raw.LogicOp = map_logic_op(logic_op);
+            }
         }
     }
 
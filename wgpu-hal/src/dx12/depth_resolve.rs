@@ -0,0 +1,466 @@
+//! Resolve of multisampled depth/stencil attachments.
+//!
+//! `ResolveSubresource` only takes color formats, so depth is resolved by a
+//! fullscreen triangle that reads all the samples of the source and writes
+//! `SV_Depth`. Pixel shaders can't write stencil, so the stencil of the target
+//! is cleared and then rebuilt one bit at a time: each of eight draws discards
+//! the pixels where sample zero doesn't have the bit set, and replaces the bit
+//! everywhere else.
+
+use alloc::format;
+use core::mem;
+
+use arrayvec::ArrayVec;
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+use windows::Win32::{
+    Foundation,
+    Graphics::{Direct3D, Direct3D12, Dxgi},
+};
+use windows_core::Interface;
+
+use super::borrow_interface_temporarily;
+use crate::auxil::{self, dxgi::result::HResult as _};
+
+const VERTEX_SHADER: &str = r"
+float4 main(uint id : SV_VertexID) : SV_Position {
+    float2 uv = float2((id << 1) & 2, id & 2);
+    return float4(uv * float2(2.0, -2.0) + float2(-1.0, 1.0), 0.0, 1.0);
+}
+";
+
+const DEPTH_SHADER: &str = r"
+cbuffer Params : register(b0) { uint mode; uint stencil_bit; };
+Texture2DMS<float> source : register(t0);
+
+float main(float4 position : SV_Position) : SV_Depth {
+    uint2 coord = uint2(position.xy);
+    uint width, height, samples;
+    source.GetDimensions(width, height, samples);
+    float depth = source.Load(coord, 0);
+    if (mode != 0) {
+        for (uint i = 1; i < samples; i++) {
+            float value = source.Load(coord, i);
+            depth = mode == 1 ? min(depth, value) : max(depth, value);
+        }
+    }
+    return depth;
+}
+";
+
+const STENCIL_SHADER: &str = r"
+cbuffer Params : register(b0) { uint mode; uint stencil_bit; };
+Texture2DMS<uint2> source : register(t0);
+
+void main(float4 position : SV_Position) {
+    uint stencil = source.Load(uint2(position.xy), 0).g;
+    if ((stencil & (1u << stencil_bit)) == 0) {
+        discard;
+    }
+}
+";
+
+/// Root parameter with the resolve mode and the stencil bit.
+const ROOT_CONSTANTS: u32 = 0;
+/// Root parameter with the descriptor table of the source SRV.
+const ROOT_SOURCE: u32 = 1;
+
+/// What is needed to read a multisampled depth/stencil view in a shader.
+///
+/// Only created for views of depth/stencil attachments when
+/// [`wgt::Features::DEPTH_STENCIL_RESOLVE`] is enabled.
+#[derive(Debug)]
+pub(super) struct ResolveSource {
+    pub depth_format: Option<Dxgi::Common::DXGI_FORMAT>,
+    pub stencil_format: Option<Dxgi::Common::DXGI_FORMAT>,
+    pub array_layer: u32,
+    /// Subresources of the aspects of the view.
+    pub subresources: ArrayVec<u32, 2>,
+}
+
+impl ResolveSource {
+    pub(super) fn new(
+        texture: &super::Texture,
+        desc: &crate::TextureViewDescriptor,
+    ) -> Option<Self> {
+        if texture.sample_count <= 1 {
+            return None;
+        }
+        let aspects = crate::FormatAspects::new(texture.format, desc.range.aspect);
+        let mip = desc.range.base_mip_level;
+        let layer = desc.range.base_array_layer;
+
+        let mut source = Self {
+            depth_format: None,
+            stencil_format: None,
+            array_layer: layer,
+            subresources: ArrayVec::new(),
+        };
+        if aspects.contains(crate::FormatAspects::DEPTH) {
+            source.depth_format = auxil::dxgi::conv::map_texture_format_for_srv_uav(
+                texture.format,
+                crate::FormatAspects::DEPTH,
+            );
+            source
+                .subresources
+                .push(texture.calc_subresource(mip, layer, 0));
+        }
+        if aspects.contains(crate::FormatAspects::STENCIL) {
+            source.stencil_format = auxil::dxgi::conv::map_texture_format_for_srv_uav(
+                texture.format,
+                crate::FormatAspects::STENCIL,
+            );
+            source
+                .subresources
+                .push(texture.calc_subresource(mip, layer, 1));
+        }
+        Some(source)
+    }
+}
+
+/// A depth/stencil resolve to run at the end of the current render pass.
+pub(super) struct PassDepthStencilResolve {
+    src: Direct3D12::ID3D12Resource,
+    src_state: Direct3D12::D3D12_RESOURCE_STATES,
+    src_subresources: ArrayVec<u32, 2>,
+    depth_srv: Option<Direct3D12::D3D12_SHADER_RESOURCE_VIEW_DESC>,
+    stencil_srv: Option<Direct3D12::D3D12_SHADER_RESOURCE_VIEW_DESC>,
+    dst_dsv: Direct3D12::D3D12_CPU_DESCRIPTOR_HANDLE,
+    dsv_format: Dxgi::Common::DXGI_FORMAT,
+    depth_mode: u32,
+    extent: wgt::Extent3d,
+}
+
+impl PassDepthStencilResolve {
+    pub(super) fn new(
+        ds: &crate::DepthStencilAttachment<super::TextureView>,
+        resolve: &crate::DepthStencilResolve<super::TextureView>,
+        extent: wgt::Extent3d,
+    ) -> Self {
+        let view = ds.target.view;
+        let source = view
+            .resolve_source
+            .as_ref()
+            .expect("Depth/stencil resolve source wasn't created for resolves");
+        let srv = |format| {
+            let mut desc = Direct3D12::D3D12_SHADER_RESOURCE_VIEW_DESC {
+                Format: format,
+                ViewDimension: Direct3D12::D3D12_SRV_DIMENSION_TEXTURE2DMS,
+                Shader4ComponentMapping: Direct3D12::D3D12_DEFAULT_SHADER_4_COMPONENT_MAPPING,
+                Anonymous: Default::default(),
+            };
+            if source.array_layer != 0 {
+                desc.ViewDimension = Direct3D12::D3D12_SRV_DIMENSION_TEXTURE2DMSARRAY;
+                desc.Anonymous.Texture2DMSArray = Direct3D12::D3D12_TEX2DMS_ARRAY_SRV {
+                    FirstArraySlice: source.array_layer,
+                    ArraySize: 1,
+                };
+            }
+            desc
+        };
+        Self {
+            src: view.texture.clone(),
+            src_state: super::conv::map_texture_usage_to_state(ds.target.usage),
+            src_subresources: source.subresources.clone(),
+            depth_srv: resolve.depth_mode.and(source.depth_format).map(srv),
+            stencil_srv: if resolve.resolve_stencil {
+                source.stencil_format.map(srv)
+            } else {
+                None
+            },
+            dst_dsv: resolve.target.view.handle_dsv_rw.as_ref().unwrap().raw,
+            dsv_format: resolve.target.view.raw_format,
+            depth_mode: match resolve.depth_mode {
+                None | Some(wgt::ResolveMode::SampleZero) => 0,
+                Some(wgt::ResolveMode::Min) => 1,
+                Some(wgt::ResolveMode::Max) => 2,
+            },
+            extent,
+        }
+    }
+}
+
+pub(super) struct DepthResolver {
+    signature: Direct3D12::ID3D12RootSignature,
+    vertex_shader: super::CompiledShader,
+    depth_shader: super::CompiledShader,
+    stencil_shader: super::CompiledShader,
+    /// Pipelines by DSV format and stencil bit, which is `None` for depth.
+    pipelines: Mutex<HashMap<(i32, Option<u8>), Direct3D12::ID3D12PipelineState>>,
+}
+
+unsafe impl Send for DepthResolver {}
+unsafe impl Sync for DepthResolver {}
+
+impl DepthResolver {
+    pub(super) fn new(device: &super::Device) -> Result<Self, crate::DeviceError> {
+        let source_range = Direct3D12::D3D12_DESCRIPTOR_RANGE {
+            RangeType: Direct3D12::D3D12_DESCRIPTOR_RANGE_TYPE_SRV,
+            NumDescriptors: 1,
+            BaseShaderRegister: 0,
+            RegisterSpace: 0,
+            OffsetInDescriptorsFromTableStart: 0,
+        };
+        let parameters = [
+            Direct3D12::D3D12_ROOT_PARAMETER {
+                ParameterType: Direct3D12::D3D12_ROOT_PARAMETER_TYPE_32BIT_CONSTANTS,
+                Anonymous: Direct3D12::D3D12_ROOT_PARAMETER_0 {
+                    Constants: Direct3D12::D3D12_ROOT_CONSTANTS {
+                        ShaderRegister: 0,
+                        RegisterSpace: 0,
+                        Num32BitValues: 2, // 0 = mode, 1 = stencil_bit
+                    },
+                },
+                ShaderVisibility: Direct3D12::D3D12_SHADER_VISIBILITY_PIXEL,
+            },
+            Direct3D12::D3D12_ROOT_PARAMETER {
+                ParameterType: Direct3D12::D3D12_ROOT_PARAMETER_TYPE_DESCRIPTOR_TABLE,
+                Anonymous: Direct3D12::D3D12_ROOT_PARAMETER_0 {
+                    DescriptorTable: Direct3D12::D3D12_ROOT_DESCRIPTOR_TABLE {
+                        NumDescriptorRanges: 1,
+                        pDescriptorRanges: &source_range,
+                    },
+                },
+                ShaderVisibility: Direct3D12::D3D12_SHADER_VISIBILITY_PIXEL,
+            },
+        ];
+        let blob = device.library.serialize_root_signature(
+            Direct3D12::D3D_ROOT_SIGNATURE_VERSION_1_0,
+            &parameters,
+            &[],
+            Direct3D12::D3D12_ROOT_SIGNATURE_FLAG_NONE,
+        )?;
+        let signature = unsafe {
+            device
+                .raw
+                .CreateRootSignature::<Direct3D12::ID3D12RootSignature>(0, blob.as_slice())
+        }
+        .into_device_result("Depth resolve root signature creation")?;
+
+        let shader_model = device.shared.private_caps.shader_model.to_str();
+        let compile = |source: &str, stage_bit, stage: &str| {
+            device
+                .compiler_container
+                .compile(
+                    device,
+                    source,
+                    None,
+                    "main",
+                    stage_bit,
+                    &format!("{stage}_{shader_model}"),
+                )
+                .map_err(|e| {
+                    log::error!("Depth resolve shader compilation failed: {e}");
+                    crate::DeviceError::Unexpected
+                })
+        };
+
+        Ok(Self {
+            signature,
+            vertex_shader: compile(VERTEX_SHADER, wgt::ShaderStages::VERTEX, "vs")?,
+            depth_shader: compile(DEPTH_SHADER, wgt::ShaderStages::FRAGMENT, "ps")?,
+            stencil_shader: compile(STENCIL_SHADER, wgt::ShaderStages::FRAGMENT, "ps")?,
+            pipelines: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn pipeline(
+        &self,
+        device: &Direct3D12::ID3D12Device,
+        format: Dxgi::Common::DXGI_FORMAT,
+        stencil_bit: Option<u8>,
+    ) -> Option<Direct3D12::ID3D12PipelineState> {
+        let mut pipelines = self.pipelines.lock();
+        if let Some(pipeline) = pipelines.get(&(format.0, stencil_bit)) {
+            return Some(pipeline.clone());
+        }
+
+        let stencil_face = Direct3D12::D3D12_DEPTH_STENCILOP_DESC {
+            StencilFailOp: Direct3D12::D3D12_STENCIL_OP_KEEP,
+            StencilDepthFailOp: Direct3D12::D3D12_STENCIL_OP_KEEP,
+            StencilPassOp: Direct3D12::D3D12_STENCIL_OP_REPLACE,
+            StencilFunc: Direct3D12::D3D12_COMPARISON_FUNC_ALWAYS,
+        };
+        let depth_stencil = match stencil_bit {
+            None => Direct3D12::D3D12_DEPTH_STENCIL_DESC {
+                DepthEnable: true.into(),
+                DepthWriteMask: Direct3D12::D3D12_DEPTH_WRITE_MASK_ALL,
+                DepthFunc: Direct3D12::D3D12_COMPARISON_FUNC_ALWAYS,
+                ..Default::default()
+            },
+            Some(bit) => Direct3D12::D3D12_DEPTH_STENCIL_DESC {
+                DepthEnable: false.into(),
+                DepthWriteMask: Direct3D12::D3D12_DEPTH_WRITE_MASK_ZERO,
+                DepthFunc: Direct3D12::D3D12_COMPARISON_FUNC_ALWAYS,
+                StencilEnable: true.into(),
+                StencilReadMask: 0xFF,
+                StencilWriteMask: 1 << bit,
+                FrontFace: stencil_face,
+                BackFace: stencil_face,
+            },
+        };
+
+        let desc = Direct3D12::D3D12_GRAPHICS_PIPELINE_STATE_DESC {
+            pRootSignature: unsafe { borrow_interface_temporarily(&self.signature) },
+            VS: self.vertex_shader.create_native_shader(),
+            PS: match stencil_bit {
+                None => self.depth_shader.create_native_shader(),
+                Some(_) => self.stencil_shader.create_native_shader(),
+            },
+            SampleMask: !0,
+            RasterizerState: Direct3D12::D3D12_RASTERIZER_DESC {
+                FillMode: Direct3D12::D3D12_FILL_MODE_SOLID,
+                CullMode: Direct3D12::D3D12_CULL_MODE_NONE,
+                DepthClipEnable: Foundation::FALSE,
+                ..Default::default()
+            },
+            DepthStencilState: depth_stencil,
+            PrimitiveTopologyType: Direct3D12::D3D12_PRIMITIVE_TOPOLOGY_TYPE_TRIANGLE,
+            DSVFormat: format,
+            SampleDesc: Dxgi::Common::DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            ..Default::default()
+        };
+        let pipeline: Direct3D12::ID3D12PipelineState =
+            match unsafe { device.CreateGraphicsPipelineState(&desc) } {
+                Ok(pipeline) => pipeline,
+                Err(e) => {
+                    log::error!("Depth resolve pipeline creation failed: {e}");
+                    return None;
+                }
+            };
+        pipelines.insert((format.0, stencil_bit), pipeline.clone());
+        Some(pipeline)
+    }
+}
+
+impl super::CommandEncoder {
+    /// Records the depth/stencil resolve of the render pass that is ending.
+    ///
+    /// The descriptor heaps of the pass must still be bound.
+    pub(super) unsafe fn resolve_depth_stencil(&mut self, resolve: PassDepthStencilResolve) {
+        let Some(resolver) = self.depth_resolver.clone() else {
+            log::error!("Depth/stencil resolve without the depth resolver");
+            return;
+        };
+        let list = self.list.as_ref().unwrap();
+
+        // The source stays in the same state for the whole pass. It is read
+        // both as a depth buffer and a shader resource during the resolve.
+        let read_state = Direct3D12::D3D12_RESOURCE_STATE_DEPTH_READ
+            | Direct3D12::D3D12_RESOURCE_STATE_PIXEL_SHADER_RESOURCE;
+        self.temp.barriers.clear();
+        if resolve.src_state != read_state {
+            for &subresource in resolve.src_subresources.iter() {
+                self.temp.barriers.push(Direct3D12::D3D12_RESOURCE_BARRIER {
+                    Type: Direct3D12::D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+                    Flags: Direct3D12::D3D12_RESOURCE_BARRIER_FLAG_NONE,
+                    Anonymous: Direct3D12::D3D12_RESOURCE_BARRIER_0 {
+                        Transition: mem::ManuallyDrop::new(
+                            Direct3D12::D3D12_RESOURCE_TRANSITION_BARRIER {
+                                pResource: unsafe { borrow_interface_temporarily(&resolve.src) },
+                                Subresource: subresource,
+                                StateBefore: resolve.src_state,
+                                StateAfter: read_state,
+                            },
+                        ),
+                    },
+                });
+            }
+        }
+        if !self.temp.barriers.is_empty() {
+            profiling::scope!("ID3D12GraphicsCommandList::ResourceBarrier");
+            unsafe { list.ResourceBarrier(&self.temp.barriers) };
+        }
+
+        let viewport = Direct3D12::D3D12_VIEWPORT {
+            TopLeftX: 0.0,
+            TopLeftY: 0.0,
+            Width: resolve.extent.width as f32,
+            Height: resolve.extent.height as f32,
+            MinDepth: 0.0,
+            MaxDepth: 1.0,
+        };
+        let rect = Foundation::RECT {
+            left: 0,
+            top: 0,
+            right: resolve.extent.width as i32,
+            bottom: resolve.extent.height as i32,
+        };
+        unsafe {
+            list.OMSetRenderTargets(0, None, false, Some(core::ptr::from_ref(&resolve.dst_dsv)));
+            list.RSSetViewports(core::slice::from_ref(&viewport));
+            list.RSSetScissorRects(core::slice::from_ref(&rect));
+            list.IASetPrimitiveTopology(Direct3D::D3D_PRIMITIVE_TOPOLOGY_TRIANGLELIST);
+            list.SetGraphicsRootSignature(&resolver.signature);
+            list.SetGraphicsRoot32BitConstant(ROOT_CONSTANTS, resolve.depth_mode, 0);
+        }
+
+        let mut bind_source = |desc: &Direct3D12::D3D12_SHADER_RESOURCE_VIEW_DESC| {
+            let handle = self.shared.heap_views.allocate_one(|cpu| unsafe {
+                self.device
+                    .CreateShaderResourceView(&resolve.src, Some(desc), cpu)
+            });
+            match handle {
+                Ok(handle) => {
+                    unsafe { list.SetGraphicsRootDescriptorTable(ROOT_SOURCE, handle.gpu) };
+                    self.temp_resolve_srvs.push(handle);
+                    true
+                }
+                Err(_) => false,
+            }
+        };
+
+        if let Some(ref desc) = resolve.depth_srv {
+            if let Some(pipeline) = resolver.pipeline(&self.device, resolve.dsv_format, None) {
+                if bind_source(desc) {
+                    unsafe {
+                        list.SetPipelineState(&pipeline);
+                        list.DrawInstanced(3, 1, 0, 0);
+                    }
+                }
+            }
+        }
+
+        if let Some(ref desc) = resolve.stencil_srv {
+            if bind_source(desc) {
+                unsafe {
+                    (Interface::vtable(list).ClearDepthStencilView)(
+                        Interface::as_raw(list),
+                        resolve.dst_dsv,
+                        Direct3D12::D3D12_CLEAR_FLAG_STENCIL,
+                        0.0,
+                        0,
+                        0,
+                        core::ptr::null(),
+                    );
+                    list.OMSetStencilRef(0xFF);
+                }
+                for bit in 0..8 {
+                    let Some(pipeline) =
+                        resolver.pipeline(&self.device, resolve.dsv_format, Some(bit))
+                    else {
+                        break;
+                    };
+                    unsafe {
+                        list.SetGraphicsRoot32BitConstant(ROOT_CONSTANTS, bit.into(), 1);
+                        list.SetPipelineState(&pipeline);
+                        list.DrawInstanced(3, 1, 0, 0);
+                    }
+                }
+            }
+        }
+
+        // Flip all the barriers to reverse, back into the state of the pass.
+        for barrier in self.temp.barriers.iter_mut() {
+            let transition = unsafe { &mut *barrier.Anonymous.Transition };
+            mem::swap(&mut transition.StateBefore, &mut transition.StateAfter);
+        }
+        if !self.temp.barriers.is_empty() {
+            profiling::scope!("ID3D12GraphicsCommandList::ResourceBarrier");
+            unsafe { list.ResourceBarrier(&self.temp.barriers) };
+        }
+    }
+}
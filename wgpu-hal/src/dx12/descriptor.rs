@@ -102,6 +102,16 @@ impl GeneralHeap {
         Ok(range.start)
     }
 
+    /// Allocates a single descriptor, which `write` fills through its CPU handle.
+    pub(super) fn allocate_one(
+        &self,
+        write: impl FnOnce(Direct3D12::D3D12_CPU_DESCRIPTOR_HANDLE),
+    ) -> Result<DualHandle, crate::DeviceError> {
+        let index = self.allocate_slice(1)?;
+        write(self.cpu_descriptor_at(index));
+        Ok(self.at(index, 1))
+    }
+
     /// Free handles previously given out by this `DescriptorHeapSlice`.
     /// Do not use this with handles not given out by this `DescriptorHeapSlice`.
     pub(crate) fn free_slice(&self, handle: DualHandle) {
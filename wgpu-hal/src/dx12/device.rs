@@ -5,7 +5,14 @@ use alloc::{
     sync::Arc,
     vec::Vec,
 };
-use core::{ffi, num::NonZeroU32, ptr, time::Duration};
+use core::{
+    ffi,
+    hash::{Hash as _, Hasher as _},
+    mem,
+    num::NonZeroU32,
+    ptr,
+    time::Duration,
+};
 use std::time::Instant;
 
 use bytemuck::TransparentWrapper;
@@ -35,6 +42,49 @@ use crate::{
 // this has to match Naga's HLSL backend, and also needs to be null-terminated
 const NAGA_LOCATION_SEMANTIC: &[u8] = c"LOC".to_bytes();
 
+/// The ray payload size reserved for ray tracing pipelines with passthrough stages,
+/// whose payloads aren't known.
+const PASSTHROUGH_RAY_PAYLOAD_SIZE: u32 = 256;
+
+/// A subobject of a [`Direct3D12::D3D12_PIPELINE_STATE_STREAM_DESC`].
+///
+/// Subobjects start with their type and are aligned to pointers, like the
+/// `CD3DX12_PIPELINE_STATE_STREAM_SUBOBJECT` helper of `d3dx12.h`.
+#[repr(C)]
+struct PipelineStateSubobject<T> {
+    _align: [*const ffi::c_void; 0],
+    ty: Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE,
+    desc: T,
+}
+
+impl<T> PipelineStateSubobject<T> {
+    fn new(ty: Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE, desc: T) -> Self {
+        Self {
+            _align: [],
+            ty,
+            desc,
+        }
+    }
+}
+
+/// The pipeline state stream of a mesh pipeline.
+#[repr(C)]
+struct MeshPipelineStateStream {
+    root_signature:
+        PipelineStateSubobject<mem::ManuallyDrop<Option<Direct3D12::ID3D12RootSignature>>>,
+    amplification_shader: PipelineStateSubobject<Direct3D12::D3D12_SHADER_BYTECODE>,
+    mesh_shader: PipelineStateSubobject<Direct3D12::D3D12_SHADER_BYTECODE>,
+    pixel_shader: PipelineStateSubobject<Direct3D12::D3D12_SHADER_BYTECODE>,
+    blend: PipelineStateSubobject<Direct3D12::D3D12_BLEND_DESC>,
+    sample_mask: PipelineStateSubobject<u32>,
+    rasterizer: PipelineStateSubobject<Direct3D12::D3D12_RASTERIZER_DESC>,
+    depth_stencil: PipelineStateSubobject<Direct3D12::D3D12_DEPTH_STENCIL_DESC>,
+    primitive_topology: PipelineStateSubobject<Direct3D12::D3D12_PRIMITIVE_TOPOLOGY_TYPE>,
+    render_target_formats: PipelineStateSubobject<Direct3D12::D3D12_RT_FORMAT_ARRAY>,
+    depth_stencil_format: PipelineStateSubobject<Dxgi::Common::DXGI_FORMAT>,
+    sample_desc: PipelineStateSubobject<Dxgi::Common::DXGI_SAMPLE_DESC>,
+}
+
 impl super::Device {
     #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
@@ -51,7 +101,7 @@ impl super::Device {
     ) -> Result<Self, crate::DeviceError> {
         if private_caps
             .instance_flags
-            .contains(wgt::InstanceFlags::VALIDATION)
+            .intersects(wgt::InstanceFlags::VALIDATION | wgt::InstanceFlags::GPU_BASED_VALIDATION)
         {
             auxil::dxgi::exception::register_exception_handler();
         }
@@ -111,6 +161,12 @@ impl super::Device {
         // Note: without `D3D12_HEAP_FLAG_CREATE_NOT_ZEROED`
         // this resource is zeroed by default.
 
+        let debug_printf = if features.contains(wgt::Features::SHADER_DEBUG_PRINTF) {
+            Some(Self::create_debug_printf_buffer(&raw)?)
+        } else {
+            None
+        };
+
         // maximum number of CBV/SRV/UAV descriptors in heap for Tier 1
         let capacity_views = limits.max_non_sampler_bindings as u64;
 
@@ -148,6 +204,20 @@ impl super::Device {
                     }],
                     0,
                 )?,
+                draw_mesh_tasks: if features.contains(wgt::Features::EXPERIMENTAL_MESH_SHADER) {
+                    Some(Self::create_command_signature(
+                        &raw,
+                        None,
+                        size_of::<wgt::DispatchIndirectArgs>(),
+                        &[Direct3D12::D3D12_INDIRECT_ARGUMENT_DESC {
+                            Type: Direct3D12::D3D12_INDIRECT_ARGUMENT_TYPE_DISPATCH_MESH,
+                            ..Default::default()
+                        }],
+                        0,
+                    )?)
+                } else {
+                    None
+                },
             },
             heap_views: descriptor::GeneralHeap::new(
                 &raw,
@@ -156,6 +226,7 @@ impl super::Device {
             )?,
             sampler_heap: super::sampler::SamplerHeap::new(&raw, &private_caps)?,
             private_caps,
+            debug_printf,
         };
 
         let mut rtv_pool =
@@ -180,7 +251,7 @@ impl super::Device {
             )
         };
 
-        Ok(super::Device {
+        let mut device = super::Device {
             raw: raw.clone(),
             present_queue,
             idler: super::Idler {
@@ -201,12 +272,23 @@ impl super::Device {
             library: Arc::clone(library),
             #[cfg(feature = "renderdoc")]
             render_doc: Default::default(),
+            graphics_analysis: super::DxgiLib::new()
+                .ok()
+                .and_then(|lib| lib.graphics_analysis()),
             null_rtv_handle,
             mem_allocator,
             compiler_container,
             shader_cache: Default::default(),
+            depth_resolver: None,
             counters: Default::default(),
-        })
+        };
+
+        if features.contains(wgt::Features::DEPTH_STENCIL_RESOLVE) {
+            device.depth_resolver =
+                Some(Arc::new(super::depth_resolve::DepthResolver::new(&device)?));
+        }
+
+        Ok(device)
     }
 
     fn create_command_signature(
@@ -233,6 +315,65 @@ impl super::Device {
         signature.ok_or(crate::DeviceError::Unexpected)
     }
 
+    /// Creates the buffer shaders write [`wgt::Features::SHADER_DEBUG_PRINTF`]
+    /// messages to, and maps it for the lifetime of the device.
+    fn create_debug_printf_buffer(
+        raw: &Direct3D12::ID3D12Device,
+    ) -> Result<super::DebugPrintfBuffer, crate::DeviceError> {
+        let raw_desc = Direct3D12::D3D12_RESOURCE_DESC {
+            Dimension: Direct3D12::D3D12_RESOURCE_DIMENSION_BUFFER,
+            Alignment: 0,
+            Width: auxil::debug_printf::BUFFER_SIZE,
+            Height: 1,
+            DepthOrArraySize: 1,
+            MipLevels: 1,
+            Format: Dxgi::Common::DXGI_FORMAT_UNKNOWN,
+            SampleDesc: Dxgi::Common::DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Layout: Direct3D12::D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+            Flags: Direct3D12::D3D12_RESOURCE_FLAG_ALLOW_UNORDERED_ACCESS,
+        };
+
+        // Shaders write to the buffer while the host reads it, so it has to
+        // live in host coherent memory rather than an upload or readback heap.
+        let heap_properties = Direct3D12::D3D12_HEAP_PROPERTIES {
+            Type: Direct3D12::D3D12_HEAP_TYPE_CUSTOM,
+            CPUPageProperty: Direct3D12::D3D12_CPU_PAGE_PROPERTY_WRITE_BACK,
+            MemoryPoolPreference: Direct3D12::D3D12_MEMORY_POOL_L0,
+            CreationNodeMask: 0,
+            VisibleNodeMask: 0,
+        };
+
+        profiling::scope!("Debug Printf Buffer Allocation");
+        let mut resource = None::<Direct3D12::ID3D12Resource>;
+        unsafe {
+            raw.CreateCommittedResource(
+                &heap_properties,
+                Direct3D12::D3D12_HEAP_FLAG_NONE,
+                &raw_desc,
+                Direct3D12::D3D12_RESOURCE_STATE_COMMON,
+                None,
+                &mut resource,
+            )
+        }
+        .into_device_result("Debug printf buffer creation")?;
+        let resource = resource.ok_or(crate::DeviceError::Unexpected)?;
+        resource.set_name("debug printf buffer")?;
+
+        let mut mapping = ptr::null_mut::<ffi::c_void>();
+        unsafe { resource.Map(0, None, Some(&mut mapping)) }
+            .into_device_result("Map debug printf buffer")?;
+        let words = ptr::NonNull::new(mapping.cast()).ok_or(crate::DeviceError::Unexpected)?;
+
+        Ok(super::DebugPrintfBuffer {
+            resource,
+            // SAFETY: the buffer is never unmapped, and no pipeline can use it yet.
+            reader: unsafe { auxil::debug_printf::DebugPrintf::new(words) },
+        })
+    }
+
     // Blocks until the dedicated present queue is finished with all of its work.
     //
     // Once this method completes, the surface is able to be resized or deleted.
@@ -255,6 +396,14 @@ impl super::Device {
         Ok(())
     }
 
+    /// Logs the [`wgt::Features::SHADER_DEBUG_PRINTF`] messages of the
+    /// submissions done so far.
+    fn poll_debug_printf(&self) {
+        if let Some(ref debug_printf) = self.shared.debug_printf {
+            debug_printf.reader.poll();
+        }
+    }
+
     /// When generating the vertex shader, the fragment stage must be passed if it exists!
     /// Otherwise, the generated HLSL may be incorrect since the fragment shader inputs are
     /// allowed to be a subset of the vertex outputs.
@@ -265,6 +414,19 @@ impl super::Device {
         naga_stage: naga::ShaderStage,
         fragment_stage: Option<&crate::ProgrammableStage<super::ShaderModule>>,
     ) -> Result<super::CompiledShader, crate::PipelineError> {
+        self.load_shader_with_entry_point(stage, layout, naga_stage, fragment_stage)
+            .map(|(shader, _)| shader)
+    }
+
+    /// Like [`Self::load_shader`], but also returns the name of the entry point in the
+    /// compiled shader, which ray tracing stages are exported from their library by.
+    fn load_shader_with_entry_point(
+        &self,
+        stage: &crate::ProgrammableStage<super::ShaderModule>,
+        layout: &super::PipelineLayout,
+        naga_stage: naga::ShaderStage,
+        fragment_stage: Option<&crate::ProgrammableStage<super::ShaderModule>>,
+    ) -> Result<(super::CompiledShader, String), crate::PipelineError> {
         let stage_bit = auxil::map_naga_stage(naga_stage);
 
         let needs_temp_options = stage.zero_initialize_workgroup_memory
@@ -316,6 +478,29 @@ impl super::Device {
 
                 let pipeline_options = hlsl::PipelineOptions {
                     entry_point: Some((naga_stage, stage.entry_point.to_string())),
+                    debug_source: naga_shader.debug_source.as_ref().map(|debug_source| {
+                        naga::back::DebugSource {
+                            file_name: debug_source.file_name.to_string(),
+                            source_code: debug_source.source_code.to_string(),
+                        }
+                    }),
+                };
+
+                // Keep the table locked until the formats of this shader are
+                // added, so that they get the indices the shader was written with.
+                let mut debug_printf_formats = self
+                    .shared
+                    .debug_printf
+                    .as_ref()
+                    .map(|debug_printf| debug_printf.reader.formats());
+                let mut debug_printf_options;
+                let naga_options = match debug_printf_formats {
+                    Some(ref formats) => {
+                        debug_printf_options = naga_options.clone();
+                        debug_printf_options.debug_printf_first_format = formats.len() as u32;
+                        &debug_printf_options
+                    }
+                    None => naga_options,
                 };
 
                 //TODO: reuse the writer
@@ -333,6 +518,10 @@ impl super::Device {
 
                     assert_eq!(reflection_info.entry_point_names.len(), 1);
 
+                    if let Some(ref mut formats) = debug_printf_formats {
+                        formats.append(&mut reflection_info.debug_printf_formats);
+                    }
+
                     let entry_point = reflection_info
                         .entry_point_names
                         .pop()
@@ -353,6 +542,7 @@ impl super::Device {
                     entry_point,
                     stage: naga_stage,
                     shader_model: naga_options.shader_model,
+                    optimization: stage.module.optimization,
                 }
             }
             super::ShaderModuleSource::HlslPassthrough(passthrough) => ShaderCacheKey {
@@ -360,11 +550,13 @@ impl super::Device {
                 entry_point: passthrough.entry_point.clone(),
                 stage: naga_stage,
                 shader_model: naga_options.shader_model,
+                optimization: stage.module.optimization,
             },
 
             super::ShaderModuleSource::DxilPassthrough(passthrough) => {
-                return Ok(super::CompiledShader::Precompiled(
-                    passthrough.shader.clone(),
+                return Ok((
+                    super::CompiledShader::Precompiled(passthrough.shader.clone()),
+                    passthrough.entry_point.clone(),
                 ));
             }
         };
@@ -374,7 +566,7 @@ impl super::Device {
             let nr_of_shaders_compiled = shader_cache.nr_of_shaders_compiled;
             if let Some(value) = shader_cache.entries.get_mut(&key) {
                 value.last_used = nr_of_shaders_compiled;
-                return Ok(value.shader.clone());
+                return Ok((value.shader.clone(), key.entry_point.clone()));
             }
         }
 
@@ -389,8 +581,10 @@ impl super::Device {
             &key.entry_point,
             stage_bit,
             &full_stage,
+            key.optimization,
         )?;
 
+        let entry_point = key.entry_point.clone();
         {
             let mut shader_cache = self.shader_cache.lock();
             shader_cache.nr_of_shaders_compiled += 1;
@@ -409,7 +603,40 @@ impl super::Device {
             }
         }
 
-        Ok(compiled_shader)
+        Ok((compiled_shader, entry_point))
+    }
+
+    /// Compile an intersection or callable stage. naga has no such stages, so these
+    /// must come from passthrough modules, which are compiled to libraries as they are.
+    fn passthrough_ray_tracing_stage(
+        &self,
+        stage: &crate::RayTracingStage<super::ShaderModule>,
+        layout: &super::PipelineLayout,
+    ) -> Result<(super::CompiledShader, String), crate::PipelineError> {
+        let module = stage.programmable.module;
+        match module.source {
+            super::ShaderModuleSource::DxilPassthrough(ref passthrough) => Ok((
+                super::CompiledShader::Precompiled(passthrough.shader.clone()),
+                passthrough.entry_point.clone(),
+            )),
+            super::ShaderModuleSource::HlslPassthrough(ref passthrough) => {
+                let full_stage = format!("lib_{}", layout.naga_options.shader_model.to_str());
+                let shader = self.compiler_container.compile(
+                    self,
+                    &passthrough.shader,
+                    module.raw_name.as_deref(),
+                    &passthrough.entry_point,
+                    stage.stage,
+                    &full_stage,
+                    module.optimization,
+                )?;
+                Ok((shader, passthrough.entry_point.clone()))
+            }
+            super::ShaderModuleSource::Naga(_) => Err(crate::PipelineError::Linkage(
+                stage.stage,
+                "intersection and callable stages must be passthrough shaders".to_string(),
+            )),
+        }
     }
 
     pub fn raw_device(&self) -> &Direct3D12::ID3D12Device {
@@ -455,6 +682,44 @@ impl super::Device {
             ),
         }
     }
+
+    fn texture_resource_desc(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Direct3D12::D3D12_RESOURCE_DESC {
+        let mut usage = desc.usage;
+        // Multisampled depth/stencil attachments are read by the shaders of
+        // the depth/stencil resolve.
+        if self.depth_resolver.is_some()
+            && desc.sample_count > 1
+            && usage.contains(wgt::TextureUses::DEPTH_STENCIL_WRITE)
+        {
+            usage |= wgt::TextureUses::RESOURCE;
+        }
+
+        Direct3D12::D3D12_RESOURCE_DESC {
+            Dimension: conv::map_texture_dimension(desc.dimension),
+            Alignment: 0,
+            Width: desc.size.width as u64,
+            Height: desc.size.height,
+            DepthOrArraySize: desc.size.depth_or_array_layers as u16,
+            MipLevels: desc.mip_level_count as u16,
+            Format: auxil::dxgi::conv::map_texture_format_for_resource(
+                desc.format,
+                usage,
+                !desc.view_formats.is_empty(),
+                self.shared
+                    .private_caps
+                    .casting_fully_typed_format_supported,
+            ),
+            SampleDesc: Dxgi::Common::DXGI_SAMPLE_DESC {
+                Count: desc.sample_count,
+                Quality: 0,
+            },
+            Layout: Direct3D12::D3D12_TEXTURE_LAYOUT_UNKNOWN,
+            Flags: conv::map_texture_usage_to_resource_flags(usage),
+        }
+    }
 }
 
 impl crate::Device for super::Device {
@@ -524,28 +789,7 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::TextureDescriptor,
     ) -> Result<super::Texture, crate::DeviceError> {
-        let raw_desc = Direct3D12::D3D12_RESOURCE_DESC {
-            Dimension: conv::map_texture_dimension(desc.dimension),
-            Alignment: 0,
-            Width: desc.size.width as u64,
-            Height: desc.size.height,
-            DepthOrArraySize: desc.size.depth_or_array_layers as u16,
-            MipLevels: desc.mip_level_count as u16,
-            Format: auxil::dxgi::conv::map_texture_format_for_resource(
-                desc.format,
-                desc.usage,
-                !desc.view_formats.is_empty(),
-                self.shared
-                    .private_caps
-                    .casting_fully_typed_format_supported,
-            ),
-            SampleDesc: Dxgi::Common::DXGI_SAMPLE_DESC {
-                Count: desc.sample_count,
-                Quality: 0,
-            },
-            Layout: Direct3D12::D3D12_TEXTURE_LAYOUT_UNKNOWN,
-            Flags: conv::map_texture_usage_to_resource_flags(desc.usage),
-        };
+        let raw_desc = self.texture_resource_desc(desc);
 
         let (resource, allocation) =
             suballocation::DeviceAllocationContext::from(self).create_texture(desc, raw_desc)?;
@@ -574,6 +818,135 @@ impl crate::Device for super::Device {
         self.counters.textures.add(1);
     }
 
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<super::MemoryHeap, crate::DeviceError> {
+        let raw = suballocation::DeviceAllocationContext::from(self).create_heap(desc)?;
+
+        Ok(super::MemoryHeap {
+            raw,
+            size: desc.size,
+        })
+    }
+    unsafe fn destroy_memory_heap(&self, heap: super::MemoryHeap) {
+        self.counters.texture_memory.sub(heap.size as isize);
+    }
+
+    unsafe fn texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, crate::DeviceError> {
+        let raw_desc = self.texture_resource_desc(desc);
+        let allocation_info = unsafe {
+            self.raw
+                .GetResourceAllocationInfo(0, core::slice::from_ref(&raw_desc))
+        };
+        if allocation_info.SizeInBytes == 0 || allocation_info.SizeInBytes == u64::MAX {
+            return Err(crate::DeviceError::OutOfMemory);
+        }
+
+        Ok(wgt::TextureMemoryRequirements {
+            size: allocation_info.SizeInBytes,
+            alignment: allocation_info.Alignment,
+        })
+    }
+
+    unsafe fn create_placed_texture(
+        &self,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let raw_desc = self.texture_resource_desc(desc);
+
+        let resource = suballocation::DeviceAllocationContext::from(self)
+            .create_texture_in_heap(&heap.raw, offset, desc, raw_desc)?;
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            allocation: suballocation::Allocation::heap(suballocation::AllocationType::Texture),
+        })
+    }
+
+    unsafe fn create_sparse_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let raw_desc = conv::map_buffer_descriptor(desc);
+
+        let resource = suballocation::DeviceAllocationContext::from(self)
+            .create_reserved_resource(desc.label, raw_desc)?;
+
+        self.counters.buffers.add(1);
+
+        Ok(super::Buffer {
+            resource,
+            size: desc.size,
+            allocation: suballocation::Allocation::reserved(suballocation::AllocationType::Buffer),
+        })
+    }
+
+    unsafe fn create_sparse_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let raw_desc = Direct3D12::D3D12_RESOURCE_DESC {
+            Layout: Direct3D12::D3D12_TEXTURE_LAYOUT_64KB_UNDEFINED_SWIZZLE,
+            ..self.texture_resource_desc(desc)
+        };
+
+        let resource = suballocation::DeviceAllocationContext::from(self)
+            .create_reserved_resource(desc.label, raw_desc)?;
+
+        // Every mip level must be made of whole standard tiles, with no packed
+        // mips that would have to be mapped as a unit.
+        let mut packed_mip_info = Direct3D12::D3D12_PACKED_MIP_INFO::default();
+        let mut tile_shape = Direct3D12::D3D12_TILE_SHAPE::default();
+        let mut subresource_tiling_count = 0;
+        unsafe {
+            self.raw.GetResourceTiling(
+                &resource,
+                None,
+                Some(&mut packed_mip_info),
+                Some(&mut tile_shape),
+                Some(&mut subresource_tiling_count),
+                0,
+                ptr::null_mut(),
+            )
+        };
+        let is_tileable = packed_mip_info.NumPackedMips == 0
+            && desc.format.sparse_tile_size().is_some_and(|size| {
+                tile_shape.WidthInTexels == size.width && tile_shape.HeightInTexels == size.height
+            });
+        if !is_tileable {
+            log::error!(
+                "Sparse texture {:?} can't be mapped in whole tiles",
+                desc.label
+            );
+            return Err(crate::DeviceError::OutOfMemory);
+        }
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            allocation: suballocation::Allocation::reserved(suballocation::AllocationType::Texture),
+        })
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &super::Texture,
@@ -671,6 +1044,13 @@ impl crate::Device for super::Device {
             } else {
                 None
             },
+            resolve_source: if self.depth_resolver.is_some()
+                && desc.usage.contains(wgt::TextureUses::DEPTH_STENCIL_WRITE)
+            {
+                super::depth_resolve::ResolveSource::new(texture, desc)
+            } else {
+                None
+            },
         })
     }
 
@@ -762,11 +1142,10 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::CommandEncoderDescriptor<super::Queue>,
     ) -> Result<super::CommandEncoder, crate::DeviceError> {
-        let allocator: Direct3D12::ID3D12CommandAllocator = unsafe {
-            self.raw
-                .CreateCommandAllocator(Direct3D12::D3D12_COMMAND_LIST_TYPE_DIRECT)
-        }
-        .into_device_result("Command allocator creation")?;
+        let list_type = desc.queue.list_type;
+        let allocator: Direct3D12::ID3D12CommandAllocator =
+            unsafe { self.raw.CreateCommandAllocator(list_type) }
+                .into_device_result("Command allocator creation")?;
 
         if let Some(label) = desc.label {
             allocator.set_name(label)?;
@@ -776,17 +1155,21 @@ impl crate::Device for super::Device {
 
         Ok(super::CommandEncoder {
             allocator,
+            list_type,
             device: self.raw.clone(),
             shared: Arc::clone(&self.shared),
             mem_allocator: self.mem_allocator.clone(),
             rtv_pool: Arc::clone(&self.rtv_pool),
             temp_rtv_handles: Vec::new(),
+            temp_resolve_srvs: Vec::new(),
+            depth_resolver: self.depth_resolver.clone(),
             null_rtv_handle: self.null_rtv_handle,
             list: None,
             free_lists: Vec::new(),
             pass: super::PassState::new(),
             temp: super::Temp::default(),
             end_of_pass_timer_query: None,
+            pass_pipeline_statistics_query: None,
             counters: Arc::clone(&self.counters),
         })
     }
@@ -811,6 +1194,7 @@ impl crate::Device for super::Device {
                 | wgt::BindingType::AccelerationStructure { .. } => num_views += count,
                 wgt::BindingType::Sampler { .. } => has_sampler_in_group = true,
                 wgt::BindingType::ExternalTexture => unimplemented!(),
+                wgt::BindingType::YcbcrTexture { .. } => unimplemented!(),
             }
         }
 
@@ -875,6 +1259,7 @@ impl crate::Device for super::Device {
         // Sampler descriptor tables: Space=0
         //   SamplerState Array: Space=0, Register=0-2047
         //   SamplerComparisonState Array: Space=0, Register=2048-4095
+        // Debug printf buffer, if the feature is enabled: Space=0
 
         //TODO: put lower bind group indices further down the root signature. See:
         // https://microsoft.github.io/DirectX-Specs/d3d/ResourceBinding.html#binding-model
@@ -1267,6 +1652,26 @@ impl crate::Device for super::Device {
             (None, None)
         };
 
+        let (debug_printf_root_index, debug_printf_binding) = if self.shared.debug_printf.is_some()
+        {
+            let parameter_index = parameters.len();
+            parameters.push(Direct3D12::D3D12_ROOT_PARAMETER {
+                ParameterType: Direct3D12::D3D12_ROOT_PARAMETER_TYPE_UAV,
+                Anonymous: Direct3D12::D3D12_ROOT_PARAMETER_0 {
+                    Descriptor: Direct3D12::D3D12_ROOT_DESCRIPTOR {
+                        ShaderRegister: bind_uav.register,
+                        RegisterSpace: bind_uav.space as u32,
+                    },
+                },
+                ShaderVisibility: Direct3D12::D3D12_SHADER_VISIBILITY_ALL,
+            });
+            let binding = bind_uav;
+            bind_uav.register += 1;
+            (Some(parameter_index as u32), Some(binding))
+        } else {
+            (None, None)
+        };
+
         let blob = self.library.serialize_root_signature(
             Direct3D12::D3D_ROOT_SIGNATURE_VERSION_1_0,
             &parameters,
@@ -1353,6 +1758,8 @@ impl crate::Device for super::Device {
                         ],
                         0,
                     )?,
+                    // Mesh shaders don't read the special constants.
+                    draw_mesh_tasks: None,
                 })
             } else {
                 None
@@ -1378,6 +1785,7 @@ impl crate::Device for super::Device {
                 special_constants,
                 root_constant_info,
                 sampler_heap_root_index,
+                debug_printf_root_index,
             },
             bind_group_infos,
             naga_options: hlsl::Options {
@@ -1385,6 +1793,8 @@ impl crate::Device for super::Device {
                 binding_map,
                 fake_missing_bindings: false,
                 special_constants_binding,
+                debug_printf_binding,
+                debug_printf_first_format: 0,
                 push_constants_target,
                 dynamic_storage_buffer_offsets_targets,
                 zero_initialize_workgroup_memory: true,
@@ -1577,6 +1987,7 @@ impl crate::Device for super::Device {
                     }
                 }
                 wgt::BindingType::ExternalTexture => unimplemented!(),
+                wgt::BindingType::YcbcrTexture { .. } => unimplemented!(),
             }
         }
 
@@ -1699,6 +2110,7 @@ impl crate::Device for super::Device {
                 source: super::ShaderModuleSource::Naga(naga),
                 raw_name,
                 runtime_checks: desc.runtime_checks,
+                optimization: desc.optimization,
             }),
             crate::ShaderInput::SpirV(_) => {
                 panic!("SPIRV_SHADER_PASSTHROUGH is not enabled for this backend")
@@ -1718,6 +2130,7 @@ impl crate::Device for super::Device {
                 }),
                 raw_name,
                 runtime_checks: desc.runtime_checks,
+                optimization: desc.optimization,
             }),
             crate::ShaderInput::Hlsl {
                 shader,
@@ -1731,6 +2144,7 @@ impl crate::Device for super::Device {
                 }),
                 raw_name,
                 runtime_checks: desc.runtime_checks,
+                optimization: desc.optimization,
             }),
         }
     }
@@ -1772,13 +2186,12 @@ impl crate::Device for super::Device {
             .enumerate()
         {
             *stride = NonZeroU32::new(vbuf.array_stride as u32);
-            let (slot_class, step_rate) = match vbuf.step_mode {
-                wgt::VertexStepMode::Vertex => {
-                    (Direct3D12::D3D12_INPUT_CLASSIFICATION_PER_VERTEX_DATA, 0)
-                }
-                wgt::VertexStepMode::Instance => {
-                    (Direct3D12::D3D12_INPUT_CLASSIFICATION_PER_INSTANCE_DATA, 1)
-                }
+            let (slot_class, step_rate) = match vbuf.step_mode.instance_rate() {
+                None => (Direct3D12::D3D12_INPUT_CLASSIFICATION_PER_VERTEX_DATA, 0),
+                Some(rate) => (
+                    Direct3D12::D3D12_INPUT_CLASSIFICATION_PER_INSTANCE_DATA,
+                    rate,
+                ),
             };
             for attribute in vbuf.attributes {
                 input_element_descs.push(Direct3D12::D3D12_INPUT_ELEMENT_DESC {
@@ -1793,45 +2206,6 @@ impl crate::Device for super::Device {
             }
         }
 
-        let mut rtv_formats = [Dxgi::Common::DXGI_FORMAT_UNKNOWN;
-            Direct3D12::D3D12_SIMULTANEOUS_RENDER_TARGET_COUNT as usize];
-        for (rtv_format, ct) in rtv_formats.iter_mut().zip(desc.color_targets) {
-            if let Some(ct) = ct.as_ref() {
-                *rtv_format = auxil::dxgi::conv::map_texture_format(ct.format);
-            }
-        }
-
-        let bias = desc
-            .depth_stencil
-            .as_ref()
-            .map(|ds| ds.bias)
-            .unwrap_or_default();
-
-        let raw_rasterizer = Direct3D12::D3D12_RASTERIZER_DESC {
-            FillMode: conv::map_polygon_mode(desc.primitive.polygon_mode),
-            CullMode: match desc.primitive.cull_mode {
-                None => Direct3D12::D3D12_CULL_MODE_NONE,
-                Some(wgt::Face::Front) => Direct3D12::D3D12_CULL_MODE_FRONT,
-                Some(wgt::Face::Back) => Direct3D12::D3D12_CULL_MODE_BACK,
-            },
-            FrontCounterClockwise: match desc.primitive.front_face {
-                wgt::FrontFace::Cw => Foundation::FALSE,
-                wgt::FrontFace::Ccw => Foundation::TRUE,
-            },
-            DepthBias: bias.constant,
-            DepthBiasClamp: bias.clamp,
-            SlopeScaledDepthBias: bias.slope_scale,
-            DepthClipEnable: Foundation::BOOL::from(!desc.primitive.unclipped_depth),
-            MultisampleEnable: Foundation::BOOL::from(desc.multisample.count > 1),
-            ForcedSampleCount: 0,
-            AntialiasedLineEnable: false.into(),
-            ConservativeRaster: if desc.primitive.conservative {
-                Direct3D12::D3D12_CONSERVATIVE_RASTERIZATION_MODE_ON
-            } else {
-                Direct3D12::D3D12_CONSERVATIVE_RASTERIZATION_MODE_OFF
-            },
-        };
-
         let raw_desc = Direct3D12::D3D12_GRAPHICS_PIPELINE_STATE_DESC {
             pRootSignature: unsafe {
                 borrow_optional_interface_temporarily(&desc.layout.shared.signature)
@@ -1859,7 +2233,11 @@ impl crate::Device for super::Device {
                 RenderTarget: conv::map_render_targets(desc.color_targets),
             },
             SampleMask: desc.multisample.mask as u32,
-            RasterizerState: raw_rasterizer,
+            RasterizerState: conv::map_rasterizer(
+                &desc.primitive,
+                desc.depth_stencil.as_ref(),
+                desc.multisample.count,
+            ),
             DepthStencilState: match desc.depth_stencil {
                 Some(ref ds) => conv::map_depth_stencil(ds),
                 None => Default::default(),
@@ -1872,7 +2250,12 @@ impl crate::Device for super::Device {
                 },
                 NumElements: input_element_descs.len() as u32,
             },
-            IBStripCutValue: match desc.primitive.strip_index_format {
+            IBStripCutValue: match desc
+                .primitive
+                .strip_index_format
+                .filter(|_| desc.primitive.primitive_restart)
+            {
+                Some(wgt::IndexFormat::Uint8) => unreachable!(),
                 Some(wgt::IndexFormat::Uint16) => {
                     Direct3D12::D3D12_INDEX_BUFFER_STRIP_CUT_VALUE_0xFFFF
                 }
@@ -1883,7 +2266,7 @@ impl crate::Device for super::Device {
             },
             PrimitiveTopologyType: topology_class,
             NumRenderTargets: desc.color_targets.len() as u32,
-            RTVFormats: rtv_formats,
+            RTVFormats: conv::map_render_target_formats(desc.color_targets),
             DSVFormat: desc
                 .depth_stencil
                 .as_ref()
@@ -1902,9 +2285,31 @@ impl crate::Device for super::Device {
             Flags: Direct3D12::D3D12_PIPELINE_STATE_FLAG_NONE,
         };
 
-        let raw: Direct3D12::ID3D12PipelineState = {
+        let create = || {
             profiling::scope!("ID3D12Device::CreateGraphicsPipelineState");
             unsafe { self.raw.CreateGraphicsPipelineState(&raw_desc) }
+        };
+        let raw: Direct3D12::ID3D12PipelineState = match desc.cache {
+            Some(cache) => {
+                let mut hasher = super::PipelineNameHasher::default();
+                hasher.write_shader(&raw_desc.VS);
+                hasher.write_shader(&raw_desc.PS);
+                for vertex_buffer in desc.vertex_buffers {
+                    vertex_buffer.array_stride.hash(&mut hasher);
+                    vertex_buffer.step_mode.hash(&mut hasher);
+                    vertex_buffer.attributes.hash(&mut hasher);
+                }
+                desc.primitive.hash(&mut hasher);
+                desc.depth_stencil.hash(&mut hasher);
+                desc.multisample.hash(&mut hasher);
+                desc.color_targets.hash(&mut hasher);
+                cache.load_or_create(
+                    &format!("wgpu-render-{:016x}", hasher.finish()),
+                    |library, name| unsafe { library.LoadGraphicsPipeline(name, &raw_desc) },
+                    create,
+                )
+            }
+            None => create(),
         }
         .map_err(|err| crate::PipelineError::Linkage(shader_stages, err.to_string()))?;
 
@@ -1924,13 +2329,159 @@ impl crate::Device for super::Device {
 
     unsafe fn create_mesh_pipeline(
         &self,
-        _desc: &crate::MeshPipelineDescriptor<
+        desc: &crate::MeshPipelineDescriptor<
             <Self::A as crate::Api>::PipelineLayout,
             <Self::A as crate::Api>::ShaderModule,
             <Self::A as crate::Api>::PipelineCache,
         >,
     ) -> Result<<Self::A as crate::Api>::RenderPipeline, crate::PipelineError> {
-        unreachable!()
+        let (topology_class, topology) = conv::map_topology(desc.primitive.topology);
+        let mut shader_stages = wgt::ShaderStages::MESH;
+
+        let blob_ts = match desc.task_stage {
+            Some(ref stage) => {
+                shader_stages |= wgt::ShaderStages::TASK;
+                Some(self.load_shader(stage, desc.layout, naga::ShaderStage::Task, None)?)
+            }
+            None => None,
+        };
+        let blob_ms =
+            self.load_shader(&desc.mesh_stage, desc.layout, naga::ShaderStage::Mesh, None)?;
+        let blob_fs = match desc.fragment_stage {
+            Some(ref stage) => {
+                shader_stages |= wgt::ShaderStages::FRAGMENT;
+                Some(self.load_shader(stage, desc.layout, naga::ShaderStage::Fragment, None)?)
+            }
+            None => None,
+        };
+
+        let native_shader = |blob: &Option<super::CompiledShader>| match blob {
+            Some(shader) => shader.create_native_shader(),
+            None => Direct3D12::D3D12_SHADER_BYTECODE::default(),
+        };
+
+        let mut stream = MeshPipelineStateStream {
+            root_signature: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_ROOT_SIGNATURE,
+                unsafe { borrow_optional_interface_temporarily(&desc.layout.shared.signature) },
+            ),
+            amplification_shader: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_AS,
+                native_shader(&blob_ts),
+            ),
+            mesh_shader: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_MS,
+                blob_ms.create_native_shader(),
+            ),
+            pixel_shader: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_PS,
+                native_shader(&blob_fs),
+            ),
+            blend: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_BLEND,
+                Direct3D12::D3D12_BLEND_DESC {
+                    AlphaToCoverageEnable: Foundation::BOOL::from(
+                        desc.multisample.alpha_to_coverage_enabled,
+                    ),
+                    IndependentBlendEnable: true.into(),
+                    RenderTarget: conv::map_render_targets(desc.color_targets),
+                },
+            ),
+            sample_mask: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_SAMPLE_MASK,
+                desc.multisample.mask as u32,
+            ),
+            rasterizer: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_RASTERIZER,
+                conv::map_rasterizer(
+                    &desc.primitive,
+                    desc.depth_stencil.as_ref(),
+                    desc.multisample.count,
+                ),
+            ),
+            depth_stencil: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_DEPTH_STENCIL,
+                match desc.depth_stencil {
+                    Some(ref ds) => conv::map_depth_stencil(ds),
+                    None => Default::default(),
+                },
+            ),
+            primitive_topology: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_PRIMITIVE_TOPOLOGY,
+                topology_class,
+            ),
+            render_target_formats: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_RENDER_TARGET_FORMATS,
+                Direct3D12::D3D12_RT_FORMAT_ARRAY {
+                    RTFormats: conv::map_render_target_formats(desc.color_targets),
+                    NumRenderTargets: desc.color_targets.len() as u32,
+                },
+            ),
+            depth_stencil_format: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_DEPTH_STENCIL_FORMAT,
+                desc.depth_stencil
+                    .as_ref()
+                    .map_or(Dxgi::Common::DXGI_FORMAT_UNKNOWN, |ds| {
+                        auxil::dxgi::conv::map_texture_format(ds.format)
+                    }),
+            ),
+            sample_desc: PipelineStateSubobject::new(
+                Direct3D12::D3D12_PIPELINE_STATE_SUBOBJECT_TYPE_SAMPLE_DESC,
+                Dxgi::Common::DXGI_SAMPLE_DESC {
+                    Count: desc.multisample.count,
+                    Quality: 0,
+                },
+            ),
+        };
+        let stream_desc = Direct3D12::D3D12_PIPELINE_STATE_STREAM_DESC {
+            SizeInBytes: size_of::<MeshPipelineStateStream>(),
+            pPipelineStateSubobjectStream: <*mut _>::cast(&mut stream),
+        };
+
+        let device = self
+            .raw
+            .cast::<Direct3D12::ID3D12Device2>()
+            .map_err(|err| crate::PipelineError::Linkage(shader_stages, err.to_string()))?;
+        let create = || {
+            profiling::scope!("ID3D12Device2::CreatePipelineState");
+            unsafe { device.CreatePipelineState(&stream_desc) }
+        };
+        let raw: Direct3D12::ID3D12PipelineState = match desc.cache {
+            Some(cache) => {
+                let mut hasher = super::PipelineNameHasher::default();
+                hasher.write_shader(&stream.amplification_shader.desc);
+                hasher.write_shader(&stream.mesh_shader.desc);
+                hasher.write_shader(&stream.pixel_shader.desc);
+                desc.primitive.hash(&mut hasher);
+                desc.depth_stencil.hash(&mut hasher);
+                desc.multisample.hash(&mut hasher);
+                desc.color_targets.hash(&mut hasher);
+                cache.load_or_create(
+                    &format!("wgpu-mesh-{:016x}", hasher.finish()),
+                    |library, name| unsafe {
+                        library
+                            .cast::<Direct3D12::ID3D12PipelineLibrary1>()?
+                            .LoadPipeline(name, &stream_desc)
+                    },
+                    create,
+                )
+            }
+            None => create(),
+        }
+        .map_err(|err| crate::PipelineError::Linkage(shader_stages, err.to_string()))?;
+
+        if let Some(label) = desc.label {
+            raw.set_name(label)?;
+        }
+
+        self.counters.render_pipelines.add(1);
+
+        Ok(super::RenderPipeline {
+            raw,
+            layout: desc.layout.shared.clone(),
+            topology,
+            vertex_strides: [None; crate::MAX_VERTEX_BUFFERS],
+        })
     }
 
     unsafe fn destroy_render_pipeline(&self, _pipeline: super::RenderPipeline) {
@@ -1948,21 +2499,31 @@ impl crate::Device for super::Device {
         let blob_cs =
             self.load_shader(&desc.stage, desc.layout, naga::ShaderStage::Compute, None)?;
 
-        let pair = {
+        let raw_desc = Direct3D12::D3D12_COMPUTE_PIPELINE_STATE_DESC {
+            pRootSignature: unsafe {
+                borrow_optional_interface_temporarily(&desc.layout.shared.signature)
+            },
+            CS: blob_cs.create_native_shader(),
+            NodeMask: 0,
+            CachedPSO: Direct3D12::D3D12_CACHED_PIPELINE_STATE::default(),
+            Flags: Direct3D12::D3D12_PIPELINE_STATE_FLAG_NONE,
+        };
+
+        let create = || {
             profiling::scope!("ID3D12Device::CreateComputePipelineState");
-            unsafe {
-                self.raw.CreateComputePipelineState(
-                    &Direct3D12::D3D12_COMPUTE_PIPELINE_STATE_DESC {
-                        pRootSignature: borrow_optional_interface_temporarily(
-                            &desc.layout.shared.signature,
-                        ),
-                        CS: blob_cs.create_native_shader(),
-                        NodeMask: 0,
-                        CachedPSO: Direct3D12::D3D12_CACHED_PIPELINE_STATE::default(),
-                        Flags: Direct3D12::D3D12_PIPELINE_STATE_FLAG_NONE,
-                    },
+            unsafe { self.raw.CreateComputePipelineState(&raw_desc) }
+        };
+        let pair = match desc.cache {
+            Some(cache) => {
+                let mut hasher = super::PipelineNameHasher::default();
+                hasher.write_shader(&raw_desc.CS);
+                cache.load_or_create(
+                    &format!("wgpu-compute-{:016x}", hasher.finish()),
+                    |library, name| unsafe { library.LoadComputePipeline(name, &raw_desc) },
+                    create,
                 )
             }
+            None => create(),
         };
 
         let raw: Direct3D12::ID3D12PipelineState = pair.map_err(|err| {
@@ -1985,14 +2546,289 @@ impl crate::Device for super::Device {
         self.counters.compute_pipelines.sub(1);
     }
 
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        desc: &crate::RayTracingPipelineDescriptor<
+            super::PipelineLayout,
+            super::ShaderModule,
+            super::PipelineCache,
+        >,
+    ) -> Result<super::RayTracingPipeline, crate::PipelineError> {
+        use windows::core::PCWSTR;
+
+        let wide = |name: &str| name.encode_utf16().chain([0]).collect::<Vec<u16>>();
+
+        // Each stage is compiled to its own DXIL library, exporting its entry point
+        // under a name that is unique within the state object.
+        let mut max_payload_size = 0;
+        let mut compiled_stages = Vec::with_capacity(desc.stages.len());
+        for stage in desc.stages {
+            let naga_stage = match stage.stage {
+                wgt::ShaderStages::RAY_GENERATION => Some(naga::ShaderStage::RayGeneration),
+                wgt::ShaderStages::MISS => Some(naga::ShaderStage::Miss),
+                wgt::ShaderStages::CLOSEST_HIT => Some(naga::ShaderStage::ClosestHit),
+                wgt::ShaderStages::ANY_HIT => Some(naga::ShaderStage::AnyHit),
+                _ => None,
+            };
+            let compiled = match naga_stage {
+                Some(naga_stage) => self.load_shader_with_entry_point(
+                    &stage.programmable,
+                    desc.layout,
+                    naga_stage,
+                    None,
+                )?,
+                None => self.passthrough_ray_tracing_stage(stage, desc.layout)?,
+            };
+            max_payload_size = max_payload_size.max(match stage.programmable.module.source {
+                super::ShaderModuleSource::Naga(ref naga_shader) => {
+                    let module = &naga_shader.module;
+                    module
+                        .global_variables
+                        .iter()
+                        .filter(|&(_, var)| {
+                            matches!(
+                                var.space,
+                                naga::AddressSpace::RayPayload
+                                    | naga::AddressSpace::IncomingRayPayload
+                            )
+                        })
+                        .map(|(_, var)| module.types[var.ty].inner.size(module.to_ctx()))
+                        .max()
+                        .unwrap_or(0)
+                }
+                _ => PASSTHROUGH_RAY_PAYLOAD_SIZE,
+            });
+            compiled_stages.push(compiled);
+        }
+
+        let stage_names = (0..desc.stages.len())
+            .map(|index| wide(&format!("stage{index}")))
+            .collect::<Vec<_>>();
+        let group_names = (0..desc.groups.len())
+            .map(|index| wide(&format!("group{index}")))
+            .collect::<Vec<_>>();
+        let entry_points = compiled_stages
+            .iter()
+            .map(|(_, entry_point)| wide(entry_point))
+            .collect::<Vec<_>>();
+
+        let exports = stage_names
+            .iter()
+            .zip(&entry_points)
+            .map(|(name, entry_point)| Direct3D12::D3D12_EXPORT_DESC {
+                Name: PCWSTR(name.as_ptr()),
+                ExportToRename: PCWSTR(entry_point.as_ptr()),
+                Flags: Direct3D12::D3D12_EXPORT_FLAG_NONE,
+            })
+            .collect::<Vec<_>>();
+        // Only the entry point is exported, so that helper functions of different
+        // libraries can't collide.
+        let libraries = compiled_stages
+            .iter()
+            .zip(&exports)
+            .map(
+                |((shader, _), export)| Direct3D12::D3D12_DXIL_LIBRARY_DESC {
+                    DXILLibrary: shader.create_native_shader(),
+                    NumExports: 1,
+                    pExports: export,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let import = |stage: Option<u32>| {
+            stage.map_or(PCWSTR::null(), |stage| {
+                PCWSTR(stage_names[stage as usize].as_ptr())
+            })
+        };
+        let hit_groups = desc
+            .groups
+            .iter()
+            .zip(&group_names)
+            .filter_map(|(group, name)| {
+                let (ty, closest_hit, any_hit, intersection) = match *group {
+                    wgt::RayTracingShaderGroup::General { .. } => return None,
+                    wgt::RayTracingShaderGroup::TrianglesHit {
+                        closest_hit,
+                        any_hit,
+                    } => (
+                        Direct3D12::D3D12_HIT_GROUP_TYPE_TRIANGLES,
+                        closest_hit,
+                        any_hit,
+                        None,
+                    ),
+                    wgt::RayTracingShaderGroup::ProceduralHit {
+                        closest_hit,
+                        any_hit,
+                        intersection,
+                    } => (
+                        Direct3D12::D3D12_HIT_GROUP_TYPE_PROCEDURAL_PRIMITIVE,
+                        closest_hit,
+                        any_hit,
+                        Some(intersection),
+                    ),
+                };
+                Some(Direct3D12::D3D12_HIT_GROUP_DESC {
+                    HitGroupExport: PCWSTR(name.as_ptr()),
+                    Type: ty,
+                    AnyHitShaderImport: import(any_hit),
+                    ClosestHitShaderImport: import(closest_hit),
+                    IntersectionShaderImport: import(intersection),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let shader_config = Direct3D12::D3D12_RAYTRACING_SHADER_CONFIG {
+            MaxPayloadSizeInBytes: max_payload_size,
+            MaxAttributeSizeInBytes: Direct3D12::D3D12_RAYTRACING_MAX_ATTRIBUTE_SIZE_IN_BYTES,
+        };
+        let pipeline_config = Direct3D12::D3D12_RAYTRACING_PIPELINE_CONFIG {
+            MaxTraceRecursionDepth: desc.max_recursion_depth,
+        };
+        let root_signature = Direct3D12::D3D12_GLOBAL_ROOT_SIGNATURE {
+            pGlobalRootSignature: unsafe {
+                borrow_optional_interface_temporarily(&desc.layout.shared.signature)
+            },
+        };
+
+        let subobject = |ty, data: *const ffi::c_void| Direct3D12::D3D12_STATE_SUBOBJECT {
+            Type: ty,
+            pDesc: data,
+        };
+        let mut subobjects = Vec::with_capacity(libraries.len() + hit_groups.len() + 3);
+        subobjects.extend(libraries.iter().map(|library| {
+            subobject(
+                Direct3D12::D3D12_STATE_SUBOBJECT_TYPE_DXIL_LIBRARY,
+                <*const _>::cast(library),
+            )
+        }));
+        subobjects.extend(hit_groups.iter().map(|hit_group| {
+            subobject(
+                Direct3D12::D3D12_STATE_SUBOBJECT_TYPE_HIT_GROUP,
+                <*const _>::cast(hit_group),
+            )
+        }));
+        subobjects.extend([
+            subobject(
+                Direct3D12::D3D12_STATE_SUBOBJECT_TYPE_RAYTRACING_SHADER_CONFIG,
+                <*const _>::cast(&shader_config),
+            ),
+            subobject(
+                Direct3D12::D3D12_STATE_SUBOBJECT_TYPE_RAYTRACING_PIPELINE_CONFIG,
+                <*const _>::cast(&pipeline_config),
+            ),
+            subobject(
+                Direct3D12::D3D12_STATE_SUBOBJECT_TYPE_GLOBAL_ROOT_SIGNATURE,
+                <*const _>::cast(&root_signature),
+            ),
+        ]);
+        let state_object_desc = Direct3D12::D3D12_STATE_OBJECT_DESC {
+            Type: Direct3D12::D3D12_STATE_OBJECT_TYPE_RAYTRACING_PIPELINE,
+            NumSubobjects: subobjects.len() as u32,
+            pSubobjects: subobjects.as_ptr(),
+        };
+
+        // Pipeline libraries can't store state objects, so `desc.cache` is unused.
+        let device5 = self.raw.cast::<Direct3D12::ID3D12Device5>().unwrap();
+        let raw: Direct3D12::ID3D12StateObject = {
+            profiling::scope!("ID3D12Device5::CreateStateObject");
+            unsafe { device5.CreateStateObject(&state_object_desc) }
+        }
+        .map_err(|err| {
+            crate::PipelineError::Linkage(wgt::ShaderStages::RAY_TRACING, err.to_string())
+        })?;
+
+        if let Some(label) = desc.label {
+            raw.set_name(label)?;
+        }
+
+        // General groups are identified by the export of their stage, and hit
+        // groups by their own export.
+        let properties = raw
+            .cast::<Direct3D12::ID3D12StateObjectProperties>()
+            .into_device_result("ID3D12StateObjectProperties")?;
+        let handle_size = Direct3D12::D3D12_SHADER_IDENTIFIER_SIZE_IN_BYTES as usize;
+        let mut group_handles = Vec::with_capacity(desc.groups.len() * handle_size);
+        for (group, group_name) in desc.groups.iter().zip(&group_names) {
+            let name = match *group {
+                wgt::RayTracingShaderGroup::General { stage } => &stage_names[stage as usize],
+                _ => group_name,
+            };
+            let identifier = unsafe { properties.GetShaderIdentifier(PCWSTR(name.as_ptr())) };
+            if identifier.is_null() {
+                return Err(crate::DeviceError::Unexpected.into());
+            }
+            group_handles.extend_from_slice(unsafe {
+                core::slice::from_raw_parts(identifier.cast::<u8>(), handle_size)
+            });
+        }
+
+        self.counters.ray_tracing_pipelines.add(1);
+
+        Ok(super::RayTracingPipeline {
+            raw,
+            layout: desc.layout.shared.clone(),
+            group_handles,
+        })
+    }
+    unsafe fn destroy_ray_tracing_pipeline(&self, _pipeline: super::RayTracingPipeline) {
+        self.counters.ray_tracing_pipelines.sub(1);
+    }
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        pipeline: &super::RayTracingPipeline,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<Vec<u8>, crate::DeviceError> {
+        let handle_size = Direct3D12::D3D12_SHADER_IDENTIFIER_SIZE_IN_BYTES as usize;
+        let start = first_group as usize * handle_size;
+        let end = start + group_count as usize * handle_size;
+        Ok(pipeline.group_handles[start..end].to_vec())
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
-        _desc: &crate::PipelineCacheDescriptor<'_>,
+        desc: &crate::PipelineCacheDescriptor<'_>,
     ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
-        Ok(super::PipelineCache)
+        let device: Direct3D12::ID3D12Device1 =
+            self.raw.cast().into_device_result("ID3D12Device1 cast")?;
+
+        let mut data = desc.data.map(<[u8]>::to_vec).unwrap_or_default();
+        let mut library = {
+            profiling::scope!("ID3D12Device1::CreatePipelineLibrary");
+            unsafe { device.CreatePipelineLibrary(&data) }
+        };
+        // The data was already validated by wgpu-core, but the driver can still
+        // reject it (e.g. if it was updated without changing its version number).
+        // Start from an empty library in that case.
+        if library.is_err() && !data.is_empty() {
+            data = Vec::new();
+            library = unsafe { device.CreatePipelineLibrary(&data) };
+        }
+        let library: Direct3D12::ID3D12PipelineLibrary =
+            library.into_device_result("CreatePipelineLibrary")?;
+
+        if let Some(label) = desc.label {
+            library.set_name(label)?;
+        }
+
+        Ok(super::PipelineCache {
+            library: Mutex::new(library),
+            _data: data,
+        })
     }
     unsafe fn destroy_pipeline_cache(&self, _: super::PipelineCache) {}
 
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]> {
+        Some(self.shared.private_caps.pipeline_cache_validation_key)
+    }
+
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        let library = cache.library.lock();
+        let mut data = vec![0; unsafe { library.GetSerializedSize() }];
+        unsafe { library.Serialize(&mut data) }.ok()?;
+        Some(data)
+    }
+
     unsafe fn create_query_set(
         &self,
         desc: &wgt::QuerySetDescriptor<crate::Label>,
@@ -2049,12 +2885,39 @@ impl crate::Device for super::Device {
             raw.set_name(label)?;
         }
 
+        let statistics = if let wgt::QueryType::PipelineStatistics(types) = desc.ty {
+            let size = desc.count as u64
+                * size_of::<Direct3D12::D3D12_QUERY_DATA_PIPELINE_STATISTICS>() as u64;
+            let (buffer, allocation) = suballocation::DeviceAllocationContext::from(self)
+                .create_buffer(&crate::BufferDescriptor {
+                    label: Some("(wgpu internal) pipeline statistics resolve"),
+                    size,
+                    usage: wgt::BufferUses::COPY_SRC | wgt::BufferUses::COPY_DST,
+                    memory_flags: crate::MemoryFlags::empty(),
+                })?;
+            Some(super::PipelineStatisticsResolve {
+                buffer,
+                allocation,
+                offsets: conv::map_pipeline_statistics(types),
+            })
+        } else {
+            None
+        };
+
         self.counters.query_sets.add(1);
 
-        Ok(super::QuerySet { raw, raw_ty })
+        Ok(super::QuerySet {
+            raw,
+            raw_ty,
+            statistics,
+        })
     }
 
-    unsafe fn destroy_query_set(&self, _set: super::QuerySet) {
+    unsafe fn destroy_query_set(&self, set: super::QuerySet) {
+        if let Some(statistics) = set.statistics {
+            suballocation::DeviceAllocationContext::from(self)
+                .free_resource(statistics.buffer, statistics.allocation);
+        }
         self.counters.query_sets.sub(1);
     }
 
@@ -2075,7 +2938,9 @@ impl crate::Device for super::Device {
         &self,
         fence: &super::Fence,
     ) -> Result<crate::FenceValue, crate::DeviceError> {
-        Ok(unsafe { fence.raw.GetCompletedValue() })
+        let value = unsafe { fence.raw.GetCompletedValue() };
+        self.poll_debug_printf();
+        Ok(value)
     }
     unsafe fn wait(
         &self,
@@ -2088,6 +2953,7 @@ impl crate::Device for super::Device {
         // We first check if the fence has already reached the value we're waiting for.
         let mut fence_value = unsafe { fence.raw.GetCompletedValue() };
         if fence_value >= value {
+            self.poll_debug_printf();
             return Ok(true);
         }
 
@@ -2154,12 +3020,58 @@ impl crate::Device for super::Device {
             log::trace!("Wait complete! Fence actual value: {}", fence_value);
 
             if fence_value >= value {
+                self.poll_debug_printf();
                 break Ok(true);
             }
         }
     }
 
+    unsafe fn create_shared_fence(&self) -> Result<super::Fence, crate::DeviceError> {
+        // All fences are created with `D3D12_FENCE_FLAG_SHARED`.
+        unsafe { self.create_fence() }
+    }
+
+    unsafe fn export_fence(
+        &self,
+        fence: &super::Fence,
+    ) -> Result<wgt::SharedFenceHandle, crate::DeviceError> {
+        let handle = unsafe {
+            self.raw.CreateSharedHandle(
+                &fence.raw,
+                None,
+                Foundation::GENERIC_ALL.0,
+                windows::core::PCWSTR::null(),
+            )
+        }
+        .into_device_result("Shared fence handle creation")?;
+        Ok(wgt::SharedFenceHandle::D3D12Fence(handle.0))
+    }
+
+    unsafe fn import_fence(
+        &self,
+        handle: wgt::SharedFenceHandle,
+    ) -> Result<super::Fence, crate::DeviceError> {
+        let wgt::SharedFenceHandle::D3D12Fence(handle) = handle else {
+            crate::hal_usage_error("DX12 devices can only import D3D12 fence handles")
+        };
+        let mut raw = None::<Direct3D12::ID3D12Fence>;
+        unsafe {
+            self.raw
+                .OpenSharedHandle(Foundation::HANDLE(handle), &mut raw)
+        }
+        .into_device_result("Shared fence import")?;
+        let raw = raw.ok_or(crate::DeviceError::Unexpected)?;
+
+        self.counters.fences.add(1);
+
+        Ok(super::Fence { raw })
+    }
+
     unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        if let Some(graphics_analysis) = self.graphics_analysis.as_ref() {
+            unsafe { graphics_analysis.BeginCapture() };
+            return true;
+        }
         #[cfg(feature = "renderdoc")]
         {
             unsafe {
@@ -2172,6 +3084,10 @@ impl crate::Device for super::Device {
     }
 
     unsafe fn stop_graphics_debugger_capture(&self) {
+        if let Some(graphics_analysis) = self.graphics_analysis.as_ref() {
+            unsafe { graphics_analysis.EndCapture() };
+            return;
+        }
         #[cfg(feature = "renderdoc")]
         unsafe {
             self.render_doc
@@ -2357,6 +3273,29 @@ impl crate::Device for super::Device {
         );
     }
 
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        data: &[u8],
+    ) -> Option<wgt::BufferAddress> {
+        let (identifier, deserialized_size) =
+            auxil::parse_serialized_acceleration_structure_header(data)?;
+        let identifier = unsafe {
+            identifier
+                .as_ptr()
+                .cast::<Direct3D12::D3D12_SERIALIZED_DATA_DRIVER_MATCHING_IDENTIFIER>()
+                .read_unaligned()
+        };
+        let device5 = self.raw.cast::<Direct3D12::ID3D12Device5>().ok()?;
+        let status = unsafe {
+            device5.CheckDriverMatchingIdentifier(
+                Direct3D12::D3D12_SERIALIZED_DATA_RAYTRACING_ACCELERATION_STRUCTURE,
+                &identifier,
+            )
+        };
+        (status == Direct3D12::D3D12_DRIVER_MATCHING_IDENTIFIER_COMPATIBLE_WITH_DEVICE)
+            .then_some(deserialized_size)
+    }
+
     fn get_internal_counters(&self) -> wgt::HalCounters {
         self.counters.as_ref().clone()
     }
@@ -2409,4 +3348,95 @@ impl crate::Device for super::Device {
 
         Ok(())
     }
+
+    fn lost_reason(&self) -> wgt::DeviceLostReason {
+        let Err(err) = (unsafe { self.raw.GetDeviceRemovedReason() }) else {
+            return wgt::DeviceLostReason::Unknown;
+        };
+        match err.code() {
+            Dxgi::DXGI_ERROR_DEVICE_HUNG => wgt::DeviceLostReason::Hung,
+            Dxgi::DXGI_ERROR_DEVICE_REMOVED => wgt::DeviceLostReason::Removed,
+            Dxgi::DXGI_ERROR_DEVICE_RESET => wgt::DeviceLostReason::DriverReset,
+            _ => wgt::DeviceLostReason::Unknown,
+        }
+    }
+
+    fn fault_info(&self) -> Option<String> {
+        // Device Removed Extended Data is only collected if it was enabled before the device
+        // was created, see `wgt::InstanceFlags::DEVICE_FAULT_DIAGNOSTICS`.
+        let dred = self
+            .raw
+            .cast::<Direct3D12::ID3D12DeviceRemovedExtendedData1>()
+            .ok()?;
+        let mut lines = Vec::new();
+
+        let mut breadcrumbs = Direct3D12::D3D12_DRED_AUTO_BREADCRUMBS_OUTPUT1::default();
+        if unsafe { dred.GetAutoBreadcrumbsOutput1(&mut breadcrumbs) }.is_ok() {
+            let mut node = breadcrumbs.pHeadAutoBreadcrumbNode;
+            while let Some(current) = unsafe { node.as_ref() } {
+                node = current.pNext;
+                let count = current.BreadcrumbCount;
+                let completed = unsafe { current.pLastBreadcrumbValue.as_ref() }
+                    .copied()
+                    .unwrap_or(0);
+                // Only report the command lists the GPU didn't finish.
+                if completed >= count || current.pCommandHistory.is_null() {
+                    continue;
+                }
+                let history =
+                    unsafe { core::slice::from_raw_parts(current.pCommandHistory, count as usize) };
+                lines.push(format!(
+                    "DRED: command list {:?} on queue {:?} completed {completed} of {count} operations, next: {:?}",
+                    dred_object_name(current.pCommandListDebugNameW),
+                    dred_object_name(current.pCommandQueueDebugNameW),
+                    history[completed as usize],
+                ));
+            }
+        }
+
+        let mut page_fault = Direct3D12::D3D12_DRED_PAGE_FAULT_OUTPUT1::default();
+        if unsafe { dred.GetPageFaultAllocationOutput1(&mut page_fault) }.is_ok()
+            && page_fault.PageFaultVA != 0
+        {
+            lines.push(format!("DRED: page fault at {:#x}", page_fault.PageFaultVA));
+            for (state, head) in [
+                ("existing", page_fault.pHeadExistingAllocationNode),
+                ("recently freed", page_fault.pHeadRecentFreedAllocationNode),
+            ] {
+                let mut node = head;
+                while let Some(current) = unsafe { node.as_ref() } {
+                    node = current.pNext;
+                    lines.push(format!(
+                        "  {state} allocation {:?} ({:?})",
+                        dred_object_name(current.ObjectNameW),
+                        current.AllocationType,
+                    ));
+                }
+            }
+        }
+
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    fn take_validation_messages(&self) -> Vec<String> {
+        if !self
+            .shared
+            .private_caps
+            .instance_flags
+            .contains(wgt::InstanceFlags::GPU_BASED_VALIDATION)
+        {
+            return Vec::new();
+        }
+        // The debug layer includes the names of the objects involved, which are set
+        // from their labels.
+        auxil::dxgi::exception::take_gpu_based_validation_messages()
+    }
+}
+
+/// Returns the name of an object reported by Device Removed Extended Data.
+fn dred_object_name(name: windows::core::PCWSTR) -> String {
+    if name.is_null() {
+        return String::new();
+    }
+    unsafe { name.to_string() }.unwrap_or_default()
 }
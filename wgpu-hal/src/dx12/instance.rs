@@ -30,9 +30,8 @@ impl crate::Instance for super::Instance {
         {
             // Enable debug layer
             if let Ok(Some(debug_controller)) = lib_main.debug_interface() {
-                if desc.flags.intersects(wgt::InstanceFlags::VALIDATION) {
-                    unsafe { debug_controller.EnableDebugLayer() }
-                }
+                // GPU-based validation is a part of the debug layer.
+                unsafe { debug_controller.EnableDebugLayer() }
                 if desc
                     .flags
                     .intersects(wgt::InstanceFlags::GPU_BASED_VALIDATION)
@@ -47,6 +46,22 @@ impl crate::Instance for super::Instance {
             }
         }
 
+        if desc
+            .flags
+            .contains(wgt::InstanceFlags::DEVICE_FAULT_DIAGNOSTICS)
+        {
+            if let Ok(Some(dred_settings)) = lib_main.dred_settings() {
+                unsafe {
+                    dred_settings
+                        .SetAutoBreadcrumbsEnablement(Direct3D12::D3D12_DRED_ENABLEMENT_FORCED_ON);
+                    dred_settings
+                        .SetPageFaultEnablement(Direct3D12::D3D12_DRED_ENABLEMENT_FORCED_ON);
+                }
+            } else {
+                log::warn!("Failed to enable Device Removed Extended Data");
+            }
+        }
+
         let (lib_dxgi, factory) = auxil::dxgi::factory::create_factory(desc.flags)?;
 
         // Create IDXGIFactoryMedia
@@ -108,6 +123,7 @@ impl crate::Instance for super::Instance {
             supports_allow_tearing,
             flags: desc.flags,
             memory_budget_thresholds: desc.memory_budget_thresholds,
+            toggles: desc.toggles,
             compiler_container: Arc::new(compiler_container),
         })
     }
@@ -146,6 +162,7 @@ impl crate::Instance for super::Instance {
                     &self.library,
                     self.flags,
                     self.memory_budget_thresholds,
+                    &self.toggles,
                     self.compiler_container.clone(),
                 )
             })
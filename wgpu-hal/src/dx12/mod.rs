@@ -75,16 +75,18 @@ Otherwise, we pass a range corresponding only to the current bind group.
 mod adapter;
 mod command;
 mod conv;
+mod depth_resolve;
 mod descriptor;
 mod device;
 mod instance;
 mod sampler;
 mod shader_compilation;
+mod shared;
 mod suballocation;
 mod types;
 mod view;
 
-use alloc::{borrow::ToOwned as _, string::String, sync::Arc, vec::Vec};
+use alloc::{borrow::ToOwned as _, string::String, sync::Arc, vec, vec::Vec};
 use core::{ffi, fmt, mem, num::NonZeroU32, ops::Deref};
 
 use arrayvec::ArrayVec;
@@ -95,7 +97,7 @@ use windows::{
     Win32::{
         Foundation,
         Graphics::{Direct3D, Direct3D12, DirectComposition, Dxgi},
-        System::Threading,
+        System::{Performance, Threading},
     },
     core::{Free, Interface},
 };
@@ -108,6 +110,8 @@ use crate::auxil::{
     },
 };
 
+pub use shared::KeyedMutex;
+
 #[derive(Debug)]
 struct DynLib {
     inner: libloading::Library,
@@ -235,6 +239,19 @@ impl D3D12Lib {
     }
 
     fn debug_interface(&self) -> Result<Option<Direct3D12::ID3D12Debug>, crate::DeviceError> {
+        self.get_debug_interface()
+    }
+
+    /// Returns the settings of Device Removed Extended Data, which apply to devices created
+    /// after they are changed.
+    fn dred_settings(
+        &self,
+    ) -> Result<Option<Direct3D12::ID3D12DeviceRemovedExtendedDataSettings>, crate::DeviceError>
+    {
+        self.get_debug_interface()
+    }
+
+    fn get_debug_interface<T: Interface>(&self) -> Result<Option<T>, crate::DeviceError> {
         // Calls windows::Win32::Graphics::Direct3D12::D3D12GetDebugInterface on d3d12.dll
         type Fun = extern "system" fn(
             riid: *const windows_core::GUID,
@@ -245,7 +262,7 @@ impl D3D12Lib {
 
         let mut result__ = None;
 
-        let res = (func)(&Direct3D12::ID3D12Debug::IID, <*mut _>::cast(&mut result__)).ok();
+        let res = (func)(&T::IID, <*mut _>::cast(&mut result__)).ok();
 
         if let Err(ref err) = res {
             match err.code() {
@@ -297,6 +314,32 @@ impl DxgiLib {
         result__.ok_or(crate::DeviceError::Unexpected).map(Some)
     }
 
+    /// Returns the programmatic capture interface of the attached graphics debugger, such
+    /// as PIX, or `None` if no graphics debugger is attached.
+    pub fn graphics_analysis(&self) -> Option<Dxgi::IDXGraphicsAnalysis> {
+        // Calls windows::Win32::Graphics::Dxgi::DXGIGetDebugInterface1 on dxgi.dll
+        type Fun = extern "system" fn(
+            flags: u32,
+            riid: *const windows_core::GUID,
+            pdebug: *mut *mut ffi::c_void,
+        ) -> windows_core::HRESULT;
+        let func: libloading::Symbol<Fun> =
+            unsafe { self.lib.get(c"DXGIGetDebugInterface1".to_bytes()) }.ok()?;
+
+        let mut result__ = None;
+
+        // Fails with `E_NOINTERFACE` if no graphics debugger is attached.
+        (func)(
+            0,
+            &Dxgi::IDXGraphicsAnalysis::IID,
+            <*mut _>::cast(&mut result__),
+        )
+        .ok()
+        .ok()?;
+
+        result__
+    }
+
     /// Will error with crate::DeviceError::Unexpected if DXGI 1.4 is not available.
     pub fn create_factory4(
         &self,
@@ -413,6 +456,7 @@ impl crate::Api for Api {
     type Sampler = Sampler;
     type QuerySet = QuerySet;
     type Fence = Fence;
+    type MemoryHeap = MemoryHeap;
 
     type BindGroupLayout = BindGroupLayout;
     type BindGroup = BindGroup;
@@ -420,6 +464,7 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type RayTracingPipeline = RayTracingPipeline;
     type PipelineCache = PipelineCache;
 
     type AccelerationStructure = AccelerationStructure;
@@ -437,10 +482,12 @@ crate::impl_dyn_resource!(
     Device,
     Fence,
     Instance,
+    MemoryHeap,
     PipelineCache,
     PipelineLayout,
     QuerySet,
     Queue,
+    RayTracingPipeline,
     RenderPipeline,
     Sampler,
     ShaderModule,
@@ -461,6 +508,7 @@ pub struct Instance {
     _lib_dxgi: DxgiLib,
     flags: wgt::InstanceFlags,
     memory_budget_thresholds: wgt::MemoryBudgetThresholds,
+    toggles: wgt::TogglesDescriptor,
     compiler_container: Arc<shader_compilation::CompilerContainer>,
 }
 
@@ -531,6 +579,11 @@ struct SwapChain {
     present_mode: wgt::PresentMode,
     format: wgt::TextureFormat,
     size: wgt::Extent3d,
+    /// The present count of `raw` when it was configured, which the IDs of
+    /// presentation statistics are relative to.
+    base_present_count: u32,
+    /// The frame statistics last reported by [`crate::Surface::get_presentation_statistics`].
+    last_statistics: Option<Dxgi::DXGI_FRAME_STATISTICS>,
 }
 
 enum SurfaceTarget {
@@ -579,6 +632,10 @@ struct PrivateCapabilities {
     suballocation_supported: bool,
     shader_model: naga::back::hlsl::ShaderModel,
     max_sampler_descriptor_heap_size: u32,
+    pipeline_library_supported: bool,
+    pipeline_cache_validation_key: [u8; 16],
+    /// The 2x4, 4x2 and 4x4 shading rates are supported.
+    additional_shading_rates_supported: bool,
 }
 
 #[derive(Default)]
@@ -637,6 +694,8 @@ struct CommandSignatures {
     draw: Direct3D12::ID3D12CommandSignature,
     draw_indexed: Direct3D12::ID3D12CommandSignature,
     dispatch: Direct3D12::ID3D12CommandSignature,
+    /// Present if [`wgt::Features::EXPERIMENTAL_MESH_SHADER`] is enabled.
+    draw_mesh_tasks: Option<Direct3D12::ID3D12CommandSignature>,
 }
 
 struct DeviceShared {
@@ -646,6 +705,15 @@ struct DeviceShared {
     heap_views: descriptor::GeneralHeap,
     sampler_heap: sampler::SamplerHeap,
     private_caps: PrivateCapabilities,
+    /// Present if [`wgt::Features::SHADER_DEBUG_PRINTF`] is enabled.
+    debug_printf: Option<DebugPrintfBuffer>,
+}
+
+/// The buffer that shaders write [`wgt::Features::SHADER_DEBUG_PRINTF`] messages to.
+struct DebugPrintfBuffer {
+    /// A persistently mapped buffer of [`auxil::debug_printf::BUFFER_SIZE`] bytes.
+    resource: Direct3D12::ID3D12Resource,
+    reader: auxil::debug_printf::DebugPrintf,
 }
 
 unsafe impl Send for DeviceShared {}
@@ -665,10 +733,15 @@ pub struct Device {
     library: Arc<D3D12Lib>,
     #[cfg(feature = "renderdoc")]
     render_doc: auxil::renderdoc::RenderDoc,
+    /// Present if PIX or another graphics debugger supporting programmatic captures
+    /// is attached.
+    graphics_analysis: Option<Dxgi::IDXGraphicsAnalysis>,
     null_rtv_handle: descriptor::Handle,
     mem_allocator: Allocator,
     compiler_container: Arc<shader_compilation::CompilerContainer>,
     shader_cache: Mutex<ShaderCache>,
+    /// Present if [`wgt::Features::DEPTH_STENCIL_RESOLVE`] is enabled.
+    depth_resolver: Option<Arc<depth_resolve::DepthResolver>>,
     counters: Arc<wgt::HalCounters>,
 }
 
@@ -679,7 +752,7 @@ impl Drop for Device {
             .shared
             .private_caps
             .instance_flags
-            .contains(wgt::InstanceFlags::VALIDATION)
+            .intersects(wgt::InstanceFlags::VALIDATION | wgt::InstanceFlags::GPU_BASED_VALIDATION)
         {
             auxil::dxgi::exception::unregister_exception_handler();
         }
@@ -691,7 +764,13 @@ unsafe impl Sync for Device {}
 
 pub struct Queue {
     raw: Direct3D12::ID3D12CommandQueue,
+    /// Type of the command lists this queue executes.
+    list_type: Direct3D12::D3D12_COMMAND_LIST_TYPE,
     temp_lists: Mutex<Vec<Option<Direct3D12::ID3D12CommandList>>>,
+    /// Fences, with values, to signal after the next submission.
+    ///
+    /// These are added by [`crate::Queue::signal_fence`].
+    pending_signals: Mutex<Vec<(Direct3D12::ID3D12Fence, crate::FenceValue)>>,
 }
 
 impl Queue {
@@ -742,6 +821,8 @@ enum RootElement {
     },
     /// Descriptor table referring to the entire sampler heap.
     SamplerHeap,
+    /// Root descriptor for the debug printf buffer.
+    DebugPrintfBuffer,
     /// Root constants for dynamic offsets.
     ///
     /// start..end is the range of values in [`PassState::dynamic_storage_buffer_offsets`]
@@ -762,6 +843,7 @@ enum PassKind {
 struct PassState {
     has_label: bool,
     resolves: ArrayVec<PassResolve, { crate::MAX_COLOR_ATTACHMENTS }>,
+    depth_stencil_resolve: Option<depth_resolve::PassDepthStencilResolve>,
     layout: PipelineLayoutShared,
     root_elements: [RootElement; MAX_ROOT_ELEMENTS],
     constant_data: [u32; MAX_ROOT_ELEMENTS],
@@ -769,6 +851,12 @@ struct PassState {
     dirty_root_elements: u64,
     vertex_buffers: [Direct3D12::D3D12_VERTEX_BUFFER_VIEW; crate::MAX_VERTEX_BUFFERS],
     dirty_vertex_buffers: usize,
+    /// D3D12 sets all viewports at once, so keep the ones set so far in the pass.
+    viewports: [Direct3D12::D3D12_VIEWPORT; wgt::MAX_VIEWPORTS as usize],
+    /// One past the highest index of the viewports set in the pass.
+    viewport_count: usize,
+    /// Scissor rectangle of every viewport.
+    scissor: Foundation::RECT,
     kind: PassKind,
 }
 
@@ -782,12 +870,14 @@ impl PassState {
         PassState {
             has_label: false,
             resolves: ArrayVec::new(),
+            depth_stencil_resolve: None,
             layout: PipelineLayoutShared {
                 signature: None,
                 total_root_elements: 0,
                 special_constants: None,
                 root_constant_info: None,
                 sampler_heap_root_index: None,
+                debug_printf_root_index: None,
             },
             root_elements: [RootElement::Empty; MAX_ROOT_ELEMENTS],
             constant_data: [0; MAX_ROOT_ELEMENTS],
@@ -795,6 +885,9 @@ impl PassState {
             dirty_root_elements: 0,
             vertex_buffers: [Default::default(); crate::MAX_VERTEX_BUFFERS],
             dirty_vertex_buffers: 0,
+            viewports: [Default::default(); wgt::MAX_VIEWPORTS as usize],
+            viewport_count: 0,
+            scissor: Default::default(),
             kind: PassKind::Transfer,
         }
     }
@@ -807,12 +900,16 @@ impl PassState {
 
 pub struct CommandEncoder {
     allocator: Direct3D12::ID3D12CommandAllocator,
+    list_type: Direct3D12::D3D12_COMMAND_LIST_TYPE,
     device: Direct3D12::ID3D12Device,
     shared: Arc<DeviceShared>,
     mem_allocator: Allocator,
 
     rtv_pool: Arc<Mutex<descriptor::CpuPool>>,
     temp_rtv_handles: Vec<descriptor::Handle>,
+    /// Shader-visible SRVs of the depth/stencil resolves, freed on reset.
+    temp_resolve_srvs: Vec<descriptor::DualHandle>,
+    depth_resolver: Option<Arc<depth_resolve::DepthResolver>>,
 
     null_rtv_handle: descriptor::Handle,
     list: Option<Direct3D12::ID3D12GraphicsCommandList>,
@@ -824,6 +921,10 @@ pub struct CommandEncoder {
     /// the given pool & location.
     end_of_pass_timer_query: Option<(Direct3D12::ID3D12QueryHeap, u32)>,
 
+    /// If set, the end of the current render/compute pass will end the pipeline
+    /// statistics query at the given heap & location.
+    pass_pipeline_statistics_query: Option<(Direct3D12::ID3D12QueryHeap, u32)>,
+
     counters: Arc<wgt::HalCounters>,
 }
 
@@ -943,6 +1044,7 @@ pub struct TextureView {
     handle_rtv: Option<descriptor::Handle>,
     handle_dsv_ro: Option<descriptor::Handle>,
     handle_dsv_rw: Option<descriptor::Handle>,
+    resolve_source: Option<depth_resolve::ResolveSource>,
 }
 
 impl crate::DynTextureView for TextureView {}
@@ -965,6 +1067,18 @@ unsafe impl Sync for Sampler {}
 pub struct QuerySet {
     raw: Direct3D12::ID3D12QueryHeap,
     raw_ty: Direct3D12::D3D12_QUERY_TYPE,
+    /// Set for pipeline statistics query sets.
+    statistics: Option<PipelineStatisticsResolve>,
+}
+
+/// D3D12 resolves every pipeline statistic of a query, so they are resolved into
+/// this buffer first and the requested ones are copied out of it.
+#[derive(Debug)]
+struct PipelineStatisticsResolve {
+    buffer: Direct3D12::ID3D12Resource,
+    allocation: suballocation::Allocation,
+    /// The offsets of the requested statistics in a `D3D12_QUERY_DATA_PIPELINE_STATISTICS`.
+    offsets: ArrayVec<u64, 5>,
 }
 
 impl crate::DynQuerySet for QuerySet {}
@@ -972,6 +1086,17 @@ impl crate::DynQuerySet for QuerySet {}
 unsafe impl Send for QuerySet {}
 unsafe impl Sync for QuerySet {}
 
+#[derive(Debug)]
+pub struct MemoryHeap {
+    raw: Direct3D12::ID3D12Heap,
+    size: wgt::BufferAddress,
+}
+
+impl crate::DynMemoryHeap for MemoryHeap {}
+
+unsafe impl Send for MemoryHeap {}
+unsafe impl Sync for MemoryHeap {}
+
 #[derive(Debug)]
 pub struct Fence {
     raw: Direct3D12::ID3D12Fence,
@@ -1056,6 +1181,7 @@ struct PipelineLayoutShared {
     special_constants: Option<PipelineLayoutSpecialConstants>,
     root_constant_info: Option<RootConstantInfo>,
     sampler_heap_root_index: Option<RootIndex>,
+    debug_printf_root_index: Option<RootIndex>,
 }
 
 unsafe impl Send for PipelineLayoutShared {}
@@ -1086,6 +1212,7 @@ pub struct ShaderModule {
     source: ShaderModuleSource,
     raw_name: Option<alloc::ffi::CString>,
     runtime_checks: wgt::ShaderRuntimeChecks,
+    optimization: wgt::ShaderOptimization,
 }
 
 impl crate::DynShaderModule for ShaderModule {}
@@ -1102,6 +1229,7 @@ pub(super) struct ShaderCacheKey {
     entry_point: String,
     stage: naga::ShaderStage,
     shader_model: naga::back::hlsl::ShaderModel,
+    optimization: wgt::ShaderOptimization,
 }
 
 pub(super) struct ShaderCacheValue {
@@ -1162,10 +1290,83 @@ unsafe impl Send for ComputePipeline {}
 unsafe impl Sync for ComputePipeline {}
 
 #[derive(Debug)]
-pub struct PipelineCache;
+pub struct PipelineCache {
+    library: Mutex<Direct3D12::ID3D12PipelineLibrary>,
+    /// The serialized library the cache was created from.
+    ///
+    /// D3D12 doesn't copy this, so it must outlive `library`.
+    _data: Vec<u8>,
+}
 
 impl crate::DynPipelineCache for PipelineCache {}
 
+unsafe impl Send for PipelineCache {}
+unsafe impl Sync for PipelineCache {}
+
+impl PipelineCache {
+    /// Load the pipeline stored under `name`, or create it with `create` and
+    /// store it under that name for next time.
+    ///
+    /// D3D12 checks that the stored pipeline was created from an identical
+    /// description, so a name collision only costs a cache miss.
+    fn load_or_create(
+        &self,
+        name: &str,
+        load: impl FnOnce(
+            &Direct3D12::ID3D12PipelineLibrary,
+            &windows::core::HSTRING,
+        ) -> windows::core::Result<Direct3D12::ID3D12PipelineState>,
+        create: impl FnOnce() -> windows::core::Result<Direct3D12::ID3D12PipelineState>,
+    ) -> windows::core::Result<Direct3D12::ID3D12PipelineState> {
+        let name = windows::core::HSTRING::from(name);
+        if let Ok(raw) = load(&self.library.lock(), &name) {
+            return Ok(raw);
+        }
+        let raw = create()?;
+        // This fails if a different pipeline is already stored under the same
+        // name, in which case this one simply isn't cached.
+        let _ = unsafe { self.library.lock().StorePipeline(&name, &raw) };
+        Ok(raw)
+    }
+}
+
+/// FNV-1a, used to name the pipelines stored in a [`PipelineCache`].
+///
+/// Names must be stable between runs of the application, which rules out
+/// randomly seeded hashers.
+struct PipelineNameHasher(u64);
+
+impl Default for PipelineNameHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl core::hash::Hasher for PipelineNameHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl PipelineNameHasher {
+    fn write_shader(&mut self, shader: &Direct3D12::D3D12_SHADER_BYTECODE) {
+        use core::hash::Hasher as _;
+
+        if !shader.pShaderBytecode.is_null() {
+            self.write(unsafe {
+                core::slice::from_raw_parts(shader.pShaderBytecode.cast(), shader.BytecodeLength)
+            });
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct AccelerationStructure {
     resource: Direct3D12::ID3D12Resource,
@@ -1174,6 +1375,19 @@ pub struct AccelerationStructure {
 
 impl crate::DynAccelerationStructure for AccelerationStructure {}
 
+#[derive(Debug)]
+pub struct RayTracingPipeline {
+    raw: Direct3D12::ID3D12StateObject,
+    layout: PipelineLayoutShared,
+    /// The shader identifiers of the shader groups, in group order.
+    group_handles: Vec<u8>,
+}
+
+impl crate::DynRayTracingPipeline for RayTracingPipeline {}
+
+unsafe impl Send for RayTracingPipeline {}
+unsafe impl Sync for RayTracingPipeline {}
+
 impl SwapChain {
     unsafe fn release_resources(mut self) -> Dxgi::IDXGISwapChain3 {
         unsafe { Foundation::HANDLE::free(&mut self.waitable) };
@@ -1361,6 +1575,83 @@ impl crate::Surface for Surface {
             | SurfaceTarget::SwapChainPanel(_) => {}
         }
 
+        // Only swap chains of windows can enter exclusive fullscreen.
+        if let SurfaceTarget::WndHandle(_) = self.target {
+            let mut fullscreen = windows::core::BOOL::default();
+            unsafe { swap_chain.GetFullscreenState(Some(&mut fullscreen), None) }
+                .into_device_result("GetFullscreenState")?;
+            if fullscreen.as_bool() != config.exclusive_fullscreen {
+                match unsafe { swap_chain.SetFullscreenState(config.exclusive_fullscreen, None) } {
+                    // The buffers must be resized after switching modes.
+                    Ok(()) => unsafe {
+                        swap_chain.ResizeBuffers(
+                            swap_chain_buffer,
+                            config.extent.width,
+                            config.extent.height,
+                            non_srgb_format,
+                            flags,
+                        )
+                    }
+                    .into_device_result("ResizeBuffers")?,
+                    // This fails for example while the window isn't focused,
+                    // in which case we continue in windowed mode.
+                    Err(err) => log::warn!("SetFullscreenState failed: {err}"),
+                }
+            }
+        }
+
+        let color_space = auxil::dxgi::conv::map_color_space(config.color_space);
+        let color_space_support = unsafe { swap_chain.CheckColorSpaceSupport(color_space) }
+            .into_device_result("CheckColorSpaceSupport")?;
+        if color_space_support & Dxgi::DXGI_SWAP_CHAIN_COLOR_SPACE_SUPPORT_FLAG_PRESENT.0 as u32
+            == 0
+        {
+            return Err(crate::SurfaceError::Other(
+                "color space is not supported with the surface format",
+            ));
+        }
+        unsafe { swap_chain.SetColorSpace1(color_space) }.into_device_result("SetColorSpace1")?;
+
+        // `IDXGISwapChain4` is available since Windows 10 1709. Without it, the metadata is ignored.
+        if let Ok(swap_chain4) = swap_chain.cast::<Dxgi::IDXGISwapChain4>() {
+            match config.hdr_metadata {
+                Some(hdr_metadata) => {
+                    // Chromaticity coordinates are in units of 0.00002.
+                    let xy = |[x, y]: [f32; 2]| [(x * 50000.0) as u16, (y * 50000.0) as u16];
+                    let [red, green, blue, white] =
+                        crate::auxil::color_space_primaries(config.color_space);
+                    let metadata = Dxgi::DXGI_HDR_METADATA_HDR10 {
+                        RedPrimary: xy(red),
+                        GreenPrimary: xy(green),
+                        BluePrimary: xy(blue),
+                        WhitePoint: xy(white),
+                        MaxMasteringLuminance: hdr_metadata.max_luminance as u32,
+                        // In units of 0.0001 nits.
+                        MinMasteringLuminance: (hdr_metadata.min_luminance * 10000.0) as u32,
+                        MaxContentLightLevel: hdr_metadata.max_content_light_level as u16,
+                        MaxFrameAverageLightLevel: hdr_metadata.max_frame_average_light_level
+                            as u16,
+                    };
+                    let metadata_bytes = unsafe {
+                        core::slice::from_raw_parts(
+                            core::ptr::from_ref(&metadata).cast::<u8>(),
+                            size_of_val(&metadata),
+                        )
+                    };
+                    unsafe {
+                        swap_chain4.SetHDRMetaData(
+                            Dxgi::DXGI_HDR_METADATA_TYPE_HDR10,
+                            Some(metadata_bytes),
+                        )
+                    }
+                }
+                None => unsafe {
+                    swap_chain4.SetHDRMetaData(Dxgi::DXGI_HDR_METADATA_TYPE_NONE, None)
+                },
+            }
+            .into_device_result("SetHDRMetaData")?;
+        }
+
         unsafe { swap_chain.SetMaximumFrameLatency(config.maximum_frame_latency) }
             .into_device_result("SetMaximumFrameLatency")?;
         let waitable = unsafe { swap_chain.GetFrameLatencyWaitableObject() };
@@ -1372,6 +1663,9 @@ impl crate::Surface for Surface {
             resources.push(resource);
         }
 
+        // Fails if nothing was presented with the swap chain yet.
+        let base_present_count = unsafe { swap_chain.GetLastPresentCount() }.unwrap_or(0);
+
         let mut swapchain = self.swap_chain.write();
         *swapchain = Some(SwapChain {
             raw: swap_chain,
@@ -1381,6 +1675,8 @@ impl crate::Surface for Surface {
             present_mode: config.present_mode,
             format: config.format,
             size: config.extent,
+            base_present_count,
+            last_statistics: None,
         });
 
         Ok(())
@@ -1397,6 +1693,9 @@ impl crate::Surface for Surface {
                 // cleaning up, without error.
                 let _ = device.wait_for_present_queue_idle();
 
+                // Swap chains can't be released in exclusive fullscreen.
+                let _ = sc.raw.SetFullscreenState(false, None);
+
                 let _raw = sc.release_resources();
             }
         }
@@ -1410,7 +1709,9 @@ impl crate::Surface for Surface {
         let mut swapchain = self.swap_chain.write();
         let sc = swapchain.as_mut().unwrap();
 
-        unsafe { sc.wait(timeout) }?;
+        if !unsafe { sc.wait(timeout) }? {
+            return Ok(None);
+        }
 
         let base_index = unsafe { sc.raw.GetCurrentBackBufferIndex() } as usize;
         let index = (base_index + sc.acquired_count) % sc.resources.len();
@@ -1438,6 +1739,76 @@ impl crate::Surface for Surface {
         let sc = swapchain.as_mut().unwrap();
         sc.acquired_count -= 1;
     }
+
+    unsafe fn set_target_present_time(&self, _time: wgt::PresentationTimestamp) {
+        // DXGI can't schedule presents.
+    }
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        let mut swapchain = self.swap_chain.write();
+        let sc = swapchain.as_mut().unwrap();
+
+        // DXGI only reports the latest present that was displayed. This fails
+        // before the first present is displayed, and while the statistics are
+        // reset, for example when the window moves to another output.
+        let mut statistics = Dxgi::DXGI_FRAME_STATISTICS::default();
+        if unsafe { sc.raw.GetFrameStatistics(&mut statistics) }.is_err() {
+            return Ok(Vec::new());
+        }
+        let present_id = statistics.PresentCount.wrapping_sub(sc.base_present_count);
+        let last = sc.last_statistics.replace(statistics);
+        if present_id == 0 || last.is_some_and(|last| last.PresentCount == statistics.PresentCount)
+        {
+            return Ok(Vec::new());
+        }
+
+        let mut frequency = 0;
+        unsafe { windows::Win32::System::Performance::QueryPerformanceFrequency(&mut frequency) }
+            .into_device_result("QueryPerformanceFrequency")?;
+        let qpc_to_ns = |qpc: i64| (qpc as u128 * 1_000_000_000) / frequency as u128;
+
+        let refresh_interval = last
+            .filter(|last| statistics.SyncRefreshCount > last.SyncRefreshCount)
+            .map(|last| {
+                let refreshes = statistics.SyncRefreshCount - last.SyncRefreshCount;
+                let elapsed = qpc_to_ns(statistics.SyncQPCTime - last.SyncQPCTime);
+                core::time::Duration::from_nanos((elapsed / refreshes as u128) as u64)
+            });
+        Ok(vec![wgt::PresentationStatistics {
+            present_id: present_id.into(),
+            present_time: wgt::PresentationTimestamp(qpc_to_ns(statistics.SyncQPCTime)),
+            refresh_interval,
+            dropped: false,
+        }])
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        let swapchain = self.swap_chain.read();
+        let sc = swapchain.as_ref().unwrap();
+
+        let mut fullscreen = windows::core::BOOL::default();
+        if unsafe { sc.raw.GetFullscreenState(Some(&mut fullscreen), None) }.is_ok()
+            && fullscreen.as_bool()
+        {
+            return wgt::PresentationPath::Direct;
+        }
+
+        // Independent flip shows up as presenting with an overlay.
+        let Ok(media) = sc.raw.cast::<Dxgi::IDXGISwapChainMedia>() else {
+            return wgt::PresentationPath::Unknown;
+        };
+        let mut statistics = Dxgi::DXGI_FRAME_STATISTICS_MEDIA::default();
+        if unsafe { media.GetFrameStatisticsMedia(&mut statistics) }.is_err() {
+            return wgt::PresentationPath::Unknown;
+        }
+        match statistics.CompositionMode {
+            Dxgi::DXGI_FRAME_PRESENTATION_MODE_COMPOSED => wgt::PresentationPath::Composed,
+            Dxgi::DXGI_FRAME_PRESENTATION_MODE_OVERLAY => wgt::PresentationPath::Direct,
+            _ => wgt::PresentationPath::Unknown,
+        }
+    }
 }
 
 impl crate::Queue for Queue {
@@ -1460,6 +1831,10 @@ impl crate::Queue for Queue {
             unsafe { self.raw.ExecuteCommandLists(&temp_lists) }
         }
 
+        for (fence, value) in self.pending_signals.lock().drain(..) {
+            unsafe { self.raw.Signal(&fence, value) }.into_device_result("Signal fence")?;
+        }
+
         unsafe { self.raw.Signal(&signal_fence.raw, signal_value) }
             .into_device_result("Signal fence")?;
 
@@ -1475,6 +1850,7 @@ impl crate::Queue for Queue {
         &self,
         surface: &Surface,
         _texture: Texture,
+        damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
         let mut swapchain = surface.swap_chain.write();
         let sc = swapchain.as_mut().unwrap();
@@ -1488,10 +1864,33 @@ impl crate::Queue for Queue {
             m => unreachable!("Cannot make surface with present mode {m:?}"),
         };
 
-        profiling::scope!("IDXGISwapchain3::Present");
-        unsafe { sc.raw.Present(interval, flags) }
-            .ok()
-            .into_device_result("Present")?;
+        if damage.is_empty() {
+            profiling::scope!("IDXGISwapchain3::Present");
+            unsafe { sc.raw.Present(interval, flags) }
+                .ok()
+                .into_device_result("Present")?;
+        } else {
+            let mut dirty_rects = damage
+                .iter()
+                .map(|rect| Foundation::RECT {
+                    left: rect.x as i32,
+                    top: rect.y as i32,
+                    right: (rect.x + rect.width) as i32,
+                    bottom: (rect.y + rect.height) as i32,
+                })
+                .collect::<Vec<_>>();
+            let parameters = Dxgi::DXGI_PRESENT_PARAMETERS {
+                DirtyRectsCount: dirty_rects.len() as u32,
+                pDirtyRects: dirty_rects.as_mut_ptr(),
+                pScrollRect: core::ptr::null_mut(),
+                pScrollOffset: core::ptr::null_mut(),
+            };
+
+            profiling::scope!("IDXGISwapchain3::Present1");
+            unsafe { sc.raw.Present1(interval, flags, &parameters) }
+                .ok()
+                .into_device_result("Present1")?;
+        }
 
         Ok(())
     }
@@ -1500,6 +1899,125 @@ impl crate::Queue for Queue {
         let frequency = unsafe { self.raw.GetTimestampFrequency() }.expect("GetTimestampFrequency");
         (1_000_000_000.0 / frequency as f64) as f32
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let (mut gpu_timestamp, mut cpu_ticks) = (0, 0);
+        unsafe {
+            self.raw
+                .GetClockCalibration(&mut gpu_timestamp, &mut cpu_ticks)
+        }
+        .ok()?;
+        // The CPU timestamp is a `QueryPerformanceCounter` value.
+        let mut cpu_frequency = 0;
+        unsafe { Performance::QueryPerformanceFrequency(&mut cpu_frequency) }.ok()?;
+        Some(wgt::TimestampCalibration {
+            gpu_timestamp,
+            cpu_timestamp: (cpu_ticks as u128 * 1_000_000_000 / cpu_frequency as u128) as u64,
+            timestamp_period: unsafe { crate::Queue::get_timestamp_period(self) },
+        })
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[crate::SparseBufferBinding<Buffer, MemoryHeap>],
+        texture_bindings: &[crate::SparseTextureBinding<Texture, MemoryHeap>],
+    ) -> Result<(), crate::DeviceError> {
+        const TILE_SIZE: u64 = Direct3D12::D3D12_TILED_RESOURCE_TILE_SIZE_IN_BYTES as u64;
+
+        // Tile mappings are updated in queue order, after previous submissions.
+        let update_tile_mappings =
+            |resource: &Direct3D12::ID3D12Resource,
+             coordinate: Direct3D12::D3D12_TILED_RESOURCE_COORDINATE,
+             region_size: Direct3D12::D3D12_TILE_REGION_SIZE,
+             memory: Option<(&MemoryHeap, wgt::BufferAddress)>| {
+                let (heap, range_flags, heap_offset) = match memory {
+                    Some((heap, offset)) => (
+                        Some(&heap.raw),
+                        Direct3D12::D3D12_TILE_RANGE_FLAG_NONE,
+                        (offset / TILE_SIZE) as u32,
+                    ),
+                    None => (None, Direct3D12::D3D12_TILE_RANGE_FLAG_NULL, 0),
+                };
+                profiling::scope!("ID3D12CommandQueue::UpdateTileMappings");
+                unsafe {
+                    self.raw.UpdateTileMappings(
+                        resource,
+                        1,
+                        Some(&coordinate),
+                        Some(&region_size),
+                        heap,
+                        1,
+                        Some(&range_flags),
+                        Some(&heap_offset),
+                        Some(&region_size.NumTiles),
+                        Direct3D12::D3D12_TILE_MAPPING_FLAG_NONE,
+                    )
+                };
+            };
+
+        for binding in buffer_bindings {
+            let coordinate = Direct3D12::D3D12_TILED_RESOURCE_COORDINATE {
+                X: (binding.range.start / TILE_SIZE) as u32,
+                Y: 0,
+                Z: 0,
+                Subresource: 0,
+            };
+            let region_size = Direct3D12::D3D12_TILE_REGION_SIZE {
+                NumTiles: ((binding.range.end - binding.range.start) / TILE_SIZE) as u32,
+                UseBox: false.into(),
+                ..Default::default()
+            };
+            update_tile_mappings(
+                &binding.buffer.resource,
+                coordinate,
+                region_size,
+                binding.memory,
+            );
+        }
+
+        for binding in texture_bindings {
+            let texture = binding.texture;
+            let tile_size = texture
+                .format
+                .sparse_tile_size()
+                .expect("sparse textures have a tile size");
+            let width = binding.size.width.div_ceil(tile_size.width);
+            let height = binding.size.height.div_ceil(tile_size.height);
+            let coordinate = Direct3D12::D3D12_TILED_RESOURCE_COORDINATE {
+                X: binding.origin.x / tile_size.width,
+                Y: binding.origin.y / tile_size.height,
+                Z: 0,
+                Subresource: binding.mip_level + binding.array_layer * texture.mip_level_count,
+            };
+            let region_size = Direct3D12::D3D12_TILE_REGION_SIZE {
+                NumTiles: width * height,
+                UseBox: true.into(),
+                Width: width,
+                Height: height as u16,
+                Depth: 1,
+            };
+            update_tile_mappings(&texture.resource, coordinate, region_size, binding.memory);
+        }
+
+        Ok(())
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        unsafe { self.raw.Wait(&fence.raw, value) }.into_device_result("Wait fence")
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        self.pending_signals.lock().push((fence.raw.clone(), value));
+        Ok(())
+    }
 }
 #[derive(Debug)]
 pub struct DxilPassthroughShader {
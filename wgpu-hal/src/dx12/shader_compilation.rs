@@ -109,6 +109,7 @@ impl CompilerContainer {
         raw_ep: &str,
         stage_bit: wgt::ShaderStages,
         full_stage: &str,
+        optimization: wgt::ShaderOptimization,
     ) -> Result<super::CompiledShader, crate::PipelineError> {
         match self {
             CompilerContainer::Fxc(CompilerFxc { fxc }) => compile_fxc(
@@ -118,6 +119,7 @@ impl CompilerContainer {
                 raw_ep,
                 stage_bit,
                 full_stage,
+                optimization,
                 fxc,
             ),
             CompilerContainer::DynamicDxc(CompilerDynamicDxc { compiler, .. })
@@ -128,6 +130,7 @@ impl CompilerContainer {
                 raw_ep,
                 stage_bit,
                 full_stage,
+                optimization,
                 compiler,
             ),
         }
@@ -223,17 +226,27 @@ fn compile_fxc(
     raw_ep: &str,
     stage_bit: wgt::ShaderStages,
     full_stage: &str,
+    optimization: wgt::ShaderOptimization,
     fxc: &FxcLib,
 ) -> Result<super::CompiledShader, crate::PipelineError> {
     profiling::scope!("compile_fxc");
     let mut compile_flags = Fxc::D3DCOMPILE_ENABLE_STRICTNESS;
-    if device
+    let debug = device
         .shared
         .private_caps
         .instance_flags
-        .contains(wgt::InstanceFlags::DEBUG)
-    {
-        compile_flags |= Fxc::D3DCOMPILE_DEBUG | Fxc::D3DCOMPILE_SKIP_OPTIMIZATION;
+        .contains(wgt::InstanceFlags::DEBUG);
+    if debug {
+        compile_flags |= Fxc::D3DCOMPILE_DEBUG;
+    }
+    match optimization {
+        wgt::ShaderOptimization::Default if debug => {
+            compile_flags |= Fxc::D3DCOMPILE_SKIP_OPTIMIZATION;
+        }
+        wgt::ShaderOptimization::Default => {}
+        wgt::ShaderOptimization::Performance => {
+            compile_flags |= Fxc::D3DCOMPILE_OPTIMIZATION_LEVEL3;
+        }
     }
 
     let mut shader_data = None;
@@ -366,12 +379,16 @@ fn compile_dxc(
     raw_ep: &str,
     stage_bit: wgt::ShaderStages,
     full_stage: &str,
+    optimization: wgt::ShaderOptimization,
     compiler: &Dxc::IDxcCompiler3,
 ) -> Result<crate::dx12::CompiledShader, crate::PipelineError> {
     profiling::scope!("compile_dxc");
 
     let source_name = source_name.and_then(|cstr| cstr.to_str().ok());
 
+    // Libraries, which ray tracing stages are compiled to, have no single entry point.
+    let is_library = full_stage.starts_with("lib_");
+
     let source_name = source_name.map(OPCWSTR::new);
     let raw_ep = OPCWSTR::new(raw_ep);
     let full_stage = OPCWSTR::new(full_stage);
@@ -382,9 +399,10 @@ fn compile_dxc(
         compile_args.push(source_name.ptr())
     }
 
+    if !is_library {
+        compile_args.extend([windows::core::w!("-E"), raw_ep.ptr()]);
+    }
     compile_args.extend([
-        windows::core::w!("-E"),
-        raw_ep.ptr(),
         windows::core::w!("-T"),
         full_stage.ptr(),
         windows::core::w!("-HV"),
@@ -393,17 +411,28 @@ fn compile_dxc(
         Dxc::DXC_ARG_ENABLE_STRICTNESS,
     ]);
 
-    if device
+    let debug = device
         .shared
         .private_caps
         .instance_flags
-        .contains(wgt::InstanceFlags::DEBUG)
-    {
+        .contains(wgt::InstanceFlags::DEBUG);
+    if debug {
         compile_args.push(Dxc::DXC_ARG_DEBUG);
-        compile_args.push(Dxc::DXC_ARG_SKIP_OPTIMIZATIONS);
+    }
+    match optimization {
+        wgt::ShaderOptimization::Default if debug => {
+            compile_args.push(Dxc::DXC_ARG_SKIP_OPTIMIZATIONS);
+        }
+        wgt::ShaderOptimization::Default => {}
+        wgt::ShaderOptimization::Performance => {
+            compile_args.push(Dxc::DXC_ARG_OPTIMIZATION_LEVEL3);
+        }
     }
 
-    if device.features.contains(wgt::Features::SHADER_F16) {
+    if device
+        .features
+        .intersects(wgt::Features::SHADER_F16 | wgt::Features::SHADER_I16)
+    {
         compile_args.push(windows::core::w!("-enable-16bit-types"));
     }
 
@@ -0,0 +1,249 @@
+//! Importing resources shared through NT handles.
+//!
+//! Other D3D12 devices, D3D11 devices and Media Foundation can share textures and
+//! buffers with us by handing out an NT handle created with `CreateSharedHandle`.
+//!
+//! Access to an imported resource can be synchronized in one of two ways:
+//!
+//! - With a shared `ID3D12Fence`, imported with [`crate::Device::import_fence`] and
+//!   waited on or signaled with [`crate::Queue::wait_for_fence`] and
+//!   [`crate::Queue::signal_fence`].
+//! - With the resource's keyed mutex, if it was created with one, through a
+//!   [`KeyedMutex`]. D3D12 can't use keyed mutexes itself, so this goes through a
+//!   D3D11On12 device running on our queue.
+
+use windows::{
+    Win32::{
+        Foundation,
+        Graphics::{Direct3D11, Direct3D11on12, Direct3D12, Dxgi},
+    },
+    core::Interface as _,
+};
+
+use super::suballocation;
+use crate::auxil::dxgi::result::HResult as _;
+
+/// Synchronizes access to an imported texture through its `IDXGIKeyedMutex`.
+///
+/// Created by [`super::Device::keyed_mutex_for_texture`].
+#[derive(Debug)]
+pub struct KeyedMutex {
+    device11on12: Direct3D11on12::ID3D11On12Device,
+    context: Direct3D11::ID3D11DeviceContext,
+    resource: Direct3D11::ID3D11Resource,
+    mutex: Dxgi::IDXGIKeyedMutex,
+}
+
+unsafe impl Send for KeyedMutex {}
+unsafe impl Sync for KeyedMutex {}
+
+impl KeyedMutex {
+    /// Waits until the mutex is released with `key`, and takes ownership of it.
+    ///
+    /// Work submitted to the device's queue after this returns `Ok(true)` runs after
+    /// the previous owner's work. Returns `Ok(false)` if `timeout_ms` elapsed first.
+    ///
+    /// # Safety
+    ///
+    /// - The mutex must not already be owned by this device.
+    pub unsafe fn acquire(&self, key: u64, timeout_ms: u32) -> Result<bool, crate::DeviceError> {
+        let resources = [Some(self.resource.clone())];
+        unsafe { self.device11on12.AcquireWrappedResources(&resources) };
+
+        // `AcquireSync` reports a timeout with a success code, which the wrapper
+        // would hide from us.
+        let hr = unsafe { (self.mutex.vtable().AcquireSync)(self.mutex.as_raw(), key, timeout_ms) };
+        if hr != windows::core::HRESULT(0) {
+            unsafe { self.device11on12.ReleaseWrappedResources(&resources) };
+            if hr == windows::core::HRESULT(Foundation::WAIT_TIMEOUT.0 as i32) {
+                return Ok(false);
+            }
+            if hr == windows::core::HRESULT(Foundation::WAIT_ABANDONED.0 as i32) {
+                log::error!("Keyed mutex was abandoned by its previous owner");
+                return Err(crate::DeviceError::Lost);
+            }
+            hr.ok().into_device_result("Keyed mutex acquisition")?;
+        }
+
+        // Submit the wait to our queue.
+        unsafe { self.context.Flush() };
+        Ok(true)
+    }
+
+    /// Gives up ownership of the mutex, letting the next owner acquire it with `key`.
+    ///
+    /// The next owner's work runs after work submitted to the device's queue before
+    /// this call.
+    ///
+    /// # Safety
+    ///
+    /// - The mutex must be owned by this device.
+    pub unsafe fn release(&self, key: u64) -> Result<(), crate::DeviceError> {
+        unsafe { self.mutex.ReleaseSync(key) }.into_device_result("Keyed mutex release")?;
+
+        let resources = [Some(self.resource.clone())];
+        unsafe { self.device11on12.ReleaseWrappedResources(&resources) };
+
+        // Submit the signal to our queue.
+        unsafe { self.context.Flush() };
+        Ok(())
+    }
+}
+
+impl super::Device {
+    /// Imports a texture from a shared NT handle.
+    ///
+    /// The handle is not consumed; the caller remains responsible for closing it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid NT handle to an `ID3D12Resource`, or to a D3D11
+    ///   texture created with `D3D11_RESOURCE_MISC_SHARED_NTHANDLE`.
+    /// - The resource must have been created on the same adapter as this device.
+    /// - `desc` must match the resource's format, and its usages must be supported
+    ///   by the resource's flags.
+    pub unsafe fn texture_from_shared_handle(
+        &self,
+        handle: Foundation::HANDLE,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let resource = unsafe { self.open_shared_resource(handle) }?;
+
+        let raw_desc = unsafe { resource.GetDesc() };
+        if raw_desc.Dimension != super::conv::map_texture_dimension(desc.dimension)
+            || raw_desc.Width != u64::from(desc.size.width)
+            || raw_desc.Height != desc.size.height
+            || u32::from(raw_desc.DepthOrArraySize) != desc.size.depth_or_array_layers
+            || u32::from(raw_desc.MipLevels) != desc.mip_level_count
+            || raw_desc.SampleDesc.Count != desc.sample_count
+        {
+            crate::hal_usage_error(format_args!(
+                "shared texture {raw_desc:?} doesn't match the texture descriptor {desc:?}"
+            ))
+        }
+
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            allocation: suballocation::Allocation::none(
+                suballocation::AllocationType::Texture,
+                desc.format.theoretical_memory_footprint(desc.size),
+            ),
+        })
+    }
+
+    /// Imports a buffer from a shared NT handle.
+    ///
+    /// The handle is not consumed; the caller remains responsible for closing it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid NT handle to an `ID3D12Resource` buffer.
+    /// - The resource must have been created on the same adapter as this device.
+    /// - `desc.usage` must be supported by the resource's flags and heap.
+    pub unsafe fn buffer_from_shared_handle(
+        &self,
+        handle: Foundation::HANDLE,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let resource = unsafe { self.open_shared_resource(handle) }?;
+
+        let raw_desc = unsafe { resource.GetDesc() };
+        if raw_desc.Dimension != Direct3D12::D3D12_RESOURCE_DIMENSION_BUFFER
+            || raw_desc.Width < desc.size
+        {
+            crate::hal_usage_error(format_args!(
+                "shared buffer {raw_desc:?} doesn't match the buffer descriptor {desc:?}"
+            ))
+        }
+
+        Ok(super::Buffer {
+            resource,
+            size: desc.size,
+            allocation: suballocation::Allocation::none(
+                suballocation::AllocationType::Buffer,
+                desc.size,
+            ),
+        })
+    }
+
+    /// Creates a [`KeyedMutex`] for synchronizing access to `texture` with the
+    /// other users of its keyed mutex.
+    ///
+    /// # Safety
+    ///
+    /// - `texture` must have been imported with
+    ///   [`Self::texture_from_shared_handle`] from a resource with a keyed mutex.
+    /// - `texture` must be idle, and in the `COMMON` state, whenever the mutex
+    ///   isn't owned by this device.
+    pub unsafe fn keyed_mutex_for_texture(
+        &self,
+        texture: &super::Texture,
+    ) -> Result<KeyedMutex, crate::DeviceError> {
+        let queues = [Some(windows::core::IUnknown::from(
+            self.present_queue.clone(),
+        ))];
+        let mut device = None::<Direct3D11::ID3D11Device>;
+        let mut context = None::<Direct3D11::ID3D11DeviceContext>;
+        unsafe {
+            Direct3D11on12::D3D11On12CreateDevice(
+                &self.raw,
+                Direct3D11::D3D11_CREATE_DEVICE_BGRA_SUPPORT.0,
+                None,
+                Some(&queues),
+                0,
+                Some(&mut device),
+                Some(&mut context),
+                None,
+            )
+        }
+        .into_device_result("D3D11On12 device creation")?;
+        let device11on12 = device
+            .ok_or(crate::DeviceError::Unexpected)?
+            .cast::<Direct3D11on12::ID3D11On12Device>()
+            .into_device_result("D3D11On12 device query")?;
+        let context = context.ok_or(crate::DeviceError::Unexpected)?;
+
+        let flags = Direct3D11on12::D3D11_RESOURCE_FLAGS::default();
+        let mut resource = None::<Direct3D11::ID3D11Resource>;
+        unsafe {
+            device11on12.CreateWrappedResource(
+                &texture.resource,
+                &flags,
+                Direct3D12::D3D12_RESOURCE_STATE_COMMON,
+                Direct3D12::D3D12_RESOURCE_STATE_COMMON,
+                &mut resource,
+            )
+        }
+        .into_device_result("Wrapped resource creation")?;
+        let resource = resource.ok_or(crate::DeviceError::Unexpected)?;
+
+        let mutex = resource
+            .cast::<Dxgi::IDXGIKeyedMutex>()
+            .into_device_result("Keyed mutex query")?;
+
+        // Wrapped resources start out acquired.
+        unsafe { device11on12.ReleaseWrappedResources(&[Some(resource.clone())]) };
+
+        Ok(KeyedMutex {
+            device11on12,
+            context,
+            resource,
+            mutex,
+        })
+    }
+
+    unsafe fn open_shared_resource(
+        &self,
+        handle: Foundation::HANDLE,
+    ) -> Result<Direct3D12::ID3D12Resource, crate::DeviceError> {
+        let mut raw = None::<Direct3D12::ID3D12Resource>;
+        unsafe { self.raw.OpenSharedHandle(handle, &mut raw) }
+            .into_device_result("Shared resource import")?;
+        raw.ok_or(crate::DeviceError::Unexpected)
+    }
+}
@@ -28,6 +28,12 @@ enum AllocationInner {
     /// This is only used for Intel Xe drivers, which have a bug that
     /// prevents suballocation from working correctly.
     Committed { size: u64 },
+    /// This resource was placed in a [`MemoryHeap`](super::MemoryHeap), which
+    /// owns the memory and accounts for it in the counters.
+    Heap,
+    /// This resource is a reserved resource, whose tiles are mapped to
+    /// [`MemoryHeap`](super::MemoryHeap)s by `UpdateTileMappings`.
+    Reserved,
 }
 
 #[derive(Debug)]
@@ -51,12 +57,33 @@ impl Allocation {
         }
     }
 
+    pub fn heap(ty: AllocationType) -> Self {
+        Self {
+            inner: AllocationInner::Heap,
+            ty,
+        }
+    }
+
+    pub fn reserved(ty: AllocationType) -> Self {
+        Self {
+            inner: AllocationInner::Reserved,
+            ty,
+        }
+    }
+
     pub fn size(&self) -> u64 {
         match self.inner {
             AllocationInner::Placed { ref inner } => inner.size(),
             AllocationInner::Committed { size } => size,
+            AllocationInner::Heap | AllocationInner::Reserved => 0,
         }
     }
+
+    /// Whether the resource lives in a [`MemoryHeap`](super::MemoryHeap) and
+    /// may alias other resources.
+    pub fn is_in_heap(&self) -> bool {
+        matches!(self.inner, AllocationInner::Heap)
+    }
 }
 
 #[derive(Clone)]
@@ -268,6 +295,98 @@ impl<'a> DeviceAllocationContext<'a> {
         Ok((resource, allocation))
     }
 
+    pub(crate) fn create_heap(
+        &self,
+        desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<Direct3D12::ID3D12Heap, crate::DeviceError> {
+        let heap_desc = Direct3D12::D3D12_HEAP_DESC {
+            SizeInBytes: desc.size,
+            Properties: Direct3D12::D3D12_HEAP_PROPERTIES {
+                Type: Direct3D12::D3D12_HEAP_TYPE_DEFAULT,
+                CPUPageProperty: Direct3D12::D3D12_CPU_PAGE_PROPERTY_UNKNOWN,
+                MemoryPoolPreference: Direct3D12::D3D12_MEMORY_POOL_UNKNOWN,
+                CreationNodeMask: 0,
+                VisibleNodeMask: 0,
+            },
+            // Large enough for multisampled textures.
+            Alignment: Direct3D12::D3D12_DEFAULT_MSAA_RESOURCE_PLACEMENT_ALIGNMENT as u64,
+            Flags: if self.shared.private_caps.heap_create_not_zeroed {
+                Direct3D12::D3D12_HEAP_FLAG_CREATE_NOT_ZEROED
+            } else {
+                Direct3D12::D3D12_HEAP_FLAG_NONE
+            },
+        };
+
+        let mut heap = None;
+        unsafe { self.raw.CreateHeap(&heap_desc, &mut heap) }
+            .into_device_result("Heap creation")?;
+        let heap: Direct3D12::ID3D12Heap = heap.ok_or(crate::DeviceError::Unexpected)?;
+
+        if let Some(label) = desc.label {
+            heap.set_name(label)?;
+        }
+
+        self.counters.texture_memory.add(desc.size as isize);
+
+        Ok(heap)
+    }
+
+    pub(crate) fn create_texture_in_heap(
+        &self,
+        heap: &Direct3D12::ID3D12Heap,
+        offset: u64,
+        desc: &crate::TextureDescriptor,
+        raw_desc: Direct3D12::D3D12_RESOURCE_DESC,
+    ) -> Result<Direct3D12::ID3D12Resource, crate::DeviceError> {
+        let mut resource = None;
+        unsafe {
+            self.raw.CreatePlacedResource(
+                heap,
+                offset,
+                &raw_desc,
+                Direct3D12::D3D12_RESOURCE_STATE_COMMON,
+                None, // clear value
+                &mut resource,
+            )
+        }
+        .into_device_result("Placed texture creation")?;
+
+        let resource: Direct3D12::ID3D12Resource =
+            resource.ok_or(crate::DeviceError::Unexpected)?;
+
+        if let Some(label) = desc.label {
+            resource.set_name(label)?;
+        }
+
+        Ok(resource)
+    }
+
+    pub(crate) fn create_reserved_resource(
+        &self,
+        label: crate::Label,
+        raw_desc: Direct3D12::D3D12_RESOURCE_DESC,
+    ) -> Result<Direct3D12::ID3D12Resource, crate::DeviceError> {
+        let mut resource = None;
+        unsafe {
+            self.raw.CreateReservedResource(
+                &raw_desc,
+                Direct3D12::D3D12_RESOURCE_STATE_COMMON,
+                None, // clear value
+                &mut resource,
+            )
+        }
+        .into_device_result("Reserved resource creation")?;
+
+        let resource: Direct3D12::ID3D12Resource =
+            resource.ok_or(crate::DeviceError::Unexpected)?;
+
+        if let Some(label) = label {
+            resource.set_name(label)?;
+        }
+
+        Ok(resource)
+    }
+
     //////////////////////////
     // Resource Destruction //
     //////////////////////////
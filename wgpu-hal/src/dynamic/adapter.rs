@@ -1,4 +1,4 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 
 use crate::{
     Adapter, Api, DeviceError, OpenDevice, SurfaceCapabilities, TextureFormatCapabilities,
@@ -9,6 +9,7 @@ use super::{DynDevice, DynQueue, DynResource, DynResourceExt, DynSurface};
 pub struct DynOpenDevice {
     pub device: Box<dyn DynDevice>,
     pub queue: Box<dyn DynQueue>,
+    pub additional_queues: Vec<Box<dyn DynQueue>>,
 }
 
 impl<A: Api> From<OpenDevice<A>> for DynOpenDevice {
@@ -16,6 +17,11 @@ impl<A: Api> From<OpenDevice<A>> for DynOpenDevice {
         Self {
             device: Box::new(open_device.device),
             queue: Box::new(open_device.queue),
+            additional_queues: open_device
+                .additional_queues
+                .into_iter()
+                .map(|queue| -> Box<dyn DynQueue> { Box::new(queue) })
+                .collect(),
         }
     }
 }
@@ -26,6 +32,7 @@ pub trait DynAdapter: DynResource {
         features: wgt::Features,
         limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
     ) -> Result<DynOpenDevice, DeviceError>;
 
     unsafe fn texture_format_capabilities(
@@ -36,6 +43,8 @@ pub trait DynAdapter: DynResource {
     unsafe fn surface_capabilities(&self, surface: &dyn DynSurface) -> Option<SurfaceCapabilities>;
 
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp;
+
+    fn memory_info(&self) -> Option<wgt::MemoryInfo>;
 }
 
 impl<A: Adapter + DynResource> DynAdapter for A {
@@ -44,11 +53,10 @@ impl<A: Adapter + DynResource> DynAdapter for A {
         features: wgt::Features,
         limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
     ) -> Result<DynOpenDevice, DeviceError> {
-        unsafe { A::open(self, features, limits, memory_hints) }.map(|open_device| DynOpenDevice {
-            device: Box::new(open_device.device),
-            queue: Box::new(open_device.queue),
-        })
+        unsafe { A::open(self, features, limits, memory_hints, additional_queues) }
+            .map(DynOpenDevice::from)
     }
 
     unsafe fn texture_format_capabilities(
@@ -66,4 +74,8 @@ impl<A: Adapter + DynResource> DynAdapter for A {
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         unsafe { A::get_presentation_timestamp(self) }
     }
+
+    fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        A::memory_info(self)
+    }
 }
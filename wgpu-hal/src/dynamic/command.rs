@@ -4,14 +4,15 @@ use core::ops::Range;
 use crate::{
     AccelerationStructureBarrier, Api, Attachment, BufferBarrier, BufferBinding, BufferCopy,
     BufferTextureCopy, BuildAccelerationStructureDescriptor, ColorAttachment, CommandEncoder,
-    ComputePassDescriptor, DepthStencilAttachment, DeviceError, Label, MemoryRange,
-    PassTimestampWrites, Rect, RenderPassDescriptor, TextureBarrier, TextureCopy,
+    ComputePassDescriptor, DepthStencilAttachment, DepthStencilResolve, DeviceError, Label,
+    MemoryRange, PassPipelineStatisticsQuery, PassTimestampWrites, Rect, RenderPassDescriptor,
+    ShaderBindingTables, TextureBarrier, TextureCopy,
 };
 
 use super::{
     DynAccelerationStructure, DynBindGroup, DynBuffer, DynCommandBuffer, DynComputePipeline,
-    DynPipelineLayout, DynQuerySet, DynRenderPipeline, DynResource, DynResourceExt as _,
-    DynTexture, DynTextureView,
+    DynPipelineLayout, DynQuerySet, DynRayTracingPipeline, DynRenderPipeline, DynResource,
+    DynResourceExt as _, DynTexture, DynTextureView,
 };
 
 pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
@@ -43,6 +44,13 @@ pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
         regions: &[TextureCopy],
     );
 
+    unsafe fn resolve_texture(
+        &mut self,
+        src: &dyn DynTexture,
+        dst: &dyn DynTexture,
+        regions: &[TextureCopy],
+    );
+
     unsafe fn copy_buffer_to_texture(
         &mut self,
         src: &dyn DynBuffer,
@@ -97,6 +105,14 @@ pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
     ) -> Result<(), DeviceError>;
     unsafe fn end_render_pass(&mut self);
 
+    unsafe fn create_parallel_render_encoder(
+        &mut self,
+    ) -> Result<Box<dyn DynCommandEncoder>, DeviceError>;
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        encoders: Vec<Box<dyn DynCommandEncoder>>,
+    ) -> Result<(), DeviceError>;
+
     unsafe fn set_render_pipeline(&mut self, pipeline: &dyn DynRenderPipeline);
 
     unsafe fn set_index_buffer<'a>(
@@ -111,9 +127,22 @@ pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
         binding: BufferBinding<'a, dyn DynBuffer>,
     );
     unsafe fn set_viewport(&mut self, rect: &Rect<f32>, depth_range: Range<f32>);
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &Rect<f32>,
+        depth_range: Range<f32>,
+    );
     unsafe fn set_scissor_rect(&mut self, rect: &Rect<u32>);
     unsafe fn set_stencil_reference(&mut self, value: u32);
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]);
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate);
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &dyn DynBuffer,
+        offset: wgt::BufferAddress,
+    );
+    unsafe fn end_conditional_rendering(&mut self);
 
     unsafe fn draw(
         &mut self,
@@ -187,6 +216,9 @@ pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
     unsafe fn dispatch(&mut self, count: [u32; 3]);
     unsafe fn dispatch_indirect(&mut self, buffer: &dyn DynBuffer, offset: wgt::BufferAddress);
 
+    unsafe fn set_ray_tracing_pipeline(&mut self, pipeline: &dyn DynRayTracingPipeline);
+    unsafe fn trace_rays(&mut self, tables: &ShaderBindingTables<dyn DynBuffer>, count: [u32; 3]);
+
     unsafe fn build_acceleration_structures<'a>(
         &mut self,
         descriptors: &'a [BuildAccelerationStructureDescriptor<
@@ -207,11 +239,28 @@ pub trait DynCommandEncoder: DynResource + core::fmt::Debug {
         dst: &dyn DynAccelerationStructure,
         copy: wgt::AccelerationStructureCopy,
     );
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        src: &dyn DynAccelerationStructure,
+        dst: &dyn DynBuffer,
+        dst_offset: wgt::BufferAddress,
+    );
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        src: &dyn DynBuffer,
+        src_offset: wgt::BufferAddress,
+        dst: &dyn DynAccelerationStructure,
+    );
     unsafe fn read_acceleration_structure_compact_size(
         &mut self,
         acceleration_structure: &dyn DynAccelerationStructure,
         buf: &dyn DynBuffer,
     );
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        acceleration_structure: &dyn DynAccelerationStructure,
+        buf: &dyn DynBuffer,
+    );
 }
 
 impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
@@ -284,6 +333,19 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         }
     }
 
+    unsafe fn resolve_texture(
+        &mut self,
+        src: &dyn DynTexture,
+        dst: &dyn DynTexture,
+        regions: &[TextureCopy],
+    ) {
+        let src = src.expect_downcast_ref();
+        let dst = dst.expect_downcast_ref();
+        unsafe {
+            C::resolve_texture(self, src, dst, regions.iter().cloned());
+        }
+    }
+
     unsafe fn copy_buffer_to_texture(
         &mut self,
         src: &dyn DynBuffer,
@@ -423,6 +485,12 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
                 occlusion_query_set: desc
                     .occlusion_query_set
                     .map(|set| set.expect_downcast_ref()),
+                pipeline_statistics_query: desc
+                    .pipeline_statistics_query
+                    .as_ref()
+                    .map(|query| query.expect_downcast()),
+                flags: desc.flags,
+                parallel: desc.parallel,
             };
         unsafe { C::begin_render_pass(self, &desc) }
     }
@@ -433,12 +501,41 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         }
     }
 
+    unsafe fn create_parallel_render_encoder(
+        &mut self,
+    ) -> Result<Box<dyn DynCommandEncoder>, DeviceError> {
+        unsafe { C::create_parallel_render_encoder(self) }
+            .map(|encoder| -> Box<dyn DynCommandEncoder> { Box::new(encoder) })
+    }
+
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        encoders: Vec<Box<dyn DynCommandEncoder>>,
+    ) -> Result<(), DeviceError> {
+        let encoders = encoders
+            .into_iter()
+            .map(|encoder| unsafe { encoder.unbox() })
+            .collect();
+        unsafe { C::execute_parallel_render_encoders(self, encoders) }
+    }
+
     unsafe fn set_viewport(&mut self, rect: &Rect<f32>, depth_range: Range<f32>) {
         unsafe {
             C::set_viewport(self, rect, depth_range);
         }
     }
 
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+        unsafe {
+            C::set_viewport_indexed(self, index, rect, depth_range);
+        }
+    }
+
     unsafe fn set_scissor_rect(&mut self, rect: &Rect<u32>) {
         unsafe {
             C::set_scissor_rect(self, rect);
@@ -455,6 +552,23 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         unsafe { C::set_blend_constants(self, color) };
     }
 
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate) {
+        unsafe { C::set_shading_rate(self, rate) };
+    }
+
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &dyn DynBuffer,
+        offset: wgt::BufferAddress,
+    ) {
+        let buffer = buffer.expect_downcast_ref();
+        unsafe { C::begin_conditional_rendering(self, buffer, offset) };
+    }
+
+    unsafe fn end_conditional_rendering(&mut self) {
+        unsafe { C::end_conditional_rendering(self) };
+    }
+
     unsafe fn draw(
         &mut self,
         first_vertex: u32,
@@ -598,6 +712,10 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
                 .timestamp_writes
                 .as_ref()
                 .map(|writes| writes.expect_downcast()),
+            pipeline_statistics_query: desc
+                .pipeline_statistics_query
+                .as_ref()
+                .map(|query| query.expect_downcast()),
         };
         unsafe { C::begin_compute_pass(self, &desc) };
     }
@@ -620,6 +738,16 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         unsafe { C::dispatch_indirect(self, buffer, offset) };
     }
 
+    unsafe fn set_ray_tracing_pipeline(&mut self, pipeline: &dyn DynRayTracingPipeline) {
+        let pipeline = pipeline.expect_downcast_ref();
+        unsafe { C::set_ray_tracing_pipeline(self, pipeline) };
+    }
+
+    unsafe fn trace_rays(&mut self, tables: &ShaderBindingTables<dyn DynBuffer>, count: [u32; 3]) {
+        let tables = tables.expect_downcast();
+        unsafe { C::trace_rays(self, &tables, count) };
+    }
+
     unsafe fn set_render_pipeline(&mut self, pipeline: &dyn DynRenderPipeline) {
         let pipeline = pipeline.expect_downcast_ref();
         unsafe { C::set_render_pipeline(self, pipeline) };
@@ -696,6 +824,26 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         let dst = dst.expect_downcast_ref();
         unsafe { C::copy_acceleration_structure_to_acceleration_structure(self, src, dst, copy) };
     }
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        src: &dyn DynAccelerationStructure,
+        dst: &dyn DynBuffer,
+        dst_offset: wgt::BufferAddress,
+    ) {
+        let src = src.expect_downcast_ref();
+        let dst = dst.expect_downcast_ref();
+        unsafe { C::copy_acceleration_structure_to_buffer(self, src, dst, dst_offset) };
+    }
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        src: &dyn DynBuffer,
+        src_offset: wgt::BufferAddress,
+        dst: &dyn DynAccelerationStructure,
+    ) {
+        let src = src.expect_downcast_ref();
+        let dst = dst.expect_downcast_ref();
+        unsafe { C::copy_buffer_to_acceleration_structure(self, src, src_offset, dst) };
+    }
     unsafe fn read_acceleration_structure_compact_size(
         &mut self,
         acceleration_structure: &dyn DynAccelerationStructure,
@@ -705,6 +853,15 @@ impl<C: CommandEncoder + DynResource> DynCommandEncoder for C {
         let buf = buf.expect_downcast_ref();
         unsafe { C::read_acceleration_structure_compact_size(self, acceleration_structure, buf) }
     }
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        acceleration_structure: &dyn DynAccelerationStructure,
+        buf: &dyn DynBuffer,
+    ) {
+        let acceleration_structure = acceleration_structure.expect_downcast_ref();
+        let buf = buf.expect_downcast_ref();
+        unsafe { C::read_acceleration_structure_serialized_size(self, acceleration_structure, buf) }
+    }
 }
 
 impl<'a> PassTimestampWrites<'a, dyn DynQuerySet> {
@@ -717,6 +874,15 @@ impl<'a> PassTimestampWrites<'a, dyn DynQuerySet> {
     }
 }
 
+impl<'a> PassPipelineStatisticsQuery<'a, dyn DynQuerySet> {
+    pub fn expect_downcast<B: DynQuerySet>(&self) -> PassPipelineStatisticsQuery<'a, B> {
+        PassPipelineStatisticsQuery {
+            query_set: self.query_set.expect_downcast_ref(),
+            query_index: self.query_index,
+        }
+    }
+}
+
 impl<'a> Attachment<'a, dyn DynTextureView> {
     pub fn expect_downcast<B: DynTextureView>(&self) -> Attachment<'a, B> {
         Attachment {
@@ -745,6 +911,20 @@ impl<'a> DepthStencilAttachment<'a, dyn DynTextureView> {
             depth_ops: self.depth_ops,
             stencil_ops: self.stencil_ops,
             clear_value: self.clear_value,
+            resolve: self
+                .resolve
+                .as_ref()
+                .map(|resolve| resolve.expect_downcast()),
+        }
+    }
+}
+
+impl<'a> DepthStencilResolve<'a, dyn DynTextureView> {
+    pub fn expect_downcast<B: DynTextureView>(&self) -> DepthStencilResolve<'a, B> {
+        DepthStencilResolve {
+            target: self.target.expect_downcast(),
+            depth_mode: self.depth_mode,
+            resolve_stencil: self.resolve_stencil,
         }
     }
 }
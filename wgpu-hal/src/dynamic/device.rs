@@ -1,20 +1,21 @@
-use alloc::{borrow::ToOwned as _, boxed::Box, vec::Vec};
+use alloc::{borrow::ToOwned as _, boxed::Box, string::String, vec::Vec};
 
 use crate::{
     AccelerationStructureBuildSizes, AccelerationStructureDescriptor, Api, BindGroupDescriptor,
     BindGroupLayoutDescriptor, BufferDescriptor, BufferMapping, CommandEncoderDescriptor,
     ComputePipelineDescriptor, Device, DeviceError, FenceValue,
-    GetAccelerationStructureBuildSizesDescriptor, Label, MemoryRange, MeshPipelineDescriptor,
-    PipelineCacheDescriptor, PipelineCacheError, PipelineError, PipelineLayoutDescriptor,
-    RenderPipelineDescriptor, SamplerDescriptor, ShaderError, ShaderInput, ShaderModuleDescriptor,
-    TextureDescriptor, TextureViewDescriptor, TlasInstance,
+    GetAccelerationStructureBuildSizesDescriptor, Label, MemoryHeapDescriptor, MemoryRange,
+    MeshPipelineDescriptor, PipelineCacheDescriptor, PipelineCacheError, PipelineError,
+    PipelineLayoutDescriptor, RayTracingPipelineDescriptor, RenderPipelineDescriptor,
+    SamplerDescriptor, ShaderError, ShaderInput, ShaderModuleDescriptor, TextureDescriptor,
+    TextureViewDescriptor, TlasInstance,
 };
 
 use super::{
     DynAccelerationStructure, DynBindGroup, DynBindGroupLayout, DynBuffer, DynCommandEncoder,
-    DynComputePipeline, DynFence, DynPipelineCache, DynPipelineLayout, DynQuerySet, DynQueue,
-    DynRenderPipeline, DynResource, DynResourceExt as _, DynSampler, DynShaderModule, DynTexture,
-    DynTextureView,
+    DynComputePipeline, DynFence, DynMemoryHeap, DynPipelineCache, DynPipelineLayout, DynQuerySet,
+    DynQueue, DynRayTracingPipeline, DynRenderPipeline, DynResource, DynResourceExt as _,
+    DynSampler, DynShaderModule, DynTexture, DynTextureView,
 };
 
 pub trait DynDevice: DynResource {
@@ -44,6 +45,31 @@ pub trait DynDevice: DynResource {
     unsafe fn destroy_texture(&self, texture: Box<dyn DynTexture>);
     unsafe fn add_raw_texture(&self, texture: &dyn DynTexture);
 
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &MemoryHeapDescriptor,
+    ) -> Result<Box<dyn DynMemoryHeap>, DeviceError>;
+    unsafe fn destroy_memory_heap(&self, heap: Box<dyn DynMemoryHeap>);
+    unsafe fn texture_memory_requirements(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, DeviceError>;
+    unsafe fn create_placed_texture(
+        &self,
+        heap: &dyn DynMemoryHeap,
+        offset: wgt::BufferAddress,
+        desc: &TextureDescriptor,
+    ) -> Result<Box<dyn DynTexture>, DeviceError>;
+
+    unsafe fn create_sparse_buffer(
+        &self,
+        desc: &BufferDescriptor,
+    ) -> Result<Box<dyn DynBuffer>, DeviceError>;
+    unsafe fn create_sparse_texture(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<Box<dyn DynTexture>, DeviceError>;
+
     unsafe fn create_texture_view(
         &self,
         texture: &dyn DynTexture,
@@ -120,6 +146,22 @@ pub trait DynDevice: DynResource {
     ) -> Result<Box<dyn DynComputePipeline>, PipelineError>;
     unsafe fn destroy_compute_pipeline(&self, pipeline: Box<dyn DynComputePipeline>);
 
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        desc: &RayTracingPipelineDescriptor<
+            dyn DynPipelineLayout,
+            dyn DynShaderModule,
+            dyn DynPipelineCache,
+        >,
+    ) -> Result<Box<dyn DynRayTracingPipeline>, PipelineError>;
+    unsafe fn destroy_ray_tracing_pipeline(&self, pipeline: Box<dyn DynRayTracingPipeline>);
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        pipeline: &dyn DynRayTracingPipeline,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<Vec<u8>, DeviceError>;
+
     unsafe fn create_pipeline_cache(
         &self,
         desc: &PipelineCacheDescriptor<'_>,
@@ -146,6 +188,16 @@ pub trait DynDevice: DynResource {
         timeout_ms: u32,
     ) -> Result<bool, DeviceError>;
 
+    unsafe fn create_shared_fence(&self) -> Result<Box<dyn DynFence>, DeviceError>;
+    unsafe fn export_fence(
+        &self,
+        fence: &dyn DynFence,
+    ) -> Result<wgt::SharedFenceHandle, DeviceError>;
+    unsafe fn import_fence(
+        &self,
+        handle: wgt::SharedFenceHandle,
+    ) -> Result<Box<dyn DynFence>, DeviceError>;
+
     unsafe fn start_graphics_debugger_capture(&self) -> bool;
     unsafe fn stop_graphics_debugger_capture(&self);
 
@@ -167,12 +219,20 @@ pub trait DynDevice: DynResource {
         &self,
         acceleration_structure: Box<dyn DynAccelerationStructure>,
     );
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        data: &[u8],
+    ) -> Option<wgt::BufferAddress>;
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8>;
 
     fn get_internal_counters(&self) -> wgt::HalCounters;
     fn generate_allocator_report(&self) -> Option<wgt::AllocatorReport>;
+    fn trim_memory(&self) -> u32;
 
     fn check_if_oom(&self) -> Result<(), DeviceError>;
+    fn lost_reason(&self) -> wgt::DeviceLostReason;
+    fn fault_info(&self) -> Option<String>;
+    fn take_validation_messages(&self) -> Vec<String>;
 }
 
 impl<D: Device + DynResource> DynDevice for D {
@@ -235,6 +295,52 @@ impl<D: Device + DynResource> DynDevice for D {
         unsafe { D::add_raw_texture(self, texture) };
     }
 
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &MemoryHeapDescriptor,
+    ) -> Result<Box<dyn DynMemoryHeap>, DeviceError> {
+        unsafe { D::create_memory_heap(self, desc) }
+            .map(|b| -> Box<dyn DynMemoryHeap> { Box::new(b) })
+    }
+
+    unsafe fn destroy_memory_heap(&self, heap: Box<dyn DynMemoryHeap>) {
+        unsafe { D::destroy_memory_heap(self, heap.unbox()) };
+    }
+
+    unsafe fn texture_memory_requirements(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, DeviceError> {
+        unsafe { D::texture_memory_requirements(self, desc) }
+    }
+
+    unsafe fn create_placed_texture(
+        &self,
+        heap: &dyn DynMemoryHeap,
+        offset: wgt::BufferAddress,
+        desc: &TextureDescriptor,
+    ) -> Result<Box<dyn DynTexture>, DeviceError> {
+        let heap = heap.expect_downcast_ref();
+        unsafe { D::create_placed_texture(self, heap, offset, desc) }
+            .map(|b| -> Box<dyn DynTexture> { Box::new(b) })
+    }
+
+    unsafe fn create_sparse_buffer(
+        &self,
+        desc: &BufferDescriptor,
+    ) -> Result<Box<dyn DynBuffer>, DeviceError> {
+        unsafe { D::create_sparse_buffer(self, desc) }
+            .map(|b| -> Box<dyn DynBuffer> { Box::new(b) })
+    }
+
+    unsafe fn create_sparse_texture(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<Box<dyn DynTexture>, DeviceError> {
+        unsafe { D::create_sparse_texture(self, desc) }
+            .map(|b| -> Box<dyn DynTexture> { Box::new(b) })
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &dyn DynTexture,
@@ -456,6 +562,46 @@ impl<D: Device + DynResource> DynDevice for D {
         unsafe { D::destroy_compute_pipeline(self, pipeline.unbox()) };
     }
 
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        desc: &RayTracingPipelineDescriptor<
+            dyn DynPipelineLayout,
+            dyn DynShaderModule,
+            dyn DynPipelineCache,
+        >,
+    ) -> Result<Box<dyn DynRayTracingPipeline>, PipelineError> {
+        let stages = desc
+            .stages
+            .iter()
+            .map(|stage| stage.expect_downcast())
+            .collect::<Vec<_>>();
+        let desc = RayTracingPipelineDescriptor {
+            label: desc.label,
+            layout: desc.layout.expect_downcast_ref(),
+            stages: &stages,
+            groups: desc.groups,
+            max_recursion_depth: desc.max_recursion_depth,
+            cache: desc.cache.as_ref().map(|c| c.expect_downcast_ref()),
+        };
+
+        unsafe { D::create_ray_tracing_pipeline(self, &desc) }
+            .map(|b| -> Box<dyn DynRayTracingPipeline> { Box::new(b) })
+    }
+
+    unsafe fn destroy_ray_tracing_pipeline(&self, pipeline: Box<dyn DynRayTracingPipeline>) {
+        unsafe { D::destroy_ray_tracing_pipeline(self, pipeline.unbox()) };
+    }
+
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        pipeline: &dyn DynRayTracingPipeline,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<Vec<u8>, DeviceError> {
+        let pipeline = pipeline.expect_downcast_ref();
+        unsafe { D::get_ray_tracing_shader_group_handles(self, pipeline, first_group, group_count) }
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         desc: &PipelineCacheDescriptor<'_>,
@@ -506,6 +652,25 @@ impl<D: Device + DynResource> DynDevice for D {
         unsafe { D::wait(self, fence, value, timeout_ms) }
     }
 
+    unsafe fn create_shared_fence(&self) -> Result<Box<dyn DynFence>, DeviceError> {
+        unsafe { D::create_shared_fence(self) }.map(|b| -> Box<dyn DynFence> { Box::new(b) })
+    }
+
+    unsafe fn export_fence(
+        &self,
+        fence: &dyn DynFence,
+    ) -> Result<wgt::SharedFenceHandle, DeviceError> {
+        let fence = fence.expect_downcast_ref();
+        unsafe { D::export_fence(self, fence) }
+    }
+
+    unsafe fn import_fence(
+        &self,
+        handle: wgt::SharedFenceHandle,
+    ) -> Result<Box<dyn DynFence>, DeviceError> {
+        unsafe { D::import_fence(self, handle) }.map(|b| -> Box<dyn DynFence> { Box::new(b) })
+    }
+
     unsafe fn start_graphics_debugger_capture(&self) -> bool {
         unsafe { D::start_graphics_debugger_capture(self) }
     }
@@ -554,6 +719,13 @@ impl<D: Device + DynResource> DynDevice for D {
         unsafe { D::destroy_acceleration_structure(self, acceleration_structure.unbox()) }
     }
 
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        data: &[u8],
+    ) -> Option<wgt::BufferAddress> {
+        unsafe { D::get_deserialized_acceleration_structure_size(self, data) }
+    }
+
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8> {
         D::tlas_instance_to_bytes(self, instance)
     }
@@ -566,7 +738,23 @@ impl<D: Device + DynResource> DynDevice for D {
         D::generate_allocator_report(self)
     }
 
+    fn trim_memory(&self) -> u32 {
+        D::trim_memory(self)
+    }
+
     fn check_if_oom(&self) -> Result<(), DeviceError> {
         D::check_if_oom(self)
     }
+
+    fn lost_reason(&self) -> wgt::DeviceLostReason {
+        D::lost_reason(self)
+    }
+
+    fn fault_info(&self) -> Option<String> {
+        D::fault_info(self)
+    }
+
+    fn take_validation_messages(&self) -> Vec<String> {
+        D::take_validation_messages(self)
+    }
 }
@@ -9,6 +9,7 @@ pub struct DynExposedAdapter {
     pub info: wgt::AdapterInfo,
     pub features: wgt::Features,
     pub capabilities: Capabilities,
+    pub toggles: wgt::Toggles,
 }
 
 impl DynExposedAdapter {
@@ -25,6 +26,7 @@ impl<A: Api> From<ExposedAdapter<A>> for DynExposedAdapter {
             info: exposed_adapter.info,
             features: exposed_adapter.features,
             capabilities: exposed_adapter.capabilities,
+            toggles: exposed_adapter.toggles,
         }
     }
 }
@@ -64,6 +66,7 @@ impl<I: Instance + DynResource> DynInstance for I {
                 info: exposed.info,
                 features: exposed.features,
                 capabilities: exposed.capabilities,
+                toggles: exposed.toggles,
             })
             .collect()
     }
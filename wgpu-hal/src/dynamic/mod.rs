@@ -23,7 +23,8 @@ use wgt::WasmNotSendSync;
 use crate::{
     AccelerationStructureAABBs, AccelerationStructureEntries, AccelerationStructureInstances,
     AccelerationStructureTriangleIndices, AccelerationStructureTriangleTransform,
-    AccelerationStructureTriangles, BufferBinding, ProgrammableStage, TextureBinding,
+    AccelerationStructureTriangles, BufferBinding, ProgrammableStage, RayTracingStage,
+    ShaderBindingTable, ShaderBindingTables, TextureBinding,
 };
 
 /// Base trait for all resources, allows downcasting via [`Any`].
@@ -111,9 +112,11 @@ pub trait DynBuffer: DynResource + fmt::Debug {}
 pub trait DynCommandBuffer: DynResource + fmt::Debug {}
 pub trait DynComputePipeline: DynResource + fmt::Debug {}
 pub trait DynFence: DynResource + fmt::Debug {}
+pub trait DynMemoryHeap: DynResource + fmt::Debug {}
 pub trait DynPipelineCache: DynResource + fmt::Debug {}
 pub trait DynPipelineLayout: DynResource + fmt::Debug {}
 pub trait DynQuerySet: DynResource + fmt::Debug {}
+pub trait DynRayTracingPipeline: DynResource + fmt::Debug {}
 pub trait DynRenderPipeline: DynResource + fmt::Debug {}
 pub trait DynSampler: DynResource + fmt::Debug {}
 pub trait DynShaderModule: DynResource + fmt::Debug {}
@@ -154,6 +157,35 @@ impl<'a> ProgrammableStage<'a, dyn DynShaderModule> {
     }
 }
 
+impl<'a> RayTracingStage<'a, dyn DynShaderModule> {
+    fn expect_downcast<T: DynShaderModule>(&self) -> RayTracingStage<'a, T> {
+        RayTracingStage {
+            stage: self.stage,
+            programmable: self.programmable.clone().expect_downcast(),
+        }
+    }
+}
+
+impl<'a> ShaderBindingTable<'a, dyn DynBuffer> {
+    fn expect_downcast<B: DynBuffer>(&self) -> ShaderBindingTable<'a, B> {
+        ShaderBindingTable {
+            buffer: self.buffer.expect_downcast_ref(),
+            region: self.region,
+        }
+    }
+}
+
+impl<'a> ShaderBindingTables<'a, dyn DynBuffer> {
+    fn expect_downcast<B: DynBuffer>(&self) -> ShaderBindingTables<'a, B> {
+        ShaderBindingTables {
+            ray_generation: self.ray_generation.expect_downcast(),
+            miss: self.miss.as_ref().map(|t| t.expect_downcast()),
+            hit: self.hit.as_ref().map(|t| t.expect_downcast()),
+            callable: self.callable.as_ref().map(|t| t.expect_downcast()),
+        }
+    }
+}
+
 impl<'a> AccelerationStructureEntries<'a, dyn DynBuffer> {
     fn expect_downcast<B: DynBuffer>(&self) -> AccelerationStructureEntries<'a, B> {
         match self {
@@ -1,8 +1,9 @@
 use alloc::{boxed::Box, vec::Vec};
 
 use crate::{
-    DeviceError, DynCommandBuffer, DynFence, DynResource, DynSurface, DynSurfaceTexture,
-    FenceValue, Queue, SurfaceError,
+    DeviceError, DynBuffer, DynCommandBuffer, DynFence, DynMemoryHeap, DynResource, DynSurface,
+    DynSurfaceTexture, DynTexture, FenceValue, Queue, SparseBufferBinding, SparseTextureBinding,
+    SurfaceError,
 };
 
 use super::DynResourceExt as _;
@@ -18,8 +19,25 @@ pub trait DynQueue: DynResource {
         &self,
         surface: &dyn DynSurface,
         texture: Box<dyn DynSurfaceTexture>,
+        damage: &[wgt::Rect],
     ) -> Result<(), SurfaceError>;
     unsafe fn get_timestamp_period(&self) -> f32;
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration>;
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[SparseBufferBinding<dyn DynBuffer, dyn DynMemoryHeap>],
+        texture_bindings: &[SparseTextureBinding<dyn DynTexture, dyn DynMemoryHeap>],
+    ) -> Result<(), DeviceError>;
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &dyn DynFence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError>;
+    unsafe fn signal_fence(
+        &self,
+        fence: &dyn DynFence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError>;
 }
 
 impl<Q: Queue + DynResource> DynQueue for Q {
@@ -45,12 +63,66 @@ impl<Q: Queue + DynResource> DynQueue for Q {
         &self,
         surface: &dyn DynSurface,
         texture: Box<dyn DynSurfaceTexture>,
+        damage: &[wgt::Rect],
     ) -> Result<(), SurfaceError> {
         let surface = surface.expect_downcast_ref();
-        unsafe { Q::present(self, surface, texture.unbox()) }
+        unsafe { Q::present(self, surface, texture.unbox(), damage) }
     }
 
     unsafe fn get_timestamp_period(&self) -> f32 {
         unsafe { Q::get_timestamp_period(self) }
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        unsafe { Q::get_timestamp_calibration(self) }
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[SparseBufferBinding<dyn DynBuffer, dyn DynMemoryHeap>],
+        texture_bindings: &[SparseTextureBinding<dyn DynTexture, dyn DynMemoryHeap>],
+    ) -> Result<(), DeviceError> {
+        let buffer_bindings = buffer_bindings
+            .iter()
+            .map(|binding| SparseBufferBinding {
+                buffer: binding.buffer.expect_downcast_ref(),
+                range: binding.range.clone(),
+                memory: binding
+                    .memory
+                    .map(|(heap, offset)| (heap.expect_downcast_ref(), offset)),
+            })
+            .collect::<Vec<_>>();
+        let texture_bindings = texture_bindings
+            .iter()
+            .map(|binding| SparseTextureBinding {
+                texture: binding.texture.expect_downcast_ref(),
+                mip_level: binding.mip_level,
+                array_layer: binding.array_layer,
+                origin: binding.origin,
+                size: binding.size,
+                memory: binding
+                    .memory
+                    .map(|(heap, offset)| (heap.expect_downcast_ref(), offset)),
+            })
+            .collect::<Vec<_>>();
+        unsafe { Q::bind_sparse_memory(self, &buffer_bindings, &texture_bindings) }
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &dyn DynFence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError> {
+        let fence = fence.expect_downcast_ref();
+        unsafe { Q::wait_for_fence(self, fence, value) }
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        fence: &dyn DynFence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError> {
+        let fence = fence.expect_downcast_ref();
+        unsafe { Q::signal_fence(self, fence, value) }
+    }
 }
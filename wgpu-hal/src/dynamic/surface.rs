@@ -1,4 +1,4 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::time::Duration;
 
 use crate::{
@@ -33,6 +33,14 @@ pub trait DynSurface: DynResource {
     ) -> Result<Option<DynAcquiredSurfaceTexture>, SurfaceError>;
 
     unsafe fn discard_texture(&self, texture: Box<dyn DynSurfaceTexture>);
+
+    unsafe fn set_target_present_time(&self, time: wgt::PresentationTimestamp);
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, SurfaceError>;
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath;
 }
 
 impl<S: Surface + DynResource> DynSurface for S {
@@ -71,4 +79,18 @@ impl<S: Surface + DynResource> DynSurface for S {
     unsafe fn discard_texture(&self, texture: Box<dyn DynSurfaceTexture>) {
         unsafe { S::discard_texture(self, texture.unbox()) }
     }
+
+    unsafe fn set_target_present_time(&self, time: wgt::PresentationTimestamp) {
+        unsafe { S::set_target_present_time(self, time) }
+    }
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, SurfaceError> {
+        unsafe { S::get_presentation_statistics(self) }
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        unsafe { S::presentation_path(self) }
+    }
 }
@@ -194,7 +194,9 @@ impl super::Adapter {
 
     pub(super) unsafe fn expose(
         context: super::AdapterContext,
+        logic_op: Option<super::LogicOpFn>,
         backend_options: wgt::GlBackendOptions,
+        toggles: wgt::TogglesDescriptor,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         let gl = context.lock();
         let extensions = gl.supported_extensions();
@@ -443,7 +445,10 @@ impl super::Adapter {
             | wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
             | wgt::Features::CLEAR_TEXTURE
             | wgt::Features::PUSH_CONSTANTS
-            | wgt::Features::DEPTH32FLOAT_STENCIL8;
+            | wgt::Features::DEPTH32FLOAT_STENCIL8
+            | wgt::Features::INDEX_FORMAT_UINT8
+            | wgt::Features::VERTEX_INSTANCE_STEP_RATE
+            | wgt::Features::REUSABLE_COMMAND_BUFFERS;
         features.set(
             wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO,
             extensions.contains("GL_EXT_texture_border_clamp")
@@ -458,9 +463,11 @@ impl super::Adapter {
             downlevel_flags.contains(wgt::DownlevelFlags::VERTEX_STORAGE)
                 && vertex_shader_storage_textures != 0,
         );
+        // Multiview attachments are only bound on WebGL, and native GLSL would need
+        // `GL_OVR_multiview2` instead of `GL_EXT_multiview`.
         features.set(
             wgt::Features::MULTIVIEW,
-            extensions.contains("OVR_multiview2") || extensions.contains("GL_OVR_multiview2"),
+            cfg!(webgl) && extensions.contains("OVR_multiview2"),
         );
         features.set(
             wgt::Features::DUAL_SOURCE_BLENDING,
@@ -471,6 +478,10 @@ impl super::Adapter {
             wgt::Features::CLIP_DISTANCES,
             full_ver.is_some() || extensions.contains("GL_EXT_clip_cull_distance"),
         );
+        features.set(
+            wgt::Features::FRAMEBUFFER_FETCH,
+            extensions.contains("GL_EXT_shader_framebuffer_fetch"),
+        );
         features.set(
             wgt::Features::SHADER_PRIMITIVE_INDEX,
             supported((3, 2), (3, 2))
@@ -481,6 +492,11 @@ impl super::Adapter {
             wgt::Features::SHADER_EARLY_DEPTH_TEST,
             supported((3, 1), (4, 2)) || extensions.contains("GL_ARB_shader_image_load_store"),
         );
+        features.set(
+            wgt::Features::TEXTURE_ATOMIC,
+            supported((3, 2), (4, 2))
+                || (supported((3, 1), (4, 2)) && extensions.contains("GL_OES_shader_image_atomic")),
+        );
         // We emulate MDI with a loop of draw calls.
         features.set(wgt::Features::MULTI_DRAW_INDIRECT, indirect_execution);
         if extensions.contains("GL_ARB_timer_query") {
@@ -525,6 +541,12 @@ impl super::Adapter {
             es_ver.is_some() || extensions.contains("GL_ARB_ES3_compatibility")
         };
         features.set(wgt::Features::TEXTURE_COMPRESSION_ETC2, has_etc);
+        #[cfg(target_os = "android")]
+        features.set(
+            wgt::Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER,
+            extensions.contains("GL_OES_EGL_image")
+                && super::android::egl_supports_android_hardware_buffer(&context),
+        );
 
         // `OES_texture_compression_astc` provides 2D + 3D, LDR + HDR support
         if extensions.contains("WEBGL_compressed_texture_astc")
@@ -580,6 +602,15 @@ impl super::Adapter {
             features |= wgt::Features::POLYGON_MODE_LINE | wgt::Features::POLYGON_MODE_POINT;
         }
 
+        let mut line_width_range = [1.0; 2];
+        unsafe {
+            gl.get_parameter_f32_slice(glow::ALIASED_LINE_WIDTH_RANGE, &mut line_width_range)
+        };
+        features.set(wgt::Features::WIDE_LINES, line_width_range[1] > 1.0);
+
+        let logic_op = logic_op.filter(|_| es_ver.is_none());
+        features.set(wgt::Features::LOGIC_OP, logic_op.is_some());
+
         // We *might* be able to emulate bgra8unorm-storage but currently don't attempt to.
 
         let mut private_caps = super::PrivateCapabilities::empty();
@@ -636,6 +667,17 @@ impl super::Adapter {
             super::PrivateCapabilities::INVALIDATE_FRAMEBUFFER,
             supported((3, 0), (4, 3)),
         );
+        // WebGL always restarts primitives and doesn't know about the enum
+        let primitive_restart_fixed_index = !cfg!(any(webgl, Emscripten))
+            && (supported((3, 0), (4, 3)) || extensions.contains("GL_ARB_ES3_compatibility"));
+        private_caps.set(
+            super::PrivateCapabilities::PRIMITIVE_RESTART_FIXED_INDEX,
+            primitive_restart_fixed_index,
+        );
+        features.set(
+            wgt::Features::PRIMITIVE_RESTART_CONTROL,
+            primitive_restart_fixed_index,
+        );
         if let Some(full_ver) = full_ver {
             let supported =
                 full_ver >= (4, 2) && extensions.contains("GL_ARB_shader_draw_parameters");
@@ -807,6 +849,7 @@ impl super::Adapter {
             max_blas_geometry_count: 0,
             max_tlas_instance_count: 0,
             max_acceleration_structures_per_shader_stage: 0,
+            max_ray_recursion_depth: 0,
         };
 
         let mut workarounds = super::Workarounds::empty();
@@ -831,6 +874,7 @@ impl super::Adapter {
             );
             workarounds.set(super::Workarounds::MESA_I915_SRGB_SHADER_CLEAR, true);
         }
+        let (workarounds, toggles) = workarounds.apply_toggles(&toggles);
 
         let downlevel_defaults = wgt::DownlevelLimits {};
         let max_samples = unsafe { gl.get_parameter_i32(glow::MAX_SAMPLES) };
@@ -855,6 +899,7 @@ impl super::Adapter {
                     program_cache: Default::default(),
                     es: es_ver.is_some(),
                     max_msaa_samples: max_samples,
+                    logic_op,
                 }),
             },
             info: Self::make_info(vendor, renderer, version),
@@ -881,8 +926,12 @@ impl super::Adapter {
                     uniform_bounds_check_alignment: wgt::BufferSize::new(1).unwrap(),
                     raw_tlas_instance_size: 0,
                     ray_tracing_scratch_buffer_alignment: 0,
+                    ray_tracing_shader_group_handle_size: 0,
+                    ray_tracing_shader_group_handle_alignment: 0,
+                    ray_tracing_shader_group_base_alignment: 0,
                 },
             },
+            toggles,
         })
     }
 
@@ -974,6 +1023,7 @@ impl crate::Adapter for super::Adapter {
         features: wgt::Features,
         _limits: &wgt::Limits,
         _memory_hints: &wgt::MemoryHints,
+        _additional_queues: &[wgt::QueueType],
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let gl = &self.shared.context.lock();
         unsafe { gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1) };
@@ -1026,6 +1076,8 @@ impl crate::Adapter for super::Adapter {
                 draw_buffer_count: AtomicU8::new(1),
                 current_index_buffer: Mutex::new(None),
             },
+            // `EXPERIMENTAL_MULTI_QUEUE` is not supported.
+            additional_queues: Vec::new(),
         })
     }
 
@@ -1160,7 +1212,7 @@ impl crate::Adapter for super::Adapter {
             | Tf::Depth32FloatStencil8
             | Tf::Depth24Plus
             | Tf::Depth24PlusStencil8 => depth,
-            Tf::NV12 => empty,
+            Tf::NV12 | Tf::P010 | Tf::I420 => empty,
             Tf::Rgb9e5Ufloat => filterable,
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
@@ -1238,6 +1290,7 @@ impl crate::Adapter for super::Adapter {
                 maximum_frame_latency: 2..=2, //TODO, unused currently
                 current_extent: None,
                 usage: wgt::TextureUses::COLOR_TARGET,
+                color_spaces: vec![wgt::SurfaceColorSpace::Srgb],
             })
         } else {
             None
@@ -1247,6 +1300,9 @@ impl crate::Adapter for super::Adapter {
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp::INVALID_TIMESTAMP
     }
+    fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        None
+    }
 }
 
 impl super::AdapterShared {
@@ -0,0 +1,180 @@
+//! Importing Android `AHardwareBuffer`s, through EGL images.
+
+#![cfg(target_os = "android")]
+
+use alloc::vec::Vec;
+use core::ffi::c_void;
+
+use glow::HasContext as _;
+
+const EGL_NATIVE_BUFFER_ANDROID: u32 = 0x3140;
+const EGL_IMAGE_PRESERVED_KHR: i32 = 0x30D2;
+
+type EglGetNativeClientBufferAndroid =
+    unsafe extern "system" fn(buffer: *const c_void) -> *mut c_void;
+type EglCreateImageKhr = unsafe extern "system" fn(
+    display: *mut c_void,
+    context: *mut c_void,
+    target: u32,
+    buffer: *mut c_void,
+    attrib_list: *const i32,
+) -> *mut c_void;
+type EglDestroyImageKhr =
+    unsafe extern "system" fn(display: *mut c_void, image: *mut c_void) -> u32;
+type GlEglImageTargetTexture2dOes = unsafe extern "system" fn(target: u32, image: *mut c_void);
+
+/// Returns whether the EGL display of `context` can import hardware buffers.
+///
+/// Binding the imported images to textures also needs `GL_OES_EGL_image`.
+pub(super) fn egl_supports_android_hardware_buffer(context: &super::AdapterContext) -> bool {
+    let (Some(egl), Some(display)) = (context.egl_instance(), context.raw_display()) else {
+        return false;
+    };
+    let Ok(egl_extensions) = egl.query_string(Some(*display), khronos_egl::EXTENSIONS) else {
+        return false;
+    };
+    let egl_extensions = egl_extensions.to_string_lossy();
+    let egl_extensions = egl_extensions.split_whitespace().collect::<Vec<_>>();
+
+    [
+        "EGL_KHR_image_base",
+        "EGL_ANDROID_get_native_client_buffer",
+        "EGL_ANDROID_image_native_buffer",
+    ]
+    .iter()
+    .all(|extension| egl_extensions.contains(extension))
+}
+
+impl super::Device {
+    /// Imports a texture from an `AHardwareBuffer`.
+    ///
+    /// The texture holds a reference to `buffer`, so the caller may release its own
+    /// reference once this returns.
+    ///
+    /// Requires [`wgt::Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER`]. YUV
+    /// hardware buffers can't be imported, as they can only be sampled through
+    /// external textures.
+    ///
+    /// # Safety
+    ///
+    /// - `buffer` must be a valid `AHardwareBuffer`, with the usage
+    ///   `AHARDWAREBUFFER_USAGE_GPU_SAMPLED_IMAGE` or `AHARDWAREBUFFER_USAGE_GPU_COLOR_OUTPUT`.
+    /// - `desc` must describe a 2D texture with the format and size of `buffer`, and a
+    ///   single mip level, array layer and sample.
+    /// - `desc.usage` must be allowed by the usage of `buffer`.
+    /// - The producer of `buffer` must be done writing to it before it is used.
+    pub unsafe fn texture_from_android_hardware_buffer(
+        &self,
+        buffer: *mut c_void,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        if !self
+            .shared
+            .features
+            .contains(wgt::Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER)
+        {
+            log::error!("EGL does not support importing Android hardware buffers");
+            return Err(crate::DeviceError::Unexpected);
+        }
+        let context = &self.shared.context;
+        let egl = context.egl_instance().unwrap();
+        let display = context.raw_display().unwrap().as_ptr();
+
+        // The feature guarantees that these are all available.
+        let (get_native_client_buffer, create_image, destroy_image, image_target_texture_2d) = unsafe {
+            (
+                core::mem::transmute::<extern "system" fn(), EglGetNativeClientBufferAndroid>(
+                    egl.get_proc_address("eglGetNativeClientBufferANDROID")
+                        .unwrap(),
+                ),
+                core::mem::transmute::<extern "system" fn(), EglCreateImageKhr>(
+                    egl.get_proc_address("eglCreateImageKHR").unwrap(),
+                ),
+                core::mem::transmute::<extern "system" fn(), EglDestroyImageKhr>(
+                    egl.get_proc_address("eglDestroyImageKHR").unwrap(),
+                ),
+                core::mem::transmute::<extern "system" fn(), GlEglImageTargetTexture2dOes>(
+                    egl.get_proc_address("glEGLImageTargetTexture2DOES")
+                        .unwrap(),
+                ),
+            )
+        };
+
+        let client_buffer = unsafe { get_native_client_buffer(buffer) };
+        if client_buffer.is_null() {
+            log::error!(
+                "eglGetNativeClientBufferANDROID failed: {:?}",
+                egl.get_error()
+            );
+            return Err(crate::DeviceError::Unexpected);
+        }
+
+        let attributes = [
+            EGL_IMAGE_PRESERVED_KHR,
+            khronos_egl::TRUE as i32,
+            khronos_egl::NONE,
+        ];
+        let image = unsafe {
+            create_image(
+                display,
+                core::ptr::null_mut(),
+                EGL_NATIVE_BUFFER_ANDROID,
+                client_buffer,
+                attributes.as_ptr(),
+            )
+        };
+        if image.is_null() {
+            log::error!("eglCreateImageKHR failed: {:?}", egl.get_error());
+            return Err(crate::DeviceError::Unexpected);
+        }
+
+        let gl = &context.lock();
+        let raw = unsafe { gl.create_texture().unwrap() };
+        unsafe { gl.bind_texture(glow::TEXTURE_2D, Some(raw)) };
+        if let Some(
+            wgt::TextureSampleType::Float { filterable: false }
+            | wgt::TextureSampleType::Uint
+            | wgt::TextureSampleType::Sint,
+        ) = desc.format.sample_type(None, Some(self.shared.features))
+        {
+            // reset default filtering mode
+            unsafe {
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MIN_FILTER,
+                    glow::NEAREST as i32,
+                );
+                gl.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MAG_FILTER,
+                    glow::NEAREST as i32,
+                );
+            }
+        }
+        unsafe { image_target_texture_2d(glow::TEXTURE_2D, image) };
+        let error = unsafe { gl.get_error() };
+        unsafe { gl.bind_texture(glow::TEXTURE_2D, None) };
+
+        // The texture keeps the hardware buffer alive without the image.
+        unsafe { destroy_image(display, image) };
+
+        if error != glow::NO_ERROR {
+            log::error!("glEGLImageTargetTexture2DOES failed: {error:#x}");
+            unsafe { gl.delete_texture(raw) };
+            return Err(crate::DeviceError::Unexpected);
+        }
+
+        Ok(super::Texture {
+            inner: super::TextureInner::Texture {
+                raw,
+                target: glow::TEXTURE_2D,
+            },
+            drop_guard: None,
+            mip_level_count: 1,
+            array_layer_count: 1,
+            format: desc.format,
+            format_desc: self.shared.describe_texture_format(desc.format),
+            copy_size: desc.copy_extent(),
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use core::{mem, ops::Range};
 
 use arrayvec::ArrayVec;
@@ -23,6 +23,7 @@ pub(super) struct State {
     stencil: super::StencilState,
     depth_bias: wgt::DepthBiasState,
     alpha_to_coverage_enabled: bool,
+    logic_op: Option<u32>,
     samplers: [Option<glow::Sampler>; super::MAX_SAMPLERS],
     texture_slots: [TextureSlotDesc; super::MAX_TEXTURE_SLOTS],
     render_size: wgt::Extent3d,
@@ -53,6 +54,7 @@ impl Default for State {
             stencil: Default::default(),
             depth_bias: Default::default(),
             alpha_to_coverage_enabled: Default::default(),
+            logic_op: Default::default(),
             samplers: Default::default(),
             texture_slots: Default::default(),
             render_size: Default::default(),
@@ -145,7 +147,10 @@ impl super::CommandEncoder {
                 };
                 let instance_offset = match buffer_desc.step {
                     wgt::VertexStepMode::Vertex => 0,
-                    wgt::VertexStepMode::Instance => first_instance * buffer_desc.stride,
+                    wgt::VertexStepMode::Instance
+                    | wgt::VertexStepMode::InstanceStepRate { .. } => {
+                        first_instance * buffer_desc.stride
+                    }
                 };
 
                 self.cmd_buffer.commands.push(C::SetVertexBuffer {
@@ -173,7 +178,7 @@ impl super::CommandEncoder {
 
                 let mut attribute_desc = attribute.clone();
                 attribute_desc.offset += vb.offset as u32;
-                if buffer_desc.step == wgt::VertexStepMode::Instance {
+                if buffer_desc.step != wgt::VertexStepMode::Vertex {
                     attribute_desc.offset += buffer_desc.stride * first_instance;
                 }
 
@@ -314,7 +319,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
             if !bar
                 .usage
                 .from
-                .contains(wgt::TextureUses::STORAGE_READ_WRITE)
+                .intersects(wgt::TextureUses::STORAGE_READ_WRITE | wgt::TextureUses::STORAGE_ATOMIC)
             {
                 continue;
             }
@@ -410,6 +415,31 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn resolve_texture<T>(&mut self, src: &super::Texture, dst: &super::Texture, regions: T)
+    where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        let view = |texture: &super::Texture, base: &crate::TextureCopyBase| super::TextureView {
+            inner: texture.inner.clone(),
+            aspects: crate::FormatAspects::COLOR,
+            mip_levels: base.mip_level..base.mip_level + 1,
+            array_layers: base.array_layer..base.array_layer + 1,
+            format: texture.format,
+        };
+        for r in regions {
+            self.cmd_buffer.commands.push(C::ResolveTexture {
+                src: view(src, &r.src_base),
+                dst: view(dst, &r.dst_base),
+                rect: wgt::Rect {
+                    x: r.dst_base.origin.x,
+                    y: r.dst_base.origin.y,
+                    width: r.size.width,
+                    height: r.size.height,
+                },
+            });
+        }
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -563,12 +593,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
                             view: cat.target.view.clone(),
                             depth_slice: cat.depth_slice,
                         });
-                        if let Some(ref rat) = cat.resolve_target {
+                        if let Some(rat) = cat.resolve_target.as_ref().filter(|_| desc.resolves()) {
                             self.state
                                 .resolve_attachments
                                 .push((attachment, rat.view.clone()));
                         }
-                        if !cat.ops.contains(crate::AttachmentOps::STORE) {
+                        if !cat
+                            .ops
+                            .with_pass_flags(desc.flags)
+                            .contains(crate::AttachmentOps::STORE)
+                        {
                             self.state.invalidate_attachments.push(attachment);
                         }
                     }
@@ -586,14 +620,20 @@ impl crate::CommandEncoder for super::CommandEncoder {
                         depth_slice: None,
                     });
                     if aspects.contains(crate::FormatAspects::DEPTH)
-                        && !dsat.depth_ops.contains(crate::AttachmentOps::STORE)
+                        && !dsat
+                            .depth_ops
+                            .with_pass_flags(desc.flags)
+                            .contains(crate::AttachmentOps::STORE)
                     {
                         self.state
                             .invalidate_attachments
                             .push(glow::DEPTH_ATTACHMENT);
                     }
                     if aspects.contains(crate::FormatAspects::STENCIL)
-                        && !dsat.stencil_ops.contains(crate::AttachmentOps::STORE)
+                        && !dsat
+                            .stencil_ops
+                            .with_pass_flags(desc.flags)
+                            .contains(crate::AttachmentOps::STORE)
                     {
                         self.state
                             .invalidate_attachments
@@ -629,7 +669,11 @@ impl crate::CommandEncoder for super::CommandEncoder {
             .filter_map(|at| at.as_ref())
             .enumerate()
         {
-            if !cat.ops.contains(crate::AttachmentOps::LOAD) {
+            if !cat
+                .ops
+                .with_pass_flags(desc.flags)
+                .contains(crate::AttachmentOps::LOAD)
+            {
                 let c = &cat.clear_value;
                 self.cmd_buffer.commands.push(
                     match cat.target.view.format.sample_type(None, None).unwrap() {
@@ -653,8 +697,14 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
 
         if let Some(ref dsat) = desc.depth_stencil_attachment {
-            let clear_depth = !dsat.depth_ops.contains(crate::AttachmentOps::LOAD);
-            let clear_stencil = !dsat.stencil_ops.contains(crate::AttachmentOps::LOAD);
+            let clear_depth = !dsat
+                .depth_ops
+                .with_pass_flags(desc.flags)
+                .contains(crate::AttachmentOps::LOAD);
+            let clear_stencil = !dsat
+                .stencil_ops
+                .with_pass_flags(desc.flags)
+                .contains(crate::AttachmentOps::LOAD);
 
             if clear_depth && clear_stencil {
                 self.cmd_buffer.commands.push(C::ClearDepthAndStencil(
@@ -695,6 +745,9 @@ impl crate::CommandEncoder for super::CommandEncoder {
         self.state.dirty_vbuf_mask = 0;
         self.state.active_first_instance = 0;
         self.state.color_targets.clear();
+        if self.state.logic_op.take().is_some() {
+            self.cmd_buffer.commands.push(C::SetLogicOp(None));
+        }
         for vat in &self.state.vertex_attributes {
             self.cmd_buffer
                 .commands
@@ -708,6 +761,19 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn create_parallel_render_encoder(
+        &mut self,
+    ) -> Result<super::CommandEncoder, crate::DeviceError> {
+        Err(crate::DeviceError::Unexpected)
+    }
+
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        _encoders: Vec<super::CommandEncoder>,
+    ) -> Result<(), crate::DeviceError> {
+        unreachable!("parallel render encoders are not supported on GL")
+    }
+
     unsafe fn set_bind_group(
         &mut self,
         layout: &super::PipelineLayout,
@@ -889,7 +955,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
             .zip(pipeline.vertex_buffers.iter())
             .enumerate()
         {
-            if pipe_desc.step == wgt::VertexStepMode::Instance {
+            if pipe_desc.step != wgt::VertexStepMode::Vertex {
                 self.state.instance_vbuf_mask |= 1 << index;
             }
             if state_desc != pipe_desc {
@@ -958,6 +1024,13 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 .push(C::SetAlphaToCoverage(pipeline.alpha_to_coverage_enabled));
         }
 
+        if pipeline.logic_op != self.state.logic_op {
+            self.state.logic_op = pipeline.logic_op;
+            self.cmd_buffer
+                .commands
+                .push(C::SetLogicOp(pipeline.logic_op));
+        }
+
         // set blend states
         if self.state.color_targets[..] != pipeline.color_targets[..] {
             if pipeline
@@ -1028,6 +1101,14 @@ impl crate::CommandEncoder for super::CommandEncoder {
             depth,
         });
     }
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        _index: u32,
+        _rect: &crate::Rect<f32>,
+        _depth_range: Range<f32>,
+    ) {
+        // `MULTI_VIEWPORT` isn't exposed on GL.
+    }
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
         self.cmd_buffer.commands.push(C::SetScissor(crate::Rect {
             x: rect.x as i32,
@@ -1045,6 +1126,20 @@ impl crate::CommandEncoder for super::CommandEncoder {
         self.cmd_buffer.commands.push(C::SetBlendConstant(*color));
     }
 
+    unsafe fn set_shading_rate(&mut self, _rate: wgt::ShadingRate) {
+        // `VARIABLE_RATE_SHADING` isn't exposed on GL.
+    }
+
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+    ) {
+        // `CONDITIONAL_RENDERING` isn't exposed on GL, whose conditional rendering
+        // reads query objects rather than buffers.
+    }
+    unsafe fn end_conditional_rendering(&mut self) {}
+
     unsafe fn draw(
         &mut self,
         first_vertex: u32,
@@ -1073,6 +1168,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
     ) {
         self.prepare_draw(first_instance);
         let (index_size, index_type) = match self.state.index_format {
+            wgt::IndexFormat::Uint8 => (1, glow::UNSIGNED_BYTE),
             wgt::IndexFormat::Uint16 => (2, glow::UNSIGNED_SHORT),
             wgt::IndexFormat::Uint32 => (4, glow::UNSIGNED_INT),
         };
@@ -1124,6 +1220,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
     ) {
         self.prepare_draw(0);
         let index_type = match self.state.index_format {
+            wgt::IndexFormat::Uint8 => glow::UNSIGNED_BYTE,
             wgt::IndexFormat::Uint16 => glow::UNSIGNED_SHORT,
             wgt::IndexFormat::Uint32 => glow::UNSIGNED_INT,
         };
@@ -1227,6 +1324,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
         });
     }
 
+    unsafe fn set_ray_tracing_pipeline(&mut self, _pipeline: &super::RayTracingPipeline) {
+        unreachable!()
+    }
+
+    unsafe fn trace_rays(
+        &mut self,
+        _tables: &crate::ShaderBindingTables<super::Buffer>,
+        _count: [u32; 3],
+    ) {
+        unreachable!()
+    }
+
     unsafe fn build_acceleration_structures<'a, T>(
         &mut self,
         _descriptor_count: u32,
@@ -1260,6 +1369,24 @@ impl crate::CommandEncoder for super::CommandEncoder {
         unimplemented!()
     }
 
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        _src: &super::AccelerationStructure,
+        _dst: &super::Buffer,
+        _dst_offset: wgt::BufferAddress,
+    ) {
+        unimplemented!()
+    }
+
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        _src: &super::Buffer,
+        _src_offset: wgt::BufferAddress,
+        _dst: &super::AccelerationStructure,
+    ) {
+        unimplemented!()
+    }
+
     unsafe fn read_acceleration_structure_compact_size(
         &mut self,
         _acceleration_structure: &super::AccelerationStructure,
@@ -1267,4 +1394,12 @@ impl crate::CommandEncoder for super::CommandEncoder {
     ) {
         unimplemented!()
     }
+
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        _acceleration_structure: &super::AccelerationStructure,
+        _buf: &super::Buffer,
+    ) {
+        unimplemented!()
+    }
 }
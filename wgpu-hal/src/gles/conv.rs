@@ -88,7 +88,7 @@ impl super::AdapterShared {
                 glow::DEPTH_STENCIL,
                 glow::UNSIGNED_INT_24_8,
             ),
-            Tf::NV12 => unreachable!(),
+            Tf::NV12 | Tf::P010 | Tf::I420 => unreachable!(),
             Tf::Rgb9e5Ufloat => (glow::RGB9_E5, glow::RGB, glow::UNSIGNED_INT_5_9_9_9_REV),
             Tf::Bc1RgbaUnorm => (glow::COMPRESSED_RGBA_S3TC_DXT1_EXT, glow::RGBA, 0),
             Tf::Bc1RgbaUnormSrgb => (glow::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT, glow::RGBA, 0),
@@ -311,6 +311,8 @@ pub(super) fn map_primitive_state(state: &wgt::PrimitiveState) -> super::Primiti
             wgt::PolygonMode::Line => glow::LINE,
             wgt::PolygonMode::Point => glow::POINT,
         },
+        line_width: state.line_width,
+        primitive_restart: state.primitive_restart && state.topology.is_strip(),
     }
 }
 
@@ -403,6 +405,28 @@ fn map_blend_component(component: &wgt::BlendComponent) -> super::BlendComponent
     }
 }
 
+pub(super) fn map_logic_op(logic_op: wgt::LogicOp) -> u32 {
+    use wgt::LogicOp as Lo;
+    match logic_op {
+        Lo::Clear => glow::CLEAR,
+        Lo::And => glow::AND,
+        Lo::AndReverse => glow::AND_REVERSE,
+        Lo::Copy => glow::COPY,
+        Lo::AndInverted => glow::AND_INVERTED,
+        Lo::NoOp => glow::NOOP,
+        Lo::Xor => glow::XOR,
+        Lo::Or => glow::OR,
+        Lo::Nor => glow::NOR,
+        Lo::Equivalent => glow::EQUIV,
+        Lo::Invert => glow::INVERT,
+        Lo::OrReverse => glow::OR_REVERSE,
+        Lo::CopyInverted => glow::COPY_INVERTED,
+        Lo::OrInverted => glow::OR_INVERTED,
+        Lo::Nand => glow::NAND,
+        Lo::Set => glow::SET,
+    }
+}
+
 pub(super) fn map_blend(blend: &wgt::BlendState) -> super::BlendDesc {
     super::BlendDesc {
         color: map_blend_component(&blend.color),
@@ -99,7 +99,12 @@ impl CompilationContext<'_> {
                     unsafe { gl.bind_frag_data_location(program, location.location, &name) }
                 }
                 naga::ShaderStage::Compute => {}
-                naga::ShaderStage::Task | naga::ShaderStage::Mesh => unreachable!(),
+                naga::ShaderStage::Task
+                | naga::ShaderStage::Mesh
+                | naga::ShaderStage::RayGeneration
+                | naga::ShaderStage::Miss
+                | naga::ShaderStage::ClosestHit
+                | naga::ShaderStage::AnyHit => unreachable!(),
             }
         }
 
@@ -175,7 +180,12 @@ impl super::Device {
             naga::ShaderStage::Vertex => glow::VERTEX_SHADER,
             naga::ShaderStage::Fragment => glow::FRAGMENT_SHADER,
             naga::ShaderStage::Compute => glow::COMPUTE_SHADER,
-            naga::ShaderStage::Task | naga::ShaderStage::Mesh => unreachable!(),
+            naga::ShaderStage::Task
+            | naga::ShaderStage::Mesh
+            | naga::ShaderStage::RayGeneration
+            | naga::ShaderStage::Miss
+            | naga::ShaderStage::ClosestHit
+            | naga::ShaderStage::AnyHit => unreachable!(),
         };
 
         let raw = unsafe { gl.create_shader(target) }.unwrap();
@@ -994,6 +1004,40 @@ impl crate::Device for super::Device {
         self.counters.textures.add(1);
     }
 
+    unsafe fn create_memory_heap(
+        &self,
+        _desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<super::MemoryHeap, crate::DeviceError> {
+        unreachable!()
+    }
+    unsafe fn destroy_memory_heap(&self, _heap: super::MemoryHeap) {}
+    unsafe fn texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, crate::DeviceError> {
+        unreachable!()
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        _heap: &super::MemoryHeap,
+        _offset: wgt::BufferAddress,
+        _desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unreachable!()
+    }
+    unsafe fn create_sparse_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        unreachable!()
+    }
+    unsafe fn create_sparse_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unreachable!()
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &super::Texture,
@@ -1204,6 +1248,7 @@ impl crate::Device for super::Device {
                     } => &mut num_storage_buffers,
                     wgt::BindingType::AccelerationStructure { .. } => unimplemented!(),
                     wgt::BindingType::ExternalTexture => unimplemented!(),
+                    wgt::BindingType::YcbcrTexture { .. } => unimplemented!(),
                 };
 
                 binding_to_slot[entry.binding as usize] = *counter;
@@ -1317,6 +1362,7 @@ impl crate::Device for super::Device {
                 }
                 wgt::BindingType::AccelerationStructure { .. } => unimplemented!(),
                 wgt::BindingType::ExternalTexture => unimplemented!(),
+                wgt::BindingType::YcbcrTexture { .. } => unimplemented!(),
             };
             contents.push(binding);
         }
@@ -1434,6 +1480,13 @@ impl crate::Device for super::Device {
                 .as_ref()
                 .map(|ds| conv::map_stencil(&ds.stencil)),
             alpha_to_coverage_enabled: desc.multisample.alpha_to_coverage_enabled,
+            // wgpu-core checks that every color target uses the same logic operation.
+            logic_op: desc
+                .color_targets
+                .iter()
+                .flatten()
+                .find_map(|ct| ct.logic_op)
+                .map(conv::map_logic_op),
         })
     }
     unsafe fn create_mesh_pipeline(
@@ -1501,6 +1554,26 @@ impl crate::Device for super::Device {
         self.counters.compute_pipelines.sub(1);
     }
 
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        _desc: &crate::RayTracingPipelineDescriptor<
+            <Self::A as crate::Api>::PipelineLayout,
+            <Self::A as crate::Api>::ShaderModule,
+            <Self::A as crate::Api>::PipelineCache,
+        >,
+    ) -> Result<<Self::A as crate::Api>::RayTracingPipeline, crate::PipelineError> {
+        unreachable!()
+    }
+    unsafe fn destroy_ray_tracing_pipeline(&self, _pipeline: super::RayTracingPipeline) {}
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        _pipeline: &super::RayTracingPipeline,
+        _first_group: u32,
+        _group_count: u32,
+    ) -> Result<Vec<u8>, crate::DeviceError> {
+        unreachable!()
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         _: &crate::PipelineCacheDescriptor<'_>,
@@ -1593,6 +1666,22 @@ impl crate::Device for super::Device {
         fence.wait(gl, wait_value, timeout_ns)
     }
 
+    unsafe fn create_shared_fence(&self) -> Result<super::Fence, crate::DeviceError> {
+        unreachable!("GLES fences can't be shared")
+    }
+    unsafe fn export_fence(
+        &self,
+        _fence: &super::Fence,
+    ) -> Result<wgt::SharedFenceHandle, crate::DeviceError> {
+        unreachable!("GLES fences can't be shared")
+    }
+    unsafe fn import_fence(
+        &self,
+        _handle: wgt::SharedFenceHandle,
+    ) -> Result<super::Fence, crate::DeviceError> {
+        unreachable!("GLES fences can't be shared")
+    }
+
     unsafe fn start_graphics_debugger_capture(&self) -> bool {
         #[cfg(all(native, feature = "renderdoc"))]
         return unsafe {
@@ -1633,6 +1722,13 @@ impl crate::Device for super::Device {
     ) {
     }
 
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        _data: &[u8],
+    ) -> Option<wgt::BufferAddress> {
+        unimplemented!()
+    }
+
     fn tlas_instance_to_bytes(&self, _instance: TlasInstance) -> Vec<u8> {
         unimplemented!()
     }
@@ -53,6 +53,13 @@ type WlEglWindowResizeFun = unsafe extern "system" fn(
 
 type WlEglWindowDestroyFun = unsafe extern "system" fn(window: *const ffi::c_void);
 
+type EglSwapBuffersWithDamageFun = unsafe extern "system" fn(
+    display: khronos_egl::EGLDisplay,
+    surface: khronos_egl::EGLSurface,
+    rects: *const khronos_egl::Int,
+    n_rects: khronos_egl::Int,
+) -> khronos_egl::Boolean;
+
 type EglLabel = *const ffi::c_void;
 
 #[allow(clippy::upper_case_acronyms)]
@@ -293,6 +300,8 @@ struct EglContext {
     display: khronos_egl::Display,
     raw: khronos_egl::Context,
     pbuffer: Option<khronos_egl::Surface>,
+    /// `eglSwapBuffersWithDamageKHR` or `eglSwapBuffersWithDamageEXT`, if supported.
+    swap_buffers_with_damage: Option<EglSwapBuffersWithDamageFun>,
 }
 
 impl EglContext {
@@ -544,6 +553,21 @@ impl Inner {
             SrgbFrameBufferKind::None
         };
 
+        let swap_buffers_with_damage = [
+            (
+                "EGL_KHR_swap_buffers_with_damage",
+                "eglSwapBuffersWithDamageKHR",
+            ),
+            (
+                "EGL_EXT_swap_buffers_with_damage",
+                "eglSwapBuffersWithDamageEXT",
+            ),
+        ]
+        .into_iter()
+        .filter(|&(extension, _)| display_extensions.contains(extension))
+        .find_map(|(_, name)| egl.get_proc_address(name))
+        .map(|addr| unsafe { core::mem::transmute::<_, EglSwapBuffersWithDamageFun>(addr) });
+
         if log::max_level() >= log::LevelFilter::Trace {
             log::trace!("Configurations:");
             let config_count = egl.get_config_count(display).unwrap();
@@ -705,6 +729,7 @@ impl Inner {
                 raw: context,
                 pbuffer,
                 version,
+                swap_buffers_with_damage,
             },
             version,
             supports_native_window,
@@ -750,6 +775,7 @@ pub struct Instance {
     wsi: WindowSystemInterface,
     flags: wgt::InstanceFlags,
     options: wgt::GlBackendOptions,
+    toggles: wgt::TogglesDescriptor,
     inner: Mutex<Inner>,
 }
 
@@ -948,6 +974,7 @@ impl crate::Instance for Instance {
             },
             flags: desc.flags,
             options: desc.backend_options.gl.clone(),
+            toggles: desc.toggles,
             inner: Mutex::new(inner),
         })
     }
@@ -1073,15 +1100,15 @@ impl crate::Instance for Instance {
         let inner = self.inner.lock();
         inner.egl.make_current();
 
-        let mut gl = unsafe {
-            glow::Context::from_loader_function(|name| {
-                inner
-                    .egl
-                    .instance
-                    .get_proc_address(name)
-                    .map_or(ptr::null(), |p| p as *const _)
-            })
+        let load = |name: &str| {
+            inner
+                .egl
+                .instance
+                .get_proc_address(name)
+                .map_or(ptr::null(), |p| p as *const ffi::c_void)
         };
+        let mut gl = unsafe { glow::Context::from_loader_function(load) };
+        let logic_op = super::load_logic_op(load);
 
         // In contrast to OpenGL ES, OpenGL requires explicitly enabling sRGB conversions,
         // as otherwise the user has to do the sRGB conversion.
@@ -1113,7 +1140,9 @@ impl crate::Instance for Instance {
                     glow: Mutex::new(gl),
                     egl: Some(inner.egl.clone()),
                 },
+                logic_op,
                 self.options.clone(),
+                self.toggles,
             )
         }
         .into_iter()
@@ -1132,9 +1161,10 @@ impl super::Adapter {
     /// - The underlying OpenGL ES context must be current when dropping this adapter and when
     ///   dropping any objects returned from this adapter.
     pub unsafe fn new_external(
-        fun: impl FnMut(&str) -> *const ffi::c_void,
+        mut fun: impl FnMut(&str) -> *const ffi::c_void,
         options: wgt::GlBackendOptions,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
+        let logic_op = super::load_logic_op(&mut fun);
         let context = unsafe { glow::Context::from_loader_function(fun) };
         unsafe {
             Self::expose(
@@ -1142,7 +1172,9 @@ impl super::Adapter {
                     glow: Mutex::new(ManuallyDrop::new(context)),
                     egl: None,
                 },
+                logic_op,
                 options,
+                wgt::TogglesDescriptor::default(),
             )
         }
     }
@@ -1192,6 +1224,7 @@ impl Surface {
         &self,
         _suf_texture: super::Texture,
         context: &AdapterContext,
+        damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
         let gl = unsafe { context.get_without_egl_lock() };
         let swapchain = self.swapchain.read();
@@ -1246,14 +1279,48 @@ impl Surface {
 
         unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
 
-        self.egl
-            .instance
-            .swap_buffers(self.egl.display, sc.surface)
-            .map_err(|e| {
-                log::error!("swap_buffers failed: {}", e);
-                crate::SurfaceError::Lost
-                // TODO: should we unset the current context here?
-            })?;
+        match self.egl.swap_buffers_with_damage {
+            Some(swap_buffers_with_damage) if !damage.is_empty() => {
+                // EGL rectangles have their origin at the bottom left, so they
+                // are Y-flipped just like the blit above.
+                let rects = damage
+                    .iter()
+                    .flat_map(|rect| {
+                        [
+                            rect.x as khronos_egl::Int,
+                            (sc.extent.height - rect.y - rect.height) as khronos_egl::Int,
+                            rect.width as khronos_egl::Int,
+                            rect.height as khronos_egl::Int,
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                let result = unsafe {
+                    swap_buffers_with_damage(
+                        self.egl.display.as_ptr(),
+                        sc.surface.as_ptr(),
+                        rects.as_ptr(),
+                        damage.len() as khronos_egl::Int,
+                    )
+                };
+                if result != khronos_egl::TRUE {
+                    log::error!(
+                        "swap_buffers_with_damage failed: {:?}",
+                        self.egl.instance.get_error()
+                    );
+                    return Err(crate::SurfaceError::Lost);
+                }
+            }
+            _ => {
+                self.egl
+                    .instance
+                    .swap_buffers(self.egl.display, sc.surface)
+                    .map_err(|e| {
+                        log::error!("swap_buffers failed: {}", e);
+                        crate::SurfaceError::Lost
+                        // TODO: should we unset the current context here?
+                    })?;
+            }
+        }
         self.egl
             .instance
             .make_current(self.egl.display, None, None, None)
@@ -1541,4 +1608,16 @@ impl crate::Surface for Surface {
         }))
     }
     unsafe fn discard_texture(&self, _texture: super::Texture) {}
+
+    unsafe fn set_target_present_time(&self, _time: wgt::PresentationTimestamp) {}
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        Ok(Vec::new())
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        wgt::PresentationPath::Unknown
+    }
 }
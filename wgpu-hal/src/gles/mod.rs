@@ -90,6 +90,7 @@ mod web;
 mod wgl;
 
 mod adapter;
+mod android;
 mod command;
 mod conv;
 mod device;
@@ -157,6 +158,7 @@ impl crate::Api for Api {
     type Sampler = Sampler;
     type QuerySet = QuerySet;
     type Fence = Fence;
+    type MemoryHeap = MemoryHeap;
     type AccelerationStructure = AccelerationStructure;
     type PipelineCache = PipelineCache;
 
@@ -166,6 +168,7 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type RayTracingPipeline = RayTracingPipeline;
 }
 
 crate::impl_dyn_resource!(
@@ -180,10 +183,12 @@ crate::impl_dyn_resource!(
     Device,
     Fence,
     Instance,
+    MemoryHeap,
     PipelineCache,
     PipelineLayout,
     QuerySet,
     Queue,
+    RayTracingPipeline,
     RenderPipeline,
     Sampler,
     ShaderModule,
@@ -230,6 +235,8 @@ bitflags::bitflags! {
         ///
         /// When this is true, instance offset emulation via vertex buffer rebinding and a shader uniform will be disabled.
         const FULLY_FEATURED_INSTANCING = 1 << 16;
+        /// Supports toggling `PRIMITIVE_RESTART_FIXED_INDEX`.
+        const PRIMITIVE_RESTART_FIXED_INDEX = 1 << 17;
     }
 }
 
@@ -249,6 +256,32 @@ bitflags::bitflags! {
     }
 }
 
+impl Workarounds {
+    /// The adapter toggle of each workaround which can be toggled.
+    const TOGGLES: [(Self, wgt::Toggles); 1] = [(
+        Self::MESA_I915_SRGB_SHADER_CLEAR,
+        wgt::Toggles::GL_MESA_I915_SRGB_SHADER_CLEAR,
+    )];
+
+    /// Forces workarounds on or off as `toggles` asks, and returns the resulting
+    /// workarounds along with their toggles.
+    fn apply_toggles(self, toggles: &wgt::TogglesDescriptor) -> (Self, wgt::Toggles) {
+        let mut workarounds = self;
+        let mut applied = wgt::Toggles::empty();
+        for (workaround, toggle) in Self::TOGGLES {
+            let default = if self.contains(workaround) {
+                toggle
+            } else {
+                wgt::Toggles::empty()
+            };
+            let enabled = toggles.apply(default).contains(toggle);
+            workarounds.set(workaround, enabled);
+            applied.set(toggle, enabled);
+        }
+        (workarounds, applied)
+    }
+}
+
 type BindTarget = u32;
 
 #[derive(Debug, Clone, Copy)]
@@ -287,6 +320,20 @@ struct AdapterShared {
     /// Cached here so it doesn't need to be queried every time texture format capabilities are requested.
     /// (this has been shown to be a significant enough overhead)
     max_msaa_samples: i32,
+    /// `glLogicOp`, only loaded on desktop GL, as OpenGL ES has no logic operations.
+    logic_op: Option<LogicOpFn>,
+}
+
+/// The signature of `glLogicOp`, which glow doesn't expose.
+type LogicOpFn = unsafe extern "system" fn(opcode: u32);
+
+/// Loads `glLogicOp` with a GL loader function.
+#[cfg(not(webgl))]
+fn load_logic_op(mut load: impl FnMut(&str) -> *const core::ffi::c_void) -> Option<LogicOpFn> {
+    let ptr = load("glLogicOp");
+    // SAFETY: A non-null `glLogicOp` has the signature of `LogicOpFn`.
+    (!ptr.is_null())
+        .then(|| unsafe { core::mem::transmute::<*const core::ffi::c_void, LogicOpFn>(ptr) })
 }
 
 pub struct Adapter {
@@ -724,6 +771,8 @@ pub struct RenderPipeline {
     depth_bias: wgt::DepthBiasState,
     stencil: Option<StencilState>,
     alpha_to_coverage_enabled: bool,
+    /// The `glLogicOp` operation shared by all color targets.
+    logic_op: Option<u32>,
 }
 
 impl crate::DynRenderPipeline for RenderPipeline {}
@@ -758,6 +807,18 @@ pub struct AccelerationStructure;
 
 impl crate::DynAccelerationStructure for AccelerationStructure {}
 
+/// Memory heaps are not supported on this backend.
+#[derive(Debug)]
+pub struct MemoryHeap;
+
+impl crate::DynMemoryHeap for MemoryHeap {}
+
+/// Ray tracing pipelines are not supported on this backend.
+#[derive(Debug)]
+pub struct RayTracingPipeline;
+
+impl crate::DynRayTracingPipeline for RayTracingPipeline {}
+
 #[derive(Debug)]
 pub struct PipelineCache;
 
@@ -813,6 +874,8 @@ struct PrimitiveState {
     cull_face: u32,
     unclipped_depth: bool,
     polygon_mode: u32,
+    line_width: f32,
+    primitive_restart: bool,
 }
 
 type InvalidatedAttachments = ArrayVec<u32, { crate::MAX_COLOR_ATTACHMENTS + 2 }>;
@@ -924,6 +987,11 @@ enum Command {
         dst: TextureView,
         size: wgt::Extent3d,
     },
+    ResolveTexture {
+        src: TextureView,
+        dst: TextureView,
+        rect: wgt::Rect,
+    },
     InvalidateAttachments(InvalidatedAttachments),
     SetDrawColorBuffers(u8),
     ClearColorF {
@@ -962,6 +1030,7 @@ enum Command {
     SetDepthBias(wgt::DepthBiasState),
     ConfigureDepthStencil(crate::FormatAspects),
     SetAlphaToCoverage(bool),
+    SetLogicOp(Option<u32>),
     SetVertexAttribute {
         buffer: Option<glow::Buffer>,
         buffer_desc: VertexBufferDesc,
@@ -86,6 +86,9 @@ impl super::Queue {
         if self.features.contains(wgt::Features::DEPTH_CLIP_CONTROL) {
             unsafe { gl.disable(glow::DEPTH_CLAMP) };
         }
+        if self.features.contains(wgt::Features::LOGIC_OP) {
+            unsafe { gl.disable(glow::COLOR_LOGIC_OP) };
+        }
 
         unsafe { gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None) };
         let mut current_index_buffer = self.current_index_buffer.lock();
@@ -1146,6 +1149,52 @@ impl super::Queue {
                 unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
                 unsafe { gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, Some(self.draw_fbo)) };
             }
+            C::ResolveTexture {
+                ref src,
+                ref dst,
+                rect,
+            } => {
+                // Multisampled blits need the same rectangle on both sides.
+                let (x0, y0) = (rect.x as i32, rect.y as i32);
+                let (x1, y1) = (x0 + rect.width as i32, y0 + rect.height as i32);
+                unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.draw_fbo)) };
+                unsafe {
+                    self.set_attachment(
+                        gl,
+                        glow::READ_FRAMEBUFFER,
+                        glow::COLOR_ATTACHMENT0,
+                        src,
+                        None,
+                    )
+                };
+                unsafe { gl.read_buffer(glow::COLOR_ATTACHMENT0) };
+                unsafe { gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, Some(self.copy_fbo)) };
+                unsafe {
+                    self.set_attachment(
+                        gl,
+                        glow::DRAW_FRAMEBUFFER,
+                        glow::COLOR_ATTACHMENT0,
+                        dst,
+                        None,
+                    )
+                };
+                unsafe {
+                    gl.blit_framebuffer(
+                        x0,
+                        y0,
+                        x1,
+                        y1,
+                        x0,
+                        y0,
+                        x1,
+                        y1,
+                        glow::COLOR_BUFFER_BIT,
+                        glow::NEAREST,
+                    )
+                };
+                unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
+                unsafe { gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, Some(self.draw_fbo)) };
+            }
             C::InvalidateAttachments(ref list) => {
                 if self
                     .shared
@@ -1253,7 +1302,8 @@ impl super::Queue {
                 if usage.intersects(
                     wgt::TextureUses::STORAGE_READ_ONLY
                         | wgt::TextureUses::STORAGE_WRITE_ONLY
-                        | wgt::TextureUses::STORAGE_READ_WRITE,
+                        | wgt::TextureUses::STORAGE_READ_WRITE
+                        | wgt::TextureUses::STORAGE_ATOMIC,
                 ) {
                     flags |= glow::SHADER_IMAGE_ACCESS_BARRIER_BIT;
                 }
@@ -1350,7 +1400,12 @@ impl super::Queue {
                             )
                         },
                     }
-                    unsafe { gl.vertex_attrib_divisor(vat.location, buffer_desc.step as u32) };
+                    unsafe {
+                        gl.vertex_attrib_divisor(
+                            vat.location,
+                            buffer_desc.step.instance_rate().unwrap_or(0),
+                        )
+                    };
                 }
             }
             C::UnsetVertexAttribute(location) => {
@@ -1361,7 +1416,9 @@ impl super::Queue {
                 ref buffer,
                 ref buffer_desc,
             } => {
-                unsafe { gl.vertex_binding_divisor(index, buffer_desc.step as u32) };
+                unsafe {
+                    gl.vertex_binding_divisor(index, buffer_desc.step.instance_rate().unwrap_or(0))
+                };
                 unsafe {
                     gl.bind_vertex_buffer(
                         index,
@@ -1402,6 +1459,16 @@ impl super::Queue {
                     unsafe { gl.disable(glow::SAMPLE_ALPHA_TO_COVERAGE) };
                 }
             }
+            C::SetLogicOp(logic_op) => {
+                if let Some(opcode) = logic_op {
+                    // `Features::LOGIC_OP` is only exposed if `glLogicOp` was loaded.
+                    let gl_logic_op = self.shared.logic_op.unwrap();
+                    unsafe { gl.enable(glow::COLOR_LOGIC_OP) };
+                    unsafe { gl_logic_op(opcode) };
+                } else {
+                    unsafe { gl.disable(glow::COLOR_LOGIC_OP) };
+                }
+            }
             C::SetProgram(program) => {
                 unsafe { gl.use_program(Some(program)) };
             }
@@ -1425,6 +1492,20 @@ impl super::Queue {
                 if self.features.contains(wgt::Features::POLYGON_MODE_LINE) {
                     unsafe { gl.polygon_mode(glow::FRONT_AND_BACK, state.polygon_mode) };
                 }
+                if self.features.contains(wgt::Features::WIDE_LINES) {
+                    unsafe { gl.line_width(state.line_width) };
+                }
+                if self
+                    .shared
+                    .private_caps
+                    .contains(PrivateCapabilities::PRIMITIVE_RESTART_FIXED_INDEX)
+                {
+                    if state.primitive_restart {
+                        unsafe { gl.enable(glow::PRIMITIVE_RESTART_FIXED_INDEX) };
+                    } else {
+                        unsafe { gl.disable(glow::PRIMITIVE_RESTART_FIXED_INDEX) };
+                    }
+                }
             }
             C::SetBlendConstant(c) => {
                 unsafe { gl.blend_color(c[0], c[1], c[2], c[3]) };
@@ -1898,13 +1979,42 @@ impl crate::Queue for super::Queue {
         &self,
         surface: &super::Surface,
         texture: super::Texture,
+        damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
-        unsafe { surface.present(texture, &self.shared.context) }
+        unsafe { surface.present(texture, &self.shared.context, damage) }
     }
 
     unsafe fn get_timestamp_period(&self) -> f32 {
         1.0
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        _buffer_bindings: &[crate::SparseBufferBinding<super::Buffer, super::MemoryHeap>],
+        _texture_bindings: &[crate::SparseTextureBinding<super::Texture, super::MemoryHeap>],
+    ) -> Result<(), crate::DeviceError> {
+        unreachable!()
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        _fence: &super::Fence,
+        _value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        unreachable!("GLES devices only have a single queue")
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        _fence: &super::Fence,
+        _value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        unreachable!("GLES fences can't be shared")
+    }
 }
 
 #[cfg(send_sync)]
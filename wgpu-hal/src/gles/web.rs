@@ -29,6 +29,7 @@ impl AdapterContext {
 #[derive(Debug)]
 pub struct Instance {
     options: wgt::GlBackendOptions,
+    toggles: wgt::TogglesDescriptor,
 }
 
 impl Instance {
@@ -118,6 +119,7 @@ impl crate::Instance for Instance {
         profiling::scope!("Init OpenGL (WebGL) Backend");
         Ok(Instance {
             options: desc.backend_options.gl.clone(),
+            toggles: desc.toggles,
         })
     }
 
@@ -134,7 +136,10 @@ impl crate::Instance for Instance {
                         glow_context: gl,
                         webgl2_context: surface_hint.webgl2_context.clone(),
                     },
+                    // WebGL has no logic operations.
+                    None,
                     self.options.clone(),
+                    self.toggles,
                 )
             }
             .into_iter()
@@ -227,6 +232,7 @@ impl Surface {
         &self,
         _suf_texture: super::Texture,
         context: &AdapterContext,
+        _damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
         let gl = &context.glow_context;
         let swapchain = self.swapchain.read();
@@ -450,4 +456,17 @@ impl crate::Surface for Surface {
     }
 
     unsafe fn discard_texture(&self, _texture: super::Texture) {}
+
+    unsafe fn set_target_present_time(&self, _time: wgt::PresentationTimestamp) {}
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        Ok(Vec::new())
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        // The browser always composites canvases with the rest of the page.
+        wgt::PresentationPath::Composed
+    }
 }
@@ -178,7 +178,9 @@ unsafe impl Sync for Inner {}
 
 pub struct Instance {
     srgb_capable: bool,
+    logic_op: Option<super::LogicOpFn>,
     options: wgt::GlBackendOptions,
+    toggles: wgt::TogglesDescriptor,
     inner: Arc<Mutex<Inner>>,
 }
 
@@ -504,6 +506,7 @@ impl crate::Instance for Instance {
         let mut gl = unsafe {
             glow::Context::from_loader_function(|name| load_gl_func(name, Some(opengl_module)))
         };
+        let logic_op = super::load_logic_op(|name| load_gl_func(name, Some(opengl_module)));
 
         let extra = Wgl::load_with(|name| load_gl_func(name, None));
         let extensions = get_extensions(&extra, dc);
@@ -544,7 +547,9 @@ impl crate::Instance for Instance {
                 context: Some(context),
             })),
             options: desc.backend_options.gl.clone(),
+            toggles: desc.toggles,
             srgb_capable,
+            logic_op,
         })
     }
 
@@ -579,7 +584,9 @@ impl crate::Instance for Instance {
                 AdapterContext {
                     inner: self.inner.clone(),
                 },
+                self.logic_op,
                 self.options.clone(),
+                self.toggles,
             )
         }
         .into_iter()
@@ -598,9 +605,10 @@ impl super::Adapter {
     /// - The underlying OpenGL ES context must be current when dropping this adapter and when
     ///   dropping any objects returned from this adapter.
     pub unsafe fn new_external(
-        fun: impl FnMut(&str) -> *const c_void,
+        mut fun: impl FnMut(&str) -> *const c_void,
         options: wgt::GlBackendOptions,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
+        let logic_op = super::load_logic_op(&mut fun);
         let context = unsafe { glow::Context::from_loader_function(fun) };
         unsafe {
             Self::expose(
@@ -611,7 +619,9 @@ impl super::Adapter {
                         context: None,
                     })),
                 },
+                logic_op,
                 options,
+                wgt::TogglesDescriptor::default(),
             )
         }
     }
@@ -669,6 +679,7 @@ impl Surface {
         &self,
         _suf_texture: super::Texture,
         context: &AdapterContext,
+        _damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
         let swapchain = self.swapchain.read();
         let sc = swapchain.as_ref().unwrap();
@@ -882,4 +893,16 @@ impl crate::Surface for Surface {
         }))
     }
     unsafe fn discard_texture(&self, _texture: super::Texture) {}
+
+    unsafe fn set_target_present_time(&self, _time: wgt::PresentationTimestamp) {}
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        Ok(Vec::new())
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        wgt::PresentationPath::Unknown
+    }
 }
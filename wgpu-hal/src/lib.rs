@@ -241,10 +241,13 @@
 extern crate alloc;
 extern crate wgpu_types as wgt;
 // Each of these backends needs `std` in some fashion; usually `std::thread` functions.
-#[cfg(any(dx12, gles_with_std, metal, vulkan))]
+#[cfg(any(cpu, dx12, gles_with_std, metal, vulkan))]
 #[macro_use]
 extern crate std;
 
+/// CPU API internals.
+#[cfg(cpu)]
+pub mod cpu;
 /// DirectX12 API internals.
 #[cfg(dx12)]
 pub mod dx12;
@@ -263,6 +266,8 @@ pub mod vulkan;
 
 pub mod auxil;
 pub mod api {
+    #[cfg(cpu)]
+    pub use super::cpu::Api as Cpu;
     #[cfg(dx12)]
     pub use super::dx12::Api as Dx12;
     #[cfg(gles)]
@@ -285,9 +290,10 @@ pub(crate) use dynamic::impl_dyn_resource;
 pub use dynamic::{
     DynAccelerationStructure, DynAcquiredSurfaceTexture, DynAdapter, DynBindGroup,
     DynBindGroupLayout, DynBuffer, DynCommandBuffer, DynCommandEncoder, DynComputePipeline,
-    DynDevice, DynExposedAdapter, DynFence, DynInstance, DynOpenDevice, DynPipelineCache,
-    DynPipelineLayout, DynQuerySet, DynQueue, DynRenderPipeline, DynResource, DynSampler,
-    DynShaderModule, DynSurface, DynSurfaceTexture, DynTexture, DynTextureView,
+    DynDevice, DynExposedAdapter, DynFence, DynInstance, DynMemoryHeap, DynOpenDevice,
+    DynPipelineCache, DynPipelineLayout, DynQuerySet, DynQueue, DynRayTracingPipeline,
+    DynRenderPipeline, DynResource, DynSampler, DynShaderModule, DynSurface, DynSurfaceTexture,
+    DynTexture, DynTextureView,
 };
 
 #[allow(unused)]
@@ -517,12 +523,17 @@ pub trait Api: Clone + fmt::Debug + Sized {
     /// finished.
     type Fence: DynFence;
 
+    /// A block of device memory that textures can be placed in with
+    /// [`Device::create_placed_texture`].
+    type MemoryHeap: DynMemoryHeap;
+
     type BindGroupLayout: DynBindGroupLayout;
     type BindGroup: DynBindGroup;
     type PipelineLayout: DynPipelineLayout;
     type ShaderModule: DynShaderModule;
     type RenderPipeline: DynRenderPipeline;
     type ComputePipeline: DynComputePipeline;
+    type RayTracingPipeline: DynRayTracingPipeline;
     type PipelineCache: DynPipelineCache;
 
     type AccelerationStructure: DynAccelerationStructure + 'static;
@@ -643,16 +654,63 @@ pub trait Surface: WasmNotSendSync {
     /// [`SurfaceTexture`]: Api::SurfaceTexture
     /// [`self.acquire_texture`]: Surface::acquire_texture
     unsafe fn discard_texture(&self, texture: <Self::A as Api>::SurfaceTexture);
+
+    /// Request that the next texture presented on `self` is not displayed
+    /// before `time`.
+    ///
+    /// Backends that can't schedule presents ignore this.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`]
+    ///   with [`wgt::Features::PRESENTATION_TIMING`] enabled.
+    unsafe fn set_target_present_time(&self, time: wgt::PresentationTimestamp);
+
+    /// Return feedback about the frames presented on `self` that the
+    /// presentation engine finished with since the previous call.
+    ///
+    /// The statistics are in present order. Backends may not report every
+    /// present.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`]
+    ///   with [`wgt::Features::PRESENTATION_TIMING`] enabled.
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, SurfaceError>;
+
+    /// Return how the frames presented on `self` currently reach the display.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath;
 }
 
 pub trait Adapter: WasmNotSendSync {
     type A: Api;
 
+    /// Create a device together with its main [`Queue`], and one additional queue
+    /// for each entry of `additional_queues`.
+    ///
+    /// Each additional queue is created for the given [`wgt::QueueType`]. When the
+    /// adapter has no spare hardware queue for it, the backend may back it with
+    /// the same hardware queue as another queue of the device, which serializes
+    /// their execution.
+    ///
+    /// # Safety
+    ///
+    /// - `additional_queues` must be empty unless `features` contains
+    ///   [`wgt::Features::EXPERIMENTAL_MULTI_QUEUE`].
+    ///
+    /// [`Queue`]: Api::Queue
     unsafe fn open(
         &self,
         features: wgt::Features,
         limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
     ) -> Result<OpenDevice<Self::A>, DeviceError>;
 
     /// Return the set of supported capabilities for a texture format.
@@ -673,6 +731,12 @@ pub trait Adapter: WasmNotSendSync {
     ///
     /// [`PresentationTimestamp`]: wgt::PresentationTimestamp
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp;
+
+    /// Returns the current memory usage and budget of the adapter, as reported by the
+    /// operating system.
+    ///
+    /// `None` means the backend or adapter can't report it.
+    fn memory_info(&self) -> Option<wgt::MemoryInfo>;
 }
 
 /// A connection to a GPU and a pool of resources to use with it.
@@ -859,6 +923,74 @@ pub trait Device: WasmNotSendSync {
     /// A hook for when a wgpu-core texture is created from a raw wgpu-hal texture.
     unsafe fn add_raw_texture(&self, texture: &<Self::A as Api>::Texture);
 
+    /// Creates a memory heap that textures can be placed in.
+    ///
+    /// Requires [`wgt::Features::TEXTURE_ALIASING`].
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &MemoryHeapDescriptor,
+    ) -> Result<<Self::A as Api>::MemoryHeap, DeviceError>;
+
+    /// # Safety
+    ///
+    /// - No texture placed in `heap` may still exist.
+    unsafe fn destroy_memory_heap(&self, heap: <Self::A as Api>::MemoryHeap);
+
+    /// Returns the size and alignment a texture described by `desc` needs
+    /// when placed in a memory heap.
+    ///
+    /// Requires [`wgt::Features::TEXTURE_ALIASING`].
+    unsafe fn texture_memory_requirements(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, DeviceError>;
+
+    /// Creates a texture in the memory of `heap`, starting at `offset`.
+    ///
+    /// Textures placed in overlapping parts of a heap alias each other. A
+    /// transition from `wgt::TextureUses::UNINITIALIZED` on a placed texture
+    /// waits for all previous writes to its memory, including those made
+    /// through other textures, and leaves its contents undefined. Placed
+    /// textures may also be transitioned back to `UNINITIALIZED` before their
+    /// memory is handed to another texture.
+    ///
+    /// # Safety
+    ///
+    /// - `offset` must be a multiple of the alignment returned by
+    ///   [`Device::texture_memory_requirements`] for `desc`, and the texture
+    ///   must fit in `heap` at `offset`.
+    /// - `heap` must outlive the texture.
+    unsafe fn create_placed_texture(
+        &self,
+        heap: &<Self::A as Api>::MemoryHeap,
+        offset: wgt::BufferAddress,
+        desc: &TextureDescriptor,
+    ) -> Result<<Self::A as Api>::Texture, DeviceError>;
+
+    /// Creates a sparse buffer, with no memory bound to it.
+    ///
+    /// `desc.size` is a multiple of [`wgt::SPARSE_PAGE_SIZE`]. Memory is bound to
+    /// the buffer with [`Queue::bind_sparse_memory`].
+    ///
+    /// Requires [`wgt::Features::SPARSE_BINDING`].
+    unsafe fn create_sparse_buffer(
+        &self,
+        desc: &BufferDescriptor,
+    ) -> Result<<Self::A as Api>::Buffer, DeviceError>;
+
+    /// Creates a sparse texture, with no memory bound to it.
+    ///
+    /// The texture is a 2D texture with a single sample, whose format has a
+    /// [`wgt::TextureFormat::sparse_tile_size`]. The width and height of every
+    /// mip level are multiples of the tile size. Memory is bound to the texture
+    /// with [`Queue::bind_sparse_memory`].
+    ///
+    /// Requires [`wgt::Features::SPARSE_BINDING`].
+    unsafe fn create_sparse_texture(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<<Self::A as Api>::Texture, DeviceError>;
+
     unsafe fn create_texture_view(
         &self,
         texture: &<Self::A as Api>::Texture,
@@ -942,6 +1074,29 @@ pub trait Device: WasmNotSendSync {
     ) -> Result<<Self::A as Api>::ComputePipeline, PipelineError>;
     unsafe fn destroy_compute_pipeline(&self, pipeline: <Self::A as Api>::ComputePipeline);
 
+    #[allow(clippy::type_complexity)]
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        desc: &RayTracingPipelineDescriptor<
+            <Self::A as Api>::PipelineLayout,
+            <Self::A as Api>::ShaderModule,
+            <Self::A as Api>::PipelineCache,
+        >,
+    ) -> Result<<Self::A as Api>::RayTracingPipeline, PipelineError>;
+    unsafe fn destroy_ray_tracing_pipeline(&self, pipeline: <Self::A as Api>::RayTracingPipeline);
+
+    /// Read back the opaque handles of `group_count` shader groups of `pipeline`,
+    /// starting at `first_group`.
+    ///
+    /// The result holds [`Alignments::ray_tracing_shader_group_handle_size`] bytes
+    /// per group, tightly packed.
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        pipeline: &<Self::A as Api>::RayTracingPipeline,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<Vec<u8>, DeviceError>;
+
     unsafe fn create_pipeline_cache(
         &self,
         desc: &PipelineCacheDescriptor<'_>,
@@ -990,6 +1145,37 @@ pub trait Device: WasmNotSendSync {
         timeout_ms: u32,
     ) -> Result<bool, DeviceError>;
 
+    /// Create a fence that can be exported with [`Device::export_fence`].
+    ///
+    /// Requires [`wgt::Features::EXPERIMENTAL_SHARED_FENCE`].
+    unsafe fn create_shared_fence(&self) -> Result<<Self::A as Api>::Fence, DeviceError>;
+
+    /// Export a handle to `fence`, for use by other APIs or processes.
+    ///
+    /// See [`wgt::SharedFenceHandle`] for who owns the returned handle.
+    ///
+    /// # Safety
+    ///
+    /// - `fence` must have been created by [`Device::create_shared_fence`] or
+    ///   [`Device::import_fence`].
+    unsafe fn export_fence(
+        &self,
+        fence: &<Self::A as Api>::Fence,
+    ) -> Result<wgt::SharedFenceHandle, DeviceError>;
+
+    /// Create a fence from a handle exported by another API or process.
+    ///
+    /// Requires [`wgt::Features::EXPERIMENTAL_SHARED_FENCE`].
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid handle of a kind this device's backend
+    ///   accepts on the current platform.
+    unsafe fn import_fence(
+        &self,
+        handle: wgt::SharedFenceHandle,
+    ) -> Result<<Self::A as Api>::Fence, DeviceError>;
+
     /// Start a graphics debugger capture.
     ///
     /// # Safety
@@ -1032,6 +1218,16 @@ pub trait Device: WasmNotSendSync {
         &self,
         acceleration_structure: <Self::A as Api>::AccelerationStructure,
     );
+    /// Checks whether an acceleration structure serialized by
+    /// [`CommandEncoder::copy_acceleration_structure_to_buffer`], possibly on another device or
+    /// driver, can be deserialized on this device.
+    ///
+    /// `data` must start with the serialized data. Returns the size of the acceleration
+    /// structure it deserializes to, or `None` if it isn't compatible.
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        data: &[u8],
+    ) -> Option<wgt::BufferAddress>;
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8>;
 
     fn get_internal_counters(&self) -> wgt::HalCounters;
@@ -1040,7 +1236,43 @@ pub trait Device: WasmNotSendSync {
         None
     }
 
+    /// Release memory that the device's allocators hold on to without using it, such as
+    /// memory blocks whose suballocations have all been freed.
+    ///
+    /// Returns the number of memory blocks that were released.
+    fn trim_memory(&self) -> u32 {
+        0
+    }
+
     fn check_if_oom(&self) -> Result<(), DeviceError>;
+
+    /// Returns why the device was lost, once an operation has failed with
+    /// [`DeviceError::Lost`].
+    ///
+    /// Backends that can't tell return [`wgt::DeviceLostReason::Unknown`].
+    fn lost_reason(&self) -> wgt::DeviceLostReason {
+        wgt::DeviceLostReason::Unknown
+    }
+
+    /// Returns a description of the fault that caused the device to be lost,
+    /// once an operation has failed with [`DeviceError::Lost`].
+    ///
+    /// This is whatever diagnostics the driver collected, such as the faulting
+    /// address or the last operations the GPU completed, formatted for humans.
+    /// Some backends only collect them when [`wgt::InstanceFlags::DEVICE_FAULT_DIAGNOSTICS`]
+    /// is set. Backends that have nothing to report return `None`.
+    fn fault_info(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the errors reported by GPU-based validation since the last call,
+    /// formatted for humans with the labels of the objects involved.
+    ///
+    /// Only backends which support [`wgt::InstanceFlags::GPU_BASED_VALIDATION`]
+    /// report anything, and only if it is set.
+    fn take_validation_messages(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 pub trait Queue: WasmNotSendSync {
@@ -1120,12 +1352,98 @@ pub trait Queue: WasmNotSendSync {
         surface_textures: &[&<Self::A as Api>::SurfaceTexture],
         signal_fence: (&mut <Self::A as Api>::Fence, FenceValue),
     ) -> Result<(), DeviceError>;
+    /// Present `texture` to `surface`.
+    ///
+    /// `damage` lists the regions of `texture` that changed since the previous
+    /// present, in texels with the origin at the top left corner. The rectangles
+    /// are within the texture's extent. An empty list means the whole texture
+    /// changed. Backends that can't pass damage to the presentation engine
+    /// ignore it.
     unsafe fn present(
         &self,
         surface: &<Self::A as Api>::Surface,
         texture: <Self::A as Api>::SurfaceTexture,
+        damage: &[wgt::Rect],
     ) -> Result<(), SurfaceError>;
     unsafe fn get_timestamp_period(&self) -> f32;
+    /// Sample a GPU timestamp and a CPU timestamp at the same time, to relate
+    /// timestamp query results to CPU time.
+    ///
+    /// Returns `None` if the backend or device can't sample both clocks.
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration>;
+
+    /// Bind memory to, or unbind memory from, parts of sparse buffers and
+    /// textures.
+    ///
+    /// The bindings take effect after every command buffer previously
+    /// submitted to this queue has completed, and before any command buffer
+    /// submitted afterwards starts.
+    ///
+    /// # Safety
+    ///
+    /// - Every buffer and texture must have been created with
+    ///   [`Device::create_sparse_buffer`] or [`Device::create_sparse_texture`].
+    ///
+    /// - The bound ranges and regions must be within their resources and
+    ///   aligned as described by [`SparseBufferBinding`] and
+    ///   [`SparseTextureBinding`], and the memory must be within its heap.
+    ///
+    /// - Every heap must remain alive until the resources it is bound to are
+    ///   destroyed, or the memory has been unbound and that has completed.
+    #[allow(clippy::type_complexity)]
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[SparseBufferBinding<
+            <Self::A as Api>::Buffer,
+            <Self::A as Api>::MemoryHeap,
+        >],
+        texture_bindings: &[SparseTextureBinding<
+            <Self::A as Api>::Texture,
+            <Self::A as Api>::MemoryHeap,
+        >],
+    ) -> Result<(), DeviceError>;
+
+    /// Make the GPU wait until `fence` reaches `value` before executing any
+    /// command buffer submitted to this queue after this call.
+    ///
+    /// This is how work on one queue is ordered after work on another queue
+    /// of the same device. Once the wait is satisfied, all results produced by
+    /// the submission that signalled `value` are visible to this queue.
+    ///
+    /// # Safety
+    ///
+    /// - `fence` must have been created by the [`Device`][d] associated with
+    ///   this queue, and must remain alive until the wait is satisfied.
+    ///
+    /// - A call to [`submit`] signalling `fence` with a value greater than or
+    ///   equal to `value` must already have been made, on another queue.
+    ///
+    /// [d]: Api::Device
+    /// [`submit`]: Queue::submit
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &<Self::A as Api>::Fence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError>;
+
+    /// Make the GPU set `fence` to `value` once the command buffers passed to
+    /// the next call to [`submit`] on this queue have completed.
+    ///
+    /// # Safety
+    ///
+    /// - `fence` must have been created by [`Device::create_shared_fence`] or
+    ///   [`Device::import_fence`] on the [`Device`][d] associated with this
+    ///   queue, and must remain alive until the next submission has completed.
+    ///
+    /// - A call to [`submit`] must follow.
+    ///
+    /// [d]: Api::Device
+    /// [`submit`]: Queue::submit
+    unsafe fn signal_fence(
+        &self,
+        fence: &<Self::A as Api>::Fence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError>;
 }
 
 /// Encoder and allocation pool for `CommandBuffer`s.
@@ -1287,6 +1605,19 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
     ) where
         T: Iterator<Item = TextureCopy>;
 
+    /// Resolve regions of a multisampled color texture into a single-sampled one.
+    /// Each region covers a single array layer, at the same origin in `src` and `dst`.
+    /// Note: `src` current usage has to be `wgt::TextureUses::COPY_SRC`,
+    /// and `dst` current usage has to be `wgt::TextureUses::COPY_DST`.
+    /// Note: Metal resolves the whole subresources of the regions.
+    unsafe fn resolve_texture<T>(
+        &mut self,
+        src: &<Self::A as Api>::Texture,
+        dst: &<Self::A as Api>::Texture,
+        regions: T,
+    ) where
+        T: Iterator<Item = TextureCopy>;
+
     /// Copy from buffer to texture.
     /// Works with a single array layer.
     /// Note: `dst` current usage has to be `wgt::TextureUses::COPY_DST`.
@@ -1317,6 +1648,40 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
         dst: &<Self::A as Api>::AccelerationStructure,
         copy: wgt::AccelerationStructureCopy,
     );
+
+    /// Serializes `src` into `dst`, starting at `dst_offset`.
+    ///
+    /// The serialized size can be read with
+    /// [`CommandEncoder::read_acceleration_structure_serialized_size`].
+    ///
+    /// # Safety
+    ///
+    /// - `dst` must have been created with [`wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY`],
+    ///   and be in that state.
+    /// - `dst_offset` must be a multiple of 256.
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        src: &<Self::A as Api>::AccelerationStructure,
+        dst: &<Self::A as Api>::Buffer,
+        dst_offset: wgt::BufferAddress,
+    );
+
+    /// Deserializes the acceleration structure serialized into `src` at `src_offset` into `dst`.
+    ///
+    /// # Safety
+    ///
+    /// - `src` must have been created with
+    ///   [`wgt::BufferUses::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT`], and be in that state.
+    /// - `src_offset` must be a multiple of 256.
+    /// - The serialized data must be compatible with the device, see
+    ///   [`Device::get_deserialized_acceleration_structure_size`], and `dst` must be at least
+    ///   as large as the size returned by it.
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        src: &<Self::A as Api>::Buffer,
+        src_offset: wgt::BufferAddress,
+        dst: &<Self::A as Api>::AccelerationStructure,
+    );
     // pass common
 
     /// Sets the bind group at `index` to `group`.
@@ -1423,6 +1788,18 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
     /// - All prior calls to [`begin_compute_pass`] on this [`CommandEncoder`] must have been followed
     ///   by a call to [`end_compute_pass`].
     ///
+    /// - If `desc.flags` contains [`wgt::RenderPassFlags::RESUMING`], the
+    ///   [`end_render_pass`] of a pass with [`wgt::RenderPassFlags::SUSPENDING`]
+    ///   and the same attachments and operations must come right before this
+    ///   call, either on this [`CommandEncoder`] or at the end of the previous
+    ///   command buffer of the same [`Queue::submit`] call.
+    ///
+    /// A backend that supports [`wgt::Features::RENDER_PASS_SUSPEND_RESUME`]
+    /// continues a suspended pass when it is resumed. Otherwise, it ends the
+    /// pass and starts it again, performing
+    /// [`AttachmentOps::with_pass_flags`] instead of the given operations and
+    /// skipping resolves when [`RenderPassDescriptor::resolves`] is false.
+    ///
     /// [`begin_render_pass`]: CommandEncoder::begin_render_pass
     /// [`begin_compute_pass`]: CommandEncoder::begin_compute_pass
     /// [`end_render_pass`]: CommandEncoder::end_render_pass
@@ -1443,6 +1820,52 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
     /// [`end_render_pass`]: CommandEncoder::end_render_pass
     unsafe fn end_render_pass(&mut self);
 
+    /// Create an encoder that records a part of the current render pass.
+    ///
+    /// The returned encoder is in the "recording" state, inside the render
+    /// pass, with no bindings and with the viewport and scissor rectangle
+    /// covering the render area. It can record render pass commands on another
+    /// thread, while this encoder creates more of them. The parts are executed
+    /// in the order they were created in, by [`execute_parallel_render_encoders`].
+    ///
+    /// Backends without [`wgt::Features::PARALLEL_RENDER_ENCODING`] return
+    /// [`DeviceError::Unexpected`].
+    ///
+    /// # Safety
+    ///
+    /// - The current render pass must have been begun with
+    ///   [`RenderPassDescriptor::parallel`] set. This encoder must not record
+    ///   any commands in it.
+    ///
+    /// - The returned encoder may only record the commands of a render pass,
+    ///   and must be passed to [`execute_parallel_render_encoders`].
+    ///
+    /// [`execute_parallel_render_encoders`]: CommandEncoder::execute_parallel_render_encoders
+    unsafe fn create_parallel_render_encoder(
+        &mut self,
+    ) -> Result<<Self::A as Api>::CommandEncoder, DeviceError>;
+
+    /// Execute the parts of the current render pass recorded by `encoders`.
+    ///
+    /// The encoders are kept alive until [`reset_all`] is called with the
+    /// command buffer they were executed in, or it is discarded.
+    ///
+    /// # Safety
+    ///
+    /// - `encoders` must be all the encoders created by
+    ///   [`create_parallel_render_encoder`] in the current render pass, in the
+    ///   order they were created in.
+    ///
+    /// - This must be called once, right before [`end_render_pass`].
+    ///
+    /// [`create_parallel_render_encoder`]: CommandEncoder::create_parallel_render_encoder
+    /// [`end_render_pass`]: CommandEncoder::end_render_pass
+    /// [`reset_all`]: CommandEncoder::reset_all
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        encoders: Vec<<Self::A as Api>::CommandEncoder>,
+    ) -> Result<(), DeviceError>;
+
     unsafe fn set_render_pipeline(&mut self, pipeline: &<Self::A as Api>::RenderPipeline);
 
     unsafe fn set_index_buffer<'a>(
@@ -1456,9 +1879,39 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
         binding: BufferBinding<'a, <Self::A as Api>::Buffer>,
     );
     unsafe fn set_viewport(&mut self, rect: &Rect<f32>, depth_range: Range<f32>);
+    /// Sets the viewport at `index`, leaving the other viewports unchanged.
+    ///
+    /// Only called if [`wgt::Features::MULTI_VIEWPORT`] is enabled, with `index` less
+    /// than [`wgt::MAX_VIEWPORTS`]. Every viewport covers the whole render area at the
+    /// start of a render pass.
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &Rect<f32>,
+        depth_range: Range<f32>,
+    );
     unsafe fn set_scissor_rect(&mut self, rect: &Rect<u32>);
     unsafe fn set_stencil_reference(&mut self, value: u32);
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]);
+    /// Sets the shading rate for the following draws.
+    ///
+    /// Only called if [`wgt::Features::VARIABLE_RATE_SHADING`] is enabled. Backends clamp
+    /// rates the device doesn't support.
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate);
+    /// Begins a block of draws that are skipped if the `u64` at `offset` in `buffer`
+    /// is zero, until [`end_conditional_rendering`] is called.
+    ///
+    /// Only called inside a render pass if [`wgt::Features::CONDITIONAL_RENDERING`] is
+    /// enabled, with `buffer` in the [`wgt::BufferUses::INDIRECT`] state. Blocks are
+    /// never nested, and are ended before the render pass.
+    ///
+    /// [`end_conditional_rendering`]: CommandEncoder::end_conditional_rendering
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &<Self::A as Api>::Buffer,
+        offset: wgt::BufferAddress,
+    );
+    unsafe fn end_conditional_rendering(&mut self);
 
     unsafe fn draw(
         &mut self,
@@ -1572,6 +2025,23 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
         offset: wgt::BufferAddress,
     );
 
+    /// Bind a ray tracing pipeline for subsequent [`trace_rays`] calls.
+    ///
+    /// Ray tracing pipelines are bound inside a compute pass, and share its bind
+    /// group and push constant state with compute pipelines. Binding a compute
+    /// pipeline afterwards switches back to dispatching compute work.
+    ///
+    /// [`trace_rays`]: CommandEncoder::trace_rays
+    unsafe fn set_ray_tracing_pipeline(&mut self, pipeline: &<Self::A as Api>::RayTracingPipeline);
+
+    /// Launch a `count[0]` by `count[1]` by `count[2]` grid of ray generation
+    /// invocations, using the shader groups described by `tables`.
+    unsafe fn trace_rays(
+        &mut self,
+        tables: &ShaderBindingTables<<Self::A as Api>::Buffer>,
+        count: [u32; 3],
+    );
+
     /// To get the required sizes for the buffer allocations use `get_acceleration_structure_build_sizes` per descriptor
     /// All buffers must be synchronized externally
     /// All buffer regions, which are written to may only be passed once per function call,
@@ -1603,6 +2073,15 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
         acceleration_structure: &<Self::A as Api>::AccelerationStructure,
         buf: &<Self::A as Api>::Buffer,
     );
+    /// Writes the size `acceleration_structure` takes up once serialized to the start of `buf`,
+    /// as a `u64`. Some backends write more information after it, so `buf` must be at least
+    /// 16 bytes large. `acceleration_structure` must have been created with
+    /// [`AccelerationStructureDescriptor::allow_serialization`].
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        acceleration_structure: &<Self::A as Api>::AccelerationStructure,
+        buf: &<Self::A as Api>::Buffer,
+    );
 }
 
 bitflags!(
@@ -1732,7 +2211,8 @@ impl From<wgt::TextureFormat> for FormatAspects {
             wgt::TextureFormat::Depth32FloatStencil8 | wgt::TextureFormat::Depth24PlusStencil8 => {
                 Self::DEPTH_STENCIL
             }
-            wgt::TextureFormat::NV12 => Self::PLANE_0 | Self::PLANE_1,
+            wgt::TextureFormat::NV12 | wgt::TextureFormat::P010 => Self::PLANE_0 | Self::PLANE_1,
+            wgt::TextureFormat::I420 => Self::PLANE_0 | Self::PLANE_1 | Self::PLANE_2,
             _ => Self::COLOR,
         }
     }
@@ -1743,6 +2223,9 @@ bitflags!(
     pub struct MemoryFlags: u32 {
         const TRANSIENT = 1 << 0;
         const PREFER_COHERENT = 1 << 1;
+        /// The texture is only used as a render pass attachment whose contents are never
+        /// loaded or stored, so the backend may avoid backing it with memory.
+        const TRANSIENT_ATTACHMENT = 1 << 2;
     }
 );
 
@@ -1756,12 +2239,30 @@ bitflags!(
     }
 );
 
+impl AttachmentOps {
+    /// Returns the operations to perform when a pass with `flags` is ended and
+    /// started again in between, rather than kept open: a suspending pass
+    /// stores the attachment, and a resuming pass loads it.
+    pub fn with_pass_flags(self, flags: wgt::RenderPassFlags) -> Self {
+        let mut ops = self;
+        if flags.contains(wgt::RenderPassFlags::SUSPENDING) {
+            ops |= Self::STORE;
+        }
+        if flags.contains(wgt::RenderPassFlags::RESUMING) {
+            ops |= Self::LOAD;
+        }
+        ops
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct InstanceDescriptor<'a> {
     pub name: &'a str,
     pub flags: wgt::InstanceFlags,
     pub memory_budget_thresholds: wgt::MemoryBudgetThresholds,
     pub backend_options: wgt::BackendOptions,
+    /// Adapter toggles to force on or off when exposing adapters.
+    pub toggles: wgt::TogglesDescriptor,
 }
 
 #[derive(Clone, Debug)]
@@ -1795,6 +2296,16 @@ pub struct Alignments {
 
     /// What the scratch buffer for building an acceleration structure must be aligned to
     pub ray_tracing_scratch_buffer_alignment: u32,
+
+    /// The size of a ray tracing shader group handle, or zero if ray tracing
+    /// pipelines are unsupported.
+    pub ray_tracing_shader_group_handle_size: u32,
+
+    /// What the stride of each shader binding table record must be aligned to.
+    pub ray_tracing_shader_group_handle_alignment: u32,
+
+    /// What the start of each shader binding table region must be aligned to.
+    pub ray_tracing_shader_group_base_alignment: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -1810,6 +2321,8 @@ pub struct ExposedAdapter<A: Api> {
     pub info: wgt::AdapterInfo,
     pub features: wgt::Features,
     pub capabilities: Capabilities,
+    /// The adapter toggles which apply to the adapter and its devices.
+    pub toggles: wgt::Toggles,
 }
 
 /// Describes information about what a `Surface`'s presentation capabilities are.
@@ -1847,6 +2360,12 @@ pub struct SurfaceCapabilities {
     ///
     /// Must be at least one.
     pub composite_alpha_modes: Vec<wgt::CompositeAlphaMode>,
+
+    /// List of supported color spaces.
+    ///
+    /// Must contain at least `wgt::SurfaceColorSpace::Srgb`, and never
+    /// contains `wgt::SurfaceColorSpace::Auto`.
+    pub color_spaces: Vec<wgt::SurfaceColorSpace>,
 }
 
 #[derive(Debug)]
@@ -1862,6 +2381,9 @@ pub struct AcquiredSurfaceTexture<A: Api> {
 pub struct OpenDevice<A: Api> {
     pub device: A::Device,
     pub queue: A::Queue,
+    /// The queues requested through the `additional_queues` argument of
+    /// [`Adapter::open`], in the same order.
+    pub additional_queues: Vec<A::Queue>,
 }
 
 #[derive(Clone, Debug)]
@@ -1878,6 +2400,12 @@ pub struct BufferDescriptor<'a> {
     pub memory_flags: MemoryFlags,
 }
 
+#[derive(Clone, Debug)]
+pub struct MemoryHeapDescriptor<'a> {
+    pub label: Label<'a>,
+    pub size: wgt::BufferAddress,
+}
+
 #[derive(Clone, Debug)]
 pub struct TextureDescriptor<'a> {
     pub label: Label<'a>,
@@ -2164,6 +2692,9 @@ pub struct BindGroupDescriptor<
 #[derive(Clone, Debug)]
 pub struct CommandEncoderDescriptor<'a, Q: DynQueue + ?Sized> {
     pub label: Label<'a>,
+    /// The queue that the encoder's command buffers will be submitted to.
+    ///
+    /// Command buffers must not be submitted to any other queue.
     pub queue: &'a Q,
 }
 
@@ -2217,6 +2748,9 @@ pub struct ShaderModuleDescriptor<'a> {
     ///
     /// [src]: wgt::ShaderRuntimeChecks
     pub runtime_checks: wgt::ShaderRuntimeChecks,
+
+    /// How much effort the backend compiler should spend optimizing the module.
+    pub optimization: wgt::ShaderOptimization,
 }
 
 #[derive(Debug, Clone)]
@@ -2270,6 +2804,53 @@ pub struct ComputePipelineDescriptor<
     pub cache: Option<&'a Pc>,
 }
 
+/// A shader stage of a ray tracing pipeline.
+#[derive(Clone, Debug)]
+pub struct RayTracingStage<'a, M: DynShaderModule + ?Sized> {
+    /// Which ray tracing stage this is. Exactly one bit of
+    /// [`wgt::ShaderStages::RAY_TRACING`] must be set.
+    pub stage: wgt::ShaderStages,
+    /// The compiled shader and its entry point.
+    pub programmable: ProgrammableStage<'a, M>,
+}
+
+/// Describes a ray tracing pipeline.
+#[derive(Clone, Debug)]
+pub struct RayTracingPipelineDescriptor<
+    'a,
+    Pl: DynPipelineLayout + ?Sized,
+    M: DynShaderModule + ?Sized,
+    Pc: DynPipelineCache + ?Sized,
+> {
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    pub layout: &'a Pl,
+    /// The shader stages of the pipeline, indexed by [`Self::groups`].
+    pub stages: &'a [RayTracingStage<'a, M>],
+    /// The shader groups of the pipeline, in shader binding table order.
+    pub groups: &'a [wgt::RayTracingShaderGroup],
+    /// The maximum depth of `traceRay` calls from within the pipeline.
+    pub max_recursion_depth: u32,
+    /// The cache which will be used and filled when compiling this pipeline
+    pub cache: Option<&'a Pc>,
+}
+
+/// One region of a shader binding table, as consumed by [`CommandEncoder::trace_rays`].
+#[derive(Debug)]
+pub struct ShaderBindingTable<'a, B: DynBuffer + ?Sized> {
+    pub buffer: &'a B,
+    pub region: wgt::ShaderBindingTableRegion,
+}
+
+/// The shader binding tables used by a [`CommandEncoder::trace_rays`] call.
+#[derive(Debug)]
+pub struct ShaderBindingTables<'a, B: DynBuffer + ?Sized> {
+    pub ray_generation: ShaderBindingTable<'a, B>,
+    pub miss: Option<ShaderBindingTable<'a, B>>,
+    pub hit: Option<ShaderBindingTable<'a, B>>,
+    pub callable: Option<ShaderBindingTable<'a, B>>,
+}
+
 pub struct PipelineCacheDescriptor<'a> {
     pub label: Label<'a>,
     pub data: Option<&'a [u8]>,
@@ -2354,6 +2935,13 @@ pub struct SurfaceConfiguration {
     pub present_mode: wgt::PresentMode,
     /// Alpha composition mode.
     pub composite_alpha_mode: wgt::CompositeAlphaMode,
+    /// Color space of the surface textures. Must be in
+    /// `SurfaceCapabilities::color_spaces`.
+    pub color_space: wgt::SurfaceColorSpace,
+    /// Luminance of the presented content, if known.
+    pub hdr_metadata: Option<wgt::HdrMetadata>,
+    /// Present without going through the compositor, if possible.
+    pub exclusive_fullscreen: bool,
     /// Format of the surface textures.
     pub format: wgt::TextureFormat,
     /// Requested texture extent. Must be in
@@ -2393,6 +2981,38 @@ pub struct TextureBarrier<'a, T: DynTexture + ?Sized> {
     pub usage: StateTransition<wgt::TextureUses>,
 }
 
+/// Binds a page-aligned range of a sparse buffer to memory, see
+/// [`Queue::bind_sparse_memory`].
+#[derive(Debug, Clone)]
+pub struct SparseBufferBinding<'a, B: DynBuffer + ?Sized, H: DynMemoryHeap + ?Sized> {
+    pub buffer: &'a B,
+    /// The range of the buffer. Both ends are multiples of
+    /// [`wgt::SPARSE_PAGE_SIZE`].
+    pub range: Range<wgt::BufferAddress>,
+    /// The heap and the offset in it that the range is bound to, or `None` to
+    /// unbind the range. The offset is a multiple of [`wgt::SPARSE_PAGE_SIZE`].
+    pub memory: Option<(&'a H, wgt::BufferAddress)>,
+}
+
+/// Binds a tile-aligned region of a sparse texture to memory, see
+/// [`Queue::bind_sparse_memory`].
+#[derive(Debug, Clone)]
+pub struct SparseTextureBinding<'a, T: DynTexture + ?Sized, H: DynMemoryHeap + ?Sized> {
+    pub texture: &'a T,
+    pub mip_level: u32,
+    pub array_layer: u32,
+    /// The origin of the region in texels, a multiple of the
+    /// [`wgt::TextureFormat::sparse_tile_size`].
+    pub origin: wgt::Origin3d,
+    /// The size of the region in texels. It is a multiple of the tile size,
+    /// unless the region reaches the edge of the mip level.
+    pub size: wgt::Extent3d,
+    /// The heap and the offset in it that the region is bound to, or `None` to
+    /// unbind the region. The tiles of the region take up consecutive pages of
+    /// [`wgt::SPARSE_PAGE_SIZE`] bytes, in row-major order.
+    pub memory: Option<(&'a H, wgt::BufferAddress)>,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct BufferCopy {
     pub src_offset: wgt::BufferAddress,
@@ -2454,6 +3074,18 @@ pub struct DepthStencilAttachment<'a, T: DynTextureView + ?Sized> {
     pub depth_ops: AttachmentOps,
     pub stencil_ops: AttachmentOps,
     pub clear_value: (f32, u32),
+    /// Requires [`wgt::Features::DEPTH_STENCIL_RESOLVE`].
+    pub resolve: Option<DepthStencilResolve<'a, T>>,
+}
+
+/// Resolve of a multisampled depth/stencil attachment into a single sampled one.
+///
+/// At least one of the aspects is resolved. Stencil always takes sample zero.
+#[derive(Clone, Debug)]
+pub struct DepthStencilResolve<'a, T: DynTextureView + ?Sized> {
+    pub target: Attachment<'a, T>,
+    pub depth_mode: Option<wgt::ResolveMode>,
+    pub resolve_stencil: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -2463,9 +3095,21 @@ pub struct PassTimestampWrites<'a, Q: DynQuerySet + ?Sized> {
     pub end_of_pass_write_index: Option<u32>,
 }
 
+/// A pipeline statistics query which records the work of a whole pass.
+///
+/// The query is begun at the start of the pass and ended at its end. The query
+/// set must be of type [`wgt::QueryType::PipelineStatistics`].
+#[derive(Clone, Debug)]
+pub struct PassPipelineStatisticsQuery<'a, Q: DynQuerySet + ?Sized> {
+    pub query_set: &'a Q,
+    pub query_index: u32,
+}
+
 #[derive(Clone, Debug)]
 pub struct RenderPassDescriptor<'a, Q: DynQuerySet + ?Sized, T: DynTextureView + ?Sized> {
     pub label: Label<'a>,
+    /// Size of the render area. `depth_or_array_layers` is the number of array
+    /// layers of every attachment, or 1 for multiview passes.
     pub extent: wgt::Extent3d,
     pub sample_count: u32,
     pub color_attachments: &'a [Option<ColorAttachment<'a, T>>],
@@ -2473,12 +3117,34 @@ pub struct RenderPassDescriptor<'a, Q: DynQuerySet + ?Sized, T: DynTextureView +
     pub multiview: Option<NonZeroU32>,
     pub timestamp_writes: Option<PassTimestampWrites<'a, Q>>,
     pub occlusion_query_set: Option<&'a Q>,
+    pub pipeline_statistics_query: Option<PassPipelineStatisticsQuery<'a, Q>>,
+    /// Whether the pass is suspended or resumes a suspended pass.
+    ///
+    /// All the parts of a suspended pass have the same attachments and
+    /// operations: the load operations apply to the first part, and the store
+    /// operations and resolves to the last. See
+    /// [`CommandEncoder::begin_render_pass`] for what backends may do in
+    /// between.
+    pub flags: wgt::RenderPassFlags,
+    /// Whether the commands of the pass are recorded by the encoders of
+    /// [`CommandEncoder::create_parallel_render_encoder`] instead of the
+    /// encoder the pass is begun on.
+    pub parallel: bool,
+}
+
+impl<Q: DynQuerySet + ?Sized, T: DynTextureView + ?Sized> RenderPassDescriptor<'_, Q, T> {
+    /// Returns true if the pass resolves its attachments when it ends, which a
+    /// suspending pass leaves to the last part of the pass.
+    pub fn resolves(&self) -> bool {
+        !self.flags.contains(wgt::RenderPassFlags::SUSPENDING)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct ComputePassDescriptor<'a, Q: DynQuerySet + ?Sized> {
     pub label: Label<'a>,
     pub timestamp_writes: Option<PassTimestampWrites<'a, Q>>,
+    pub pipeline_statistics_query: Option<PassPipelineStatisticsQuery<'a, Q>>,
 }
 
 #[test]
@@ -2493,6 +3159,8 @@ pub struct AccelerationStructureDescriptor<'a> {
     pub size: wgt::BufferAddress,
     pub format: AccelerationStructureFormat,
     pub allow_compaction: bool,
+    /// Whether the serialized size of the acceleration structure can be read.
+    pub allow_serialization: bool,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
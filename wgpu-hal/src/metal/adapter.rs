@@ -6,7 +6,7 @@ use objc::{class, msg_send, sel, sel_impl};
 use parking_lot::Mutex;
 use wgt::{AstcBlock, AstcChannel};
 
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 
 use super::TimestampQuerySupport;
 
@@ -46,12 +46,15 @@ impl crate::Adapter for super::Adapter {
         features: wgt::Features,
         _limits: &wgt::Limits,
         _memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        let queue = self
-            .shared
-            .device
-            .lock()
-            .new_command_queue_with_max_command_buffer_count(MAX_COMMAND_BUFFERS);
+        let new_queue = || {
+            self.shared
+                .device
+                .lock()
+                .new_command_queue_with_max_command_buffer_count(MAX_COMMAND_BUFFERS)
+        };
+        let queue = new_queue();
 
         // Acquiring the meaning of timestamp ticks is hard with Metal!
         // The only thing there is is a method correlating cpu & gpu timestamps (`device.sample_timestamps`).
@@ -84,11 +87,27 @@ impl crate::Adapter for super::Adapter {
                 shared: Arc::clone(&self.shared),
                 features,
                 counters: Default::default(),
+                command_buffer_error: Mutex::new(None),
+                validation_messages: Mutex::new(Vec::new()),
+                debug_printf: features
+                    .contains(wgt::Features::SHADER_DEBUG_PRINTF)
+                    .then(|| super::DebugPrintfBuffer::new(&self.shared.device.lock())),
             },
             queue: super::Queue {
                 raw: Arc::new(Mutex::new(queue)),
                 timestamp_period,
+                pending_signals: Mutex::new(Vec::new()),
             },
+            // Metal command queues are not tied to a kind of work, and the GPU
+            // schedules work from all of them in parallel.
+            additional_queues: additional_queues
+                .iter()
+                .map(|_| super::Queue {
+                    raw: Arc::new(Mutex::new(new_queue())),
+                    timestamp_period,
+                    pending_signals: Mutex::new(Vec::new()),
+                })
+                .collect(),
         })
     }
 
@@ -289,7 +308,7 @@ impl crate::Adapter for super::Adapter {
                 }
                 flags
             }
-            Tf::NV12 => return Tfc::empty(),
+            Tf::NV12 | Tf::P010 | Tf::I420 => return Tfc::empty(),
             Tf::Rgb9e5Ufloat => {
                 if pc.msaa_apple3 {
                     all_caps
@@ -368,6 +387,15 @@ impl crate::Adapter for super::Adapter {
         }
 
         let pc = &self.shared.private_caps;
+        let mut color_spaces = vec![wgt::SurfaceColorSpace::Srgb];
+        if pc.can_set_extended_dynamic_range {
+            color_spaces.push(wgt::SurfaceColorSpace::ExtendedSrgb);
+            color_spaces.push(wgt::SurfaceColorSpace::ExtendedSrgbLinear);
+            if pc.supports_hdr10_color_space {
+                color_spaces.push(wgt::SurfaceColorSpace::Hdr10);
+            }
+        }
+
         Some(crate::SurfaceCapabilities {
             formats,
             // We use this here to govern the maximum number of drawables + 1.
@@ -396,6 +424,7 @@ impl crate::Adapter for super::Adapter {
                 | wgt::TextureUses::STORAGE_READ_ONLY
                 | wgt::TextureUses::STORAGE_WRITE_ONLY
                 | wgt::TextureUses::STORAGE_READ_WRITE,
+            color_spaces,
         })
     }
 
@@ -404,6 +433,19 @@ impl crate::Adapter for super::Adapter {
 
         wgt::PresentationTimestamp(timestamp)
     }
+    fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        if !self.shared.private_caps.supports_memory_info {
+            return None;
+        }
+        let device = self.shared.device.lock();
+        Some(wgt::MemoryInfo {
+            heaps: vec![wgt::MemoryHeapInfo {
+                usage: device.current_allocated_size(),
+                budget: device.recommended_max_working_set_size(),
+                device_local: true,
+            }],
+        })
+    }
 }
 
 const RESOURCE_HEAP_SUPPORT: &[MTLFeatureSet] = &[
@@ -499,6 +541,12 @@ const LAYERED_RENDERING_SUPPORT: &[MTLFeatureSet] = &[
     MTLFeatureSet::macOS_GPUFamily2_v1,
 ];
 
+const VIEWPORT_ARRAY_SUPPORT: &[MTLFeatureSet] = &[
+    MTLFeatureSet::iOS_GPUFamily5_v1,
+    MTLFeatureSet::macOS_GPUFamily1_v3,
+    MTLFeatureSet::macOS_GPUFamily2_v1,
+];
+
 const FUNCTION_SPECIALIZATION_SUPPORT: &[MTLFeatureSet] = &[
     MTLFeatureSet::iOS_GPUFamily1_v3,
     MTLFeatureSet::tvOS_GPUFamily1_v2,
@@ -604,6 +652,14 @@ impl super::PrivateCapabilities {
             // `TimestampQuerySupport::INSIDE_WGPU_PASSES` emerges from the other flags.
         }
 
+        // Statistics are sampled at the boundaries of a pass, like its timestamps.
+        let supports_pipeline_statistics = timestamp_query_support
+            .contains(TimestampQuerySupport::STAGE_BOUNDARIES)
+            && device
+                .counter_sets()
+                .iter()
+                .any(|counter_set| counter_set.name() == "statistic");
+
         let argument_buffers = device.argument_buffers_support();
 
         Self {
@@ -656,6 +712,7 @@ impl super::PrivateCapabilities {
             low_power: !os_is_mac || device.is_low_power(),
             headless: os_is_mac && device.is_headless(),
             layered_rendering: Self::supports_any(device, LAYERED_RENDERING_SUPPORT),
+            viewport_arrays: Self::supports_any(device, VIEWPORT_ARRAY_SUPPORT),
             function_specialization: Self::supports_any(device, FUNCTION_SPECIALIZATION_SUPPORT),
             depth_clip_mode: Self::supports_any(device, DEPTH_CLIP_MODE),
             texture_cube_array: Self::supports_any(device, TEXTURE_CUBE_ARRAY_SUPPORT),
@@ -858,6 +915,12 @@ impl super::PrivateCapabilities {
             can_set_maximum_drawables_count: version.at_least((10, 14), (11, 2), os_is_mac),
             can_set_display_sync: version.at_least((10, 13), OS_NOT_SUPPORT, os_is_mac),
             can_set_next_drawable_timeout: version.at_least((10, 13), (11, 0), os_is_mac),
+            can_set_extended_dynamic_range: version.at_least((10, 11), (16, 0), os_is_mac),
+            // `kCGColorSpaceITUR_2100_PQ`
+            supports_hdr10_color_space: version.at_least((11, 0), (14, 0), os_is_mac),
+            // `MTLDrawable.presentedTime` needs macOS 10.15.4.
+            supports_presentation_timing: version.at_least((11, 0), (10, 3), os_is_mac),
+            supports_deferred_store_action: version.at_least((10, 13), (11, 0), os_is_mac),
             supports_arrays_of_textures: Self::supports_any(
                 device,
                 &[
@@ -883,6 +946,7 @@ impl super::PrivateCapabilities {
                 None
             },
             timestamp_query_support,
+            supports_pipeline_statistics,
             supports_simd_scoped_operations: family_check
                 && (device.supports_family(MTLGPUFamily::Metal3)
                     || device.supports_family(MTLGPUFamily::Mac2)
@@ -901,6 +965,33 @@ impl super::PrivateCapabilities {
                 && (device.supports_family(MTLGPUFamily::Apple7)
                     || device.supports_family(MTLGPUFamily::Mac2)),
             supports_shared_event: version.at_least((10, 14), (12, 0), os_is_mac),
+            // `recommendedMaxWorkingSetSize` and `currentAllocatedSize`.
+            supports_memory_info: os_is_xr || version.at_least((10, 13), (16, 0), os_is_mac),
+            // Memoryless render targets live only in tile memory, which only Apple GPUs have.
+            supports_memoryless_storage: family_check
+                && device.supports_family(MTLGPUFamily::Apple2),
+            supports_placement_heaps: version.at_least((10, 15), (13, 0), os_is_mac),
+            // Depth and stencil resolves with selectable depth filters.
+            supports_depth_resolve_filters: version.at_least((10, 14), (12, 0), os_is_mac)
+                && (os_is_mac || (family_check && device.supports_family(MTLGPUFamily::Apple3))),
+            // Reading color attachments in fragment shaders with `[[color(n)]]` needs tile
+            // memory, which only Apple GPUs have. Apple silicon Macs support it since macOS 11.
+            supports_framebuffer_fetch: family_check
+                && device.supports_family(MTLGPUFamily::Apple2)
+                && (os_is_xr || version.at_least((11, 0), (8, 0), os_is_mac)),
+            pipeline_cache_validation_key: {
+                // Metal doesn't report vendor or device IDs, so identify the device by
+                // name. Binary archives are tied to the OS version that compiled them.
+                let name = device.name().bytes().fold(0x811c_9dc5u32, |hash, byte| {
+                    (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+                });
+                let mut key = [0; 16];
+                key[0..4].copy_from_slice(&(version.major as u32).to_be_bytes());
+                key[4..8].copy_from_slice(&(version.minor as u32).to_be_bytes());
+                key[8..12].copy_from_slice(&(version.patch as u32).to_be_bytes());
+                key[12..16].copy_from_slice(&name.to_be_bytes());
+                key
+            },
         }
     }
 
@@ -925,10 +1016,36 @@ impl super::PrivateCapabilities {
             | F::CLEAR_TEXTURE
             | F::TEXTURE_FORMAT_16BIT_NORM
             | F::SHADER_F16
+            | F::SHADER_I8
+            | F::SHADER_I16
             | F::DEPTH32FLOAT_STENCIL8
-            | F::BGRA8UNORM_STORAGE;
+            | F::BGRA8UNORM_STORAGE
+            | F::EXPERIMENTAL_MULTI_QUEUE
+            | F::SHADER_DEBUG_PRINTF
+            | F::PARALLEL_RENDER_ENCODING;
 
         features.set(F::FLOAT32_FILTERABLE, self.supports_float_filtering);
+        features.set(F::PRESENTATION_TIMING, self.supports_presentation_timing);
+        features.set(F::EXPERIMENTAL_SHARED_FENCE, self.supports_shared_event);
+        features.set(F::TEXTURE_ALIASING, self.supports_placement_heaps);
+        features.set(
+            F::DEPTH_STENCIL_RESOLVE,
+            self.supports_depth_resolve_filters,
+        );
+        features.set(F::FRAMEBUFFER_FETCH, self.supports_framebuffer_fetch);
+        features.set(
+            F::RENDER_PASS_SUSPEND_RESUME,
+            self.supports_deferred_store_action,
+        );
+        features.set(
+            F::MULTI_VIEWPORT,
+            self.layered_rendering && self.viewport_arrays,
+        );
+        // Vertex pulling needs the base instance to step instance data
+        features.set(
+            F::VERTEX_INSTANCE_STEP_RATE,
+            self.base_vertex_first_instance_drawing,
+        );
         features.set(
             F::INDIRECT_FIRST_INSTANCE | F::MULTI_DRAW_INDIRECT,
             self.indirect_draw_dispatch,
@@ -943,6 +1060,10 @@ impl super::PrivateCapabilities {
             self.timestamp_query_support
                 .contains(TimestampQuerySupport::INSIDE_WGPU_PASSES),
         );
+        features.set(
+            F::PIPELINE_STATISTICS_QUERY,
+            self.supports_pipeline_statistics,
+        );
         features.set(
             F::DUAL_SOURCE_BLENDING,
             self.msl_version >= MTLLanguageVersion::V1_2 && self.dual_source_blending,
@@ -955,6 +1076,7 @@ impl super::PrivateCapabilities {
         features.set(F::TEXTURE_COMPRESSION_ETC2, self.format_eac_etc);
 
         features.set(F::DEPTH_CLIP_CONTROL, self.supports_depth_clip_control);
+        features.set(F::PIPELINE_CACHE, self.supports_binary_archives);
         features.set(
             F::SHADER_PRIMITIVE_INDEX,
             self.supports_shader_primitive_index,
@@ -1087,6 +1209,7 @@ impl super::PrivateCapabilities {
                 // > [Acceleration structures] are opaque objects that can be bound directly using
                 // buffer binding points or via argument buffers
                 max_acceleration_structures_per_shader_stage: 0,
+                max_ray_recursion_depth: 0,
             },
             alignments: crate::Alignments {
                 buffer_copy_offset: wgt::BufferSize::new(self.buffer_alignment).unwrap(),
@@ -1097,6 +1220,9 @@ impl super::PrivateCapabilities {
                 uniform_bounds_check_alignment: wgt::BufferSize::new(1).unwrap(),
                 raw_tlas_instance_size: 0,
                 ray_tracing_scratch_buffer_alignment: 0,
+                ray_tracing_shader_group_handle_size: 0,
+                ray_tracing_shader_group_handle_alignment: 0,
+                ray_tracing_shader_group_base_alignment: 0,
             },
             downlevel,
         }
@@ -1168,7 +1294,7 @@ impl super::PrivateCapabilities {
                     MTL::Depth32Float_Stencil8
                 }
             }
-            Tf::NV12 => unreachable!(),
+            Tf::NV12 | Tf::P010 | Tf::I420 => unreachable!(),
             Tf::Rgb9e5Ufloat => MTL::RGB9E5Float,
             Tf::Bc1RgbaUnorm => MTL::BC1_RGBA,
             Tf::Bc1RgbaUnormSrgb => MTL::BC1_RGBA_sRGB,
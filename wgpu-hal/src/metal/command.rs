@@ -2,13 +2,15 @@ use super::{AsNative, TimestampQuerySupport, conv};
 use crate::CommandEncoder as _;
 use alloc::{
     borrow::{Cow, ToOwned as _},
+    sync::Arc,
     vec::Vec,
 };
 use core::ops::Range;
 use metal::{
     MTLIndexType, MTLLoadAction, MTLPrimitiveType, MTLScissorRect, MTLSize, MTLStoreAction,
-    MTLViewport, MTLVisibilityResultMode, NSRange,
+    MTLViewport, MTLVisibilityResultMode, NSRange, NSUInteger, foreign_types::ForeignTypeRef as _,
 };
+use objc::{msg_send, runtime::Object};
 
 // has to match `Temp::binding_sizes`
 const WORD_SIZE: usize = 4;
@@ -18,6 +20,7 @@ impl Default for super::CommandState {
         Self {
             blit: None,
             render: None,
+            parallel_render: None,
             compute: None,
             raw_primitive_type: MTLPrimitiveType::Point,
             index: None,
@@ -28,13 +31,56 @@ impl Default for super::CommandState {
             work_group_memory_sizes: Vec::new(),
             push_constants: Vec::new(),
             pending_timer_queries: Vec::new(),
+            viewports: [MTLViewport {
+                originX: 0.0,
+                originY: 0.0,
+                width: 0.0,
+                height: 0.0,
+                znear: 0.0,
+                zfar: 1.0,
+            }; wgt::MAX_VIEWPORTS as usize],
+            viewport_count: 0,
+            scissor: MTLScissorRect {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            },
+            deferred_render: None,
+            suspended_render: None,
         }
     }
 }
 
 impl super::CommandEncoder {
+    /// Sets the viewports set so far in the pass, each with the pass scissor.
+    fn flush_viewports(&self) {
+        let encoder = self.state.render.as_ref().unwrap();
+        let count = self.state.viewport_count;
+        if count == 1 {
+            encoder.set_viewport(self.state.viewports[0]);
+            encoder.set_scissor_rect(self.state.scissor);
+            return;
+        }
+        let scissors = [self.state.scissor; wgt::MAX_VIEWPORTS as usize];
+        unsafe {
+            let raw = encoder.as_ptr() as *mut Object;
+            let () = msg_send![
+                raw,
+                setViewports: self.state.viewports.as_ptr()
+                count: count as NSUInteger
+            ];
+            let () = msg_send![
+                raw,
+                setScissorRects: scissors.as_ptr()
+                count: count as NSUInteger
+            ];
+        }
+    }
+
     fn enter_blit(&mut self) -> &metal::BlitCommandEncoderRef {
         if self.state.blit.is_none() {
+            self.end_suspended_render();
             debug_assert!(self.state.render.is_none() && self.state.compute.is_none());
             let cmd_buf = self.raw_cmd_buf.as_ref().unwrap();
 
@@ -137,9 +183,287 @@ impl super::CommandEncoder {
         }
     }
 
+    /// Ends the render encoder of a suspended pass that isn't continued on it,
+    /// storing the attachments for the pass resuming it to load them.
+    fn end_suspended_render(&mut self) {
+        if let Some((encoder, deferred)) = self.state.suspended_render.take() {
+            deferred.set_store_actions(&encoder, true);
+            encoder.end_encoding();
+        }
+    }
+
+    /// Begins a render encoder for `desc`.
+    ///
+    /// The store actions of a suspending pass are deferred if possible, to keep
+    /// the encoder open for the pass resuming it. Otherwise, the attachments
+    /// are stored for the resuming pass to load them.
+    fn begin_render_encoder(
+        &mut self,
+        desc: &crate::RenderPassDescriptor<super::QuerySet, super::TextureView>,
+    ) {
+        let defer = desc.flags.contains(wgt::RenderPassFlags::SUSPENDING)
+            && self.shared.private_caps.supports_deferred_store_action;
+        let mut deferred = super::DeferredRender {
+            color_store_actions: ArrayVec::new(),
+            depth_store_action: None,
+            stencil_store_action: None,
+            occlusion_query_buffer: desc.occlusion_query_set.map(|set| set.raw_buffer.clone()),
+            suspending: true,
+        };
+        // Returns the store action to begin with, keeping `action` for the end
+        // of the pass if it's deferred.
+        let store =
+            |actions: &mut ArrayVec<_, { crate::MAX_COLOR_ATTACHMENTS }>, index: usize, action| {
+                if defer {
+                    actions.push((index as NSUInteger, action));
+                    MTLStoreAction::Unknown
+                } else if desc.resolves() {
+                    action
+                } else {
+                    MTLStoreAction::Store
+                }
+            };
+        let store_single = |slot: &mut Option<_>, action| {
+            if defer {
+                *slot = Some(action);
+                MTLStoreAction::Unknown
+            } else if desc.resolves() {
+                action
+            } else {
+                MTLStoreAction::Store
+            }
+        };
+
+        objc::rc::autoreleasepool(|| {
+            let descriptor = metal::RenderPassDescriptor::new();
+
+            for (i, at) in desc.color_attachments.iter().enumerate() {
+                if let Some(at) = at.as_ref() {
+                    let at_descriptor = descriptor.color_attachments().object_at(i as u64).unwrap();
+                    at_descriptor.set_texture(Some(&at.target.view.raw));
+                    if let Some(depth_slice) = at.depth_slice {
+                        at_descriptor.set_depth_plane(depth_slice as u64);
+                    }
+                    if let Some(ref resolve) = at.resolve_target {
+                        //Note: the selection of levels and slices is already handled by `TextureView`
+                        at_descriptor.set_resolve_texture(Some(&resolve.view.raw));
+                    }
+                    let load_action = if at
+                        .ops
+                        .with_pass_flags(desc.flags)
+                        .contains(crate::AttachmentOps::LOAD)
+                    {
+                        MTLLoadAction::Load
+                    } else {
+                        at_descriptor.set_clear_color(conv::map_clear_color(&at.clear_value));
+                        MTLLoadAction::Clear
+                    };
+                    let store_action = conv::map_store_action(
+                        at.ops.contains(crate::AttachmentOps::STORE),
+                        at.resolve_target.is_some(),
+                    );
+                    at_descriptor.set_load_action(load_action);
+                    at_descriptor.set_store_action(store(
+                        &mut deferred.color_store_actions,
+                        i,
+                        store_action,
+                    ));
+                }
+            }
+
+            if let Some(ref at) = desc.depth_stencil_attachment {
+                if at.target.view.aspects.contains(crate::FormatAspects::DEPTH) {
+                    let at_descriptor = descriptor.depth_attachment().unwrap();
+                    at_descriptor.set_texture(Some(&at.target.view.raw));
+
+                    let load_action = if at
+                        .depth_ops
+                        .with_pass_flags(desc.flags)
+                        .contains(crate::AttachmentOps::LOAD)
+                    {
+                        MTLLoadAction::Load
+                    } else {
+                        at_descriptor.set_clear_depth(at.clear_value.0 as f64);
+                        MTLLoadAction::Clear
+                    };
+                    let depth_resolve = at
+                        .resolve
+                        .as_ref()
+                        .and_then(|resolve| Some((resolve, resolve.depth_mode?)));
+                    if let Some((resolve, mode)) = depth_resolve {
+                        at_descriptor.set_resolve_texture(Some(&resolve.target.view.raw));
+                        at_descriptor
+                            .set_depth_resolve_filter(conv::map_depth_resolve_filter(mode));
+                    }
+                    let store_action = conv::map_store_action(
+                        at.depth_ops.contains(crate::AttachmentOps::STORE),
+                        depth_resolve.is_some(),
+                    );
+                    at_descriptor.set_load_action(load_action);
+                    at_descriptor.set_store_action(store_single(
+                        &mut deferred.depth_store_action,
+                        store_action,
+                    ));
+                }
+                if at
+                    .target
+                    .view
+                    .aspects
+                    .contains(crate::FormatAspects::STENCIL)
+                {
+                    let at_descriptor = descriptor.stencil_attachment().unwrap();
+                    at_descriptor.set_texture(Some(&at.target.view.raw));
+
+                    let load_action = if at
+                        .stencil_ops
+                        .with_pass_flags(desc.flags)
+                        .contains(crate::AttachmentOps::LOAD)
+                    {
+                        MTLLoadAction::Load
+                    } else {
+                        at_descriptor.set_clear_stencil(at.clear_value.1);
+                        MTLLoadAction::Clear
+                    };
+                    // The default stencil resolve filter takes sample zero.
+                    let stencil_resolve = at
+                        .resolve
+                        .as_ref()
+                        .filter(|resolve| resolve.resolve_stencil);
+                    if let Some(resolve) = stencil_resolve {
+                        at_descriptor.set_resolve_texture(Some(&resolve.target.view.raw));
+                    }
+                    let store_action = conv::map_store_action(
+                        at.stencil_ops.contains(crate::AttachmentOps::STORE),
+                        stencil_resolve.is_some(),
+                    );
+                    at_descriptor.set_load_action(load_action);
+                    at_descriptor.set_store_action(store_single(
+                        &mut deferred.stencil_store_action,
+                        store_action,
+                    ));
+                }
+            }
+
+            let mut sba_index = 0;
+            let mut next_sba_descriptor = || {
+                let sba_descriptor = descriptor
+                    .sample_buffer_attachments()
+                    .object_at(sba_index)
+                    .unwrap();
+
+                sba_descriptor.set_end_of_vertex_sample_index(metal::COUNTER_DONT_SAMPLE);
+                sba_descriptor.set_start_of_fragment_sample_index(metal::COUNTER_DONT_SAMPLE);
+
+                sba_index += 1;
+                sba_descriptor
+            };
+
+            for (set, index) in self.state.pending_timer_queries.drain(..) {
+                let sba_descriptor = next_sba_descriptor();
+                sba_descriptor.set_sample_buffer(set.counter_sample_buffer.as_ref().unwrap());
+                sba_descriptor.set_start_of_vertex_sample_index(index as _);
+                sba_descriptor.set_end_of_fragment_sample_index(metal::COUNTER_DONT_SAMPLE);
+            }
+
+            if let Some(ref timestamp_writes) = desc.timestamp_writes {
+                let sba_descriptor = next_sba_descriptor();
+                sba_descriptor.set_sample_buffer(
+                    timestamp_writes
+                        .query_set
+                        .counter_sample_buffer
+                        .as_ref()
+                        .unwrap(),
+                );
+
+                sba_descriptor.set_start_of_vertex_sample_index(
+                    timestamp_writes
+                        .beginning_of_pass_write_index
+                        .map_or(metal::COUNTER_DONT_SAMPLE, |i| i as _),
+                );
+                sba_descriptor.set_end_of_fragment_sample_index(
+                    timestamp_writes
+                        .end_of_pass_write_index
+                        .map_or(metal::COUNTER_DONT_SAMPLE, |i| i as _),
+                );
+            }
+
+            if let Some(ref query) = desc.pipeline_statistics_query {
+                let sba_descriptor = next_sba_descriptor();
+                sba_descriptor
+                    .set_sample_buffer(query.query_set.counter_sample_buffer.as_ref().unwrap());
+                sba_descriptor.set_start_of_vertex_sample_index(query.query_index as u64 * 2);
+                sba_descriptor.set_end_of_fragment_sample_index(query.query_index as u64 * 2 + 1);
+            }
+
+            // Without multiview, attachments with several array layers are rendered
+            // to as a whole, with the vertex shader picking the layer.
+            if desc.multiview.is_none() && desc.extent.depth_or_array_layers > 1 {
+                let raw = descriptor.as_ptr() as *mut Object;
+                let () = unsafe {
+                    msg_send![
+                        raw,
+                        setRenderTargetArrayLength: desc.extent.depth_or_array_layers
+                            as NSUInteger
+                    ]
+                };
+            }
+
+            if let Some(occlusion_query_set) = desc.occlusion_query_set {
+                descriptor
+                    .set_visibility_result_buffer(Some(occlusion_query_set.raw_buffer.as_ref()))
+            }
+
+            let raw = self.raw_cmd_buf.as_ref().unwrap();
+            if desc.parallel {
+                let encoder = raw.new_parallel_render_command_encoder(descriptor);
+                if let Some(label) = desc.label {
+                    encoder.set_label(label);
+                }
+                self.state.parallel_render = Some(encoder.to_owned());
+            } else {
+                let encoder = raw.new_render_command_encoder(descriptor);
+                if let Some(label) = desc.label {
+                    encoder.set_label(label);
+                }
+                self.state.render = Some(encoder.to_owned());
+            }
+        });
+
+        if defer {
+            self.state.deferred_render = Some(deferred);
+        }
+    }
+
     fn begin_pass(&mut self) {
         self.state.reset();
         self.leave_blit();
+        self.end_suspended_render();
+    }
+}
+
+impl super::DeferredRender {
+    /// Sets the store actions of `encoder`, or [`MTLStoreAction::Store`] for
+    /// every attachment if `store_all`.
+    fn set_store_actions(&self, encoder: &metal::RenderCommandEncoderRef, store_all: bool) {
+        let action = |action| {
+            if store_all {
+                MTLStoreAction::Store
+            } else {
+                action
+            }
+        };
+        let raw = encoder.as_ptr() as *mut Object;
+        unsafe {
+            for &(index, store_action) in &self.color_store_actions {
+                let () = msg_send![raw, setColorStoreAction: action(store_action) atIndex: index];
+            }
+            if let Some(store_action) = self.depth_store_action {
+                let () = msg_send![raw, setDepthStoreAction: action(store_action)];
+            }
+            if let Some(store_action) = self.stencil_store_action {
+                let () = msg_send![raw, setStencilStoreAction: action(store_action)];
+            }
+        }
     }
 }
 
@@ -212,11 +536,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
 
     unsafe fn discard_encoding(&mut self) {
         self.leave_blit();
+        self.end_suspended_render();
+        self.state.deferred_render = None;
         // when discarding, we don't have a guarantee that
         // everything is in a good state, so check carefully
         if let Some(encoder) = self.state.render.take() {
             encoder.end_encoding();
         }
+        if let Some(encoder) = self.state.parallel_render.take() {
+            encoder.end_encoding();
+        }
         if let Some(encoder) = self.state.compute.take() {
             encoder.end_encoding();
         }
@@ -231,6 +560,9 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
 
         self.leave_blit();
+        // A pass suspended at the end of the command buffer is resumed on the
+        // next one, which can't continue the render encoder.
+        self.end_suspended_render();
         debug_assert!(self.state.render.is_none());
         debug_assert!(self.state.compute.is_none());
         debug_assert!(self.state.pending_timer_queries.is_empty());
@@ -321,6 +653,43 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn resolve_texture<T>(&mut self, src: &super::Texture, dst: &super::Texture, regions: T)
+    where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        self.leave_blit();
+
+        // Blit encoders can't resolve, so an empty render pass resolves
+        // the whole subresource of each region instead.
+        let mut resolved = Vec::<(u32, u32, u32)>::new();
+        for r in regions {
+            let subresources = (
+                r.src_base.array_layer,
+                r.dst_base.mip_level,
+                r.dst_base.array_layer,
+            );
+            if resolved.contains(&subresources) {
+                continue;
+            }
+            resolved.push(subresources);
+
+            objc::rc::autoreleasepool(|| {
+                let descriptor = metal::RenderPassDescriptor::new();
+                let at_descriptor = descriptor.color_attachments().object_at(0).unwrap();
+                at_descriptor.set_texture(Some(&src.raw));
+                at_descriptor.set_slice(r.src_base.array_layer as u64);
+                at_descriptor.set_load_action(MTLLoadAction::Load);
+                at_descriptor.set_store_action(MTLStoreAction::MultisampleResolve);
+                at_descriptor.set_resolve_texture(Some(&dst.raw));
+                at_descriptor.set_resolve_level(r.dst_base.mip_level as u64);
+                at_descriptor.set_resolve_slice(r.dst_base.array_layer as u64);
+
+                let raw = self.raw_cmd_buf.as_ref().unwrap();
+                raw.new_render_command_encoder(descriptor).end_encoding();
+            });
+        }
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -409,6 +778,24 @@ impl crate::CommandEncoder for super::CommandEncoder {
         unimplemented!()
     }
 
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        _src: &super::AccelerationStructure,
+        _dst: &super::Buffer,
+        _dst_offset: wgt::BufferAddress,
+    ) {
+        unimplemented!()
+    }
+
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        _src: &super::Buffer,
+        _src_offset: wgt::BufferAddress,
+        _dst: &super::AccelerationStructure,
+    ) {
+        unimplemented!()
+    }
+
     unsafe fn begin_query(&mut self, set: &super::QuerySet, index: u32) {
         match set.ty {
             wgt::QueryType::Occlusion => {
@@ -512,7 +899,40 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     offset,
                 );
             }
-            wgt::QueryType::PipelineStatistics(_) => todo!(),
+            wgt::QueryType::PipelineStatistics(types) => {
+                let sample_size = conv::STATISTIC_COUNTER_COUNT * crate::QUERY_SIZE;
+                encoder.resolve_counters(
+                    set.counter_sample_buffer.as_ref().unwrap(),
+                    NSRange::new(range.start as u64 * 2, (range.end - range.start) as u64 * 2),
+                    &set.raw_buffer,
+                    range.start as u64 * 2 * sample_size,
+                );
+                self.leave_blit();
+
+                // The resolved counters are subtracted and compacted into the
+                // destination on the GPU.
+                let counters = conv::map_pipeline_statistics(types);
+                let mut params = [0u32; 8];
+                params[0] = range.start;
+                params[1] = range.end - range.start;
+                params[2] = counters.len() as u32;
+                params[3..3 + counters.len()].copy_from_slice(&counters);
+
+                let encoder = self
+                    .raw_cmd_buf
+                    .as_ref()
+                    .unwrap()
+                    .new_compute_command_encoder();
+                encoder.set_compute_pipeline_state(set.statistics_pipeline.as_ref().unwrap());
+                encoder.set_buffer(0, Some(&set.raw_buffer), 0);
+                encoder.set_buffer(1, Some(&buffer.raw), offset);
+                encoder.set_bytes(2, size_of_val(&params) as u64, params.as_ptr().cast());
+                encoder.dispatch_thread_groups(
+                    MTLSize::new((range.end - range.start).div_ceil(64) as u64, 1, 1),
+                    MTLSize::new(64, 1, 1),
+                );
+                encoder.end_encoding();
+            }
         }
     }
 
@@ -522,6 +942,21 @@ impl crate::CommandEncoder for super::CommandEncoder {
         &mut self,
         desc: &crate::RenderPassDescriptor<super::QuerySet, super::TextureView>,
     ) -> Result<(), crate::DeviceError> {
+        // A suspended pass is continued on its render encoder, unless the
+        // resuming pass needs a new one for its queries.
+        let occlusion_query_buffer = desc.occlusion_query_set.map(|set| set.raw_buffer.as_ptr());
+        let continued = self.state.suspended_render.take_if(|(_, deferred)| {
+            desc.flags.contains(wgt::RenderPassFlags::RESUMING)
+                && desc.timestamp_writes.is_none()
+                && desc.pipeline_statistics_query.is_none()
+                && self.state.pending_timer_queries.is_empty()
+                && deferred
+                    .occlusion_query_buffer
+                    .as_ref()
+                    .map(|buffer| buffer.as_ptr())
+                    == occlusion_query_buffer
+        });
+
         self.begin_pass();
         self.state.index = None;
 
@@ -529,142 +964,107 @@ impl crate::CommandEncoder for super::CommandEncoder {
         assert!(self.state.compute.is_none());
         assert!(self.state.render.is_none());
 
-        objc::rc::autoreleasepool(|| {
-            let descriptor = metal::RenderPassDescriptor::new();
-
-            for (i, at) in desc.color_attachments.iter().enumerate() {
-                if let Some(at) = at.as_ref() {
-                    let at_descriptor = descriptor.color_attachments().object_at(i as u64).unwrap();
-                    at_descriptor.set_texture(Some(&at.target.view.raw));
-                    if let Some(depth_slice) = at.depth_slice {
-                        at_descriptor.set_depth_plane(depth_slice as u64);
-                    }
-                    if let Some(ref resolve) = at.resolve_target {
-                        //Note: the selection of levels and slices is already handled by `TextureView`
-                        at_descriptor.set_resolve_texture(Some(&resolve.view.raw));
-                    }
-                    let load_action = if at.ops.contains(crate::AttachmentOps::LOAD) {
-                        MTLLoadAction::Load
-                    } else {
-                        at_descriptor.set_clear_color(conv::map_clear_color(&at.clear_value));
-                        MTLLoadAction::Clear
-                    };
-                    let store_action = conv::map_store_action(
-                        at.ops.contains(crate::AttachmentOps::STORE),
-                        at.resolve_target.is_some(),
-                    );
-                    at_descriptor.set_load_action(load_action);
-                    at_descriptor.set_store_action(store_action);
-                }
-            }
-
-            if let Some(ref at) = desc.depth_stencil_attachment {
-                if at.target.view.aspects.contains(crate::FormatAspects::DEPTH) {
-                    let at_descriptor = descriptor.depth_attachment().unwrap();
-                    at_descriptor.set_texture(Some(&at.target.view.raw));
-
-                    let load_action = if at.depth_ops.contains(crate::AttachmentOps::LOAD) {
-                        MTLLoadAction::Load
-                    } else {
-                        at_descriptor.set_clear_depth(at.clear_value.0 as f64);
-                        MTLLoadAction::Clear
-                    };
-                    let store_action = if at.depth_ops.contains(crate::AttachmentOps::STORE) {
-                        MTLStoreAction::Store
-                    } else {
-                        MTLStoreAction::DontCare
-                    };
-                    at_descriptor.set_load_action(load_action);
-                    at_descriptor.set_store_action(store_action);
-                }
-                if at
-                    .target
-                    .view
-                    .aspects
-                    .contains(crate::FormatAspects::STENCIL)
-                {
-                    let at_descriptor = descriptor.stencil_attachment().unwrap();
-                    at_descriptor.set_texture(Some(&at.target.view.raw));
-
-                    let load_action = if at.stencil_ops.contains(crate::AttachmentOps::LOAD) {
-                        MTLLoadAction::Load
-                    } else {
-                        at_descriptor.set_clear_stencil(at.clear_value.1);
-                        MTLLoadAction::Clear
-                    };
-                    let store_action = if at.stencil_ops.contains(crate::AttachmentOps::STORE) {
-                        MTLStoreAction::Store
-                    } else {
-                        MTLStoreAction::DontCare
-                    };
-                    at_descriptor.set_load_action(load_action);
-                    at_descriptor.set_store_action(store_action);
-                }
-            }
-
-            let mut sba_index = 0;
-            let mut next_sba_descriptor = || {
-                let sba_descriptor = descriptor
-                    .sample_buffer_attachments()
-                    .object_at(sba_index)
-                    .unwrap();
+        let suspending = desc.flags.contains(wgt::RenderPassFlags::SUSPENDING);
+        if let Some((encoder, deferred)) = continued {
+            // The encoder keeps the viewport and scissor of the suspended pass.
+            encoder.set_viewport(MTLViewport {
+                originX: 0.0,
+                originY: 0.0,
+                width: desc.extent.width as _,
+                height: desc.extent.height as _,
+                znear: 0.0,
+                zfar: 1.0,
+            });
+            encoder.set_scissor_rect(MTLScissorRect {
+                x: 0,
+                y: 0,
+                width: desc.extent.width as _,
+                height: desc.extent.height as _,
+            });
+            self.state.render = Some(encoder);
+            self.state.deferred_render = Some(super::DeferredRender {
+                suspending,
+                ..deferred
+            });
+        } else {
+            self.begin_render_encoder(desc);
+        }
 
-                sba_descriptor.set_end_of_vertex_sample_index(metal::COUNTER_DONT_SAMPLE);
-                sba_descriptor.set_start_of_fragment_sample_index(metal::COUNTER_DONT_SAMPLE);
+        // Metal starts with the first viewport covering the attachments, but the
+        // others have to match it before `set_viewport_indexed` flushes them.
+        self.state.viewports = [MTLViewport {
+            originX: 0.0,
+            originY: 0.0,
+            width: desc.extent.width as _,
+            height: desc.extent.height as _,
+            znear: 0.0,
+            zfar: 1.0,
+        }; wgt::MAX_VIEWPORTS as usize];
+        self.state.viewport_count = 1;
+        self.state.scissor = MTLScissorRect {
+            x: 0,
+            y: 0,
+            width: desc.extent.width as _,
+            height: desc.extent.height as _,
+        };
 
-                sba_index += 1;
-                sba_descriptor
-            };
+        Ok(())
+    }
 
-            for (set, index) in self.state.pending_timer_queries.drain(..) {
-                let sba_descriptor = next_sba_descriptor();
-                sba_descriptor.set_sample_buffer(set.counter_sample_buffer.as_ref().unwrap());
-                sba_descriptor.set_start_of_vertex_sample_index(index as _);
-                sba_descriptor.set_end_of_fragment_sample_index(metal::COUNTER_DONT_SAMPLE);
+    unsafe fn end_render_pass(&mut self) {
+        if let Some(encoder) = self.state.parallel_render.take() {
+            encoder.end_encoding();
+            return;
+        }
+        let encoder = self.state.render.take().unwrap();
+        match self.state.deferred_render.take() {
+            Some(deferred) if deferred.suspending => {
+                self.state.suspended_render = Some((encoder, deferred));
             }
-
-            if let Some(ref timestamp_writes) = desc.timestamp_writes {
-                let sba_descriptor = next_sba_descriptor();
-                sba_descriptor.set_sample_buffer(
-                    timestamp_writes
-                        .query_set
-                        .counter_sample_buffer
-                        .as_ref()
-                        .unwrap(),
-                );
-
-                sba_descriptor.set_start_of_vertex_sample_index(
-                    timestamp_writes
-                        .beginning_of_pass_write_index
-                        .map_or(metal::COUNTER_DONT_SAMPLE, |i| i as _),
-                );
-                sba_descriptor.set_end_of_fragment_sample_index(
-                    timestamp_writes
-                        .end_of_pass_write_index
-                        .map_or(metal::COUNTER_DONT_SAMPLE, |i| i as _),
-                );
+            Some(deferred) => {
+                deferred.set_store_actions(&encoder, false);
+                encoder.end_encoding();
             }
+            None => encoder.end_encoding(),
+        }
+    }
 
-            if let Some(occlusion_query_set) = desc.occlusion_query_set {
-                descriptor
-                    .set_visibility_result_buffer(Some(occlusion_query_set.raw_buffer.as_ref()))
-            }
+    unsafe fn create_parallel_render_encoder(
+        &mut self,
+    ) -> Result<super::CommandEncoder, crate::DeviceError> {
+        let parallel = self.state.parallel_render.as_ref().unwrap();
+        // The parts are executed in the order their encoders are created in.
+        let render = objc::rc::autoreleasepool(|| parallel.render_command_encoder().to_owned());
+        self.counters.command_encoders.add(1);
+        Ok(super::CommandEncoder {
+            shared: Arc::clone(&self.shared),
+            raw_queue: Arc::clone(&self.raw_queue),
+            raw_cmd_buf: None,
+            state: super::CommandState {
+                render: Some(render),
+                viewports: self.state.viewports,
+                viewport_count: self.state.viewport_count,
+                scissor: self.state.scissor,
+                ..Default::default()
+            },
+            temp: super::Temp::default(),
+            counters: Arc::clone(&self.counters),
+            debug_printf_buffer: self.debug_printf_buffer.clone(),
+        })
+    }
 
-            let raw = self.raw_cmd_buf.as_ref().unwrap();
-            let encoder = raw.new_render_command_encoder(descriptor);
-            if let Some(label) = desc.label {
-                encoder.set_label(label);
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        encoders: Vec<super::CommandEncoder>,
+    ) -> Result<(), crate::DeviceError> {
+        for mut encoder in encoders {
+            if let Some(render) = encoder.state.render.take() {
+                render.end_encoding();
             }
-            self.state.render = Some(encoder.to_owned());
-        });
-
+        }
         Ok(())
     }
 
-    unsafe fn end_render_pass(&mut self) {
-        self.state.render.take().unwrap().end_encoding();
-    }
-
     unsafe fn set_bind_group(
         &mut self,
         layout: &super::PipelineLayout,
@@ -949,6 +1349,19 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 );
             }
         }
+
+        if let Some(ref buffer) = self.debug_printf_buffer {
+            if let Some(slot) = pipeline.vs_info.debug_printf_slot {
+                encoder.set_vertex_buffer(slot as _, Some(buffer), 0);
+            }
+            if let Some(slot) = pipeline
+                .fs_info
+                .as_ref()
+                .and_then(|info| info.debug_printf_slot)
+            {
+                encoder.set_fragment_buffer(slot as _, Some(buffer), 0);
+            }
+        }
     }
 
     unsafe fn set_index_buffer<'a>(
@@ -957,6 +1370,8 @@ impl crate::CommandEncoder for super::CommandEncoder {
         format: wgt::IndexFormat,
     ) {
         let (stride, raw_type) = match format {
+            // Metal has no 8 bit index type
+            wgt::IndexFormat::Uint8 => unreachable!(),
             wgt::IndexFormat::Uint16 => (2, MTLIndexType::UInt16),
             wgt::IndexFormat::Uint32 => (4, MTLIndexType::UInt32),
         };
@@ -1000,31 +1415,40 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
 
     unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {
+        unsafe { self.set_viewport_indexed(0, rect, depth_range) };
+    }
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
         let zfar = if self.shared.disabilities.broken_viewport_near_depth {
             depth_range.end - depth_range.start
         } else {
             depth_range.end
         };
-        let encoder = self.state.render.as_ref().unwrap();
-        encoder.set_viewport(MTLViewport {
+        let index = index as usize;
+        self.state.viewports[index] = MTLViewport {
             originX: rect.x as _,
             originY: rect.y as _,
             width: rect.w as _,
             height: rect.h as _,
             znear: depth_range.start as _,
             zfar: zfar as _,
-        });
+        };
+        self.state.viewport_count = self.state.viewport_count.max(index + 1);
+        self.flush_viewports();
     }
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
         //TODO: support empty scissors by modifying the viewport
-        let scissor = MTLScissorRect {
+        self.state.scissor = MTLScissorRect {
             x: rect.x as _,
             y: rect.y as _,
             width: rect.w as _,
             height: rect.h as _,
         };
-        let encoder = self.state.render.as_ref().unwrap();
-        encoder.set_scissor_rect(scissor);
+        self.flush_viewports();
     }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
         let encoder = self.state.render.as_ref().unwrap();
@@ -1035,6 +1459,19 @@ impl crate::CommandEncoder for super::CommandEncoder {
         encoder.set_blend_color(color[0], color[1], color[2], color[3]);
     }
 
+    unsafe fn set_shading_rate(&mut self, _rate: wgt::ShadingRate) {
+        // `VARIABLE_RATE_SHADING` isn't exposed on Metal, which only has rasterization rate maps.
+    }
+
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        _buffer: &super::Buffer,
+        _offset: wgt::BufferAddress,
+    ) {
+        // `CONDITIONAL_RENDERING` isn't exposed on Metal, which has no predicated draws.
+    }
+    unsafe fn end_conditional_rendering(&mut self) {}
+
     unsafe fn draw(
         &mut self,
         first_vertex: u32,
@@ -1206,7 +1643,8 @@ impl crate::CommandEncoder for super::CommandEncoder {
 
         objc::rc::autoreleasepool(|| {
             // TimeStamp Queries and ComputePassDescriptor were both introduced in Metal 2.3 (macOS 11, iOS 14)
-            // and we currently only need ComputePassDescriptor for timestamp queries
+            // and we currently only need ComputePassDescriptor for timestamp and pipeline statistics queries,
+            // which are only exposed together with timestamp queries
             let encoder = if self.shared.private_caps.timestamp_query_support.is_empty() {
                 raw.new_compute_command_encoder()
             } else {
@@ -1251,6 +1689,15 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     );
                 }
 
+                if let Some(ref query) = desc.pipeline_statistics_query {
+                    let sba_descriptor = next_sba_descriptor();
+                    sba_descriptor
+                        .set_sample_buffer(query.query_set.counter_sample_buffer.as_ref().unwrap());
+                    sba_descriptor.set_start_of_encoder_sample_index(query.query_index as u64 * 2);
+                    sba_descriptor
+                        .set_end_of_encoder_sample_index(query.query_index as u64 * 2 + 1);
+                }
+
                 raw.compute_command_encoder_with_descriptor(descriptor)
             };
 
@@ -1272,6 +1719,13 @@ impl crate::CommandEncoder for super::CommandEncoder {
         let encoder = self.state.compute.as_ref().unwrap();
         encoder.set_compute_pipeline_state(&pipeline.raw);
 
+        if let (Some(buffer), Some(slot)) = (
+            self.debug_printf_buffer.as_ref(),
+            pipeline.cs_info.debug_printf_slot,
+        ) {
+            encoder.set_buffer(slot as _, Some(buffer), 0);
+        }
+
         if let Some((index, sizes)) = self
             .state
             .make_sizes_buffer_update(naga::ShaderStage::Compute, &mut self.temp.binding_sizes)
@@ -1319,6 +1773,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
         encoder.dispatch_thread_groups_indirect(&buffer.raw, offset, self.state.raw_wg_size);
     }
 
+    unsafe fn set_ray_tracing_pipeline(&mut self, _pipeline: &super::RayTracingPipeline) {
+        unreachable!()
+    }
+
+    unsafe fn trace_rays(
+        &mut self,
+        _tables: &crate::ShaderBindingTables<super::Buffer>,
+        _count: [u32; 3],
+    ) {
+        unreachable!()
+    }
+
     unsafe fn build_acceleration_structures<'a, T>(
         &mut self,
         _descriptor_count: u32,
@@ -1350,6 +1816,14 @@ impl crate::CommandEncoder for super::CommandEncoder {
     ) {
         unimplemented!()
     }
+
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        _acceleration_structure: &super::AccelerationStructure,
+        _buf: &super::Buffer,
+    ) {
+        unimplemented!()
+    }
 }
 
 impl Drop for super::CommandEncoder {
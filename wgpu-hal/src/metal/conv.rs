@@ -1,9 +1,10 @@
 use metal::{
     MTLBlendFactor, MTLBlendOperation, MTLBlitOption, MTLClearColor, MTLColorWriteMask,
-    MTLCompareFunction, MTLCullMode, MTLOrigin, MTLPrimitiveTopologyClass, MTLPrimitiveType,
-    MTLRenderStages, MTLResourceUsage, MTLSamplerAddressMode, MTLSamplerBorderColor,
-    MTLSamplerMinMagFilter, MTLSize, MTLStencilOperation, MTLStoreAction, MTLTextureType,
-    MTLTextureUsage, MTLVertexFormat, MTLVertexStepFunction, MTLWinding, NSRange,
+    MTLCompareFunction, MTLCullMode, MTLMultisampleDepthResolveFilter, MTLOrigin,
+    MTLPrimitiveTopologyClass, MTLPrimitiveType, MTLRenderStages, MTLResourceUsage,
+    MTLSamplerAddressMode, MTLSamplerBorderColor, MTLSamplerMinMagFilter, MTLSize,
+    MTLStencilOperation, MTLStoreAction, MTLTextureType, MTLTextureUsage, MTLVertexFormat,
+    MTLVertexStepFunction, MTLWinding, NSRange,
 };
 
 pub fn map_texture_usage(format: wgt::TextureFormat, usage: wgt::TextureUses) -> MTLTextureUsage {
@@ -237,7 +238,9 @@ pub fn map_vertex_format(format: wgt::VertexFormat) -> MTLVertexFormat {
 pub fn map_step_mode(mode: wgt::VertexStepMode) -> MTLVertexStepFunction {
     match mode {
         wgt::VertexStepMode::Vertex => MTLVertexStepFunction::PerVertex,
-        wgt::VertexStepMode::Instance => MTLVertexStepFunction::PerInstance,
+        wgt::VertexStepMode::Instance | wgt::VertexStepMode::InstanceStepRate { .. } => {
+            MTLVertexStepFunction::PerInstance
+        }
     }
 }
 
@@ -305,6 +308,15 @@ pub fn map_store_action(store: bool, resolve: bool) -> MTLStoreAction {
     }
 }
 
+pub fn map_depth_resolve_filter(mode: wgt::ResolveMode) -> MTLMultisampleDepthResolveFilter {
+    use MTLMultisampleDepthResolveFilter as MTL;
+    match mode {
+        wgt::ResolveMode::SampleZero => MTL::Sample0,
+        wgt::ResolveMode::Min => MTL::Min,
+        wgt::ResolveMode::Max => MTL::Max,
+    }
+}
+
 pub fn map_clear_color(color: &wgt::Color) -> MTLClearColor {
     MTLClearColor {
         red: color.r,
@@ -353,3 +365,24 @@ pub fn map_resource_usage(ty: &wgt::BindingType) -> MTLResourceUsage {
         _ => unreachable!(),
     }
 }
+
+/// Number of counters in a `MTLCounterResultStatistic`.
+pub const STATISTIC_COUNTER_COUNT: u64 = 8;
+
+/// Maps pipeline statistics to their indices in a `MTLCounterResultStatistic`,
+/// in the order they are written out.
+pub fn map_pipeline_statistics(types: wgt::PipelineStatisticsTypes) -> arrayvec::ArrayVec<u32, 5> {
+    use wgt::PipelineStatisticsTypes as Pst;
+
+    [
+        (Pst::VERTEX_SHADER_INVOCATIONS, 1),
+        (Pst::CLIPPER_INVOCATIONS, 3),
+        (Pst::CLIPPER_PRIMITIVES_OUT, 4),
+        (Pst::FRAGMENT_SHADER_INVOCATIONS, 5),
+        (Pst::COMPUTE_SHADER_INVOCATIONS, 7),
+    ]
+    .into_iter()
+    .filter(|&(ty, _)| types.contains(ty))
+    .map(|(_, index)| index)
+    .collect()
+}
@@ -1,7 +1,13 @@
-use alloc::{borrow::ToOwned as _, sync::Arc, vec::Vec};
+use alloc::{
+    borrow::ToOwned as _,
+    string::{String, ToString as _},
+    sync::Arc,
+    vec::Vec,
+};
 use core::{ptr::NonNull, sync::atomic};
 use std::{thread, time};
 
+use objc::{msg_send, sel, sel_impl};
 use parking_lot::Mutex;
 
 use super::{PassthroughShader, conv};
@@ -10,14 +16,42 @@ use crate::auxil::map_naga_stage;
 use crate::metal::ShaderModuleSource;
 
 use metal::{
-    MTLCommandBufferStatus, MTLDepthClipMode, MTLLanguageVersion, MTLMutability, MTLPixelFormat,
-    MTLPrimitiveTopologyClass, MTLResourceID, MTLResourceOptions, MTLSamplerAddressMode,
-    MTLSamplerMipFilter, MTLSize, MTLStorageMode, MTLTextureType, MTLTriangleFillMode,
-    MTLVertexStepFunction, NSRange, foreign_types::ForeignType,
+    MTLCommandBufferStatus, MTLDepthClipMode, MTLHazardTrackingMode, MTLHeapType,
+    MTLLanguageVersion, MTLMutability, MTLPixelFormat, MTLPrimitiveTopologyClass, MTLResourceID,
+    MTLResourceOptions, MTLSamplerAddressMode, MTLSamplerMipFilter, MTLSize, MTLStorageMode,
+    MTLTextureType, MTLTriangleFillMode, MTLVertexStepFunction, NSRange,
+    foreign_types::ForeignType,
 };
 
 type DeviceResult<T> = Result<T, crate::DeviceError>;
 
+/// Writes the pipeline statistics of each query of a range, computed from the
+/// `MTLCounterResultStatistic`s resolved at the beginning and end of the query.
+///
+/// `params` holds the first query of the range, the number of queries, the number
+/// of statistics to write, and the index of each statistic.
+const PIPELINE_STATISTICS_MSL: &str = "
+#include <metal_stdlib>
+using namespace metal;
+
+kernel void resolve_pipeline_statistics(
+    device const ulong *samples [[buffer(0)]],
+    device ulong *destination [[buffer(1)]],
+    constant uint *params [[buffer(2)]],
+    uint query [[thread_position_in_grid]]
+) {
+    if (query >= params[1]) {
+        return;
+    }
+    device const ulong *begin = samples + (params[0] + query) * 16;
+    device const ulong *end = begin + 8;
+    for (uint i = 0; i < params[2]; i++) {
+        uint counter = params[3 + i];
+        destination[query * params[2] + i] = end[counter] - begin[counter];
+    }
+}
+";
+
 struct CompiledShader {
     library: metal::Library,
     function: metal::Function,
@@ -120,6 +154,69 @@ const fn convert_vertex_format_to_naga(format: wgt::VertexFormat) -> naga::back:
 }
 
 impl super::Device {
+    fn create_counter_sample_buffer(
+        device: &metal::DeviceRef,
+        counter_set_name: &str,
+        sample_count: u64,
+        label: crate::Label,
+    ) -> DeviceResult<metal::CounterSampleBuffer> {
+        let csb_desc = metal::CounterSampleBufferDescriptor::new();
+        csb_desc.set_storage_mode(MTLStorageMode::Shared);
+        csb_desc.set_sample_count(sample_count);
+        if let Some(label) = label {
+            csb_desc.set_label(label);
+        }
+
+        let counter_sets = device.counter_sets();
+        let counter_set = match counter_sets.iter().find(|cs| cs.name() == counter_set_name) {
+            Some(counter) => counter,
+            None => {
+                log::error!("Failed to obtain {counter_set_name} counter set.");
+                return Err(crate::DeviceError::Unexpected);
+            }
+        };
+        csb_desc.set_counter_set(counter_set);
+
+        match device.new_counter_sample_buffer_with_descriptor(&csb_desc) {
+            Ok(buffer) => Ok(buffer),
+            Err(err) => {
+                log::error!("Failed to create counter sample buffer: {:?}", err);
+                Err(crate::DeviceError::Unexpected)
+            }
+        }
+    }
+
+    fn pipeline_statistics_pipeline(
+        &self,
+        device: &metal::DeviceRef,
+    ) -> DeviceResult<metal::ComputePipelineState> {
+        let mut cached = self.shared.statistics_pipeline.lock();
+        if let Some(ref pipeline) = *cached {
+            return Ok(pipeline.clone());
+        }
+
+        let library = device
+            .new_library_with_source(PIPELINE_STATISTICS_MSL, &metal::CompileOptions::new())
+            .map_err(|err| {
+                log::error!("Failed to compile the pipeline statistics shader: {err}");
+                crate::DeviceError::Unexpected
+            })?;
+        let function = library
+            .get_function("resolve_pipeline_statistics", None)
+            .map_err(|err| {
+                log::error!("Failed to get the pipeline statistics function: {err}");
+                crate::DeviceError::Unexpected
+            })?;
+        let pipeline = device
+            .new_compute_pipeline_state_with_function(&function)
+            .map_err(|err| {
+                log::error!("Failed to create the pipeline statistics pipeline: {err}");
+                crate::DeviceError::Unexpected
+            })?;
+        *cached = Some(pipeline.clone());
+        Ok(pipeline)
+    }
+
     fn load_shader(
         &self,
         stage: &crate::ProgrammableStage<super::ShaderModule>,
@@ -150,6 +247,13 @@ impl super::Device {
             naga::proc::BoundsCheckPolicy::Unchecked
         };
 
+        // Keep the table locked until the formats of this shader are added, so
+        // that they get the indices the shader was written with.
+        let mut debug_printf_formats = self
+            .debug_printf
+            .as_ref()
+            .map(|debug_printf| debug_printf.reader.formats());
+
         let options = naga::back::msl::Options {
             lang_version: match self.shared.private_caps.msl_version {
                 MTLLanguageVersion::V1_0 => (1, 0),
@@ -179,6 +283,9 @@ impl super::Device {
             },
             zero_initialize_workgroup_memory: stage.zero_initialize_workgroup_memory,
             force_loop_bounding: stage.module.bounds_checks.force_loop_bounding,
+            debug_printf_first_format: debug_printf_formats
+                .as_ref()
+                .map_or(0, |formats| formats.len() as u32),
         };
 
         let pipeline_options = naga::back::msl::PipelineOptions {
@@ -189,12 +296,23 @@ impl super::Device {
             },
             vertex_pulling_transform: true,
             vertex_buffer_mappings: vertex_buffer_mappings.to_vec(),
+            debug_source: naga_shader.debug_source.as_ref().map(|debug_source| {
+                naga::back::DebugSource {
+                    file_name: debug_source.file_name.to_string(),
+                    source_code: debug_source.source_code.to_string(),
+                }
+            }),
         };
 
-        let (source, info) =
+        let (source, mut info) =
             naga::back::msl::write_string(&module, &module_info, &options, &pipeline_options)
                 .map_err(|e| crate::PipelineError::Linkage(stage_bit, format!("MSL: {:?}", e)))?;
 
+        if let Some(ref mut formats) = debug_printf_formats {
+            formats.append(&mut info.debug_printf_formats);
+        }
+        drop(debug_printf_formats);
+
         log::debug!(
             "Naga generated shader for entry point '{}' and stage {:?}\n{}",
             stage.entry_point,
@@ -337,6 +455,8 @@ impl super::Device {
             shared: Arc::new(super::AdapterShared::new(raw)),
             features,
             counters: Default::default(),
+            command_buffer_error: Mutex::new(None),
+            validation_messages: Mutex::new(Vec::new()),
         }
     }
 
@@ -344,9 +464,89 @@ impl super::Device {
         super::Buffer { raw, size }
     }
 
+    /// Returns [`crate::DeviceError::Lost`] if `cmd_buf` failed, keeping its error to
+    /// report in [`crate::Device::fault_info`].
+    fn check_command_buffer(&self, cmd_buf: &metal::CommandBufferRef) -> DeviceResult<()> {
+        let Some(error) = super::CommandBufferError::from_command_buffer(cmd_buf) else {
+            return Ok(());
+        };
+        log::error!("{error}");
+        // Shader validation fails the command buffer, with a description of the
+        // failure naming the labels of the command buffer and resources involved.
+        if self.shared.settings.shader_validation {
+            self.validation_messages.lock().push(error.to_string());
+        }
+        self.command_buffer_error.lock().get_or_insert(error);
+        Err(crate::DeviceError::Lost)
+    }
+
+    /// Logs the [`wgt::Features::SHADER_DEBUG_PRINTF`] messages of the
+    /// command buffers completed so far.
+    fn poll_debug_printf(&self) {
+        if let Some(ref debug_printf) = self.debug_printf {
+            debug_printf.reader.poll();
+        }
+    }
+
+    /// Enables shader validation on a render or compute pipeline `descriptor` if
+    /// [`wgt::InstanceFlags::GPU_BASED_VALIDATION`] is set and the OS supports it.
+    fn set_shader_validation(&self, descriptor: *mut objc::runtime::Object) {
+        /// `MTLShaderValidationEnabled`.
+        const MTL_SHADER_VALIDATION_ENABLED: metal::NSInteger = 1;
+
+        if !self.shared.settings.shader_validation {
+            return;
+        }
+        let supported: objc::runtime::BOOL =
+            unsafe { msg_send![descriptor, respondsToSelector: sel!(setShaderValidation:)] };
+        if supported == objc::runtime::YES {
+            let () = unsafe {
+                msg_send![descriptor, setShaderValidation: MTL_SHADER_VALIDATION_ENABLED]
+            };
+        } else {
+            log::warn!("Shader validation is not supported by this OS version");
+        }
+    }
+
     pub fn raw_device(&self) -> &Mutex<metal::Device> {
         &self.shared.device
     }
+
+    fn texture_descriptor(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> (metal::TextureDescriptor, MTLTextureType) {
+        let descriptor = metal::TextureDescriptor::new();
+
+        let mtl_type = match desc.dimension {
+            wgt::TextureDimension::D1 => MTLTextureType::D1,
+            wgt::TextureDimension::D2 => {
+                if desc.sample_count > 1 {
+                    descriptor.set_sample_count(desc.sample_count as u64);
+                    MTLTextureType::D2Multisample
+                } else if desc.size.depth_or_array_layers > 1 {
+                    descriptor.set_array_length(desc.size.depth_or_array_layers as u64);
+                    MTLTextureType::D2Array
+                } else {
+                    MTLTextureType::D2
+                }
+            }
+            wgt::TextureDimension::D3 => {
+                descriptor.set_depth(desc.size.depth_or_array_layers as u64);
+                MTLTextureType::D3
+            }
+        };
+
+        descriptor.set_texture_type(mtl_type);
+        descriptor.set_width(desc.size.width as u64);
+        descriptor.set_height(desc.size.height as u64);
+        descriptor.set_mipmap_level_count(desc.mip_level_count as u64);
+        descriptor.set_pixel_format(self.shared.private_caps.map_format(desc.format));
+        descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
+        descriptor.set_storage_mode(MTLStorageMode::Private);
+
+        (descriptor, mtl_type)
+    }
 }
 
 impl crate::Device for super::Device {
@@ -410,37 +610,15 @@ impl crate::Device for super::Device {
     ) -> DeviceResult<super::Texture> {
         use metal::foreign_types::ForeignType as _;
 
-        let mtl_format = self.shared.private_caps.map_format(desc.format);
-
         objc::rc::autoreleasepool(|| {
-            let descriptor = metal::TextureDescriptor::new();
-
-            let mtl_type = match desc.dimension {
-                wgt::TextureDimension::D1 => MTLTextureType::D1,
-                wgt::TextureDimension::D2 => {
-                    if desc.sample_count > 1 {
-                        descriptor.set_sample_count(desc.sample_count as u64);
-                        MTLTextureType::D2Multisample
-                    } else if desc.size.depth_or_array_layers > 1 {
-                        descriptor.set_array_length(desc.size.depth_or_array_layers as u64);
-                        MTLTextureType::D2Array
-                    } else {
-                        MTLTextureType::D2
-                    }
-                }
-                wgt::TextureDimension::D3 => {
-                    descriptor.set_depth(desc.size.depth_or_array_layers as u64);
-                    MTLTextureType::D3
-                }
-            };
-
-            descriptor.set_texture_type(mtl_type);
-            descriptor.set_width(desc.size.width as u64);
-            descriptor.set_height(desc.size.height as u64);
-            descriptor.set_mipmap_level_count(desc.mip_level_count as u64);
-            descriptor.set_pixel_format(mtl_format);
-            descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
-            descriptor.set_storage_mode(MTLStorageMode::Private);
+            let (descriptor, mtl_type) = self.texture_descriptor(desc);
+            if desc
+                .memory_flags
+                .contains(crate::MemoryFlags::TRANSIENT_ATTACHMENT)
+                && self.shared.private_caps.supports_memoryless_storage
+            {
+                descriptor.set_storage_mode(MTLStorageMode::Memoryless);
+            }
 
             let raw = self.shared.device.lock().new_texture(&descriptor);
             if raw.as_ptr().is_null() {
@@ -471,6 +649,100 @@ impl crate::Device for super::Device {
         self.counters.textures.add(1);
     }
 
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &crate::MemoryHeapDescriptor,
+    ) -> DeviceResult<super::MemoryHeap> {
+        objc::rc::autoreleasepool(|| {
+            let descriptor = metal::HeapDescriptor::new();
+            descriptor.set_heap_type(MTLHeapType::Placement);
+            descriptor.set_storage_mode(MTLStorageMode::Private);
+            // Lets Metal order work on textures that alias each other.
+            descriptor.set_hazard_tracking_mode(MTLHazardTrackingMode::Tracked);
+            descriptor.set_size(desc.size);
+
+            let raw = self.shared.device.lock().new_heap(&descriptor);
+            if raw.as_ptr().is_null() {
+                return Err(crate::DeviceError::OutOfMemory);
+            }
+            if let Some(label) = desc.label {
+                raw.set_label(label);
+            }
+
+            self.counters.texture_memory.add(desc.size as isize);
+
+            Ok(super::MemoryHeap {
+                raw,
+                size: desc.size,
+            })
+        })
+    }
+    unsafe fn destroy_memory_heap(&self, heap: super::MemoryHeap) {
+        self.counters.texture_memory.sub(heap.size as isize);
+    }
+
+    unsafe fn texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<wgt::TextureMemoryRequirements> {
+        objc::rc::autoreleasepool(|| {
+            let (descriptor, _) = self.texture_descriptor(desc);
+            let size_and_align = self
+                .shared
+                .device
+                .lock()
+                .heap_texture_size_and_align(&descriptor);
+
+            Ok(wgt::TextureMemoryRequirements {
+                size: size_and_align.size,
+                alignment: size_and_align.align,
+            })
+        })
+    }
+
+    unsafe fn create_placed_texture(
+        &self,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<super::Texture> {
+        objc::rc::autoreleasepool(|| {
+            let (descriptor, mtl_type) = self.texture_descriptor(desc);
+
+            let raw = heap
+                .raw
+                .new_texture_with_offset(&descriptor, offset)
+                .ok_or(crate::DeviceError::OutOfMemory)?;
+            if let Some(label) = desc.label {
+                raw.set_label(label);
+            }
+
+            self.counters.textures.add(1);
+
+            Ok(super::Texture {
+                raw,
+                format: desc.format,
+                raw_type: mtl_type,
+                mip_levels: desc.mip_level_count,
+                array_layers: desc.array_layer_count(),
+                copy_size: desc.copy_extent(),
+            })
+        })
+    }
+
+    unsafe fn create_sparse_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> DeviceResult<super::Buffer> {
+        unreachable!()
+    }
+    unsafe fn create_sparse_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<super::Texture> {
+        unreachable!()
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &super::Texture,
@@ -617,6 +889,10 @@ impl crate::Device for super::Device {
             state: super::CommandState::default(),
             temp: super::Temp::default(),
             counters: Arc::clone(&self.counters),
+            debug_printf_buffer: self
+                .debug_printf
+                .as_ref()
+                .map(|debug_printf| debug_printf.raw.clone()),
         })
     }
 
@@ -647,6 +923,7 @@ impl crate::Device for super::Device {
             pc_limit: u32,
             sizes_buffer: Option<super::ResourceIndex>,
             need_sizes_buffer: bool,
+            debug_printf_buffer: Option<super::ResourceIndex>,
             resources: naga::back::msl::BindingMap,
         }
 
@@ -657,6 +934,7 @@ impl crate::Device for super::Device {
             pc_limit: 0,
             sizes_buffer: None,
             need_sizes_buffer: false,
+            debug_printf_buffer: None,
             resources: Default::default(),
         });
         let mut bind_group_infos = arrayvec::ArrayVec::new();
@@ -748,6 +1026,7 @@ impl crate::Device for super::Device {
                             }
                             wgt::BindingType::AccelerationStructure { .. } => unimplemented!(),
                             wgt::BindingType::ExternalTexture => unimplemented!(),
+                            wgt::BindingType::YcbcrTexture { .. } => unimplemented!(),
                         }
                     }
 
@@ -773,6 +1052,11 @@ impl crate::Device for super::Device {
                 info.counters.buffers += 1;
             }
 
+            if self.debug_printf.is_some() {
+                info.debug_printf_buffer = Some(info.counters.buffers);
+                info.counters.buffers += 1;
+            }
+
             if info.counters.buffers > self.shared.private_caps.max_buffers_per_stage
                 || info.counters.textures > self.shared.private_caps.max_textures_per_stage
                 || info.counters.samplers > self.shared.private_caps.max_samplers_per_stage
@@ -798,6 +1082,9 @@ impl crate::Device for super::Device {
             sizes_buffer: info
                 .sizes_buffer
                 .map(|buffer_index| buffer_index as naga::back::msl::Slot),
+            debug_printf_buffer: info
+                .debug_printf_buffer
+                .map(|buffer_index| buffer_index as naga::back::msl::Slot),
             resources: info.resources,
         });
 
@@ -981,6 +1268,7 @@ impl crate::Device for super::Device {
                             }
                             wgt::BindingType::AccelerationStructure { .. } => unimplemented!(),
                             wgt::BindingType::ExternalTexture => unimplemented!(),
+                            wgt::BindingType::YcbcrTexture { .. } => unimplemented!(),
                         }
                     }
                 }
@@ -1059,6 +1347,7 @@ impl crate::Device for super::Device {
     ) -> Result<super::RenderPipeline, crate::PipelineError> {
         objc::rc::autoreleasepool(|| {
             let descriptor = metal::RenderPipelineDescriptor::new();
+            self.set_shader_validation(descriptor.as_ptr().cast());
 
             let raw_triangle_fill_mode = match desc.primitive.polygon_mode {
                 wgt::PolygonMode::Fill => MTLTriangleFillMode::Fill,
@@ -1099,6 +1388,7 @@ impl crate::Device for super::Device {
                                 .unwrap()
                         },
                         indexed_by_vertex: (vbl.step_mode == wgt::VertexStepMode::Vertex {}),
+                        step_rate: vbl.step_mode.instance_rate().unwrap_or(0),
                         attributes,
                     });
                 }
@@ -1112,6 +1402,11 @@ impl crate::Device for super::Device {
                 )?;
 
                 descriptor.set_vertex_function(Some(&vs.function));
+                // Layered rendering needs the primitive class to route primitives to
+                // the `render_target_array_index` that the vertex shader writes.
+                if self.shared.private_caps.layered_rendering {
+                    descriptor.set_input_primitive_topology(primitive_class);
+                }
                 if self.shared.private_caps.supports_mutability {
                     Self::set_buffers_mutability(
                         descriptor.vertex_buffers().unwrap(),
@@ -1122,6 +1417,7 @@ impl crate::Device for super::Device {
                 let info = super::PipelineStageInfo {
                     push_constants: desc.layout.push_constants_infos.vs,
                     sizes_slot: desc.layout.per_stage_map.vs.sizes_buffer,
+                    debug_printf_slot: desc.layout.per_stage_map.vs.debug_printf_buffer,
                     sized_bindings: vs.sized_bindings,
                     vertex_buffer_mappings,
                 };
@@ -1151,6 +1447,7 @@ impl crate::Device for super::Device {
                     let info = super::PipelineStageInfo {
                         push_constants: desc.layout.push_constants_infos.fs,
                         sizes_slot: desc.layout.per_stage_map.fs.sizes_buffer,
+                        debug_printf_slot: desc.layout.per_stage_map.fs.debug_printf_buffer,
                         sized_bindings: fs.sized_bindings,
                         vertex_buffer_mappings: vec![],
                     };
@@ -1254,6 +1551,9 @@ impl crate::Device for super::Device {
                     } else {
                         buffer_desc.set_stride(vb.array_stride);
                         buffer_desc.set_step_function(conv::map_step_mode(vb.step_mode));
+                        if let Some(rate) = vb.step_mode.instance_rate() {
+                            buffer_desc.set_step_rate(rate.into());
+                        }
                     }
 
                     for at in vb.attributes {
@@ -1281,6 +1581,11 @@ impl crate::Device for super::Device {
                 descriptor.set_label(name);
             }
 
+            let archive = desc.cache.map(|cache| cache.archive.lock());
+            if let Some(archive) = archive.as_deref() {
+                descriptor.set_binary_archives(&[archive]);
+            }
+
             let raw = self
                 .shared
                 .device
@@ -1293,6 +1598,13 @@ impl crate::Device for super::Device {
                     )
                 })?;
 
+            if let Some(archive) = archive {
+                // Failing to cache the pipeline doesn't affect the pipeline itself.
+                if let Err(e) = archive.add_render_pipeline_functions_with_descriptor(&descriptor) {
+                    log::warn!("Failed to add render pipeline to pipeline cache: {e}");
+                }
+            }
+
             self.counters.render_pipelines.add(1);
 
             Ok(super::RenderPipeline {
@@ -1344,6 +1656,7 @@ impl crate::Device for super::Device {
     ) -> Result<super::ComputePipeline, crate::PipelineError> {
         objc::rc::autoreleasepool(|| {
             let descriptor = metal::ComputePipelineDescriptor::new();
+            self.set_shader_validation(descriptor.as_ptr().cast());
 
             let module = desc.stage.module;
             let cs = if let ShaderModuleSource::Passthrough(desc) = &module.source {
@@ -1381,6 +1694,7 @@ impl crate::Device for super::Device {
             let cs_info = super::PipelineStageInfo {
                 push_constants: desc.layout.push_constants_infos.cs,
                 sizes_slot: desc.layout.per_stage_map.cs.sizes_buffer,
+                debug_printf_slot: desc.layout.per_stage_map.cs.debug_printf_buffer,
                 sized_bindings: cs.sized_bindings,
                 vertex_buffer_mappings: vec![],
             };
@@ -1389,6 +1703,11 @@ impl crate::Device for super::Device {
                 descriptor.set_label(name);
             }
 
+            let archive = desc.cache.map(|cache| cache.archive.lock());
+            if let Some(archive) = archive.as_deref() {
+                descriptor.set_binary_archives(&[archive]);
+            }
+
             let raw = self
                 .shared
                 .device
@@ -1401,6 +1720,14 @@ impl crate::Device for super::Device {
                     )
                 })?;
 
+            if let Some(archive) = archive {
+                // Failing to cache the pipeline doesn't affect the pipeline itself.
+                if let Err(e) = archive.add_compute_pipeline_functions_with_descriptor(&descriptor)
+                {
+                    log::warn!("Failed to add compute pipeline to pipeline cache: {e}");
+                }
+            }
+
             self.counters.compute_pipelines.add(1);
 
             Ok(super::ComputePipeline {
@@ -1417,13 +1744,107 @@ impl crate::Device for super::Device {
         self.counters.compute_pipelines.sub(1);
     }
 
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        _desc: &crate::RayTracingPipelineDescriptor<
+            <Self::A as crate::Api>::PipelineLayout,
+            <Self::A as crate::Api>::ShaderModule,
+            <Self::A as crate::Api>::PipelineCache,
+        >,
+    ) -> Result<<Self::A as crate::Api>::RayTracingPipeline, crate::PipelineError> {
+        unreachable!()
+    }
+    unsafe fn destroy_ray_tracing_pipeline(&self, _pipeline: super::RayTracingPipeline) {}
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        _pipeline: &super::RayTracingPipeline,
+        _first_group: u32,
+        _group_count: u32,
+    ) -> Result<Vec<u8>, crate::DeviceError> {
+        unreachable!()
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
-        _desc: &crate::PipelineCacheDescriptor<'_>,
+        desc: &crate::PipelineCacheDescriptor<'_>,
     ) -> Result<super::PipelineCache, crate::PipelineCacheError> {
-        Ok(super::PipelineCache)
+        objc::rc::autoreleasepool(|| {
+            let device = self.shared.device.lock();
+
+            let file = desc.data.and_then(|data| {
+                let path = super::PipelineCache::temp_path();
+                match std::fs::write(&path, data) {
+                    Ok(()) => Some(path),
+                    Err(e) => {
+                        log::warn!("Failed to write pipeline cache data to {path:?}: {e}");
+                        None
+                    }
+                }
+            });
+
+            let loaded = file.as_deref().and_then(|path| {
+                let descriptor = metal::BinaryArchiveDescriptor::new();
+                descriptor.set_url(super::PipelineCache::file_url(path)?);
+                device
+                    .new_binary_archive_with_descriptor(&descriptor)
+                    .inspect_err(|e| log::warn!("Failed to load pipeline cache: {e}"))
+                    .ok()
+            });
+            let (archive, file) = match loaded {
+                Some(archive) => (archive, file),
+                None => {
+                    // Start from an empty archive, as with a cache created without data.
+                    if let Some(path) = file {
+                        let _ = std::fs::remove_file(path);
+                    }
+                    let archive = device
+                        .new_binary_archive_with_descriptor(&metal::BinaryArchiveDescriptor::new())
+                        .map_err(|e| {
+                            log::error!("Failed to create pipeline cache: {e}");
+                            crate::DeviceError::Unexpected
+                        })?;
+                    (archive, None)
+                }
+            };
+
+            if let Some(label) = desc.label {
+                archive.set_label(label);
+            }
+
+            Ok(super::PipelineCache {
+                archive: Mutex::new(archive),
+                file,
+            })
+        })
+    }
+    unsafe fn destroy_pipeline_cache(&self, cache: super::PipelineCache) {
+        drop(cache.archive);
+        if let Some(path) = cache.file {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    fn pipeline_cache_validation_key(&self) -> Option<[u8; 16]> {
+        Some(self.shared.private_caps.pipeline_cache_validation_key)
+    }
+
+    unsafe fn pipeline_cache_get_data(&self, cache: &super::PipelineCache) -> Option<Vec<u8>> {
+        objc::rc::autoreleasepool(|| {
+            let path = super::PipelineCache::temp_path();
+            let url = super::PipelineCache::file_url(&path)?;
+            let serialized = cache.archive.lock().serialize_to_url(url);
+            let data = match serialized {
+                Ok(true) => std::fs::read(&path).ok(),
+                Ok(false) => None,
+                Err(e) => {
+                    log::warn!("Failed to serialize pipeline cache: {e}");
+                    None
+                }
+            };
+            let _ = std::fs::remove_file(&path);
+            data
+        })
     }
-    unsafe fn destroy_pipeline_cache(&self, _: super::PipelineCache) {}
 
     unsafe fn create_query_set(
         &self,
@@ -1442,6 +1863,7 @@ impl crate::Device for super::Device {
                     Ok(super::QuerySet {
                         raw_buffer,
                         counter_sample_buffer: None,
+                        statistics_pipeline: None,
                         ty: desc.ty,
                     })
                 }
@@ -1450,43 +1872,47 @@ impl crate::Device for super::Device {
                     let device = self.shared.device.lock();
                     let destination_buffer = device.new_buffer(size, MTLResourceOptions::empty());
 
-                    let csb_desc = metal::CounterSampleBufferDescriptor::new();
-                    csb_desc.set_storage_mode(MTLStorageMode::Shared);
-                    csb_desc.set_sample_count(desc.count as _);
-                    if let Some(label) = desc.label {
-                        csb_desc.set_label(label);
-                    }
-
-                    let counter_sets = device.counter_sets();
-                    let timestamp_counter =
-                        match counter_sets.iter().find(|cs| cs.name() == "timestamp") {
-                            Some(counter) => counter,
-                            None => {
-                                log::error!("Failed to obtain timestamp counter set.");
-                                return Err(crate::DeviceError::Unexpected);
-                            }
-                        };
-                    csb_desc.set_counter_set(timestamp_counter);
-
-                    let counter_sample_buffer =
-                        match device.new_counter_sample_buffer_with_descriptor(&csb_desc) {
-                            Ok(buffer) => buffer,
-                            Err(err) => {
-                                log::error!("Failed to create counter sample buffer: {:?}", err);
-                                return Err(crate::DeviceError::Unexpected);
-                            }
-                        };
+                    let counter_sample_buffer = Self::create_counter_sample_buffer(
+                        &device,
+                        "timestamp",
+                        desc.count as _,
+                        desc.label,
+                    )?;
 
                     self.counters.query_sets.add(1);
 
                     Ok(super::QuerySet {
                         raw_buffer: destination_buffer,
                         counter_sample_buffer: Some(counter_sample_buffer),
+                        statistics_pipeline: None,
                         ty: desc.ty,
                     })
                 }
-                _ => {
-                    todo!()
+                wgt::QueryType::PipelineStatistics(_) => {
+                    // Each query samples the counters at its beginning and end.
+                    let sample_count = desc.count as u64 * 2;
+                    let device = self.shared.device.lock();
+                    let resolve_buffer = device.new_buffer(
+                        sample_count * conv::STATISTIC_COUNTER_COUNT * crate::QUERY_SIZE,
+                        MTLResourceOptions::StorageModePrivate,
+                    );
+
+                    let counter_sample_buffer = Self::create_counter_sample_buffer(
+                        &device,
+                        "statistic",
+                        sample_count,
+                        desc.label,
+                    )?;
+                    let statistics_pipeline = self.pipeline_statistics_pipeline(&device)?;
+
+                    self.counters.query_sets.add(1);
+
+                    Ok(super::QuerySet {
+                        raw_buffer: resolve_buffer,
+                        counter_sample_buffer: Some(counter_sample_buffer),
+                        statistics_pipeline: Some(statistics_pipeline),
+                        ty: desc.ty,
+                    })
                 }
             }
         })
@@ -1510,6 +1936,35 @@ impl crate::Device for super::Device {
         })
     }
 
+    unsafe fn create_shared_fence(&self) -> DeviceResult<super::Fence> {
+        // Fences are backed by shared events whenever the feature is available.
+        unsafe { self.create_fence() }
+    }
+
+    unsafe fn export_fence(&self, fence: &super::Fence) -> DeviceResult<wgt::SharedFenceHandle> {
+        let Some(ref shared_event) = fence.shared_event else {
+            crate::hal_usage_error("only fences with a shared event can be exported")
+        };
+        Ok(wgt::SharedFenceHandle::MetalSharedEvent(
+            shared_event.as_ptr().cast(),
+        ))
+    }
+
+    unsafe fn import_fence(&self, handle: wgt::SharedFenceHandle) -> DeviceResult<super::Fence> {
+        use metal::foreign_types::ForeignTypeRef as _;
+
+        let wgt::SharedFenceHandle::MetalSharedEvent(raw) = handle else {
+            crate::hal_usage_error("Metal devices can only import shared events")
+        };
+        self.counters.fences.add(1);
+        let shared_event = unsafe { metal::SharedEventRef::from_ptr(raw.cast()) }.to_owned();
+        Ok(super::Fence {
+            completed_value: Arc::new(atomic::AtomicU64::new(0)),
+            pending_command_buffers: Vec::new(),
+            shared_event: Some(shared_event),
+        })
+    }
+
     unsafe fn destroy_fence(&self, _fence: super::Fence) {
         self.counters.fences.sub(1);
     }
@@ -1520,7 +1975,9 @@ impl crate::Device for super::Device {
             if cmd_buf.status() == MTLCommandBufferStatus::Completed {
                 max_value = value;
             }
+            self.check_command_buffer(cmd_buf)?;
         }
+        self.poll_debug_printf();
         Ok(max_value)
     }
     unsafe fn wait(
@@ -1530,6 +1987,7 @@ impl crate::Device for super::Device {
         timeout_ms: u32,
     ) -> DeviceResult<bool> {
         if wait_value <= fence.completed_value.load(atomic::Ordering::Acquire) {
+            self.poll_debug_printf();
             return Ok(true);
         }
 
@@ -1548,8 +2006,10 @@ impl crate::Device for super::Device {
         let start = time::Instant::now();
         loop {
             if let MTLCommandBufferStatus::Completed = cmd_buf.status() {
+                self.poll_debug_printf();
                 return Ok(true);
             }
+            self.check_command_buffer(cmd_buf)?;
             if start.elapsed().as_millis() >= timeout_ms as u128 {
                 return Ok(false);
             }
@@ -1606,6 +2066,13 @@ impl crate::Device for super::Device {
         unimplemented!()
     }
 
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        _data: &[u8],
+    ) -> Option<wgt::BufferAddress> {
+        unimplemented!()
+    }
+
     fn tlas_instance_to_bytes(&self, _instance: TlasInstance) -> Vec<u8> {
         unimplemented!()
     }
@@ -1619,4 +2086,22 @@ impl crate::Device for super::Device {
 
         Ok(())
     }
+
+    fn lost_reason(&self) -> wgt::DeviceLostReason {
+        self.command_buffer_error
+            .lock()
+            .as_ref()
+            .map_or(wgt::DeviceLostReason::Unknown, |error| error.lost_reason())
+    }
+
+    fn fault_info(&self) -> Option<String> {
+        self.command_buffer_error
+            .lock()
+            .as_ref()
+            .map(|error| error.to_string())
+    }
+
+    fn take_validation_messages(&self) -> Vec<String> {
+        core::mem::take(&mut *self.validation_messages.lock())
+    }
 }
@@ -0,0 +1,118 @@
+//! Importing `IOSurface`s as Metal textures.
+//!
+//! `IOSurface`s are how AVFoundation, CoreVideo and other processes hand out
+//! GPU-accessible images, so wrapping one in a texture lets us read from and
+//! render to them without a copy.
+
+use core::ffi::c_void;
+
+use metal::{MTLStorageMode, MTLTextureType, foreign_types::ForeignType as _};
+use objc::{msg_send, sel, sel_impl};
+
+type IOSurfaceRef = *mut c_void;
+
+#[link(name = "IOSurface", kind = "framework")]
+unsafe extern "C" {
+    fn IOSurfaceGetWidth(buffer: IOSurfaceRef) -> usize;
+    fn IOSurfaceGetHeight(buffer: IOSurfaceRef) -> usize;
+    fn IOSurfaceGetPlaneCount(buffer: IOSurfaceRef) -> usize;
+    fn IOSurfaceGetWidthOfPlane(buffer: IOSurfaceRef, plane_index: usize) -> usize;
+    fn IOSurfaceGetHeightOfPlane(buffer: IOSurfaceRef, plane_index: usize) -> usize;
+}
+
+/// Returns the size in texels of `plane` of `io_surface`, or `None` if it has no
+/// such plane.
+///
+/// Surfaces that aren't planar have a single plane, `0`, covering the whole surface.
+///
+/// # Safety
+///
+/// - `io_surface` must be a valid `IOSurfaceRef`.
+pub unsafe fn io_surface_plane_size(io_surface: *mut c_void, plane: u32) -> Option<(u32, u32)> {
+    let plane = plane as usize;
+    let (width, height) = unsafe {
+        match IOSurfaceGetPlaneCount(io_surface) {
+            0 if plane == 0 => (
+                IOSurfaceGetWidth(io_surface),
+                IOSurfaceGetHeight(io_surface),
+            ),
+            count if plane < count => (
+                IOSurfaceGetWidthOfPlane(io_surface, plane),
+                IOSurfaceGetHeightOfPlane(io_surface, plane),
+            ),
+            _ => return None,
+        }
+    };
+    Some((width.try_into().ok()?, height.try_into().ok()?))
+}
+
+impl super::Device {
+    /// Creates a texture that aliases `plane` of `io_surface`.
+    ///
+    /// The texture retains the surface, so the caller may release its own reference
+    /// once this returns.
+    ///
+    /// # Safety
+    ///
+    /// - `io_surface` must be a valid `IOSurfaceRef`.
+    /// - `desc` must describe a 2D texture with a single mip level, array layer and
+    ///   sample, whose size matches [`io_surface_plane_size`] for `plane`.
+    /// - `desc.format` must be compatible with the pixel format of `plane`.
+    pub unsafe fn texture_from_io_surface(
+        &self,
+        io_surface: *mut c_void,
+        plane: u32,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let mtl_format = self.shared.private_caps.map_format(desc.format);
+
+        objc::rc::autoreleasepool(|| {
+            let descriptor = metal::TextureDescriptor::new();
+            descriptor.set_texture_type(MTLTextureType::D2);
+            descriptor.set_width(desc.size.width as u64);
+            descriptor.set_height(desc.size.height as u64);
+            descriptor.set_pixel_format(mtl_format);
+            descriptor.set_usage(super::conv::map_texture_usage(desc.format, desc.usage));
+            // Surfaces live in system memory, so they can't be private to the GPU.
+            descriptor.set_storage_mode(if cfg!(target_os = "macos") {
+                MTLStorageMode::Managed
+            } else {
+                MTLStorageMode::Shared
+            });
+
+            let raw = {
+                let device = self.shared.device.lock();
+                // Methods starting with `new` return a retained object.
+                let ptr: *mut metal::MTLTexture = unsafe {
+                    msg_send![
+                        device.as_ref(),
+                        newTextureWithDescriptor: descriptor.as_ref()
+                        iosurface: io_surface
+                        plane: plane as u64
+                    ]
+                };
+                if ptr.is_null() {
+                    log::error!(
+                        "Metal refused to create a texture from plane {plane} of an IOSurface"
+                    );
+                    return Err(crate::DeviceError::Unexpected);
+                }
+                unsafe { metal::Texture::from_ptr(ptr) }
+            };
+            if let Some(label) = desc.label {
+                raw.set_label(label);
+            }
+
+            self.counters.textures.add(1);
+
+            Ok(super::Texture {
+                raw,
+                format: desc.format,
+                raw_type: MTLTextureType::D2,
+                mip_levels: 1,
+                array_layers: 1,
+                copy_size: desc.copy_extent(),
+            })
+        })
+    }
+}
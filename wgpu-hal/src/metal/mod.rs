@@ -22,9 +22,11 @@ mod adapter;
 mod command;
 mod conv;
 mod device;
+mod io_surface;
 mod layer_observer;
 mod surface;
 mod time;
+mod video;
 
 use alloc::{borrow::ToOwned as _, string::String, sync::Arc, vec::Vec};
 use core::{fmt, iter, ops, ptr::NonNull, sync::atomic};
@@ -39,8 +41,12 @@ use metal::{
     MTLTriangleFillMode, MTLWinding, foreign_types::ForeignTypeRef as _,
 };
 use naga::FastHashMap;
+use objc::{class, msg_send, sel, sel_impl};
 use parking_lot::{Mutex, RwLock};
 
+pub use io_surface::io_surface_plane_size;
+pub use video::{DecodedFrame, VideoDecodeError, VideoDecoder};
+
 #[derive(Clone, Debug)]
 pub struct Api;
 
@@ -63,6 +69,7 @@ impl crate::Api for Api {
     type Sampler = Sampler;
     type QuerySet = QuerySet;
     type Fence = Fence;
+    type MemoryHeap = MemoryHeap;
 
     type BindGroupLayout = BindGroupLayout;
     type BindGroup = BindGroup;
@@ -70,6 +77,7 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type RayTracingPipeline = RayTracingPipeline;
     type PipelineCache = PipelineCache;
 
     type AccelerationStructure = AccelerationStructure;
@@ -87,10 +95,12 @@ crate::impl_dyn_resource!(
     Device,
     Fence,
     Instance,
+    MemoryHeap,
     PipelineCache,
     PipelineLayout,
     QuerySet,
     Queue,
+    RayTracingPipeline,
     RenderPipeline,
     Sampler,
     ShaderModule,
@@ -100,7 +110,10 @@ crate::impl_dyn_resource!(
     TextureView
 );
 
-pub struct Instance {}
+pub struct Instance {
+    /// Whether [`wgt::InstanceFlags::GPU_BASED_VALIDATION`] is set.
+    shader_validation: bool,
+}
 
 impl Instance {
     pub fn create_surface_from_layer(&self, layer: &metal::MetalLayerRef) -> Surface {
@@ -111,11 +124,16 @@ impl Instance {
 impl crate::Instance for Instance {
     type A = Api;
 
-    unsafe fn init(_desc: &crate::InstanceDescriptor) -> Result<Self, crate::InstanceError> {
+    unsafe fn init(desc: &crate::InstanceDescriptor) -> Result<Self, crate::InstanceError> {
         profiling::scope!("Init Metal Backend");
         // We do not enable metal validation based on the validation flags as it affects the entire
         // process. Instead, we enable the validation inside the test harness itself in tests/src/native.rs.
-        Ok(Instance {})
+        // Shader validation can be enabled per pipeline though.
+        Ok(Instance {
+            shader_validation: desc
+                .flags
+                .contains(wgt::InstanceFlags::GPU_BASED_VALIDATION),
+        })
     }
 
     unsafe fn create_surface(
@@ -147,7 +165,8 @@ impl crate::Instance for Instance {
             .into_iter()
             .map(|dev| {
                 let name = dev.name().into();
-                let shared = AdapterShared::new(dev);
+                let mut shared = AdapterShared::new(dev);
+                shared.settings.shader_validation = self.shader_validation;
                 crate::ExposedAdapter {
                     info: wgt::AdapterInfo {
                         name,
@@ -160,6 +179,8 @@ impl crate::Instance for Instance {
                     },
                     features: shared.private_caps.features(),
                     capabilities: shared.private_caps.capabilities(),
+                    // Metal has no workarounds that can be toggled.
+                    toggles: wgt::Toggles::empty(),
                     adapter: Adapter::new(Arc::new(shared)),
                 }
             })
@@ -213,6 +234,7 @@ struct PrivateCapabilities {
     low_power: bool,
     headless: bool,
     layered_rendering: bool,
+    viewport_arrays: bool,
     function_specialization: bool,
     depth_clip_mode: bool,
     texture_cube_array: bool,
@@ -285,6 +307,10 @@ struct PrivateCapabilities {
     can_set_maximum_drawables_count: bool,
     can_set_display_sync: bool,
     can_set_next_drawable_timeout: bool,
+    can_set_extended_dynamic_range: bool,
+    supports_hdr10_color_space: bool,
+    supports_presentation_timing: bool,
+    supports_deferred_store_action: bool,
     supports_arrays_of_textures: bool,
     supports_arrays_of_textures_write: bool,
     supports_mutability: bool,
@@ -293,11 +319,18 @@ struct PrivateCapabilities {
     supports_shader_primitive_index: bool,
     has_unified_memory: Option<bool>,
     timestamp_query_support: TimestampQuerySupport,
+    supports_pipeline_statistics: bool,
     supports_simd_scoped_operations: bool,
     int64: bool,
     int64_atomics: bool,
     float_atomics: bool,
     supports_shared_event: bool,
+    supports_memory_info: bool,
+    supports_memoryless_storage: bool,
+    supports_placement_heaps: bool,
+    supports_depth_resolve_filters: bool,
+    supports_framebuffer_fetch: bool,
+    pipeline_cache_validation_key: [u8; 16],
 }
 
 #[derive(Clone, Debug)]
@@ -312,12 +345,16 @@ struct PrivateDisabilities {
 #[derive(Debug)]
 struct Settings {
     retain_command_buffer_references: bool,
+    /// Whether pipelines are created with shader validation enabled, see
+    /// [`wgt::InstanceFlags::GPU_BASED_VALIDATION`].
+    shader_validation: bool,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             retain_command_buffer_references: true,
+            shader_validation: false,
         }
     }
 }
@@ -328,6 +365,9 @@ struct AdapterShared {
     private_caps: PrivateCapabilities,
     settings: Settings,
     presentation_timer: time::PresentationTimer,
+    /// Resolves pipeline statistics queries, compiled by the first
+    /// [`QuerySet`] that needs it and shared by all later ones.
+    statistics_pipeline: Mutex<Option<metal::ComputePipelineState>>,
 }
 
 unsafe impl Send for AdapterShared {}
@@ -344,6 +384,7 @@ impl AdapterShared {
             device: Mutex::new(device),
             settings: Settings::default(),
             presentation_timer: time::PresentationTimer::new(),
+            statistics_pipeline: Mutex::new(None),
         }
     }
 }
@@ -355,6 +396,10 @@ pub struct Adapter {
 pub struct Queue {
     raw: Arc<Mutex<metal::CommandQueue>>,
     timestamp_period: f32,
+    /// Shared events, with values, to signal after the next submission.
+    ///
+    /// These are added by [`crate::Queue::signal_fence`].
+    pending_signals: Mutex<Vec<(metal::SharedEvent, crate::FenceValue)>>,
 }
 
 unsafe impl Send for Queue {}
@@ -365,6 +410,7 @@ impl Queue {
         Self {
             raw: Arc::new(Mutex::new(raw)),
             timestamp_period,
+            pending_signals: Mutex::new(Vec::new()),
         }
     }
 
@@ -377,12 +423,119 @@ pub struct Device {
     shared: Arc<AdapterShared>,
     features: wgt::Features,
     counters: Arc<wgt::HalCounters>,
+    /// The error of the first command buffer found to have failed, reported when
+    /// the device is lost.
+    command_buffer_error: Mutex<Option<CommandBufferError>>,
+    /// Errors of command buffers that failed while shader validation was enabled,
+    /// see [`crate::Device::take_validation_messages`].
+    validation_messages: Mutex<Vec<String>>,
+    /// Present if [`wgt::Features::SHADER_DEBUG_PRINTF`] is enabled.
+    debug_printf: Option<DebugPrintfBuffer>,
+}
+
+/// The buffer that shaders write [`wgt::Features::SHADER_DEBUG_PRINTF`] messages to.
+struct DebugPrintfBuffer {
+    /// A shared storage buffer of [`crate::auxil::debug_printf::BUFFER_SIZE`] bytes.
+    raw: metal::Buffer,
+    reader: crate::auxil::debug_printf::DebugPrintf,
+}
+
+impl DebugPrintfBuffer {
+    fn new(device: &metal::DeviceRef) -> Self {
+        let raw = device.new_buffer(
+            crate::auxil::debug_printf::BUFFER_SIZE,
+            metal::MTLResourceOptions::StorageModeShared,
+        );
+        raw.set_label("debug printf buffer");
+        let words = NonNull::new(raw.contents().cast()).unwrap();
+        Self {
+            // SAFETY: shared storage buffers stay mapped for their whole
+            // lifetime, and no pipeline can use this one yet.
+            reader: unsafe { crate::auxil::debug_printf::DebugPrintf::new(words) },
+            raw,
+        }
+    }
+}
+
+/// The `NSError` of a command buffer with the `MTLCommandBufferStatusError` status.
+#[derive(Debug)]
+struct CommandBufferError {
+    /// The `MTLCommandBufferError` code.
+    code: metal::NSInteger,
+    label: String,
+    description: String,
+}
+
+impl CommandBufferError {
+    /// Returns the error of `cmd_buf`, if it failed.
+    fn from_command_buffer(cmd_buf: &metal::CommandBufferRef) -> Option<Self> {
+        if cmd_buf.status() != MTLCommandBufferStatus::Error {
+            return None;
+        }
+        let error: *mut objc::runtime::Object = unsafe { msg_send![cmd_buf, error] };
+        if error.is_null() {
+            return None;
+        }
+        let code: metal::NSInteger = unsafe { msg_send![error, code] };
+        let description: *mut objc::runtime::Object =
+            unsafe { msg_send![error, localizedDescription] };
+        let description: *const core::ffi::c_char = unsafe { msg_send![description, UTF8String] };
+        let description = if description.is_null() {
+            String::new()
+        } else {
+            unsafe { core::ffi::CStr::from_ptr(description) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        Some(Self {
+            code,
+            label: cmd_buf.label().to_owned(),
+            description,
+        })
+    }
+
+    fn lost_reason(&self) -> wgt::DeviceLostReason {
+        // Values of `MTLCommandBufferError`.
+        match self.code {
+            2 => wgt::DeviceLostReason::Hung,
+            8 => wgt::DeviceLostReason::OutOfMemory,
+            11 => wgt::DeviceLostReason::Removed,
+            _ => wgt::DeviceLostReason::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for CommandBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Command buffer {:?} failed with MTLCommandBufferError {}: {}",
+            self.label, self.code, self.description
+        )
+    }
+}
+
+/// State of [`wgt::Features::PRESENTATION_TIMING`] for a surface.
+#[derive(Debug, Default)]
+struct PresentationTiming {
+    /// Whether the surface is configured on a device with the feature enabled.
+    enabled: bool,
+    /// Counts the configurations of the surface, so that presented handlers of
+    /// a previous configuration are ignored.
+    generation: u64,
+    /// The ID given to the next present.
+    next_present_id: u64,
+    /// When the next present should be displayed, in seconds of `CACurrentMediaTime`.
+    next_present_time: Option<f64>,
+    /// Statistics of presents that weren't reported yet.
+    statistics: Vec<wgt::PresentationStatistics>,
 }
 
 pub struct Surface {
     render_layer: Mutex<metal::MetalLayer>,
     swapchain_format: RwLock<Option<wgt::TextureFormat>>,
     extent: RwLock<wgt::Extent3d>,
+    presentation_timing: Arc<Mutex<PresentationTiming>>,
     // Useful for UI-intensive applications that are sensitive to
     // window resizing.
     pub present_with_transaction: bool,
@@ -449,6 +602,10 @@ impl crate::Queue for Queue {
                     .pending_command_buffers
                     .push((signal_value, raw.to_owned()));
 
+                // Signal shared fences before the fence that tracks completion.
+                for (shared_event, value) in self.pending_signals.lock().drain(..) {
+                    raw.encode_signal_event(&shared_event, value);
+                }
                 if let Some(shared_event) = signal_fence.shared_event.as_ref() {
                     raw.encode_signal_event(shared_event, signal_value);
                 }
@@ -471,24 +628,67 @@ impl crate::Queue for Queue {
     }
     unsafe fn present(
         &self,
-        _surface: &Surface,
+        surface: &Surface,
         texture: SurfaceTexture,
+        _damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
         let queue = &self.raw.lock();
         objc::rc::autoreleasepool(|| {
+            let mut timing = surface.presentation_timing.lock();
+            let present_time = if timing.enabled {
+                let present_id = timing.next_present_id;
+                timing.next_present_id += 1;
+                let generation = timing.generation;
+                let presentation_timing = Arc::clone(&surface.presentation_timing);
+                let block = block::ConcreteBlock::new(move |drawable: &metal::DrawableRef| {
+                    // This is zero if the drawable was never displayed.
+                    let presented_time = drawable.presented_time();
+                    let mut timing = presentation_timing.lock();
+                    if timing.generation == generation {
+                        timing.statistics.push(wgt::PresentationStatistics {
+                            present_id,
+                            present_time: if presented_time > 0.0 {
+                                wgt::PresentationTimestamp((presented_time * 1e9) as u128)
+                            } else {
+                                wgt::PresentationTimestamp::INVALID_TIMESTAMP
+                            },
+                            refresh_interval: None,
+                            dropped: presented_time <= 0.0,
+                        });
+                    }
+                })
+                .copy();
+                texture.drawable.add_presented_handler(&block);
+                timing.next_present_time.take()
+            } else {
+                None
+            };
+            drop(timing);
+
+            let drawable: &metal::DrawableRef = &texture.drawable;
             let command_buffer = queue.new_command_buffer();
             command_buffer.set_label("(wgpu internal) Present");
 
             // https://developer.apple.com/documentation/quartzcore/cametallayer/1478157-presentswithtransaction?language=objc
             if !texture.present_with_transaction {
-                command_buffer.present_drawable(&texture.drawable);
+                match present_time {
+                    Some(time) => {
+                        let () = msg_send![command_buffer, presentDrawable: drawable atTime: time];
+                    }
+                    None => command_buffer.present_drawable(drawable),
+                }
             }
 
             command_buffer.commit();
 
             if texture.present_with_transaction {
                 command_buffer.wait_until_scheduled();
-                texture.drawable.present();
+                match present_time {
+                    Some(time) => {
+                        let () = msg_send![drawable, presentAtTime: time];
+                    }
+                    None => drawable.present(),
+                }
             }
         });
         Ok(())
@@ -497,6 +697,78 @@ impl crate::Queue for Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         self.timestamp_period
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let queue = self.raw.lock();
+        let device = queue.device();
+        // `sampleTimestamps:gpuTimestamp:` needs macOS 10.15 or iOS 14.
+        let supported: objc::runtime::BOOL =
+            unsafe { msg_send![device, respondsToSelector: sel!(sampleTimestamps:gpuTimestamp:)] };
+        if supported != objc::runtime::YES {
+            return None;
+        }
+        let (mut cpu_timestamp, mut gpu_timestamp) = (0, 0);
+        device.sample_timestamps(&mut cpu_timestamp, &mut gpu_timestamp);
+        Some(wgt::TimestampCalibration {
+            gpu_timestamp,
+            cpu_timestamp,
+            timestamp_period: self.timestamp_period,
+        })
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        _buffer_bindings: &[crate::SparseBufferBinding<Buffer, MemoryHeap>],
+        _texture_bindings: &[crate::SparseTextureBinding<Texture, MemoryHeap>],
+    ) -> Result<(), crate::DeviceError> {
+        unreachable!()
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        if value <= fence.completed_value.load(atomic::Ordering::Acquire) {
+            return Ok(());
+        }
+
+        match fence.shared_event {
+            Some(ref shared_event) => objc::rc::autoreleasepool(|| {
+                let queue = self.raw.lock();
+                let raw = queue.new_command_buffer_with_unretained_references();
+                raw.set_label("(wgpu internal) Wait");
+                raw.encode_wait_for_event(shared_event, value);
+                raw.commit();
+            }),
+            // Without shared events, wait on the CPU for the command buffer
+            // that signals the fence.
+            None => {
+                let cmd_buf = fence
+                    .pending_command_buffers
+                    .iter()
+                    .find(|&&(pending_value, _)| pending_value >= value)
+                    .map(|(_, cmd_buf)| cmd_buf)
+                    .ok_or(crate::DeviceError::Lost)?;
+                cmd_buf.wait_until_completed();
+            }
+        }
+        Ok(())
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        let Some(ref shared_event) = fence.shared_event else {
+            crate::hal_usage_error("only fences with a shared event can be signalled")
+        };
+        self.pending_signals
+            .lock()
+            .push((shared_event.clone(), value));
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -617,7 +889,12 @@ impl<T> ops::Index<naga::ShaderStage> for MultiStageData<T> {
             naga::ShaderStage::Vertex => &self.vs,
             naga::ShaderStage::Fragment => &self.fs,
             naga::ShaderStage::Compute => &self.cs,
-            naga::ShaderStage::Task | naga::ShaderStage::Mesh => unreachable!(),
+            naga::ShaderStage::Task
+            | naga::ShaderStage::Mesh
+            | naga::ShaderStage::RayGeneration
+            | naga::ShaderStage::Miss
+            | naga::ShaderStage::ClosestHit
+            | naga::ShaderStage::AnyHit => unreachable!(),
         }
     }
 }
@@ -816,6 +1093,9 @@ struct PipelineStageInfo {
     /// See `device::CompiledShader::sized_bindings` for more details.
     sizes_slot: Option<naga::back::msl::Slot>,
 
+    /// The buffer argument table index at which we pass the debug printf buffer.
+    debug_printf_slot: Option<naga::back::msl::Slot>,
+
     /// Bindings of all WGSL `storage` globals that contain runtime-sized arrays.
     ///
     /// See `device::CompiledShader::sized_bindings` for more details.
@@ -829,6 +1109,7 @@ impl PipelineStageInfo {
     fn clear(&mut self) {
         self.push_constants = None;
         self.sizes_slot = None;
+        self.debug_printf_slot = None;
         self.sized_bindings.clear();
         self.vertex_buffer_mappings.clear();
     }
@@ -836,6 +1117,7 @@ impl PipelineStageInfo {
     fn assign_from(&mut self, other: &Self) {
         self.push_constants = other.push_constants;
         self.sizes_slot = other.sizes_slot;
+        self.debug_printf_slot = other.debug_printf_slot;
         self.sized_bindings.clear();
         self.sized_bindings.extend_from_slice(&other.sized_bindings);
         self.vertex_buffer_mappings.clear();
@@ -886,6 +1168,9 @@ pub struct QuerySet {
     raw_buffer: metal::Buffer,
     //Metal has a custom buffer for counters.
     counter_sample_buffer: Option<metal::CounterSampleBuffer>,
+    /// Computes the pipeline statistics of each query from the counters sampled
+    /// at its beginning and end.
+    statistics_pipeline: Option<metal::ComputePipelineState>,
     ty: wgt::QueryType,
 }
 
@@ -894,6 +1179,17 @@ impl crate::DynQuerySet for QuerySet {}
 unsafe impl Send for QuerySet {}
 unsafe impl Sync for QuerySet {}
 
+#[derive(Debug)]
+pub struct MemoryHeap {
+    raw: metal::Heap,
+    size: wgt::BufferAddress,
+}
+
+impl crate::DynMemoryHeap for MemoryHeap {}
+
+unsafe impl Send for MemoryHeap {}
+unsafe impl Sync for MemoryHeap {}
+
 #[derive(Debug)]
 pub struct Fence {
     completed_value: Arc<atomic::AtomicU64>,
@@ -944,6 +1240,10 @@ struct Temp {
 struct CommandState {
     blit: Option<metal::BlitCommandEncoder>,
     render: Option<metal::RenderCommandEncoder>,
+    /// The encoder of a render pass begun with
+    /// [`crate::RenderPassDescriptor::parallel`], which creates the render
+    /// encoders of its parts.
+    parallel_render: Option<metal::ParallelRenderCommandEncoder>,
     compute: Option<metal::ComputeCommandEncoder>,
     raw_primitive_type: MTLPrimitiveType,
     index: Option<IndexState>,
@@ -978,6 +1278,35 @@ struct CommandState {
 
     /// Timer query that should be executed when the next pass starts.
     pending_timer_queries: Vec<(QuerySet, u32)>,
+
+    /// Metal sets all viewports at once, so keep the ones set so far in the pass.
+    viewports: [metal::MTLViewport; wgt::MAX_VIEWPORTS as usize],
+    /// One past the highest index of the viewports set in the pass.
+    viewport_count: usize,
+    /// Scissor rectangle of every viewport.
+    scissor: metal::MTLScissorRect,
+
+    /// How to end the current render encoder, if it was begun with deferred
+    /// store actions for a suspended pass.
+    deferred_render: Option<DeferredRender>,
+    /// The render encoder of a suspended pass, kept open for the pass resuming
+    /// it.
+    suspended_render: Option<(metal::RenderCommandEncoder, DeferredRender)>,
+}
+
+/// A render encoder begun with [`metal::MTLStoreAction::Unknown`], which stays
+/// open while its pass is suspended.
+struct DeferredRender {
+    /// The store action of each color attachment, by index.
+    color_store_actions:
+        ArrayVec<(metal::NSUInteger, metal::MTLStoreAction), { crate::MAX_COLOR_ATTACHMENTS }>,
+    depth_store_action: Option<metal::MTLStoreAction>,
+    stencil_store_action: Option<metal::MTLStoreAction>,
+    /// The visibility result buffer of the encoder, which a pass must use to
+    /// be continued on it.
+    occlusion_query_buffer: Option<metal::Buffer>,
+    /// Whether the current pass on the encoder is suspended rather than ended.
+    suspending: bool,
 }
 
 pub struct CommandEncoder {
@@ -987,6 +1316,9 @@ pub struct CommandEncoder {
     state: CommandState,
     temp: Temp,
     counters: Arc<wgt::HalCounters>,
+    /// The buffer of [`Device::debug_printf`], bound with each pipeline that
+    /// has a [`PipelineStageInfo::debug_printf_slot`].
+    debug_printf_buffer: Option<metal::Buffer>,
 }
 
 impl fmt::Debug for CommandEncoder {
@@ -1012,11 +1344,51 @@ unsafe impl Send for CommandBuffer {}
 unsafe impl Sync for CommandBuffer {}
 
 #[derive(Debug)]
-pub struct PipelineCache;
+pub struct PipelineCache {
+    archive: Mutex<metal::BinaryArchive>,
+    /// The file the archive was loaded from.
+    ///
+    /// Metal may keep reading from it for as long as the archive is alive.
+    file: Option<std::path::PathBuf>,
+}
 
 impl crate::DynPipelineCache for PipelineCache {}
 
+impl PipelineCache {
+    /// Return a fresh path in the temporary directory.
+    ///
+    /// Metal only reads and writes binary archives through file URLs, so cache
+    /// data is passed to and from it through temporary files.
+    fn temp_path() -> std::path::PathBuf {
+        static COUNTER: atomic::AtomicU32 = atomic::AtomicU32::new(0);
+        std::env::temp_dir().join(format!(
+            "wgpu-pipeline-cache-{}-{}.metallib",
+            std::process::id(),
+            COUNTER.fetch_add(1, atomic::Ordering::Relaxed)
+        ))
+    }
+
+    /// Create a `file://` URL for `path`.
+    ///
+    /// The URL is autoreleased, so this must be called inside an autorelease pool.
+    fn file_url<'a>(path: &std::path::Path) -> Option<&'a metal::URLRef> {
+        let path = std::ffi::CString::new(path.to_str()?).ok()?;
+        unsafe {
+            let path: *mut objc::runtime::Object =
+                msg_send![class!(NSString), stringWithUTF8String: path.as_ptr()];
+            let url: *mut objc::runtime::Object = msg_send![class!(NSURL), fileURLWithPath: path];
+            (!url.is_null()).then(|| metal::URLRef::from_ptr(url.cast()))
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct AccelerationStructure;
 
 impl crate::DynAccelerationStructure for AccelerationStructure {}
+
+/// Ray tracing pipelines are not supported on this backend.
+#[derive(Debug)]
+pub struct RayTracingPipeline;
+
+impl crate::DynRayTracingPipeline for RayTracingPipeline {}
@@ -1,8 +1,11 @@
 #![allow(clippy::let_unit_value)] // `let () =` being used to constrain result type
 
-use alloc::borrow::ToOwned as _;
-use core::mem::ManuallyDrop;
-use core::ptr::NonNull;
+use alloc::{borrow::ToOwned as _, vec::Vec};
+use core::{
+    ffi,
+    mem::ManuallyDrop,
+    ptr::{self, NonNull},
+};
 
 use core_graphics_types::{
     base::CGFloat,
@@ -12,7 +15,7 @@ use metal::{MTLTextureType, foreign_types::ForeignType};
 use objc::{
     class, msg_send,
     rc::{StrongPtr, autoreleasepool},
-    runtime::{BOOL, NO, Object, YES},
+    runtime::{BOOL, Class, NO, Object, YES},
     sel, sel_impl,
 };
 use parking_lot::{Mutex, RwLock};
@@ -22,12 +25,103 @@ use crate::metal::layer_observer::new_observer_layer;
 #[link(name = "QuartzCore", kind = "framework")]
 unsafe extern "C" {}
 
+type CFStringRef = *const ffi::c_void;
+type CGColorSpaceRef = *mut ffi::c_void;
+
+/// `kCFStringEncodingUTF8`
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+#[link(name = "CoreFoundation", kind = "framework")]
+unsafe extern "C" {
+    fn CFStringCreateWithCString(
+        allocator: *const ffi::c_void,
+        c_str: *const ffi::c_char,
+        encoding: u32,
+    ) -> CFStringRef;
+    fn CFRelease(cf: *const ffi::c_void);
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+unsafe extern "C" {
+    fn CGColorSpaceCreateWithName(name: CFStringRef) -> CGColorSpaceRef;
+    fn CGColorSpaceRelease(space: CGColorSpaceRef);
+}
+
+/// Creates the color space with the given name, which is also the value of the
+/// `kCGColorSpace*` constant of the same name. The constants themselves aren't
+/// linked against, as some of them don't exist on older OS versions.
+fn create_color_space(name: &ffi::CStr) -> CGColorSpaceRef {
+    unsafe {
+        let name = CFStringCreateWithCString(ptr::null(), name.as_ptr(), CF_STRING_ENCODING_UTF8);
+        let color_space = CGColorSpaceCreateWithName(name);
+        CFRelease(name);
+        color_space
+    }
+}
+
+/// Tags `layer` with the color space of `config`, opting in to EDR for extended
+/// color spaces.
+fn set_color_space(
+    layer: &metal::MetalLayerRef,
+    config: &crate::SurfaceConfiguration,
+) -> Result<(), crate::SurfaceError> {
+    let color_space_name = match config.color_space {
+        // Leave the layer untagged, as it has always been for SDR content.
+        wgt::SurfaceColorSpace::Srgb => None,
+        wgt::SurfaceColorSpace::ExtendedSrgb => Some(c"kCGColorSpaceExtendedSRGB"),
+        wgt::SurfaceColorSpace::ExtendedSrgbLinear => Some(c"kCGColorSpaceExtendedLinearSRGB"),
+        wgt::SurfaceColorSpace::Hdr10 => Some(c"kCGColorSpaceITUR_2100_PQ"),
+        wgt::SurfaceColorSpace::Auto => unreachable!(),
+    };
+    let color_space = match color_space_name {
+        Some(name) => {
+            let color_space = create_color_space(name);
+            if color_space.is_null() {
+                return Err(crate::SurfaceError::Other("color space is not supported"));
+            }
+            color_space
+        }
+        None => ptr::null_mut(),
+    };
+    let () = unsafe { msg_send![layer, setColorspace: color_space] };
+    if !color_space.is_null() {
+        unsafe { CGColorSpaceRelease(color_space) };
+    }
+
+    // opt-in to Metal EDR for extended color spaces
+    // EDR potentially more power used in display and more bandwidth, memory footprint.
+    let wants_edr = color_space_name.is_some();
+    if wants_edr != layer.wants_extended_dynamic_range_content() {
+        layer.set_wants_extended_dynamic_range_content(wants_edr);
+    }
+
+    if let Some(edr_metadata_class) = Class::get("CAEDRMetadata") {
+        autoreleasepool(|| {
+            let edr_metadata: *mut Object = match config.hdr_metadata {
+                Some(hdr_metadata) if config.color_space == wgt::SurfaceColorSpace::Hdr10 => unsafe {
+                    // Reference white of 100 nits, as used for HDR10 content.
+                    msg_send![
+                        edr_metadata_class,
+                        HDR10MetadataWithMinLuminance: hdr_metadata.min_luminance
+                        maxLuminance: hdr_metadata.max_luminance
+                        opticalOutputScale: 100.0f32
+                    ]
+                },
+                _ => ptr::null_mut(),
+            };
+            let () = unsafe { msg_send![layer, setEDRMetadata: edr_metadata] };
+        });
+    }
+    Ok(())
+}
+
 impl super::Surface {
     fn new(layer: metal::MetalLayer) -> Self {
         Self {
             render_layer: Mutex::new(layer),
             swapchain_format: RwLock::new(None),
             extent: RwLock::new(wgt::Extent3d::default()),
+            presentation_timing: Default::default(),
             present_with_transaction: false,
         }
     }
@@ -144,6 +238,16 @@ impl crate::Surface for super::Surface {
         let caps = &device.shared.private_caps;
         *self.swapchain_format.write() = Some(config.format);
         *self.extent.write() = config.extent;
+        {
+            let mut timing = self.presentation_timing.lock();
+            *timing = super::PresentationTiming {
+                enabled: device.features.contains(wgt::Features::PRESENTATION_TIMING),
+                generation: timing.generation + 1,
+                next_present_id: 1,
+                next_present_time: None,
+                statistics: Vec::new(),
+            };
+        }
 
         let render_layer = self.render_layer.lock();
         let framebuffer_only = config.usage == wgt::TextureUses::COLOR_TARGET;
@@ -164,12 +268,12 @@ impl crate::Surface for super::Surface {
         render_layer.set_device(&device_raw);
         render_layer.set_pixel_format(caps.map_format(config.format));
         render_layer.set_framebuffer_only(framebuffer_only);
-        render_layer.set_presents_with_transaction(self.present_with_transaction);
-        // opt-in to Metal EDR
-        // EDR potentially more power used in display and more bandwidth, memory footprint.
-        let wants_edr = config.format == wgt::TextureFormat::Rgba16Float;
-        if wants_edr != render_layer.wants_extended_dynamic_range_content() {
-            render_layer.set_wants_extended_dynamic_range_content(wants_edr);
+        // Layers presenting with a transaction are never displayed directly.
+        render_layer.set_presents_with_transaction(
+            self.present_with_transaction && !config.exclusive_fullscreen,
+        );
+        if caps.can_set_extended_dynamic_range {
+            set_color_space(&render_layer, config)?;
         }
 
         // this gets ignored on iOS for certain OS/device combinations (iphone5s iOS 10.3)
@@ -230,4 +334,23 @@ impl crate::Surface for super::Surface {
     }
 
     unsafe fn discard_texture(&self, _texture: super::SurfaceTexture) {}
+
+    unsafe fn set_target_present_time(&self, time: wgt::PresentationTimestamp) {
+        // Presentation timestamps are in nanoseconds of `mach_absolute_time`,
+        // like `CACurrentMediaTime` is in seconds.
+        self.presentation_timing.lock().next_present_time = Some(time.0 as f64 / 1e9);
+    }
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        let mut statistics = core::mem::take(&mut self.presentation_timing.lock().statistics);
+        // Presented handlers don't necessarily run in present order.
+        statistics.sort_by_key(|statistics| statistics.present_id);
+        Ok(statistics)
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        wgt::PresentationPath::Unknown
+    }
 }
@@ -0,0 +1,549 @@
+//! Decoding H.264 and HEVC video with VideoToolbox.
+//!
+//! Frames are decoded into `IOSurface`-backed NV12 pixel buffers, whose planes can
+//! be wrapped in textures with [`super::Device::texture_from_io_surface`] without a
+//! copy.
+
+use alloc::vec::Vec;
+use core::{ffi::c_void, ptr};
+
+type OSStatus = i32;
+type CFTypeRef = *const c_void;
+type CFAllocatorRef = *const c_void;
+type CFDictionaryRef = *const c_void;
+type CFStringRef = *const c_void;
+type CMFormatDescriptionRef = *mut c_void;
+type CMBlockBufferRef = *mut c_void;
+type CMSampleBufferRef = *mut c_void;
+type CVPixelBufferRef = *mut c_void;
+type VTDecompressionSessionRef = *mut c_void;
+
+/// `kCVPixelFormatType_420YpCbCr8BiPlanarVideoRange`, or `'420v'`.
+const PIXEL_FORMAT_NV12_VIDEO_RANGE: i32 = 0x3432_3076;
+const CF_NUMBER_SINT32_TYPE: isize = 3;
+const CM_BLOCK_BUFFER_ASSURE_MEMORY_NOW_FLAG: u32 = 1 << 0;
+/// `kVTVideoDecoderBadDataErr`
+const VT_VIDEO_DECODER_BAD_DATA_ERR: OSStatus = -12909;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CMTime {
+    value: i64,
+    timescale: i32,
+    flags: u32,
+    epoch: i64,
+}
+
+type VTDecompressionOutputCallback = unsafe extern "C" fn(
+    decompression_output_ref_con: *mut c_void,
+    source_frame_ref_con: *mut c_void,
+    status: OSStatus,
+    info_flags: u32,
+    image_buffer: CVPixelBufferRef,
+    presentation_time_stamp: CMTime,
+    presentation_duration: CMTime,
+);
+
+#[repr(C)]
+struct VTDecompressionOutputCallbackRecord {
+    decompression_output_callback: VTDecompressionOutputCallback,
+    decompression_output_ref_con: *mut c_void,
+}
+
+/// Opaque `CFDictionaryKeyCallBacks` and `CFDictionaryValueCallBacks`, only ever
+/// used by address.
+#[repr(C)]
+struct CFDictionaryCallBacks {
+    _private: [u8; 0],
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+unsafe extern "C" {
+    static kCFTypeDictionaryKeyCallBacks: CFDictionaryCallBacks;
+    static kCFTypeDictionaryValueCallBacks: CFDictionaryCallBacks;
+    static kCFBooleanTrue: CFTypeRef;
+
+    fn CFRetain(cf: CFTypeRef) -> CFTypeRef;
+    fn CFRelease(cf: CFTypeRef);
+    fn CFDictionaryCreate(
+        allocator: CFAllocatorRef,
+        keys: *const CFTypeRef,
+        values: *const CFTypeRef,
+        num_values: isize,
+        key_call_backs: *const CFDictionaryCallBacks,
+        value_call_backs: *const CFDictionaryCallBacks,
+    ) -> CFDictionaryRef;
+    fn CFNumberCreate(
+        allocator: CFAllocatorRef,
+        the_type: isize,
+        value_ptr: *const c_void,
+    ) -> CFTypeRef;
+}
+
+#[link(name = "CoreMedia", kind = "framework")]
+unsafe extern "C" {
+    fn CMVideoFormatDescriptionCreateFromH264ParameterSets(
+        allocator: CFAllocatorRef,
+        parameter_set_count: usize,
+        parameter_set_pointers: *const *const u8,
+        parameter_set_sizes: *const usize,
+        nal_unit_header_length: i32,
+        format_description_out: *mut CMFormatDescriptionRef,
+    ) -> OSStatus;
+    fn CMVideoFormatDescriptionCreateFromHEVCParameterSets(
+        allocator: CFAllocatorRef,
+        parameter_set_count: usize,
+        parameter_set_pointers: *const *const u8,
+        parameter_set_sizes: *const usize,
+        nal_unit_header_length: i32,
+        extensions: CFDictionaryRef,
+        format_description_out: *mut CMFormatDescriptionRef,
+    ) -> OSStatus;
+    fn CMBlockBufferCreateWithMemoryBlock(
+        structure_allocator: CFAllocatorRef,
+        memory_block: *mut c_void,
+        block_length: usize,
+        block_allocator: CFAllocatorRef,
+        custom_block_source: *const c_void,
+        offset_to_data: usize,
+        data_length: usize,
+        flags: u32,
+        block_buffer_out: *mut CMBlockBufferRef,
+    ) -> OSStatus;
+    fn CMBlockBufferReplaceDataBytes(
+        source_bytes: *const c_void,
+        destination_buffer: CMBlockBufferRef,
+        offset_into_destination: usize,
+        data_length: usize,
+    ) -> OSStatus;
+    fn CMSampleBufferCreateReady(
+        allocator: CFAllocatorRef,
+        data_buffer: CMBlockBufferRef,
+        format_description: CMFormatDescriptionRef,
+        num_samples: isize,
+        num_sample_timing_entries: isize,
+        sample_timing_array: *const c_void,
+        num_sample_size_entries: isize,
+        sample_size_array: *const usize,
+        sample_buffer_out: *mut CMSampleBufferRef,
+    ) -> OSStatus;
+}
+
+#[link(name = "CoreVideo", kind = "framework")]
+unsafe extern "C" {
+    static kCVPixelBufferPixelFormatTypeKey: CFStringRef;
+    static kCVPixelBufferIOSurfacePropertiesKey: CFStringRef;
+    static kCVPixelBufferMetalCompatibilityKey: CFStringRef;
+
+    fn CVPixelBufferGetIOSurface(pixel_buffer: CVPixelBufferRef) -> *mut c_void;
+    fn CVPixelBufferGetWidth(pixel_buffer: CVPixelBufferRef) -> usize;
+    fn CVPixelBufferGetHeight(pixel_buffer: CVPixelBufferRef) -> usize;
+}
+
+#[link(name = "VideoToolbox", kind = "framework")]
+unsafe extern "C" {
+    fn VTDecompressionSessionCreate(
+        allocator: CFAllocatorRef,
+        video_format_description: CMFormatDescriptionRef,
+        video_decoder_specification: CFDictionaryRef,
+        destination_image_buffer_attributes: CFDictionaryRef,
+        output_callback: *const VTDecompressionOutputCallbackRecord,
+        decompression_session_out: *mut VTDecompressionSessionRef,
+    ) -> OSStatus;
+    fn VTDecompressionSessionDecodeFrame(
+        session: VTDecompressionSessionRef,
+        sample_buffer: CMSampleBufferRef,
+        decode_flags: u32,
+        source_frame_ref_con: *mut c_void,
+        info_flags_out: *mut u32,
+    ) -> OSStatus;
+    fn VTDecompressionSessionWaitForAsynchronousFrames(
+        session: VTDecompressionSessionRef,
+    ) -> OSStatus;
+    fn VTDecompressionSessionInvalidate(session: VTDecompressionSessionRef);
+}
+
+/// Error when decoding video with a [`VideoDecoder`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum VideoDecodeError {
+    #[error("Coded data was submitted before the stream's parameter sets")]
+    MissingParameterSets,
+    #[error("The bitstream is invalid")]
+    InvalidBitstream,
+    #[error("VideoToolbox failed with status {0}")]
+    Status(i32),
+}
+
+fn check(status: OSStatus) -> Result<(), VideoDecodeError> {
+    match status {
+        0 => Ok(()),
+        VT_VIDEO_DECODER_BAD_DATA_ERR => Err(VideoDecodeError::InvalidBitstream),
+        status => Err(VideoDecodeError::Status(status)),
+    }
+}
+
+/// A decoded frame: an `IOSurface`-backed pixel buffer in the NV12 format, with
+/// limited range.
+///
+/// VideoToolbox recycles the surface once the frame is dropped, so the frame must
+/// outlive any textures created from it.
+#[derive(Debug)]
+pub struct DecodedFrame {
+    pixel_buffer: CVPixelBufferRef,
+}
+
+unsafe impl Send for DecodedFrame {}
+unsafe impl Sync for DecodedFrame {}
+
+impl DecodedFrame {
+    /// Returns the `IOSurfaceRef` holding the frame.
+    ///
+    /// Plane 0 holds luma, and plane 1 interleaved chroma at half the width and
+    /// height. They can be wrapped in [`wgt::TextureFormat::R8Unorm`] and
+    /// [`wgt::TextureFormat::Rg8Unorm`] textures.
+    pub fn io_surface(&self) -> *mut c_void {
+        unsafe { CVPixelBufferGetIOSurface(self.pixel_buffer) }
+    }
+
+    /// Returns the size of the frame in texels.
+    pub fn size(&self) -> (u32, u32) {
+        unsafe {
+            (
+                CVPixelBufferGetWidth(self.pixel_buffer) as u32,
+                CVPixelBufferGetHeight(self.pixel_buffer) as u32,
+            )
+        }
+    }
+}
+
+impl Drop for DecodedFrame {
+    fn drop(&mut self) {
+        unsafe { CFRelease(self.pixel_buffer) };
+    }
+}
+
+/// Where the output callback stores the result of decoding a single frame.
+type FrameSlot = Option<Result<DecodedFrame, VideoDecodeError>>;
+
+unsafe extern "C" fn decompression_output_callback(
+    _decompression_output_ref_con: *mut c_void,
+    source_frame_ref_con: *mut c_void,
+    status: OSStatus,
+    _info_flags: u32,
+    image_buffer: CVPixelBufferRef,
+    _presentation_time_stamp: CMTime,
+    _presentation_duration: CMTime,
+) {
+    let slot = unsafe { &mut *source_frame_ref_con.cast::<FrameSlot>() };
+    *slot = match check(status) {
+        // Dropped frames have no image.
+        Ok(()) if image_buffer.is_null() => None,
+        Ok(()) => {
+            unsafe { CFRetain(image_buffer) };
+            Some(Ok(DecodedFrame {
+                pixel_buffer: image_buffer,
+            }))
+        }
+        Err(error) => Some(Err(error)),
+    };
+}
+
+/// Splits an Annex B bitstream into NAL units, without their start codes.
+fn nal_units(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = data;
+    core::iter::from_fn(move || {
+        let start = rest.windows(3).position(|window| window == [0, 0, 1])? + 3;
+        rest = &rest[start..];
+        let end = rest
+            .windows(3)
+            .position(|window| window == [0, 0, 1])
+            .unwrap_or(rest.len());
+        let nal_unit = &rest[..end];
+        rest = &rest[end..];
+        // The leading zero of a four byte start code belongs to the next NAL unit.
+        let trailing_zeros = nal_unit.iter().rev().take_while(|&&byte| byte == 0).count();
+        Some(&nal_unit[..nal_unit.len() - trailing_zeros])
+    })
+    .filter(|nal_unit| !nal_unit.is_empty())
+}
+
+/// A hardware accelerated H.264 or HEVC decoder.
+///
+/// Streams are decoded one access unit at a time. Frames come out in decode order,
+/// so streams with B-frames must be reordered by presentation time by the caller.
+/// Only a single sequence and picture parameter set is tracked at a time; new ones
+/// replace the old ones, and reconfigure the decoder.
+#[derive(Debug)]
+pub struct VideoDecoder {
+    codec: wgt::VideoCodec,
+    /// The latest VPS, SPS and PPS, indexed by [`Self::parameter_set_index`].
+    parameter_sets: [Option<Vec<u8>>; 3],
+    format_description: CMFormatDescriptionRef,
+    session: VTDecompressionSessionRef,
+}
+
+unsafe impl Send for VideoDecoder {}
+
+impl VideoDecoder {
+    /// Creates a decoder for `codec`.
+    ///
+    /// The decoding session is created once the stream's parameter sets have been
+    /// submitted to [`Self::decode`].
+    pub fn new(codec: wgt::VideoCodec) -> Self {
+        Self {
+            codec,
+            parameter_sets: [None, None, None],
+            format_description: ptr::null_mut(),
+            session: ptr::null_mut(),
+        }
+    }
+
+    /// Returns the index into `parameter_sets` of a NAL unit that holds a
+    /// parameter set, or `None` for other NAL units.
+    fn parameter_set_index(&self, nal_unit: &[u8]) -> Option<usize> {
+        match self.codec {
+            wgt::VideoCodec::H264 => match nal_unit[0] & 0x1f {
+                7 => Some(1),
+                8 => Some(2),
+                _ => None,
+            },
+            wgt::VideoCodec::Hevc => match (nal_unit[0] >> 1) & 0x3f {
+                32 => Some(0),
+                33 => Some(1),
+                34 => Some(2),
+                _ => None,
+            },
+        }
+    }
+
+    /// Decodes an access unit in Annex B format, containing the coded data of a
+    /// single frame, and any parameter sets before it.
+    ///
+    /// Returns `Ok(None)` if the access unit contained no coded data, or the decoder
+    /// dropped the frame.
+    pub fn decode(&mut self, access_unit: &[u8]) -> Result<Option<DecodedFrame>, VideoDecodeError> {
+        let mut parameter_sets_changed = false;
+        // Coded data, with each NAL unit prefixed by its four byte length.
+        let mut sample = Vec::new();
+        for nal_unit in nal_units(access_unit) {
+            if let Some(index) = self.parameter_set_index(nal_unit) {
+                if self.parameter_sets[index].as_deref() != Some(nal_unit) {
+                    self.parameter_sets[index] = Some(nal_unit.to_vec());
+                    parameter_sets_changed = true;
+                }
+            } else {
+                let length = u32::try_from(nal_unit.len())
+                    .map_err(|_| VideoDecodeError::InvalidBitstream)?;
+                sample.extend_from_slice(&length.to_be_bytes());
+                sample.extend_from_slice(nal_unit);
+            }
+        }
+
+        if parameter_sets_changed {
+            self.reconfigure()?;
+        }
+        if sample.is_empty() {
+            return Ok(None);
+        }
+        if self.session.is_null() {
+            return Err(VideoDecodeError::MissingParameterSets);
+        }
+
+        let sample_buffer = unsafe { self.create_sample_buffer(&sample) }?;
+        let mut slot: FrameSlot = None;
+        // Without `kVTDecodeFrame_EnableAsynchronousDecompression`, the callback is
+        // called before this returns.
+        let status = unsafe {
+            VTDecompressionSessionDecodeFrame(
+                self.session,
+                sample_buffer,
+                0,
+                ptr::from_mut(&mut slot).cast(),
+                ptr::null_mut(),
+            )
+        };
+        unsafe {
+            VTDecompressionSessionWaitForAsynchronousFrames(self.session);
+            CFRelease(sample_buffer);
+        }
+        check(status)?;
+        slot.transpose()
+    }
+
+    /// Recreates the format description and decoding session from the current
+    /// parameter sets, once all of them have been received.
+    fn reconfigure(&mut self) -> Result<(), VideoDecodeError> {
+        let parameter_sets = match self.codec {
+            wgt::VideoCodec::H264 => &self.parameter_sets[1..],
+            wgt::VideoCodec::Hevc => &self.parameter_sets[..],
+        };
+        let Some(parameter_sets) = parameter_sets
+            .iter()
+            .map(Option::as_deref)
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(());
+        };
+        let pointers = parameter_sets
+            .iter()
+            .map(|set| set.as_ptr())
+            .collect::<Vec<_>>();
+        let sizes = parameter_sets
+            .iter()
+            .map(|set| set.len())
+            .collect::<Vec<_>>();
+
+        self.destroy_session();
+
+        let mut format_description = ptr::null_mut();
+        check(unsafe {
+            match self.codec {
+                wgt::VideoCodec::H264 => CMVideoFormatDescriptionCreateFromH264ParameterSets(
+                    ptr::null(),
+                    pointers.len(),
+                    pointers.as_ptr(),
+                    sizes.as_ptr(),
+                    4,
+                    &mut format_description,
+                ),
+                wgt::VideoCodec::Hevc => CMVideoFormatDescriptionCreateFromHEVCParameterSets(
+                    ptr::null(),
+                    pointers.len(),
+                    pointers.as_ptr(),
+                    sizes.as_ptr(),
+                    4,
+                    ptr::null(),
+                    &mut format_description,
+                ),
+            }
+        })?;
+        self.format_description = format_description;
+
+        let attributes = unsafe { create_output_attributes() };
+        let callback = VTDecompressionOutputCallbackRecord {
+            decompression_output_callback,
+            decompression_output_ref_con: ptr::null_mut(),
+        };
+        let mut session = ptr::null_mut();
+        let status = unsafe {
+            VTDecompressionSessionCreate(
+                ptr::null(),
+                self.format_description,
+                ptr::null(),
+                attributes,
+                &callback,
+                &mut session,
+            )
+        };
+        unsafe { CFRelease(attributes) };
+        check(status)?;
+        self.session = session;
+        Ok(())
+    }
+
+    /// Wraps `sample` in a `CMSampleBuffer`.
+    unsafe fn create_sample_buffer(
+        &self,
+        sample: &[u8],
+    ) -> Result<CMSampleBufferRef, VideoDecodeError> {
+        let mut block_buffer = ptr::null_mut();
+        check(unsafe {
+            CMBlockBufferCreateWithMemoryBlock(
+                ptr::null(),
+                ptr::null_mut(),
+                sample.len(),
+                ptr::null(),
+                ptr::null(),
+                0,
+                sample.len(),
+                CM_BLOCK_BUFFER_ASSURE_MEMORY_NOW_FLAG,
+                &mut block_buffer,
+            )
+        })?;
+
+        let mut sample_buffer = ptr::null_mut();
+        let sample_size = sample.len();
+        let status = unsafe {
+            match CMBlockBufferReplaceDataBytes(
+                sample.as_ptr().cast(),
+                block_buffer,
+                0,
+                sample_size,
+            ) {
+                0 => CMSampleBufferCreateReady(
+                    ptr::null(),
+                    block_buffer,
+                    self.format_description,
+                    1,
+                    0,
+                    ptr::null(),
+                    1,
+                    &sample_size,
+                    &mut sample_buffer,
+                ),
+                status => status,
+            }
+        };
+        // The sample buffer retains the block buffer.
+        unsafe { CFRelease(block_buffer) };
+        check(status)?;
+        Ok(sample_buffer)
+    }
+
+    fn destroy_session(&mut self) {
+        unsafe {
+            if !self.session.is_null() {
+                VTDecompressionSessionInvalidate(self.session);
+                CFRelease(self.session);
+                self.session = ptr::null_mut();
+            }
+            if !self.format_description.is_null() {
+                CFRelease(self.format_description);
+                self.format_description = ptr::null_mut();
+            }
+        }
+    }
+}
+
+impl Drop for VideoDecoder {
+    fn drop(&mut self) {
+        self.destroy_session();
+    }
+}
+
+/// Creates the attributes of the pixel buffers frames are decoded into: NV12,
+/// backed by an `IOSurface`, and usable by Metal.
+unsafe fn create_output_attributes() -> CFDictionaryRef {
+    unsafe {
+        let pixel_format = CFNumberCreate(
+            ptr::null(),
+            CF_NUMBER_SINT32_TYPE,
+            ptr::from_ref(&PIXEL_FORMAT_NV12_VIDEO_RANGE).cast(),
+        );
+        let io_surface_properties = CFDictionaryCreate(
+            ptr::null(),
+            ptr::null(),
+            ptr::null(),
+            0,
+            &raw const kCFTypeDictionaryKeyCallBacks,
+            &raw const kCFTypeDictionaryValueCallBacks,
+        );
+        let keys = [
+            kCVPixelBufferPixelFormatTypeKey,
+            kCVPixelBufferIOSurfacePropertiesKey,
+            kCVPixelBufferMetalCompatibilityKey,
+        ];
+        let values = [pixel_format, io_surface_properties, kCFBooleanTrue];
+        let attributes = CFDictionaryCreate(
+            ptr::null(),
+            keys.as_ptr(),
+            values.as_ptr(),
+            keys.len() as isize,
+            &raw const kCFTypeDictionaryKeyCallBacks,
+            &raw const kCFTypeDictionaryValueCallBacks,
+        );
+        CFRelease(pixel_format);
+        CFRelease(io_surface_properties);
+        attributes
+    }
+}
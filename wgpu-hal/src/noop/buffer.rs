@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use core::{cell::UnsafeCell, ops::Range, ptr};
 
 cfg_if::cfg_if! {
@@ -20,6 +20,9 @@ pub struct Buffer {
     /// This is redundant with `storage.get().len()`, but that method is not
     /// available until our MSRV is 1.79 or greater.
     size: usize,
+
+    /// Label of the buffer, to identify it in recorded commands.
+    label: Option<String>,
 }
 
 /// SAFETY:
@@ -30,9 +33,9 @@ unsafe impl Send for Buffer {}
 unsafe impl Sync for Buffer {}
 
 impl Buffer {
-    pub(super) fn new(desc: &crate::BufferDescriptor) -> Result<Self, crate::DeviceError> {
+    pub(crate) fn new(desc: &crate::BufferDescriptor) -> Result<Self, crate::DeviceError> {
         let &crate::BufferDescriptor {
-            label: _,
+            label,
             size,
             usage: _,
             memory_flags: _,
@@ -54,7 +57,21 @@ impl Buffer {
         let storage: Arc<UnsafeCell<[u8]>> =
             unsafe { Arc::from_raw(Arc::into_raw(storage) as *mut UnsafeCell<[u8]>) };
 
-        Ok(Buffer { storage, size })
+        Ok(Buffer {
+            storage,
+            size,
+            label: label.map(String::from),
+        })
+    }
+
+    /// Returns the size of the allocation, in bytes.
+    #[cfg(cpu)]
+    pub(crate) fn size(&self) -> wgt::BufferAddress {
+        self.size as wgt::BufferAddress
+    }
+
+    pub(crate) fn label(&self) -> Option<String> {
+        self.label.clone()
     }
 
     /// Returns a pointer to the memory owned by this buffer within the given `range`.
@@ -62,7 +79,7 @@ impl Buffer {
     /// This may be used to create any number of simultaneous pointers;
     /// aliasing is only a concern when actually reading, writing, or converting the pointer
     /// to a reference.
-    pub(super) fn get_slice_ptr(&self, range: crate::MemoryRange) -> *mut [u8] {
+    pub(crate) fn get_slice_ptr(&self, range: crate::MemoryRange) -> *mut [u8] {
         let base_ptr = self.storage.get();
         let range = range_to_usize(range, self.size);
 
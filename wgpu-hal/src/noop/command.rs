@@ -1,13 +1,17 @@
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use core::mem;
 use core::ops::Range;
 
+use wgt::{RecordedCommand as Rc, RecordedDrawKind};
+
 use super::{Api, Buffer, DeviceResult, Resource};
 
 /// Command buffer type, which performs double duty as the command encoder type too.
 #[derive(Debug)]
 pub struct CommandBuffer {
     commands: Vec<Command>,
+    /// The recorded commands, if recording, see [`wgt::NoopBackendOptions::record`].
+    recording: Option<wgt::RecordedCommandBuffer>,
 }
 
 #[derive(Debug)]
@@ -34,9 +38,39 @@ impl CommandBuffer {
         }
     }
 
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(record: bool) -> Self {
         Self {
             commands: Vec::new(),
+            recording: record.then(Default::default),
+        }
+    }
+
+    #[cfg(feature = "noop_recording")]
+    pub(crate) fn recorded(&self) -> Option<wgt::RecordedCommandBuffer> {
+        self.recording.clone()
+    }
+
+    fn record_indirect(
+        &mut self,
+        kind: RecordedDrawKind,
+        buffer: &Buffer,
+        offset: wgt::BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(&Buffer, wgt::BufferAddress)>,
+    ) {
+        self.record(|| Rc::DrawIndirect {
+            kind,
+            buffer: buffer.label(),
+            offset,
+            max_count,
+            count_buffer: count_buffer.map(|(buffer, offset)| (buffer.label(), offset)),
+        });
+    }
+
+    /// Records the command returned by `command`, if recording.
+    fn record(&mut self, command: impl FnOnce() -> Rc) {
+        if let Some(ref mut recording) = self.recording {
+            recording.commands.push(command());
         }
     }
 }
@@ -46,14 +80,21 @@ impl crate::CommandEncoder for CommandBuffer {
 
     unsafe fn begin_encoding(&mut self, label: crate::Label) -> DeviceResult<()> {
         assert!(self.commands.is_empty());
+        if let Some(ref mut recording) = self.recording {
+            recording.label = label.map(String::from);
+        }
         Ok(())
     }
     unsafe fn discard_encoding(&mut self) {
         self.commands.clear();
+        if let Some(ref mut recording) = self.recording {
+            *recording = Default::default();
+        }
     }
     unsafe fn end_encoding(&mut self) -> DeviceResult<CommandBuffer> {
         Ok(CommandBuffer {
             commands: mem::take(&mut self.commands),
+            recording: self.recording.as_mut().map(mem::take),
         })
     }
     unsafe fn reset_all<I>(&mut self, command_buffers: I) {}
@@ -62,15 +103,34 @@ impl crate::CommandEncoder for CommandBuffer {
     where
         T: Iterator<Item = crate::BufferBarrier<'a, Buffer>>,
     {
+        for barrier in barriers {
+            self.record(|| Rc::BufferBarrier {
+                buffer: barrier.buffer.label(),
+                from: barrier.usage.from,
+                to: barrier.usage.to,
+            });
+        }
     }
 
     unsafe fn transition_textures<'a, T>(&mut self, barriers: T)
     where
         T: Iterator<Item = crate::TextureBarrier<'a, Resource>>,
     {
+        for barrier in barriers {
+            self.record(|| Rc::TextureBarrier {
+                texture: barrier.texture.label.clone(),
+                range: barrier.range,
+                from: barrier.usage.from,
+                to: barrier.usage.to,
+            });
+        }
     }
 
     unsafe fn clear_buffer(&mut self, buffer: &Buffer, range: crate::MemoryRange) {
+        self.record(|| Rc::ClearBuffer {
+            buffer: buffer.label(),
+            range: range.clone(),
+        });
         self.commands.push(Command::ClearBuffer {
             buffer: buffer.clone(),
             range,
@@ -81,6 +141,10 @@ impl crate::CommandEncoder for CommandBuffer {
     where
         T: Iterator<Item = crate::BufferCopy>,
     {
+        self.record(|| Rc::CopyBufferToBuffer {
+            src: src.label(),
+            dst: dst.label(),
+        });
         self.commands.push(Command::CopyBufferToBuffer {
             src: src.clone(),
             dst: dst.clone(),
@@ -98,6 +162,7 @@ impl crate::CommandEncoder for CommandBuffer {
     ) where
         T: Iterator<Item = crate::TextureCopy>,
     {
+        self.record(|| Rc::Other("copy_external_image_to_texture"));
     }
 
     unsafe fn copy_texture_to_texture<T>(
@@ -108,10 +173,25 @@ impl crate::CommandEncoder for CommandBuffer {
         regions: T,
     ) {
         // TODO: consider implementing this and other texture manipulation
+        self.record(|| Rc::CopyTextureToTexture {
+            src: src.label.clone(),
+            dst: dst.label.clone(),
+        });
+    }
+
+    unsafe fn resolve_texture<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {
+        self.record(|| Rc::ResolveTexture {
+            src: src.label.clone(),
+            dst: dst.label.clone(),
+        });
     }
 
     unsafe fn copy_buffer_to_texture<T>(&mut self, src: &Buffer, dst: &Resource, regions: T) {
         // TODO: consider implementing this and other texture manipulation
+        self.record(|| Rc::CopyBufferToTexture {
+            src: src.label(),
+            dst: dst.label.clone(),
+        });
     }
 
     unsafe fn copy_texture_to_buffer<T>(
@@ -122,18 +202,50 @@ impl crate::CommandEncoder for CommandBuffer {
         regions: T,
     ) {
         // TODO: consider implementing this and other texture manipulation
+        self.record(|| Rc::CopyTextureToBuffer {
+            src: src.label.clone(),
+            dst: dst.label(),
+        });
     }
 
-    unsafe fn begin_query(&mut self, set: &Resource, index: u32) {}
-    unsafe fn end_query(&mut self, set: &Resource, index: u32) {}
-    unsafe fn write_timestamp(&mut self, set: &Resource, index: u32) {}
+    unsafe fn begin_query(&mut self, set: &Resource, index: u32) {
+        self.record(|| Rc::BeginQuery {
+            query_set: set.label.clone(),
+            index,
+        });
+    }
+    unsafe fn end_query(&mut self, set: &Resource, index: u32) {
+        self.record(|| Rc::EndQuery {
+            query_set: set.label.clone(),
+            index,
+        });
+    }
+    unsafe fn write_timestamp(&mut self, set: &Resource, index: u32) {
+        self.record(|| Rc::WriteTimestamp {
+            query_set: set.label.clone(),
+            index,
+        });
+    }
     unsafe fn read_acceleration_structure_compact_size(
         &mut self,
         acceleration_structure: &Resource,
         buf: &Buffer,
     ) {
+        self.record(|| Rc::Other("read_acceleration_structure_compact_size"));
+    }
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        acceleration_structure: &Resource,
+        buf: &Buffer,
+    ) {
+        self.record(|| Rc::Other("read_acceleration_structure_serialized_size"));
+    }
+    unsafe fn reset_queries(&mut self, set: &Resource, range: Range<u32>) {
+        self.record(|| Rc::ResetQueries {
+            query_set: set.label.clone(),
+            range,
+        });
     }
-    unsafe fn reset_queries(&mut self, set: &Resource, range: Range<u32>) {}
     unsafe fn copy_query_results(
         &mut self,
         set: &Resource,
@@ -142,6 +254,12 @@ impl crate::CommandEncoder for CommandBuffer {
         offset: wgt::BufferAddress,
         stride: wgt::BufferSize,
     ) {
+        self.record(|| Rc::CopyQueryResults {
+            query_set: set.label.clone(),
+            range,
+            buffer: buffer.label(),
+            offset,
+        });
     }
 
     // render
@@ -150,9 +268,39 @@ impl crate::CommandEncoder for CommandBuffer {
         &mut self,
         desc: &crate::RenderPassDescriptor<Resource, Resource>,
     ) -> DeviceResult<()> {
+        self.record(|| Rc::BeginRenderPass {
+            label: desc.label.map(String::from),
+            color_attachments: desc
+                .color_attachments
+                .iter()
+                .map(|attachment| attachment.as_ref()?.target.view.label.clone())
+                .collect(),
+            depth_stencil_attachment: desc
+                .depth_stencil_attachment
+                .as_ref()
+                .and_then(|attachment| attachment.target.view.label.clone()),
+        });
+        Ok(())
+    }
+    unsafe fn end_render_pass(&mut self) {
+        self.record(|| Rc::EndRenderPass);
+    }
+
+    unsafe fn create_parallel_render_encoder(&mut self) -> DeviceResult<CommandBuffer> {
+        Ok(CommandBuffer::new(self.recording.is_some()))
+    }
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        encoders: Vec<CommandBuffer>,
+    ) -> DeviceResult<()> {
+        let mut parts = Vec::with_capacity(encoders.len());
+        for encoder in encoders {
+            self.commands.extend(encoder.commands);
+            parts.extend(encoder.recording.map(|recording| recording.commands));
+        }
+        self.record(|| Rc::ExecuteParallelEncoders(parts));
         Ok(())
     }
-    unsafe fn end_render_pass(&mut self) {}
 
     unsafe fn set_bind_group(
         &mut self,
@@ -161,6 +309,11 @@ impl crate::CommandEncoder for CommandBuffer {
         group: &Resource,
         dynamic_offsets: &[wgt::DynamicOffset],
     ) {
+        self.record(|| Rc::SetBindGroup {
+            index,
+            bind_group: group.label.clone(),
+            dynamic_offsets: dynamic_offsets.to_vec(),
+        });
     }
     unsafe fn set_push_constants(
         &mut self,
@@ -169,30 +322,97 @@ impl crate::CommandEncoder for CommandBuffer {
         offset_bytes: u32,
         data: &[u32],
     ) {
+        self.record(|| Rc::SetPushConstants {
+            stages,
+            offset: offset_bytes,
+            data: data.to_vec(),
+        });
     }
 
-    unsafe fn insert_debug_marker(&mut self, label: &str) {}
-    unsafe fn begin_debug_marker(&mut self, group_label: &str) {}
-    unsafe fn end_debug_marker(&mut self) {}
+    unsafe fn insert_debug_marker(&mut self, label: &str) {
+        self.record(|| Rc::InsertDebugMarker(String::from(label)));
+    }
+    unsafe fn begin_debug_marker(&mut self, group_label: &str) {
+        self.record(|| Rc::PushDebugGroup(String::from(group_label)));
+    }
+    unsafe fn end_debug_marker(&mut self) {
+        self.record(|| Rc::PopDebugGroup);
+    }
 
-    unsafe fn set_render_pipeline(&mut self, pipeline: &Resource) {}
+    unsafe fn set_render_pipeline(&mut self, pipeline: &Resource) {
+        self.record(|| Rc::SetRenderPipeline {
+            pipeline: pipeline.label.clone(),
+        });
+    }
 
     unsafe fn set_index_buffer<'a>(
         &mut self,
         binding: crate::BufferBinding<'a, Buffer>,
         format: wgt::IndexFormat,
     ) {
+        self.record(|| Rc::SetIndexBuffer {
+            buffer: binding.buffer.label(),
+            offset: binding.offset,
+            size: binding.size,
+            format,
+        });
     }
     unsafe fn set_vertex_buffer<'a>(
         &mut self,
         index: u32,
         binding: crate::BufferBinding<'a, Buffer>,
     ) {
+        self.record(|| Rc::SetVertexBuffer {
+            slot: index,
+            buffer: binding.buffer.label(),
+            offset: binding.offset,
+            size: binding.size,
+        });
+    }
+    unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {
+        unsafe { self.set_viewport_indexed(0, rect, depth_range) };
+    }
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+        self.record(|| Rc::SetViewport {
+            index,
+            x: rect.x,
+            y: rect.y,
+            width: rect.w,
+            height: rect.h,
+            depth: depth_range,
+        });
+    }
+    unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
+        self.record(|| Rc::SetScissorRect {
+            x: rect.x,
+            y: rect.y,
+            width: rect.w,
+            height: rect.h,
+        });
+    }
+    unsafe fn set_stencil_reference(&mut self, value: u32) {
+        self.record(|| Rc::SetStencilReference(value));
+    }
+    unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
+        self.record(|| Rc::SetBlendConstant(*color));
+    }
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate) {
+        self.record(|| Rc::Other("set_shading_rate"));
+    }
+    unsafe fn begin_conditional_rendering(&mut self, buffer: &Buffer, offset: wgt::BufferAddress) {
+        self.record(|| Rc::BeginConditionalRendering {
+            buffer: buffer.label(),
+            offset,
+        });
+    }
+    unsafe fn end_conditional_rendering(&mut self) {
+        self.record(|| Rc::EndConditionalRendering);
     }
-    unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {}
-    unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {}
-    unsafe fn set_stencil_reference(&mut self, value: u32) {}
-    unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {}
 
     unsafe fn draw(
         &mut self,
@@ -201,6 +421,10 @@ impl crate::CommandEncoder for CommandBuffer {
         first_instance: u32,
         instance_count: u32,
     ) {
+        self.record(|| Rc::Draw {
+            vertices: first_vertex..first_vertex + vertex_count,
+            instances: first_instance..first_instance + instance_count,
+        });
     }
     unsafe fn draw_indexed(
         &mut self,
@@ -210,6 +434,11 @@ impl crate::CommandEncoder for CommandBuffer {
         first_instance: u32,
         instance_count: u32,
     ) {
+        self.record(|| Rc::DrawIndexed {
+            indices: first_index..first_index + index_count,
+            base_vertex,
+            instances: first_instance..first_instance + instance_count,
+        });
     }
     unsafe fn draw_mesh_tasks(
         &mut self,
@@ -217,6 +446,9 @@ impl crate::CommandEncoder for CommandBuffer {
         group_count_y: u32,
         group_count_z: u32,
     ) {
+        self.record(|| Rc::DrawMeshTasks {
+            group_count: [group_count_x, group_count_y, group_count_z],
+        });
     }
     unsafe fn draw_indirect(
         &mut self,
@@ -224,6 +456,7 @@ impl crate::CommandEncoder for CommandBuffer {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        self.record_indirect(RecordedDrawKind::Draw, buffer, offset, draw_count, None);
     }
     unsafe fn draw_indexed_indirect(
         &mut self,
@@ -231,6 +464,13 @@ impl crate::CommandEncoder for CommandBuffer {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        self.record_indirect(
+            RecordedDrawKind::DrawIndexed,
+            buffer,
+            offset,
+            draw_count,
+            None,
+        );
     }
     unsafe fn draw_mesh_tasks_indirect(
         &mut self,
@@ -238,6 +478,13 @@ impl crate::CommandEncoder for CommandBuffer {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
+        self.record_indirect(
+            RecordedDrawKind::DrawMeshTasks,
+            buffer,
+            offset,
+            draw_count,
+            None,
+        );
     }
     unsafe fn draw_indirect_count(
         &mut self,
@@ -247,6 +494,8 @@ impl crate::CommandEncoder for CommandBuffer {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        let count = Some((count_buffer, count_offset));
+        self.record_indirect(RecordedDrawKind::Draw, buffer, offset, max_count, count);
     }
     unsafe fn draw_indexed_indirect_count(
         &mut self,
@@ -256,6 +505,14 @@ impl crate::CommandEncoder for CommandBuffer {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        let count = Some((count_buffer, count_offset));
+        self.record_indirect(
+            RecordedDrawKind::DrawIndexed,
+            buffer,
+            offset,
+            max_count,
+            count,
+        );
     }
     unsafe fn draw_mesh_tasks_indirect_count(
         &mut self,
@@ -265,32 +522,67 @@ impl crate::CommandEncoder for CommandBuffer {
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
+        let count = Some((count_buffer, count_offset));
+        self.record_indirect(
+            RecordedDrawKind::DrawMeshTasks,
+            buffer,
+            offset,
+            max_count,
+            count,
+        );
     }
 
     // compute
 
-    unsafe fn begin_compute_pass(&mut self, desc: &crate::ComputePassDescriptor<Resource>) {}
-    unsafe fn end_compute_pass(&mut self) {}
+    unsafe fn begin_compute_pass(&mut self, desc: &crate::ComputePassDescriptor<Resource>) {
+        self.record(|| Rc::BeginComputePass {
+            label: desc.label.map(String::from),
+        });
+    }
+    unsafe fn end_compute_pass(&mut self) {
+        self.record(|| Rc::EndComputePass);
+    }
 
-    unsafe fn set_compute_pipeline(&mut self, pipeline: &Resource) {}
+    unsafe fn set_compute_pipeline(&mut self, pipeline: &Resource) {
+        self.record(|| Rc::SetComputePipeline {
+            pipeline: pipeline.label.clone(),
+        });
+    }
 
-    unsafe fn dispatch(&mut self, count: [u32; 3]) {}
-    unsafe fn dispatch_indirect(&mut self, buffer: &Buffer, offset: wgt::BufferAddress) {}
+    unsafe fn dispatch(&mut self, count: [u32; 3]) {
+        self.record(|| Rc::Dispatch { group_count: count });
+    }
+    unsafe fn dispatch_indirect(&mut self, buffer: &Buffer, offset: wgt::BufferAddress) {
+        self.record(|| Rc::DispatchIndirect {
+            buffer: buffer.label(),
+            offset,
+        });
+    }
 
-    unsafe fn build_acceleration_structures<'a, T>(
-        &mut self,
-        _descriptor_count: u32,
-        descriptors: T,
-    ) where
+    unsafe fn set_ray_tracing_pipeline(&mut self, pipeline: &Resource) {
+        self.record(|| Rc::SetRayTracingPipeline {
+            pipeline: pipeline.label.clone(),
+        });
+    }
+    unsafe fn trace_rays(&mut self, tables: &crate::ShaderBindingTables<Buffer>, count: [u32; 3]) {
+        self.record(|| Rc::TraceRays { count });
+    }
+
+    unsafe fn build_acceleration_structures<'a, T>(&mut self, descriptor_count: u32, descriptors: T)
+    where
         Api: 'a,
         T: IntoIterator<Item = crate::BuildAccelerationStructureDescriptor<'a, Buffer, Resource>>,
     {
+        self.record(|| Rc::BuildAccelerationStructures {
+            count: descriptor_count,
+        });
     }
 
     unsafe fn place_acceleration_structure_barrier(
         &mut self,
         _barriers: crate::AccelerationStructureBarrier,
     ) {
+        self.record(|| Rc::Other("place_acceleration_structure_barrier"));
     }
 
     unsafe fn copy_acceleration_structure_to_acceleration_structure(
@@ -299,6 +591,23 @@ impl crate::CommandEncoder for CommandBuffer {
         dst: &Resource,
         copy: wgt::AccelerationStructureCopy,
     ) {
+        self.record(|| Rc::Other("copy_acceleration_structure_to_acceleration_structure"));
+    }
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        src: &Resource,
+        dst: &Buffer,
+        dst_offset: wgt::BufferAddress,
+    ) {
+        self.record(|| Rc::Other("copy_acceleration_structure_to_buffer"));
+    }
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        src: &Buffer,
+        src_offset: wgt::BufferAddress,
+        dst: &Resource,
+    ) {
+        self.record(|| Rc::Other("copy_buffer_to_acceleration_structure"));
     }
 }
 
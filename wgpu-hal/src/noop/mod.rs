@@ -17,11 +17,20 @@ pub use command::CommandBuffer;
 
 #[derive(Clone, Debug)]
 pub struct Api;
-pub struct Context;
+pub struct Context {
+    /// Whether command encoders record their commands, see [`wgt::NoopBackendOptions::record`].
+    record: bool,
+    /// The command buffers submitted to this queue since they were last taken, if recording.
+    #[cfg(feature = "noop_recording")]
+    recorded: parking_lot::Mutex<Vec<wgt::RecordedCommandBuffer>>,
+}
 #[derive(Debug)]
 pub struct Encoder;
 #[derive(Debug)]
-pub struct Resource;
+pub struct Resource {
+    /// Label of the resource, to identify it in recorded commands.
+    label: Option<String>,
+}
 
 #[derive(Debug)]
 pub struct Fence {
@@ -47,6 +56,7 @@ impl crate::Api for Api {
     type Sampler = Resource;
     type QuerySet = Resource;
     type Fence = Fence;
+    type MemoryHeap = Resource;
     type AccelerationStructure = Resource;
     type PipelineCache = Resource;
 
@@ -56,6 +66,7 @@ impl crate::Api for Api {
     type ShaderModule = Resource;
     type RenderPipeline = Resource;
     type ComputePipeline = Resource;
+    type RayTracingPipeline = Resource;
 }
 
 crate::impl_dyn_resource!(Buffer, CommandBuffer, Context, Fence, Resource);
@@ -67,9 +78,11 @@ impl crate::DynBuffer for Buffer {}
 impl crate::DynCommandBuffer for CommandBuffer {}
 impl crate::DynComputePipeline for Resource {}
 impl crate::DynFence for Fence {}
+impl crate::DynMemoryHeap for Resource {}
 impl crate::DynPipelineCache for Resource {}
 impl crate::DynPipelineLayout for Resource {}
 impl crate::DynQuerySet for Resource {}
+impl crate::DynRayTracingPipeline for Resource {}
 impl crate::DynRenderPipeline for Resource {}
 impl crate::DynSampler for Resource {}
 impl crate::DynShaderModule for Resource {}
@@ -83,6 +96,34 @@ impl core::borrow::Borrow<dyn crate::DynTexture> for Resource {
     }
 }
 
+impl Context {
+    fn new(record: bool) -> Self {
+        Self {
+            record,
+            #[cfg(feature = "noop_recording")]
+            recorded: Default::default(),
+        }
+    }
+
+    /// Takes the command buffers submitted to this queue since the last call.
+    ///
+    /// Returns an empty list unless [`wgt::NoopBackendOptions::record`] is enabled.
+    pub fn take_recorded_command_buffers(&self) -> Vec<wgt::RecordedCommandBuffer> {
+        #[cfg(feature = "noop_recording")]
+        return core::mem::take(&mut *self.recorded.lock());
+        #[cfg(not(feature = "noop_recording"))]
+        Vec::new()
+    }
+}
+
+impl Resource {
+    fn new(label: crate::Label) -> Self {
+        Self {
+            label: label.map(String::from),
+        }
+    }
+}
+
 impl crate::Instance for Context {
     type A = Api;
 
@@ -90,19 +131,24 @@ impl crate::Instance for Context {
         let crate::InstanceDescriptor {
             backend_options:
                 wgt::BackendOptions {
-                    noop: wgt::NoopBackendOptions { enable },
+                    noop: wgt::NoopBackendOptions { enable, record },
                     ..
                 },
             name: _,
             flags: _,
             memory_budget_thresholds: _,
+            toggles: _,
         } = *desc;
-        if enable {
-            Ok(Context)
-        } else {
+        if !enable {
             Err(crate::InstanceError::new(String::from(
                 "noop backend disabled because NoopBackendOptions::enable is false",
             )))
+        } else if record && !cfg!(feature = "noop_recording") {
+            Err(crate::InstanceError::new(String::from(
+                "noop backend can't record commands without the `noop_recording` feature",
+            )))
+        } else {
+            Ok(Context::new(record))
         }
     }
     unsafe fn create_surface(
@@ -110,14 +156,14 @@ impl crate::Instance for Context {
         _display_handle: raw_window_handle::RawDisplayHandle,
         _window_handle: raw_window_handle::RawWindowHandle,
     ) -> Result<Context, crate::InstanceError> {
-        Ok(Context)
+        Ok(Context::new(false))
     }
     unsafe fn enumerate_adapters(
         &self,
         _surface_hint: Option<&Context>,
     ) -> Vec<crate::ExposedAdapter<Api>> {
         vec![crate::ExposedAdapter {
-            adapter: Context,
+            adapter: Context::new(self.record),
             info: wgt::AdapterInfo {
                 name: String::from("noop wgpu backend"),
                 vendor: 0,
@@ -127,8 +173,10 @@ impl crate::Instance for Context {
                 driver_info: String::new(),
                 backend: wgt::Backend::Noop,
             },
-            features: wgt::Features::all(),
+            // There is nothing outside of this backend to share fences with.
+            features: wgt::Features::all() - wgt::Features::EXPERIMENTAL_SHARED_FENCE,
             capabilities: CAPABILITIES,
+            toggles: wgt::Toggles::empty(),
         }]
     }
 }
@@ -181,6 +229,7 @@ const CAPABILITIES: crate::Capabilities = {
             max_blas_geometry_count: ALLOC_MAX_U32,
             max_tlas_instance_count: ALLOC_MAX_U32,
             max_acceleration_structures_per_shader_stage: ALLOC_MAX_U32,
+            max_ray_recursion_depth: ALLOC_MAX_U32,
         },
         alignments: crate::Alignments {
             // All maximally permissive
@@ -189,6 +238,9 @@ const CAPABILITIES: crate::Capabilities = {
             uniform_bounds_check_alignment: wgt::BufferSize::MIN,
             raw_tlas_instance_size: 0,
             ray_tracing_scratch_buffer_alignment: 1,
+            ray_tracing_shader_group_handle_size: 32,
+            ray_tracing_shader_group_handle_alignment: 32,
+            ray_tracing_shader_group_base_alignment: 64,
         },
         downlevel: wgt::DownlevelCapabilities {
             flags: wgt::DownlevelFlags::all(),
@@ -219,6 +271,18 @@ impl crate::Surface for Context {
         Ok(None)
     }
     unsafe fn discard_texture(&self, texture: Resource) {}
+
+    unsafe fn set_target_present_time(&self, time: wgt::PresentationTimestamp) {}
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        Ok(Vec::new())
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        wgt::PresentationPath::Unknown
+    }
 }
 
 impl crate::Adapter for Context {
@@ -229,10 +293,15 @@ impl crate::Adapter for Context {
         features: wgt::Features,
         _limits: &wgt::Limits,
         _memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
     ) -> DeviceResult<crate::OpenDevice<Api>> {
         Ok(crate::OpenDevice {
-            device: Context,
-            queue: Context,
+            device: Context::new(self.record),
+            queue: Context::new(self.record),
+            additional_queues: additional_queues
+                .iter()
+                .map(|_| Context::new(self.record))
+                .collect(),
         })
     }
     unsafe fn texture_format_capabilities(
@@ -249,6 +318,9 @@ impl crate::Adapter for Context {
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp::INVALID_TIMESTAMP
     }
+    fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        None
+    }
 }
 
 impl crate::Queue for Context {
@@ -267,6 +339,10 @@ impl crate::Queue for Context {
             unsafe {
                 cb.execute();
             }
+            #[cfg(feature = "noop_recording")]
+            if let Some(recorded) = cb.recorded() {
+                self.recorded.lock().push(recorded);
+            }
         }
         fence.value.store(fence_value, Ordering::Release);
         Ok(())
@@ -275,6 +351,7 @@ impl crate::Queue for Context {
         &self,
         surface: &Context,
         texture: Resource,
+        damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
         Ok(())
     }
@@ -282,6 +359,30 @@ impl crate::Queue for Context {
     unsafe fn get_timestamp_period(&self) -> f32 {
         1.0
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[crate::SparseBufferBinding<Buffer, Resource>],
+        texture_bindings: &[crate::SparseTextureBinding<Resource, Resource>],
+    ) -> DeviceResult<()> {
+        // Sparse resources are fully backed by memory from the start.
+        Ok(())
+    }
+
+    unsafe fn wait_for_fence(&self, fence: &Fence, value: crate::FenceValue) -> DeviceResult<()> {
+        // All commands are executed synchronously, so the fence has already been signalled.
+        Ok(())
+    }
+
+    unsafe fn signal_fence(&self, fence: &Fence, value: crate::FenceValue) -> DeviceResult<()> {
+        // The next submission completes as soon as it is made, so signal right away.
+        fence.value.fetch_max(value, Ordering::Release);
+        Ok(())
+    }
 }
 
 impl crate::Device for Context {
@@ -312,21 +413,69 @@ impl crate::Device for Context {
     unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &Buffer, ranges: I) {}
 
     unsafe fn create_texture(&self, desc: &crate::TextureDescriptor) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_texture(&self, texture: Resource) {}
     unsafe fn add_raw_texture(&self, _texture: &Resource) {}
 
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &crate::MemoryHeapDescriptor,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource::new(desc.label))
+    }
+    unsafe fn destroy_memory_heap(&self, heap: Resource) {}
+    unsafe fn texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<wgt::TextureMemoryRequirements> {
+        let block_size = desc.format.block_copy_size(None).unwrap_or(4) as u64;
+        let (block_width, block_height) = desc.format.block_dimensions();
+        let mut size = 0;
+        for mip_level in 0..desc.mip_level_count {
+            let extent = desc.size.mip_level_size(mip_level, desc.dimension);
+            let blocks_wide = extent.width.div_ceil(block_width) as u64;
+            let blocks_high = extent.height.div_ceil(block_height) as u64;
+            size += blocks_wide
+                * blocks_high
+                * extent.depth_or_array_layers as u64
+                * block_size
+                * desc.sample_count as u64;
+        }
+        Ok(wgt::TextureMemoryRequirements {
+            size,
+            alignment: 256,
+        })
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        heap: &Resource,
+        offset: wgt::BufferAddress,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource::new(desc.label))
+    }
+
+    unsafe fn create_sparse_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Buffer> {
+        Buffer::new(desc)
+    }
+    unsafe fn create_sparse_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource::new(desc.label))
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &Resource,
         desc: &crate::TextureViewDescriptor,
     ) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_texture_view(&self, view: Resource) {}
     unsafe fn create_sampler(&self, desc: &crate::SamplerDescriptor) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_sampler(&self, sampler: Resource) {}
 
@@ -334,28 +483,28 @@ impl crate::Device for Context {
         &self,
         desc: &crate::CommandEncoderDescriptor<Context>,
     ) -> DeviceResult<CommandBuffer> {
-        Ok(CommandBuffer::new())
+        Ok(CommandBuffer::new(self.record))
     }
 
     unsafe fn create_bind_group_layout(
         &self,
         desc: &crate::BindGroupLayoutDescriptor,
     ) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_bind_group_layout(&self, bg_layout: Resource) {}
     unsafe fn create_pipeline_layout(
         &self,
         desc: &crate::PipelineLayoutDescriptor<Resource>,
     ) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_pipeline_layout(&self, pipeline_layout: Resource) {}
     unsafe fn create_bind_group(
         &self,
         desc: &crate::BindGroupDescriptor<Resource, Buffer, Resource, Resource, Resource>,
     ) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_bind_group(&self, group: Resource) {}
 
@@ -364,14 +513,14 @@ impl crate::Device for Context {
         desc: &crate::ShaderModuleDescriptor,
         shader: crate::ShaderInput,
     ) -> Result<Resource, crate::ShaderError> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_shader_module(&self, module: Resource) {}
     unsafe fn create_render_pipeline(
         &self,
         desc: &crate::RenderPipelineDescriptor<Resource, Resource, Resource>,
     ) -> Result<Resource, crate::PipelineError> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn create_mesh_pipeline(
         &self,
@@ -381,21 +530,37 @@ impl crate::Device for Context {
             <Self::A as crate::Api>::PipelineCache,
         >,
     ) -> Result<<Self::A as crate::Api>::RenderPipeline, crate::PipelineError> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_render_pipeline(&self, pipeline: Resource) {}
     unsafe fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<Resource, Resource, Resource>,
     ) -> Result<Resource, crate::PipelineError> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_compute_pipeline(&self, pipeline: Resource) {}
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        desc: &crate::RayTracingPipelineDescriptor<Resource, Resource, Resource>,
+    ) -> Result<Resource, crate::PipelineError> {
+        Ok(Resource::new(desc.label))
+    }
+    unsafe fn destroy_ray_tracing_pipeline(&self, pipeline: Resource) {}
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        pipeline: &Resource,
+        first_group: u32,
+        group_count: u32,
+    ) -> DeviceResult<Vec<u8>> {
+        let handle_size = CAPABILITIES.alignments.ray_tracing_shader_group_handle_size;
+        Ok(vec![0; (group_count * handle_size) as usize])
+    }
     unsafe fn create_pipeline_cache(
         &self,
         desc: &crate::PipelineCacheDescriptor<'_>,
     ) -> Result<Resource, crate::PipelineCacheError> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_pipeline_cache(&self, cache: Resource) {}
 
@@ -403,7 +568,7 @@ impl crate::Device for Context {
         &self,
         desc: &wgt::QuerySetDescriptor<crate::Label>,
     ) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn destroy_query_set(&self, set: Resource) {}
     unsafe fn create_fence(&self) -> DeviceResult<Fence> {
@@ -430,6 +595,15 @@ impl crate::Device for Context {
         );
         Ok(true)
     }
+    unsafe fn create_shared_fence(&self) -> DeviceResult<Fence> {
+        unsafe { self.create_fence() }
+    }
+    unsafe fn export_fence(&self, fence: &Fence) -> DeviceResult<wgt::SharedFenceHandle> {
+        Err(crate::DeviceError::Unexpected)
+    }
+    unsafe fn import_fence(&self, handle: wgt::SharedFenceHandle) -> DeviceResult<Fence> {
+        Err(crate::DeviceError::Unexpected)
+    }
 
     unsafe fn start_graphics_debugger_capture(&self) -> bool {
         false
@@ -439,7 +613,7 @@ impl crate::Device for Context {
         &self,
         desc: &crate::AccelerationStructureDescriptor,
     ) -> DeviceResult<Resource> {
-        Ok(Resource)
+        Ok(Resource::new(desc.label))
     }
     unsafe fn get_acceleration_structure_build_sizes<'a>(
         &self,
@@ -455,6 +629,13 @@ impl crate::Device for Context {
     }
     unsafe fn destroy_acceleration_structure(&self, _acceleration_structure: Resource) {}
 
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        data: &[u8],
+    ) -> Option<wgt::BufferAddress> {
+        Some(0)
+    }
+
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8> {
         vec![]
     }
@@ -1,7 +1,7 @@
 use alloc::{borrow::ToOwned as _, boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 use core::{ffi::CStr, marker::PhantomData};
 
-use ash::{ext, google, khr, vk};
+use ash::{android, ext, google, khr, vk};
 use parking_lot::Mutex;
 
 use super::conv;
@@ -69,6 +69,9 @@ pub struct PhysicalDeviceFeatures {
     /// Features provided by `VK_KHR_16bit_storage`, promoted to Vulkan 1.1
     _16bit_storage: Option<vk::PhysicalDevice16BitStorageFeatures<'static>>,
 
+    /// Features provided by `VK_KHR_8bit_storage`, promoted to Vulkan 1.2
+    _8bit_storage: Option<vk::PhysicalDevice8BitStorageFeatures<'static>>,
+
     /// Features provided by `VK_KHR_acceleration_structure`.
     acceleration_structure: Option<vk::PhysicalDeviceAccelerationStructureFeaturesKHR<'static>>,
 
@@ -99,6 +102,11 @@ pub struct PhysicalDeviceFeatures {
     /// [`Instance::expose_adapter`]: super::Instance::expose_adapter
     ray_query: Option<vk::PhysicalDeviceRayQueryFeaturesKHR<'static>>,
 
+    /// Features provided by `VK_KHR_ray_tracing_pipeline`.
+    ///
+    /// Like [`Self::ray_query`], this is only populated when creating a device.
+    ray_tracing_pipeline: Option<vk::PhysicalDeviceRayTracingPipelineFeaturesKHR<'static>>,
+
     /// Features provided by `VK_KHR_zero_initialize_workgroup_memory`, promoted
     /// to Vulkan 1.3.
     zero_initialize_workgroup_memory:
@@ -126,6 +134,27 @@ pub struct PhysicalDeviceFeatures {
     /// Features provided by `VK_KHR_shader_integer_dot_product`, promoted to Vulkan 1.3.
     shader_integer_dot_product:
         Option<vk::PhysicalDeviceShaderIntegerDotProductFeaturesKHR<'static>>,
+
+    /// Features provided by `VK_KHR_fragment_shading_rate`.
+    fragment_shading_rate: Option<vk::PhysicalDeviceFragmentShadingRateFeaturesKHR<'static>>,
+
+    /// Features provided by `VK_EXT_line_rasterization`.
+    line_rasterization: Option<vk::PhysicalDeviceLineRasterizationFeaturesEXT<'static>>,
+
+    /// Features provided by `VK_EXT_index_type_uint8`.
+    index_type_uint8: Option<vk::PhysicalDeviceIndexTypeUint8FeaturesEXT<'static>>,
+
+    /// Features provided by `VK_EXT_vertex_attribute_divisor`.
+    vertex_attribute_divisor: Option<vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT<'static>>,
+
+    /// Features provided by `VK_EXT_device_fault`.
+    device_fault: Option<vk::PhysicalDeviceFaultFeaturesEXT<'static>>,
+
+    /// Features provided by `VK_EXT_conditional_rendering`.
+    conditional_rendering: Option<vk::PhysicalDeviceConditionalRenderingFeaturesEXT<'static>>,
+
+    /// Features provided by `VK_KHR_dynamic_rendering`, promoted to Vulkan 1.3.
+    dynamic_rendering: Option<vk::PhysicalDeviceDynamicRenderingFeaturesKHR<'static>>,
 }
 
 impl PhysicalDeviceFeatures {
@@ -150,6 +179,9 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.multiview {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.sampler_ycbcr_conversion {
+            info = info.push_next(feature);
+        }
         if let Some(ref mut feature) = self.astc_hdr {
             info = info.push_next(feature);
         }
@@ -159,6 +191,9 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self._16bit_storage {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self._8bit_storage {
+            info = info.push_next(feature);
+        }
         if let Some(ref mut feature) = self.zero_initialize_workgroup_memory {
             info = info.push_next(feature);
         }
@@ -171,6 +206,9 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.ray_query {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.ray_tracing_pipeline {
+            info = info.push_next(feature);
+        }
         if let Some(ref mut feature) = self.shader_atomic_int64 {
             info = info.push_next(feature);
         }
@@ -195,6 +233,27 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.shader_integer_dot_product {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.fragment_shading_rate {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.line_rasterization {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.index_type_uint8 {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.vertex_attribute_divisor {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.device_fault {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.conditional_rendering {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.dynamic_rendering {
+            info = info.push_next(feature);
+        }
         info
     }
 
@@ -262,12 +321,14 @@ impl PhysicalDeviceFeatures {
                 .multi_draw_indirect(
                     requested_features.contains(wgt::Features::MULTI_DRAW_INDIRECT),
                 )
+                .wide_lines(requested_features.contains(wgt::Features::WIDE_LINES))
+                .logic_op(requested_features.contains(wgt::Features::LOGIC_OP))
                 .fill_mode_non_solid(requested_features.intersects(
                     wgt::Features::POLYGON_MODE_LINE | wgt::Features::POLYGON_MODE_POINT,
                 ))
                 //.depth_bounds(requested_features.contains(wgt::Features::DEPTH_BOUNDS))
                 //.alpha_to_one(requested_features.contains(wgt::Features::ALPHA_TO_ONE))
-                //.multi_viewport(requested_features.contains(wgt::Features::MULTI_VIEWPORTS))
+                .multi_viewport(requested_features.contains(wgt::Features::MULTI_VIEWPORT))
                 .sampler_anisotropy(
                     downlevel_flags.contains(wgt::DownlevelFlags::ANISOTROPIC_FILTERING),
                 )
@@ -316,7 +377,12 @@ impl PhysicalDeviceFeatures {
                 //.shader_resource_residency(requested_features.contains(wgt::Features::SHADER_RESOURCE_RESIDENCY))
                 .geometry_shader(requested_features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX))
                 .depth_clamp(requested_features.contains(wgt::Features::DEPTH_CLIP_CONTROL))
-                .dual_src_blend(requested_features.contains(wgt::Features::DUAL_SOURCE_BLENDING)),
+                .dual_src_blend(requested_features.contains(wgt::Features::DUAL_SOURCE_BLENDING))
+                .sparse_binding(requested_features.contains(wgt::Features::SPARSE_BINDING))
+                .sparse_residency_buffer(requested_features.contains(wgt::Features::SPARSE_BINDING))
+                .sparse_residency_image2_d(
+                    requested_features.contains(wgt::Features::SPARSE_BINDING),
+                ),
             descriptor_indexing: if requested_features.intersects(INDEXING_FEATURES) {
                 Some(
                     vk::PhysicalDeviceDescriptorIndexingFeaturesEXT::default()
@@ -374,7 +440,10 @@ impl PhysicalDeviceFeatures {
                 || enabled_extensions.contains(&khr::sampler_ycbcr_conversion::NAME)
             {
                 Some(
-                    vk::PhysicalDeviceSamplerYcbcrConversionFeatures::default(), // .sampler_ycbcr_conversion(requested_features.contains(wgt::Features::TEXTURE_FORMAT_NV12))
+                    vk::PhysicalDeviceSamplerYcbcrConversionFeatures::default()
+                        .sampler_ycbcr_conversion(
+                            requested_features.contains(wgt::Features::YUV_SAMPLING),
+                        ),
                 )
             } else {
                 None
@@ -395,16 +464,29 @@ impl PhysicalDeviceFeatures {
                 ),
                 _ => None,
             },
-            _16bit_storage: if requested_features.contains(wgt::Features::SHADER_F16) {
+            _16bit_storage: if requested_features
+                .intersects(wgt::Features::SHADER_F16 | wgt::Features::SHADER_I16)
+            {
                 Some(
                     vk::PhysicalDevice16BitStorageFeatures::default()
                         .storage_buffer16_bit_access(true)
-                        .storage_input_output16(true)
+                        .storage_input_output16(
+                            requested_features.contains(wgt::Features::SHADER_F16),
+                        )
                         .uniform_and_storage_buffer16_bit_access(true),
                 )
             } else {
                 None
             },
+            _8bit_storage: if requested_features.contains(wgt::Features::SHADER_I8) {
+                Some(
+                    vk::PhysicalDevice8BitStorageFeatures::default()
+                        .storage_buffer8_bit_access(true)
+                        .uniform_and_storage_buffer8_bit_access(true),
+                )
+            } else {
+                None
+            },
             acceleration_structure: if enabled_extensions
                 .contains(&khr::acceleration_structure::NAME)
             {
@@ -429,6 +511,14 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            ray_tracing_pipeline: if enabled_extensions.contains(&khr::ray_tracing_pipeline::NAME) {
+                Some(
+                    vk::PhysicalDeviceRayTracingPipelineFeaturesKHR::default()
+                        .ray_tracing_pipeline(true),
+                )
+            } else {
+                None
+            },
             zero_initialize_workgroup_memory: if device_api_version >= vk::API_VERSION_1_3
                 || enabled_extensions.contains(&khr::zero_initialize_workgroup_memory::NAME)
             {
@@ -508,6 +598,75 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            fragment_shading_rate: if enabled_extensions.contains(&khr::fragment_shading_rate::NAME)
+            {
+                Some(
+                    vk::PhysicalDeviceFragmentShadingRateFeaturesKHR::default()
+                        .pipeline_fragment_shading_rate(
+                            requested_features.contains(wgt::Features::VARIABLE_RATE_SHADING),
+                        ),
+                )
+            } else {
+                None
+            },
+            line_rasterization: if enabled_extensions.contains(&ext::line_rasterization::NAME) {
+                let needed = requested_features.contains(wgt::Features::LINE_STIPPLE);
+                Some(
+                    vk::PhysicalDeviceLineRasterizationFeaturesEXT::default()
+                        .bresenham_lines(needed)
+                        .stippled_bresenham_lines(needed),
+                )
+            } else {
+                None
+            },
+            index_type_uint8: if enabled_extensions.contains(&ext::index_type_uint8::NAME) {
+                Some(
+                    vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::default().index_type_uint8(
+                        requested_features.contains(wgt::Features::INDEX_FORMAT_UINT8),
+                    ),
+                )
+            } else {
+                None
+            },
+            vertex_attribute_divisor: if enabled_extensions
+                .contains(&ext::vertex_attribute_divisor::NAME)
+            {
+                Some(
+                    vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT::default()
+                        .vertex_attribute_instance_rate_divisor(
+                            requested_features.contains(wgt::Features::VERTEX_INSTANCE_STEP_RATE),
+                        ),
+                )
+            } else {
+                None
+            },
+            device_fault: if enabled_extensions.contains(&ext::device_fault::NAME) {
+                Some(vk::PhysicalDeviceFaultFeaturesEXT::default().device_fault(true))
+            } else {
+                None
+            },
+            conditional_rendering: if enabled_extensions.contains(&ext::conditional_rendering::NAME)
+            {
+                Some(
+                    vk::PhysicalDeviceConditionalRenderingFeaturesEXT::default()
+                        .conditional_rendering(
+                            requested_features.contains(wgt::Features::CONDITIONAL_RENDERING),
+                        ),
+                )
+            } else {
+                None
+            },
+            dynamic_rendering: if device_api_version >= vk::API_VERSION_1_3
+                || enabled_extensions.contains(&khr::dynamic_rendering::NAME)
+            {
+                Some(
+                    vk::PhysicalDeviceDynamicRenderingFeaturesKHR::default().dynamic_rendering(
+                        requested_features.contains(wgt::Features::RENDER_PASS_SUSPEND_RESUME),
+                    ),
+                )
+            } else {
+                None
+            },
             maintenance4: if enabled_extensions.contains(&khr::maintenance4::NAME) {
                 let needed = requested_features.contains(wgt::Features::EXPERIMENTAL_MESH_SHADER);
                 Some(vk::PhysicalDeviceMaintenance4FeaturesKHR::default().maintenance4(needed))
@@ -555,7 +714,11 @@ impl PhysicalDeviceFeatures {
             | F::CLEAR_TEXTURE
             | F::PIPELINE_CACHE
             | F::SHADER_EARLY_DEPTH_TEST
-            | F::TEXTURE_ATOMIC;
+            | F::TEXTURE_ATOMIC
+            | F::TEXTURE_ALIASING
+            | F::PRIMITIVE_RESTART_CONTROL
+            | F::REUSABLE_COMMAND_BUFFERS
+            | F::PARALLEL_RENDER_ENCODING;
 
         let mut dl_flags = Df::COMPUTE_SHADERS
             | Df::BASE_VERTEX
@@ -598,9 +761,10 @@ impl PhysicalDeviceFeatures {
         features.set(F::MULTI_DRAW_INDIRECT, self.core.multi_draw_indirect != 0);
         features.set(F::POLYGON_MODE_LINE, self.core.fill_mode_non_solid != 0);
         features.set(F::POLYGON_MODE_POINT, self.core.fill_mode_non_solid != 0);
+        features.set(F::WIDE_LINES, self.core.wide_lines != 0);
+        features.set(F::LOGIC_OP, self.core.logic_op != 0);
         //if self.core.depth_bounds != 0 {
         //if self.core.alpha_to_one != 0 {
-        //if self.core.multi_viewport != 0 {
         features.set(
             F::TEXTURE_COMPRESSION_ETC2,
             self.core.texture_compression_etc2 != 0,
@@ -618,7 +782,7 @@ impl PhysicalDeviceFeatures {
             self.core.texture_compression_bc != 0, // BC guarantees Sliced 3D
         );
         features.set(
-            F::PIPELINE_STATISTICS_QUERY,
+            F::PIPELINE_STATISTICS_QUERY | F::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES,
             self.core.pipeline_statistics_query != 0,
         );
         features.set(
@@ -628,7 +792,22 @@ impl PhysicalDeviceFeatures {
 
         features.set(F::SHADER_F64, self.core.shader_float64 != 0);
         features.set(F::SHADER_INT64, self.core.shader_int64 != 0);
-        features.set(F::SHADER_I16, self.core.shader_int16 != 0);
+        features.set(
+            F::SHADER_I16,
+            self.core.shader_int16 != 0
+                && self._16bit_storage.is_some_and(|bit16| {
+                    bit16.storage_buffer16_bit_access != 0
+                        && bit16.uniform_and_storage_buffer16_bit_access != 0
+                }),
+        );
+        if let (Some(ref f16_i8), Some(ref bit8)) = (self.shader_float16_int8, self._8bit_storage) {
+            features.set(
+                F::SHADER_I8,
+                f16_i8.shader_int8 != 0
+                    && bit8.storage_buffer8_bit_access != 0
+                    && bit8.uniform_and_storage_buffer8_bit_access != 0,
+            );
+        }
 
         features.set(F::SHADER_PRIMITIVE_INDEX, self.core.geometry_shader != 0);
 
@@ -668,10 +847,21 @@ impl PhysicalDeviceFeatures {
             F::CONSERVATIVE_RASTERIZATION,
             caps.supports_extension(ext::conservative_rasterization::NAME),
         );
+        features.set(
+            F::CONSERVATIVE_RASTERIZATION_FULLY_COVERED,
+            caps.conservative_rasterization.is_some_and(|conservative| {
+                conservative.fully_covered_fragment_shader_input_variable != 0
+            }),
+        );
         features.set(
             F::EXPERIMENTAL_RAY_HIT_VERTEX_RETURN,
             caps.supports_extension(khr::ray_tracing_position_fetch::NAME),
         );
+        features.set(
+            F::MULTI_VIEWPORT,
+            self.core.multi_viewport != 0
+                && caps.supports_extension(ext::shader_viewport_index_layer::NAME),
+        );
 
         if let Some(ref descriptor_indexing) = self.descriptor_indexing {
             // We use update-after-bind descriptors for all bind groups containing binding arrays.
@@ -808,9 +998,15 @@ impl PhysicalDeviceFeatures {
             F::EXPERIMENTAL_RAY_QUERY
             // Although this doesn't really require ray queries, it does not make sense to be enabled if acceleration structures
             // aren't enabled. 
-                | F::EXTENDED_ACCELERATION_STRUCTURE_VERTEX_FORMATS,
+                | F::EXTENDED_ACCELERATION_STRUCTURE_VERTEX_FORMATS
+                | F::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION,
             supports_acceleration_structures && caps.supports_extension(khr::ray_query::NAME),
         );
+        features.set(
+            F::EXPERIMENTAL_RAY_TRACING_PIPELINE,
+            supports_acceleration_structures
+                && caps.supports_extension(khr::ray_tracing_pipeline::NAME),
+        );
 
         let rg11b10ufloat_renderable = supports_format(
             instance,
@@ -832,26 +1028,55 @@ impl PhysicalDeviceFeatures {
             is_float32_filterable_supported(instance, phd),
         );
 
-        if let Some(ref _sampler_ycbcr_conversion) = self.sampler_ycbcr_conversion {
-            features.set(
-                F::TEXTURE_FORMAT_NV12,
-                supports_format(
+        if let Some(ref sampler_ycbcr_conversion) = self.sampler_ycbcr_conversion {
+            let is_moltenvk = caps
+                .driver
+                .map(|driver| driver.driver_id == vk::DriverId::MOLTENVK)
+                .unwrap_or_default();
+            // Y'CbCr conversions are created with the core 1.1 entry points, and every
+            // supported multi-planar format must be usable with any chroma location
+            // and chroma filter.
+            let mut yuv_sampling = caps.device_api_version >= vk::API_VERSION_1_1
+                && sampler_ycbcr_conversion.sampler_ycbcr_conversion != 0
+                && !is_moltenvk;
+            for (feature, format) in [
+                (F::TEXTURE_FORMAT_NV12, vk::Format::G8_B8R8_2PLANE_420_UNORM),
+                (
+                    F::TEXTURE_FORMAT_P010,
+                    vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16,
+                ),
+                (
+                    F::TEXTURE_FORMAT_I420,
+                    vk::Format::G8_B8_R8_3PLANE_420_UNORM,
+                ),
+            ] {
+                let supported = supports_format(
                     instance,
                     phd,
-                    vk::Format::G8_B8R8_2PLANE_420_UNORM,
+                    format,
                     vk::ImageTiling::OPTIMAL,
                     vk::FormatFeatureFlags::SAMPLED_IMAGE
                         | vk::FormatFeatureFlags::TRANSFER_SRC
                         | vk::FormatFeatureFlags::TRANSFER_DST,
-                ) && !caps
-                    .driver
-                    .map(|driver| driver.driver_id == vk::DriverId::MOLTENVK)
-                    .unwrap_or_default(),
-            );
+                ) && !is_moltenvk;
+                features.set(feature, supported);
+                if supported {
+                    yuv_sampling &= supports_format(
+                        instance,
+                        phd,
+                        format,
+                        vk::ImageTiling::OPTIMAL,
+                        vk::FormatFeatureFlags::MIDPOINT_CHROMA_SAMPLES
+                            | vk::FormatFeatureFlags::COSITED_CHROMA_SAMPLES
+                            | vk::FormatFeatureFlags::SAMPLED_IMAGE_YCBCR_CONVERSION_LINEAR_FILTER,
+                    );
+                }
+            }
+            features.set(F::YUV_SAMPLING, yuv_sampling);
         }
 
         features.set(
-            F::VULKAN_GOOGLE_DISPLAY_TIMING,
+            F::VULKAN_GOOGLE_DISPLAY_TIMING | F::PRESENTATION_TIMING,
             caps.supports_extension(google::display_timing::NAME),
         );
 
@@ -859,6 +1084,24 @@ impl PhysicalDeviceFeatures {
             F::VULKAN_EXTERNAL_MEMORY_WIN32,
             caps.supports_extension(khr::external_memory_win32::NAME),
         );
+        features.set(
+            F::VULKAN_EXTERNAL_MEMORY_DMA_BUF,
+            cfg!(all(
+                unix,
+                not(target_vendor = "apple"),
+                not(target_family = "wasm")
+            )) && caps.device_api_version >= vk::API_VERSION_1_2
+                && caps.supports_extension(khr::external_memory_fd::NAME)
+                && caps.supports_extension(ext::external_memory_dma_buf::NAME)
+                && caps.supports_extension(ext::image_drm_format_modifier::NAME),
+        );
+        features.set(
+            F::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER,
+            cfg!(target_os = "android")
+                && caps.device_api_version >= vk::API_VERSION_1_1
+                && caps.supports_extension(android::external_memory_android_hardware_buffer::NAME)
+                && caps.supports_extension(ext::queue_family_foreign::NAME),
+        );
         features.set(
             F::EXPERIMENTAL_MESH_SHADER,
             caps.supports_extension(ext::mesh_shader::NAME),
@@ -869,6 +1112,78 @@ impl PhysicalDeviceFeatures {
                 mesh_shader.multiview_mesh_shader != 0,
             );
         }
+        features.set(
+            F::LINE_STIPPLE,
+            self.line_rasterization.is_some_and(|line| {
+                line.bresenham_lines != 0 && line.stippled_bresenham_lines != 0
+            }),
+        );
+        features.set(
+            F::INDEX_FORMAT_UINT8,
+            self.index_type_uint8
+                .is_some_and(|uint8| uint8.index_type_uint8 != 0),
+        );
+        features.set(
+            F::VERTEX_INSTANCE_STEP_RATE,
+            self.vertex_attribute_divisor
+                .is_some_and(|divisor| divisor.vertex_attribute_instance_rate_divisor != 0),
+        );
+        features.set(
+            F::VARIABLE_RATE_SHADING,
+            self.fragment_shading_rate
+                .is_some_and(|fsr| fsr.pipeline_fragment_shading_rate != 0),
+        );
+        features.set(
+            F::CONDITIONAL_RENDERING,
+            self.conditional_rendering
+                .is_some_and(|conditional| conditional.conditional_rendering != 0),
+        );
+        features.set(
+            F::RENDER_PASS_SUSPEND_RESUME,
+            self.dynamic_rendering
+                .is_some_and(|dynamic| dynamic.dynamic_rendering != 0),
+        );
+        features.set(
+            F::SHADER_DEBUG_PRINTF,
+            caps.device_api_version >= vk::API_VERSION_1_3
+                || caps.supports_extension(khr::shader_non_semantic_info::NAME),
+        );
+
+        // Sparse memory is bound on the main queue, which is from the first
+        // family. Standard block shapes make every tile of a 2D image take up
+        // `SPARSE_PAGE_SIZE` bytes.
+        let main_queue_flags = unsafe { instance.get_physical_device_queue_family_properties(phd) }
+            .first()
+            .map(|family| family.queue_flags)
+            .unwrap_or_default();
+        features.set(
+            F::SPARSE_BINDING,
+            self.core.sparse_binding != 0
+                && self.core.sparse_residency_buffer != 0
+                && self.core.sparse_residency_image2_d != 0
+                && caps
+                    .properties
+                    .sparse_properties
+                    .residency_standard2_d_block_shape
+                    != 0
+                && main_queue_flags.contains(vk::QueueFlags::SPARSE_BINDING),
+        );
+
+        // Stencil always resolves sample zero, so depth needs to be able to
+        // use a different mode, or none at all.
+        features.set(
+            F::DEPTH_STENCIL_RESOLVE,
+            caps.depth_stencil_resolve.is_some_and(|resolve| {
+                resolve.supported_depth_resolve_modes.contains(
+                    vk::ResolveModeFlags::SAMPLE_ZERO
+                        | vk::ResolveModeFlags::MIN
+                        | vk::ResolveModeFlags::MAX,
+                ) && resolve
+                    .supported_stencil_resolve_modes
+                    .contains(vk::ResolveModeFlags::SAMPLE_ZERO)
+                    && resolve.independent_resolve != 0
+            }),
+        );
         (features, dl_flags)
     }
 }
@@ -915,6 +1230,10 @@ pub struct PhysicalDeviceProperties {
     /// `VK_KHR_acceleration_structure` extension.
     acceleration_structure: Option<vk::PhysicalDeviceAccelerationStructurePropertiesKHR<'static>>,
 
+    /// Additional `vk::PhysicalDevice` properties from the
+    /// `VK_KHR_ray_tracing_pipeline` extension.
+    ray_tracing_pipeline: Option<vk::PhysicalDeviceRayTracingPipelinePropertiesKHR<'static>>,
+
     /// Additional `vk::PhysicalDevice` properties from the
     /// `VK_KHR_driver_properties` extension, promoted to Vulkan 1.2.
     driver: Option<vk::PhysicalDeviceDriverPropertiesKHR<'static>>,
@@ -934,12 +1253,21 @@ pub struct PhysicalDeviceProperties {
     /// `VK_EXT_mesh_shader` extension.
     _mesh_shader: Option<vk::PhysicalDeviceMeshShaderPropertiesEXT<'static>>,
 
+    /// Additional `vk::PhysicalDevice` properties from the
+    /// `VK_KHR_depth_stencil_resolve` extension, promoted to Vulkan 1.2.
+    depth_stencil_resolve: Option<vk::PhysicalDeviceDepthStencilResolveProperties<'static>>,
+
+    /// Additional `vk::PhysicalDevice` properties from the
+    /// `VK_EXT_conservative_rasterization` extension.
+    conservative_rasterization:
+        Option<vk::PhysicalDeviceConservativeRasterizationPropertiesEXT<'static>>,
+
     /// The device API version.
     ///
     /// Which is the version of Vulkan supported for device-level functionality.
     ///
     /// It is associated with a `VkPhysicalDevice` and its children.
-    device_api_version: u32,
+    pub(super) device_api_version: u32,
 }
 
 impl PhysicalDeviceProperties {
@@ -986,12 +1314,18 @@ impl PhysicalDeviceProperties {
             }
 
             // Require `VK_KHR_sampler_ycbcr_conversion` if the associated feature was requested
-            if requested_features.contains(wgt::Features::TEXTURE_FORMAT_NV12) {
+            if requested_features.intersects(
+                wgt::Features::TEXTURE_FORMAT_NV12
+                    | wgt::Features::TEXTURE_FORMAT_P010
+                    | wgt::Features::TEXTURE_FORMAT_I420
+                    | wgt::Features::YUV_SAMPLING,
+            ) {
                 extensions.push(khr::sampler_ycbcr_conversion::NAME);
             }
 
-            // Require `VK_KHR_16bit_storage` if the feature `SHADER_F16` was requested
-            if requested_features.contains(wgt::Features::SHADER_F16) {
+            // Require `VK_KHR_16bit_storage` if the feature `SHADER_F16` or `SHADER_I16` was requested
+            if requested_features.intersects(wgt::Features::SHADER_F16 | wgt::Features::SHADER_I16)
+            {
                 // - Feature `SHADER_F16` also requires `VK_KHR_shader_float16_int8`, but we always
                 //   require that anyway (if it is available) below.
                 // - `VK_KHR_16bit_storage` requires `VK_KHR_storage_buffer_storage_class`, however
@@ -1030,7 +1364,15 @@ impl PhysicalDeviceProperties {
                 extensions.push(khr::shader_float16_int8::NAME);
             }
 
-            if requested_features.intersects(wgt::Features::EXPERIMENTAL_MESH_SHADER) {
+            // Require `VK_KHR_8bit_storage` if the feature `SHADER_I8` was requested
+            if requested_features.contains(wgt::Features::SHADER_I8) {
+                extensions.push(khr::_8bit_storage::NAME);
+            }
+
+            if requested_features.intersects(
+                wgt::Features::EXPERIMENTAL_MESH_SHADER
+                    | wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE,
+            ) {
                 extensions.push(khr::spirv_1_4::NAME);
             }
 
@@ -1079,11 +1421,41 @@ impl PhysicalDeviceProperties {
             extensions.push(khr::external_memory_fd::NAME);
         }
 
+        // Optional `VK_KHR_external_semaphore_fd`
+        if self.supports_extension(khr::external_semaphore_fd::NAME) {
+            extensions.push(khr::external_semaphore_fd::NAME);
+        }
+
+        // Optional `VK_KHR_external_semaphore_win32`
+        if self.supports_extension(khr::external_semaphore_win32::NAME) {
+            extensions.push(khr::external_semaphore_win32::NAME);
+        }
+
         // Optional `VK_EXT_external_memory_dma`
         if self.supports_extension(ext::external_memory_dma_buf::NAME) {
             extensions.push(ext::external_memory_dma_buf::NAME);
         }
 
+        // Optional `VK_EXT_hdr_metadata`
+        if self.supports_extension(ext::hdr_metadata::NAME) {
+            extensions.push(ext::hdr_metadata::NAME);
+        }
+
+        // Optional `VK_EXT_calibrated_timestamps`
+        if self.supports_extension(ext::calibrated_timestamps::NAME) {
+            extensions.push(ext::calibrated_timestamps::NAME);
+        }
+
+        // Optional `VK_KHR_incremental_present`
+        if self.supports_extension(khr::incremental_present::NAME) {
+            extensions.push(khr::incremental_present::NAME);
+        }
+
+        // Optional `VK_EXT_full_screen_exclusive`
+        if self.supports_extension(ext::full_screen_exclusive::NAME) {
+            extensions.push(ext::full_screen_exclusive::NAME);
+        }
+
         // Optional `VK_EXT_memory_budget`
         if self.supports_extension(ext::memory_budget::NAME) {
             extensions.push(ext::memory_budget::NAME);
@@ -1098,23 +1470,55 @@ impl PhysicalDeviceProperties {
             extensions.push(khr::draw_indirect_count::NAME);
         }
 
-        // Require `VK_KHR_deferred_host_operations`, `VK_KHR_acceleration_structure` `VK_KHR_buffer_device_address` (for acceleration structures) and`VK_KHR_ray_query` if `EXPERIMENTAL_RAY_QUERY` was requested
-        if requested_features.contains(wgt::Features::EXPERIMENTAL_RAY_QUERY) {
+        // Require `VK_KHR_deferred_host_operations`, `VK_KHR_acceleration_structure` and `VK_KHR_buffer_device_address` (for acceleration structures) if either ray tracing feature was requested
+        if requested_features.intersects(
+            wgt::Features::EXPERIMENTAL_RAY_QUERY
+                | wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE,
+        ) {
             extensions.push(khr::deferred_host_operations::NAME);
             extensions.push(khr::acceleration_structure::NAME);
             extensions.push(khr::buffer_device_address::NAME);
+        }
+
+        // Require `VK_EXT_image_drm_format_modifier` if `VULKAN_EXTERNAL_MEMORY_DMA_BUF` was requested
+        if requested_features.contains(wgt::Features::VULKAN_EXTERNAL_MEMORY_DMA_BUF) {
+            extensions.push(ext::image_drm_format_modifier::NAME);
+        }
+
+        // Require `VK_ANDROID_external_memory_android_hardware_buffer` and `VK_EXT_queue_family_foreign`
+        // if `EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER` was requested
+        if requested_features.contains(wgt::Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER) {
+            extensions.push(android::external_memory_android_hardware_buffer::NAME);
+            extensions.push(ext::queue_family_foreign::NAME);
+        }
+
+        // Require `VK_KHR_ray_query` if `EXPERIMENTAL_RAY_QUERY` was requested
+        if requested_features.contains(wgt::Features::EXPERIMENTAL_RAY_QUERY) {
             extensions.push(khr::ray_query::NAME);
         }
 
+        // Require `VK_KHR_ray_tracing_pipeline` if `EXPERIMENTAL_RAY_TRACING_PIPELINE` was requested
+        if requested_features.contains(wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE) {
+            extensions.push(khr::ray_tracing_pipeline::NAME);
+        }
+
         if requested_features.contains(wgt::Features::EXPERIMENTAL_RAY_HIT_VERTEX_RETURN) {
             extensions.push(khr::ray_tracing_position_fetch::NAME)
         }
 
-        // Require `VK_EXT_conservative_rasterization` if the associated feature was requested
-        if requested_features.contains(wgt::Features::CONSERVATIVE_RASTERIZATION) {
+        // Require `VK_EXT_conservative_rasterization` if the associated features were requested
+        if requested_features.intersects(
+            wgt::Features::CONSERVATIVE_RASTERIZATION
+                | wgt::Features::CONSERVATIVE_RASTERIZATION_FULLY_COVERED,
+        ) {
             extensions.push(ext::conservative_rasterization::NAME);
         }
 
+        // Require `VK_EXT_shader_viewport_index_layer` if the associated feature was requested
+        if requested_features.contains(wgt::Features::MULTI_VIEWPORT) {
+            extensions.push(ext::shader_viewport_index_layer::NAME);
+        }
+
         // Require `VK_KHR_portability_subset` on macOS/iOS
         #[cfg(target_vendor = "apple")]
         extensions.push(khr::portability_subset::NAME);
@@ -1141,8 +1545,10 @@ impl PhysicalDeviceProperties {
             extensions.push(ext::shader_atomic_float::NAME);
         }
 
-        // Require VK_GOOGLE_display_timing if the associated feature was requested
-        if requested_features.contains(wgt::Features::VULKAN_GOOGLE_DISPLAY_TIMING) {
+        // Require VK_GOOGLE_display_timing if one of the associated features was requested
+        if requested_features.intersects(
+            wgt::Features::VULKAN_GOOGLE_DISPLAY_TIMING | wgt::Features::PRESENTATION_TIMING,
+        ) {
             extensions.push(google::display_timing::NAME);
         }
 
@@ -1150,6 +1556,73 @@ impl PhysicalDeviceProperties {
             extensions.push(ext::mesh_shader::NAME);
         }
 
+        // Require `VK_KHR_shader_non_semantic_info` if the associated feature was requested,
+        // it is promoted to 1.3
+        if self.device_api_version < vk::API_VERSION_1_3
+            && requested_features.contains(wgt::Features::SHADER_DEBUG_PRINTF)
+        {
+            extensions.push(khr::shader_non_semantic_info::NAME);
+        }
+
+        // Require `VK_KHR_depth_stencil_resolve` and the `VK_KHR_create_renderpass2` it
+        // depends on if the associated feature was requested, both are promoted to 1.2
+        if self.device_api_version < vk::API_VERSION_1_2
+            && requested_features.contains(wgt::Features::DEPTH_STENCIL_RESOLVE)
+        {
+            extensions.push(khr::create_renderpass2::NAME);
+            extensions.push(khr::depth_stencil_resolve::NAME);
+        }
+
+        // Require `VK_EXT_line_rasterization` if the associated feature was requested
+        if requested_features.contains(wgt::Features::LINE_STIPPLE) {
+            extensions.push(ext::line_rasterization::NAME);
+        }
+
+        // Require `VK_EXT_index_type_uint8` if the associated feature was requested
+        if requested_features.contains(wgt::Features::INDEX_FORMAT_UINT8) {
+            extensions.push(ext::index_type_uint8::NAME);
+        }
+
+        // Require `VK_EXT_vertex_attribute_divisor` if the associated feature was requested
+        if requested_features.contains(wgt::Features::VERTEX_INSTANCE_STEP_RATE) {
+            extensions.push(ext::vertex_attribute_divisor::NAME);
+        }
+
+        // Require `VK_KHR_fragment_shading_rate` if the associated feature was requested,
+        // along with `VK_KHR_create_renderpass2`, which it depends on before 1.2
+        if requested_features.contains(wgt::Features::VARIABLE_RATE_SHADING) {
+            if self.device_api_version < vk::API_VERSION_1_2
+                && !extensions.contains(&khr::create_renderpass2::NAME)
+            {
+                extensions.push(khr::create_renderpass2::NAME);
+            }
+            extensions.push(khr::fragment_shading_rate::NAME);
+        }
+
+        // Require `VK_EXT_conditional_rendering` if the associated feature was requested
+        if requested_features.contains(wgt::Features::CONDITIONAL_RENDERING) {
+            extensions.push(ext::conditional_rendering::NAME);
+        }
+
+        // Require `VK_KHR_dynamic_rendering` if the associated feature was requested, along
+        // with the `VK_KHR_depth_stencil_resolve` and `VK_KHR_create_renderpass2` it depends
+        // on before 1.2. It is promoted to 1.3.
+        if self.device_api_version < vk::API_VERSION_1_3
+            && requested_features.contains(wgt::Features::RENDER_PASS_SUSPEND_RESUME)
+        {
+            if self.device_api_version < vk::API_VERSION_1_2 {
+                for name in [
+                    khr::create_renderpass2::NAME,
+                    khr::depth_stencil_resolve::NAME,
+                ] {
+                    if !extensions.contains(&name) {
+                        extensions.push(name);
+                    }
+                }
+            }
+            extensions.push(khr::dynamic_rendering::NAME);
+        }
+
         extensions
     }
 
@@ -1270,6 +1743,9 @@ impl PhysicalDeviceProperties {
             max_blas_primitive_count,
             max_blas_geometry_count,
             max_tlas_instance_count,
+            max_ray_recursion_depth: self
+                .ray_tracing_pipeline
+                .map_or(0, |properties| properties.max_ray_recursion_depth),
             max_acceleration_structures_per_shader_stage,
         }
     }
@@ -1313,6 +1789,15 @@ impl PhysicalDeviceProperties {
                     acceleration_structure.min_acceleration_structure_scratch_offset_alignment
                 },
             ),
+            ray_tracing_shader_group_handle_size: self
+                .ray_tracing_pipeline
+                .map_or(0, |properties| properties.shader_group_handle_size),
+            ray_tracing_shader_group_handle_alignment: self
+                .ray_tracing_pipeline
+                .map_or(0, |properties| properties.shader_group_handle_alignment),
+            ray_tracing_shader_group_base_alignment: self
+                .ray_tracing_pipeline
+                .map_or(0, |properties| properties.shader_group_base_alignment),
         }
     }
 }
@@ -1343,12 +1828,22 @@ impl super::InstanceShared {
                     >= vk::API_VERSION_1_3
                     || capabilities.supports_extension(ext::subgroup_size_control::NAME);
                 let supports_robustness2 = capabilities.supports_extension(ext::robustness2::NAME);
+                let supports_depth_stencil_resolve = capabilities.device_api_version
+                    >= vk::API_VERSION_1_2
+                    || (capabilities.supports_extension(khr::depth_stencil_resolve::NAME)
+                        && capabilities.supports_extension(khr::create_renderpass2::NAME));
 
                 let supports_acceleration_structure =
                     capabilities.supports_extension(khr::acceleration_structure::NAME);
 
+                let supports_ray_tracing_pipeline =
+                    capabilities.supports_extension(khr::ray_tracing_pipeline::NAME);
+
                 let supports_mesh_shader = capabilities.supports_extension(ext::mesh_shader::NAME);
 
+                let supports_conservative_rasterization =
+                    capabilities.supports_extension(ext::conservative_rasterization::NAME);
+
                 let mut properties2 = vk::PhysicalDeviceProperties2KHR::default();
                 if supports_maintenance3 {
                     let next = capabilities
@@ -1371,6 +1866,13 @@ impl super::InstanceShared {
                     properties2 = properties2.push_next(next);
                 }
 
+                if supports_ray_tracing_pipeline {
+                    let next = capabilities
+                        .ray_tracing_pipeline
+                        .insert(vk::PhysicalDeviceRayTracingPipelinePropertiesKHR::default());
+                    properties2 = properties2.push_next(next);
+                }
+
                 if supports_driver_properties {
                     let next = capabilities
                         .driver
@@ -1392,6 +1894,13 @@ impl super::InstanceShared {
                     properties2 = properties2.push_next(next);
                 }
 
+                if supports_depth_stencil_resolve {
+                    let next = capabilities
+                        .depth_stencil_resolve
+                        .insert(vk::PhysicalDeviceDepthStencilResolveProperties::default());
+                    properties2 = properties2.push_next(next);
+                }
+
                 if supports_robustness2 {
                     let next = capabilities
                         .robustness2
@@ -1406,6 +1915,13 @@ impl super::InstanceShared {
                     properties2 = properties2.push_next(next);
                 }
 
+                if supports_conservative_rasterization {
+                    let next = capabilities.conservative_rasterization.insert(
+                        vk::PhysicalDeviceConservativeRasterizationPropertiesEXT::default(),
+                    );
+                    properties2 = properties2.push_next(next);
+                }
+
                 unsafe {
                     get_device_properties.get_physical_device_properties2(phd, &mut properties2)
                 };
@@ -1523,6 +2039,16 @@ impl super::InstanceShared {
                     .insert(vk::PhysicalDevice16BitStorageFeaturesKHR::default());
                 features2 = features2.push_next(next);
             }
+
+            // `VK_KHR_8bit_storage` is promoted to 1.2
+            if capabilities.device_api_version >= vk::API_VERSION_1_2
+                || capabilities.supports_extension(khr::_8bit_storage::NAME)
+            {
+                let next = features
+                    ._8bit_storage
+                    .insert(vk::PhysicalDevice8BitStorageFeaturesKHR::default());
+                features2 = features2.push_next(next);
+            }
             if capabilities.supports_extension(khr::acceleration_structure::NAME) {
                 let next = features
                     .acceleration_structure
@@ -1564,6 +2090,58 @@ impl super::InstanceShared {
                 features2 = features2.push_next(next);
             }
 
+            if capabilities.supports_extension(khr::fragment_shading_rate::NAME) {
+                let next = features
+                    .fragment_shading_rate
+                    .insert(vk::PhysicalDeviceFragmentShadingRateFeaturesKHR::default());
+                features2 = features2.push_next(next);
+            }
+
+            if capabilities.supports_extension(ext::line_rasterization::NAME) {
+                let next = features
+                    .line_rasterization
+                    .insert(vk::PhysicalDeviceLineRasterizationFeaturesEXT::default());
+                features2 = features2.push_next(next);
+            }
+
+            if capabilities.supports_extension(ext::index_type_uint8::NAME) {
+                let next = features
+                    .index_type_uint8
+                    .insert(vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::default());
+                features2 = features2.push_next(next);
+            }
+
+            if capabilities.supports_extension(ext::vertex_attribute_divisor::NAME) {
+                let next = features
+                    .vertex_attribute_divisor
+                    .insert(vk::PhysicalDeviceVertexAttributeDivisorFeaturesEXT::default());
+                features2 = features2.push_next(next);
+            }
+
+            if capabilities.supports_extension(ext::device_fault::NAME) {
+                let next = features
+                    .device_fault
+                    .insert(vk::PhysicalDeviceFaultFeaturesEXT::default());
+                features2 = features2.push_next(next);
+            }
+
+            if capabilities.supports_extension(ext::conditional_rendering::NAME) {
+                let next = features
+                    .conditional_rendering
+                    .insert(vk::PhysicalDeviceConditionalRenderingFeaturesEXT::default());
+                features2 = features2.push_next(next);
+            }
+
+            // `VK_KHR_dynamic_rendering` is promoted to 1.3
+            if capabilities.device_api_version >= vk::API_VERSION_1_3
+                || capabilities.supports_extension(khr::dynamic_rendering::NAME)
+            {
+                let next = features
+                    .dynamic_rendering
+                    .insert(vk::PhysicalDeviceDynamicRenderingFeaturesKHR::default());
+                features2 = features2.push_next(next);
+            }
+
             // `VK_KHR_shader_integer_dot_product` is promoted to 1.3
             if capabilities.device_api_version >= vk::API_VERSION_1_3
                 || capabilities.supports_extension(khr::shader_integer_dot_product::NAME)
@@ -1633,7 +2211,7 @@ impl super::Instance {
             },
             backend: wgt::Backend::Vulkan,
         };
-        let (available_features, downlevel_flags) =
+        let (mut available_features, downlevel_flags) =
             phd_features.to_wgpu(&self.shared.raw, phd, &phd_capabilities);
         let mut workarounds = super::Workarounds::empty();
         {
@@ -1648,6 +2226,7 @@ impl super::Instance {
                 phd_capabilities.properties.vendor_id == db::nvidia::VENDOR,
             );
         };
+        let (workarounds, toggles) = workarounds.apply_toggles(&self.shared.toggles);
 
         if let Some(driver) = phd_capabilities.driver {
             if driver.conformance_version.major == 0 {
@@ -1763,7 +2342,29 @@ impl super::Instance {
             shader_int8: phd_features
                 .shader_float16_int8
                 .is_some_and(|features| features.shader_int8 != 0),
+            ray_tracing_shader_group_handle_size: phd_capabilities
+                .ray_tracing_pipeline
+                .map_or(0, |properties| properties.shader_group_handle_size),
+            ray_tracing_shader_group_base_alignment: phd_capabilities
+                .ray_tracing_pipeline
+                .map_or(0, |properties| properties.shader_group_base_alignment),
         };
+        // Each additional queue signals its own fence, which other queues wait on
+        // with timeline semaphore waits.
+        available_features.set(
+            wgt::Features::EXPERIMENTAL_MULTI_QUEUE,
+            private_caps.timeline_semaphores,
+        );
+        available_features.set(
+            wgt::Features::EXPERIMENTAL_SHARED_FENCE,
+            private_caps.timeline_semaphores
+                && phd_capabilities.supports_extension(super::SHARED_FENCE_EXTENSION)
+                && supports_shared_timeline_semaphore(
+                    &self.shared.raw,
+                    phd,
+                    phd_capabilities.device_api_version,
+                ),
+        );
         let capabilities = crate::Capabilities {
             limits: phd_capabilities.to_wgpu_limits(),
             alignments: phd_capabilities.to_hal_alignments(private_caps.robust_buffer_access2),
@@ -1777,7 +2378,7 @@ impl super::Instance {
         let adapter = super::Adapter {
             raw: phd,
             instance: Arc::clone(&self.shared),
-            //queue_families,
+            queue_families,
             known_memory_flags: vk::MemoryPropertyFlags::DEVICE_LOCAL
                 | vk::MemoryPropertyFlags::HOST_VISIBLE
                 | vk::MemoryPropertyFlags::HOST_COHERENT
@@ -1795,6 +2396,7 @@ impl super::Instance {
             info,
             features: available_features,
             capabilities,
+            toggles,
         })
     }
 }
@@ -1821,6 +2423,29 @@ impl super::Adapter {
                 self.phd_capabilities.supports_extension(extension)
             });
 
+        let mut supported_extensions = supported_extensions;
+        // `VK_EXT_full_screen_exclusive` depends on an instance extension.
+        if !self
+            .instance
+            .extensions
+            .contains(&khr::get_surface_capabilities2::NAME)
+        {
+            supported_extensions.retain(|&extension| extension != ext::full_screen_exclusive::NAME);
+        }
+
+        // Optional `VK_EXT_device_fault`, only enabled when asked for as it may add overhead.
+        if self
+            .instance
+            .flags
+            .contains(wgt::InstanceFlags::DEVICE_FAULT_DIAGNOSTICS)
+            && self
+                .phd_features
+                .device_fault
+                .is_some_and(|features| features.device_fault != 0)
+        {
+            supported_extensions.push(ext::device_fault::NAME);
+        }
+
         if !unsupported_extensions.is_empty() {
             log::warn!("Missing extensions: {:?}", unsupported_extensions);
         }
@@ -1875,6 +2500,37 @@ impl super::Adapter {
         memory_hints: &wgt::MemoryHints,
         family_index: u32,
         queue_index: u32,
+    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
+        unsafe {
+            self.device_from_raw_with_queues(
+                raw_device,
+                drop_callback,
+                enabled_extensions,
+                features,
+                memory_hints,
+                (family_index, queue_index),
+                &[],
+            )
+        }
+    }
+
+    /// Like [`Self::device_from_raw`], but also retrieves the additional queues
+    /// at the `(family index, queue index)` locations of `additional_queues`.
+    ///
+    /// # Safety
+    ///
+    /// - Same as [`Self::device_from_raw`] plus
+    /// - `raw_device` must be created with all the queues in `additional_queues`.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn device_from_raw_with_queues(
+        &self,
+        raw_device: ash::Device,
+        drop_callback: Option<crate::DropCallback>,
+        enabled_extensions: &[&'static CStr],
+        features: wgt::Features,
+        memory_hints: &wgt::MemoryHints,
+        (family_index, queue_index): (u32, u32),
+        additional_queues: &[(u32, u32)],
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let mem_properties = {
             profiling::scope!("vkGetPhysicalDeviceMemoryProperties");
@@ -1892,6 +2548,33 @@ impl super::Adapter {
                 u
             }
         });
+        let lazily_allocated_memory_types =
+            memory_types.iter().enumerate().fold(0, |u, (i, mem)| {
+                if mem
+                    .property_flags
+                    .contains(vk::MemoryPropertyFlags::LAZILY_ALLOCATED)
+                {
+                    u | (1 << i)
+                } else {
+                    u
+                }
+            });
+
+        // Prefer memory that is only device local, so that heaps don't eat into
+        // the (possibly small) host visible part of VRAM.
+        let placed_memory_type = memory_types
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| valid_ash_memory_types & (1 << i) != 0)
+            .filter(|(_, mem)| {
+                mem.property_flags
+                    .contains(vk::MemoryPropertyFlags::DEVICE_LOCAL)
+                    && !mem
+                        .property_flags
+                        .contains(vk::MemoryPropertyFlags::LAZILY_ALLOCATED)
+            })
+            .min_by_key(|(_, mem)| mem.property_flags != vk::MemoryPropertyFlags::DEVICE_LOCAL)
+            .map(|(i, _)| i as u32);
 
         let swapchain_fn = khr::swapchain::Device::new(&self.instance.raw, &raw_device);
 
@@ -1923,6 +2606,26 @@ impl super::Adapter {
         } else {
             None
         };
+        let create_renderpass2_fn = if enabled_extensions.contains(&khr::create_renderpass2::NAME) {
+            Some(super::ExtensionFn::Extension(
+                khr::create_renderpass2::Device::new(&self.instance.raw, &raw_device),
+            ))
+        } else if self.phd_capabilities.device_api_version >= vk::API_VERSION_1_2 {
+            Some(super::ExtensionFn::Promoted)
+        } else {
+            None
+        };
+        // Render passes are only begun with dynamic rendering if they can be suspended.
+        let dynamic_rendering_fn = if !features.contains(wgt::Features::RENDER_PASS_SUSPEND_RESUME)
+        {
+            None
+        } else if enabled_extensions.contains(&khr::dynamic_rendering::NAME) {
+            Some(super::ExtensionFn::Extension(
+                khr::dynamic_rendering::Device::new(&self.instance.raw, &raw_device),
+            ))
+        } else {
+            Some(super::ExtensionFn::Promoted)
+        };
         let ray_tracing_fns = if enabled_extensions.contains(&khr::acceleration_structure::NAME)
             && enabled_extensions.contains(&khr::buffer_device_address::NAME)
         {
@@ -1939,6 +2642,103 @@ impl super::Adapter {
         } else {
             None
         };
+        let ray_tracing_pipeline_fn =
+            if enabled_extensions.contains(&khr::ray_tracing_pipeline::NAME) {
+                Some(khr::ray_tracing_pipeline::Device::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+        #[cfg(not(windows))]
+        let external_semaphore_fd_fn =
+            if enabled_extensions.contains(&khr::external_semaphore_fd::NAME) {
+                Some(khr::external_semaphore_fd::Device::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+        #[cfg(windows)]
+        let external_semaphore_win32_fn =
+            if enabled_extensions.contains(&khr::external_semaphore_win32::NAME) {
+                Some(khr::external_semaphore_win32::Device::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+        #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+        let external_memory_fd_fn = if enabled_extensions.contains(&khr::external_memory_fd::NAME) {
+            Some(khr::external_memory_fd::Device::new(
+                &self.instance.raw,
+                &raw_device,
+            ))
+        } else {
+            None
+        };
+        #[cfg(target_os = "android")]
+        let external_memory_android_hardware_buffer_fn = if enabled_extensions
+            .contains(&android::external_memory_android_hardware_buffer::NAME)
+        {
+            Some(
+                android::external_memory_android_hardware_buffer::Device::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ),
+            )
+        } else {
+            None
+        };
+        let hdr_metadata_fn = if enabled_extensions.contains(&ext::hdr_metadata::NAME) {
+            Some(ext::hdr_metadata::Device::new(
+                &self.instance.raw,
+                &raw_device,
+            ))
+        } else {
+            None
+        };
+        let device_fault_fn = if enabled_extensions.contains(&ext::device_fault::NAME) {
+            Some(ext::device_fault::Device::new(
+                &self.instance.raw,
+                &raw_device,
+            ))
+        } else {
+            None
+        };
+        let calibrated_timestamps_fn = if enabled_extensions
+            .contains(&ext::calibrated_timestamps::NAME)
+        {
+            // Calibration needs both the device's clock and the CPU clock we report.
+            let time_domains = unsafe {
+                ext::calibrated_timestamps::Instance::new(&self.instance.entry, &self.instance.raw)
+                    .get_physical_device_calibrateable_time_domains(self.raw)
+            }
+            .unwrap_or_default();
+            if time_domains.contains(&vk::TimeDomainEXT::DEVICE)
+                && time_domains.contains(&super::CALIBRATION_CPU_TIME_DOMAIN)
+            {
+                Some(ext::calibrated_timestamps::Device::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        let display_timing_fn = if enabled_extensions.contains(&google::display_timing::NAME) {
+            Some(google::display_timing::Device::new(
+                &self.instance.raw,
+                &raw_device,
+            ))
+        } else {
+            None
+        };
         let mesh_shading_fns = if enabled_extensions.contains(&ext::mesh_shader::NAME) {
             Some(ext::mesh_shader::Device::new(
                 &self.instance.raw,
@@ -1947,6 +2747,24 @@ impl super::Adapter {
         } else {
             None
         };
+        let fragment_shading_rate_fns =
+            if enabled_extensions.contains(&khr::fragment_shading_rate::NAME) {
+                Some(khr::fragment_shading_rate::Device::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+        let conditional_rendering_fns =
+            if enabled_extensions.contains(&ext::conditional_rendering::NAME) {
+                Some(ext::conditional_rendering::Device::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
 
         let naga_options = {
             use naga::back::spv;
@@ -2018,6 +2836,10 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::Float16);
             }
 
+            if features.contains(wgt::Features::SHADER_I16) {
+                capabilities.push(spv::Capability::Int16);
+            }
+
             if features.intersects(
                 wgt::Features::SHADER_INT64_ATOMIC_ALL_OPS
                     | wgt::Features::SHADER_INT64_ATOMIC_MIN_MAX
@@ -2038,6 +2860,14 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::ClipDistance);
             }
 
+            if features.contains(wgt::Features::CONSERVATIVE_RASTERIZATION_FULLY_COVERED) {
+                capabilities.push(spv::Capability::FragmentFullyCoveredEXT);
+            }
+
+            if features.contains(wgt::Features::MULTI_VIEWPORT) {
+                capabilities.push(spv::Capability::ShaderViewportIndexLayerEXT);
+            }
+
             let mut flags = spv::WriterFlags::empty();
             flags.set(
                 spv::WriterFlags::DEBUG,
@@ -2057,6 +2887,9 @@ impl super::Adapter {
             if features.contains(wgt::Features::EXPERIMENTAL_RAY_QUERY) {
                 capabilities.push(spv::Capability::RayQueryKHR);
             }
+            if features.contains(wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE) {
+                capabilities.push(spv::Capability::RayTracingKHR);
+            }
             if features.contains(wgt::Features::EXPERIMENTAL_RAY_HIT_VERTEX_RETURN) {
                 capabilities.push(spv::Capability::RayQueryPositionFetchKHR)
             }
@@ -2078,6 +2911,12 @@ impl super::Adapter {
                     // Use maximum supported SPIR-V version according to
                     // <https://github.com/KhronosGroup/Vulkan-Docs/blob/19b7651/appendices/spirvenv.adoc?plain=1#L21-L40>.
                     vk::API_VERSION_1_0..vk::API_VERSION_1_1 => (1, 0),
+                    // Ray tracing pipelines enable `VK_KHR_spirv_1_4`.
+                    vk::API_VERSION_1_1..vk::API_VERSION_1_2
+                        if features.contains(wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE) =>
+                    {
+                        (1, 4)
+                    }
                     vk::API_VERSION_1_1..vk::API_VERSION_1_2 => (1, 3),
                     vk::API_VERSION_1_2..vk::API_VERSION_1_3 => (1, 5),
                     vk::API_VERSION_1_3.. => (1, 6),
@@ -2135,11 +2974,19 @@ impl super::Adapter {
 
         let drop_guard = crate::DropGuard::from_option(drop_callback);
 
+        let mut queue_family_indices = Vec::from([family_index]);
+        for &(additional_family_index, _) in additional_queues {
+            if !queue_family_indices.contains(&additional_family_index) {
+                queue_family_indices.push(additional_family_index);
+            }
+        }
+
         let shared = Arc::new(super::DeviceShared {
             raw: raw_device,
             family_index,
             queue_index,
             raw_queue,
+            queue_family_indices,
             drop_guard,
             instance: Arc::clone(&self.instance),
             physical_device: self.raw,
@@ -2148,8 +2995,25 @@ impl super::Adapter {
                 debug_utils: debug_utils_fn,
                 draw_indirect_count: indirect_count_fn,
                 timeline_semaphore: timeline_semaphore_fn,
+                create_renderpass2: create_renderpass2_fn,
+                dynamic_rendering: dynamic_rendering_fn,
                 ray_tracing: ray_tracing_fns,
+                ray_tracing_pipeline: ray_tracing_pipeline_fn,
                 mesh_shading: mesh_shading_fns,
+                fragment_shading_rate: fragment_shading_rate_fns,
+                conditional_rendering: conditional_rendering_fns,
+                hdr_metadata: hdr_metadata_fn,
+                display_timing: display_timing_fn,
+                calibrated_timestamps: calibrated_timestamps_fn,
+                device_fault: device_fault_fn,
+                #[cfg(not(windows))]
+                external_semaphore_fd: external_semaphore_fd_fn,
+                #[cfg(windows)]
+                external_semaphore_win32: external_semaphore_win32_fn,
+                #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+                external_memory_fd: external_memory_fd_fn,
+                #[cfg(target_os = "android")]
+                external_memory_android_hardware_buffer: external_memory_android_hardware_buffer_fn,
             },
             pipeline_cache_validation_key,
             vendor_id: self.phd_capabilities.properties.vendor_id,
@@ -2164,17 +3028,41 @@ impl super::Adapter {
             memory_allocations_counter: Default::default(),
         });
 
-        let relay_semaphores = super::RelaySemaphores::new(&shared)?;
-
-        let queue = super::Queue {
-            raw: raw_queue,
-            swapchain_fn,
-            device: Arc::clone(&shared),
-            family_index,
-            relay_semaphores: Mutex::new(relay_semaphores),
-            signal_semaphores: Default::default(),
+        // Queues sharing a location share the `vk::Queue`, and the lock guarding it.
+        let mut raw_locks = Vec::<((u32, u32), Arc<Mutex<()>>)>::new();
+        let mut create_queue = |location: (u32, u32)| -> Result<super::Queue, crate::DeviceError> {
+            let raw = if location == (family_index, queue_index) {
+                raw_queue
+            } else {
+                profiling::scope!("vkGetDeviceQueue");
+                unsafe { shared.raw.get_device_queue(location.0, location.1) }
+            };
+            let raw_lock = match raw_locks.iter().find(|&&(l, _)| l == location) {
+                Some((_, raw_lock)) => Arc::clone(raw_lock),
+                None => {
+                    let raw_lock = Arc::new(Mutex::new(()));
+                    raw_locks.push((location, Arc::clone(&raw_lock)));
+                    raw_lock
+                }
+            };
+            Ok(super::Queue {
+                raw,
+                raw_lock,
+                swapchain_fn: swapchain_fn.clone(),
+                device: Arc::clone(&shared),
+                family_index: location.0,
+                relay_semaphores: Mutex::new(super::RelaySemaphores::new(&shared)?),
+                signal_semaphores: Default::default(),
+                timeline_waits: Default::default(),
+            })
         };
 
+        let queue = create_queue((family_index, queue_index))?;
+        let additional_queues = additional_queues
+            .iter()
+            .map(|&location| create_queue(location))
+            .collect::<Result<Vec<_>, _>>()?;
+
         let mem_allocator = {
             let limits = self.phd_capabilities.properties.limits;
 
@@ -2269,13 +3157,21 @@ impl super::Adapter {
             mem_allocator: Mutex::new(mem_allocator),
             desc_allocator: Mutex::new(desc_allocator),
             valid_ash_memory_types,
+            lazily_allocated_memory_types,
+            placed_memory_type,
             naga_options,
             #[cfg(feature = "renderdoc")]
             render_doc: Default::default(),
+            #[cfg(feature = "spirv-opt")]
+            spirv_optimizer: Default::default(),
             counters: Default::default(),
         };
 
-        Ok(crate::OpenDevice { device, queue })
+        Ok(crate::OpenDevice {
+            device,
+            queue,
+            additional_queues,
+        })
     }
 
     pub fn texture_format_as_raw(&self, texture_format: wgt::TextureFormat) -> vk::Format {
@@ -2291,15 +3187,110 @@ impl super::Adapter {
         features: wgt::Features,
         memory_hints: &wgt::MemoryHints,
         callback: Option<Box<super::CreateDeviceCallback<'a>>>,
+    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
+        unsafe { self.open_with_queues(features, memory_hints, &[], callback) }
+    }
+
+    /// Pick a `(family index, queue index)` location for each of `additional_queues`.
+    ///
+    /// Dedicated compute and transfer families are preferred, so that work on
+    /// those queues can run in parallel with the main queue. When no family has
+    /// a spare queue left, the last queue of the best family is shared.
+    ///
+    /// Also returns the number of queues to create in each family.
+    fn select_additional_queues(
+        &self,
+        main_family_index: u32,
+        additional_queues: &[wgt::QueueType],
+    ) -> (Vec<(u32, u32)>, Vec<u32>) {
+        let mut queue_counts = vec![0u32; self.queue_families.len()];
+        queue_counts[main_family_index as usize] = 1;
+
+        // Families where copies have a granularity of whole mip levels can't
+        // execute all of wgpu's copies.
+        let is_dedicated = |properties: &vk::QueueFamilyProperties, flags: vk::QueueFlags| {
+            let granularity = properties.min_image_transfer_granularity;
+            properties.queue_flags.contains(flags)
+                && !properties.queue_flags.contains(vk::QueueFlags::GRAPHICS)
+                && (granularity.width, granularity.height, granularity.depth) == (1, 1, 1)
+        };
+        let families_with = |flags: vk::QueueFlags, excluded: vk::QueueFlags| {
+            self.queue_families
+                .iter()
+                .enumerate()
+                .filter(move |&(_, properties)| {
+                    is_dedicated(properties, flags) && !properties.queue_flags.intersects(excluded)
+                })
+                .map(|(index, _)| index as u32)
+        };
+
+        let locations = additional_queues
+            .iter()
+            .map(|&queue_type| {
+                let mut candidates = Vec::new();
+                if queue_type == wgt::QueueType::Transfer {
+                    candidates.extend(families_with(
+                        vk::QueueFlags::TRANSFER,
+                        vk::QueueFlags::COMPUTE,
+                    ));
+                }
+                if queue_type != wgt::QueueType::Graphics {
+                    candidates.extend(families_with(
+                        vk::QueueFlags::COMPUTE,
+                        vk::QueueFlags::empty(),
+                    ));
+                }
+                candidates.push(main_family_index);
+
+                let spare = candidates.iter().copied().find(|&family_index| {
+                    queue_counts[family_index as usize]
+                        < self.queue_families[family_index as usize].queue_count
+                });
+                match spare {
+                    Some(family_index) => {
+                        let queue_index = queue_counts[family_index as usize];
+                        queue_counts[family_index as usize] += 1;
+                        (family_index, queue_index)
+                    }
+                    None => {
+                        let family_index = candidates[0];
+                        (family_index, queue_counts[family_index as usize] - 1)
+                    }
+                }
+            })
+            .collect();
+
+        (locations, queue_counts)
+    }
+
+    /// # Safety:
+    /// - Same as `open_with_callback` plus
+    /// - `additional_queues` must be empty unless `features` contains
+    ///   [`wgt::Features::EXPERIMENTAL_MULTI_QUEUE`].
+    unsafe fn open_with_queues<'a>(
+        &self,
+        features: wgt::Features,
+        memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
+        callback: Option<Box<super::CreateDeviceCallback<'a>>>,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let mut enabled_extensions = self.required_device_extensions(features);
         let mut enabled_phd_features = self.physical_device_features(&enabled_extensions, features);
 
         let family_index = 0; //TODO
-        let family_info = vk::DeviceQueueCreateInfo::default()
-            .queue_family_index(family_index)
-            .queue_priorities(&[1.0]);
-        let mut family_infos = Vec::from([family_info]);
+        let (additional_locations, queue_counts) =
+            self.select_additional_queues(family_index, additional_queues);
+        let queue_priorities = vec![1.0; queue_counts.iter().copied().max().unwrap_or(0) as usize];
+        let mut family_infos = queue_counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count != 0)
+            .map(|(index, &count)| {
+                vk::DeviceQueueCreateInfo::default()
+                    .queue_family_index(index as u32)
+                    .queue_priorities(&queue_priorities[..count as usize])
+            })
+            .collect::<Vec<_>>();
 
         let mut pre_info = vk::DeviceCreateInfo::default();
 
@@ -2346,14 +3337,14 @@ impl super::Adapter {
         }
 
         unsafe {
-            self.device_from_raw(
+            self.device_from_raw_with_queues(
                 raw_device,
                 None,
                 &enabled_extensions,
                 features,
                 memory_hints,
-                family_info.queue_family_index,
-                0,
+                (family_index, 0),
+                &additional_locations,
             )
         }
     }
@@ -2367,8 +3358,9 @@ impl crate::Adapter for super::Adapter {
         features: wgt::Features,
         _limits: &wgt::Limits,
         memory_hints: &wgt::MemoryHints,
+        additional_queues: &[wgt::QueueType],
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        unsafe { self.open_with_callback(features, memory_hints, None) }
+        unsafe { self.open_with_queues(features, memory_hints, additional_queues, None) }
     }
 
     unsafe fn texture_format_capabilities(
@@ -2582,10 +3574,22 @@ impl crate::Adapter for super::Adapter {
             }
         };
 
-        let formats = raw_surface_formats
-            .into_iter()
-            .filter_map(conv::map_vk_surface_formats)
-            .collect();
+        // A format may be listed for several color spaces.
+        let mut formats = Vec::new();
+        let mut color_spaces = vec![wgt::SurfaceColorSpace::Srgb];
+        for sf in raw_surface_formats {
+            let Some(format) = conv::map_vk_surface_formats(sf) else {
+                continue;
+            };
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+            if let Some(color_space) = conv::map_vk_color_space(sf.color_space) {
+                if !color_spaces.contains(&color_space) {
+                    color_spaces.push(color_space);
+                }
+            }
+        }
         Some(crate::SurfaceCapabilities {
             formats,
             // TODO: Right now we're always trunkating the swap chain
@@ -2600,6 +3604,7 @@ impl crate::Adapter for super::Adapter {
                 .flat_map(conv::map_vk_present_mode)
                 .collect(),
             composite_alpha_modes: conv::map_vk_composite_alpha(caps.supported_composite_alpha),
+            color_spaces,
         })
     }
 
@@ -2627,6 +3632,40 @@ impl crate::Adapter for super::Adapter {
             wgt::PresentationTimestamp::INVALID_TIMESTAMP
         }
     }
+
+    fn memory_info(&self) -> Option<wgt::MemoryInfo> {
+        if !self
+            .phd_capabilities
+            .supports_extension(ext::memory_budget::NAME)
+        {
+            return None;
+        }
+        let get_physical_device_properties =
+            self.instance.get_physical_device_properties.as_ref()?;
+
+        let mut memory_budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties =
+            vk::PhysicalDeviceMemoryProperties2::default().push_next(&mut memory_budget_properties);
+
+        unsafe {
+            get_physical_device_properties
+                .get_physical_device_memory_properties2(self.raw, &mut memory_properties);
+        }
+
+        let memory_properties = memory_properties.memory_properties;
+        let heaps = memory_properties
+            .memory_heaps_as_slice()
+            .iter()
+            .enumerate()
+            .map(|(i, heap)| wgt::MemoryHeapInfo {
+                usage: memory_budget_properties.heap_usage[i],
+                budget: memory_budget_properties.heap_budget[i],
+                device_local: heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL),
+            })
+            .collect();
+
+        Some(wgt::MemoryInfo { heaps })
+    }
 }
 
 fn is_format_16bit_norm_supported(instance: &ash::Instance, phd: vk::PhysicalDevice) -> bool {
@@ -2773,6 +3812,32 @@ fn supports_bgra8unorm_storage(
     }
 }
 
+fn supports_shared_timeline_semaphore(
+    instance: &ash::Instance,
+    phd: vk::PhysicalDevice,
+    device_api_version: u32,
+) -> bool {
+    // This check gates the function call and structures used below.
+    if device_api_version < vk::API_VERSION_1_2 {
+        return false;
+    }
+
+    let mut type_info =
+        vk::SemaphoreTypeCreateInfo::default().semaphore_type(vk::SemaphoreType::TIMELINE);
+    let info = vk::PhysicalDeviceExternalSemaphoreInfo::default()
+        .handle_type(super::SHARED_FENCE_HANDLE_TYPE)
+        .push_next(&mut type_info);
+    let mut properties = vk::ExternalSemaphoreProperties::default();
+    unsafe {
+        instance.get_physical_device_external_semaphore_properties(phd, &info, &mut properties)
+    };
+
+    properties.external_semaphore_features.contains(
+        vk::ExternalSemaphoreFeatureFlags::EXPORTABLE
+            | vk::ExternalSemaphoreFeatureFlags::IMPORTABLE,
+    )
+}
+
 // For https://github.com/gfx-rs/wgpu/issues/4599
 // Intel iGPUs with outdated drivers can break rendering if `VK_EXT_robustness2` is used.
 // Driver version 31.0.101.2115 works, but there's probably an earlier functional version.
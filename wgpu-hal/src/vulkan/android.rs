@@ -0,0 +1,233 @@
+//! Importing Android `AHardwareBuffer`s, through
+//! `VK_ANDROID_external_memory_android_hardware_buffer`.
+
+#![cfg(target_os = "android")]
+
+use core::ffi::c_void;
+
+use ash::vk;
+use parking_lot::Mutex;
+
+const HANDLE_TYPE: vk::ExternalMemoryHandleTypeFlags =
+    vk::ExternalMemoryHandleTypeFlags::ANDROID_HARDWARE_BUFFER_ANDROID;
+
+impl super::Device {
+    fn external_memory_android_hardware_buffer(
+        &self,
+    ) -> Result<&ash::android::external_memory_android_hardware_buffer::Device, crate::DeviceError>
+    {
+        if !self
+            .shared
+            .features
+            .contains(wgt::Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER)
+        {
+            log::error!(
+                "Vulkan driver does not support VK_ANDROID_external_memory_android_hardware_buffer"
+            );
+            return Err(crate::DeviceError::Unexpected);
+        }
+        Ok(self
+            .shared
+            .extension_fns
+            .external_memory_android_hardware_buffer
+            .as_ref()
+            .unwrap())
+    }
+
+    /// Imports the memory of `buffer` for a resource that may use
+    /// `memory_type_bits`.
+    fn import_android_hardware_buffer_memory(
+        &self,
+        buffer: *mut c_void,
+        allocation_size: vk::DeviceSize,
+        memory_type_bits: u32,
+        mut dedicated_info: Option<vk::MemoryDedicatedAllocateInfo>,
+        mut flags_info: Option<vk::MemoryAllocateFlagsInfo>,
+    ) -> Result<vk::DeviceMemory, crate::DeviceError> {
+        let mem_type_index = self
+            .find_memory_type_index(memory_type_bits, vk::MemoryPropertyFlags::empty())
+            .ok_or(crate::DeviceError::Unexpected)?;
+
+        let mut import_info = vk::ImportAndroidHardwareBufferInfoANDROID::default().buffer(buffer);
+        let mut allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(allocation_size)
+            .memory_type_index(mem_type_index as _)
+            .push_next(&mut import_info);
+        if let Some(ref mut dedicated_info) = dedicated_info {
+            allocate_info = allocate_info.push_next(dedicated_info);
+        }
+        if let Some(ref mut flags_info) = flags_info {
+            allocate_info = allocate_info.push_next(flags_info);
+        }
+
+        unsafe { self.shared.raw.allocate_memory(&allocate_info, None) }
+            .map_err(super::map_host_device_oom_err)
+    }
+
+    /// Imports a texture from an `AHardwareBuffer`.
+    ///
+    /// The texture holds a reference to `buffer`, so the caller may release its own
+    /// reference once this returns.
+    ///
+    /// Requires [`wgt::Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER`]. Hardware
+    /// buffers with an external format, which can only be sampled through a YCbCr
+    /// conversion, can't be imported.
+    ///
+    /// # Safety
+    ///
+    /// - `buffer` must be a valid `AHardwareBuffer`, with the usage
+    ///   `AHARDWAREBUFFER_USAGE_GPU_SAMPLED_IMAGE` or `AHARDWAREBUFFER_USAGE_GPU_COLOR_OUTPUT`.
+    /// - `desc` must describe a 2D texture with the format and size of `buffer`, a
+    ///   single mip level and sample, and as many array layers as `buffer` has.
+    /// - `desc.usage` must be allowed by the usage of `buffer`.
+    /// - The producer of `buffer` must be done writing to it before it is used.
+    pub unsafe fn texture_from_android_hardware_buffer(
+        &self,
+        buffer: *mut c_void,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let functions = self.external_memory_android_hardware_buffer()?;
+
+        let mut format_properties = vk::AndroidHardwareBufferFormatPropertiesANDROID::default();
+        let mut properties =
+            vk::AndroidHardwareBufferPropertiesANDROID::default().push_next(&mut format_properties);
+        unsafe { functions.get_android_hardware_buffer_properties(buffer, &mut properties) }
+            .map_err(super::map_host_oom_err)?;
+        let (allocation_size, memory_type_bits) =
+            (properties.allocation_size, properties.memory_type_bits);
+
+        if format_properties.format == vk::Format::UNDEFINED {
+            log::error!(
+                "Hardware buffer has external format {:#x}, which can only be sampled through a YCbCr conversion",
+                format_properties.external_format
+            );
+            return Err(crate::DeviceError::Unexpected);
+        }
+        let vk_format = self.shared.private_caps.map_texture_format(desc.format);
+        if vk_format != format_properties.format {
+            crate::hal_usage_error(format_args!(
+                "hardware buffer has format {:?}, but the texture descriptor has {:?}",
+                format_properties.format, desc.format
+            ))
+        }
+
+        let mut external_memory_image_info =
+            vk::ExternalMemoryImageCreateInfo::default().handle_types(HANDLE_TYPE);
+        let image =
+            self.create_image_without_memory(desc, Some(&mut external_memory_image_info), None)?;
+
+        let memory = self
+            .import_android_hardware_buffer_memory(
+                buffer,
+                allocation_size,
+                memory_type_bits,
+                Some(vk::MemoryDedicatedAllocateInfo::default().image(image.raw)),
+                None,
+            )
+            .inspect_err(|_| unsafe { self.shared.raw.destroy_image(image.raw, None) })?;
+
+        unsafe { self.shared.raw.bind_image_memory(image.raw, memory, 0) }
+            .map_err(super::map_host_device_oom_err)
+            .inspect_err(|_| unsafe {
+                self.shared.raw.destroy_image(image.raw, None);
+                self.shared.raw.free_memory(memory, None);
+            })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: Some(memory),
+            block: None,
+            placed: false,
+            format: desc.format,
+            copy_size: image.copy_size,
+        })
+    }
+
+    /// Imports a buffer from an `AHardwareBuffer` with the format
+    /// `AHARDWAREBUFFER_FORMAT_BLOB`.
+    ///
+    /// The buffer holds a reference to `buffer`, so the caller may release its own
+    /// reference once this returns.
+    ///
+    /// Requires [`wgt::Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER`].
+    ///
+    /// # Safety
+    ///
+    /// - `buffer` must be a valid `AHardwareBuffer` with the format
+    ///   `AHARDWAREBUFFER_FORMAT_BLOB` and the usage `AHARDWAREBUFFER_USAGE_GPU_DATA_BUFFER`,
+    ///   that is at least `desc.size` bytes large.
+    /// - Imported buffers can't be mapped, so `desc.usage` must not contain
+    ///   [`wgt::BufferUses::MAP_READ`] or [`wgt::BufferUses::MAP_WRITE`].
+    /// - The producer of `buffer` must be done writing to it before it is used.
+    pub unsafe fn buffer_from_android_hardware_buffer(
+        &self,
+        buffer: *mut c_void,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let functions = self.external_memory_android_hardware_buffer()?;
+
+        let mut properties = vk::AndroidHardwareBufferPropertiesANDROID::default();
+        unsafe { functions.get_android_hardware_buffer_properties(buffer, &mut properties) }
+            .map_err(super::map_host_oom_err)?;
+
+        let (sharing_mode, queue_family_indices) = self.shared.sharing_mode();
+        let usage = self.shared.buffer_usage(desc.usage);
+        let mut external_memory_buffer_info =
+            vk::ExternalMemoryBufferCreateInfo::default().handle_types(HANDLE_TYPE);
+        let vk_info = vk::BufferCreateInfo::default()
+            .size(desc.size)
+            .usage(usage)
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(queue_family_indices)
+            .push_next(&mut external_memory_buffer_info);
+
+        let raw = unsafe { self.shared.raw.create_buffer(&vk_info, None) }
+            .map_err(super::map_host_device_oom_and_ioca_err)?;
+        let requirements = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+
+        let flags_info = usage
+            .contains(vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS)
+            .then(|| {
+                vk::MemoryAllocateFlagsInfo::default()
+                    .flags(vk::MemoryAllocateFlags::DEVICE_ADDRESS)
+            });
+        let memory = self
+            .import_android_hardware_buffer_memory(
+                buffer,
+                properties.allocation_size,
+                requirements.memory_type_bits & properties.memory_type_bits,
+                None,
+                flags_info,
+            )
+            .inspect_err(|_| unsafe { self.shared.raw.destroy_buffer(raw, None) })?;
+
+        unsafe { self.shared.raw.bind_buffer_memory(raw, memory, 0) }
+            .map_err(super::map_host_device_oom_and_ioca_err)
+            .inspect_err(|_| unsafe {
+                self.shared.raw.destroy_buffer(raw, None);
+                self.shared.raw.free_memory(memory, None);
+            })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(raw, label) };
+        }
+
+        self.counters
+            .buffer_memory
+            .add(properties.allocation_size as isize);
+
+        Ok(super::Buffer {
+            raw,
+            block: Some(Mutex::new(super::BufferMemoryBacking::VulkanMemory {
+                memory,
+                offset: 0,
+                size: properties.allocation_size,
+            })),
+        })
+    }
+}
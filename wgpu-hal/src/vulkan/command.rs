@@ -1,4 +1,6 @@
 use super::conv;
+use crate::CommandEncoder as _;
+use alloc::{sync::Arc, vec::Vec};
 use arrayvec::ArrayVec;
 use ash::vk;
 use core::{mem, ops::Range};
@@ -38,7 +40,182 @@ impl super::Texture {
     }
 }
 
+/// Ray tracing pipelines run inside compute passes, so any barrier that waits on
+/// compute shaders must also wait on ray tracing shaders.
+fn with_ray_tracing_stages(
+    stages: vk::PipelineStageFlags,
+    ray_tracing: bool,
+) -> vk::PipelineStageFlags {
+    if ray_tracing && stages.contains(vk::PipelineStageFlags::COMPUTE_SHADER) {
+        stages | vk::PipelineStageFlags::RAY_TRACING_SHADER_KHR
+    } else {
+        stages
+    }
+}
+
+/// Conditional rendering reads its predicate from buffers in the `INDIRECT` state,
+/// so any barrier that waits on indirect reads must also wait on it.
+fn with_conditional_rendering(
+    (stages, access): (vk::PipelineStageFlags, vk::AccessFlags),
+    conditional_rendering: bool,
+) -> (vk::PipelineStageFlags, vk::AccessFlags) {
+    if conditional_rendering && access.contains(vk::AccessFlags::INDIRECT_COMMAND_READ) {
+        (
+            stages | vk::PipelineStageFlags::CONDITIONAL_RENDERING_EXT,
+            access | vk::AccessFlags::CONDITIONAL_RENDERING_READ_EXT,
+        )
+    } else {
+        (stages, access)
+    }
+}
+
 impl super::CommandEncoder {
+    /// # Safety
+    ///
+    /// - `family_index` must be the index of a queue family of `device`.
+    pub(super) unsafe fn new(
+        device: &Arc<super::DeviceShared>,
+        family_index: u32,
+        counters: &Arc<wgt::HalCounters>,
+    ) -> Result<Self, crate::DeviceError> {
+        let vk_info = vk::CommandPoolCreateInfo::default()
+            .queue_family_index(family_index)
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT);
+
+        let raw = unsafe {
+            device
+                .raw
+                .create_command_pool(&vk_info, None)
+                .map_err(super::map_host_device_oom_err)?
+        };
+
+        counters.command_encoders.add(1);
+
+        Ok(Self {
+            raw,
+            device: Arc::clone(device),
+            active: vk::CommandBuffer::null(),
+            bind_point: vk::PipelineBindPoint::default(),
+            temp: super::Temp::default(),
+            free: Vec::new(),
+            discarded: Vec::new(),
+            rpass_debug_marker_active: false,
+            end_of_pass_timer_query: None,
+            pass_pipeline_statistics_query: None,
+            framebuffers: Default::default(),
+            temp_texture_views: Default::default(),
+            family_index,
+            parallel_pass: None,
+            parallel_encoders: Vec::new(),
+            free_parallel_encoders: Vec::new(),
+            counters: Arc::clone(counters),
+        })
+    }
+
+    /// Returns how the command buffers of this encoder are used.
+    fn command_buffer_usage(&self) -> vk::CommandBufferUsageFlags {
+        // Command buffers of reusable wgpu command buffers may be submitted
+        // again, even while a previous submission is still pending.
+        if self
+            .device
+            .features
+            .contains(wgt::Features::REUSABLE_COMMAND_BUFFERS)
+        {
+            vk::CommandBufferUsageFlags::SIMULTANEOUS_USE
+        } else {
+            vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT
+        }
+    }
+
+    /// Sets the viewports and scissor rectangles to cover `extent`.
+    fn set_pass_viewports(&mut self, extent: wgt::Extent3d) {
+        let vk_viewports = [vk::Viewport {
+            x: 0.0,
+            y: extent.height as f32,
+            width: extent.width as f32,
+            height: -(extent.height as f32),
+            min_depth: 0.0,
+            max_depth: 1.0,
+        }; wgt::MAX_VIEWPORTS as usize];
+        let vk_scissors = [vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent: vk::Extent2D {
+                width: extent.width,
+                height: extent.height,
+            },
+        }; wgt::MAX_VIEWPORTS as usize];
+        let viewport_count = self.device.viewport_count() as usize;
+
+        unsafe {
+            self.device
+                .raw
+                .cmd_set_viewport(self.active, 0, &vk_viewports[..viewport_count]);
+            self.device
+                .raw
+                .cmd_set_scissor(self.active, 0, &vk_scissors[..viewport_count]);
+        }
+    }
+
+    /// Begins recording a secondary command buffer inside `pass`.
+    unsafe fn begin_parallel_encoding(
+        &mut self,
+        pass: &super::ParallelRenderPass,
+        usage: vk::CommandBufferUsageFlags,
+    ) -> Result<(), crate::DeviceError> {
+        if self.free.is_empty() {
+            let vk_info = vk::CommandBufferAllocateInfo::default()
+                .command_pool(self.raw)
+                .level(vk::CommandBufferLevel::SECONDARY)
+                .command_buffer_count(1);
+            let cmd_buf_vec = unsafe {
+                self.device
+                    .raw
+                    .allocate_command_buffers(&vk_info)
+                    .map_err(super::map_host_device_oom_err)?
+            };
+            self.free.extend(cmd_buf_vec);
+        }
+        let raw = self.free.pop().unwrap();
+
+        let mut vk_rendering_info = vk::CommandBufferInheritanceRenderingInfo::default()
+            .color_attachment_formats(&pass.color_formats)
+            .depth_attachment_format(pass.depth_format)
+            .stencil_attachment_format(pass.stencil_format)
+            .rasterization_samples(pass.samples)
+            .view_mask(pass.view_mask);
+        let mut vk_inheritance = vk::CommandBufferInheritanceInfo::default()
+            .render_pass(pass.raw_pass)
+            .subpass(0)
+            .framebuffer(pass.framebuffer);
+        if pass.raw_pass == vk::RenderPass::null() {
+            vk_inheritance = vk_inheritance.push_next(&mut vk_rendering_info);
+        }
+        let vk_info = vk::CommandBufferBeginInfo::default()
+            .flags(usage | vk::CommandBufferUsageFlags::RENDER_PASS_CONTINUE)
+            .inheritance_info(&vk_inheritance);
+        unsafe { self.device.raw.begin_command_buffer(raw, &vk_info) }
+            .map_err(super::map_host_device_oom_err)?;
+        self.active = raw;
+        self.bind_point = vk::PipelineBindPoint::GRAPHICS;
+
+        // Secondary command buffers don't inherit any state.
+        self.set_pass_viewports(pass.extent);
+
+        Ok(())
+    }
+
+    fn ray_tracing_pipelines_enabled(&self) -> bool {
+        self.device
+            .features
+            .contains(wgt::Features::EXPERIMENTAL_RAY_TRACING_PIPELINE)
+    }
+
+    fn conditional_rendering_enabled(&self) -> bool {
+        self.device
+            .features
+            .contains(wgt::Features::CONDITIONAL_RENDERING)
+    }
+
     fn write_pass_end_timestamp_if_requested(&mut self) {
         if let Some((query_set, index)) = self.end_of_pass_timer_query.take() {
             unsafe {
@@ -52,6 +229,242 @@ impl super::CommandEncoder {
         }
     }
 
+    fn begin_pass_pipeline_statistics_query(
+        &mut self,
+        query: Option<&crate::PassPipelineStatisticsQuery<'_, super::QuerySet>>,
+    ) {
+        if let Some(query) = query {
+            unsafe {
+                self.device.raw.cmd_begin_query(
+                    self.active,
+                    query.query_set.raw,
+                    query.query_index,
+                    vk::QueryControlFlags::empty(),
+                )
+            };
+            self.pass_pipeline_statistics_query = Some((query.query_set.raw, query.query_index));
+        }
+    }
+
+    fn end_pass_pipeline_statistics_query_if_requested(&mut self) {
+        if let Some((query_set, index)) = self.pass_pipeline_statistics_query.take() {
+            unsafe { self.device.raw.cmd_end_query(self.active, query_set, index) };
+        }
+    }
+
+    /// Begins the debug marker and timestamp of a render pass, sets the viewport
+    /// and scissor to cover its extent, and returns its render area.
+    unsafe fn begin_render_pass_state(
+        &mut self,
+        desc: &crate::RenderPassDescriptor<super::QuerySet, super::TextureView>,
+    ) -> vk::Rect2D {
+        let render_area = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent: vk::Extent2D {
+                width: desc.extent.width,
+                height: desc.extent.height,
+            },
+        };
+
+        if let Some(label) = desc.label {
+            unsafe { self.begin_debug_marker(label) };
+            self.rpass_debug_marker_active = true;
+        }
+
+        // Start timestamp if any (before all other commands but after debug marker)
+        if let Some(timestamp_writes) = desc.timestamp_writes.as_ref() {
+            if let Some(index) = timestamp_writes.beginning_of_pass_write_index {
+                unsafe {
+                    self.write_timestamp(timestamp_writes.query_set, index);
+                }
+            }
+            self.end_of_pass_timer_query = timestamp_writes
+                .end_of_pass_write_index
+                .map(|index| (timestamp_writes.query_set.raw, index));
+        }
+
+        self.set_pass_viewports(desc.extent);
+
+        render_area
+    }
+
+    /// Begins a render pass with dynamic rendering, which suspends and resumes
+    /// it as `desc.flags` say.
+    unsafe fn begin_rendering(
+        &mut self,
+        desc: &crate::RenderPassDescriptor<super::QuerySet, super::TextureView>,
+    ) -> Result<(), crate::DeviceError> {
+        let mut vk_color_attachments =
+            ArrayVec::<vk::RenderingAttachmentInfo, { crate::MAX_COLOR_ATTACHMENTS }>::new();
+        for cat in desc.color_attachments {
+            let Some(cat) = cat.as_ref() else {
+                // Without an image view, the attachment is unused.
+                vk_color_attachments.push(vk::RenderingAttachmentInfo::default());
+                continue;
+            };
+            let color_view = if cat.target.view.dimension == wgt::TextureViewDimension::D3 {
+                let key = super::TempTextureViewKey {
+                    texture: cat.target.view.raw_texture,
+                    format: cat.target.view.raw_format,
+                    mip_level: cat.target.view.base_mip_level,
+                    depth_slice: cat.depth_slice.unwrap(),
+                };
+                self.make_temp_texture_view(key)?
+            } else {
+                cat.target.view.raw
+            };
+
+            let (load_op, store_op) = conv::map_attachment_ops(cat.ops);
+            let mut vk_attachment = vk::RenderingAttachmentInfo::default()
+                .image_view(color_view)
+                .image_layout(conv::derive_image_layout(
+                    cat.target.usage,
+                    cat.target.view.format,
+                ))
+                .load_op(load_op)
+                .store_op(store_op)
+                .clear_value(vk::ClearValue {
+                    color: unsafe { cat.make_vk_clear_color() },
+                });
+            if let Some(ref resolve) = cat.resolve_target {
+                vk_attachment = vk_attachment
+                    .resolve_mode(vk::ResolveModeFlags::AVERAGE)
+                    .resolve_image_view(resolve.view.raw)
+                    .resolve_image_layout(conv::derive_image_layout(
+                        resolve.usage,
+                        resolve.view.format,
+                    ));
+            }
+            vk_color_attachments.push(vk_attachment);
+        }
+
+        let mut vk_depth_attachment = None;
+        let mut vk_stencil_attachment = None;
+        if let Some(ref ds) = desc.depth_stencil_attachment {
+            let clear_value = vk::ClearValue {
+                depth_stencil: vk::ClearDepthStencilValue {
+                    depth: ds.clear_value.0,
+                    stencil: ds.clear_value.1,
+                },
+            };
+            // Aspects that aren't resolved have no resolve mode, so they keep
+            // their contents in the resolve target.
+            let make_attachment = |ops, resolve_mode| {
+                let (load_op, store_op) = conv::map_attachment_ops(ops);
+                let mut vk_attachment = vk::RenderingAttachmentInfo::default()
+                    .image_view(ds.target.view.raw)
+                    .image_layout(conv::derive_image_layout(
+                        ds.target.usage,
+                        ds.target.view.format,
+                    ))
+                    .load_op(load_op)
+                    .store_op(store_op)
+                    .clear_value(clear_value);
+                if let Some(ref resolve) = ds.resolve {
+                    if resolve_mode != vk::ResolveModeFlags::NONE {
+                        vk_attachment = vk_attachment
+                            .resolve_mode(resolve_mode)
+                            .resolve_image_view(resolve.target.view.raw)
+                            .resolve_image_layout(conv::derive_image_layout(
+                                resolve.target.usage,
+                                resolve.target.view.format,
+                            ));
+                    }
+                }
+                vk_attachment
+            };
+            let aspects = crate::FormatAspects::from(ds.target.view.format);
+            if aspects.contains(crate::FormatAspects::DEPTH) {
+                let mode = ds.resolve.as_ref().and_then(|resolve| resolve.depth_mode);
+                vk_depth_attachment =
+                    Some(make_attachment(ds.depth_ops, conv::map_resolve_mode(mode)));
+            }
+            if aspects.contains(crate::FormatAspects::STENCIL) {
+                let mode = ds
+                    .resolve
+                    .as_ref()
+                    .filter(|resolve| resolve.resolve_stencil)
+                    .map(|_| wgt::ResolveMode::SampleZero);
+                vk_stencil_attachment = Some(make_attachment(
+                    ds.stencil_ops,
+                    conv::map_resolve_mode(mode),
+                ));
+            }
+        }
+
+        let mut flags = vk::RenderingFlags::empty();
+        if desc.flags.contains(wgt::RenderPassFlags::SUSPENDING) {
+            flags |= vk::RenderingFlags::SUSPENDING;
+        }
+        if desc.flags.contains(wgt::RenderPassFlags::RESUMING) {
+            flags |= vk::RenderingFlags::RESUMING;
+        }
+        if desc.parallel {
+            flags |= vk::RenderingFlags::CONTENTS_SECONDARY_COMMAND_BUFFERS;
+        }
+
+        let render_area = unsafe { self.begin_render_pass_state(desc) };
+
+        // All views are rendered to, like `make_render_pass` does.
+        let view_mask = desc
+            .multiview
+            .map_or(0, |multiview| (1 << multiview.get()) - 1);
+        let mut vk_info = vk::RenderingInfo::default()
+            .flags(flags)
+            .render_area(render_area)
+            .layer_count(desc.extent.depth_or_array_layers)
+            .view_mask(view_mask)
+            .color_attachments(&vk_color_attachments);
+        if let Some(ref vk_attachment) = vk_depth_attachment {
+            vk_info = vk_info.depth_attachment(vk_attachment);
+        }
+        if let Some(ref vk_attachment) = vk_stencil_attachment {
+            vk_info = vk_info.stencil_attachment(vk_attachment);
+        }
+
+        match self.device.extension_fns.dynamic_rendering {
+            Some(super::ExtensionFn::Extension(ref ext)) => unsafe {
+                ext.cmd_begin_rendering(self.active, &vk_info)
+            },
+            Some(super::ExtensionFn::Promoted) => unsafe {
+                self.device.raw.cmd_begin_rendering(self.active, &vk_info)
+            },
+            None => unreachable!(),
+        }
+
+        if desc.parallel {
+            let format = |aspect| {
+                desc.depth_stencil_attachment
+                    .as_ref()
+                    .filter(|ds| crate::FormatAspects::from(ds.target.view.format).contains(aspect))
+                    .map_or(vk::Format::UNDEFINED, |ds| ds.target.view.raw_format)
+            };
+            self.parallel_pass = Some(super::ParallelRenderPass {
+                raw_pass: vk::RenderPass::null(),
+                framebuffer: vk::Framebuffer::null(),
+                color_formats: desc
+                    .color_attachments
+                    .iter()
+                    .map(|cat| {
+                        cat.as_ref()
+                            .map_or(vk::Format::UNDEFINED, |cat| cat.target.view.raw_format)
+                    })
+                    .collect(),
+                depth_format: format(crate::FormatAspects::DEPTH),
+                stencil_format: format(crate::FormatAspects::STENCIL),
+                samples: vk::SampleCountFlags::from_raw(desc.sample_count),
+                view_mask,
+                extent: desc.extent,
+            });
+        }
+
+        self.begin_pass_pipeline_statistics_query(desc.pipeline_statistics_query.as_ref());
+
+        self.bind_point = vk::PipelineBindPoint::GRAPHICS;
+
+        Ok(())
+    }
+
     fn make_framebuffer(
         &mut self,
         key: super::FramebufferKey,
@@ -109,6 +522,46 @@ impl super::CommandEncoder {
             }
         })
     }
+    /// Writes `query_type` of `acceleration_structure` to the start of `buffer`, going
+    /// through `query_pool`.
+    unsafe fn write_acceleration_structure_property(
+        &mut self,
+        acceleration_structure: &super::AccelerationStructure,
+        buffer: &super::Buffer,
+        query_pool: vk::QueryPool,
+        query_type: vk::QueryType,
+    ) {
+        let ray_tracing_functions = self
+            .device
+            .extension_fns
+            .ray_tracing
+            .as_ref()
+            .expect("Feature `RAY_TRACING` not enabled");
+        unsafe {
+            self.device
+                .raw
+                .cmd_reset_query_pool(self.active, query_pool, 0, 1);
+            ray_tracing_functions
+                .acceleration_structure
+                .cmd_write_acceleration_structures_properties(
+                    self.active,
+                    &[acceleration_structure.raw],
+                    query_type,
+                    query_pool,
+                    0,
+                );
+            self.device.raw.cmd_copy_query_pool_results(
+                self.active,
+                query_pool,
+                0,
+                1,
+                buffer.raw,
+                0,
+                wgt::QUERY_SIZE as vk::DeviceSize,
+                vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WAIT,
+            )
+        };
+    }
 }
 
 impl crate::CommandEncoder for super::CommandEncoder {
@@ -136,8 +589,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         // Reset this in case the last renderpass was never ended.
         self.rpass_debug_marker_active = false;
 
-        let vk_info = vk::CommandBufferBeginInfo::default()
-            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        let vk_info = vk::CommandBufferBeginInfo::default().flags(self.command_buffer_usage());
         unsafe { self.device.raw.begin_command_buffer(raw, &vk_info) }
             .map_err(super::map_host_device_oom_err)?;
         self.active = raw;
@@ -180,6 +632,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 .raw
                 .reset_command_pool(self.raw, vk::CommandPoolResetFlags::default())
         };
+        for (mut encoder, raw) in self.parallel_encoders.drain(..) {
+            unsafe { encoder.reset_all(core::iter::once(super::CommandBuffer { raw })) };
+            self.free_parallel_encoders.push(encoder);
+        }
     }
 
     unsafe fn transition_buffers<'a, T>(&mut self, barriers: T)
@@ -189,14 +645,22 @@ impl crate::CommandEncoder for super::CommandEncoder {
         //Note: this is done so that we never end up with empty stage flags
         let mut src_stages = vk::PipelineStageFlags::TOP_OF_PIPE;
         let mut dst_stages = vk::PipelineStageFlags::BOTTOM_OF_PIPE;
+        let ray_tracing = self.ray_tracing_pipelines_enabled();
+        let conditional_rendering = self.conditional_rendering_enabled();
         let vk_barriers = &mut self.temp.buffer_barriers;
         vk_barriers.clear();
 
         for bar in barriers {
-            let (src_stage, src_access) = conv::map_buffer_usage_to_barrier(bar.usage.from);
-            src_stages |= src_stage;
-            let (dst_stage, dst_access) = conv::map_buffer_usage_to_barrier(bar.usage.to);
-            dst_stages |= dst_stage;
+            let (src_stage, src_access) = with_conditional_rendering(
+                conv::map_buffer_usage_to_barrier(bar.usage.from),
+                conditional_rendering,
+            );
+            src_stages |= with_ray_tracing_stages(src_stage, ray_tracing);
+            let (dst_stage, dst_access) = with_conditional_rendering(
+                conv::map_buffer_usage_to_barrier(bar.usage.to),
+                conditional_rendering,
+            );
+            dst_stages |= with_ray_tracing_stages(dst_stage, ray_tracing);
 
             vk_barriers.push(
                 vk::BufferMemoryBarrier::default()
@@ -228,19 +692,37 @@ impl crate::CommandEncoder for super::CommandEncoder {
     {
         let mut src_stages = vk::PipelineStageFlags::empty();
         let mut dst_stages = vk::PipelineStageFlags::empty();
+        let ray_tracing = self.ray_tracing_pipelines_enabled();
         let vk_barriers = &mut self.temp.image_barriers;
         vk_barriers.clear();
 
         for bar in barriers {
+            // Images can't be transitioned to `UNDEFINED`. Their contents are
+            // discarded anyway, so there is nothing to wait for.
+            if bar.usage.to == wgt::TextureUses::UNINITIALIZED {
+                continue;
+            }
+
             let range = conv::map_subresource_range_combined_aspect(
                 &bar.range,
                 bar.texture.format,
                 &self.device.private_caps,
             );
-            let (src_stage, src_access) = conv::map_texture_usage_to_barrier(bar.usage.from);
+            let (src_stage, src_access) =
+                if bar.texture.placed && bar.usage.from == wgt::TextureUses::UNINITIALIZED {
+                    // The memory may have been written through an aliasing texture.
+                    (
+                        vk::PipelineStageFlags::ALL_COMMANDS,
+                        vk::AccessFlags::MEMORY_WRITE,
+                    )
+                } else {
+                    conv::map_texture_usage_to_barrier(bar.usage.from)
+                };
+            let src_stage = with_ray_tracing_stages(src_stage, ray_tracing);
             let src_layout = conv::derive_image_layout(bar.usage.from, bar.texture.format);
             src_stages |= src_stage;
             let (dst_stage, dst_access) = conv::map_texture_usage_to_barrier(bar.usage.to);
+            let dst_stage = with_ray_tracing_stages(dst_stage, ray_tracing);
             let dst_layout = conv::derive_image_layout(bar.usage.to, bar.texture.format);
             dst_stages |= dst_stage;
 
@@ -374,6 +856,34 @@ impl crate::CommandEncoder for super::CommandEncoder {
         };
     }
 
+    unsafe fn resolve_texture<T>(&mut self, src: &super::Texture, dst: &super::Texture, regions: T)
+    where
+        T: Iterator<Item = crate::TextureCopy>,
+    {
+        let vk_regions_iter = regions.map(|r| {
+            let (src_subresource, src_offset) = conv::map_subresource_layers(&r.src_base);
+            let (dst_subresource, dst_offset) = conv::map_subresource_layers(&r.dst_base);
+            vk::ImageResolve {
+                src_subresource,
+                src_offset,
+                dst_subresource,
+                dst_offset,
+                extent: conv::map_copy_extent(&r.size),
+            }
+        });
+
+        unsafe {
+            self.device.raw.cmd_resolve_image(
+                self.active,
+                src.raw,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                dst.raw,
+                DST_IMAGE_LAYOUT,
+                &smallvec::SmallVec::<vk::ImageResolve, 32>::from_iter(vk_regions_iter),
+            )
+        };
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -446,38 +956,28 @@ impl crate::CommandEncoder for super::CommandEncoder {
         acceleration_structure: &super::AccelerationStructure,
         buffer: &super::Buffer,
     ) {
-        let ray_tracing_functions = self
-            .device
-            .extension_fns
-            .ray_tracing
-            .as_ref()
-            .expect("Feature `RAY_TRACING` not enabled");
-        let query_pool = acceleration_structure
-            .compacted_size_query
-            .as_ref()
-            .unwrap();
+        let query_pool = acceleration_structure.compacted_size_query.unwrap();
         unsafe {
-            self.device
-                .raw
-                .cmd_reset_query_pool(self.active, *query_pool, 0, 1);
-            ray_tracing_functions
-                .acceleration_structure
-                .cmd_write_acceleration_structures_properties(
-                    self.active,
-                    &[acceleration_structure.raw],
-                    vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR,
-                    *query_pool,
-                    0,
-                );
-            self.device.raw.cmd_copy_query_pool_results(
-                self.active,
-                *query_pool,
-                0,
-                1,
-                buffer.raw,
-                0,
-                wgt::QUERY_SIZE as vk::DeviceSize,
-                vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WAIT,
+            self.write_acceleration_structure_property(
+                acceleration_structure,
+                buffer,
+                query_pool,
+                vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR,
+            )
+        };
+    }
+    unsafe fn read_acceleration_structure_serialized_size(
+        &mut self,
+        acceleration_structure: &super::AccelerationStructure,
+        buffer: &super::Buffer,
+    ) {
+        let query_pool = acceleration_structure.serialized_size_query.unwrap();
+        unsafe {
+            self.write_acceleration_structure_property(
+                acceleration_structure,
+                buffer,
+                query_pool,
+                vk::QueryType::ACCELERATION_STRUCTURE_SERIALIZATION_SIZE_KHR,
             )
         };
     }
@@ -775,6 +1275,12 @@ impl crate::CommandEncoder for super::CommandEncoder {
         &mut self,
         desc: &crate::RenderPassDescriptor<super::QuerySet, super::TextureView>,
     ) -> Result<(), crate::DeviceError> {
+        if self.device.extension_fns.dynamic_rendering.is_some() {
+            return unsafe { self.begin_rendering(desc) };
+        }
+
+        // Without dynamic rendering, a suspended pass is ended and started again.
+        let resolve_targets = desc.resolves();
         let mut vk_clear_values =
             ArrayVec::<vk::ClearValue, { super::MAX_TOTAL_ATTACHMENTS }>::new();
         let mut rp_key = super::RenderPassKey {
@@ -806,17 +1312,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 vk_clear_values.push(vk::ClearValue {
                     color: unsafe { cat.make_vk_clear_color() },
                 });
+                let resolve_target = cat.resolve_target.as_ref().filter(|_| resolve_targets);
                 let color = super::ColorAttachmentKey {
-                    base: cat.target.make_attachment_key(cat.ops),
-                    resolve: cat
-                        .resolve_target
-                        .as_ref()
+                    base: cat
+                        .target
+                        .make_attachment_key(cat.ops.with_pass_flags(desc.flags)),
+                    resolve: resolve_target
                         .map(|target| target.make_attachment_key(crate::AttachmentOps::STORE)),
                 };
 
                 rp_key.colors.push(Some(color));
                 fb_key.attachments.push(color_view);
-                if let Some(ref at) = cat.resolve_target {
+                if let Some(at) = resolve_target {
                     vk_clear_values.push(unsafe { mem::zeroed() });
                     fb_key.attachments.push(at.view.raw);
                 }
@@ -840,11 +1347,34 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     stencil: ds.clear_value.1,
                 },
             });
+            // Aspects that aren't resolved are loaded and stored, so they keep their contents.
+            let resolve_ops = |resolved| {
+                if resolved {
+                    crate::AttachmentOps::STORE
+                } else {
+                    crate::AttachmentOps::LOAD | crate::AttachmentOps::STORE
+                }
+            };
+            let resolve = ds.resolve.as_ref().filter(|_| resolve_targets);
             rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
-                base: ds.target.make_attachment_key(ds.depth_ops),
-                stencil_ops: ds.stencil_ops,
+                base: ds
+                    .target
+                    .make_attachment_key(ds.depth_ops.with_pass_flags(desc.flags)),
+                stencil_ops: ds.stencil_ops.with_pass_flags(desc.flags),
+                resolve: resolve.map(|resolve| super::DepthStencilResolveKey {
+                    base: resolve
+                        .target
+                        .make_attachment_key(resolve_ops(resolve.depth_mode.is_some())),
+                    stencil_ops: resolve_ops(resolve.resolve_stencil),
+                    depth_mode: resolve.depth_mode,
+                    resolve_stencil: resolve.resolve_stencil,
+                }),
             });
             fb_key.attachments.push(ds.target.view.raw);
+            if let Some(resolve) = resolve {
+                vk_clear_values.push(unsafe { mem::zeroed() });
+                fb_key.attachments.push(resolve.target.view.raw);
+            }
 
             // Assert this attachment is valid for the detected multiview, as a sanity check
             // The driver crash for this is really bad on AMD, so the check is worth it
@@ -853,70 +1383,58 @@ impl crate::CommandEncoder for super::CommandEncoder {
             }
         }
 
-        let render_area = vk::Rect2D {
-            offset: vk::Offset2D { x: 0, y: 0 },
-            extent: vk::Extent2D {
-                width: desc.extent.width,
-                height: desc.extent.height,
-            },
-        };
-        let vk_viewports = [vk::Viewport {
-            x: 0.0,
-            y: desc.extent.height as f32,
-            width: desc.extent.width as f32,
-            height: -(desc.extent.height as f32),
-            min_depth: 0.0,
-            max_depth: 1.0,
-        }];
-
         let raw_pass = self.device.make_render_pass(rp_key).unwrap();
         fb_key.raw_pass = raw_pass;
         let raw_framebuffer = self.make_framebuffer(fb_key).unwrap();
 
+        let render_area = unsafe { self.begin_render_pass_state(desc) };
+
         let vk_info = vk::RenderPassBeginInfo::default()
             .render_pass(raw_pass)
             .render_area(render_area)
             .clear_values(&vk_clear_values)
             .framebuffer(raw_framebuffer);
 
-        if let Some(label) = desc.label {
-            unsafe { self.begin_debug_marker(label) };
-            self.rpass_debug_marker_active = true;
-        }
-
-        // Start timestamp if any (before all other commands but after debug marker)
-        if let Some(timestamp_writes) = desc.timestamp_writes.as_ref() {
-            if let Some(index) = timestamp_writes.beginning_of_pass_write_index {
-                unsafe {
-                    self.write_timestamp(timestamp_writes.query_set, index);
-                }
-            }
-            self.end_of_pass_timer_query = timestamp_writes
-                .end_of_pass_write_index
-                .map(|index| (timestamp_writes.query_set.raw, index));
-        }
-
+        let contents = if desc.parallel {
+            self.parallel_pass = Some(super::ParallelRenderPass {
+                raw_pass,
+                framebuffer: raw_framebuffer,
+                color_formats: ArrayVec::new(),
+                depth_format: vk::Format::UNDEFINED,
+                stencil_format: vk::Format::UNDEFINED,
+                samples: vk::SampleCountFlags::empty(),
+                view_mask: 0,
+                extent: desc.extent,
+            });
+            vk::SubpassContents::SECONDARY_COMMAND_BUFFERS
+        } else {
+            vk::SubpassContents::INLINE
+        };
         unsafe {
             self.device
                 .raw
-                .cmd_set_viewport(self.active, 0, &vk_viewports);
-            self.device
-                .raw
-                .cmd_set_scissor(self.active, 0, &[render_area]);
-            self.device.raw.cmd_begin_render_pass(
-                self.active,
-                &vk_info,
-                vk::SubpassContents::INLINE,
-            );
+                .cmd_begin_render_pass(self.active, &vk_info, contents);
         };
 
+        // Queries in a render pass must begin and end within the same subpass.
+        self.begin_pass_pipeline_statistics_query(desc.pipeline_statistics_query.as_ref());
+
         self.bind_point = vk::PipelineBindPoint::GRAPHICS;
 
         Ok(())
     }
     unsafe fn end_render_pass(&mut self) {
-        unsafe {
-            self.device.raw.cmd_end_render_pass(self.active);
+        self.end_pass_pipeline_statistics_query_if_requested();
+        self.parallel_pass = None;
+
+        match self.device.extension_fns.dynamic_rendering {
+            Some(super::ExtensionFn::Extension(ref ext)) => unsafe {
+                ext.cmd_end_rendering(self.active)
+            },
+            Some(super::ExtensionFn::Promoted) => unsafe {
+                self.device.raw.cmd_end_rendering(self.active)
+            },
+            None => unsafe { self.device.raw.cmd_end_render_pass(self.active) },
         }
 
         // After all other commands but before debug marker, so this is still seen as part of this pass.
@@ -930,6 +1448,36 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn create_parallel_render_encoder(
+        &mut self,
+    ) -> Result<super::CommandEncoder, crate::DeviceError> {
+        let mut encoder = match self.free_parallel_encoders.pop() {
+            Some(encoder) => encoder,
+            None => unsafe {
+                super::CommandEncoder::new(&self.device, self.family_index, &self.counters)?
+            },
+        };
+        let pass = self.parallel_pass.as_ref().unwrap();
+        unsafe { encoder.begin_parallel_encoding(pass, self.command_buffer_usage()) }?;
+        Ok(encoder)
+    }
+
+    unsafe fn execute_parallel_render_encoders(
+        &mut self,
+        encoders: Vec<super::CommandEncoder>,
+    ) -> Result<(), crate::DeviceError> {
+        let mut raws = Vec::with_capacity(encoders.len());
+        for mut encoder in encoders {
+            let cmd_buf = unsafe { encoder.end_encoding() }?;
+            raws.push(cmd_buf.raw);
+            self.parallel_encoders.push((encoder, cmd_buf.raw));
+        }
+        if !raws.is_empty() {
+            unsafe { self.device.raw.cmd_execute_commands(self.active, &raws) };
+        }
+        Ok(())
+    }
+
     unsafe fn set_bind_group(
         &mut self,
         layout: &super::PipelineLayout,
@@ -1039,7 +1587,28 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 .cmd_set_viewport(self.active, 0, &vk_viewports)
         };
     }
+    unsafe fn set_viewport_indexed(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+        let vk_viewports = [vk::Viewport {
+            x: rect.x,
+            y: rect.y + rect.h,
+            width: rect.w,
+            height: -rect.h, // flip Y
+            min_depth: depth_range.start,
+            max_depth: depth_range.end,
+        }];
+        unsafe {
+            self.device
+                .raw
+                .cmd_set_viewport(self.active, index, &vk_viewports)
+        };
+    }
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
+        // The scissor applies to every viewport.
         let vk_scissors = [vk::Rect2D {
             offset: vk::Offset2D {
                 x: rect.x as i32,
@@ -1049,11 +1618,12 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 width: rect.w,
                 height: rect.h,
             },
-        }];
+        }; wgt::MAX_VIEWPORTS as usize];
+        let viewport_count = self.device.viewport_count() as usize;
         unsafe {
             self.device
                 .raw
-                .cmd_set_scissor(self.active, 0, &vk_scissors)
+                .cmd_set_scissor(self.active, 0, &vk_scissors[..viewport_count])
         };
     }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
@@ -1068,6 +1638,46 @@ impl crate::CommandEncoder for super::CommandEncoder {
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
         unsafe { self.device.raw.cmd_set_blend_constants(self.active, color) };
     }
+    unsafe fn set_shading_rate(&mut self, rate: wgt::ShadingRate) {
+        let (width, height) = rate.block_size();
+        // This sets the pipeline rate, which the combiners keep over the primitive and
+        // attachment rates. Unsupported sizes are clamped by the implementation.
+        let combiner_ops = [vk::FragmentShadingRateCombinerOpKHR::KEEP; 2];
+        if let Some(ref t) = self.device.extension_fns.fragment_shading_rate {
+            unsafe {
+                (t.fp().cmd_set_fragment_shading_rate_khr)(
+                    self.active,
+                    &vk::Extent2D { width, height },
+                    &combiner_ops,
+                )
+            };
+        } else {
+            panic!("Feature `VARIABLE_RATE_SHADING` not enabled");
+        }
+    }
+
+    unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &super::Buffer,
+        offset: wgt::BufferAddress,
+    ) {
+        let info = vk::ConditionalRenderingBeginInfoEXT::default()
+            .buffer(buffer.raw)
+            .offset(offset);
+        if let Some(ref t) = self.device.extension_fns.conditional_rendering {
+            unsafe { (t.fp().cmd_begin_conditional_rendering_ext)(self.active, &info) };
+        } else {
+            panic!("Feature `CONDITIONAL_RENDERING` not enabled");
+        }
+    }
+
+    unsafe fn end_conditional_rendering(&mut self) {
+        if let Some(ref t) = self.device.extension_fns.conditional_rendering {
+            unsafe { (t.fp().cmd_end_conditional_rendering_ext)(self.active) };
+        } else {
+            panic!("Feature `CONDITIONAL_RENDERING` not enabled");
+        }
+    }
 
     unsafe fn draw(
         &mut self,
@@ -1273,8 +1883,11 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 .end_of_pass_write_index
                 .map(|index| (timestamp_writes.query_set.raw, index));
         }
+
+        self.begin_pass_pipeline_statistics_query(desc.pipeline_statistics_query.as_ref());
     }
     unsafe fn end_compute_pass(&mut self) {
+        self.end_pass_pipeline_statistics_query_if_requested();
         self.write_pass_end_timestamp_if_requested();
 
         if self.rpass_debug_marker_active {
@@ -1284,6 +1897,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
 
     unsafe fn set_compute_pipeline(&mut self, pipeline: &super::ComputePipeline) {
+        self.bind_point = vk::PipelineBindPoint::COMPUTE;
         unsafe {
             self.device.raw.cmd_bind_pipeline(
                 self.active,
@@ -1308,6 +1922,71 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn set_ray_tracing_pipeline(&mut self, pipeline: &super::RayTracingPipeline) {
+        self.bind_point = vk::PipelineBindPoint::RAY_TRACING_KHR;
+        unsafe {
+            self.device.raw.cmd_bind_pipeline(
+                self.active,
+                vk::PipelineBindPoint::RAY_TRACING_KHR,
+                pipeline.raw,
+            )
+        };
+    }
+
+    unsafe fn trace_rays(
+        &mut self,
+        tables: &crate::ShaderBindingTables<super::Buffer>,
+        count: [u32; 3],
+    ) {
+        let ray_tracing_functions = self
+            .device
+            .extension_fns
+            .ray_tracing
+            .as_ref()
+            .expect("Feature `RAY_TRACING` not enabled");
+        let ray_tracing_pipeline = self
+            .device
+            .extension_fns
+            .ray_tracing_pipeline
+            .as_ref()
+            .expect("Feature `EXPERIMENTAL_RAY_TRACING_PIPELINE` not enabled");
+
+        let get_region = |table: Option<&crate::ShaderBindingTable<super::Buffer>>| {
+            let Some(table) = table else {
+                return vk::StridedDeviceAddressRegionKHR::default();
+            };
+            let buffer_address = unsafe {
+                ray_tracing_functions
+                    .buffer_device_address
+                    .get_buffer_device_address(
+                        &vk::BufferDeviceAddressInfo::default().buffer(table.buffer.raw),
+                    )
+            };
+            vk::StridedDeviceAddressRegionKHR::default()
+                .device_address(buffer_address + table.region.offset)
+                .stride(table.region.stride)
+                .size(table.region.size)
+        };
+
+        let ray_generation = get_region(Some(&tables.ray_generation));
+        let miss = get_region(tables.miss.as_ref());
+        let hit = get_region(tables.hit.as_ref());
+        let callable = get_region(tables.callable.as_ref());
+
+        unsafe {
+            ray_tracing_pipeline.cmd_trace_rays(
+                self.active,
+                &ray_generation,
+                &miss,
+                &hit,
+                &callable,
+                count[0],
+                count[1],
+                count[2],
+            )
+        };
+    }
+
     unsafe fn copy_acceleration_structure_to_acceleration_structure(
         &mut self,
         src: &super::AccelerationStructure,
@@ -1344,6 +2023,68 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 );
         }
     }
+
+    unsafe fn copy_acceleration_structure_to_buffer(
+        &mut self,
+        src: &super::AccelerationStructure,
+        dst: &super::Buffer,
+        dst_offset: wgt::BufferAddress,
+    ) {
+        let ray_tracing_functions = self
+            .device
+            .extension_fns
+            .ray_tracing
+            .as_ref()
+            .expect("Feature `RAY_TRACING` not enabled");
+
+        unsafe {
+            let dst_address = ray_tracing_functions
+                .buffer_device_address
+                .get_buffer_device_address(&vk::BufferDeviceAddressInfo::default().buffer(dst.raw));
+            ray_tracing_functions
+                .acceleration_structure
+                .cmd_copy_acceleration_structure_to_memory(
+                    self.active,
+                    &vk::CopyAccelerationStructureToMemoryInfoKHR::default()
+                        .src(src.raw)
+                        .dst(vk::DeviceOrHostAddressKHR {
+                            device_address: dst_address + dst_offset,
+                        })
+                        .mode(vk::CopyAccelerationStructureModeKHR::SERIALIZE),
+                );
+        }
+    }
+
+    unsafe fn copy_buffer_to_acceleration_structure(
+        &mut self,
+        src: &super::Buffer,
+        src_offset: wgt::BufferAddress,
+        dst: &super::AccelerationStructure,
+    ) {
+        let ray_tracing_functions = self
+            .device
+            .extension_fns
+            .ray_tracing
+            .as_ref()
+            .expect("Feature `RAY_TRACING` not enabled");
+
+        unsafe {
+            let src_address = ray_tracing_functions
+                .buffer_device_address
+                .get_buffer_device_address(&vk::BufferDeviceAddressInfo::default().buffer(src.raw));
+            ray_tracing_functions
+                .acceleration_structure
+                .cmd_copy_memory_to_acceleration_structure(
+                    self.active,
+                    &vk::CopyMemoryToAccelerationStructureInfoKHR::default()
+                        .src(vk::DeviceOrHostAddressConstKHR {
+                            device_address: src_address + src_offset,
+                        })
+                        .dst(dst.raw)
+                        .mode(vk::CopyAccelerationStructureModeKHR::DESERIALIZE),
+                );
+        }
+    }
 }
 
 #[test]
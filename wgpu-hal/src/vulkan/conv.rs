@@ -78,6 +78,8 @@ impl super::PrivateCapabilities {
             }
             Tf::Depth16Unorm => F::D16_UNORM,
             Tf::NV12 => F::G8_B8R8_2PLANE_420_UNORM,
+            Tf::P010 => F::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16,
+            Tf::I420 => F::G8_B8_R8_3PLANE_420_UNORM,
             Tf::Rgb9e5Ufloat => F::E5B9G9R9_UFLOAT_PACK32,
             Tf::Bc1RgbaUnorm => F::BC1_RGBA_UNORM_BLOCK,
             Tf::Bc1RgbaUnormSrgb => F::BC1_RGBA_SRGB_BLOCK,
@@ -177,10 +179,41 @@ pub fn map_vk_surface_formats(sf: vk::SurfaceFormatKHR) -> Option<wgt::TextureFo
             F::A2B10G10R10_UNORM_PACK32 => Tf::Rgb10a2Unorm,
             _ => return None,
         },
+        vk::ColorSpaceKHR::EXTENDED_SRGB_NONLINEAR_EXT => match sf.format {
+            F::R16G16B16A16_SFLOAT => Tf::Rgba16Float,
+            _ => return None,
+        },
+        vk::ColorSpaceKHR::HDR10_ST2084_EXT => match sf.format {
+            F::R16G16B16A16_SFLOAT => Tf::Rgba16Float,
+            F::A2B10G10R10_UNORM_PACK32 => Tf::Rgb10a2Unorm,
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+pub fn map_vk_color_space(color_space: vk::ColorSpaceKHR) -> Option<wgt::SurfaceColorSpace> {
+    use wgt::SurfaceColorSpace as Scs;
+    Some(match color_space {
+        vk::ColorSpaceKHR::SRGB_NONLINEAR => Scs::Srgb,
+        vk::ColorSpaceKHR::EXTENDED_SRGB_NONLINEAR_EXT => Scs::ExtendedSrgb,
+        vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT => Scs::ExtendedSrgbLinear,
+        vk::ColorSpaceKHR::HDR10_ST2084_EXT => Scs::Hdr10,
         _ => return None,
     })
 }
 
+pub fn map_color_space(color_space: wgt::SurfaceColorSpace) -> vk::ColorSpaceKHR {
+    use wgt::SurfaceColorSpace as Scs;
+    match color_space {
+        Scs::Srgb => vk::ColorSpaceKHR::SRGB_NONLINEAR,
+        Scs::ExtendedSrgb => vk::ColorSpaceKHR::EXTENDED_SRGB_NONLINEAR_EXT,
+        Scs::ExtendedSrgbLinear => vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT,
+        Scs::Hdr10 => vk::ColorSpaceKHR::HDR10_ST2084_EXT,
+        Scs::Auto => unreachable!(),
+    }
+}
+
 impl crate::Attachment<'_, super::TextureView> {
     pub(super) fn make_attachment_key(&self, ops: crate::AttachmentOps) -> super::AttachmentKey {
         super::AttachmentKey {
@@ -360,6 +393,7 @@ pub fn map_texture_dimension(dim: wgt::TextureDimension) -> vk::ImageType {
 
 pub fn map_index_format(index_format: wgt::IndexFormat) -> vk::IndexType {
     match index_format {
+        wgt::IndexFormat::Uint8 => vk::IndexType::UINT8_EXT,
         wgt::IndexFormat::Uint16 => vk::IndexType::UINT16,
         wgt::IndexFormat::Uint32 => vk::IndexType::UINT32,
     }
@@ -455,6 +489,15 @@ pub fn map_attachment_ops(
     (load_op, store_op)
 }
 
+pub fn map_resolve_mode(mode: Option<wgt::ResolveMode>) -> vk::ResolveModeFlags {
+    match mode {
+        None => vk::ResolveModeFlags::NONE,
+        Some(wgt::ResolveMode::SampleZero) => vk::ResolveModeFlags::SAMPLE_ZERO,
+        Some(wgt::ResolveMode::Min) => vk::ResolveModeFlags::MIN,
+        Some(wgt::ResolveMode::Max) => vk::ResolveModeFlags::MAX,
+    }
+}
+
 pub fn map_present_mode(mode: wgt::PresentMode) -> vk::PresentModeKHR {
     match mode {
         wgt::PresentMode::Immediate => vk::PresentModeKHR::IMMEDIATE,
@@ -551,7 +594,12 @@ pub fn map_buffer_usage(usage: wgt::BufferUses) -> vk::BufferUsageFlags {
             | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
     }
     if usage.intersects(wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY) {
-        flags |= vk::BufferUsageFlags::TRANSFER_DST;
+        // Serialized acceleration structures are written through a device address.
+        flags |= vk::BufferUsageFlags::TRANSFER_DST | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
+    }
+    if usage.contains(wgt::BufferUses::SHADER_BINDING_TABLE) {
+        flags |= vk::BufferUsageFlags::SHADER_BINDING_TABLE_KHR
+            | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
     }
     flags
 }
@@ -615,10 +663,19 @@ pub fn map_buffer_usage_to_barrier(
             | vk::AccessFlags::ACCELERATION_STRUCTURE_WRITE_KHR
             | vk::AccessFlags::SHADER_READ;
     }
+    if usage.contains(wgt::BufferUses::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT) {
+        // Deserialization reads its source as a transfer in the build stage.
+        access |= vk::AccessFlags::TRANSFER_READ;
+    }
     if usage.contains(wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY) {
-        stages |= vk::PipelineStageFlags::TRANSFER;
+        stages |= vk::PipelineStageFlags::TRANSFER
+            | vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR;
         access |= vk::AccessFlags::TRANSFER_WRITE;
     }
+    if usage.contains(wgt::BufferUses::SHADER_BINDING_TABLE) {
+        stages |= vk::PipelineStageFlags::RAY_TRACING_SHADER_KHR;
+        access |= vk::AccessFlags::SHADER_READ;
+    }
 
     (stages, access)
 }
@@ -695,6 +752,28 @@ pub fn map_filter_mode(mode: wgt::FilterMode) -> vk::Filter {
     }
 }
 
+pub fn map_yuv_matrix(matrix: wgt::YuvMatrix) -> vk::SamplerYcbcrModelConversion {
+    match matrix {
+        wgt::YuvMatrix::Bt601 => vk::SamplerYcbcrModelConversion::YCBCR_601,
+        wgt::YuvMatrix::Bt709 => vk::SamplerYcbcrModelConversion::YCBCR_709,
+        wgt::YuvMatrix::Bt2020 => vk::SamplerYcbcrModelConversion::YCBCR_2020,
+    }
+}
+
+pub fn map_yuv_range(range: wgt::YuvRange) -> vk::SamplerYcbcrRange {
+    match range {
+        wgt::YuvRange::Full => vk::SamplerYcbcrRange::ITU_FULL,
+        wgt::YuvRange::Limited => vk::SamplerYcbcrRange::ITU_NARROW,
+    }
+}
+
+pub fn map_chroma_location(location: wgt::ChromaLocation) -> vk::ChromaLocation {
+    match location {
+        wgt::ChromaLocation::CositedEven => vk::ChromaLocation::COSITED_EVEN,
+        wgt::ChromaLocation::Midpoint => vk::ChromaLocation::MIDPOINT,
+    }
+}
+
 pub fn map_mip_filter_mode(mode: wgt::FilterMode) -> vk::SamplerMipmapMode {
     match mode {
         wgt::FilterMode::Nearest => vk::SamplerMipmapMode::NEAREST,
@@ -747,6 +826,24 @@ pub fn map_shader_stage(stage: wgt::ShaderStages) -> vk::ShaderStageFlags {
     if stage.contains(wgt::ShaderStages::COMPUTE) {
         flags |= vk::ShaderStageFlags::COMPUTE;
     }
+    if stage.contains(wgt::ShaderStages::RAY_GENERATION) {
+        flags |= vk::ShaderStageFlags::RAYGEN_KHR;
+    }
+    if stage.contains(wgt::ShaderStages::MISS) {
+        flags |= vk::ShaderStageFlags::MISS_KHR;
+    }
+    if stage.contains(wgt::ShaderStages::CLOSEST_HIT) {
+        flags |= vk::ShaderStageFlags::CLOSEST_HIT_KHR;
+    }
+    if stage.contains(wgt::ShaderStages::ANY_HIT) {
+        flags |= vk::ShaderStageFlags::ANY_HIT_KHR;
+    }
+    if stage.contains(wgt::ShaderStages::INTERSECTION) {
+        flags |= vk::ShaderStageFlags::INTERSECTION_KHR;
+    }
+    if stage.contains(wgt::ShaderStages::CALLABLE) {
+        flags |= vk::ShaderStageFlags::CALLABLE_KHR;
+    }
     flags
 }
 
@@ -773,6 +870,7 @@ pub fn map_binding_type(ty: wgt::BindingType) -> vk::DescriptorType {
             vk::DescriptorType::ACCELERATION_STRUCTURE_KHR
         }
         wgt::BindingType::ExternalTexture => unimplemented!(),
+        wgt::BindingType::YcbcrTexture { .. } => vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
     }
 }
 
@@ -882,6 +980,28 @@ pub fn map_blend_component(
     (op, src, dst)
 }
 
+pub fn map_logic_op(logic_op: wgt::LogicOp) -> vk::LogicOp {
+    use wgt::LogicOp as Lo;
+    match logic_op {
+        Lo::Clear => vk::LogicOp::CLEAR,
+        Lo::And => vk::LogicOp::AND,
+        Lo::AndReverse => vk::LogicOp::AND_REVERSE,
+        Lo::Copy => vk::LogicOp::COPY,
+        Lo::AndInverted => vk::LogicOp::AND_INVERTED,
+        Lo::NoOp => vk::LogicOp::NO_OP,
+        Lo::Xor => vk::LogicOp::XOR,
+        Lo::Or => vk::LogicOp::OR,
+        Lo::Nor => vk::LogicOp::NOR,
+        Lo::Equivalent => vk::LogicOp::EQUIVALENT,
+        Lo::Invert => vk::LogicOp::INVERT,
+        Lo::OrReverse => vk::LogicOp::OR_REVERSE,
+        Lo::CopyInverted => vk::LogicOp::COPY_INVERTED,
+        Lo::OrInverted => vk::LogicOp::OR_INVERTED,
+        Lo::Nand => vk::LogicOp::NAND,
+        Lo::Set => vk::LogicOp::SET,
+    }
+}
+
 pub fn map_pipeline_statistics(
     types: wgt::PipelineStatisticsTypes,
 ) -> vk::QueryPipelineStatisticFlags {
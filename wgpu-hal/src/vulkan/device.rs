@@ -2,6 +2,7 @@ use alloc::{
     borrow::{Cow, ToOwned as _},
     collections::BTreeMap,
     ffi::CString,
+    string::{String, ToString as _},
     sync::Arc,
     vec::Vec,
 };
@@ -9,7 +10,7 @@ use core::{
     ffi::CStr,
     mem::{self, MaybeUninit},
     num::NonZeroU32,
-    ptr,
+    ptr, slice,
 };
 
 use arrayvec::ArrayVec;
@@ -20,6 +21,56 @@ use parking_lot::Mutex;
 use super::{RawTlasInstance, conv};
 use crate::TlasInstance;
 
+/// The attachment formats a pipeline is created with instead of a compatible
+/// render pass, when render passes are begun with dynamic rendering.
+struct RenderingFormats {
+    colors: ArrayVec<vk::Format, { crate::MAX_COLOR_ATTACHMENTS }>,
+    depth: vk::Format,
+    stencil: vk::Format,
+    view_mask: u32,
+}
+
+impl RenderingFormats {
+    fn new(key: &super::RenderPassKey, depth_stencil_format: Option<wgt::TextureFormat>) -> Self {
+        let aspects = depth_stencil_format.map_or(crate::FormatAspects::empty(), Into::into);
+        let depth_stencil = key
+            .depth_stencil
+            .as_ref()
+            .map_or(vk::Format::UNDEFINED, |ds| ds.base.format);
+        let format_if = |aspect| {
+            if aspects.contains(aspect) {
+                depth_stencil
+            } else {
+                vk::Format::UNDEFINED
+            }
+        };
+        Self {
+            colors: key
+                .colors
+                .iter()
+                .map(|cat| {
+                    cat.as_ref()
+                        .map_or(vk::Format::UNDEFINED, |cat| cat.base.format)
+                })
+                .collect(),
+            depth: format_if(crate::FormatAspects::DEPTH),
+            stencil: format_if(crate::FormatAspects::STENCIL),
+            // All views are rendered to, like `make_render_pass` does.
+            view_mask: key
+                .multiview
+                .map_or(0, |multiview| (1 << multiview.get()) - 1),
+        }
+    }
+
+    fn to_vk(&self) -> vk::PipelineRenderingCreateInfo<'_> {
+        vk::PipelineRenderingCreateInfo::default()
+            .view_mask(self.view_mask)
+            .color_attachment_formats(&self.colors)
+            .depth_attachment_format(self.depth)
+            .stencil_attachment_format(self.stencil)
+    }
+}
+
 impl super::DeviceShared {
     /// Set the name of `object` to `name`.
     ///
@@ -86,6 +137,7 @@ impl super::DeviceShared {
                 let mut color_refs = Vec::with_capacity(colors.len());
                 let mut resolve_refs = Vec::with_capacity(color_refs.capacity());
                 let mut ds_ref = None;
+                let mut ds_resolve = None;
                 let samples = vk::SampleCountFlags::from_raw(sample_count);
                 let unused = vk::AttachmentReference {
                     attachment: vk::ATTACHMENT_UNUSED,
@@ -152,6 +204,7 @@ impl super::DeviceShared {
                     let super::DepthStencilAttachmentKey {
                         ref base,
                         stencil_ops,
+                        ref resolve,
                     } = *ds;
 
                     let super::AttachmentKey {
@@ -176,6 +229,35 @@ impl super::DeviceShared {
                         .initial_layout(layout)
                         .final_layout(layout);
                     vk_attachments.push(vk_attachment);
+
+                    if let Some(ref resolve) = *resolve {
+                        let super::AttachmentKey {
+                            format,
+                            layout,
+                            ops,
+                        } = resolve.base;
+
+                        ds_resolve = Some((
+                            vk::AttachmentReference {
+                                attachment: vk_attachments.len() as u32,
+                                layout,
+                            },
+                            resolve,
+                        ));
+                        let (load_op, store_op) = conv::map_attachment_ops(ops);
+                        let (stencil_load_op, stencil_store_op) =
+                            conv::map_attachment_ops(resolve.stencil_ops);
+                        let vk_attachment = vk::AttachmentDescription::default()
+                            .format(format)
+                            .samples(vk::SampleCountFlags::TYPE_1)
+                            .load_op(load_op)
+                            .store_op(store_op)
+                            .stencil_load_op(stencil_load_op)
+                            .stencil_store_op(stencil_store_op)
+                            .initial_layout(layout)
+                            .final_layout(layout);
+                        vk_attachments.push(vk_attachment);
+                    }
                 }
 
                 let vk_subpasses = [{
@@ -202,6 +284,20 @@ impl super::DeviceShared {
                     .attachments(&vk_attachments)
                     .subpasses(&vk_subpasses);
 
+                if let Some((resolve_ref, resolve)) = ds_resolve {
+                    // Depth/stencil resolves only exist in the `*2` render pass structures.
+                    let raw = unsafe {
+                        self.create_render_pass2(
+                            &vk_attachments,
+                            &vk_subpasses[0],
+                            &resolve_ref,
+                            resolve,
+                            multiview,
+                        )?
+                    };
+                    return Ok(*e.insert(raw));
+                }
+
                 let mut multiview_info;
                 let mask;
                 if let Some(multiview) = multiview {
@@ -232,6 +328,115 @@ impl super::DeviceShared {
         })
     }
 
+    /// Returns the render pass to create a pipeline compatible with `key` with,
+    /// or a null handle and the attachment formats to create it with instead if
+    /// render passes are begun with dynamic rendering.
+    fn make_pipeline_render_pass(
+        &self,
+        key: super::RenderPassKey,
+        depth_stencil_format: Option<wgt::TextureFormat>,
+    ) -> Result<(vk::RenderPass, Option<RenderingFormats>), crate::DeviceError> {
+        if self.extension_fns.dynamic_rendering.is_some() {
+            let formats = RenderingFormats::new(&key, depth_stencil_format);
+            Ok((vk::RenderPass::null(), Some(formats)))
+        } else {
+            Ok((self.make_render_pass(key)?, None))
+        }
+    }
+
+    /// Creates a single subpass render pass with a depth/stencil resolve, by
+    /// translating the `vk::RenderPassCreateInfo` structures built by
+    /// [`Self::make_render_pass`] to their `*2` counterparts.
+    unsafe fn create_render_pass2(
+        &self,
+        attachments: &[vk::AttachmentDescription],
+        subpass: &vk::SubpassDescription,
+        resolve_ref: &vk::AttachmentReference,
+        resolve: &super::DepthStencilResolveKey,
+        multiview: Option<NonZeroU32>,
+    ) -> Result<vk::RenderPass, crate::DeviceError> {
+        let map_ref = |reference: &vk::AttachmentReference| {
+            vk::AttachmentReference2::default()
+                .attachment(reference.attachment)
+                .layout(reference.layout)
+        };
+        let map_refs = |references: *const vk::AttachmentReference, count: u32| {
+            if references.is_null() {
+                Vec::new()
+            } else {
+                unsafe { slice::from_raw_parts(references, count as usize) }
+                    .iter()
+                    .map(map_ref)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let vk_attachments = attachments
+            .iter()
+            .map(|at| {
+                vk::AttachmentDescription2::default()
+                    .format(at.format)
+                    .samples(at.samples)
+                    .load_op(at.load_op)
+                    .store_op(at.store_op)
+                    .stencil_load_op(at.stencil_load_op)
+                    .stencil_store_op(at.stencil_store_op)
+                    .initial_layout(at.initial_layout)
+                    .final_layout(at.final_layout)
+            })
+            .collect::<Vec<_>>();
+        let color_refs = map_refs(subpass.p_color_attachments, subpass.color_attachment_count);
+        let resolve_refs = map_refs(
+            subpass.p_resolve_attachments,
+            subpass.color_attachment_count,
+        );
+        let ds_ref = map_ref(unsafe { &*subpass.p_depth_stencil_attachment });
+        let ds_resolve_ref = map_ref(resolve_ref);
+
+        let mut ds_resolve_info = vk::SubpassDescriptionDepthStencilResolve::default()
+            .depth_resolve_mode(conv::map_resolve_mode(resolve.depth_mode))
+            .stencil_resolve_mode(conv::map_resolve_mode(
+                resolve
+                    .resolve_stencil
+                    .then_some(wgt::ResolveMode::SampleZero),
+            ))
+            .depth_stencil_resolve_attachment(&ds_resolve_ref);
+
+        // Right now we enable all bits on the view masks and correlation masks,
+        // like `make_render_pass` does.
+        let mask = multiview.map_or(0, |multiview| (1 << multiview.get()) - 1);
+        let correlation_masks = [mask];
+
+        let mut vk_subpass = vk::SubpassDescription2::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .view_mask(mask)
+            .color_attachments(&color_refs)
+            .depth_stencil_attachment(&ds_ref)
+            .push_next(&mut ds_resolve_info);
+        if !resolve_refs.is_empty() {
+            vk_subpass = vk_subpass.resolve_attachments(&resolve_refs);
+        }
+        let vk_subpasses = [vk_subpass];
+
+        let mut vk_info = vk::RenderPassCreateInfo2::default()
+            .attachments(&vk_attachments)
+            .subpasses(&vk_subpasses);
+        if multiview.is_some() {
+            vk_info = vk_info.correlated_view_masks(&correlation_masks);
+        }
+
+        let result = match self.extension_fns.create_renderpass2 {
+            Some(super::ExtensionFn::Extension(ref ext)) => unsafe {
+                ext.create_render_pass2(&vk_info, None)
+            },
+            Some(super::ExtensionFn::Promoted) => unsafe {
+                self.raw.create_render_pass2(&vk_info, None)
+            },
+            None => panic!("Feature `DEPTH_STENCIL_RESOLVE` not enabled"),
+        };
+        result.map_err(super::map_host_device_oom_err)
+    }
+
     fn make_memory_ranges<'a, I: 'a + Iterator<Item = crate::MemoryRange>>(
         &self,
         buffer: &'a super::Buffer,
@@ -484,6 +689,21 @@ struct CompiledStage {
 }
 
 impl super::Device {
+    /// Create a timeline semaphore, exportable as described by `export_info`.
+    unsafe fn create_timeline_semaphore(
+        &self,
+        export_info: Option<&mut vk::ExportSemaphoreCreateInfo<'_>>,
+    ) -> Result<vk::Semaphore, crate::DeviceError> {
+        let mut sem_type_info =
+            vk::SemaphoreTypeCreateInfo::default().semaphore_type(vk::SemaphoreType::TIMELINE);
+        let mut vk_info = vk::SemaphoreCreateInfo::default().push_next(&mut sem_type_info);
+        if let Some(export_info) = export_info {
+            vk_info = vk_info.push_next(export_info);
+        }
+        unsafe { self.shared.raw.create_semaphore(&vk_info, None) }
+            .map_err(super::map_host_device_oom_err)
+    }
+
     pub(super) unsafe fn create_swapchain(
         &self,
         surface: &super::Surface,
@@ -498,15 +718,36 @@ impl super::Device {
             None => vk::SwapchainKHR::null(),
         };
 
-        let color_space = if config.format == wgt::TextureFormat::Rgba16Float {
-            // Enable wide color gamut mode
-            // Vulkan swapchain for Android only supports DISPLAY_P3_NONLINEAR_EXT and EXTENDED_SRGB_LINEAR_EXT
-            vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT
-        } else {
-            vk::ColorSpaceKHR::SRGB_NONLINEAR
+        let color_space = conv::map_color_space(config.color_space);
+        let original_format = self.shared.private_caps.map_texture_format(config.format);
+
+        // Color spaces are only supported for some of the surface formats.
+        let surface_formats = unsafe {
+            surface
+                .functor
+                .get_physical_device_surface_formats(self.shared.physical_device, surface.raw)
         };
+        let error = match surface_formats {
+            Ok(surface_formats)
+                if surface_formats
+                    .iter()
+                    .any(|sf| sf.format == original_format && sf.color_space == color_space) =>
+            {
+                None
+            }
+            Ok(_) => Some(crate::SurfaceError::Other(
+                "Color space is not supported with the surface format",
+            )),
+            Err(vk::Result::ERROR_SURFACE_LOST_KHR) => Some(crate::SurfaceError::Lost),
+            Err(other) => Some(super::map_host_device_oom_and_lost_err(other).into()),
+        };
+        if let Some(error) = error {
+            if old_swapchain != vk::SwapchainKHR::null() {
+                unsafe { functor.destroy_swapchain(old_swapchain, None) }
+            }
+            return Err(error);
+        }
 
-        let original_format = self.shared.private_caps.map_texture_format(config.format);
         let mut raw_flags = vk::SwapchainCreateFlagsKHR::empty();
         let mut raw_view_formats: Vec<vk::Format> = vec![];
         if !config.view_formats.is_empty() {
@@ -544,6 +785,17 @@ impl super::Device {
             info = info.push_next(&mut format_list_info);
         }
 
+        let mut full_screen_exclusive_info = vk::SurfaceFullScreenExclusiveInfoEXT::default()
+            .full_screen_exclusive(vk::FullScreenExclusiveEXT::ALLOWED);
+        if config.exclusive_fullscreen
+            && self
+                .shared
+                .enabled_extensions
+                .contains(&ext::full_screen_exclusive::NAME)
+        {
+            info = info.push_next(&mut full_screen_exclusive_info);
+        }
+
         let result = {
             profiling::scope!("vkCreateSwapchainKHR");
             unsafe { functor.create_swapchain(&info, None) }
@@ -570,6 +822,24 @@ impl super::Device {
             }
         };
 
+        if let (Some(hdr_metadata), Some(hdr_metadata_fn)) = (
+            config.hdr_metadata,
+            self.shared.extension_fns.hdr_metadata.as_ref(),
+        ) {
+            let [red, green, blue, white] = crate::auxil::color_space_primaries(config.color_space);
+            let xy = |[x, y]: [f32; 2]| vk::XYColorEXT { x, y };
+            let vk_metadata = vk::HdrMetadataEXT::default()
+                .display_primary_red(xy(red))
+                .display_primary_green(xy(green))
+                .display_primary_blue(xy(blue))
+                .white_point(xy(white))
+                .max_luminance(hdr_metadata.max_luminance)
+                .min_luminance(hdr_metadata.min_luminance)
+                .max_content_light_level(hdr_metadata.max_content_light_level)
+                .max_frame_average_light_level(hdr_metadata.max_frame_average_light_level);
+            unsafe { hdr_metadata_fn.set_hdr_metadata(&[raw], &[vk_metadata]) };
+        }
+
         let images =
             unsafe { functor.get_swapchain_images(raw) }.map_err(super::map_host_device_oom_err)?;
 
@@ -593,6 +863,8 @@ impl super::Device {
             surface_semaphores,
             next_semaphore_index: 0,
             next_present_time: None,
+            next_present_id: 1,
+            last_reported_present_id: 0,
         })
     }
 
@@ -631,13 +903,17 @@ impl super::Device {
             drop_guard,
             external_memory: None,
             block: None,
+            placed: false,
             format: desc.format,
             copy_size: desc.copy_extent(),
         }
     }
 
-    #[cfg(windows)]
-    fn find_memory_type_index(
+    #[cfg(any(
+        windows,
+        all(unix, not(target_vendor = "apple"), not(target_family = "wasm"))
+    ))]
+    pub(super) fn find_memory_type_index(
         &self,
         type_bits_req: u32,
         flags_req: vk::MemoryPropertyFlags,
@@ -662,14 +938,29 @@ impl super::Device {
         None
     }
 
-    fn create_image_without_memory(
+    pub(super) fn create_image_without_memory(
+        &self,
+        desc: &crate::TextureDescriptor,
+        external_memory_image_create_info: Option<&mut vk::ExternalMemoryImageCreateInfo>,
+        drm_format_modifier_info: Option<&mut vk::ImageDrmFormatModifierExplicitCreateInfoEXT>,
+    ) -> Result<ImageWithoutMemory, crate::DeviceError> {
+        self.create_image_with_flags(
+            desc,
+            vk::ImageCreateFlags::empty(),
+            external_memory_image_create_info,
+            drm_format_modifier_info,
+        )
+    }
+
+    fn create_image_with_flags(
         &self,
         desc: &crate::TextureDescriptor,
+        mut raw_flags: vk::ImageCreateFlags,
         external_memory_image_create_info: Option<&mut vk::ExternalMemoryImageCreateInfo>,
+        drm_format_modifier_info: Option<&mut vk::ImageDrmFormatModifierExplicitCreateInfoEXT>,
     ) -> Result<ImageWithoutMemory, crate::DeviceError> {
         let copy_size = desc.copy_extent();
 
-        let mut raw_flags = vk::ImageCreateFlags::empty();
         if desc.dimension == wgt::TextureDimension::D3
             && desc.usage.contains(wgt::TextureUses::COLOR_TARGET)
         {
@@ -697,6 +988,15 @@ impl super::Device {
             raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
         }
 
+        let mut usage = conv::map_texture_usage(desc.usage);
+        if desc
+            .memory_flags
+            .contains(crate::MemoryFlags::TRANSIENT_ATTACHMENT)
+        {
+            usage |= vk::ImageUsageFlags::TRANSIENT_ATTACHMENT;
+        }
+
+        let (sharing_mode, queue_family_indices) = self.shared.sharing_mode();
         let mut vk_info = vk::ImageCreateInfo::default()
             .flags(raw_flags)
             .image_type(conv::map_texture_dimension(desc.dimension))
@@ -706,8 +1006,9 @@ impl super::Device {
             .array_layers(desc.array_layer_count())
             .samples(vk::SampleCountFlags::from_raw(desc.sample_count))
             .tiling(vk::ImageTiling::OPTIMAL)
-            .usage(conv::map_texture_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .usage(usage)
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(queue_family_indices)
             .initial_layout(vk::ImageLayout::UNDEFINED);
 
         let mut format_list_info = vk::ImageFormatListCreateInfo::default();
@@ -716,17 +1017,38 @@ impl super::Device {
             vk_info = vk_info.push_next(&mut format_list_info);
         }
 
+        // The memory requirements of images imported from Android hardware buffers come
+        // from the buffer, and can't be queried before memory is bound to them.
+        let is_android_hardware_buffer =
+            external_memory_image_create_info
+                .as_ref()
+                .is_some_and(|info| {
+                    info.handle_types.contains(
+                        vk::ExternalMemoryHandleTypeFlags::ANDROID_HARDWARE_BUFFER_ANDROID,
+                    )
+                });
+
         if let Some(ext_info) = external_memory_image_create_info {
             vk_info = vk_info.push_next(ext_info);
         }
 
+        if let Some(modifier_info) = drm_format_modifier_info {
+            vk_info = vk_info
+                .tiling(vk::ImageTiling::DRM_FORMAT_MODIFIER_EXT)
+                .push_next(modifier_info);
+        }
+
         let raw = unsafe { self.shared.raw.create_image(&vk_info, None) }.map_err(map_err)?;
         fn map_err(err: vk::Result) -> crate::DeviceError {
             // We don't use VK_EXT_image_compression_control
             // VK_ERROR_COMPRESSION_EXHAUSTED_EXT
             super::map_host_device_oom_and_ioca_err(err)
         }
-        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
+        let req = if is_android_hardware_buffer {
+            vk::MemoryRequirements::default()
+        } else {
+            unsafe { self.shared.raw.get_image_memory_requirements(raw) }
+        };
 
         Ok(ImageWithoutMemory {
             raw,
@@ -759,7 +1081,7 @@ impl super::Device {
             .handle_types(vk::ExternalMemoryHandleTypeFlags::D3D11_TEXTURE);
 
         let image =
-            self.create_image_without_memory(desc, Some(&mut external_memory_image_info))?;
+            self.create_image_without_memory(desc, Some(&mut external_memory_image_info), None)?;
 
         // Some external memory types require dedicated allocation
         // https://docs.vulkan.org/guide/latest/extensions/external.html#_importing_memory
@@ -803,11 +1125,160 @@ impl super::Device {
             drop_guard: None,
             external_memory: Some(memory),
             block: None,
+            placed: false,
             format: desc.format,
             copy_size: image.copy_size,
         })
     }
 
+    /// Backs a transient attachment with lazily allocated memory, which
+    /// tile-based GPUs may never actually commit.
+    unsafe fn create_lazily_allocated_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        image: ImageWithoutMemory,
+        memory_types: u32,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let memory_allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(image.requirements.size)
+            .memory_type_index(memory_types.trailing_zeros());
+        let memory = unsafe { self.shared.raw.allocate_memory(&memory_allocate_info, None) }
+            .map_err(super::map_host_device_oom_err)
+            .inspect_err(|_| {
+                unsafe { self.shared.raw.destroy_image(image.raw, None) };
+            })?;
+
+        unsafe { self.shared.raw.bind_image_memory(image.raw, memory, 0) }
+            .map_err(super::map_host_device_oom_err)
+            .inspect_err(|_| unsafe {
+                self.shared.raw.destroy_image(image.raw, None);
+                self.shared.raw.free_memory(memory, None);
+            })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: Some(memory),
+            block: None,
+            placed: false,
+            format: desc.format,
+            copy_size: image.copy_size,
+        })
+    }
+
+    /// Returns true if `module` has an image global that a pipeline layout could bind
+    /// with a Y'CbCr conversion, in which case it can only be translated once the
+    /// layout is known.
+    fn may_bind_ycbcr_texture(&self, module: &naga::Module) -> bool {
+        self.shared.features.contains(wgt::Features::YUV_SAMPLING)
+            && module.global_variables.iter().any(|(_, var)| {
+                matches!(
+                    module.types[var.ty].inner,
+                    naga::TypeInner::Image {
+                        dim: naga::ImageDimension::D2,
+                        arrayed: false,
+                        class: naga::ImageClass::Sampled {
+                            kind: naga::ScalarKind::Float,
+                            multi: false,
+                        },
+                    }
+                )
+            })
+    }
+
+    /// Creates the immutable sampler and Y'CbCr conversion of a
+    /// [`wgt::BindingType::YcbcrTexture`] binding.
+    unsafe fn create_ycbcr_sampler(
+        &self,
+        binding: u32,
+        conversion: &wgt::SamplerYcbcrConversion,
+    ) -> Result<super::YcbcrSampler, crate::DeviceError> {
+        let filter = conv::map_filter_mode(conversion.chroma_filter);
+        let conversion_info = vk::SamplerYcbcrConversionCreateInfo::default()
+            .format(
+                self.shared
+                    .private_caps
+                    .map_texture_format(conversion.format),
+            )
+            .ycbcr_model(conv::map_yuv_matrix(conversion.matrix))
+            .ycbcr_range(conv::map_yuv_range(conversion.range))
+            .x_chroma_offset(conv::map_chroma_location(conversion.x_chroma_offset))
+            .y_chroma_offset(conv::map_chroma_location(conversion.y_chroma_offset))
+            .chroma_filter(filter);
+        let raw_conversion = unsafe {
+            self.shared
+                .raw
+                .create_sampler_ycbcr_conversion(&conversion_info, None)
+        }
+        .map_err(super::map_host_device_oom_err)?;
+
+        // Without `SAMPLED_IMAGE_YCBCR_CONVERSION_SEPARATE_RECONSTRUCTION_FILTER`,
+        // the sampler filters must match the chroma filter.
+        let mut sampler_conversion_info =
+            vk::SamplerYcbcrConversionInfo::default().conversion(raw_conversion);
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(filter)
+            .min_filter(filter)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .push_next(&mut sampler_conversion_info);
+        let raw = match unsafe { self.shared.raw.create_sampler(&sampler_info, None) } {
+            Ok(raw) => raw,
+            Err(err) => {
+                unsafe {
+                    self.shared
+                        .raw
+                        .destroy_sampler_ycbcr_conversion(raw_conversion, None)
+                };
+                return Err(super::map_host_device_oom_err(err));
+            }
+        };
+
+        Ok(super::YcbcrSampler {
+            binding,
+            conversion: raw_conversion,
+            raw,
+        })
+    }
+
+    unsafe fn destroy_ycbcr_sampler(&self, sampler: super::YcbcrSampler) {
+        unsafe {
+            self.shared.raw.destroy_sampler(sampler.raw, None);
+            self.shared
+                .raw
+                .destroy_sampler_ycbcr_conversion(sampler.conversion, None);
+        }
+    }
+
+    /// Creates a view of `view`'s texture that samples it through `conversion`.
+    unsafe fn create_ycbcr_view(
+        &self,
+        view: &super::TextureView,
+        conversion: vk::SamplerYcbcrConversion,
+    ) -> Result<vk::ImageView, crate::DeviceError> {
+        let mut conversion_info = vk::SamplerYcbcrConversionInfo::default().conversion(conversion);
+        let vk_info = vk::ImageViewCreateInfo::default()
+            .image(view.raw_texture)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(view.raw_format)
+            .subresource_range(vk::ImageSubresourceRange {
+                // Views of all the planes use the color aspect.
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                ..view.subresource_range
+            })
+            .push_next(&mut conversion_info);
+        unsafe { self.shared.raw.create_image_view(&vk_info, None) }
+            .map_err(super::map_host_device_oom_and_ioca_err)
+    }
+
     fn create_shader_module_impl(
         &self,
         spv: &[u32],
@@ -831,6 +1302,32 @@ impl super::Device {
         Ok(raw)
     }
 
+    /// Runs `spv` through the SPIRV-Tools optimizer if `optimization` asks for it.
+    ///
+    /// This is best-effort: the unoptimized module is returned if the optimizer can't be
+    /// loaded or fails.
+    fn optimize_spv(&self, spv: Vec<u32>, optimization: wgt::ShaderOptimization) -> Vec<u32> {
+        match optimization {
+            wgt::ShaderOptimization::Default => spv,
+            #[cfg(feature = "spirv-opt")]
+            wgt::ShaderOptimization::Performance => {
+                profiling::scope!("spirv_opt::optimize");
+                let optimizer = self
+                    .spirv_optimizer
+                    .get_or_init(super::spirv_opt::SpirvOptimizer::new);
+                match optimizer.optimize(&spv, self.naga_options.lang_version) {
+                    Ok(optimized) => optimized,
+                    Err(e) => {
+                        log::warn!("SPIR-V optimization failed, using the unoptimized module: {e}");
+                        spv
+                    }
+                }
+            }
+            #[cfg(not(feature = "spirv-opt"))]
+            wgt::ShaderOptimization::Performance => spv,
+        }
+    }
+
     fn compile_stage(
         &self,
         stage: &crate::ProgrammableStage<super::ShaderModule>,
@@ -843,6 +1340,7 @@ impl super::Device {
             super::ShaderModule::Intermediate {
                 ref naga_shader,
                 runtime_checks,
+                optimization,
             } => {
                 let pipeline_options = naga::back::spv::PipelineOptions {
                     entry_point: stage.entry_point.to_owned(),
@@ -903,6 +1401,7 @@ impl super::Device {
                     naga::back::spv::write_vec(&module, &info, options, Some(&pipeline_options))
                 }
                 .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))?;
+                let spv = self.optimize_spv(spv, optimization);
                 self.create_shader_module_impl(&spv)?
             }
         };
@@ -931,6 +1430,33 @@ impl super::Device {
         })
     }
 
+    /// Describes an intersection or callable stage of a ray tracing pipeline,
+    /// whose module can't be compiled by Naga.
+    fn passthrough_ray_tracing_stage(
+        &self,
+        stage: &crate::RayTracingStage<super::ShaderModule>,
+    ) -> Result<CompiledStage, crate::PipelineError> {
+        let super::ShaderModule::Raw(raw) = *stage.programmable.module else {
+            return Err(crate::PipelineError::Linkage(
+                stage.stage,
+                "intersection and callable stages require a passthrough SPIR-V module".to_string(),
+            ));
+        };
+        let entry_point = CString::new(stage.programmable.entry_point).unwrap();
+        let mut create_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(conv::map_shader_stage(stage.stage))
+            .module(raw);
+
+        // Circumvent struct lifetime check because of a self-reference inside CompiledStage
+        create_info.p_name = entry_point.as_ptr();
+
+        Ok(CompiledStage {
+            create_info,
+            _entry_point: entry_point,
+            temp_raw_module: None,
+        })
+    }
+
     /// Returns the queue family index of the device's internal queue.
     ///
     /// This is useful for constructing memory barriers needed for queue family ownership transfer when
@@ -1063,10 +1589,12 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::BufferDescriptor,
     ) -> Result<super::Buffer, crate::DeviceError> {
+        let (sharing_mode, queue_family_indices) = self.shared.sharing_mode();
         let vk_info = vk::BufferCreateInfo::default()
             .size(desc.size)
-            .usage(conv::map_buffer_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+            .usage(self.shared.buffer_usage(desc.usage))
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(queue_family_indices);
 
         let raw = unsafe {
             self.shared
@@ -1106,7 +1634,25 @@ impl crate::Device for super::Device {
                 unsafe { self.shared.raw.destroy_buffer(raw, None) };
             })?;
 
-        let alignment_mask = req.alignment - 1;
+        let mut alignment_mask = req.alignment - 1;
+        // Shader binding table regions are addressed from the start of the buffer,
+        // which must itself be aligned like the regions are.
+        if desc.usage.contains(wgt::BufferUses::SHADER_BINDING_TABLE) {
+            alignment_mask |= u64::from(
+                self.shared
+                    .private_caps
+                    .ray_tracing_shader_group_base_alignment
+                    .saturating_sub(1),
+            );
+        }
+        // Serialized acceleration structures are copied through device addresses
+        // that must be 256-byte aligned.
+        if desc.usage.intersects(
+            wgt::BufferUses::ACCELERATION_STRUCTURE_QUERY
+                | wgt::BufferUses::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT,
+        ) {
+            alignment_mask |= 255;
+        }
 
         let block = unsafe {
             self.mem_allocator.lock().alloc(
@@ -1236,7 +1782,17 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::TextureDescriptor,
     ) -> Result<super::Texture, crate::DeviceError> {
-        let image = self.create_image_without_memory(desc, None)?;
+        let image = self.create_image_without_memory(desc, None, None)?;
+
+        let lazy_memory_types =
+            image.requirements.memory_type_bits & self.lazily_allocated_memory_types;
+        if desc
+            .memory_flags
+            .contains(crate::MemoryFlags::TRANSIENT_ATTACHMENT)
+            && lazy_memory_types != 0
+        {
+            return unsafe { self.create_lazily_allocated_texture(desc, image, lazy_memory_types) };
+        }
 
         self.error_if_would_oom_on_resource_allocation(false, image.requirements.size)
             .inspect_err(|_| {
@@ -1281,6 +1837,7 @@ impl crate::Device for super::Device {
             drop_guard: None,
             external_memory: None,
             block: Some(block),
+            placed: false,
             format: desc.format,
             copy_size: image.copy_size,
         })
@@ -1305,6 +1862,207 @@ impl crate::Device for super::Device {
         self.counters.textures.add(1);
     }
 
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<super::MemoryHeap, crate::DeviceError> {
+        let Some(memory_type) = self.placed_memory_type else {
+            return Err(crate::DeviceError::OutOfMemory);
+        };
+
+        self.error_if_would_oom_on_resource_allocation(false, desc.size)?;
+
+        let memory_allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(desc.size)
+            .memory_type_index(memory_type);
+        let raw = unsafe { self.shared.raw.allocate_memory(&memory_allocate_info, None) }
+            .map_err(super::map_host_device_oom_err)?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(raw, label) };
+        }
+
+        self.counters.texture_memory.add(desc.size as isize);
+
+        Ok(super::MemoryHeap {
+            raw,
+            memory_type,
+            size: desc.size,
+        })
+    }
+    unsafe fn destroy_memory_heap(&self, heap: super::MemoryHeap) {
+        unsafe { self.shared.raw.free_memory(heap.raw, None) };
+
+        self.counters.texture_memory.sub(heap.size as isize);
+    }
+
+    unsafe fn texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::TextureMemoryRequirements, crate::DeviceError> {
+        let image = self.create_image_without_memory(desc, None, None)?;
+        unsafe { self.shared.raw.destroy_image(image.raw, None) };
+
+        Ok(wgt::TextureMemoryRequirements {
+            size: image.requirements.size,
+            alignment: image.requirements.alignment,
+        })
+    }
+
+    unsafe fn create_placed_texture(
+        &self,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let image = self.create_image_without_memory(desc, None, None)?;
+
+        if image.requirements.memory_type_bits & (1 << heap.memory_type) == 0 {
+            log::error!(
+                "Texture {:?} can't be placed in memory type {}",
+                desc.label,
+                heap.memory_type
+            );
+            unsafe { self.shared.raw.destroy_image(image.raw, None) };
+            return Err(crate::DeviceError::OutOfMemory);
+        }
+
+        unsafe {
+            self.shared
+                .raw
+                .bind_image_memory(image.raw, heap.raw, offset)
+        }
+        .map_err(super::map_host_device_oom_err)
+        .inspect_err(|_| {
+            unsafe { self.shared.raw.destroy_image(image.raw, None) };
+        })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: None,
+            block: None,
+            placed: true,
+            format: desc.format,
+            copy_size: image.copy_size,
+        })
+    }
+
+    unsafe fn create_sparse_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let Some(memory_type) = self.placed_memory_type else {
+            return Err(crate::DeviceError::OutOfMemory);
+        };
+
+        let (sharing_mode, queue_family_indices) = self.shared.sharing_mode();
+        let vk_info = vk::BufferCreateInfo::default()
+            .flags(vk::BufferCreateFlags::SPARSE_BINDING | vk::BufferCreateFlags::SPARSE_RESIDENCY)
+            .size(desc.size)
+            .usage(self.shared.buffer_usage(desc.usage))
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(queue_family_indices);
+
+        let raw = unsafe {
+            self.shared
+                .raw
+                .create_buffer(&vk_info, None)
+                .map_err(super::map_host_device_oom_and_ioca_err)?
+        };
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+
+        // Pages are bound at multiples of `SPARSE_PAGE_SIZE`, from heaps of `placed_memory_type`.
+        if wgt::SPARSE_PAGE_SIZE % req.alignment != 0
+            || req.memory_type_bits & (1 << memory_type) == 0
+        {
+            log::error!(
+                "Sparse buffer {:?} can't be bound in pages of memory type {}",
+                desc.label,
+                memory_type
+            );
+            unsafe { self.shared.raw.destroy_buffer(raw, None) };
+            return Err(crate::DeviceError::OutOfMemory);
+        }
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(raw, label) };
+        }
+
+        self.counters.buffers.add(1);
+
+        Ok(super::Buffer { raw, block: None })
+    }
+
+    unsafe fn create_sparse_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let Some(memory_type) = self.placed_memory_type else {
+            return Err(crate::DeviceError::OutOfMemory);
+        };
+
+        let image = self.create_image_with_flags(
+            desc,
+            vk::ImageCreateFlags::SPARSE_BINDING | vk::ImageCreateFlags::SPARSE_RESIDENCY,
+            None,
+            None,
+        )?;
+
+        // Every mip level must be made of whole tiles of the standard block shape,
+        // with no mip tail that would have to be bound as a unit.
+        let sparse_requirements = unsafe {
+            self.shared
+                .raw
+                .get_image_sparse_memory_requirements(image.raw)
+        };
+        let tile_size = desc.format.sparse_tile_size();
+        let is_tileable = sparse_requirements.iter().any(|req| {
+            req.format_properties
+                .aspect_mask
+                .contains(vk::ImageAspectFlags::COLOR)
+                && req.image_mip_tail_first_lod >= desc.mip_level_count
+                && tile_size.is_some_and(|size| {
+                    let granularity = req.format_properties.image_granularity;
+                    granularity.width == size.width && granularity.height == size.height
+                })
+        });
+        if !is_tileable
+            || wgt::SPARSE_PAGE_SIZE % image.requirements.alignment != 0
+            || image.requirements.memory_type_bits & (1 << memory_type) == 0
+        {
+            log::error!(
+                "Sparse texture {:?} can't be bound in tiles of memory type {}",
+                desc.label,
+                memory_type
+            );
+            unsafe { self.shared.raw.destroy_image(image.raw, None) };
+            return Err(crate::DeviceError::OutOfMemory);
+        }
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        self.counters.textures.add(1);
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: None,
+            block: None,
+            placed: false,
+            format: desc.format,
+            copy_size: image.copy_size,
+        })
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &super::Texture,
@@ -1328,12 +2086,21 @@ impl crate::Device for super::Device {
             vk_info = vk_info.push_next(&mut image_view_info);
         }
 
-        let raw = unsafe { self.shared.raw.create_image_view(&vk_info, None) }
-            .map_err(super::map_host_device_oom_and_ioca_err)?;
+        // Views of all the planes of a multi-planar texture can only be used with a
+        // Y'CbCr conversion, and are created when they are bound.
+        let raw = if texture.format.is_multi_planar_format()
+            && desc.range.aspect == wgt::TextureAspect::All
+        {
+            vk::ImageView::null()
+        } else {
+            let raw = unsafe { self.shared.raw.create_image_view(&vk_info, None) }
+                .map_err(super::map_host_device_oom_and_ioca_err)?;
 
-        if let Some(label) = desc.label {
-            unsafe { self.shared.set_object_name(raw, label) };
-        }
+            if let Some(label) = desc.label {
+                unsafe { self.shared.set_object_name(raw, label) };
+            }
+            raw
+        };
 
         self.counters.texture_views.add(1);
 
@@ -1345,6 +2112,7 @@ impl crate::Device for super::Device {
             raw_format,
             base_mip_level: desc.range.base_mip_level,
             dimension: desc.dimension,
+            subresource_range,
         })
     }
     unsafe fn destroy_texture_view(&self, view: super::TextureView) {
@@ -1417,33 +2185,7 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::CommandEncoderDescriptor<super::Queue>,
     ) -> Result<super::CommandEncoder, crate::DeviceError> {
-        let vk_info = vk::CommandPoolCreateInfo::default()
-            .queue_family_index(desc.queue.family_index)
-            .flags(vk::CommandPoolCreateFlags::TRANSIENT);
-
-        let raw = unsafe {
-            self.shared
-                .raw
-                .create_command_pool(&vk_info, None)
-                .map_err(super::map_host_device_oom_err)?
-        };
-
-        self.counters.command_encoders.add(1);
-
-        Ok(super::CommandEncoder {
-            raw,
-            device: Arc::clone(&self.shared),
-            active: vk::CommandBuffer::null(),
-            bind_point: vk::PipelineBindPoint::default(),
-            temp: super::Temp::default(),
-            free: Vec::new(),
-            discarded: Vec::new(),
-            rpass_debug_marker_active: false,
-            end_of_pass_timer_query: None,
-            framebuffers: Default::default(),
-            temp_texture_views: Default::default(),
-            counters: Arc::clone(&self.counters),
-        })
+        unsafe { super::CommandEncoder::new(&self.shared, desc.queue.family_index, &self.counters) }
     }
 
     unsafe fn create_bind_group_layout(
@@ -1499,6 +2241,26 @@ impl crate::Device for super::Device {
                     desc_count.acceleration_structure += count;
                 }
                 wgt::BindingType::ExternalTexture => unimplemented!(),
+                wgt::BindingType::YcbcrTexture { conversion } => {
+                    // Implementations may use one descriptor per plane.
+                    desc_count.combined_image_sampler +=
+                        count * conversion.format.planes().unwrap_or(1);
+                }
+            }
+        }
+
+        let mut ycbcr_samplers = Vec::new();
+        for entry in desc.entries {
+            if let wgt::BindingType::YcbcrTexture { conversion } = entry.ty {
+                match unsafe { self.create_ycbcr_sampler(entry.binding, &conversion) } {
+                    Ok(sampler) => ycbcr_samplers.push(sampler),
+                    Err(err) => {
+                        for sampler in ycbcr_samplers {
+                            unsafe { self.destroy_ycbcr_sampler(sampler) };
+                        }
+                        return Err(err);
+                    }
+                }
             }
         }
 
@@ -1511,7 +2273,10 @@ impl crate::Device for super::Device {
                 descriptor_type: types[entry.binding as usize].0,
                 descriptor_count: types[entry.binding as usize].1,
                 stage_flags: conv::map_shader_stage(entry.visibility),
-                p_immutable_samplers: ptr::null(),
+                p_immutable_samplers: ycbcr_samplers
+                    .iter()
+                    .find(|sampler| sampler.binding == entry.binding)
+                    .map_or(ptr::null(), |sampler| ptr::from_ref(&sampler.raw)),
                 _marker: Default::default(),
             })
             .collect::<Vec<_>>();
@@ -1558,11 +2323,14 @@ impl crate::Device for super::Device {
 
         let vk_info = vk_info.push_next(&mut binding_flag_info);
 
-        let raw = unsafe {
-            self.shared
-                .raw
-                .create_descriptor_set_layout(&vk_info, None)
-                .map_err(super::map_host_device_oom_err)?
+        let raw = match unsafe { self.shared.raw.create_descriptor_set_layout(&vk_info, None) } {
+            Ok(raw) => raw,
+            Err(err) => {
+                for sampler in ycbcr_samplers {
+                    unsafe { self.destroy_ycbcr_sampler(sampler) };
+                }
+                return Err(super::map_host_device_oom_err(err));
+            }
         };
 
         if let Some(label) = desc.label {
@@ -1576,6 +2344,7 @@ impl crate::Device for super::Device {
             desc_count,
             types: types.into_boxed_slice(),
             binding_arrays,
+            ycbcr_samplers,
         })
     }
     unsafe fn destroy_bind_group_layout(&self, bg_layout: super::BindGroupLayout) {
@@ -1584,6 +2353,9 @@ impl crate::Device for super::Device {
                 .raw
                 .destroy_descriptor_set_layout(bg_layout.raw, None)
         };
+        for sampler in bg_layout.ycbcr_samplers {
+            unsafe { self.destroy_ycbcr_sampler(sampler) };
+        }
 
         self.counters.bind_group_layouts.sub(1);
     }
@@ -1627,16 +2399,29 @@ impl crate::Device for super::Device {
             unsafe { self.shared.set_object_name(raw, label) };
         }
 
-        let mut binding_arrays = BTreeMap::new();
+        let mut binding_map = BTreeMap::new();
         for (group, &layout) in desc.bind_group_layouts.iter().enumerate() {
             for &(binding, binding_array_size) in &layout.binding_arrays {
-                binding_arrays.insert(
+                binding_map.insert(
                     naga::ResourceBinding {
                         group: group as u32,
                         binding,
                     },
                     naga::back::spv::BindingInfo {
                         binding_array_size: Some(binding_array_size.get()),
+                        ..Default::default()
+                    },
+                );
+            }
+            for sampler in &layout.ycbcr_samplers {
+                binding_map.insert(
+                    naga::ResourceBinding {
+                        group: group as u32,
+                        binding: sampler.binding,
+                    },
+                    naga::back::spv::BindingInfo {
+                        combined_sampler: true,
+                        ..Default::default()
                     },
                 );
             }
@@ -1644,10 +2429,7 @@ impl crate::Device for super::Device {
 
         self.counters.pipeline_layouts.add(1);
 
-        Ok(super::PipelineLayout {
-            raw,
-            binding_arrays,
-        })
+        Ok(super::PipelineLayout { raw, binding_map })
     }
     unsafe fn destroy_pipeline_layout(&self, pipeline_layout: super::PipelineLayout) {
         unsafe {
@@ -1669,6 +2451,29 @@ impl crate::Device for super::Device {
             super::AccelerationStructure,
         >,
     ) -> Result<super::BindGroup, crate::DeviceError> {
+        // Images sampled with a Y'CbCr conversion need views created with the same conversion.
+        let mut ycbcr_views = Vec::with_capacity(desc.layout.ycbcr_samplers.len());
+        for entry in desc.entries {
+            let Some(sampler) = desc
+                .layout
+                .ycbcr_samplers
+                .iter()
+                .find(|sampler| sampler.binding == entry.binding)
+            else {
+                continue;
+            };
+            let view = desc.textures[entry.resource_index as usize].view;
+            match unsafe { self.create_ycbcr_view(view, sampler.conversion) } {
+                Ok(raw) => ycbcr_views.push(raw),
+                Err(err) => {
+                    for raw in ycbcr_views {
+                        unsafe { self.shared.raw.destroy_image_view(raw, None) };
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
         let contains_binding_arrays = !desc.layout.binding_arrays.is_empty();
 
         let desc_set_layout_flags = if contains_binding_arrays {
@@ -1677,14 +2482,22 @@ impl crate::Device for super::Device {
             gpu_descriptor::DescriptorSetLayoutCreateFlags::empty()
         };
 
-        let mut vk_sets = unsafe {
+        let mut vk_sets = match unsafe {
             self.desc_allocator.lock().allocate(
                 &*self.shared,
                 &desc.layout.raw,
                 desc_set_layout_flags,
                 &desc.layout.desc_count,
                 1,
-            )?
+            )
+        } {
+            Ok(sets) => sets,
+            Err(err) => {
+                for raw in ycbcr_views {
+                    unsafe { self.shared.raw.destroy_image_view(raw, None) };
+                }
+                return Err(err.into());
+            }
         };
 
         let set = vk_sets.pop().unwrap();
@@ -1757,6 +2570,7 @@ impl crate::Device for super::Device {
             Vec::with_capacity(desc.acceleration_structures.len());
         let mut raw_acceleration_structures =
             ExtendStack::from_vec_capacity(&mut raw_acceleration_structures);
+        let mut ycbcr_view_iter = ycbcr_views.iter();
         for entry in desc.entries {
             let (ty, size) = desc.layout.types[entry.binding as usize];
             if size == 0 {
@@ -1794,6 +2608,18 @@ impl crate::Device for super::Device {
                         ));
                     write.image_info(local_image_infos)
                 }
+                vk::DescriptorType::COMBINED_IMAGE_SAMPLER => {
+                    // The sampler is immutable, so only the view is written.
+                    let binding = &desc.textures[entry.resource_index as usize];
+                    let layout = conv::derive_image_layout(binding.usage, binding.view.format);
+                    let local_image_info;
+                    (image_infos, local_image_info) = image_infos.extend_one(
+                        vk::DescriptorImageInfo::default()
+                            .image_view(*ycbcr_view_iter.next().unwrap())
+                            .image_layout(layout),
+                    );
+                    write.image_info(slice::from_ref(local_image_info))
+                }
                 vk::DescriptorType::UNIFORM_BUFFER
                 | vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC
                 | vk::DescriptorType::STORAGE_BUFFER
@@ -1851,7 +2677,7 @@ impl crate::Device for super::Device {
 
         self.counters.bind_groups.add(1);
 
-        Ok(super::BindGroup { set })
+        Ok(super::BindGroup { set, ycbcr_views })
     }
 
     unsafe fn destroy_bind_group(&self, group: super::BindGroup) {
@@ -1860,6 +2686,9 @@ impl crate::Device for super::Device {
                 .lock()
                 .free(&*self.shared, Some(group.set))
         };
+        for raw in group.ycbcr_views {
+            unsafe { self.shared.raw.destroy_image_view(raw, None) };
+        }
 
         self.counters.bind_groups.sub(1);
     }
@@ -1876,10 +2705,12 @@ impl crate::Device for super::Device {
                     .workarounds
                     .contains(super::Workarounds::SEPARATE_ENTRY_POINTS)
                     || !naga_shader.module.overrides.is_empty()
+                    || self.may_bind_ycbcr_texture(&naga_shader.module)
                 {
                     return Ok(super::ShaderModule::Intermediate {
                         naga_shader,
                         runtime_checks: desc.runtime_checks,
+                        optimization: desc.optimization,
                     });
                 }
                 let mut naga_options = self.naga_options.clone();
@@ -1900,15 +2731,14 @@ impl crate::Device for super::Device {
                         binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
                     };
                 }
-                Cow::Owned(
-                    naga::back::spv::write_vec(
-                        &naga_shader.module,
-                        &naga_shader.info,
-                        &naga_options,
-                        None,
-                    )
-                    .map_err(|e| crate::ShaderError::Compilation(format!("{e}")))?,
+                let spv = naga::back::spv::write_vec(
+                    &naga_shader.module,
+                    &naga_shader.info,
+                    &naga_options,
+                    None,
                 )
+                .map_err(|e| crate::ShaderError::Compilation(format!("{e}")))?;
+                Cow::Owned(self.optimize_spv(spv, desc.optimization))
             }
             crate::ShaderInput::Msl { .. } => {
                 panic!("MSL_SHADER_PASSTHROUGH is not enabled for this backend")
@@ -1949,12 +2779,19 @@ impl crate::Device for super::Device {
             super::PipelineCache,
         >,
     ) -> Result<super::RenderPipeline, crate::PipelineError> {
-        let dynamic_states = [
+        let mut dynamic_states = ArrayVec::<_, 5>::from_iter([
             vk::DynamicState::VIEWPORT,
             vk::DynamicState::SCISSOR,
             vk::DynamicState::BLEND_CONSTANTS,
             vk::DynamicState::STENCIL_REFERENCE,
-        ];
+        ]);
+        if self
+            .shared
+            .features
+            .contains(wgt::Features::VARIABLE_RATE_SHADING)
+        {
+            dynamic_states.push(vk::DynamicState::FRAGMENT_SHADING_RATE_KHR);
+        }
         let mut compatible_rp_key = super::RenderPassKey {
             sample_count: desc.multisample.count,
             multiview: desc.multiview,
@@ -1962,6 +2799,7 @@ impl crate::Device for super::Device {
         };
         let mut stages = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
         let mut vertex_buffers = Vec::with_capacity(desc.vertex_buffers.len());
+        let mut vertex_divisors = Vec::new();
         let mut vertex_attributes = Vec::new();
 
         for (i, vb) in desc.vertex_buffers.iter().enumerate() {
@@ -1970,9 +2808,18 @@ impl crate::Device for super::Device {
                 stride: vb.array_stride as u32,
                 input_rate: match vb.step_mode {
                     wgt::VertexStepMode::Vertex => vk::VertexInputRate::VERTEX,
-                    wgt::VertexStepMode::Instance => vk::VertexInputRate::INSTANCE,
+                    wgt::VertexStepMode::Instance
+                    | wgt::VertexStepMode::InstanceStepRate { .. } => vk::VertexInputRate::INSTANCE,
                 },
             });
+            if let wgt::VertexStepMode::InstanceStepRate { rate } = vb.step_mode {
+                if rate != 1 {
+                    vertex_divisors.push(vk::VertexInputBindingDivisorDescriptionEXT {
+                        binding: i as u32,
+                        divisor: rate,
+                    });
+                }
+            }
             for at in vb.attributes {
                 vertex_attributes.push(vk::VertexInputAttributeDescription {
                     location: at.shader_location,
@@ -1983,18 +2830,25 @@ impl crate::Device for super::Device {
             }
         }
 
-        let vk_vertex_input = vk::PipelineVertexInputStateCreateInfo::default()
+        let mut vk_vertex_divisor = vk::PipelineVertexInputDivisorStateCreateInfoEXT::default()
+            .vertex_binding_divisors(&vertex_divisors);
+        let mut vk_vertex_input = vk::PipelineVertexInputStateCreateInfo::default()
             .vertex_binding_descriptions(&vertex_buffers)
             .vertex_attribute_descriptions(&vertex_attributes);
+        if !vertex_divisors.is_empty() {
+            vk_vertex_input = vk_vertex_input.push_next(&mut vk_vertex_divisor);
+        }
 
         let vk_input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
             .topology(conv::map_topology(desc.primitive.topology))
-            .primitive_restart_enable(desc.primitive.strip_index_format.is_some());
+            .primitive_restart_enable(
+                desc.primitive.strip_index_format.is_some() && desc.primitive.primitive_restart,
+            );
 
         let compiled_vs = self.compile_stage(
             &desc.vertex_stage,
             naga::ShaderStage::Vertex,
-            &desc.layout.binding_arrays,
+            &desc.layout.binding_map,
         )?;
         stages.push(compiled_vs.create_info);
         let compiled_fs = match desc.fragment_stage {
@@ -2002,7 +2856,7 @@ impl crate::Device for super::Device {
                 let compiled = self.compile_stage(
                     stage,
                     naga::ShaderStage::Fragment,
-                    &desc.layout.binding_arrays,
+                    &desc.layout.binding_map,
                 )?;
                 stages.push(compiled.create_info);
                 Some(compiled)
@@ -2013,7 +2867,7 @@ impl crate::Device for super::Device {
         let mut vk_rasterization = vk::PipelineRasterizationStateCreateInfo::default()
             .polygon_mode(conv::map_polygon_mode(desc.primitive.polygon_mode))
             .front_face(conv::map_front_face(desc.primitive.front_face))
-            .line_width(1.0)
+            .line_width(desc.primitive.line_width)
             .depth_clamp_enable(desc.primitive.unclipped_depth);
         if let Some(face) = desc.primitive.cull_mode {
             vk_rasterization = vk_rasterization.cull_mode(conv::map_cull_face(face))
@@ -2026,6 +2880,16 @@ impl crate::Device for super::Device {
         if desc.primitive.conservative {
             vk_rasterization = vk_rasterization.push_next(&mut vk_rasterization_conservative_state);
         }
+        let mut vk_rasterization_line_state = desc.primitive.line_stipple.map(|stipple| {
+            vk::PipelineRasterizationLineStateCreateInfoEXT::default()
+                .line_rasterization_mode(vk::LineRasterizationModeEXT::BRESENHAM)
+                .stippled_line_enable(true)
+                .line_stipple_factor(stipple.factor)
+                .line_stipple_pattern(stipple.pattern)
+        });
+        if let Some(ref mut line_state) = vk_rasterization_line_state {
+            vk_rasterization = vk_rasterization.push_next(line_state);
+        }
 
         let mut vk_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default();
         if let Some(ref ds) = desc.depth_stencil {
@@ -2038,6 +2902,7 @@ impl crate::Device for super::Device {
             compatible_rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
                 base: super::AttachmentKey::compatible(vk_format, vk_layout),
                 stencil_ops: crate::AttachmentOps::all(),
+                resolve: None,
             });
 
             if ds.is_depth_enabled() {
@@ -2065,10 +2930,11 @@ impl crate::Device for super::Device {
             }
         }
 
+        let viewport_count = self.shared.viewport_count();
         let vk_viewport = vk::PipelineViewportStateCreateInfo::default()
             .flags(vk::PipelineViewportStateCreateFlags::empty())
-            .scissor_count(1)
-            .viewport_count(1);
+            .scissor_count(viewport_count)
+            .viewport_count(viewport_count);
 
         let vk_sample_mask = [
             desc.multisample.mask as u32,
@@ -2116,16 +2982,31 @@ impl crate::Device for super::Device {
             vk_attachments.push(attarchment);
         }
 
-        let vk_color_blend =
+        let mut vk_color_blend =
             vk::PipelineColorBlendStateCreateInfo::default().attachments(&vk_attachments);
+        // wgpu-core checks that every color target uses the same logic operation.
+        if let Some(logic_op) = desc
+            .color_targets
+            .iter()
+            .flatten()
+            .find_map(|cat| cat.logic_op)
+        {
+            vk_color_blend = vk_color_blend
+                .logic_op_enable(true)
+                .logic_op(conv::map_logic_op(logic_op));
+        }
 
         let vk_dynamic_state =
             vk::PipelineDynamicStateCreateInfo::default().dynamic_states(&dynamic_states);
 
-        let raw_pass = self.shared.make_render_pass(compatible_rp_key)?;
+        let (raw_pass, rendering_formats) = self.shared.make_pipeline_render_pass(
+            compatible_rp_key,
+            desc.depth_stencil.as_ref().map(|ds| ds.format),
+        )?;
+        let mut vk_rendering = rendering_formats.as_ref().map(RenderingFormats::to_vk);
 
         let vk_infos = [{
-            vk::GraphicsPipelineCreateInfo::default()
+            let mut info = vk::GraphicsPipelineCreateInfo::default()
                 .layout(desc.layout.raw)
                 .stages(&stages)
                 .vertex_input_state(&vk_vertex_input)
@@ -2136,7 +3017,11 @@ impl crate::Device for super::Device {
                 .depth_stencil_state(&vk_depth_stencil)
                 .color_blend_state(&vk_color_blend)
                 .dynamic_state(&vk_dynamic_state)
-                .render_pass(raw_pass)
+                .render_pass(raw_pass);
+            if let Some(ref mut vk_rendering) = vk_rendering {
+                info = info.push_next(vk_rendering);
+            }
+            info
         }];
 
         let pipeline_cache = desc
@@ -2182,12 +3067,19 @@ impl crate::Device for super::Device {
             <Self::A as crate::Api>::PipelineCache,
         >,
     ) -> Result<<Self::A as crate::Api>::RenderPipeline, crate::PipelineError> {
-        let dynamic_states = [
+        let mut dynamic_states = ArrayVec::<_, 5>::from_iter([
             vk::DynamicState::VIEWPORT,
             vk::DynamicState::SCISSOR,
             vk::DynamicState::BLEND_CONSTANTS,
             vk::DynamicState::STENCIL_REFERENCE,
-        ];
+        ]);
+        if self
+            .shared
+            .features
+            .contains(wgt::Features::VARIABLE_RATE_SHADING)
+        {
+            dynamic_states.push(vk::DynamicState::FRAGMENT_SHADING_RATE_KHR);
+        }
         let mut compatible_rp_key = super::RenderPassKey {
             sample_count: desc.multisample.count,
             multiview: desc.multiview,
@@ -2197,15 +3089,14 @@ impl crate::Device for super::Device {
 
         let vk_input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
             .topology(conv::map_topology(desc.primitive.topology))
-            .primitive_restart_enable(desc.primitive.strip_index_format.is_some());
+            .primitive_restart_enable(
+                desc.primitive.strip_index_format.is_some() && desc.primitive.primitive_restart,
+            );
 
         let compiled_ts = match desc.task_stage {
             Some(ref stage) => {
-                let mut compiled = self.compile_stage(
-                    stage,
-                    naga::ShaderStage::Task,
-                    &desc.layout.binding_arrays,
-                )?;
+                let mut compiled =
+                    self.compile_stage(stage, naga::ShaderStage::Task, &desc.layout.binding_map)?;
                 compiled.create_info.stage = vk::ShaderStageFlags::TASK_EXT;
                 stages.push(compiled.create_info);
                 Some(compiled)
@@ -2216,7 +3107,7 @@ impl crate::Device for super::Device {
         let mut compiled_ms = self.compile_stage(
             &desc.mesh_stage,
             naga::ShaderStage::Mesh,
-            &desc.layout.binding_arrays,
+            &desc.layout.binding_map,
         )?;
         compiled_ms.create_info.stage = vk::ShaderStageFlags::MESH_EXT;
         stages.push(compiled_ms.create_info);
@@ -2225,7 +3116,7 @@ impl crate::Device for super::Device {
                 let compiled = self.compile_stage(
                     stage,
                     naga::ShaderStage::Fragment,
-                    &desc.layout.binding_arrays,
+                    &desc.layout.binding_map,
                 )?;
                 stages.push(compiled.create_info);
                 Some(compiled)
@@ -2236,7 +3127,7 @@ impl crate::Device for super::Device {
         let mut vk_rasterization = vk::PipelineRasterizationStateCreateInfo::default()
             .polygon_mode(conv::map_polygon_mode(desc.primitive.polygon_mode))
             .front_face(conv::map_front_face(desc.primitive.front_face))
-            .line_width(1.0)
+            .line_width(desc.primitive.line_width)
             .depth_clamp_enable(desc.primitive.unclipped_depth);
         if let Some(face) = desc.primitive.cull_mode {
             vk_rasterization = vk_rasterization.cull_mode(conv::map_cull_face(face))
@@ -2249,6 +3140,16 @@ impl crate::Device for super::Device {
         if desc.primitive.conservative {
             vk_rasterization = vk_rasterization.push_next(&mut vk_rasterization_conservative_state);
         }
+        let mut vk_rasterization_line_state = desc.primitive.line_stipple.map(|stipple| {
+            vk::PipelineRasterizationLineStateCreateInfoEXT::default()
+                .line_rasterization_mode(vk::LineRasterizationModeEXT::BRESENHAM)
+                .stippled_line_enable(true)
+                .line_stipple_factor(stipple.factor)
+                .line_stipple_pattern(stipple.pattern)
+        });
+        if let Some(ref mut line_state) = vk_rasterization_line_state {
+            vk_rasterization = vk_rasterization.push_next(line_state);
+        }
 
         let mut vk_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default();
         if let Some(ref ds) = desc.depth_stencil {
@@ -2261,6 +3162,7 @@ impl crate::Device for super::Device {
             compatible_rp_key.depth_stencil = Some(super::DepthStencilAttachmentKey {
                 base: super::AttachmentKey::compatible(vk_format, vk_layout),
                 stencil_ops: crate::AttachmentOps::all(),
+                resolve: None,
             });
 
             if ds.is_depth_enabled() {
@@ -2288,10 +3190,11 @@ impl crate::Device for super::Device {
             }
         }
 
+        let viewport_count = self.shared.viewport_count();
         let vk_viewport = vk::PipelineViewportStateCreateInfo::default()
             .flags(vk::PipelineViewportStateCreateFlags::empty())
-            .scissor_count(1)
-            .viewport_count(1);
+            .scissor_count(viewport_count)
+            .viewport_count(viewport_count);
 
         let vk_sample_mask = [
             desc.multisample.mask as u32,
@@ -2339,16 +3242,31 @@ impl crate::Device for super::Device {
             vk_attachments.push(attarchment);
         }
 
-        let vk_color_blend =
+        let mut vk_color_blend =
             vk::PipelineColorBlendStateCreateInfo::default().attachments(&vk_attachments);
+        // wgpu-core checks that every color target uses the same logic operation.
+        if let Some(logic_op) = desc
+            .color_targets
+            .iter()
+            .flatten()
+            .find_map(|cat| cat.logic_op)
+        {
+            vk_color_blend = vk_color_blend
+                .logic_op_enable(true)
+                .logic_op(conv::map_logic_op(logic_op));
+        }
 
         let vk_dynamic_state =
             vk::PipelineDynamicStateCreateInfo::default().dynamic_states(&dynamic_states);
 
-        let raw_pass = self.shared.make_render_pass(compatible_rp_key)?;
+        let (raw_pass, rendering_formats) = self.shared.make_pipeline_render_pass(
+            compatible_rp_key,
+            desc.depth_stencil.as_ref().map(|ds| ds.format),
+        )?;
+        let mut vk_rendering = rendering_formats.as_ref().map(RenderingFormats::to_vk);
 
         let vk_infos = [{
-            vk::GraphicsPipelineCreateInfo::default()
+            let mut info = vk::GraphicsPipelineCreateInfo::default()
                 .layout(desc.layout.raw)
                 .stages(&stages)
                 .input_assembly_state(&vk_input_assembly)
@@ -2358,7 +3276,11 @@ impl crate::Device for super::Device {
                 .depth_stencil_state(&vk_depth_stencil)
                 .color_blend_state(&vk_color_blend)
                 .dynamic_state(&vk_dynamic_state)
-                .render_pass(raw_pass)
+                .render_pass(raw_pass);
+            if let Some(ref mut vk_rendering) = vk_rendering {
+                info = info.push_next(vk_rendering);
+            }
+            info
         }];
 
         let pipeline_cache = desc
@@ -2421,7 +3343,7 @@ impl crate::Device for super::Device {
         let compiled = self.compile_stage(
             &desc.stage,
             naga::ShaderStage::Compute,
-            &desc.layout.binding_arrays,
+            &desc.layout.binding_map,
         )?;
 
         let vk_infos = [{
@@ -2465,6 +3387,151 @@ impl crate::Device for super::Device {
         self.counters.compute_pipelines.sub(1);
     }
 
+    unsafe fn create_ray_tracing_pipeline(
+        &self,
+        desc: &crate::RayTracingPipelineDescriptor<
+            super::PipelineLayout,
+            super::ShaderModule,
+            super::PipelineCache,
+        >,
+    ) -> Result<super::RayTracingPipeline, crate::PipelineError> {
+        let compiled_stages = desc
+            .stages
+            .iter()
+            .map(|stage| {
+                let naga_stage = match stage.stage {
+                    wgt::ShaderStages::RAY_GENERATION => naga::ShaderStage::RayGeneration,
+                    wgt::ShaderStages::MISS => naga::ShaderStage::Miss,
+                    wgt::ShaderStages::CLOSEST_HIT => naga::ShaderStage::ClosestHit,
+                    wgt::ShaderStages::ANY_HIT => naga::ShaderStage::AnyHit,
+                    // Naga has no intersection or callable stages, so their modules
+                    // are always passthrough SPIR-V.
+                    _ => return self.passthrough_ray_tracing_stage(stage),
+                };
+                self.compile_stage(&stage.programmable, naga_stage, &desc.layout.binding_map)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let vk_stages = compiled_stages
+            .iter()
+            .map(|stage| stage.create_info)
+            .collect::<Vec<_>>();
+
+        let vk_groups = desc
+            .groups
+            .iter()
+            .map(|group| {
+                let info = vk::RayTracingShaderGroupCreateInfoKHR::default()
+                    .general_shader(vk::SHADER_UNUSED_KHR)
+                    .closest_hit_shader(vk::SHADER_UNUSED_KHR)
+                    .any_hit_shader(vk::SHADER_UNUSED_KHR)
+                    .intersection_shader(vk::SHADER_UNUSED_KHR);
+                match *group {
+                    wgt::RayTracingShaderGroup::General { stage } => info
+                        .ty(vk::RayTracingShaderGroupTypeKHR::GENERAL)
+                        .general_shader(stage),
+                    wgt::RayTracingShaderGroup::TrianglesHit {
+                        closest_hit,
+                        any_hit,
+                    } => info
+                        .ty(vk::RayTracingShaderGroupTypeKHR::TRIANGLES_HIT_GROUP)
+                        .closest_hit_shader(closest_hit.unwrap_or(vk::SHADER_UNUSED_KHR))
+                        .any_hit_shader(any_hit.unwrap_or(vk::SHADER_UNUSED_KHR)),
+                    wgt::RayTracingShaderGroup::ProceduralHit {
+                        closest_hit,
+                        any_hit,
+                        intersection,
+                    } => info
+                        .ty(vk::RayTracingShaderGroupTypeKHR::PROCEDURAL_HIT_GROUP)
+                        .closest_hit_shader(closest_hit.unwrap_or(vk::SHADER_UNUSED_KHR))
+                        .any_hit_shader(any_hit.unwrap_or(vk::SHADER_UNUSED_KHR))
+                        .intersection_shader(intersection),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let vk_infos = [vk::RayTracingPipelineCreateInfoKHR::default()
+            .layout(desc.layout.raw)
+            .stages(&vk_stages)
+            .groups(&vk_groups)
+            .max_pipeline_ray_recursion_depth(desc.max_recursion_depth)];
+
+        let pipeline_cache = desc
+            .cache
+            .map(|it| it.raw)
+            .unwrap_or(vk::PipelineCache::null());
+
+        let ray_tracing_pipeline = self
+            .shared
+            .extension_fns
+            .ray_tracing_pipeline
+            .as_ref()
+            .expect("Feature `EXPERIMENTAL_RAY_TRACING_PIPELINE` not enabled");
+
+        let mut raw_vec = {
+            profiling::scope!("vkCreateRayTracingPipelinesKHR");
+            unsafe {
+                ray_tracing_pipeline
+                    .create_ray_tracing_pipelines(
+                        vk::DeferredOperationKHR::null(),
+                        pipeline_cache,
+                        &vk_infos,
+                        None,
+                    )
+                    .map_err(|(_, e)| super::map_pipeline_err(e))
+            }?
+        };
+
+        let raw = raw_vec.pop().unwrap();
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(raw, label) };
+        }
+
+        for raw_module in compiled_stages
+            .iter()
+            .filter_map(|stage| stage.temp_raw_module)
+        {
+            unsafe { self.shared.raw.destroy_shader_module(raw_module, None) };
+        }
+
+        self.counters.ray_tracing_pipelines.add(1);
+
+        Ok(super::RayTracingPipeline { raw })
+    }
+
+    unsafe fn destroy_ray_tracing_pipeline(&self, pipeline: super::RayTracingPipeline) {
+        unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
+
+        self.counters.ray_tracing_pipelines.sub(1);
+    }
+
+    unsafe fn get_ray_tracing_shader_group_handles(
+        &self,
+        pipeline: &super::RayTracingPipeline,
+        first_group: u32,
+        group_count: u32,
+    ) -> Result<Vec<u8>, crate::DeviceError> {
+        let ray_tracing_pipeline = self
+            .shared
+            .extension_fns
+            .ray_tracing_pipeline
+            .as_ref()
+            .expect("Feature `EXPERIMENTAL_RAY_TRACING_PIPELINE` not enabled");
+        let handle_size = self
+            .shared
+            .private_caps
+            .ray_tracing_shader_group_handle_size;
+
+        unsafe {
+            ray_tracing_pipeline.get_ray_tracing_shader_group_handles(
+                pipeline.raw,
+                first_group,
+                group_count,
+                (group_count * handle_size) as usize,
+            )
+        }
+        .map_err(super::map_host_device_oom_err)
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         desc: &crate::PipelineCacheDescriptor<'_>,
@@ -2534,12 +3601,7 @@ impl crate::Device for super::Device {
         self.counters.fences.add(1);
 
         Ok(if self.shared.private_caps.timeline_semaphores {
-            let mut sem_type_info =
-                vk::SemaphoreTypeCreateInfo::default().semaphore_type(vk::SemaphoreType::TIMELINE);
-            let vk_info = vk::SemaphoreCreateInfo::default().push_next(&mut sem_type_info);
-            let raw = unsafe { self.shared.raw.create_semaphore(&vk_info, None) }
-                .map_err(super::map_host_device_oom_err)?;
-
+            let raw = unsafe { self.create_timeline_semaphore(None) }?;
             super::Fence::TimelineSemaphore(raw)
         } else {
             super::Fence::FencePool {
@@ -2589,6 +3651,112 @@ impl crate::Device for super::Device {
         self.shared.wait_for_fence(fence, wait_value, timeout_ns)
     }
 
+    unsafe fn create_shared_fence(&self) -> Result<super::Fence, crate::DeviceError> {
+        let mut export_info =
+            vk::ExportSemaphoreCreateInfo::default().handle_types(super::SHARED_FENCE_HANDLE_TYPE);
+        let raw = unsafe { self.create_timeline_semaphore(Some(&mut export_info)) }?;
+
+        self.counters.fences.add(1);
+
+        Ok(super::Fence::TimelineSemaphore(raw))
+    }
+
+    unsafe fn export_fence(
+        &self,
+        fence: &super::Fence,
+    ) -> Result<wgt::SharedFenceHandle, crate::DeviceError> {
+        let super::Fence::TimelineSemaphore(raw) = *fence else {
+            crate::hal_usage_error("only timeline semaphores can be exported")
+        };
+
+        // VK_ERROR_TOO_MANY_OBJECTS is reported as unexpected.
+        #[cfg(windows)]
+        {
+            let info = vk::SemaphoreGetWin32HandleInfoKHR::default()
+                .semaphore(raw)
+                .handle_type(super::SHARED_FENCE_HANDLE_TYPE);
+            let handle = unsafe {
+                self.shared
+                    .extension_fns
+                    .external_semaphore_win32
+                    .as_ref()
+                    .unwrap()
+                    .get_semaphore_win32_handle(&info)
+            }
+            .map_err(super::map_host_oom_err)?;
+            Ok(wgt::SharedFenceHandle::VulkanOpaqueWin32(handle as _))
+        }
+        #[cfg(not(windows))]
+        {
+            let info = vk::SemaphoreGetFdInfoKHR::default()
+                .semaphore(raw)
+                .handle_type(super::SHARED_FENCE_HANDLE_TYPE);
+            let fd = unsafe {
+                self.shared
+                    .extension_fns
+                    .external_semaphore_fd
+                    .as_ref()
+                    .unwrap()
+                    .get_semaphore_fd(&info)
+            }
+            .map_err(super::map_host_oom_err)?;
+            Ok(wgt::SharedFenceHandle::VulkanOpaqueFd(fd))
+        }
+    }
+
+    unsafe fn import_fence(
+        &self,
+        handle: wgt::SharedFenceHandle,
+    ) -> Result<super::Fence, crate::DeviceError> {
+        let raw = unsafe { self.create_timeline_semaphore(None) }?;
+
+        // The handle is valid, so VK_ERROR_INVALID_EXTERNAL_HANDLE can't happen.
+        #[cfg(windows)]
+        let result = {
+            let wgt::SharedFenceHandle::VulkanOpaqueWin32(handle) = handle else {
+                crate::hal_usage_error("Vulkan devices can only import opaque Win32 handles")
+            };
+            let info = vk::ImportSemaphoreWin32HandleInfoKHR::default()
+                .semaphore(raw)
+                .handle_type(super::SHARED_FENCE_HANDLE_TYPE)
+                .handle(handle as _);
+            unsafe {
+                self.shared
+                    .extension_fns
+                    .external_semaphore_win32
+                    .as_ref()
+                    .unwrap()
+                    .import_semaphore_win32_handle(&info)
+            }
+        };
+        #[cfg(not(windows))]
+        let result = {
+            let wgt::SharedFenceHandle::VulkanOpaqueFd(fd) = handle else {
+                crate::hal_usage_error("Vulkan devices can only import opaque file descriptors")
+            };
+            let info = vk::ImportSemaphoreFdInfoKHR::default()
+                .semaphore(raw)
+                .handle_type(super::SHARED_FENCE_HANDLE_TYPE)
+                .fd(fd);
+            unsafe {
+                self.shared
+                    .extension_fns
+                    .external_semaphore_fd
+                    .as_ref()
+                    .unwrap()
+                    .import_semaphore_fd(&info)
+            }
+        };
+        if let Err(err) = result {
+            unsafe { self.shared.raw.destroy_semaphore(raw, None) };
+            return Err(super::map_host_oom_err(err));
+        }
+
+        self.counters.fences.add(1);
+
+        Ok(super::Fence::TimelineSemaphore(raw))
+    }
+
     unsafe fn start_graphics_debugger_capture(&self) -> bool {
         #[cfg(feature = "renderdoc")]
         {
@@ -2808,13 +3976,15 @@ impl crate::Device for super::Device {
             .as_ref()
             .expect("Feature `RAY_TRACING` not enabled");
 
+        let (sharing_mode, queue_family_indices) = self.shared.sharing_mode();
         let vk_buffer_info = vk::BufferCreateInfo::default()
             .size(desc.size)
             .usage(
                 vk::BufferUsageFlags::ACCELERATION_STRUCTURE_STORAGE_KHR
                     | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
             )
-            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(queue_family_indices);
 
         unsafe {
             let raw_buffer = self
@@ -2876,17 +4046,36 @@ impl crate::Device for super::Device {
                     .set_object_name(raw_acceleration_structure, label);
             }
 
-            let pool = if desc.allow_compaction {
+            let create_pool = |query_type| {
                 let vk_info = vk::QueryPoolCreateInfo::default()
-                    .query_type(vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR)
+                    .query_type(query_type)
                     .query_count(1);
 
-                let raw = self
-                    .shared
+                self.shared
                     .raw
                     .create_query_pool(&vk_info, None)
                     .map_err(super::map_host_device_oom_err)
+            };
+
+            let pool = if desc.allow_compaction {
+                let raw = create_pool(vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR)
+                    .inspect_err(|_| {
+                        ray_tracing_functions
+                            .acceleration_structure
+                            .destroy_acceleration_structure(raw_acceleration_structure, None);
+                        self.shared.raw.destroy_buffer(raw_buffer, None);
+                    })?;
+                Some(raw)
+            } else {
+                None
+            };
+
+            let serialized_pool = if desc.allow_serialization {
+                let raw = create_pool(vk::QueryType::ACCELERATION_STRUCTURE_SERIALIZATION_SIZE_KHR)
                     .inspect_err(|_| {
+                        if let Some(pool) = pool {
+                            self.shared.raw.destroy_query_pool(pool, None);
+                        }
                         ray_tracing_functions
                             .acceleration_structure
                             .destroy_acceleration_structure(raw_acceleration_structure, None);
@@ -2902,6 +4091,7 @@ impl crate::Device for super::Device {
                 buffer: raw_buffer,
                 block: Mutex::new(block),
                 compacted_size_query: pool,
+                serialized_size_query: serialized_pool,
             })
         }
     }
@@ -2930,7 +4120,37 @@ impl crate::Device for super::Device {
             if let Some(query) = acceleration_structure.compacted_size_query {
                 self.shared.raw.destroy_query_pool(query, None)
             }
+            if let Some(query) = acceleration_structure.serialized_size_query {
+                self.shared.raw.destroy_query_pool(query, None)
+            }
+        }
+    }
+
+    unsafe fn get_deserialized_acceleration_structure_size(
+        &self,
+        data: &[u8],
+    ) -> Option<wgt::BufferAddress> {
+        let ray_tracing_functions = self
+            .shared
+            .extension_fns
+            .ray_tracing
+            .as_ref()
+            .expect("Feature `RAY_TRACING` not enabled");
+
+        let (identifier, deserialized_size) =
+            crate::auxil::parse_serialized_acceleration_structure_header(data)?;
+        let version_info =
+            vk::AccelerationStructureVersionInfoKHR::default().version_data(identifier);
+        let compatibility = unsafe {
+            ray_tracing_functions
+                .acceleration_structure
+                .get_device_acceleration_structure_compatibility(&version_info)
+        };
+        if compatibility != vk::AccelerationStructureCompatibilityKHR::COMPATIBLE {
+            return None;
         }
+
+        Some(deserialized_size)
     }
 
     fn get_internal_counters(&self) -> wgt::HalCounters {
@@ -2941,6 +4161,18 @@ impl crate::Device for super::Device {
         self.counters.as_ref().clone()
     }
 
+    fn trim_memory(&self) -> u32 {
+        let mut mem_allocator = self.mem_allocator.lock();
+        let remaining_before = mem_allocator.remaining_allocations();
+        unsafe { mem_allocator.cleanup(&*self.shared) };
+        let freed_blocks = mem_allocator.remaining_allocations() - remaining_before;
+        drop(mem_allocator);
+
+        unsafe { self.desc_allocator.lock().cleanup(&*self.shared) };
+
+        freed_blocks
+    }
+
     fn tlas_instance_to_bytes(&self, instance: TlasInstance) -> Vec<u8> {
         const MAX_U24: u32 = (1u32 << 24u32) - 1u32;
         let temp = RawTlasInstance {
@@ -3003,6 +4235,83 @@ impl crate::Device for super::Device {
 
         Ok(())
     }
+
+    fn fault_info(&self) -> Option<String> {
+        use core::fmt::Write as _;
+
+        let device_fault = self.shared.extension_fns.device_fault.as_ref()?;
+        let get_device_fault_info = device_fault.fp().get_device_fault_info_ext;
+        let raw_device = self.shared.raw.handle();
+
+        let mut counts = vk::DeviceFaultCountsEXT::default();
+        unsafe { get_device_fault_info(raw_device, &mut counts, ptr::null_mut()) }
+            .result()
+            .ok()?;
+
+        let mut address_infos =
+            vec![vk::DeviceFaultAddressInfoEXT::default(); counts.address_info_count as usize];
+        let mut vendor_infos =
+            vec![vk::DeviceFaultVendorInfoEXT::default(); counts.vendor_info_count as usize];
+        // The vendor binary is only useful to vendor tools, don't fetch it.
+        counts.vendor_binary_size = 0;
+        let mut info = vk::DeviceFaultInfoEXT {
+            p_address_infos: address_infos.as_mut_ptr(),
+            p_vendor_infos: vendor_infos.as_mut_ptr(),
+            ..Default::default()
+        };
+        match unsafe { get_device_fault_info(raw_device, &mut counts, &mut info) } {
+            vk::Result::SUCCESS | vk::Result::INCOMPLETE => {}
+            _ => return None,
+        }
+        address_infos.truncate(counts.address_info_count as usize);
+        vendor_infos.truncate(counts.vendor_info_count as usize);
+
+        let mut output = String::from("VK_EXT_device_fault: ");
+        output.push_str(
+            &info
+                .description_as_c_str()
+                .unwrap_or_default()
+                .to_string_lossy(),
+        );
+        for address_info in address_infos {
+            let _ = write!(
+                output,
+                "\n  {:?} at {:#x} (precision {:#x})",
+                address_info.address_type,
+                address_info.reported_address,
+                address_info.address_precision,
+            );
+        }
+        for vendor_info in vendor_infos {
+            let _ = write!(
+                output,
+                "\n  vendor fault {:#x} (data {:#x}): {}",
+                vendor_info.vendor_fault_code,
+                vendor_info.vendor_fault_data,
+                vendor_info
+                    .description_as_c_str()
+                    .unwrap_or_default()
+                    .to_string_lossy(),
+            );
+        }
+        Some(output)
+    }
+
+    fn take_validation_messages(&self) -> Vec<String> {
+        // The debug messenger is shared by every device of the instance, so this
+        // may also return errors of other devices.
+        self.shared
+            .instance
+            .debug_utils
+            .as_ref()
+            .and_then(|debug_utils| {
+                debug_utils
+                    .callback_data
+                    .gpu_based_validation_messages
+                    .as_ref()
+            })
+            .map_or_else(Vec::new, |messages| mem::take(&mut *messages.lock()))
+    }
 }
 
 impl super::DeviceShared {
@@ -3014,6 +4323,38 @@ impl super::DeviceShared {
         }
     }
 
+    /// The sharing mode, and queue families for concurrent sharing, to create
+    /// buffers and images with.
+    pub(super) fn sharing_mode(&self) -> (vk::SharingMode, &[u32]) {
+        if self.queue_family_indices.len() > 1 {
+            (vk::SharingMode::CONCURRENT, &self.queue_family_indices)
+        } else {
+            (vk::SharingMode::EXCLUSIVE, &[])
+        }
+    }
+
+    /// The usage to create buffers with. Indirect buffers may also hold the
+    /// predicate of conditional rendering.
+    pub(super) fn buffer_usage(&self, usage: wgt::BufferUses) -> vk::BufferUsageFlags {
+        let mut flags = conv::map_buffer_usage(usage);
+        if usage.contains(wgt::BufferUses::INDIRECT)
+            && self.features.contains(wgt::Features::CONDITIONAL_RENDERING)
+        {
+            flags |= vk::BufferUsageFlags::CONDITIONAL_RENDERING_EXT;
+        }
+        flags
+    }
+
+    /// The number of viewports and scissors that pipelines are created with, and
+    /// that are set at the start of render passes.
+    pub(super) fn viewport_count(&self) -> u32 {
+        if self.features.contains(wgt::Features::MULTI_VIEWPORT) {
+            wgt::MAX_VIEWPORTS
+        } else {
+            1
+        }
+    }
+
     pub(super) fn wait_for_fence(
         &self,
         fence: &super::Fence,
@@ -3108,8 +4449,8 @@ fn handle_unexpected(err: vk::Result) -> ! {
     panic!("Unexpected Vulkan error: `{err}`")
 }
 
-struct ImageWithoutMemory {
-    raw: vk::Image,
-    requirements: vk::MemoryRequirements,
-    copy_size: crate::CopyExtent,
+pub(super) struct ImageWithoutMemory {
+    pub(super) raw: vk::Image,
+    pub(super) requirements: vk::MemoryRequirements,
+    pub(super) copy_size: crate::CopyExtent,
 }
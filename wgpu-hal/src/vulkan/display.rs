@@ -0,0 +1,232 @@
+#![cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ffi::CStr;
+
+use ash::{khr, vk};
+
+/// A physical display connected to a Vulkan physical device, which can be
+/// presented to without a windowing system.
+///
+/// Obtained from [`Instance::enumerate_displays`](super::Instance::enumerate_displays).
+#[derive(Debug)]
+pub struct Display {
+    physical_device: vk::PhysicalDevice,
+    raw: vk::DisplayKHR,
+    raw_modes: Vec<vk::DisplayModeKHR>,
+    info: wgt::DisplayInfo,
+}
+
+unsafe impl Send for Display {}
+unsafe impl Sync for Display {}
+
+impl Display {
+    /// Information about the display.
+    ///
+    /// `info().modes` has the same order as the modes used by
+    /// [`Instance::create_surface_from_display`](super::Instance::create_surface_from_display).
+    pub fn info(&self) -> &wgt::DisplayInfo {
+        &self.info
+    }
+
+    /// Returns the raw `VkDisplayKHR` handle.
+    pub fn raw_handle(&self) -> vk::DisplayKHR {
+        self.raw
+    }
+}
+
+impl super::Instance {
+    /// Enumerates the displays attached to all physical devices.
+    ///
+    /// Returns an empty list if `VK_KHR_display` is not available. Displays
+    /// that are currently driven by a windowing system are usually not
+    /// reported by the driver.
+    pub fn enumerate_displays(&self) -> Vec<Display> {
+        if !self.shared.extensions.contains(&khr::display::NAME) {
+            return Vec::new();
+        }
+
+        let raw_devices = match unsafe { self.shared.raw.enumerate_physical_devices() } {
+            Ok(devices) => devices,
+            Err(err) => {
+                log::error!("enumerate_displays: {}", err);
+                return Vec::new();
+            }
+        };
+
+        let display_instance = khr::display::Instance::new(&self.shared.entry, &self.shared.raw);
+
+        let mut displays = Vec::new();
+        for physical_device in raw_devices {
+            let properties = match unsafe {
+                display_instance.get_physical_device_display_properties(physical_device)
+            } {
+                Ok(properties) => properties,
+                Err(err) => {
+                    log::warn!("get_physical_device_display_properties: {}", err);
+                    continue;
+                }
+            };
+
+            for properties in properties {
+                let mode_properties = match unsafe {
+                    display_instance
+                        .get_display_mode_properties(physical_device, properties.display)
+                } {
+                    Ok(modes) => modes,
+                    Err(err) => {
+                        log::warn!("get_display_mode_properties: {}", err);
+                        continue;
+                    }
+                };
+
+                let name = if properties.display_name.is_null() {
+                    String::new()
+                } else {
+                    unsafe { CStr::from_ptr(properties.display_name) }
+                        .to_string_lossy()
+                        .into_owned()
+                };
+
+                let modes = mode_properties
+                    .iter()
+                    .map(|mode| wgt::DisplayMode {
+                        width: mode.parameters.visible_region.width,
+                        height: mode.parameters.visible_region.height,
+                        refresh_rate: mode.parameters.refresh_rate,
+                    })
+                    .collect();
+
+                displays.push(Display {
+                    physical_device,
+                    raw: properties.display,
+                    raw_modes: mode_properties
+                        .iter()
+                        .map(|mode| mode.display_mode)
+                        .collect(),
+                    info: wgt::DisplayInfo {
+                        name,
+                        physical_width: properties.physical_dimensions.width,
+                        physical_height: properties.physical_dimensions.height,
+                        native_resolution: wgt::Extent3d {
+                            width: properties.physical_resolution.width,
+                            height: properties.physical_resolution.height,
+                            depth_or_array_layers: 1,
+                        },
+                        modes,
+                    },
+                });
+            }
+        }
+
+        displays
+    }
+
+    /// Creates a new surface presenting directly to `display`, driving it with `mode`.
+    ///
+    /// The first display plane that can be used with `display` and isn't
+    /// already bound to a different display is used.
+    ///
+    /// # Safety
+    ///
+    /// - `display` must have been returned by [`Self::enumerate_displays`] on this instance.
+    /// - The display must not be in use by a windowing system or another surface.
+    pub unsafe fn create_surface_from_display(
+        &self,
+        display: &Display,
+        mode: &wgt::DisplayMode,
+    ) -> Result<super::Surface, crate::InstanceError> {
+        let mode_index = display
+            .info
+            .modes
+            .iter()
+            .position(|m| m == mode)
+            .ok_or_else(|| {
+                crate::InstanceError::new("Display does not support the given mode".to_string())
+            })?;
+        let raw_mode = display.raw_modes[mode_index];
+
+        let display_instance = khr::display::Instance::new(&self.shared.entry, &self.shared.raw);
+
+        let planes = unsafe {
+            display_instance.get_physical_device_display_plane_properties(display.physical_device)
+        }
+        .map_err(|err| {
+            crate::InstanceError::with_source(
+                String::from("get_physical_device_display_plane_properties() failed"),
+                err,
+            )
+        })?;
+
+        let mut plane_index = None;
+        for (index, plane) in planes.iter().enumerate() {
+            let index = index as u32;
+            if plane.current_display != vk::DisplayKHR::null()
+                && plane.current_display != display.raw
+            {
+                continue;
+            }
+            let supported = unsafe {
+                display_instance
+                    .get_display_plane_supported_displays(display.physical_device, index)
+            }
+            .unwrap_or_default();
+            if supported.contains(&display.raw) {
+                plane_index = Some(index);
+                break;
+            }
+        }
+
+        let plane_index = plane_index.ok_or_else(|| {
+            crate::InstanceError::new("No display plane available for the display".to_string())
+        })?;
+
+        let capabilities = unsafe {
+            display_instance.get_display_plane_capabilities(
+                display.physical_device,
+                raw_mode,
+                plane_index,
+            )
+        }
+        .map_err(|err| {
+            crate::InstanceError::with_source(
+                String::from("get_display_plane_capabilities() failed"),
+                err,
+            )
+        })?;
+
+        let alpha_mode = [
+            vk::DisplayPlaneAlphaFlagsKHR::OPAQUE,
+            vk::DisplayPlaneAlphaFlagsKHR::GLOBAL,
+            vk::DisplayPlaneAlphaFlagsKHR::PER_PIXEL,
+            vk::DisplayPlaneAlphaFlagsKHR::PER_PIXEL_PREMULTIPLIED,
+        ]
+        .into_iter()
+        .find(|&flag| capabilities.supported_alpha.contains(flag))
+        .unwrap_or(vk::DisplayPlaneAlphaFlagsKHR::OPAQUE);
+
+        let create_info = vk::DisplaySurfaceCreateInfoKHR::default()
+            .display_mode(raw_mode)
+            .image_extent(vk::Extent2D {
+                width: mode.width,
+                height: mode.height,
+            })
+            .transform(vk::SurfaceTransformFlagsKHR::IDENTITY)
+            .alpha_mode(alpha_mode)
+            .global_alpha(1.0)
+            .plane_index(plane_index);
+
+        let surface = unsafe { display_instance.create_display_plane_surface(&create_info, None) }
+            .map_err(|err| {
+                crate::InstanceError::with_source(
+                    String::from("create_display_plane_surface() failed"),
+                    err,
+                )
+            })?;
+
+        Ok(self.create_surface_from_vk_surface_khr(surface))
+    }
+}
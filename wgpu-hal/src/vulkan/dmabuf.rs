@@ -0,0 +1,378 @@
+//! Importing Linux DMA-BUFs, through `VK_EXT_external_memory_dma_buf` and
+//! `VK_EXT_image_drm_format_modifier`.
+
+#![cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+
+use alloc::vec::Vec;
+use std::os::fd::{AsRawFd as _, FromRawFd as _, IntoRawFd as _, OwnedFd};
+
+use ash::vk;
+use parking_lot::Mutex;
+
+use super::conv;
+
+/// A [DRM format modifier], describing how the pixels of an image are laid out
+/// in memory.
+///
+/// [DRM format modifier]: https://docs.kernel.org/gpu/drm-kms.html#format-modifiers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DrmFormatModifier(pub u64);
+
+impl DrmFormatModifier {
+    /// `DRM_FORMAT_MOD_LINEAR`: the image is laid out row by row, with no tiling.
+    pub const LINEAR: Self = Self(0);
+}
+
+/// A [`DrmFormatModifier`] that textures of some format can be imported with,
+/// as returned by [`Adapter::drm_format_modifiers`].
+///
+/// [`Adapter::drm_format_modifiers`]: super::Adapter::drm_format_modifiers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DrmFormatModifierProperties {
+    pub modifier: DrmFormatModifier,
+    /// The number of memory planes of images with this modifier.
+    ///
+    /// A texture imported with this modifier needs one [`DmaBufPlane`] for
+    /// each memory plane.
+    pub plane_count: u32,
+}
+
+/// Where a memory plane of an image lies in a DMA-BUF.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DmaBufPlane {
+    /// The offset of the plane from the start of the DMA-BUF, in bytes.
+    pub offset: u64,
+    /// The distance between the starts of consecutive rows of the plane, in bytes.
+    pub stride: u64,
+}
+
+/// Describes how an image is laid out in a DMA-BUF, for
+/// [`Device::texture_from_dmabuf`].
+///
+/// [`Device::texture_from_dmabuf`]: super::Device::texture_from_dmabuf
+#[derive(Clone, Copy, Debug)]
+pub struct DmaBufTextureLayout<'a> {
+    pub modifier: DrmFormatModifier,
+    /// One entry for each memory plane of the image, in order.
+    pub planes: &'a [DmaBufPlane],
+}
+
+const HANDLE_TYPE: vk::ExternalMemoryHandleTypeFlags =
+    vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT;
+
+impl super::Adapter {
+    /// Returns the DRM format modifiers that textures with `format` and `usage`
+    /// can be imported from DMA-BUFs with.
+    ///
+    /// Producers of DMA-BUFs, such as compositors and video decoders, usually
+    /// accept a list of modifiers to choose from. Pass them the modifiers
+    /// returned here, so that [`Device::texture_from_dmabuf`] can import the
+    /// result.
+    ///
+    /// Returns an empty list if the adapter doesn't support
+    /// [`wgt::Features::VULKAN_EXTERNAL_MEMORY_DMA_BUF`].
+    ///
+    /// [`Device::texture_from_dmabuf`]: super::Device::texture_from_dmabuf
+    pub fn drm_format_modifiers(
+        &self,
+        format: wgt::TextureFormat,
+        usage: wgt::TextureUses,
+    ) -> Vec<DrmFormatModifierProperties> {
+        if self.phd_capabilities.device_api_version < vk::API_VERSION_1_2
+            || !self
+                .phd_capabilities
+                .supports_extension(ash::ext::image_drm_format_modifier::NAME)
+            || !self
+                .phd_capabilities
+                .supports_extension(ash::ext::external_memory_dma_buf::NAME)
+        {
+            return Vec::new();
+        }
+
+        let instance = &self.instance.raw;
+        let vk_format = self.private_caps.map_texture_format(format);
+
+        let mut modifier_list = vk::DrmFormatModifierPropertiesListEXT::default();
+        let mut properties = vk::FormatProperties2::default().push_next(&mut modifier_list);
+        unsafe {
+            instance.get_physical_device_format_properties2(self.raw, vk_format, &mut properties)
+        };
+        let mut modifiers = alloc::vec![
+            vk::DrmFormatModifierPropertiesEXT::default();
+            modifier_list.drm_format_modifier_count as usize
+        ];
+        let mut modifier_list = vk::DrmFormatModifierPropertiesListEXT::default()
+            .drm_format_modifier_properties(&mut modifiers);
+        let mut properties = vk::FormatProperties2::default().push_next(&mut modifier_list);
+        unsafe {
+            instance.get_physical_device_format_properties2(self.raw, vk_format, &mut properties)
+        };
+        let count = modifier_list.drm_format_modifier_count as usize;
+        modifiers.truncate(count);
+
+        // Textures are created with the same flags by `create_image_without_memory`.
+        let mut flags = vk::ImageCreateFlags::empty();
+        if format.is_multi_planar_format() {
+            flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
+        }
+
+        modifiers
+            .into_iter()
+            .filter(|properties| {
+                let mut modifier_info = vk::PhysicalDeviceImageDrmFormatModifierInfoEXT::default()
+                    .drm_format_modifier(properties.drm_format_modifier)
+                    .sharing_mode(vk::SharingMode::EXCLUSIVE);
+                let mut external_info =
+                    vk::PhysicalDeviceExternalImageFormatInfo::default().handle_type(HANDLE_TYPE);
+                let format_info = vk::PhysicalDeviceImageFormatInfo2::default()
+                    .format(vk_format)
+                    .ty(vk::ImageType::TYPE_2D)
+                    .tiling(vk::ImageTiling::DRM_FORMAT_MODIFIER_EXT)
+                    .usage(conv::map_texture_usage(usage))
+                    .flags(flags)
+                    .push_next(&mut modifier_info)
+                    .push_next(&mut external_info);
+
+                let mut external_properties = vk::ExternalImageFormatProperties::default();
+                let mut image_properties =
+                    vk::ImageFormatProperties2::default().push_next(&mut external_properties);
+                let result = unsafe {
+                    instance.get_physical_device_image_format_properties2(
+                        self.raw,
+                        &format_info,
+                        &mut image_properties,
+                    )
+                };
+
+                result.is_ok()
+                    && external_properties
+                        .external_memory_properties
+                        .external_memory_features
+                        .contains(vk::ExternalMemoryFeatureFlags::IMPORTABLE)
+            })
+            .map(|properties| DrmFormatModifierProperties {
+                modifier: DrmFormatModifier(properties.drm_format_modifier),
+                plane_count: properties.drm_format_modifier_plane_count,
+            })
+            .collect()
+    }
+}
+
+impl super::Device {
+    fn external_memory_fd(
+        &self,
+    ) -> Result<&ash::khr::external_memory_fd::Device, crate::DeviceError> {
+        if !self
+            .shared
+            .features
+            .contains(wgt::Features::VULKAN_EXTERNAL_MEMORY_DMA_BUF)
+        {
+            log::error!("Vulkan driver does not support VK_EXT_external_memory_dma_buf");
+            return Err(crate::DeviceError::Unexpected);
+        }
+        Ok(self
+            .shared
+            .extension_fns
+            .external_memory_fd
+            .as_ref()
+            .unwrap())
+    }
+
+    /// Imports memory from `fd` for a resource with `requirements`.
+    ///
+    /// `fd` is owned by the returned memory, or closed if importing it fails.
+    fn import_dmabuf_memory(
+        &self,
+        fd: OwnedFd,
+        requirements: vk::MemoryRequirements,
+        mut dedicated_info: vk::MemoryDedicatedAllocateInfo,
+        mut flags_info: Option<vk::MemoryAllocateFlagsInfo>,
+    ) -> Result<vk::DeviceMemory, crate::DeviceError> {
+        let external_memory_fd = self.external_memory_fd()?;
+
+        let mut fd_properties = vk::MemoryFdPropertiesKHR::default();
+        unsafe {
+            external_memory_fd.get_memory_fd_properties(
+                HANDLE_TYPE,
+                fd.as_raw_fd(),
+                &mut fd_properties,
+            )
+        }
+        .map_err(super::map_host_oom_err)?;
+
+        let mem_type_index = self
+            .find_memory_type_index(
+                requirements.memory_type_bits & fd_properties.memory_type_bits,
+                vk::MemoryPropertyFlags::empty(),
+            )
+            .ok_or(crate::DeviceError::Unexpected)?;
+
+        // A successful import takes ownership of the file descriptor.
+        let raw_fd = fd.into_raw_fd();
+        let mut import_info = vk::ImportMemoryFdInfoKHR::default()
+            .handle_type(HANDLE_TYPE)
+            .fd(raw_fd);
+        let mut allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(requirements.size)
+            .memory_type_index(mem_type_index as _)
+            .push_next(&mut import_info)
+            .push_next(&mut dedicated_info);
+        if let Some(ref mut flags_info) = flags_info {
+            allocate_info = allocate_info.push_next(flags_info);
+        }
+
+        unsafe { self.shared.raw.allocate_memory(&allocate_info, None) }
+            .map_err(super::map_host_device_oom_err)
+            .inspect_err(|_| drop(unsafe { OwnedFd::from_raw_fd(raw_fd) }))
+    }
+
+    /// Imports a texture from a DMA-BUF.
+    ///
+    /// `fd` is owned by the texture, or closed if importing it fails.
+    ///
+    /// Requires [`wgt::Features::VULKAN_EXTERNAL_MEMORY_DMA_BUF`]. Images whose
+    /// planes are in separate DMA-BUFs can't be imported.
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a DMA-BUF holding an image with `layout`, and the
+    ///   format and size in `desc`.
+    /// - `layout.modifier` must be one of the modifiers returned by
+    ///   [`Adapter::drm_format_modifiers`] for `desc.format` and `desc.usage`,
+    ///   and `layout.planes` must have one entry for each of its planes.
+    /// - `desc` must describe a 2D texture with a single mip level, array layer
+    ///   and sample.
+    ///
+    /// [`Adapter::drm_format_modifiers`]: super::Adapter::drm_format_modifiers
+    pub unsafe fn texture_from_dmabuf(
+        &self,
+        fd: OwnedFd,
+        layout: &DmaBufTextureLayout<'_>,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        self.external_memory_fd()?;
+
+        let plane_layouts = layout
+            .planes
+            .iter()
+            .map(|plane| vk::SubresourceLayout {
+                offset: plane.offset,
+                row_pitch: plane.stride,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let mut modifier_info = vk::ImageDrmFormatModifierExplicitCreateInfoEXT::default()
+            .drm_format_modifier(layout.modifier.0)
+            .plane_layouts(&plane_layouts);
+        let mut external_memory_image_info =
+            vk::ExternalMemoryImageCreateInfo::default().handle_types(HANDLE_TYPE);
+
+        let image = self.create_image_without_memory(
+            desc,
+            Some(&mut external_memory_image_info),
+            Some(&mut modifier_info),
+        )?;
+
+        let memory = self
+            .import_dmabuf_memory(
+                fd,
+                image.requirements,
+                vk::MemoryDedicatedAllocateInfo::default().image(image.raw),
+                None,
+            )
+            .inspect_err(|_| unsafe { self.shared.raw.destroy_image(image.raw, None) })?;
+
+        unsafe { self.shared.raw.bind_image_memory(image.raw, memory, 0) }
+            .map_err(super::map_host_device_oom_err)
+            .inspect_err(|_| unsafe {
+                self.shared.raw.destroy_image(image.raw, None);
+                self.shared.raw.free_memory(memory, None);
+            })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(image.raw, label) };
+        }
+
+        Ok(super::Texture {
+            raw: image.raw,
+            drop_guard: None,
+            external_memory: Some(memory),
+            block: None,
+            placed: false,
+            format: desc.format,
+            copy_size: image.copy_size,
+        })
+    }
+
+    /// Imports a buffer from a DMA-BUF.
+    ///
+    /// `fd` is owned by the buffer, or closed if importing it fails.
+    ///
+    /// Requires [`wgt::Features::VULKAN_EXTERNAL_MEMORY_DMA_BUF`].
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a DMA-BUF that is large enough for a buffer of `desc.size`
+    ///   bytes.
+    /// - Imported buffers can't be mapped, so `desc.usage` must not contain
+    ///   [`wgt::BufferUses::MAP_READ`] or [`wgt::BufferUses::MAP_WRITE`].
+    pub unsafe fn buffer_from_dmabuf(
+        &self,
+        fd: OwnedFd,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        self.external_memory_fd()?;
+
+        let (sharing_mode, queue_family_indices) = self.shared.sharing_mode();
+        let usage = self.shared.buffer_usage(desc.usage);
+        let mut external_memory_buffer_info =
+            vk::ExternalMemoryBufferCreateInfo::default().handle_types(HANDLE_TYPE);
+        let vk_info = vk::BufferCreateInfo::default()
+            .size(desc.size)
+            .usage(usage)
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(queue_family_indices)
+            .push_next(&mut external_memory_buffer_info);
+
+        let raw = unsafe { self.shared.raw.create_buffer(&vk_info, None) }
+            .map_err(super::map_host_device_oom_and_ioca_err)?;
+        let requirements = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+
+        let flags_info = usage
+            .contains(vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS)
+            .then(|| {
+                vk::MemoryAllocateFlagsInfo::default()
+                    .flags(vk::MemoryAllocateFlags::DEVICE_ADDRESS)
+            });
+        let memory = self
+            .import_dmabuf_memory(
+                fd,
+                requirements,
+                vk::MemoryDedicatedAllocateInfo::default().buffer(raw),
+                flags_info,
+            )
+            .inspect_err(|_| unsafe { self.shared.raw.destroy_buffer(raw, None) })?;
+
+        unsafe { self.shared.raw.bind_buffer_memory(raw, memory, 0) }
+            .map_err(super::map_host_device_oom_and_ioca_err)
+            .inspect_err(|_| unsafe {
+                self.shared.raw.destroy_buffer(raw, None);
+                self.shared.raw.free_memory(memory, None);
+            })?;
+
+        if let Some(label) = desc.label {
+            unsafe { self.shared.set_object_name(raw, label) };
+        }
+
+        self.counters.buffer_memory.add(requirements.size as isize);
+
+        Ok(super::Buffer {
+            raw,
+            block: Some(Mutex::new(super::BufferMemoryBacking::VulkanMemory {
+                memory,
+                offset: 0,
+                size: requirements.size,
+            })),
+        })
+    }
+}
@@ -91,6 +91,15 @@ unsafe extern "system" fn debug_utils_messenger_callback(
         unsafe { cd.message_id_name_as_c_str() }.map_or(Cow::Borrowed(""), CStr::to_string_lossy);
     let message = unsafe { cd.message_as_c_str() }.map_or(Cow::Borrowed(""), CStr::to_string_lossy);
 
+    // Messages printed by `debugPrintf` in shaders aren't validation errors, whatever
+    // severity the layer reports them with.
+    if message_id_name.contains("DEBUG-PRINTF") {
+        let _ = std::panic::catch_unwind(|| {
+            log::info!("{message}");
+        });
+        return vk::FALSE;
+    }
+
     let _ = std::panic::catch_unwind(|| {
         log::log!(
             level,
@@ -128,6 +137,7 @@ unsafe extern "system" fn debug_utils_messenger_callback(
         });
     }
 
+    let mut object_names = Vec::new();
     if cd.object_count != 0 {
         let labels = unsafe { slice::from_raw_parts(cd.p_objects, cd.object_count as usize) };
         //TODO: use color fields of `vk::DebugUtilsLabelExt`?
@@ -146,6 +156,22 @@ unsafe extern "system" fn debug_utils_messenger_callback(
         let _ = std::panic::catch_unwind(|| {
             log::log!(level, "\tobjects: {}", names.join(", "));
         });
+        object_names = names;
+    }
+
+    if let Some(messages) = user_data.gpu_based_validation_messages.as_ref() {
+        if level == log::Level::Error
+            && message_type.contains(vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION)
+        {
+            let mut validation_message = format!("[{message_id_name}] {message}");
+            if !object_names.is_empty() {
+                validation_message = format!(
+                    "{validation_message}\n\tobjects: {}",
+                    object_names.join(", ")
+                );
+            }
+            messages.lock().push(validation_message);
+        }
     }
 
     #[cfg(feature = "validation_canary")]
@@ -286,6 +312,8 @@ impl super::Instance {
         if cfg!(target_os = "windows") {
             // VK_KHR_win32_surface
             extensions.push(khr::win32_surface::NAME);
+            // VK_KHR_get_surface_capabilities2, required by VK_EXT_full_screen_exclusive
+            extensions.push(khr::get_surface_capabilities2::NAME);
         }
         if cfg!(target_os = "macos") {
             // VK_EXT_metal_surface
@@ -306,7 +334,8 @@ impl super::Instance {
             extensions.push(khr::get_display_properties2::NAME);
         }
 
-        if flags.contains(wgt::InstanceFlags::DEBUG) {
+        // GPU-based validation reports its errors through the debug messenger.
+        if flags.intersects(wgt::InstanceFlags::DEBUG | wgt::InstanceFlags::GPU_BASED_VALIDATION) {
             // VK_EXT_debug_utils
             extensions.push(ext::debug_utils::NAME);
         }
@@ -357,6 +386,7 @@ impl super::Instance {
         extensions: Vec<&'static CStr>,
         flags: wgt::InstanceFlags,
         memory_budget_thresholds: wgt::MemoryBudgetThresholds,
+        toggles: wgt::TogglesDescriptor,
         has_nv_optimus: bool,
         drop_callback: Option<crate::DropCallback>,
     ) -> Result<Self, crate::InstanceError> {
@@ -408,6 +438,7 @@ impl super::Instance {
                 drop_guard,
                 flags,
                 memory_budget_thresholds,
+                toggles,
                 debug_utils,
                 get_physical_device_properties,
                 entry,
@@ -717,6 +748,8 @@ impl super::Instance {
             Box::new(super::DebugUtilsMessengerUserData {
                 validation_layer_properties: None,
                 has_obs_layer,
+                gpu_based_validation_messages: should_enable_gpu_based_validation
+                    .then(|| parking_lot::Mutex::new(Vec::new())),
             })
         });
 
@@ -845,6 +878,10 @@ impl super::Instance {
                     validation_feature_list.push(vk::ValidationFeatureEnableEXT::GPU_ASSISTED);
                     validation_feature_list
                         .push(vk::ValidationFeatureEnableEXT::GPU_ASSISTED_RESERVE_BINDING_SLOT);
+                } else {
+                    // Print the messages of `debugPrintf` in shaders. The layer can't do
+                    // this and GPU assisted validation at the same time.
+                    validation_feature_list.push(vk::ValidationFeatureEnableEXT::DEBUG_PRINTF);
                 }
 
                 validation_features = vk::ValidationFeaturesEXT::default()
@@ -874,6 +911,7 @@ impl super::Instance {
                 extensions,
                 desc.flags,
                 desc.memory_budget_thresholds,
+                desc.toggles,
                 has_nv_optimus,
                 None,
             )
@@ -1093,11 +1131,13 @@ impl crate::Surface for super::Surface {
         // thus waited for `locked_swapchain_semaphores.acquire`, wait for all
         // of them to finish, thus ensuring that it's okay to pass `acquire` to
         // `vkAcquireNextImageKHR` again.
-        swapchain.device.wait_for_fence(
+        if !swapchain.device.wait_for_fence(
             fence,
             locked_swapchain_semaphores.previously_used_submission_index,
             timeout_ns,
-        )?;
+        )? {
+            return Ok(None);
+        }
 
         // will block if no image is available
         let (index, suboptimal) = match unsafe {
@@ -1118,11 +1158,13 @@ impl crate::Surface for super::Surface {
             Err(error) => {
                 return match error {
                     vk::Result::TIMEOUT => Ok(None),
+                    // Returned instead of `VK_TIMEOUT` when not waiting at all.
+                    vk::Result::NOT_READY if timeout_ns == 0 => Ok(None),
                     vk::Result::NOT_READY | vk::Result::ERROR_OUT_OF_DATE_KHR => {
                         Err(crate::SurfaceError::Outdated)
                     }
                     vk::Result::ERROR_SURFACE_LOST_KHR => Err(crate::SurfaceError::Lost),
-                    // We don't use VK_EXT_full_screen_exclusive
+                    // We don't use application-controlled VK_EXT_full_screen_exclusive
                     // VK_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT
                     other => Err(super::map_host_device_oom_and_lost_err(other).into()),
                 };
@@ -1145,6 +1187,7 @@ impl crate::Surface for super::Surface {
                 raw: swapchain.images[index as usize],
                 drop_guard: None,
                 block: None,
+                placed: false,
                 external_memory: None,
                 format: swapchain.config.format,
                 copy_size: crate::CopyExtent {
@@ -1162,4 +1205,72 @@ impl crate::Surface for super::Surface {
     }
 
     unsafe fn discard_texture(&self, _texture: super::SurfaceTexture) {}
+
+    unsafe fn set_target_present_time(&self, time: wgt::PresentationTimestamp) {
+        let mut swapchain = self.swapchain.write();
+        let swapchain = swapchain.as_mut().unwrap();
+        swapchain.next_present_time = Some(vk::PresentTimeGOOGLE {
+            present_id: 0,
+            desired_present_time: time.0 as u64,
+        });
+    }
+
+    unsafe fn get_presentation_statistics(
+        &self,
+    ) -> Result<Vec<wgt::PresentationStatistics>, crate::SurfaceError> {
+        let mut swapchain = self.swapchain.write();
+        let swapchain = swapchain.as_mut().unwrap();
+        let display_timing = swapchain
+            .device
+            .extension_fns
+            .display_timing
+            .as_ref()
+            .unwrap();
+
+        let map_err = |error| match error {
+            vk::Result::ERROR_OUT_OF_DATE_KHR => crate::SurfaceError::Outdated,
+            vk::Result::ERROR_SURFACE_LOST_KHR => crate::SurfaceError::Lost,
+            other => super::map_host_device_oom_and_lost_err(other).into(),
+        };
+        let refresh_cycle =
+            unsafe { display_timing.get_refresh_cycle_duration(swapchain.raw) }.map_err(map_err)?;
+        let mut timings = unsafe { display_timing.get_past_presentation_timing(swapchain.raw) }
+            .map_err(map_err)?;
+        timings.sort_by_key(|timing| timing.present_id);
+
+        let refresh_interval = Some(core::time::Duration::from_nanos(
+            refresh_cycle.refresh_duration,
+        ));
+        let mut statistics = Vec::new();
+        for timing in timings {
+            if timing.present_id <= swapchain.last_reported_present_id {
+                continue;
+            }
+            // Presents that were never displayed have no timing.
+            statistics.extend(
+                (swapchain.last_reported_present_id + 1..timing.present_id).map(|present_id| {
+                    wgt::PresentationStatistics {
+                        present_id: present_id.into(),
+                        present_time: wgt::PresentationTimestamp::INVALID_TIMESTAMP,
+                        refresh_interval,
+                        dropped: true,
+                    }
+                }),
+            );
+            statistics.push(wgt::PresentationStatistics {
+                present_id: timing.present_id.into(),
+                present_time: wgt::PresentationTimestamp(timing.actual_present_time.into()),
+                refresh_interval,
+                dropped: false,
+            });
+            swapchain.last_reported_present_id = timing.present_id;
+        }
+        Ok(statistics)
+    }
+
+    unsafe fn presentation_path(&self) -> wgt::PresentationPath {
+        // With `VK_FULL_SCREEN_EXCLUSIVE_ALLOWED_EXT`, the driver doesn't report
+        // whether it switched to exclusive fullscreen.
+        wgt::PresentationPath::Unknown
+    }
 }
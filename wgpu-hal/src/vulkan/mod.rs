@@ -25,21 +25,32 @@ Otherwise, we manage a pool of `VkFence` objects behind each `hal::Fence`.
 !*/
 
 mod adapter;
+mod android;
 mod command;
 mod conv;
 mod device;
+mod display;
+mod dmabuf;
 mod drm;
 mod instance;
 mod sampler;
 mod semaphore_list;
+#[cfg(feature = "spirv-opt")]
+mod spirv_opt;
 
 pub use adapter::PhysicalDeviceFeatures;
+#[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+pub use display::Display;
+#[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+pub use dmabuf::{
+    DmaBufPlane, DmaBufTextureLayout, DrmFormatModifier, DrmFormatModifierProperties,
+};
 
-use alloc::{boxed::Box, ffi::CString, sync::Arc, vec::Vec};
-use core::{borrow::Borrow, ffi::CStr, fmt, marker::PhantomData, mem, num::NonZeroU32};
+use alloc::{boxed::Box, ffi::CString, string::String, sync::Arc, vec::Vec};
+use core::{borrow::Borrow, ffi::CStr, fmt, marker::PhantomData, mem, num::NonZeroU32, slice};
 
 use arrayvec::ArrayVec;
-use ash::{ext, khr, vk};
+use ash::{ext, google, khr, vk};
 use bytemuck::{Pod, Zeroable};
 use hashbrown::HashSet;
 use parking_lot::{Mutex, RwLock};
@@ -50,7 +61,21 @@ use wgt::InternalCounter;
 use semaphore_list::SemaphoreList;
 
 const MILLIS_TO_NANOS: u64 = 1_000_000;
-const MAX_TOTAL_ATTACHMENTS: usize = crate::MAX_COLOR_ATTACHMENTS * 2 + 1;
+const MAX_TOTAL_ATTACHMENTS: usize = crate::MAX_COLOR_ATTACHMENTS * 2 + 2;
+
+/// The kind of handle that shared fences are exported as and imported from.
+#[cfg(windows)]
+const SHARED_FENCE_HANDLE_TYPE: vk::ExternalSemaphoreHandleTypeFlags =
+    vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_WIN32;
+#[cfg(not(windows))]
+const SHARED_FENCE_HANDLE_TYPE: vk::ExternalSemaphoreHandleTypeFlags =
+    vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD;
+
+/// The extension that exports and imports [`SHARED_FENCE_HANDLE_TYPE`] handles.
+#[cfg(windows)]
+const SHARED_FENCE_EXTENSION: &CStr = khr::external_semaphore_win32::NAME;
+#[cfg(not(windows))]
+const SHARED_FENCE_EXTENSION: &CStr = khr::external_semaphore_fd::NAME;
 
 #[derive(Clone, Debug)]
 pub struct Api;
@@ -72,6 +97,7 @@ impl crate::Api for Api {
     type Sampler = Sampler;
     type QuerySet = QuerySet;
     type Fence = Fence;
+    type MemoryHeap = MemoryHeap;
     type AccelerationStructure = AccelerationStructure;
     type PipelineCache = PipelineCache;
 
@@ -81,6 +107,7 @@ impl crate::Api for Api {
     type ShaderModule = ShaderModule;
     type RenderPipeline = RenderPipeline;
     type ComputePipeline = ComputePipeline;
+    type RayTracingPipeline = RayTracingPipeline;
 }
 
 crate::impl_dyn_resource!(
@@ -95,10 +122,12 @@ crate::impl_dyn_resource!(
     Device,
     Fence,
     Instance,
+    MemoryHeap,
     PipelineCache,
     PipelineLayout,
     QuerySet,
     Queue,
+    RayTracingPipeline,
     RenderPipeline,
     Sampler,
     ShaderModule,
@@ -117,7 +146,6 @@ struct DebugUtils {
     /// `InstanceShared::drop` destroys the debug messenger before
     /// dropping this, so the callback should never receive a dangling
     /// user data pointer.
-    #[allow(dead_code)]
     callback_data: Box<DebugUtilsMessengerUserData>,
 }
 
@@ -150,6 +178,10 @@ pub struct DebugUtilsMessengerUserData {
     /// If the OBS layer is present. OBS never increments the version of their layer,
     /// so there's no reason to have the version.
     has_obs_layer: bool,
+
+    /// Validation errors that no device has taken yet, if GPU-based validation is
+    /// enabled. See [`crate::Device::take_validation_messages`].
+    gpu_based_validation_messages: Option<Mutex<Vec<String>>>,
 }
 
 pub struct InstanceShared {
@@ -158,6 +190,8 @@ pub struct InstanceShared {
     drop_guard: Option<crate::DropGuard>,
     flags: wgt::InstanceFlags,
     memory_budget_thresholds: wgt::MemoryBudgetThresholds,
+    /// Adapter toggles to force on or off when exposing adapters.
+    toggles: wgt::TogglesDescriptor,
     debug_utils: Option<DebugUtils>,
     get_physical_device_properties: Option<khr::get_physical_device_properties2::Instance>,
     entry: ash::Entry,
@@ -363,9 +397,14 @@ struct Swapchain {
     ///
     /// # Safety
     ///
-    /// This must only be set if [`wgt::Features::VULKAN_GOOGLE_DISPLAY_TIMING`] is enabled, and
-    /// so the VK_GOOGLE_display_timing extension is present.
+    /// This must only be set if [`wgt::Features::VULKAN_GOOGLE_DISPLAY_TIMING`] or
+    /// [`wgt::Features::PRESENTATION_TIMING`] is enabled, and so the VK_GOOGLE_display_timing
+    /// extension is present.
     next_present_time: Option<vk::PresentTimeGOOGLE>,
+    /// The ID given to the next present if [`wgt::Features::PRESENTATION_TIMING`] is enabled.
+    next_present_id: u32,
+    /// The ID of the last present reported by [`crate::Surface::get_presentation_statistics`].
+    last_reported_present_id: u32,
 }
 
 impl Swapchain {
@@ -403,6 +442,9 @@ impl Surface {
     ///
     /// This can also be used to add a "not before" timestamp to the presentation.
     ///
+    /// If [`wgt::Features::PRESENTATION_TIMING`] is enabled, the `present_id` is replaced
+    /// by the number of the present, as reported by [`crate::Surface::get_presentation_statistics`].
+    ///
     /// The exact semantics of the fields are also documented in the [specification](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPresentTimeGOOGLE.html) for the extension.
     ///
     /// # Panics
@@ -457,7 +499,7 @@ impl Borrow<dyn crate::DynTexture> for SurfaceTexture {
 pub struct Adapter {
     raw: vk::PhysicalDevice,
     instance: Arc<InstanceShared>,
-    //queue_families: Vec<vk::QueueFamilyProperties>,
+    queue_families: Vec<vk::QueueFamilyProperties>,
     known_memory_flags: vk::MemoryPropertyFlags,
     phd_capabilities: adapter::PhysicalDeviceProperties,
     phd_features: PhysicalDeviceFeatures,
@@ -474,12 +516,39 @@ enum ExtensionFn<T> {
     Promoted,
 }
 
+/// The CPU clock that [`wgt::TimestampCalibration`] reports on this platform.
+#[cfg(windows)]
+const CALIBRATION_CPU_TIME_DOMAIN: vk::TimeDomainEXT = vk::TimeDomainEXT::QUERY_PERFORMANCE_COUNTER;
+#[cfg(not(windows))]
+const CALIBRATION_CPU_TIME_DOMAIN: vk::TimeDomainEXT = vk::TimeDomainEXT::CLOCK_MONOTONIC;
+
 struct DeviceExtensionFunctions {
     debug_utils: Option<ext::debug_utils::Device>,
     draw_indirect_count: Option<khr::draw_indirect_count::Device>,
     timeline_semaphore: Option<ExtensionFn<khr::timeline_semaphore::Device>>,
+    create_renderpass2: Option<ExtensionFn<khr::create_renderpass2::Device>>,
+    /// Only set if [`wgt::Features::RENDER_PASS_SUSPEND_RESUME`] is enabled, in
+    /// which case every render pass is begun with dynamic rendering.
+    dynamic_rendering: Option<ExtensionFn<khr::dynamic_rendering::Device>>,
     ray_tracing: Option<RayTracingDeviceExtensionFunctions>,
+    ray_tracing_pipeline: Option<khr::ray_tracing_pipeline::Device>,
     mesh_shading: Option<ext::mesh_shader::Device>,
+    fragment_shading_rate: Option<khr::fragment_shading_rate::Device>,
+    conditional_rendering: Option<ext::conditional_rendering::Device>,
+    hdr_metadata: Option<ext::hdr_metadata::Device>,
+    display_timing: Option<google::display_timing::Device>,
+    /// Only set if both the device and [`CALIBRATION_CPU_TIME_DOMAIN`] can be sampled.
+    calibrated_timestamps: Option<ext::calibrated_timestamps::Device>,
+    device_fault: Option<ext::device_fault::Device>,
+    #[cfg(not(windows))]
+    external_semaphore_fd: Option<khr::external_semaphore_fd::Device>,
+    #[cfg(windows)]
+    external_semaphore_win32: Option<khr::external_semaphore_win32::Device>,
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    external_memory_fd: Option<khr::external_memory_fd::Device>,
+    #[cfg(target_os = "android")]
+    external_memory_android_hardware_buffer:
+        Option<ash::android::external_memory_android_hardware_buffer::Device>,
 }
 
 struct RayTracingDeviceExtensionFunctions {
@@ -556,6 +625,18 @@ struct PrivateCapabilities {
     /// [`VK_KHR_shader_float16_int8`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_shader_float16_int8.html
     /// [see spec]: https://registry.khronos.org/vulkan/specs/latest/man/html/VkPhysicalDeviceShaderFloat16Int8Features.html#extension-features-shaderInt8
     shader_int8: bool,
+
+    /// The size of a shader group handle, from the [`VK_KHR_ray_tracing_pipeline`]
+    /// properties. Zero if the extension is unavailable.
+    ///
+    /// [`VK_KHR_ray_tracing_pipeline`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_ray_tracing_pipeline.html
+    ray_tracing_shader_group_handle_size: u32,
+
+    /// The required alignment of the start of a shader binding table. Zero if
+    /// [`VK_KHR_ray_tracing_pipeline`] is unavailable.
+    ///
+    /// [`VK_KHR_ray_tracing_pipeline`]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_ray_tracing_pipeline.html
+    ray_tracing_shader_group_base_alignment: u32,
 }
 
 bitflags::bitflags!(
@@ -592,6 +673,42 @@ bitflags::bitflags!(
     }
 );
 
+impl Workarounds {
+    /// The adapter toggle of each workaround.
+    const TOGGLES: [(Self, wgt::Toggles); 3] = [
+        (
+            Self::SEPARATE_ENTRY_POINTS,
+            wgt::Toggles::VULKAN_SEPARATE_ENTRY_POINTS,
+        ),
+        (
+            Self::EMPTY_RESOLVE_ATTACHMENT_LISTS,
+            wgt::Toggles::VULKAN_EMPTY_RESOLVE_ATTACHMENT_LISTS,
+        ),
+        (
+            Self::FORCE_FILL_BUFFER_WITH_SIZE_GREATER_4096_ALIGNED_OFFSET_16,
+            wgt::Toggles::VULKAN_ALIGNED_FILL_BUFFER,
+        ),
+    ];
+
+    /// Forces workarounds on or off as `toggles` asks, and returns the resulting
+    /// workarounds along with their toggles.
+    fn apply_toggles(self, toggles: &wgt::TogglesDescriptor) -> (Self, wgt::Toggles) {
+        let mut workarounds = self;
+        let mut applied = wgt::Toggles::empty();
+        for (workaround, toggle) in Self::TOGGLES {
+            let default = if self.contains(workaround) {
+                toggle
+            } else {
+                wgt::Toggles::empty()
+            };
+            let enabled = toggles.apply(default).contains(toggle);
+            workarounds.set(workaround, enabled);
+            applied.set(toggle, enabled);
+        }
+        (workarounds, applied)
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 struct AttachmentKey {
     format: vk::Format,
@@ -620,6 +737,15 @@ struct ColorAttachmentKey {
 struct DepthStencilAttachmentKey {
     base: AttachmentKey,
     stencil_ops: crate::AttachmentOps,
+    resolve: Option<DepthStencilResolveKey>,
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+struct DepthStencilResolveKey {
+    base: AttachmentKey,
+    stencil_ops: crate::AttachmentOps,
+    depth_mode: Option<wgt::ResolveMode>,
+    resolve_stencil: bool,
 }
 
 #[derive(Clone, Eq, Default, Hash, PartialEq)]
@@ -635,6 +761,12 @@ struct DeviceShared {
     family_index: u32,
     queue_index: u32,
     raw_queue: vk::Queue,
+    /// The distinct queue families that the device's queues belong to.
+    ///
+    /// When there is more than one, buffers and images are created with
+    /// concurrent sharing between these families, so that they can be used by
+    /// every queue without ownership transfers.
+    queue_family_indices: Vec<u32>,
     drop_guard: Option<crate::DropGuard>,
     instance: Arc<InstanceShared>,
     physical_device: vk::PhysicalDevice,
@@ -668,9 +800,19 @@ pub struct Device {
     desc_allocator:
         Mutex<gpu_descriptor::DescriptorAllocator<vk::DescriptorPool, vk::DescriptorSet>>,
     valid_ash_memory_types: u32,
+    /// Memory types that may be used to back transient attachments.
+    ///
+    /// `gpu_alloc` refuses to allocate from these, so transient textures get
+    /// a dedicated allocation instead.
+    lazily_allocated_memory_types: u32,
+    /// Memory type used for [`MemoryHeap`]s.
+    placed_memory_type: Option<u32>,
     naga_options: naga::back::spv::Options<'static>,
     #[cfg(feature = "renderdoc")]
     render_doc: crate::auxil::renderdoc::RenderDoc,
+    /// Loaded the first time a shader module asks for [`wgt::ShaderOptimization::Performance`].
+    #[cfg(feature = "spirv-opt")]
+    spirv_optimizer: std::sync::OnceLock<spirv_opt::SpirvOptimizer>,
     counters: Arc<wgt::HalCounters>,
 }
 
@@ -758,11 +900,20 @@ impl RelaySemaphores {
 
 pub struct Queue {
     raw: vk::Queue,
+    /// Held while submitting to or presenting with `raw`.
+    ///
+    /// This is shared with every other `Queue` that `raw` backs, as access to
+    /// a `vk::Queue` must be externally synchronized.
+    raw_lock: Arc<Mutex<()>>,
     swapchain_fn: khr::swapchain::Device,
     device: Arc<DeviceShared>,
     family_index: u32,
     relay_semaphores: Mutex<RelaySemaphores>,
     signal_semaphores: Mutex<SemaphoreList>,
+    /// Timeline semaphores, with values, that the next submission must wait on.
+    ///
+    /// These are added by [`crate::Queue::wait_for_fence`].
+    timeline_waits: Mutex<Vec<(vk::Semaphore, crate::FenceValue)>>,
 }
 
 impl Queue {
@@ -850,6 +1001,7 @@ pub struct AccelerationStructure {
     buffer: vk::Buffer,
     block: Mutex<gpu_alloc::MemoryBlock<vk::DeviceMemory>>,
     compacted_size_query: Option<vk::QueryPool>,
+    serialized_size_query: Option<vk::QueryPool>,
 }
 
 impl crate::DynAccelerationStructure for AccelerationStructure {}
@@ -860,6 +1012,9 @@ pub struct Texture {
     drop_guard: Option<crate::DropGuard>,
     external_memory: Option<vk::DeviceMemory>,
     block: Option<gpu_alloc::MemoryBlock<vk::DeviceMemory>>,
+    /// Whether the texture lives in a [`MemoryHeap`] and may alias other
+    /// textures.
+    placed: bool,
     format: wgt::TextureFormat,
     copy_size: crate::CopyExtent,
 }
@@ -875,6 +1030,15 @@ impl Texture {
     }
 }
 
+#[derive(Debug)]
+pub struct MemoryHeap {
+    raw: vk::DeviceMemory,
+    memory_type: u32,
+    size: wgt::BufferAddress,
+}
+
+impl crate::DynMemoryHeap for MemoryHeap {}
+
 #[derive(Debug)]
 pub struct TextureView {
     raw_texture: vk::Image,
@@ -884,6 +1048,9 @@ pub struct TextureView {
     raw_format: vk::Format,
     base_mip_level: u32,
     dimension: wgt::TextureViewDimension,
+    /// Used to create the views of multi-planar textures that are bound
+    /// with a Y'CbCr conversion.
+    subresource_range: vk::ImageSubresourceRange,
 }
 
 impl crate::DynTextureView for TextureView {}
@@ -912,14 +1079,24 @@ pub struct BindGroupLayout {
     types: Box<[(vk::DescriptorType, u32)]>,
     /// Map of binding index to size,
     binding_arrays: Vec<(u32, NonZeroU32)>,
+    /// Immutable samplers of the [`wgt::BindingType::YcbcrTexture`] bindings.
+    ycbcr_samplers: Vec<YcbcrSampler>,
 }
 
 impl crate::DynBindGroupLayout for BindGroupLayout {}
 
+/// An immutable sampler with a Y'CbCr conversion, bound at `binding`.
+#[derive(Debug)]
+struct YcbcrSampler {
+    binding: u32,
+    conversion: vk::SamplerYcbcrConversion,
+    raw: vk::Sampler,
+}
+
 #[derive(Debug)]
 pub struct PipelineLayout {
     raw: vk::PipelineLayout,
-    binding_arrays: naga::back::spv::BindingMap,
+    binding_map: naga::back::spv::BindingMap,
 }
 
 impl crate::DynPipelineLayout for PipelineLayout {}
@@ -927,6 +1104,8 @@ impl crate::DynPipelineLayout for PipelineLayout {}
 #[derive(Debug)]
 pub struct BindGroup {
     set: gpu_descriptor::DescriptorSet<vk::DescriptorSet>,
+    /// Views created with the Y'CbCr conversion of their binding.
+    ycbcr_views: Vec<vk::ImageView>,
 }
 
 impl crate::DynBindGroup for BindGroup {}
@@ -1005,12 +1184,47 @@ pub struct CommandEncoder {
     /// the given pool & location.
     end_of_pass_timer_query: Option<(vk::QueryPool, u32)>,
 
+    /// If set, the end of the current render/compute pass will end the pipeline
+    /// statistics query at the given pool & location.
+    pass_pipeline_statistics_query: Option<(vk::QueryPool, u32)>,
+
     framebuffers: FastHashMap<FramebufferKey, vk::Framebuffer>,
     temp_texture_views: FastHashMap<TempTextureViewKey, vk::ImageView>,
 
+    /// The queue family of the command pool, which the pools of the encoders of
+    /// parallel render passes are created for too.
+    family_index: u32,
+
+    /// If the current render pass is a parallel one, what its secondary command
+    /// buffers inherit from it.
+    parallel_pass: Option<ParallelRenderPass>,
+
+    /// The encoders of parallel render passes, with the secondary command buffers
+    /// they recorded, which must live until the command buffers executing them
+    /// are reset.
+    parallel_encoders: Vec<(CommandEncoder, vk::CommandBuffer)>,
+
+    /// Encoders of parallel render passes that have been reset, for reuse.
+    free_parallel_encoders: Vec<CommandEncoder>,
+
     counters: Arc<wgt::HalCounters>,
 }
 
+/// What the secondary command buffers recording a render pass begun with
+/// [`crate::RenderPassDescriptor::parallel`] inherit from it.
+struct ParallelRenderPass {
+    /// The render pass, or null if the pass was begun with dynamic rendering.
+    raw_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    /// The formats of the attachments, for dynamic rendering.
+    color_formats: ArrayVec<vk::Format, { crate::MAX_COLOR_ATTACHMENTS }>,
+    depth_format: vk::Format,
+    stencil_format: vk::Format,
+    samples: vk::SampleCountFlags,
+    view_mask: u32,
+    extent: wgt::Extent3d,
+}
+
 impl Drop for CommandEncoder {
     fn drop(&mut self) {
         // SAFETY:
@@ -1073,6 +1287,7 @@ pub enum ShaderModule {
     Intermediate {
         naga_shader: crate::NagaShader,
         runtime_checks: wgt::ShaderRuntimeChecks,
+        optimization: wgt::ShaderOptimization,
     },
 }
 
@@ -1092,6 +1307,13 @@ pub struct ComputePipeline {
 
 impl crate::DynComputePipeline for ComputePipeline {}
 
+#[derive(Debug)]
+pub struct RayTracingPipeline {
+    raw: vk::Pipeline,
+}
+
+impl crate::DynRayTracingPipeline for RayTracingPipeline {}
+
 #[derive(Debug)]
 pub struct PipelineCache {
     raw: vk::PipelineCache,
@@ -1351,6 +1573,19 @@ impl crate::Queue for Queue {
             .map(|cmd| cmd.raw)
             .collect::<Vec<_>>();
 
+        // Waits on other queues' fences are timeline semaphore waits, which need
+        // a value for every wait semaphore. Binary semaphores ignore theirs.
+        let mut wait_values = Vec::new();
+        let timeline_waits = mem::take(&mut *self.timeline_waits.lock());
+        if !timeline_waits.is_empty() {
+            wait_values.resize(wait_semaphores.len(), 0);
+            for (semaphore, value) in timeline_waits {
+                wait_stage_masks.push(vk::PipelineStageFlags::ALL_COMMANDS);
+                wait_semaphores.push(semaphore);
+                wait_values.push(value);
+            }
+        }
+
         let mut vk_info = vk::SubmitInfo::default().command_buffers(&vk_cmd_buffers);
 
         vk_info = vk_info
@@ -1358,9 +1593,10 @@ impl crate::Queue for Queue {
             .wait_dst_stage_mask(&wait_stage_masks);
 
         let mut vk_timeline_info = mem::MaybeUninit::uninit();
-        vk_info = signal_semaphores.add_to_submit(vk_info, &mut vk_timeline_info);
+        vk_info = signal_semaphores.add_to_submit(vk_info, &wait_values, &mut vk_timeline_info);
 
         profiling::scope!("vkQueueSubmit");
+        let _raw_guard = self.raw_lock.lock();
         unsafe {
             self.device
                 .raw
@@ -1374,6 +1610,7 @@ impl crate::Queue for Queue {
         &self,
         surface: &Surface,
         texture: SurfaceTexture,
+        damage: &[wgt::Rect],
     ) -> Result<(), crate::SurfaceError> {
         let mut swapchain = surface.swapchain.write();
         let ssc = swapchain.as_mut().unwrap();
@@ -1386,14 +1623,27 @@ impl crate::Queue for Queue {
             .image_indices(&image_indices)
             .wait_semaphores(swapchain_semaphores.get_present_wait_semaphores());
 
+        let mut next_present_time = ssc.next_present_time.take();
+        if ssc
+            .device
+            .features
+            .contains(wgt::Features::PRESENTATION_TIMING)
+        {
+            // Number every present, so that the statistics tell which ones were dropped.
+            let present_time = next_present_time.get_or_insert_with(Default::default);
+            present_time.present_id = ssc.next_present_id;
+            ssc.next_present_id += 1;
+        }
+
         let mut display_timing;
         let present_times;
-        let vk_info = if let Some(present_time) = ssc.next_present_time.take() {
+        let vk_info = if let Some(present_time) = next_present_time {
             debug_assert!(
-                ssc.device
-                    .features
-                    .contains(wgt::Features::VULKAN_GOOGLE_DISPLAY_TIMING),
-                "`next_present_time` should only be set if `VULKAN_GOOGLE_DISPLAY_TIMING` is enabled"
+                ssc.device.features.intersects(
+                    wgt::Features::VULKAN_GOOGLE_DISPLAY_TIMING
+                        | wgt::Features::PRESENTATION_TIMING
+                ),
+                "`next_present_time` should only be set if `VULKAN_GOOGLE_DISPLAY_TIMING` or `PRESENTATION_TIMING` is enabled"
             );
             present_times = [present_time];
             display_timing = vk::PresentTimesInfoGOOGLE::default().times(&present_times);
@@ -1403,13 +1653,44 @@ impl crate::Queue for Queue {
             vk_info
         };
 
+        let rectangles;
+        let regions;
+        let mut present_regions;
+        let vk_info = if !damage.is_empty()
+            && ssc
+                .device
+                .enabled_extensions
+                .contains(&khr::incremental_present::NAME)
+        {
+            rectangles = damage
+                .iter()
+                .map(|rect| vk::RectLayerKHR {
+                    offset: vk::Offset2D {
+                        x: rect.x as i32,
+                        y: rect.y as i32,
+                    },
+                    extent: vk::Extent2D {
+                        width: rect.width,
+                        height: rect.height,
+                    },
+                    layer: 0,
+                })
+                .collect::<Vec<_>>();
+            regions = [vk::PresentRegionKHR::default().rectangles(&rectangles)];
+            present_regions = vk::PresentRegionsKHR::default().regions(&regions);
+            vk_info.push_next(&mut present_regions)
+        } else {
+            vk_info
+        };
+
         let suboptimal = {
             profiling::scope!("vkQueuePresentKHR");
+            let _raw_guard = self.raw_lock.lock();
             unsafe { self.swapchain_fn.queue_present(self.raw, &vk_info) }.map_err(|error| {
                 match error {
                     vk::Result::ERROR_OUT_OF_DATE_KHR => crate::SurfaceError::Outdated,
                     vk::Result::ERROR_SURFACE_LOST_KHR => crate::SurfaceError::Lost,
-                    // We don't use VK_EXT_full_screen_exclusive
+                    // We don't use application-controlled VK_EXT_full_screen_exclusive
                     // VK_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT
                     _ => map_host_device_oom_and_lost_err(error).into(),
                 }
@@ -1429,6 +1710,168 @@ impl crate::Queue for Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         self.device.timestamp_period
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let calibrated_timestamps = self.device.extension_fns.calibrated_timestamps.as_ref()?;
+        let infos = [
+            vk::CalibratedTimestampInfoEXT::default().time_domain(vk::TimeDomainEXT::DEVICE),
+            vk::CalibratedTimestampInfoEXT::default().time_domain(CALIBRATION_CPU_TIME_DOMAIN),
+        ];
+        let mut timestamps = [0; 2];
+        let mut max_deviation = 0;
+        unsafe {
+            (calibrated_timestamps.fp().get_calibrated_timestamps_ext)(
+                self.device.raw.handle(),
+                infos.len() as u32,
+                infos.as_ptr(),
+                timestamps.as_mut_ptr(),
+                &mut max_deviation,
+            )
+        }
+        .result()
+        .ok()?;
+        let [gpu_timestamp, cpu_timestamp] = timestamps;
+
+        // `QueryPerformanceCounter` counts ticks of its own frequency.
+        #[cfg(windows)]
+        let cpu_timestamp = {
+            let mut frequency = 0;
+            unsafe {
+                windows::Win32::System::Performance::QueryPerformanceFrequency(&mut frequency)
+            }
+            .ok()?;
+            (cpu_timestamp as u128 * 1_000_000_000 / frequency as u128) as u64
+        };
+
+        Some(wgt::TimestampCalibration {
+            gpu_timestamp,
+            cpu_timestamp,
+            timestamp_period: self.device.timestamp_period,
+        })
+    }
+
+    unsafe fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[crate::SparseBufferBinding<Buffer, MemoryHeap>],
+        texture_bindings: &[crate::SparseTextureBinding<Texture, MemoryHeap>],
+    ) -> Result<(), crate::DeviceError> {
+        let buffer_binds = buffer_bindings
+            .iter()
+            .map(|binding| {
+                let (memory, memory_offset) = binding
+                    .memory
+                    .map_or((vk::DeviceMemory::null(), 0), |(heap, offset)| {
+                        (heap.raw, offset)
+                    });
+                vk::SparseMemoryBind {
+                    resource_offset: binding.range.start,
+                    size: binding.range.end - binding.range.start,
+                    memory,
+                    memory_offset,
+                    flags: vk::SparseMemoryBindFlags::empty(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let buffer_infos = buffer_bindings
+            .iter()
+            .zip(&buffer_binds)
+            .map(|(binding, bind)| {
+                vk::SparseBufferMemoryBindInfo::default()
+                    .buffer(binding.buffer.raw)
+                    .binds(slice::from_ref(bind))
+            })
+            .collect::<Vec<_>>();
+
+        let image_binds = texture_bindings
+            .iter()
+            .map(|binding| {
+                let (memory, memory_offset) = binding
+                    .memory
+                    .map_or((vk::DeviceMemory::null(), 0), |(heap, offset)| {
+                        (heap.raw, offset)
+                    });
+                vk::SparseImageMemoryBind {
+                    subresource: vk::ImageSubresource {
+                        aspect_mask: vk::ImageAspectFlags::COLOR,
+                        mip_level: binding.mip_level,
+                        array_layer: binding.array_layer,
+                    },
+                    offset: vk::Offset3D {
+                        x: binding.origin.x as i32,
+                        y: binding.origin.y as i32,
+                        z: binding.origin.z as i32,
+                    },
+                    extent: vk::Extent3D {
+                        width: binding.size.width,
+                        height: binding.size.height,
+                        depth: binding.size.depth_or_array_layers,
+                    },
+                    memory,
+                    memory_offset,
+                    flags: vk::SparseMemoryBindFlags::empty(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let image_infos = texture_bindings
+            .iter()
+            .zip(&image_binds)
+            .map(|(binding, bind)| {
+                vk::SparseImageMemoryBindInfo::default()
+                    .image(binding.texture.raw)
+                    .binds(slice::from_ref(bind))
+            })
+            .collect::<Vec<_>>();
+
+        // Binding is ordered with submissions through the same relay semaphores.
+        let semaphore_state = self.relay_semaphores.lock().advance(&self.device)?;
+        let wait_semaphores = semaphore_state.wait.as_slice();
+        let signal_semaphores = [semaphore_state.signal];
+
+        let vk_info = vk::BindSparseInfo::default()
+            .wait_semaphores(wait_semaphores)
+            .buffer_binds(&buffer_infos)
+            .image_binds(&image_infos)
+            .signal_semaphores(&signal_semaphores);
+
+        profiling::scope!("vkQueueBindSparse");
+        let _raw_guard = self.raw_lock.lock();
+        unsafe {
+            self.device
+                .raw
+                .queue_bind_sparse(self.raw, &[vk_info], vk::Fence::null())
+                .map_err(map_host_device_oom_and_lost_err)?
+        };
+        Ok(())
+    }
+
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        match *fence {
+            Fence::TimelineSemaphore(raw) => {
+                self.timeline_waits.lock().push((raw, value));
+            }
+            // Fence pools can't be waited on by the GPU, so wait on the CPU instead.
+            Fence::FencePool { .. } => {
+                self.device.wait_for_fence(fence, value, u64::MAX)?;
+            }
+        }
+        Ok(())
+    }
+
+    unsafe fn signal_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        let Fence::TimelineSemaphore(raw) = *fence else {
+            crate::hal_usage_error("only timeline semaphores can be signalled")
+        };
+        self.add_signal_semaphore(raw, Some(value));
+        Ok(())
+    }
 }
 
 impl Queue {
@@ -45,21 +45,29 @@ impl SemaphoreList {
     /// - Set `submit_info`'s `pSignalSemaphores` list to this list's
     ///   semaphores.
     ///
-    /// - If this list contains any timeline semaphores, then initialize
-    ///   `timeline_info`, set its `pSignalSemaphoreValues` to this
-    ///   list's values, and add it to `submit_info`s extension chain.
+    /// - If this list contains any timeline semaphores, or `wait_values` is
+    ///   not empty, then initialize `timeline_info`, set its
+    ///   `pSignalSemaphoreValues` to this list's values and its
+    ///   `pWaitSemaphoreValues` to `wait_values`, and add it to
+    ///   `submit_info`s extension chain.
+    ///
+    /// `wait_values` must either be empty, or hold one value for each of
+    /// `submit_info`'s wait semaphores.
     ///
     /// Return the revised `submit_info` value.
     pub fn add_to_submit<'i, 's: 'i>(
         &'s self,
         submit_info: vk::SubmitInfo<'i>,
+        wait_values: &'i [u64],
         timeline_info: &'i mut MaybeUninit<vk::TimelineSemaphoreSubmitInfo<'i>>,
     ) -> vk::SubmitInfo<'i> {
         self.check();
         let mut submit_info = submit_info.signal_semaphores(&self.semaphores);
-        if !self.values.is_empty() {
+        if !self.values.is_empty() || !wait_values.is_empty() {
             let timeline_info = timeline_info.write(
-                vk::TimelineSemaphoreSubmitInfo::default().signal_semaphore_values(&self.values),
+                vk::TimelineSemaphoreSubmitInfo::default()
+                    .wait_semaphore_values(wait_values)
+                    .signal_semaphore_values(&self.values),
             );
             submit_info = submit_info.push_next(timeline_info);
         }
@@ -0,0 +1,165 @@
+//! SPIRV-Tools optimizer integration - <https://github.com/KhronosGroup/SPIRV-Tools>
+//!
+//! The `SPIRV-Tools-shared` library is loaded at runtime, the first time a shader module
+//! asks for [`wgt::ShaderOptimization::Performance`], and used through its C API.
+
+use alloc::{format, string::String, vec::Vec};
+use core::{ffi, ptr, slice};
+
+#[cfg(windows)]
+const LIBRARY_NAME: &str = "SPIRV-Tools-shared.dll";
+#[cfg(target_vendor = "apple")]
+const LIBRARY_NAME: &str = "libSPIRV-Tools-shared.dylib";
+#[cfg(not(any(windows, target_vendor = "apple")))]
+const LIBRARY_NAME: &str = "libSPIRV-Tools-shared.so";
+
+/// `SPV_SUCCESS` of `spv_result_t`.
+const SPV_SUCCESS: ffi::c_int = 0;
+
+// Vulkan variants of `spv_target_env`.
+const SPV_ENV_VULKAN_1_0: ffi::c_int = 1;
+const SPV_ENV_VULKAN_1_1: ffi::c_int = 18;
+const SPV_ENV_VULKAN_1_1_SPIRV_1_4: ffi::c_int = 21;
+const SPV_ENV_VULKAN_1_2: ffi::c_int = 23;
+const SPV_ENV_VULKAN_1_3: ffi::c_int = 25;
+
+/// `spv_binary_t`
+#[repr(C)]
+struct SpvBinary {
+    code: *mut u32,
+    word_count: usize,
+}
+
+type OptimizerCreateFn = unsafe extern "C" fn(env: ffi::c_int) -> *mut ffi::c_void;
+type OptimizerDestroyFn = unsafe extern "C" fn(optimizer: *mut ffi::c_void);
+type RegisterPerformancePassesFn = unsafe extern "C" fn(optimizer: *mut ffi::c_void) -> bool;
+type OptionsCreateFn = unsafe extern "C" fn() -> *mut ffi::c_void;
+type OptionsDestroyFn = unsafe extern "C" fn(options: *mut ffi::c_void);
+type OptimizerRunFn = unsafe extern "C" fn(
+    optimizer: *mut ffi::c_void,
+    binary: *const u32,
+    word_count: usize,
+    optimized_binary: *mut *mut SpvBinary,
+    options: *mut ffi::c_void,
+) -> ffi::c_int;
+type BinaryDestroyFn = unsafe extern "C" fn(binary: *mut SpvBinary);
+
+/// The dynamically loaded SPIRV-Tools optimizer functions
+#[derive(Debug)]
+pub struct SpirvToolsApi {
+    optimizer_create: OptimizerCreateFn,
+    optimizer_destroy: OptimizerDestroyFn,
+    register_performance_passes: RegisterPerformancePassesFn,
+    options_create: OptionsCreateFn,
+    options_destroy: OptionsDestroyFn,
+    optimizer_run: OptimizerRunFn,
+    binary_destroy: BinaryDestroyFn,
+    /// Keeps the function pointers above valid.
+    _lib: libloading::Library,
+}
+
+/// SPIRV-Tools optimizer type
+#[derive(Debug)]
+pub enum SpirvOptimizer {
+    /// The SPIRV-Tools optimizer is available
+    Available {
+        /// SPIRV-Tools API with function pointers
+        api: SpirvToolsApi,
+    },
+    /// The SPIRV-Tools optimizer is _not_ available
+    NotAvailable {
+        /// A description why the optimizer is not available
+        reason: String,
+    },
+}
+
+unsafe fn get<T: Copy>(lib: &libloading::Library, name: &ffi::CStr) -> Result<T, String> {
+    unsafe { lib.get::<T>(name.to_bytes()) }
+        .map(|symbol| *symbol)
+        .map_err(|e| {
+            format!("Unable to get {name:?} from SPIRV-Tools library '{LIBRARY_NAME}': {e:?}")
+        })
+}
+
+impl SpirvToolsApi {
+    unsafe fn load() -> Result<Self, String> {
+        let lib = unsafe { libloading::Library::new(LIBRARY_NAME) }
+            .map_err(|e| format!("Unable to load SPIRV-Tools library '{LIBRARY_NAME}': {e:?}"))?;
+        unsafe {
+            Ok(Self {
+                optimizer_create: get(&lib, c"spvOptimizerCreate")?,
+                optimizer_destroy: get(&lib, c"spvOptimizerDestroy")?,
+                register_performance_passes: get(&lib, c"spvOptimizerRegisterPerformancePasses")?,
+                options_create: get(&lib, c"spvOptimizerOptionsCreate")?,
+                options_destroy: get(&lib, c"spvOptimizerOptionsDestroy")?,
+                optimizer_run: get(&lib, c"spvOptimizerRun")?,
+                binary_destroy: get(&lib, c"spvBinaryDestroy")?,
+                _lib: lib,
+            })
+        }
+    }
+
+    fn optimize(&self, spv: &[u32], target_env: ffi::c_int) -> Result<Vec<u32>, String> {
+        let optimizer = unsafe { (self.optimizer_create)(target_env) };
+        if optimizer.is_null() {
+            return Err(format!(
+                "Unable to create a SPIRV-Tools optimizer for target environment {target_env}"
+            ));
+        }
+        let registered = unsafe { (self.register_performance_passes)(optimizer) };
+        let mut binary = ptr::null_mut();
+        let result = if registered {
+            let options = unsafe { (self.options_create)() };
+            let result = unsafe {
+                (self.optimizer_run)(optimizer, spv.as_ptr(), spv.len(), &mut binary, options)
+            };
+            unsafe { (self.options_destroy)(options) };
+            result
+        } else {
+            SPV_SUCCESS
+        };
+        unsafe { (self.optimizer_destroy)(optimizer) };
+
+        if !registered {
+            return Err("Unable to register the SPIRV-Tools performance passes".into());
+        }
+        if result != SPV_SUCCESS || binary.is_null() {
+            unsafe { (self.binary_destroy)(binary) };
+            return Err(format!(
+                "SPIRV-Tools optimizer failed with error code {result}"
+            ));
+        }
+        let optimized = unsafe {
+            let binary = &*binary;
+            slice::from_raw_parts(binary.code, binary.word_count).to_vec()
+        };
+        unsafe { (self.binary_destroy)(binary) };
+        Ok(optimized)
+    }
+}
+
+impl SpirvOptimizer {
+    pub fn new() -> Self {
+        match unsafe { SpirvToolsApi::load() } {
+            Ok(api) => Self::Available { api },
+            Err(reason) => Self::NotAvailable { reason },
+        }
+    }
+
+    /// Runs the SPIRV-Tools performance passes over `spv`, a module using SPIR-V `lang_version`.
+    pub fn optimize(&self, spv: &[u32], lang_version: (u8, u8)) -> Result<Vec<u32>, String> {
+        let api = match *self {
+            Self::Available { ref api } => api,
+            Self::NotAvailable { ref reason } => return Err(reason.clone()),
+        };
+        // The oldest Vulkan environment accepting this SPIR-V version.
+        let target_env = match lang_version {
+            (1, 0) => SPV_ENV_VULKAN_1_0,
+            (1, 1..=3) => SPV_ENV_VULKAN_1_1,
+            (1, 4) => SPV_ENV_VULKAN_1_1_SPIRV_1_4,
+            (1, 5) => SPV_ENV_VULKAN_1_2,
+            _ => SPV_ENV_VULKAN_1_3,
+        };
+        api.optimize(spv, target_env)
+    }
+}
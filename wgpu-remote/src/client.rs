@@ -0,0 +1,2760 @@
+//! The backend of the instances created by [`connect`], which sends the calls to the server.
+
+use std::{
+    fmt, future,
+    io::{self, Read, Write},
+    mem,
+    ops::Range,
+    pin::Pin,
+    ptr::NonNull,
+    slice,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+use wgpu::custom::{
+    self, AdapterInterface, BindGroupInterface, BindGroupLayoutInterface, BlasInterface,
+    BufferInterface, BufferMappedRangeInterface, CommandBufferInterface, CommandEncoderInterface,
+    ComputePassInterface, ComputePipelineInterface, DeviceInterface, InstanceInterface,
+    MemoryHeapInterface, PipelineCacheInterface, PipelineLayoutInterface, QuerySetInterface,
+    QueueInterface, QueueWriteBufferInterface, RayTracingPipelineInterface,
+    RenderBundleEncoderInterface, RenderBundleInterface, RenderPassInterface,
+    RenderPipelineInterface, SamplerInterface, ShaderModuleInterface, SharedFenceInterface,
+    TextureInterface, TextureViewInterface, TlasInterface,
+};
+
+use crate::{
+    protocol::{
+        self, AdapterDescription, Command, ComputeCommand, Event, Id, InvalidObject, Message,
+        RenderCommand, Reply, Request, Response,
+    },
+    transport,
+};
+
+/// Connects to a [`Server`](crate::Server) over `stream`, and returns an instance which sends
+/// the calls made with it to the server.
+///
+/// The connection is closed when the instance, and all the objects created with it, are
+/// dropped.
+///
+/// # Errors
+///
+/// Returns an error if the stream fails, or if the server runs another version of this crate.
+pub fn connect(stream: impl Read + Write + Send + 'static) -> io::Result<wgpu::Instance> {
+    let mut stream: Box<dyn Stream> = Box::new(stream);
+    transport::write_message(
+        &mut stream,
+        &Message {
+            reply: true,
+            request: Request::Handshake {
+                version: protocol::VERSION,
+            },
+        },
+    )?;
+    match transport::read_message::<Reply>(&mut stream)? {
+        Some(Reply {
+            response: Response::Handshake { version },
+            ..
+        }) if version == protocol::VERSION => {}
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the server runs another version of wgpu-remote",
+            ));
+        }
+        None => return Err(io::ErrorKind::UnexpectedEof.into()),
+    }
+
+    let context = Arc::new(Context {
+        stream: Mutex::new(Some(stream)),
+        next_id: AtomicU64::new(0),
+        callbacks: Mutex::default(),
+    });
+    Ok(wgpu::Instance::from_custom(RemoteInstance { context }))
+}
+
+trait Stream: Read + Write + Send {}
+impl<T: Read + Write + Send> Stream for T {}
+
+/// The connection to the server, shared by all the objects created over it.
+struct Context {
+    /// The stream to the server, or `None` once the connection is lost.
+    stream: Mutex<Option<Box<dyn Stream>>>,
+    next_id: AtomicU64,
+    callbacks: Mutex<Callbacks>,
+}
+
+/// The callbacks waiting for [`Event`]s.
+#[derive(Default)]
+struct Callbacks {
+    maps: HashMap<Id, PendingMap>,
+    uncaptured_error_handlers: HashMap<Id, Box<dyn wgpu::UncapturedErrorHandler>>,
+    device_lost: HashMap<Id, custom::BoxDeviceLostCallback>,
+    queues: HashMap<Id, QueueState>,
+}
+
+struct PendingMap {
+    mapping: Arc<Mutex<Option<Mapping>>>,
+    mode: wgpu::MapMode,
+    offset: wgpu::BufferAddress,
+    callback: custom::BufferMapCallback,
+}
+
+/// The submissions of a queue, which are numbered from 1.
+#[derive(Default)]
+struct QueueState {
+    submitted: u64,
+    completed: u64,
+    callbacks: Vec<(u64, custom::BoxSubmittedWorkDoneCallback)>,
+}
+
+impl QueueState {
+    /// Marks the submissions up to `index` as completed, and returns the callbacks to call.
+    fn complete(&mut self, index: u64) -> Vec<custom::BoxSubmittedWorkDoneCallback> {
+        self.completed = self.completed.max(index);
+        let completed = self.completed;
+        let (done, pending) = mem::take(&mut self.callbacks)
+            .into_iter()
+            .partition(|&(index, _)| index <= completed);
+        self.callbacks = pending;
+        done.into_iter().map(|(_, callback)| callback).collect()
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("connected", &self.is_connected())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Context {
+    fn id(&self) -> Id {
+        Id(self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Reports `error` as a validation error of `device`, for a call which needs a feature
+    /// `protocol::unsupported_features` masks.
+    fn report_unsupported(&self, device: Id, error: &str) {
+        self.send(Request::ReportValidationError {
+            device,
+            error: error.to_owned(),
+        });
+    }
+
+    fn is_connected(&self) -> bool {
+        self.stream.lock().is_some()
+    }
+
+    /// Sends a request without waiting for the server.
+    fn send(&self, request: Request) {
+        let mut stream = self.stream.lock();
+        let Some(connection) = stream.as_mut() else {
+            return;
+        };
+        let message = Message {
+            reply: false,
+            request,
+        };
+        if let Err(err) = transport::write_message(connection, &message) {
+            *stream = None;
+            drop(stream);
+            self.lose(&err);
+        }
+    }
+
+    /// Sends a request and waits for the response, or returns `None` if the connection is
+    /// lost.
+    fn call(&self, request: Request) -> Option<Response> {
+        let mut stream = self.stream.lock();
+        let connection = stream.as_mut()?;
+        let message = Message {
+            reply: true,
+            request,
+        };
+        let reply = transport::write_message(connection, &message)
+            .and_then(|()| transport::read_message::<Reply>(connection))
+            .and_then(|reply| reply.ok_or_else(|| io::ErrorKind::UnexpectedEof.into()));
+        match reply {
+            Ok(reply) => {
+                drop(stream);
+                self.handle(reply.events);
+                Some(reply.response)
+            }
+            Err(err) => {
+                *stream = None;
+                drop(stream);
+                self.lose(&err);
+                None
+            }
+        }
+    }
+
+    /// Creates an object with `request`, which is given the id of the object.
+    fn create(self: &Arc<Self>, request: impl FnOnce(Id) -> Request) -> Handle {
+        let id = self.id();
+        self.send(request(id));
+        Handle {
+            id,
+            context: self.clone(),
+        }
+    }
+
+    /// Calls the callbacks of `events`.
+    ///
+    /// This must be called without any lock held, as the callbacks may call into wgpu.
+    fn handle(&self, events: Vec<Event>) {
+        for event in events {
+            match event {
+                Event::BufferMapped { map, data } => {
+                    let Some(pending) = self.callbacks.lock().maps.remove(&map) else {
+                        continue;
+                    };
+                    let result = match data {
+                        Some(data) => {
+                            *pending.mapping.lock() = Some(Mapping {
+                                mode: pending.mode,
+                                offset: pending.offset,
+                                memory: Arc::new(MappedMemory::new(data)),
+                            });
+                            Ok(())
+                        }
+                        None => Err(wgpu::BufferAsyncError),
+                    };
+                    (pending.callback)(result);
+                }
+                Event::SubmissionDone { queue, index } => {
+                    let callbacks = match self.callbacks.lock().queues.get_mut(&queue) {
+                        Some(state) => state.complete(index),
+                        None => continue,
+                    };
+                    for callback in callbacks {
+                        callback();
+                    }
+                }
+                Event::UncapturedError { device, error } => {
+                    let error = wgpu::Error::from(error);
+                    let handler = self
+                        .callbacks
+                        .lock()
+                        .uncaptured_error_handlers
+                        .remove(&device);
+                    match handler {
+                        Some(handler) => {
+                            handler(error);
+                            // Keep the handler the callback may have set instead.
+                            self.callbacks
+                                .lock()
+                                .uncaptured_error_handlers
+                                .entry(device)
+                                .or_insert(handler);
+                        }
+                        None => {
+                            log::error!("Handling wgpu errors as fatal by default");
+                            panic!("wgpu error: {error}\n");
+                        }
+                    }
+                }
+                Event::DeviceLost {
+                    device,
+                    reason,
+                    message,
+                } => {
+                    let callback = self.callbacks.lock().device_lost.remove(&device);
+                    if let Some(callback) = callback {
+                        callback(reason, message);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Releases the callbacks after the connection is lost: the devices are lost, the maps
+    /// fail, and the submissions are done.
+    fn lose(&self, err: &io::Error) {
+        log::error!("Lost the connection to the wgpu server: {err}");
+        let callbacks = mem::take(&mut *self.callbacks.lock());
+        for (_, callback) in callbacks.device_lost {
+            callback(
+                wgpu::DeviceLostReason::Unknown,
+                "connection to the server was lost".to_owned(),
+            );
+        }
+        for (_, pending) in callbacks.maps {
+            (pending.callback)(Err(wgpu::BufferAsyncError));
+        }
+        for (_, state) in callbacks.queues {
+            for (_, callback) in state.callbacks {
+                callback();
+            }
+        }
+    }
+}
+
+fn unexpected(response: Response) -> ! {
+    panic!("unexpected response from the wgpu server: {response:?}")
+}
+
+/// The id of an object on the server, which is released when the handle is dropped.
+#[derive(Debug)]
+struct Handle {
+    id: Id,
+    context: Arc<Context>,
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        self.context.send(Request::Drop(self.id));
+    }
+}
+
+/// An object of this backend, which has a [`Handle`].
+trait Remote {
+    fn handle(&self) -> &Handle;
+}
+
+/// Returns the id of an object passed to this backend.
+fn id<T: Remote>(object: Option<&T>) -> Id {
+    object
+        .expect("objects of other backends can't be used with the remote backend")
+        .handle()
+        .id
+}
+
+macro_rules! remote_objects {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[derive(Debug)]
+            struct $name {
+                handle: Handle,
+            }
+
+            impl Remote for $name {
+                fn handle(&self) -> &Handle {
+                    &self.handle
+                }
+            }
+        )*
+    };
+}
+
+remote_objects!(
+    RemoteBindGroupLayout,
+    RemoteBindGroup,
+    RemoteTextureView,
+    RemoteSampler,
+    RemoteTexture,
+    RemoteQuerySet,
+    RemotePipelineLayout,
+    RemoteRenderPipeline,
+    RemoteComputePipeline,
+    RemoteCommandBuffer,
+    RemoteRenderBundle,
+);
+
+/// An object which needs a feature `protocol::unsupported_features` masks, made when the
+/// feature is used anyway.
+///
+/// Its error is reported when it is made, and it only exists on the client: it can only be
+/// passed to calls which need the same features, and report their own errors.
+#[derive(Debug)]
+struct Unsupported;
+
+impl BlasInterface for Unsupported {
+    fn prepare_compact_async(&self, callback: custom::BlasCompactCallback) {
+        callback(Err(wgpu::BlasAsyncError));
+    }
+
+    fn ready_for_compaction(&self) -> bool {
+        false
+    }
+
+    fn compacted_size(&self) -> Option<wgpu::BufferAddress> {
+        None
+    }
+
+    fn serialize(&self, callback: custom::BlasSerializeCallback) {
+        callback(Err(wgpu::BlasAsyncError));
+    }
+}
+
+impl TlasInterface for Unsupported {}
+
+impl SharedFenceInterface for Unsupported {
+    fn export(&self) -> Option<wgpu::SharedFenceHandle> {
+        None
+    }
+}
+
+impl MemoryHeapInterface for Unsupported {}
+
+impl RayTracingPipelineInterface for Unsupported {
+    fn shader_binding_table(
+        &self,
+        _desc: &wgpu::ShaderBindingTableDescriptor<'_>,
+    ) -> (Vec<u8>, wgpu::ShaderBindingTableLayout) {
+        (Vec::new(), wgpu::ShaderBindingTableLayout::default())
+    }
+}
+
+impl PipelineCacheInterface for Unsupported {
+    fn get_data(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+impl BindGroupLayoutInterface for RemoteBindGroupLayout {}
+impl BindGroupInterface for RemoteBindGroup {}
+impl TextureViewInterface for RemoteTextureView {}
+impl SamplerInterface for RemoteSampler {}
+impl QuerySetInterface for RemoteQuerySet {}
+impl PipelineLayoutInterface for RemotePipelineLayout {}
+impl CommandBufferInterface for RemoteCommandBuffer {}
+impl RenderBundleInterface for RemoteRenderBundle {}
+
+#[derive(Debug)]
+struct RemoteInstance {
+    context: Arc<Context>,
+}
+
+impl InstanceInterface for RemoteInstance {
+    fn new(_desc: &wgpu::InstanceDescriptor) -> Self
+    where
+        Self: Sized,
+    {
+        panic!("instances of the remote backend are created with `wgpu_remote::connect`")
+    }
+
+    unsafe fn create_surface(
+        &self,
+        _target: wgpu::SurfaceTargetUnsafe,
+    ) -> Result<custom::DispatchSurface, wgpu::CreateSurfaceError> {
+        Err(wgpu::CreateSurfaceError::from_custom(
+            "Surfaces are not supported by the remote backend",
+        ))
+    }
+
+    fn request_adapter(
+        &self,
+        options: &wgpu::RequestAdapterOptions<'_, '_>,
+    ) -> Pin<Box<dyn custom::RequestAdapterFuture>> {
+        let id = self.context.id();
+        let response = self.context.call(Request::RequestAdapter {
+            id,
+            options: wgt::RequestAdapterOptions {
+                power_preference: options.power_preference,
+                force_fallback_adapter: options.force_fallback_adapter,
+                compatible_surface: None,
+            },
+        });
+        let result = match response {
+            Some(Response::Adapter(result)) => result.map(|description| {
+                custom::DispatchAdapter::custom(RemoteAdapter {
+                    handle: Handle {
+                        id,
+                        context: self.context.clone(),
+                    },
+                    description,
+                })
+            }),
+            Some(response) => unexpected(response),
+            None => Err(wgt::RequestAdapterError::NotFound {
+                active_backends: wgt::Backends::empty(),
+                requested_backends: wgt::Backends::empty(),
+                supported_backends: wgt::Backends::empty(),
+                no_fallback_backends: wgt::Backends::empty(),
+                no_adapter_backends: wgt::Backends::empty(),
+                incompatible_surface_backends: wgt::Backends::empty(),
+            }),
+        };
+        Box::pin(future::ready(result))
+    }
+
+    fn poll_all_devices(&self, force_wait: bool) -> bool {
+        match self.context.call(Request::PollAllDevices { force_wait }) {
+            Some(Response::Bool(all_queues_empty)) => all_queues_empty,
+            Some(response) => unexpected(response),
+            None => true,
+        }
+    }
+
+    unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        // The server doesn't let clients make captures.
+        false
+    }
+
+    unsafe fn stop_graphics_debugger_capture(&self) {
+        // The server doesn't let clients make captures.
+    }
+
+    fn wgsl_language_features(&self) -> wgpu::WgslLanguageFeatures {
+        match self.context.call(Request::WgslLanguageFeatures) {
+            Some(Response::WgslLanguageFeatures(bits)) => {
+                wgpu::WgslLanguageFeatures::from_bits_truncate(bits)
+            }
+            Some(response) => unexpected(response),
+            None => wgpu::WgslLanguageFeatures::empty(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RemoteAdapter {
+    handle: Handle,
+    description: AdapterDescription,
+}
+
+impl RemoteAdapter {
+    fn request_device_now(
+        &self,
+        desc: &wgpu::DeviceDescriptor<'_>,
+    ) -> Result<(custom::DispatchDevice, custom::DispatchQueue), wgpu::RequestDeviceError> {
+        let context = &self.handle.context;
+        let device = context.id();
+        let queue = context.id();
+        let response = context.call(Request::RequestDevice {
+            adapter: self.handle.id,
+            device,
+            queue,
+            desc: desc.map_label(|label| label.map(String::from)),
+        });
+        match response {
+            Some(Response::Device(Ok(()))) => {}
+            Some(Response::Device(Err(message))) => {
+                return Err(wgpu::RequestDeviceError::from_custom(message));
+            }
+            Some(response) => unexpected(response),
+            None => {
+                return Err(wgpu::RequestDeviceError::from_custom(
+                    "connection to the server was lost",
+                ));
+            }
+        }
+        context
+            .callbacks
+            .lock()
+            .queues
+            .insert(queue, QueueState::default());
+
+        let device = RemoteDevice {
+            handle: Handle {
+                id: device,
+                context: context.clone(),
+            },
+            features: desc.required_features,
+            limits: desc.required_limits.clone(),
+        };
+        let queue = RemoteQueue {
+            handle: Handle {
+                id: queue,
+                context: context.clone(),
+            },
+            device: device.handle.id,
+        };
+        Ok((
+            custom::DispatchDevice::custom(device),
+            custom::DispatchQueue::custom(queue),
+        ))
+    }
+}
+
+impl AdapterInterface for RemoteAdapter {
+    fn request_device(
+        &self,
+        desc: &wgpu::DeviceDescriptor<'_>,
+    ) -> Pin<Box<dyn custom::RequestDeviceFuture>> {
+        Box::pin(future::ready(self.request_device_now(desc)))
+    }
+
+    fn request_device_with_queues(
+        &self,
+        desc: &wgpu::DeviceDescriptor<'_>,
+        queues: &[wgpu::QueueDescriptor<'_>],
+    ) -> Pin<Box<dyn custom::RequestDeviceWithQueuesFuture>> {
+        let result = if queues.is_empty() {
+            self.request_device_now(desc)
+                .map(|(device, queue)| (device, queue, Vec::new()))
+        } else {
+            Err(wgpu::RequestDeviceError::from_custom(
+                "multiple queues are not supported by the remote backend",
+            ))
+        };
+        Box::pin(future::ready(result))
+    }
+
+    fn is_surface_supported(&self, _surface: &custom::DispatchSurface) -> bool {
+        false
+    }
+
+    fn features(&self) -> wgpu::Features {
+        self.description.features
+    }
+
+    fn applied_workarounds(&self) -> wgpu::Toggles {
+        self.description.applied_workarounds
+    }
+
+    fn limits(&self) -> wgpu::Limits {
+        self.description.limits.clone()
+    }
+
+    fn downlevel_capabilities(&self) -> wgpu::DownlevelCapabilities {
+        self.description.downlevel_capabilities.clone()
+    }
+
+    fn get_info(&self) -> wgpu::AdapterInfo {
+        self.description.info.clone()
+    }
+
+    fn get_texture_format_features(
+        &self,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureFormatFeatures {
+        let response = self.handle.context.call(Request::GetTextureFormatFeatures {
+            adapter: self.handle.id,
+            format,
+        });
+        match response {
+            Some(Response::TextureFormatFeatures(features)) => features,
+            Some(response) => unexpected(response),
+            None => wgpu::TextureFormatFeatures {
+                allowed_usages: wgpu::TextureUsages::empty(),
+                flags: wgpu::TextureFormatFeatureFlags::empty(),
+            },
+        }
+    }
+
+    fn get_presentation_timestamp(&self) -> wgpu::PresentationTimestamp {
+        wgpu::PresentationTimestamp::INVALID_TIMESTAMP
+    }
+
+    fn memory_info(&self) -> Option<wgpu::MemoryInfo> {
+        let response = self.handle.context.call(Request::GetMemoryInfo {
+            adapter: self.handle.id,
+        });
+        match response {
+            Some(Response::MemoryInfo(info)) => info,
+            Some(response) => unexpected(response),
+            None => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RemoteDevice {
+    handle: Handle,
+    features: wgpu::Features,
+    limits: wgpu::Limits,
+}
+
+impl RemoteDevice {
+    fn create(&self, request: impl FnOnce(Id) -> Request) -> Handle {
+        self.handle.context.create(request)
+    }
+
+    /// Creates an invalid object on the server, for a call the server can't make, and
+    /// reports `error` as its validation error.
+    fn create_invalid(&self, label: wgpu::Label<'_>, kind: InvalidObject, error: &str) -> Handle {
+        self.create(|id| Request::CreateInvalid {
+            device: self.handle.id,
+            id,
+            label: self::label(label),
+            kind,
+            error: error.to_owned(),
+        })
+    }
+
+    /// Reports `error` for a call which needs a feature `protocol::unsupported_features`
+    /// masks.
+    fn report_unsupported(&self, error: &str) {
+        self.handle
+            .context
+            .report_unsupported(self.handle.id, error);
+    }
+}
+
+/// Records a command which needs a feature `protocol::unsupported_features` masks, whose
+/// error is reported when the command buffer is created.
+fn record_unsupported(encoder: &Mutex<Vec<Command>>, error: &str) {
+    encoder.lock().push(Command::Unsupported(error.to_owned()));
+}
+
+fn label(label: wgpu::Label<'_>) -> Option<String> {
+    label.map(String::from)
+}
+
+fn stage(
+    module: &wgpu::ShaderModule,
+    entry_point: Option<&str>,
+    options: &wgpu::PipelineCompilationOptions<'_>,
+) -> protocol::ProgrammableStage {
+    protocol::ProgrammableStage {
+        module: id(module.as_custom::<RemoteShaderModule>()),
+        entry_point: entry_point.map(String::from),
+        constants: options
+            .constants
+            .iter()
+            .map(|&(name, value)| (name.to_owned(), value))
+            .collect(),
+        zero_initialize_workgroup_memory: options.zero_initialize_workgroup_memory,
+    }
+}
+
+fn buffer_binding(binding: &wgpu::BufferBinding<'_>) -> protocol::BufferBinding {
+    protocol::BufferBinding {
+        buffer: id(binding.buffer.as_custom::<RemoteBuffer>()),
+        offset: binding.offset,
+        size: binding.size,
+    }
+}
+
+fn texel_copy_buffer(info: wgpu::TexelCopyBufferInfo<'_>) -> wgt::TexelCopyBufferInfo<Id> {
+    wgt::TexelCopyBufferInfo {
+        buffer: id(info.buffer.as_custom::<RemoteBuffer>()),
+        layout: info.layout,
+    }
+}
+
+fn texel_copy_texture(info: wgpu::TexelCopyTextureInfo<'_>) -> wgt::TexelCopyTextureInfo<Id> {
+    wgt::TexelCopyTextureInfo {
+        texture: id(info.texture.as_custom::<RemoteTexture>()),
+        mip_level: info.mip_level,
+        origin: info.origin,
+        aspect: info.aspect,
+    }
+}
+
+fn pipeline_statistics_query(
+    query: &wgpu::PassPipelineStatisticsQuery<'_>,
+) -> protocol::PassPipelineStatisticsQuery {
+    protocol::PassPipelineStatisticsQuery {
+        query_set: id(query.query_set.as_custom::<RemoteQuerySet>()),
+        query_index: query.query_index,
+    }
+}
+
+impl DeviceInterface for RemoteDevice {
+    fn features(&self) -> wgpu::Features {
+        self.features
+    }
+
+    fn limits(&self) -> wgpu::Limits {
+        self.limits.clone()
+    }
+
+    fn create_shader_module(
+        &self,
+        desc: wgpu::ShaderModuleDescriptor<'_>,
+        _shader_bound_checks: wgpu::ShaderRuntimeChecks,
+        _optimization: wgpu::ShaderOptimization,
+    ) -> custom::DispatchShaderModule {
+        let (wgsl, defines) = match desc.source {
+            wgpu::ShaderSource::Wgsl(source) => (source.into_owned(), Vec::new()),
+            wgpu::ShaderSource::WgslWithDefines { shader, defines } => (
+                shader.into_owned(),
+                defines.iter().map(|&define| define.to_owned()).collect(),
+            ),
+            // The server only compiles WGSL, so other sources make an invalid module, like
+            // a shader which fails to compile.
+            _ => {
+                let error = "Only WGSL shaders are supported by the remote backend";
+                let handle = self.create_invalid(desc.label, InvalidObject::ShaderModule, error);
+                return custom::DispatchShaderModule::custom(RemoteShaderModule {
+                    handle,
+                    error: Some(error.to_owned()),
+                });
+            }
+        };
+        let handle = self.create(|id| Request::CreateShaderModule {
+            device: self.handle.id,
+            id,
+            label: label(desc.label),
+            wgsl,
+            defines,
+        });
+        custom::DispatchShaderModule::custom(RemoteShaderModule {
+            handle,
+            error: None,
+        })
+    }
+
+    unsafe fn create_shader_module_passthrough(
+        &self,
+        desc: &wgpu::ShaderModuleDescriptorPassthrough<'_>,
+    ) -> custom::DispatchShaderModule {
+        // Requires a `*_SHADER_PASSTHROUGH` feature, which `protocol::unsupported_features`
+        // masks.
+        let error = "Passthrough shaders are not supported by the remote backend";
+        let handle = self.create_invalid(*desc.label(), InvalidObject::ShaderModule, error);
+        custom::DispatchShaderModule::custom(RemoteShaderModule {
+            handle,
+            error: Some(error.to_owned()),
+        })
+    }
+
+    fn create_bind_group_layout(
+        &self,
+        desc: &wgpu::BindGroupLayoutDescriptor<'_>,
+    ) -> custom::DispatchBindGroupLayout {
+        let handle = self.create(|id| Request::CreateBindGroupLayout {
+            device: self.handle.id,
+            id,
+            label: label(desc.label),
+            entries: desc.entries.to_vec(),
+        });
+        custom::DispatchBindGroupLayout::custom(RemoteBindGroupLayout { handle })
+    }
+
+    fn create_bind_group(&self, desc: &wgpu::BindGroupDescriptor<'_>) -> custom::DispatchBindGroup {
+        let entries = desc
+            .entries
+            .iter()
+            .map(|entry| {
+                let resource = match entry.resource {
+                    wgpu::BindingResource::Buffer(ref binding) => {
+                        protocol::BindingResource::Buffer(buffer_binding(binding))
+                    }
+                    wgpu::BindingResource::BufferArray(bindings) => {
+                        protocol::BindingResource::BufferArray(
+                            bindings.iter().map(buffer_binding).collect(),
+                        )
+                    }
+                    wgpu::BindingResource::Sampler(sampler) => {
+                        protocol::BindingResource::Sampler(id(sampler.as_custom::<RemoteSampler>()))
+                    }
+                    wgpu::BindingResource::SamplerArray(samplers) => {
+                        protocol::BindingResource::SamplerArray(
+                            samplers
+                                .iter()
+                                .map(|sampler| id(sampler.as_custom::<RemoteSampler>()))
+                                .collect(),
+                        )
+                    }
+                    wgpu::BindingResource::TextureView(view) => {
+                        protocol::BindingResource::TextureView(id(
+                            view.as_custom::<RemoteTextureView>()
+                        ))
+                    }
+                    wgpu::BindingResource::TextureViewArray(views) => {
+                        protocol::BindingResource::TextureViewArray(
+                            views
+                                .iter()
+                                .map(|view| id(view.as_custom::<RemoteTextureView>()))
+                                .collect(),
+                        )
+                    }
+                    wgpu::BindingResource::AccelerationStructure(_) => {
+                        // Requires `EXPERIMENTAL_RAY_QUERY`, which `protocol::unsupported_features`
+                        // masks.
+                        return Err("Ray tracing is not supported by the remote backend");
+                    }
+                    wgpu::BindingResource::ExternalTexture(_) => {
+                        // Requires `EXTERNAL_TEXTURE`, which `protocol::unsupported_features`
+                        // masks.
+                        return Err("External textures are not supported by the remote backend");
+                    }
+                    _ => return Err("Binding resource is not supported by the remote backend"),
+                };
+                Ok(protocol::BindGroupEntry {
+                    binding: entry.binding,
+                    resource,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>();
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(error) => {
+                let handle = self.create_invalid(desc.label, InvalidObject::BindGroup, error);
+                return custom::DispatchBindGroup::custom(RemoteBindGroup { handle });
+            }
+        };
+        let handle = self.create(|id| Request::CreateBindGroup {
+            device: self.handle.id,
+            id,
+            desc: protocol::BindGroupDescriptor {
+                label: label(desc.label),
+                layout: self::id(desc.layout.as_custom::<RemoteBindGroupLayout>()),
+                entries,
+            },
+        });
+        custom::DispatchBindGroup::custom(RemoteBindGroup { handle })
+    }
+
+    fn create_pipeline_layout(
+        &self,
+        desc: &wgpu::PipelineLayoutDescriptor<'_>,
+    ) -> custom::DispatchPipelineLayout {
+        let handle = self.create(|id| Request::CreatePipelineLayout {
+            device: self.handle.id,
+            id,
+            desc: protocol::PipelineLayoutDescriptor {
+                label: label(desc.label),
+                bind_group_layouts: desc
+                    .bind_group_layouts
+                    .iter()
+                    .map(|layout| self::id(layout.as_custom::<RemoteBindGroupLayout>()))
+                    .collect(),
+                push_constant_ranges: desc.push_constant_ranges.to_vec(),
+            },
+        });
+        custom::DispatchPipelineLayout::custom(RemotePipelineLayout { handle })
+    }
+
+    fn create_render_pipeline(
+        &self,
+        desc: &wgpu::RenderPipelineDescriptor<'_>,
+    ) -> custom::DispatchRenderPipeline {
+        if desc.cache.is_some() {
+            // Requires `PIPELINE_CACHE`, which `protocol::unsupported_features` masks.
+            let handle = self.create_invalid(
+                desc.label,
+                InvalidObject::RenderPipeline,
+                "Pipeline caches are not supported by the remote backend",
+            );
+            return custom::DispatchRenderPipeline::custom(RemoteRenderPipeline { handle });
+        }
+        let vertex = &desc.vertex;
+        let desc = protocol::RenderPipelineDescriptor {
+            label: label(desc.label),
+            layout: desc
+                .layout
+                .map(|layout| id(layout.as_custom::<RemotePipelineLayout>())),
+            vertex: stage(
+                vertex.module,
+                vertex.entry_point,
+                &vertex.compilation_options,
+            ),
+            vertex_buffers: vertex
+                .buffers
+                .iter()
+                .map(|layout| layout.map_attributes(|attributes| attributes.to_vec()))
+                .collect(),
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+            fragment: desc
+                .fragment
+                .as_ref()
+                .map(|fragment| protocol::FragmentState {
+                    stage: stage(
+                        fragment.module,
+                        fragment.entry_point,
+                        &fragment.compilation_options,
+                    ),
+                    targets: fragment.targets.to_vec(),
+                }),
+            multiview: desc.multiview,
+        };
+        let handle = self.create(|id| Request::CreateRenderPipeline {
+            device: self.handle.id,
+            id,
+            desc,
+        });
+        custom::DispatchRenderPipeline::custom(RemoteRenderPipeline { handle })
+    }
+
+    fn create_mesh_pipeline(
+        &self,
+        desc: &wgpu::MeshPipelineDescriptor<'_>,
+    ) -> custom::DispatchRenderPipeline {
+        // Requires `EXPERIMENTAL_MESH_SHADER`, which `protocol::unsupported_features` masks.
+        let handle = self.create_invalid(
+            desc.label,
+            InvalidObject::RenderPipeline,
+            "Mesh shaders are not supported by the remote backend",
+        );
+        custom::DispatchRenderPipeline::custom(RemoteRenderPipeline { handle })
+    }
+
+    fn create_compute_pipeline(
+        &self,
+        desc: &wgpu::ComputePipelineDescriptor<'_>,
+    ) -> custom::DispatchComputePipeline {
+        if desc.cache.is_some() {
+            // Requires `PIPELINE_CACHE`, which `protocol::unsupported_features` masks.
+            let handle = self.create_invalid(
+                desc.label,
+                InvalidObject::ComputePipeline,
+                "Pipeline caches are not supported by the remote backend",
+            );
+            return custom::DispatchComputePipeline::custom(RemoteComputePipeline { handle });
+        }
+        let desc = protocol::ComputePipelineDescriptor {
+            label: label(desc.label),
+            layout: desc
+                .layout
+                .map(|layout| id(layout.as_custom::<RemotePipelineLayout>())),
+            stage: stage(desc.module, desc.entry_point, &desc.compilation_options),
+        };
+        let handle = self.create(|id| Request::CreateComputePipeline {
+            device: self.handle.id,
+            id,
+            desc,
+        });
+        custom::DispatchComputePipeline::custom(RemoteComputePipeline { handle })
+    }
+
+    fn create_ray_tracing_pipeline(
+        &self,
+        _desc: &wgpu::RayTracingPipelineDescriptor<'_>,
+    ) -> custom::DispatchRayTracingPipeline {
+        // Requires `EXPERIMENTAL_RAY_TRACING_PIPELINE`, which `protocol::unsupported_features`
+        // masks.
+        self.report_unsupported("Ray tracing is not supported by the remote backend");
+        custom::DispatchRayTracingPipeline::custom(Unsupported)
+    }
+
+    unsafe fn create_pipeline_cache(
+        &self,
+        _desc: &wgpu::PipelineCacheDescriptor<'_>,
+    ) -> custom::DispatchPipelineCache {
+        // Requires `PIPELINE_CACHE`, which `protocol::unsupported_features` masks.
+        self.report_unsupported("Pipeline caches are not supported by the remote backend");
+        custom::DispatchPipelineCache::custom(Unsupported)
+    }
+
+    fn create_buffer(&self, desc: &wgpu::BufferDescriptor<'_>) -> custom::DispatchBuffer {
+        let handle = self.create(|id| Request::CreateBuffer {
+            device: self.handle.id,
+            id,
+            desc: desc.map_label(|label| label.map(String::from)),
+        });
+        let mapping = desc.mapped_at_creation.then(|| Mapping {
+            mode: wgpu::MapMode::Write,
+            offset: 0,
+            memory: Arc::new(MappedMemory::new(vec![0; desc.size as usize])),
+        });
+        custom::DispatchBuffer::custom(RemoteBuffer {
+            handle,
+            mapping: Arc::new(Mutex::new(mapping)),
+        })
+    }
+
+    fn create_texture(&self, desc: &wgpu::TextureDescriptor<'_>) -> custom::DispatchTexture {
+        let handle = self.create(|id| Request::CreateTexture {
+            device: self.handle.id,
+            id,
+            desc: desc.map_label_and_view_formats(
+                |label| label.map(String::from),
+                |view_formats| view_formats.to_vec(),
+            ),
+        });
+        custom::DispatchTexture::custom(RemoteTexture { handle })
+    }
+
+    fn create_memory_heap(
+        &self,
+        _desc: &wgpu::MemoryHeapDescriptor<'_>,
+    ) -> custom::DispatchMemoryHeap {
+        // Requires `TEXTURE_ALIASING`, which `protocol::unsupported_features` masks.
+        self.report_unsupported("Memory heaps are not supported by the remote backend");
+        custom::DispatchMemoryHeap::custom(Unsupported)
+    }
+
+    fn texture_memory_requirements(
+        &self,
+        _desc: &wgpu::TextureDescriptor<'_>,
+    ) -> Option<wgpu::TextureMemoryRequirements> {
+        // Requires `TEXTURE_ALIASING`, which `protocol::unsupported_features` masks.
+        self.report_unsupported("Memory heaps are not supported by the remote backend");
+        None
+    }
+
+    fn create_texture_aliased(
+        &self,
+        _heap: &custom::DispatchMemoryHeap,
+        _offset: wgpu::BufferAddress,
+        desc: &wgpu::TextureDescriptor<'_>,
+    ) -> custom::DispatchTexture {
+        // Requires `TEXTURE_ALIASING`, which `protocol::unsupported_features` masks.
+        let handle = self.create_invalid(
+            desc.label,
+            InvalidObject::Texture,
+            "Memory heaps are not supported by the remote backend",
+        );
+        custom::DispatchTexture::custom(RemoteTexture { handle })
+    }
+
+    fn create_sparse_buffer(&self, desc: &wgpu::BufferDescriptor<'_>) -> custom::DispatchBuffer {
+        // Requires `SPARSE_BINDING`, which `protocol::unsupported_features` masks.
+        let handle = self.create_invalid(
+            desc.label,
+            InvalidObject::Buffer,
+            "Sparse resources are not supported by the remote backend",
+        );
+        custom::DispatchBuffer::custom(RemoteBuffer {
+            handle,
+            mapping: Arc::default(),
+        })
+    }
+
+    fn create_sparse_texture(&self, desc: &wgpu::TextureDescriptor<'_>) -> custom::DispatchTexture {
+        // Requires `SPARSE_BINDING`, which `protocol::unsupported_features` masks.
+        let handle = self.create_invalid(
+            desc.label,
+            InvalidObject::Texture,
+            "Sparse resources are not supported by the remote backend",
+        );
+        custom::DispatchTexture::custom(RemoteTexture { handle })
+    }
+
+    fn create_blas(
+        &self,
+        _desc: &wgpu::CreateBlasDescriptor<'_>,
+        _sizes: wgpu::BlasGeometrySizeDescriptors,
+    ) -> (Option<u64>, custom::DispatchBlas) {
+        // Requires `EXPERIMENTAL_RAY_QUERY`, which `protocol::unsupported_features` masks.
+        self.report_unsupported("Ray tracing is not supported by the remote backend");
+        (None, custom::DispatchBlas::custom(Unsupported))
+    }
+
+    fn create_blas_from_serialized(
+        &self,
+        _desc: &wgpu::CreateBlasDescriptor<'_>,
+        _sizes: wgpu::BlasGeometrySizeDescriptors,
+        _data: &[u8],
+    ) -> (Option<u64>, custom::DispatchBlas) {
+        // Requires `EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION`, which
+        // `protocol::unsupported_features` masks.
+        self.report_unsupported("Ray tracing is not supported by the remote backend");
+        (None, custom::DispatchBlas::custom(Unsupported))
+    }
+
+    fn is_serialized_blas_compatible(&self, _data: &[u8]) -> bool {
+        // No acceleration structure can be created from serialized data.
+        false
+    }
+
+    fn create_tlas(&self, _desc: &wgpu::CreateTlasDescriptor<'_>) -> custom::DispatchTlas {
+        // Requires `EXPERIMENTAL_RAY_QUERY`, which `protocol::unsupported_features` masks.
+        self.report_unsupported("Ray tracing is not supported by the remote backend");
+        custom::DispatchTlas::custom(Unsupported)
+    }
+
+    fn create_sampler(&self, desc: &wgpu::SamplerDescriptor<'_>) -> custom::DispatchSampler {
+        let handle = self.create(|id| Request::CreateSampler {
+            device: self.handle.id,
+            id,
+            desc: wgt::SamplerDescriptor {
+                label: label(desc.label),
+                address_mode_u: desc.address_mode_u,
+                address_mode_v: desc.address_mode_v,
+                address_mode_w: desc.address_mode_w,
+                mag_filter: desc.mag_filter,
+                min_filter: desc.min_filter,
+                mipmap_filter: desc.mipmap_filter,
+                lod_min_clamp: desc.lod_min_clamp,
+                lod_max_clamp: desc.lod_max_clamp,
+                compare: desc.compare,
+                anisotropy_clamp: desc.anisotropy_clamp,
+                border_color: desc.border_color,
+            },
+        });
+        custom::DispatchSampler::custom(RemoteSampler { handle })
+    }
+
+    fn create_query_set(&self, desc: &wgpu::QuerySetDescriptor<'_>) -> custom::DispatchQuerySet {
+        let handle = self.create(|id| Request::CreateQuerySet {
+            device: self.handle.id,
+            id,
+            desc: desc.map_label(|label| label.map(String::from)),
+        });
+        custom::DispatchQuerySet::custom(RemoteQuerySet { handle })
+    }
+
+    fn create_shared_fence(
+        &self,
+        _desc: &wgpu::SharedFenceDescriptor<'_>,
+    ) -> custom::DispatchSharedFence {
+        // Requires `EXPERIMENTAL_SHARED_FENCE`, which `protocol::unsupported_features` masks.
+        self.report_unsupported("Shared fences are not supported by the remote backend");
+        custom::DispatchSharedFence::custom(Unsupported)
+    }
+
+    unsafe fn import_shared_fence(
+        &self,
+        _handle: wgpu::SharedFenceHandle,
+        _desc: &wgpu::SharedFenceDescriptor<'_>,
+    ) -> custom::DispatchSharedFence {
+        // Requires `EXPERIMENTAL_SHARED_FENCE`, which `protocol::unsupported_features` masks.
+        self.report_unsupported("Shared fences are not supported by the remote backend");
+        custom::DispatchSharedFence::custom(Unsupported)
+    }
+
+    fn create_command_encoder(
+        &self,
+        desc: &wgpu::CommandEncoderDescriptor<'_>,
+    ) -> custom::DispatchCommandEncoder {
+        custom::DispatchCommandEncoder::custom(RemoteCommandEncoder {
+            context: self.handle.context.clone(),
+            device: self.handle.id,
+            desc: desc.map_label(|label| label.map(String::from)),
+            commands: Arc::default(),
+        })
+    }
+
+    fn create_command_encoder_for_queue(
+        &self,
+        _queue: &custom::DispatchQueue,
+        desc: &wgpu::CommandEncoderDescriptor<'_>,
+    ) -> custom::DispatchCommandEncoder {
+        // Devices only have a single queue.
+        self.create_command_encoder(desc)
+    }
+
+    fn create_render_bundle_encoder(
+        &self,
+        desc: &wgpu::RenderBundleEncoderDescriptor<'_>,
+    ) -> custom::DispatchRenderBundleEncoder {
+        custom::DispatchRenderBundleEncoder::custom(RemoteRenderBundleEncoder {
+            context: self.handle.context.clone(),
+            device: self.handle.id,
+            desc: Some(protocol::RenderBundleEncoderDescriptor {
+                label: label(desc.label),
+                color_formats: desc.color_formats.to_vec(),
+                depth_stencil: desc.depth_stencil,
+                sample_count: desc.sample_count,
+                multiview: desc.multiview,
+                inheritance: desc.inheritance,
+            }),
+            commands: Vec::new(),
+        })
+    }
+
+    fn set_device_lost_callback(&self, device_lost_callback: custom::BoxDeviceLostCallback) {
+        let context = &self.handle.context;
+        context
+            .callbacks
+            .lock()
+            .device_lost
+            .insert(self.handle.id, device_lost_callback);
+        if !context.is_connected() {
+            let callback = context.callbacks.lock().device_lost.remove(&self.handle.id);
+            if let Some(callback) = callback {
+                callback(
+                    wgpu::DeviceLostReason::Unknown,
+                    "connection to the server was lost".to_owned(),
+                );
+            }
+        }
+    }
+
+    fn set_memory_budget_callback(&self, _memory_budget_callback: custom::ArcMemoryBudgetCallback) {
+        // Memory budgets aren't reported by the server.
+    }
+
+    fn on_uncaptured_error(&self, handler: Box<dyn wgpu::UncapturedErrorHandler>) {
+        self.handle
+            .context
+            .callbacks
+            .lock()
+            .uncaptured_error_handlers
+            .insert(self.handle.id, handler);
+    }
+
+    fn push_error_scope(&self, filter: wgpu::ErrorFilter) {
+        self.handle.context.send(Request::PushErrorScope {
+            device: self.handle.id,
+            filter: filter.into(),
+        });
+    }
+
+    fn pop_error_scope(&self) -> Pin<Box<dyn custom::PopErrorScopeFuture>> {
+        let response = self.handle.context.call(Request::PopErrorScope {
+            device: self.handle.id,
+        });
+        let error = match response {
+            Some(Response::Error(error)) => error.map(wgpu::Error::from),
+            Some(response) => unexpected(response),
+            None => None,
+        };
+        Box::pin(future::ready(error))
+    }
+
+    unsafe fn start_graphics_debugger_capture(&self) {
+        // The server doesn't let clients make captures.
+    }
+
+    unsafe fn stop_graphics_debugger_capture(&self) {
+        // The server doesn't let clients make captures.
+    }
+
+    fn poll(&self, poll_type: wgt::PollType<u64>) -> Result<wgpu::PollStatus, wgpu::PollError> {
+        let response = self.handle.context.call(Request::Poll {
+            device: self.handle.id,
+            poll_type,
+        });
+        match response {
+            Some(Response::Poll(result)) => result,
+            Some(response) => unexpected(response),
+            None => Ok(wgpu::PollStatus::QueueEmpty),
+        }
+    }
+
+    fn get_internal_counters(&self) -> wgpu::InternalCounters {
+        wgpu::InternalCounters::default()
+    }
+
+    fn generate_allocator_report(&self) -> Option<wgt::AllocatorReport> {
+        None
+    }
+
+    fn generate_memory_report(&self, _include_resources: bool) -> Option<wgt::MemoryReport> {
+        None
+    }
+
+    fn trim_memory(&self) -> wgt::TrimMemoryReport {
+        wgt::TrimMemoryReport::default()
+    }
+
+    fn set_barrier_logging(&self, _enabled: bool) {}
+
+    fn take_barrier_report(&self) -> Option<wgt::BarrierReport> {
+        None
+    }
+
+    fn destroy(&self) {
+        self.handle
+            .context
+            .send(Request::DestroyDevice(self.handle.id));
+    }
+}
+
+#[derive(Debug)]
+struct RemoteQueue {
+    handle: Handle,
+    device: Id,
+}
+
+impl QueueInterface for RemoteQueue {
+    fn write_buffer(
+        &self,
+        buffer: &custom::DispatchBuffer,
+        offset: wgpu::BufferAddress,
+        data: &[u8],
+    ) {
+        self.handle.context.send(Request::WriteBuffer {
+            queue: self.handle.id,
+            buffer: id(buffer.as_custom::<RemoteBuffer>()),
+            offset,
+            data: data.to_vec(),
+        });
+    }
+
+    fn create_staging_buffer(
+        &self,
+        size: wgpu::BufferSize,
+    ) -> Option<custom::DispatchQueueWriteBuffer> {
+        Some(custom::DispatchQueueWriteBuffer::custom(StagingBuffer(
+            vec![0; size.get() as usize],
+        )))
+    }
+
+    fn validate_write_buffer(
+        &self,
+        _buffer: &custom::DispatchBuffer,
+        _offset: wgpu::BufferAddress,
+        _size: wgpu::BufferSize,
+    ) -> Option<()> {
+        // The write is validated by the server.
+        Some(())
+    }
+
+    fn write_staging_buffer(
+        &self,
+        buffer: &custom::DispatchBuffer,
+        offset: wgpu::BufferAddress,
+        staging_buffer: &custom::DispatchQueueWriteBuffer,
+    ) {
+        let staging_buffer = staging_buffer
+            .as_custom::<StagingBuffer>()
+            .expect("staging buffers of other backends can't be used with the remote backend");
+        self.write_buffer(buffer, offset, &staging_buffer.0);
+    }
+
+    fn write_texture(
+        &self,
+        texture: wgpu::TexelCopyTextureInfo<'_>,
+        data: &[u8],
+        data_layout: wgpu::TexelCopyBufferLayout,
+        size: wgpu::Extent3d,
+    ) {
+        self.handle.context.send(Request::WriteTexture {
+            queue: self.handle.id,
+            texture: texel_copy_texture(texture),
+            data: data.to_vec(),
+            layout: data_layout,
+            size,
+        });
+    }
+
+    fn submit(
+        &self,
+        command_buffers: &mut dyn Iterator<Item = custom::DispatchCommandBuffer>,
+    ) -> u64 {
+        // Keep the command buffers alive until they are submitted.
+        let command_buffers = command_buffers.collect::<Vec<_>>();
+        let context = &self.handle.context;
+        let index = match context.callbacks.lock().queues.get_mut(&self.handle.id) {
+            Some(state) => {
+                state.submitted += 1;
+                state.submitted
+            }
+            None => 0,
+        };
+        context.send(Request::Submit {
+            queue: self.handle.id,
+            index,
+            command_buffers: command_buffers
+                .iter()
+                .map(|command_buffer| id(command_buffer.as_custom::<RemoteCommandBuffer>()))
+                .collect(),
+        });
+        index
+    }
+
+    fn submit_with_external_sync(
+        &self,
+        _command_buffers: &mut dyn Iterator<Item = custom::DispatchCommandBuffer>,
+        _wait: &[wgt::SharedFenceValue<&custom::DispatchSharedFence>],
+        _signal: &[wgt::SharedFenceValue<&custom::DispatchSharedFence>],
+    ) -> u64 {
+        // Requires `EXPERIMENTAL_SHARED_FENCE`, which `protocol::unsupported_features` masks.
+        // Like a submission which fails validation, the command buffers are consumed without
+        // running, and the returned index is that of an empty submission.
+        self.handle.context.report_unsupported(
+            self.device,
+            "Shared fences are not supported by the remote backend",
+        );
+        self.submit(&mut std::iter::empty::<custom::DispatchCommandBuffer>())
+    }
+
+    fn bind_sparse_memory(
+        &self,
+        _buffer_bindings: &[wgt::SparseBufferBinding<
+            &custom::DispatchBuffer,
+            &custom::DispatchMemoryHeap,
+        >],
+        _texture_bindings: &[wgt::SparseTextureBinding<
+            &custom::DispatchTexture,
+            &custom::DispatchMemoryHeap,
+        >],
+    ) {
+        // Requires `SPARSE_BINDING`, which `protocol::unsupported_features` masks.
+        self.handle.context.report_unsupported(
+            self.device,
+            "Sparse resources are not supported by the remote backend",
+        );
+    }
+
+    fn get_timestamp_period(&self) -> f32 {
+        let response = self.handle.context.call(Request::GetTimestampPeriod {
+            queue: self.handle.id,
+        });
+        match response {
+            Some(Response::F32(period)) => period,
+            Some(response) => unexpected(response),
+            None => 1.0,
+        }
+    }
+
+    fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
+    fn on_submitted_work_done(&self, callback: custom::BoxSubmittedWorkDoneCallback) {
+        let submitted = match self
+            .handle
+            .context
+            .callbacks
+            .lock()
+            .queues
+            .get(&self.handle.id)
+        {
+            Some(state) => state.submitted,
+            None => 0,
+        };
+        self.on_submission_done(submitted, callback);
+    }
+
+    fn on_submission_done(
+        &self,
+        submission_index: u64,
+        callback: custom::BoxSubmittedWorkDoneCallback,
+    ) {
+        let mut callbacks = self.handle.context.callbacks.lock();
+        match callbacks.queues.get_mut(&self.handle.id) {
+            Some(state) if submission_index > state.completed => {
+                state.callbacks.push((submission_index, callback));
+            }
+            // The submission is done, or the connection is lost.
+            _ => {
+                drop(callbacks);
+                callback();
+            }
+        }
+    }
+
+    fn get_completed_submission_index(&self) -> u64 {
+        match self
+            .handle
+            .context
+            .callbacks
+            .lock()
+            .queues
+            .get(&self.handle.id)
+        {
+            Some(state) => state.completed,
+            None => u64::MAX,
+        }
+    }
+
+    fn poll(&self, poll_type: wgt::PollType<u64>) -> Result<wgpu::PollStatus, wgpu::PollError> {
+        let response = self.handle.context.call(Request::Poll {
+            device: self.device,
+            poll_type,
+        });
+        match response {
+            Some(Response::Poll(result)) => result,
+            Some(response) => unexpected(response),
+            None => Ok(wgpu::PollStatus::QueueEmpty),
+        }
+    }
+
+    fn wait_for_submission(&self, _submission_index: u64) {
+        // Devices only have a single queue.
+    }
+
+    fn compact_blas(&self, _blas: &custom::DispatchBlas) -> (Option<u64>, custom::DispatchBlas) {
+        // Requires `EXPERIMENTAL_RAY_QUERY`, which `protocol::unsupported_features` masks.
+        self.handle.context.report_unsupported(
+            self.device,
+            "Ray tracing is not supported by the remote backend",
+        );
+        (None, custom::DispatchBlas::custom(Unsupported))
+    }
+}
+
+/// The staging memory of [`wgpu::Queue::write_buffer_with`], which is sent to the server
+/// when it is written to the buffer.
+#[derive(Debug)]
+struct StagingBuffer(Vec<u8>);
+
+impl QueueWriteBufferInterface for StagingBuffer {
+    fn slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn slice_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+#[derive(Debug)]
+struct RemoteShaderModule {
+    handle: Handle,
+    /// The error of a module created from a source which isn't sent to the server.
+    error: Option<String>,
+}
+
+impl Remote for RemoteShaderModule {
+    fn handle(&self) -> &Handle {
+        &self.handle
+    }
+}
+
+impl ShaderModuleInterface for RemoteShaderModule {
+    fn get_compilation_info(&self) -> Pin<Box<dyn custom::ShaderCompilationInfoFuture>> {
+        if let Some(ref error) = self.error {
+            return Box::pin(future::ready(wgpu::CompilationInfo {
+                messages: vec![wgpu::CompilationMessage {
+                    message: error.clone(),
+                    message_type: wgpu::CompilationMessageType::Error,
+                    location: None,
+                    file_name: None,
+                }],
+            }));
+        }
+        let response = self.handle.context.call(Request::GetCompilationInfo {
+            shader_module: self.handle.id,
+        });
+        let messages = match response {
+            Some(Response::CompilationInfo(messages)) => {
+                messages.into_iter().map(Into::into).collect()
+            }
+            Some(response) => unexpected(response),
+            None => Vec::new(),
+        };
+        Box::pin(future::ready(wgpu::CompilationInfo { messages }))
+    }
+
+    fn binding(&self, name: &str) -> Option<(u32, u32)> {
+        let response = self.handle.context.call(Request::GetShaderBinding {
+            shader_module: self.handle.id,
+            name: name.to_owned(),
+        });
+        match response {
+            Some(Response::ShaderBinding(binding)) => binding,
+            Some(response) => unexpected(response),
+            None => None,
+        }
+    }
+
+    fn workgroup_memory(&self, entry_point: &str) -> Option<wgt::WorkgroupMemoryInfo> {
+        let response = self.handle.context.call(Request::GetWorkgroupMemory {
+            shader_module: self.handle.id,
+            entry_point: entry_point.to_owned(),
+        });
+        match response {
+            Some(Response::WorkgroupMemory(info)) => info,
+            Some(response) => unexpected(response),
+            None => None,
+        }
+    }
+
+    fn check_layout(
+        &self,
+        _name: &str,
+        _layout: &wgt::ShaderTypeLayout,
+    ) -> Result<(), wgt::ShaderLayoutError> {
+        // Layouts are made of `'static` references, which the server can't deserialize.
+        Err(wgt::ShaderLayoutError::NotReflected)
+    }
+}
+
+fn get_bind_group_layout(pipeline: &Handle, index: u32) -> custom::DispatchBindGroupLayout {
+    let handle = pipeline.context.create(|id| Request::GetBindGroupLayout {
+        pipeline: pipeline.id,
+        index,
+        id,
+    });
+    custom::DispatchBindGroupLayout::custom(RemoteBindGroupLayout { handle })
+}
+
+impl RenderPipelineInterface for RemoteRenderPipeline {
+    fn get_bind_group_layout(&self, index: u32) -> custom::DispatchBindGroupLayout {
+        get_bind_group_layout(&self.handle, index)
+    }
+}
+
+impl ComputePipelineInterface for RemoteComputePipeline {
+    fn get_bind_group_layout(&self, index: u32) -> custom::DispatchBindGroupLayout {
+        get_bind_group_layout(&self.handle, index)
+    }
+}
+
+impl TextureInterface for RemoteTexture {
+    fn create_view(&self, desc: &wgpu::TextureViewDescriptor<'_>) -> custom::DispatchTextureView {
+        let handle = self.handle.context.create(|id| Request::CreateTextureView {
+            texture: self.handle.id,
+            id,
+            desc: wgt::TextureViewDescriptor {
+                label: label(desc.label),
+                format: desc.format,
+                dimension: desc.dimension,
+                usage: desc.usage,
+                aspect: desc.aspect,
+                base_mip_level: desc.base_mip_level,
+                mip_level_count: desc.mip_level_count,
+                base_array_layer: desc.base_array_layer,
+                array_layer_count: desc.array_layer_count,
+            },
+        });
+        custom::DispatchTextureView::custom(RemoteTextureView { handle })
+    }
+
+    fn destroy(&self) {
+        self.handle
+            .context
+            .send(Request::DestroyTexture(self.handle.id));
+    }
+}
+
+#[derive(Debug)]
+struct RemoteBuffer {
+    handle: Handle,
+    mapping: Arc<Mutex<Option<Mapping>>>,
+}
+
+impl Remote for RemoteBuffer {
+    fn handle(&self) -> &Handle {
+        &self.handle
+    }
+}
+
+/// A mapped range of a buffer, with a copy of its contents.
+#[derive(Debug)]
+struct Mapping {
+    mode: wgpu::MapMode,
+    offset: wgpu::BufferAddress,
+    memory: Arc<MappedMemory>,
+}
+
+/// The memory of a [`Mapping`], which the [`MappedRange`]s point into.
+///
+/// Mapped ranges get mutable slices of the memory while others exist, so it is only
+/// accessed through raw pointers.
+#[derive(Debug)]
+struct MappedMemory(NonNull<[u8]>);
+
+// SAFETY: The memory is owned, and `wgpu` checks that the mapped ranges don't overlap.
+unsafe impl Send for MappedMemory {}
+// SAFETY: See above.
+unsafe impl Sync for MappedMemory {}
+
+impl MappedMemory {
+    fn new(data: Vec<u8>) -> Self {
+        Self(NonNull::from(Box::leak(data.into_boxed_slice())))
+    }
+
+    fn to_vec(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.0.len());
+        // SAFETY: The memory is valid for `len` bytes, and `data` has the capacity for them.
+        unsafe {
+            self.0
+                .cast::<u8>()
+                .as_ptr()
+                .copy_to_nonoverlapping(data.as_mut_ptr(), self.0.len());
+            data.set_len(self.0.len());
+        }
+        data
+    }
+}
+
+impl Drop for MappedMemory {
+    fn drop(&mut self) {
+        // SAFETY: The memory was leaked from a `Box` by `new`.
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
+impl BufferInterface for RemoteBuffer {
+    fn map_async(
+        &self,
+        mode: wgpu::MapMode,
+        range: Range<wgpu::BufferAddress>,
+        callback: custom::BufferMapCallback,
+    ) {
+        let context = &self.handle.context;
+        let map = context.id();
+        context.callbacks.lock().maps.insert(
+            map,
+            PendingMap {
+                mapping: self.mapping.clone(),
+                mode,
+                offset: range.start,
+                callback,
+            },
+        );
+        context.send(Request::MapBuffer {
+            buffer: self.handle.id,
+            map,
+            mode: mode.into(),
+            range,
+        });
+        if !context.is_connected() {
+            let pending = context.callbacks.lock().maps.remove(&map);
+            if let Some(pending) = pending {
+                (pending.callback)(Err(wgpu::BufferAsyncError));
+            }
+        }
+    }
+
+    fn get_mapped_range(
+        &self,
+        sub_range: Range<wgpu::BufferAddress>,
+    ) -> custom::DispatchBufferMappedRange {
+        let mapping = self.mapping.lock();
+        let mapping = mapping.as_ref().expect("buffer is not mapped");
+        let offset = (sub_range.start - mapping.offset) as usize;
+        let size = (sub_range.end - sub_range.start) as usize;
+        assert!(offset + size <= mapping.memory.0.len());
+        custom::DispatchBufferMappedRange::custom(MappedRange {
+            memory: mapping.memory.clone(),
+            // SAFETY: The range is within the memory, as checked above.
+            ptr: unsafe { mapping.memory.0.cast::<u8>().add(offset) },
+            size,
+        })
+    }
+
+    fn unmap(&self) {
+        let data = match self.mapping.lock().take() {
+            Some(Mapping {
+                mode: wgpu::MapMode::Write,
+                memory,
+                ..
+            }) => Some(memory.to_vec()),
+            _ => None,
+        };
+        self.handle.context.send(Request::UnmapBuffer {
+            buffer: self.handle.id,
+            data,
+        });
+    }
+
+    fn destroy(&self) {
+        self.handle
+            .context
+            .send(Request::DestroyBuffer(self.handle.id));
+    }
+}
+
+#[derive(Debug)]
+struct MappedRange {
+    /// Keeps the memory `ptr` points into alive.
+    #[expect(dead_code)]
+    memory: Arc<MappedMemory>,
+    ptr: NonNull<u8>,
+    size: usize,
+}
+
+// SAFETY: The range points into memory it keeps alive, which is `Send` and `Sync`.
+unsafe impl Send for MappedRange {}
+// SAFETY: See above.
+unsafe impl Sync for MappedRange {}
+
+impl BufferMappedRangeInterface for MappedRange {
+    fn slice(&self) -> &[u8] {
+        // SAFETY: The range is within the memory, which `self.memory` keeps alive.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.size) }
+    }
+
+    fn slice_mut(&mut self) -> &mut [u8] {
+        // SAFETY: As above, and the mapped ranges of a buffer don't overlap.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.size) }
+    }
+}
+
+#[derive(Debug)]
+struct RemoteCommandEncoder {
+    context: Arc<Context>,
+    device: Id,
+    desc: wgt::CommandEncoderDescriptor<Option<String>>,
+    /// The commands recorded so far, which passes add themselves to when they end.
+    commands: Arc<Mutex<Vec<Command>>>,
+}
+
+impl RemoteCommandEncoder {
+    fn record(&self, command: Command) {
+        self.commands.lock().push(command);
+    }
+}
+
+impl CommandEncoderInterface for RemoteCommandEncoder {
+    fn copy_buffer_to_buffer(
+        &self,
+        source: &custom::DispatchBuffer,
+        source_offset: wgpu::BufferAddress,
+        destination: &custom::DispatchBuffer,
+        destination_offset: wgpu::BufferAddress,
+        copy_size: Option<wgpu::BufferAddress>,
+    ) {
+        self.record(Command::CopyBufferToBuffer {
+            source: id(source.as_custom::<RemoteBuffer>()),
+            source_offset,
+            destination: id(destination.as_custom::<RemoteBuffer>()),
+            destination_offset,
+            size: copy_size,
+        });
+    }
+
+    fn copy_buffer_to_texture(
+        &self,
+        source: wgpu::TexelCopyBufferInfo<'_>,
+        destination: wgpu::TexelCopyTextureInfo<'_>,
+        copy_size: wgpu::Extent3d,
+    ) {
+        self.record(Command::CopyBufferToTexture {
+            source: texel_copy_buffer(source),
+            destination: texel_copy_texture(destination),
+            size: copy_size,
+        });
+    }
+
+    fn copy_texture_to_buffer(
+        &self,
+        source: wgpu::TexelCopyTextureInfo<'_>,
+        destination: wgpu::TexelCopyBufferInfo<'_>,
+        copy_size: wgpu::Extent3d,
+    ) {
+        self.record(Command::CopyTextureToBuffer {
+            source: texel_copy_texture(source),
+            destination: texel_copy_buffer(destination),
+            size: copy_size,
+        });
+    }
+
+    fn copy_texture_to_texture(
+        &self,
+        source: wgpu::TexelCopyTextureInfo<'_>,
+        destination: wgpu::TexelCopyTextureInfo<'_>,
+        copy_size: wgpu::Extent3d,
+    ) {
+        self.record(Command::CopyTextureToTexture {
+            source: texel_copy_texture(source),
+            destination: texel_copy_texture(destination),
+            size: copy_size,
+        });
+    }
+
+    fn resolve_texture(
+        &self,
+        source: &custom::DispatchTextureView,
+        destination: &custom::DispatchTextureView,
+        regions: &[wgpu::Rect],
+    ) {
+        self.record(Command::ResolveTexture {
+            source: id(source.as_custom::<RemoteTextureView>()),
+            destination: id(destination.as_custom::<RemoteTextureView>()),
+            regions: regions.to_vec(),
+        });
+    }
+
+    fn begin_compute_pass(
+        &self,
+        desc: &wgpu::ComputePassDescriptor<'_>,
+    ) -> custom::DispatchComputePass {
+        let desc = protocol::ComputePassDescriptor {
+            label: label(desc.label),
+            timestamp_writes: desc.timestamp_writes.as_ref().map(|writes| {
+                protocol::PassTimestampWrites {
+                    query_set: id(writes.query_set.as_custom::<RemoteQuerySet>()),
+                    beginning_of_pass_write_index: writes.beginning_of_pass_write_index,
+                    end_of_pass_write_index: writes.end_of_pass_write_index,
+                }
+            }),
+            pipeline_statistics_query: desc
+                .pipeline_statistics_query
+                .as_ref()
+                .map(pipeline_statistics_query),
+        };
+        custom::DispatchComputePass::custom(RemoteComputePass {
+            encoder: self.commands.clone(),
+            desc: Some(desc),
+            commands: Vec::new(),
+        })
+    }
+
+    fn begin_render_pass(
+        &self,
+        desc: &wgpu::RenderPassDescriptor<'_>,
+    ) -> custom::DispatchRenderPass {
+        let view = |view: &wgpu::TextureView| id(view.as_custom::<RemoteTextureView>());
+        let desc = protocol::RenderPassDescriptor {
+            label: label(desc.label),
+            color_attachments: desc
+                .color_attachments
+                .iter()
+                .map(|attachment| {
+                    attachment
+                        .as_ref()
+                        .map(|attachment| protocol::RenderPassColorAttachment {
+                            view: view(attachment.view),
+                            depth_slice: attachment.depth_slice,
+                            resolve_target: attachment.resolve_target.map(view),
+                            ops: attachment.ops,
+                        })
+                })
+                .collect(),
+            depth_stencil_attachment: desc.depth_stencil_attachment.as_ref().map(|attachment| {
+                protocol::RenderPassDepthStencilAttachment {
+                    view: view(attachment.view),
+                    depth_ops: attachment.depth_ops,
+                    stencil_ops: attachment.stencil_ops,
+                    resolve: attachment.resolve.map(|resolve| wgt::DepthStencilResolve {
+                        target: view(resolve.target),
+                        depth_mode: resolve.depth_mode,
+                        resolve_stencil: resolve.resolve_stencil,
+                    }),
+                }
+            }),
+            timestamp_writes: desc.timestamp_writes.as_ref().map(|writes| {
+                protocol::PassTimestampWrites {
+                    query_set: id(writes.query_set.as_custom::<RemoteQuerySet>()),
+                    beginning_of_pass_write_index: writes.beginning_of_pass_write_index,
+                    end_of_pass_write_index: writes.end_of_pass_write_index,
+                }
+            }),
+            occlusion_query_set: desc
+                .occlusion_query_set
+                .map(|query_set| id(query_set.as_custom::<RemoteQuerySet>())),
+            pipeline_statistics_query: desc
+                .pipeline_statistics_query
+                .as_ref()
+                .map(pipeline_statistics_query),
+            flags: desc.flags,
+            multiview: desc.multiview,
+        };
+        custom::DispatchRenderPass::custom(RemoteRenderPass {
+            encoder: self.commands.clone(),
+            target: Some(RenderPassTarget::Encoder(desc)),
+            commands: Vec::new(),
+            parallel_encoders: Vec::new(),
+        })
+    }
+
+    fn finish(&mut self, reusable: bool) -> custom::DispatchCommandBuffer {
+        let commands = mem::take(&mut *self.commands.lock());
+        // `REUSABLE_COMMAND_BUFFERS` is never enabled, so the server reports a validation
+        // error for reusable command buffers.
+        let handle = self.context.create(|id| Request::CreateCommandBuffer {
+            device: self.device,
+            id,
+            desc: self.desc.clone(),
+            commands,
+            reusable,
+        });
+        custom::DispatchCommandBuffer::custom(RemoteCommandBuffer { handle })
+    }
+
+    fn clear_texture(
+        &self,
+        texture: &custom::DispatchTexture,
+        subresource_range: &wgpu::ImageSubresourceRange,
+    ) {
+        self.record(Command::ClearTexture {
+            texture: id(texture.as_custom::<RemoteTexture>()),
+            range: *subresource_range,
+        });
+    }
+
+    fn clear_buffer(
+        &self,
+        buffer: &custom::DispatchBuffer,
+        offset: wgpu::BufferAddress,
+        size: Option<wgpu::BufferAddress>,
+    ) {
+        self.record(Command::ClearBuffer {
+            buffer: id(buffer.as_custom::<RemoteBuffer>()),
+            offset,
+            size,
+        });
+    }
+
+    fn insert_debug_marker(&self, label: &str) {
+        self.record(Command::InsertDebugMarker(label.to_owned()));
+    }
+
+    fn push_debug_group(&self, label: &str) {
+        self.record(Command::PushDebugGroup(label.to_owned()));
+    }
+
+    fn pop_debug_group(&self) {
+        self.record(Command::PopDebugGroup);
+    }
+
+    fn write_timestamp(&self, query_set: &custom::DispatchQuerySet, query_index: u32) {
+        self.record(Command::WriteTimestamp {
+            query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+            index: query_index,
+        });
+    }
+
+    fn resolve_query_set(
+        &self,
+        query_set: &custom::DispatchQuerySet,
+        first_query: u32,
+        query_count: u32,
+        destination: &custom::DispatchBuffer,
+        destination_offset: wgpu::BufferAddress,
+    ) {
+        self.record(Command::ResolveQuerySet {
+            query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+            queries: first_query..first_query + query_count,
+            destination: id(destination.as_custom::<RemoteBuffer>()),
+            destination_offset,
+        });
+    }
+
+    fn mark_acceleration_structures_built<'a>(
+        &self,
+        _blas: &mut dyn Iterator<Item = &'a wgpu::Blas>,
+        _tlas: &mut dyn Iterator<Item = &'a wgpu::Tlas>,
+    ) {
+        // Requires `EXPERIMENTAL_RAY_QUERY`, which `protocol::unsupported_features` masks.
+        record_unsupported(
+            &self.commands,
+            "Ray tracing is not supported by the remote backend",
+        );
+    }
+
+    fn build_acceleration_structures<'a>(
+        &self,
+        _blas: &mut dyn Iterator<Item = &'a wgpu::BlasBuildEntry<'a>>,
+        _tlas: &mut dyn Iterator<Item = &'a wgpu::Tlas>,
+    ) {
+        // Requires `EXPERIMENTAL_RAY_QUERY`, which `protocol::unsupported_features` masks.
+        record_unsupported(
+            &self.commands,
+            "Ray tracing is not supported by the remote backend",
+        );
+    }
+
+    fn trace_rays(&self, _desc: &wgpu::TraceRaysDescriptor<'_>) {
+        // Requires `EXPERIMENTAL_RAY_TRACING_PIPELINE`, which `protocol::unsupported_features`
+        // masks.
+        record_unsupported(
+            &self.commands,
+            "Ray tracing is not supported by the remote backend",
+        );
+    }
+
+    fn transition_resources<'a>(
+        &mut self,
+        _buffer_transitions: &mut dyn Iterator<
+            Item = wgt::BufferTransition<&'a custom::DispatchBuffer>,
+        >,
+        _texture_transitions: &mut dyn Iterator<
+            Item = wgt::TextureTransition<&'a custom::DispatchTexture>,
+        >,
+    ) {
+        // Transitions are only hints, which the server does without.
+    }
+}
+
+/// A compute pass, which records its commands until it ends.
+#[derive(Debug)]
+struct RemoteComputePass {
+    encoder: Arc<Mutex<Vec<Command>>>,
+    /// The descriptor of the pass, which is taken when it ends.
+    desc: Option<protocol::ComputePassDescriptor>,
+    commands: Vec<ComputeCommand>,
+}
+
+impl ComputePassInterface for RemoteComputePass {
+    fn set_pipeline(&mut self, pipeline: &custom::DispatchComputePipeline) {
+        self.commands.push(ComputeCommand::SetPipeline(id(pipeline
+            .as_custom::<RemoteComputePipeline>(
+        ))));
+    }
+
+    fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: Option<&custom::DispatchBindGroup>,
+        offsets: &[wgpu::DynamicOffset],
+    ) {
+        self.commands.push(ComputeCommand::SetBindGroup {
+            index,
+            bind_group: bind_group.map(|bind_group| id(bind_group.as_custom::<RemoteBindGroup>())),
+            offsets: offsets.to_vec(),
+        });
+    }
+
+    fn set_push_constants(&mut self, offset: u32, data: &[u8]) {
+        self.commands.push(ComputeCommand::SetPushConstants {
+            offset,
+            data: data.to_vec(),
+        });
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        self.commands
+            .push(ComputeCommand::InsertDebugMarker(label.to_owned()));
+    }
+
+    fn push_debug_group(&mut self, group_label: &str) {
+        self.commands
+            .push(ComputeCommand::PushDebugGroup(group_label.to_owned()));
+    }
+
+    fn pop_debug_group(&mut self) {
+        self.commands.push(ComputeCommand::PopDebugGroup);
+    }
+
+    fn write_timestamp(&mut self, query_set: &custom::DispatchQuerySet, query_index: u32) {
+        self.commands.push(ComputeCommand::WriteTimestamp {
+            query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+            index: query_index,
+        });
+    }
+
+    fn begin_pipeline_statistics_query(
+        &mut self,
+        query_set: &custom::DispatchQuerySet,
+        query_index: u32,
+    ) {
+        self.commands
+            .push(ComputeCommand::BeginPipelineStatisticsQuery {
+                query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+                index: query_index,
+            });
+    }
+
+    fn end_pipeline_statistics_query(&mut self) {
+        self.commands
+            .push(ComputeCommand::EndPipelineStatisticsQuery);
+    }
+
+    fn dispatch_workgroups(&mut self, x: u32, y: u32, z: u32) {
+        self.commands.push(ComputeCommand::Dispatch([x, y, z]));
+    }
+
+    fn dispatch_workgroups_indirect(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+    ) {
+        self.commands.push(ComputeCommand::DispatchIndirect {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+        });
+    }
+
+    fn end(&mut self) {
+        if let Some(desc) = self.desc.take() {
+            self.encoder.lock().push(Command::ComputePass {
+                desc,
+                commands: mem::take(&mut self.commands),
+            });
+        }
+    }
+}
+
+impl Drop for RemoteComputePass {
+    fn drop(&mut self) {
+        self.end();
+    }
+}
+
+/// The commands of a parallel encoder, which it fills in when it ends.
+type ParallelEncoderCommands = Arc<Mutex<Option<Vec<RenderCommand>>>>;
+
+/// Where the commands of a render pass go when it ends.
+#[derive(Debug)]
+enum RenderPassTarget {
+    /// A pass of the command encoder, with the descriptor of the pass.
+    Encoder(protocol::RenderPassDescriptor),
+    /// A parallel encoder of another pass.
+    ParallelEncoder(ParallelEncoderCommands),
+}
+
+/// A render pass, which records its commands until it ends.
+#[derive(Debug)]
+struct RemoteRenderPass {
+    encoder: Arc<Mutex<Vec<Command>>>,
+    /// Where the commands go, which is taken when the pass ends.
+    target: Option<RenderPassTarget>,
+    commands: Vec<RenderCommand>,
+    /// The parallel encoders created by the pass, with the index of their command in
+    /// `commands`.
+    parallel_encoders: Vec<(usize, ParallelEncoderCommands)>,
+}
+
+impl RenderPassInterface for RemoteRenderPass {
+    fn set_pipeline(&mut self, pipeline: &custom::DispatchRenderPipeline) {
+        self.commands.push(RenderCommand::SetPipeline(id(
+            pipeline.as_custom::<RemoteRenderPipeline>()
+        )));
+    }
+
+    fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: Option<&custom::DispatchBindGroup>,
+        offsets: &[wgpu::DynamicOffset],
+    ) {
+        self.commands.push(RenderCommand::SetBindGroup {
+            index,
+            bind_group: bind_group.map(|bind_group| id(bind_group.as_custom::<RemoteBindGroup>())),
+            offsets: offsets.to_vec(),
+        });
+    }
+
+    fn set_index_buffer(
+        &mut self,
+        buffer: &custom::DispatchBuffer,
+        index_format: wgpu::IndexFormat,
+        offset: wgpu::BufferAddress,
+        size: Option<wgpu::BufferSize>,
+    ) {
+        self.commands.push(RenderCommand::SetIndexBuffer {
+            buffer: id(buffer.as_custom::<RemoteBuffer>()),
+            format: index_format,
+            offset,
+            size,
+        });
+    }
+
+    fn set_vertex_buffer(
+        &mut self,
+        slot: u32,
+        buffer: &custom::DispatchBuffer,
+        offset: wgpu::BufferAddress,
+        size: Option<wgpu::BufferSize>,
+    ) {
+        self.commands.push(RenderCommand::SetVertexBuffer {
+            slot,
+            buffer: id(buffer.as_custom::<RemoteBuffer>()),
+            offset,
+            size,
+        });
+    }
+
+    fn set_push_constants(&mut self, stages: wgpu::ShaderStages, offset: u32, data: &[u8]) {
+        self.commands.push(RenderCommand::SetPushConstants {
+            stages,
+            offset,
+            data: data.to_vec(),
+        });
+    }
+
+    fn set_blend_constant(&mut self, color: wgpu::Color) {
+        self.commands.push(RenderCommand::SetBlendConstant(color));
+    }
+
+    fn set_scissor_rect(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        self.commands.push(RenderCommand::SetScissorRect {
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    fn set_viewport(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        min_depth: f32,
+        max_depth: f32,
+    ) {
+        self.commands.push(RenderCommand::SetViewport {
+            x,
+            y,
+            width,
+            height,
+            depth: min_depth..max_depth,
+        });
+    }
+
+    fn set_stencil_reference(&mut self, reference: u32) {
+        self.commands
+            .push(RenderCommand::SetStencilReference(reference));
+    }
+
+    fn set_shading_rate(&mut self, rate: wgpu::ShadingRate) {
+        self.commands.push(RenderCommand::SetShadingRate(rate));
+    }
+
+    fn set_viewports(&mut self, viewports: &[wgpu::Viewport]) {
+        self.commands
+            .push(RenderCommand::SetViewports(viewports.to_vec()));
+    }
+
+    fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        self.commands.push(RenderCommand::Draw {
+            vertices,
+            instances,
+        });
+    }
+
+    fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        self.commands.push(RenderCommand::DrawIndexed {
+            indices,
+            base_vertex,
+            instances,
+        });
+    }
+
+    fn draw_indirect(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+    ) {
+        self.commands.push(RenderCommand::DrawIndirect {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+        });
+    }
+
+    fn draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+    ) {
+        self.commands.push(RenderCommand::DrawIndexedIndirect {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+        });
+    }
+
+    fn multi_draw_indirect(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+        count: u32,
+    ) {
+        self.commands.push(RenderCommand::MultiDrawIndirect {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+            count,
+        });
+    }
+
+    fn multi_draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+        count: u32,
+    ) {
+        self.commands.push(RenderCommand::MultiDrawIndexedIndirect {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+            count,
+        });
+    }
+
+    fn multi_draw_indirect_count(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+        count_buffer: &custom::DispatchBuffer,
+        count_buffer_offset: wgpu::BufferAddress,
+        max_count: u32,
+    ) {
+        self.commands.push(RenderCommand::MultiDrawIndirectCount {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+            count_buffer: id(count_buffer.as_custom::<RemoteBuffer>()),
+            count_offset: count_buffer_offset,
+            max_count,
+        });
+    }
+
+    fn multi_draw_indexed_indirect_count(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+        count_buffer: &custom::DispatchBuffer,
+        count_buffer_offset: wgpu::BufferAddress,
+        max_count: u32,
+    ) {
+        self.commands
+            .push(RenderCommand::MultiDrawIndexedIndirectCount {
+                buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+                offset: indirect_offset,
+                count_buffer: id(count_buffer.as_custom::<RemoteBuffer>()),
+                count_offset: count_buffer_offset,
+                max_count,
+            });
+    }
+
+    fn draw_mesh_tasks(&mut self, _group_count_x: u32, _group_count_y: u32, _group_count_z: u32) {
+        // Requires `EXPERIMENTAL_MESH_SHADER`, which `protocol::unsupported_features` masks.
+        record_unsupported(
+            &self.encoder,
+            "Mesh shaders are not supported by the remote backend",
+        );
+    }
+
+    fn draw_mesh_tasks_indirect(
+        &mut self,
+        _indirect_buffer: &custom::DispatchBuffer,
+        _indirect_offset: wgpu::BufferAddress,
+    ) {
+        // Requires `EXPERIMENTAL_MESH_SHADER`, which `protocol::unsupported_features` masks.
+        record_unsupported(
+            &self.encoder,
+            "Mesh shaders are not supported by the remote backend",
+        );
+    }
+
+    fn multi_draw_mesh_tasks_indirect(
+        &mut self,
+        _indirect_buffer: &custom::DispatchBuffer,
+        _indirect_offset: wgpu::BufferAddress,
+        _count: u32,
+    ) {
+        // Requires `EXPERIMENTAL_MESH_SHADER`, which `protocol::unsupported_features` masks.
+        record_unsupported(
+            &self.encoder,
+            "Mesh shaders are not supported by the remote backend",
+        );
+    }
+
+    fn multi_draw_mesh_tasks_indirect_count(
+        &mut self,
+        _indirect_buffer: &custom::DispatchBuffer,
+        _indirect_offset: wgpu::BufferAddress,
+        _count_buffer: &custom::DispatchBuffer,
+        _count_buffer_offset: wgpu::BufferAddress,
+        _max_count: u32,
+    ) {
+        // Requires `EXPERIMENTAL_MESH_SHADER`, which `protocol::unsupported_features` masks.
+        record_unsupported(
+            &self.encoder,
+            "Mesh shaders are not supported by the remote backend",
+        );
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        self.commands
+            .push(RenderCommand::InsertDebugMarker(label.to_owned()));
+    }
+
+    fn push_debug_group(&mut self, group_label: &str) {
+        self.commands
+            .push(RenderCommand::PushDebugGroup(group_label.to_owned()));
+    }
+
+    fn pop_debug_group(&mut self) {
+        self.commands.push(RenderCommand::PopDebugGroup);
+    }
+
+    fn write_timestamp(&mut self, query_set: &custom::DispatchQuerySet, query_index: u32) {
+        self.commands.push(RenderCommand::WriteTimestamp {
+            query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+            index: query_index,
+        });
+    }
+
+    fn begin_occlusion_query(&mut self, query_index: u32) {
+        self.commands
+            .push(RenderCommand::BeginOcclusionQuery(query_index));
+    }
+
+    fn end_occlusion_query(&mut self) {
+        self.commands.push(RenderCommand::EndOcclusionQuery);
+    }
+
+    fn begin_pipeline_statistics_query(
+        &mut self,
+        query_set: &custom::DispatchQuerySet,
+        query_index: u32,
+    ) {
+        self.commands
+            .push(RenderCommand::BeginPipelineStatisticsQuery {
+                query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+                index: query_index,
+            });
+    }
+
+    fn end_pipeline_statistics_query(&mut self) {
+        self.commands
+            .push(RenderCommand::EndPipelineStatisticsQuery);
+    }
+
+    fn begin_conditional_block(
+        &mut self,
+        buffer: &custom::DispatchBuffer,
+        offset: wgpu::BufferAddress,
+    ) {
+        self.commands.push(RenderCommand::BeginConditionalBlock {
+            buffer: id(buffer.as_custom::<RemoteBuffer>()),
+            offset,
+        });
+    }
+
+    fn end_conditional_block(&mut self) {
+        self.commands.push(RenderCommand::EndConditionalBlock);
+    }
+
+    fn execute_bundles(
+        &mut self,
+        render_bundles: &mut dyn Iterator<Item = &custom::DispatchRenderBundle>,
+    ) {
+        self.commands.push(RenderCommand::ExecuteBundles(
+            render_bundles
+                .map(|bundle| id(bundle.as_custom::<RemoteRenderBundle>()))
+                .collect(),
+        ));
+    }
+
+    fn create_parallel_encoder(&mut self) -> custom::DispatchRenderPass {
+        let commands = ParallelEncoderCommands::default();
+        self.parallel_encoders
+            .push((self.commands.len(), commands.clone()));
+        self.commands.push(RenderCommand::ParallelEncoder(None));
+        custom::DispatchRenderPass::custom(RemoteRenderPass {
+            encoder: self.encoder.clone(),
+            target: Some(RenderPassTarget::ParallelEncoder(commands)),
+            commands: Vec::new(),
+            parallel_encoders: Vec::new(),
+        })
+    }
+
+    fn end(&mut self) {
+        let Some(target) = self.target.take() else {
+            return;
+        };
+        for (index, commands) in self.parallel_encoders.drain(..) {
+            self.commands[index] = RenderCommand::ParallelEncoder(commands.lock().take());
+        }
+        let commands = mem::take(&mut self.commands);
+        match target {
+            RenderPassTarget::Encoder(desc) => {
+                self.encoder
+                    .lock()
+                    .push(Command::RenderPass { desc, commands });
+            }
+            RenderPassTarget::ParallelEncoder(slot) => *slot.lock() = Some(commands),
+        }
+    }
+}
+
+impl Drop for RemoteRenderPass {
+    fn drop(&mut self) {
+        self.end();
+    }
+}
+
+#[derive(Debug)]
+struct RemoteRenderBundleEncoder {
+    context: Arc<Context>,
+    device: Id,
+    /// The descriptor of the encoder, which is taken when it is finished.
+    desc: Option<protocol::RenderBundleEncoderDescriptor>,
+    commands: Vec<RenderCommand>,
+}
+
+impl RenderBundleEncoderInterface for RemoteRenderBundleEncoder {
+    fn set_pipeline(&mut self, pipeline: &custom::DispatchRenderPipeline) {
+        self.commands.push(RenderCommand::SetPipeline(id(
+            pipeline.as_custom::<RemoteRenderPipeline>()
+        )));
+    }
+
+    fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: Option<&custom::DispatchBindGroup>,
+        offsets: &[wgpu::DynamicOffset],
+    ) {
+        self.commands.push(RenderCommand::SetBindGroup {
+            index,
+            bind_group: bind_group.map(|bind_group| id(bind_group.as_custom::<RemoteBindGroup>())),
+            offsets: offsets.to_vec(),
+        });
+    }
+
+    fn set_index_buffer(
+        &mut self,
+        buffer: &custom::DispatchBuffer,
+        index_format: wgpu::IndexFormat,
+        offset: wgpu::BufferAddress,
+        size: Option<wgpu::BufferSize>,
+    ) {
+        self.commands.push(RenderCommand::SetIndexBuffer {
+            buffer: id(buffer.as_custom::<RemoteBuffer>()),
+            format: index_format,
+            offset,
+            size,
+        });
+    }
+
+    fn set_vertex_buffer(
+        &mut self,
+        slot: u32,
+        buffer: &custom::DispatchBuffer,
+        offset: wgpu::BufferAddress,
+        size: Option<wgpu::BufferSize>,
+    ) {
+        self.commands.push(RenderCommand::SetVertexBuffer {
+            slot,
+            buffer: id(buffer.as_custom::<RemoteBuffer>()),
+            offset,
+            size,
+        });
+    }
+
+    fn set_push_constants(&mut self, stages: wgpu::ShaderStages, offset: u32, data: &[u8]) {
+        self.commands.push(RenderCommand::SetPushConstants {
+            stages,
+            offset,
+            data: data.to_vec(),
+        });
+    }
+
+    fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        self.commands.push(RenderCommand::Draw {
+            vertices,
+            instances,
+        });
+    }
+
+    fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        self.commands.push(RenderCommand::DrawIndexed {
+            indices,
+            base_vertex,
+            instances,
+        });
+    }
+
+    fn draw_indirect(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+    ) {
+        self.commands.push(RenderCommand::DrawIndirect {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+        });
+    }
+
+    fn draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: &custom::DispatchBuffer,
+        indirect_offset: wgpu::BufferAddress,
+    ) {
+        self.commands.push(RenderCommand::DrawIndexedIndirect {
+            buffer: id(indirect_buffer.as_custom::<RemoteBuffer>()),
+            offset: indirect_offset,
+        });
+    }
+
+    fn write_timestamp(&mut self, query_set: &custom::DispatchQuerySet, query_index: u32) {
+        self.commands.push(RenderCommand::WriteTimestamp {
+            query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+            index: query_index,
+        });
+    }
+
+    fn begin_occlusion_query(&mut self, query_index: u32) {
+        self.commands
+            .push(RenderCommand::BeginOcclusionQuery(query_index));
+    }
+
+    fn end_occlusion_query(&mut self) {
+        self.commands.push(RenderCommand::EndOcclusionQuery);
+    }
+
+    fn begin_pipeline_statistics_query(
+        &mut self,
+        query_set: &custom::DispatchQuerySet,
+        query_index: u32,
+    ) {
+        self.commands
+            .push(RenderCommand::BeginPipelineStatisticsQuery {
+                query_set: id(query_set.as_custom::<RemoteQuerySet>()),
+                index: query_index,
+            });
+    }
+
+    fn end_pipeline_statistics_query(&mut self) {
+        self.commands
+            .push(RenderCommand::EndPipelineStatisticsQuery);
+    }
+
+    fn finish(&mut self, desc: &wgpu::RenderBundleDescriptor<'_>) -> custom::DispatchRenderBundle {
+        let encoder_desc = self
+            .desc
+            .take()
+            .expect("render bundle encoder finished twice");
+        let handle = self.context.create(|id| Request::CreateRenderBundle {
+            device: self.device,
+            id,
+            desc: encoder_desc,
+            label: label(desc.label),
+            commands: mem::take(&mut self.commands),
+        });
+        custom::DispatchRenderBundle::custom(RemoteRenderBundle { handle })
+    }
+}
@@ -0,0 +1,81 @@
+//! A backend for `wgpu` which forwards the calls to a server, usually in another process,
+//! which owns the devices.
+//!
+//! This lets a process use `wgpu` without any access to the GPU or to its drivers, which
+//! is useful for sandboxing plugins or untrusted code, and to survive crashes of the drivers,
+//! which only take down the server. The client is an [`Instance`](wgpu::Instance) created
+//! with [`connect`], which is used like any other instance. The server is a [`Server`],
+//! which executes the calls with an instance of its own.
+//!
+//! The client and the server communicate over any byte stream, such as a socket or a pipe.
+//! Each stream is a connection, and the objects created over a connection are released when
+//! it is closed. Both sides must be built from the same version of this crate.
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> {
+//! use std::{
+//!     net::{TcpListener, TcpStream},
+//!     thread,
+//! };
+//!
+//! // In the process owning the device:
+//! let server = wgpu_remote::Server::new(wgpu::Instance::default());
+//! let listener = TcpListener::bind("127.0.0.1:0")?;
+//! let address = listener.local_addr()?;
+//! thread::spawn(move || {
+//!     for stream in listener.incoming() {
+//!         server.serve(stream.unwrap()).unwrap();
+//!     }
+//! });
+//!
+//! // In the sandboxed process:
+//! let instance = wgpu_remote::connect(TcpStream::connect(address)?)?;
+//! let adapter = instance.request_adapter(&Default::default());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Behavior
+//!
+//! Calls which return nothing are sent without waiting for the server, and the objects are
+//! created on the server in the order of the calls. Calls which return something, such as
+//! [`Device::poll`](wgpu::Device::poll) or [`Device::pop_error_scope`](wgpu::Device::pop_error_scope),
+//! wait for the reply of the server. Callbacks, such as the ones of
+//! [`BufferSlice::map_async`](wgpu::BufferSlice::map_async) or of uncaptured errors, are called
+//! on the client when it receives a reply, so they need the device to be polled like with
+//! native backends.
+//!
+//! The commands of command encoders and render bundle encoders are recorded on the client,
+//! and sent to the server when the encoder is finished. Mapped buffers are copied to the
+//! client when they are mapped, and back to the server when they are unmapped.
+//!
+//! Errors are reported by the server as with any other backend. If the connection is lost,
+//! the devices are lost with [`DeviceLostReason::Unknown`](wgpu::DeviceLostReason::Unknown),
+//! and the calls are ignored from then on.
+//!
+//! The server only enables the backends of the `wgpu` it is built with, so the crate which
+//! runs it must enable the features of `wgpu` for them.
+//!
+//! # Limitations
+//!
+//! Shaders must be WGSL. Surfaces, pipeline caches, ray tracing, mesh shaders, memory heaps,
+//! sparse resources, shared fences, multiple queues and reusable command buffers aren't
+//! supported, and the adapters don't report the features they need.
+//!
+//! For the security of the server, shader modules are always created with runtime checks,
+//! and device traces and graphics debugger captures are ignored.
+
+#![cfg(not(target_arch = "wasm32"))]
+#![warn(clippy::allow_attributes, unsafe_op_in_unsafe_fn)]
+// Proving that `wgpu-core`'s instance is `Send` goes deeper than the default limit.
+#![recursion_limit = "256"]
+
+extern crate wgpu_types as wgt;
+
+mod client;
+mod protocol;
+mod server;
+mod transport;
+
+pub use client::connect;
+pub use server::Server;
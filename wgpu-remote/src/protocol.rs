@@ -0,0 +1,859 @@
+//! The messages exchanged by the client and the server.
+//!
+//! Wherever `wgpu` passes references to objects, the messages carry the [`Id`]s of the
+//! objects instead, and labels and slices are owned. The types of `wgpu-types` are used
+//! as-is where they are already serializable.
+//!
+//! The protocol is private: the client and the server must be built from the same version
+//! of this crate, which [`Request::Handshake`] checks.
+
+use core::{fmt, num::NonZeroU32, ops::Range};
+
+use serde::{Deserialize, Serialize};
+use wgt::{
+    AdapterInfo, BindGroupLayoutEntry, BufferAddress, BufferDescriptor, BufferSize, Color,
+    ColorTargetState, CommandEncoderDescriptor, DepthStencilResolve, DepthStencilState,
+    DeviceDescriptor, DeviceLostReason, DownlevelCapabilities, DynamicOffset, Extent3d, Features,
+    ImageSubresourceRange, IndexFormat, Limits, MemoryInfo, MultisampleState, Operations,
+    PollError, PollStatus, PollType, PrimitiveState, PushConstantRange, QuerySetDescriptor, Rect,
+    RenderBundleDepthStencil, RenderBundleInheritance, RenderPassFlags, RequestAdapterError,
+    RequestAdapterOptions, SamplerDescriptor, ShaderStages, ShadingRate, TexelCopyBufferInfo,
+    TexelCopyBufferLayout, TexelCopyTextureInfo, TextureDescriptor, TextureFormat,
+    TextureFormatFeatures, TextureViewDescriptor, Toggles, VertexAttribute, VertexBufferLayout,
+    Viewport, WorkgroupMemoryInfo,
+};
+
+/// The version of the protocol, which is bumped whenever a message changes.
+pub(crate) const VERSION: u32 = 3;
+
+type Label = Option<String>;
+
+/// Identifies an object on the server.
+///
+/// Ids are allocated by the client, so that objects can be created without waiting for the
+/// server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub(crate) struct Id(pub u64);
+
+/// A request of the client.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Message {
+    /// Whether the client waits for a [`Reply`] to the request.
+    pub reply: bool,
+    pub request: Request,
+}
+
+/// The reply of the server to a [`Message`] which asked for one.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Reply {
+    /// The events which happened since the previous reply.
+    pub events: Vec<Event>,
+    pub response: Response,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[expect(clippy::enum_variant_names)]
+pub(crate) enum Request {
+    /// The first request of a connection, answered with [`Response::Handshake`].
+    Handshake {
+        version: u32,
+    },
+    /// Releases the object. Objects which the server consumes, such as submitted command
+    /// buffers, are still released by the client, so unknown ids are ignored.
+    Drop(Id),
+
+    RequestAdapter {
+        id: Id,
+        options: RequestAdapterOptions<()>,
+    },
+    PollAllDevices {
+        force_wait: bool,
+    },
+    WgslLanguageFeatures,
+
+    GetTextureFormatFeatures {
+        adapter: Id,
+        format: TextureFormat,
+    },
+    GetMemoryInfo {
+        adapter: Id,
+    },
+    RequestDevice {
+        adapter: Id,
+        device: Id,
+        queue: Id,
+        desc: DeviceDescriptor<Label>,
+    },
+
+    CreateShaderModule {
+        device: Id,
+        id: Id,
+        label: Label,
+        wgsl: String,
+        /// The defines of `ShaderSource::WgslWithDefines`.
+        defines: Vec<String>,
+    },
+    /// Creates an invalid object, for a call the client can't send, and reports `error` as
+    /// a validation error.
+    CreateInvalid {
+        device: Id,
+        id: Id,
+        label: Label,
+        kind: InvalidObject,
+        error: String,
+    },
+    /// Reports `error` as a validation error, for a call the client can't send which
+    /// doesn't create an object.
+    ReportValidationError {
+        device: Id,
+        error: String,
+    },
+    GetCompilationInfo {
+        shader_module: Id,
+    },
+    GetShaderBinding {
+        shader_module: Id,
+        name: String,
+    },
+    GetWorkgroupMemory {
+        shader_module: Id,
+        entry_point: String,
+    },
+    CreateBindGroupLayout {
+        device: Id,
+        id: Id,
+        label: Label,
+        entries: Vec<BindGroupLayoutEntry>,
+    },
+    CreateBindGroup {
+        device: Id,
+        id: Id,
+        desc: BindGroupDescriptor,
+    },
+    CreatePipelineLayout {
+        device: Id,
+        id: Id,
+        desc: PipelineLayoutDescriptor,
+    },
+    CreateRenderPipeline {
+        device: Id,
+        id: Id,
+        desc: RenderPipelineDescriptor,
+    },
+    CreateComputePipeline {
+        device: Id,
+        id: Id,
+        desc: ComputePipelineDescriptor,
+    },
+    /// Gets a bind group layout of a render or compute pipeline.
+    GetBindGroupLayout {
+        pipeline: Id,
+        index: u32,
+        id: Id,
+    },
+    CreateBuffer {
+        device: Id,
+        id: Id,
+        desc: BufferDescriptor<Label>,
+    },
+    CreateTexture {
+        device: Id,
+        id: Id,
+        desc: TextureDescriptor<Label, Vec<TextureFormat>>,
+    },
+    CreateTextureView {
+        texture: Id,
+        id: Id,
+        desc: TextureViewDescriptor<Label>,
+    },
+    CreateSampler {
+        device: Id,
+        id: Id,
+        desc: SamplerDescriptor<Label>,
+    },
+    CreateQuerySet {
+        device: Id,
+        id: Id,
+        desc: QuerySetDescriptor<Label>,
+    },
+    /// Encodes a command buffer from the commands recorded by the client.
+    CreateCommandBuffer {
+        device: Id,
+        id: Id,
+        desc: CommandEncoderDescriptor<Label>,
+        commands: Vec<Command>,
+        reusable: bool,
+    },
+    /// Encodes a render bundle from the commands recorded by the client.
+    CreateRenderBundle {
+        device: Id,
+        id: Id,
+        desc: RenderBundleEncoderDescriptor,
+        label: Label,
+        commands: Vec<RenderCommand>,
+    },
+    PushErrorScope {
+        device: Id,
+        filter: ErrorKind,
+    },
+    PopErrorScope {
+        device: Id,
+    },
+    /// Polls the device, with the submission indices of the client.
+    Poll {
+        device: Id,
+        poll_type: PollType<u64>,
+    },
+    DestroyDevice(Id),
+
+    WriteBuffer {
+        queue: Id,
+        buffer: Id,
+        offset: BufferAddress,
+        data: Vec<u8>,
+    },
+    WriteTexture {
+        queue: Id,
+        texture: TexelCopyTextureInfo<Id>,
+        data: Vec<u8>,
+        layout: TexelCopyBufferLayout,
+        size: Extent3d,
+    },
+    /// Submits the command buffers, whose submission has the index `index` on the client.
+    Submit {
+        queue: Id,
+        index: u64,
+        command_buffers: Vec<Id>,
+    },
+    GetTimestampPeriod {
+        queue: Id,
+    },
+
+    /// Maps the buffer, which is reported with [`Event::BufferMapped`] for `map`.
+    MapBuffer {
+        buffer: Id,
+        map: Id,
+        mode: MapMode,
+        range: Range<BufferAddress>,
+    },
+    /// Unmaps the buffer, after writing back the contents of the mapped range if it was
+    /// mapped for writing.
+    UnmapBuffer {
+        buffer: Id,
+        data: Option<Vec<u8>>,
+    },
+    DestroyBuffer(Id),
+    DestroyTexture(Id),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[expect(clippy::large_enum_variant)]
+pub(crate) enum Response {
+    /// The response to requests which return nothing.
+    None,
+    Handshake {
+        version: u32,
+    },
+    Adapter(Result<AdapterDescription, RequestAdapterError>),
+    /// The error message if the device couldn't be created.
+    Device(Result<(), String>),
+    Bool(bool),
+    F32(f32),
+    WgslLanguageFeatures(u32),
+    TextureFormatFeatures(TextureFormatFeatures),
+    MemoryInfo(Option<MemoryInfo>),
+    CompilationInfo(Vec<CompilationMessage>),
+    ShaderBinding(Option<(u32, u32)>),
+    WorkgroupMemory(Option<WorkgroupMemoryInfo>),
+    Error(Option<Error>),
+    Poll(Result<PollStatus, PollError>),
+}
+
+/// Something which happened on the server without being requested by the client.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Event {
+    /// The contents of the range mapped by [`Request::MapBuffer`], or `None` if the buffer
+    /// couldn't be mapped.
+    BufferMapped {
+        map: Id,
+        data: Option<Vec<u8>>,
+    },
+    /// The submission with the index `index` on the client has completed, along with all
+    /// submissions before it.
+    SubmissionDone {
+        queue: Id,
+        index: u64,
+    },
+    UncapturedError {
+        device: Id,
+        error: Error,
+    },
+    DeviceLost {
+        device: Id,
+        reason: DeviceLostReason,
+        message: String,
+    },
+}
+
+/// What the client caches about an adapter.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AdapterDescription {
+    pub info: AdapterInfo,
+    pub features: Features,
+    pub limits: Limits,
+    pub downlevel_capabilities: DownlevelCapabilities,
+    pub applied_workarounds: Toggles,
+}
+
+/// The features which the protocol has no requests for.
+pub(crate) fn unsupported_features() -> Features {
+    Features::MSL_SHADER_PASSTHROUGH
+        | Features::SPIRV_SHADER_PASSTHROUGH
+        | Features::HLSL_DXIL_SHADER_PASSTHROUGH
+        | Features::EXTERNAL_TEXTURE
+        | Features::EXPERIMENTAL_RAY_QUERY
+        | Features::EXPERIMENTAL_RAY_HIT_VERTEX_RETURN
+        | Features::EXTENDED_ACCELERATION_STRUCTURE_VERTEX_FORMATS
+        | Features::EXPERIMENTAL_RAY_TRACING_PIPELINE
+        | Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION
+        | Features::EXPERIMENTAL_MESH_SHADER
+        | Features::EXPERIMENTAL_MESH_SHADER_MULTIVIEW
+        | Features::EXPERIMENTAL_MULTI_QUEUE
+        | Features::EXPERIMENTAL_SHARED_FENCE
+        | Features::PIPELINE_CACHE
+        | Features::TEXTURE_ALIASING
+        | Features::SPARSE_BINDING
+        | Features::REUSABLE_COMMAND_BUFFERS
+        | Features::PRESENTATION_TIMING
+        | Features::VULKAN_GOOGLE_DISPLAY_TIMING
+        | Features::VULKAN_EXTERNAL_MEMORY_WIN32
+        | Features::VULKAN_EXTERNAL_MEMORY_DMA_BUF
+        | Features::EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER
+}
+
+/// The type of the object created by [`Request::CreateInvalid`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub(crate) enum InvalidObject {
+    ShaderModule,
+    BindGroup,
+    RenderPipeline,
+    ComputePipeline,
+    Buffer,
+    Texture,
+}
+
+/// A [`wgpu::Error`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Error {
+    pub kind: ErrorKind,
+    pub description: String,
+}
+
+/// The kind of a [`wgpu::Error`], which is also its [`wgpu::ErrorFilter`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub(crate) enum ErrorKind {
+    OutOfMemory,
+    Validation,
+    Internal,
+}
+
+impl From<&wgpu::Error> for Error {
+    fn from(error: &wgpu::Error) -> Self {
+        let (kind, description) = match error {
+            wgpu::Error::OutOfMemory { .. } => (ErrorKind::OutOfMemory, error.to_string()),
+            wgpu::Error::Validation { description, .. } => {
+                (ErrorKind::Validation, description.clone())
+            }
+            wgpu::Error::Internal { description, .. } => (ErrorKind::Internal, description.clone()),
+        };
+        Self { kind, description }
+    }
+}
+
+impl From<Error> for wgpu::Error {
+    fn from(error: Error) -> Self {
+        let source = Box::new(ServerError(error.description.clone()));
+        match error.kind {
+            ErrorKind::OutOfMemory => Self::OutOfMemory { source },
+            ErrorKind::Validation => Self::Validation {
+                source,
+                description: error.description,
+            },
+            ErrorKind::Internal => Self::Internal {
+                source,
+                description: error.description,
+            },
+        }
+    }
+}
+
+/// The source of the errors reported by the server, which only have their description.
+#[derive(Debug)]
+struct ServerError(String);
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+impl From<wgpu::ErrorFilter> for ErrorKind {
+    fn from(filter: wgpu::ErrorFilter) -> Self {
+        match filter {
+            wgpu::ErrorFilter::OutOfMemory => Self::OutOfMemory,
+            wgpu::ErrorFilter::Validation => Self::Validation,
+            wgpu::ErrorFilter::Internal => Self::Internal,
+        }
+    }
+}
+
+impl From<ErrorKind> for wgpu::ErrorFilter {
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::OutOfMemory => Self::OutOfMemory,
+            ErrorKind::Validation => Self::Validation,
+            ErrorKind::Internal => Self::Internal,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub(crate) enum MapMode {
+    Read,
+    Write,
+}
+
+impl From<wgpu::MapMode> for MapMode {
+    fn from(mode: wgpu::MapMode) -> Self {
+        match mode {
+            wgpu::MapMode::Read => Self::Read,
+            wgpu::MapMode::Write => Self::Write,
+        }
+    }
+}
+
+impl From<MapMode> for wgpu::MapMode {
+    fn from(mode: MapMode) -> Self {
+        match mode {
+            MapMode::Read => Self::Read,
+            MapMode::Write => Self::Write,
+        }
+    }
+}
+
+/// A [`wgpu::CompilationMessage`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CompilationMessage {
+    pub message: String,
+    pub message_type: CompilationMessageType,
+    /// The line number, line position, offset and length of the location, followed by the
+    /// line position, offset and length in UTF-16 code units.
+    pub location: Option<[u32; 7]>,
+    pub file_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum CompilationMessageType {
+    Error,
+    Warning,
+    Info,
+}
+
+impl From<&wgpu::CompilationMessage> for CompilationMessage {
+    fn from(message: &wgpu::CompilationMessage) -> Self {
+        Self {
+            message: message.message.clone(),
+            message_type: match message.message_type {
+                wgpu::CompilationMessageType::Error => CompilationMessageType::Error,
+                wgpu::CompilationMessageType::Warning => CompilationMessageType::Warning,
+                wgpu::CompilationMessageType::Info => CompilationMessageType::Info,
+            },
+            location: message.location.map(|location| {
+                [
+                    location.line_number,
+                    location.line_position,
+                    location.offset,
+                    location.length,
+                    location.utf16_line_position,
+                    location.utf16_offset,
+                    location.utf16_length,
+                ]
+            }),
+            file_name: message.file_name.clone(),
+        }
+    }
+}
+
+impl From<CompilationMessage> for wgpu::CompilationMessage {
+    fn from(message: CompilationMessage) -> Self {
+        Self {
+            message: message.message,
+            message_type: match message.message_type {
+                CompilationMessageType::Error => wgpu::CompilationMessageType::Error,
+                CompilationMessageType::Warning => wgpu::CompilationMessageType::Warning,
+                CompilationMessageType::Info => wgpu::CompilationMessageType::Info,
+            },
+            location: message.location.map(
+                |[
+                    line_number,
+                    line_position,
+                    offset,
+                    length,
+                    utf16_line_position,
+                    utf16_offset,
+                    utf16_length,
+                ]| wgpu::SourceLocation {
+                    line_number,
+                    line_position,
+                    offset,
+                    length,
+                    utf16_line_position,
+                    utf16_offset,
+                    utf16_length,
+                },
+            ),
+            file_name: message.file_name,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BindGroupDescriptor {
+    pub label: Label,
+    pub layout: Id,
+    pub entries: Vec<BindGroupEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BindGroupEntry {
+    pub binding: u32,
+    pub resource: BindingResource,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum BindingResource {
+    Buffer(BufferBinding),
+    BufferArray(Vec<BufferBinding>),
+    Sampler(Id),
+    SamplerArray(Vec<Id>),
+    TextureView(Id),
+    TextureViewArray(Vec<Id>),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BufferBinding {
+    pub buffer: Id,
+    pub offset: BufferAddress,
+    pub size: Option<BufferSize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct PipelineLayoutDescriptor {
+    pub label: Label,
+    pub bind_group_layouts: Vec<Id>,
+    pub push_constant_ranges: Vec<PushConstantRange>,
+}
+
+/// A shader entry point with its [`wgpu::PipelineCompilationOptions`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ProgrammableStage {
+    pub module: Id,
+    pub entry_point: Option<String>,
+    pub constants: Vec<(String, f64)>,
+    pub zero_initialize_workgroup_memory: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct FragmentState {
+    pub stage: ProgrammableStage,
+    pub targets: Vec<Option<ColorTargetState>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RenderPipelineDescriptor {
+    pub label: Label,
+    pub layout: Option<Id>,
+    pub vertex: ProgrammableStage,
+    pub vertex_buffers: Vec<VertexBufferLayout<Vec<VertexAttribute>>>,
+    pub primitive: PrimitiveState,
+    pub depth_stencil: Option<DepthStencilState>,
+    pub multisample: MultisampleState,
+    pub fragment: Option<FragmentState>,
+    pub multiview: Option<NonZeroU32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ComputePipelineDescriptor {
+    pub label: Label,
+    pub layout: Option<Id>,
+    pub stage: ProgrammableStage,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RenderBundleEncoderDescriptor {
+    pub label: Label,
+    pub color_formats: Vec<Option<TextureFormat>>,
+    pub depth_stencil: Option<RenderBundleDepthStencil>,
+    pub sample_count: u32,
+    pub multiview: Option<NonZeroU32>,
+    pub inheritance: RenderBundleInheritance,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct PassTimestampWrites {
+    pub query_set: Id,
+    pub beginning_of_pass_write_index: Option<u32>,
+    pub end_of_pass_write_index: Option<u32>,
+}
+
+/// A [`wgpu::PassPipelineStatisticsQuery`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct PassPipelineStatisticsQuery {
+    pub query_set: Id,
+    pub query_index: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ComputePassDescriptor {
+    pub label: Label,
+    pub timestamp_writes: Option<PassTimestampWrites>,
+    pub pipeline_statistics_query: Option<PassPipelineStatisticsQuery>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RenderPassColorAttachment {
+    pub view: Id,
+    pub depth_slice: Option<u32>,
+    pub resolve_target: Option<Id>,
+    pub ops: Operations<Color>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RenderPassDepthStencilAttachment {
+    pub view: Id,
+    pub depth_ops: Option<Operations<f32>>,
+    pub stencil_ops: Option<Operations<u32>>,
+    pub resolve: Option<DepthStencilResolve<Id>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RenderPassDescriptor {
+    pub label: Label,
+    pub color_attachments: Vec<Option<RenderPassColorAttachment>>,
+    pub depth_stencil_attachment: Option<RenderPassDepthStencilAttachment>,
+    pub timestamp_writes: Option<PassTimestampWrites>,
+    pub occlusion_query_set: Option<Id>,
+    pub pipeline_statistics_query: Option<PassPipelineStatisticsQuery>,
+    pub flags: RenderPassFlags,
+    pub multiview: Option<NonZeroU32>,
+}
+
+/// A command recorded by a [`wgpu::CommandEncoder`].
+///
+/// Passes are recorded as a single command, when they end.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Command {
+    CopyBufferToBuffer {
+        source: Id,
+        source_offset: BufferAddress,
+        destination: Id,
+        destination_offset: BufferAddress,
+        size: Option<BufferAddress>,
+    },
+    CopyBufferToTexture {
+        source: TexelCopyBufferInfo<Id>,
+        destination: TexelCopyTextureInfo<Id>,
+        size: Extent3d,
+    },
+    CopyTextureToBuffer {
+        source: TexelCopyTextureInfo<Id>,
+        destination: TexelCopyBufferInfo<Id>,
+        size: Extent3d,
+    },
+    CopyTextureToTexture {
+        source: TexelCopyTextureInfo<Id>,
+        destination: TexelCopyTextureInfo<Id>,
+        size: Extent3d,
+    },
+    ResolveTexture {
+        source: Id,
+        destination: Id,
+        regions: Vec<Rect>,
+    },
+    ClearTexture {
+        texture: Id,
+        range: ImageSubresourceRange,
+    },
+    ClearBuffer {
+        buffer: Id,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+    },
+    InsertDebugMarker(String),
+    PushDebugGroup(String),
+    PopDebugGroup,
+    WriteTimestamp {
+        query_set: Id,
+        index: u32,
+    },
+    ResolveQuerySet {
+        query_set: Id,
+        queries: Range<u32>,
+        destination: Id,
+        destination_offset: BufferAddress,
+    },
+    ComputePass {
+        desc: ComputePassDescriptor,
+        commands: Vec<ComputeCommand>,
+    },
+    RenderPass {
+        desc: RenderPassDescriptor,
+        commands: Vec<RenderCommand>,
+    },
+    /// A command of the encoder or one of its passes which the client can't send, whose
+    /// error is reported as a validation error when the command buffer is created.
+    Unsupported(String),
+}
+
+/// A command recorded by a [`wgpu::ComputePass`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum ComputeCommand {
+    SetPipeline(Id),
+    SetBindGroup {
+        index: u32,
+        bind_group: Option<Id>,
+        offsets: Vec<DynamicOffset>,
+    },
+    SetPushConstants {
+        offset: u32,
+        data: Vec<u8>,
+    },
+    InsertDebugMarker(String),
+    PushDebugGroup(String),
+    PopDebugGroup,
+    WriteTimestamp {
+        query_set: Id,
+        index: u32,
+    },
+    BeginPipelineStatisticsQuery {
+        query_set: Id,
+        index: u32,
+    },
+    EndPipelineStatisticsQuery,
+    Dispatch([u32; 3]),
+    DispatchIndirect {
+        buffer: Id,
+        offset: BufferAddress,
+    },
+}
+
+/// A command recorded by a [`wgpu::RenderPass`] or a [`wgpu::RenderBundleEncoder`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum RenderCommand {
+    SetPipeline(Id),
+    SetBindGroup {
+        index: u32,
+        bind_group: Option<Id>,
+        offsets: Vec<DynamicOffset>,
+    },
+    SetIndexBuffer {
+        buffer: Id,
+        format: IndexFormat,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    },
+    SetVertexBuffer {
+        slot: u32,
+        buffer: Id,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    },
+    SetPushConstants {
+        stages: ShaderStages,
+        offset: u32,
+        data: Vec<u8>,
+    },
+    SetBlendConstant(Color),
+    SetScissorRect {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    SetViewport {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        depth: Range<f32>,
+    },
+    SetViewports(Vec<Viewport>),
+    SetStencilReference(u32),
+    SetShadingRate(ShadingRate),
+    Draw {
+        vertices: Range<u32>,
+        instances: Range<u32>,
+    },
+    DrawIndexed {
+        indices: Range<u32>,
+        base_vertex: i32,
+        instances: Range<u32>,
+    },
+    DrawIndirect {
+        buffer: Id,
+        offset: BufferAddress,
+    },
+    DrawIndexedIndirect {
+        buffer: Id,
+        offset: BufferAddress,
+    },
+    MultiDrawIndirect {
+        buffer: Id,
+        offset: BufferAddress,
+        count: u32,
+    },
+    MultiDrawIndexedIndirect {
+        buffer: Id,
+        offset: BufferAddress,
+        count: u32,
+    },
+    MultiDrawIndirectCount {
+        buffer: Id,
+        offset: BufferAddress,
+        count_buffer: Id,
+        count_offset: BufferAddress,
+        max_count: u32,
+    },
+    MultiDrawIndexedIndirectCount {
+        buffer: Id,
+        offset: BufferAddress,
+        count_buffer: Id,
+        count_offset: BufferAddress,
+        max_count: u32,
+    },
+    InsertDebugMarker(String),
+    PushDebugGroup(String),
+    PopDebugGroup,
+    WriteTimestamp {
+        query_set: Id,
+        index: u32,
+    },
+    BeginOcclusionQuery(u32),
+    EndOcclusionQuery,
+    BeginPipelineStatisticsQuery {
+        query_set: Id,
+        index: u32,
+    },
+    EndPipelineStatisticsQuery,
+    BeginConditionalBlock {
+        buffer: Id,
+        offset: BufferAddress,
+    },
+    EndConditionalBlock,
+    ExecuteBundles(Vec<Id>),
+    /// A parallel encoder created at this point of the pass, with its commands, or `None`
+    /// if it wasn't ended before the pass.
+    ParallelEncoder(Option<Vec<RenderCommand>>),
+}
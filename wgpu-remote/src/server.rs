@@ -0,0 +1,1381 @@
+//! The server, which executes the requests of the clients with a `wgpu` instance.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Write},
+    mem,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use hashbrown::HashMap;
+use parking_lot::Mutex;
+use wgpu::util::RenderEncoder;
+
+use crate::{
+    protocol::{
+        self, AdapterDescription, Command, ComputeCommand, Event, Id, InvalidObject, Message,
+        RenderCommand, Reply, Request, Response,
+    },
+    transport,
+};
+
+/// Serves the clients created by [`connect`](crate::connect), executing their calls with an
+/// instance of `wgpu`.
+#[derive(Debug)]
+pub struct Server {
+    instance: wgpu::Instance,
+}
+
+impl Server {
+    /// Creates a server which executes the calls of the clients with `instance`.
+    pub fn new(instance: wgpu::Instance) -> Self {
+        Self { instance }
+    }
+
+    /// Serves the client connected with `stream`, until it disconnects.
+    ///
+    /// The objects created by the client are released when this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream fails, or if the client sends an invalid request, such
+    /// as one with an unknown object, in which case the connection is closed.
+    pub fn serve(&self, mut stream: impl Read + Write) -> io::Result<()> {
+        match transport::read_message::<Message>(&mut stream)? {
+            Some(Message {
+                request: Request::Handshake { version },
+                ..
+            }) => {
+                let reply = Reply {
+                    events: Vec::new(),
+                    response: Response::Handshake {
+                        version: protocol::VERSION,
+                    },
+                };
+                transport::write_message(&mut stream, &reply)?;
+                if version != protocol::VERSION {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "the client runs another version of wgpu-remote",
+                    ));
+                }
+            }
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the client didn't start with a handshake",
+                ));
+            }
+            None => return Ok(()),
+        }
+
+        let mut session = Session::new(&self.instance);
+        while let Some(Message { reply, request }) = transport::read_message(&mut stream)? {
+            // `wgpu` panics on some invalid calls, which the client would have caught if it
+            // used `wgpu` directly.
+            let response = panic::catch_unwind(AssertUnwindSafe(|| {
+                let response = session.handle(request);
+                session.collect_events();
+                response
+            }))
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the client sent an invalid request",
+                )
+            })?;
+            if reply {
+                let events = mem::take(&mut session.events);
+                transport::write_message(&mut stream, &Reply { events, response })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Polls a future of `wgpu` to completion.
+///
+/// The futures of native backends are ready immediately.
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("the futures of native backends are ready immediately"),
+    }
+}
+
+struct Device {
+    device: wgpu::Device,
+    queue: Id,
+    /// The error scopes pushed by the client, which are also pushed on `device`.
+    error_scopes: Vec<ErrorScope>,
+}
+
+/// An error scope, with the errors reported by the server rather than `wgpu`.
+struct ErrorScope {
+    filter: wgpu::ErrorFilter,
+    /// The first error of the scope, if it was reported before the errors left in the
+    /// scope of `wgpu`.
+    error: Option<protocol::Error>,
+}
+
+struct Queue {
+    queue: wgpu::Queue,
+    /// The submissions which may not have completed yet, by their index on the client.
+    submissions: BTreeMap<u64, wgpu::SubmissionIndex>,
+}
+
+struct Buffer {
+    buffer: wgpu::Buffer,
+    /// The mapped range, if the buffer is mapped.
+    mapping: Option<(protocol::MapMode, Range<wgpu::BufferAddress>)>,
+}
+
+/// An object type which can be looked up in a [`Session`].
+trait Kind: Sized {
+    const NAME: &'static str;
+
+    fn get(object: &Object) -> Option<&Self>;
+    fn get_mut(object: &mut Object) -> Option<&mut Self>;
+}
+
+macro_rules! objects {
+    ($($name:ident($ty:ty)),* $(,)?) => {
+        enum Object {
+            $($name($ty),)*
+        }
+
+        $(
+            impl From<$ty> for Object {
+                fn from(object: $ty) -> Self {
+                    Self::$name(object)
+                }
+            }
+
+            impl Kind for $ty {
+                const NAME: &'static str = stringify!($name);
+
+                fn get(object: &Object) -> Option<&Self> {
+                    match object {
+                        Object::$name(object) => Some(object),
+                        _ => None,
+                    }
+                }
+
+                fn get_mut(object: &mut Object) -> Option<&mut Self> {
+                    match object {
+                        Object::$name(object) => Some(object),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+objects!(
+    Adapter(wgpu::Adapter),
+    Device(Device),
+    Queue(Queue),
+    ShaderModule(wgpu::ShaderModule),
+    BindGroupLayout(wgpu::BindGroupLayout),
+    BindGroup(wgpu::BindGroup),
+    PipelineLayout(wgpu::PipelineLayout),
+    RenderPipeline(wgpu::RenderPipeline),
+    ComputePipeline(wgpu::ComputePipeline),
+    Buffer(Buffer),
+    Texture(wgpu::Texture),
+    TextureView(wgpu::TextureView),
+    Sampler(wgpu::Sampler),
+    QuerySet(wgpu::QuerySet),
+    CommandBuffer(wgpu::CommandBuffer),
+    RenderBundle(wgpu::RenderBundle),
+);
+
+/// What the callbacks of `wgpu` report, until the session turns it into [`Event`]s.
+enum Pending {
+    Mapped {
+        map: Id,
+        buffer: Id,
+        mode: protocol::MapMode,
+        range: Range<wgpu::BufferAddress>,
+        result: Result<(), wgpu::BufferAsyncError>,
+    },
+    Event(Event),
+}
+
+/// The objects of a client.
+struct Session<'a> {
+    instance: &'a wgpu::Instance,
+    objects: HashMap<Id, Object>,
+    /// Filled by the callbacks of `wgpu`, which may be called from other threads.
+    pending: Arc<Mutex<Vec<Pending>>>,
+    /// The events to send with the next reply.
+    events: Vec<Event>,
+}
+
+impl<'a> Session<'a> {
+    fn new(instance: &'a wgpu::Instance) -> Self {
+        Self {
+            instance,
+            objects: HashMap::new(),
+            pending: Arc::default(),
+            events: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, id: Id, object: impl Into<Object>) {
+        self.objects.insert(id, object.into());
+    }
+
+    fn get<T: Kind>(&self, id: Id) -> &T {
+        self.objects
+            .get(&id)
+            .and_then(T::get)
+            .unwrap_or_else(|| panic!("{id:?} is not a {}", T::NAME))
+    }
+
+    fn get_mut<T: Kind>(&mut self, id: Id) -> &mut T {
+        self.objects
+            .get_mut(&id)
+            .and_then(T::get_mut)
+            .unwrap_or_else(|| panic!("{id:?} is not a {}", T::NAME))
+    }
+
+    fn device(&self, id: Id) -> &wgpu::Device {
+        &self.get::<Device>(id).device
+    }
+
+    /// Reports a validation error on the device `id`, as if `wgpu` had raised it.
+    fn report_validation_error(&mut self, id: Id, description: String) {
+        let error = protocol::Error {
+            kind: protocol::ErrorKind::Validation,
+            description,
+        };
+        let device = self.get_mut::<Device>(id);
+        let Some(index) = device
+            .error_scopes
+            .iter()
+            .rposition(|scope| scope.filter == wgpu::ErrorFilter::Validation)
+        else {
+            self.pending
+                .lock()
+                .push(Pending::Event(Event::UncapturedError { device: id, error }));
+            return;
+        };
+        // The errors `wgpu` already caught in the scope, or in the scopes above it, came
+        // first, so they are moved to our scopes before the scopes are pushed again.
+        for scope in device.error_scopes[index..].iter_mut().rev() {
+            let caught = block_on(device.device.pop_error_scope());
+            if scope.error.is_none() {
+                scope.error = caught.as_ref().map(Into::into);
+            }
+        }
+        device.error_scopes[index].error.get_or_insert(error);
+        for scope in &device.error_scopes[index..] {
+            device.device.push_error_scope(scope.filter);
+        }
+    }
+
+    fn buffer(&self, id: Id) -> &wgpu::Buffer {
+        &self.get::<Buffer>(id).buffer
+    }
+
+    fn buffer_slice(
+        &self,
+        id: Id,
+        offset: wgpu::BufferAddress,
+        size: Option<wgpu::BufferSize>,
+    ) -> wgpu::BufferSlice<'_> {
+        let buffer = self.buffer(id);
+        match size {
+            Some(size) => buffer.slice(offset..offset + size.get()),
+            None => buffer.slice(offset..),
+        }
+    }
+
+    fn texel_copy_buffer(
+        &self,
+        info: wgt::TexelCopyBufferInfo<Id>,
+    ) -> wgpu::TexelCopyBufferInfo<'_> {
+        wgpu::TexelCopyBufferInfo {
+            buffer: self.buffer(info.buffer),
+            layout: info.layout,
+        }
+    }
+
+    fn texel_copy_texture(
+        &self,
+        info: wgt::TexelCopyTextureInfo<Id>,
+    ) -> wgpu::TexelCopyTextureInfo<'_> {
+        wgpu::TexelCopyTextureInfo {
+            texture: self.get(info.texture),
+            mip_level: info.mip_level,
+            origin: info.origin,
+            aspect: info.aspect,
+        }
+    }
+
+    fn pipeline_statistics_query(
+        &self,
+        query: Option<protocol::PassPipelineStatisticsQuery>,
+    ) -> Option<wgpu::PassPipelineStatisticsQuery<'_>> {
+        query.map(|query| wgpu::PassPipelineStatisticsQuery {
+            query_set: self.get(query.query_set),
+            query_index: query.query_index,
+        })
+    }
+
+    fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Handshake { .. } => panic!("the handshake was already made"),
+            // Objects consumed by the server are already gone.
+            Request::Drop(id) => {
+                self.objects.remove(&id);
+            }
+
+            Request::RequestAdapter { id, options } => {
+                let adapter =
+                    block_on(self.instance.request_adapter(&wgpu::RequestAdapterOptions {
+                        power_preference: options.power_preference,
+                        force_fallback_adapter: options.force_fallback_adapter,
+                        compatible_surface: None,
+                    }));
+                return Response::Adapter(adapter.map(|adapter| {
+                    let description = AdapterDescription {
+                        info: adapter.get_info(),
+                        features: adapter.features() - protocol::unsupported_features(),
+                        limits: adapter.limits(),
+                        downlevel_capabilities: adapter.get_downlevel_capabilities(),
+                        applied_workarounds: adapter.applied_workarounds(),
+                    };
+                    self.insert(id, adapter);
+                    description
+                }));
+            }
+            Request::PollAllDevices { force_wait } => {
+                return Response::Bool(self.instance.poll_all(force_wait));
+            }
+            Request::WgslLanguageFeatures => {
+                return Response::WgslLanguageFeatures(
+                    self.instance.wgsl_language_features().bits(),
+                );
+            }
+
+            Request::GetTextureFormatFeatures { adapter, format } => {
+                let adapter = self.get::<wgpu::Adapter>(adapter);
+                return Response::TextureFormatFeatures(
+                    adapter.get_texture_format_features(format),
+                );
+            }
+            Request::GetMemoryInfo { adapter } => {
+                return Response::MemoryInfo(self.get::<wgpu::Adapter>(adapter).memory_info());
+            }
+            Request::RequestDevice {
+                adapter,
+                device: device_id,
+                queue: queue_id,
+                desc,
+            } => {
+                let unsupported = desc.required_features & protocol::unsupported_features();
+                if !unsupported.is_empty() {
+                    return Response::Device(Err(format!(
+                        "Features {unsupported:?} are not supported by the remote backend"
+                    )));
+                }
+                let mut desc = desc.map_label(|_| desc.label.as_deref());
+                // Traces would be written on the server.
+                desc.trace = wgt::Trace::Off;
+                let adapter = self.get::<wgpu::Adapter>(adapter);
+                let (device, queue) = match block_on(adapter.request_device(&desc)) {
+                    Ok(device_and_queue) => device_and_queue,
+                    Err(err) => return Response::Device(Err(err.to_string())),
+                };
+
+                let pending = self.pending.clone();
+                device.on_uncaptured_error(Box::new(move |error| {
+                    pending.lock().push(Pending::Event(Event::UncapturedError {
+                        device: device_id,
+                        error: (&error).into(),
+                    }));
+                }));
+                let pending = self.pending.clone();
+                device.set_device_lost_callback(move |reason, message| {
+                    pending.lock().push(Pending::Event(Event::DeviceLost {
+                        device: device_id,
+                        reason,
+                        message,
+                    }));
+                });
+
+                self.insert(
+                    device_id,
+                    Device {
+                        device,
+                        queue: queue_id,
+                        error_scopes: Vec::new(),
+                    },
+                );
+                self.insert(
+                    queue_id,
+                    Queue {
+                        queue,
+                        submissions: BTreeMap::new(),
+                    },
+                );
+                return Response::Device(Ok(()));
+            }
+
+            Request::CreateShaderModule {
+                device,
+                id,
+                label,
+                wgsl,
+                defines,
+            } => {
+                let defines = defines.iter().map(String::as_str).collect::<Vec<_>>();
+                // Shaders are always created with runtime checks.
+                let module =
+                    self.device(device)
+                        .create_shader_module(wgpu::ShaderModuleDescriptor {
+                            label: label.as_deref(),
+                            source: wgpu::ShaderSource::WgslWithDefines {
+                                shader: wgsl.into(),
+                                defines: &defines,
+                            },
+                        });
+                self.insert(id, module);
+            }
+            Request::CreateInvalid {
+                device,
+                id,
+                label,
+                kind,
+                error,
+            } => {
+                self.create_invalid(device, id, label.as_deref(), kind);
+                self.report_validation_error(device, error);
+            }
+            Request::ReportValidationError { device, error } => {
+                self.report_validation_error(device, error);
+            }
+            Request::GetCompilationInfo { shader_module } => {
+                let module = self.get::<wgpu::ShaderModule>(shader_module);
+                let info = block_on(module.get_compilation_info());
+                return Response::CompilationInfo(info.messages.iter().map(Into::into).collect());
+            }
+            Request::GetShaderBinding {
+                shader_module,
+                name,
+            } => {
+                let module = self.get::<wgpu::ShaderModule>(shader_module);
+                return Response::ShaderBinding(module.binding(&name));
+            }
+            Request::GetWorkgroupMemory {
+                shader_module,
+                entry_point,
+            } => {
+                let module = self.get::<wgpu::ShaderModule>(shader_module);
+                return Response::WorkgroupMemory(module.workgroup_memory(&entry_point));
+            }
+            Request::CreateBindGroupLayout {
+                device,
+                id,
+                label,
+                entries,
+            } => {
+                let layout = self.device(device).create_bind_group_layout(
+                    &wgpu::BindGroupLayoutDescriptor {
+                        label: label.as_deref(),
+                        entries: &entries,
+                    },
+                );
+                self.insert(id, layout);
+            }
+            Request::CreateBindGroup { device, id, desc } => {
+                let bind_group = self.create_bind_group(device, desc);
+                self.insert(id, bind_group);
+            }
+            Request::CreatePipelineLayout { device, id, desc } => {
+                let bind_group_layouts = desc
+                    .bind_group_layouts
+                    .iter()
+                    .map(|&layout| self.get::<wgpu::BindGroupLayout>(layout))
+                    .collect::<Vec<_>>();
+                let layout =
+                    self.device(device)
+                        .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                            label: desc.label.as_deref(),
+                            bind_group_layouts: &bind_group_layouts,
+                            push_constant_ranges: &desc.push_constant_ranges,
+                        });
+                self.insert(id, layout);
+            }
+            Request::CreateRenderPipeline { device, id, desc } => {
+                let pipeline = self.create_render_pipeline(device, desc);
+                self.insert(id, pipeline);
+            }
+            Request::CreateComputePipeline { device, id, desc } => {
+                let constants = constants(&desc.stage);
+                let pipeline =
+                    self.device(device)
+                        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                            label: desc.label.as_deref(),
+                            layout: desc.layout.map(|layout| self.get(layout)),
+                            module: self.get(desc.stage.module),
+                            entry_point: desc.stage.entry_point.as_deref(),
+                            compilation_options: wgpu::PipelineCompilationOptions {
+                                constants: &constants,
+                                zero_initialize_workgroup_memory: desc
+                                    .stage
+                                    .zero_initialize_workgroup_memory,
+                            },
+                            cache: None,
+                        });
+                self.insert(id, pipeline);
+            }
+            Request::GetBindGroupLayout {
+                pipeline,
+                index,
+                id,
+            } => {
+                let layout = match self.objects.get(&pipeline) {
+                    Some(Object::RenderPipeline(pipeline)) => pipeline.get_bind_group_layout(index),
+                    Some(Object::ComputePipeline(pipeline)) => {
+                        pipeline.get_bind_group_layout(index)
+                    }
+                    _ => panic!("{pipeline:?} is not a pipeline"),
+                };
+                self.insert(id, layout);
+            }
+            Request::CreateBuffer { device, id, desc } => {
+                let buffer = self
+                    .device(device)
+                    .create_buffer(&desc.map_label(|_| desc.label.as_deref()));
+                let mapping = desc
+                    .mapped_at_creation
+                    .then_some((protocol::MapMode::Write, 0..desc.size));
+                self.insert(id, Buffer { buffer, mapping });
+            }
+            Request::CreateTexture { device, id, desc } => {
+                let texture = self
+                    .device(device)
+                    .create_texture(&desc.map_label_and_view_formats(
+                        |_| desc.label.as_deref(),
+                        |_| &desc.view_formats[..],
+                    ));
+                self.insert(id, texture);
+            }
+            Request::CreateTextureView { texture, id, desc } => {
+                let view =
+                    self.get::<wgpu::Texture>(texture)
+                        .create_view(&wgpu::TextureViewDescriptor {
+                            label: desc.label.as_deref(),
+                            format: desc.format,
+                            dimension: desc.dimension,
+                            usage: desc.usage,
+                            aspect: desc.aspect,
+                            base_mip_level: desc.base_mip_level,
+                            mip_level_count: desc.mip_level_count,
+                            base_array_layer: desc.base_array_layer,
+                            array_layer_count: desc.array_layer_count,
+                        });
+                self.insert(id, view);
+            }
+            Request::CreateSampler { device, id, desc } => {
+                let sampler = self
+                    .device(device)
+                    .create_sampler(&wgpu::SamplerDescriptor {
+                        label: desc.label.as_deref(),
+                        address_mode_u: desc.address_mode_u,
+                        address_mode_v: desc.address_mode_v,
+                        address_mode_w: desc.address_mode_w,
+                        mag_filter: desc.mag_filter,
+                        min_filter: desc.min_filter,
+                        mipmap_filter: desc.mipmap_filter,
+                        lod_min_clamp: desc.lod_min_clamp,
+                        lod_max_clamp: desc.lod_max_clamp,
+                        compare: desc.compare,
+                        anisotropy_clamp: desc.anisotropy_clamp,
+                        border_color: desc.border_color,
+                    });
+                self.insert(id, sampler);
+            }
+            Request::CreateQuerySet { device, id, desc } => {
+                let query_set = self
+                    .device(device)
+                    .create_query_set(&desc.map_label(|_| desc.label.as_deref()));
+                self.insert(id, query_set);
+            }
+            Request::CreateCommandBuffer {
+                device,
+                id,
+                desc,
+                commands,
+                reusable,
+            } => {
+                let mut encoder = self
+                    .device(device)
+                    .create_command_encoder(&desc.map_label(|_| desc.label.as_deref()));
+                let mut errors = Vec::new();
+                for command in commands {
+                    match command {
+                        Command::Unsupported(error) => errors.push(error),
+                        command => self.encode(&mut encoder, command),
+                    }
+                }
+                let command_buffer = if reusable {
+                    encoder.finish_reusable()
+                } else {
+                    encoder.finish()
+                };
+                self.insert(id, command_buffer);
+                for error in errors {
+                    self.report_validation_error(device, error);
+                }
+            }
+            Request::CreateRenderBundle {
+                device,
+                id,
+                desc,
+                label,
+                commands,
+            } => {
+                let mut encoder = self.device(device).create_render_bundle_encoder(
+                    &wgpu::RenderBundleEncoderDescriptor {
+                        label: desc.label.as_deref(),
+                        color_formats: &desc.color_formats,
+                        depth_stencil: desc.depth_stencil,
+                        sample_count: desc.sample_count,
+                        multiview: desc.multiview,
+                        inheritance: desc.inheritance,
+                    },
+                );
+                for command in commands {
+                    match command {
+                        RenderCommand::WriteTimestamp { query_set, index } => {
+                            encoder.write_timestamp(self.get(query_set), index);
+                        }
+                        RenderCommand::BeginOcclusionQuery(index) => {
+                            encoder.begin_occlusion_query(index);
+                        }
+                        RenderCommand::EndOcclusionQuery => encoder.end_occlusion_query(),
+                        RenderCommand::BeginPipelineStatisticsQuery { query_set, index } => {
+                            encoder.begin_pipeline_statistics_query(self.get(query_set), index);
+                        }
+                        RenderCommand::EndPipelineStatisticsQuery => {
+                            encoder.end_pipeline_statistics_query();
+                        }
+                        command => self.encode_render(&mut encoder, command),
+                    }
+                }
+                let bundle = encoder.finish(&wgpu::RenderBundleDescriptor {
+                    label: label.as_deref(),
+                });
+                self.insert(id, bundle);
+            }
+            Request::PushErrorScope { device, filter } => {
+                let device = self.get_mut::<Device>(device);
+                let filter = filter.into();
+                device.device.push_error_scope(filter);
+                device.error_scopes.push(ErrorScope {
+                    filter,
+                    error: None,
+                });
+            }
+            Request::PopErrorScope { device } => {
+                let device = self.get_mut::<Device>(device);
+                let error = block_on(device.device.pop_error_scope());
+                let reported = device.error_scopes.pop().and_then(|scope| scope.error);
+                return Response::Error(reported.or_else(|| error.as_ref().map(Into::into)));
+            }
+            Request::Poll { device, poll_type } => {
+                let device = self.get::<Device>(device);
+                let queue = self.get::<Queue>(device.queue);
+                let poll_type = poll_type.map_index(|index| queue.submissions.get(&index).cloned());
+                let result = match poll_type {
+                    // The submission has already completed.
+                    wgt::PollType::WaitForSubmissionIndex(None)
+                    | wgt::PollType::WaitForSubmissionWithTimeout(None, _) => device
+                        .device
+                        .poll(wgpu::PollType::Poll)
+                        .map(|_| wgpu::PollStatus::WaitSucceeded),
+                    poll_type => device.device.poll(poll_type.map_index(Option::unwrap)),
+                };
+                return Response::Poll(result);
+            }
+            Request::DestroyDevice(device) => self.device(device).destroy(),
+
+            Request::WriteBuffer {
+                queue,
+                buffer,
+                offset,
+                data,
+            } => {
+                let queue = &self.get::<Queue>(queue).queue;
+                queue.write_buffer(self.buffer(buffer), offset, &data);
+            }
+            Request::WriteTexture {
+                queue,
+                texture,
+                data,
+                layout,
+                size,
+            } => {
+                let queue = &self.get::<Queue>(queue).queue;
+                queue.write_texture(self.texel_copy_texture(texture), &data, layout, size);
+            }
+            Request::Submit {
+                queue: queue_id,
+                index,
+                command_buffers,
+            } => {
+                let command_buffers = command_buffers
+                    .into_iter()
+                    .map(|id| match self.objects.remove(&id) {
+                        Some(Object::CommandBuffer(command_buffer)) => command_buffer,
+                        _ => panic!("{id:?} is not a CommandBuffer"),
+                    })
+                    .collect::<Vec<_>>();
+                let pending = self.pending.clone();
+                let queue = self.get_mut::<Queue>(queue_id);
+                let submission = queue.queue.submit(command_buffers);
+                queue.queue.on_submitted_work_done(move || {
+                    pending.lock().push(Pending::Event(Event::SubmissionDone {
+                        queue: queue_id,
+                        index,
+                    }));
+                });
+                queue.submissions.insert(index, submission);
+            }
+            Request::GetTimestampPeriod { queue } => {
+                return Response::F32(self.get::<Queue>(queue).queue.get_timestamp_period());
+            }
+
+            Request::MapBuffer {
+                buffer,
+                map,
+                mode,
+                range,
+            } => {
+                let pending = self.pending.clone();
+                let callback_range = range.clone();
+                self.buffer(buffer)
+                    .map_async(mode.into(), range, move |result| {
+                        pending.lock().push(Pending::Mapped {
+                            map,
+                            buffer,
+                            mode,
+                            range: callback_range,
+                            result,
+                        });
+                    });
+            }
+            Request::UnmapBuffer { buffer, data } => {
+                let buffer = self.get_mut::<Buffer>(buffer);
+                if let (Some(data), Some((protocol::MapMode::Write, range))) =
+                    (data, buffer.mapping.take())
+                {
+                    let mut view = buffer.buffer.slice(range).get_mapped_range_mut();
+                    assert_eq!(data.len(), view.len(), "mapped range has the wrong size");
+                    view.copy_from_slice(&data);
+                }
+                buffer.buffer.unmap();
+            }
+            Request::DestroyBuffer(buffer) => self.buffer(buffer).destroy(),
+            Request::DestroyTexture(texture) => self.get::<wgpu::Texture>(texture).destroy(),
+        }
+        Response::None
+    }
+
+    fn create_bind_group(
+        &self,
+        device: Id,
+        desc: protocol::BindGroupDescriptor,
+    ) -> wgpu::BindGroup {
+        /// The resources of an entry, which array bindings borrow.
+        enum Resource<'a> {
+            Buffer(wgpu::BufferBinding<'a>),
+            BufferArray(Vec<wgpu::BufferBinding<'a>>),
+            Sampler(&'a wgpu::Sampler),
+            SamplerArray(Vec<&'a wgpu::Sampler>),
+            TextureView(&'a wgpu::TextureView),
+            TextureViewArray(Vec<&'a wgpu::TextureView>),
+        }
+
+        let buffer_binding = |binding: &protocol::BufferBinding| wgpu::BufferBinding {
+            buffer: self.buffer(binding.buffer),
+            offset: binding.offset,
+            size: binding.size,
+        };
+        let resources = desc
+            .entries
+            .iter()
+            .map(|entry| match entry.resource {
+                protocol::BindingResource::Buffer(ref binding) => {
+                    Resource::Buffer(buffer_binding(binding))
+                }
+                protocol::BindingResource::BufferArray(ref bindings) => {
+                    Resource::BufferArray(bindings.iter().map(buffer_binding).collect())
+                }
+                protocol::BindingResource::Sampler(sampler) => Resource::Sampler(self.get(sampler)),
+                protocol::BindingResource::SamplerArray(ref samplers) => Resource::SamplerArray(
+                    samplers.iter().map(|&sampler| self.get(sampler)).collect(),
+                ),
+                protocol::BindingResource::TextureView(view) => {
+                    Resource::TextureView(self.get(view))
+                }
+                protocol::BindingResource::TextureViewArray(ref views) => {
+                    Resource::TextureViewArray(views.iter().map(|&view| self.get(view)).collect())
+                }
+            })
+            .collect::<Vec<_>>();
+        let entries = desc
+            .entries
+            .iter()
+            .zip(&resources)
+            .map(|(entry, resource)| wgpu::BindGroupEntry {
+                binding: entry.binding,
+                resource: match *resource {
+                    Resource::Buffer(ref binding) => wgpu::BindingResource::Buffer(binding.clone()),
+                    Resource::BufferArray(ref bindings) => {
+                        wgpu::BindingResource::BufferArray(bindings)
+                    }
+                    Resource::Sampler(sampler) => wgpu::BindingResource::Sampler(sampler),
+                    Resource::SamplerArray(ref samplers) => {
+                        wgpu::BindingResource::SamplerArray(samplers)
+                    }
+                    Resource::TextureView(view) => wgpu::BindingResource::TextureView(view),
+                    Resource::TextureViewArray(ref views) => {
+                        wgpu::BindingResource::TextureViewArray(views)
+                    }
+                },
+            })
+            .collect::<Vec<_>>();
+        self.device(device)
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: desc.label.as_deref(),
+                layout: self.get(desc.layout),
+                entries: &entries,
+            })
+    }
+
+    /// Creates an invalid object of type `kind`, whose error isn't reported.
+    fn create_invalid(&mut self, device: Id, id: Id, label: Option<&str>, kind: InvalidObject) {
+        // `wgpu` can't create invalid objects directly, so they are made from invalid
+        // descriptors, whose errors are left in an error scope.
+        let device = self.device(device);
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let object = match kind {
+            InvalidObject::ShaderModule => Object::from(invalid_shader_module(device, label)),
+            InvalidObject::BindGroup => {
+                // The layout declares the same binding twice.
+                let entry = wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::empty(),
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                };
+                let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label,
+                    entries: &[entry, entry],
+                });
+                Object::from(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label,
+                    layout: &layout,
+                    entries: &[],
+                }))
+            }
+            InvalidObject::RenderPipeline => {
+                let module = invalid_shader_module(device, label);
+                Object::from(
+                    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                        label,
+                        layout: None,
+                        vertex: wgpu::VertexState {
+                            module: &module,
+                            entry_point: None,
+                            compilation_options: Default::default(),
+                            buffers: &[],
+                        },
+                        primitive: Default::default(),
+                        depth_stencil: None,
+                        multisample: Default::default(),
+                        fragment: None,
+                        multiview: None,
+                        cache: None,
+                    }),
+                )
+            }
+            InvalidObject::ComputePipeline => {
+                let module = invalid_shader_module(device, label);
+                Object::from(
+                    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                        label,
+                        layout: None,
+                        module: &module,
+                        entry_point: None,
+                        compilation_options: Default::default(),
+                        cache: None,
+                    }),
+                )
+            }
+            // Buffers and textures without usages are invalid.
+            InvalidObject::Buffer => Object::from(Buffer {
+                buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                    label,
+                    size: 0,
+                    usage: wgpu::BufferUsages::empty(),
+                    mapped_at_creation: false,
+                }),
+                mapping: None,
+            }),
+            InvalidObject::Texture => {
+                Object::from(device.create_texture(&wgpu::TextureDescriptor {
+                    label,
+                    size: wgpu::Extent3d::default(),
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    usage: wgpu::TextureUsages::empty(),
+                    view_formats: &[],
+                }))
+            }
+        };
+        let _ = block_on(device.pop_error_scope());
+        self.insert(id, object);
+    }
+
+    fn create_render_pipeline(
+        &self,
+        device: Id,
+        desc: protocol::RenderPipelineDescriptor,
+    ) -> wgpu::RenderPipeline {
+        let vertex_constants = constants(&desc.vertex);
+        let vertex_buffers = desc
+            .vertex_buffers
+            .iter()
+            .map(|layout| layout.map_attributes(Vec::as_slice))
+            .collect::<Vec<_>>();
+        let fragment_constants = desc
+            .fragment
+            .as_ref()
+            .map(|fragment| constants(&fragment.stage))
+            .unwrap_or_default();
+        self.device(device)
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: desc.label.as_deref(),
+                layout: desc.layout.map(|layout| self.get(layout)),
+                vertex: wgpu::VertexState {
+                    module: self.get(desc.vertex.module),
+                    entry_point: desc.vertex.entry_point.as_deref(),
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: &vertex_constants,
+                        zero_initialize_workgroup_memory: desc
+                            .vertex
+                            .zero_initialize_workgroup_memory,
+                    },
+                    buffers: &vertex_buffers,
+                },
+                primitive: desc.primitive,
+                depth_stencil: desc.depth_stencil,
+                multisample: desc.multisample,
+                fragment: desc.fragment.as_ref().map(|fragment| wgpu::FragmentState {
+                    module: self.get(fragment.stage.module),
+                    entry_point: fragment.stage.entry_point.as_deref(),
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: &fragment_constants,
+                        zero_initialize_workgroup_memory: fragment
+                            .stage
+                            .zero_initialize_workgroup_memory,
+                    },
+                    targets: &fragment.targets,
+                }),
+                multiview: desc.multiview,
+                cache: None,
+            })
+    }
+
+    fn encode(&self, encoder: &mut wgpu::CommandEncoder, command: Command) {
+        match command {
+            Command::CopyBufferToBuffer {
+                source,
+                source_offset,
+                destination,
+                destination_offset,
+                size,
+            } => encoder.copy_buffer_to_buffer(
+                self.buffer(source),
+                source_offset,
+                self.buffer(destination),
+                destination_offset,
+                size,
+            ),
+            Command::CopyBufferToTexture {
+                source,
+                destination,
+                size,
+            } => encoder.copy_buffer_to_texture(
+                self.texel_copy_buffer(source),
+                self.texel_copy_texture(destination),
+                size,
+            ),
+            Command::CopyTextureToBuffer {
+                source,
+                destination,
+                size,
+            } => encoder.copy_texture_to_buffer(
+                self.texel_copy_texture(source),
+                self.texel_copy_buffer(destination),
+                size,
+            ),
+            Command::CopyTextureToTexture {
+                source,
+                destination,
+                size,
+            } => encoder.copy_texture_to_texture(
+                self.texel_copy_texture(source),
+                self.texel_copy_texture(destination),
+                size,
+            ),
+            Command::ResolveTexture {
+                source,
+                destination,
+                regions,
+            } => encoder.resolve_texture(self.get(source), self.get(destination), &regions),
+            Command::ClearTexture { texture, range } => {
+                encoder.clear_texture(self.get(texture), &range);
+            }
+            Command::ClearBuffer {
+                buffer,
+                offset,
+                size,
+            } => encoder.clear_buffer(self.buffer(buffer), offset, size),
+            Command::InsertDebugMarker(label) => encoder.insert_debug_marker(&label),
+            Command::PushDebugGroup(label) => encoder.push_debug_group(&label),
+            Command::PopDebugGroup => encoder.pop_debug_group(),
+            Command::WriteTimestamp { query_set, index } => {
+                encoder.write_timestamp(self.get(query_set), index);
+            }
+            Command::ResolveQuerySet {
+                query_set,
+                queries,
+                destination,
+                destination_offset,
+            } => encoder.resolve_query_set(
+                self.get(query_set),
+                queries,
+                self.buffer(destination),
+                destination_offset,
+            ),
+            Command::ComputePass { desc, commands } => {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: desc.label.as_deref(),
+                    timestamp_writes: desc.timestamp_writes.map(|writes| {
+                        wgpu::ComputePassTimestampWrites {
+                            query_set: self.get(writes.query_set),
+                            beginning_of_pass_write_index: writes.beginning_of_pass_write_index,
+                            end_of_pass_write_index: writes.end_of_pass_write_index,
+                        }
+                    }),
+                    pipeline_statistics_query: self
+                        .pipeline_statistics_query(desc.pipeline_statistics_query),
+                });
+                for command in commands {
+                    self.encode_compute(&mut pass, command);
+                }
+            }
+            Command::RenderPass { desc, commands } => {
+                let color_attachments = desc
+                    .color_attachments
+                    .into_iter()
+                    .map(|attachment| {
+                        attachment.map(|attachment| wgpu::RenderPassColorAttachment {
+                            view: self.get(attachment.view),
+                            depth_slice: attachment.depth_slice,
+                            resolve_target: attachment.resolve_target.map(|view| self.get(view)),
+                            ops: attachment.ops,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: desc.label.as_deref(),
+                    color_attachments: &color_attachments,
+                    depth_stencil_attachment: desc.depth_stencil_attachment.map(|attachment| {
+                        wgpu::RenderPassDepthStencilAttachment {
+                            view: self.get(attachment.view),
+                            depth_ops: attachment.depth_ops,
+                            stencil_ops: attachment.stencil_ops,
+                            resolve: attachment.resolve.map(|resolve| wgt::DepthStencilResolve {
+                                target: self.get(resolve.target),
+                                depth_mode: resolve.depth_mode,
+                                resolve_stencil: resolve.resolve_stencil,
+                            }),
+                        }
+                    }),
+                    timestamp_writes: desc.timestamp_writes.map(|writes| {
+                        wgpu::RenderPassTimestampWrites {
+                            query_set: self.get(writes.query_set),
+                            beginning_of_pass_write_index: writes.beginning_of_pass_write_index,
+                            end_of_pass_write_index: writes.end_of_pass_write_index,
+                        }
+                    }),
+                    occlusion_query_set: desc
+                        .occlusion_query_set
+                        .map(|query_set| self.get(query_set)),
+                    pipeline_statistics_query: self
+                        .pipeline_statistics_query(desc.pipeline_statistics_query),
+                    flags: desc.flags,
+                    multiview: desc.multiview,
+                });
+                let mut unended = Vec::new();
+                for command in commands {
+                    match command {
+                        // `wgpu` reports the parallel encoders which aren't ended before
+                        // their pass.
+                        RenderCommand::ParallelEncoder(None) => {
+                            unended.push(pass.create_parallel_encoder());
+                        }
+                        command => self.encode_render_pass(&mut pass, command),
+                    }
+                }
+                drop(pass);
+            }
+            Command::Unsupported(_) => {
+                unreachable!("unsupported commands are reported instead of encoded")
+            }
+        }
+    }
+
+    fn encode_compute(&self, pass: &mut wgpu::ComputePass<'_>, command: ComputeCommand) {
+        match command {
+            ComputeCommand::SetPipeline(pipeline) => pass.set_pipeline(self.get(pipeline)),
+            ComputeCommand::SetBindGroup {
+                index,
+                bind_group,
+                offsets,
+            } => pass.set_bind_group(
+                index,
+                bind_group.map(|bind_group| self.get::<wgpu::BindGroup>(bind_group)),
+                &offsets,
+            ),
+            ComputeCommand::SetPushConstants { offset, data } => {
+                pass.set_push_constants(offset, &data);
+            }
+            ComputeCommand::InsertDebugMarker(label) => pass.insert_debug_marker(&label),
+            ComputeCommand::PushDebugGroup(label) => pass.push_debug_group(&label),
+            ComputeCommand::PopDebugGroup => pass.pop_debug_group(),
+            ComputeCommand::WriteTimestamp { query_set, index } => {
+                pass.write_timestamp(self.get(query_set), index);
+            }
+            ComputeCommand::BeginPipelineStatisticsQuery { query_set, index } => {
+                pass.begin_pipeline_statistics_query(self.get(query_set), index);
+            }
+            ComputeCommand::EndPipelineStatisticsQuery => pass.end_pipeline_statistics_query(),
+            ComputeCommand::Dispatch([x, y, z]) => pass.dispatch_workgroups(x, y, z),
+            ComputeCommand::DispatchIndirect { buffer, offset } => {
+                pass.dispatch_workgroups_indirect(self.buffer(buffer), offset);
+            }
+        }
+    }
+
+    fn encode_render_pass<'b>(&'b self, pass: &mut wgpu::RenderPass<'b>, command: RenderCommand) {
+        match command {
+            RenderCommand::SetBlendConstant(color) => pass.set_blend_constant(color),
+            RenderCommand::SetScissorRect {
+                x,
+                y,
+                width,
+                height,
+            } => pass.set_scissor_rect(x, y, width, height),
+            RenderCommand::SetViewport {
+                x,
+                y,
+                width,
+                height,
+                depth,
+            } => pass.set_viewport(x, y, width, height, depth.start, depth.end),
+            RenderCommand::SetViewports(viewports) => pass.set_viewports(&viewports),
+            RenderCommand::SetStencilReference(reference) => pass.set_stencil_reference(reference),
+            RenderCommand::SetShadingRate(rate) => pass.set_shading_rate(rate),
+            RenderCommand::MultiDrawIndirect {
+                buffer,
+                offset,
+                count,
+            } => pass.multi_draw_indirect(self.buffer(buffer), offset, count),
+            RenderCommand::MultiDrawIndexedIndirect {
+                buffer,
+                offset,
+                count,
+            } => pass.multi_draw_indexed_indirect(self.buffer(buffer), offset, count),
+            RenderCommand::MultiDrawIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_offset,
+                max_count,
+            } => pass.multi_draw_indirect_count(
+                self.buffer(buffer),
+                offset,
+                self.buffer(count_buffer),
+                count_offset,
+                max_count,
+            ),
+            RenderCommand::MultiDrawIndexedIndirectCount {
+                buffer,
+                offset,
+                count_buffer,
+                count_offset,
+                max_count,
+            } => pass.multi_draw_indexed_indirect_count(
+                self.buffer(buffer),
+                offset,
+                self.buffer(count_buffer),
+                count_offset,
+                max_count,
+            ),
+            RenderCommand::InsertDebugMarker(label) => pass.insert_debug_marker(&label),
+            RenderCommand::PushDebugGroup(label) => pass.push_debug_group(&label),
+            RenderCommand::PopDebugGroup => pass.pop_debug_group(),
+            RenderCommand::WriteTimestamp { query_set, index } => {
+                pass.write_timestamp(self.get(query_set), index);
+            }
+            RenderCommand::BeginOcclusionQuery(index) => pass.begin_occlusion_query(index),
+            RenderCommand::EndOcclusionQuery => pass.end_occlusion_query(),
+            RenderCommand::BeginPipelineStatisticsQuery { query_set, index } => {
+                pass.begin_pipeline_statistics_query(self.get(query_set), index);
+            }
+            RenderCommand::EndPipelineStatisticsQuery => pass.end_pipeline_statistics_query(),
+            RenderCommand::BeginConditionalBlock { buffer, offset } => {
+                pass.begin_conditional_block(self.buffer(buffer), offset);
+            }
+            RenderCommand::EndConditionalBlock => pass.end_conditional_block(),
+            RenderCommand::ExecuteBundles(bundles) => {
+                pass.execute_bundles(bundles.into_iter().map(|bundle| self.get(bundle)));
+            }
+            RenderCommand::ParallelEncoder(commands) => {
+                let mut encoder: wgpu::RenderPass<'b> = pass.create_parallel_encoder();
+                for command in commands.unwrap_or_default() {
+                    self.encode_render_pass(&mut encoder, command);
+                }
+            }
+            command => self.encode_render(pass, command),
+        }
+    }
+
+    /// Encodes the commands which both render passes and render bundles have.
+    fn encode_render<'b>(&'b self, encoder: &mut impl RenderEncoder<'b>, command: RenderCommand) {
+        match command {
+            RenderCommand::SetPipeline(pipeline) => encoder.set_pipeline(self.get(pipeline)),
+            RenderCommand::SetBindGroup {
+                index,
+                bind_group,
+                offsets,
+            } => encoder.set_bind_group(
+                index,
+                bind_group.map(|bind_group| self.get::<wgpu::BindGroup>(bind_group)),
+                &offsets,
+            ),
+            RenderCommand::SetIndexBuffer {
+                buffer,
+                format,
+                offset,
+                size,
+            } => encoder.set_index_buffer(self.buffer_slice(buffer, offset, size), format),
+            RenderCommand::SetVertexBuffer {
+                slot,
+                buffer,
+                offset,
+                size,
+            } => encoder.set_vertex_buffer(slot, self.buffer_slice(buffer, offset, size)),
+            RenderCommand::SetPushConstants {
+                stages,
+                offset,
+                data,
+            } => encoder.set_push_constants(stages, offset, &data),
+            RenderCommand::Draw {
+                vertices,
+                instances,
+            } => encoder.draw(vertices, instances),
+            RenderCommand::DrawIndexed {
+                indices,
+                base_vertex,
+                instances,
+            } => encoder.draw_indexed(indices, base_vertex, instances),
+            RenderCommand::DrawIndirect { buffer, offset } => {
+                encoder.draw_indirect(self.buffer(buffer), offset);
+            }
+            RenderCommand::DrawIndexedIndirect { buffer, offset } => {
+                encoder.draw_indexed_indirect(self.buffer(buffer), offset);
+            }
+            command => panic!("{command:?} can't be recorded in a render bundle"),
+        }
+    }
+
+    /// Turns what the callbacks of `wgpu` reported into events.
+    fn collect_events(&mut self) {
+        let pending = mem::take(&mut *self.pending.lock());
+        for pending in pending {
+            let event = match pending {
+                Pending::Mapped {
+                    map,
+                    buffer,
+                    mode,
+                    range,
+                    result,
+                } => {
+                    let buffer = match self.objects.get_mut(&buffer) {
+                        Some(Object::Buffer(buffer)) if result.is_ok() => buffer,
+                        _ => {
+                            self.events.push(Event::BufferMapped { map, data: None });
+                            continue;
+                        }
+                    };
+                    // Buffers mapped for writing may have contents too.
+                    let data = buffer
+                        .buffer
+                        .slice(range.clone())
+                        .get_mapped_range()
+                        .to_vec();
+                    buffer.mapping = Some((mode, range));
+                    Event::BufferMapped {
+                        map,
+                        data: Some(data),
+                    }
+                }
+                Pending::Event(event) => {
+                    if let Event::SubmissionDone { queue, index } = event {
+                        if let Some(Object::Queue(queue)) = self.objects.get_mut(&queue) {
+                            queue
+                                .submissions
+                                .retain(|&submission, _| submission > index);
+                        }
+                    }
+                    event
+                }
+            };
+            self.events.push(event);
+        }
+    }
+}
+
+/// Creates a shader module from invalid WGSL.
+fn invalid_shader_module(device: &wgpu::Device, label: Option<&str>) -> wgpu::ShaderModule {
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label,
+        source: wgpu::ShaderSource::Wgsl("!".into()),
+    })
+}
+
+/// The pipeline-overridable constants of a stage, as `wgpu` takes them.
+fn constants(stage: &protocol::ProgrammableStage) -> Vec<(&str, f64)> {
+    stage
+        .constants
+        .iter()
+        .map(|(name, value)| (name.as_str(), *value))
+        .collect()
+}
@@ -0,0 +1,43 @@
+//! Framing of the messages on the stream.
+//!
+//! Each message is encoded with `bincode`, and prefixed with the length of the encoding as
+//! a little-endian `u32`.
+
+use std::io::{self, Read, Write};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+fn config() -> bincode::config::Configuration {
+    bincode::config::standard()
+}
+
+pub(crate) fn write_message<T: Serialize>(stream: &mut impl Write, message: &T) -> io::Result<()> {
+    let data = bincode::serde::encode_to_vec(message, config())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let len = u32::try_from(data.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "message too large"))?;
+    stream.write_all(&len.to_le_bytes())?;
+    stream.write_all(&data)?;
+    stream.flush()
+}
+
+/// Reads a message, or returns `None` if the stream was closed before it.
+pub(crate) fn read_message<T: DeserializeOwned>(stream: &mut impl Read) -> io::Result<Option<T>> {
+    let mut len = [0; 4];
+    match stream.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    let len = u32::from_le_bytes(len) as usize;
+
+    // Don't trust the length enough to allocate it upfront.
+    let mut data = Vec::new();
+    stream.take(len as u64).read_to_end(&mut data)?;
+    if data.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let (message, _) = bincode::serde::decode_from_slice(&data, config())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(Some(message))
+}
@@ -114,6 +114,7 @@ pub struct HalCounters {
     pub bind_group_layouts: InternalCounter,
     pub render_pipelines: InternalCounter,
     pub compute_pipelines: InternalCounter,
+    pub ray_tracing_pipelines: InternalCounter,
     pub pipeline_layouts: InternalCounter,
     pub samplers: InternalCounter,
     pub command_encoders: InternalCounter,
@@ -219,6 +220,107 @@ impl fmt::Debug for AllocatorReport {
     }
 }
 
+/// A report of the memory used by a device's resources, generated for debugging purposes
+/// by [`Device::generate_memory_report()`](../wgpu/struct.Device.html#method.generate_memory_report).
+///
+/// Unlike [`AllocatorReport`], which describes the backend's memory allocator, this
+/// attributes memory to the live resources of a device. Sizes are those requested by the
+/// resources; the allocations backing them may be larger due to alignment and padding.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// Memory used by all live buffers.
+    pub buffers: ResourceTypeMemoryReport,
+    /// Memory used by all live textures, as estimated from their descriptors.
+    pub textures: ResourceTypeMemoryReport,
+    /// Every live buffer and texture, if requested, sorted by decreasing size.
+    ///
+    /// Empty if the list of resources wasn't requested.
+    pub resources: Vec<ResourceMemoryReport>,
+}
+
+/// Memory used by all live resources of one type, part of [`MemoryReport`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceTypeMemoryReport {
+    /// Number of live resources of this type.
+    pub count: usize,
+    /// Sum of the sizes of the live resources of this type, in bytes.
+    pub bytes: u64,
+}
+
+/// Memory used by a single resource, part of [`MemoryReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceMemoryReport {
+    /// The type of the resource.
+    pub resource_type: MemoryReportResourceType,
+    /// The label the resource was created with, empty if it had none.
+    pub label: String,
+    /// The size of the resource in bytes, for textures as estimated by
+    /// [`TextureFormat::theoretical_memory_footprint`](crate::TextureFormat::theoretical_memory_footprint)
+    /// for all mip levels and samples.
+    pub size: u64,
+    /// The kind of memory the resource is allocated in.
+    pub heap: MemoryReportHeap,
+}
+
+/// The type of a resource in a [`MemoryReport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MemoryReportResourceType {
+    /// A buffer.
+    Buffer,
+    /// A texture.
+    Texture,
+}
+
+/// The kind of memory a resource in a [`MemoryReport`] is allocated in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MemoryReportHeap {
+    /// Memory preferably local to the device, used for all resources that can't be mapped.
+    DeviceLocal,
+    /// Memory visible to the host, used for buffers with
+    /// [`BufferUsages::MAP_READ`](crate::BufferUsages::MAP_READ) or
+    /// [`BufferUsages::MAP_WRITE`](crate::BufferUsages::MAP_WRITE) usage.
+    HostVisible,
+}
+
+/// What was released by
+/// [`Device::trim_memory()`](../wgpu/struct.Device.html#method.trim_memory).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrimMemoryReport {
+    /// Number of memory blocks released by the backend's memory allocator.
+    ///
+    /// This is always 0 on backends other than Vulkan. Metal creates every resource in
+    /// its own allocation, which is released with the resource. The DX12 allocator
+    /// releases empty memory blocks as soon as they are empty, except for one block per
+    /// memory type, which it has no way to release early.
+    pub freed_memory_blocks: u32,
+    /// Number of idle command encoders released, along with the memory of the command
+    /// buffers they allocated.
+    pub freed_command_encoders: u32,
+}
+
+/// Barriers inserted while barrier logging was enabled, as returned by
+/// [`Device::take_barrier_report()`](../wgpu/struct.Device.html#method.take_barrier_report).
+///
+/// See [`Device::set_barrier_logging()`](../wgpu/struct.Device.html#method.set_barrier_logging).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BarrierReport {
+    /// One entry per pass or submission which needed barriers, in the order they were
+    /// recorded.
+    pub passes: Vec<PassBarrierReport>,
+}
+
+/// Barriers inserted for a single pass or submission, in a [`BarrierReport`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PassBarrierReport {
+    /// Where the barriers were inserted, for example `compute pass "label"` or
+    /// `submission of CommandBuffer with 'label' label`.
+    pub origin: String,
+    /// Number of buffer barriers.
+    pub buffer_barriers: u32,
+    /// Number of texture barriers.
+    pub texture_barriers: u32,
+}
+
 struct FmtBytes(u64);
 
 impl fmt::Display for FmtBytes {
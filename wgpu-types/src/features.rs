@@ -531,7 +531,7 @@ bitflags_array! {
     /// https://gpuweb.github.io/gpuweb/#enumdef-gpufeaturename).
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
-    pub struct Features: [u64; 2];
+    pub struct Features: [u64; 3];
 
     /// Features that are not guaranteed to be supported.
     ///
@@ -548,11 +548,14 @@ bitflags_array! {
     #[cfg_attr(feature = "serde", serde(transparent))]
     #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
     pub struct FeaturesWGPU features_wgpu {
-        /// Allows shaders to use f32 atomic load, store, add, sub, and exchange.
+        /// Allows shaders to use f32 atomic load, store, add, sub, and exchange on
+        /// `atomic<f32>` values in storage buffers.
         ///
         /// Supported platforms:
         /// - Metal (with MSL 3.0+ and Apple7+/Mac2)
         /// - Vulkan (with [VK_EXT_shader_atomic_float])
+        /// - DX12 (add and sub are emulated with a compare-and-swap loop, which is slower
+        ///   when many invocations update the same value)
         ///
         /// This is a native only feature.
         ///
@@ -616,20 +619,24 @@ bitflags_array! {
         // API:
 
         /// Enables use of Pipeline Statistics Queries. These queries tell the count of various operations
-        /// performed between the start and stop call. Call [`RenderPass::begin_pipeline_statistics_query`] to start
-        /// a query, then call [`RenderPass::end_pipeline_statistics_query`] to stop one.
+        /// performed by a whole render or compute pass. Set the `pipeline_statistics_query` field of
+        /// [`RenderPassDescriptor`] or [`ComputePassDescriptor`] to record one for a pass.
         ///
         /// They must be resolved using [`CommandEncoder::resolve_query_set`] into a buffer.
         /// The rules on how these resolve into buffers are detailed in the documentation for [`PipelineStatisticsTypes`].
         ///
+        /// For queries which start and stop inside of passes refer to
+        /// [`Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES`].
+        ///
         /// Supported Platforms:
         /// - Vulkan
         /// - DX12
+        /// - Metal (on GPUs exposing the `statistic` counter set)
         ///
         /// This is a native only feature with a [proposal](https://github.com/gpuweb/gpuweb/blob/0008bd30da2366af88180b511a5d0d0c1dffbc36/proposals/pipeline-statistics-query.md) for the web.
         ///
-        /// [`RenderPass::begin_pipeline_statistics_query`]: https://docs.rs/wgpu/latest/wgpu/struct.RenderPass.html#method.begin_pipeline_statistics_query
-        /// [`RenderPass::end_pipeline_statistics_query`]: https://docs.rs/wgpu/latest/wgpu/struct.RenderPass.html#method.end_pipeline_statistics_query
+        /// [`RenderPassDescriptor`]: https://docs.rs/wgpu/latest/wgpu/struct.RenderPassDescriptor.html
+        /// [`ComputePassDescriptor`]: https://docs.rs/wgpu/latest/wgpu/struct.ComputePassDescriptor.html
         /// [`CommandEncoder::resolve_query_set`]: https://docs.rs/wgpu/latest/wgpu/struct.CommandEncoder.html#method.resolve_query_set
         /// [`PipelineStatisticsTypes`]: super::PipelineStatisticsTypes
         const PIPELINE_STATISTICS_QUERY = 1 << 4;
@@ -811,6 +818,11 @@ bitflags_array! {
         /// - DX12
         /// - Vulkan 1.2+ (or VK_KHR_draw_indirect_count)
         ///
+        /// Emulated on all other platforms that support indirect execution and compute shaders,
+        /// by running a compute pass before the render pass that zeroes out the draws past the count.
+        /// All `max_count` draws are then submitted, so prefer a tight `max_count`.
+        /// Requesting the feature where it is emulated turns on indirect call validation.
+        ///
         /// This is a native only feature.
         ///
         /// [`RenderPass::multi_draw_indirect_count`]: ../wgpu/struct.RenderPass.html#method.multi_draw_indirect_count
@@ -904,11 +916,13 @@ bitflags_array! {
         /// Only triangles are supported.
         ///
         /// Supported platforms:
-        /// - Vulkan
+        /// - Vulkan (with [VK_EXT_conservative_rasterization])
+        /// - DX12 (Conservative rasterization tier 1 or above)
         ///
         /// This is a native only feature.
         ///
         /// [`PrimitiveState::conservative`]: super::PrimitiveState::conservative
+        /// [VK_EXT_conservative_rasterization]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_conservative_rasterization.html
         const CONSERVATIVE_RASTERIZATION = 1 << 21;
         /// Enables bindings of writable storage buffers and textures visible to vertex shaders.
         ///
@@ -951,9 +965,16 @@ bitflags_array! {
         const SPIRV_SHADER_PASSTHROUGH = 1 << 25;
         /// Enables multiview render passes and `builtin(view_index)` in vertex shaders.
         ///
+        /// A multiview pass renders to several array layers of its attachments at once, see
+        /// [`RenderPassDescriptor::multiview`]. Render pipelines and bundles used in the pass
+        /// must have the same view count.
+        ///
         /// Supported platforms:
         /// - Vulkan
-        /// - OpenGL (web only)
+        /// - OpenGL (WebGL with [OVR_multiview2] only)
+        ///
+        /// [`RenderPassDescriptor::multiview`]: ../wgpu/struct.RenderPassDescriptor.html#structfield.multiview
+        /// [OVR_multiview2]: https://registry.khronos.org/webgl/extensions/OVR_multiview2/
         ///
         /// This is a native only feature.
         const MULTIVIEW = 1 << 26;
@@ -967,12 +988,21 @@ bitflags_array! {
         const VERTEX_ATTRIBUTE_64BIT = 1 << 27;
         /// Enables image atomic fetch add, and, xor, or, min, and max for R32Uint and R32Sint textures.
         ///
+        /// In WGSL, these are the `textureAtomic*` functions on `texture_storage_*<r32uint, atomic>`
+        /// and `texture_storage_*<r32sint, atomic>` textures, which are bound with
+        /// [`StorageTextureAccess::Atomic`] and must have [`TextureUsages::STORAGE_ATOMIC`].
+        ///
         /// Supported platforms:
         /// - Vulkan
         /// - DX12
         /// - Metal (with MSL 3.1+)
+        /// - OpenGL (with GL 4.2+, GLES 3.2+ or GLES 3.1 with [OES_shader_image_atomic])
         ///
         /// This is a native only feature.
+        ///
+        /// [`StorageTextureAccess::Atomic`]: super::StorageTextureAccess::Atomic
+        /// [`TextureUsages::STORAGE_ATOMIC`]: super::TextureUsages::STORAGE_ATOMIC
+        /// [OES_shader_image_atomic]: https://registry.khronos.org/OpenGL/extensions/OES/OES_shader_image_atomic.txt
         const TEXTURE_ATOMIC = 1 << 28;
         /// Allows for creation of textures of format [`TextureFormat::NV12`]
         ///
@@ -1013,20 +1043,35 @@ bitflags_array! {
         ///
         /// This is a native-only feature.
         const EXPERIMENTAL_RAY_QUERY = 1 << 31;
-        /// Enables 64-bit floating point types in SPIR-V shaders.
+        /// Enables 64-bit floating point types in shaders: `f64` in WGSL, with `lf`-suffixed
+        /// literals like `1.0lf`, and `double` in SPIR-V and GLSL shaders.
         ///
         /// Note: even when supported by GPU hardware, 64-bit floating point operations are
         /// frequently between 16 and 64 _times_ slower than equivalent operations on 32-bit floats.
         ///
+        /// On DX12, HLSL only implements a few built-in functions for `double`, such as `abs`,
+        /// `min`, `max` and `fma`: shaders calling other built-in functions with `f64`
+        /// arguments fail to compile.
+        ///
         /// Supported Platforms:
         /// - Vulkan
+        /// - DX12
         ///
         /// This is a native only feature.
         const SHADER_F64 = 1 << 32;
-        /// Allows shaders to use i16. Not currently supported in `naga`, only available through `spirv-passthrough`.
+        /// Allows shaders to use the 16-bit integer types `i16` and `u16`.
+        ///
+        /// In WGSL, these types can be used in storage and uniform buffers, and for
+        /// function and private variables. Their size and alignment are 2 bytes, but arrays
+        /// of them in uniform buffers still need a stride that is a multiple of 16 bytes.
+        /// They can't be used in push constants or for inter-stage variables, and there are
+        /// no literals of these types: write `u16(x)` to convert an `x` of another integer
+        /// type. Floats must be converted to `i32` or `u32` first.
         ///
         /// Supported platforms:
-        /// - Vulkan
+        /// - Vulkan (with `shaderInt16` and 16-bit storage buffer access)
+        /// - DX12 (with Shader Model 6.2 and native 16-bit shader ops)
+        /// - Metal
         ///
         /// This is a native only feature.
         const SHADER_I16 = 1 << 33;
@@ -1109,10 +1154,8 @@ bitflags_array! {
         ///
         /// Supported platforms:
         /// - Vulkan
-        ///
-        /// Unimplemented Platforms:
-        /// - DX12
-        /// - Metal
+        /// - DX12 (with pipeline library support)
+        /// - Metal (with binary archive support)
         const PIPELINE_CACHE = 1 << 40;
         /// Allows shaders to use i64 and u64 atomic min and max.
         ///
@@ -1187,11 +1230,14 @@ bitflags_array! {
 
         /// Enables mesh shaders and task shaders in mesh shader pipelines.
         ///
+        /// Task and mesh stages must be passthrough shaders, as Naga can't translate them
+        /// from WGSL yet. On DX12, task shaders are amplification shaders.
+        ///
         /// Supported platforms:
         /// - Vulkan (with [VK_EXT_mesh_shader](https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_mesh_shader.html))
+        /// - DX12 (with Shader Model 6.5 and mesh shader tier 1)
         ///
         /// Potential Platforms:
-        /// - DX12
         /// - Metal
         ///
         /// This is a native only feature.
@@ -1242,6 +1288,224 @@ bitflags_array! {
         ///
         /// This is a native only feature.
         const HLSL_DXIL_SHADER_PASSTHROUGH = 1 << 51;
+
+        /// ***THIS IS EXPERIMENTAL:*** Features enabled by this may have
+        /// major bugs in them and are expected to be subject to breaking changes, suggestions
+        /// for the API exposed by this should be posted on [the ray-tracing issue](https://github.com/gfx-rs/wgpu/issues/1040)
+        ///
+        /// Allows for the creation of ray tracing pipelines, whose shaders are invoked through
+        /// shader binding tables by `CommandEncoder::trace_rays`.
+        ///
+        /// Ray generation, miss, closest hit and any hit stages can be written in WGSL
+        /// with `enable wgpu_ray_tracing_pipeline;`. Intersection and callable stages must
+        /// be passthrough shader modules: SPIR-V on Vulkan, which requires
+        /// [`Features::SPIRV_SHADER_PASSTHROUGH`], and HLSL or DXIL libraries on DX12, which
+        /// require [`Features::HLSL_DXIL_SHADER_PASSTHROUGH`]. On DX12, pipelines with
+        /// passthrough stages support ray payloads of up to 256 bytes.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_KHR_ray_tracing_pipeline](https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_ray_tracing_pipeline.html))
+        /// - DX12 (with DXR tier 1.1 and Shader Model 6.5)
+        ///
+        /// This is a native only feature.
+        const EXPERIMENTAL_RAY_TRACING_PIPELINE = 1 << 52;
+
+        /// ***THIS IS EXPERIMENTAL:*** Features enabled by this may have
+        /// major bugs in them and are expected to be subject to breaking changes, suggestions
+        /// for the API exposed by this should be posted on [the ray-tracing issue](https://github.com/gfx-rs/wgpu/issues/1040)
+        ///
+        /// Allows serializing bottom level acceleration structures with `Blas::serialize`, and
+        /// recreating them, possibly in a later run of the application, with
+        /// `Device::create_blas_from_serialized`.
+        ///
+        /// Serialized data can only be deserialized by a compatible driver, which can be
+        /// checked with `Device::is_serialized_blas_compatible`.
+        ///
+        /// Requires [`Features::EXPERIMENTAL_RAY_QUERY`].
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - DX12
+        ///
+        /// This is a native only feature.
+        const EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION = 1 << 53;
+
+        /// ***THIS IS EXPERIMENTAL:*** Features enabled by this may have
+        /// major bugs in them and are expected to be subject to breaking changes.
+        ///
+        /// Allows requesting additional compute, transfer and graphics queues with
+        /// `Adapter::request_device_with_queues`, so that work on one queue can overlap with
+        /// work on another. Command buffers for an additional queue are recorded with
+        /// `Device::create_command_encoder_for_queue`, and `Queue::wait_for_submission` orders
+        /// work across queues.
+        ///
+        /// When the adapter has fewer hardware queues than requested, some queues share
+        /// hardware with another queue. Their work is then executed in submission order
+        /// rather than in parallel.
+        ///
+        /// Surface textures can only be used on the main queue. Depth and stencil textures
+        /// should be written on the main queue before being used on other queues, as their
+        /// lazy zero-initialization requires a render pass.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with timeline semaphores)
+        /// - DX12
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const EXPERIMENTAL_MULTI_QUEUE = 1 << 54;
+
+        /// Allows creating fences that can be shared with other APIs and processes, with
+        /// `Device::create_shared_fence` and `Device::import_shared_fence`.
+        ///
+        /// `Queue::submit_with_external_sync` makes a submission wait for shared fences to
+        /// reach given values, and signals shared fences once the submission completes. This
+        /// synchronizes wgpu with external renderers, video decoders or compute APIs that
+        /// signal and wait on the same fence.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with timeline semaphores and [VK_KHR_external_semaphore_fd] or
+        ///   [VK_KHR_external_semaphore_win32])
+        /// - DX12
+        /// - Metal (with `MTLSharedEvent`)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [VK_KHR_external_semaphore_fd]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_external_semaphore_fd.html
+        /// [VK_KHR_external_semaphore_win32]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_external_semaphore_win32.html
+        const EXPERIMENTAL_SHARED_FENCE = 1 << 55;
+
+        /// Allows importing Linux DMA-BUFs as textures and buffers, with the
+        /// [VK_EXT_external_memory_dma_buf] and [VK_EXT_image_drm_format_modifier] Vulkan
+        /// extensions.
+        ///
+        /// DMA-BUFs are imported through `wgpu_hal::vulkan::Device::texture_from_dmabuf` and
+        /// `wgpu_hal::vulkan::Device::buffer_from_dmabuf`, and the result wrapped with
+        /// [`Device::create_texture_from_hal()`] or [`Device::create_buffer_from_hal()`].
+        /// `wgpu_hal::vulkan::Adapter::drm_format_modifiers` lists the DRM format modifiers
+        /// that textures can be imported with.
+        ///
+        /// Supported platforms:
+        /// - Vulkan on Linux and Android (with [VK_EXT_external_memory_dma_buf] and
+        ///   [VK_EXT_image_drm_format_modifier])
+        ///
+        /// This is a native only feature.
+        ///
+        /// [VK_EXT_external_memory_dma_buf]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_external_memory_dma_buf.html
+        /// [VK_EXT_image_drm_format_modifier]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_image_drm_format_modifier.html
+        /// [`Device::create_texture_from_hal()`]: https://docs.rs/wgpu/latest/wgpu/struct.Device.html#method.create_texture_from_hal
+        /// [`Device::create_buffer_from_hal()`]: https://docs.rs/wgpu/latest/wgpu/struct.Device.html#method.create_buffer_from_hal
+        const VULKAN_EXTERNAL_MEMORY_DMA_BUF = 1 << 56;
+
+        /// Allows importing Android `AHardwareBuffer`s as textures, and on Vulkan as
+        /// buffers.
+        ///
+        /// Hardware buffers are imported through
+        /// `wgpu_hal::vulkan::Device::texture_from_android_hardware_buffer`,
+        /// `wgpu_hal::vulkan::Device::buffer_from_android_hardware_buffer` or
+        /// `wgpu_hal::gles::Device::texture_from_android_hardware_buffer`, and the result
+        /// wrapped with [`Device::create_texture_from_hal()`] or
+        /// [`Device::create_buffer_from_hal()`].
+        ///
+        /// Only hardware buffers whose format has an equivalent [`TextureFormat`] can be
+        /// imported. This includes the YUV formats [`TextureFormat::NV12`] and
+        /// [`TextureFormat::P010`] on Vulkan, whose planes can be viewed separately.
+        ///
+        /// Supported platforms:
+        /// - Vulkan on Android (with [VK_ANDROID_external_memory_android_hardware_buffer])
+        /// - OpenGL ES on Android (with `EGL_ANDROID_get_native_client_buffer`,
+        ///   `EGL_ANDROID_image_native_buffer` and `GL_OES_EGL_image`)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`TextureFormat`]: super::TextureFormat
+        /// [`TextureFormat::NV12`]: super::TextureFormat::NV12
+        /// [`TextureFormat::P010`]: super::TextureFormat::P010
+        /// [VK_ANDROID_external_memory_android_hardware_buffer]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_ANDROID_external_memory_android_hardware_buffer.html
+        /// [`Device::create_texture_from_hal()`]: https://docs.rs/wgpu/latest/wgpu/struct.Device.html#method.create_texture_from_hal
+        /// [`Device::create_buffer_from_hal()`]: https://docs.rs/wgpu/latest/wgpu/struct.Device.html#method.create_buffer_from_hal
+        const EXTERNAL_MEMORY_ANDROID_HARDWARE_BUFFER = 1 << 57;
+
+        /// Allows for creation of textures of format [`TextureFormat::P010`]
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`TextureFormat::P010`]: super::TextureFormat::P010
+        const TEXTURE_FORMAT_P010 = 1 << 58;
+        /// Allows for creation of textures of format [`TextureFormat::I420`]
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`TextureFormat::I420`]: super::TextureFormat::I420
+        const TEXTURE_FORMAT_I420 = 1 << 59;
+        /// Allows querying feedback about presented frames with
+        /// `Surface::get_presentation_statistics` and scheduling the time at which a frame
+        /// is presented with `SurfaceTexture::set_target_present_time`.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_GOOGLE_display_timing])
+        /// - DX12 (target present times are ignored)
+        /// - Metal on macOS 11+ and iOS 10.3+ (the refresh interval isn't reported)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [VK_GOOGLE_display_timing]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_GOOGLE_display_timing.html
+        const PRESENTATION_TIMING = 1 << 60;
+        /// Allows creating memory heaps with `Device::create_memory_heap` and placing textures
+        /// in them with `Device::create_texture_aliased`.
+        ///
+        /// Textures placed in overlapping regions of a heap share memory, which lets
+        /// render graphs reuse the memory of attachments that are never alive at the same
+        /// time. Overlapping textures can't be used in the same pass, and the contents of a
+        /// placed texture don't persist from one command buffer to the next.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - DX12 (with resource heap tier 2)
+        /// - Metal on macOS 10.15+ and iOS 13+
+        ///
+        /// This is a native only feature.
+        const TEXTURE_ALIASING = 1 << 61;
+        /// Allows creating sparse buffers and textures with `Device::create_sparse_buffer` and
+        /// `Device::create_sparse_texture`, whose memory is bound page by page with
+        /// `Queue::bind_sparse_memory`.
+        ///
+        /// Sparse resources can be much larger than the memory bound to them, which lets
+        /// virtual texturing and streaming systems keep only the parts they need resident.
+        /// The memory comes from memory heaps, so this also needs [`Features::TEXTURE_ALIASING`].
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with sparse residency for buffers and 2D images, and standard block shapes)
+        /// - DX12 (with tiled resources and resource heap tier 2)
+        ///
+        /// Metal isn't supported, as its sparse heaps choose the memory of each tile themselves.
+        ///
+        /// This is a native only feature.
+        const SPARSE_BINDING = 1 << 62;
+        /// Allows resolving multisampled depth/stencil attachments at the end of a render pass
+        /// with [`RenderPassDepthStencilAttachment::resolve`][RPDSAr].
+        ///
+        /// Depth can be resolved by taking sample zero, or the minimum or maximum of all samples
+        /// (see [`ResolveMode`]). Stencil is always resolved by taking sample zero.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_KHR_depth_stencil_resolve] and independent resolve modes)
+        /// - DX12 (resolved with an internal shader)
+        /// - Metal on macOS 10.14+ and iOS 12+ (Apple3 GPU family)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [RPDSAr]: ../wgpu/struct.RenderPassDepthStencilAttachment.html#structfield.resolve
+        /// [`ResolveMode`]: super::ResolveMode
+        /// [VK_KHR_depth_stencil_resolve]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_depth_stencil_resolve.html
+        const DEPTH_STENCIL_RESOLVE = 1 << 63;
     }
 
     /// Features that are not guaranteed to be supported.
@@ -1476,6 +1740,7 @@ bitflags_array! {
         ///
         /// Supported platforms:
         /// - OpenGL ES (with GL_EXT_blend_func_extended)
+        /// - OpenGL (with GL_ARB_blend_func_extended)
         /// - Metal (with MSL 1.2+)
         /// - Vulkan (with dualSrcBlend)
         /// - DX12
@@ -1492,6 +1757,335 @@ bitflags_array! {
         /// This is a web and native feature.
         const CLIP_DISTANCES = WEBGPU_FEATURE_CLIP_DISTANCES;
     }
+
+    /// Features that are not guaranteed to be supported.
+    ///
+    /// These are extension features supported by wgpu when targeting native, which no longer
+    /// fit in [`FeaturesWGPU`]. For all features see [`Features`]
+    ///
+    /// If you want to use a feature, you need to first verify that the adapter supports
+    /// the feature. If the adapter does not support the feature, requesting a device with it enabled
+    /// will panic.
+    #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct FeaturesWGPU2 features_wgpu2 {
+        /// Allows fragment shaders to read the value of a color attachment at the current
+        /// fragment.
+        ///
+        /// In WGSL, this is enabled with `enable wgpu_framebuffer_fetch;`, and the value is read
+        /// through a fragment input with the `@color(N)` attribute, where `N` is the index of the
+        /// color target. The input must have the same type as the fragment output for that
+        /// target, which must be a 32-bit scalar or vector. Only color targets can be read:
+        /// fetching depth and stencil values is not implemented yet.
+        ///
+        /// Supported platforms:
+        /// - Metal (Apple GPU families)
+        /// - OpenGL ES (with [GL_EXT_shader_framebuffer_fetch])
+        ///
+        /// Potential Platforms:
+        /// - Vulkan (subpass input attachments, not implemented yet)
+        ///
+        /// Not supported on DX12, desktop OpenGL or WebGL.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [GL_EXT_shader_framebuffer_fetch]: https://registry.khronos.org/OpenGL/extensions/EXT/EXT_shader_framebuffer_fetch.txt
+        const FRAMEBUFFER_FETCH = 1 << 0;
+        /// Allows [`RenderPass::set_shading_rate`] to lower the rate at which the fragment
+        /// shader runs for the following draws, so one invocation colors a block of pixels.
+        ///
+        /// Only the rates up to [`ShadingRate::TwoByTwo`] are guaranteed. Larger rates are
+        /// clamped to the largest rate the device supports that fits inside them. Rates are
+        /// reset to [`ShadingRate::OneByOne`] at the start of every render pass.
+        ///
+        /// Supported platforms:
+        /// - DX12 (Variable shading rate tier 1 or above)
+        /// - Vulkan (with [VK_KHR_fragment_shading_rate] and `pipelineFragmentShadingRate`)
+        ///
+        /// Shading rate attachments and Metal rasterization rate maps are not supported yet.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::set_shading_rate`]: ../wgpu/struct.RenderPass.html#method.set_shading_rate
+        /// [`ShadingRate::TwoByTwo`]: crate::ShadingRate::TwoByTwo
+        /// [`ShadingRate::OneByOne`]: crate::ShadingRate::OneByOne
+        /// [VK_KHR_fragment_shading_rate]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_fragment_shading_rate.html
+        const VARIABLE_RATE_SHADING = 1 << 1;
+        /// Allows fragment shaders to read the `fully_covered` built-in, a `bool` that is
+        /// true when the primitive covers the whole fragment. This gives an under-estimate
+        /// on top of the over-estimate from [`PrimitiveState::conservative`], which
+        /// pipelines reading the built-in must enable.
+        ///
+        /// This requires [`Features::CONSERVATIVE_RASTERIZATION`] to be useful.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `fullyCoveredFragmentShaderInputVariable`)
+        ///
+        /// DX12 inner coverage (`SV_InnerCoverage`) is not supported yet.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`PrimitiveState::conservative`]: super::PrimitiveState::conservative
+        const CONSERVATIVE_RASTERIZATION_FULLY_COVERED = 1 << 2;
+        /// Allows [`PrimitiveState::line_width`] to be other than `1.0`.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `wideLines`)
+        /// - OpenGL/GLES (when `ALIASED_LINE_WIDTH_RANGE` goes above 1)
+        ///
+        /// Elsewhere, wide lines are emulated by drawing each segment of a line as a quad,
+        /// which restricts the pipelines and draws that can use them:
+        /// - The topology must be a line list or strip, without stippling or polygon
+        ///   mode [`PolygonMode::Line`], and the pipeline can't be a mesh pipeline.
+        /// - The layout can't have push constant ranges, and the shader can't be a
+        ///   passthrough shader.
+        /// - Vertex buffers must use [`VertexStepMode::Vertex`], and each of them and
+        ///   their attributes count twice against the limits.
+        /// - Only non-indexed, direct draws are supported, outside of render bundles,
+        ///   and each instance is a separate draw.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`PrimitiveState::line_width`]: super::PrimitiveState::line_width
+        /// [`PolygonMode::Line`]: super::PolygonMode::Line
+        /// [`VertexStepMode::Vertex`]: super::VertexStepMode::Vertex
+        const WIDE_LINES = 1 << 3;
+        /// Allows [`PrimitiveState::line_stipple`] to draw dashed and dotted lines.
+        ///
+        /// Stippled lines are rasterized with Bresenham's algorithm, so they aren't
+        /// antialiased and can't be used with alpha to coverage.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_EXT_line_rasterization] and `stippledBresenhamLines`)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`PrimitiveState::line_stipple`]: super::PrimitiveState::line_stipple
+        /// [VK_EXT_line_rasterization]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_line_rasterization.html
+        const LINE_STIPPLE = 1 << 4;
+        /// Allows [`ColorTargetState::logic_op`] to combine fragment outputs with color
+        /// targets using bitwise operations instead of blending.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `logicOp`)
+        /// - DX12 (with `OutputMergerLogicOp`)
+        /// - OpenGL (with `glLogicOp`)
+        ///
+        /// OpenGL ES, WebGL and Metal have no logic operations.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`ColorTargetState::logic_op`]: super::ColorTargetState::logic_op
+        const LOGIC_OP = 1 << 5;
+        /// Allows [`RenderPass::set_viewports`] to set up to [`MAX_VIEWPORTS`] viewports,
+        /// and vertex shaders to write the `viewport_index` and `layer` built-ins to pick
+        /// the viewport and the attachment array layer that each primitive is rasterized
+        /// into.
+        ///
+        /// Render pass attachments may also have more than one array layer without
+        /// [`RenderPassDescriptor::multiview`], as long as they all have the same number
+        /// of layers. A cube map can then be rendered in a single pass, through a
+        /// [`TextureViewDimension::D2Array`] view of its six faces.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `multiViewport` and [VK_EXT_shader_viewport_index_layer])
+        /// - DX12 (with `VPAndRTArrayIndexFromAnyShaderFeedingRasterizerSupportedWithoutGSEmulation`)
+        /// - Metal (Apple5+ and Mac2 families, or Mac1 on macOS 10.13+)
+        ///
+        /// Not supported on OpenGL, OpenGL ES or WebGL.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::set_viewports`]: ../wgpu/struct.RenderPass.html#method.set_viewports
+        /// [`MAX_VIEWPORTS`]: crate::MAX_VIEWPORTS
+        /// [`RenderPassDescriptor::multiview`]: ../wgpu/struct.RenderPassDescriptor.html#structfield.multiview
+        /// [`TextureViewDimension::D2Array`]: crate::TextureViewDimension::D2Array
+        /// [VK_EXT_shader_viewport_index_layer]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_shader_viewport_index_layer.html
+        const MULTI_VIEWPORT = 1 << 6;
+        /// Allows [`IndexFormat::Uint8`] index buffers, for meshes that don't need
+        /// more than 256 distinct vertices.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_EXT_index_type_uint8])
+        /// - OpenGL/GLES
+        /// - Emulated elsewhere with compute shaders, including DX12 and Metal
+        ///
+        /// Emulated 8 bit index buffers are widened to 16 bit indices by a compute
+        /// shader before each render pass that draws with them. The buffer is then used
+        /// as a read-only storage buffer by the pass, and emulated 8 bit index buffers
+        /// can't be used in render bundles.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`IndexFormat::Uint8`]: super::IndexFormat::Uint8
+        /// [VK_EXT_index_type_uint8]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_index_type_uint8.html
+        const INDEX_FORMAT_UINT8 = 1 << 7;
+        /// Allows [`VertexStepMode::InstanceStepRate`] vertex buffers to use a step `rate`
+        /// other than 1, so that groups of instances share the same per-instance data.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_EXT_vertex_attribute_divisor])
+        /// - DX12
+        /// - Metal
+        /// - OpenGL/GLES
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`VertexStepMode::InstanceStepRate`]: super::VertexStepMode::InstanceStepRate
+        /// [VK_EXT_vertex_attribute_divisor]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_vertex_attribute_divisor.html
+        const VERTEX_INSTANCE_STEP_RATE = 1 << 8;
+        /// Allows [`PrimitiveState::primitive_restart`] to be disabled for strip topologies,
+        /// so that the largest index value is drawn like any other index.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - DX12
+        /// - OpenGL (desktop, with GL 4.3 or `GL_ARB_ES3_compatibility`)
+        /// - OpenGL ES
+        ///
+        /// Metal and WebGL always restart strips.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`PrimitiveState::primitive_restart`]: super::PrimitiveState::primitive_restart
+        const PRIMITIVE_RESTART_CONTROL = 1 << 9;
+        /// Allows command buffers to be finished with [`CommandBufferDescriptor::reusable`],
+        /// so that they can be submitted any number of times without recording their
+        /// commands again.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - DX12
+        /// - OpenGL/GLES
+        ///
+        /// Metal command buffers can only be committed once.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`CommandBufferDescriptor::reusable`]: super::CommandBufferDescriptor::reusable
+        const REUSABLE_COMMAND_BUFFERS = 1 << 10;
+        /// Allows shaders to print formatted messages for debugging.
+        ///
+        /// In WGSL, this is enabled with `enable wgpu_debug_printf;`, and messages are
+        /// printed with the `debugPrintf("format", args...)` statement. The format string
+        /// uses the conversion specifiers of `printf`, such as `%d`, `%u`, `%x` and `%f`,
+        /// with a size prefix for vectors, as in `%v4f`. Arguments must be 32-bit scalars
+        /// or vectors.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_KHR_shader_non_semantic_info])
+        /// - DX12
+        /// - Metal
+        ///
+        /// On Vulkan, the messages are printed by the Khronos validation layer, so they are
+        /// only printed when [`InstanceFlags::VALIDATION`] is set and the layer is installed.
+        /// On DX12 and Metal, shaders write the messages to a buffer, which is read back and
+        /// formatted once the submissions writing them are done, when the device is polled.
+        /// Messages written faster than they are read back are dropped with a warning.
+        /// Either way, they are logged at the `Info` level.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`InstanceFlags::VALIDATION`]: crate::InstanceFlags::VALIDATION
+        /// [VK_KHR_shader_non_semantic_info]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_shader_non_semantic_info.html
+        const SHADER_DEBUG_PRINTF = 1 << 11;
+        /// Allows for pipeline statistics queries to be started and stopped inside of render
+        /// passes, compute passes and render bundles, using:
+        /// - [`RenderPass::begin_pipeline_statistics_query`]
+        /// - [`ComputePass::begin_pipeline_statistics_query`]
+        /// - [`RenderBundleEncoder::begin_pipeline_statistics_query`]
+        ///
+        /// Implies [`Features::PIPELINE_STATISTICS_QUERY`] is supported.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - DX12
+        ///
+        /// Metal can only sample its counters at the boundaries of a pass.
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::begin_pipeline_statistics_query`]: https://docs.rs/wgpu/latest/wgpu/struct.RenderPass.html#method.begin_pipeline_statistics_query
+        /// [`ComputePass::begin_pipeline_statistics_query`]: https://docs.rs/wgpu/latest/wgpu/struct.ComputePass.html#method.begin_pipeline_statistics_query
+        /// [`RenderBundleEncoder::begin_pipeline_statistics_query`]: https://docs.rs/wgpu/latest/wgpu/struct.RenderBundleEncoder.html#method.begin_pipeline_statistics_query
+        const PIPELINE_STATISTICS_QUERY_INSIDE_PASSES = 1 << 12;
+        /// Makes [`RenderPass::begin_conditional_block`] skip the draws of the block when
+        /// its predicate, usually a resolved occlusion query, is zero.
+        ///
+        /// Conditional blocks can be recorded without this feature, but then their draws
+        /// are always executed.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with [VK_EXT_conditional_rendering])
+        /// - DX12
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::begin_conditional_block`]: https://docs.rs/wgpu/latest/wgpu/struct.RenderPass.html#method.begin_conditional_block
+        /// [VK_EXT_conditional_rendering]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_EXT_conditional_rendering.html
+        const CONDITIONAL_RENDERING = 1 << 13;
+        /// Allows shaders to use the 8-bit integer types `i8` and `u8`.
+        ///
+        /// These follow the same rules as the types enabled by [`Features::SHADER_I16`],
+        /// with a size and alignment of 1 byte: write `u8(x)` to convert an `x` of
+        /// another integer type.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with `shaderInt8` and 8-bit storage buffer access)
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const SHADER_I8 = 1 << 14;
+        /// Allows [`BindingType::YcbcrTexture`] bindings, which sample multi-planar
+        /// textures such as [`TextureFormat::NV12`] through a [`SamplerYcbcrConversion`],
+        /// returning RGB with the chosen matrix, range and chroma siting.
+        ///
+        /// The formats themselves are enabled by [`Features::TEXTURE_FORMAT_NV12`],
+        /// [`Features::TEXTURE_FORMAT_P010`] and [`Features::TEXTURE_FORMAT_I420`].
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.1 (with `samplerYcbcrConversion`, and linear chroma filtering with
+        ///   both chroma locations on every multi-planar format that is supported)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`BindingType::YcbcrTexture`]: super::BindingType::YcbcrTexture
+        /// [`TextureFormat::NV12`]: super::TextureFormat::NV12
+        /// [`SamplerYcbcrConversion`]: super::SamplerYcbcrConversion
+        const YUV_SAMPLING = 1 << 15;
+        /// Keeps a render pass suspended with [`RenderPassFlags::SUSPENDING`] open until
+        /// the pass resuming it, so its attachments stay in tile memory instead of
+        /// being stored to memory and loaded back.
+        ///
+        /// Passes can be suspended and resumed without this feature, but then the pass
+        /// is ended and started again in between.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (1.3 or [VK_KHR_dynamic_rendering]; every render pass of the device is
+        ///   then begun with dynamic rendering)
+        /// - Metal (for passes suspended and resumed in the same command encoder)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPassFlags::SUSPENDING`]: super::RenderPassFlags::SUSPENDING
+        /// [VK_KHR_dynamic_rendering]: https://registry.khronos.org/vulkan/specs/latest/man/html/VK_KHR_dynamic_rendering.html
+        const RENDER_PASS_SUSPEND_RESUME = 1 << 16;
+        /// Allows `RenderPass::create_parallel_encoder` to record parts of a render pass
+        /// on several threads at once.
+        ///
+        /// Each part is validated and encoded on the thread that records it, into a
+        /// secondary command buffer on Vulkan and into a render command encoder of a
+        /// parallel render command encoder on Metal.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const PARALLEL_RENDER_ENCODING = 1 << 17;
+    }
 }
 
 impl Features {
@@ -1501,6 +2095,7 @@ impl Features {
         Self::from_bits_truncate(FeatureBits([
             FeaturesWGPU::empty().bits(),
             FeaturesWebGPU::all().bits(),
+            FeaturesWGPU2::empty().bits(),
         ]))
     }
 
@@ -1510,6 +2105,7 @@ impl Features {
         Self::from_bits_truncate(FeatureBits([
             FeaturesWGPU::all().bits(),
             FeaturesWebGPU::empty().bits(),
+            FeaturesWGPU2::all().bits(),
         ]))
     }
 
@@ -1533,7 +2129,7 @@ impl Features {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Features, FeaturesWGPU, FeaturesWebGPU};
+    use crate::{Features, FeaturesWGPU, FeaturesWGPU2, FeaturesWebGPU};
 
     #[cfg(feature = "serde")]
     #[test]
@@ -1625,6 +2221,9 @@ mod tests {
         let features: Features = FeaturesWebGPU::TIMESTAMP_QUERY.into();
         assert_eq!(features, Features::TIMESTAMP_QUERY);
 
+        let features: Features = FeaturesWGPU2::FRAMEBUFFER_FETCH.into();
+        assert_eq!(features, Features::FRAMEBUFFER_FETCH);
+
         let features: Features = Features::from(FeaturesWGPU::TEXTURE_ATOMIC)
             | Features::from(FeaturesWebGPU::TIMESTAMP_QUERY);
         assert_eq!(
@@ -1635,7 +2234,8 @@ mod tests {
             features,
             Features::from_internal_flags(
                 FeaturesWGPU::TEXTURE_ATOMIC,
-                FeaturesWebGPU::TIMESTAMP_QUERY
+                FeaturesWebGPU::TIMESTAMP_QUERY,
+                FeaturesWGPU2::empty()
             )
         );
     }
@@ -2,13 +2,18 @@
 
 use alloc::string::String;
 
-use crate::Backends;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Backends, TogglesDescriptor};
 
 #[cfg(doc)]
 use crate::{Backend, DownlevelFlags};
 
 /// Options for creating an instance.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct InstanceDescriptor {
     /// Which `Backends` to enable.
     pub backends: Backends,
@@ -18,6 +23,9 @@ pub struct InstanceDescriptor {
     pub memory_budget_thresholds: MemoryBudgetThresholds,
     /// Options the control the behavior of various backends.
     pub backend_options: BackendOptions,
+    /// Adapter toggles to force on or off. Device toggles are ignored here, and are set
+    /// on each device instead.
+    pub toggles: TogglesDescriptor,
 }
 
 impl InstanceDescriptor {
@@ -37,11 +45,13 @@ impl InstanceDescriptor {
         let backends = self.backends.with_env();
         let flags = self.flags.with_env();
         let backend_options = self.backend_options.with_env();
+        let toggles = self.toggles.with_env();
         Self {
             backends,
             flags,
             memory_budget_thresholds: MemoryBudgetThresholds::default(),
             backend_options,
+            toggles,
         }
     }
 }
@@ -53,6 +63,8 @@ bitflags::bitflags! {
     ///
     /// Defaults to enabling debugging-related flags if the build configuration has `debug_assertions`.
     #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
     pub struct InstanceFlags: u32 {
         /// Generate debug information in shaders and objects.
@@ -80,7 +92,7 @@ bitflags::bitflags! {
         /// When `Self::from_env()` is used takes value from `WGPU_ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER` environment variable.
         const ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER = 1 << 3;
         /// Enable GPU-based validation. Implies [`Self::VALIDATION`]. Currently, this only changes
-        /// behavior on the DX12 and Vulkan backends.
+        /// behavior on the DX12, Vulkan and Metal backends.
         ///
         /// Supported platforms:
         ///
@@ -88,6 +100,13 @@ bitflags::bitflags! {
         ///   "GBV"](https://web.archive.org/web/20230206120404/https://learn.microsoft.com/en-us/windows/win32/direct3d12/using-d3d12-debug-layer-gpu-based-validation)
         /// - Vulkan, via the `VK_LAYER_KHRONOS_validation` layer; called ["GPU-Assisted
         ///   Validation"](https://github.com/KhronosGroup/Vulkan-ValidationLayers/blob/e45aeb85079e0835694cb8f03e6681fd18ae72c9/docs/gpu_validation.md#gpu-assisted-validation)
+        /// - Metal, on macOS 15 and iOS 18 or later; called "shader validation". Only enabled
+        ///   for pipelines created by wgpu, not for the whole process.
+        ///
+        /// The errors found by GPU-based validation are reported to the uncaptured error handler
+        /// of the device, with the labels of the objects involved, when the device is polled.
+        /// They are found while the GPU executes commands, so error scopes can't catch them.
+        /// On D3D12 and Vulkan, errors are reported to every device of the instance.
         ///
         /// When `Self::from_env()` is used takes value from `WGPU_GPU_BASED_VALIDATION` environment variable.
         const GPU_BASED_VALIDATION = 1 << 4;
@@ -128,6 +147,35 @@ bitflags::bitflags! {
         ///
         /// [rqs]: ../wgpu/struct.CommandEncoder.html#method.resolve_query_set
         const AUTOMATIC_TIMESTAMP_NORMALIZATION = 1 << 6;
+
+        /// Restrict devices to what can also be used through the WebGPU backend, to catch
+        /// code that won't run in browsers during native development.
+        ///
+        /// With this flag set, requesting a device fails if it requires features that are
+        /// not part of WebGPU (see [`Features::all_webgpu_mask()`][awm]), or requires
+        /// native-only limits, such as push constants or binding arrays, above their
+        /// defaults. Creating a shader module from anything but WGSL logs a warning.
+        ///
+        /// When `Self::from_env()` is used takes value from `WGPU_STRICT_WEBGPU_PORTABILITY` environment variable.
+        ///
+        /// [awm]: crate::Features::all_webgpu_mask
+        const STRICT_WEBGPU_PORTABILITY = 1 << 7;
+
+        /// Collect diagnostics about GPU faults, and add them to the message passed to
+        /// the device lost callback when a device is lost.
+        ///
+        /// Supported platforms:
+        ///
+        /// - D3D12; enables [Device Removed Extended Data](https://learn.microsoft.com/en-us/windows/win32/direct3d12/use-dred)
+        ///   auto-breadcrumbs and page fault reporting. These settings are process-wide and
+        ///   apply to devices created afterwards, and add some CPU overhead to command recording.
+        /// - Vulkan; enables `VK_EXT_device_fault`, if supported.
+        ///
+        /// Metal always reports the error of the command buffer that failed, whether or not
+        /// this flag is set.
+        ///
+        /// When `Self::from_env()` is used takes value from `WGPU_DEVICE_FAULT_DIAGNOSTICS` environment variable.
+        const DEVICE_FAULT_DIAGNOSTICS = 1 << 8;
     }
 }
 
@@ -185,6 +233,8 @@ impl InstanceFlags {
     /// - `WGPU_ALLOW_UNDERLYING_NONCOMPLIANT_ADAPTER`
     /// - `WGPU_GPU_BASED_VALIDATION`
     /// - `WGPU_VALIDATION_INDIRECT_CALL`
+    /// - `WGPU_STRICT_WEBGPU_PORTABILITY`
+    /// - `WGPU_DEVICE_FAULT_DIAGNOSTICS`
     #[must_use]
     pub fn with_env(mut self) -> Self {
         fn env(key: &str) -> Option<bool> {
@@ -213,6 +263,12 @@ impl InstanceFlags {
         if let Some(bit) = env("WGPU_VALIDATION_INDIRECT_CALL") {
             self.set(Self::VALIDATION_INDIRECT_CALL, bit);
         }
+        if let Some(bit) = env("WGPU_STRICT_WEBGPU_PORTABILITY") {
+            self.set(Self::STRICT_WEBGPU_PORTABILITY, bit);
+        }
+        if let Some(bit) = env("WGPU_DEVICE_FAULT_DIAGNOSTICS") {
+            self.set(Self::DEVICE_FAULT_DIAGNOSTICS, bit);
+        }
 
         self
     }
@@ -222,6 +278,8 @@ impl InstanceFlags {
 ///
 /// Currently only the D3D12 and (optionally) Vulkan backends support these options.
 #[derive(Default, Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MemoryBudgetThresholds {
     /// Threshold at which texture, buffer, query set and acceleration structure creation will start to return OOM errors.
     /// This is a percent of the memory budget reported by native APIs.
@@ -240,6 +298,8 @@ pub struct MemoryBudgetThresholds {
 ///
 /// Part of [`InstanceDescriptor`].
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct BackendOptions {
     /// Options for the OpenGL/OpenGLES backend, [`Backend::Gl`].
     pub gl: GlBackendOptions,
@@ -279,6 +339,8 @@ impl BackendOptions {
 ///
 /// Part of [`BackendOptions`].
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GlBackendOptions {
     /// Which OpenGL ES 3 minor version to request, if using OpenGL ES.
     pub gles_minor_version: Gles3MinorVersion,
@@ -317,6 +379,8 @@ impl GlBackendOptions {
 ///
 /// Part of [`BackendOptions`].
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Dx12BackendOptions {
     /// Which DX12 shader compiler to use.
     pub shader_compiler: Dx12Compiler,
@@ -348,6 +412,8 @@ impl Dx12BackendOptions {
 ///
 /// Part of [`BackendOptions`].
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct NoopBackendOptions {
     /// Whether to allow the noop backend to be used.
     ///
@@ -355,6 +421,17 @@ pub struct NoopBackendOptions {
     /// it must not be used when not expected. Therefore, it will not be used unless explicitly
     /// enabled.
     pub enable: bool,
+    /// Whether the command buffers submitted to the queues of the noop backend are recorded.
+    ///
+    /// The recorded commands can be taken with `wgpu::Queue::take_recorded_command_buffers`,
+    /// to check which commands some rendering code encodes, with the barriers and other commands
+    /// `wgpu-core` adds after validating them, without any GPU. See [`RecordedCommandBuffer`].
+    ///
+    /// Recording requires the `noop_recording` feature of `wgpu-hal`, which the `noop` feature
+    /// of `wgpu` enables.
+    ///
+    /// [`RecordedCommandBuffer`]: crate::RecordedCommandBuffer
+    pub record: bool,
 }
 
 impl NoopBackendOptions {
@@ -366,6 +443,7 @@ impl NoopBackendOptions {
     pub fn from_env_or_default() -> Self {
         Self {
             enable: Self::enable_from_env().unwrap_or(false),
+            record: false,
         }
     }
 
@@ -377,6 +455,7 @@ impl NoopBackendOptions {
     pub fn with_env(self) -> Self {
         Self {
             enable: Self::enable_from_env().unwrap_or(self.enable),
+            record: self.record,
         }
     }
 
@@ -392,6 +471,7 @@ impl NoopBackendOptions {
 
 /// DXC shader model.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(missing_docs)]
 pub enum DxcShaderModel {
     V6_0,
@@ -406,6 +486,7 @@ pub enum DxcShaderModel {
 
 /// Selects which DX12 shader compiler to use.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Dx12Compiler {
     /// The Fxc compiler (default) is old, slow and unmaintained.
     ///
@@ -480,6 +561,7 @@ impl Dx12Compiler {
 ///
 /// When using ANGLE as an OpenGL ES/EGL implementation, explicitly requesting `Version1` can provide a non-conformant ES 3.1 on APIs like D3D11.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Gles3MinorVersion {
     /// No explicit minor version is requested, the driver automatically picks the highest available.
     #[default]
@@ -530,6 +612,7 @@ impl Gles3MinorVersion {
 
 /// Dictate the behavior of fences in OpenGL.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GlFenceBehavior {
     /// Fences in OpenGL behave normally. If you don't know what to pick, this is what you want.
     #[default]
@@ -40,11 +40,17 @@ pub mod error;
 mod features;
 pub mod instance;
 pub mod math;
+mod recording;
+mod shader_layout;
+mod toggles;
 mod transfers;
 
 pub use counters::*;
 pub use features::*;
 pub use instance::*;
+pub use recording::*;
+pub use shader_layout::*;
+pub use toggles::*;
 pub use transfers::*;
 
 /// Integral type used for [`Buffer`] offsets and sizes.
@@ -100,12 +106,12 @@ pub const MAP_ALIGNMENT: BufferAddress = 8;
 /// [Vertex buffer offsets] and [strides] have to be a multiple of this number.
 ///
 /// [Vertex buffer offsets]: ../wgpu/util/trait.RenderEncoder.html#tymethod.set_vertex_buffer
-/// [strides]: ../wgpu/struct.VertexBufferLayout.html#structfield.array_stride
+/// [strides]: VertexBufferLayout::array_stride
 pub const VERTEX_ALIGNMENT: BufferAddress = 4;
 
 /// [Vertex buffer strides] have to be a multiple of this number.
 ///
-/// [Vertex buffer strides]: ../wgpu/struct.VertexBufferLayout.html#structfield.array_stride
+/// [Vertex buffer strides]: VertexBufferLayout::array_stride
 #[deprecated(note = "Use `VERTEX_ALIGNMENT` instead", since = "27.0.0")]
 pub const VERTEX_STRIDE_ALIGNMENT: BufferAddress = 4;
 
@@ -122,6 +128,18 @@ pub const QUERY_SET_MAX_QUERIES: u32 = 4096;
 /// [query]: ../wgpu/struct.QuerySet.html
 pub const QUERY_SIZE: u32 = 8;
 
+/// Maximum number of viewports that can be set with [`RenderPass::set_viewports`].
+///
+/// [`RenderPass::set_viewports`]: ../wgpu/struct.RenderPass.html#method.set_viewports
+pub const MAX_VIEWPORTS: u32 = 16;
+
+/// Size in bytes of a page of memory bound to a sparse resource.
+///
+/// Memory is bound to sparse buffers in whole pages, and to sparse textures in
+/// whole tiles of [`TextureFormat::sparse_tile_size`] texels, each of which takes up
+/// a page.
+pub const SPARSE_PAGE_SIZE: BufferAddress = 64 * 1024;
+
 /// Backends supported by wgpu.
 ///
 /// See also [`Backends`].
@@ -160,6 +178,12 @@ pub enum Backend {
     Gl = 4,
     /// WebGPU in the browser
     BrowserWebGpu = 5,
+    /// Software implementation running on the CPU, written in pure Rust.
+    ///
+    /// It executes compute and simple render workloads deterministically, so that they can be
+    /// run and tested on machines without a GPU. It only supports the downlevel defaults of
+    /// [`Limits`] and a subset of the texture formats, and it is much slower than a GPU.
+    Cpu = 6,
 }
 
 impl Backend {
@@ -171,6 +195,7 @@ impl Backend {
         Self::Dx12,
         Self::Gl,
         Self::BrowserWebGpu,
+        Self::Cpu,
     ];
 
     /// Returns the string name of the backend.
@@ -183,6 +208,7 @@ impl Backend {
             Backend::Dx12 => "dx12",
             Backend::Gl => "gl",
             Backend::BrowserWebGpu => "webgpu",
+            Backend::Cpu => "cpu",
         }
     }
 }
@@ -260,6 +286,10 @@ bitflags::bitflags! {
         /// *not* upon adapter creation. See `wgpu::Instance::new`.
         const BROWSER_WEBGPU = 1 << Backend::BrowserWebGpu as u32;
 
+        /// [`Backend::Cpu`].
+        /// Supported on every platform with `std`, with the `cpu` feature enabled.
+        const CPU = 1 << Backend::Cpu as u32;
+
         /// All the apis that wgpu offers first tier of support for.
         ///
         /// * [`Backends::VULKAN`]
@@ -321,6 +351,7 @@ impl Backends {
     /// - metal  = "metal" or "mtl"
     /// - gles   = "opengl" or "gles" or "gl"
     /// - webgpu = "webgpu"
+    /// - cpu    = "cpu"
     pub fn from_comma_list(string: &str) -> Self {
         let mut backends = Self::empty();
         for backend in string.to_lowercase().split(',') {
@@ -331,6 +362,7 @@ impl Backends {
                 "opengl" | "gles" | "gl" => Self::GL,
                 "webgpu" => Self::BROWSER_WEBGPU,
                 "noop" => Self::NOOP,
+                "cpu" => Self::CPU,
                 b => {
                     log::warn!("unknown backend string '{b}'");
                     continue;
@@ -631,6 +663,10 @@ pub struct Limits {
     /// Requesting more than 0 during device creation only makes sense if [`Features::EXPERIMENTAL_RAY_QUERY`]
     /// is enabled.
     pub max_acceleration_structures_per_shader_stage: u32,
+    /// The maximum depth of recursive ray tracing a ray tracing pipeline may be created with.
+    /// Requesting more than 0 during device creation only makes sense if
+    /// [`Features::EXPERIMENTAL_RAY_TRACING_PIPELINE`] is enabled.
+    pub max_ray_recursion_depth: u32,
 }
 
 impl Default for Limits {
@@ -687,6 +723,7 @@ impl Limits {
     ///     max_blas_geometry_count: 0,
     ///     max_tlas_instance_count: 0,
     ///     max_acceleration_structures_per_shader_stage: 0,
+    ///     max_ray_recursion_depth: 0,
     /// });
     /// ```
     ///
@@ -735,6 +772,7 @@ impl Limits {
             max_blas_geometry_count: 0,
             max_tlas_instance_count: 0,
             max_acceleration_structures_per_shader_stage: 0,
+            max_ray_recursion_depth: 0,
         }
     }
 
@@ -784,6 +822,7 @@ impl Limits {
     ///     max_blas_geometry_count: 0,
     ///     max_tlas_instance_count: 0,
     ///     max_acceleration_structures_per_shader_stage: 0,
+    ///     max_ray_recursion_depth: 0,
     /// });
     /// ```
     #[must_use]
@@ -848,6 +887,7 @@ impl Limits {
     ///     max_blas_geometry_count: 0,
     ///     max_tlas_instance_count: 0,
     ///     max_acceleration_structures_per_shader_stage: 0,
+    ///     max_ray_recursion_depth: 0,
     /// });
     /// ```
     #[must_use]
@@ -929,6 +969,16 @@ impl Limits {
         }
     }
 
+    /// The minimum guaranteed limits for ray tracing pipelines if you enable
+    /// [`Features::EXPERIMENTAL_RAY_TRACING_PIPELINE`].
+    #[must_use]
+    pub const fn using_minimum_supported_ray_tracing_pipeline_values(self) -> Self {
+        Self {
+            max_ray_recursion_depth: 1, // Vulkan's minimum
+            ..self
+        }
+    }
+
     /// Compares every limits within self is within the limits given in `allowed`.
     ///
     /// If you need detailed information on failures, look at [`Limits::check_limits_with_fail_fn`].
@@ -1011,6 +1061,7 @@ impl Limits {
         compare!(max_blas_primitive_count, Less);
         compare!(max_blas_geometry_count, Less);
         compare!(max_tlas_instance_count, Less);
+        compare!(max_ray_recursion_depth, Less);
     }
 }
 
@@ -1283,6 +1334,55 @@ pub struct AdapterInfo {
     pub backend: Backend,
 }
 
+/// Memory usage and budget of an adapter, as reported by the operating system.
+///
+/// Only available on native backends:
+/// - Vulkan: [`VK_EXT_memory_budget`], one entry per memory heap.
+/// - DX12: `IDXGIAdapter3::QueryVideoMemoryInfo`, one entry for local memory, and one for
+///   non-local memory on adapters with dedicated memory.
+/// - Metal: `recommendedMaxWorkingSetSize` and `currentAllocatedSize`, as a single heap.
+///
+/// The budget is an estimate of how much memory the process can use without
+/// causing performance problems such as paging, and can change over time, for example
+/// when other processes allocate memory. Exceeding it is allowed but not recommended.
+///
+/// [`VK_EXT_memory_budget`]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VK_EXT_memory_budget.html
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryInfo {
+    /// Usage and budget of each memory heap of the adapter.
+    pub heaps: Vec<MemoryHeapInfo>,
+}
+
+impl MemoryInfo {
+    /// Returns `true` if the budget of any heap differs between `self` and `other`.
+    ///
+    /// Usage is ignored, as it changes with every allocation.
+    #[must_use]
+    pub fn budget_differs(&self, other: &Self) -> bool {
+        self.heaps.len() != other.heaps.len()
+            || self
+                .heaps
+                .iter()
+                .zip(&other.heaps)
+                .any(|(a, b)| a.budget != b.budget)
+    }
+}
+
+/// Usage and budget of a single memory heap, part of [`MemoryInfo`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryHeapInfo {
+    /// Memory currently used by this process in this heap, in bytes.
+    pub usage: u64,
+    /// Memory this process can use in this heap before running into performance
+    /// problems, in bytes.
+    pub budget: u64,
+    /// `true` if the heap is local to the device, such as the video memory of a discrete
+    /// GPU, or all memory of an integrated GPU.
+    pub device_local: bool,
+}
+
 /// Hints to the device about the memory allocation strategy.
 ///
 /// Some backends may ignore these hints.
@@ -1342,6 +1442,9 @@ pub struct DeviceDescriptor<L> {
     /// Whether API tracing for debugging is enabled,
     /// and where the trace is written if so.
     pub trace: Trace,
+    /// Device toggles to force on or off. See [`Toggles::DEVICE`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub toggles: TogglesDescriptor,
 }
 
 impl<L> DeviceDescriptor<L> {
@@ -1354,14 +1457,15 @@ impl<L> DeviceDescriptor<L> {
             required_limits: self.required_limits.clone(),
             memory_hints: self.memory_hints.clone(),
             trace: self.trace.clone(),
+            toggles: self.toggles,
         }
     }
 }
 
 /// Controls API call tracing and specifies where the trace is written.
 ///
-/// **Note:** Tracing is currently unavailable.
-/// See [issue 5974](https://github.com/gfx-rs/wgpu/issues/5974) for updates.
+/// Tracing requires the `trace` feature. A trace can be replayed on any backend
+/// with `wgpu-player`.
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 // This enum must be non-exhaustive so that enabling the "trace" feature is not a semver break.
@@ -1371,13 +1475,115 @@ pub enum Trace {
     #[default]
     Off,
 
-    /// Tracing enabled.
+    /// Tracing enabled, into the given directory, which is created if it doesn't exist.
     #[cfg(feature = "trace")]
     // This must be owned rather than `&'a Path`, because if it were that, then the lifetime
     // parameter would be unused when the "trace" feature is disabled, which is prohibited.
     Directory(std::path::PathBuf),
 }
 
+/// Kind of work a queue is able to execute.
+///
+/// The queue returned alongside a device is always a [`QueueType::Graphics`] queue. Additional
+/// queues of any type can be requested with `Adapter::request_device_with_queues` when
+/// [`Features::EXPERIMENTAL_MULTI_QUEUE`] is enabled.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum QueueType {
+    /// The queue accepts all commands, including render passes.
+    #[default]
+    Graphics,
+    /// The queue accepts compute passes and transfer commands.
+    ///
+    /// Backends map this to dedicated asynchronous compute hardware where available.
+    Compute,
+    /// The queue accepts transfer commands only: copies, buffer clears, and clears of
+    /// color textures.
+    ///
+    /// Backends map this to dedicated copy engines where available.
+    Transfer,
+}
+
+/// Describes an additional [`Queue`](../wgpu/struct.Queue.html) requested along with a device.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueueDescriptor<L> {
+    /// Debug label for the queue.
+    pub label: L,
+    /// Kind of work the queue will execute.
+    pub queue_type: QueueType,
+}
+
+impl<L> QueueDescriptor<L> {
+    /// Takes a closure and maps the label of the queue descriptor into another.
+    #[must_use]
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> QueueDescriptor<K> {
+        QueueDescriptor {
+            label: fun(&self.label),
+            queue_type: self.queue_type,
+        }
+    }
+}
+
+/// Describes a [`SharedFence`](../wgpu/struct.SharedFence.html).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SharedFenceDescriptor<L> {
+    /// Debug label for the fence.
+    pub label: L,
+}
+
+impl<L> SharedFenceDescriptor<L> {
+    /// Takes a closure and maps the label of the shared fence descriptor into another.
+    #[must_use]
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> SharedFenceDescriptor<K> {
+        SharedFenceDescriptor {
+            label: fun(&self.label),
+        }
+    }
+}
+
+/// A platform handle to a fence shared with other APIs or processes.
+///
+/// Fences hold a 64-bit value that only ever increases. Which kind of handle a device exports
+/// and accepts depends on its backend and platform.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SharedFenceHandle {
+    /// A POSIX file descriptor for a Vulkan timeline semaphore, with handle type
+    /// `VK_EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT`.
+    ///
+    /// Exporting creates a new file descriptor owned by the caller. Importing transfers
+    /// ownership of the file descriptor to the fence.
+    VulkanOpaqueFd(i32),
+    /// An NT handle for a Vulkan timeline semaphore, with handle type
+    /// `VK_EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT`.
+    ///
+    /// Exporting creates a new handle owned by the caller, which must close it with
+    /// `CloseHandle`. Importing doesn't take ownership of the handle.
+    VulkanOpaqueWin32(*mut core::ffi::c_void),
+    /// An NT handle for an `ID3D12Fence` created with `D3D12_FENCE_FLAG_SHARED`.
+    ///
+    /// Exporting creates a new handle owned by the caller, which must close it with
+    /// `CloseHandle`. Importing doesn't take ownership of the handle.
+    D3D12Fence(*mut core::ffi::c_void),
+    /// A pointer to an `MTLSharedEvent`.
+    ///
+    /// Exporting returns a pointer that is only valid while the fence is alive; retain it to
+    /// keep the event alive for longer. Importing retains the event.
+    MetalSharedEvent(*mut core::ffi::c_void),
+}
+
+/// A value of a shared fence, that a submission waits for or signals.
+#[derive(Clone, Debug)]
+pub struct SharedFenceValue<F> {
+    /// The fence to wait on or signal.
+    pub fence: F,
+    /// The value to wait for the fence to reach, or to set the fence to.
+    pub value: u64,
+}
+
 bitflags::bitflags! {
     /// Describes the shader stages that a binding will be visible from.
     ///
@@ -1406,11 +1612,31 @@ bitflags::bitflags! {
         const TASK = 1 << 3;
         /// Binding is visible from the mesh shader of a mesh pipeline
         const MESH = 1 << 4;
+        /// Binding is visible from the ray generation shaders of a ray tracing pipeline.
+        const RAY_GENERATION = 1 << 5;
+        /// Binding is visible from the miss shaders of a ray tracing pipeline.
+        const MISS = 1 << 6;
+        /// Binding is visible from the closest hit shaders of a ray tracing pipeline.
+        const CLOSEST_HIT = 1 << 7;
+        /// Binding is visible from the any hit shaders of a ray tracing pipeline.
+        const ANY_HIT = 1 << 8;
+        /// Binding is visible from the intersection shaders of a ray tracing pipeline.
+        const INTERSECTION = 1 << 9;
+        /// Binding is visible from the callable shaders of a ray tracing pipeline.
+        const CALLABLE = 1 << 10;
+        /// Binding is visible from all shader stages of a ray tracing pipeline.
+        const RAY_TRACING = Self::RAY_GENERATION.bits()
+            | Self::MISS.bits()
+            | Self::CLOSEST_HIT.bits()
+            | Self::ANY_HIT.bits()
+            | Self::INTERSECTION.bits()
+            | Self::CALLABLE.bits();
     }
 }
 
 /// Order in which texture data is laid out in memory.
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TextureDataOrder {
     /// The texture is laid out densely in memory as:
     ///
@@ -1680,6 +1906,14 @@ pub struct ColorTargetState {
     /// Mask which enables/disables writes to different color/alpha channel.
     #[cfg_attr(feature = "serde", serde(default))]
     pub write_mask: ColorWrites,
+    /// The bitwise logic operation to combine the fragment output with the target,
+    /// replacing blending.
+    ///
+    /// Setting this requires [`Features::LOGIC_OP`] to be enabled, a `Uint` format,
+    /// and [`blend`](Self::blend) to be `None`. Every color target of the pipeline
+    /// must use the same logic operation.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub logic_op: Option<LogicOp>,
 }
 
 impl From<TextureFormat> for ColorTargetState {
@@ -1688,10 +1922,54 @@ impl From<TextureFormat> for ColorTargetState {
             format,
             blend: None,
             write_mask: ColorWrites::ALL,
+            logic_op: None,
         }
     }
 }
 
+/// Bitwise operation combining the fragment output (`s`) with the value already
+/// in the color target (`d`).
+///
+/// Used in [`ColorTargetState::logic_op`]. Requires [`Features::LOGIC_OP`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum LogicOp {
+    /// `0`
+    Clear = 0,
+    /// `s & d`
+    And = 1,
+    /// `s & !d`
+    AndReverse = 2,
+    /// `s`
+    Copy = 3,
+    /// `!s & d`
+    AndInverted = 4,
+    /// `d`
+    NoOp = 5,
+    /// `s ^ d`
+    Xor = 6,
+    /// `s | d`
+    Or = 7,
+    /// `!(s | d)`
+    Nor = 8,
+    /// `!(s ^ d)`
+    Equivalent = 9,
+    /// `!d`
+    Invert = 10,
+    /// `s | !d`
+    OrReverse = 11,
+    /// `!s`
+    CopyInverted = 12,
+    /// `!s | d`
+    OrInverted = 13,
+    /// `!(s & d)`
+    Nand = 14,
+    /// All ones.
+    Set = 15,
+}
+
 /// Primitive type the input mesh is composed of.
 ///
 /// Corresponds to [WebGPU `GPUPrimitiveTopology`](
@@ -1789,7 +2067,7 @@ pub enum PolygonMode {
 /// Corresponds to [WebGPU `GPUPrimitiveState`](
 /// https://gpuweb.github.io/gpuweb/#dictdef-gpuprimitivestate).
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct PrimitiveState {
@@ -1798,10 +2076,18 @@ pub struct PrimitiveState {
     /// When drawing strip topologies with indices, this is the required format for the index buffer.
     /// This has no effect on non-indexed or non-strip draws.
     ///
-    /// Specifying this value enables primitive restart, allowing individual strips to be separated
-    /// with the index value `0xFFFF` when using `Uint16`, or `0xFFFFFFFF` when using `Uint32`.
+    /// Indexed draws with a strip topology and [`primitive_restart`](Self::primitive_restart)
+    /// enabled need this to be set, so that every backend knows which index value restarts strips.
     #[cfg_attr(feature = "serde", serde(default))]
     pub strip_index_format: Option<IndexFormat>,
+    /// Whether indexed draws with a strip topology restart the strip at the index value given by
+    /// [`IndexFormat::primitive_restart_index`], which is `0xFFFF` for `Uint16` and `0xFFFFFFFF`
+    /// for `Uint32`. This has no effect on non-indexed or non-strip draws.
+    ///
+    /// Primitive restart is always enabled in WebGPU. Disabling it for a strip topology requires
+    /// [`Features::PRIMITIVE_RESTART_CONTROL`] to be enabled.
+    #[cfg_attr(feature = "serde", serde(default = "default_primitive_restart"))]
+    pub primitive_restart: bool,
     /// The face to consider the front for the purpose of culling and stencil operations.
     #[cfg_attr(feature = "serde", serde(default))]
     pub front_face: FrontFace,
@@ -1824,7 +2110,100 @@ pub struct PrimitiveState {
     /// Only valid for `[PolygonMode::Fill`]!
     ///
     /// Enabling this requires [`Features::CONSERVATIVE_RASTERIZATION`] to be enabled.
+    ///
+    /// Fragment shaders reading the `fully_covered` built-in require this to be set.
+    /// See [`Features::CONSERVATIVE_RASTERIZATION_FULLY_COVERED`].
     pub conservative: bool,
+    /// Width of rasterized lines, in pixels. Applies to line topologies and to
+    /// [`PolygonMode::Line`]. Widths the device can't draw are rounded to the nearest
+    /// supported width.
+    ///
+    /// Setting this to anything other than `1.0` requires [`Features::WIDE_LINES`] to be enabled.
+    #[cfg_attr(feature = "serde", serde(default = "default_line_width"))]
+    pub line_width: f32,
+    /// Stipple pattern to draw lines with, or `None` to draw solid lines.
+    ///
+    /// Setting this requires [`Features::LINE_STIPPLE`] to be enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub line_stipple: Option<LineStipple>,
+}
+
+#[cfg(feature = "serde")]
+fn default_line_width() -> f32 {
+    1.0
+}
+
+#[cfg(feature = "serde")]
+fn default_primitive_restart() -> bool {
+    true
+}
+
+impl Default for PrimitiveState {
+    fn default() -> Self {
+        Self {
+            topology: PrimitiveTopology::default(),
+            strip_index_format: None,
+            primitive_restart: true,
+            front_face: FrontFace::default(),
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: PolygonMode::default(),
+            conservative: false,
+            line_width: 1.0,
+            line_stipple: None,
+        }
+    }
+}
+
+impl Hash for PrimitiveState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.topology.hash(state);
+        self.strip_index_format.hash(state);
+        self.primitive_restart.hash(state);
+        self.front_face.hash(state);
+        self.cull_mode.hash(state);
+        self.unclipped_depth.hash(state);
+        self.polygon_mode.hash(state);
+        self.conservative.hash(state);
+        self.line_width.to_bits().hash(state);
+        self.line_stipple.hash(state);
+    }
+}
+
+impl PartialEq for PrimitiveState {
+    fn eq(&self, other: &Self) -> bool {
+        (self.topology == other.topology)
+            && (self.strip_index_format == other.strip_index_format)
+            && (self.primitive_restart == other.primitive_restart)
+            && (self.front_face == other.front_face)
+            && (self.cull_mode == other.cull_mode)
+            && (self.unclipped_depth == other.unclipped_depth)
+            && (self.polygon_mode == other.polygon_mode)
+            && (self.conservative == other.conservative)
+            && (self.line_width.to_bits() == other.line_width.to_bits())
+            && (self.line_stipple == other.line_stipple)
+    }
+}
+
+impl Eq for PrimitiveState {}
+
+/// A pattern of lit and unlit pixels to draw lines with.
+///
+/// Bit `i` of [`pattern`](Self::pattern) says whether to draw the pixels at positions
+/// `factor * i` through `factor * (i + 1) - 1` along the line, repeating every
+/// `16 * factor` pixels. The pattern restarts at the beginning of every line in a
+/// line list, and at the beginning of every strip.
+///
+/// Used in [`PrimitiveState::line_stipple`]. Requires [`Features::LINE_STIPPLE`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct LineStipple {
+    /// How many pixels each bit of the pattern covers. Must be between 1 and 256.
+    pub factor: u32,
+    /// The bits to draw, starting from the least significant bit.
+    pub pattern: u16,
 }
 
 /// Describes the multi-sampling state of a render pipeline.
@@ -2163,6 +2542,34 @@ pub enum TextureFormat {
     ///
     /// [`Features::TEXTURE_FORMAT_NV12`] must be enabled to use this texture format.
     NV12,
+    /// YUV 4:2:0 chroma subsampled format.
+    ///
+    /// Contains two planes:
+    /// - 0: Single 16 bit channel luminance, of which only the high 10 bits are used.
+    /// - 1: Dual 16 bit channel chrominance at half width and half height, of which
+    ///   only the high 10 bits are used.
+    ///
+    /// Valid view formats for luminance are [`TextureFormat::R16Unorm`].
+    ///
+    /// Valid view formats for chrominance are [`TextureFormat::Rg16Unorm`].
+    ///
+    /// Width and height must be even.
+    ///
+    /// [`Features::TEXTURE_FORMAT_P010`] must be enabled to use this texture format.
+    P010,
+    /// YUV 4:2:0 chroma subsampled format.
+    ///
+    /// Contains three planes:
+    /// - 0: Single 8 bit channel luminance.
+    /// - 1: Single 8 bit channel blue-difference chrominance at half width and half height.
+    /// - 2: Single 8 bit channel red-difference chrominance at half width and half height.
+    ///
+    /// Valid view formats for all planes are [`TextureFormat::R8Unorm`].
+    ///
+    /// Width and height must be even.
+    ///
+    /// [`Features::TEXTURE_FORMAT_I420`] must be enabled to use this texture format.
+    I420,
 
     // Compressed textures usable with `TEXTURE_COMPRESSION_BC` feature. `TEXTURE_COMPRESSION_SLICED_3D` is required to use with 3D textures.
     /// 4x4 block compressed texture. 8 bytes per block (4 bit/px). 4 color + alpha pallet. 5 bit R + 6 bit G + 5 bit B + 1 bit alpha.
@@ -2409,6 +2816,8 @@ impl<'de> Deserialize<'de> for TextureFormat {
                     "depth24plus" => TextureFormat::Depth24Plus,
                     "depth24plus-stencil8" => TextureFormat::Depth24PlusStencil8,
                     "nv12" => TextureFormat::NV12,
+                    "p010" => TextureFormat::P010,
+                    "i420" => TextureFormat::I420,
                     "rgb9e5ufloat" => TextureFormat::Rgb9e5Ufloat,
                     "bc1-rgba-unorm" => TextureFormat::Bc1RgbaUnorm,
                     "bc1-rgba-unorm-srgb" => TextureFormat::Bc1RgbaUnormSrgb,
@@ -2538,6 +2947,8 @@ impl Serialize for TextureFormat {
             TextureFormat::Depth24Plus => "depth24plus",
             TextureFormat::Depth24PlusStencil8 => "depth24plus-stencil8",
             TextureFormat::NV12 => "nv12",
+            TextureFormat::P010 => "p010",
+            TextureFormat::I420 => "i420",
             TextureFormat::Rgb9e5Ufloat => "rgb9e5ufloat",
             TextureFormat::Bc1RgbaUnorm => "bc1-rgba-unorm",
             TextureFormat::Bc1RgbaUnormSrgb => "bc1-rgba-unorm-srgb",
@@ -2628,6 +3039,11 @@ impl TextureFormat {
             (Self::Depth32FloatStencil8, TextureAspect::DepthOnly) => Some(Self::Depth32Float),
             (Self::NV12, TextureAspect::Plane0) => Some(Self::R8Unorm),
             (Self::NV12, TextureAspect::Plane1) => Some(Self::Rg8Unorm),
+            (Self::P010, TextureAspect::Plane0) => Some(Self::R16Unorm),
+            (Self::P010, TextureAspect::Plane1) => Some(Self::Rg16Unorm),
+            (Self::I420, TextureAspect::Plane0 | TextureAspect::Plane1 | TextureAspect::Plane2) => {
+                Some(Self::R8Unorm)
+            }
             // views to multi-planar formats must specify the plane
             (format, TextureAspect::All) if !format.is_multi_planar_format() => Some(format),
             _ => None,
@@ -2682,7 +3098,8 @@ impl TextureFormat {
     #[must_use]
     pub fn planes(&self) -> Option<u32> {
         match *self {
-            Self::NV12 => Some(2),
+            Self::NV12 | Self::P010 => Some(2),
+            Self::I420 => Some(3),
             _ => None,
         }
     }
@@ -2719,11 +3136,42 @@ impl TextureFormat {
     #[must_use]
     pub fn size_multiple_requirement(&self) -> (u32, u32) {
         match *self {
-            Self::NV12 => (2, 2),
+            Self::NV12 | Self::P010 | Self::I420 => (2, 2),
             _ => self.block_dimensions(),
         }
     }
 
+    /// Returns the size in texels of a tile of a sparse 2D texture of this format.
+    ///
+    /// Every tile takes up [`SPARSE_PAGE_SIZE`] bytes of memory. The tile shapes are the
+    /// standard ones shared by Vulkan and D3D12.
+    ///
+    /// Returns `None` for formats sparse textures can't have: depth, stencil and
+    /// multi-planar formats.
+    #[must_use]
+    pub fn sparse_tile_size(&self) -> Option<Extent3d> {
+        if !self.has_color_aspect() || self.is_multi_planar_format() {
+            return None;
+        }
+
+        // Tile sizes in blocks, by block size in bytes.
+        let (blocks_wide, blocks_high) = match self.block_copy_size(None)? {
+            1 => (256, 256),
+            2 => (256, 128),
+            4 => (128, 128),
+            8 => (128, 64),
+            16 => (64, 64),
+            _ => return None,
+        };
+        let (block_width, block_height) = self.block_dimensions();
+
+        Some(Extent3d {
+            width: blocks_wide * block_width,
+            height: blocks_high * block_height,
+            depth_or_array_layers: 1,
+        })
+    }
+
     /// Returns the dimension of a [block](https://gpuweb.github.io/gpuweb/#texel-block) of texels.
     ///
     /// Uncompressed formats have a block dimension of `(1, 1)`.
@@ -2780,7 +3228,9 @@ impl TextureFormat {
             | Self::Depth24PlusStencil8
             | Self::Depth32Float
             | Self::Depth32FloatStencil8
-            | Self::NV12 => (1, 1),
+            | Self::NV12
+            | Self::P010
+            | Self::I420 => (1, 1),
 
             Self::Bc1RgbaUnorm
             | Self::Bc1RgbaUnormSrgb
@@ -2898,6 +3348,8 @@ impl TextureFormat {
             Self::Depth32FloatStencil8 => Features::DEPTH32FLOAT_STENCIL8,
 
             Self::NV12 => Features::TEXTURE_FORMAT_NV12,
+            Self::P010 => Features::TEXTURE_FORMAT_P010,
+            Self::I420 => Features::TEXTURE_FORMAT_I420,
 
             Self::R16Unorm
             | Self::R16Snorm
@@ -3031,8 +3483,10 @@ impl TextureFormat {
             Self::Depth32Float =>         (        msaa, attachment),
             Self::Depth32FloatStencil8 => (        msaa, attachment),
 
-            // We only support sampling nv12 textures until we implement transfer plane data.
+            // We only support sampling multi-planar textures until we implement transfer plane data.
             Self::NV12 =>                 (        none,    binding),
+            Self::P010 =>                 (        none,    binding),
+            Self::I420 =>                 (        none,    binding),
 
             Self::R16Unorm =>             (        msaa | s_ro_wo,    storage),
             Self::R16Snorm =>             (        msaa | s_ro_wo,    storage),
@@ -3162,13 +3616,20 @@ impl TextureFormat {
                 _ => None,
             },
 
-            Self::NV12 => match aspect {
+            Self::NV12 | Self::P010 => match aspect {
                 Some(TextureAspect::Plane0) | Some(TextureAspect::Plane1) => {
                     Some(unfilterable_float)
                 }
                 _ => None,
             },
 
+            Self::I420 => match aspect {
+                Some(TextureAspect::Plane0 | TextureAspect::Plane1 | TextureAspect::Plane2) => {
+                    Some(unfilterable_float)
+                }
+                _ => None,
+            },
+
             Self::R16Unorm
             | Self::R16Snorm
             | Self::Rg16Unorm
@@ -3295,6 +3756,19 @@ impl TextureFormat {
                 _ => None,
             },
 
+            Self::P010 => match aspect {
+                Some(TextureAspect::Plane0) => Some(2),
+                Some(TextureAspect::Plane1) => Some(4),
+                _ => None,
+            },
+
+            Self::I420 => match aspect {
+                Some(TextureAspect::Plane0 | TextureAspect::Plane1 | TextureAspect::Plane2) => {
+                    Some(1)
+                }
+                _ => None,
+            },
+
             Self::Bc1RgbaUnorm | Self::Bc1RgbaUnormSrgb | Self::Bc4RUnorm | Self::Bc4RSnorm => {
                 Some(8)
             }
@@ -3380,6 +3854,8 @@ impl TextureFormat {
             | Self::Depth32Float
             | Self::Depth32FloatStencil8
             | Self::NV12
+            | Self::P010
+            | Self::I420
             | Self::Rgb9e5Ufloat
             | Self::Bc1RgbaUnorm
             | Self::Bc1RgbaUnormSrgb
@@ -3463,6 +3939,8 @@ impl TextureFormat {
             | Self::Depth32Float
             | Self::Depth32FloatStencil8
             | Self::NV12
+            | Self::P010
+            | Self::I420
             | Self::Rgb9e5Ufloat
             | Self::Bc1RgbaUnorm
             | Self::Bc1RgbaUnormSrgb
@@ -3557,12 +4035,17 @@ impl TextureFormat {
                 _ => 2,
             },
 
-            Self::NV12 => match aspect {
+            Self::NV12 | Self::P010 => match aspect {
                 TextureAspect::Plane0 => 1,
                 TextureAspect::Plane1 => 2,
                 _ => 3,
             },
 
+            Self::I420 => match aspect {
+                TextureAspect::Plane0 | TextureAspect::Plane1 | TextureAspect::Plane2 => 1,
+                _ => 3,
+            },
+
             Self::Bc4RUnorm | Self::Bc4RSnorm => 1,
             Self::Bc5RgUnorm | Self::Bc5RgSnorm => 2,
             Self::Bc6hRgbUfloat | Self::Bc6hRgbFloat => 3,
@@ -3667,6 +4150,10 @@ impl TextureFormat {
                 Self::Stencil8 => 1,
                 // Two chroma bytes per block, one luma byte per block
                 Self::NV12 => 3,
+                // Two chroma u16s per block, one luma u16 per block
+                Self::P010 => 6,
+                // One byte per block for each of the planes
+                Self::I420 => 3,
                 f => {
                     log::warn!("Memory footprint for format {f:?} is not implemented");
                     0
@@ -4320,6 +4807,7 @@ impl Default for ColorWrites {
 
 /// Passed to `Device::poll` to control how and if it should block.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PollType<T> {
     /// On wgpu-core based backends, block until the given submission has
     /// completed execution, and any callbacks have been invoked.
@@ -4327,6 +4815,16 @@ pub enum PollType<T> {
     /// On WebGPU, this has no effect. Callbacks are invoked from the
     /// window event loop.
     WaitForSubmissionIndex(T),
+    /// Same as `WaitForSubmissionIndex`, but stops waiting once the given
+    /// duration has passed, in which case the poll returns
+    /// [`PollError::Timeout`]. A duration of zero only checks whether the
+    /// submission has completed.
+    ///
+    /// The duration is rounded up to whole milliseconds. On devices with
+    /// several queues, it bounds the whole wait, across all queues.
+    ///
+    /// On WebGPU, this has no effect, like `WaitForSubmissionIndex`.
+    WaitForSubmissionWithTimeout(T, core::time::Duration),
     /// Same as `WaitForSubmissionIndex` but waits for the most recent submission.
     Wait,
     /// Check the device for a single time without blocking.
@@ -4352,11 +4850,19 @@ impl<T> PollType<T> {
         Self::WaitForSubmissionIndex(submission_index)
     }
 
+    /// Construct a [`Self::WaitForSubmissionWithTimeout`] variant
+    #[must_use]
+    pub fn wait_for_with_timeout(submission_index: T, timeout: core::time::Duration) -> Self {
+        Self::WaitForSubmissionWithTimeout(submission_index, timeout)
+    }
+
     /// This `PollType` represents a wait of some kind.
     #[must_use]
     pub fn is_wait(&self) -> bool {
         match *self {
-            Self::WaitForSubmissionIndex(..) | Self::Wait => true,
+            Self::WaitForSubmissionIndex(..)
+            | Self::WaitForSubmissionWithTimeout(..)
+            | Self::Wait => true,
             Self::Poll => false,
         }
     }
@@ -4369,6 +4875,9 @@ impl<T> PollType<T> {
     {
         match self {
             Self::WaitForSubmissionIndex(i) => PollType::WaitForSubmissionIndex(func(i)),
+            Self::WaitForSubmissionWithTimeout(i, timeout) => {
+                PollType::WaitForSubmissionWithTimeout(func(i), timeout)
+            }
             Self::Wait => PollType::Wait,
             Self::Poll => PollType::Poll,
         }
@@ -4378,6 +4887,7 @@ impl<T> PollType<T> {
 /// Error states after a device poll
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PollError {
     /// The requested Wait timed out before the submission was completed.
     #[cfg_attr(
@@ -4389,6 +4899,7 @@ pub enum PollError {
 
 /// Status of device poll operation.
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PollStatus {
     /// There are no active submissions in flight as of the beginning of the poll call.
     /// Other submissions may have been queued on other threads during the call.
@@ -4600,6 +5111,158 @@ impl<V: Default> Default for Operations<V> {
     }
 }
 
+/// How the samples of a multisampled depth attachment are combined when it is resolved.
+///
+/// Used by [`DepthStencilResolve`]. Requires [`Features::DEPTH_STENCIL_RESOLVE`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ResolveMode {
+    /// Takes the value of sample zero.
+    #[default]
+    SampleZero = 0,
+    /// Takes the smallest value of all samples.
+    Min = 1,
+    /// Takes the largest value of all samples.
+    Max = 2,
+}
+
+/// Describes how a multisampled depth/stencil attachment is resolved at the end of a render
+/// pass.
+///
+/// Requires [`Features::DEPTH_STENCIL_RESOLVE`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepthStencilResolve<V> {
+    /// The single sampled view to resolve into. It must have the same format and size as the
+    /// multisampled attachment.
+    pub target: V,
+    /// How depth samples are combined, or `None` to leave the depth aspect of the target
+    /// untouched.
+    pub depth_mode: Option<ResolveMode>,
+    /// Whether the stencil aspect is resolved. Stencil always takes the value of sample zero.
+    /// Ignored if the format has no stencil aspect.
+    pub resolve_stencil: bool,
+}
+
+impl<V> DepthStencilResolve<V> {
+    /// Resolves depth with `depth_mode` and stencil, if the format has it, from sample zero.
+    pub fn new(target: V, depth_mode: ResolveMode) -> Self {
+        Self {
+            target,
+            depth_mode: Some(depth_mode),
+            resolve_stencil: true,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    /// Flags that let consecutive render passes form one logical render pass.
+    ///
+    /// A pass with [`SUSPENDING`](Self::SUSPENDING) must be followed by a pass with
+    /// [`RESUMING`](Self::RESUMING) that uses the same attachments, either later in the same
+    /// command encoder or at the start of the next command buffer in the same submission.
+    /// No other commands may be recorded in between. This allows work for one pass to be
+    /// recorded on several command encoders, while tile-based GPUs can keep the attachments in
+    /// tile memory instead of writing them out and reading them back.
+    ///
+    /// Every part of the logical pass must have the same attachments, load and store
+    /// operations, clear values, resolve targets and occlusion query set, as if each part
+    /// described the whole pass. The load operations happen at the start of the first part,
+    /// and the store operations and resolves at the end of the last part. A suspending part
+    /// can't write a timestamp at its end, and a resuming part can't write one at its
+    /// beginning. No part can have a pipeline statistics query or a transient attachment.
+    ///
+    /// With [`Features::RENDER_PASS_SUSPEND_RESUME`], supported backends keep the pass open
+    /// in between. Otherwise, the pass is ended and started again, storing the attachments
+    /// and loading them back.
+    ///
+    /// When a part is in a later command buffer than the part it resumes, the barriers and
+    /// memory initialization that command buffer needs before the pass are done before the
+    /// earlier parts, so it must use the resources of the earlier parts the same way.
+    #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct RenderPassFlags: u8 {
+        /// The pass is continued by the next render pass, which must be [`Self::RESUMING`].
+        const SUSPENDING = 1 << 0;
+        /// The pass continues the render pass suspended right before it.
+        const RESUMING = 1 << 1;
+    }
+}
+
+/// Size of the block of pixels that a single fragment shader invocation colors.
+///
+/// Set with [`RenderPass::set_shading_rate`][RPssr]. Requires
+/// [`Features::VARIABLE_RATE_SHADING`]. Variants are named width by height.
+///
+/// [RPssr]: ../wgpu/struct.RenderPass.html#method.set_shading_rate
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ShadingRate {
+    /// One invocation per pixel.
+    #[default]
+    OneByOne = 0,
+    /// One invocation per 1x2 block of pixels.
+    OneByTwo = 1,
+    /// One invocation per 2x1 block of pixels.
+    TwoByOne = 2,
+    /// One invocation per 2x2 block of pixels.
+    TwoByTwo = 3,
+    /// One invocation per 2x4 block of pixels.
+    TwoByFour = 4,
+    /// One invocation per 4x2 block of pixels.
+    FourByTwo = 5,
+    /// One invocation per 4x4 block of pixels.
+    FourByFour = 6,
+}
+
+impl ShadingRate {
+    /// Returns the width and height of the block of pixels, in that order.
+    #[must_use]
+    pub fn block_size(self) -> (u32, u32) {
+        match self {
+            Self::OneByOne => (1, 1),
+            Self::OneByTwo => (1, 2),
+            Self::TwoByOne => (2, 1),
+            Self::TwoByTwo => (2, 2),
+            Self::TwoByFour => (2, 4),
+            Self::FourByTwo => (4, 2),
+            Self::FourByFour => (4, 4),
+        }
+    }
+}
+
+/// Region of the render targets that normalized device coordinates are mapped to.
+///
+/// Set with [`RenderPass::set_viewports`][RPsv]. Requires [`Features::MULTI_VIEWPORT`].
+/// Vertex shaders pick the viewport for each primitive by writing the `viewport_index`
+/// built-in.
+///
+/// [RPsv]: ../wgpu/struct.RenderPass.html#method.set_viewports
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Viewport {
+    /// Left edge of the viewport, in pixels.
+    pub x: f32,
+    /// Top edge of the viewport, in pixels.
+    pub y: f32,
+    /// Width of the viewport, in pixels.
+    pub width: f32,
+    /// Height of the viewport, in pixels.
+    pub height: f32,
+    /// Depth that a normalized depth of 0 is mapped to.
+    pub min_depth: f32,
+    /// Depth that a normalized depth of 1 is mapped to.
+    pub max_depth: f32,
+}
+
 /// Describes the depth/stencil state in a render pipeline.
 ///
 /// Corresponds to [WebGPU `GPUDepthStencilState`](
@@ -4665,16 +5328,33 @@ pub enum IndexFormat {
     /// Indices are 32 bit unsigned integers.
     #[default]
     Uint32 = 1,
+    /// Indices are 8 bit unsigned integers.
+    ///
+    /// [`Features::INDEX_FORMAT_UINT8`] must be enabled to use this index format.
+    Uint8 = 2,
 }
 
 impl IndexFormat {
     /// Returns the size in bytes of the index format
     pub fn byte_size(&self) -> usize {
         match self {
+            IndexFormat::Uint8 => 1,
             IndexFormat::Uint16 => 2,
             IndexFormat::Uint32 => 4,
         }
     }
+
+    /// Returns the index value that restarts strips when
+    /// [`PrimitiveState::primitive_restart`] is enabled, which is the largest
+    /// value of the index format.
+    #[must_use]
+    pub fn primitive_restart_index(&self) -> u32 {
+        match self {
+            IndexFormat::Uint8 => 0xFF,
+            IndexFormat::Uint16 => 0xFFFF,
+            IndexFormat::Uint32 => 0xFFFF_FFFF,
+        }
+    }
 }
 
 /// Operation to perform on the stencil value.
@@ -4856,12 +5536,11 @@ impl CompareFunction {
 /// https://gpuweb.github.io/gpuweb/#enumdef-gpuvertexstepmode).
 ///
 /// [`RenderPass::draw`]: ../wgpu/struct.RenderPass.html#method.draw
-/// [`VertexBufferLayout`]: ../wgpu/struct.VertexBufferLayout.html
-/// [`step_mode`]: ../wgpu/struct.VertexBufferLayout.html#structfield.step_mode
-/// [`attributes`]: ../wgpu/struct.VertexBufferLayout.html#structfield.attributes
+/// [`step_mode`]: VertexBufferLayout::step_mode
+/// [`attributes`]: VertexBufferLayout::attributes
 /// [`Vertex`]: VertexStepMode::Vertex
 /// [`Instance`]: VertexStepMode::Instance
-#[repr(C)]
+#[repr(u32)]
 #[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
@@ -4871,6 +5550,32 @@ pub enum VertexStepMode {
     Vertex = 0,
     /// Vertex data is advanced every instance.
     Instance = 1,
+    /// Vertex data is advanced every `rate` instances.
+    ///
+    /// An instanced draw starting at `first_instance` reads element
+    /// `first_instance + (i - first_instance) / rate` for instance `i`, so that
+    /// consecutive groups of `rate` instances share their per-instance data.
+    /// A `rate` of 1 is the same as [`Instance`](Self::Instance).
+    ///
+    /// `rate` must be at least 1. Rates other than 1 require
+    /// [`Features::VERTEX_INSTANCE_STEP_RATE`].
+    InstanceStepRate {
+        /// Number of instances that use each element of the buffer.
+        rate: u32,
+    } = 2,
+}
+
+impl VertexStepMode {
+    /// Returns the number of instances that use each element of the buffer, or
+    /// `None` if the buffer is indexed by vertex number.
+    #[must_use]
+    pub fn instance_rate(&self) -> Option<u32> {
+        match *self {
+            Self::Vertex => None,
+            Self::Instance => Some(1),
+            Self::InstanceStepRate { rate } => Some(rate),
+        }
+    }
 }
 
 /// Vertex inputs (attributes) to shaders.
@@ -4884,7 +5589,6 @@ pub enum VertexStepMode {
 /// https://gpuweb.github.io/gpuweb/#dictdef-gpuvertexattribute).
 ///
 /// [`vertex_attr_array!`]: ../wgpu/macro.vertex_attr_array.html
-/// [`VertexBufferLayout`]: ../wgpu/struct.VertexBufferLayout.html
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -4898,6 +5602,83 @@ pub struct VertexAttribute {
     pub shader_location: ShaderLocation,
 }
 
+/// Specifies an interpretation of the bytes of a vertex buffer as vertex attributes.
+///
+/// Corresponds to [WebGPU `GPUVertexBufferLayout`](
+/// https://gpuweb.github.io/gpuweb/#dictdef-gpuvertexbufferlayout).
+///
+/// `wgpu` uses this as `VertexBufferLayout<&[VertexAttribute]>`. Deserializing needs an owned
+/// list of attributes, such as `VertexBufferLayout<Vec<VertexAttribute>>`, which can be borrowed
+/// with [`VertexBufferLayout::map_attributes`].
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct VertexBufferLayout<A> {
+    /// The stride, in bytes, between elements of this buffer (between vertices).
+    ///
+    /// This must be a multiple of [`VERTEX_ALIGNMENT`].
+    pub array_stride: BufferAddress,
+    /// How often this vertex buffer is "stepped" forward.
+    pub step_mode: VertexStepMode,
+    /// The list of attributes which comprise a single vertex.
+    pub attributes: A,
+}
+
+impl<A> VertexBufferLayout<A> {
+    /// Takes a closure and maps the attributes of the vertex buffer layout into another.
+    #[must_use]
+    pub fn map_attributes<'a, K>(&'a self, fun: impl FnOnce(&'a A) -> K) -> VertexBufferLayout<K> {
+        VertexBufferLayout {
+            array_stride: self.array_stride,
+            step_mode: self.step_mode,
+            attributes: fun(&self.attributes),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn vertex_buffer_layout_serde() {
+    use alloc::vec::Vec;
+
+    let json = r#"{
+        "arrayStride": 20,
+        "stepMode": { "instance": { "rate": 1 } },
+        "attributes": [
+            { "format": "float32x3", "offset": 0, "shaderLocation": 0 },
+            { "format": "unorm8x4", "offset": 12, "shaderLocation": 1 }
+        ]
+    }"#;
+    let layout: VertexBufferLayout<Vec<VertexAttribute>> = serde_json::from_str(json).unwrap();
+    let attributes = [
+        VertexAttribute {
+            format: VertexFormat::Float32x3,
+            offset: 0,
+            shader_location: 0,
+        },
+        VertexAttribute {
+            format: VertexFormat::Unorm8x4,
+            offset: 12,
+            shader_location: 1,
+        },
+    ];
+    assert_eq!(
+        layout.map_attributes(Vec::as_slice),
+        VertexBufferLayout {
+            array_stride: 20,
+            step_mode: VertexStepMode::Instance,
+            attributes: &attributes[..],
+        }
+    );
+    assert_eq!(
+        serde_json::from_str::<VertexBufferLayout<Vec<VertexAttribute>>>(
+            &serde_json::to_string(&layout).unwrap()
+        )
+        .unwrap(),
+        layout
+    );
+}
+
 /// Vertex Format for a [`VertexAttribute`] (input).
 ///
 /// Corresponds to [WebGPU `GPUVertexFormat`](
@@ -5124,6 +5905,8 @@ bitflags::bitflags! {
         const BLAS_INPUT = 1 << 10;
         /// Allows a buffer to be used as input for a top level acceleration structure build
         const TLAS_INPUT = 1 << 11;
+        /// Allows a buffer to hold a shader binding table used by a ray tracing pipeline.
+        const SHADER_BINDING_TABLE = 1 << 12;
     }
 }
 
@@ -5165,10 +5948,13 @@ bitflags::bitflags! {
         const TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT = 1 << 13;
         /// A buffer used to store the compacted size of an acceleration structure
         const ACCELERATION_STRUCTURE_QUERY = 1 << 14;
+        /// A shader binding table used to trace rays.
+        const SHADER_BINDING_TABLE = 1 << 15;
         /// The combination of states that a buffer may be in _at the same time_.
         const INCLUSIVE = Self::MAP_READ.bits() | Self::COPY_SRC.bits() |
             Self::INDEX.bits() | Self::VERTEX.bits() | Self::UNIFORM.bits() |
-            Self::STORAGE_READ_ONLY.bits() | Self::INDIRECT.bits() | Self::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT.bits() | Self::TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT.bits();
+            Self::STORAGE_READ_ONLY.bits() | Self::INDIRECT.bits() | Self::BOTTOM_LEVEL_ACCELERATION_STRUCTURE_INPUT.bits() | Self::TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT.bits() |
+            Self::SHADER_BINDING_TABLE.bits();
         /// The combination of states that a buffer must exclusively be in.
         const EXCLUSIVE = Self::MAP_WRITE.bits() | Self::COPY_DST.bits() | Self::STORAGE_READ_WRITE.bits() | Self::ACCELERATION_STRUCTURE_SCRATCH.bits();
         /// The combination of all usages that the are guaranteed to be be ordered by the hardware.
@@ -5382,6 +6168,84 @@ impl Default for CompositeAlphaMode {
     }
 }
 
+/// Specifies how the presentation engine interprets the colors of the
+/// textures presented to a surface.
+///
+/// Every color space except [`SurfaceColorSpace::Srgb`] allows values beyond
+/// the sRGB gamut and above SDR white, for output to wide gamut or HDR displays.
+/// These are meant to be used with [`TextureFormat::Rgba16Float`], or with
+/// [`TextureFormat::Rgb10a2Unorm`] for [`SurfaceColorSpace::Hdr10`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum SurfaceColorSpace {
+    /// Chooses [`SurfaceColorSpace::ExtendedSrgbLinear`] for
+    /// [`TextureFormat::Rgba16Float`] surfaces if the surface supports it,
+    /// and [`SurfaceColorSpace::Srgb`] otherwise.
+    #[default]
+    Auto = 0,
+    /// sRGB primaries and transfer function, with values in the `0.0..=1.0` range.
+    ///
+    /// This is the only color space that all surfaces support.
+    Srgb = 1,
+    /// sRGB primaries and transfer function, with values outside of the
+    /// `0.0..=1.0` range representing colors outside of the sRGB gamut or
+    /// brighter than SDR white.
+    ///
+    /// Not supported on DX12.
+    ExtendedSrgb = 2,
+    /// sRGB primaries with a linear transfer function, where `1.0` is SDR white
+    /// (80 nits). Also known as scRGB.
+    ///
+    /// Not supported on WebGPU.
+    ExtendedSrgbLinear = 3,
+    /// BT.2020 primaries with the SMPTE ST 2084 (PQ) transfer function,
+    /// where `1.0` is 10000 nits. Also known as HDR10.
+    ///
+    /// Not supported on WebGPU.
+    Hdr10 = 4,
+}
+
+/// Describes the luminance of HDR content presented to a surface.
+///
+/// The presentation engine may use this to tone map the content to the
+/// capabilities of the display.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HdrMetadata {
+    /// Maximum luminance of the display the content was mastered on, in nits.
+    pub max_luminance: f32,
+    /// Minimum luminance of the display the content was mastered on, in nits.
+    pub min_luminance: f32,
+    /// Luminance of the brightest pixel of the content, in nits.
+    pub max_content_light_level: f32,
+    /// Maximum average luminance of any frame of the content, in nits.
+    pub max_frame_average_light_level: f32,
+}
+
+impl Hash for HdrMetadata {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.max_luminance.to_bits().hash(state);
+        self.min_luminance.to_bits().hash(state);
+        self.max_content_light_level.to_bits().hash(state);
+        self.max_frame_average_light_level.to_bits().hash(state);
+    }
+}
+
+impl PartialEq for HdrMetadata {
+    fn eq(&self, other: &Self) -> bool {
+        (self.max_luminance.to_bits() == other.max_luminance.to_bits())
+            && (self.min_luminance.to_bits() == other.min_luminance.to_bits())
+            && (self.max_content_light_level.to_bits() == other.max_content_light_level.to_bits())
+            && (self.max_frame_average_light_level.to_bits()
+                == other.max_frame_average_light_level.to_bits())
+    }
+}
+
+impl Eq for HdrMetadata {}
+
 bitflags::bitflags! {
     /// Different ways that you can use a texture.
     ///
@@ -5420,6 +6284,22 @@ bitflags::bitflags! {
         //
         /// Allows a texture to be used with image atomics. Requires [`Features::TEXTURE_ATOMIC`].
         const STORAGE_ATOMIC = 1 << 16;
+        /// Marks a texture as a transient attachment, whose contents only live within a
+        /// single render pass, such as a multisampled color attachment that is resolved,
+        /// or a depth attachment that isn't needed after the pass.
+        ///
+        /// Tile-based GPUs can keep such attachments entirely in tile memory without
+        /// backing them with memory:
+        /// - Vulkan: the texture is created with `VK_IMAGE_USAGE_TRANSIENT_ATTACHMENT_BIT`
+        ///   and, if the device has one, allocated in a lazily allocated memory type.
+        /// - Metal: the texture uses `MTLStorageModeMemoryless` on Apple GPUs.
+        /// - Other backends allocate memory for the texture as usual.
+        ///
+        /// The only other usage allowed with this one is [`TextureUsages::RENDER_ATTACHMENT`],
+        /// which is required. Render passes using the texture as an attachment must use
+        /// [`LoadOp::Clear`] and [`StoreOp::Discard`] for it, and it can't be a resolve
+        /// target.
+        const TRANSIENT = 1 << 17;
     }
 }
 
@@ -5518,6 +6398,10 @@ pub struct SurfaceCapabilities {
     ///
     /// The usage [`TextureUsages::RENDER_ATTACHMENT`] is guaranteed.
     pub usages: TextureUsages,
+    /// List of supported color spaces to use with the given adapter.
+    ///
+    /// Will return at least [`SurfaceColorSpace::Srgb`].
+    pub color_spaces: Vec<SurfaceColorSpace>,
 }
 
 impl Default for SurfaceCapabilities {
@@ -5527,6 +6411,7 @@ impl Default for SurfaceCapabilities {
             present_modes: Vec::new(),
             alpha_modes: vec![CompositeAlphaMode::Opaque],
             usages: TextureUsages::RENDER_ATTACHMENT,
+            color_spaces: vec![SurfaceColorSpace::Srgb],
         }
     }
 }
@@ -5584,6 +6469,26 @@ pub struct SurfaceConfiguration<V> {
     pub desired_maximum_frame_latency: u32,
     /// Specifies how the alpha channel of the textures should be handled during compositing.
     pub alpha_mode: CompositeAlphaMode,
+    /// Specifies how the presentation engine interprets the colors of the textures.
+    ///
+    /// Defaults to [`SurfaceColorSpace::Auto`] when created via `Surface::get_default_config`.
+    pub color_space: SurfaceColorSpace,
+    /// Describes the luminance of the presented content, for color spaces
+    /// which allow HDR output.
+    ///
+    /// This is a hint, which is ignored where unsupported.
+    pub hdr_metadata: Option<HdrMetadata>,
+    /// Requests that frames be shown on the display without going through the
+    /// system compositor, for the lowest possible latency.
+    ///
+    /// - On DX12, this switches the window to exclusive fullscreen.
+    /// - On Vulkan, this allows the driver to use `VK_EXT_full_screen_exclusive`
+    ///   (Windows only).
+    /// - On Metal, this makes the layer present without a Core Animation transaction,
+    ///   so that it can be eligible for direct-to-display.
+    ///
+    /// This is a hint. Use `Surface::presentation_path` to check what was obtained.
+    pub exclusive_fullscreen: bool,
     /// Specifies what view formats will be allowed when calling `Texture::create_view` on the texture returned by `Surface::get_current_texture`.
     ///
     /// View formats of the same format as the texture are always allowed.
@@ -5603,6 +6508,9 @@ impl<V: Clone> SurfaceConfiguration<V> {
             present_mode: self.present_mode,
             desired_maximum_frame_latency: self.desired_maximum_frame_latency,
             alpha_mode: self.alpha_mode,
+            color_space: self.color_space,
+            hdr_metadata: self.hdr_metadata,
+            exclusive_fullscreen: self.exclusive_fullscreen,
             view_formats: fun(self.view_formats.clone()),
         }
     }
@@ -5670,6 +6578,126 @@ impl PresentationTimestamp {
     }
 }
 
+/// A GPU timestamp and a CPU timestamp sampled at the same time, as returned by
+/// [`Queue::get_timestamp_calibration`][Qgtc].
+///
+/// This places timestamp query results on the same timeline as CPU timestamps, for example
+/// to show GPU and CPU work side by side in a profiler.
+///
+/// The CPU clock depends on the backend:
+///
+/// <table>
+/// <tr>
+///     <td>Backend</td>
+///     <td>Clock</td>
+/// </tr>
+/// <tr>
+///     <td>DX12, Vulkan on Windows</td>
+///     <td><a href="https://docs.microsoft.com/en-us/windows/win32/api/profileapi/nf-profileapi-queryperformancecounter">QueryPerformanceCounter</a></td>
+/// </tr>
+/// <tr>
+///     <td>Metal</td>
+///     <td><a href="https://developer.apple.com/documentation/kernel/1462446-mach_absolute_time">mach_absolute_time</a></td>
+/// </tr>
+/// <tr>
+///     <td>Vulkan elsewhere</td>
+///     <td><a href="https://linux.die.net/man/3/clock_gettime">clock_gettime(CLOCK_MONOTONIC)</a></td>
+/// </tr>
+/// </table>
+///
+/// [Qgtc]: ../wgpu/struct.Queue.html#method.get_timestamp_calibration
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimestampCalibration {
+    /// GPU timestamp, in the same unit as the results of timestamp queries.
+    pub gpu_timestamp: u64,
+    /// CPU timestamp in nanoseconds.
+    pub cpu_timestamp: u64,
+    /// The number of nanoseconds of a GPU timestamp tick, like
+    /// [`Queue::get_timestamp_period`][Qgtp].
+    ///
+    /// [Qgtp]: ../wgpu/struct.Queue.html#method.get_timestamp_period
+    pub timestamp_period: f32,
+}
+
+impl TimestampCalibration {
+    /// Convert a GPU timestamp, read from a timestamp query, to a CPU timestamp in nanoseconds.
+    ///
+    /// The GPU and CPU clocks may drift apart over time, so calibrations should be taken
+    /// regularly, for example once per frame.
+    #[must_use]
+    pub fn gpu_to_cpu_timestamp(&self, gpu_timestamp: u64) -> u64 {
+        let delta_ns = (gpu_timestamp as i128 - self.gpu_timestamp as i128) as f64
+            * self.timestamp_period as f64;
+        (self.cpu_timestamp as i128 + delta_ns as i128).clamp(0, u64::MAX as i128) as u64
+    }
+}
+
+/// Feedback about a frame presented to a surface.
+///
+/// Returned by `Surface::get_presentation_statistics`, which requires
+/// [`Features::PRESENTATION_TIMING`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PresentationStatistics {
+    /// Which present this is about, counting the presents since the surface was last
+    /// configured, starting at 1.
+    pub present_id: u64,
+    /// When the frame started being displayed, in the same time domain as
+    /// `Adapter::get_presentation_timestamp`.
+    ///
+    /// This is [`PresentationTimestamp::INVALID_TIMESTAMP`] if the frame was dropped.
+    pub present_time: PresentationTimestamp,
+    /// Duration of a refresh cycle of the display, if known.
+    pub refresh_interval: Option<core::time::Duration>,
+    /// `true` if the frame was never displayed, because a later frame replaced it.
+    pub dropped: bool,
+}
+
+/// How frames presented to a surface reach the display.
+///
+/// Returned by `Surface::presentation_path`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PresentationPath {
+    /// The backend can't tell how frames are shown.
+    Unknown,
+    /// Frames are composited with other content by the system compositor.
+    Composed,
+    /// Frames are scanned out without going through the compositor, for example
+    /// through exclusive fullscreen, independent flip or a hardware overlay.
+    Direct,
+}
+
+/// A video mode supported by a [`DisplayInfo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DisplayMode {
+    /// Width of the visible region, in pixels.
+    pub width: u32,
+    /// Height of the visible region, in pixels.
+    pub height: u32,
+    /// Refresh rate, in millihertz.
+    pub refresh_rate: u32,
+}
+
+/// Information about a physical display that can be presented to directly,
+/// without a windowing system.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DisplayInfo {
+    /// Human readable name of the display, if the driver provides one.
+    pub name: String,
+    /// Physical width of the display, in millimeters.
+    pub physical_width: u32,
+    /// Physical height of the display, in millimeters.
+    pub physical_height: u32,
+    /// Native resolution of the display, in pixels.
+    pub native_resolution: Extent3d,
+    /// Modes the display can be driven with.
+    pub modes: Vec<DisplayMode>,
+}
+
 /// RGBA double precision color.
 ///
 /// This is not to be used as a generic color type, only for specific wgpu interfaces.
@@ -5783,6 +6811,25 @@ impl core::fmt::Debug for Origin2d {
     }
 }
 
+/// A rectangle of texels, with its origin at the top left corner.
+///
+/// Used to describe the regions of a surface texture that changed, see
+/// `SurfaceTexture::present_with_damage`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Rect {
+    /// X position of the left edge
+    pub x: u32,
+    /// Y position of the top edge
+    pub y: u32,
+    /// Width of the rectangle
+    pub width: u32,
+    /// Height of the rectangle
+    pub height: u32,
+}
+
 /// Origin of a copy to/from a texture.
 ///
 /// Corresponds to [WebGPU `GPUOrigin3D`](
@@ -6038,6 +7085,7 @@ fn test_max_mips() {
 /// [`TextureView`]: ../wgpu/struct.TextureView.html
 /// [`Texture::create_view()`]: ../wgpu/struct.Texture.html#method.create_view
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TextureViewDescriptor<L> {
     /// Debug label of the texture view. This will show up in graphics debuggers for easy identification.
     pub label: L,
@@ -6202,6 +7250,91 @@ impl<L, V> TextureDescriptor<L, V> {
     }
 }
 
+/// Describes a [`MemoryHeap`](../wgpu/struct.MemoryHeap.html).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryHeapDescriptor<L> {
+    /// Debug label for the heap.
+    pub label: L,
+    /// Size of the heap in bytes.
+    pub size: BufferAddress,
+}
+
+impl<L> MemoryHeapDescriptor<L> {
+    /// Takes a closure and maps the label of the memory heap descriptor into another.
+    #[must_use]
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> MemoryHeapDescriptor<K> {
+        MemoryHeapDescriptor {
+            label: fun(&self.label),
+            size: self.size,
+        }
+    }
+}
+
+/// A location in a memory heap at which a texture is placed, or sparse memory is bound.
+///
+/// Textures placed in overlapping regions of the same heap alias each other.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HeapRegion<H> {
+    /// The heap the memory is in.
+    pub heap: H,
+    /// Offset in bytes from the start of the heap. Must be a multiple of the
+    /// [`TextureMemoryRequirements::alignment`] of a placed texture, or of
+    /// [`SPARSE_PAGE_SIZE`] for sparse memory.
+    pub offset: BufferAddress,
+}
+
+/// Binds memory to, or unbinds memory from, a range of a sparse buffer.
+///
+/// For use with `Queue::bind_sparse_memory`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SparseBufferBinding<B, H> {
+    /// The sparse buffer to bind memory to.
+    pub buffer: B,
+    /// Offset in bytes of the range in the buffer. Must be a multiple of
+    /// [`SPARSE_PAGE_SIZE`].
+    pub offset: BufferAddress,
+    /// Size in bytes of the range. Must be a multiple of [`SPARSE_PAGE_SIZE`].
+    pub size: BufferAddress,
+    /// The memory to back the range with, or `None` to unbind the range.
+    pub memory: Option<HeapRegion<H>>,
+}
+
+/// Binds memory to, or unbinds memory from, a region of a sparse texture.
+///
+/// For use with `Queue::bind_sparse_memory`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SparseTextureBinding<T, H> {
+    /// The sparse texture to bind memory to.
+    pub texture: T,
+    /// The mip level the region is in.
+    pub mip_level: u32,
+    /// The array layer the region is in.
+    pub array_layer: u32,
+    /// Origin of the region in texels. Must be a multiple of
+    /// [`TextureFormat::sparse_tile_size`].
+    pub origin: Origin3d,
+    /// Size of the region in texels. Must be a multiple of
+    /// [`TextureFormat::sparse_tile_size`], unless the region reaches the edge of
+    /// the mip level. `depth_or_array_layers` must be 1.
+    pub size: Extent3d,
+    /// The memory to back the region with, or `None` to unbind the region.
+    ///
+    /// The tiles of the region take up consecutive pages of the heap, in row-major
+    /// order.
+    pub memory: Option<HeapRegion<H>>,
+}
+
+/// How much memory a texture needs when it is placed in a memory heap.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextureMemoryRequirements {
+    /// Size of the texture in bytes, including any padding the backend needs.
+    pub size: BufferAddress,
+    /// Required alignment of the texture's offset in the heap, in bytes.
+    pub alignment: BufferAddress,
+}
+
 /// Describes a `Sampler`.
 ///
 /// For use with `Device::create_sampler`.
@@ -6338,6 +7471,72 @@ pub enum FilterMode {
     Linear = 1,
 }
 
+/// The matrix used to convert YCbCr to RGB.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum YuvMatrix {
+    /// ITU-R BT.601, used by SD video.
+    Bt601 = 0,
+    /// ITU-R BT.709, used by HD video.
+    #[default]
+    Bt709 = 1,
+    /// ITU-R BT.2020, used by UHD and HDR video.
+    Bt2020 = 2,
+}
+
+/// The range of values the YCbCr channels use.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum YuvRange {
+    /// Luma and chroma use the full range of values.
+    Full = 0,
+    /// Luma uses `16..=235` and chroma `16..=240`, scaled to the bit depth. This is
+    /// what most video uses.
+    #[default]
+    Limited = 1,
+}
+
+/// Where chroma samples sit relative to the luma samples, along one axis.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ChromaLocation {
+    /// Chroma samples sit on the even luma samples.
+    #[default]
+    CositedEven = 0,
+    /// Chroma samples sit halfway between pairs of luma samples.
+    Midpoint = 1,
+}
+
+/// A conversion from the YCbCr values of a multi-planar texture to RGB, applied by
+/// the sampler of a [`BindingType::YcbcrTexture`] binding.
+///
+/// Corresponds to [Vulkan `VkSamplerYcbcrConversionCreateInfo`](
+/// https://registry.khronos.org/vulkan/specs/latest/man/html/VkSamplerYcbcrConversionCreateInfo.html).
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SamplerYcbcrConversion {
+    /// The multi-planar format of the textures that are converted, such as
+    /// [`TextureFormat::NV12`].
+    pub format: TextureFormat,
+    /// The matrix that converts YCbCr to RGB.
+    pub matrix: YuvMatrix,
+    /// The range of the YCbCr values.
+    pub range: YuvRange,
+    /// Where chroma samples sit horizontally.
+    pub x_chroma_offset: ChromaLocation,
+    /// Where chroma samples sit vertically.
+    pub y_chroma_offset: ChromaLocation,
+    /// How chroma is reconstructed at the luma resolution. This is also the
+    /// minification and magnification filter of the sampler.
+    pub chroma_filter: FilterMode,
+}
+
 /// A range of push constant memory to pass to a shader stage.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -6360,6 +7559,14 @@ pub struct PushConstantRange {
 pub struct CommandBufferDescriptor<L> {
     /// Debug label of this command buffer.
     pub label: L,
+    /// If true, the command buffer can be submitted more than once. Each
+    /// submission checks again that the resources it uses are still alive.
+    ///
+    /// A reusable command buffer can't suspend or resume render passes across
+    /// command buffers, nor use surface textures.
+    ///
+    /// Requires [`Features::REUSABLE_COMMAND_BUFFERS`].
+    pub reusable: bool,
 }
 
 impl<L> CommandBufferDescriptor<L> {
@@ -6368,6 +7575,7 @@ impl<L> CommandBufferDescriptor<L> {
     pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> CommandBufferDescriptor<K> {
         CommandBufferDescriptor {
             label: fun(&self.label),
+            reusable: self.reusable,
         }
     }
 }
@@ -6399,6 +7607,30 @@ pub struct RenderBundleDepthStencil {
     pub stencil_read_only: bool,
 }
 
+bitflags::bitflags! {
+    /// State of the render pass that a render bundle inherits when it is executed.
+    ///
+    /// By default a render bundle is isolated from the pass executing it. Each flag lifts part
+    /// of that isolation, so the bundle can only be executed in passes that provide the
+    /// inherited state.
+    #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct RenderBundleInheritance: u8 {
+        /// The bundle may begin and end occlusion queries, which use the
+        /// [`occlusion_query_set`] of the render pass.
+        ///
+        /// [`occlusion_query_set`]: ../wgpu/struct.RenderPassDescriptor.html#structfield.occlusion_query_set
+        const OCCLUSION_QUERY_SET = 1 << 0;
+        /// Setting a pipeline in the bundle doesn't zero its push constants, but sets them to the
+        /// values last set on the render pass instead.
+        ///
+        /// Requires [`Features::PUSH_CONSTANTS`].
+        const PUSH_CONSTANTS = 1 << 1;
+    }
+}
+
 /// Describes a [`RenderBundle`](../wgpu/struct.RenderBundle.html).
 ///
 /// Corresponds to [WebGPU `GPURenderBundleDescriptor`](
@@ -6884,6 +8116,34 @@ pub enum BindingType {
     ///
     /// Requires [`Features::EXTERNAL_TEXTURE`]
     ExternalTexture,
+
+    /// A multi-planar texture, sampled through an immutable sampler that converts its
+    /// YCbCr values to RGB.
+    ///
+    /// Example WGSL syntax:
+    /// ```rust,ignore
+    /// @group(0) @binding(0)
+    /// var t: texture_2d<f32>;
+    /// @group(0) @binding(1)
+    /// var s: sampler;
+    ///
+    /// let rgb = textureSample(t, s, uv).rgb;
+    /// ```
+    ///
+    /// The texture is sampled with the filter and chroma reconstruction of the
+    /// conversion, whichever sampler `textureSample` is given, and must only be read
+    /// with `textureSample` on the global itself, not through a function argument.
+    /// It is bound to a [`TextureViewDimension::D2`] view of all
+    /// the planes of a texture, in the format of the conversion, and can't be part of
+    /// a binding array.
+    ///
+    /// Corresponds to a combined image sampler with an immutable sampler in Vulkan.
+    ///
+    /// Requires [`Features::YUV_SAMPLING`]
+    YcbcrTexture {
+        /// The conversion applied when sampling the texture.
+        conversion: SamplerYcbcrConversion,
+    },
 }
 
 impl BindingType {
@@ -7343,6 +8603,13 @@ bitflags::bitflags! {
     /// and `COMPUTE_SHADER_INVOCATIONS`, it would write 16 bytes,
     /// the first 8 bytes being the primitive out value, the last 8
     /// bytes being the compute shader invocation count.
+    ///
+    /// `VERTEX_SHADER_INVOCATIONS`, `CLIPPER_PRIMITIVES_OUT`, `FRAGMENT_SHADER_INVOCATIONS`
+    /// and `COMPUTE_SHADER_INVOCATIONS` are the common subset which every backend supporting
+    /// [`Features::PIPELINE_STATISTICS_QUERY`] counts the same way, so they can be compared
+    /// across backends. `CLIPPER_INVOCATIONS` is recorded everywhere too, but what a
+    /// clipper invocation is differs between drivers. All counts are approximate: drivers
+    /// may count work which is skipped, such as helper fragment invocations.
     #[repr(transparent)]
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[cfg_attr(feature = "serde", serde(transparent))]
@@ -7467,6 +8734,15 @@ pub struct ShaderRuntimeChecks {
     /// conclusions about other safety-critical code paths. This option SHOULD NOT be disabled
     /// when running untrusted code.
     pub force_loop_bounding: bool,
+    /// Zero-initialize the workgroup memory of compute, task and mesh shaders before
+    /// they run, as WebGPU requires.
+    ///
+    /// When this is `false`, pipelines created from the module never zero-initialize
+    /// workgroup memory, whatever their
+    /// `PipelineCompilationOptions::zero_initialize_workgroup_memory` says, so the caller
+    /// MUST ensure that shaders write to workgroup memory before reading from it.
+    /// Otherwise, they may read values left by other workgroups, pipelines or processes.
+    pub zero_initialize_workgroup_memory: bool,
 }
 
 impl ShaderRuntimeChecks {
@@ -7499,6 +8775,7 @@ impl ShaderRuntimeChecks {
         Self {
             bounds_checks: all_checks,
             force_loop_bounding: all_checks,
+            zero_initialize_workgroup_memory: all_checks,
         }
     }
 }
@@ -7509,6 +8786,51 @@ impl Default for ShaderRuntimeChecks {
     }
 }
 
+/// How much effort the backend shader compiler should spend optimizing a shader module.
+///
+/// This is a hint: backends that have no control over the optimizations applied to
+/// the shaders they are given treat every level like [`ShaderOptimization::Default`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShaderOptimization {
+    /// Compile the shader the way the backend normally would.
+    ///
+    /// On DX12 this disables optimizations when [`InstanceFlags::DEBUG`] is set.
+    #[default]
+    Default,
+    /// Spend more compilation time to produce faster shaders.
+    ///
+    /// - On every backend, shaders that are not passed through are first simplified by
+    ///   naga: constant expressions are folded, branches and loops that are decided
+    ///   before the shader runs are removed, and so is the code no entry point uses.
+    /// - On DX12, FXC and DXC are invoked with their highest optimization level,
+    ///   even when [`InstanceFlags::DEBUG`] is set.
+    /// - On Vulkan, when the `spirv-opt` feature is enabled and the `SPIRV-Tools-shared`
+    ///   library can be loaded at runtime, the generated SPIR-V is run through the
+    ///   SPIRV-Tools performance passes before being handed to the driver.
+    Performance,
+}
+
+/// The workgroup memory of a compute, task or mesh shader entry point.
+///
+/// Returned by `ShaderModule::workgroup_memory`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkgroupMemoryInfo {
+    /// The number of bytes of workgroup memory used by the entry point.
+    pub size: u32,
+    /// Whether this memory is zero-initialized before the entry point runs.
+    ///
+    /// This is `false` if the entry point uses no workgroup memory, or if the module was
+    /// created with [`ShaderRuntimeChecks::zero_initialize_workgroup_memory`] disabled.
+    /// Otherwise, pipelines zero-initialize it unless their
+    /// `PipelineCompilationOptions::zero_initialize_workgroup_memory` is `false`: Naga
+    /// emits code which zeroes each workgroup variable and waits on a workgroup barrier
+    /// at the start of the entry point, except on Vulkan devices supporting
+    /// `VK_KHR_zero_initialize_workgroup_memory`, where the driver does it.
+    pub zero_initialized: bool,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// Descriptor for all size defining attributes of a single triangle geometry inside a bottom level acceleration structure.
@@ -7551,7 +8873,23 @@ pub enum AccelerationStructureUpdateMode {
     ///
     /// Not advised for major topology changes.
     /// (Useful for e.g. skinning)
+    ///
+    /// An update is performed if the acceleration structure has
+    /// [`AccelerationStructureFlags::ALLOW_UPDATE`] and a build of it with the same geometry
+    /// counts (vertex and index counts of each geometry, or the instance count of a TLAS) was
+    /// submitted before the current build was recorded. Otherwise it is fully built.
     PreferUpdate,
+    /// Once built, always perform an incremental update, refitting the acceleration structure
+    /// around the new positions of its geometry without changing its topology.
+    ///
+    /// This is much faster than a full build, and is meant for animated geometry which keeps
+    /// its primitives (e.g. skinning). The acceleration structure must have been created with
+    /// [`AccelerationStructureFlags::ALLOW_UPDATE`].
+    ///
+    /// A build recorded before any build of the acceleration structure was submitted is a full
+    /// build. Every other build is an update, and must use the same geometry counts as the
+    /// build before it.
+    Refit,
 }
 
 #[repr(C)]
@@ -7611,8 +8949,8 @@ bitflags::bitflags!(
     #[cfg_attr(feature = "serde", serde(transparent))]
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     pub struct AccelerationStructureFlags: u8 {
-        /// Allow for incremental updates (no change in size), currently this is unimplemented
-        /// and will build as normal (this is fine, update vs build should be unnoticeable)
+        /// Allow for incremental updates (no change in size), see
+        /// [`AccelerationStructureUpdateMode`].
         const ALLOW_UPDATE = 1 << 0;
         /// Allow the acceleration structure to be compacted in a copy operation
         /// (`Blas::prepare_for_compaction`, `CommandEncoder::compact_blas`).
@@ -7679,6 +9017,62 @@ pub const TRANSFORM_BUFFER_ALIGNMENT: BufferAddress = 16;
 /// Alignment requirement for instance buffers used in acceleration structure builds (`build_acceleration_structures_unsafe_tlas`)
 pub const INSTANCE_BUFFER_ALIGNMENT: BufferAddress = 16;
 
+/// A shader group of a ray tracing pipeline.
+///
+/// Stages are referred to by their index in the list of stages the pipeline is created with.
+/// Groups are in turn referred to by their index when building a shader binding table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RayTracingShaderGroup {
+    /// A group holding a single ray generation, miss or callable stage.
+    General {
+        /// Index of the stage.
+        stage: u32,
+    },
+    /// A hit group used for triangle geometry.
+    TrianglesHit {
+        /// Index of the closest hit stage, if any.
+        closest_hit: Option<u32>,
+        /// Index of the any hit stage, if any.
+        any_hit: Option<u32>,
+    },
+    /// A hit group used for procedural (AABB) geometry.
+    ProceduralHit {
+        /// Index of the closest hit stage, if any.
+        closest_hit: Option<u32>,
+        /// Index of the any hit stage, if any.
+        any_hit: Option<u32>,
+        /// Index of the intersection stage.
+        intersection: u32,
+    },
+}
+
+/// Where the records of one kind of shader group are in a shader binding table buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShaderBindingTableRegion {
+    /// Offset of the first record, in bytes.
+    pub offset: BufferAddress,
+    /// Distance between two consecutive records, in bytes.
+    pub stride: BufferAddress,
+    /// Size of the region, in bytes. A region of size 0 is unused.
+    pub size: BufferAddress,
+}
+
+/// Where the records of each kind of shader group are in a shader binding table buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShaderBindingTableLayout {
+    /// The record of the ray generation group. Its size must be equal to its stride.
+    pub ray_generation: ShaderBindingTableRegion,
+    /// The records of the miss groups.
+    pub miss: ShaderBindingTableRegion,
+    /// The records of the hit groups.
+    pub hit: ShaderBindingTableRegion,
+    /// The records of the callable groups.
+    pub callable: ShaderBindingTableRegion,
+}
+
 pub use send_sync::*;
 
 #[doc(hidden)]
@@ -7754,6 +9148,10 @@ mod send_sync {
 
 /// Corresponds to a [`GPUDeviceLostReason`].
 ///
+/// The variants other than `Unknown` and `Destroyed` are unique to `wgpu`, and are
+/// only reported by backends that can tell the causes apart. Others report
+/// `Unknown` instead.
+///
 /// [`GPUDeviceLostReason`]: https://www.w3.org/TR/webgpu/#enumdef-gpudevicelostreason
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -7763,6 +9161,16 @@ pub enum DeviceLostReason {
     Unknown = 0,
     /// The device's `destroy` method was called.
     Destroyed = 1,
+    /// The GPU stopped responding to commands, usually because they took too
+    /// long to run, and the driver reset it.
+    Hung = 2,
+    /// The GPU was physically removed, or its driver was disabled or updated.
+    Removed = 3,
+    /// The driver reset the GPU, possibly because of a fault in another
+    /// application.
+    DriverReset = 4,
+    /// The device ran out of memory.
+    OutOfMemory = 5,
 }
 
 /// Descriptor for creating a shader module.
@@ -7917,3 +9325,15 @@ pub struct ShaderModuleDescriptorSpirV<'a, L> {
     /// Binary SPIR-V data, in 4-byte words.
     pub source: Cow<'a, [u32]>,
 }
+
+/// A video compression format that can be decoded into textures.
+///
+/// This type is unique to the Rust API of `wgpu`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VideoCodec {
+    /// H.264, also known as AVC.
+    H264,
+    /// H.265, also known as HEVC.
+    Hevc,
+}
@@ -0,0 +1,215 @@
+//! Types for the commands recorded by the noop backend.
+
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    BufferAddress, BufferSize, BufferUses, DynamicOffset, ImageSubresourceRange, IndexFormat,
+    ShaderStages, TextureUses,
+};
+
+/// A command buffer submitted to a queue of the noop backend, with the commands recorded
+/// into it.
+///
+/// Recording is enabled by [`NoopBackendOptions::record`](crate::NoopBackendOptions::record).
+/// Resources are identified by their labels, which are `None` for resources without a label.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RecordedCommandBuffer {
+    /// The label of the command encoder the commands were recorded with.
+    ///
+    /// `wgpu-core` encodes each pass into a command buffer of its own, which has the
+    /// label of the pass.
+    pub label: Option<String>,
+    /// The commands, in the order they were encoded in.
+    ///
+    /// These are the commands which `wgpu-core` encodes after validation, so they also
+    /// contain the barriers and the commands it inserts itself, such as the clears of
+    /// resources which are read before they are initialized.
+    pub commands: Vec<RecordedCommand>,
+}
+
+/// A command recorded by the noop backend, see [`RecordedCommandBuffer`].
+///
+/// The commands and their fields are named after the methods and arguments of the
+/// command encoders and passes of `wgpu` they are encoded by.
+#[derive(Clone, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub enum RecordedCommand {
+    /// A barrier between two uses of a buffer.
+    BufferBarrier {
+        buffer: Option<String>,
+        from: BufferUses,
+        to: BufferUses,
+    },
+    /// A barrier between two uses of subresources of a texture.
+    TextureBarrier {
+        texture: Option<String>,
+        range: ImageSubresourceRange,
+        from: TextureUses,
+        to: TextureUses,
+    },
+    ClearBuffer {
+        buffer: Option<String>,
+        range: Range<BufferAddress>,
+    },
+    CopyBufferToBuffer {
+        src: Option<String>,
+        dst: Option<String>,
+    },
+    CopyBufferToTexture {
+        src: Option<String>,
+        dst: Option<String>,
+    },
+    CopyTextureToBuffer {
+        src: Option<String>,
+        dst: Option<String>,
+    },
+    CopyTextureToTexture {
+        src: Option<String>,
+        dst: Option<String>,
+    },
+    ResolveTexture {
+        src: Option<String>,
+        dst: Option<String>,
+    },
+    BeginQuery {
+        query_set: Option<String>,
+        index: u32,
+    },
+    EndQuery {
+        query_set: Option<String>,
+        index: u32,
+    },
+    WriteTimestamp {
+        query_set: Option<String>,
+        index: u32,
+    },
+    ResetQueries {
+        query_set: Option<String>,
+        range: Range<u32>,
+    },
+    CopyQueryResults {
+        query_set: Option<String>,
+        range: Range<u32>,
+        buffer: Option<String>,
+        offset: BufferAddress,
+    },
+    /// The beginning of a render pass, with the texture views it renders to.
+    BeginRenderPass {
+        label: Option<String>,
+        color_attachments: Vec<Option<String>>,
+        depth_stencil_attachment: Option<String>,
+    },
+    EndRenderPass,
+    /// The parts of a render pass recorded by its parallel encoders, with the commands
+    /// of each part, in the order they are executed in.
+    ExecuteParallelEncoders(Vec<Vec<RecordedCommand>>),
+    BeginComputePass {
+        label: Option<String>,
+    },
+    EndComputePass,
+    SetBindGroup {
+        index: u32,
+        bind_group: Option<String>,
+        dynamic_offsets: Vec<DynamicOffset>,
+    },
+    SetPushConstants {
+        stages: ShaderStages,
+        offset: u32,
+        data: Vec<u32>,
+    },
+    InsertDebugMarker(String),
+    PushDebugGroup(String),
+    PopDebugGroup,
+    SetRenderPipeline {
+        pipeline: Option<String>,
+    },
+    SetComputePipeline {
+        pipeline: Option<String>,
+    },
+    SetRayTracingPipeline {
+        pipeline: Option<String>,
+    },
+    SetIndexBuffer {
+        buffer: Option<String>,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+        format: IndexFormat,
+    },
+    SetVertexBuffer {
+        slot: u32,
+        buffer: Option<String>,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    },
+    SetViewport {
+        /// The index of the viewport, which is 0 unless multiple viewports are used.
+        index: u32,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        depth: Range<f32>,
+    },
+    SetScissorRect {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    SetStencilReference(u32),
+    SetBlendConstant([f32; 4]),
+    BeginConditionalRendering {
+        buffer: Option<String>,
+        offset: BufferAddress,
+    },
+    EndConditionalRendering,
+    Draw {
+        vertices: Range<u32>,
+        instances: Range<u32>,
+    },
+    DrawIndexed {
+        indices: Range<u32>,
+        base_vertex: i32,
+        instances: Range<u32>,
+    },
+    DrawMeshTasks {
+        group_count: [u32; 3],
+    },
+    /// An indirect draw of `max_count` draws, or of the number of draws read from
+    /// `count_buffer` if it is a multi-draw with a count buffer.
+    DrawIndirect {
+        kind: RecordedDrawKind,
+        buffer: Option<String>,
+        offset: BufferAddress,
+        max_count: u32,
+        count_buffer: Option<(Option<String>, BufferAddress)>,
+    },
+    Dispatch {
+        group_count: [u32; 3],
+    },
+    DispatchIndirect {
+        buffer: Option<String>,
+        offset: BufferAddress,
+    },
+    TraceRays {
+        count: [u32; 3],
+    },
+    /// A build of acceleration structures, with the number of acceleration structures built.
+    BuildAccelerationStructures {
+        count: u32,
+    },
+    /// Any other command, with the name of the `wgpu-hal` method it was encoded with.
+    Other(&'static str),
+}
+
+/// The kind of draws of a [`RecordedCommand::DrawIndirect`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordedDrawKind {
+    /// Non-indexed draws.
+    Draw,
+    /// Indexed draws.
+    DrawIndexed,
+    /// Mesh shader task dispatches.
+    DrawMeshTasks,
+}
@@ -0,0 +1,348 @@
+//! Descriptions of the memory layout of Rust types which are shared with shaders.
+
+use alloc::string::String;
+use core::fmt;
+
+/// A Rust type which can be stored in a uniform or storage buffer and read by shaders.
+///
+/// [`Self::LAYOUT`] describes where the scalars of the type are in memory, and is compared
+/// with the type the shader declares by `ShaderModule::check_layout`. It is usually derived
+/// with `#[derive(wgpu::ShaderType)]`, which requires `#[repr(C)]` and describes the offsets
+/// Rust actually chose for the fields, so nothing is assumed about the layout.
+///
+/// Arrays of 2 to 4 scalars also match WGSL vectors with the same layout, and arrays of them
+/// match matrices, e.g. `[f32; 3]` matches `vec3<f32>` and `[[f32; 4]; 4]` matches
+/// `mat4x4<f32>`. A `mat3x3<f32>` has padded columns, so it is matched by `[[f32; 4]; 3]`.
+pub trait ShaderType {
+    /// The layout of the type.
+    const LAYOUT: ShaderTypeLayout;
+}
+
+/// A scalar type in a [`ShaderTypeLayout`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderScalar {
+    /// `f32`
+    F32,
+    /// `f64`
+    F64,
+    /// `i32`
+    I32,
+    /// `u32`
+    U32,
+    /// `i64`
+    I64,
+    /// `u64`
+    U64,
+}
+
+impl ShaderScalar {
+    /// Size of the scalar in bytes.
+    #[must_use]
+    pub const fn size(self) -> u32 {
+        match self {
+            Self::F32 | Self::I32 | Self::U32 => 4,
+            Self::F64 | Self::I64 | Self::U64 => 8,
+        }
+    }
+}
+
+impl fmt::Display for ShaderScalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::I32 => "i32",
+            Self::U32 => "u32",
+            Self::I64 => "i64",
+            Self::U64 => "u64",
+        })
+    }
+}
+
+/// The memory layout of a [`ShaderType`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShaderTypeLayout {
+    /// A single scalar.
+    Scalar(ShaderScalar),
+    /// Elements of the same type, `stride` bytes apart.
+    Array {
+        /// The layout of the elements.
+        element: &'static ShaderTypeLayout,
+        /// The number of elements, or `None` for a runtime-sized array, which must be the
+        /// last member of a storage buffer.
+        length: Option<u32>,
+        /// The distance between the starts of consecutive elements, in bytes.
+        stride: u32,
+    },
+    /// A struct, whose members are compared in order.
+    Struct {
+        /// The name of the Rust type, for error messages.
+        name: &'static str,
+        /// The members of the struct.
+        members: &'static [ShaderMemberLayout],
+        /// The size of the struct in bytes, including trailing padding.
+        size: u32,
+    },
+}
+
+/// A member of a [`ShaderTypeLayout::Struct`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderMemberLayout {
+    /// The name of the Rust field, for error messages.
+    pub name: &'static str,
+    /// The offset of the member from the start of the struct, in bytes.
+    pub offset: u32,
+    /// The layout of the member.
+    pub ty: &'static ShaderTypeLayout,
+}
+
+impl ShaderTypeLayout {
+    /// The size of the type in bytes, or `None` if it is runtime-sized.
+    #[must_use]
+    pub const fn size(&self) -> Option<u32> {
+        match *self {
+            Self::Scalar(scalar) => Some(scalar.size()),
+            Self::Array {
+                length: Some(length),
+                stride,
+                ..
+            } => Some(length * stride),
+            Self::Array { length: None, .. } => None,
+            Self::Struct { size, .. } => Some(size),
+        }
+    }
+
+    /// Whether this is an array which may also be a WGSL vector: 2 to 4 tightly packed
+    /// scalars.
+    #[must_use]
+    pub const fn may_be_vector(&self) -> bool {
+        match *self {
+            Self::Array {
+                element: &Self::Scalar(scalar),
+                length: Some(2..=4),
+                stride,
+            } => stride == scalar.size(),
+            _ => false,
+        }
+    }
+
+    /// Whether this is an array which may also be a WGSL matrix: 2 to 4 arrays which may be
+    /// vectors.
+    #[must_use]
+    pub const fn may_be_matrix(&self) -> bool {
+        match *self {
+            Self::Array {
+                element: column,
+                length: Some(2..=4),
+                ..
+            } => column.may_be_vector(),
+            _ => false,
+        }
+    }
+
+    /// Checks the WGSL rules for types in the `uniform` address space, which are stricter
+    /// than the rules for `storage`, like those of std140 compared to std430.
+    ///
+    /// Arrays must have a stride which is a multiple of 16, and arrays and structs nested in
+    /// structs must start at offsets which are multiples of 16 and be followed by padding to
+    /// a multiple of 16 bytes. Arrays which may be vectors or matrices are not checked as
+    /// arrays.
+    ///
+    /// Returns a description of the first violation. This is a `const fn` so that
+    /// `#[shader_type(uniform)]` can check it at compile time.
+    #[must_use]
+    pub const fn uniform_layout_error(&self) -> Option<&'static str> {
+        match *self {
+            Self::Scalar(_) => None,
+            Self::Array { .. } if self.may_be_vector() || self.may_be_matrix() => None,
+            Self::Array { length: None, .. } => {
+                Some("uniform buffers can't contain runtime-sized arrays")
+            }
+            Self::Array {
+                element, stride, ..
+            } => {
+                if stride % 16 != 0 {
+                    return Some(
+                        "arrays in uniform buffers must have a stride which is a multiple of 16",
+                    );
+                }
+                element.uniform_layout_error()
+            }
+            Self::Struct { members, size, .. } => {
+                let mut i = 0;
+                while i < members.len() {
+                    let member = members[i];
+                    let nested = match *member.ty {
+                        Self::Scalar(_) => false,
+                        Self::Array { .. } => {
+                            !member.ty.may_be_vector() && !member.ty.may_be_matrix()
+                        }
+                        Self::Struct { .. } => true,
+                    };
+                    if nested {
+                        if member.offset % 16 != 0 {
+                            return Some(
+                                "arrays and structs in uniform buffers must start at an offset \
+                                 which is a multiple of 16",
+                            );
+                        }
+                        let end = if i + 1 < members.len() {
+                            members[i + 1].offset
+                        } else {
+                            size
+                        };
+                        let member_size = match member.ty.size() {
+                            Some(member_size) => member_size,
+                            None => 0,
+                        };
+                        if end - member.offset < member_size.div_ceil(16) * 16 {
+                            return Some(
+                                "structs in uniform buffers must be followed by padding to a \
+                                 multiple of 16 bytes",
+                            );
+                        }
+                    }
+                    if let Some(error) = member.ty.uniform_layout_error() {
+                        return Some(error);
+                    }
+                    i += 1;
+                }
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for ShaderTypeLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Scalar(scalar) => write!(f, "{scalar}"),
+            Self::Array {
+                element,
+                length: Some(length),
+                ..
+            } => write!(f, "[{element}; {length}]"),
+            Self::Array {
+                element,
+                length: None,
+                ..
+            } => write!(f, "[{element}]"),
+            Self::Struct { name, .. } => f.write_str(name),
+        }
+    }
+}
+
+macro_rules! impl_shader_type_for_scalar {
+    ($($ty:ty => $scalar:ident),* $(,)?) => {
+        $(
+            impl ShaderType for $ty {
+                const LAYOUT: ShaderTypeLayout = ShaderTypeLayout::Scalar(ShaderScalar::$scalar);
+            }
+        )*
+    };
+}
+
+impl_shader_type_for_scalar!(
+    f32 => F32,
+    f64 => F64,
+    i32 => I32,
+    u32 => U32,
+    i64 => I64,
+    u64 => U64,
+);
+
+impl<T: ShaderType, const N: usize> ShaderType for [T; N] {
+    const LAYOUT: ShaderTypeLayout = ShaderTypeLayout::Array {
+        element: &T::LAYOUT,
+        length: Some(N as u32),
+        stride: size_of::<T>() as u32,
+    };
+}
+
+impl<T: ShaderType> ShaderType for [T] {
+    const LAYOUT: ShaderTypeLayout = ShaderTypeLayout::Array {
+        element: &T::LAYOUT,
+        length: None,
+        stride: size_of::<T>() as u32,
+    };
+}
+
+/// Error returned by `ShaderModule::check_layout`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ShaderLayoutError {
+    /// The shader module doesn't declare a uniform or storage buffer variable with the name.
+    NotFound {
+        /// The name of the variable.
+        name: String,
+    },
+    /// The shader module isn't reflected: it was created from passthrough shader code or is
+    /// invalid, or it belongs to a backend which doesn't reflect shaders, like WebGPU.
+    NotReflected,
+    /// The type of the variable doesn't match the layout.
+    Mismatch {
+        /// The path to the mismatched part of the variable, like `lights[].color`.
+        path: String,
+        /// How the shader's type and the layout differ.
+        reason: String,
+    },
+}
+
+impl core::error::Error for ShaderLayoutError {}
+impl fmt::Display for ShaderLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { name } => {
+                write!(
+                    f,
+                    "The shader module doesn't declare a buffer named `{name}`"
+                )
+            }
+            Self::NotReflected => write!(f, "The shader module isn't reflected"),
+            Self::Mismatch { path, reason } => {
+                write!(
+                    f,
+                    "The layout of `{path}` doesn't match the shader: {reason}"
+                )
+            }
+        }
+    }
+}
+
+#[test]
+fn uniform_layout() {
+    #[repr(C)]
+    struct Light {
+        position: [f32; 3],
+        intensity: f32,
+    }
+    impl ShaderType for Light {
+        const LAYOUT: ShaderTypeLayout = ShaderTypeLayout::Struct {
+            name: "Light",
+            members: &[
+                ShaderMemberLayout {
+                    name: "position",
+                    offset: 0,
+                    ty: &<[f32; 3]>::LAYOUT,
+                },
+                ShaderMemberLayout {
+                    name: "intensity",
+                    offset: 12,
+                    ty: &f32::LAYOUT,
+                },
+            ],
+            size: 16,
+        };
+    }
+
+    assert_eq!(<[f32; 3]>::LAYOUT.uniform_layout_error(), None);
+    assert_eq!(<[[f32; 4]; 3]>::LAYOUT.uniform_layout_error(), None);
+    assert_eq!(<[Light; 4]>::LAYOUT.uniform_layout_error(), None);
+    assert!(<[f32; 8]>::LAYOUT.uniform_layout_error().is_some());
+    assert!(<[Light]>::LAYOUT.uniform_layout_error().is_some());
+    assert_eq!(<[Light]>::LAYOUT.size(), None);
+    assert_eq!(
+        alloc::format!("{}", <[[f32; 4]; 3]>::LAYOUT),
+        "[[f32; 4]; 3]"
+    );
+}
@@ -0,0 +1,149 @@
+//! Types for forcing internal workarounds and behaviors on or off.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+bitflags::bitflags! {
+    /// Internal workarounds and behaviors of wgpu.
+    ///
+    /// wgpu decides which toggles apply from the adapter and driver it runs on.
+    /// A [`TogglesDescriptor`] overrides that decision, for example to find out whether
+    /// a driver-specific misrendering is caused or fixed by a workaround.
+    ///
+    /// Toggles are either device toggles, which are read from the `DeviceDescriptor`,
+    /// or adapter toggles, which are read from the `InstanceDescriptor` and apply to every
+    /// adapter of the instance. [`Self::DEVICE`] contains every device toggle.
+    ///
+    /// These are not part of the WebGPU standard, and are ignored on the WebGPU backend.
+    #[repr(transparent)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct Toggles: u32 {
+        /// Zero-initialize buffers and textures before they are first read, so that
+        /// their previous contents are never observed.
+        ///
+        /// Device toggle, enabled by default. With this toggle disabled, the contents of
+        /// new buffers and textures are undefined.
+        const LAZY_ZERO_INITIALIZATION = 1 << 0;
+        /// Add bounds checks to the shaders of shader modules, so that out-of-bounds
+        /// accesses to buffers, textures and arrays are contained.
+        ///
+        /// Device toggle, enabled by default. With this toggle disabled, shader modules
+        /// are created as if by `Device::create_shader_module_trusted` with
+        /// [`ShaderRuntimeChecks::unchecked()`](crate::ShaderRuntimeChecks::unchecked),
+        /// except that workgroup memory is still zero-initialized unless the module
+        /// disables [`ShaderRuntimeChecks::zero_initialize_workgroup_memory`](crate::ShaderRuntimeChecks::zero_initialize_workgroup_memory).
+        const SHADER_BOUNDS_CHECKS = 1 << 1;
+
+        /// Vulkan: compile a separate SPIR-V module for each entry point of a shader module.
+        ///
+        /// Adapter toggle, enabled by default.
+        const VULKAN_SEPARATE_ENTRY_POINTS = 1 << 8;
+        /// Vulkan: pass a null array of resolve attachments to render passes without color
+        /// attachments, which some Qualcomm drivers run out of memory on otherwise.
+        ///
+        /// Adapter toggle, enabled by default on Qualcomm adapters.
+        const VULKAN_EMPTY_RESOLVE_ATTACHMENT_LISTS = 1 << 9;
+        /// Vulkan: split buffer clears of 4096 bytes or more into ranges with an offset
+        /// aligned to 16 bytes, which some NVIDIA drivers fill the wrong range for otherwise.
+        ///
+        /// Adapter toggle, enabled by default on NVIDIA adapters.
+        const VULKAN_ALIGNED_FILL_BUFFER = 1 << 10;
+
+        /// OpenGL: clear sRGB textures by drawing with a shader, which some Intel
+        /// drivers of Mesa clear with the wrong color otherwise.
+        ///
+        /// Adapter toggle, enabled by default on Skylake derivatives running Mesa's i915 driver.
+        const GL_MESA_I915_SRGB_SHADER_CLEAR = 1 << 16;
+
+        /// DX12: don't reuse CPU descriptors once they have been copied to a GPU heap,
+        /// which WARP still reads from afterwards.
+        ///
+        /// Adapter toggle, enabled by default on software adapters.
+        const DX12_AVOID_CPU_DESCRIPTOR_OVERWRITES = 1 << 24;
+    }
+}
+
+impl Toggles {
+    /// The device toggles, which are read from the `DeviceDescriptor`.
+    pub const DEVICE: Self = Self::LAZY_ZERO_INITIALIZATION.union(Self::SHADER_BOUNDS_CHECKS);
+}
+
+/// Toggles to force on or off, overriding the toggles wgpu would otherwise apply.
+///
+/// Toggles contained in both fields are forced off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TogglesDescriptor {
+    /// Toggles to apply even if wgpu wouldn't.
+    pub force_enabled: Toggles,
+    /// Toggles not to apply even if wgpu would.
+    pub force_disabled: Toggles,
+}
+
+impl TogglesDescriptor {
+    /// Returns the toggles which apply, given the toggles wgpu would apply by default.
+    #[must_use]
+    pub fn apply(&self, defaults: Toggles) -> Toggles {
+        (defaults | self.force_enabled) - self.force_disabled
+    }
+
+    /// Takes the given toggles, modifies them based on the environment variables, and returns the result.
+    ///
+    /// `WGPU_FORCE_ENABLED_TOGGLES` and `WGPU_FORCE_DISABLED_TOGGLES` are added to
+    /// [`Self::force_enabled`] and [`Self::force_disabled`] respectively. They list toggle
+    /// names separated by `|`, for example `WGPU_FORCE_DISABLED_TOGGLES="VULKAN_SEPARATE_ENTRY_POINTS"`.
+    /// Variables which don't parse are ignored with a warning.
+    #[must_use]
+    pub fn with_env(self) -> Self {
+        fn env(key: &str) -> Toggles {
+            let Some(value) = crate::env::var(key) else {
+                return Toggles::empty();
+            };
+            bitflags::parser::from_str(&value).unwrap_or_else(|error| {
+                log::warn!("Ignoring {key}={value:?}: {error}");
+                Toggles::empty()
+            })
+        }
+
+        Self {
+            force_enabled: self.force_enabled | env("WGPU_FORCE_ENABLED_TOGGLES"),
+            force_disabled: self.force_disabled | env("WGPU_FORCE_DISABLED_TOGGLES"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        let desc = TogglesDescriptor {
+            force_enabled: Toggles::VULKAN_SEPARATE_ENTRY_POINTS
+                | Toggles::VULKAN_ALIGNED_FILL_BUFFER,
+            force_disabled: Toggles::LAZY_ZERO_INITIALIZATION | Toggles::VULKAN_ALIGNED_FILL_BUFFER,
+        };
+        assert_eq!(
+            desc.apply(Toggles::LAZY_ZERO_INITIALIZATION | Toggles::SHADER_BOUNDS_CHECKS),
+            Toggles::SHADER_BOUNDS_CHECKS | Toggles::VULKAN_SEPARATE_ENTRY_POINTS
+        );
+        assert_eq!(
+            TogglesDescriptor::default().apply(Toggles::DEVICE),
+            Toggles::DEVICE
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            bitflags::parser::from_str::<Toggles>(
+                "LAZY_ZERO_INITIALIZATION | DX12_AVOID_CPU_DESCRIPTOR_OVERWRITES"
+            )
+            .unwrap(),
+            Toggles::LAZY_ZERO_INITIALIZATION | Toggles::DX12_AVOID_CPU_DESCRIPTOR_OVERWRITES
+        );
+        assert!(bitflags::parser::from_str::<Toggles>("NOT_A_TOGGLE").is_err());
+    }
+}
@@ -30,14 +30,15 @@ fn main() {
             all(target_vendor = "apple", feature = "angle")
         ) },
         noop: { feature = "noop" },
+        cpu: { feature = "cpu" },
 
         wgpu_core: {
             any(
                 // On native, wgpu_core is currently always enabled, even if there's no backend enabled at all.
                 native,
                 // `wgpu_core` is implied if any backend other than WebGPU is enabled.
-                // (this is redundant except for `gles` and `noop`)
-                webgl, dx12, metal, vulkan, gles, noop
+                // (this is redundant except for `gles`, `noop` and `cpu`)
+                webgl, dx12, metal, vulkan, gles, noop, cpu
             )
         },
 
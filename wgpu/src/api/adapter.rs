@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::future::Future;
 #[cfg(wgpu_core)]
 use core::ops::Deref;
@@ -58,6 +59,7 @@ impl Adapter {
         &self,
         desc: &DeviceDescriptor<'_>,
     ) -> impl Future<Output = Result<(Device, Queue), RequestDeviceError>> + WasmNotSend {
+        api_span!("Adapter::request_device", label = desc.label);
         let device = self.inner.request_device(desc);
         async move {
             device
@@ -66,6 +68,47 @@ impl Adapter {
         }
     }
 
+    /// Requests a connection to a physical device, creating a logical device along with
+    /// additional queues.
+    ///
+    /// Returns the [`Device`] and its main [`Queue`], like [`Adapter::request_device`],
+    /// followed by one additional [`Queue`] for each of `queues`, in order. Work
+    /// submitted to different queues may run in parallel: use
+    /// [`Device::create_command_encoder_for_queue`] to record commands for an additional
+    /// queue, and [`Queue::wait_for_submission`] to order work across queues.
+    ///
+    /// Requesting any additional queue requires [`Features::EXPERIMENTAL_MULTI_QUEUE`]
+    /// in `desc.required_features`.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`Adapter::request_device`].
+    pub fn request_device_with_queues(
+        &self,
+        desc: &DeviceDescriptor<'_>,
+        queues: &[QueueDescriptor<'_>],
+    ) -> impl Future<Output = Result<(Device, Queue, Vec<Queue>), RequestDeviceError>> + WasmNotSend
+    {
+        api_span!(
+            "Adapter::request_device_with_queues",
+            label = desc.label,
+            queues = queues.len()
+        );
+        let device = self.inner.request_device_with_queues(desc, queues);
+        async move {
+            device.await.map(|(device, queue, additional_queues)| {
+                (
+                    Device { inner: device },
+                    Queue { inner: queue },
+                    additional_queues
+                        .into_iter()
+                        .map(|queue| Queue { inner: queue })
+                        .collect(),
+                )
+            })
+        }
+    }
+
     /// Create a wgpu [`Device`] and [`Queue`] from a wgpu-hal `OpenDevice`
     ///
     /// # Safety
@@ -150,6 +193,15 @@ impl Adapter {
         self.inner.features()
     }
 
+    /// The adapter toggles which apply to this adapter and the devices created from it.
+    ///
+    /// These are the workarounds wgpu chose for this adapter and its driver, with the
+    /// toggles of [`InstanceDescriptor::toggles`] forced on or off. Always empty on the
+    /// WebGPU backend.
+    pub fn applied_workarounds(&self) -> Toggles {
+        self.inner.applied_workarounds()
+    }
+
     /// The best limits which can be used to create devices on this adapter.
     pub fn limits(&self) -> Limits {
         self.inner.limits()
@@ -197,4 +249,17 @@ impl Adapter {
     pub fn get_presentation_timestamp(&self) -> PresentationTimestamp {
         self.inner.get_presentation_timestamp()
     }
+
+    /// Returns the current memory usage and budget of the adapter, as reported by the
+    /// operating system.
+    ///
+    /// Returns `None` if the backend or adapter can't report it, which is always the
+    /// case on WebGPU and OpenGL. See [`MemoryInfo`] for how it is queried on other
+    /// backends.
+    ///
+    /// Usage is process-wide: it includes memory used by all devices created from this
+    /// adapter, and may include memory allocated outside of wgpu.
+    pub fn memory_info(&self) -> Option<MemoryInfo> {
+        self.inner.memory_info()
+    }
 }
@@ -81,6 +81,11 @@ pub enum BindingResource<'a> {
     ///   built using `build_acceleration_structures` a validation error is generated otherwise this is a part of the
     ///   safety section of `build_acceleration_structures_unsafe_tlas` and so undefined behavior occurs.
     AccelerationStructure(&'a Tlas),
+    /// Binding is backed by an external texture.
+    ///
+    /// Corresponds to [`wgt::BindingType::ExternalTexture`] with [`BindGroupLayoutEntry::count`]
+    /// set to None.
+    ExternalTexture(&'a ExternalTexture),
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(BindingResource<'_>: Send, Sync);
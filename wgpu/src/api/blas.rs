@@ -6,7 +6,7 @@ use alloc::{boxed::Box, vec::Vec};
 use wgt::{WasmNotSend, WasmNotSendSync};
 
 use crate::dispatch;
-use crate::{Buffer, Label};
+use crate::{Buffer, BufferAddress, Label};
 
 /// Descriptor for the size defining attributes of a triangle geometry, for a bottom level acceleration structure.
 pub type BlasTriangleGeometrySizeDescriptor = wgt::BlasTriangleGeometrySizeDescriptor;
@@ -231,7 +231,8 @@ pub struct ContextBlasBuildEntry<'a> {
     pub(crate) geometries: ContextBlasGeometries<'a>,
 }
 
-/// Error occurred when trying to asynchronously prepare a blas for compaction.
+/// Error occurred when trying to asynchronously prepare a blas for compaction, or to
+/// serialize it.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct BlasAsyncError;
 static_assertions::assert_impl_all!(BlasAsyncError: Send, Sync);
@@ -240,7 +241,7 @@ impl core::fmt::Display for BlasAsyncError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
-            "Error occurred when trying to asynchronously prepare a blas for compaction"
+            "Error occurred when trying to asynchronously prepare a blas for compaction or serialize it"
         )
     }
 }
@@ -275,4 +276,43 @@ impl Blas {
     pub fn ready_for_compaction(&self) -> bool {
         self.inner.ready_for_compaction()
     }
+
+    /// Returns the size in bytes this BLAS will take up once compacted by
+    /// [`Queue::compact_blas`], or `None` if it is not [ready for compaction].
+    ///
+    /// This allows comparing the compacted size against the current size before deciding
+    /// whether compacting is worth it.
+    ///
+    /// [`Queue::compact_blas`]: crate::Queue::compact_blas
+    /// [ready for compaction]: Blas::ready_for_compaction
+    pub fn compacted_size(&self) -> Option<BufferAddress> {
+        self.inner.compacted_size()
+    }
+
+    /// Asynchronously serializes this BLAS. The callback is called with the serialized data,
+    /// which [`Device::create_blas_from_serialized`] can turn back into a BLAS, possibly in a
+    /// later run of the application.
+    ///
+    /// The BLAS must have been built. Serialized data can only be deserialized by a
+    /// compatible driver, which can be checked with [`Device::is_serialized_blas_compatible`].
+    /// To store a compacted BLAS, serialize it after [compacting] it.
+    ///
+    /// Requires [`Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION`].
+    ///
+    /// ### Interaction with other functions
+    /// Serialization first reads back the serialized size of the BLAS, then the serialized
+    /// data. Each step is submitted with the next `queue.submit(..)`, and finishes when the
+    /// device is polled after that submission is done, so the callback is only called after
+    /// two submissions. Submitting an empty list of command buffers is enough.
+    ///
+    /// [`Device::create_blas_from_serialized`]: crate::Device::create_blas_from_serialized
+    /// [`Device::is_serialized_blas_compatible`]: crate::Device::is_serialized_blas_compatible
+    /// [compacting]: crate::Queue::compact_blas
+    /// [`Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION`]: wgt::Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION
+    pub fn serialize(
+        &self,
+        callback: impl FnOnce(Result<Vec<u8>, BlasAsyncError>) + WasmNotSend + 'static,
+    ) {
+        self.inner.serialize(Box::new(callback));
+    }
 }
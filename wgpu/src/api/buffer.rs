@@ -1,4 +1,6 @@
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
+#[cfg(feature = "async")]
+use core::future::Future;
 use core::{
     error, fmt,
     ops::{Bound, Deref, DerefMut, Range, RangeBounds},
@@ -270,12 +272,14 @@ impl Buffer {
     /// This terminates the effect of all previous [`map_async()`](Self::map_async) operations and
     /// makes the buffer available for use by the GPU again.
     pub fn unmap(&self) {
+        api_span!("Buffer::unmap", size = self.size);
         self.map_context.lock().reset();
         self.inner.unmap();
     }
 
     /// Destroy the associated native resources as soon as possible.
     pub fn destroy(&self) {
+        api_span!("Buffer::destroy", size = self.size);
         self.inner.destroy();
     }
 
@@ -482,6 +486,7 @@ impl<'a> BufferSlice<'a> {
         mode: MapMode,
         callback: impl FnOnce(Result<(), BufferAsyncError>) + WasmNotSend + 'static,
     ) {
+        api_span!("Buffer::map_async", mode = ?mode, offset = self.offset, size = self.size.get());
         let mut mc = self.buffer.map_context.lock();
         assert_eq!(mc.initial_range, 0..0, "Buffer is already mapped");
         let end = self.offset + self.size.get();
@@ -492,6 +497,29 @@ impl<'a> BufferSlice<'a> {
             .map_async(mode, self.offset..end, Box::new(callback));
     }
 
+    /// Map the buffer to host (CPU) memory, returning a future which resolves once the
+    /// mapping is complete.
+    ///
+    /// This behaves like [`BufferSlice::map_async()`], but reports the result through the
+    /// returned future instead of a callback. As with the callback, the future only
+    /// resolves once the device has been polled, so on native backends
+    /// [`Device::poll()`] must be called elsewhere, e.g. from an event loop or another
+    /// thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`BufferSlice::map_async()`].
+    #[cfg(feature = "async")]
+    pub fn map_async_await(
+        &self,
+        mode: MapMode,
+    ) -> impl Future<Output = Result<(), BufferAsyncError>> + WasmNotSend + use<> {
+        let future = util::CallbackFuture::new();
+        let sender = future.sender();
+        self.map_async(mode, move |result| sender.send(result));
+        future
+    }
+
     /// Gain read-only access to the bytes of a [mapped] [`Buffer`].
     ///
     /// Returns a [`BufferView`] referring to the buffer range represented by
@@ -510,6 +538,11 @@ impl<'a> BufferSlice<'a> {
     ///
     /// [mapped]: Buffer#mapping-buffers
     pub fn get_mapped_range(&self) -> BufferView<'a> {
+        api_span!(
+            "Buffer::get_mapped_range",
+            offset = self.offset,
+            size = self.size.get()
+        );
         let end = self.buffer.map_context.lock().add(self.offset, self.size);
         let range = self.buffer.inner.get_mapped_range(self.offset..end);
         BufferView {
@@ -536,6 +569,11 @@ impl<'a> BufferSlice<'a> {
     ///
     /// [mapped]: Buffer#mapping-buffers
     pub fn get_mapped_range_mut(&self) -> BufferViewMut<'a> {
+        api_span!(
+            "Buffer::get_mapped_range_mut",
+            offset = self.offset,
+            size = self.size.get()
+        );
         let end = self.buffer.map_context.lock().add(self.offset, self.size);
         let range = self.buffer.inner.get_mapped_range(self.offset..end);
         BufferViewMut {
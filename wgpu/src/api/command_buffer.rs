@@ -6,8 +6,11 @@ use crate::*;
 /// queue with [`Queue::submit`]. A `CommandBuffer` is obtained by recording a series of commands to
 /// a [`CommandEncoder`] and then calling [`CommandEncoder::finish`].
 ///
+/// Cloning a `CommandBuffer` returns another handle to the same command buffer. Only command
+/// buffers returned by [`CommandEncoder::finish_reusable`] can be submitted more than once.
+///
 /// Corresponds to [WebGPU `GPUCommandBuffer`](https://gpuweb.github.io/gpuweb/#command-buffer).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CommandBuffer {
     pub(crate) buffer: dispatch::DispatchCommandBuffer,
 }
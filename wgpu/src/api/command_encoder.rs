@@ -53,7 +53,24 @@ static_assertions::assert_impl_all!(TexelCopyTextureInfo<'_>: Send, Sync);
 impl CommandEncoder {
     /// Finishes recording and returns a [`CommandBuffer`] that can be submitted for execution.
     pub fn finish(mut self) -> CommandBuffer {
-        let buffer = self.inner.finish();
+        api_span!("CommandEncoder::finish");
+        let buffer = self.inner.finish(false);
+
+        CommandBuffer { buffer }
+    }
+
+    /// Finishes recording and returns a [`CommandBuffer`] that can be submitted
+    /// for execution any number of times, by submitting clones of it.
+    ///
+    /// Each submission checks again that the resources used by the commands
+    /// are still alive, and initializes their memory if needed. The command
+    /// buffer can't suspend or resume render passes across command buffers,
+    /// nor use surface textures.
+    ///
+    /// Requires [`Features::REUSABLE_COMMAND_BUFFERS`].
+    pub fn finish_reusable(mut self) -> CommandBuffer {
+        api_span!("CommandEncoder::finish_reusable");
+        let buffer = self.inner.finish(true);
 
         CommandBuffer { buffer }
     }
@@ -72,6 +89,7 @@ impl CommandEncoder {
         &'encoder mut self,
         desc: &RenderPassDescriptor<'_>,
     ) -> RenderPass<'encoder> {
+        api_span!("CommandEncoder::begin_render_pass", label = desc.label);
         let rpass = self.inner.begin_render_pass(desc);
         RenderPass {
             inner: rpass,
@@ -93,6 +111,7 @@ impl CommandEncoder {
         &'encoder mut self,
         desc: &ComputePassDescriptor<'_>,
     ) -> ComputePass<'encoder> {
+        api_span!("CommandEncoder::begin_compute_pass", label = desc.label);
         let cpass = self.inner.begin_compute_pass(desc);
         ComputePass {
             inner: cpass,
@@ -115,12 +134,19 @@ impl CommandEncoder {
         destination_offset: BufferAddress,
         copy_size: impl Into<Option<BufferAddress>>,
     ) {
+        let copy_size = copy_size.into();
+        api_span!(
+            "CommandEncoder::copy_buffer_to_buffer",
+            source_offset,
+            destination_offset,
+            size = copy_size
+        );
         self.inner.copy_buffer_to_buffer(
             &source.inner,
             source_offset,
             &destination.inner,
             destination_offset,
-            copy_size.into(),
+            copy_size,
         );
     }
 
@@ -131,6 +157,12 @@ impl CommandEncoder {
         destination: TexelCopyTextureInfo<'_>,
         copy_size: Extent3d,
     ) {
+        api_span!(
+            "CommandEncoder::copy_buffer_to_texture",
+            width = copy_size.width,
+            height = copy_size.height,
+            depth_or_array_layers = copy_size.depth_or_array_layers
+        );
         self.inner
             .copy_buffer_to_texture(source, destination, copy_size);
     }
@@ -142,6 +174,12 @@ impl CommandEncoder {
         destination: TexelCopyBufferInfo<'_>,
         copy_size: Extent3d,
     ) {
+        api_span!(
+            "CommandEncoder::copy_texture_to_buffer",
+            width = copy_size.width,
+            height = copy_size.height,
+            depth_or_array_layers = copy_size.depth_or_array_layers
+        );
         self.inner
             .copy_texture_to_buffer(source, destination, copy_size);
     }
@@ -159,10 +197,46 @@ impl CommandEncoder {
         destination: TexelCopyTextureInfo<'_>,
         copy_size: Extent3d,
     ) {
+        api_span!(
+            "CommandEncoder::copy_texture_to_texture",
+            width = copy_size.width,
+            height = copy_size.height,
+            depth_or_array_layers = copy_size.depth_or_array_layers
+        );
         self.inner
             .copy_texture_to_texture(source, destination, copy_size);
     }
 
+    /// Resolves `regions` of the multisampled `source` view into the single-sampled
+    /// `destination` view.
+    ///
+    /// This lets renderers that only redraw parts of a multisampled target resolve the
+    /// damaged regions, instead of the whole attachment like
+    /// [`RenderPassColorAttachment::resolve_target`] does. Each region is resolved at the
+    /// same position in both views, for each of their array layers.
+    ///
+    /// The source texture needs [`TextureUsages::COPY_SRC`] and the destination texture
+    /// [`TextureUsages::COPY_DST`].
+    ///
+    /// On Metal, the whole subresources of the regions are resolved.
+    ///
+    /// # Panics
+    ///
+    /// - `source` isn't multisampled, or `destination` is
+    /// - The textures of the views don't have the same format, or it can't be resolved
+    /// - The views don't have a single mip level and the same number of array layers
+    /// - A region is out of the bounds of the views
+    pub fn resolve_texture(
+        &mut self,
+        source: &TextureView,
+        destination: &TextureView,
+        regions: &[Rect],
+    ) {
+        api_span!("CommandEncoder::resolve_texture", regions = regions.len());
+        self.inner
+            .resolve_texture(&source.inner, &destination.inner, regions);
+    }
+
     /// Clears texture to zero.
     ///
     /// Note that unlike with clear_buffer, `COPY_DST` usage is not required.
@@ -177,6 +251,7 @@ impl CommandEncoder {
     /// - `CLEAR_TEXTURE` extension not enabled
     /// - Range is out of bounds
     pub fn clear_texture(&mut self, texture: &Texture, subresource_range: &ImageSubresourceRange) {
+        api_span!("CommandEncoder::clear_texture");
         self.inner.clear_texture(&texture.inner, subresource_range);
     }
 
@@ -192,6 +267,7 @@ impl CommandEncoder {
         offset: BufferAddress,
         size: Option<BufferAddress>,
     ) {
+        api_span!("CommandEncoder::clear_buffer", offset, size);
         self.inner.clear_buffer(&buffer.inner, offset, size);
     }
 
@@ -223,6 +299,12 @@ impl CommandEncoder {
         destination: &Buffer,
         destination_offset: BufferAddress,
     ) {
+        api_span!(
+            "CommandEncoder::resolve_query_set",
+            first_query = query_range.start,
+            query_count = query_range.len(),
+            destination_offset
+        );
         self.inner.resolve_query_set(
             &query_set.inner,
             query_range.start,
@@ -282,6 +364,7 @@ impl CommandEncoder {
     /// recorded so far and all before all commands recorded after.
     /// This may depend both on the backend and the driver.
     pub fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32) {
+        api_span!("CommandEncoder::write_timestamp", query_index);
         self.inner.write_timestamp(&query_set.inner, query_index);
     }
 }
@@ -340,6 +423,7 @@ impl CommandEncoder {
         blas: impl IntoIterator<Item = &'a BlasBuildEntry<'a>>,
         tlas: impl IntoIterator<Item = &'a Tlas>,
     ) {
+        api_span!("CommandEncoder::build_acceleration_structures");
         self.inner
             .build_acceleration_structures(&mut blas.into_iter(), &mut tlas.into_iter());
     }
@@ -396,6 +480,7 @@ impl CommandEncoder {
         buffer_transitions: impl Iterator<Item = wgt::BufferTransition<&'a Buffer>>,
         texture_transitions: impl Iterator<Item = wgt::TextureTransition<&'a Texture>>,
     ) {
+        api_span!("CommandEncoder::transition_resources");
         self.inner.transition_resources(
             &mut buffer_transitions.map(|t| wgt::BufferTransition {
                 buffer: &t.buffer.inner,
@@ -409,3 +494,16 @@ impl CommandEncoder {
         );
     }
 }
+
+/// [`Features::EXPERIMENTAL_RAY_TRACING_PIPELINE`] must be enabled on the device in order to call these functions.
+impl CommandEncoder {
+    /// Launches `width * height * depth` invocations of the ray generation stage in the
+    /// shader binding table of `desc`, using its ray tracing pipeline.
+    ///
+    /// The bind groups and push constants of `desc` are bound for the launch only; nothing
+    /// set here carries over to later passes.
+    pub fn trace_rays(&mut self, desc: &TraceRaysDescriptor<'_>) {
+        api_span!("CommandEncoder::trace_rays");
+        self.inner.trace_rays(desc);
+    }
+}
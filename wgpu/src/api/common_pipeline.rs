@@ -19,6 +19,11 @@ pub struct PipelineCompilationOptions<'a> {
     ///
     /// This is required by the WebGPU spec, but may have overhead which can be avoided
     /// for cross-platform applications
+    ///
+    /// This has no effect if the stage's module was created with
+    /// [`ShaderRuntimeChecks::zero_initialize_workgroup_memory`](crate::ShaderRuntimeChecks::zero_initialize_workgroup_memory)
+    /// disabled. Use [`ShaderModule::workgroup_memory()`](crate::ShaderModule::workgroup_memory)
+    /// to find out how much workgroup memory an entry point uses.
     pub zero_initialize_workgroup_memory: bool,
 }
 
@@ -130,10 +130,11 @@ impl ComputePass<'_> {
     }
 }
 
-/// [`Features::PIPELINE_STATISTICS_QUERY`] must be enabled on the device in order to call these functions.
+/// [`Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES`] must be enabled on the device in order to call these functions.
 impl ComputePass<'_> {
     /// Start a pipeline statistics query on this compute pass. It can be ended with
-    /// `end_pipeline_statistics_query`. Pipeline statistics queries may not be nested.
+    /// `end_pipeline_statistics_query`. Pipeline statistics queries may not be nested, and
+    /// may not be started in a pass with a [`ComputePassDescriptor::pipeline_statistics_query`].
     pub fn begin_pipeline_statistics_query(&mut self, query_set: &QuerySet, query_index: u32) {
         self.inner
             .begin_pipeline_statistics_query(&query_set.inner, query_index);
@@ -179,6 +180,10 @@ pub struct ComputePassDescriptor<'a> {
     ///
     /// Requires [`Features::TIMESTAMP_QUERY`] to be enabled.
     pub timestamp_writes: Option<ComputePassTimestampWrites<'a>>,
+    /// Defines where the pipeline statistics of the whole pass will be written to.
+    ///
+    /// Requires [`Features::PIPELINE_STATISTICS_QUERY`] to be enabled.
+    pub pipeline_statistics_query: Option<PassPipelineStatisticsQuery<'a>>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(ComputePassDescriptor<'_>: Send, Sync);
@@ -55,6 +55,30 @@ impl Device {
     /// aspects of constructing a device through `Instance`.
     #[cfg(feature = "noop")]
     pub fn noop(desc: &DeviceDescriptor<'_>) -> (Device, Queue) {
+        Self::noop_with_options(desc, NoopBackendOptions::default())
+    }
+
+    /// Constructs a stub device for testing using [`Backend::Noop`], which records the
+    /// commands submitted to its queue.
+    ///
+    /// The commands are taken with [`Queue::take_recorded_command_buffers`]. Everything
+    /// else behaves as with [`Device::noop`], including the validation of all commands.
+    #[cfg(feature = "noop")]
+    pub fn noop_recording(desc: &DeviceDescriptor<'_>) -> (Device, Queue) {
+        Self::noop_with_options(
+            desc,
+            NoopBackendOptions {
+                record: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[cfg(feature = "noop")]
+    fn noop_with_options(
+        desc: &DeviceDescriptor<'_>,
+        options: NoopBackendOptions,
+    ) -> (Device, Queue) {
         use core::future::Future as _;
         use core::pin::pin;
         use core::task;
@@ -63,7 +87,10 @@ impl Device {
         let instance = Instance::new(&InstanceDescriptor {
             backends: Backends::NOOP,
             backend_options: BackendOptions {
-                noop: NoopBackendOptions { enable: true },
+                noop: NoopBackendOptions {
+                    enable: true,
+                    ..options
+                },
                 ..Default::default()
             },
             ..Default::default()
@@ -93,6 +120,7 @@ impl Device {
     ///
     /// When running on WebGPU, this is a no-op. `Device`s are automatically polled.
     pub fn poll(&self, poll_type: PollType) -> Result<crate::PollStatus, crate::PollError> {
+        api_span!("Device::poll", poll_type = ?poll_type);
         self.inner.poll(poll_type.map_index(|s| s.index))
     }
 
@@ -128,9 +156,37 @@ impl Device {
     /// </div>
     #[must_use]
     pub fn create_shader_module(&self, desc: ShaderModuleDescriptor<'_>) -> ShaderModule {
-        let module = self
-            .inner
-            .create_shader_module(desc, wgt::ShaderRuntimeChecks::checked());
+        api_span!("Device::create_shader_module", label = desc.label);
+        let module = self.inner.create_shader_module(
+            desc,
+            wgt::ShaderRuntimeChecks::checked(),
+            wgt::ShaderOptimization::Default,
+        );
+        ShaderModule { inner: module }
+    }
+
+    /// Creates a shader module, asking the backend compiler to optimize it as requested.
+    ///
+    /// This is otherwise the same as [`create_shader_module`][csm]. See the documentation
+    /// for [`ShaderOptimization`][so] for what each backend does with each level.
+    ///
+    /// [csm]: Self::create_shader_module
+    /// [so]: crate::ShaderOptimization
+    #[must_use]
+    pub fn create_shader_module_with_optimization(
+        &self,
+        desc: ShaderModuleDescriptor<'_>,
+        optimization: crate::ShaderOptimization,
+    ) -> ShaderModule {
+        api_span!(
+            "Device::create_shader_module_with_optimization",
+            label = desc.label
+        );
+        let module = self.inner.create_shader_module(
+            desc,
+            wgt::ShaderRuntimeChecks::checked(),
+            optimization,
+        );
         ShaderModule { inner: module }
     }
 
@@ -175,10 +231,37 @@ impl Device {
         desc: ShaderModuleDescriptor<'_>,
         runtime_checks: crate::ShaderRuntimeChecks,
     ) -> ShaderModule {
-        let module = self.inner.create_shader_module(desc, runtime_checks);
+        api_span!("Device::create_shader_module_trusted", label = desc.label);
+        let module = self.inner.create_shader_module(
+            desc,
+            runtime_checks,
+            crate::ShaderOptimization::Default,
+        );
         ShaderModule { inner: module }
     }
 
+    /// Creates a shader module from a Naga module cached with [`naga::cache::to_bytes`].
+    ///
+    /// This skips parsing the shader, which can take a noticeable part of the startup time of
+    /// programs with many shaders: they can be parsed and validated offline or on the first
+    /// run, and loaded from the cache afterwards. The module is still validated by wgpu, like
+    /// a [`ShaderSource::Naga`] module.
+    ///
+    /// Returns an error, without creating a shader module, if `ir` isn't a module cached by
+    /// the version of Naga which wgpu uses.
+    #[cfg(feature = "naga-ir-cache")]
+    pub fn create_shader_module_from_naga_ir(
+        &self,
+        label: Label<'_>,
+        ir: &[u8],
+    ) -> Result<ShaderModule, naga::cache::CacheError> {
+        let (module, _info) = naga::cache::from_bytes(ir)?;
+        Ok(self.create_shader_module(ShaderModuleDescriptor {
+            label,
+            source: ShaderSource::Naga(alloc::borrow::Cow::Owned(module)),
+        }))
+    }
+
     /// Creates a shader module which will bypass wgpu's shader tooling and validation and be used directly by the backend.
     ///
     /// # Safety
@@ -190,6 +273,7 @@ impl Device {
         &self,
         desc: ShaderModuleDescriptorPassthrough<'_>,
     ) -> ShaderModule {
+        api_span!("Device::create_shader_module_passthrough");
         let module = unsafe { self.inner.create_shader_module_passthrough(&desc) };
         ShaderModule { inner: module }
     }
@@ -197,16 +281,41 @@ impl Device {
     /// Creates an empty [`CommandEncoder`].
     #[must_use]
     pub fn create_command_encoder(&self, desc: &CommandEncoderDescriptor<'_>) -> CommandEncoder {
+        api_span!("Device::create_command_encoder", label = desc.label);
         let encoder = self.inner.create_command_encoder(desc);
         CommandEncoder { inner: encoder }
     }
 
+    /// Creates an empty [`CommandEncoder`] whose command buffers can only be submitted
+    /// to `queue`.
+    ///
+    /// This is needed to submit commands to the additional queues returned by
+    /// [`Adapter::request_device_with_queues`]. Command encoders for a
+    /// [`QueueType::Compute`] queue can't begin render passes, and those for a
+    /// [`QueueType::Transfer`] queue can't begin any passes.
+    #[must_use]
+    pub fn create_command_encoder_for_queue(
+        &self,
+        queue: &Queue,
+        desc: &CommandEncoderDescriptor<'_>,
+    ) -> CommandEncoder {
+        api_span!(
+            "Device::create_command_encoder_for_queue",
+            label = desc.label
+        );
+        let encoder = self
+            .inner
+            .create_command_encoder_for_queue(&queue.inner, desc);
+        CommandEncoder { inner: encoder }
+    }
+
     /// Creates an empty [`RenderBundleEncoder`].
     #[must_use]
     pub fn create_render_bundle_encoder<'a>(
         &self,
         desc: &RenderBundleEncoderDescriptor<'_>,
     ) -> RenderBundleEncoder<'a> {
+        api_span!("Device::create_render_bundle_encoder", label = desc.label);
         let encoder = self.inner.create_render_bundle_encoder(desc);
         RenderBundleEncoder {
             inner: encoder,
@@ -217,6 +326,11 @@ impl Device {
     /// Creates a new [`BindGroup`].
     #[must_use]
     pub fn create_bind_group(&self, desc: &BindGroupDescriptor<'_>) -> BindGroup {
+        api_span!(
+            "Device::create_bind_group",
+            label = desc.label,
+            entries = desc.entries.len()
+        );
         let group = self.inner.create_bind_group(desc);
         BindGroup { inner: group }
     }
@@ -227,6 +341,7 @@ impl Device {
         &self,
         desc: &BindGroupLayoutDescriptor<'_>,
     ) -> BindGroupLayout {
+        api_span!("Device::create_bind_group_layout", label = desc.label);
         let layout = self.inner.create_bind_group_layout(desc);
         BindGroupLayout { inner: layout }
     }
@@ -234,6 +349,7 @@ impl Device {
     /// Creates a [`PipelineLayout`].
     #[must_use]
     pub fn create_pipeline_layout(&self, desc: &PipelineLayoutDescriptor<'_>) -> PipelineLayout {
+        api_span!("Device::create_pipeline_layout", label = desc.label);
         let layout = self.inner.create_pipeline_layout(desc);
         PipelineLayout { inner: layout }
     }
@@ -241,20 +357,101 @@ impl Device {
     /// Creates a [`RenderPipeline`].
     #[must_use]
     pub fn create_render_pipeline(&self, desc: &RenderPipelineDescriptor<'_>) -> RenderPipeline {
+        api_span!("Device::create_render_pipeline", label = desc.label);
         let pipeline = self.inner.create_render_pipeline(desc);
         RenderPipeline { inner: pipeline }
     }
 
+    /// Creates a mesh shader [`RenderPipeline`].
+    ///
+    /// Requires [`Features::EXPERIMENTAL_MESH_SHADER`]. The task and mesh stages can't be
+    /// written in WGSL yet, so their modules usually come from
+    /// [`Device::create_shader_module_passthrough()`].
+    #[must_use]
+    pub fn create_mesh_pipeline(&self, desc: &MeshPipelineDescriptor<'_>) -> RenderPipeline {
+        api_span!("Device::create_mesh_pipeline", label = desc.label);
+        let pipeline = self.inner.create_mesh_pipeline(desc);
+        RenderPipeline { inner: pipeline }
+    }
+
     /// Creates a [`ComputePipeline`].
     #[must_use]
     pub fn create_compute_pipeline(&self, desc: &ComputePipelineDescriptor<'_>) -> ComputePipeline {
+        api_span!("Device::create_compute_pipeline", label = desc.label);
         let pipeline = self.inner.create_compute_pipeline(desc);
         ComputePipeline { inner: pipeline }
     }
 
+    /// Creates a [`RayTracingPipeline`].
+    ///
+    /// Requires [`Features::EXPERIMENTAL_RAY_TRACING_PIPELINE`]. See [`RayTracingStage::module`]
+    /// for the shader modules each stage can use. Only Vulkan is supported.
+    #[must_use]
+    pub fn create_ray_tracing_pipeline(
+        &self,
+        desc: &RayTracingPipelineDescriptor<'_>,
+    ) -> RayTracingPipeline {
+        api_span!("Device::create_ray_tracing_pipeline", label = desc.label);
+        let pipeline = self.inner.create_ray_tracing_pipeline(desc);
+        RayTracingPipeline { inner: pipeline }
+    }
+
+    /// Creates a [`ShaderBindingTable`] holding the handles of the shader groups of
+    /// `desc.pipeline` that `desc` selects.
+    ///
+    /// The table's buffer is created with [`BufferUsages::SHADER_BINDING_TABLE`] and filled
+    /// at creation, with each kind of record aligned as the adapter requires.
+    #[must_use]
+    pub fn create_shader_binding_table(
+        &self,
+        desc: &ShaderBindingTableDescriptor<'_>,
+    ) -> ShaderBindingTable {
+        api_span!("Device::create_shader_binding_table", label = desc.label);
+        use crate::util::DeviceExt as _;
+
+        let (contents, layout) = desc.pipeline.inner.shader_binding_table(desc);
+        let buffer = self.create_buffer_init(&crate::util::BufferInitDescriptor {
+            label: desc.label,
+            contents: &contents,
+            usage: BufferUsages::SHADER_BINDING_TABLE,
+        });
+        ShaderBindingTable { buffer, layout }
+    }
+
+    /// Creates a [`RenderPipeline`], returning a future which resolves to the pipeline.
+    ///
+    /// The pipeline is created when this method is called, as with
+    /// [`Device::create_render_pipeline()`], and the returned future is already resolved.
+    /// This lets code which creates pipelines from async contexts share one code path
+    /// with the other future-returning APIs.
+    #[cfg(feature = "async")]
+    pub fn create_render_pipeline_async(
+        &self,
+        desc: &RenderPipelineDescriptor<'_>,
+    ) -> core::future::Ready<RenderPipeline> {
+        core::future::ready(self.create_render_pipeline(desc))
+    }
+
+    /// Creates a [`ComputePipeline`], returning a future which resolves to the pipeline.
+    ///
+    /// See [`Device::create_render_pipeline_async()`] for when the pipeline is created.
+    #[cfg(feature = "async")]
+    pub fn create_compute_pipeline_async(
+        &self,
+        desc: &ComputePipelineDescriptor<'_>,
+    ) -> core::future::Ready<ComputePipeline> {
+        core::future::ready(self.create_compute_pipeline(desc))
+    }
+
     /// Creates a [`Buffer`].
     #[must_use]
     pub fn create_buffer(&self, desc: &BufferDescriptor<'_>) -> Buffer {
+        api_span!(
+            "Device::create_buffer",
+            label = desc.label,
+            size = desc.size,
+            usage = ?desc.usage
+        );
         let mut map_context = MapContext::new();
         if desc.mapped_at_creation {
             map_context.initial_range = 0..desc.size;
@@ -275,6 +472,15 @@ impl Device {
     /// `desc` specifies the general format of the texture.
     #[must_use]
     pub fn create_texture(&self, desc: &TextureDescriptor<'_>) -> Texture {
+        api_span!(
+            "Device::create_texture",
+            label = desc.label,
+            width = desc.size.width,
+            height = desc.size.height,
+            depth_or_array_layers = desc.size.depth_or_array_layers,
+            mip_level_count = desc.mip_level_count,
+            format = ?desc.format
+        );
         let texture = self.inner.create_texture(desc);
 
         Texture {
@@ -287,6 +493,130 @@ impl Device {
         }
     }
 
+    /// Creates a new [`MemoryHeap`] that textures can be placed in with
+    /// [`Device::create_texture_aliased`].
+    ///
+    /// Requires [`Features::TEXTURE_ALIASING`].
+    #[must_use]
+    pub fn create_memory_heap(&self, desc: &MemoryHeapDescriptor<'_>) -> MemoryHeap {
+        api_span!(
+            "Device::create_memory_heap",
+            label = desc.label,
+            size = desc.size
+        );
+        let heap = self.inner.create_memory_heap(desc);
+        MemoryHeap { inner: heap }
+    }
+
+    /// Returns how much memory a texture created with `desc` takes up in a
+    /// [`MemoryHeap`], and how its offset in the heap must be aligned.
+    ///
+    /// Returns `None` if `desc` is invalid. The error is reported to the
+    /// device's error handler.
+    ///
+    /// Requires [`Features::TEXTURE_ALIASING`].
+    pub fn texture_memory_requirements(
+        &self,
+        desc: &TextureDescriptor<'_>,
+    ) -> Option<TextureMemoryRequirements> {
+        self.inner.texture_memory_requirements(desc)
+    }
+
+    /// Creates a new [`Texture`] in the given region of a [`MemoryHeap`].
+    ///
+    /// The texture aliases every other texture whose region overlaps its own.
+    /// Aliased textures can't be used in the same pass or copy, and a texture
+    /// starts with undefined contents whenever it takes the memory over from
+    /// another texture. See [`MemoryHeap`] for details.
+    ///
+    /// `region.offset` must be a multiple of the alignment returned by
+    /// [`Device::texture_memory_requirements`], and the texture must fit in the
+    /// heap. Transient textures can't be placed in a heap.
+    ///
+    /// Requires [`Features::TEXTURE_ALIASING`].
+    #[must_use]
+    pub fn create_texture_aliased(
+        &self,
+        region: &HeapRegion<'_>,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        api_span!(
+            "Device::create_texture_aliased",
+            label = desc.label,
+            offset = region.offset,
+            width = desc.size.width,
+            height = desc.size.height,
+            format = ?desc.format
+        );
+        let texture = self
+            .inner
+            .create_texture_aliased(&region.heap.inner, region.offset, desc);
+
+        Texture {
+            inner: texture,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+        }
+    }
+
+    /// Creates a new sparse [`Buffer`], which has no memory of its own.
+    ///
+    /// Memory from [`MemoryHeap`]s is bound to it in pages of [`SPARSE_PAGE_SIZE`]
+    /// bytes with [`Queue::bind_sparse_memory`]; the buffer spans `desc.size`
+    /// rounded up to a whole number of pages. Sparse buffers can't be mapped.
+    ///
+    /// Requires [`Features::SPARSE_BINDING`].
+    #[must_use]
+    pub fn create_sparse_buffer(&self, desc: &BufferDescriptor<'_>) -> Buffer {
+        api_span!(
+            "Device::create_sparse_buffer",
+            label = desc.label,
+            size = desc.size,
+            usage = ?desc.usage
+        );
+        let buffer = self.inner.create_sparse_buffer(desc);
+
+        Buffer {
+            inner: buffer,
+            map_context: Arc::new(Mutex::new(MapContext::new())),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    /// Creates a new sparse [`Texture`], which has no memory of its own.
+    ///
+    /// Memory from [`MemoryHeap`]s is bound to it in tiles of
+    /// [`TextureFormat::sparse_tile_size`] texels with [`Queue::bind_sparse_memory`].
+    /// Sparse textures are 2D and single-sampled, and each of their mip levels must
+    /// be made of whole tiles.
+    ///
+    /// Requires [`Features::SPARSE_BINDING`].
+    #[must_use]
+    pub fn create_sparse_texture(&self, desc: &TextureDescriptor<'_>) -> Texture {
+        api_span!(
+            "Device::create_sparse_texture",
+            label = desc.label,
+            width = desc.size.width,
+            height = desc.size.height,
+            depth_or_array_layers = desc.size.depth_or_array_layers,
+            format = ?desc.format
+        );
+        let texture = self.inner.create_sparse_texture(desc);
+
+        Texture {
+            inner: texture,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+        }
+    }
+
     /// Creates a [`Texture`] from a wgpu-hal Texture.
     ///
     /// # Safety
@@ -317,6 +647,49 @@ impl Device {
         }
     }
 
+    /// Creates a [`Texture`] that aliases `plane` of an `IOSurface`.
+    ///
+    /// This lets textures be shared with AVFoundation, CoreVideo and other processes
+    /// without copying. Unlike [`Device::create_texture_from_hal`], the descriptor is
+    /// validated, and the texture's contents are considered initialized.
+    ///
+    /// `desc` must describe a 2D texture with a single mip level, array layer and
+    /// sample, whose size matches that of the plane. Planes are numbered from 0; a
+    /// surface that isn't planar has a single plane covering the whole surface.
+    ///
+    /// # Safety
+    ///
+    /// - `io_surface` must be a valid `IOSurfaceRef`. The texture retains it, so the
+    ///   caller may release its own reference afterwards.
+    /// - `desc.format` must be compatible with the pixel format of `plane`.
+    ///
+    /// # Panics
+    ///
+    /// - If this device is not backed by `wgpu-core`.
+    #[cfg(metal)]
+    #[must_use]
+    pub unsafe fn create_texture_from_io_surface(
+        &self,
+        io_surface: *mut core::ffi::c_void,
+        plane: u32,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        let texture = unsafe {
+            let core_device = self.inner.as_core();
+            core_device
+                .context
+                .create_texture_from_io_surface(io_surface, plane, core_device, desc)
+        };
+        Texture {
+            inner: texture.into(),
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+        }
+    }
+
     /// Creates a [`Buffer`] from a wgpu-hal Buffer.
     ///
     /// # Safety
@@ -357,6 +730,7 @@ impl Device {
     /// `desc` specifies the behavior of the sampler.
     #[must_use]
     pub fn create_sampler(&self, desc: &SamplerDescriptor<'_>) -> Sampler {
+        api_span!("Device::create_sampler", label = desc.label);
         let sampler = self.inner.create_sampler(desc);
         Sampler { inner: sampler }
     }
@@ -364,10 +738,65 @@ impl Device {
     /// Creates a new [`QuerySet`].
     #[must_use]
     pub fn create_query_set(&self, desc: &QuerySetDescriptor<'_>) -> QuerySet {
+        api_span!(
+            "Device::create_query_set",
+            label = desc.label,
+            ty = ?desc.ty,
+            count = desc.count
+        );
         let query_set = self.inner.create_query_set(desc);
         QuerySet { inner: query_set }
     }
 
+    /// Creates a new [`SharedFence`], whose value starts at zero.
+    ///
+    /// Requires [`Features::EXPERIMENTAL_SHARED_FENCE`].
+    #[must_use]
+    pub fn create_shared_fence(&self, desc: &SharedFenceDescriptor<'_>) -> SharedFence {
+        api_span!("Device::create_shared_fence", label = desc.label);
+        let fence = self.inner.create_shared_fence(desc);
+        SharedFence { inner: fence }
+    }
+
+    /// Creates a [`SharedFence`] from a fence created outside of `wgpu`.
+    ///
+    /// Requires [`Features::EXPERIMENTAL_SHARED_FENCE`].
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid handle of the kind used by this device's
+    ///   backend, as described by [`SharedFenceHandle`].
+    /// - The fence must have been created on the same physical adapter as this
+    ///   device.
+    /// - The fence's value must only ever increase.
+    #[must_use]
+    pub unsafe fn import_shared_fence(
+        &self,
+        handle: SharedFenceHandle,
+        desc: &SharedFenceDescriptor<'_>,
+    ) -> SharedFence {
+        api_span!("Device::import_shared_fence", label = desc.label);
+        let fence = unsafe { self.inner.import_shared_fence(handle, desc) };
+        SharedFence { inner: fence }
+    }
+
+    /// Imports the current frame of `source` as an [`ExternalTexture`].
+    ///
+    /// The external texture expires once the browser has presented a newer
+    /// frame of the video, so it should be imported again every frame.
+    ///
+    /// # Panics
+    ///
+    /// - The device is not from the `webgpu` backend.
+    #[cfg(webgpu)]
+    #[must_use]
+    pub fn import_external_texture(&self, source: &web_sys::HtmlVideoElement) -> ExternalTexture {
+        let external_texture = self.inner.import_external_texture(source);
+        ExternalTexture {
+            inner: external_texture,
+        }
+    }
+
     /// Set a callback for errors that are not handled in error scopes.
     pub fn on_uncaptured_error(&self, handler: Box<dyn UncapturedErrorHandler>) {
         self.inner.on_uncaptured_error(handler)
@@ -375,19 +804,37 @@ impl Device {
 
     /// Push an error scope.
     pub fn push_error_scope(&self, filter: ErrorFilter) {
+        api_span!("Device::push_error_scope", filter = ?filter);
         self.inner.push_error_scope(filter)
     }
 
     /// Pop an error scope.
     pub fn pop_error_scope(&self) -> impl Future<Output = Option<Error>> + WasmNotSend {
+        api_span!("Device::pop_error_scope");
         self.inner.pop_error_scope()
     }
 
+    /// Pop an error scope, returning its error without going through a future.
+    ///
+    /// `wgpu-core` reports errors synchronously, so the result of the scope is known
+    /// as soon as it is popped. This lets code which wraps calls in error scopes check
+    /// for errors without an executor.
+    ///
+    /// # Panics
+    ///
+    /// - If this device doesn't use `wgpu-core`, i.e. on the WebGPU or a custom backend.
+    /// - If there is no error scope to pop.
+    #[cfg(wgpu_core)]
+    pub fn pop_error_scope_blocking(&self) -> Option<Error> {
+        self.inner.as_core().pop_error_scope_blocking()
+    }
+
     /// Starts a capture in the attached graphics debugger.
     ///
     /// This behaves differently depending on which graphics debugger is attached:
     ///
     /// - Renderdoc: Calls [`StartFrameCapture(device, NULL)`][rd].
+    /// - PIX: Calls [`IDXGraphicsAnalysis::BeginCapture`][pix].
     /// - Xcode: Creates a capture with [`MTLCaptureManager`][xcode].
     /// - None: No action is taken.
     ///
@@ -408,8 +855,10 @@ impl Device {
     ///   before stopping the capture.
     ///
     /// [rd]: https://renderdoc.org/docs/in_application_api.html#_CPPv417StartFrameCapture23RENDERDOC_DevicePointer22RENDERDOC_WindowHandle
+    /// [pix]: https://devblogs.microsoft.com/pix/programmatic-capture/
     /// [xcode]: https://developer.apple.com/documentation/metal/mtlcapturemanager
     #[doc(alias = "start_renderdoc_capture")]
+    #[doc(alias = "start_pix_capture")]
     #[doc(alias = "start_xcode_capture")]
     pub unsafe fn start_graphics_debugger_capture(&self) {
         unsafe { self.inner.start_graphics_debugger_capture() }
@@ -420,6 +869,7 @@ impl Device {
     /// This behaves differently depending on which graphics debugger is attached:
     ///
     /// - Renderdoc: Calls [`EndFrameCapture(device, NULL)`][rd].
+    /// - PIX: Calls [`IDXGraphicsAnalysis::EndCapture`][pix].
     /// - Xcode: Stops the capture with [`MTLCaptureManager`][xcode].
     /// - None: No action is taken.
     ///
@@ -437,13 +887,45 @@ impl Device {
     ///   for that work to finish before stopping the capture.
     ///
     /// [rd]: https://renderdoc.org/docs/in_application_api.html#_CPPv415EndFrameCapture23RENDERDOC_DevicePointer22RENDERDOC_WindowHandle
+    /// [pix]: https://devblogs.microsoft.com/pix/programmatic-capture/
     /// [xcode]: https://developer.apple.com/documentation/metal/mtlcapturemanager
     #[doc(alias = "stop_renderdoc_capture")]
+    #[doc(alias = "stop_pix_capture")]
     #[doc(alias = "stop_xcode_capture")]
     pub unsafe fn stop_graphics_debugger_capture(&self) {
         unsafe { self.inner.stop_graphics_debugger_capture() }
     }
 
+    /// Start recording the API calls made on this device into a trace in the directory
+    /// `path`, replacing the trace in progress, if any.
+    ///
+    /// The directory holds a versioned `trace.ron` file along with the shaders and data
+    /// it refers to, and can be replayed on any backend with `wgpu-player`. Resources
+    /// created before the trace started aren't recorded, so for the trace to be
+    /// replayable it should be started right after creating the device, or the device
+    /// should be created with [`Trace::Directory`](wgt::Trace::Directory) instead.
+    ///
+    /// Returns an error of kind [`Unsupported`](std::io::ErrorKind::Unsupported) on the
+    /// WebGPU backend.
+    #[cfg(feature = "trace")]
+    pub fn start_trace(&self, path: impl Into<std::path::PathBuf>) -> std::io::Result<()> {
+        #[cfg(wgpu_core)]
+        if let Some(device) = self.inner.as_core_opt() {
+            return device.context.device_start_trace(device, path.into());
+        }
+        let _ = path;
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    /// Finish the trace started with [`Device::start_trace`] or at device creation, if any.
+    #[cfg(feature = "trace")]
+    pub fn stop_trace(&self) {
+        #[cfg(wgpu_core)]
+        if let Some(device) = self.inner.as_core_opt() {
+            device.context.device_stop_trace(device);
+        }
+    }
+
     /// Query internal counters from the native backend for debugging purposes.
     ///
     /// Some backends may not set all counters, or may not set any counter at all.
@@ -465,6 +947,65 @@ impl Device {
         self.inner.generate_allocator_report()
     }
 
+    /// Release memory that this device holds on to without using it, for example at
+    /// quiet moments of a long-running application.
+    ///
+    /// This releases memory blocks of the backend's allocator that no resource is
+    /// allocated in anymore, and command encoders that are pooled for reuse. Live
+    /// resources are not moved to defragment memory, so memory blocks that are only
+    /// partially used are kept. Only the Vulkan backend releases memory blocks, see
+    /// [`TrimMemoryReport::freed_memory_blocks`].
+    /// Later allocations may be slower, as released memory has to be allocated again.
+    ///
+    /// Returns what was released. Does nothing on WebGPU.
+    pub fn trim_memory(&self) -> TrimMemoryReport {
+        api_span!("Device::trim_memory");
+        self.inner.trim_memory()
+    }
+
+    /// Generate a report of the memory used by this device's buffers and textures, for
+    /// tracking down leaks.
+    ///
+    /// The report contains the number and total size of live buffers and textures. If
+    /// `include_resources` is true, it also lists every one of them with its label, size
+    /// and the kind of memory it is allocated in, largest first. Destroyed resources are
+    /// not included. Texture sizes are estimates, see [`ResourceMemoryReport::size`].
+    ///
+    /// Returns `None` on WebGPU, which doesn't expose the resources of a device.
+    #[must_use]
+    pub fn generate_memory_report(&self, include_resources: bool) -> Option<MemoryReport> {
+        api_span!("Device::generate_memory_report", include_resources);
+        self.inner.generate_memory_report(include_resources)
+    }
+
+    /// Enable or disable logging of the barriers that `wgpu` inserts between uses of
+    /// buffers and textures, to track down unexpected synchronization.
+    ///
+    /// While enabled, every transition inserted at the end of a compute or render pass,
+    /// by [`CommandEncoder::transition_resources`], or when a command buffer is
+    /// submitted is logged at the `info` level, with the resource, its old and new
+    /// usages, and where the barrier was inserted. The number of barriers of each pass
+    /// is also added to the report returned by
+    /// [`take_barrier_report()`](Self::take_barrier_report). Barriers of copies and
+    /// clears recorded outside of passes are not logged.
+    ///
+    /// Logging is disabled by default and has a cost, so it should only be enabled
+    /// while debugging. Does nothing on WebGPU.
+    pub fn set_barrier_logging(&self, enabled: bool) {
+        api_span!("Device::set_barrier_logging", enabled);
+        self.inner.set_barrier_logging(enabled)
+    }
+
+    /// Take the barrier counts of every pass recorded since the last call, while
+    /// barrier logging was enabled with [`set_barrier_logging()`](Self::set_barrier_logging).
+    ///
+    /// Passes that needed no barriers are not included. Returns `None` on WebGPU.
+    #[must_use]
+    pub fn take_barrier_report(&self) -> Option<BarrierReport> {
+        api_span!("Device::take_barrier_report");
+        self.inner.take_barrier_report()
+    }
+
     /// Get the [`wgpu_hal`] device from this `Device`.
     ///
     /// Find the Api struct corresponding to the active backend in [`wgpu_hal::api`],
@@ -497,10 +1038,15 @@ impl Device {
 
     /// Destroy this device.
     pub fn destroy(&self) {
+        api_span!("Device::destroy");
         self.inner.destroy()
     }
 
     /// Set a DeviceLostCallback on this device.
+    ///
+    /// The callback is given why the device was lost and a message describing it. When
+    /// the backend collected diagnostics about the GPU fault that lost the device, they
+    /// are appended to the message; see [`InstanceFlags::DEVICE_FAULT_DIAGNOSTICS`].
     pub fn set_device_lost_callback(
         &self,
         callback: impl Fn(DeviceLostReason, String) + Send + 'static,
@@ -508,6 +1054,23 @@ impl Device {
         self.inner.set_device_lost_callback(Box::new(callback))
     }
 
+    /// Set a callback to be called when the memory budget of the device's adapter
+    /// changes, replacing any previous callback.
+    ///
+    /// The budget is checked whenever the device is polled, with [`Device::poll()`] or
+    /// [`Instance::poll_all()`](crate::Instance::poll_all), and the callback is called
+    /// from there with the new [`MemoryInfo`]. Changes in usage alone don't trigger it;
+    /// use [`Adapter::memory_info()`](crate::Adapter::memory_info) to query usage.
+    ///
+    /// The callback is never called if [`Adapter::memory_info()`](crate::Adapter::memory_info)
+    /// returns `None`.
+    pub fn on_memory_budget_change(
+        &self,
+        callback: impl Fn(MemoryInfo) + WasmNotSendSync + 'static,
+    ) {
+        self.inner.set_memory_budget_callback(Arc::new(callback))
+    }
+
     /// Create a [`PipelineCache`] with initial data
     ///
     /// This can be passed to [`Device::create_compute_pipeline`]
@@ -550,6 +1113,7 @@ impl Device {
         &self,
         desc: &PipelineCacheDescriptor<'_>,
     ) -> PipelineCache {
+        api_span!("Device::create_pipeline_cache", label = desc.label);
         let cache = unsafe { self.inner.create_pipeline_cache(desc) };
         PipelineCache { inner: cache }
     }
@@ -579,6 +1143,7 @@ impl Device {
         desc: &CreateBlasDescriptor<'_>,
         sizes: BlasGeometrySizeDescriptors,
     ) -> Blas {
+        api_span!("Device::create_blas", label = desc.label);
         let (handle, blas) = self.inner.create_blas(desc, sizes);
 
         Blas {
@@ -587,6 +1152,56 @@ impl Device {
         }
     }
 
+    /// Create a bottom level acceleration structure from data serialized by [`Blas::serialize`].
+    /// - `desc` and `sizes`: The descriptor and geometry sizes the serialized BLAS was created
+    ///   with.
+    /// - `data`: The serialized data.
+    ///
+    /// The returned BLAS is already built, so it can be used in TLAS builds right away.
+    /// [`AccelerationStructureFlags::ALLOW_COMPACTION`] is ignored, as a deserialized BLAS
+    /// can't be compacted.
+    ///
+    /// # Validation
+    /// If any of the following is not satisfied a validation error is generated
+    ///
+    /// The device ***must*** have [`Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION`]
+    /// enabled, `data` must be compatible with the device (see
+    /// [`Device::is_serialized_blas_compatible`]), and `desc` and `sizes` must be valid for
+    /// [`Device::create_blas`].
+    ///
+    /// [`AccelerationStructureFlags::ALLOW_COMPACTION`]: wgt::AccelerationStructureFlags::ALLOW_COMPACTION
+    /// [`Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION`]: wgt::Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION
+    #[must_use]
+    pub fn create_blas_from_serialized(
+        &self,
+        desc: &CreateBlasDescriptor<'_>,
+        sizes: BlasGeometrySizeDescriptors,
+        data: &[u8],
+    ) -> Blas {
+        api_span!(
+            "Device::create_blas_from_serialized",
+            label = desc.label,
+            size = data.len()
+        );
+        let (handle, blas) = self.inner.create_blas_from_serialized(desc, sizes, data);
+
+        Blas {
+            inner: blas,
+            handle,
+        }
+    }
+
+    /// Returns whether data serialized by [`Blas::serialize`], possibly on another device or
+    /// driver, can be deserialized with [`Device::create_blas_from_serialized`] on this device.
+    ///
+    /// Always returns `false` if the device doesn't have
+    /// [`Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION`] enabled.
+    ///
+    /// [`Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION`]: wgt::Features::EXPERIMENTAL_ACCELERATION_STRUCTURE_SERIALIZATION
+    pub fn is_serialized_blas_compatible(&self, data: &[u8]) -> bool {
+        self.inner.is_serialized_blas_compatible(data)
+    }
+
     /// Create a top level acceleration structure, used for ray tracing.
     /// - `desc`: The descriptor of the acceleration structure.
     ///
@@ -598,6 +1213,11 @@ impl Device {
     /// [`Features::EXPERIMENTAL_RAY_QUERY`]: wgt::Features::EXPERIMENTAL_RAY_QUERY
     #[must_use]
     pub fn create_tlas(&self, desc: &CreateTlasDescriptor<'_>) -> Tlas {
+        api_span!(
+            "Device::create_tlas",
+            label = desc.label,
+            max_instances = desc.max_instances
+        );
         let tlas = self.inner.create_tlas(desc);
 
         Tlas {
@@ -625,6 +1245,10 @@ pub(crate) enum RequestDeviceErrorKind {
     /// (This is currently never used by the webgl backend, but it could be.)
     #[cfg(webgpu)]
     WebGpu(String),
+
+    /// Error reported by a custom backend.
+    #[cfg(custom)]
+    Custom(String),
 }
 
 static_assertions::assert_impl_all!(RequestDeviceError: Send, Sync);
@@ -638,7 +1262,11 @@ impl fmt::Display for RequestDeviceError {
             RequestDeviceErrorKind::WebGpu(error) => {
                 write!(_f, "{error}")
             }
-            #[cfg(not(any(webgpu, wgpu_core)))]
+            #[cfg(custom)]
+            RequestDeviceErrorKind::Custom(error) => {
+                write!(_f, "{error}")
+            }
+            #[cfg(not(any(webgpu, wgpu_core, custom)))]
             _ => unimplemented!("unknown `RequestDeviceErrorKind`"),
         }
     }
@@ -651,12 +1279,24 @@ impl error::Error for RequestDeviceError {
             RequestDeviceErrorKind::Core(error) => error.source(),
             #[cfg(webgpu)]
             RequestDeviceErrorKind::WebGpu(_) => None,
-            #[cfg(not(any(webgpu, wgpu_core)))]
+            #[cfg(custom)]
+            RequestDeviceErrorKind::Custom(_) => None,
+            #[cfg(not(any(webgpu, wgpu_core, custom)))]
             _ => unimplemented!("unknown `RequestDeviceErrorKind`"),
         }
     }
 }
 
+impl RequestDeviceError {
+    /// Creates an error for custom backends, described by `message`.
+    #[cfg(custom)]
+    pub fn from_custom(message: impl Into<String>) -> Self {
+        Self {
+            inner: RequestDeviceErrorKind::Custom(message.into()),
+        }
+    }
+}
+
 #[cfg(wgpu_core)]
 impl From<wgc::instance::RequestDeviceError> for RequestDeviceError {
     fn from(error: wgc::instance::RequestDeviceError) -> Self {
@@ -747,6 +1387,135 @@ impl fmt::Display for Error {
     }
 }
 
+#[cfg(wgpu_core)]
+impl Error {
+    /// The typed `wgpu-core` error behind this error, for matching on the cause of the
+    /// error programmatically.
+    ///
+    /// Returns `None` if the error wasn't reported by `wgpu-core`, e.g. on the WebGPU
+    /// backend.
+    ///
+    /// ```no_run
+    /// # let error: wgpu::Error = todo!();
+    /// use wgpu::{ErrorCause, wgc::command::CommandEncoderError};
+    ///
+    /// match error.cause() {
+    ///     Some(ErrorCause::CreateBindGroup(e)) => println!("bad bind group: {e}"),
+    ///     Some(ErrorCause::CommandEncoder(CommandEncoderError::RenderPass(e))) => {
+    ///         println!("bad render pass: {:?}", e.inner())
+    ///     }
+    ///     _ => println!("{error}"),
+    /// }
+    /// ```
+    pub fn cause(&self) -> Option<ErrorCause<'_>> {
+        let context = self.context()?;
+        Some(ErrorCause::from_source(&*context.source))
+    }
+
+    /// The name of the operation that caused this error, e.g. `"Device::create_buffer"`.
+    ///
+    /// Returns `None` if the error wasn't reported by `wgpu-core`.
+    pub fn operation(&self) -> Option<&'static str> {
+        let context = self.context()?;
+        Some(context.fn_ident)
+    }
+
+    fn context(&self) -> Option<&wgc::error::ContextError> {
+        let source = match self {
+            Error::OutOfMemory { source } => source,
+            Error::Validation { source, .. } => source,
+            Error::Internal { source, .. } => source,
+        };
+        source.downcast_ref()
+    }
+}
+
+/// The typed `wgpu-core` error behind an [`Error`], returned by [`Error::cause()`].
+///
+/// Each variant borrows the structured error reported by the corresponding operation.
+/// Errors of types without a dedicated variant are returned as [`ErrorCause::Other`];
+/// more variants may be added in the future.
+#[cfg(wgpu_core)]
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum ErrorCause<'a> {
+    /// Error from [`Device::create_buffer()`].
+    CreateBuffer(&'a wgc::resource::CreateBufferError),
+    /// Error from [`Device::create_texture()`].
+    CreateTexture(&'a wgc::resource::CreateTextureError),
+    /// Error from [`Texture::create_view()`].
+    CreateTextureView(&'a wgc::resource::CreateTextureViewError),
+    /// Error from [`Device::create_sampler()`].
+    CreateSampler(&'a wgc::resource::CreateSamplerError),
+    /// Error from [`Device::create_query_set()`].
+    CreateQuerySet(&'a wgc::resource::CreateQuerySetError),
+    /// Error from [`Device::create_bind_group_layout()`].
+    CreateBindGroupLayout(&'a wgc::binding_model::CreateBindGroupLayoutError),
+    /// Error from [`Device::create_bind_group()`].
+    CreateBindGroup(&'a wgc::binding_model::CreateBindGroupError),
+    /// Error from [`Device::create_pipeline_layout()`].
+    CreatePipelineLayout(&'a wgc::binding_model::CreatePipelineLayoutError),
+    /// Error from [`Device::create_shader_module()`].
+    CreateShaderModule(&'a wgc::pipeline::CreateShaderModuleError),
+    /// Error from [`Device::create_render_pipeline()`].
+    CreateRenderPipeline(&'a wgc::pipeline::CreateRenderPipelineError),
+    /// Error from [`Device::create_compute_pipeline()`].
+    CreateComputePipeline(&'a wgc::pipeline::CreateComputePipelineError),
+    /// Error from mapping or unmapping a [`Buffer`].
+    BufferAccess(&'a wgc::resource::BufferAccessError),
+    /// Error from recording commands, reported when the [`CommandEncoder`] is finished.
+    ///
+    /// Errors from render and compute passes are reported through this variant, as
+    /// [`CommandEncoderError::RenderPass`](wgc::command::CommandEncoderError::RenderPass)
+    /// and [`CommandEncoderError::ComputePass`](wgc::command::CommandEncoderError::ComputePass).
+    CommandEncoder(&'a wgc::command::CommandEncoderError),
+    /// Error from using a [`CommandEncoder`] or pass in the wrong state.
+    EncoderState(&'a wgc::command::EncoderStateError),
+    /// Error from a [`Queue`] write operation.
+    QueueWrite(&'a wgc::device::queue::QueueWriteError),
+    /// Error from [`Queue::submit()`].
+    QueueSubmit(&'a wgc::device::queue::QueueSubmitError),
+    /// Error from [`Surface::configure()`].
+    ConfigureSurface(&'a wgc::present::ConfigureSurfaceError),
+    /// An error of a type without a dedicated variant.
+    Other(&'a (dyn error::Error + 'static)),
+}
+
+#[cfg(wgpu_core)]
+impl<'a> ErrorCause<'a> {
+    fn from_source(source: &'a (dyn error::Error + 'static)) -> Self {
+        macro_rules! downcast {
+            ($($variant:ident($ty:ty)),* $(,)?) => {
+                $(
+                    if let Some(e) = source.downcast_ref::<$ty>() {
+                        return Self::$variant(e);
+                    }
+                )*
+            };
+        }
+        downcast!(
+            CreateBuffer(wgc::resource::CreateBufferError),
+            CreateTexture(wgc::resource::CreateTextureError),
+            CreateTextureView(wgc::resource::CreateTextureViewError),
+            CreateSampler(wgc::resource::CreateSamplerError),
+            CreateQuerySet(wgc::resource::CreateQuerySetError),
+            CreateBindGroupLayout(wgc::binding_model::CreateBindGroupLayoutError),
+            CreateBindGroup(wgc::binding_model::CreateBindGroupError),
+            CreatePipelineLayout(wgc::binding_model::CreatePipelineLayoutError),
+            CreateShaderModule(wgc::pipeline::CreateShaderModuleError),
+            CreateRenderPipeline(wgc::pipeline::CreateRenderPipelineError),
+            CreateComputePipeline(wgc::pipeline::CreateComputePipelineError),
+            BufferAccess(wgc::resource::BufferAccessError),
+            CommandEncoder(wgc::command::CommandEncoderError),
+            EncoderState(wgc::command::EncoderStateError),
+            QueueWrite(wgc::device::queue::QueueWriteError),
+            QueueSubmit(wgc::device::queue::QueueSubmitError),
+            ConfigureSurface(wgc::present::ConfigureSurfaceError),
+        );
+        Self::Other(source)
+    }
+}
+
 // Copied from [`futures::task::noop_waker`].
 // Needed until MSRV is 1.85 with `task::Waker::noop()` available
 #[cfg(feature = "noop")]
@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Handle to a texture sampling the frames of an external video source.
+///
+/// It can be imported from a video element with [`Device::import_external_texture`],
+/// and bound with [`BindingResource::ExternalTexture`] to a binding of type
+/// [`BindingType::ExternalTexture`], which is `texture_external` in WGSL.
+///
+/// External textures are only supported by the `webgpu` backend.
+///
+/// Corresponds to [WebGPU `GPUExternalTexture`](https://gpuweb.github.io/gpuweb/#gpuexternaltexture).
+#[derive(Debug, Clone)]
+pub struct ExternalTexture {
+    pub(crate) inner: dispatch::DispatchExternalTexture,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(ExternalTexture: Send, Sync);
+
+crate::cmp::impl_eq_ord_hash_proxy!(ExternalTexture => .inner);
+
+impl ExternalTexture {
+    #[cfg(custom)]
+    /// Returns custom implementation of ExternalTexture (if custom backend and is internally T)
+    pub fn as_custom<T: custom::ExternalTextureInterface>(&self) -> Option<&T> {
+        self.inner.as_custom()
+    }
+}
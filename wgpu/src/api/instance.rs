@@ -78,6 +78,9 @@ impl Instance {
         if cfg!(dx12) {
             backends = backends.union(Backends::DX12);
         }
+        if cfg!(cpu) {
+            backends = backends.union(Backends::CPU);
+        }
         if cfg!(webgpu) {
             backends = backends.union(Backends::BROWSER_WEBGPU);
         }
@@ -211,7 +214,9 @@ impl Instance {
     }
 
     #[cfg(custom)]
-    /// Creates instance from custom context implementation
+    /// Creates an instance from a custom backend implementation.
+    ///
+    /// See [`custom`] for how to implement a backend.
     pub fn from_custom<T: InstanceInterface>(instance: T) -> Self {
         Self {
             inner: dispatch::DispatchInstance::Custom(backend::custom::DynContext::new(instance)),
@@ -231,6 +236,7 @@ impl Instance {
     /// - `backends` - Backends from which to enumerate adapters.
     #[cfg(wgpu_core)]
     pub fn enumerate_adapters(&self, backends: Backends) -> Vec<Adapter> {
+        api_span!("Instance::enumerate_adapters", backends = ?backends);
         let Some(core_instance) = self.inner.as_core_opt() else {
             return Vec::new();
         };
@@ -248,6 +254,30 @@ impl Instance {
             .collect()
     }
 
+    /// Retrieves all physical displays that can be presented to directly, without a
+    /// windowing system.
+    ///
+    /// Displays that are driven by a running windowing system are usually not reported.
+    ///
+    /// # Platform Support
+    ///
+    /// This function is only available on non-apple Unix-like platforms (Linux, FreeBSD) and
+    /// currently only works with the Vulkan backend. It returns an empty list otherwise.
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    pub fn enumerate_displays(&self) -> Vec<Display> {
+        let Some(core_instance) = self.inner.as_core_opt() else {
+            return Vec::new();
+        };
+
+        core_instance
+            .enumerate_displays()
+            .into_iter()
+            .map(|display| Display {
+                inner: alloc::sync::Arc::new(display),
+            })
+            .collect()
+    }
+
     /// Retrieves an [`Adapter`] which matches the given [`RequestAdapterOptions`].
     ///
     /// Some options are "soft", so treated as non-mandatory. Others are "hard".
@@ -260,6 +290,11 @@ impl Instance {
         &self,
         options: &RequestAdapterOptions<'_, '_>,
     ) -> impl Future<Output = Result<Adapter, RequestAdapterError>> + WasmNotSend {
+        api_span!(
+            "Instance::request_adapter",
+            power_preference = ?options.power_preference,
+            force_fallback_adapter = options.force_fallback_adapter
+        );
         let future = self.inner.request_adapter(options);
         async move { future.await.map(|adapter| Adapter { inner: adapter }) }
     }
@@ -297,6 +332,7 @@ impl Instance {
         &self,
         target: impl Into<SurfaceTarget<'window>>,
     ) -> Result<Surface<'window>, CreateSurfaceError> {
+        api_span!("Instance::create_surface");
         // Handle origin (i.e. window) to optionally take ownership of to make the surface outlast the window.
         let handle_source;
 
@@ -381,6 +417,55 @@ impl Instance {
         })
     }
 
+    /// Creates a new surface presenting directly to `display`, driving it with `mode`.
+    ///
+    /// This is a shorthand for [`Instance::create_surface_unsafe`] with
+    /// [`SurfaceTargetUnsafe::Display`].
+    ///
+    /// # Safety
+    ///
+    /// - `display` must have been returned by [`Instance::enumerate_displays`] on this instance.
+    /// - The display must not be in use by a windowing system or another surface.
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    pub unsafe fn create_surface_from_display(
+        &self,
+        display: &Display,
+        mode: &DisplayMode,
+    ) -> Result<Surface<'static>, CreateSurfaceError> {
+        unsafe {
+            self.create_surface_unsafe(SurfaceTargetUnsafe::Display {
+                display: display.clone(),
+                mode: *mode,
+            })
+        }
+    }
+
+    /// Creates a new surface that isn't connected to any window.
+    ///
+    /// The surface is configured and presented to like any other surface, but its
+    /// textures are regular offscreen textures of the given size and `format`.
+    /// Presented frames can be retrieved with [`Surface::take_presented_frames`],
+    /// which allows testing presentation code without a windowing system.
+    ///
+    /// Returns `None` if this instance is not backed by `wgpu-core`.
+    #[cfg(wgpu_core)]
+    pub fn create_headless_surface(
+        &self,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+    ) -> Option<Surface<'static>> {
+        api_span!("Instance::create_headless_surface", width, height, format = ?format);
+        let core_instance = self.inner.as_core_opt()?;
+        let surface = core_instance.create_headless_surface(width, height, format);
+
+        Some(Surface {
+            _handle_source: None,
+            inner: surface.into(),
+            config: Mutex::new(None),
+        })
+    }
+
     /// Polls all devices.
     ///
     /// If `force_wait` is true and this is not running on the web, then this
@@ -398,9 +483,48 @@ impl Instance {
     ///
     /// [`Queue`s]: Queue
     pub fn poll_all(&self, force_wait: bool) -> bool {
+        api_span!("Instance::poll_all", force_wait);
         self.inner.poll_all_devices(force_wait)
     }
 
+    /// Starts a capture in the attached graphics debugger, without needing a [`Device`].
+    ///
+    /// The capture is of the first device of this instance that the graphics debugger can
+    /// capture, which is the only device in most applications. See
+    /// [`Device::start_graphics_debugger_capture`] for the supported graphics debuggers:
+    /// RenderDoc, PIX and Xcode.
+    ///
+    /// Returns `true` if a capture was started, or `false` if no graphics debugger is
+    /// attached, no device exists, or a capture started by this function is still active.
+    /// This lets tests capture a frame which went wrong when run under a graphics debugger,
+    /// and run as usual otherwise.
+    ///
+    /// On the web, this is a no-op which returns `false`.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Device::start_graphics_debugger_capture`].
+    #[doc(alias = "start_renderdoc_capture")]
+    #[doc(alias = "start_pix_capture")]
+    #[doc(alias = "start_xcode_capture")]
+    pub unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        unsafe { self.inner.start_graphics_debugger_capture() }
+    }
+
+    /// Stops the capture started by [`Instance::start_graphics_debugger_capture`].
+    ///
+    /// Does nothing if no capture was started.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Device::stop_graphics_debugger_capture`].
+    #[doc(alias = "stop_renderdoc_capture")]
+    #[doc(alias = "stop_pix_capture")]
+    #[doc(alias = "stop_xcode_capture")]
+    pub unsafe fn stop_graphics_debugger_capture(&self) {
+        unsafe { self.inner.stop_graphics_debugger_capture() }
+    }
+
     /// Generates memory report.
     ///
     /// Returns `None` if the feature is not supported by the backend
@@ -0,0 +1,66 @@
+use crate::*;
+
+/// Handle to a block of GPU memory that textures can be placed in.
+///
+/// It can be created with [`Device::create_memory_heap`]. Textures are placed in
+/// it with [`Device::create_texture_aliased`]; textures placed in overlapping
+/// regions of the same heap alias each other. Its memory can also be bound to
+/// sparse resources with [`Queue::bind_sparse_memory`].
+///
+/// Aliased textures can't be used at the same time. Within a command buffer,
+/// the last aliased texture to be used owns the memory, and a texture that
+/// takes the memory over starts with undefined contents, as if it had just
+/// been discarded. The contents of aliased textures don't persist from one
+/// command buffer to the next.
+///
+/// The heap's memory is freed once the heap, every texture placed in it, and
+/// every sparse resource it has been bound to have been dropped.
+///
+/// This type is unique to the Rust API of `wgpu`. It requires
+/// [`Features::TEXTURE_ALIASING`].
+#[derive(Debug, Clone)]
+pub struct MemoryHeap {
+    pub(crate) inner: dispatch::DispatchMemoryHeap,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(MemoryHeap: Send, Sync);
+
+crate::cmp::impl_eq_ord_hash_proxy!(MemoryHeap => .inner);
+
+impl MemoryHeap {
+    #[cfg(custom)]
+    /// Returns custom implementation of MemoryHeap (if custom backend and is internally T)
+    pub fn as_custom<T: custom::MemoryHeapInterface>(&self) -> Option<&T> {
+        self.inner.as_custom()
+    }
+}
+
+/// Describes a [`MemoryHeap`].
+///
+/// For use with [`Device::create_memory_heap`].
+pub type MemoryHeapDescriptor<'a> = wgt::MemoryHeapDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(MemoryHeapDescriptor<'_>: Send, Sync);
+
+/// A [`MemoryHeap`] and an offset in it to place a texture at, or to bind to a
+/// sparse resource.
+///
+/// For use with [`Device::create_texture_aliased`] and [`Queue::bind_sparse_memory`].
+pub type HeapRegion<'a> = wgt::HeapRegion<&'a MemoryHeap>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(HeapRegion<'_>: Send, Sync);
+
+/// Binds memory of a [`MemoryHeap`] to, or unbinds memory from, a range of a
+/// sparse [`Buffer`].
+///
+/// For use with [`Queue::bind_sparse_memory`].
+pub type SparseBufferBinding<'a> = wgt::SparseBufferBinding<&'a Buffer, &'a MemoryHeap>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(SparseBufferBinding<'_>: Send, Sync);
+
+/// Binds memory of a [`MemoryHeap`] to, or unbinds memory from, a region of a
+/// sparse [`Texture`].
+///
+/// For use with [`Queue::bind_sparse_memory`].
+pub type SparseTextureBinding<'a> = wgt::SparseTextureBinding<&'a Texture, &'a MemoryHeap>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(SparseTextureBinding<'_>: Send, Sync);
@@ -32,17 +32,21 @@ mod common_pipeline;
 mod compute_pass;
 mod compute_pipeline;
 mod device;
+mod external_texture;
 mod instance;
+mod memory_heap;
 mod pipeline_cache;
 mod pipeline_layout;
 mod query_set;
 mod queue;
+mod ray_tracing_pipeline;
 mod render_bundle;
 mod render_bundle_encoder;
 mod render_pass;
 mod render_pipeline;
 mod sampler;
 mod shader_module;
+mod shared_fence;
 mod surface;
 mod surface_texture;
 mod texture;
@@ -60,17 +64,21 @@ pub use common_pipeline::*;
 pub use compute_pass::*;
 pub use compute_pipeline::*;
 pub use device::*;
+pub use external_texture::*;
 pub use instance::*;
+pub use memory_heap::*;
 pub use pipeline_cache::*;
 pub use pipeline_layout::*;
 pub use query_set::*;
 pub use queue::*;
+pub use ray_tracing_pipeline::*;
 pub use render_bundle::*;
 pub use render_bundle_encoder::*;
 pub use render_pass::*;
 pub use render_pipeline::*;
 pub use sampler::*;
 pub use shader_module::*;
+pub use shared_fence::*;
 pub use surface::*;
 pub use surface_texture::*;
 pub use texture::*;
@@ -63,6 +63,8 @@ use crate::*;
 ///
 /// This resource currently only works on the following backends:
 ///  - Vulkan
+///  - DX12, using `ID3D12PipelineLibrary`
+///  - Metal, using binary archives
 ///
 /// This type is unique to the Rust API of `wgpu`.
 ///
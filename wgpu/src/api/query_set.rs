@@ -31,3 +31,21 @@ impl QuerySet {
 /// https://gpuweb.github.io/gpuweb/#dictdef-gpuquerysetdescriptor).
 pub type QuerySetDescriptor<'a> = wgt::QuerySetDescriptor<Label<'a>>;
 static_assertions::assert_impl_all!(QuerySetDescriptor<'_>: Send, Sync);
+
+/// Describes a pipeline statistics query recording the work of a whole render or compute pass.
+///
+/// For use with [`RenderPassDescriptor`] and [`ComputePassDescriptor`].
+///
+/// The query is started right at the beginning of the pass and stopped right at its end.
+/// Unlike [`RenderPass::begin_pipeline_statistics_query`], this only requires
+/// [`Features::PIPELINE_STATISTICS_QUERY`], and is supported on every backend with that
+/// feature, including Metal.
+#[derive(Clone, Debug)]
+pub struct PassPipelineStatisticsQuery<'a> {
+    /// The query set to write to. It must be of type [`QueryType::PipelineStatistics`].
+    pub query_set: &'a QuerySet,
+    /// The index of the query in the query set.
+    pub query_index: u32,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(PassPipelineStatisticsQuery<'_>: Send, Sync);
@@ -1,5 +1,9 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
+use core::cmp::Ordering;
+#[cfg(feature = "async")]
+use core::future::Future;
 use core::ops::{Deref, DerefMut};
+use core::time::Duration;
 
 use crate::*;
 
@@ -35,19 +39,86 @@ impl Queue {
     }
 }
 
-/// Identifier for a particular call to [`Queue::submit`]. Can be used
-/// as part of an argument to [`Device::poll`] to block for a particular
+/// Describes an additional [`Queue`] to request with [`Adapter::request_device_with_queues`].
+pub type QueueDescriptor<'a> = wgt::QueueDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(QueueDescriptor<'_>: Send, Sync);
+
+/// Identifier for a particular call to [`Queue::submit`]. Can be awaited with
+/// [`SubmissionIndex::wait_async`], waited for with [`SubmissionIndex::wait`], or
+/// used as part of an argument to [`Device::poll`] to block for a particular
 /// submission to finish.
 ///
+/// Submission indices of a device are ordered by submission, and can be compared
+/// with [`Queue::get_completed_submission_index`] to tell whether a submission
+/// has finished.
+///
 /// This type is unique to the Rust API of `wgpu`.
 /// There is no analogue in the WebGPU specification.
 #[derive(Debug, Clone)]
 pub struct SubmissionIndex {
     pub(crate) index: u64,
+    pub(crate) queue: dispatch::DispatchQueue,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(SubmissionIndex: Send, Sync);
 
+impl SubmissionIndex {
+    /// Returns a future which resolves when this submission, and every submission
+    /// before it, have finished running on the gpu.
+    ///
+    /// As with [`Queue::on_submitted_work_done_async()`], the future only resolves
+    /// once the device has been polled.
+    ///
+    /// On WebGPU, the future resolves once all the work submitted before this call
+    /// has finished.
+    #[cfg(feature = "async")]
+    pub fn wait_async(&self) -> impl Future<Output = ()> + WasmNotSend + use<> {
+        let future = util::CallbackFuture::new();
+        let sender = future.sender();
+        self.queue
+            .on_submission_done(self.index, Box::new(move || sender.send(())));
+        future
+    }
+
+    /// Blocks until this submission has finished running on the gpu, and invokes
+    /// the callbacks that are ready, like [`Device::poll`].
+    ///
+    /// Gives up and returns [`PollError::Timeout`] once `timeout` has passed; a
+    /// timeout of zero only checks whether the submission has finished. `None`
+    /// waits as long as [`PollType::WaitForSubmissionIndex`] does.
+    ///
+    /// When running on WebGPU, this is a no-op. `Device`s are automatically polled.
+    ///
+    /// [`PollType::WaitForSubmissionIndex`]: wgt::PollType::WaitForSubmissionIndex
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<PollStatus, PollError> {
+        let poll_type = match timeout {
+            Some(timeout) => wgt::PollType::wait_for_with_timeout(self.index, timeout),
+            None => wgt::PollType::wait_for(self.index),
+        };
+        self.queue.poll(poll_type)
+    }
+}
+
+impl PartialEq for SubmissionIndex {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for SubmissionIndex {}
+
+impl PartialOrd for SubmissionIndex {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SubmissionIndex {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
 /// Passed to [`Device::poll`] to control how and if it should block.
 pub type PollType = wgt::PollType<SubmissionIndex>;
 #[cfg(send_sync)]
@@ -147,6 +218,7 @@ impl Queue {
     ///   be able to use [`StagingBelt`](crate::util::StagingBelt),
     ///   or buffers you explicitly create, map, and unmap yourself.
     pub fn write_buffer(&self, buffer: &Buffer, offset: BufferAddress, data: &[u8]) {
+        api_span!("Queue::write_buffer", offset, size = data.len());
         self.inner.write_buffer(&buffer.inner, offset, data);
     }
 
@@ -191,6 +263,7 @@ impl Queue {
         size: BufferSize,
     ) -> Option<QueueWriteBufferView<'a>> {
         profiling::scope!("Queue::write_buffer_with");
+        api_span!("Queue::write_buffer_with", offset, size = size.get());
         self.inner
             .validate_write_buffer(&buffer.inner, offset, size)?;
         let staging_buffer = self.inner.create_staging_buffer(size)?;
@@ -214,6 +287,11 @@ impl Queue {
     ///
     /// This method fails if `size` overruns the size of `texture`, or if `data` is too short.
     ///
+    /// Unlike [`CommandEncoder::copy_buffer_to_texture()`], `data_layout.bytes_per_row` doesn't
+    /// need to be a multiple of [`COPY_BYTES_PER_ROW_ALIGNMENT`]. Rows that don't meet the
+    /// backend's alignment are repacked while staging; for large uploads, this happens on the
+    /// GPU, so `data` is only copied once on the CPU.
+    ///
     /// # Performance considerations
     ///
     /// This operation has the same performance considerations as [`Queue::write_buffer()`];
@@ -230,6 +308,14 @@ impl Queue {
         data_layout: TexelCopyBufferLayout,
         size: Extent3d,
     ) {
+        api_span!(
+            "Queue::write_texture",
+            mip_level = texture.mip_level,
+            width = size.width,
+            height = size.height,
+            depth_or_array_layers = size.depth_or_array_layers,
+            bytes = data.len()
+        );
         self.inner.write_texture(texture, data, data_layout, size);
     }
 
@@ -250,11 +336,123 @@ impl Queue {
         &self,
         command_buffers: I,
     ) -> SubmissionIndex {
+        api_span!("Queue::submit", submission_index = tracing::field::Empty);
         let mut command_buffers = command_buffers.into_iter().map(|comb| comb.buffer);
 
         let index = self.inner.submit(&mut command_buffers);
+        api_record!(submission_index = index);
 
-        SubmissionIndex { index }
+        SubmissionIndex {
+            index,
+            queue: self.inner.clone(),
+        }
+    }
+
+    /// Submits a series of finished command buffers for execution, synchronized
+    /// with work outside of `wgpu`.
+    ///
+    /// The command buffers don't start executing until each fence in `wait`
+    /// has reached at least its value. Once they have finished executing, each
+    /// fence in `signal` is set to its value.
+    ///
+    /// Requires [`Features::EXPERIMENTAL_SHARED_FENCE`].
+    pub fn submit_with_external_sync<I: IntoIterator<Item = CommandBuffer>>(
+        &self,
+        command_buffers: I,
+        wait: &[SharedFenceValue<'_>],
+        signal: &[SharedFenceValue<'_>],
+    ) -> SubmissionIndex {
+        api_span!(
+            "Queue::submit_with_external_sync",
+            wait = wait.len(),
+            signal = signal.len(),
+            submission_index = tracing::field::Empty
+        );
+        fn map_values<'a>(
+            values: &[SharedFenceValue<'a>],
+        ) -> Vec<wgt::SharedFenceValue<&'a dispatch::DispatchSharedFence>> {
+            values
+                .iter()
+                .map(|value| wgt::SharedFenceValue {
+                    fence: &value.fence.inner,
+                    value: value.value,
+                })
+                .collect()
+        }
+
+        let mut command_buffers = command_buffers.into_iter().map(|comb| comb.buffer);
+
+        let index = self.inner.submit_with_external_sync(
+            &mut command_buffers,
+            &map_values(wait),
+            &map_values(signal),
+        );
+        api_record!(submission_index = index);
+
+        SubmissionIndex {
+            index,
+            queue: self.inner.clone(),
+        }
+    }
+
+    /// Binds memory to, or unbinds memory from, regions of sparse buffers and
+    /// textures.
+    ///
+    /// The bindings take effect after all work previously submitted to this queue,
+    /// and before any work submitted after this call. Writes made with
+    /// [`Queue::write_buffer`] or [`Queue::write_texture`] are only submitted with
+    /// the next call to [`Queue::submit`], so they land after the bindings.
+    ///
+    /// Reading an unbound part of a sparse resource returns zeros, and writes to
+    /// it are discarded. Newly bound buffer memory is zeroed before it is first
+    /// read, but the contents of newly bound texture tiles are undefined until
+    /// they are written.
+    ///
+    /// Bindings can only be made on the device's main queue.
+    ///
+    /// Requires [`Features::SPARSE_BINDING`].
+    pub fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[SparseBufferBinding<'_>],
+        texture_bindings: &[SparseTextureBinding<'_>],
+    ) {
+        api_span!(
+            "Queue::bind_sparse_memory",
+            buffer_bindings = buffer_bindings.len(),
+            texture_bindings = texture_bindings.len()
+        );
+        fn map_memory<'a>(
+            memory: &Option<HeapRegion<'a>>,
+        ) -> Option<wgt::HeapRegion<&'a dispatch::DispatchMemoryHeap>> {
+            memory.map(|region| wgt::HeapRegion {
+                heap: &region.heap.inner,
+                offset: region.offset,
+            })
+        }
+
+        let buffer_bindings = buffer_bindings
+            .iter()
+            .map(|binding| wgt::SparseBufferBinding {
+                buffer: &binding.buffer.inner,
+                offset: binding.offset,
+                size: binding.size,
+                memory: map_memory(&binding.memory),
+            })
+            .collect::<Vec<_>>();
+        let texture_bindings = texture_bindings
+            .iter()
+            .map(|binding| wgt::SparseTextureBinding {
+                texture: &binding.texture.inner,
+                mip_level: binding.mip_level,
+                array_layer: binding.array_layer,
+                origin: binding.origin,
+                size: binding.size,
+                memory: map_memory(&binding.memory),
+            })
+            .collect::<Vec<_>>();
+
+        self.inner
+            .bind_sparse_memory(&buffer_bindings, &texture_bindings);
     }
 
     /// Gets the amount of nanoseconds each tick of a timestamp query represents.
@@ -267,6 +465,19 @@ impl Queue {
         self.inner.get_timestamp_period()
     }
 
+    /// Samples the GPU and CPU clocks at the same time, so that timestamp query results can
+    /// be placed on the same timeline as CPU timestamps, for example by a profiler.
+    ///
+    /// See [`TimestampCalibration`] for the CPU clock of each backend. The clocks drift
+    /// apart over time, so this should be called regularly, for example once per frame.
+    ///
+    /// Returns `None` if the backend or device can't sample both clocks: on WebGPU, OpenGL,
+    /// and on Vulkan without `VK_EXT_calibrated_timestamps`.
+    pub fn get_timestamp_calibration(&self) -> Option<TimestampCalibration> {
+        api_span!("Queue::get_timestamp_calibration");
+        self.inner.get_timestamp_calibration()
+    }
+
     /// Registers a callback when the previous call to submit finishes running on the gpu. This callback
     /// being called implies that all mapped buffer callbacks which were registered before this call will
     /// have been called.
@@ -279,9 +490,79 @@ impl Queue {
     /// call to the function will not complete until the callback returns, so prefer keeping callbacks short
     /// and used to set flags, send messages, etc.
     pub fn on_submitted_work_done(&self, callback: impl FnOnce() + Send + 'static) {
+        api_span!("Queue::on_submitted_work_done");
         self.inner.on_submitted_work_done(Box::new(callback));
     }
 
+    /// Returns the index of the last submission that has finished running on the gpu,
+    /// along with every submission before it.
+    ///
+    /// This is cheap, and doesn't invoke any callbacks; compare the result with the
+    /// [`SubmissionIndex`] returned by [`Queue::submit`] to tell whether a submission
+    /// has finished, for example to limit the number of frames in flight.
+    ///
+    /// On WebGPU, submission indices aren't tracked, and every submission compares as
+    /// finished.
+    pub fn get_completed_submission_index(&self) -> SubmissionIndex {
+        SubmissionIndex {
+            index: self.inner.get_completed_submission_index(),
+            queue: self.inner.clone(),
+        }
+    }
+
+    /// Makes the command buffers submitted to this queue from now on wait for
+    /// `submission_index`, and every submission before it, to finish running on the
+    /// device's other queues.
+    ///
+    /// Submissions to different queues of a device may run in parallel, and their
+    /// [`SubmissionIndex`]es only give their submission order. Use this to consume on
+    /// one queue the results of work submitted to another.
+    ///
+    /// This does not block the calling thread. It has no effect unless
+    /// [`Features::EXPERIMENTAL_MULTI_QUEUE`] is enabled.
+    ///
+    /// # Panics
+    ///
+    /// - `submission_index` was not returned by a submission to this queue's device.
+    pub fn wait_for_submission(&self, submission_index: &SubmissionIndex) {
+        api_span!(
+            "Queue::wait_for_submission",
+            submission_index = submission_index.index
+        );
+        self.inner.wait_for_submission(submission_index.index);
+    }
+
+    /// Returns a future which resolves when the previous call to submit finishes running
+    /// on the gpu.
+    ///
+    /// This behaves like [`Queue::on_submitted_work_done()`], but reports completion
+    /// through the returned future instead of a callback. As with the callback, the
+    /// future only resolves once the device has been polled.
+    #[cfg(feature = "async")]
+    pub fn on_submitted_work_done_async(&self) -> impl Future<Output = ()> + WasmNotSend + use<> {
+        let future = util::CallbackFuture::new();
+        let sender = future.sender();
+        self.inner
+            .on_submitted_work_done(Box::new(move || sender.send(())));
+        future
+    }
+
+    /// Takes the command buffers submitted to this queue since the last call, with the
+    /// commands recorded into them.
+    ///
+    /// Commands are only recorded by the [`Backend::Noop`] backend, when enabled with
+    /// [`NoopBackendOptions::record`], for example by [`Device::noop_recording`]. This allows
+    /// testing which commands rendering code encodes, after they have been validated,
+    /// without a GPU. Returns an empty `Vec` for queues of other backends.
+    #[cfg(feature = "noop")]
+    pub fn take_recorded_command_buffers(&self) -> Vec<RecordedCommandBuffer> {
+        // SAFETY: The queue is only used to take the recorded commands.
+        match unsafe { self.as_hal::<wgc::api::Noop>() } {
+            Some(queue) => queue.take_recorded_command_buffers(),
+            None => Vec::new(),
+        }
+    }
+
     /// Get the [`wgpu_hal`] device from this `Queue`.
     ///
     /// Find the Api struct corresponding to the active backend in [`wgpu_hal::api`],
@@ -318,6 +599,7 @@ impl Queue {
     /// The returned BLAS is more restricted than a normal BLAS because it may not be rebuilt or
     /// compacted.
     pub fn compact_blas(&self, blas: &Blas) -> Blas {
+        api_span!("Queue::compact_blas");
         let (handle, dispatch) = self.inner.compact_blas(&blas.inner);
         Blas {
             handle,
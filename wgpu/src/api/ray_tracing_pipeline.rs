@@ -0,0 +1,147 @@
+use crate::*;
+
+/// Handle to a ray tracing pipeline.
+///
+/// A `RayTracingPipeline` object holds the shader stages that can be launched by
+/// [`CommandEncoder::trace_rays`], grouped into the shader groups that a [`ShaderBindingTable`]
+/// refers to. It can be created with [`Device::create_ray_tracing_pipeline`].
+///
+/// Ray tracing pipelines are a native-only extension, requiring
+/// [`Features::EXPERIMENTAL_RAY_TRACING_PIPELINE`].
+#[derive(Debug, Clone)]
+pub struct RayTracingPipeline {
+    pub(crate) inner: dispatch::DispatchRayTracingPipeline,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RayTracingPipeline: Send, Sync);
+
+crate::cmp::impl_eq_ord_hash_proxy!(RayTracingPipeline => .inner);
+
+impl RayTracingPipeline {
+    #[cfg(custom)]
+    /// Returns custom implementation of RayTracingPipeline (if custom backend and is internally T)
+    pub fn as_custom<T: custom::RayTracingPipelineInterface>(&self) -> Option<&T> {
+        self.inner.as_custom()
+    }
+}
+
+/// Describes a shader stage of a ray tracing pipeline.
+///
+/// For use with [`RayTracingPipelineDescriptor`].
+#[derive(Clone, Debug)]
+pub struct RayTracingStage<'a> {
+    /// Which ray tracing stage this is. Exactly one bit of [`ShaderStages::RAY_TRACING`]
+    /// must be set.
+    pub stage: ShaderStages,
+    /// The compiled shader module for this stage.
+    ///
+    /// Ray generation, miss, closest hit and any hit stages can be written in WGSL with
+    /// `enable wgpu_ray_tracing_pipeline;`. WGSL has no intersection or callable stages, so
+    /// their modules must come from [`Device::create_shader_module_passthrough()`], with
+    /// SPIR-V on Vulkan and HLSL or DXIL libraries on DX12.
+    pub module: &'a ShaderModule,
+    /// The name of the entry point in the compiled shader to use.
+    pub entry_point: &'a str,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RayTracingStage<'_>: Send, Sync);
+
+/// Describes a ray tracing pipeline.
+///
+/// For use with [`Device::create_ray_tracing_pipeline`].
+#[derive(Clone, Debug)]
+pub struct RayTracingPipelineDescriptor<'a> {
+    /// Debug label of the pipeline. This will show up in graphics debuggers for easy identification.
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    ///
+    /// Unlike other pipelines, the layout can't be derived from the shader modules, so it
+    /// must always be given.
+    pub layout: &'a PipelineLayout,
+    /// The shader stages of the pipeline. Shader groups refer to these by index.
+    pub stages: &'a [RayTracingStage<'a>],
+    /// The shader groups of the pipeline. Shader binding tables refer to these by index.
+    pub groups: &'a [RayTracingShaderGroup],
+    /// The maximum depth of `traceRay` calls made from within the pipeline.
+    ///
+    /// Must not be greater than [`Limits::max_ray_recursion_depth`].
+    pub max_recursion_depth: u32,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<&'a PipelineCache>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RayTracingPipelineDescriptor<'_>: Send, Sync);
+
+/// Describes a shader binding table.
+///
+/// For use with [`Device::create_shader_binding_table`]. Each field names shader groups of
+/// `pipeline`, by their index in [`RayTracingPipelineDescriptor::groups`].
+#[derive(Clone, Debug)]
+pub struct ShaderBindingTableDescriptor<'a> {
+    /// Debug label of the table's buffer. This will show up in graphics debuggers for easy
+    /// identification.
+    pub label: Label<'a>,
+    /// The pipeline whose shader group handles make up the table.
+    pub pipeline: &'a RayTracingPipeline,
+    /// The general group holding the ray generation stage to launch.
+    pub ray_generation: u32,
+    /// The general groups holding miss stages, in record order.
+    pub miss: &'a [u32],
+    /// The hit groups, in record order.
+    pub hit: &'a [u32],
+    /// The general groups holding callable stages, in record order.
+    pub callable: &'a [u32],
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(ShaderBindingTableDescriptor<'_>: Send, Sync);
+
+/// A shader binding table: the shader group handles of a [`RayTracingPipeline`], laid out
+/// in a buffer for [`CommandEncoder::trace_rays`].
+///
+/// It can be created with [`Device::create_shader_binding_table`].
+#[derive(Debug, Clone)]
+pub struct ShaderBindingTable {
+    pub(crate) buffer: Buffer,
+    pub(crate) layout: ShaderBindingTableLayout,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(ShaderBindingTable: Send, Sync);
+
+impl ShaderBindingTable {
+    /// The buffer holding the table, created with [`BufferUsages::SHADER_BINDING_TABLE`].
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Where the records of each kind of shader group are in [`Self::buffer`].
+    pub fn layout(&self) -> ShaderBindingTableLayout {
+        self.layout
+    }
+}
+
+/// Describes a launch of a [`RayTracingPipeline`].
+///
+/// For use with [`CommandEncoder::trace_rays`].
+#[derive(Clone, Debug)]
+pub struct TraceRaysDescriptor<'a> {
+    /// Debug label of the launch. This will show up in graphics debuggers for easy identification.
+    pub label: Label<'a>,
+    /// The pipeline to launch.
+    pub pipeline: &'a RayTracingPipeline,
+    /// The bind groups used by the traced shaders, bound at the index of their position in the
+    /// slice, with their dynamic offsets.
+    pub bind_groups: &'a [(&'a BindGroup, &'a [DynamicOffset])],
+    /// Push constant data for the ray tracing stages, written at offset 0.
+    pub push_constants: &'a [u8],
+    /// The shader binding table to take the ray generation, miss, hit and callable records
+    /// from. It must have been created for `pipeline`.
+    pub shader_binding_table: &'a ShaderBindingTable,
+    /// Width of the launch, in ray generation invocations.
+    pub width: u32,
+    /// Height of the launch, in ray generation invocations.
+    pub height: u32,
+    /// Depth of the launch, in ray generation invocations.
+    pub depth: u32,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(TraceRaysDescriptor<'_>: Send, Sync);
@@ -1,6 +1,5 @@
 use core::{marker::PhantomData, num::NonZeroU32, ops::Range};
 
-use crate::dispatch::RenderBundleEncoderInterface;
 use crate::*;
 
 /// Encodes a series of GPU operations into a reusable "render bundle".
@@ -46,20 +45,20 @@ pub struct RenderBundleEncoderDescriptor<'a> {
     pub sample_count: u32,
     /// If this render bundle will rendering to multiple array layers in the attachments at the same time.
     pub multiview: Option<NonZeroU32>,
+    /// State of the render pass that this render bundle inherits, instead of being isolated from
+    /// it. See [`RenderBundleInheritance`].
+    ///
+    /// Not supported on WebGPU, where the flags are ignored.
+    pub inheritance: RenderBundleInheritance,
 }
 static_assertions::assert_impl_all!(RenderBundleEncoderDescriptor<'_>: Send, Sync);
 
 impl<'a> RenderBundleEncoder<'a> {
     /// Finishes recording and returns a [`RenderBundle`] that can be executed in other render passes.
     pub fn finish(self, desc: &RenderBundleDescriptor<'_>) -> RenderBundle {
-        let bundle = match self.inner {
-            #[cfg(wgpu_core)]
-            dispatch::DispatchRenderBundleEncoder::Core(b) => b.finish(desc),
-            #[cfg(webgpu)]
-            dispatch::DispatchRenderBundleEncoder::WebGPU(b) => b.finish(desc),
-            #[cfg(custom)]
-            dispatch::DispatchRenderBundleEncoder::Custom(_) => unimplemented!(),
-        };
+        api_span!("RenderBundleEncoder::finish", label = desc.label);
+        let mut inner = self.inner;
+        let bundle = inner.finish(desc);
 
         RenderBundle { inner: bundle }
     }
@@ -230,3 +229,49 @@ impl RenderBundleEncoder<'_> {
         self.inner.set_push_constants(stages, offset, data);
     }
 }
+
+/// [`Features::TIMESTAMP_QUERY_INSIDE_PASSES`] must be enabled on the device in order to call these functions.
+impl RenderBundleEncoder<'_> {
+    /// Issue a timestamp command at this point in the queue, each time the bundle is executed.
+    /// The timestamp will be written to the specified query set, at the specified index.
+    ///
+    /// Like in a [`RenderPass`], each query may only be written once per render pass, so
+    /// the bundle can only be executed once in each pass.
+    pub fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32) {
+        self.inner.write_timestamp(&query_set.inner, query_index);
+    }
+}
+
+/// The bundle must be created with [`RenderBundleInheritance::OCCLUSION_QUERY_SET`] in order to call these functions.
+impl RenderBundleEncoder<'_> {
+    /// Start an occlusion query in the [`occlusion_query_set`] of the render pass the bundle is
+    /// executed in. It must be ended with [`end_occlusion_query`](Self::end_occlusion_query)
+    /// within this bundle. Occlusion queries may not be nested.
+    ///
+    /// [`occlusion_query_set`]: RenderPassDescriptor::occlusion_query_set
+    pub fn begin_occlusion_query(&mut self, query_index: u32) {
+        self.inner.begin_occlusion_query(query_index);
+    }
+
+    /// End the occlusion query started with [`begin_occlusion_query`](Self::begin_occlusion_query).
+    pub fn end_occlusion_query(&mut self) {
+        self.inner.end_occlusion_query();
+    }
+}
+
+/// [`Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES`] must be enabled on the device in order to call these functions.
+impl RenderBundleEncoder<'_> {
+    /// Start a pipeline statistics query. It must be ended with
+    /// [`end_pipeline_statistics_query`](Self::end_pipeline_statistics_query) within this bundle.
+    /// Pipeline statistics queries may not be nested.
+    pub fn begin_pipeline_statistics_query(&mut self, query_set: &QuerySet, query_index: u32) {
+        self.inner
+            .begin_pipeline_statistics_query(&query_set.inner, query_index);
+    }
+
+    /// End the pipeline statistics query started with
+    /// [`begin_pipeline_statistics_query`](Self::begin_pipeline_statistics_query).
+    pub fn end_pipeline_statistics_query(&mut self) {
+        self.inner.end_pipeline_statistics_query();
+    }
+}
@@ -1,4 +1,4 @@
-use core::ops::Range;
+use core::{num::NonZeroU32, ops::Range};
 
 use crate::*;
 pub use wgt::{LoadOp, Operations, StoreOp};
@@ -87,6 +87,9 @@ impl RenderPass<'_> {
     /// Subsequent blending tests will test against this value.
     /// If this method has not been called, the blend constant defaults to [`Color::TRANSPARENT`]
     /// (all components zero).
+    ///
+    /// The constant is shared by every color target. Vulkan, DX12, Metal and GL only have
+    /// one blend constant, so there is no way to give each target its own.
     pub fn set_blend_constant(&mut self, color: Color) {
         self.inner.set_blend_constant(color);
     }
@@ -262,6 +265,32 @@ impl RenderPass<'_> {
 
         self.inner.execute_bundles(&mut render_bundles);
     }
+
+    /// Creates a parallel encoder: a render pass whose commands are executed at this point
+    /// of this render pass, in the order the parallel encoders were created in.
+    ///
+    /// The returned pass doesn't borrow this one and can be sent to another thread, so that
+    /// several threads record parts of the same render pass at once, while this pass keeps
+    /// recording the commands that follow. It has no attachments of its own, and must be
+    /// ended, by dropping it, before this render pass is. Its commands are validated and
+    /// encoded on the thread that ends it, into a secondary command buffer on Vulkan and a
+    /// render command encoder of a parallel render command encoder on Metal.
+    ///
+    /// Each parallel encoder, and each run of commands this pass records between them,
+    /// starts with no pipeline, bind groups, vertex or index buffers, and with the default
+    /// viewport, scissor rectangle, blend constant and stencil reference. Parallel encoders
+    /// can record any render pass command, except creating parallel encoders of their own.
+    /// Passes that are suspended, resume a suspended pass or have a pipeline statistics
+    /// query can't create parallel encoders.
+    ///
+    /// Requires [`Features::PARALLEL_RENDER_ENCODING`]. On WebGPU, the parallel encoder
+    /// records its commands directly into this render pass as they are issued.
+    pub fn create_parallel_encoder(&mut self) -> RenderPass<'static> {
+        RenderPass {
+            inner: self.inner.create_parallel_encoder(),
+            _encoder_guard: crate::api::PhantomDrop::default(),
+        }
+    }
 }
 
 /// [`Features::MULTI_DRAW_INDIRECT`] must be enabled on the device in order to call these functions.
@@ -397,6 +426,82 @@ impl RenderPass<'_> {
     }
 }
 
+/// [`Features::EXPERIMENTAL_MESH_SHADER`] must be enabled on the device in order to call these functions.
+impl RenderPass<'_> {
+    /// Draws using a mesh shader pipeline, launching `group_count_x * group_count_y *
+    /// group_count_z` workgroups of the task shader, or of the mesh shader if the pipeline
+    /// has no task stage.
+    ///
+    /// The current pipeline must have been created with [`Device::create_mesh_pipeline`].
+    ///
+    /// This drawing command uses the current render state, as set by preceding `set_*()` methods.
+    /// It is not affected by changes to the state that are performed after it is called.
+    pub fn draw_mesh_tasks(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) {
+        self.inner
+            .draw_mesh_tasks(group_count_x, group_count_y, group_count_z);
+    }
+
+    /// Draws using a mesh shader pipeline, based on the contents of the `indirect_buffer`.
+    ///
+    /// This is like calling [`RenderPass::draw_mesh_tasks`] but the contents of the call are
+    /// specified in the `indirect_buffer`. The structure expected in `indirect_buffer` must
+    /// conform to [`DispatchIndirectArgs`](crate::util::DispatchIndirectArgs).
+    pub fn draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: &Buffer,
+        indirect_offset: BufferAddress,
+    ) {
+        self.inner
+            .draw_mesh_tasks_indirect(&indirect_buffer.inner, indirect_offset);
+    }
+
+    /// Issues multiple mesh shader draws based on the contents of the `indirect_buffer`.
+    /// `count` draw calls are issued.
+    ///
+    /// The structure expected in `indirect_buffer` must conform to
+    /// [`DispatchIndirectArgs`](crate::util::DispatchIndirectArgs).
+    /// These draw structures are expected to be tightly packed.
+    ///
+    /// Requires [`Features::MULTI_DRAW_INDIRECT`] if `count` is not 1.
+    pub fn multi_draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: &Buffer,
+        indirect_offset: BufferAddress,
+        count: u32,
+    ) {
+        self.inner
+            .multi_draw_mesh_tasks_indirect(&indirect_buffer.inner, indirect_offset, count);
+    }
+
+    /// Issues multiple mesh shader draws based on the contents of the `indirect_buffer`.
+    /// The count buffer is read to determine how many draws to issue.
+    ///
+    /// The indirect buffer must be long enough to account for `max_count` draws, however only
+    /// `count` draws will be read. If `count` is greater than `max_count`, `max_count` will be used.
+    ///
+    /// The structure expected in `indirect_buffer` must conform to
+    /// [`DispatchIndirectArgs`](crate::util::DispatchIndirectArgs), and `count_buffer` must
+    /// contain a single `u32` at `count_offset`, as for [`RenderPass::multi_draw_indirect_count`].
+    ///
+    /// Requires [`Features::MULTI_DRAW_INDIRECT_COUNT`].
+    pub fn multi_draw_mesh_tasks_indirect_count(
+        &mut self,
+        indirect_buffer: &Buffer,
+        indirect_offset: BufferAddress,
+        count_buffer: &Buffer,
+        count_offset: BufferAddress,
+        max_count: u32,
+    ) {
+        self.inner.multi_draw_mesh_tasks_indirect_count(
+            &indirect_buffer.inner,
+            indirect_offset,
+            &count_buffer.inner,
+            count_offset,
+            max_count,
+        );
+    }
+}
+
 /// [`Features::PUSH_CONSTANTS`] must be enabled on the device in order to call these functions.
 impl RenderPass<'_> {
     /// Set push constant data for subsequent draw calls.
@@ -444,6 +549,33 @@ impl RenderPass<'_> {
     }
 }
 
+/// [`Features::VARIABLE_RATE_SHADING`] must be enabled on the device in order to call these functions.
+impl RenderPass<'_> {
+    /// Sets the size of the block of pixels that each fragment shader invocation colors
+    /// in subsequent draw calls.
+    ///
+    /// Rates the device doesn't support are clamped to a smaller supported rate.
+    /// If this method has not been called, the shading rate defaults to
+    /// [`ShadingRate::OneByOne`].
+    pub fn set_shading_rate(&mut self, rate: ShadingRate) {
+        self.inner.set_shading_rate(rate);
+    }
+}
+
+/// [`Features::MULTI_VIEWPORT`] must be enabled on the device in order to call these functions.
+impl RenderPass<'_> {
+    /// Sets the viewports at indices `0..viewports.len()`, which vertex shaders pick
+    /// between by writing the `viewport_index` built-in.
+    ///
+    /// At most [`MAX_VIEWPORTS`] viewports can be set. Viewports that aren't set keep
+    /// their previous value, which defaults to the entire bounds of the render targets.
+    /// [`set_viewport()`](Self::set_viewport) only sets the first viewport, and the
+    /// scissor rectangle applies to every viewport.
+    pub fn set_viewports(&mut self, viewports: &[Viewport]) {
+        self.inner.set_viewports(viewports);
+    }
+}
+
 /// [`Features::TIMESTAMP_QUERY_INSIDE_PASSES`] must be enabled on the device in order to call these functions.
 impl RenderPass<'_> {
     /// Issue a timestamp command at this point in the queue. The
@@ -474,11 +606,37 @@ impl RenderPass<'_> {
     }
 }
 
-/// [`Features::PIPELINE_STATISTICS_QUERY`] must be enabled on the device in order to call these functions.
+impl RenderPass<'_> {
+    /// Start a conditional block on this render pass. It can be ended with
+    /// [`end_conditional_block`](Self::end_conditional_block).
+    ///
+    /// Draws recorded inside the block are skipped if the 64-bit predicate at `offset`
+    /// in `buffer` is zero, so resolving an occlusion query into `buffer` skips draws
+    /// whose occlusion test found no visible samples. The predicate is read when the
+    /// block begins. On Vulkan only its low 32 bits are read.
+    ///
+    /// `buffer` must have [`BufferUsages::INDIRECT`] and `offset` must be a multiple of 8.
+    /// Conditional blocks may not be nested, and must be ended before the render pass.
+    ///
+    /// Without [`Features::CONDITIONAL_RENDERING`], the block is validated but its
+    /// draws are always executed.
+    pub fn begin_conditional_block(&mut self, buffer: &Buffer, offset: BufferAddress) {
+        self.inner.begin_conditional_block(&buffer.inner, offset);
+    }
+
+    /// End the conditional block on this render pass. It can be started with
+    /// [`begin_conditional_block`](Self::begin_conditional_block).
+    pub fn end_conditional_block(&mut self) {
+        self.inner.end_conditional_block();
+    }
+}
+
+/// [`Features::PIPELINE_STATISTICS_QUERY_INSIDE_PASSES`] must be enabled on the device in order to call these functions.
 impl RenderPass<'_> {
     /// Start a pipeline statistics query on this render pass. It can be ended with
     /// [`end_pipeline_statistics_query`](Self::end_pipeline_statistics_query).
-    /// Pipeline statistics queries may not be nested.
+    /// Pipeline statistics queries may not be nested, and may not be started in a pass
+    /// with a [`RenderPassDescriptor::pipeline_statistics_query`].
     pub fn begin_pipeline_statistics_query(&mut self, query_set: &QuerySet, query_index: u32) {
         self.inner
             .begin_pipeline_statistics_query(&query_set.inner, query_index);
@@ -548,10 +706,22 @@ pub struct RenderPassDepthStencilAttachment<'tex> {
     pub depth_ops: Option<Operations<f32>>,
     /// What operations will be performed on the stencil part of the attachment.
     pub stencil_ops: Option<Operations<u32>>,
+    /// The view that will receive the resolved depth and/or stencil at the end of the pass,
+    /// if the attachment is multisampled.
+    ///
+    /// Requires [`Features::DEPTH_STENCIL_RESOLVE`].
+    pub resolve: Option<RenderPassDepthStencilResolve<'tex>>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPassDepthStencilAttachment<'_>: Send, Sync);
 
+/// Describes how a multisampled depth/stencil attachment is resolved into a [`TextureView`].
+///
+/// For use with [`RenderPassDepthStencilAttachment::resolve`].
+pub type RenderPassDepthStencilResolve<'a> = wgt::DepthStencilResolve<&'a TextureView>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(RenderPassDepthStencilResolve<'_>: Send, Sync);
+
 /// Describes the attachments of a render pass.
 ///
 /// For use with [`CommandEncoder::begin_render_pass`].
@@ -572,6 +742,26 @@ pub struct RenderPassDescriptor<'a> {
     pub timestamp_writes: Option<RenderPassTimestampWrites<'a>>,
     /// Defines where the occlusion query results will be stored for this pass.
     pub occlusion_query_set: Option<&'a QuerySet>,
+    /// Defines where the pipeline statistics of the whole pass will be written to.
+    ///
+    /// Requires [`Features::PIPELINE_STATISTICS_QUERY`] to be enabled.
+    pub pipeline_statistics_query: Option<PassPipelineStatisticsQuery<'a>>,
+    /// Lets this pass continue in a later render pass, possibly recorded on another command
+    /// encoder, or continue a pass suspended right before it. See [`RenderPassFlags`].
+    ///
+    /// On WebGPU, the parts are separate render passes, which store and load the attachments
+    /// in between.
+    pub flags: RenderPassFlags,
+    /// If set, this is a multiview pass that renders to this many array layers of every
+    /// attachment at once, for example one per eye. Shaders can tell the views apart with
+    /// `@builtin(view_index)`.
+    ///
+    /// Every attachment must be a [`TextureViewDimension::D2Array`] view with exactly this many
+    /// array layers, and render pipelines and bundles used in the pass must have the same
+    /// `multiview`. If `None`, every attachment must have a single array layer.
+    ///
+    /// Requires [`Features::MULTIVIEW`]. Not supported on WebGPU.
+    pub multiview: Option<NonZeroU32>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPassDescriptor<'_>: Send, Sync);
@@ -72,17 +72,7 @@ impl RenderPipeline {
 /// }
 ///
 /// # assert_eq!(Vertex::LAYOUT.attributes[2].offset, Vertex::LAYOUT.array_stride - 2 * 4);
-#[derive(Clone, Debug, Hash, Eq, PartialEq)]
-pub struct VertexBufferLayout<'a> {
-    /// The stride, in bytes, between elements of this buffer (between vertices).
-    ///
-    /// This must be a multiple of [`VERTEX_ALIGNMENT`].
-    pub array_stride: BufferAddress,
-    /// How often this vertex buffer is "stepped" forward.
-    pub step_mode: VertexStepMode,
-    /// The list of attributes which comprise a single vertex.
-    pub attributes: &'a [VertexAttribute],
-}
+pub type VertexBufferLayout<'a> = wgt::VertexBufferLayout<&'a [VertexAttribute]>;
 static_assertions::assert_impl_all!(VertexBufferLayout<'_>: Send, Sync);
 
 /// Describes the vertex processing in a render pipeline.
@@ -193,3 +183,86 @@ pub struct RenderPipelineDescriptor<'a> {
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(RenderPipelineDescriptor<'_>: Send, Sync);
+
+/// Describes the task stage in a mesh pipeline.
+///
+/// For use in [`MeshPipelineDescriptor`].
+#[derive(Clone, Debug)]
+pub struct TaskState<'a> {
+    /// The compiled shader module for this stage.
+    pub module: &'a ShaderModule,
+    /// The name of the entry point in the compiled shader to use.
+    ///
+    /// If [`Some`], there must be a task-stage shader entry point with this name in `module`.
+    /// Otherwise, expect exactly one task-stage entry point in `module`, which will be
+    /// selected.
+    pub entry_point: Option<&'a str>,
+    /// Advanced options for when this pipeline is compiled
+    ///
+    /// This implements `Default`, and for most users can be set to `Default::default()`
+    pub compilation_options: PipelineCompilationOptions<'a>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(TaskState<'_>: Send, Sync);
+
+/// Describes the mesh stage in a mesh pipeline.
+///
+/// For use in [`MeshPipelineDescriptor`].
+#[derive(Clone, Debug)]
+pub struct MeshState<'a> {
+    /// The compiled shader module for this stage.
+    pub module: &'a ShaderModule,
+    /// The name of the entry point in the compiled shader to use.
+    ///
+    /// If [`Some`], there must be a mesh-stage shader entry point with this name in `module`.
+    /// Otherwise, expect exactly one mesh-stage entry point in `module`, which will be
+    /// selected.
+    pub entry_point: Option<&'a str>,
+    /// Advanced options for when this pipeline is compiled
+    ///
+    /// This implements `Default`, and for most users can be set to `Default::default()`
+    pub compilation_options: PipelineCompilationOptions<'a>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(MeshState<'_>: Send, Sync);
+
+/// Describes a mesh shader pipeline.
+///
+/// A mesh pipeline is a [`RenderPipeline`] whose primitives are generated by workgroups of
+/// a mesh shader, optionally launched by a task shader, instead of being fetched from vertex
+/// buffers and transformed by a vertex shader. It is drawn with
+/// [`RenderPass::draw_mesh_tasks`] and the related indirect methods.
+///
+/// For use with [`Device::create_mesh_pipeline`].
+/// Requires [`Features::EXPERIMENTAL_MESH_SHADER`].
+#[derive(Clone, Debug)]
+pub struct MeshPipelineDescriptor<'a> {
+    /// Debug label of the pipeline. This will show up in graphics debuggers for easy identification.
+    pub label: Label<'a>,
+    /// The layout of bind groups for this pipeline.
+    ///
+    /// See [`RenderPipelineDescriptor::layout`] for details.
+    pub layout: Option<&'a PipelineLayout>,
+    /// The compiled task stage and its entry point, if any.
+    pub task: Option<TaskState<'a>>,
+    /// The compiled mesh stage and its entry point.
+    pub mesh: MeshState<'a>,
+    /// The properties of the pipeline at the primitive assembly and rasterization level.
+    pub primitive: PrimitiveState,
+    /// The effect of draw calls on the depth and stencil aspects of the output target, if any.
+    pub depth_stencil: Option<DepthStencilState>,
+    /// The multi-sampling properties of the pipeline.
+    pub multisample: MultisampleState,
+    /// The compiled fragment stage, its entry point, and the color targets.
+    pub fragment: Option<FragmentState<'a>>,
+    /// If the pipeline will be used with a multiview render pass, this indicates how many array
+    /// layers the attachments will have.
+    ///
+    /// Requires [`Features::EXPERIMENTAL_MESH_SHADER_MULTIVIEW`] in addition to
+    /// [`Features::MULTIVIEW`].
+    pub multiview: Option<NonZeroU32>,
+    /// The pipeline cache to use when creating this pipeline.
+    pub cache: Option<&'a PipelineCache>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(MeshPipelineDescriptor<'_>: Send, Sync);
@@ -26,6 +26,59 @@ impl ShaderModule {
         self.inner.get_compilation_info()
     }
 
+    /// Look up the `(group, binding)` pair of the resource variable named `name`.
+    ///
+    /// For example, for a module declaring
+    /// `@group(1) @binding(2) var my_texture: texture_2d<f32>;`,
+    /// `module.binding("my_texture")` returns `Some((1, 2))`.
+    ///
+    /// Returns `None` if the module doesn't declare a resource with that name, or if
+    /// the module isn't reflected: modules created with
+    /// [`Device::create_shader_module_passthrough()`](crate::Device::create_shader_module_passthrough),
+    /// invalid modules, and, on the WebGPU backend, WGSL modules when none of the `naga-ir`,
+    /// `spirv` and `glsl` features is enabled. WebGPU doesn't expose shader reflection,
+    /// so there the module is parsed by Naga the first time this is called.
+    pub fn binding(&self, name: &str) -> Option<(u32, u32)> {
+        self.inner.binding(name)
+    }
+
+    /// Look up the workgroup memory used by the compute, task or mesh entry point named
+    /// `entry_point`, and whether pipelines zero-initialize it before the entry point runs.
+    ///
+    /// For example, for a module declaring `var<workgroup> tile: array<u32, 64>;` and
+    /// a compute entry point `main` using it, `module.workgroup_memory("main")` returns
+    /// a [`WorkgroupMemoryInfo`] with a `size` of 256.
+    ///
+    /// Returns `None` if the module doesn't declare such an entry point, or for the
+    /// modules which [`binding()`](Self::binding) can't look into.
+    pub fn workgroup_memory(&self, entry_point: &str) -> Option<WorkgroupMemoryInfo> {
+        self.inner.workgroup_memory(entry_point)
+    }
+
+    /// Check that the uniform or storage buffer variable named `name` has the memory layout
+    /// of `T`.
+    ///
+    /// Each scalar, vector and matrix of the variable's type must be at the same offset as a
+    /// value of the same type in `T`, and arrays must have the same length and stride. This
+    /// catches Rust types which have drifted from the shader, or which are missing padding,
+    /// right after the module is created instead of as garbage in the shader's output.
+    /// See [`ShaderType`] for how Rust types are matched with WGSL types.
+    ///
+    /// Returns [`ShaderLayoutError::NotReflected`] for the modules which
+    /// [`binding()`](Self::binding) can't look into.
+    ///
+    /// ```no_run
+    /// # let module: wgpu::ShaderModule = todo!();
+    /// // For `@group(0) @binding(0) var<uniform> bones: array<mat4x4<f32>, 64>;`
+    /// module.check_layout::<[[[f32; 4]; 4]; 64]>("bones").unwrap();
+    /// ```
+    pub fn check_layout<T: ShaderType + ?Sized>(
+        &self,
+        name: &str,
+    ) -> Result<(), ShaderLayoutError> {
+        self.inner.check_layout(name, &T::LAYOUT)
+    }
+
     #[cfg(custom)]
     /// Returns custom implementation of ShaderModule (if custom backend and is internally T)
     pub fn as_custom<T: custom::ShaderModuleInterface>(&self) -> Option<&T> {
@@ -36,7 +89,8 @@ impl ShaderModule {
 /// Compilation information for a shader module.
 ///
 /// Corresponds to [WebGPU `GPUCompilationInfo`](https://gpuweb.github.io/gpuweb/#gpucompilationinfo).
-/// The source locations use bytes, and index a UTF-8 encoded string.
+/// The source locations are given both in bytes, indexing a UTF-8 encoded string, and in
+/// UTF-16 code units, like in WebGPU.
 #[derive(Debug, Clone)]
 pub struct CompilationInfo {
     /// The messages from the shader compilation process.
@@ -46,15 +100,20 @@ pub struct CompilationInfo {
 /// A single message from the shader compilation process.
 ///
 /// Roughly corresponds to [`GPUCompilationMessage`](https://www.w3.org/TR/webgpu/#gpucompilationmessage),
-/// except that the location uses UTF-8 for all positions.
+/// except that the location also has positions in UTF-8.
 #[derive(Debug, Clone)]
 pub struct CompilationMessage {
     /// The text of the message.
+    ///
+    /// This is the message alone, without the source code around `location`.
     pub message: String,
     /// The type of the message.
     pub message_type: CompilationMessageType,
     /// Where in the source code the message points at.
     pub location: Option<SourceLocation>,
+    /// The name of the file `location` is in, if the shader is made of several files, as
+    /// with [`ShaderSource::WgslFiles`].
+    pub file_name: Option<String>,
 }
 
 /// The type of a compilation message.
@@ -70,10 +129,11 @@ pub enum CompilationMessageType {
 
 /// A human-readable representation for a span, tailored for text source.
 ///
-/// Roughly corresponds to the positional members of [`GPUCompilationMessage`][gcm] from
-/// the WebGPU specification, except
-/// - `offset` and `length` are in bytes (UTF-8 code units), instead of UTF-16 code units.
-/// - `line_position` is in bytes (UTF-8 code units), and is usually not directly intended for humans.
+/// Corresponds to the positional members of [`GPUCompilationMessage`][gcm] from
+/// the WebGPU specification, which are the `utf16_*` fields, with
+/// - `offset` and `length` also in bytes (UTF-8 code units), to index a Rust string.
+/// - `line_position` also in bytes (UTF-8 code units), which is usually not directly intended
+///   for humans. Editors usually count columns in UTF-16 code units, like `utf16_line_position`.
 ///
 /// [gcm]: https://www.w3.org/TR/webgpu/#gpucompilationmessage
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -87,20 +147,69 @@ pub struct SourceLocation {
     pub offset: u32,
     /// Length in code units (in bytes) of the span.
     pub length: u32,
+    /// 1-based column in UTF-16 code units of the start of the span.
+    pub utf16_line_position: u32,
+    /// 0-based offset in UTF-16 code units of the start of the span.
+    pub utf16_offset: u32,
+    /// Length in UTF-16 code units of the span.
+    pub utf16_length: u32,
+}
+
+#[cfg(any(wgpu_core, naga))]
+impl SourceLocation {
+    /// Converts a location in `source` from Naga, adding the UTF-16 positions.
+    fn from_naga(location: crate::naga::SourceLocation, source: &str) -> Self {
+        let utf16_len = |range: core::ops::Range<usize>| {
+            source
+                .get(range)
+                .map_or(0, |text| text.encode_utf16().count() as u32)
+        };
+        let start = location.offset as usize;
+        let line_start = start + 1 - location.line_position as usize;
+        SourceLocation {
+            line_number: location.line_number,
+            line_position: location.line_position,
+            offset: location.offset,
+            length: location.length,
+            utf16_line_position: utf16_len(line_start..start) + 1,
+            utf16_offset: utf16_len(0..start),
+            utf16_length: utf16_len(start..start + location.length as usize),
+        }
+    }
+}
+
+#[cfg(any(wgpu_core, naga))]
+impl CompilationMessage {
+    /// An error message about the shader of `error`, pointing at `span`.
+    fn from_naga_error<E>(
+        error: &crate::naga::error::ShaderError<E>,
+        message: String,
+        span: Option<crate::naga::Span>,
+    ) -> Self {
+        use alloc::string::ToString;
+        let file = span
+            .filter(|_| !error.source.is_empty())
+            .and_then(|span| error.file(span));
+        CompilationMessage {
+            message,
+            message_type: CompilationMessageType::Error,
+            location: file
+                .map(|(_, source, span)| SourceLocation::from_naga(span.location(source), source)),
+            file_name: file.and_then(|(file_name, _, _)| file_name.map(ToString::to_string)),
+        }
+    }
 }
 
-#[cfg(all(feature = "wgsl", wgpu_core))]
+#[cfg(all(feature = "wgsl", any(wgpu_core, naga)))]
 impl From<crate::naga::error::ShaderError<crate::naga::front::wgsl::ParseError>>
     for CompilationInfo
 {
     fn from(value: crate::naga::error::ShaderError<crate::naga::front::wgsl::ParseError>) -> Self {
         use alloc::{string::ToString, vec};
+        let span = value.inner.labels().next().map(|(span, _)| span);
+        let message = value.inner.message().to_string();
         CompilationInfo {
-            messages: vec![CompilationMessage {
-                message: value.to_string(),
-                message_type: CompilationMessageType::Error,
-                location: value.inner.location(&value.source).map(Into::into),
-            }],
+            messages: vec![CompilationMessage::from_naga_error(&value, message, span)],
         }
     }
 }
@@ -111,12 +220,8 @@ impl From<naga::error::ShaderError<naga::front::glsl::ParseErrors>> for Compilat
         let messages = value
             .inner
             .errors
-            .into_iter()
-            .map(|err| CompilationMessage {
-                message: err.to_string(),
-                message_type: CompilationMessageType::Error,
-                location: err.location(&value.source).map(Into::into),
-            })
+            .iter()
+            .map(|err| CompilationMessage::from_naga_error(&value, err.to_string(), Some(err.meta)))
             .collect();
         CompilationInfo { messages }
     }
@@ -126,12 +231,9 @@ impl From<naga::error::ShaderError<naga::front::glsl::ParseErrors>> for Compilat
 impl From<naga::error::ShaderError<naga::front::spv::Error>> for CompilationInfo {
     fn from(value: naga::error::ShaderError<naga::front::spv::Error>) -> Self {
         use alloc::{string::ToString, vec};
+        let message = value.inner.to_string();
         CompilationInfo {
-            messages: vec![CompilationMessage {
-                message: value.to_string(),
-                message_type: CompilationMessageType::Error,
-                location: None,
-            }],
+            messages: vec![CompilationMessage::from_naga_error(&value, message, None)],
         }
     }
 }
@@ -148,24 +250,20 @@ impl
         >,
     ) -> Self {
         use alloc::{string::ToString, vec};
-        CompilationInfo {
-            messages: vec![CompilationMessage {
-                message: value.to_string(),
-                message_type: CompilationMessageType::Error,
-                location: value.inner.location(&value.source).map(Into::into),
-            }],
-        }
-    }
-}
+        use core::error::Error;
 
-#[cfg(any(wgpu_core, naga))]
-impl From<crate::naga::SourceLocation> for SourceLocation {
-    fn from(value: crate::naga::SourceLocation) -> Self {
-        SourceLocation {
-            length: value.length,
-            offset: value.offset,
-            line_number: value.line_number,
-            line_position: value.line_position,
+        // The outer errors only say which function or expression is invalid, so the
+        // message is made of the whole chain, like the notes of the formatted error.
+        let mut message = value.inner.to_string();
+        let mut source: &dyn Error = &*value.inner;
+        while let Some(next) = source.source() {
+            message.push_str(": ");
+            message.push_str(&next.to_string());
+            source = next;
+        }
+        let span = value.inner.spans().next().map(|&(span, _)| span);
+        CompilationInfo {
+            messages: vec![CompilationMessage::from_naga_error(&value, message, span)],
         }
     }
 }
@@ -205,6 +303,35 @@ pub enum ShaderSource<'a> {
     /// WGSL module as a string slice.
     #[cfg(feature = "wgsl")]
     Wgsl(alloc::borrow::Cow<'a, str>),
+    /// WGSL module as a string slice, with defines for conditional compilation.
+    ///
+    /// Declarations, struct members and compound statements can be marked with an
+    /// `@if(…)` attribute, and are left out of the module unless its condition holds, for
+    /// example `@if(shadows && !mobile)`. Conditions are made of defines, `true`, `false`,
+    /// `!`, `&&`, `||` and parentheses. This lets permutations of a shader share one source
+    /// file, whose error messages point at the lines of that file.
+    ///
+    /// On WebGPU, this requires the `naga-ir` feature, since the module is converted to
+    /// WGSL without the attributes by Naga.
+    #[cfg(feature = "wgsl")]
+    WgslWithDefines {
+        /// The source code of the shader.
+        shader: alloc::borrow::Cow<'a, str>,
+        /// The defines which are `true` in the conditions of `@if(…)` attributes. Any other
+        /// name is `false`.
+        defines: &'a [&'a str],
+    },
+    /// WGSL module made of several named files, as `(name, source)` pairs.
+    ///
+    /// The files are compiled as one module, as if they were a single file, so they share
+    /// one namespace. Unlike concatenating the files yourself, error messages and
+    /// [`CompilationMessage::file_name`] name the file a message points at, and its
+    /// [`location`](CompilationMessage::location) is in that file. `import` directives in
+    /// the files must name other files of the list, and have no other effect.
+    ///
+    /// On WebGPU, this requires the `naga-ir` feature, since the files are combined by Naga.
+    #[cfg(feature = "wgsl")]
+    WgslFiles(&'a [(&'a str, &'a str)]),
     /// Naga module.
     #[cfg(feature = "naga-ir")]
     Naga(alloc::borrow::Cow<'static, naga::Module>),
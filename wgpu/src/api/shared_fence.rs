@@ -0,0 +1,54 @@
+use crate::*;
+
+/// Handle to a fence that can be shared with other APIs and processes.
+///
+/// It can be created with [`Device::create_shared_fence`] and exported with
+/// [`SharedFence::export`], or imported from elsewhere with
+/// [`Device::import_shared_fence`]. Submissions can wait on and signal shared
+/// fences with [`Queue::submit_with_external_sync`].
+///
+/// A shared fence holds a 64-bit value that only ever increases.
+///
+/// This type is unique to the Rust API of `wgpu`. It requires
+/// [`Features::EXPERIMENTAL_SHARED_FENCE`].
+#[derive(Debug, Clone)]
+pub struct SharedFence {
+    pub(crate) inner: dispatch::DispatchSharedFence,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(SharedFence: Send, Sync);
+
+crate::cmp::impl_eq_ord_hash_proxy!(SharedFence => .inner);
+
+impl SharedFence {
+    /// Exports a handle to this fence, so that it can be waited on and signaled
+    /// outside of `wgpu`.
+    ///
+    /// See [`SharedFenceHandle`] for the kind of handle returned on each backend,
+    /// and who owns it.
+    ///
+    /// Returns `None` if the fence could not be exported. The error is
+    /// reported to the device's error handler.
+    pub fn export(&self) -> Option<SharedFenceHandle> {
+        self.inner.export()
+    }
+
+    #[cfg(custom)]
+    /// Returns custom implementation of SharedFence (if custom backend and is internally T)
+    pub fn as_custom<T: custom::SharedFenceInterface>(&self) -> Option<&T> {
+        self.inner.as_custom()
+    }
+}
+
+/// Describes a [`SharedFence`].
+///
+/// For use with [`Device::create_shared_fence`] and [`Device::import_shared_fence`].
+pub type SharedFenceDescriptor<'a> = wgt::SharedFenceDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(SharedFenceDescriptor<'_>: Send, Sync);
+
+/// A [`SharedFence`] and a value of it to wait for or signal.
+///
+/// For use with [`Queue::submit_with_external_sync`].
+pub type SharedFenceValue<'a> = wgt::SharedFenceValue<&'a SharedFence>;
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(SharedFenceValue<'_>: Send, Sync);
@@ -8,6 +8,26 @@ use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use crate::util::Mutex;
 use crate::*;
 
+/// A physical display that can be presented to directly, without a windowing system.
+///
+/// Returned by [`Instance::enumerate_displays`], and used to create a surface with
+/// [`Instance::create_surface_from_display`].
+#[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+#[derive(Clone, Debug)]
+pub struct Display {
+    pub(crate) inner: alloc::sync::Arc<wgc::instance::Display>,
+}
+#[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+static_assertions::assert_impl_all!(Display: Send, Sync);
+
+#[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+impl Display {
+    /// Returns information about the display, including the modes it supports.
+    pub fn info(&self) -> &DisplayInfo {
+        self.inner.info()
+    }
+}
+
 /// Describes a [`Surface`].
 ///
 /// For use with [`Surface::configure`].
@@ -72,6 +92,9 @@ impl Surface<'_> {
             desired_maximum_frame_latency: 2,
             present_mode: *caps.present_modes.first()?,
             alpha_mode: wgt::CompositeAlphaMode::Auto,
+            color_space: wgt::SurfaceColorSpace::Auto,
+            hdr_metadata: None,
+            exclusive_fullscreen: false,
             view_formats: vec![],
         })
     }
@@ -91,6 +114,13 @@ impl Surface<'_> {
     /// - Texture format requested is unsupported on the surface.
     /// - `config.width` or `config.height` is zero.
     pub fn configure(&self, device: &Device, config: &SurfaceConfiguration) {
+        api_span!(
+            "Surface::configure",
+            width = config.width,
+            height = config.height,
+            format = ?config.format,
+            present_mode = ?config.present_mode
+        );
         self.inner.configure(&device.inner, config);
 
         let mut conf = self.config.lock();
@@ -106,7 +136,48 @@ impl Surface<'_> {
     /// If a SurfaceTexture referencing this surface is alive when the swapchain is recreated,
     /// recreating the swapchain will panic.
     pub fn get_current_texture(&self) -> Result<SurfaceTexture, SurfaceError> {
-        let (texture, status, detail) = self.inner.get_current_texture();
+        self.get_current_texture_impl(None)
+    }
+
+    /// Returns the next texture to be presented by the swapchain for drawing,
+    /// waiting at most `timeout` for one to become available.
+    ///
+    /// Returns [`SurfaceError::Timeout`] if no texture became available in time.
+    /// Otherwise behaves like [`Surface::get_current_texture`].
+    ///
+    /// # Platform dependent behavior
+    ///
+    /// - Metal, OpenGL and WebGPU ignore the timeout.
+    /// - Vulkan on Android before Android 11 ignores the timeout.
+    pub fn get_current_texture_with_timeout(
+        &self,
+        timeout: core::time::Duration,
+    ) -> Result<SurfaceTexture, SurfaceError> {
+        self.get_current_texture_impl(Some(timeout))
+    }
+
+    /// Returns the next texture to be presented by the swapchain for drawing, if one
+    /// is available without waiting.
+    ///
+    /// Returns [`SurfaceError::WouldBlock`] if the presentation engine still holds all
+    /// textures, so the calling thread can do other work before trying again.
+    /// Otherwise behaves like [`Surface::get_current_texture`].
+    ///
+    /// On platforms that ignore the timeout of
+    /// [`Surface::get_current_texture_with_timeout`], this may block.
+    pub fn try_get_current_texture(&self) -> Result<SurfaceTexture, SurfaceError> {
+        match self.get_current_texture_impl(Some(core::time::Duration::ZERO)) {
+            Err(SurfaceError::Timeout) => Err(SurfaceError::WouldBlock),
+            result => result,
+        }
+    }
+
+    fn get_current_texture_impl(
+        &self,
+        timeout: Option<core::time::Duration>,
+    ) -> Result<SurfaceTexture, SurfaceError> {
+        api_span!("Surface::get_current_texture", timeout = ?timeout);
+        let (texture, status, detail) = self.inner.get_current_texture(timeout);
 
         let suboptimal = match status {
             SurfaceStatus::Good => false,
@@ -150,6 +221,38 @@ impl Surface<'_> {
             .ok_or(SurfaceError::Lost)
     }
 
+    /// Returns feedback about the frames presented on this surface that the
+    /// presentation engine finished with since the previous call, in present order.
+    ///
+    /// The [`PresentationStatistics::present_id`] of a frame is its number among the
+    /// presents since the surface was last configured, starting at 1. Frames that are
+    /// still waiting to be displayed are reported by a later call.
+    ///
+    /// Requires [`Features::PRESENTATION_TIMING`].
+    ///
+    /// # Platform dependent behavior
+    ///
+    /// - DX12 only reports the latest displayed frame, so not every present is reported,
+    ///   and never reports dropped frames.
+    /// - Metal doesn't report the refresh interval.
+    pub fn get_presentation_statistics(&self) -> Vec<PresentationStatistics> {
+        self.inner.get_presentation_statistics()
+    }
+
+    /// Returns how frames presented to this surface currently reach the display,
+    /// for example whether [`SurfaceConfiguration::exclusive_fullscreen`] was honored.
+    ///
+    /// The surface must be configured.
+    ///
+    /// # Platform dependent behavior
+    ///
+    /// - DX12 reports exclusive fullscreen, and independent flip once a frame was displayed.
+    /// - Vulkan, Metal and OpenGL always report [`PresentationPath::Unknown`].
+    /// - WebGPU and WebGL always report [`PresentationPath::Composed`].
+    pub fn presentation_path(&self) -> PresentationPath {
+        self.inner.presentation_path()
+    }
+
     /// Get the [`wgpu_hal`] surface from this `Surface`.
     ///
     /// Find the Api struct corresponding to the active backend in [`wgpu_hal::api`],
@@ -181,6 +284,27 @@ impl Surface<'_> {
         unsafe { core_surface.context.surface_as_hal::<A>(core_surface) }
     }
 
+    /// Takes the frames presented to a surface created with
+    /// [`Instance::create_headless_surface`] since the previous call, oldest first.
+    ///
+    /// Only the most recent 16 frames are kept. Returns `None` if this surface is
+    /// not backed by `wgpu-core`.
+    #[cfg(wgpu_core)]
+    pub fn take_presented_frames(&self) -> Option<Vec<Texture>> {
+        let core_surface = self.inner.as_core_opt()?;
+
+        let frames = core_surface
+            .context
+            .surface_take_presented_frames(core_surface)
+            .into_iter()
+            .map(|(texture, desc)| Texture {
+                inner: texture.into(),
+                descriptor: desc.map_label_and_view_formats(|_| None, |_| &[][..]),
+            })
+            .collect();
+        Some(frames)
+    }
+
     #[cfg(custom)]
     /// Returns custom implementation of Surface (if custom backend and is internally T)
     pub fn as_custom<T: custom::SurfaceInterface>(&self) -> Option<&T> {
@@ -329,6 +453,22 @@ pub enum SurfaceTargetUnsafe {
         refresh_rate: u32,
     },
 
+    /// Surface presenting directly to a physical display, without a windowing system.
+    ///
+    /// See [`Instance::enumerate_displays`].
+    ///
+    /// # Safety
+    ///
+    /// - `display` must have been returned by [`Instance::enumerate_displays`] on the same instance.
+    /// - The display must not be in use by a windowing system or another surface.
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    Display {
+        /// The display to present to.
+        display: Display,
+        /// The mode to drive the display with. Must be one of `display.info().modes`.
+        mode: DisplayMode,
+    },
+
     /// Surface from `CoreAnimationLayer`.
     ///
     /// # Safety
@@ -402,6 +542,10 @@ pub(crate) enum CreateSurfaceErrorKind {
     /// Error when trying to get a [`DisplayHandle`] or a [`WindowHandle`] from
     /// `raw_window_handle`.
     RawHandle(raw_window_handle::HandleError),
+
+    /// Error reported by a custom backend.
+    #[cfg(custom)]
+    Custom(String),
 }
 static_assertions::assert_impl_all!(CreateSurfaceError: Send, Sync);
 
@@ -412,6 +556,8 @@ impl fmt::Display for CreateSurfaceError {
             CreateSurfaceErrorKind::Hal(e) => e.fmt(f),
             CreateSurfaceErrorKind::Web(e) => e.fmt(f),
             CreateSurfaceErrorKind::RawHandle(e) => e.fmt(f),
+            #[cfg(custom)]
+            CreateSurfaceErrorKind::Custom(e) => e.fmt(f),
         }
     }
 }
@@ -426,6 +572,18 @@ impl error::Error for CreateSurfaceError {
             CreateSurfaceErrorKind::RawHandle(e) => e.source(),
             #[cfg(not(feature = "std"))]
             CreateSurfaceErrorKind::RawHandle(_) => None,
+            #[cfg(custom)]
+            CreateSurfaceErrorKind::Custom(_) => None,
+        }
+    }
+}
+
+impl CreateSurfaceError {
+    /// Creates an error for custom backends, described by `message`.
+    #[cfg(custom)]
+    pub fn from_custom(message: impl Into<String>) -> Self {
+        Self {
+            inner: CreateSurfaceErrorKind::Custom(message.into()),
         }
     }
 }
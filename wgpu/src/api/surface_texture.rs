@@ -34,8 +34,40 @@ impl SurfaceTexture {
     /// state. If it is desired to do things such as request a frame callback, scale the surface using the viewporter
     /// or synchronize other double buffered state, then these operations should be done before the call to `present`.
     pub fn present(mut self) {
+        api_span!("SurfaceTexture::present");
         self.presented = true;
-        self.detail.present();
+        self.detail.present(&[]);
+    }
+
+    /// Schedule this texture to be presented, hinting that only the `damage` regions
+    /// changed since the previous present.
+    ///
+    /// The rectangles are in texels, with the origin at the top left corner, and are
+    /// clipped to the texture's extent. The texture must still contain the complete
+    /// frame: the damage only lets the compositor skip the unchanged parts. An empty
+    /// slice behaves like [`SurfaceTexture::present`].
+    ///
+    /// # Platform dependent behavior
+    ///
+    /// - Vulkan passes the damage with `VK_KHR_incremental_present`, if available.
+    /// - EGL passes the damage with `eglSwapBuffersWithDamageKHR`, if available.
+    /// - DX12 passes the damage as dirty rectangles to `IDXGISwapChain1::Present1`.
+    /// - Other backends ignore the damage.
+    pub fn present_with_damage(mut self, damage: &[Rect]) {
+        api_span!("SurfaceTexture::present_with_damage", rects = damage.len());
+        self.presented = true;
+        self.detail.present(damage);
+    }
+
+    /// Requests that this texture isn't displayed before `time` once it is presented.
+    ///
+    /// `time` is in the time domain of [`Adapter::get_presentation_timestamp`]. The request
+    /// is a hint: compare the present times reported by
+    /// [`Surface::get_presentation_statistics`] to check how it was honored.
+    ///
+    /// Requires [`Features::PRESENTATION_TIMING`]. This is ignored on DX12.
+    pub fn set_target_present_time(&self, time: PresentationTimestamp) {
+        self.detail.set_target_present_time(time);
     }
 
     #[cfg(custom)]
@@ -58,6 +90,10 @@ impl Drop for SurfaceTexture {
 pub enum SurfaceError {
     /// A timeout was encountered while trying to acquire the next frame.
     Timeout,
+    /// No frame could be acquired without waiting.
+    ///
+    /// Only returned by [`Surface::try_get_current_texture`].
+    WouldBlock,
     /// The underlying surface has changed, and therefore the swap chain must be updated.
     Outdated,
     /// The swap chain has been lost and needs to be recreated.
@@ -76,6 +112,7 @@ impl fmt::Display for SurfaceError {
             "{}",
             match self {
                 Self::Timeout => "A timeout was encountered while trying to acquire the next frame",
+                Self::WouldBlock => "No frame could be acquired without waiting",
                 Self::Outdated =>
                     "The underlying surface has changed, and therefore the swap chain must be updated",
                 Self::Lost => "The swap chain has been lost and needs to be recreated",
@@ -68,6 +68,7 @@ impl Texture {
     /// Texture views are needed to use a texture as a binding in a [`BindGroup`]
     /// or as an attachment in a [`RenderPass`].
     pub fn create_view(&self, desc: &TextureViewDescriptor<'_>) -> TextureView {
+        api_span!("Texture::create_view", label = desc.label);
         let view = self.inner.create_view(desc);
 
         TextureView {
@@ -78,6 +79,7 @@ impl Texture {
 
     /// Destroy the associated native resources as soon as possible.
     pub fn destroy(&self) {
+        api_span!("Texture::destroy");
         self.inner.destroy();
     }
 
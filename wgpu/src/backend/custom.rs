@@ -1,4 +1,31 @@
-//! Provides wrappers custom backend implementations
+//! Custom backends, which implement the wgpu API with types of their own.
+//!
+//! A custom backend can forward the calls made through wgpu to another implementation,
+//! such as a process it communicates with over IPC, or record them for tests.
+//!
+//! # Implementing a backend
+//!
+//! Each type of the wgpu API is backed by an object implementing the corresponding interface
+//! trait of this module, such as [`DeviceInterface`] for [`Device`](crate::Device). A backend
+//! starts with an instance, which is wrapped with [`Instance::from_custom`](crate::Instance::from_custom).
+//! The objects it creates are returned wrapped in the corresponding dispatch type, such as
+//! [`DispatchBuffer::custom`] for the buffers of [`DeviceInterface::create_buffer`]. Objects passed
+//! back to the backend as arguments are unwrapped with the `as_custom` methods of the
+//! dispatch types, which return `None` for objects of other backends.
+//!
+//! The methods of the interface traits are called by the methods of the same name of the wgpu
+//! API types, which document them. The arguments have already been converted to the types
+//! of the interfaces, but haven't been validated: validation is up to the backend.
+//!
+//! Objects are destroyed by dropping them, so a backend which needs to release resources
+//! elsewhere should implement [`Drop`] for them. Objects which can be cloned in the API are
+//! shared by reference counting, and objects are compared and hashed by their address.
+//!
+//! # Stability
+//!
+//! The interface traits follow the versioning of wgpu: methods are only added or changed in
+//! breaking releases, and the changes are listed in the changelog. Methods which need features
+//! a backend doesn't report from [`AdapterInterface::features`] may panic.
 
 #![allow(ambiguous_wide_pointer_comparisons)]
 
@@ -82,9 +109,13 @@ dyn_type!(pub ref struct DynTexture(dyn TextureInterface));
 dyn_type!(pub ref struct DynBlas(dyn BlasInterface));
 dyn_type!(pub ref struct DynTlas(dyn TlasInterface));
 dyn_type!(pub ref struct DynQuerySet(dyn QuerySetInterface));
+dyn_type!(pub ref struct DynSharedFence(dyn SharedFenceInterface));
+dyn_type!(pub ref struct DynMemoryHeap(dyn MemoryHeapInterface));
+dyn_type!(pub ref struct DynExternalTexture(dyn ExternalTextureInterface));
 dyn_type!(pub ref struct DynPipelineLayout(dyn PipelineLayoutInterface));
 dyn_type!(pub ref struct DynRenderPipeline(dyn RenderPipelineInterface));
 dyn_type!(pub ref struct DynComputePipeline(dyn ComputePipelineInterface));
+dyn_type!(pub ref struct DynRayTracingPipeline(dyn RayTracingPipelineInterface));
 dyn_type!(pub ref struct DynPipelineCache(dyn PipelineCacheInterface));
 dyn_type!(pub mut struct DynCommandEncoder(dyn CommandEncoderInterface));
 dyn_type!(pub mut struct DynComputePass(dyn ComputePassInterface));
@@ -29,7 +29,7 @@ use wasm_bindgen::{JsCast, prelude::*};
 
 use crate::{
     Blas, SurfaceTargetUnsafe, Tlas,
-    dispatch::{self, BlasCompactCallback},
+    dispatch::{self, BlasCompactCallback, BlasSerializeCallback},
 };
 
 use defined_non_null_js_value::DefinedNonNullJsValue;
@@ -89,6 +89,10 @@ impl crate::Error {
 pub struct WebShaderModule {
     module: webgpu_sys::GpuShaderModule,
     compilation_info: WebShaderCompilationInfo,
+    /// Names and `(group, binding)` pairs of the module's resource variables, reflected
+    /// by Naga since the WebGPU API doesn't expose them. Transformed modules fill this
+    /// in when they are created, WGSL modules are only parsed by the first lookup.
+    bindings: OnceCell<Vec<(String, u32, u32)>>,
     /// Unique identifier for this shader module.
     ident: crate::cmp::Identifier,
 }
@@ -125,6 +129,19 @@ fn map_utf16_to_utf8_offset(utf16_offset: u32, text: &str) -> u32 {
     }
 }
 
+/// Collects the names and `(group, binding)` pairs of the resource variables of `module`.
+#[cfg(naga)]
+fn reflect_bindings(module: &naga::Module) -> Vec<(String, u32, u32)> {
+    module
+        .global_variables
+        .iter()
+        .filter_map(|(_, var)| {
+            let binding = var.binding.as_ref()?;
+            Some((var.name.clone()?, binding.group, binding.binding))
+        })
+        .collect()
+}
+
 impl crate::CompilationMessage {
     fn from_js(
         js_message: webgpu_sys::GpuCompilationMessage,
@@ -156,6 +173,9 @@ impl crate::CompilationMessage {
                     length,
                     line_number,
                     line_position,
+                    utf16_line_position: js_message.line_pos() as u32,
+                    utf16_offset,
+                    utf16_length,
                 })
             }
             WebShaderCompilationInfo::Transformed { .. } => None,
@@ -165,6 +185,7 @@ impl crate::CompilationMessage {
             message: js_message.message(),
             message_type,
             location: span,
+            file_name: None,
         }
     }
 }
@@ -498,6 +519,7 @@ fn map_index_format(format: wgt::IndexFormat) -> webgpu_sys::GpuIndexFormat {
     use webgpu_sys::GpuIndexFormat as f;
     use wgt::IndexFormat;
     match format {
+        IndexFormat::Uint8 => unimplemented!("Index format {format:?} has no WebGPU equivalent"),
         IndexFormat::Uint16 => f::Uint16,
         IndexFormat::Uint32 => f::Uint32,
     }
@@ -562,7 +584,10 @@ fn map_vertex_step_mode(mode: wgt::VertexStepMode) -> webgpu_sys::GpuVertexStepM
     use wgt::VertexStepMode;
     match mode {
         VertexStepMode::Vertex => sm::Vertex,
-        VertexStepMode::Instance => sm::Instance,
+        VertexStepMode::Instance | VertexStepMode::InstanceStepRate { rate: 1 } => sm::Instance,
+        VertexStepMode::InstanceStepRate { rate } => {
+            unimplemented!("Instance step rate {rate} has no WebGPU equivalent")
+        }
     }
 }
 
@@ -832,6 +857,7 @@ fn map_wgt_limits(limits: webgpu_sys::GpuSupportedLimits) -> wgt::Limits {
         max_tlas_instance_count: wgt::Limits::default().max_tlas_instance_count,
         max_acceleration_structures_per_shader_stage: wgt::Limits::default()
             .max_acceleration_structures_per_shader_stage,
+        max_ray_recursion_depth: wgt::Limits::default().max_ray_recursion_depth,
     }
 }
 
@@ -987,6 +1013,7 @@ fn future_compilation_info(
                 message: "Getting compilation info failed".to_string(),
                 message_type: crate::CompilationMessageType::Error,
                 location: None,
+                file_name: None,
             }))
             .collect(),
     };
@@ -1267,6 +1294,12 @@ pub struct WebBlas {
     ident: crate::cmp::Identifier,
 }
 
+#[derive(Debug)]
+pub struct WebRayTracingPipeline {
+    /// Unique identifier for this RayTracingPipeline.
+    ident: crate::cmp::Identifier,
+}
+
 #[derive(Debug)]
 pub struct WebTlas {
     /// Unique identifier for this Blas.
@@ -1280,6 +1313,25 @@ pub struct WebQuerySet {
     ident: crate::cmp::Identifier,
 }
 
+#[derive(Debug)]
+pub struct WebSharedFence {
+    /// Unique identifier for this SharedFence.
+    ident: crate::cmp::Identifier,
+}
+
+#[derive(Debug)]
+pub struct WebMemoryHeap {
+    /// Unique identifier for this MemoryHeap.
+    ident: crate::cmp::Identifier,
+}
+
+#[derive(Debug)]
+pub struct WebExternalTexture {
+    pub(crate) inner: webgpu_sys::GpuExternalTexture,
+    /// Unique identifier for this ExternalTexture.
+    ident: crate::cmp::Identifier,
+}
+
 #[derive(Debug)]
 pub struct WebPipelineLayout {
     pub(crate) inner: webgpu_sys::GpuPipelineLayout,
@@ -1324,6 +1376,9 @@ pub struct WebComputePassEncoder {
 #[derive(Debug)]
 pub struct WebRenderPassEncoder {
     pub(crate) inner: webgpu_sys::GpuRenderPassEncoder,
+    /// Whether this is a parallel encoder, which records into the pass that created it
+    /// and doesn't end it.
+    parallel: bool,
     /// Unique identifier for this RenderPassEncoder.
     ident: crate::cmp::Identifier,
 }
@@ -1395,8 +1450,12 @@ impl_send_sync!(WebSampler);
 impl_send_sync!(WebBuffer);
 impl_send_sync!(WebTexture);
 impl_send_sync!(WebBlas);
+impl_send_sync!(WebRayTracingPipeline);
 impl_send_sync!(WebTlas);
 impl_send_sync!(WebQuerySet);
+impl_send_sync!(WebSharedFence);
+impl_send_sync!(WebMemoryHeap);
+impl_send_sync!(WebExternalTexture);
 impl_send_sync!(WebPipelineLayout);
 impl_send_sync!(WebRenderPipeline);
 impl_send_sync!(WebComputePipeline);
@@ -1424,8 +1483,12 @@ crate::cmp::impl_eq_ord_hash_proxy!(WebSampler => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebBuffer => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebTexture => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebBlas => .ident);
+crate::cmp::impl_eq_ord_hash_proxy!(WebRayTracingPipeline => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebTlas => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebQuerySet => .ident);
+crate::cmp::impl_eq_ord_hash_proxy!(WebSharedFence => .ident);
+crate::cmp::impl_eq_ord_hash_proxy!(WebMemoryHeap => .ident);
+crate::cmp::impl_eq_ord_hash_proxy!(WebExternalTexture => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebPipelineLayout => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebRenderPipeline => .ident);
 crate::cmp::impl_eq_ord_hash_proxy!(WebComputePipeline => .ident);
@@ -1562,6 +1625,15 @@ impl dispatch::InstanceInterface for ContextWebGpu {
         true
     }
 
+    unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        // No capturing api in webgpu
+        false
+    }
+
+    unsafe fn stop_graphics_debugger_capture(&self) {
+        // No capturing api in webgpu
+    }
+
     #[cfg(feature = "wgsl")]
     fn wgsl_language_features(&self) -> crate::WgslLanguageFeatures {
         let mut wgsl_language_features = crate::WgslLanguageFeatures::empty();
@@ -1650,6 +1722,26 @@ impl dispatch::AdapterInterface for WebAdapter {
         ))
     }
 
+    fn request_device_with_queues(
+        &self,
+        desc: &crate::DeviceDescriptor<'_>,
+        queues: &[crate::QueueDescriptor<'_>],
+    ) -> Pin<Box<dyn dispatch::RequestDeviceWithQueuesFuture>> {
+        if !queues.is_empty() {
+            return Box::pin(core::future::ready(Err(crate::RequestDeviceError {
+                inner: crate::RequestDeviceErrorKind::WebGpu(String::from(
+                    "WebGPU devices only have a single queue",
+                )),
+            })));
+        }
+
+        let future = self.request_device(desc);
+        Box::pin(async move {
+            let (device, queue) = future.await?;
+            Ok((device, queue, Vec::new()))
+        })
+    }
+
     fn is_surface_supported(&self, _surface: &dispatch::DispatchSurface) -> bool {
         // All surfaces are inherently supported.
         true
@@ -1659,6 +1751,11 @@ impl dispatch::AdapterInterface for WebAdapter {
         map_wgt_features(self.inner.features())
     }
 
+    fn applied_workarounds(&self) -> crate::Toggles {
+        // The browser's workarounds aren't visible through WebGPU.
+        crate::Toggles::empty()
+    }
+
     fn limits(&self) -> crate::Limits {
         map_wgt_limits(self.inner.limits())
     }
@@ -1691,6 +1788,10 @@ impl dispatch::AdapterInterface for WebAdapter {
     fn get_presentation_timestamp(&self) -> crate::PresentationTimestamp {
         crate::PresentationTimestamp::INVALID_TIMESTAMP
     }
+
+    fn memory_info(&self) -> Option<crate::MemoryInfo> {
+        None
+    }
 }
 impl Drop for WebAdapter {
     fn drop(&mut self) {
@@ -1711,6 +1812,7 @@ impl dispatch::DeviceInterface for WebDevice {
         &self,
         desc: crate::ShaderModuleDescriptor<'_>,
         _shader_runtime_checks: crate::ShaderRuntimeChecks,
+        _optimization: crate::ShaderOptimization,
     ) -> dispatch::DispatchShaderModule {
         let shader_module_result = match desc.source {
             #[cfg(feature = "spirv")]
@@ -1728,19 +1830,25 @@ impl dispatch::DeviceInterface for WebDevice {
                     .map_err(|inner| {
                         crate::CompilationInfo::from(naga::error::ShaderError {
                             source: String::new(),
+                            files: Vec::new(),
                             label: desc.label.map(|s| s.to_string()),
                             inner: Box::new(inner),
                         })
                     })
                     .and_then(|spv_module| {
-                        validate_transformed_shader_module(&spv_module, "", &desc).map(|v| {
-                            (
-                                v,
-                                WebShaderCompilationInfo::Transformed {
-                                    compilation_info: crate::CompilationInfo { messages: vec![] },
-                                },
-                            )
-                        })
+                        validate_transformed_shader_module(&spv_module, "", &desc).map(
+                            |(v, bindings)| {
+                                (
+                                    v,
+                                    WebShaderCompilationInfo::Transformed {
+                                        compilation_info: crate::CompilationInfo {
+                                            messages: vec![],
+                                        },
+                                    },
+                                    OnceCell::from(bindings),
+                                )
+                            },
+                        )
                     })
             }
             #[cfg(feature = "glsl")]
@@ -1765,19 +1873,25 @@ impl dispatch::DeviceInterface for WebDevice {
                     .map_err(|inner| {
                         crate::CompilationInfo::from(naga::error::ShaderError {
                             source: shader.to_string(),
+                            files: Vec::new(),
                             label: desc.label.map(|s| s.to_string()),
                             inner: Box::new(inner),
                         })
                     })
                     .and_then(|glsl_module| {
-                        validate_transformed_shader_module(&glsl_module, shader, &desc).map(|v| {
-                            (
-                                v,
-                                WebShaderCompilationInfo::Transformed {
-                                    compilation_info: crate::CompilationInfo { messages: vec![] },
-                                },
-                            )
-                        })
+                        validate_transformed_shader_module(&glsl_module, shader, &desc).map(
+                            |(v, bindings)| {
+                                (
+                                    v,
+                                    WebShaderCompilationInfo::Transformed {
+                                        compilation_info: crate::CompilationInfo {
+                                            messages: vec![],
+                                        },
+                                    },
+                                    OnceCell::from(bindings),
+                                )
+                            },
+                        )
                     })
             }
             #[cfg(feature = "wgsl")]
@@ -1788,16 +1902,110 @@ impl dispatch::DeviceInterface for WebDevice {
                     WebShaderCompilationInfo::Wgsl {
                         source: code.to_string(),
                     },
+                    OnceCell::new(),
                 ))
             }
+            #[cfg(all(feature = "wgsl", naga))]
+            crate::ShaderSource::WgslWithDefines {
+                ref shader,
+                defines,
+            } => {
+                use naga::front;
+
+                // WebGPU doesn't know `@if` attributes, so the module is parsed with the
+                // defines and written back to WGSL.
+                let options = front::wgsl::Options {
+                    defines: defines.iter().map(|&define| String::from(define)).collect(),
+                    ..front::wgsl::Options::new()
+                };
+                let mut parser = front::wgsl::Frontend::new_with_options(options);
+                parser
+                    .parse(shader)
+                    .map_err(|inner| {
+                        crate::CompilationInfo::from(naga::error::ShaderError {
+                            source: shader.to_string(),
+                            files: Vec::new(),
+                            label: desc.label.map(|s| s.to_string()),
+                            inner: Box::new(inner),
+                        })
+                    })
+                    .and_then(|wgsl_module| {
+                        validate_transformed_shader_module(&wgsl_module, shader, &desc).map(
+                            |(v, bindings)| {
+                                (
+                                    v,
+                                    WebShaderCompilationInfo::Transformed {
+                                        compilation_info: crate::CompilationInfo {
+                                            messages: vec![],
+                                        },
+                                    },
+                                    OnceCell::from(bindings),
+                                )
+                            },
+                        )
+                    })
+            }
+            #[cfg(all(feature = "wgsl", naga))]
+            crate::ShaderSource::WgslFiles(files) => {
+                use naga::front;
+
+                // WebGPU compiles a single file, so the files are parsed together by Naga
+                // and written back to WGSL.
+                let mut sources = front::wgsl::Sources::new();
+                for &(name, source) in files {
+                    sources.push(name, source);
+                }
+                let mut parser = front::wgsl::Frontend::new();
+                parser
+                    .parse_sources(&sources)
+                    .map_err(|inner| {
+                        crate::CompilationInfo::from(naga::error::ShaderError {
+                            source: sources.text().to_string(),
+                            files: sources.file_ranges().to_vec(),
+                            label: desc.label.map(|s| s.to_string()),
+                            inner: Box::new(inner),
+                        })
+                    })
+                    .and_then(|wgsl_module| {
+                        validate_transformed_shader_module_in_files(
+                            &wgsl_module,
+                            sources.text(),
+                            sources.file_ranges(),
+                            &desc,
+                        )
+                        .map(|(v, bindings)| {
+                            (
+                                v,
+                                WebShaderCompilationInfo::Transformed {
+                                    compilation_info: crate::CompilationInfo { messages: vec![] },
+                                },
+                                OnceCell::from(bindings),
+                            )
+                        })
+                    })
+            }
+            #[cfg(all(feature = "wgsl", not(naga)))]
+            crate::ShaderSource::WgslWithDefines { .. } | crate::ShaderSource::WgslFiles(_) => {
+                Err(crate::CompilationInfo {
+                    messages: vec![crate::CompilationMessage {
+                        message: "`ShaderSource::WgslWithDefines` and `ShaderSource::WgslFiles` \
+                                  require the `naga-ir` feature on WebGPU"
+                            .to_string(),
+                        message_type: crate::CompilationMessageType::Error,
+                        location: None,
+                        file_name: None,
+                    }],
+                })
+            }
             #[cfg(feature = "naga-ir")]
             crate::ShaderSource::Naga(ref module) => {
-                validate_transformed_shader_module(module, "", &desc).map(|v| {
+                validate_transformed_shader_module(module, "", &desc).map(|(v, bindings)| {
                     (
                         v,
                         WebShaderCompilationInfo::Transformed {
                             compilation_info: crate::CompilationInfo { messages: vec![] },
                         },
+                        OnceCell::from(bindings),
                     )
                 })
             }
@@ -1811,13 +2019,38 @@ impl dispatch::DeviceInterface for WebDevice {
             module: &naga::Module,
             source: &str,
             desc: &crate::ShaderModuleDescriptor<'_>,
-        ) -> Result<webgpu_sys::GpuShaderModuleDescriptor, crate::CompilationInfo> {
+        ) -> Result<
+            (
+                webgpu_sys::GpuShaderModuleDescriptor,
+                Vec<(String, u32, u32)>,
+            ),
+            crate::CompilationInfo,
+        > {
+            validate_transformed_shader_module_in_files(module, source, &[], desc)
+        }
+
+        /// Like `validate_transformed_shader_module`, for a module made of the `files` of
+        /// `source`.
+        #[cfg(naga)]
+        fn validate_transformed_shader_module_in_files(
+            module: &naga::Module,
+            source: &str,
+            files: &[(String, core::ops::Range<usize>)],
+            desc: &crate::ShaderModuleDescriptor<'_>,
+        ) -> Result<
+            (
+                webgpu_sys::GpuShaderModuleDescriptor,
+                Vec<(String, u32, u32)>,
+            ),
+            crate::CompilationInfo,
+        > {
             use naga::{back, valid};
             let mut validator =
                 valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all());
             let module_info = validator.validate(module).map_err(|err| {
                 crate::CompilationInfo::from(naga::error::ShaderError {
                     source: source.to_string(),
+                    files: files.to_vec(),
                     label: desc.label.map(|s| s.to_string()),
                     inner: Box::new(err),
                 })
@@ -1825,15 +2058,17 @@ impl dispatch::DeviceInterface for WebDevice {
 
             let writer_flags = naga::back::wgsl::WriterFlags::empty();
             let wgsl_text = back::wgsl::write_string(module, &module_info, writer_flags).unwrap();
-            Ok(webgpu_sys::GpuShaderModuleDescriptor::new(
-                wgsl_text.as_str(),
+            Ok((
+                webgpu_sys::GpuShaderModuleDescriptor::new(wgsl_text.as_str()),
+                reflect_bindings(module),
             ))
         }
-        let (descriptor, compilation_info) = match shader_module_result {
+        let (descriptor, compilation_info, bindings) = match shader_module_result {
             Ok(v) => v,
             Err(compilation_info) => (
                 webgpu_sys::GpuShaderModuleDescriptor::new(""),
                 WebShaderCompilationInfo::Transformed { compilation_info },
+                OnceCell::from(Vec::new()),
             ),
         };
         if let Some(label) = desc.label {
@@ -1842,6 +2077,7 @@ impl dispatch::DeviceInterface for WebDevice {
         WebShaderModule {
             module: self.inner.create_shader_module(&descriptor),
             compilation_info,
+            bindings,
             ident: crate::cmp::Identifier::create(),
         }
         .into()
@@ -1949,6 +2185,9 @@ impl dispatch::DeviceInterface for WebDevice {
                             &webgpu_sys::GpuExternalTextureBindingLayout::new(),
                         );
                     }
+                    wgt::BindingType::YcbcrTexture { .. } => {
+                        panic!("Y'CbCr texture bindings are not supported on WebGPU")
+                    }
                 }
 
                 mapped_entry
@@ -2011,6 +2250,10 @@ impl dispatch::DeviceInterface for WebDevice {
                     crate::BindingResource::AccelerationStructure(_) => {
                         unimplemented!("Raytracing not implemented for web")
                     }
+                    crate::BindingResource::ExternalTexture(external_texture) => {
+                        let external_texture = &external_texture.inner.as_webgpu().inner;
+                        JsValue::from(external_texture)
+                    }
                 };
 
                 webgpu_sys::GpuBindGroupEntry::new(binding.binding, &mapped_resource)
@@ -2165,6 +2408,20 @@ impl dispatch::DeviceInterface for WebDevice {
         .into()
     }
 
+    fn create_mesh_pipeline(
+        &self,
+        _desc: &crate::MeshPipelineDescriptor<'_>,
+    ) -> dispatch::DispatchRenderPipeline {
+        unimplemented!("Mesh shaders not implemented for web")
+    }
+
+    fn create_ray_tracing_pipeline(
+        &self,
+        _desc: &crate::RayTracingPipelineDescriptor<'_>,
+    ) -> dispatch::DispatchRayTracingPipeline {
+        unimplemented!("Raytracing not implemented for web")
+    }
+
     fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<'_>,
@@ -2222,7 +2479,8 @@ impl dispatch::DeviceInterface for WebDevice {
         let mapped_desc = webgpu_sys::GpuTextureDescriptor::new(
             map_texture_format(desc.format),
             &map_extent_3d(desc.size),
-            desc.usage.bits(),
+            // WebGPU has no transient textures, the browser allocates them as usual.
+            (desc.usage - wgt::TextureUsages::TRANSIENT).bits(),
         );
         if let Some(label) = desc.label {
             mapped_desc.set_label(label);
@@ -2253,6 +2511,19 @@ impl dispatch::DeviceInterface for WebDevice {
         unimplemented!("Raytracing not implemented for web");
     }
 
+    fn create_blas_from_serialized(
+        &self,
+        _desc: &crate::CreateBlasDescriptor<'_>,
+        _sizes: crate::BlasGeometrySizeDescriptors,
+        _data: &[u8],
+    ) -> (Option<u64>, dispatch::DispatchBlas) {
+        unimplemented!("Raytracing not implemented for web");
+    }
+
+    fn is_serialized_blas_compatible(&self, _data: &[u8]) -> bool {
+        unimplemented!("Raytracing not implemented for web");
+    }
+
     fn create_tlas(&self, _desc: &crate::CreateTlasDescriptor<'_>) -> dispatch::DispatchTlas {
         unimplemented!("Raytracing not implemented for web");
     }
@@ -2324,6 +2595,82 @@ impl dispatch::DeviceInterface for WebDevice {
         .into()
     }
 
+    fn create_memory_heap(
+        &self,
+        _desc: &crate::MemoryHeapDescriptor<'_>,
+    ) -> dispatch::DispatchMemoryHeap {
+        unimplemented!("Memory heaps not implemented for web");
+    }
+
+    fn texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor<'_>,
+    ) -> Option<crate::TextureMemoryRequirements> {
+        unimplemented!("Memory heaps not implemented for web");
+    }
+
+    fn create_texture_aliased(
+        &self,
+        _heap: &dispatch::DispatchMemoryHeap,
+        _offset: crate::BufferAddress,
+        _desc: &crate::TextureDescriptor<'_>,
+    ) -> dispatch::DispatchTexture {
+        unimplemented!("Memory heaps not implemented for web");
+    }
+
+    fn create_sparse_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor<'_>,
+    ) -> dispatch::DispatchBuffer {
+        unimplemented!("Sparse resources not implemented for web");
+    }
+
+    fn create_sparse_texture(
+        &self,
+        _desc: &crate::TextureDescriptor<'_>,
+    ) -> dispatch::DispatchTexture {
+        unimplemented!("Sparse resources not implemented for web");
+    }
+
+    fn create_shared_fence(
+        &self,
+        _desc: &crate::SharedFenceDescriptor<'_>,
+    ) -> dispatch::DispatchSharedFence {
+        unimplemented!("Shared fences not implemented for web");
+    }
+
+    unsafe fn import_shared_fence(
+        &self,
+        _handle: crate::SharedFenceHandle,
+        _desc: &crate::SharedFenceDescriptor<'_>,
+    ) -> dispatch::DispatchSharedFence {
+        unimplemented!("Shared fences not implemented for web");
+    }
+
+    fn import_external_texture(
+        &self,
+        source: &web_sys::HtmlVideoElement,
+    ) -> dispatch::DispatchExternalTexture {
+        let mapped_desc = webgpu_sys::GpuExternalTextureDescriptor::new(source);
+
+        let external_texture = self.inner.import_external_texture(&mapped_desc).unwrap();
+
+        WebExternalTexture {
+            inner: external_texture,
+            ident: crate::cmp::Identifier::create(),
+        }
+        .into()
+    }
+
+    fn create_command_encoder_for_queue(
+        &self,
+        _queue: &dispatch::DispatchQueue,
+        desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> dispatch::DispatchCommandEncoder {
+        // WebGPU devices only have a single queue.
+        self.create_command_encoder(desc)
+    }
+
     fn create_render_bundle_encoder(
         &self,
         desc: &crate::RenderBundleEncoderDescriptor<'_>,
@@ -2376,6 +2723,13 @@ impl dispatch::DeviceInterface for WebDevice {
         let _ = self.inner.lost().then(&closure);
     }
 
+    fn set_memory_budget_callback(
+        &self,
+        _memory_budget_callback: dispatch::ArcMemoryBudgetCallback,
+    ) {
+        // WebGPU doesn't expose memory budgets.
+    }
+
     fn on_uncaptured_error(&self, handler: Box<dyn crate::UncapturedErrorHandler>) {
         let f = Closure::wrap(Box::new(move |event: webgpu_sys::GpuUncapturedErrorEvent| {
             let error = crate::Error::from_js(event.error().value_of());
@@ -2424,6 +2778,20 @@ impl dispatch::DeviceInterface for WebDevice {
         None
     }
 
+    fn generate_memory_report(&self, _include_resources: bool) -> Option<wgt::MemoryReport> {
+        None
+    }
+
+    fn trim_memory(&self) -> wgt::TrimMemoryReport {
+        wgt::TrimMemoryReport::default()
+    }
+
+    fn set_barrier_logging(&self, _enabled: bool) {}
+
+    fn take_barrier_report(&self) -> Option<wgt::BarrierReport> {
+        None
+    }
+
     fn destroy(&self) {
         self.inner.destroy();
     }
@@ -2592,11 +2960,38 @@ impl dispatch::QueueInterface for WebQueue {
         0
     }
 
+    fn submit_with_external_sync(
+        &self,
+        _command_buffers: &mut dyn Iterator<Item = dispatch::DispatchCommandBuffer>,
+        _wait: &[wgt::SharedFenceValue<&dispatch::DispatchSharedFence>],
+        _signal: &[wgt::SharedFenceValue<&dispatch::DispatchSharedFence>],
+    ) -> u64 {
+        unimplemented!("Shared fences not implemented for web");
+    }
+
+    fn bind_sparse_memory(
+        &self,
+        _buffer_bindings: &[wgt::SparseBufferBinding<
+            &dispatch::DispatchBuffer,
+            &dispatch::DispatchMemoryHeap,
+        >],
+        _texture_bindings: &[wgt::SparseTextureBinding<
+            &dispatch::DispatchTexture,
+            &dispatch::DispatchMemoryHeap,
+        >],
+    ) {
+        unimplemented!("Sparse resources not implemented for web");
+    }
+
     fn get_timestamp_period(&self) -> f32 {
         // Timestamp values are always in nanoseconds, see https://gpuweb.github.io/gpuweb/#timestamp
         1.0
     }
 
+    fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
     fn on_submitted_work_done(&self, callback: dispatch::BoxSubmittedWorkDoneCallback) {
         let promise = self.inner.on_submitted_work_done();
         wasm_bindgen_futures::spawn_local(async move {
@@ -2610,6 +3005,29 @@ impl dispatch::QueueInterface for WebQueue {
         });
     }
 
+    fn on_submission_done(
+        &self,
+        _submission_index: u64,
+        callback: dispatch::BoxSubmittedWorkDoneCallback,
+    ) {
+        // Submission indices aren't tracked, so wait for all the work submitted so far.
+        dispatch::QueueInterface::on_submitted_work_done(self, callback);
+    }
+
+    fn get_completed_submission_index(&self) -> u64 {
+        // Submission indices aren't tracked, and are all zero.
+        0
+    }
+
+    fn poll(&self, _poll_type: wgt::PollType<u64>) -> Result<crate::PollStatus, crate::PollError> {
+        // Device is polled automatically
+        Ok(crate::PollStatus::QueueEmpty)
+    }
+
+    fn wait_for_submission(&self, _submission_index: u64) {
+        // WebGPU devices only have a single queue.
+    }
+
     fn compact_blas(
         &self,
         _blas: &dispatch::DispatchBlas,
@@ -2635,6 +3053,32 @@ impl dispatch::ShaderModuleInterface for WebShaderModule {
             map_future,
         ))
     }
+
+    fn binding(&self, name: &str) -> Option<(u32, u32)> {
+        let bindings = self.bindings.get_or_init(|| match self.compilation_info {
+            #[cfg(all(feature = "wgsl", naga))]
+            WebShaderCompilationInfo::Wgsl { ref source } => naga::front::wgsl::parse_str(source)
+                .map(|module| reflect_bindings(&module))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        });
+        bindings
+            .iter()
+            .find(|(binding_name, _, _)| binding_name == name)
+            .map(|&(_, group, binding)| (group, binding))
+    }
+
+    fn workgroup_memory(&self, _entry_point: &str) -> Option<wgt::WorkgroupMemoryInfo> {
+        None
+    }
+
+    fn check_layout(
+        &self,
+        _name: &str,
+        _layout: &wgt::ShaderTypeLayout,
+    ) -> Result<(), wgt::ShaderLayoutError> {
+        Err(wgt::ShaderLayoutError::NotReflected)
+    }
 }
 impl Drop for WebShaderModule {
     fn drop(&mut self) {
@@ -2769,6 +3213,13 @@ impl dispatch::BlasInterface for WebBlas {
     fn ready_for_compaction(&self) -> bool {
         unimplemented!("Raytracing not implemented for web")
     }
+
+    fn compacted_size(&self) -> Option<wgt::BufferAddress> {
+        unimplemented!("Raytracing not implemented for web")
+    }
+    fn serialize(&self, _callback: BlasSerializeCallback) {
+        unimplemented!("Raytracing not implemented for web")
+    }
 }
 impl Drop for WebBlas {
     fn drop(&mut self) {
@@ -2776,6 +3227,20 @@ impl Drop for WebBlas {
     }
 }
 
+impl dispatch::RayTracingPipelineInterface for WebRayTracingPipeline {
+    fn shader_binding_table(
+        &self,
+        _desc: &crate::ShaderBindingTableDescriptor<'_>,
+    ) -> (Vec<u8>, crate::ShaderBindingTableLayout) {
+        unimplemented!("Raytracing not implemented for web")
+    }
+}
+impl Drop for WebRayTracingPipeline {
+    fn drop(&mut self) {
+        // no-op
+    }
+}
+
 impl dispatch::TlasInterface for WebTlas {}
 impl Drop for WebTlas {
     fn drop(&mut self) {
@@ -2790,6 +3255,31 @@ impl Drop for WebQuerySet {
     }
 }
 
+impl dispatch::SharedFenceInterface for WebSharedFence {
+    fn export(&self) -> Option<crate::SharedFenceHandle> {
+        unimplemented!("Shared fences not implemented for web")
+    }
+}
+impl Drop for WebSharedFence {
+    fn drop(&mut self) {
+        // no-op
+    }
+}
+
+impl dispatch::MemoryHeapInterface for WebMemoryHeap {}
+impl Drop for WebMemoryHeap {
+    fn drop(&mut self) {
+        // no-op
+    }
+}
+
+impl dispatch::ExternalTextureInterface for WebExternalTexture {}
+impl Drop for WebExternalTexture {
+    fn drop(&mut self) {
+        // no-op
+    }
+}
+
 impl dispatch::PipelineLayoutInterface for WebPipelineLayout {}
 impl Drop for WebPipelineLayout {
     fn drop(&mut self) {
@@ -2910,6 +3400,15 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
             .unwrap();
     }
 
+    fn resolve_texture(
+        &self,
+        _source: &dispatch::DispatchTextureView,
+        _destination: &dispatch::DispatchTextureView,
+        _regions: &[crate::Rect],
+    ) {
+        unimplemented!("Texture resolves not implemented for web");
+    }
+
     fn begin_compute_pass(
         &self,
         desc: &crate::ComputePassDescriptor<'_>,
@@ -2944,6 +3443,18 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
         &self,
         desc: &crate::RenderPassDescriptor<'_>,
     ) -> dispatch::DispatchRenderPass {
+        // WebGPU can't suspend render passes, so the parts of one are separate
+        // passes, which store and load the attachments in between.
+        let suspending = desc.flags.contains(crate::RenderPassFlags::SUSPENDING);
+        let resuming = desc.flags.contains(crate::RenderPassFlags::RESUMING);
+        let map_part_store_op = |store| {
+            if suspending {
+                webgpu_sys::GpuStoreOp::Store
+            } else {
+                map_store_op(store)
+            }
+        };
+
         let mapped_color_attachments = desc
             .color_attachments
             .iter()
@@ -2951,6 +3462,7 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
                 Some(ca) => {
                     let mut clear_value: Option<wasm_bindgen::JsValue> = None;
                     let load_value = match ca.ops.load {
+                        _ if resuming => webgpu_sys::GpuLoadOp::Load,
                         crate::LoadOp::Clear(color) => {
                             clear_value = Some(wasm_bindgen::JsValue::from(map_color(color)));
                             webgpu_sys::GpuLoadOp::Clear
@@ -2962,17 +3474,18 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
 
                     let mapped_color_attachment = webgpu_sys::GpuRenderPassColorAttachment::new(
                         load_value,
-                        map_store_op(ca.ops.store),
+                        map_part_store_op(ca.ops.store),
                         view,
                     );
                     if let Some(cv) = clear_value {
                         mapped_color_attachment.set_clear_value(&cv);
                     }
-                    if let Some(rt) = ca.resolve_target {
+                    // Only the last part resolves.
+                    if let Some(rt) = ca.resolve_target.filter(|_| !suspending) {
                         let resolve_target_view = &rt.inner.as_webgpu().inner;
                         mapped_color_attachment.set_resolve_target(resolve_target_view);
                     }
-                    mapped_color_attachment.set_store_op(map_store_op(ca.ops.store));
+                    mapped_color_attachment.set_store_op(map_part_store_op(ca.ops.store));
 
                     wasm_bindgen::JsValue::from(mapped_color_attachment)
                 }
@@ -2992,6 +3505,7 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
                 webgpu_sys::GpuRenderPassDepthStencilAttachment::new(depth_stencil_attachment);
             if let Some(ref ops) = dsa.depth_ops {
                 let load_op = match ops.load {
+                    _ if resuming => webgpu_sys::GpuLoadOp::Load,
                     crate::LoadOp::Clear(v) => {
                         mapped_depth_stencil_attachment.set_depth_clear_value(v);
                         webgpu_sys::GpuLoadOp::Clear
@@ -2999,11 +3513,12 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
                     crate::LoadOp::Load => webgpu_sys::GpuLoadOp::Load,
                 };
                 mapped_depth_stencil_attachment.set_depth_load_op(load_op);
-                mapped_depth_stencil_attachment.set_depth_store_op(map_store_op(ops.store));
+                mapped_depth_stencil_attachment.set_depth_store_op(map_part_store_op(ops.store));
             }
             mapped_depth_stencil_attachment.set_depth_read_only(dsa.depth_ops.is_none());
             if let Some(ref ops) = dsa.stencil_ops {
                 let load_op = match ops.load {
+                    _ if resuming => webgpu_sys::GpuLoadOp::Load,
                     crate::LoadOp::Clear(v) => {
                         mapped_depth_stencil_attachment.set_stencil_clear_value(v);
                         webgpu_sys::GpuLoadOp::Clear
@@ -3011,7 +3526,7 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
                     crate::LoadOp::Load => webgpu_sys::GpuLoadOp::Load,
                 };
                 mapped_depth_stencil_attachment.set_stencil_load_op(load_op);
-                mapped_depth_stencil_attachment.set_stencil_store_op(map_store_op(ops.store));
+                mapped_depth_stencil_attachment.set_stencil_store_op(map_part_store_op(ops.store));
             }
             mapped_depth_stencil_attachment.set_stencil_read_only(dsa.stencil_ops.is_none());
             mapped_desc.set_depth_stencil_attachment(&mapped_depth_stencil_attachment);
@@ -3033,12 +3548,16 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
 
         WebRenderPassEncoder {
             inner: render_pass,
+            parallel: false,
             ident: crate::cmp::Identifier::create(),
         }
         .into()
     }
 
-    fn finish(&mut self) -> dispatch::DispatchCommandBuffer {
+    fn finish(&mut self, reusable: bool) -> dispatch::DispatchCommandBuffer {
+        if reusable {
+            unimplemented!("Reusable command buffers are not supported on WebGPU");
+        }
         let label = self.inner.label();
         let buffer = if label.is_empty() {
             self.inner.finish()
@@ -3142,6 +3661,10 @@ impl dispatch::CommandEncoderInterface for WebCommandEncoder {
         unimplemented!("Raytracing not implemented for web");
     }
 
+    fn trace_rays(&self, _desc: &crate::TraceRaysDescriptor<'_>) {
+        unimplemented!("Raytracing not implemented for web");
+    }
+
     fn transition_resources<'a>(
         &mut self,
         _buffer_transitions: &mut dyn Iterator<
@@ -3375,6 +3898,14 @@ impl dispatch::RenderPassInterface for WebRenderPassEncoder {
         self.inner.set_stencil_reference(reference);
     }
 
+    fn set_shading_rate(&mut self, _rate: crate::ShadingRate) {
+        unimplemented!("Variable rate shading not implemented for web")
+    }
+
+    fn set_viewports(&mut self, _viewports: &[crate::Viewport]) {
+        unimplemented!("Multiple viewports not implemented for web")
+    }
+
     fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
         self.inner
             .draw_with_instance_count_and_first_vertex_and_first_instance(
@@ -3472,6 +4003,38 @@ impl dispatch::RenderPassInterface for WebRenderPassEncoder {
         )
     }
 
+    fn draw_mesh_tasks(&mut self, _group_count_x: u32, _group_count_y: u32, _group_count_z: u32) {
+        unimplemented!("Mesh shaders not implemented for web")
+    }
+
+    fn draw_mesh_tasks_indirect(
+        &mut self,
+        _indirect_buffer: &dispatch::DispatchBuffer,
+        _indirect_offset: crate::BufferAddress,
+    ) {
+        unimplemented!("Mesh shaders not implemented for web")
+    }
+
+    fn multi_draw_mesh_tasks_indirect(
+        &mut self,
+        _indirect_buffer: &dispatch::DispatchBuffer,
+        _indirect_offset: crate::BufferAddress,
+        _count: u32,
+    ) {
+        unimplemented!("Mesh shaders not implemented for web")
+    }
+
+    fn multi_draw_mesh_tasks_indirect_count(
+        &mut self,
+        _indirect_buffer: &dispatch::DispatchBuffer,
+        _indirect_offset: crate::BufferAddress,
+        _count_buffer: &dispatch::DispatchBuffer,
+        _count_buffer_offset: crate::BufferAddress,
+        _max_count: u32,
+    ) {
+        unimplemented!("Mesh shaders not implemented for web")
+    }
+
     fn insert_debug_marker(&mut self, _label: &str) {
         // Not available in gecko yet
         // self.inner.insert_debug_marker(label);
@@ -3518,6 +4081,18 @@ impl dispatch::RenderPassInterface for WebRenderPassEncoder {
         // self.inner.end_pipeline_statistics_query();
     }
 
+    fn begin_conditional_block(
+        &mut self,
+        _buffer: &dispatch::DispatchBuffer,
+        _offset: crate::BufferAddress,
+    ) {
+        unimplemented!("Conditional rendering is not supported on WebGPU")
+    }
+
+    fn end_conditional_block(&mut self) {
+        unimplemented!("Conditional rendering is not supported on WebGPU")
+    }
+
     fn execute_bundles(
         &mut self,
         render_bundles: &mut dyn Iterator<Item = &dispatch::DispatchRenderBundle>,
@@ -3528,8 +4103,21 @@ impl dispatch::RenderPassInterface for WebRenderPassEncoder {
         self.inner.execute_bundles(&mapped);
     }
 
+    fn create_parallel_encoder(&mut self) -> dispatch::DispatchRenderPass {
+        // WebGPU can't encode a pass on several threads, so the parallel encoder records
+        // its commands inline.
+        WebRenderPassEncoder {
+            inner: self.inner.clone(),
+            parallel: true,
+            ident: crate::cmp::Identifier::create(),
+        }
+        .into()
+    }
+
     fn end(&mut self) {
-        self.inner.end();
+        if !self.parallel {
+            self.inner.end();
+        }
     }
 }
 impl Drop for WebRenderPassEncoder {
@@ -3667,10 +4255,34 @@ impl dispatch::RenderBundleEncoderInterface for WebRenderBundleEncoder {
             .draw_indexed_indirect_with_f64(&buffer.inner, indirect_offset as f64);
     }
 
-    fn finish(self, desc: &crate::RenderBundleDescriptor<'_>) -> dispatch::DispatchRenderBundle
-    where
-        Self: Sized,
-    {
+    fn write_timestamp(&mut self, _query_set: &dispatch::DispatchQuerySet, _query_index: u32) {
+        unimplemented!("Queries in render bundles are not supported on WebGPU")
+    }
+
+    fn begin_occlusion_query(&mut self, _query_index: u32) {
+        unimplemented!("Queries in render bundles are not supported on WebGPU")
+    }
+
+    fn end_occlusion_query(&mut self) {
+        unimplemented!("Queries in render bundles are not supported on WebGPU")
+    }
+
+    fn begin_pipeline_statistics_query(
+        &mut self,
+        _query_set: &dispatch::DispatchQuerySet,
+        _query_index: u32,
+    ) {
+        unimplemented!("Queries in render bundles are not supported on WebGPU")
+    }
+
+    fn end_pipeline_statistics_query(&mut self) {
+        unimplemented!("Queries in render bundles are not supported on WebGPU")
+    }
+
+    fn finish(
+        &mut self,
+        desc: &crate::RenderBundleDescriptor<'_>,
+    ) -> dispatch::DispatchRenderBundle {
         let bundle = match desc.label {
             Some(label) => {
                 let mapped_desc = webgpu_sys::GpuRenderBundleDescriptor::new();
@@ -3727,6 +4339,11 @@ impl dispatch::SurfaceInterface for WebSurface {
             alpha_modes: vec![wgt::CompositeAlphaMode::Opaque],
             // Statically set to RENDER_ATTACHMENT for now. See https://gpuweb.github.io/gpuweb/#dom-gpucanvasconfiguration-usage
             usages: wgt::TextureUsages::RENDER_ATTACHMENT,
+            // Extended is a tone mapping mode of the canvas. See https://gpuweb.github.io/gpuweb/#gpucanvastonemappingmode
+            color_spaces: vec![
+                wgt::SurfaceColorSpace::Srgb,
+                wgt::SurfaceColorSpace::ExtendedSrgb,
+            ],
         }
     }
 
@@ -3756,12 +4373,24 @@ impl dispatch::SurfaceInterface for WebSurface {
             wgt::CompositeAlphaMode::PreMultiplied => webgpu_sys::GpuCanvasAlphaMode::Premultiplied,
             _ => webgpu_sys::GpuCanvasAlphaMode::Opaque,
         };
+        let tone_mapping_mode = match config.color_space {
+            wgt::SurfaceColorSpace::Auto | wgt::SurfaceColorSpace::Srgb => {
+                webgpu_sys::GpuCanvasToneMappingMode::Standard
+            }
+            wgt::SurfaceColorSpace::ExtendedSrgb => webgpu_sys::GpuCanvasToneMappingMode::Extended,
+            wgt::SurfaceColorSpace::ExtendedSrgbLinear | wgt::SurfaceColorSpace::Hdr10 => {
+                panic!("Only Srgb/Auto or ExtendedSrgb color spaces are supported on web")
+            }
+        };
+        let tone_mapping = webgpu_sys::GpuCanvasToneMapping::new();
+        tone_mapping.set_mode(tone_mapping_mode);
         let mapped = webgpu_sys::GpuCanvasConfiguration::new(
             &device.inner,
             map_texture_format(config.format),
         );
         mapped.set_usage(config.usage.bits());
         mapped.set_alpha_mode(alpha_mode);
+        mapped.set_tone_mapping(&tone_mapping);
         let mapped_view_formats = config
             .view_formats
             .iter()
@@ -3773,6 +4402,7 @@ impl dispatch::SurfaceInterface for WebSurface {
 
     fn get_current_texture(
         &self,
+        _timeout: Option<core::time::Duration>,
     ) -> (
         Option<dispatch::DispatchTexture>,
         crate::SurfaceStatus,
@@ -3794,6 +4424,15 @@ impl dispatch::SurfaceInterface for WebSurface {
             .into(),
         )
     }
+
+    fn get_presentation_statistics(&self) -> Vec<crate::PresentationStatistics> {
+        unimplemented!("Presentation timing not implemented for web")
+    }
+
+    fn presentation_path(&self) -> crate::PresentationPath {
+        // The browser always composites canvases with the rest of the page.
+        crate::PresentationPath::Composed
+    }
 }
 impl Drop for WebSurface {
     fn drop(&mut self) {
@@ -3802,7 +4441,11 @@ impl Drop for WebSurface {
 }
 
 impl dispatch::SurfaceOutputDetailInterface for WebSurfaceOutputDetail {
-    fn present(&self) {
+    fn set_target_present_time(&self, _time: crate::PresentationTimestamp) {
+        unimplemented!("Presentation timing not implemented for web")
+    }
+
+    fn present(&self, _damage: &[crate::Rect]) {
         // Swapchain is presented automatically on the web.
     }
 
@@ -11,6 +11,7 @@ use core::{
     error::Error,
     fmt,
     future::ready,
+    mem,
     ops::{Deref, Range},
     pin::Pin,
     ptr::NonNull,
@@ -20,8 +21,10 @@ use core::{
 use arrayvec::ArrayVec;
 use smallvec::SmallVec;
 use wgc::{
-    command::bundle_ffi::*, error::ContextErrorSource, pipeline::CreateShaderModuleError,
-    resource::BlasPrepareCompactResult,
+    command::bundle_ffi::*,
+    error::ContextErrorSource,
+    pipeline::CreateShaderModuleError,
+    resource::{BlasPrepareCompactResult, BlasSerializeResult},
 };
 use wgt::{
     WasmNotSendSync,
@@ -33,7 +36,7 @@ use crate::{
     BindingResource, Blas, BufferBinding, BufferDescriptor, CompilationInfo, CompilationMessage,
     CompilationMessageType, ErrorSource, Features, Label, LoadOp, MapMode, Operations,
     ShaderSource, SurfaceTargetUnsafe, TextureDescriptor, Tlas, api,
-    dispatch::{self, BlasCompactCallback, BufferMappedRangeInterface},
+    dispatch::{self, BlasCompactCallback, BlasSerializeCallback, BufferMappedRangeInterface},
 };
 
 #[derive(Clone)]
@@ -79,6 +82,11 @@ impl ContextWgpuCore {
         self.0.enumerate_adapters(backends)
     }
 
+    #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+    pub fn enumerate_displays(&self) -> Vec<wgc::instance::Display> {
+        self.0.instance_enumerate_displays()
+    }
+
     pub unsafe fn create_adapter_from_hal<A: wgc::hal_api::HalApi>(
         &self,
         hal_adapter: hal::ExposedAdapter<A>,
@@ -100,18 +108,30 @@ impl ContextWgpuCore {
         unsafe { self.0.buffer_as_hal::<A>(buffer.id) }
     }
 
+    #[cfg(feature = "trace")]
+    pub fn device_start_trace(
+        &self,
+        device: &CoreDevice,
+        path: std::path::PathBuf,
+    ) -> Result<(), std::io::Error> {
+        self.0.device_start_trace(device.id, path)
+    }
+
+    #[cfg(feature = "trace")]
+    pub fn device_stop_trace(&self, device: &CoreDevice) {
+        self.0.device_stop_trace(device.id)
+    }
+
     pub unsafe fn create_device_from_hal<A: wgc::hal_api::HalApi>(
         &self,
         adapter: &CoreAdapter,
         hal_device: hal::OpenDevice<A>,
         desc: &crate::DeviceDescriptor<'_>,
     ) -> Result<(CoreDevice, CoreQueue), crate::RequestDeviceError> {
+        #[cfg(not(feature = "trace"))]
         if !matches!(desc.trace, wgt::Trace::Off) {
             log::error!(
-                "
-                Feature 'trace' has been removed temporarily; \
-                see https://github.com/gfx-rs/wgpu/issues/5974. \
-                The `trace` parameter will have no effect."
+                "wgpu feature 'trace' is not enabled; the `trace` parameter will have no effect"
             );
         }
 
@@ -125,6 +145,7 @@ impl ContextWgpuCore {
             )
         }?;
         let error_sink = Arc::new(Mutex::new(ErrorSinkRaw::new()));
+        self.set_gpu_validation_callback(device_id, &error_sink);
         let device = CoreDevice {
             context: self.clone(),
             id: device_id,
@@ -165,6 +186,93 @@ impl ContextWgpuCore {
         }
     }
 
+    /// # Safety
+    ///
+    /// - `io_surface` must be a valid `IOSurfaceRef`.
+    /// - `desc.format` must be compatible with the pixel format of `plane`.
+    #[cfg(metal)]
+    pub unsafe fn create_texture_from_io_surface(
+        &self,
+        io_surface: *mut core::ffi::c_void,
+        plane: u32,
+        device: &CoreDevice,
+        desc: &TextureDescriptor<'_>,
+    ) -> CoreTexture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = unsafe {
+            self.0.device_create_texture_from_io_surface(
+                device.id,
+                io_surface,
+                plane,
+                &descriptor,
+                None,
+            )
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                desc.label,
+                "Device::create_texture_from_io_surface",
+            );
+        }
+        CoreTexture {
+            context: self.clone(),
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    pub fn create_headless_surface(
+        &self,
+        width: u32,
+        height: u32,
+        format: wgt::TextureFormat,
+    ) -> CoreSurface {
+        let id = self
+            .0
+            .instance_create_headless_surface(width, height, format, None);
+        CoreSurface {
+            context: self.clone(),
+            id,
+            configured_device: Mutex::default(),
+            error_sink: Mutex::default(),
+        }
+    }
+
+    pub fn surface_take_presented_frames(
+        &self,
+        surface: &CoreSurface,
+    ) -> Vec<(CoreTexture, wgt::TextureDescriptor<(), ()>)> {
+        match self.0.surface_take_presented_frames(surface.id) {
+            Ok(frames) => frames
+                .into_iter()
+                .map(|frame| {
+                    let texture = CoreTexture {
+                        context: self.clone(),
+                        id: frame.texture,
+                        error_sink: Arc::new(Mutex::new(ErrorSinkRaw::new())),
+                    };
+                    (texture, frame.desc)
+                })
+                .collect(),
+            Err(err) => {
+                let error_sink = surface.error_sink.lock();
+                match error_sink.as_ref() {
+                    Some(error_sink) => {
+                        self.handle_error_nolabel(
+                            error_sink,
+                            err,
+                            "Surface::take_presented_frames",
+                        );
+                        Vec::new()
+                    }
+                    None => self.handle_error_fatal(err, "Surface::take_presented_frames"),
+                }
+            }
+        }
+    }
+
     /// # Safety
     ///
     /// - `hal_buffer` must be created from `device`.
@@ -264,6 +372,36 @@ impl ContextWgpuCore {
         self.0.generate_report()
     }
 
+    /// Report the errors of GPU-based validation on `device_id` to the uncaptured
+    /// error handler of `error_sink`, if [`crate::InstanceFlags::GPU_BASED_VALIDATION`]
+    /// is set.
+    ///
+    /// These errors are found while the GPU executes commands, long after the calls
+    /// which caused them returned, so error scopes can't catch them.
+    fn set_gpu_validation_callback(&self, device_id: wgc::id::DeviceId, error_sink: &ErrorSink) {
+        if !self
+            .0
+            .instance
+            .flags
+            .contains(wgt::InstanceFlags::GPU_BASED_VALIDATION)
+        {
+            return;
+        }
+        let error_sink = Arc::clone(error_sink);
+        self.0.device_set_gpu_validation_callback(
+            device_id,
+            Arc::new(move |message| {
+                let description = format!("GPU-based validation error: {message}");
+                error_sink
+                    .lock()
+                    .handle_uncaptured_error(crate::Error::Validation {
+                        source: Box::new(GpuValidationError(message)),
+                        description,
+                    });
+            }),
+        );
+    }
+
     #[cold]
     #[track_caller]
     #[inline(never)]
@@ -507,6 +645,23 @@ pub struct CoreQuerySet {
     id: wgc::id::QuerySetId,
 }
 
+#[derive(Debug)]
+pub struct CoreSharedFence {
+    pub(crate) context: ContextWgpuCore,
+    id: wgc::id::SharedFenceId,
+    error_sink: ErrorSink,
+}
+
+#[derive(Debug)]
+pub struct CoreMemoryHeap {
+    pub(crate) context: ContextWgpuCore,
+    id: wgc::id::MemoryHeapId,
+}
+
+/// `wgpu-core` has no external textures, so this can't be constructed.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoreExternalTexture {}
+
 #[derive(Debug)]
 pub struct CorePipelineLayout {
     pub(crate) context: ContextWgpuCore,
@@ -551,6 +706,13 @@ pub struct CoreComputePipeline {
     error_sink: ErrorSink,
 }
 
+#[derive(Debug)]
+pub struct CoreRayTracingPipeline {
+    pub(crate) context: ContextWgpuCore,
+    id: wgc::id::RayTracingPipelineId,
+    error_sink: ErrorSink,
+}
+
 #[derive(Debug)]
 pub struct CoreRenderPipeline {
     pub(crate) context: ContextWgpuCore,
@@ -640,14 +802,18 @@ impl ErrorSinkRaw {
                     scope.error = Some(err);
                 }
             }
-            None => {
-                if let Some(custom_handler) = self.uncaptured_handler.as_ref() {
-                    (custom_handler)(err);
-                } else {
-                    // direct call preserves #[track_caller] where dyn can't
-                    default_error_handler(err);
-                }
-            }
+            None => self.handle_uncaptured_error(err),
+        }
+    }
+
+    /// Report `err` to the uncaptured error handler, whatever error scopes are pushed.
+    #[track_caller]
+    fn handle_uncaptured_error(&mut self, err: crate::Error) {
+        if let Some(custom_handler) = self.uncaptured_handler.as_ref() {
+            (custom_handler)(err);
+        } else {
+            // direct call preserves #[track_caller] where dyn can't
+            default_error_handler(err);
         }
     }
 }
@@ -658,6 +824,19 @@ impl fmt::Debug for ErrorSinkRaw {
     }
 }
 
+/// An error reported by GPU-based validation, see
+/// [`ContextWgpuCore::set_gpu_validation_callback`].
+#[derive(Debug)]
+struct GpuValidationError(String);
+
+impl fmt::Display for GpuValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for GpuValidationError {}
+
 #[track_caller]
 fn default_error_handler(err: crate::Error) {
     log::error!("Handling wgpu errors as fatal by default");
@@ -687,6 +866,7 @@ impl From<CreateShaderModuleError> for CompilationInfo {
                     message: value.to_string(),
                     message_type: CompilationMessageType::Error,
                     location: None,
+                    file_name: None,
                 }],
             },
         }
@@ -731,9 +911,12 @@ crate::cmp::impl_eq_ord_hash_proxy!(CoreTexture => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreBlas => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreTlas => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreQuerySet => .id);
+crate::cmp::impl_eq_ord_hash_proxy!(CoreSharedFence => .id);
+crate::cmp::impl_eq_ord_hash_proxy!(CoreMemoryHeap => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CorePipelineLayout => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreRenderPipeline => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreComputePipeline => .id);
+crate::cmp::impl_eq_ord_hash_proxy!(CoreRayTracingPipeline => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CorePipelineCache => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreCommandEncoder => .id);
 crate::cmp::impl_eq_ord_hash_proxy!(CoreComputePass => .id);
@@ -787,6 +970,12 @@ impl dispatch::InstanceInterface for ContextWgpuCore {
                 )
             },
 
+            #[cfg(all(unix, not(target_vendor = "apple"), not(target_family = "wasm")))]
+            SurfaceTargetUnsafe::Display { display, mode } => unsafe {
+                self.0
+                    .instance_create_surface_from_display(&display.inner, &mode, None)
+            },
+
             #[cfg(metal)]
             SurfaceTargetUnsafe::CoreAnimationLayer(layer) => unsafe {
                 self.0.instance_create_surface_metal(layer, None)
@@ -852,6 +1041,14 @@ impl dispatch::InstanceInterface for ContextWgpuCore {
         }
     }
 
+    unsafe fn start_graphics_debugger_capture(&self) -> bool {
+        unsafe { self.0.instance_start_graphics_debugger_capture() }
+    }
+
+    unsafe fn stop_graphics_debugger_capture(&self) {
+        unsafe { self.0.instance_stop_graphics_debugger_capture() }
+    }
+
     #[cfg(feature = "wgsl")]
     fn wgsl_language_features(&self) -> crate::WgslLanguageFeatures {
         use wgc::naga::front::wgsl::ImplementedLanguageExtension;
@@ -879,28 +1076,53 @@ impl dispatch::AdapterInterface for CoreAdapter {
         &self,
         desc: &crate::DeviceDescriptor<'_>,
     ) -> Pin<Box<dyn dispatch::RequestDeviceFuture>> {
+        #[cfg(not(feature = "trace"))]
+        if !matches!(desc.trace, wgt::Trace::Off) {
+            log::error!(
+                "wgpu feature 'trace' is not enabled; the `trace` parameter will have no effect"
+            );
+        }
+
+        let future = self.request_device_with_queues(desc, &[]);
+        Box::pin(async move {
+            let (device, queue, _) = future.await?;
+            Ok((device, queue))
+        })
+    }
+
+    fn request_device_with_queues(
+        &self,
+        desc: &crate::DeviceDescriptor<'_>,
+        queues: &[crate::QueueDescriptor<'_>],
+    ) -> Pin<Box<dyn dispatch::RequestDeviceWithQueuesFuture>> {
+        #[cfg(not(feature = "trace"))]
         if !matches!(desc.trace, wgt::Trace::Off) {
             log::error!(
-                "
-                Feature 'trace' has been removed temporarily; \
-                see https://github.com/gfx-rs/wgpu/issues/5974. \
-                The `trace` parameter will have no effect."
+                "wgpu feature 'trace' is not enabled; the `trace` parameter will have no effect"
             );
         }
 
-        let res = self.context.0.adapter_request_device(
+        let queue_types = queues
+            .iter()
+            .map(|queue| queue.queue_type)
+            .collect::<Vec<_>>();
+        let res = self.context.0.adapter_request_device_with_queues(
             self.id,
             &desc.map_label(|l| l.map(Borrowed)),
+            &queue_types,
+            None,
             None,
             None,
         );
-        let (device_id, queue_id) = match res {
+        let (device_id, queue_id, additional_queue_ids) = match res {
             Ok(ids) => ids,
             Err(err) => {
                 return Box::pin(ready(Err(err.into())));
             }
         };
         let error_sink = Arc::new(Mutex::new(ErrorSinkRaw::new()));
+        self.context
+            .set_gpu_validation_callback(device_id, &error_sink);
         let device = CoreDevice {
             context: self.context.clone(),
             id: device_id,
@@ -910,9 +1132,20 @@ impl dispatch::AdapterInterface for CoreAdapter {
         let queue = CoreQueue {
             context: self.context.clone(),
             id: queue_id,
-            error_sink,
+            error_sink: error_sink.clone(),
         };
-        Box::pin(ready(Ok((device.into(), queue.into()))))
+        let additional_queues = additional_queue_ids
+            .into_iter()
+            .map(|id| {
+                CoreQueue {
+                    context: self.context.clone(),
+                    id,
+                    error_sink: error_sink.clone(),
+                }
+                .into()
+            })
+            .collect();
+        Box::pin(ready(Ok((device.into(), queue.into(), additional_queues))))
     }
 
     fn is_surface_supported(&self, surface: &dispatch::DispatchSurface) -> bool {
@@ -927,6 +1160,10 @@ impl dispatch::AdapterInterface for CoreAdapter {
         self.context.0.adapter_features(self.id)
     }
 
+    fn applied_workarounds(&self) -> crate::Toggles {
+        self.context.0.adapter_applied_workarounds(self.id)
+    }
+
     fn limits(&self) -> crate::Limits {
         self.context.0.adapter_limits(self.id)
     }
@@ -951,6 +1188,10 @@ impl dispatch::AdapterInterface for CoreAdapter {
     fn get_presentation_timestamp(&self) -> crate::PresentationTimestamp {
         self.context.0.adapter_get_presentation_timestamp(self.id)
     }
+
+    fn memory_info(&self) -> Option<crate::MemoryInfo> {
+        self.context.0.adapter_memory_info(self.id)
+    }
 }
 
 impl Drop for CoreAdapter {
@@ -959,6 +1200,16 @@ impl Drop for CoreAdapter {
     }
 }
 
+impl CoreDevice {
+    /// Errors are reported synchronously, so the result of the scope is known as soon as
+    /// it is popped.
+    pub(crate) fn pop_error_scope_blocking(&self) -> Option<crate::Error> {
+        let mut error_sink = self.error_sink.lock();
+        let scope = error_sink.scopes.pop().unwrap();
+        scope.error
+    }
+}
+
 impl dispatch::DeviceInterface for CoreDevice {
     fn features(&self) -> crate::Features {
         self.context.0.device_features(self.id)
@@ -982,10 +1233,12 @@ impl dispatch::DeviceInterface for CoreDevice {
         &self,
         desc: crate::ShaderModuleDescriptor<'_>,
         shader_bound_checks: wgt::ShaderRuntimeChecks,
+        optimization: wgt::ShaderOptimization,
     ) -> dispatch::DispatchShaderModule {
         let descriptor = wgc::pipeline::ShaderModuleDescriptor {
             label: desc.label.map(Borrowed),
             runtime_checks: shader_bound_checks,
+            optimization,
         };
         let source = match desc.source {
             #[cfg(feature = "spirv")]
@@ -1014,7 +1267,32 @@ impl dispatch::DeviceInterface for CoreDevice {
                 wgc::pipeline::ShaderModuleSource::Glsl(Borrowed(shader), options)
             }
             #[cfg(feature = "wgsl")]
-            ShaderSource::Wgsl(ref code) => wgc::pipeline::ShaderModuleSource::Wgsl(Borrowed(code)),
+            ShaderSource::Wgsl(ref code) => wgc::pipeline::ShaderModuleSource::Wgsl(
+                Borrowed(code),
+                wgc::naga::front::wgsl::Options::new(),
+            ),
+            #[cfg(feature = "wgsl")]
+            ShaderSource::WgslWithDefines {
+                ref shader,
+                defines,
+            } => {
+                let options = wgc::naga::front::wgsl::Options {
+                    defines: defines.iter().map(|&define| String::from(define)).collect(),
+                    ..wgc::naga::front::wgsl::Options::new()
+                };
+                wgc::pipeline::ShaderModuleSource::Wgsl(Borrowed(shader), options)
+            }
+            #[cfg(feature = "wgsl")]
+            ShaderSource::WgslFiles(files) => {
+                let mut sources = wgc::naga::front::wgsl::Sources::new();
+                for &(name, source) in files {
+                    sources.push(name, source);
+                }
+                wgc::pipeline::ShaderModuleSource::WgslFiles(
+                    sources,
+                    wgc::naga::front::wgsl::Options::new(),
+                )
+            }
             #[cfg(feature = "naga-ir")]
             ShaderSource::Naga(module) => wgc::pipeline::ShaderModuleSource::Naga(module),
             ShaderSource::Dummy(_) => panic!("found `ShaderSource::Dummy`"),
@@ -1183,6 +1461,9 @@ impl dispatch::DeviceInterface for CoreDevice {
                             acceleration_structure.inner.as_core().id,
                         )
                     }
+                    BindingResource::ExternalTexture(external_texture) => {
+                        match *external_texture.inner.as_core() {}
+                    }
                 },
             })
             .collect::<Vec<_>>();
@@ -1344,6 +1625,78 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
+    fn create_mesh_pipeline(
+        &self,
+        desc: &crate::MeshPipelineDescriptor<'_>,
+    ) -> dispatch::DispatchRenderPipeline {
+        use wgc::pipeline as pipe;
+
+        fn stage<'a>(
+            module: &'a crate::ShaderModule,
+            entry_point: Option<&'a str>,
+            compilation_options: &crate::PipelineCompilationOptions<'a>,
+        ) -> pipe::ProgrammableStageDescriptor<'a> {
+            pipe::ProgrammableStageDescriptor {
+                module: module.inner.as_core().id,
+                entry_point: entry_point.map(Borrowed),
+                constants: compilation_options
+                    .constants
+                    .iter()
+                    .map(|&(key, value)| (String::from(key), value))
+                    .collect(),
+                zero_initialize_workgroup_memory: compilation_options
+                    .zero_initialize_workgroup_memory,
+            }
+        }
+
+        let descriptor = pipe::MeshPipelineDescriptor {
+            label: desc.label.map(Borrowed),
+            layout: desc.layout.map(|layout| layout.inner.as_core().id),
+            task: desc.task.as_ref().map(|task| pipe::TaskState {
+                stage: stage(task.module, task.entry_point, &task.compilation_options),
+            }),
+            mesh: pipe::MeshState {
+                stage: stage(
+                    desc.mesh.module,
+                    desc.mesh.entry_point,
+                    &desc.mesh.compilation_options,
+                ),
+            },
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+            fragment: desc.fragment.as_ref().map(|frag| pipe::FragmentState {
+                stage: stage(frag.module, frag.entry_point, &frag.compilation_options),
+                targets: Borrowed(frag.targets),
+            }),
+            multiview: desc.multiview,
+            cache: desc.cache.map(|cache| cache.inner.as_core().id),
+        };
+
+        let (id, error) =
+            self.context
+                .0
+                .device_create_mesh_pipeline(self.id, &descriptor, None, None);
+        if let Some(cause) = error {
+            if let wgc::pipeline::CreateRenderPipelineError::Internal { stage, ref error } = cause {
+                log::error!("Shader translation error for stage {:?}: {}", stage, error);
+                log::error!("Please report it to https://github.com/gfx-rs/wgpu");
+            }
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_mesh_pipeline",
+            );
+        }
+        CoreRenderPipeline {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
     fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<'_>,
@@ -1399,6 +1752,51 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
+    fn create_ray_tracing_pipeline(
+        &self,
+        desc: &crate::RayTracingPipelineDescriptor<'_>,
+    ) -> dispatch::DispatchRayTracingPipeline {
+        use wgc::pipeline as pipe;
+
+        let stages = desc
+            .stages
+            .iter()
+            .map(|stage| pipe::RayTracingStageDescriptor {
+                stage: stage.stage,
+                module: stage.module.inner.as_core().id,
+                entry_point: Borrowed(stage.entry_point),
+            })
+            .collect::<Vec<_>>();
+
+        let descriptor = pipe::RayTracingPipelineDescriptor {
+            label: desc.label.map(Borrowed),
+            layout: desc.layout.inner.as_core().id,
+            stages: Cow::Owned(stages),
+            groups: Borrowed(desc.groups),
+            max_recursion_depth: desc.max_recursion_depth,
+            cache: desc.cache.map(|cache| cache.inner.as_core().id),
+        };
+
+        let (id, error) =
+            self.context
+                .0
+                .device_create_ray_tracing_pipeline(self.id, &descriptor, None);
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_ray_tracing_pipeline",
+            );
+        }
+        CoreRayTracingPipeline {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
     unsafe fn create_pipeline_cache(
         &self,
         desc: &crate::PipelineCacheDescriptor<'_>,
@@ -1472,35 +1870,199 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
-    fn create_blas(
+    fn create_memory_heap(
         &self,
-        desc: &crate::CreateBlasDescriptor<'_>,
-        sizes: crate::BlasGeometrySizeDescriptors,
-    ) -> (Option<u64>, dispatch::DispatchBlas) {
-        let global = &self.context.0;
-        let (id, handle, error) =
-            global.device_create_blas(self.id, &desc.map_label(|l| l.map(Borrowed)), sizes, None);
+        desc: &crate::MemoryHeapDescriptor<'_>,
+    ) -> dispatch::DispatchMemoryHeap {
+        let (id, error) = self.context.0.device_create_memory_heap(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
         if let Some(cause) = error {
-            self.context
-                .handle_error(&self.error_sink, cause, desc.label, "Device::create_blas");
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_memory_heap",
+            );
         }
-        (
-            handle,
-            CoreBlas {
-                context: self.context.clone(),
-                id,
-                error_sink: Arc::clone(&self.error_sink),
-            }
-            .into(),
-        )
+        CoreMemoryHeap {
+            context: self.context.clone(),
+            id,
+        }
+        .into()
     }
 
-    fn create_tlas(&self, desc: &crate::CreateTlasDescriptor<'_>) -> dispatch::DispatchTlas {
-        let global = &self.context.0;
-        let (id, error) =
-            global.device_create_tlas(self.id, &desc.map_label(|l| l.map(Borrowed)), None);
-        if let Some(cause) = error {
-            self.context
+    fn texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor<'_>,
+    ) -> Option<crate::TextureMemoryRequirements> {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        match self
+            .context
+            .0
+            .device_texture_memory_requirements(self.id, &wgt_desc)
+        {
+            Ok(requirements) => Some(requirements),
+            Err(cause) => {
+                self.context.handle_error(
+                    &self.error_sink,
+                    cause,
+                    desc.label,
+                    "Device::texture_memory_requirements",
+                );
+                None
+            }
+        }
+    }
+
+    fn create_texture_aliased(
+        &self,
+        heap: &dispatch::DispatchMemoryHeap,
+        offset: crate::BufferAddress,
+        desc: &crate::TextureDescriptor<'_>,
+    ) -> dispatch::DispatchTexture {
+        let heap = heap.as_core();
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = self
+            .context
+            .0
+            .device_create_texture_aliased(self.id, heap.id, offset, &wgt_desc, None);
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_texture_aliased",
+            );
+        }
+
+        CoreTexture {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
+    fn create_sparse_buffer(&self, desc: &crate::BufferDescriptor<'_>) -> dispatch::DispatchBuffer {
+        let (id, error) = self.context.0.device_create_sparse_buffer(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_sparse_buffer",
+            );
+        }
+
+        CoreBuffer {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
+    fn create_sparse_texture(
+        &self,
+        desc: &crate::TextureDescriptor<'_>,
+    ) -> dispatch::DispatchTexture {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = self
+            .context
+            .0
+            .device_create_sparse_texture(self.id, &wgt_desc, None);
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_sparse_texture",
+            );
+        }
+
+        CoreTexture {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
+    fn create_blas(
+        &self,
+        desc: &crate::CreateBlasDescriptor<'_>,
+        sizes: crate::BlasGeometrySizeDescriptors,
+    ) -> (Option<u64>, dispatch::DispatchBlas) {
+        let global = &self.context.0;
+        let (id, handle, error) =
+            global.device_create_blas(self.id, &desc.map_label(|l| l.map(Borrowed)), sizes, None);
+        if let Some(cause) = error {
+            self.context
+                .handle_error(&self.error_sink, cause, desc.label, "Device::create_blas");
+        }
+        (
+            handle,
+            CoreBlas {
+                context: self.context.clone(),
+                id,
+                error_sink: Arc::clone(&self.error_sink),
+            }
+            .into(),
+        )
+    }
+
+    fn create_blas_from_serialized(
+        &self,
+        desc: &crate::CreateBlasDescriptor<'_>,
+        sizes: crate::BlasGeometrySizeDescriptors,
+        data: &[u8],
+    ) -> (Option<u64>, dispatch::DispatchBlas) {
+        let global = &self.context.0;
+        let (id, handle, error) = global.device_create_blas_from_serialized(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            sizes,
+            data,
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_blas_from_serialized",
+            );
+        }
+        (
+            handle,
+            CoreBlas {
+                context: self.context.clone(),
+                id,
+                error_sink: Arc::clone(&self.error_sink),
+            }
+            .into(),
+        )
+    }
+
+    fn is_serialized_blas_compatible(&self, data: &[u8]) -> bool {
+        self.context
+            .0
+            .device_is_serialized_blas_compatible(self.id, data)
+    }
+
+    fn create_tlas(&self, desc: &crate::CreateTlasDescriptor<'_>) -> dispatch::DispatchTlas {
+        let global = &self.context.0;
+        let (id, error) =
+            global.device_create_tlas(self.id, &desc.map_label(|l| l.map(Borrowed)), None);
+        if let Some(cause) = error {
+            self.context
                 .handle_error(&self.error_sink, cause, desc.label, "Device::create_tlas");
         }
         CoreTlas {
@@ -1565,6 +2127,68 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
+    fn create_shared_fence(
+        &self,
+        desc: &crate::SharedFenceDescriptor<'_>,
+    ) -> dispatch::DispatchSharedFence {
+        let (id, error) = self.context.0.device_create_shared_fence(
+            self.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_shared_fence",
+            );
+        }
+        CoreSharedFence {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
+    unsafe fn import_shared_fence(
+        &self,
+        handle: crate::SharedFenceHandle,
+        desc: &crate::SharedFenceDescriptor<'_>,
+    ) -> dispatch::DispatchSharedFence {
+        let (id, error) = unsafe {
+            self.context.0.device_import_shared_fence(
+                self.id,
+                handle,
+                &desc.map_label(|l| l.map(Borrowed)),
+                None,
+            )
+        };
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::import_shared_fence",
+            );
+        }
+        CoreSharedFence {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+        }
+        .into()
+    }
+
+    #[cfg(webgpu)]
+    fn import_external_texture(
+        &self,
+        _source: &web_sys::HtmlVideoElement,
+    ) -> dispatch::DispatchExternalTexture {
+        unimplemented!("External textures are only implemented for WebGPU")
+    }
+
     fn create_command_encoder(
         &self,
         desc: &crate::CommandEncoderDescriptor<'_>,
@@ -1592,6 +2216,37 @@ impl dispatch::DeviceInterface for CoreDevice {
         .into()
     }
 
+    fn create_command_encoder_for_queue(
+        &self,
+        queue: &dispatch::DispatchQueue,
+        desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> dispatch::DispatchCommandEncoder {
+        let queue = queue.as_core();
+
+        let (id, error) = self.context.0.device_create_command_encoder_for_queue(
+            self.id,
+            queue.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None,
+        );
+        if let Some(cause) = error {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "Device::create_command_encoder_for_queue",
+            );
+        }
+
+        CoreCommandEncoder {
+            context: self.context.clone(),
+            id,
+            error_sink: Arc::clone(&self.error_sink),
+            open: true,
+        }
+        .into()
+    }
+
     fn create_render_bundle_encoder(
         &self,
         desc: &crate::RenderBundleEncoderDescriptor<'_>,
@@ -1602,6 +2257,7 @@ impl dispatch::DeviceInterface for CoreDevice {
             depth_stencil: desc.depth_stencil,
             sample_count: desc.sample_count,
             multiview: desc.multiview,
+            inheritance: desc.inheritance,
         };
         let encoder = match wgc::command::RenderBundleEncoder::new(&descriptor, self.id, None) {
             Ok(encoder) => encoder,
@@ -1622,6 +2278,15 @@ impl dispatch::DeviceInterface for CoreDevice {
             .device_set_device_lost_closure(self.id, device_lost_callback);
     }
 
+    fn set_memory_budget_callback(
+        &self,
+        memory_budget_callback: dispatch::ArcMemoryBudgetCallback,
+    ) {
+        self.context
+            .0
+            .device_set_memory_budget_callback(self.id, memory_budget_callback);
+    }
+
     fn on_uncaptured_error(&self, handler: Box<dyn crate::UncapturedErrorHandler>) {
         let mut error_sink = self.error_sink.lock();
         error_sink.uncaptured_handler = Some(handler);
@@ -1636,9 +2301,7 @@ impl dispatch::DeviceInterface for CoreDevice {
     }
 
     fn pop_error_scope(&self) -> Pin<Box<dyn dispatch::PopErrorScopeFuture>> {
-        let mut error_sink = self.error_sink.lock();
-        let scope = error_sink.scopes.pop().unwrap();
-        Box::pin(ready(scope.error))
+        Box::pin(ready(self.pop_error_scope_blocking()))
     }
 
     unsafe fn start_graphics_debugger_capture(&self) {
@@ -1678,6 +2341,26 @@ impl dispatch::DeviceInterface for CoreDevice {
         self.context.0.device_generate_allocator_report(self.id)
     }
 
+    fn trim_memory(&self) -> wgt::TrimMemoryReport {
+        self.context.0.device_trim_memory(self.id)
+    }
+
+    fn generate_memory_report(&self, include_resources: bool) -> Option<wgt::MemoryReport> {
+        Some(
+            self.context
+                .0
+                .device_generate_memory_report(self.id, include_resources),
+        )
+    }
+
+    fn set_barrier_logging(&self, enabled: bool) {
+        self.context.0.device_set_barrier_logging(self.id, enabled);
+    }
+
+    fn take_barrier_report(&self) -> Option<wgt::BarrierReport> {
+        Some(self.context.0.device_take_barrier_report(self.id))
+    }
+
     fn destroy(&self) {
         self.context.0.device_destroy(self.id);
     }
@@ -1865,16 +2548,172 @@ impl dispatch::QueueInterface for CoreQueue {
         index
     }
 
+    fn submit_with_external_sync(
+        &self,
+        command_buffers: &mut dyn Iterator<Item = dispatch::DispatchCommandBuffer>,
+        wait: &[wgt::SharedFenceValue<&dispatch::DispatchSharedFence>],
+        signal: &[wgt::SharedFenceValue<&dispatch::DispatchSharedFence>],
+    ) -> u64 {
+        let temp_command_buffers =
+            command_buffers.collect::<SmallVec<dispatch::DispatchCommandBuffer, 4>>();
+        let command_buffer_ids = temp_command_buffers
+            .iter()
+            .map(|cmdbuf| cmdbuf.as_core().id)
+            .collect::<SmallVec<wgc::id::CommandBufferId, 4>>();
+        let map_values = |values: &[wgt::SharedFenceValue<&dispatch::DispatchSharedFence>]| {
+            values
+                .iter()
+                .map(|value| wgt::SharedFenceValue {
+                    fence: value.fence.as_core().id,
+                    value: value.value,
+                })
+                .collect::<SmallVec<_, 4>>()
+        };
+        let wait = map_values(wait);
+        let signal = map_values(signal);
+
+        let index = match self.context.0.queue_submit_with_external_sync(
+            self.id,
+            &command_buffer_ids,
+            &wait,
+            &signal,
+        ) {
+            Ok(index) => index,
+            Err((index, err)) => {
+                self.context.handle_error_nolabel(
+                    &self.error_sink,
+                    err,
+                    "Queue::submit_with_external_sync",
+                );
+                index
+            }
+        };
+
+        drop(temp_command_buffers);
+
+        index
+    }
+
+    fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[wgt::SparseBufferBinding<
+            &dispatch::DispatchBuffer,
+            &dispatch::DispatchMemoryHeap,
+        >],
+        texture_bindings: &[wgt::SparseTextureBinding<
+            &dispatch::DispatchTexture,
+            &dispatch::DispatchMemoryHeap,
+        >],
+    ) {
+        let map_memory = |memory: &Option<wgt::HeapRegion<&dispatch::DispatchMemoryHeap>>| {
+            memory.map(|region| wgt::HeapRegion {
+                heap: region.heap.as_core().id,
+                offset: region.offset,
+            })
+        };
+        let buffer_bindings = buffer_bindings
+            .iter()
+            .map(|binding| wgt::SparseBufferBinding {
+                buffer: binding.buffer.as_core().id,
+                offset: binding.offset,
+                size: binding.size,
+                memory: map_memory(&binding.memory),
+            })
+            .collect::<SmallVec<_, 4>>();
+        let texture_bindings = texture_bindings
+            .iter()
+            .map(|binding| wgt::SparseTextureBinding {
+                texture: binding.texture.as_core().id,
+                mip_level: binding.mip_level,
+                array_layer: binding.array_layer,
+                origin: binding.origin,
+                size: binding.size,
+                memory: map_memory(&binding.memory),
+            })
+            .collect::<SmallVec<_, 4>>();
+
+        if let Err(cause) =
+            self.context
+                .0
+                .queue_bind_sparse_memory(self.id, &buffer_bindings, &texture_bindings)
+        {
+            self.context
+                .handle_error_nolabel(&self.error_sink, cause, "Queue::bind_sparse_memory");
+        }
+    }
+
     fn get_timestamp_period(&self) -> f32 {
         self.context.0.queue_get_timestamp_period(self.id)
     }
 
+    fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        self.context.0.queue_get_timestamp_calibration(self.id)
+    }
+
     fn on_submitted_work_done(&self, callback: dispatch::BoxSubmittedWorkDoneCallback) {
         self.context
             .0
             .queue_on_submitted_work_done(self.id, callback);
     }
 
+    fn on_submission_done(
+        &self,
+        submission_index: u64,
+        callback: dispatch::BoxSubmittedWorkDoneCallback,
+    ) {
+        self.context
+            .0
+            .queue_on_submission_done(self.id, submission_index, callback);
+    }
+
+    fn get_completed_submission_index(&self) -> u64 {
+        match self.context.0.queue_get_completed_submission_index(self.id) {
+            Ok(submission_index) => submission_index,
+            Err(err) => {
+                self.context.handle_error_nolabel(
+                    &self.error_sink,
+                    err,
+                    "Queue::get_completed_submission_index",
+                );
+                0
+            }
+        }
+    }
+
+    fn poll(&self, poll_type: wgt::PollType<u64>) -> Result<crate::PollStatus, crate::PollError> {
+        match self.context.0.queue_poll(self.id, poll_type) {
+            Ok(status) => Ok(status),
+            Err(err) => {
+                if let Some(poll_error) = err.to_poll_error() {
+                    return Err(poll_error);
+                }
+
+                self.context
+                    .handle_error_fatal(err, "SubmissionIndex::wait")
+            }
+        }
+    }
+
+    fn wait_for_submission(&self, submission_index: u64) {
+        match self
+            .context
+            .0
+            .queue_wait_for_submission(self.id, submission_index)
+        {
+            Ok(()) => {}
+            Err(wgc::device::WaitIdleError::Device(err)) => {
+                self.context.handle_error_nolabel(
+                    &self.error_sink,
+                    err,
+                    "Queue::wait_for_submission",
+                );
+            }
+            Err(err) => self
+                .context
+                .handle_error_fatal(err, "Queue::wait_for_submission"),
+        }
+    }
+
     fn compact_blas(&self, blas: &dispatch::DispatchBlas) -> (Option<u64>, dispatch::DispatchBlas) {
         let (id, handle, error) =
             self.context
@@ -1907,6 +2746,29 @@ impl dispatch::ShaderModuleInterface for CoreShaderModule {
     fn get_compilation_info(&self) -> Pin<Box<dyn dispatch::ShaderCompilationInfoFuture>> {
         Box::pin(ready(self.compilation_info.clone()))
     }
+
+    fn binding(&self, name: &str) -> Option<(u32, u32)> {
+        self.context
+            .0
+            .shader_module_resource_binding(self.id, name)
+            .map(|binding| (binding.group, binding.binding))
+    }
+
+    fn workgroup_memory(&self, entry_point: &str) -> Option<wgt::WorkgroupMemoryInfo> {
+        self.context
+            .0
+            .shader_module_workgroup_memory(self.id, entry_point)
+    }
+
+    fn check_layout(
+        &self,
+        name: &str,
+        layout: &wgt::ShaderTypeLayout,
+    ) -> Result<(), wgt::ShaderLayoutError> {
+        self.context
+            .0
+            .shader_module_check_buffer_layout(self.id, name, layout)
+    }
 }
 
 impl Drop for CoreShaderModule {
@@ -2098,6 +2960,32 @@ impl dispatch::BlasInterface for CoreBlas {
             }
         }
     }
+
+    fn compacted_size(&self) -> Option<wgt::BufferAddress> {
+        match self.context.0.blas_compacted_size(self.id) {
+            Ok(size) => size,
+            Err(cause) => {
+                self.context
+                    .handle_error_nolabel(&self.error_sink, cause, "Blas::compacted_size");
+                None
+            }
+        }
+    }
+
+    fn serialize(&self, callback: BlasSerializeCallback) {
+        let callback: wgc::resource::BlasSerializeCallback =
+            Box::new(|result: BlasSerializeResult| {
+                callback(result.map_err(|_| crate::BlasAsyncError));
+            });
+
+        match self.context.0.blas_serialize_async(self.id, callback) {
+            Ok(()) => (),
+            Err(cause) => {
+                self.context
+                    .handle_error_nolabel(&self.error_sink, cause, "Blas::serialize")
+            }
+        }
+    }
 }
 
 impl Drop for CoreBlas {
@@ -2122,6 +3010,35 @@ impl Drop for CoreQuerySet {
     }
 }
 
+impl dispatch::SharedFenceInterface for CoreSharedFence {
+    fn export(&self) -> Option<crate::SharedFenceHandle> {
+        match self.context.0.shared_fence_export(self.id) {
+            Ok(handle) => Some(handle),
+            Err(cause) => {
+                self.context
+                    .handle_error_nolabel(&self.error_sink, cause, "SharedFence::export");
+                None
+            }
+        }
+    }
+}
+
+impl Drop for CoreSharedFence {
+    fn drop(&mut self) {
+        self.context.0.shared_fence_drop(self.id)
+    }
+}
+
+impl dispatch::MemoryHeapInterface for CoreMemoryHeap {}
+
+impl Drop for CoreMemoryHeap {
+    fn drop(&mut self) {
+        self.context.0.memory_heap_drop(self.id)
+    }
+}
+
+impl dispatch::ExternalTextureInterface for CoreExternalTexture {}
+
 impl dispatch::PipelineLayoutInterface for CorePipelineLayout {}
 
 impl Drop for CorePipelineLayout {
@@ -2184,6 +3101,42 @@ impl Drop for CoreComputePipeline {
     }
 }
 
+impl dispatch::RayTracingPipelineInterface for CoreRayTracingPipeline {
+    fn shader_binding_table(
+        &self,
+        desc: &crate::ShaderBindingTableDescriptor<'_>,
+    ) -> (Vec<u8>, crate::ShaderBindingTableLayout) {
+        let descriptor = wgc::pipeline::ShaderBindingTableDescriptor {
+            ray_generation: desc.ray_generation,
+            miss: Borrowed(desc.miss),
+            hit: Borrowed(desc.hit),
+            callable: Borrowed(desc.callable),
+        };
+        match self
+            .context
+            .0
+            .ray_tracing_pipeline_shader_binding_table(self.id, &descriptor)
+        {
+            Ok(table) => table,
+            Err(cause) => {
+                self.context.handle_error(
+                    &self.error_sink,
+                    cause,
+                    desc.label,
+                    "Device::create_shader_binding_table",
+                );
+                (Vec::new(), crate::ShaderBindingTableLayout::default())
+            }
+        }
+    }
+}
+
+impl Drop for CoreRayTracingPipeline {
+    fn drop(&mut self) {
+        self.context.0.ray_tracing_pipeline_drop(self.id)
+    }
+}
+
 impl dispatch::PipelineCacheInterface for CorePipelineCache {
     fn get_data(&self) -> Option<Vec<u8>> {
         self.context.0.pipeline_cache_get_data(self.id)
@@ -2279,7 +3232,27 @@ impl dispatch::CommandEncoderInterface for CoreCommandEncoder {
             self.context.handle_error_nolabel(
                 &self.error_sink,
                 cause,
-                "CommandEncoder::copy_texture_to_texture",
+                "CommandEncoder::copy_texture_to_texture",
+            );
+        }
+    }
+
+    fn resolve_texture(
+        &self,
+        source: &dispatch::DispatchTextureView,
+        destination: &dispatch::DispatchTextureView,
+        regions: &[crate::Rect],
+    ) {
+        if let Err(cause) = self.context.0.command_encoder_resolve_texture(
+            self.id,
+            source.as_core().id,
+            destination.as_core().id,
+            regions,
+        ) {
+            self.context.handle_error_nolabel(
+                &self.error_sink,
+                cause,
+                "CommandEncoder::resolve_texture",
             );
         }
     }
@@ -2297,11 +3270,19 @@ impl dispatch::CommandEncoderInterface for CoreCommandEncoder {
                     end_of_pass_write_index: tw.end_of_pass_write_index,
                 });
 
+        let pipeline_statistics_query = desc.pipeline_statistics_query.as_ref().map(|psq| {
+            wgc::command::PassPipelineStatisticsQuery {
+                query_set: psq.query_set.inner.as_core().id,
+                query_index: psq.query_index,
+            }
+        });
+
         let (pass, err) = self.context.0.command_encoder_begin_compute_pass(
             self.id,
             &wgc::command::ComputePassDescriptor {
                 label: desc.label.map(Borrowed),
                 timestamp_writes,
+                pipeline_statistics_query,
             },
         );
 
@@ -2347,6 +3328,11 @@ impl dispatch::CommandEncoderInterface for CoreCommandEncoder {
                 view: dsa.view.inner.as_core().id,
                 depth: map_pass_channel(dsa.depth_ops.as_ref()),
                 stencil: map_pass_channel(dsa.stencil_ops.as_ref()),
+                resolve: dsa.resolve.map(|resolve| wgt::DepthStencilResolve {
+                    target: resolve.target.inner.as_core().id,
+                    depth_mode: resolve.depth_mode,
+                    resolve_stencil: resolve.resolve_stencil,
+                }),
             }
         });
 
@@ -2359,6 +3345,13 @@ impl dispatch::CommandEncoderInterface for CoreCommandEncoder {
                     end_of_pass_write_index: tw.end_of_pass_write_index,
                 });
 
+        let pipeline_statistics_query = desc.pipeline_statistics_query.as_ref().map(|psq| {
+            wgc::command::PassPipelineStatisticsQuery {
+                query_set: psq.query_set.inner.as_core().id,
+                query_index: psq.query_index,
+            }
+        });
+
         let (pass, err) = self.context.0.command_encoder_begin_render_pass(
             self.id,
             &wgc::command::RenderPassDescriptor {
@@ -2367,6 +3360,9 @@ impl dispatch::CommandEncoderInterface for CoreCommandEncoder {
                 color_attachments: Borrowed(&colors),
                 depth_stencil_attachment: depth_stencil.as_ref(),
                 occlusion_query_set: desc.occlusion_query_set.map(|qs| qs.inner.as_core().id),
+                pipeline_statistics_query: pipeline_statistics_query.as_ref(),
+                flags: desc.flags,
+                multiview: desc.multiview,
             },
         );
 
@@ -2388,8 +3384,11 @@ impl dispatch::CommandEncoderInterface for CoreCommandEncoder {
         .into()
     }
 
-    fn finish(&mut self) -> dispatch::DispatchCommandBuffer {
-        let descriptor = wgt::CommandBufferDescriptor::default();
+    fn finish(&mut self, reusable: bool) -> dispatch::DispatchCommandBuffer {
+        let descriptor = wgt::CommandBufferDescriptor {
+            label: None,
+            reusable,
+        };
         self.open = false; // prevent the drop
         let (id, error) = self.context.0.command_encoder_finish(self.id, &descriptor);
         if let Some(cause) = error {
@@ -2612,6 +3611,34 @@ impl dispatch::CommandEncoderInterface for CoreCommandEncoder {
         }
     }
 
+    fn trace_rays(&self, desc: &crate::TraceRaysDescriptor<'_>) {
+        let bind_groups = desc
+            .bind_groups
+            .iter()
+            .map(|&(bind_group, offsets)| (bind_group.inner.as_core().id, offsets))
+            .collect::<SmallVec<_, 4>>();
+
+        if let Err(cause) = self.context.0.command_encoder_trace_rays(
+            self.id,
+            &wgc::command::TraceRaysDescriptor {
+                label: desc.label.map(Borrowed),
+                pipeline: desc.pipeline.inner.as_core().id,
+                bind_groups: &bind_groups,
+                push_constants: desc.push_constants,
+                shader_binding_table: desc.shader_binding_table.buffer.inner.as_core().id,
+                layout: desc.shader_binding_table.layout,
+                size: [desc.width, desc.height, desc.depth],
+            },
+        ) {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                desc.label,
+                "CommandEncoder::trace_rays",
+            );
+        }
+    }
+
     fn transition_resources<'a>(
         &mut self,
         buffer_transitions: &mut dyn Iterator<
@@ -3040,6 +4067,36 @@ impl dispatch::RenderPassInterface for CoreRenderPass {
         }
     }
 
+    fn set_shading_rate(&mut self, rate: crate::ShadingRate) {
+        if let Err(cause) = self
+            .context
+            .0
+            .render_pass_set_shading_rate(&mut self.pass, rate)
+        {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::set_shading_rate",
+            );
+        }
+    }
+
+    fn set_viewports(&mut self, viewports: &[crate::Viewport]) {
+        if let Err(cause) = self
+            .context
+            .0
+            .render_pass_set_viewports(&mut self.pass, viewports)
+        {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::set_viewports",
+            );
+        }
+    }
+
     fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
         if let Err(cause) = self.context.0.render_pass_draw(
             &mut self.pass,
@@ -3223,6 +4280,98 @@ impl dispatch::RenderPassInterface for CoreRenderPass {
         }
     }
 
+    fn draw_mesh_tasks(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) {
+        if let Err(cause) = self.context.0.render_pass_draw_mesh_tasks(
+            &mut self.pass,
+            group_count_x,
+            group_count_y,
+            group_count_z,
+        ) {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::draw_mesh_tasks",
+            );
+        }
+    }
+
+    fn draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: &dispatch::DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+    ) {
+        let indirect_buffer = indirect_buffer.as_core();
+
+        if let Err(cause) = self.context.0.render_pass_draw_mesh_tasks_indirect(
+            &mut self.pass,
+            indirect_buffer.id,
+            indirect_offset,
+        ) {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::draw_mesh_tasks_indirect",
+            );
+        }
+    }
+
+    fn multi_draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: &dispatch::DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        count: u32,
+    ) {
+        let indirect_buffer = indirect_buffer.as_core();
+
+        if let Err(cause) = self.context.0.render_pass_multi_draw_mesh_tasks_indirect(
+            &mut self.pass,
+            indirect_buffer.id,
+            indirect_offset,
+            count,
+        ) {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::multi_draw_mesh_tasks_indirect",
+            );
+        }
+    }
+
+    fn multi_draw_mesh_tasks_indirect_count(
+        &mut self,
+        indirect_buffer: &dispatch::DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        count_buffer: &dispatch::DispatchBuffer,
+        count_buffer_offset: crate::BufferAddress,
+        max_count: u32,
+    ) {
+        let indirect_buffer = indirect_buffer.as_core();
+        let count_buffer = count_buffer.as_core();
+
+        if let Err(cause) = self
+            .context
+            .0
+            .render_pass_multi_draw_mesh_tasks_indirect_count(
+                &mut self.pass,
+                indirect_buffer.id,
+                indirect_offset,
+                count_buffer.id,
+                count_buffer_offset,
+                max_count,
+            )
+        {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::multi_draw_mesh_tasks_indirect_count",
+            );
+        }
+    }
+
     fn insert_debug_marker(&mut self, label: &str) {
         if let Err(cause) = self
             .context
@@ -3347,6 +4496,42 @@ impl dispatch::RenderPassInterface for CoreRenderPass {
         }
     }
 
+    fn begin_conditional_block(
+        &mut self,
+        buffer: &dispatch::DispatchBuffer,
+        offset: crate::BufferAddress,
+    ) {
+        let buffer = buffer.as_core();
+
+        if let Err(cause) =
+            self.context
+                .0
+                .render_pass_begin_conditional_block(&mut self.pass, buffer.id, offset)
+        {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::begin_conditional_block",
+            );
+        }
+    }
+
+    fn end_conditional_block(&mut self) {
+        if let Err(cause) = self
+            .context
+            .0
+            .render_pass_end_conditional_block(&mut self.pass)
+        {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::end_conditional_block",
+            );
+        }
+    }
+
     fn execute_bundles(
         &mut self,
         render_bundles: &mut dyn Iterator<Item = &dispatch::DispatchRenderBundle>,
@@ -3368,6 +4553,30 @@ impl dispatch::RenderPassInterface for CoreRenderPass {
         }
     }
 
+    fn create_parallel_encoder(&mut self) -> dispatch::DispatchRenderPass {
+        let (pass, err) = self
+            .context
+            .0
+            .render_pass_create_parallel_encoder(&mut self.pass);
+
+        if let Some(cause) = err {
+            self.context.handle_error(
+                &self.error_sink,
+                cause,
+                self.pass.label(),
+                "RenderPass::create_parallel_encoder",
+            );
+        }
+
+        CoreRenderPass {
+            context: self.context.clone(),
+            pass,
+            error_sink: self.error_sink.clone(),
+            id: crate::cmp::Identifier::create(),
+        }
+        .into()
+    }
+
     fn end(&mut self) {
         if let Err(cause) = self.context.0.render_pass_end(&mut self.pass) {
             self.context.handle_error(
@@ -3494,12 +4703,49 @@ impl dispatch::RenderBundleEncoderInterface for CoreRenderBundleEncoder {
         )
     }
 
-    fn finish(self, desc: &crate::RenderBundleDescriptor<'_>) -> dispatch::DispatchRenderBundle
-    where
-        Self: Sized,
-    {
+    fn write_timestamp(&mut self, query_set: &dispatch::DispatchQuerySet, query_index: u32) {
+        let query_set = query_set.as_core();
+
+        wgpu_render_bundle_write_timestamp(&mut self.encoder, query_set.id, query_index)
+    }
+
+    fn begin_occlusion_query(&mut self, query_index: u32) {
+        wgpu_render_bundle_begin_occlusion_query(&mut self.encoder, query_index)
+    }
+
+    fn end_occlusion_query(&mut self) {
+        wgpu_render_bundle_end_occlusion_query(&mut self.encoder)
+    }
+
+    fn begin_pipeline_statistics_query(
+        &mut self,
+        query_set: &dispatch::DispatchQuerySet,
+        query_index: u32,
+    ) {
+        let query_set = query_set.as_core();
+
+        wgpu_render_bundle_begin_pipeline_statistics_query(
+            &mut self.encoder,
+            query_set.id,
+            query_index,
+        )
+    }
+
+    fn end_pipeline_statistics_query(&mut self) {
+        wgpu_render_bundle_end_pipeline_statistics_query(&mut self.encoder)
+    }
+
+    fn finish(
+        &mut self,
+        desc: &crate::RenderBundleDescriptor<'_>,
+    ) -> dispatch::DispatchRenderBundle {
+        let parent = self.encoder.parent();
+        let encoder = mem::replace(
+            &mut self.encoder,
+            wgc::command::RenderBundleEncoder::dummy(parent),
+        );
         let (id, error) = self.context.0.render_bundle_encoder_finish(
-            self.encoder,
+            encoder,
             &desc.map_label(|l| l.map(Borrowed)),
             None,
         );
@@ -3538,6 +4784,7 @@ impl dispatch::SurfaceInterface for CoreSurface {
 
     fn get_current_texture(
         &self,
+        timeout: Option<core::time::Duration>,
     ) -> (
         Option<dispatch::DispatchTexture>,
         crate::SurfaceStatus,
@@ -3549,7 +4796,14 @@ impl dispatch::SurfaceInterface for CoreSurface {
         }
         .into();
 
-        match self.context.0.surface_get_current_texture(self.id, None) {
+        let output = match timeout {
+            Some(timeout) => self
+                .context
+                .0
+                .surface_get_current_texture_with_timeout(self.id, timeout, None),
+            None => self.context.0.surface_get_current_texture(self.id, None),
+        };
+        match output {
             Ok(wgc::present::SurfaceOutput {
                 status,
                 texture: texture_id,
@@ -3582,6 +4836,50 @@ impl dispatch::SurfaceInterface for CoreSurface {
             }
         }
     }
+
+    fn get_presentation_statistics(&self) -> Vec<crate::PresentationStatistics> {
+        match self.context.0.surface_get_presentation_statistics(self.id) {
+            Ok(statistics) => statistics,
+            Err(err) => {
+                let error_sink = self.error_sink.lock();
+                match error_sink.as_ref() {
+                    Some(error_sink) => {
+                        self.context.handle_error_nolabel(
+                            error_sink,
+                            err,
+                            "Surface::get_presentation_statistics",
+                        );
+                        Vec::new()
+                    }
+                    None => self
+                        .context
+                        .handle_error_fatal(err, "Surface::get_presentation_statistics"),
+                }
+            }
+        }
+    }
+
+    fn presentation_path(&self) -> crate::PresentationPath {
+        match self.context.0.surface_presentation_path(self.id) {
+            Ok(path) => path,
+            Err(err) => {
+                let error_sink = self.error_sink.lock();
+                match error_sink.as_ref() {
+                    Some(error_sink) => {
+                        self.context.handle_error_nolabel(
+                            error_sink,
+                            err,
+                            "Surface::presentation_path",
+                        );
+                        crate::PresentationPath::Unknown
+                    }
+                    None => self
+                        .context
+                        .handle_error_fatal(err, "Surface::presentation_path"),
+                }
+            }
+        }
+    }
 }
 
 impl Drop for CoreSurface {
@@ -3591,8 +4889,23 @@ impl Drop for CoreSurface {
 }
 
 impl dispatch::SurfaceOutputDetailInterface for CoreSurfaceOutputDetail {
-    fn present(&self) {
-        match self.context.0.surface_present(self.surface_id) {
+    fn set_target_present_time(&self, time: crate::PresentationTimestamp) {
+        if let Err(err) = self
+            .context
+            .0
+            .surface_set_target_present_time(self.surface_id, time)
+        {
+            self.context
+                .handle_error_fatal(err, "SurfaceTexture::set_target_present_time")
+        }
+    }
+
+    fn present(&self, damage: &[crate::Rect]) {
+        match self
+            .context
+            .0
+            .surface_present_with_damage(self.surface_id, damage)
+        {
             Ok(_status) => (),
             Err(err) => self.context.handle_error_fatal(err, "Surface::present"),
         }
@@ -2,15 +2,17 @@
 //!
 //! - `wgpu_core`: An implementation of the the wgpu api on top of various native graphics APIs.
 //! - `webgpu`: An implementation of the wgpu api which calls WebGPU directly.
+//! - `custom`: Implementations provided by users, see [`crate::custom`].
 //!
-//! The interface traits are all object safe and listed in the `InterfaceTypes` trait.
+//! There is an interface trait for each type of the wgpu api, which all backends implement.
+//! The interface traits are all object safe, as custom backends are used as trait objects.
 //!
 //! The method for dispatching should optimize well if only one backend is compiled in,
 //! as-if there was no dispatching at all.
 
 #![allow(drop_bounds)] // This exists to remind implementors to impl drop.
 #![allow(clippy::too_many_arguments)] // It's fine.
-#![allow(missing_docs, clippy::missing_safety_doc)] // Interfaces are not documented
+#![allow(missing_docs, clippy::missing_safety_doc)] // Methods are documented on the api types calling them.
 
 use crate::{Blas, Tlas, WasmNotSend, WasmNotSendSync};
 
@@ -37,6 +39,7 @@ macro_rules! trait_alias {
 // Various return futures in the API.
 trait_alias!(RequestAdapterFuture: Future<Output = Result<DispatchAdapter, wgt::RequestAdapterError>> + WasmNotSend + 'static);
 trait_alias!(RequestDeviceFuture: Future<Output = Result<(DispatchDevice, DispatchQueue), crate::RequestDeviceError>> + WasmNotSend + 'static);
+trait_alias!(RequestDeviceWithQueuesFuture: Future<Output = Result<(DispatchDevice, DispatchQueue, Vec<DispatchQueue>), crate::RequestDeviceError>> + WasmNotSend + 'static);
 trait_alias!(PopErrorScopeFuture: Future<Output = Option<crate::Error>> + WasmNotSend + 'static);
 trait_alias!(ShaderCompilationInfoFuture: Future<Output = crate::CompilationInfo> + WasmNotSend + 'static);
 
@@ -46,6 +49,10 @@ pub type BoxDeviceLostCallback = Box<dyn FnOnce(crate::DeviceLostReason, String)
 #[cfg(not(send_sync))]
 pub type BoxDeviceLostCallback = Box<dyn FnOnce(crate::DeviceLostReason, String) + 'static>;
 #[cfg(send_sync)]
+pub type ArcMemoryBudgetCallback = Arc<dyn Fn(crate::MemoryInfo) + Send + Sync + 'static>;
+#[cfg(not(send_sync))]
+pub type ArcMemoryBudgetCallback = Arc<dyn Fn(crate::MemoryInfo) + 'static>;
+#[cfg(send_sync)]
 pub type BoxSubmittedWorkDoneCallback = Box<dyn FnOnce() + Send + 'static>;
 #[cfg(not(send_sync))]
 pub type BoxSubmittedWorkDoneCallback = Box<dyn FnOnce() + 'static>;
@@ -59,6 +66,12 @@ pub type BlasCompactCallback = Box<dyn FnOnce(Result<(), crate::BlasAsyncError>)
 #[cfg(not(send_sync))]
 pub type BlasCompactCallback = Box<dyn FnOnce(Result<(), crate::BlasAsyncError>) + 'static>;
 
+#[cfg(send_sync)]
+pub type BlasSerializeCallback =
+    Box<dyn FnOnce(Result<Vec<u8>, crate::BlasAsyncError>) + Send + 'static>;
+#[cfg(not(send_sync))]
+pub type BlasSerializeCallback = Box<dyn FnOnce(Result<Vec<u8>, crate::BlasAsyncError>) + 'static>;
+
 // remove when rust 1.86
 #[cfg_attr(not(custom), expect(dead_code))]
 pub trait AsAny {
@@ -74,6 +87,10 @@ impl<T: 'static> AsAny for T {
 // Common traits on all the interface traits
 trait_alias!(CommonTraits: AsAny + Any + Debug + WasmNotSendSync);
 
+/// An [`Instance`](crate::Instance).
+///
+/// This is the entry point of a backend: the objects of all other interfaces are created,
+/// directly or indirectly, by an instance.
 pub trait InstanceInterface: CommonTraits {
     fn new(desc: &crate::InstanceDescriptor) -> Self
     where
@@ -91,20 +108,31 @@ pub trait InstanceInterface: CommonTraits {
 
     fn poll_all_devices(&self, force_wait: bool) -> bool;
 
+    unsafe fn start_graphics_debugger_capture(&self) -> bool;
+    unsafe fn stop_graphics_debugger_capture(&self);
+
     #[cfg(feature = "wgsl")]
     fn wgsl_language_features(&self) -> crate::WgslLanguageFeatures;
 }
 
+/// An [`Adapter`](crate::Adapter).
 pub trait AdapterInterface: CommonTraits {
     fn request_device(
         &self,
         desc: &crate::DeviceDescriptor<'_>,
     ) -> Pin<Box<dyn RequestDeviceFuture>>;
+    fn request_device_with_queues(
+        &self,
+        desc: &crate::DeviceDescriptor<'_>,
+        queues: &[crate::QueueDescriptor<'_>],
+    ) -> Pin<Box<dyn RequestDeviceWithQueuesFuture>>;
 
     fn is_surface_supported(&self, surface: &DispatchSurface) -> bool;
 
     fn features(&self) -> crate::Features;
 
+    fn applied_workarounds(&self) -> crate::Toggles;
+
     fn limits(&self) -> crate::Limits;
 
     fn downlevel_capabilities(&self) -> crate::DownlevelCapabilities;
@@ -117,8 +145,11 @@ pub trait AdapterInterface: CommonTraits {
     ) -> crate::TextureFormatFeatures;
 
     fn get_presentation_timestamp(&self) -> crate::PresentationTimestamp;
+
+    fn memory_info(&self) -> Option<crate::MemoryInfo>;
 }
 
+/// A [`Device`](crate::Device).
 pub trait DeviceInterface: CommonTraits {
     fn features(&self) -> crate::Features;
     fn limits(&self) -> crate::Limits;
@@ -127,6 +158,7 @@ pub trait DeviceInterface: CommonTraits {
         &self,
         desc: crate::ShaderModuleDescriptor<'_>,
         shader_bound_checks: crate::ShaderRuntimeChecks,
+        optimization: crate::ShaderOptimization,
     ) -> DispatchShaderModule;
 
     unsafe fn create_shader_module_passthrough(
@@ -147,34 +179,79 @@ pub trait DeviceInterface: CommonTraits {
         &self,
         desc: &crate::RenderPipelineDescriptor<'_>,
     ) -> DispatchRenderPipeline;
+    fn create_mesh_pipeline(
+        &self,
+        desc: &crate::MeshPipelineDescriptor<'_>,
+    ) -> DispatchRenderPipeline;
     fn create_compute_pipeline(
         &self,
         desc: &crate::ComputePipelineDescriptor<'_>,
     ) -> DispatchComputePipeline;
+    fn create_ray_tracing_pipeline(
+        &self,
+        desc: &crate::RayTracingPipelineDescriptor<'_>,
+    ) -> DispatchRayTracingPipeline;
     unsafe fn create_pipeline_cache(
         &self,
         desc: &crate::PipelineCacheDescriptor<'_>,
     ) -> DispatchPipelineCache;
     fn create_buffer(&self, desc: &crate::BufferDescriptor<'_>) -> DispatchBuffer;
     fn create_texture(&self, desc: &crate::TextureDescriptor<'_>) -> DispatchTexture;
+    fn create_memory_heap(&self, desc: &crate::MemoryHeapDescriptor<'_>) -> DispatchMemoryHeap;
+    fn texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor<'_>,
+    ) -> Option<crate::TextureMemoryRequirements>;
+    fn create_texture_aliased(
+        &self,
+        heap: &DispatchMemoryHeap,
+        offset: crate::BufferAddress,
+        desc: &crate::TextureDescriptor<'_>,
+    ) -> DispatchTexture;
+    fn create_sparse_buffer(&self, desc: &crate::BufferDescriptor<'_>) -> DispatchBuffer;
+    fn create_sparse_texture(&self, desc: &crate::TextureDescriptor<'_>) -> DispatchTexture;
     fn create_blas(
         &self,
         desc: &crate::CreateBlasDescriptor<'_>,
         sizes: crate::BlasGeometrySizeDescriptors,
     ) -> (Option<u64>, DispatchBlas);
+    fn create_blas_from_serialized(
+        &self,
+        desc: &crate::CreateBlasDescriptor<'_>,
+        sizes: crate::BlasGeometrySizeDescriptors,
+        data: &[u8],
+    ) -> (Option<u64>, DispatchBlas);
+    fn is_serialized_blas_compatible(&self, data: &[u8]) -> bool;
     fn create_tlas(&self, desc: &crate::CreateTlasDescriptor<'_>) -> DispatchTlas;
     fn create_sampler(&self, desc: &crate::SamplerDescriptor<'_>) -> DispatchSampler;
     fn create_query_set(&self, desc: &crate::QuerySetDescriptor<'_>) -> DispatchQuerySet;
+    fn create_shared_fence(&self, desc: &crate::SharedFenceDescriptor<'_>) -> DispatchSharedFence;
+    unsafe fn import_shared_fence(
+        &self,
+        handle: crate::SharedFenceHandle,
+        desc: &crate::SharedFenceDescriptor<'_>,
+    ) -> DispatchSharedFence;
+    #[cfg(webgpu)]
+    fn import_external_texture(
+        &self,
+        source: &web_sys::HtmlVideoElement,
+    ) -> DispatchExternalTexture;
     fn create_command_encoder(
         &self,
         desc: &crate::CommandEncoderDescriptor<'_>,
     ) -> DispatchCommandEncoder;
+    fn create_command_encoder_for_queue(
+        &self,
+        queue: &DispatchQueue,
+        desc: &crate::CommandEncoderDescriptor<'_>,
+    ) -> DispatchCommandEncoder;
     fn create_render_bundle_encoder(
         &self,
         desc: &crate::RenderBundleEncoderDescriptor<'_>,
     ) -> DispatchRenderBundleEncoder;
 
     fn set_device_lost_callback(&self, device_lost_callback: BoxDeviceLostCallback);
+    fn set_memory_budget_callback(&self, memory_budget_callback: ArcMemoryBudgetCallback);
 
     fn on_uncaptured_error(&self, handler: Box<dyn crate::UncapturedErrorHandler>);
     fn push_error_scope(&self, filter: crate::ErrorFilter);
@@ -187,10 +264,15 @@ pub trait DeviceInterface: CommonTraits {
 
     fn get_internal_counters(&self) -> crate::InternalCounters;
     fn generate_allocator_report(&self) -> Option<crate::AllocatorReport>;
+    fn generate_memory_report(&self, include_resources: bool) -> Option<crate::MemoryReport>;
+    fn trim_memory(&self) -> crate::TrimMemoryReport;
+    fn set_barrier_logging(&self, enabled: bool);
+    fn take_barrier_report(&self) -> Option<crate::BarrierReport>;
 
     fn destroy(&self);
 }
 
+/// A [`Queue`](crate::Queue).
 pub trait QueueInterface: CommonTraits {
     fn write_buffer(&self, buffer: &DispatchBuffer, offset: crate::BufferAddress, data: &[u8]);
 
@@ -224,20 +306,51 @@ pub trait QueueInterface: CommonTraits {
     );
 
     fn submit(&self, command_buffers: &mut dyn Iterator<Item = DispatchCommandBuffer>) -> u64;
+    fn submit_with_external_sync(
+        &self,
+        command_buffers: &mut dyn Iterator<Item = DispatchCommandBuffer>,
+        wait: &[wgt::SharedFenceValue<&DispatchSharedFence>],
+        signal: &[wgt::SharedFenceValue<&DispatchSharedFence>],
+    ) -> u64;
+
+    fn bind_sparse_memory(
+        &self,
+        buffer_bindings: &[wgt::SparseBufferBinding<&DispatchBuffer, &DispatchMemoryHeap>],
+        texture_bindings: &[wgt::SparseTextureBinding<&DispatchTexture, &DispatchMemoryHeap>],
+    );
 
     fn get_timestamp_period(&self) -> f32;
+    fn get_timestamp_calibration(&self) -> Option<crate::TimestampCalibration>;
     fn on_submitted_work_done(&self, callback: BoxSubmittedWorkDoneCallback);
+    #[cfg_attr(not(feature = "async"), expect(dead_code))]
+    fn on_submission_done(&self, submission_index: u64, callback: BoxSubmittedWorkDoneCallback);
+    fn get_completed_submission_index(&self) -> u64;
+    fn poll(&self, poll_type: wgt::PollType<u64>) -> Result<crate::PollStatus, crate::PollError>;
+    fn wait_for_submission(&self, submission_index: u64);
 
     fn compact_blas(&self, blas: &DispatchBlas) -> (Option<u64>, DispatchBlas);
 }
 
+/// A [`ShaderModule`](crate::ShaderModule).
 pub trait ShaderModuleInterface: CommonTraits {
     fn get_compilation_info(&self) -> Pin<Box<dyn ShaderCompilationInfoFuture>>;
+    fn binding(&self, name: &str) -> Option<(u32, u32)>;
+    fn workgroup_memory(&self, entry_point: &str) -> Option<wgt::WorkgroupMemoryInfo>;
+    fn check_layout(
+        &self,
+        name: &str,
+        layout: &wgt::ShaderTypeLayout,
+    ) -> Result<(), wgt::ShaderLayoutError>;
 }
+/// A [`BindGroupLayout`](crate::BindGroupLayout).
 pub trait BindGroupLayoutInterface: CommonTraits {}
+/// A [`BindGroup`](crate::BindGroup).
 pub trait BindGroupInterface: CommonTraits {}
+/// A [`TextureView`](crate::TextureView).
 pub trait TextureViewInterface: CommonTraits {}
+/// A [`Sampler`](crate::Sampler).
 pub trait SamplerInterface: CommonTraits {}
+/// A [`Buffer`](crate::Buffer).
 pub trait BufferInterface: CommonTraits {
     fn map_async(
         &self,
@@ -252,27 +365,53 @@ pub trait BufferInterface: CommonTraits {
 
     fn destroy(&self);
 }
+/// A [`Texture`](crate::Texture).
 pub trait TextureInterface: CommonTraits {
     fn create_view(&self, desc: &crate::TextureViewDescriptor<'_>) -> DispatchTextureView;
 
     fn destroy(&self);
 }
+/// A [`Blas`](crate::Blas).
 pub trait BlasInterface: CommonTraits {
     fn prepare_compact_async(&self, callback: BlasCompactCallback);
     fn ready_for_compaction(&self) -> bool;
+    fn compacted_size(&self) -> Option<crate::BufferAddress>;
+    fn serialize(&self, callback: BlasSerializeCallback);
 }
+/// A [`Tlas`](crate::Tlas).
 pub trait TlasInterface: CommonTraits {}
+/// A [`QuerySet`](crate::QuerySet).
 pub trait QuerySetInterface: CommonTraits {}
+/// A [`SharedFence`](crate::SharedFence).
+pub trait SharedFenceInterface: CommonTraits {
+    fn export(&self) -> Option<crate::SharedFenceHandle>;
+}
+/// A [`MemoryHeap`](crate::MemoryHeap).
+pub trait MemoryHeapInterface: CommonTraits {}
+/// An [`ExternalTexture`](crate::ExternalTexture).
+pub trait ExternalTextureInterface: CommonTraits {}
+/// A [`PipelineLayout`](crate::PipelineLayout).
 pub trait PipelineLayoutInterface: CommonTraits {}
+/// A [`RenderPipeline`](crate::RenderPipeline).
 pub trait RenderPipelineInterface: CommonTraits {
     fn get_bind_group_layout(&self, index: u32) -> DispatchBindGroupLayout;
 }
+/// A [`ComputePipeline`](crate::ComputePipeline).
 pub trait ComputePipelineInterface: CommonTraits {
     fn get_bind_group_layout(&self, index: u32) -> DispatchBindGroupLayout;
 }
+/// A [`RayTracingPipeline`](crate::RayTracingPipeline).
+pub trait RayTracingPipelineInterface: CommonTraits {
+    fn shader_binding_table(
+        &self,
+        desc: &crate::ShaderBindingTableDescriptor<'_>,
+    ) -> (Vec<u8>, crate::ShaderBindingTableLayout);
+}
+/// A [`PipelineCache`](crate::PipelineCache).
 pub trait PipelineCacheInterface: CommonTraits {
     fn get_data(&self) -> Option<Vec<u8>>;
 }
+/// A [`CommandEncoder`](crate::CommandEncoder).
 pub trait CommandEncoderInterface: CommonTraits {
     fn copy_buffer_to_buffer(
         &self,
@@ -300,10 +439,16 @@ pub trait CommandEncoderInterface: CommonTraits {
         destination: crate::TexelCopyTextureInfo<'_>,
         copy_size: crate::Extent3d,
     );
+    fn resolve_texture(
+        &self,
+        source: &DispatchTextureView,
+        destination: &DispatchTextureView,
+        regions: &[crate::Rect],
+    );
 
     fn begin_compute_pass(&self, desc: &crate::ComputePassDescriptor<'_>) -> DispatchComputePass;
     fn begin_render_pass(&self, desc: &crate::RenderPassDescriptor<'_>) -> DispatchRenderPass;
-    fn finish(&mut self) -> DispatchCommandBuffer;
+    fn finish(&mut self, reusable: bool) -> DispatchCommandBuffer;
 
     fn clear_texture(
         &self,
@@ -342,12 +487,15 @@ pub trait CommandEncoderInterface: CommonTraits {
         tlas: &mut dyn Iterator<Item = &'a crate::Tlas>,
     );
 
+    fn trace_rays(&self, desc: &crate::TraceRaysDescriptor<'_>);
+
     fn transition_resources<'a>(
         &mut self,
         buffer_transitions: &mut dyn Iterator<Item = wgt::BufferTransition<&'a DispatchBuffer>>,
         texture_transitions: &mut dyn Iterator<Item = wgt::TextureTransition<&'a DispatchTexture>>,
     );
 }
+/// A [`ComputePass`](crate::ComputePass).
 pub trait ComputePassInterface: CommonTraits {
     fn set_pipeline(&mut self, pipeline: &DispatchComputePipeline);
     fn set_bind_group(
@@ -374,6 +522,7 @@ pub trait ComputePassInterface: CommonTraits {
     );
     fn end(&mut self);
 }
+/// A [`RenderPass`](crate::RenderPass).
 pub trait RenderPassInterface: CommonTraits {
     fn set_pipeline(&mut self, pipeline: &DispatchRenderPipeline);
     fn set_bind_group(
@@ -409,6 +558,8 @@ pub trait RenderPassInterface: CommonTraits {
         max_depth: f32,
     );
     fn set_stencil_reference(&mut self, reference: u32);
+    fn set_shading_rate(&mut self, rate: crate::ShadingRate);
+    fn set_viewports(&mut self, viewports: &[crate::Viewport]);
 
     fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>);
     fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>);
@@ -452,6 +603,27 @@ pub trait RenderPassInterface: CommonTraits {
         max_count: u32,
     );
 
+    fn draw_mesh_tasks(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32);
+    fn draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: &DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+    );
+    fn multi_draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: &DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        count: u32,
+    );
+    fn multi_draw_mesh_tasks_indirect_count(
+        &mut self,
+        indirect_buffer: &DispatchBuffer,
+        indirect_offset: crate::BufferAddress,
+        count_buffer: &DispatchBuffer,
+        count_buffer_offset: crate::BufferAddress,
+        max_count: u32,
+    );
+
     fn insert_debug_marker(&mut self, label: &str);
     fn push_debug_group(&mut self, group_label: &str);
     fn pop_debug_group(&mut self);
@@ -461,12 +633,16 @@ pub trait RenderPassInterface: CommonTraits {
     fn end_occlusion_query(&mut self);
     fn begin_pipeline_statistics_query(&mut self, query_set: &DispatchQuerySet, query_index: u32);
     fn end_pipeline_statistics_query(&mut self);
+    fn begin_conditional_block(&mut self, buffer: &DispatchBuffer, offset: crate::BufferAddress);
+    fn end_conditional_block(&mut self);
 
     fn execute_bundles(&mut self, render_bundles: &mut dyn Iterator<Item = &DispatchRenderBundle>);
+    fn create_parallel_encoder(&mut self) -> DispatchRenderPass;
 
     fn end(&mut self);
 }
 
+/// A [`RenderBundleEncoder`](crate::RenderBundleEncoder).
 pub trait RenderBundleEncoderInterface: CommonTraits {
     fn set_pipeline(&mut self, pipeline: &DispatchRenderPipeline);
     fn set_bind_group(
@@ -504,38 +680,57 @@ pub trait RenderBundleEncoderInterface: CommonTraits {
         indirect_offset: crate::BufferAddress,
     );
 
-    fn finish(self, desc: &crate::RenderBundleDescriptor<'_>) -> DispatchRenderBundle
-    where
-        Self: Sized;
+    fn write_timestamp(&mut self, query_set: &DispatchQuerySet, query_index: u32);
+    fn begin_occlusion_query(&mut self, query_index: u32);
+    fn end_occlusion_query(&mut self);
+    fn begin_pipeline_statistics_query(&mut self, query_set: &DispatchQuerySet, query_index: u32);
+    fn end_pipeline_statistics_query(&mut self);
+
+    /// The encoder is dropped right after, without any other calls.
+    fn finish(&mut self, desc: &crate::RenderBundleDescriptor<'_>) -> DispatchRenderBundle;
 }
 
+/// A [`CommandBuffer`](crate::CommandBuffer).
 pub trait CommandBufferInterface: CommonTraits {}
+/// A [`RenderBundle`](crate::RenderBundle).
 pub trait RenderBundleInterface: CommonTraits {}
 
+/// A [`Surface`](crate::Surface).
 pub trait SurfaceInterface: CommonTraits {
     fn get_capabilities(&self, adapter: &DispatchAdapter) -> crate::SurfaceCapabilities;
 
     fn configure(&self, device: &DispatchDevice, config: &crate::SurfaceConfiguration);
+    /// `timeout` is the longest time to wait for a texture, or `None` to use
+    /// the backend's default.
     fn get_current_texture(
         &self,
+        timeout: Option<core::time::Duration>,
     ) -> (
         Option<DispatchTexture>,
         crate::SurfaceStatus,
         DispatchSurfaceOutputDetail,
     );
+    fn get_presentation_statistics(&self) -> Vec<crate::PresentationStatistics>;
+    fn presentation_path(&self) -> crate::PresentationPath;
 }
 
+/// The backend specific part of a [`SurfaceTexture`](crate::SurfaceTexture), which
+/// presents or discards it.
 pub trait SurfaceOutputDetailInterface: CommonTraits {
-    fn present(&self);
+    fn set_target_present_time(&self, time: crate::PresentationTimestamp);
+    fn present(&self, damage: &[crate::Rect]);
     fn texture_discard(&self);
 }
 
+/// The staging memory of a [`QueueWriteBufferView`](crate::QueueWriteBufferView).
 pub trait QueueWriteBufferInterface: CommonTraits {
     fn slice(&self) -> &[u8];
 
     fn slice_mut(&mut self) -> &mut [u8];
 }
 
+/// The memory of a mapped range of a buffer, see [`BufferView`](crate::BufferView)
+/// and [`BufferViewMut`](crate::BufferViewMut).
 pub trait BufferMappedRangeInterface: CommonTraits {
     fn slice(&self) -> &[u8];
     fn slice_mut(&mut self) -> &mut [u8];
@@ -665,6 +860,8 @@ macro_rules! dispatch_types {
         mut type $name:ident: $interface:ident = $core_type:ident,$webgpu_type:ident,$custom_type:ident
     ) => {
         #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        // Core passes are recorded in place, custom ones are behind a pointer.
+        #[allow(clippy::allow_attributes, clippy::large_enum_variant)]
         pub enum $name {
             #[cfg(wgpu_core)]
             Core($core_type),
@@ -843,9 +1040,13 @@ dispatch_types! {ref type DispatchTexture: TextureInterface = CoreTexture, WebTe
 dispatch_types! {ref type DispatchBlas: BlasInterface = CoreBlas, WebBlas, DynBlas}
 dispatch_types! {ref type DispatchTlas: TlasInterface = CoreTlas, WebTlas, DynTlas}
 dispatch_types! {ref type DispatchQuerySet: QuerySetInterface = CoreQuerySet, WebQuerySet, DynQuerySet}
+dispatch_types! {ref type DispatchSharedFence: SharedFenceInterface = CoreSharedFence, WebSharedFence, DynSharedFence}
+dispatch_types! {ref type DispatchMemoryHeap: MemoryHeapInterface = CoreMemoryHeap, WebMemoryHeap, DynMemoryHeap}
+dispatch_types! {ref type DispatchExternalTexture: ExternalTextureInterface = CoreExternalTexture, WebExternalTexture, DynExternalTexture}
 dispatch_types! {ref type DispatchPipelineLayout: PipelineLayoutInterface = CorePipelineLayout, WebPipelineLayout, DynPipelineLayout}
 dispatch_types! {ref type DispatchRenderPipeline: RenderPipelineInterface = CoreRenderPipeline, WebRenderPipeline, DynRenderPipeline}
 dispatch_types! {ref type DispatchComputePipeline: ComputePipelineInterface = CoreComputePipeline, WebComputePipeline, DynComputePipeline}
+dispatch_types! {ref type DispatchRayTracingPipeline: RayTracingPipelineInterface = CoreRayTracingPipeline, WebRayTracingPipeline, DynRayTracingPipeline}
 dispatch_types! {ref type DispatchPipelineCache: PipelineCacheInterface = CorePipelineCache, WebPipelineCache, DynPipelineCache}
 dispatch_types! {mut type DispatchCommandEncoder: CommandEncoderInterface = CoreCommandEncoder, WebCommandEncoder, DynCommandEncoder}
 dispatch_types! {mut type DispatchComputePass: ComputePassInterface = CoreComputePass, WebComputePassEncoder, DynComputePass}
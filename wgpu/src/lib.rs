@@ -51,6 +51,7 @@ mod cmp;
 mod dispatch;
 mod macros;
 pub mod util;
+pub mod video;
 
 //
 //
@@ -70,32 +71,61 @@ pub use backend::custom;
 pub use api::*;
 pub use wgt::{
     AdapterInfo, AddressMode, AllocatorReport, AstcBlock, AstcChannel, Backend, BackendOptions,
-    Backends, BindGroupLayoutEntry, BindingType, BlendComponent, BlendFactor, BlendOperation,
-    BlendState, BufferAddress, BufferBindingType, BufferSize, BufferTextureCopyInfo,
-    BufferTransition, BufferUsages, BufferUses, COPY_BUFFER_ALIGNMENT,
-    COPY_BYTES_PER_ROW_ALIGNMENT, Color, ColorTargetState, ColorWrites, CommandBufferDescriptor,
-    CompareFunction, CompositeAlphaMode, CopyExternalImageDestInfo, CoreCounters, DepthBiasState,
-    DepthStencilState, DeviceLostReason, DeviceType, DownlevelCapabilities, DownlevelFlags,
-    DownlevelLimits, Dx12BackendOptions, Dx12Compiler, DxcShaderModel, DynamicOffset, Extent3d,
-    Face, Features, FeaturesWGPU, FeaturesWebGPU, FilterMode, FrontFace, GlBackendOptions,
-    GlFenceBehavior, Gles3MinorVersion, HalCounters, ImageSubresourceRange, IndexFormat,
-    InstanceDescriptor, InstanceFlags, InternalCounters, Limits, MAP_ALIGNMENT,
-    MemoryBudgetThresholds, MemoryHints, MultisampleState, NoopBackendOptions, Origin2d, Origin3d,
-    PUSH_CONSTANT_ALIGNMENT, PipelineStatisticsTypes, PollError, PollStatus, PolygonMode,
-    PowerPreference, PredefinedColorSpace, PresentMode, PresentationTimestamp, PrimitiveState,
+    Backends, BarrierReport, BindGroupLayoutEntry, BindingType, BlendComponent, BlendFactor,
+    BlendOperation, BlendState, BufferAddress, BufferBindingType, BufferSize,
+    BufferTextureCopyInfo, BufferTransition, BufferUsages, BufferUses, COPY_BUFFER_ALIGNMENT,
+    COPY_BYTES_PER_ROW_ALIGNMENT, ChromaLocation, Color, ColorTargetState, ColorWrites,
+    CommandBufferDescriptor, CompareFunction, CompositeAlphaMode, CopyExternalImageDestInfo,
+    CoreCounters, DepthBiasState, DepthStencilState, DeviceLostReason, DeviceType, DisplayInfo,
+    DisplayMode, DownlevelCapabilities, DownlevelFlags, DownlevelLimits, Dx12BackendOptions,
+    Dx12Compiler, DxcShaderModel, DynamicOffset, Extent3d, Face, Features, FeaturesWGPU,
+    FeaturesWGPU2, FeaturesWebGPU, FilterMode, FrontFace, GlBackendOptions, GlFenceBehavior,
+    Gles3MinorVersion, HalCounters, HdrMetadata, ImageSubresourceRange, IndexFormat,
+    InstanceDescriptor, InstanceFlags, InternalCounters, Limits, LineStipple, LogicOp,
+    MAP_ALIGNMENT, MAX_VIEWPORTS, MemoryBudgetThresholds, MemoryHeapInfo, MemoryHints, MemoryInfo,
+    MemoryReport, MemoryReportHeap, MemoryReportResourceType, MultisampleState, NoopBackendOptions,
+    Origin2d, Origin3d, PUSH_CONSTANT_ALIGNMENT, PassBarrierReport, PipelineStatisticsTypes,
+    PollError, PollStatus, PolygonMode, PowerPreference, PredefinedColorSpace, PresentMode,
+    PresentationPath, PresentationStatistics, PresentationTimestamp, PrimitiveState,
     PrimitiveTopology, PushConstantRange, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES,
-    QUERY_SIZE, QueryType, RenderBundleDepthStencil, RequestAdapterError, SamplerBindingType,
-    SamplerBorderColor, ShaderLocation, ShaderModel, ShaderRuntimeChecks, ShaderStages,
-    StencilFaceState, StencilOperation, StencilState, StorageTextureAccess, SurfaceCapabilities,
-    SurfaceStatus, TexelCopyBufferLayout, TextureAspect, TextureDimension, TextureFormat,
-    TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType, TextureTransition,
-    TextureUsages, TextureUses, TextureViewDimension, Trace, VERTEX_ALIGNMENT, VertexAttribute,
-    VertexFormat, VertexStepMode, WasmNotSend, WasmNotSendSync, WasmNotSync,
+    QUERY_SIZE, QueryType, QueueType, RayTracingShaderGroup, RecordedCommand,
+    RecordedCommandBuffer, RecordedDrawKind, Rect, RenderBundleDepthStencil,
+    RenderBundleInheritance, RenderPassFlags, RequestAdapterError, ResolveMode,
+    ResourceMemoryReport, ResourceTypeMemoryReport, SPARSE_PAGE_SIZE, SamplerBindingType,
+    SamplerBorderColor, SamplerYcbcrConversion, ShaderBindingTableLayout, ShaderBindingTableRegion,
+    ShaderLayoutError, ShaderLocation, ShaderMemberLayout, ShaderModel, ShaderOptimization,
+    ShaderRuntimeChecks, ShaderScalar, ShaderStages, ShaderType, ShaderTypeLayout, ShadingRate,
+    SharedFenceHandle, StencilFaceState, StencilOperation, StencilState, StorageTextureAccess,
+    SurfaceCapabilities, SurfaceColorSpace, SurfaceStatus, TexelCopyBufferLayout, TextureAspect,
+    TextureDimension, TextureFormat, TextureFormatFeatureFlags, TextureFormatFeatures,
+    TextureMemoryRequirements, TextureSampleType, TextureTransition, TextureUsages, TextureUses,
+    TextureViewDimension, TimestampCalibration, Toggles, TogglesDescriptor, Trace,
+    TrimMemoryReport, VERTEX_ALIGNMENT, VertexAttribute, VertexFormat, VertexStepMode, Viewport,
+    WasmNotSend, WasmNotSendSync, WasmNotSync, WorkgroupMemoryInfo, YuvMatrix, YuvRange,
 };
 
 #[expect(deprecated)]
 pub use wgt::VERTEX_STRIDE_ALIGNMENT;
 
+/// Derives [`ShaderType`](trait@ShaderType) for a `#[repr(C)]` struct whose fields all
+/// implement it.
+///
+/// Add `#[shader_type(uniform)]` to also check at compile time that the layout follows the
+/// rules for uniform buffers, see [`ShaderTypeLayout::uniform_layout_error`].
+///
+/// ```
+/// #[derive(wgpu::ShaderType)]
+/// #[shader_type(uniform)]
+/// #[repr(C)]
+/// struct Camera {
+///     view_projection: [[f32; 4]; 4],
+///     position: [f32; 3],
+///     exposure: f32,
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use wgpu_derive::ShaderType;
+
 // wasm-only types, we try to keep as many types non-platform
 // specific, but these need to depend on web-sys.
 #[cfg(web)]
@@ -125,3 +155,5 @@ pub use web_sys;
 
 #[doc(hidden)]
 pub use macros::helpers as __macro_helpers;
+
+pub(crate) use macros::{api_record, api_span};
@@ -145,3 +145,29 @@ pub mod helpers {
     pub use alloc::borrow::Cow;
     pub use core::{include_bytes, include_str};
 }
+
+/// Enter a [`tracing`] span for a call of the public API, until the end of the current
+/// scope, when the `tracing` feature is enabled.
+///
+/// Takes the name of the call, then the fields of the span in the syntax of
+/// `tracing::span!`. The fields aren't evaluated when the feature is disabled.
+macro_rules! api_span {
+    ($name:literal $(, $($fields:tt)+)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(target: "wgpu", $name $(, $($fields)+)?).entered();
+    };
+}
+
+/// Record values of fields declared as `tracing::field::Empty` by [`api_span!`] on the
+/// current span, when the `tracing` feature is enabled.
+macro_rules! api_record {
+    ($($field:ident = $value:expr),+ $(,)?) => {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            $(span.record(stringify!($field), $value);)+
+        }
+    };
+}
+
+pub(crate) use {api_record, api_span};
@@ -1,10 +1,10 @@
 use crate::{
     Buffer, BufferAddress, BufferDescriptor, BufferSize, BufferSlice, BufferUsages, BufferViewMut,
-    CommandEncoder, Device, MapMode, util::align_to,
+    CommandEncoder, Device, MapMode,
+    util::{Mutex, align_to},
 };
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 use core::fmt;
-use std::sync::mpsc;
 
 /// Efficiently performs many buffer writes by sharing and reusing temporary buffers.
 ///
@@ -31,10 +31,26 @@ pub struct StagingBelt {
     /// Chunks that are back from the GPU and ready to be mapped for write and put
     /// into `active_chunks`.
     free_chunks: Vec<Chunk>,
-    /// When closed chunks are mapped again, the map callback sends them here.
-    sender: Exclusive<mpsc::Sender<Chunk>>,
-    /// Free chunks are received here to be put on `self.free_chunks`.
-    receiver: Exclusive<mpsc::Receiver<Chunk>>,
+    /// When closed chunks are mapped again, the map callback pushes them here,
+    /// to be moved onto `self.free_chunks`.
+    returned_chunks: Arc<Mutex<Vec<Chunk>>>,
+    /// Number and total size of the chunks currently owned by the belt, in any state.
+    chunk_count: usize,
+    resident_bytes: BufferAddress,
+    /// Largest value `resident_bytes` has reached.
+    peak_resident_bytes: BufferAddress,
+}
+
+/// Memory usage of a [`StagingBelt`], as returned by [`StagingBelt::stats()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StagingBeltStats {
+    /// Number of staging buffers currently allocated by the belt.
+    pub chunk_count: usize,
+    /// Total size in bytes of the staging buffers currently allocated by the belt.
+    pub resident_bytes: BufferAddress,
+    /// The largest value `resident_bytes` has had over the lifetime of the belt.
+    pub peak_resident_bytes: BufferAddress,
 }
 
 impl StagingBelt {
@@ -49,14 +65,15 @@ impl StagingBelt {
     ///   (per [`StagingBelt::finish()`]); and
     /// * bigger is better, within these bounds.
     pub fn new(chunk_size: BufferAddress) -> Self {
-        let (sender, receiver) = mpsc::channel();
         StagingBelt {
             chunk_size,
             active_chunks: Vec::new(),
             closed_chunks: Vec::new(),
             free_chunks: Vec::new(),
-            sender: Exclusive::new(sender),
-            receiver: Exclusive::new(receiver),
+            returned_chunks: Arc::new(Mutex::new(Vec::new())),
+            chunk_count: 0,
+            resident_bytes: 0,
+            peak_resident_bytes: 0,
         }
     }
 
@@ -141,10 +158,14 @@ impl StagingBelt {
             {
                 self.free_chunks.swap_remove(index)
             } else {
+                let chunk_size = self.chunk_size.max(size.get());
+                self.chunk_count += 1;
+                self.resident_bytes += chunk_size;
+                self.peak_resident_bytes = self.peak_resident_bytes.max(self.resident_bytes);
                 Chunk {
                     buffer: device.create_buffer(&BufferDescriptor {
                         label: Some("(wgpu internal) StagingBelt staging buffer"),
-                        size: self.chunk_size.max(size.get()),
+                        size: chunk_size,
                         usage: BufferUsages::MAP_WRITE | BufferUsages::COPY_SRC,
                         mapped_at_creation: true,
                     }),
@@ -187,21 +208,57 @@ impl StagingBelt {
         self.receive_chunks();
 
         for chunk in self.closed_chunks.drain(..) {
-            let sender = self.sender.get_mut().clone();
+            let returned_chunks = Arc::clone(&self.returned_chunks);
             chunk
                 .buffer
                 .clone()
                 .slice(..)
                 .map_async(MapMode::Write, move |_| {
-                    let _ = sender.send(chunk);
+                    returned_chunks.lock().push(chunk);
                 });
         }
     }
 
+    /// Return statistics about the memory used by the belt.
+    ///
+    /// Staging buffers are counted from the moment they are allocated until they are
+    /// dropped by [`StagingBelt::trim()`] or by dropping the belt, whether or not they
+    /// are currently in use.
+    pub fn stats(&self) -> StagingBeltStats {
+        StagingBeltStats {
+            chunk_count: self.chunk_count,
+            resident_bytes: self.resident_bytes,
+            peak_resident_bytes: self.peak_resident_bytes,
+        }
+    }
+
+    /// Free unused staging buffers until at most `max_retained_bytes` of staging
+    /// memory remains allocated.
+    ///
+    /// Only buffers the GPU is done with can be freed, so more memory than requested
+    /// may remain allocated while transfers are in flight. Calling this periodically,
+    /// after [`StagingBelt::recall()`], bounds the memory held on to by a belt whose
+    /// usage spiked at some point.
+    pub fn trim(&mut self, max_retained_bytes: BufferAddress) {
+        self.receive_chunks();
+
+        // Free the largest buffers first; those larger than `chunk_size` were
+        // allocated for unusually large writes.
+        self.free_chunks
+            .sort_unstable_by_key(|chunk| chunk.buffer.size());
+        while self.resident_bytes > max_retained_bytes {
+            let Some(chunk) = self.free_chunks.pop() else {
+                break;
+            };
+            self.chunk_count -= 1;
+            self.resident_bytes -= chunk.buffer.size();
+        }
+    }
+
     /// Move all chunks that the GPU is done with (and are now mapped again)
-    /// from `self.receiver` to `self.free_chunks`.
+    /// from `self.returned_chunks` to `self.free_chunks`.
     fn receive_chunks(&mut self) {
-        while let Ok(mut chunk) = self.receiver.get_mut().try_recv() {
+        for mut chunk in self.returned_chunks.lock().drain(..) {
             chunk.offset = 0;
             self.free_chunks.push(chunk);
         }
@@ -215,6 +272,7 @@ impl fmt::Debug for StagingBelt {
             .field("active_chunks", &self.active_chunks.len())
             .field("closed_chunks", &self.closed_chunks.len())
             .field("free_chunks", &self.free_chunks.len())
+            .field("resident_bytes", &self.resident_bytes)
             .finish_non_exhaustive()
     }
 }
@@ -241,24 +299,3 @@ impl Chunk {
         alloc_start
     }
 }
-
-use exclusive::Exclusive;
-mod exclusive {
-    /// `Sync` wrapper that works by providing only exclusive access.
-    ///
-    /// See <https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html>
-    pub(super) struct Exclusive<T>(T);
-
-    /// Safety: `&Exclusive` has no operations.
-    unsafe impl<T> Sync for Exclusive<T> {}
-
-    impl<T> Exclusive<T> {
-        pub fn new(value: T) -> Self {
-            Self(value)
-        }
-
-        pub fn get_mut(&mut self) -> &mut T {
-            &mut self.0
-        }
-    }
-}
@@ -0,0 +1,262 @@
+use crate::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindingResource, Buffer,
+    BufferAddress, BufferBinding, BufferSize, Device, ExternalTexture, Sampler, TextureView, Tlas,
+};
+use alloc::vec::Vec;
+use core::fmt;
+use hashbrown::HashMap;
+
+/// Fluent builder for [`BindGroupDescriptor`]s.
+///
+/// Each method adds one entry to the bind group. Once all entries have been added,
+/// create the bind group with [`BindGroupBuilder::build()`], or look it up in a
+/// [`BindGroupCache`] with [`BindGroupBuilder::build_cached()`].
+///
+/// ```no_run
+/// # let device: wgpu::Device = todo!();
+/// # let layout: wgpu::BindGroupLayout = todo!();
+/// # let uniforms: wgpu::Buffer = todo!();
+/// # let view: wgpu::TextureView = todo!();
+/// # let sampler: wgpu::Sampler = todo!();
+/// let bind_group = wgpu::util::BindGroupBuilder::new(&layout)
+///     .label("material")
+///     .buffer(0, &uniforms)
+///     .texture_view(1, &view)
+///     .sampler(2, &sampler)
+///     .build(&device);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BindGroupBuilder<'a> {
+    label: Option<&'a str>,
+    layout: &'a BindGroupLayout,
+    entries: Vec<BindGroupEntry<'a>>,
+}
+
+impl<'a> BindGroupBuilder<'a> {
+    /// Start building a bind group with the given layout and no entries.
+    pub fn new(layout: &'a BindGroupLayout) -> Self {
+        Self {
+            label: None,
+            layout,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Set the debug label of the bind group.
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Bind `resource` to `binding`.
+    pub fn resource(mut self, binding: u32, resource: BindingResource<'a>) -> Self {
+        self.entries.push(BindGroupEntry { binding, resource });
+        self
+    }
+
+    /// Bind the whole of `buffer` to `binding`.
+    pub fn buffer(self, binding: u32, buffer: &'a Buffer) -> Self {
+        self.resource(binding, buffer.as_entire_binding())
+    }
+
+    /// Bind `size` bytes of `buffer`, starting at `offset`, to `binding`.
+    ///
+    /// A `size` of `None` binds the rest of the buffer.
+    pub fn buffer_range(
+        self,
+        binding: u32,
+        buffer: &'a Buffer,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    ) -> Self {
+        self.resource(
+            binding,
+            BindingResource::Buffer(BufferBinding {
+                buffer,
+                offset,
+                size,
+            }),
+        )
+    }
+
+    /// Bind `view` to `binding`.
+    pub fn texture_view(self, binding: u32, view: &'a TextureView) -> Self {
+        self.resource(binding, BindingResource::TextureView(view))
+    }
+
+    /// Bind `sampler` to `binding`.
+    pub fn sampler(self, binding: u32, sampler: &'a Sampler) -> Self {
+        self.resource(binding, BindingResource::Sampler(sampler))
+    }
+
+    /// The entries added so far.
+    pub fn entries(&self) -> &[BindGroupEntry<'a>] {
+        &self.entries
+    }
+
+    /// The descriptor for the bind group being built.
+    pub fn descriptor(&self) -> BindGroupDescriptor<'_> {
+        BindGroupDescriptor {
+            label: self.label,
+            layout: self.layout,
+            entries: &self.entries,
+        }
+    }
+
+    /// Create the bind group.
+    pub fn build(&self, device: &Device) -> BindGroup {
+        device.create_bind_group(&self.descriptor())
+    }
+
+    /// Return the bind group from `cache`, creating it if the cache doesn't have an
+    /// identical one yet.
+    pub fn build_cached(&self, device: &Device, cache: &mut BindGroupCache) -> BindGroup {
+        cache.get_or_create(device, &self.descriptor())
+    }
+}
+
+/// De-duplicates bind groups by the resources they bind.
+///
+/// Renderers that create bind groups on the fly often create many identical ones, each
+/// of which costs validation and descriptor allocations. [`BindGroupCache::get_or_create()`]
+/// returns an existing bind group if one with the same layout and entries was already
+/// created through the cache.
+///
+/// Entries are compared by the identity of the bound resources, together with buffer
+/// offsets and sizes. Labels are not compared; a cached bind group keeps the label it
+/// was created with.
+///
+/// Cached bind groups keep the resources they bind alive. To release them, call
+/// [`BindGroupCache::evict_unused()`] periodically, e.g. once per frame, or
+/// [`BindGroupCache::clear()`].
+#[derive(Default)]
+pub struct BindGroupCache {
+    groups: HashMap<BindGroupKey, CachedBindGroup>,
+}
+
+impl BindGroupCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a bind group matching `desc`, creating and caching it if needed.
+    pub fn get_or_create(&mut self, device: &Device, desc: &BindGroupDescriptor<'_>) -> BindGroup {
+        let key = BindGroupKey::new(desc);
+        let cached = self.groups.entry(key).or_insert_with(|| CachedBindGroup {
+            bind_group: device.create_bind_group(desc),
+            used: false,
+        });
+        cached.used = true;
+        cached.bind_group.clone()
+    }
+
+    /// Remove the bind groups which haven't been returned by
+    /// [`BindGroupCache::get_or_create()`] since the previous call to this method.
+    pub fn evict_unused(&mut self) {
+        self.groups
+            .retain(|_, cached| core::mem::take(&mut cached.used));
+    }
+
+    /// Remove all bind groups from the cache.
+    pub fn clear(&mut self) {
+        self.groups.clear();
+    }
+
+    /// The number of bind groups in the cache.
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+impl fmt::Debug for BindGroupCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BindGroupCache")
+            .field("len", &self.groups.len())
+            .finish_non_exhaustive()
+    }
+}
+
+struct CachedBindGroup {
+    bind_group: BindGroup,
+    /// Whether the bind group was requested since the last call to `evict_unused`.
+    used: bool,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct BindGroupKey {
+    layout: BindGroupLayout,
+    entries: Vec<(u32, ResourceKey)>,
+}
+
+impl BindGroupKey {
+    fn new(desc: &BindGroupDescriptor<'_>) -> Self {
+        Self {
+            layout: desc.layout.clone(),
+            entries: desc
+                .entries
+                .iter()
+                .map(|entry| (entry.binding, ResourceKey::new(&entry.resource)))
+                .collect(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum ResourceKey {
+    Buffer(BufferKey),
+    BufferArray(Vec<BufferKey>),
+    Sampler(Sampler),
+    SamplerArray(Vec<Sampler>),
+    TextureView(TextureView),
+    TextureViewArray(Vec<TextureView>),
+    AccelerationStructure(Tlas),
+    ExternalTexture(ExternalTexture),
+}
+
+impl ResourceKey {
+    fn new(resource: &BindingResource<'_>) -> Self {
+        match *resource {
+            BindingResource::Buffer(ref binding) => Self::Buffer(BufferKey::new(binding)),
+            BindingResource::BufferArray(bindings) => {
+                Self::BufferArray(bindings.iter().map(BufferKey::new).collect())
+            }
+            BindingResource::Sampler(sampler) => Self::Sampler(sampler.clone()),
+            BindingResource::SamplerArray(samplers) => {
+                Self::SamplerArray(samplers.iter().map(|&sampler| sampler.clone()).collect())
+            }
+            BindingResource::TextureView(view) => Self::TextureView(view.clone()),
+            BindingResource::TextureViewArray(views) => {
+                Self::TextureViewArray(views.iter().map(|&view| view.clone()).collect())
+            }
+            BindingResource::AccelerationStructure(tlas) => {
+                Self::AccelerationStructure(tlas.clone())
+            }
+            BindingResource::ExternalTexture(external_texture) => {
+                Self::ExternalTexture(external_texture.clone())
+            }
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct BufferKey {
+    buffer: Buffer,
+    offset: BufferAddress,
+    size: Option<BufferSize>,
+}
+
+impl BufferKey {
+    fn new(binding: &BufferBinding<'_>) -> Self {
+        Self {
+            buffer: binding.buffer.clone(),
+            offset: binding.offset,
+            size: binding.size,
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use crate::{
+    BindingResource, Buffer, BufferAddress, BufferBinding, BufferDescriptor, BufferSize,
+    BufferSlice, BufferUsages, Device, Queue,
+    util::{Mutex, align_to},
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{fmt, ops::Range};
+
+/// Ranges of blocks whose allocations have been dropped, as `(block index, range)`.
+type Ranges = Arc<Mutex<Vec<(usize, Range<BufferAddress>)>>>;
+
+/// Suballocates many small buffers from a few large ones.
+///
+/// Every buffer created with [`Device::create_buffer()`] is a separate allocation in
+/// the backend, which is slow and may run into the backend's limit on the number of
+/// allocations when creating many small buffers. `BufferAllocator` instead hands out
+/// [`BufferAllocation`]s, which are ranges of larger buffers ("blocks") that all share
+/// the same usage.
+///
+/// Dropping a [`BufferAllocation`] doesn't make its range available again right away,
+/// since the GPU may still be using it. Instead, using an allocator goes as follows:
+/// 1. Use [`BufferAllocator::allocate()`] to allocate ranges, and record commands using them.
+/// 2. Drop the allocations that are no longer needed.
+/// 3. Submit all command encoders that used the dropped allocations.
+/// 4. Call [`BufferAllocator::recall()`].
+///
+/// The ranges of the allocations dropped in step 2 are reused once the GPU has finished
+/// the work submitted in step 3.
+pub struct BufferAllocator {
+    device: Device,
+    usage: BufferUsages,
+    block_size: BufferAddress,
+    /// Alignment of the offset and size of every allocation.
+    alignment: BufferAddress,
+    blocks: Vec<Block>,
+    /// Ranges dropped since the last [`BufferAllocator::recall()`].
+    dropped: Ranges,
+    /// Ranges the GPU is done with, pushed by the callbacks registered in
+    /// [`BufferAllocator::recall()`], to be moved into the blocks' free lists.
+    reclaimed: Ranges,
+    allocated_bytes: BufferAddress,
+}
+
+/// Memory usage of a [`BufferAllocator`], as returned by [`BufferAllocator::stats()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BufferAllocatorStats {
+    /// Number of blocks currently allocated by the allocator.
+    pub block_count: usize,
+    /// Total size in bytes of the blocks currently allocated by the allocator.
+    pub resident_bytes: BufferAddress,
+    /// Total size in bytes of the ranges in use, including the ranges of dropped
+    /// allocations that haven't been reclaimed yet and padding for alignment.
+    pub allocated_bytes: BufferAddress,
+}
+
+impl BufferAllocator {
+    /// Create a new allocator whose blocks are `block_size` bytes large and have the
+    /// given `usage`.
+    ///
+    /// Allocations are aligned so that they can be bound or copied with any of the
+    /// `usage`: at least to [`COPY_BUFFER_ALIGNMENT`](crate::COPY_BUFFER_ALIGNMENT), and to
+    /// the device's minimum uniform and storage buffer offset alignment if `usage`
+    /// contains [`BufferUsages::UNIFORM`] or [`BufferUsages::STORAGE`] respectively.
+    ///
+    /// # Panics
+    ///
+    /// - If `usage` contains [`BufferUsages::MAP_READ`] or [`BufferUsages::MAP_WRITE`],
+    ///   since ranges of a buffer can't be mapped independently.
+    pub fn new(device: &Device, usage: BufferUsages, block_size: BufferAddress) -> Self {
+        assert!(
+            !usage.intersects(BufferUsages::MAP_READ | BufferUsages::MAP_WRITE),
+            "mappable buffers can't be suballocated"
+        );
+
+        let limits = device.limits();
+        let mut alignment = crate::COPY_BUFFER_ALIGNMENT;
+        if usage.contains(BufferUsages::UNIFORM) {
+            alignment = alignment.max(limits.min_uniform_buffer_offset_alignment.into());
+        }
+        if usage.contains(BufferUsages::STORAGE) {
+            alignment = alignment.max(limits.min_storage_buffer_offset_alignment.into());
+        }
+
+        Self {
+            device: device.clone(),
+            usage,
+            block_size: align_to(block_size, alignment),
+            alignment,
+            blocks: Vec::new(),
+            dropped: Arc::new(Mutex::new(Vec::new())),
+            reclaimed: Arc::new(Mutex::new(Vec::new())),
+            allocated_bytes: 0,
+        }
+    }
+
+    /// Allocate a range of `size` bytes.
+    ///
+    /// The contents of the range are undefined; they may have been written through a
+    /// previous allocation of the same range.
+    ///
+    /// If `size` is larger than the block size, a dedicated buffer is created for the
+    /// allocation, which is destroyed once the allocation is dropped and the GPU is done
+    /// with it. Such allocations are not counted by [`BufferAllocator::stats()`].
+    pub fn allocate(&mut self, size: BufferSize) -> BufferAllocation {
+        let aligned_size = align_to(size.get(), self.alignment);
+
+        if aligned_size > self.block_size {
+            return BufferAllocation {
+                buffer: self.device.create_buffer(&BufferDescriptor {
+                    label: Some("(wgpu internal) BufferAllocator dedicated buffer"),
+                    size: aligned_size,
+                    usage: self.usage,
+                    mapped_at_creation: false,
+                }),
+                offset: 0,
+                size,
+                block: None,
+            };
+        }
+
+        self.receive_ranges();
+
+        let found = self
+            .blocks
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, block)| {
+                block
+                    .allocate(aligned_size)
+                    .map(|offset| (index, block, offset))
+            });
+        let (index, block, offset) = match found {
+            Some(found) => found,
+            None => {
+                self.blocks.push(Block {
+                    buffer: self.device.create_buffer(&BufferDescriptor {
+                        label: Some("(wgpu internal) BufferAllocator block"),
+                        size: self.block_size,
+                        usage: self.usage,
+                        mapped_at_creation: false,
+                    }),
+                    free: alloc::vec![0..self.block_size],
+                });
+                let index = self.blocks.len() - 1;
+                let block = &mut self.blocks[index];
+                let offset = block.allocate(aligned_size).unwrap();
+                (index, block, offset)
+            }
+        };
+        self.allocated_bytes += aligned_size;
+
+        BufferAllocation {
+            buffer: block.buffer.clone(),
+            offset,
+            size,
+            block: Some(AllocationBlock {
+                index,
+                range: offset..offset + aligned_size,
+                dropped: Arc::clone(&self.dropped),
+            }),
+        }
+    }
+
+    /// Make the ranges of the allocations dropped so far available again, once the GPU
+    /// is done with the work submitted to `queue` so far.
+    ///
+    /// This must only be called after all command encoders using the dropped allocations
+    /// have been submitted. Additional calls are harmless. Not calling this as soon as
+    /// possible may result in increased buffer memory usage.
+    pub fn recall(&mut self, queue: &Queue) {
+        self.receive_ranges();
+
+        let dropped = core::mem::take(&mut *self.dropped.lock());
+        if dropped.is_empty() {
+            return;
+        }
+        let reclaimed = Arc::clone(&self.reclaimed);
+        // `Queue::on_submitted_work_done()` always requires a `Send` callback, which
+        // `Ranges` isn't without `std` or `parking_lot`. The backend only requires
+        // the callback to be `Send` where wgpu types are.
+        queue.inner.on_submitted_work_done(Box::new(move || {
+            reclaimed.lock().extend(dropped);
+        }));
+    }
+
+    /// Return statistics about the memory used by the allocator.
+    pub fn stats(&self) -> BufferAllocatorStats {
+        BufferAllocatorStats {
+            block_count: self.blocks.len(),
+            resident_bytes: self.blocks.len() as BufferAddress * self.block_size,
+            allocated_bytes: self.allocated_bytes,
+        }
+    }
+
+    /// Move all ranges that the GPU is done with from `self.reclaimed` to the free
+    /// lists of their blocks.
+    fn receive_ranges(&mut self) {
+        for (index, range) in self.reclaimed.lock().drain(..) {
+            self.allocated_bytes -= range.end - range.start;
+            self.blocks[index].free(range);
+        }
+    }
+}
+
+impl fmt::Debug for BufferAllocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferAllocator")
+            .field("usage", &self.usage)
+            .field("block_size", &self.block_size)
+            .field("alignment", &self.alignment)
+            .field("blocks", &self.blocks.len())
+            .field("allocated_bytes", &self.allocated_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A range of a buffer, allocated by a [`BufferAllocator`].
+///
+/// Dropping the allocation returns the range to the allocator, see
+/// [`BufferAllocator`] for when it is reused.
+pub struct BufferAllocation {
+    buffer: Buffer,
+    offset: BufferAddress,
+    size: BufferSize,
+    /// `None` for dedicated buffers.
+    block: Option<AllocationBlock>,
+}
+
+struct AllocationBlock {
+    index: usize,
+    /// The range in the block, including padding for alignment.
+    range: Range<BufferAddress>,
+    dropped: Ranges,
+}
+
+impl BufferAllocation {
+    /// The buffer containing the allocation.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The offset of the allocation in [`BufferAllocation::buffer()`].
+    pub fn offset(&self) -> BufferAddress {
+        self.offset
+    }
+
+    /// The size of the allocation, as requested.
+    pub fn size(&self) -> BufferSize {
+        self.size
+    }
+
+    /// The allocated range of [`BufferAllocation::buffer()`].
+    pub fn slice(&self) -> BufferSlice<'_> {
+        self.buffer
+            .slice(self.offset..self.offset + self.size.get())
+    }
+
+    /// A binding of the allocated range.
+    pub fn as_binding(&self) -> BufferBinding<'_> {
+        BufferBinding {
+            buffer: &self.buffer,
+            offset: self.offset,
+            size: Some(self.size),
+        }
+    }
+
+    /// A binding resource for the allocated range.
+    pub fn as_entire_binding(&self) -> BindingResource<'_> {
+        BindingResource::Buffer(self.as_binding())
+    }
+}
+
+impl Drop for BufferAllocation {
+    fn drop(&mut self) {
+        if let Some(block) = self.block.take() {
+            block.dropped.lock().push((block.index, block.range));
+        }
+    }
+}
+
+impl fmt::Debug for BufferAllocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferAllocation")
+            .field("buffer", &self.buffer)
+            .field("offset", &self.offset)
+            .field("size", &self.size)
+            .field("dedicated", &self.block.is_none())
+            .finish()
+    }
+}
+
+struct Block {
+    buffer: Buffer,
+    /// Free ranges, sorted and coalesced.
+    free: Vec<Range<BufferAddress>>,
+}
+
+impl Block {
+    /// Allocate `size` bytes from the first free range that is large enough.
+    fn allocate(&mut self, size: BufferAddress) -> Option<BufferAddress> {
+        let index = self
+            .free
+            .iter()
+            .position(|range| range.end - range.start >= size)?;
+        let range = &mut self.free[index];
+        let offset = range.start;
+        range.start += size;
+        if range.is_empty() {
+            self.free.remove(index);
+        }
+        Some(offset)
+    }
+
+    fn free(&mut self, range: Range<BufferAddress>) {
+        let index = self.free.partition_point(|free| free.start < range.start);
+        let merges_prev = index > 0 && self.free[index - 1].end == range.start;
+        let merges_next = index < self.free.len() && self.free[index].start == range.end;
+        match (merges_prev, merges_next) {
+            (true, true) => {
+                self.free[index - 1].end = self.free[index].end;
+                self.free.remove(index);
+            }
+            (true, false) => self.free[index - 1].end = range.end,
+            (false, true) => self.free[index].start = range.start,
+            (false, false) => self.free.insert(index, range),
+        }
+    }
+}
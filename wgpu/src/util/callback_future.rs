@@ -0,0 +1,130 @@
+use crate::{BufferAsyncError, util::Mutex};
+use alloc::sync::Arc;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// A future resolved by a callback, used to expose callback-based APIs as futures.
+///
+/// Create the future, resolve the [`CallbackSender`] returned by
+/// [`CallbackFuture::sender()`] from the callback passed to the callback-based API, and
+/// return the future to the caller.
+///
+/// If the sender is dropped without being resolved, for example because the device was
+/// lost before the callback was called, the future resolves to [`Canceled::canceled()`].
+pub(crate) struct CallbackFuture<T: Canceled> {
+    state: Arc<Mutex<CallbackState<T>>>,
+}
+
+struct CallbackState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// The value a [`CallbackFuture`] resolves to when its callback is dropped without
+/// being called.
+pub(crate) trait Canceled {
+    fn canceled() -> Self;
+}
+
+impl Canceled for () {
+    fn canceled() -> Self {}
+}
+
+impl<T> Canceled for Result<T, BufferAsyncError> {
+    fn canceled() -> Self {
+        Err(BufferAsyncError)
+    }
+}
+
+impl<T: Canceled> CallbackFuture<T> {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CallbackState {
+                value: None,
+                waker: None,
+            })),
+        }
+    }
+
+    /// Returns the sender which resolves this future.
+    pub fn sender(&self) -> CallbackSender<T> {
+        CallbackSender {
+            state: Some(Arc::clone(&self.state)),
+        }
+    }
+}
+
+/// Resolves a [`CallbackFuture`].
+pub(crate) struct CallbackSender<T: Canceled> {
+    /// `None` once the future has been resolved.
+    state: Option<Arc<Mutex<CallbackState<T>>>>,
+}
+
+impl<T: Canceled> CallbackSender<T> {
+    /// Resolve the future with `value`.
+    pub fn send(mut self, value: T) {
+        self.resolve(value);
+    }
+
+    fn resolve(&mut self, value: T) {
+        let Some(state) = self.state.take() else {
+            return;
+        };
+        let mut state = state.lock();
+        state.value = Some(value);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: Canceled> Drop for CallbackSender<T> {
+    fn drop(&mut self) {
+        self.resolve(T::canceled());
+    }
+}
+
+impl<T: Canceled> Future for CallbackFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock();
+        match state.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll<T: Canceled>(future: &mut CallbackFuture<T>) -> Poll<T> {
+        Pin::new(future).poll(&mut Context::from_waker(Waker::noop()))
+    }
+
+    #[test]
+    fn resolves_to_sent_value() {
+        let mut future = CallbackFuture::<Result<u32, BufferAsyncError>>::new();
+        let sender = future.sender();
+        assert_eq!(poll(&mut future), Poll::Pending);
+        sender.send(Ok(1));
+        assert_eq!(poll(&mut future), Poll::Ready(Ok(1)));
+    }
+
+    #[test]
+    fn dropped_sender_cancels() {
+        let mut future = CallbackFuture::<Result<u32, BufferAsyncError>>::new();
+        let sender = future.sender();
+        assert_eq!(poll(&mut future), Poll::Pending);
+        drop(sender);
+        assert_eq!(poll(&mut future), Poll::Ready(Err(BufferAsyncError)));
+    }
+}
@@ -1,4 +1,5 @@
-use alloc::borrow::ToOwned as _;
+use alloc::{borrow::ToOwned as _, string::String, vec::Vec};
+use core::fmt;
 
 use wgt::TextureDataOrder;
 
@@ -14,6 +15,45 @@ pub struct BufferInitDescriptor<'a> {
     pub usage: wgt::BufferUsages,
 }
 
+/// Error returned by [`DeviceExt::create_bind_group_by_name()`] when a named resource
+/// can't be mapped to a binding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NamedBindingError {
+    /// The shader module doesn't declare a resource variable with this name, or isn't
+    /// reflected (see [`ShaderModule::binding()`](crate::ShaderModule::binding)).
+    NotFound {
+        /// Name of the resource.
+        name: String,
+    },
+    /// The resource is in a different bind group than the resources before it.
+    GroupMismatch {
+        /// Name of the resource.
+        name: String,
+        /// Bind group of the resource.
+        group: u32,
+        /// Bind group of the resources before it.
+        expected: u32,
+    },
+}
+
+impl fmt::Display for NamedBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { name } => write!(f, "Shader module has no resource named `{name}`"),
+            Self::GroupMismatch {
+                name,
+                group,
+                expected,
+            } => write!(
+                f,
+                "Resource `{name}` is in bind group {group}, but other resources are in bind group {expected}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for NamedBindingError {}
+
 /// Utility methods not meant to be in the main API.
 pub trait DeviceExt {
     /// Creates a [Buffer](crate::Buffer) with data to initialize it.
@@ -34,6 +74,22 @@ pub trait DeviceExt {
         order: TextureDataOrder,
         data: &[u8],
     ) -> crate::Texture;
+
+    /// Creates a [BindGroup](crate::BindGroup) whose entries are identified by the names
+    /// of the resource variables they bind in `module`, rather than by binding index.
+    ///
+    /// The binding indices are looked up with [`ShaderModule::binding()`](crate::ShaderModule::binding).
+    ///
+    /// Returns an error, without creating a bind group, if `module` doesn't declare a
+    /// resource with one of the given names or isn't reflected, or if the named resources
+    /// don't all belong to the same bind group.
+    fn create_bind_group_by_name(
+        &self,
+        label: crate::Label<'_>,
+        layout: &crate::BindGroupLayout,
+        module: &crate::ShaderModule,
+        resources: &[(&str, crate::BindingResource<'_>)],
+    ) -> Result<crate::BindGroup, NamedBindingError>;
 }
 
 impl DeviceExt for crate::Device {
@@ -162,4 +218,43 @@ impl DeviceExt for crate::Device {
 
         texture
     }
+
+    fn create_bind_group_by_name(
+        &self,
+        label: crate::Label<'_>,
+        layout: &crate::BindGroupLayout,
+        module: &crate::ShaderModule,
+        resources: &[(&str, crate::BindingResource<'_>)],
+    ) -> Result<crate::BindGroup, NamedBindingError> {
+        let mut group_index = None;
+        let entries = resources
+            .iter()
+            .map(|(name, resource)| {
+                let (group, binding) =
+                    module
+                        .binding(name)
+                        .ok_or_else(|| NamedBindingError::NotFound {
+                            name: String::from(*name),
+                        })?;
+                let expected = *group_index.get_or_insert(group);
+                if group != expected {
+                    return Err(NamedBindingError::GroupMismatch {
+                        name: String::from(*name),
+                        group,
+                        expected,
+                    });
+                }
+                Ok(crate::BindGroupEntry {
+                    binding,
+                    resource: resource.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self.create_bind_group(&crate::BindGroupDescriptor {
+            label,
+            layout,
+            entries: &entries,
+        }))
+    }
 }
@@ -0,0 +1,251 @@
+use crate::{
+    Adapter, Device, DeviceDescriptor, DeviceLostReason, Instance, PowerPreference, Queue,
+    RequestAdapterError, RequestAdapterOptions, RequestDeviceError, Surface, WasmNotSend,
+    util::Mutex,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    fmt,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// Value of a device's loss slot while it hasn't been lost.
+const NOT_LOST: u8 = u8::MAX;
+
+#[cfg(send_sync)]
+type RecreateCallback = Box<dyn FnMut(&Adapter, &Device, &Queue) + Send>;
+#[cfg(not(send_sync))]
+type RecreateCallback = Box<dyn FnMut(&Adapter, &Device, &Queue)>;
+
+/// Identifies a callback registered with [`DeviceRecreator::on_recreate()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RecreateCallbackId(u64);
+
+/// Error returned when a [`DeviceRecreator`] fails to get a device.
+#[derive(Clone, Debug)]
+pub enum RecreateDeviceError {
+    /// No adapter matched the recreator's adapter options.
+    RequestAdapter(RequestAdapterError),
+    /// The adapter couldn't create a device matching the recreator's descriptor.
+    RequestDevice(RequestDeviceError),
+}
+
+impl fmt::Display for RecreateDeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RequestAdapter(error) => write!(f, "Failed to request an adapter: {error}"),
+            Self::RequestDevice(error) => write!(f, "Failed to request a device: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for RecreateDeviceError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::RequestAdapter(error) => Some(error),
+            Self::RequestDevice(error) => Some(error),
+        }
+    }
+}
+
+/// Owns a [`Device`] and gets a new one after the current one is lost.
+///
+/// A device can be lost at any time: the driver may reset a GPU that stopped responding
+/// (a "TDR" on Windows), the GPU may be removed, or the device may run out of memory.
+/// Every object created from a lost device is unusable, so recovering means requesting a
+/// new adapter and device and recreating all GPU resources from them.
+///
+/// A `DeviceRecreator` remembers how its device was requested and watches it for loss.
+/// Once [`lost_reason()`](Self::lost_reason) reports the device as lost, call
+/// [`recreate()`](Self::recreate) to request a new adapter and device with the same
+/// options. Parts of the application which own GPU resources register a callback with
+/// [`on_recreate()`](Self::on_recreate), and are called with the new adapter, device and
+/// queue to recreate their resources.
+///
+/// Cloning a `DeviceRecreator` gives another handle to the same device and callbacks.
+#[derive(Clone)]
+pub struct DeviceRecreator {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    instance: Instance,
+    power_preference: PowerPreference,
+    force_fallback_adapter: bool,
+    device_descriptor: DeviceDescriptor<'static>,
+    state: Mutex<State>,
+    callbacks: Mutex<Callbacks>,
+}
+
+struct State {
+    adapter: Adapter,
+    device: Device,
+    queue: Queue,
+    /// The [`DeviceLostReason`] of `device`, or [`NOT_LOST`].
+    lost: Arc<AtomicU8>,
+}
+
+#[derive(Default)]
+struct Callbacks {
+    registered: Vec<(RecreateCallbackId, RecreateCallback)>,
+    next_id: u64,
+}
+
+impl DeviceRecreator {
+    /// Request an adapter from `instance` and a device from it.
+    ///
+    /// `options` and `device_descriptor` are kept to request the adapter and device again
+    /// in [`recreate()`](Self::recreate).
+    pub async fn new(
+        instance: Instance,
+        options: &RequestAdapterOptions<'_, '_>,
+        device_descriptor: DeviceDescriptor<'static>,
+    ) -> Result<Self, RecreateDeviceError> {
+        let state = request_state(&instance, options, &device_descriptor).await?;
+        Ok(Self {
+            shared: Arc::new(Shared {
+                instance,
+                power_preference: options.power_preference,
+                force_fallback_adapter: options.force_fallback_adapter,
+                device_descriptor,
+                state: Mutex::new(state),
+                callbacks: Mutex::default(),
+            }),
+        })
+    }
+
+    /// The current adapter.
+    pub fn adapter(&self) -> Adapter {
+        self.shared.state.lock().adapter.clone()
+    }
+
+    /// The current device.
+    pub fn device(&self) -> Device {
+        self.shared.state.lock().device.clone()
+    }
+
+    /// The current device's queue.
+    pub fn queue(&self) -> Queue {
+        self.shared.state.lock().queue.clone()
+    }
+
+    /// Why the current device was lost, or `None` if it hasn't been lost.
+    ///
+    /// Loss is reported when the device is polled, so this may return `None` for a short
+    /// time after the device is lost.
+    pub fn lost_reason(&self) -> Option<DeviceLostReason> {
+        let lost = self.shared.state.lock().lost.load(Ordering::Acquire);
+        Some(match lost {
+            NOT_LOST => return None,
+            1 => DeviceLostReason::Destroyed,
+            2 => DeviceLostReason::Hung,
+            3 => DeviceLostReason::Removed,
+            4 => DeviceLostReason::DriverReset,
+            5 => DeviceLostReason::OutOfMemory,
+            _ => DeviceLostReason::Unknown,
+        })
+    }
+
+    /// Register a callback called with the new adapter, device and queue every time the
+    /// device is recreated.
+    ///
+    /// The callback should recreate every resource its owner created from the old device.
+    pub fn on_recreate(
+        &self,
+        callback: impl FnMut(&Adapter, &Device, &Queue) + WasmNotSend + 'static,
+    ) -> RecreateCallbackId {
+        let mut callbacks = self.shared.callbacks.lock();
+        let id = RecreateCallbackId(callbacks.next_id);
+        callbacks.next_id += 1;
+        callbacks.registered.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Unregister a callback registered with [`on_recreate()`](Self::on_recreate).
+    ///
+    /// Returns `false` if `id` wasn't registered.
+    pub fn remove_on_recreate(&self, id: RecreateCallbackId) -> bool {
+        let mut callbacks = self.shared.callbacks.lock();
+        let len = callbacks.registered.len();
+        callbacks.registered.retain(|&(other, _)| other != id);
+        callbacks.registered.len() != len
+    }
+
+    /// Request a new adapter and device, replace the current ones with them, and call
+    /// every callback registered with [`on_recreate()`](Self::on_recreate).
+    ///
+    /// This is usually called once [`lost_reason()`](Self::lost_reason) returns `Some`, but
+    /// can be called at any time, for example to move to an adapter which can present to
+    /// `compatible_surface`. If requesting the adapter or device fails, the current ones are
+    /// kept and no callback is called; the request can be retried later, as a GPU which was
+    /// reset may take some time to become available again.
+    pub async fn recreate(
+        &self,
+        compatible_surface: Option<&Surface<'_>>,
+    ) -> Result<(), RecreateDeviceError> {
+        let options = RequestAdapterOptions {
+            power_preference: self.shared.power_preference,
+            force_fallback_adapter: self.shared.force_fallback_adapter,
+            compatible_surface,
+        };
+        let state = request_state(
+            &self.shared.instance,
+            &options,
+            &self.shared.device_descriptor,
+        )
+        .await?;
+        let (adapter, device, queue) = (
+            state.adapter.clone(),
+            state.device.clone(),
+            state.queue.clone(),
+        );
+        *self.shared.state.lock() = state;
+
+        // Callbacks are called without the lock held, so they can register new callbacks
+        // themselves.
+        let mut called = core::mem::take(&mut self.shared.callbacks.lock().registered);
+        for (_, callback) in called.iter_mut() {
+            callback(&adapter, &device, &queue);
+        }
+        let mut callbacks = self.shared.callbacks.lock();
+        let added = core::mem::replace(&mut callbacks.registered, called);
+        callbacks.registered.extend(added);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for DeviceRecreator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceRecreator")
+            .field("device", &self.shared.state.lock().device)
+            .finish_non_exhaustive()
+    }
+}
+
+async fn request_state(
+    instance: &Instance,
+    options: &RequestAdapterOptions<'_, '_>,
+    device_descriptor: &DeviceDescriptor<'static>,
+) -> Result<State, RecreateDeviceError> {
+    let adapter = instance
+        .request_adapter(options)
+        .await
+        .map_err(RecreateDeviceError::RequestAdapter)?;
+    let (device, queue) = adapter
+        .request_device(device_descriptor)
+        .await
+        .map_err(RecreateDeviceError::RequestDevice)?;
+
+    let lost = Arc::new(AtomicU8::new(NOT_LOST));
+    let slot = Arc::clone(&lost);
+    device.set_device_lost_callback(move |reason, _message| {
+        slot.store(reason as u8, Ordering::Release);
+    });
+
+    Ok(State {
+        adapter,
+        device,
+        queue,
+        lost,
+    })
+}
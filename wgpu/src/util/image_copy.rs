@@ -0,0 +1,351 @@
+#![cfg(feature = "wgsl")]
+
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType, BufferUsages,
+    ColorTargetState, ColorWrites, CommandEncoderDescriptor, CopyExternalImageDestInfo, Device,
+    Extent3d, FragmentState, FrontFace, LoadOp, MultisampleState, Origin2d, Origin3d,
+    PipelineCompilationOptions, PipelineLayoutDescriptor, PredefinedColorSpace, PrimitiveState,
+    PrimitiveTopology, Queue, RenderPassDescriptor, RenderPassFlags, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, StoreOp, TexelCopyBufferLayout, TexelCopyTextureInfo,
+    Texture, TextureAspect, TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType,
+    TextureUsages, TextureViewDescriptor, TextureViewDimension, VertexState, include_wgsl,
+    util::{BufferInitDescriptor, DeviceExt as _},
+};
+
+// Flags of the `Params` struct in `image_copy.wgsl`.
+const FLIP_Y: u32 = 1;
+const SOURCE_PREMULTIPLIED: u32 = 2;
+const TARGET_PREMULTIPLIED: u32 = 4;
+const CONVERT_COLOR_SPACE: u32 = 8;
+const TARGET_SRGB: u32 = 16;
+
+/// Columns of the matrix converting linear sRGB to linear Display P3.
+#[rustfmt::skip]
+const SRGB_TO_DISPLAY_P3: [f32; 9] = [
+    0.822_462_1, 0.033_194_2, 0.017_082_7,
+    0.177_538, 0.966_805_8, 0.072_397_4,
+    0.0, 0.0, 0.910_519_9,
+];
+
+/// Columns of the matrix converting linear Display P3 to linear sRGB.
+#[rustfmt::skip]
+const DISPLAY_P3_TO_SRGB: [f32; 9] = [
+    1.224_940_2, -0.042_056_9, -0.019_637_6,
+    -0.224_940_4, 1.042_057_1, -0.078_636_1,
+    0.0, 0.0, 1.098_273_5,
+];
+
+/// An image in CPU memory, to be copied to a texture with
+/// [`ImageCopier::copy_image_to_texture`].
+///
+/// This is the counterpart of `CopyExternalImageSourceInfo`, which describes images
+/// of the web platform.
+#[derive(Clone, Copy, Debug)]
+pub struct CopyImageSourceInfo<'a> {
+    /// The texels of the image.
+    pub data: &'a [u8],
+    /// The layout of the texels in `data`.
+    pub layout: TexelCopyBufferLayout,
+    /// The format of the texels in `data`. It has to be an uncompressed color format
+    /// with a float sample type, such as [`TextureFormat::Rgba8Unorm`] or
+    /// [`TextureFormat::Rgba16Float`].
+    ///
+    /// The `Srgb` variants of formats are treated like the formats without the
+    /// suffix, since the image's transfer function is given by `color_space`.
+    pub format: TextureFormat,
+    /// The color space the image's colors are encoded in.
+    pub color_space: PredefinedColorSpace,
+    /// If the image's colors are premultiplied by alpha.
+    pub premultiplied_alpha: bool,
+    /// The base texel of the copied region of the image, relative to its top left.
+    pub origin: Origin2d,
+    /// If the Y coordinate of the image should be flipped. Even if this is
+    /// true, `origin` is still relative to the top left.
+    pub flip_y: bool,
+}
+
+/// Image Upload Utility
+///
+/// Copies images from CPU memory to textures, converting their format, color space
+/// and alpha premultiplication, and optionally flipping them, on the GPU. This is
+/// what `Queue::copy_external_image_to_texture` does with images of the web
+/// platform, and unlike it, works on every backend.
+///
+/// The image is written to a temporary texture, which is then rendered to the
+/// target texture.
+pub struct ImageCopier {
+    pipeline: RenderPipeline,
+    bind_group_layout: BindGroupLayout,
+    format: TextureFormat,
+}
+
+impl ImageCopier {
+    /// Returns a new [`ImageCopier`].
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `format` - The [`TextureFormat`] of the textures that will be copied to. This has to be a renderable color format.
+    pub fn new(device: &Device, format: TextureFormat) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::ImageCopier::bind_group_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::ImageCopier::pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let shader = device.create_shader_module(include_wgsl!("image_copy.wgsl"));
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("wgpu::util::ImageCopier::pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                primitive_restart: true,
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: wgt::PolygonMode::Fill,
+                conservative: false,
+                line_width: 1.0,
+                line_stipple: None,
+            },
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                    logic_op: None,
+                })],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            format,
+        }
+    }
+
+    /// Schedules a copy of `size` texels of the `source` image into `dest`, and
+    /// submits it to `queue`.
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `queue` - The [`Queue`] the copy is submitted to.
+    /// - `source` - The image and the region of it to copy.
+    /// - `dest` - A 2D [`Texture`] with the `RENDER_ATTACHMENT` usage, of the format specified in [`ImageCopier::new`], and the region of it to copy to.
+    /// - `size` - The size of the copied region. `depth_or_array_layers` has to be 1.
+    pub fn copy_image_to_texture(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        source: &CopyImageSourceInfo<'_>,
+        dest: CopyExternalImageDestInfo<&Texture>,
+        size: Extent3d,
+    ) {
+        let source_format = source.format.remove_srgb_suffix();
+        assert!(
+            matches!(
+                source_format.sample_type(None, None),
+                Some(TextureSampleType::Float { .. })
+            ) && source_format.block_dimensions() == (1, 1)
+                && !source_format.is_multi_planar_format(),
+            "source format must be an uncompressed color format with a float sample type"
+        );
+        assert_eq!(
+            dest.texture.format(),
+            self.format,
+            "destination format must match the format of the ImageCopier"
+        );
+        assert_eq!(
+            dest.texture.dimension(),
+            TextureDimension::D2,
+            "destination texture must be 2D"
+        );
+        assert_eq!(
+            dest.aspect,
+            TextureAspect::All,
+            "destination aspect must be `TextureAspect::All`"
+        );
+        assert_eq!(
+            size.depth_or_array_layers, 1,
+            "only a single image can be copied at once"
+        );
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        // Start the upload at the copied region, so it fits in a texture of its size.
+        let block_size = u64::from(source_format.block_copy_size(None).unwrap());
+        let mut layout = source.layout;
+        layout.offset += u64::from(source.origin.x) * block_size;
+        if source.origin.y != 0 {
+            let bytes_per_row = layout
+                .bytes_per_row
+                .expect("bytes_per_row must be specified when copying from a non-zero row");
+            layout.offset += u64::from(source.origin.y) * u64::from(bytes_per_row);
+        }
+
+        let staging = device.create_texture(&TextureDescriptor {
+            label: Some("wgpu::util::ImageCopier::staging"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: source_format,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &staging,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            source.data,
+            layout,
+            size,
+        );
+
+        let mut flags = 0;
+        if source.flip_y {
+            flags |= FLIP_Y;
+        }
+        if source.premultiplied_alpha {
+            flags |= SOURCE_PREMULTIPLIED;
+        }
+        if dest.premultiplied_alpha {
+            flags |= TARGET_PREMULTIPLIED;
+        }
+        if self.format.is_srgb() {
+            flags |= TARGET_SRGB;
+        }
+        let color_matrix = match (source.color_space, dest.color_space) {
+            (PredefinedColorSpace::Srgb, PredefinedColorSpace::DisplayP3) => {
+                flags |= CONVERT_COLOR_SPACE;
+                SRGB_TO_DISPLAY_P3
+            }
+            (PredefinedColorSpace::DisplayP3, PredefinedColorSpace::Srgb) => {
+                flags |= CONVERT_COLOR_SPACE;
+                DISPLAY_P3_TO_SRGB
+            }
+            _ => [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+        };
+
+        // Laid out like the `Params` struct in `image_copy.wgsl`.
+        #[rustfmt::skip]
+        let params: [f32; 16] = [
+            color_matrix[0], color_matrix[1], color_matrix[2], 0.0,
+            color_matrix[3], color_matrix[4], color_matrix[5], 0.0,
+            color_matrix[6], color_matrix[7], color_matrix[8], 0.0,
+            dest.origin.x as f32, dest.origin.y as f32, size.height as f32, f32::from_bits(flags),
+        ];
+        let uniform_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("wgpu::util::ImageCopier::uniform_buffer"),
+            contents: bytemuck::cast_slice(&params),
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let staging_view = staging.create_view(&TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::ImageCopier::bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&staging_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let target = dest.texture.create_view(&TextureViewDescriptor {
+            label: Some("wgpu::util::ImageCopier::target"),
+            dimension: Some(TextureViewDimension::D2),
+            base_mip_level: dest.mip_level,
+            mip_level_count: Some(1),
+            base_array_layer: dest.origin.z,
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("wgpu::util::ImageCopier::encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("wgpu::util::ImageCopier::pass"),
+                color_attachments: &[Some(crate::RenderPassColorAttachment {
+                    view: &target,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgt::Operations {
+                        load: LoadOp::Load,
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                pipeline_statistics_query: None,
+                flags: RenderPassFlags::empty(),
+                multiview: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.set_viewport(
+                dest.origin.x as f32,
+                dest.origin.y as f32,
+                size.width as f32,
+                size.height as f32,
+                0.0,
+                1.0,
+            );
+            pass.draw(0..3, 0..1);
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+}
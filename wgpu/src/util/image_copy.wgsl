@@ -0,0 +1,75 @@
+struct Params {
+    // Linear RGB to linear RGB matrix, converting between color spaces.
+    color_matrix: mat3x3<f32>,
+    // Position of the copied region in the target, in texels.
+    target_origin: vec2<f32>,
+    // Height of the copied region, in texels.
+    height: f32,
+    // A combination of the flags below.
+    flags: u32,
+}
+
+const FLIP_Y: u32 = 1u;
+const SOURCE_PREMULTIPLIED: u32 = 2u;
+const TARGET_PREMULTIPLIED: u32 = 4u;
+const CONVERT_COLOR_SPACE: u32 = 8u;
+// The target format encodes to sRGB on write, so linear values must be written.
+const TARGET_SRGB: u32 = 16u;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vi: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the viewport, which is set to the copied region.
+    let uv = vec2<f32>(f32((vi << 1u) & 2u), f32(vi & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> params: Params;
+
+// Both sRGB and Display P3 use the sRGB transfer function. Negative values, which
+// colors outside of the gamut of the target color space have, are mirrored.
+fn to_linear(c: vec3<f32>) -> vec3<f32> {
+    let a = abs(c);
+    let linear = select(pow((a + 0.055) / 1.055, vec3<f32>(2.4)), a / 12.92, a <= vec3<f32>(0.04045));
+    return sign(c) * linear;
+}
+
+fn from_linear(c: vec3<f32>) -> vec3<f32> {
+    let a = abs(c);
+    let encoded = select(1.055 * pow(a, vec3<f32>(1.0 / 2.4)) - 0.055, a * 12.92, a <= vec3<f32>(0.0031308));
+    return sign(c) * encoded;
+}
+
+fn has_flag(flag: u32) -> bool {
+    return (params.flags & flag) != 0u;
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    var texel = vec2<i32>(floor(position.xy - params.target_origin));
+    if has_flag(FLIP_Y) {
+        texel.y = i32(params.height) - 1 - texel.y;
+    }
+    let color = textureLoad(source, texel, 0);
+
+    var rgb = color.rgb;
+    let source_premultiplied = has_flag(SOURCE_PREMULTIPLIED);
+    let convert = has_flag(CONVERT_COLOR_SPACE);
+    // Colors are converted without alpha applied.
+    let unpremultiply = source_premultiplied && (convert || !has_flag(TARGET_PREMULTIPLIED));
+    if unpremultiply && color.a > 0.0 {
+        rgb /= color.a;
+    }
+    if convert {
+        rgb = from_linear(params.color_matrix * to_linear(rgb));
+    }
+    if has_flag(TARGET_PREMULTIPLIED) && (unpremultiply || !source_premultiplied) {
+        rgb *= color.a;
+    }
+    if has_flag(TARGET_SRGB) {
+        rgb = to_linear(rgb);
+    }
+    return vec4<f32>(rgb, color.a);
+}
@@ -0,0 +1,263 @@
+#![cfg(feature = "wgsl")]
+
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, BufferDescriptor, BufferUsages,
+    CommandEncoder, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, Device,
+    PipelineCompilationOptions, PipelineLayoutDescriptor, RenderPass, ShaderStages, include_wgsl,
+    util::{BufferInitDescriptor, DeviceExt as _, DrawIndexedIndirectArgs},
+};
+
+/// Workgroup size of `indirect_draw.wgsl`.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// An object that may be drawn by the draws built with [`IndirectDrawBuilder`].
+///
+/// This is laid out like the `DrawCommand` struct in `indirect_draw.wgsl`, so a
+/// slice of these can be written to the commands buffer as is.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct IndirectDrawCommand {
+    /// The draw, which is written to [`IndirectDraws`] if the object is visible.
+    pub args: DrawIndexedIndirectArgs,
+    /// The center of the object's bounding sphere, in world space.
+    pub center: [f32; 3],
+    /// The radius of the object's bounding sphere. If it is negative, the object
+    /// is never culled.
+    pub radius: f32,
+}
+
+// SAFETY: `IndirectDrawCommand` is `repr(C)`, has no padding and only contains
+// `Pod` fields.
+unsafe impl bytemuck::Zeroable for IndirectDrawCommand {}
+// SAFETY: See above.
+unsafe impl bytemuck::Pod for IndirectDrawCommand {}
+
+impl IndirectDrawCommand {
+    /// Returns the bytes representation of the struct, ready to be written in a buffer.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+/// The draws built by [`IndirectDrawBuilder::build`].
+///
+/// Holds a buffer of [`DrawIndexedIndirectArgs`] and the number of draws in it,
+/// ready to be used with [`RenderPass::multi_draw_indexed_indirect_count`].
+pub struct IndirectDraws {
+    args_buffer: Buffer,
+    count_buffer: Buffer,
+    max_count: u32,
+}
+
+impl IndirectDraws {
+    /// Returns new [`IndirectDraws`] with room for `max_count` draws.
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `max_count` - The largest number of commands that will be passed to [`IndirectDrawBuilder::build`].
+    pub fn new(device: &Device, max_count: u32) -> Self {
+        let args_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("wgpu::util::IndirectDraws::args_buffer"),
+            size: u64::from(max_count.max(1)) * size_of::<DrawIndexedIndirectArgs>() as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::INDIRECT | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let count_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("wgpu::util::IndirectDraws::count_buffer"),
+            size: size_of::<u32>() as u64,
+            usage: BufferUsages::STORAGE
+                | BufferUsages::INDIRECT
+                | BufferUsages::COPY_SRC
+                | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            args_buffer,
+            count_buffer,
+            max_count,
+        }
+    }
+
+    /// The buffer of [`DrawIndexedIndirectArgs`]. Only the first `count` entries are valid.
+    pub fn args_buffer(&self) -> &Buffer {
+        &self.args_buffer
+    }
+
+    /// The buffer containing the number of draws, as a single `u32`.
+    pub fn count_buffer(&self) -> &Buffer {
+        &self.count_buffer
+    }
+
+    /// The largest number of draws these buffers can hold.
+    pub fn max_count(&self) -> u32 {
+        self.max_count
+    }
+
+    /// Issues the draws with [`RenderPass::multi_draw_indexed_indirect_count`].
+    ///
+    /// Requires [`Features::MULTI_DRAW_INDIRECT_COUNT`](crate::Features::MULTI_DRAW_INDIRECT_COUNT).
+    pub fn draw(&self, pass: &mut RenderPass<'_>) {
+        pass.multi_draw_indexed_indirect_count(
+            &self.args_buffer,
+            0,
+            &self.count_buffer,
+            0,
+            self.max_count,
+        );
+    }
+}
+
+/// GPU-Driven Draw Building Utility
+///
+/// Culls a buffer of [`IndirectDrawCommand`]s against a view frustum on the GPU and
+/// compacts the visible ones into [`IndirectDraws`], which can then be drawn with a
+/// single [`RenderPass::multi_draw_indexed_indirect_count`].
+///
+/// The culling runs in a compute pass recorded into the given [`CommandEncoder`].
+/// wgpu tracks the buffers' usages, so no further synchronization is needed
+/// between building the draws and drawing them.
+pub struct IndirectDrawBuilder {
+    pipeline: ComputePipeline,
+    bind_group_layout: BindGroupLayout,
+}
+
+impl IndirectDrawBuilder {
+    /// Returns a new [`IndirectDrawBuilder`].
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    pub fn new(device: &Device) -> Self {
+        let storage_entry = |binding, read_only| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::IndirectDrawBuilder::bind_group_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_entry(1, true),
+                storage_entry(2, false),
+                storage_entry(3, false),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::IndirectDrawBuilder::pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let shader = device.create_shader_module(include_wgsl!("indirect_draw.wgsl"));
+        let pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("wgpu::util::IndirectDrawBuilder::pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+        }
+    }
+
+    /// Writes the draws of the visible commands to `draws`, replacing the previous ones.
+    ///
+    /// The order of the draws is not preserved.
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `encoder` - A [`CommandEncoder`]
+    /// - `commands` - A [`Buffer`] with the `STORAGE` usage, containing `command_count` [`IndirectDrawCommand`]s.
+    /// - `command_count` - The number of commands in `commands`. It can't be larger than the `max_count` of `draws`.
+    /// - `view_projection` - The column major view projection matrix, whose frustum the commands' bounding spheres are culled against.
+    /// - `draws` - The [`IndirectDraws`] that get the visible draws.
+    pub fn build(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        commands: &Buffer,
+        command_count: u32,
+        view_projection: [[f32; 4]; 4],
+        draws: &IndirectDraws,
+    ) {
+        assert!(
+            command_count <= draws.max_count,
+            "command count ({command_count}) must not be larger than the max count of the draws ({})",
+            draws.max_count
+        );
+
+        encoder.clear_buffer(&draws.count_buffer, 0, None);
+
+        if command_count == 0 {
+            return;
+        }
+
+        let [c0, c1, c2, c3] = view_projection;
+        // Laid out like the `Params` struct in `indirect_draw.wgsl`.
+        #[rustfmt::skip]
+        let params: [f32; 20] = [
+            c0[0], c0[1], c0[2], c0[3],
+            c1[0], c1[1], c1[2], c1[3],
+            c2[0], c2[1], c2[2], c2[3],
+            c3[0], c3[1], c3[2], c3[3],
+            f32::from_bits(command_count), 0.0, 0.0, 0.0,
+        ];
+        let params_buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("wgpu::util::IndirectDrawBuilder::params_buffer"),
+            contents: bytemuck::cast_slice(&params),
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::IndirectDrawBuilder::bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: commands.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: draws.args_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: draws.count_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("wgpu::util::IndirectDrawBuilder::pass"),
+            timestamp_writes: None,
+            pipeline_statistics_query: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(command_count.div_ceil(WORKGROUP_SIZE), 1, 1);
+    }
+}
@@ -0,0 +1,88 @@
+struct DrawCommand {
+    // `DrawIndexedIndirectArgs`
+    index_count: u32,
+    instance_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32,
+    // Bounding sphere of the object, in world space.
+    center_x: f32,
+    center_y: f32,
+    center_z: f32,
+    // A negative radius means the object is never culled.
+    radius: f32,
+}
+
+struct DrawIndexedIndirectArgs {
+    index_count: u32,
+    instance_count: u32,
+    first_index: u32,
+    base_vertex: i32,
+    first_instance: u32,
+}
+
+struct Params {
+    view_projection: mat4x4<f32>,
+    command_count: u32,
+}
+
+@group(0) @binding(0)
+var<uniform> params: Params;
+@group(0) @binding(1)
+var<storage, read> commands: array<DrawCommand>;
+@group(0) @binding(2)
+var<storage, read_write> draws: array<DrawIndexedIndirectArgs>;
+@group(0) @binding(3)
+var<storage, read_write> draw_count: atomic<u32>;
+
+fn is_visible(center: vec3<f32>, radius: f32) -> bool {
+    if radius < 0.0 {
+        return true;
+    }
+
+    // Extract the frustum planes from the rows of the view projection matrix,
+    // for a depth range of 0 to 1.
+    let m = transpose(params.view_projection);
+    var planes = array<vec4<f32>, 6>(
+        m[3] + m[0],
+        m[3] - m[0],
+        m[3] + m[1],
+        m[3] - m[1],
+        m[2],
+        m[3] - m[2],
+    );
+
+    for (var i = 0u; i < 6u; i++) {
+        let plane = planes[i];
+        let distance = dot(plane.xyz, center) + plane.w;
+        if distance < -radius * length(plane.xyz) {
+            return false;
+        }
+    }
+    return true;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_invocation_id: vec3u) {
+    let index = global_invocation_id.x;
+    if index >= params.command_count {
+        return;
+    }
+
+    let command = commands[index];
+    if command.instance_count == 0u || command.index_count == 0u {
+        return;
+    }
+    if !is_visible(vec3<f32>(command.center_x, command.center_y, command.center_z), command.radius) {
+        return;
+    }
+
+    let draw_index = atomicAdd(&draw_count, 1u);
+    draws[draw_index] = DrawIndexedIndirectArgs(
+        command.index_count,
+        command.instance_count,
+        command.first_index,
+        command.base_vertex,
+        command.first_instance,
+    );
+}
@@ -3,33 +3,61 @@
 //! Nothing in this module is a part of the WebGPU API specification;
 //! they are unique to the `wgpu` library.
 
-// TODO: For [`belt::StagingBelt`] to be available in `no_std` its usage of [`std::sync::mpsc`]
-// must be replaced with an appropriate alternative.
-#[cfg(std)]
 mod belt;
+mod bind_group;
+mod buffer_allocator;
+mod callback_future;
 mod device;
+mod device_recreator;
 mod encoder;
+mod image_copy;
+mod indirect_draw;
 mod init;
 mod mutex;
+mod pipeline;
+mod profiler;
+mod surface_capture;
 mod texture_blitter;
+mod texture_readback;
+mod upload_stream;
+mod yuv_converter;
 
 use alloc::{borrow::Cow, format, string::String, vec};
-use core::ptr::copy_nonoverlapping;
+use core::{
+    future::Future,
+    pin::Pin,
+    ptr::copy_nonoverlapping,
+    task::{Context, Poll},
+};
 
-#[cfg(std)]
-pub use belt::StagingBelt;
-pub use device::{BufferInitDescriptor, DeviceExt};
+pub use belt::{StagingBelt, StagingBeltStats};
+pub use bind_group::{BindGroupBuilder, BindGroupCache};
+pub use buffer_allocator::{BufferAllocation, BufferAllocator, BufferAllocatorStats};
+pub use device::{BufferInitDescriptor, DeviceExt, NamedBindingError};
+pub use device_recreator::{DeviceRecreator, RecreateCallbackId, RecreateDeviceError};
 pub use encoder::RenderEncoder;
+#[cfg(feature = "wgsl")]
+pub use image_copy::{CopyImageSourceInfo, ImageCopier};
+#[cfg(feature = "wgsl")]
+pub use indirect_draw::{IndirectDrawBuilder, IndirectDrawCommand, IndirectDraws};
 pub use init::*;
+pub use pipeline::{ComputePipelineBuilder, RenderPipelineBuilder};
+pub use profiler::{GpuProfiler, GpuProfilerScope, TimestampEncoder};
+pub use surface_capture::{RgbaImageData, capture_surface_texture, capture_texture};
 #[cfg(feature = "wgsl")]
 pub use texture_blitter::{TextureBlitter, TextureBlitterBuilder};
+pub use texture_readback::read_texture_to_vec;
+pub use upload_stream::UploadStream;
 pub use wgt::{
     DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs, TextureDataOrder, math::*,
 };
+#[cfg(feature = "wgsl")]
+pub use yuv_converter::{YuvConverter, YuvConverterBuilder};
 
+pub(crate) use callback_future::CallbackFuture;
 pub(crate) use mutex::Mutex;
 
-use crate::dispatch;
+use crate::{WasmNotSend, dispatch};
 
 /// Treat the given byte slice as a SPIR-V module.
 ///
@@ -105,6 +133,48 @@ impl DownloadBuffer {
         buffer: &super::BufferSlice<'_>,
         callback: impl FnOnce(Result<Self, super::BufferAsyncError>) + Send + 'static,
     ) {
+        let download = Self::copy_to_download_buffer(device, queue, buffer);
+
+        download
+            .clone()
+            .slice(..)
+            .map_async(super::MapMode::Read, move |result| {
+                callback(result.map(|()| Self::from_mapped(download)));
+            });
+    }
+
+    /// Asynchronously read the contents of a buffer, returning a future which resolves
+    /// once the data is available.
+    ///
+    /// Each time the future is polled before the data is available, it polls the device with
+    /// [`PollType::Poll`](super::PollType::Poll), which doesn't block. While the copy is still
+    /// in flight the future asks to be polled again, so it completes under any executor without
+    /// the caller having to call [`Device::poll`](super::Device::poll).
+    pub fn read_buffer_async(
+        device: &super::Device,
+        queue: &super::Queue,
+        buffer: &super::BufferSlice<'_>,
+    ) -> impl Future<Output = Result<Self, super::BufferAsyncError>> + WasmNotSend + use<> {
+        let download = Self::copy_to_download_buffer(device, queue, buffer);
+
+        let future = CallbackFuture::new();
+        let sender = future.sender();
+        download
+            .slice(..)
+            .map_async(super::MapMode::Read, move |result| sender.send(result));
+
+        ReadBufferFuture {
+            device: device.clone(),
+            download,
+            future,
+        }
+    }
+
+    fn copy_to_download_buffer(
+        device: &super::Device,
+        queue: &super::Queue,
+        buffer: &super::BufferSlice<'_>,
+    ) -> super::Buffer {
         let size = buffer.size.into();
 
         let download = device.create_buffer(&super::BufferDescriptor {
@@ -121,20 +191,64 @@ impl DownloadBuffer {
         queue.submit(Some(command_buffer));
 
         download
-            .clone()
-            .slice(..)
-            .map_async(super::MapMode::Read, move |result| {
-                if let Err(e) = result {
-                    callback(Err(e));
-                    return;
-                }
-
-                let mapped_range = download.inner.get_mapped_range(0..size);
-                callback(Ok(Self {
-                    _gpu_buffer: download,
-                    mapped_range,
-                }));
-            });
+    }
+
+    fn from_mapped(download: super::Buffer) -> Self {
+        let mapped_range = download.inner.get_mapped_range(0..download.size());
+        Self {
+            _gpu_buffer: download,
+            mapped_range,
+        }
+    }
+}
+
+/// Future returned by [`DownloadBuffer::read_buffer_async`].
+struct ReadBufferFuture {
+    device: super::Device,
+    download: super::Buffer,
+    future: CallbackFuture<Result<(), super::BufferAsyncError>>,
+}
+
+impl Future for ReadBufferFuture {
+    type Output = Result<DownloadBuffer, super::BufferAsyncError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut result = Pin::new(&mut self.future).poll(cx);
+        if result.is_pending() {
+            // Runs the map callback, which wakes the future, if the copy has finished. Errors
+            // are reported through the map callback as well.
+            let status = self.device.poll(super::PollType::Poll);
+            result = Pin::new(&mut self.future).poll(cx);
+            if result.is_pending() && matches!(status, Ok(status) if !status.is_queue_empty()) {
+                cx.waker().wake_by_ref();
+            }
+        }
+
+        result.map(|result| result.map(|()| DownloadBuffer::from_mapped(self.download.clone())))
+    }
+}
+
+impl DownloadBuffer {
+    /// Interpret the downloaded data as a slice of `T`, without copying it.
+    ///
+    /// # Panics
+    ///
+    /// - If the length of the data is not a multiple of the size of `T`.
+    /// - If the data is not sufficiently aligned for `T`. Mappings are aligned to at
+    ///   least [`MAP_ALIGNMENT`](crate::MAP_ALIGNMENT) bytes, so this can only happen
+    ///   for types with a larger alignment.
+    ///
+    /// See [`DownloadBuffer::try_as_slice_of`] for a non-panicking version.
+    pub fn as_slice_of<T: bytemuck::Pod>(&self) -> &[T] {
+        bytemuck::cast_slice(self)
+    }
+
+    /// Interpret the downloaded data as a slice of `T`, without copying it.
+    ///
+    /// Returns an error if the length of the data is not a multiple of the size of `T`,
+    /// or if the data is not sufficiently aligned for `T`.
+    pub fn try_as_slice_of<T: bytemuck::Pod>(&self) -> Result<&[T], bytemuck::PodCastError> {
+        bytemuck::try_cast_slice(self)
     }
 }
 
@@ -209,6 +323,20 @@ pub fn pipeline_cache_key(adapter_info: &wgt::AdapterInfo) -> Option<String> {
             "wgpu_pipeline_cache_vulkan_{}_{}",
             adapter_info.vendor, adapter_info.device
         )),
+        wgt::Backend::Dx12 => Some(format!(
+            "wgpu_pipeline_cache_dx12_{}_{}",
+            adapter_info.vendor, adapter_info.device
+        )),
+        // Metal doesn't report vendor or device IDs, so use the device name,
+        // restricted to characters which are safe to use in a filename.
+        wgt::Backend::Metal => Some(format!(
+            "wgpu_pipeline_cache_metal_{}",
+            adapter_info
+                .name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>()
+        )),
         _ => None,
     }
 }
@@ -0,0 +1,308 @@
+use crate::{
+    ColorTargetState, CompareFunction, ComputePipeline, ComputePipelineDescriptor,
+    DepthStencilState, Device, Face, FragmentState, MultisampleState, PipelineCache,
+    PipelineCompilationOptions, PipelineLayout, PrimitiveState, PrimitiveTopology, RenderPipeline,
+    RenderPipelineDescriptor, ShaderModule, TextureFormat, VertexBufferLayout, VertexState,
+};
+use alloc::vec::Vec;
+use core::num::NonZeroU32;
+
+/// Fluent builder for [`RenderPipelineDescriptor`]s.
+///
+/// Every part of the pipeline which isn't set explicitly has its default value: the shader
+/// modules' only entry points, no vertex buffers, a triangle list without culling, no
+/// depth-stencil state and a single sample. Once everything has been set, create the pipeline
+/// with [`RenderPipelineBuilder::build()`].
+///
+/// ```no_run
+/// # let device: wgpu::Device = todo!();
+/// # let layout: wgpu::PipelineLayout = todo!();
+/// # let shader: wgpu::ShaderModule = todo!();
+/// # let vertex_layout: wgpu::VertexBufferLayout<'static> = todo!();
+/// let pipeline = wgpu::util::RenderPipelineBuilder::new(Some(&layout), &shader, &shader)
+///     .label("mesh")
+///     .vertex_buffer(vertex_layout)
+///     .color_target(wgpu::TextureFormat::Bgra8UnormSrgb)
+///     .depth(wgpu::TextureFormat::Depth32Float, wgpu::CompareFunction::Less)
+///     .build(&device);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RenderPipelineBuilder<'a> {
+    label: Option<&'a str>,
+    layout: Option<&'a PipelineLayout>,
+    vertex: &'a ShaderModule,
+    vertex_entry_point: Option<&'a str>,
+    vertex_compilation_options: PipelineCompilationOptions<'a>,
+    vertex_buffers: Vec<VertexBufferLayout<'a>>,
+    primitive: PrimitiveState,
+    depth_stencil: Option<DepthStencilState>,
+    multisample: MultisampleState,
+    fragment: Option<&'a ShaderModule>,
+    fragment_entry_point: Option<&'a str>,
+    fragment_compilation_options: PipelineCompilationOptions<'a>,
+    targets: Vec<Option<ColorTargetState>>,
+    multiview: Option<NonZeroU32>,
+    cache: Option<&'a PipelineCache>,
+}
+
+impl<'a> RenderPipelineBuilder<'a> {
+    /// Start building a pipeline with the given vertex and fragment shaders.
+    ///
+    /// If `layout` is `None`, the pipeline has a default layout deduced from the shaders,
+    /// see [`RenderPipelineDescriptor::layout`].
+    pub fn new(
+        layout: Option<&'a PipelineLayout>,
+        vertex: &'a ShaderModule,
+        fragment: &'a ShaderModule,
+    ) -> Self {
+        Self {
+            fragment: Some(fragment),
+            ..Self::new_vertex_only(layout, vertex)
+        }
+    }
+
+    /// Start building a pipeline without a fragment shader, such as one which only writes
+    /// depth for shadow maps.
+    pub fn new_vertex_only(layout: Option<&'a PipelineLayout>, vertex: &'a ShaderModule) -> Self {
+        Self {
+            label: None,
+            layout,
+            vertex,
+            vertex_entry_point: None,
+            vertex_compilation_options: PipelineCompilationOptions::default(),
+            vertex_buffers: Vec::new(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: None,
+            fragment_entry_point: None,
+            fragment_compilation_options: PipelineCompilationOptions::default(),
+            targets: Vec::new(),
+            multiview: None,
+            cache: None,
+        }
+    }
+
+    /// Set the debug label of the pipeline.
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Set the name of the vertex shader entry point.
+    pub fn vertex_entry_point(mut self, entry_point: &'a str) -> Self {
+        self.vertex_entry_point = Some(entry_point);
+        self
+    }
+
+    /// Set the compilation options of the vertex shader.
+    pub fn vertex_compilation_options(mut self, options: PipelineCompilationOptions<'a>) -> Self {
+        self.vertex_compilation_options = options;
+        self
+    }
+
+    /// Add a vertex buffer, in the next slot.
+    pub fn vertex_buffer(mut self, layout: VertexBufferLayout<'a>) -> Self {
+        self.vertex_buffers.push(layout);
+        self
+    }
+
+    /// Set the name of the fragment shader entry point.
+    ///
+    /// This has no effect on pipelines without a fragment shader.
+    pub fn fragment_entry_point(mut self, entry_point: &'a str) -> Self {
+        self.fragment_entry_point = Some(entry_point);
+        self
+    }
+
+    /// Set the compilation options of the fragment shader.
+    ///
+    /// This has no effect on pipelines without a fragment shader.
+    pub fn fragment_compilation_options(mut self, options: PipelineCompilationOptions<'a>) -> Self {
+        self.fragment_compilation_options = options;
+        self
+    }
+
+    /// Add a color target, in the next slot.
+    ///
+    /// A [`TextureFormat`] adds a target of that format without blending, which writes
+    /// all channels.
+    pub fn color_target(mut self, target: impl Into<ColorTargetState>) -> Self {
+        self.targets.push(Some(target.into()));
+        self
+    }
+
+    /// Skip the next color target slot, which the fragment shader doesn't write.
+    pub fn empty_color_target(mut self) -> Self {
+        self.targets.push(None);
+        self
+    }
+
+    /// Set the primitive state, replacing the topology and cull mode set so far.
+    pub fn primitive(mut self, primitive: PrimitiveState) -> Self {
+        self.primitive = primitive;
+        self
+    }
+
+    /// Set the primitive topology.
+    pub fn topology(mut self, topology: PrimitiveTopology) -> Self {
+        self.primitive.topology = topology;
+        self
+    }
+
+    /// Cull the primitives facing the given way.
+    pub fn cull_mode(mut self, face: Face) -> Self {
+        self.primitive.cull_mode = Some(face);
+        self
+    }
+
+    /// Test and write depth in a depth attachment of the given format, without stencil or
+    /// depth bias.
+    pub fn depth(self, format: TextureFormat, compare: CompareFunction) -> Self {
+        self.depth_stencil(DepthStencilState {
+            format,
+            depth_write_enabled: true,
+            depth_compare: compare,
+            stencil: Default::default(),
+            bias: Default::default(),
+        })
+    }
+
+    /// Set the depth-stencil state.
+    pub fn depth_stencil(mut self, depth_stencil: DepthStencilState) -> Self {
+        self.depth_stencil = Some(depth_stencil);
+        self
+    }
+
+    /// Set the number of samples of the attachments.
+    pub fn sample_count(mut self, count: u32) -> Self {
+        self.multisample.count = count;
+        self
+    }
+
+    /// Set the multisample state, replacing the sample count set so far.
+    pub fn multisample(mut self, multisample: MultisampleState) -> Self {
+        self.multisample = multisample;
+        self
+    }
+
+    /// Render to `views` array layers at once, in a multiview render pass.
+    pub fn multiview(mut self, views: NonZeroU32) -> Self {
+        self.multiview = Some(views);
+        self
+    }
+
+    /// Use `cache` when creating the pipeline.
+    pub fn cache(mut self, cache: &'a PipelineCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The descriptor for the pipeline being built.
+    pub fn descriptor(&self) -> RenderPipelineDescriptor<'_> {
+        RenderPipelineDescriptor {
+            label: self.label,
+            layout: self.layout,
+            vertex: VertexState {
+                module: self.vertex,
+                entry_point: self.vertex_entry_point,
+                compilation_options: self.vertex_compilation_options.clone(),
+                buffers: &self.vertex_buffers,
+            },
+            primitive: self.primitive,
+            depth_stencil: self.depth_stencil.clone(),
+            multisample: self.multisample,
+            fragment: self.fragment.map(|module| FragmentState {
+                module,
+                entry_point: self.fragment_entry_point,
+                compilation_options: self.fragment_compilation_options.clone(),
+                targets: &self.targets,
+            }),
+            multiview: self.multiview,
+            cache: self.cache,
+        }
+    }
+
+    /// Create the pipeline.
+    pub fn build(&self, device: &Device) -> RenderPipeline {
+        device.create_render_pipeline(&self.descriptor())
+    }
+}
+
+/// Fluent builder for [`ComputePipelineDescriptor`]s.
+///
+/// ```no_run
+/// # let device: wgpu::Device = todo!();
+/// # let shader: wgpu::ShaderModule = todo!();
+/// let pipeline = wgpu::util::ComputePipelineBuilder::new(None, &shader)
+///     .label("cull")
+///     .entry_point("cull_instances")
+///     .build(&device);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ComputePipelineBuilder<'a> {
+    label: Option<&'a str>,
+    layout: Option<&'a PipelineLayout>,
+    module: &'a ShaderModule,
+    entry_point: Option<&'a str>,
+    compilation_options: PipelineCompilationOptions<'a>,
+    cache: Option<&'a PipelineCache>,
+}
+
+impl<'a> ComputePipelineBuilder<'a> {
+    /// Start building a pipeline with the given compute shader.
+    ///
+    /// If `layout` is `None`, the pipeline has a default layout deduced from the shader,
+    /// see [`ComputePipelineDescriptor::layout`].
+    pub fn new(layout: Option<&'a PipelineLayout>, module: &'a ShaderModule) -> Self {
+        Self {
+            label: None,
+            layout,
+            module,
+            entry_point: None,
+            compilation_options: PipelineCompilationOptions::default(),
+            cache: None,
+        }
+    }
+
+    /// Set the debug label of the pipeline.
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Set the name of the compute shader entry point.
+    pub fn entry_point(mut self, entry_point: &'a str) -> Self {
+        self.entry_point = Some(entry_point);
+        self
+    }
+
+    /// Set the compilation options of the compute shader.
+    pub fn compilation_options(mut self, options: PipelineCompilationOptions<'a>) -> Self {
+        self.compilation_options = options;
+        self
+    }
+
+    /// Use `cache` when creating the pipeline.
+    pub fn cache(mut self, cache: &'a PipelineCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The descriptor for the pipeline being built.
+    pub fn descriptor(&self) -> ComputePipelineDescriptor<'_> {
+        ComputePipelineDescriptor {
+            label: self.label,
+            layout: self.layout,
+            module: self.module,
+            entry_point: self.entry_point,
+            compilation_options: self.compilation_options.clone(),
+            cache: self.cache,
+        }
+    }
+
+    /// Create the pipeline.
+    pub fn build(&self, device: &Device) -> ComputePipeline {
+        device.create_compute_pipeline(&self.descriptor())
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    Buffer, BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder, ComputePass,
+    ComputePassTimestampWrites, Device, Features, MapMode, QUERY_SET_MAX_QUERIES, QUERY_SIZE,
+    QuerySet, QuerySetDescriptor, QueryType, Queue, RenderPass, RenderPassTimestampWrites,
+    util::Mutex,
+};
+use alloc::{collections::VecDeque, string::String, sync::Arc, vec::Vec};
+use core::{fmt, time::Duration};
+
+/// Methods shared by [`CommandEncoder`], [`RenderPass`] and [`ComputePass`] for
+/// writing timestamps, used by [`GpuProfiler`] scopes.
+pub trait TimestampEncoder {
+    /// Issue a timestamp command at this point in the queue. The timestamp will be
+    /// written to the specified query set, at the specified index.
+    fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32);
+}
+
+impl TimestampEncoder for CommandEncoder {
+    #[inline(always)]
+    fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32) {
+        Self::write_timestamp(self, query_set, query_index);
+    }
+}
+
+impl TimestampEncoder for RenderPass<'_> {
+    #[inline(always)]
+    fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32) {
+        Self::write_timestamp(self, query_set, query_index);
+    }
+}
+
+impl TimestampEncoder for ComputePass<'_> {
+    #[inline(always)]
+    fn write_timestamp(&mut self, query_set: &QuerySet, query_index: u32) {
+        Self::write_timestamp(self, query_set, query_index);
+    }
+}
+
+/// The GPU time taken by one scope of a frame recorded by a [`GpuProfiler`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GpuProfilerScope {
+    /// The label the scope was opened with.
+    pub label: String,
+    /// The number of scopes this scope is nested in.
+    pub depth: usize,
+    /// The time elapsed on the GPU between the start and the end of the scope.
+    pub duration: Duration,
+}
+
+/// Measures how long labeled scopes of GPU work take, using timestamp queries.
+///
+/// Using a profiler generally goes as follows, once per frame:
+/// 1. Record scopes, either with [`GpuProfiler::begin_scope()`] and
+///    [`GpuProfiler::end_scope()`] around commands in a command encoder or pass, or
+///    around whole passes with [`GpuProfiler::render_pass_timestamp_writes()`] and
+///    [`GpuProfiler::compute_pass_timestamp_writes()`]. Scopes may be nested.
+/// 2. Call [`GpuProfiler::resolve_queries()`] on the last command encoder of the frame.
+/// 3. Submit all command encoders that were used in steps 1 and 2.
+/// 4. Call [`GpuProfiler::end_frame()`].
+/// 5. Call [`GpuProfiler::process_finished_frame()`] to retrieve the results of
+///    earlier frames, once the GPU is done with them and the device has been polled.
+///
+/// Frames that haven't been processed yet each hold on to a readback buffer, so
+/// results should be retrieved regularly.
+///
+/// The device must have [`Features::TIMESTAMP_QUERY`] enabled. Scopes opened on command
+/// encoders additionally require [`Features::TIMESTAMP_QUERY_INSIDE_ENCODERS`], and
+/// scopes opened inside passes require [`Features::TIMESTAMP_QUERY_INSIDE_PASSES`].
+pub struct GpuProfiler {
+    device: Device,
+    query_set: QuerySet,
+    resolve_buffer: Buffer,
+    max_scopes_per_frame: u32,
+    /// Nanoseconds per timestamp tick.
+    timestamp_period: f32,
+    /// Scopes of the current frame, in the order they were opened.
+    scopes: Vec<PendingScope>,
+    /// Indices into `scopes` of the scopes that are still open, innermost last.
+    /// `None` for scopes that were dropped because the frame ran out of queries.
+    open_scopes: Vec<Option<usize>>,
+    /// Whether the queries of the current frame have been resolved.
+    resolved: bool,
+    /// The buffer the current frame's timestamps were copied into by
+    /// [`GpuProfiler::resolve_queries()`], if it had any scopes.
+    readback: Option<Buffer>,
+    /// Frames that have been submitted but not processed yet, oldest first.
+    pending_frames: VecDeque<PendingFrame>,
+    /// Readback buffers no longer in use by a frame.
+    free_readback_buffers: Vec<Buffer>,
+}
+
+impl GpuProfiler {
+    /// Create a new profiler, recording up to `max_scopes_per_frame` scopes each frame.
+    ///
+    /// Scopes opened once that many have been recorded in a frame are ignored.
+    ///
+    /// # Panics
+    ///
+    /// - If the device doesn't have [`Features::TIMESTAMP_QUERY`] enabled.
+    /// - If `max_scopes_per_frame` is zero, or needs more than [`QUERY_SET_MAX_QUERIES`]
+    ///   queries (two per scope).
+    pub fn new(device: &Device, queue: &Queue, max_scopes_per_frame: u32) -> Self {
+        assert!(
+            device.features().contains(Features::TIMESTAMP_QUERY),
+            "GpuProfiler requires Features::TIMESTAMP_QUERY"
+        );
+        assert!(
+            max_scopes_per_frame > 0 && max_scopes_per_frame <= QUERY_SET_MAX_QUERIES / 2,
+            "max_scopes_per_frame must be between 1 and {}",
+            QUERY_SET_MAX_QUERIES / 2
+        );
+
+        let query_count = max_scopes_per_frame * 2;
+        let query_set = device.create_query_set(&QuerySetDescriptor {
+            label: Some("(wgpu internal) GpuProfiler"),
+            ty: QueryType::Timestamp,
+            count: query_count,
+        });
+        let resolve_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("(wgpu internal) GpuProfiler resolve buffer"),
+            size: u64::from(query_count) * QUERY_SIZE as u64,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            device: device.clone(),
+            query_set,
+            resolve_buffer,
+            max_scopes_per_frame,
+            timestamp_period: queue.get_timestamp_period(),
+            scopes: Vec::new(),
+            open_scopes: Vec::new(),
+            resolved: false,
+            readback: None,
+            pending_frames: VecDeque::new(),
+            free_readback_buffers: Vec::new(),
+        }
+    }
+
+    /// Open a scope, writing its start timestamp into `encoder`.
+    ///
+    /// The scope must be closed with [`GpuProfiler::end_scope()`] before the queries
+    /// are resolved, and both timestamps must be written in the same frame.
+    pub fn begin_scope(&mut self, label: impl Into<String>, encoder: &mut impl TimestampEncoder) {
+        let scope = self.push_scope(label.into());
+        if let Some(index) = scope {
+            encoder.write_timestamp(&self.query_set, self.scopes[index].begin_query);
+        }
+        self.open_scopes.push(scope);
+    }
+
+    /// Close the innermost open scope, writing its end timestamp into `encoder`.
+    ///
+    /// # Panics
+    ///
+    /// - If there is no open scope.
+    pub fn end_scope(&mut self, encoder: &mut impl TimestampEncoder) {
+        let scope = self
+            .open_scopes
+            .pop()
+            .expect("GpuProfiler::end_scope called without an open scope");
+        if let Some(index) = scope {
+            encoder.write_timestamp(&self.query_set, self.scopes[index].begin_query + 1);
+        }
+    }
+
+    /// Record a scope covering a whole render pass.
+    ///
+    /// Pass the returned value as [`RenderPassDescriptor::timestamp_writes`]. Returns
+    /// `None` if the frame has run out of scopes.
+    ///
+    /// The scope is closed as soon as it is recorded, so scopes opened inside the
+    /// pass are not counted as nested in it.
+    ///
+    /// [`RenderPassDescriptor::timestamp_writes`]: crate::RenderPassDescriptor::timestamp_writes
+    pub fn render_pass_timestamp_writes(
+        &mut self,
+        label: impl Into<String>,
+    ) -> Option<RenderPassTimestampWrites<'_>> {
+        let index = self.push_scope(label.into())?;
+        let begin_query = self.scopes[index].begin_query;
+        Some(RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(begin_query),
+            end_of_pass_write_index: Some(begin_query + 1),
+        })
+    }
+
+    /// Record a scope covering a whole compute pass.
+    ///
+    /// Pass the returned value as [`ComputePassDescriptor::timestamp_writes`]. Returns
+    /// `None` if the frame has run out of scopes.
+    ///
+    /// The scope is closed as soon as it is recorded, so scopes opened inside the
+    /// pass are not counted as nested in it.
+    ///
+    /// [`ComputePassDescriptor::timestamp_writes`]: crate::ComputePassDescriptor::timestamp_writes
+    pub fn compute_pass_timestamp_writes(
+        &mut self,
+        label: impl Into<String>,
+    ) -> Option<ComputePassTimestampWrites<'_>> {
+        let index = self.push_scope(label.into())?;
+        let begin_query = self.scopes[index].begin_query;
+        Some(ComputePassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(begin_query),
+            end_of_pass_write_index: Some(begin_query + 1),
+        })
+    }
+
+    /// Copy the timestamps written this frame into a buffer which can be read back.
+    ///
+    /// `encoder` must be submitted after every command encoder used to record the
+    /// frame's scopes, and before [`GpuProfiler::end_frame()`] is called.
+    ///
+    /// # Panics
+    ///
+    /// - If a scope is still open.
+    pub fn resolve_queries(&mut self, encoder: &mut CommandEncoder) {
+        assert!(
+            self.open_scopes.is_empty(),
+            "all GpuProfiler scopes must be closed before resolving queries"
+        );
+        assert!(
+            !self.resolved,
+            "queries have already been resolved this frame"
+        );
+        self.resolved = true;
+
+        let query_count = self.scopes.len() as u32 * 2;
+        if query_count == 0 {
+            return;
+        }
+
+        let readback = self.free_readback_buffers.pop().unwrap_or_else(|| {
+            self.device.create_buffer(&BufferDescriptor {
+                label: Some("(wgpu internal) GpuProfiler readback buffer"),
+                size: self.resolve_buffer.size(),
+                usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            })
+        });
+        encoder.resolve_query_set(&self.query_set, 0..query_count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &readback,
+            0,
+            u64::from(query_count) * QUERY_SIZE as u64,
+        );
+        self.readback = Some(readback);
+    }
+
+    /// Finish the current frame and start recording the next one.
+    ///
+    /// This must be called after the command encoder passed to
+    /// [`GpuProfiler::resolve_queries()`] has been submitted.
+    ///
+    /// # Panics
+    ///
+    /// - If scopes were recorded this frame, but their queries weren't resolved.
+    pub fn end_frame(&mut self) {
+        assert!(
+            self.resolved || self.scopes.is_empty(),
+            "GpuProfiler::resolve_queries must be called before ending a frame with scopes"
+        );
+        assert!(
+            self.open_scopes.is_empty(),
+            "all GpuProfiler scopes must be closed before ending a frame"
+        );
+        self.resolved = false;
+
+        let map_result = Arc::new(Mutex::new(None));
+        let readback = self.readback.take();
+        if let Some(ref readback) = readback {
+            let map_result = Arc::clone(&map_result);
+            readback.slice(..).map_async(MapMode::Read, move |result| {
+                *map_result.lock() = Some(result);
+            });
+        }
+        self.pending_frames.push_back(PendingFrame {
+            scopes: core::mem::take(&mut self.scopes),
+            readback,
+            map_result,
+        });
+    }
+
+    /// Return the results of the oldest frame which the GPU has finished, if any.
+    ///
+    /// Frames are returned in the order they were recorded, each one exactly once. As
+    /// with [`Buffer::map_async()`], results only become available once the device has
+    /// been polled after the frame completed on the GPU.
+    pub fn process_finished_frame(&mut self) -> Option<Vec<GpuProfilerScope>> {
+        loop {
+            let frame = self.pending_frames.front()?;
+            let map_result = match frame.readback {
+                Some(_) => frame.map_result.lock().take()?,
+                None => Ok(()),
+            };
+            let frame = self.pending_frames.pop_front().unwrap();
+            // If the readback buffer couldn't be mapped, the frame's results are lost.
+            if map_result.is_ok() {
+                return Some(self.read_frame(frame));
+            }
+        }
+    }
+
+    /// Compute the durations of the scopes of a frame whose readback buffer is mapped.
+    fn read_frame(&mut self, frame: PendingFrame) -> Vec<GpuProfilerScope> {
+        let Some(readback) = frame.readback else {
+            return Vec::new();
+        };
+
+        let scopes = {
+            let view = readback.slice(..).get_mapped_range();
+            let timestamps: &[u64] = bytemuck::cast_slice(&view);
+            frame
+                .scopes
+                .into_iter()
+                .map(|scope| {
+                    let begin = timestamps[scope.begin_query as usize];
+                    let end = timestamps[scope.begin_query as usize + 1];
+                    let nanoseconds =
+                        end.saturating_sub(begin) as f64 * self.timestamp_period as f64;
+                    GpuProfilerScope {
+                        label: scope.label,
+                        depth: scope.depth,
+                        duration: Duration::from_nanos(nanoseconds as u64),
+                    }
+                })
+                .collect()
+        };
+        readback.unmap();
+        self.free_readback_buffers.push(readback);
+        scopes
+    }
+
+    /// Record a new scope in the current frame, returning its index in `self.scopes`,
+    /// or `None` if the frame is out of queries.
+    fn push_scope(&mut self, label: String) -> Option<usize> {
+        assert!(
+            !self.resolved,
+            "GpuProfiler scopes can't be recorded after resolving queries; call end_frame first"
+        );
+        if self.scopes.len() as u32 == self.max_scopes_per_frame {
+            return None;
+        }
+        self.scopes.push(PendingScope {
+            label,
+            depth: self.open_scopes.len(),
+            begin_query: self.scopes.len() as u32 * 2,
+        });
+        Some(self.scopes.len() - 1)
+    }
+}
+
+impl fmt::Debug for GpuProfiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GpuProfiler")
+            .field("max_scopes_per_frame", &self.max_scopes_per_frame)
+            .field("scopes", &self.scopes.len())
+            .field("pending_frames", &self.pending_frames.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A scope recorded in a frame whose results haven't been read yet.
+struct PendingScope {
+    label: String,
+    depth: usize,
+    /// The query the start timestamp is written to; the end timestamp follows it.
+    begin_query: u32,
+}
+
+/// A frame submitted to the GPU whose results haven't been read yet.
+struct PendingFrame {
+    scopes: Vec<PendingScope>,
+    /// The buffer the timestamps were copied into, or `None` if the frame had no scopes.
+    readback: Option<Buffer>,
+    /// Set by the map callback of `readback`.
+    map_result: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+}
@@ -0,0 +1,288 @@
+use crate::{
+    BufferAsyncError, Device, Extent3d, LoadOp, Operations, Origin3d, Queue,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPassFlags, StoreOp, SurfaceTexture,
+    Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages, WasmNotSend, util,
+};
+use alloc::vec::Vec;
+use core::future::Future;
+
+/// An image read back by [`capture_surface_texture`] or [`capture_texture`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RgbaImageData {
+    /// Width of the image in pixels.
+    pub width: u32,
+    /// Height of the image in pixels.
+    pub height: u32,
+    /// Tightly packed 8-bit RGBA pixels, row by row starting at the top, encoded in sRGB
+    /// as they would be displayed. Alpha is not premultiplied by this function, and is
+    /// passed through as stored in the texture.
+    pub data: Vec<u8>,
+}
+
+/// Asynchronously read the frame in `surface_texture` back to the CPU.
+///
+/// This is a shorthand for [`capture_texture`] with the surface texture's
+/// [`texture`](SurfaceTexture::texture). It must be called before the frame is
+/// presented, and the surface must have been configured with
+/// [`TextureUsages::COPY_SRC`] in its usage.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`capture_texture`].
+pub fn capture_surface_texture(
+    device: &Device,
+    queue: &Queue,
+    surface_texture: &SurfaceTexture,
+) -> impl Future<Output = Result<RgbaImageData, BufferAsyncError>> + WasmNotSend + use<> {
+    capture_texture(device, queue, &surface_texture.texture)
+}
+
+/// Asynchronously read mip level 0 of a 2D texture back to the CPU as 8-bit RGBA.
+///
+/// Multisampled textures are resolved first. The data is converted from the texture's
+/// format to sRGB-encoded RGBA: BGRA is swizzled, 10-bit channels are rounded, and
+/// [`TextureFormat::Rgba16Float`] is treated as linear (extended) sRGB, clamped to
+/// `[0, 1]` and encoded.
+///
+/// The texture must have [`TextureUsages::COPY_SRC`] usage, or if it is multisampled,
+/// [`TextureUsages::RENDER_ATTACHMENT`] usage. As with
+/// [`Buffer::map_async()`](crate::Buffer::map_async), the future only resolves once the
+/// device has been polled.
+///
+/// # Panics
+///
+/// - If the texture's format is not one of [`TextureFormat::Rgba8Unorm`],
+///   [`TextureFormat::Rgba8UnormSrgb`], [`TextureFormat::Bgra8Unorm`],
+///   [`TextureFormat::Bgra8UnormSrgb`], [`TextureFormat::Rgb10a2Unorm`] or
+///   [`TextureFormat::Rgba16Float`].
+pub fn capture_texture(
+    device: &Device,
+    queue: &Queue,
+    texture: &Texture,
+) -> impl Future<Output = Result<RgbaImageData, BufferAsyncError>> + WasmNotSend + use<> {
+    let format = texture.format();
+    assert!(
+        matches!(
+            format,
+            TextureFormat::Rgba8Unorm
+                | TextureFormat::Rgba8UnormSrgb
+                | TextureFormat::Bgra8Unorm
+                | TextureFormat::Bgra8UnormSrgb
+                | TextureFormat::Rgb10a2Unorm
+                | TextureFormat::Rgba16Float
+        ),
+        "capturing textures of format {format:?} is not supported"
+    );
+
+    let width = texture.width();
+    let height = texture.height();
+    let resolved;
+    let source = if texture.sample_count() > 1 {
+        resolved = resolve(device, queue, texture);
+        &resolved
+    } else {
+        texture
+    };
+
+    let future = util::CallbackFuture::new();
+    let sender = future.sender();
+    util::read_texture_to_vec(
+        device,
+        queue,
+        source,
+        0,
+        Origin3d::ZERO,
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        move |result| {
+            sender.send(result.map(|data| RgbaImageData {
+                width,
+                height,
+                data: to_rgba8(format, &data),
+            }))
+        },
+    );
+    future
+}
+
+/// Resolves a multisampled texture into a new single-sampled one.
+fn resolve(device: &Device, queue: &Queue, texture: &Texture) -> Texture {
+    let resolved = device.create_texture(&TextureDescriptor {
+        label: Some("(wgpu internal) capture_texture resolve"),
+        size: Extent3d {
+            width: texture.width(),
+            height: texture.height(),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: texture.format(),
+        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    let view = texture.create_view(&Default::default());
+    let resolve_view = resolved.create_view(&Default::default());
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.begin_render_pass(&RenderPassDescriptor {
+        label: Some("(wgpu internal) capture_texture resolve"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &view,
+            depth_slice: None,
+            resolve_target: Some(&resolve_view),
+            ops: Operations {
+                load: LoadOp::Load,
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        pipeline_statistics_query: None,
+        flags: RenderPassFlags::empty(),
+        multiview: None,
+    });
+    queue.submit(Some(encoder.finish()));
+
+    resolved
+}
+
+/// Converts tightly packed texels of `format` to 8-bit sRGB-encoded RGBA.
+fn to_rgba8(format: TextureFormat, data: &[u8]) -> Vec<u8> {
+    match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => data.to_vec(),
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => data
+            .chunks_exact(4)
+            .flat_map(|bgra| [bgra[2], bgra[1], bgra[0], bgra[3]])
+            .collect(),
+        TextureFormat::Rgb10a2Unorm => data
+            .chunks_exact(4)
+            .flat_map(|texel| {
+                let packed = u32::from_le_bytes([texel[0], texel[1], texel[2], texel[3]]);
+                let channel = |shift: u32| (((packed >> shift) & 0x3ff) * 255 + 511) / 1023;
+                [
+                    channel(0) as u8,
+                    channel(10) as u8,
+                    channel(20) as u8,
+                    ((packed >> 30) * 85) as u8,
+                ]
+            })
+            .collect(),
+        TextureFormat::Rgba16Float => data
+            .chunks_exact(8)
+            .flat_map(|texel| {
+                let channel = |i: usize| f16_to_f32(u16::from_le_bytes([texel[i], texel[i + 1]]));
+                [
+                    linear_to_srgb8(channel(0)),
+                    linear_to_srgb8(channel(2)),
+                    linear_to_srgb8(channel(4)),
+                    unorm8(channel(6)),
+                ]
+            })
+            .collect(),
+        _ => unreachable!(),
+    }
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits >> 15) << 31;
+    let exponent = u32::from((bits >> 10) & 0x1f);
+    let mantissa = u32::from(bits & 0x3ff);
+    let magnitude = match exponent {
+        // Zero and subnormals.
+        0 => {
+            let value = mantissa as f32 / 16777216.0;
+            return if sign != 0 { -value } else { value };
+        }
+        // Infinity and NaN.
+        0x1f => 0xff << 23 | mantissa << 13,
+        _ => (exponent + 127 - 15) << 23 | mantissa << 13,
+    };
+    f32::from_bits(sign | magnitude)
+}
+
+fn unorm8(value: f32) -> u8 {
+    // `as` saturates, and maps NaN to 0.
+    (value * 255.0 + 0.5) as u8
+}
+
+/// Encodes a linear value with the sRGB transfer function.
+fn linear_to_srgb8(value: f32) -> u8 {
+    let value = f64::from(value);
+    if value.is_nan() || value <= 0.0031308 {
+        return unorm8((value * 12.92) as f32);
+    }
+    if value >= 1.0 {
+        return 255;
+    }
+
+    // Find the largest code whose lower rounding boundary decodes to at most `value`.
+    // Decoding is `((c + 0.055) / 1.055)^2.4`; comparing the 5th powers avoids a
+    // fractional power, which isn't available without `std`.
+    let value_5 = pow(value, 5);
+    let decodes_to_at_most = |code: u32| {
+        let boundary = (f64::from(code) - 0.5) / 255.0;
+        pow((boundary + 0.055) / 1.055, 12) <= value_5
+    };
+    let (mut low, mut high) = (0u32, 255);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if decodes_to_at_most(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low as u8
+}
+
+fn pow(base: f64, exponent: u32) -> f64 {
+    (0..exponent).fold(1.0, |acc, _| acc * base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bgra_is_swizzled() {
+        assert_eq!(
+            to_rgba8(TextureFormat::Bgra8UnormSrgb, &[1, 2, 3, 4, 5, 6, 7, 8]),
+            [3, 2, 1, 4, 7, 6, 5, 8]
+        );
+    }
+
+    #[test]
+    fn rgb10a2_is_rounded() {
+        let packed: u32 = 1023 | 512 << 10 | 3 << 30;
+        assert_eq!(
+            to_rgba8(TextureFormat::Rgb10a2Unorm, &packed.to_le_bytes()),
+            [255, 128, 0, 255]
+        );
+    }
+
+    #[test]
+    fn rgba16float_is_srgb_encoded() {
+        // 0.0, 0.5, 1.0 and 2.0 as half floats.
+        let halves: [u16; 4] = [0x0000, 0x3800, 0x3c00, 0x4000];
+        let data: Vec<u8> = halves.iter().flat_map(|h| h.to_le_bytes()).collect();
+        // Linear 0.5 is sRGB 0.7354, or 187.5; values above 1 are clamped.
+        assert_eq!(
+            to_rgba8(TextureFormat::Rgba16Float, &data),
+            [0, 188, 255, 255]
+        );
+    }
+
+    #[test]
+    fn f16_conversion() {
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x0001), 1.0 / 16777216.0);
+        assert_eq!(f16_to_f32(0x8001), -1.0 / 16777216.0);
+        assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+    }
+}
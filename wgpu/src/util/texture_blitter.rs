@@ -7,9 +7,10 @@ use crate::{
     BindGroupLayoutEntry, BindingType, ColorTargetState, ColorWrites, CommandEncoder, Device,
     FilterMode, FragmentState, FrontFace, LoadOp, MultisampleState, PipelineCompilationOptions,
     PipelineLayoutDescriptor, PrimitiveState, PrimitiveTopology, RenderPassDescriptor,
-    RenderPipeline, RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor,
-    ShaderStages, StoreOp, TextureFormat, TextureSampleType, TextureView, TextureViewDimension,
-    VertexState, include_wgsl,
+    RenderPassFlags, RenderPipeline, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+    SamplerDescriptor, ShaderStages, StoreOp, Texture, TextureDimension, TextureFormat,
+    TextureSampleType, TextureView, TextureViewDescriptor, TextureViewDimension, VertexState,
+    include_wgsl,
 };
 
 /// A builder for the [`TextureBlitter`] utility.
@@ -59,6 +60,18 @@ impl<'a> TextureBlitterBuilder<'a> {
             ..Default::default()
         });
 
+        // Each mip level is a 2:1 minification of the one before it, so the mip chain is
+        // generated with a sampler that also applies the filter when minifying.
+        let mip_sampler = self.device.create_sampler(&SamplerDescriptor {
+            label: Some("wgpu::util::TextureBlitter::mip_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: self.sample_type,
+            min_filter: self.sample_type,
+            ..Default::default()
+        });
+
         let bind_group_layout = self
             .device
             .create_bind_group_layout(&BindGroupLayoutDescriptor {
@@ -112,11 +125,14 @@ impl<'a> TextureBlitterBuilder<'a> {
                 primitive: PrimitiveState {
                     topology: PrimitiveTopology::TriangleList,
                     strip_index_format: None,
+                    primitive_restart: true,
                     front_face: FrontFace::Ccw,
                     cull_mode: None,
                     unclipped_depth: false,
                     polygon_mode: wgt::PolygonMode::Fill,
                     conservative: false,
+                    line_width: 1.0,
+                    line_stipple: None,
                 },
                 depth_stencil: None,
                 multisample: MultisampleState::default(),
@@ -128,6 +144,7 @@ impl<'a> TextureBlitterBuilder<'a> {
                         format: self.format,
                         blend: self.blend_state,
                         write_mask: ColorWrites::ALL,
+                        logic_op: None,
                     })],
                 }),
                 multiview: None,
@@ -138,6 +155,8 @@ impl<'a> TextureBlitterBuilder<'a> {
             pipeline,
             bind_group_layout,
             sampler,
+            mip_sampler,
+            format: self.format,
         }
     }
 }
@@ -148,10 +167,16 @@ impl<'a> TextureBlitterBuilder<'a> {
 /// - Textures are in incompatible formats.
 /// - Textures are of different sizes.
 /// - Your copy destination is the surface texture and does not have the `COPY_DST` usage.
+///
+/// Individual array layers (including cube faces) can be copied with [`TextureBlitter::copy_layer`]
+/// and [`TextureBlitter::copy_layers`], and the mip chain of a texture can be filled in with
+/// [`TextureBlitter::generate_mipmaps`].
 pub struct TextureBlitter {
     pipeline: RenderPipeline,
     bind_group_layout: BindGroupLayout,
     sampler: Sampler,
+    mip_sampler: Sampler,
+    format: TextureFormat,
 }
 
 impl TextureBlitter {
@@ -179,6 +204,17 @@ impl TextureBlitter {
         encoder: &mut CommandEncoder,
         source: &TextureView,
         target: &TextureView,
+    ) {
+        self.blit(device, encoder, source, target, &self.sampler);
+    }
+
+    fn blit(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        source: &TextureView,
+        target: &TextureView,
+        sampler: &Sampler,
     ) {
         let bind_group = device.create_bind_group(&BindGroupDescriptor {
             label: Some("wgpu::util::TextureBlitter::bind_group"),
@@ -190,7 +226,7 @@ impl TextureBlitter {
                 },
                 BindGroupEntry {
                     binding: 1,
-                    resource: crate::BindingResource::Sampler(&self.sampler),
+                    resource: crate::BindingResource::Sampler(sampler),
                 },
             ],
         });
@@ -209,9 +245,120 @@ impl TextureBlitter {
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: RenderPassFlags::empty(),
+            multiview: None,
         });
         pass.set_pipeline(&self.pipeline);
         pass.set_bind_group(0, &bind_group, &[]);
         pass.draw(0..3, 0..1);
     }
+
+    /// Copies a single array layer of the `source` [`Texture`] to an array layer of the `target` [`Texture`].
+    ///
+    /// For cube and cube array textures each face is an array layer, in the order
+    /// +X, -X, +Y, -Y, +Z, -Z.
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `encoder` - A [`CommandEncoder`]
+    /// - `source` - A 2D [`Texture`] with the `TEXTURE_BINDING` usage, of which the first mip level of `source_layer` gets copied. The format does not matter.
+    /// - `target` - A 2D [`Texture`] with the `RENDER_ATTACHMENT` usage, of which the first mip level of `target_layer` gets the data copied from the `source`. It has to be the same format as the format specified in [`TextureBlitter::new`]
+    pub fn copy_layer(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        source: &Texture,
+        source_layer: u32,
+        target: &Texture,
+        target_layer: u32,
+    ) {
+        self.copy(
+            device,
+            encoder,
+            &level_view(source, 0, source_layer),
+            &level_view(target, 0, target_layer),
+        );
+    }
+
+    /// Copies every array layer of the `source` [`Texture`] to the matching array layer of the `target` [`Texture`].
+    ///
+    /// This is equivalent to calling [`TextureBlitter::copy_layer`] for each layer, and
+    /// covers all six faces of cube textures.
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `encoder` - A [`CommandEncoder`]
+    /// - `source` - A 2D [`Texture`] with the `TEXTURE_BINDING` usage. The format does not matter.
+    /// - `target` - A 2D [`Texture`] with the `RENDER_ATTACHMENT` usage and as many array layers as `source`. It has to be the same format as the format specified in [`TextureBlitter::new`]
+    pub fn copy_layers(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        source: &Texture,
+        target: &Texture,
+    ) {
+        assert_eq!(
+            source.depth_or_array_layers(),
+            target.depth_or_array_layers(),
+            "source and target must have the same number of array layers"
+        );
+
+        for layer in 0..source.depth_or_array_layers() {
+            self.copy_layer(device, encoder, source, layer, target, layer);
+        }
+    }
+
+    /// Fills every mip level of `texture` after the first by blitting each level down from
+    /// the one before it.
+    ///
+    /// Every array layer of a 2D or 2D array texture is handled separately.
+    ///
+    /// A blitter built with [`FilterMode::Linear`] averages neighbouring texels, while
+    /// [`FilterMode::Nearest`] point samples each level. sRGB textures are filtered in linear
+    /// space, as sampling decodes and rendering encodes the sRGB values.
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `encoder` - A [`CommandEncoder`]
+    /// - `texture` - A 2D [`Texture`] with the `TEXTURE_BINDING` and `RENDER_ATTACHMENT` usages. It has to be the same format as the format specified in [`TextureBlitter::new`]
+    pub fn generate_mipmaps(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        texture: &Texture,
+    ) {
+        assert_eq!(
+            texture.dimension(),
+            TextureDimension::D2,
+            "mipmaps can only be generated for 2D textures"
+        );
+        assert_eq!(
+            texture.format(),
+            self.format,
+            "texture format must match the format of the TextureBlitter"
+        );
+
+        for layer in 0..texture.depth_or_array_layers() {
+            let mut source = level_view(texture, 0, layer);
+            for level in 1..texture.mip_level_count() {
+                let target = level_view(texture, level, layer);
+                self.blit(device, encoder, &source, &target, &self.mip_sampler);
+                source = target;
+            }
+        }
+    }
+}
+
+/// Creates a 2D view of a single mip level of a single array layer of `texture`.
+fn level_view(texture: &Texture, mip_level: u32, array_layer: u32) -> TextureView {
+    texture.create_view(&TextureViewDescriptor {
+        label: Some("wgpu::util::TextureBlitter::level_view"),
+        dimension: Some(TextureViewDimension::D2),
+        base_mip_level: mip_level,
+        mip_level_count: Some(1),
+        base_array_layer: array_layer,
+        array_layer_count: Some(1),
+        ..Default::default()
+    })
 }
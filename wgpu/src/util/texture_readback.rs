@@ -0,0 +1,106 @@
+use crate::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT,
+    CommandEncoderDescriptor, Device, Extent3d, MapMode, Origin3d, Queue, TexelCopyBufferInfo,
+    TexelCopyBufferLayout, TexelCopyTextureInfo, Texture, TextureAspect, WasmNotSend,
+    util::align_to,
+};
+use alloc::vec::Vec;
+
+/// Asynchronously read a region of a texture back to the CPU.
+///
+/// Copies `extent` texels, starting at `origin` in mip level `mip_level` of `texture`,
+/// into a staging buffer and calls `callback` with the data once it has been mapped.
+/// The data is tightly packed: rows follow each other without the padding to
+/// [`COPY_BYTES_PER_ROW_ALIGNMENT`] that a texture-to-buffer copy requires, and
+/// images (array layers or depth slices) follow each other in the same way. For
+/// compressed formats, a "row" is a row of blocks.
+///
+/// As with [`Buffer::map_async()`](crate::Buffer::map_async), the callback is only
+/// called once the device has been polled. If `extent` is empty in any dimension, nothing
+/// is copied and the callback is called right away with an empty `Vec`.
+///
+/// The texture must have [`TextureUsages::COPY_SRC`](crate::TextureUsages::COPY_SRC)
+/// usage, and `origin` and `extent` must meet the usual requirements of
+/// [`CommandEncoder::copy_texture_to_buffer()`](crate::CommandEncoder::copy_texture_to_buffer).
+///
+/// # Panics
+///
+/// - If the texture's format cannot be copied out of as a whole, which is the case for
+///   combined depth-stencil formats, multi-planar formats and `Depth24Plus`.
+pub fn read_texture_to_vec(
+    device: &Device,
+    queue: &Queue,
+    texture: &Texture,
+    mip_level: u32,
+    origin: Origin3d,
+    extent: Extent3d,
+    callback: impl FnOnce(Result<Vec<u8>, BufferAsyncError>) + WasmNotSend + 'static,
+) {
+    if extent.width == 0 || extent.height == 0 || extent.depth_or_array_layers == 0 {
+        callback(Ok(Vec::new()));
+        return;
+    }
+
+    let format = texture.format();
+    let block_size = format
+        .block_copy_size(None)
+        .expect("texture format must have a single copyable aspect");
+    let (block_width, block_height) = format.block_dimensions();
+
+    let blocks_per_row = extent.width.div_ceil(block_width);
+    let rows_per_image = extent.height.div_ceil(block_height);
+    let images = extent.depth_or_array_layers;
+
+    let unpadded_bytes_per_row = blocks_per_row * block_size;
+    let padded_bytes_per_row = align_to(unpadded_bytes_per_row, COPY_BYTES_PER_ROW_ALIGNMENT);
+    let rows = rows_per_image as u64 * images as u64;
+
+    let download = device.create_buffer(&BufferDescriptor {
+        label: Some("(wgpu internal) read_texture_to_vec"),
+        size: padded_bytes_per_row as u64 * rows,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("(wgpu internal) read_texture_to_vec"),
+    });
+    encoder.copy_texture_to_buffer(
+        TexelCopyTextureInfo {
+            texture,
+            mip_level,
+            origin,
+            aspect: TextureAspect::All,
+        },
+        TexelCopyBufferInfo {
+            buffer: &download,
+            layout: TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(rows_per_image),
+            },
+        },
+        extent,
+    );
+    queue.submit(Some(encoder.finish()));
+
+    download
+        .clone()
+        .slice(..)
+        .map_async(MapMode::Read, move |result| {
+            if let Err(e) = result {
+                callback(Err(e));
+                return;
+            }
+
+            let mut data = Vec::with_capacity(unpadded_bytes_per_row as usize * rows as usize);
+            {
+                let mapped = download.slice(..).get_mapped_range();
+                for row in mapped.chunks_exact(padded_bytes_per_row as usize) {
+                    data.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+                }
+            }
+            download.unmap();
+            callback(Ok(data));
+        });
+}
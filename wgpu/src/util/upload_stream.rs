@@ -0,0 +1,182 @@
+use crate::{
+    Buffer, BufferAddress, BufferSize, COPY_BUFFER_ALIGNMENT, CommandEncoderDescriptor, Device,
+    PollType, Queue, SubmissionIndex,
+    util::{StagingBelt, align_to},
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::fmt;
+
+/// Maximum number of submissions an [`UploadStream`] keeps in flight before
+/// waiting on the oldest one, bounding the staging memory it uses.
+const MAX_IN_FLIGHT_SUBMISSIONS: usize = 2;
+
+/// Streams data into a GPU buffer through a bounded amount of staging memory.
+///
+/// Where [`Queue::write_buffer()`] needs the whole payload in memory at once,
+/// an `UploadStream` pulls data from a reader or an iterator of chunks and copies it
+/// into the destination buffer `chunk_size` bytes at a time, each chunk in its own
+/// submission. Staging buffers are recycled through a [`StagingBelt`], and the stream
+/// waits for older submissions to complete before it allocates more, so memory use
+/// stays proportional to `chunk_size` regardless of the size of the payload.
+///
+/// Because it waits on the device with [`Device::poll()`], an `UploadStream` only bounds
+/// its memory use on backends where polling can block; on WebGPU, staging buffers are
+/// reclaimed as the browser completes the copies.
+///
+/// [`Queue::write_buffer()`]: crate::Queue::write_buffer
+pub struct UploadStream {
+    chunk_size: BufferAddress,
+    belt: StagingBelt,
+    /// Data read from the source that has not been copied yet.
+    scratch: Vec<u8>,
+    /// Submissions which may still be reading from the belt's staging buffers.
+    in_flight: VecDeque<SubmissionIndex>,
+}
+
+impl UploadStream {
+    /// Create a new upload stream.
+    ///
+    /// The `chunk_size` is the amount of data copied per submission, and must be a
+    /// multiple of [`COPY_BUFFER_ALIGNMENT`].
+    pub fn new(chunk_size: BufferSize) -> Self {
+        let chunk_size = chunk_size.get();
+        assert_eq!(
+            chunk_size % COPY_BUFFER_ALIGNMENT,
+            0,
+            "chunk_size must be a multiple of COPY_BUFFER_ALIGNMENT"
+        );
+        Self {
+            chunk_size,
+            belt: StagingBelt::new(chunk_size),
+            scratch: Vec::with_capacity(chunk_size as usize),
+            in_flight: VecDeque::new(),
+        }
+    }
+
+    /// Copy every chunk yielded by `chunks` into `target`, starting at `offset`.
+    ///
+    /// The chunks may be of any size; they are regrouped into copies of `chunk_size`
+    /// bytes. Returns the number of bytes written.
+    ///
+    /// If the total length is not a multiple of [`COPY_BUFFER_ALIGNMENT`], the final copy
+    /// is padded with zeros up to that alignment, so `target` must have room for the
+    /// padded length.
+    pub fn write_from_iter<I>(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        target: &Buffer,
+        offset: BufferAddress,
+        chunks: I,
+    ) -> BufferAddress
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mut written = 0;
+        for chunk in chunks {
+            let mut data = chunk.as_ref();
+            while !data.is_empty() {
+                let count = data
+                    .len()
+                    .min(self.chunk_size as usize - self.scratch.len());
+                self.scratch.extend_from_slice(&data[..count]);
+                data = &data[count..];
+
+                if self.scratch.len() as BufferAddress == self.chunk_size {
+                    written += self.flush(device, queue, target, offset + written);
+                }
+            }
+        }
+        written += self.flush(device, queue, target, offset + written);
+        written
+    }
+
+    /// Copy everything read from `reader` into `target`, starting at `offset`.
+    ///
+    /// Returns the number of bytes written. Padding of the final copy behaves as
+    /// described in [`UploadStream::write_from_iter()`].
+    ///
+    /// If reading fails, the data read so far has still been copied into `target`.
+    #[cfg(std)]
+    pub fn write_from_reader<R: std::io::Read>(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        target: &Buffer,
+        offset: BufferAddress,
+        mut reader: R,
+    ) -> std::io::Result<BufferAddress> {
+        let mut written = 0;
+        let result = loop {
+            let filled = self.scratch.len();
+            self.scratch.resize(self.chunk_size as usize, 0);
+            match reader.read(&mut self.scratch[filled..]) {
+                Ok(0) => {
+                    self.scratch.truncate(filled);
+                    break Ok(());
+                }
+                Ok(count) => self.scratch.truncate(filled + count),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                    self.scratch.truncate(filled);
+                }
+                Err(e) => {
+                    self.scratch.truncate(filled);
+                    break Err(e);
+                }
+            }
+
+            if self.scratch.len() as BufferAddress == self.chunk_size {
+                written += self.flush(device, queue, target, offset + written);
+            }
+        };
+        written += self.flush(device, queue, target, offset + written);
+        result.map(|()| written)
+    }
+
+    /// Copy the contents of `self.scratch` into `target` at `offset` and submit it,
+    /// returning the number of bytes of payload copied.
+    fn flush(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        target: &Buffer,
+        offset: BufferAddress,
+    ) -> BufferAddress {
+        let len = self.scratch.len() as BufferAddress;
+        let Some(size) = BufferSize::new(align_to(len, COPY_BUFFER_ALIGNMENT)) else {
+            return 0;
+        };
+        self.scratch.resize(size.get() as usize, 0);
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("(wgpu internal) UploadStream"),
+        });
+        self.belt
+            .write_buffer(&mut encoder, target, offset, size, device)
+            .copy_from_slice(&self.scratch);
+        self.belt.finish();
+        self.in_flight
+            .push_back(queue.submit(Some(encoder.finish())));
+        self.belt.recall();
+        self.scratch.clear();
+
+        if self.in_flight.len() > MAX_IN_FLIGHT_SUBMISSIONS {
+            let oldest = self.in_flight.pop_front().unwrap();
+            // A failed wait only means the belt may allocate another staging buffer.
+            let _ = device.poll(PollType::wait_for(oldest));
+        }
+
+        len
+    }
+}
+
+impl fmt::Debug for UploadStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadStream")
+            .field("chunk_size", &self.chunk_size)
+            .field("belt", &self.belt)
+            .field("in_flight", &self.in_flight.len())
+            .finish_non_exhaustive()
+    }
+}
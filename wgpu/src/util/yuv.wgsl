@@ -0,0 +1,69 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+struct Conversion {
+    // YCbCr to RGB matrix, with the range expansion folded in.
+    matrix: mat3x3<f32>,
+    // Subtracted from the YCbCr values before applying `matrix`.
+    offset: vec3<f32>,
+    // Position of the first chroma sample, in luma texels.
+    chroma_offset: vec2<f32>,
+    // 2 if both chroma channels are in one plane, 3 if they are in separate planes.
+    planes: u32,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vi: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    out.tex_coords = vec2<f32>(
+        f32((vi << 1u) & 2u),
+        f32(vi & 2u),
+    );
+
+    out.position = vec4<f32>(out.tex_coords * 2.0 - 1.0, 0.0, 1.0);
+
+    // Invert y so the texture is not upside down
+    out.tex_coords.y = 1.0 - out.tex_coords.y;
+    return out;
+}
+
+@group(0) @binding(0)
+var luma: texture_2d<f32>;
+@group(0) @binding(1)
+var chroma_b: texture_2d<f32>;
+@group(0) @binding(2)
+var chroma_r: texture_2d<f32>;
+@group(0) @binding(3)
+var<uniform> conversion: Conversion;
+
+fn load_chroma(coords: vec2<i32>) -> vec2<f32> {
+    let size = vec2<i32>(textureDimensions(chroma_b));
+    let clamped = clamp(coords, vec2<i32>(0), size - 1);
+    if conversion.planes == 2u {
+        return textureLoad(chroma_b, clamped, 0).rg;
+    }
+    return vec2<f32>(textureLoad(chroma_b, clamped, 0).r, textureLoad(chroma_r, clamped, 0).r);
+}
+
+@fragment
+fn fs_main(vs: VertexOutput) -> @location(0) vec4<f32> {
+    let luma_size = vec2<f32>(textureDimensions(luma));
+    let luma_position = vs.tex_coords * luma_size;
+    let y = textureLoad(luma, vec2<i32>(min(luma_position, luma_size - 1.0)), 0).r;
+
+    // The planes aren't filterable, so chroma is reconstructed by hand, by
+    // interpolating between the four nearest chroma samples.
+    let chroma_position = (luma_position - conversion.chroma_offset) * 0.5;
+    let base = floor(chroma_position);
+    let t = chroma_position - base;
+    let texel = vec2<i32>(base);
+    let top = mix(load_chroma(texel), load_chroma(texel + vec2<i32>(1, 0)), t.x);
+    let bottom = mix(load_chroma(texel + vec2<i32>(0, 1)), load_chroma(texel + vec2<i32>(1, 1)), t.x);
+    let cbcr = mix(top, bottom, t.y);
+
+    let rgb = conversion.matrix * (vec3<f32>(y, cbcr) - conversion.offset);
+    return vec4<f32>(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0);
+}
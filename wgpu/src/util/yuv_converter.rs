@@ -0,0 +1,320 @@
+#![cfg(feature = "wgsl")]
+
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, BufferUsages, ChromaLocation,
+    ColorTargetState, ColorWrites, CommandEncoder, Device, FragmentState, FrontFace, LoadOp,
+    MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState,
+    PrimitiveTopology, RenderPassDescriptor, RenderPassFlags, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, StoreOp, Texture, TextureAspect, TextureFormat,
+    TextureSampleType, TextureView, TextureViewDescriptor, TextureViewDimension, VertexState,
+    YuvMatrix, YuvRange, include_wgsl,
+    util::{BufferInitDescriptor, DeviceExt as _},
+};
+
+/// Returns the red and blue luma coefficients, `Kr` and `Kb`, of a matrix.
+fn coefficients(matrix: YuvMatrix) -> (f32, f32) {
+    match matrix {
+        YuvMatrix::Bt601 => (0.299, 0.114),
+        YuvMatrix::Bt709 => (0.2126, 0.0722),
+        YuvMatrix::Bt2020 => (0.2627, 0.0593),
+    }
+}
+
+/// Returns the position of the first chroma sample, in luma texels.
+fn chroma_position(location: ChromaLocation) -> f32 {
+    match location {
+        ChromaLocation::CositedEven => 0.5,
+        ChromaLocation::Midpoint => 1.0,
+    }
+}
+
+/// A builder for the [`YuvConverter`] utility.
+/// If you want the default [`YuvConverter`] use [`YuvConverter::new`] instead.
+pub struct YuvConverterBuilder<'a> {
+    device: &'a Device,
+    source_format: TextureFormat,
+    format: TextureFormat,
+    matrix: YuvMatrix,
+    range: YuvRange,
+    x_chroma_location: ChromaLocation,
+    y_chroma_location: ChromaLocation,
+}
+
+impl<'a> YuvConverterBuilder<'a> {
+    /// Returns a new [`YuvConverterBuilder`]
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `source_format` - The multi-planar [`TextureFormat`] of the textures that will be converted.
+    /// - `format` - The [`TextureFormat`] of the texture that will be converted to. This has to have the `RENDER_TARGET` usage.
+    pub fn new(device: &'a Device, source_format: TextureFormat, format: TextureFormat) -> Self {
+        assert!(
+            source_format.is_multi_planar_format(),
+            "source format must be a multi-planar format"
+        );
+        Self {
+            device,
+            source_format,
+            format,
+            matrix: YuvMatrix::default(),
+            range: YuvRange::default(),
+            x_chroma_location: ChromaLocation::CositedEven,
+            y_chroma_location: ChromaLocation::Midpoint,
+        }
+    }
+
+    /// Sets the [`YuvMatrix`]. Defaults to [`YuvMatrix::Bt709`].
+    pub fn matrix(mut self, matrix: YuvMatrix) -> Self {
+        self.matrix = matrix;
+        self
+    }
+
+    /// Sets the [`YuvRange`]. Defaults to [`YuvRange::Limited`].
+    pub fn range(mut self, range: YuvRange) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Sets the horizontal and vertical [`ChromaLocation`]s.
+    ///
+    /// Defaults to [`ChromaLocation::CositedEven`] horizontally and [`ChromaLocation::Midpoint`]
+    /// vertically, which is what H.264 and HEVC use unless told otherwise.
+    pub fn chroma_location(mut self, x: ChromaLocation, y: ChromaLocation) -> Self {
+        self.x_chroma_location = x;
+        self.y_chroma_location = y;
+        self
+    }
+
+    /// Returns a new [`YuvConverter`] with given settings.
+    pub fn build(self) -> YuvConverter {
+        let (kr, kb) = coefficients(self.matrix);
+        let kg = 1.0 - kr - kb;
+        // Chroma is stored with an offset of half the range, like `128` for 8 bit video.
+        let (luma_offset, luma_scale, chroma_scale) = match self.range {
+            YuvRange::Full => (0.0, 1.0, 1.0),
+            YuvRange::Limited => (16.0 / 255.0, 255.0 / 219.0, 255.0 / 224.0),
+        };
+        let chroma_offset = 128.0 / 255.0;
+        let planes = self.source_format.planes().unwrap();
+
+        // Laid out like the `Conversion` struct in `yuv.wgsl`.
+        #[rustfmt::skip]
+        let conversion: [f32; 20] = [
+            // Matrix columns, for Y, Cb and Cr.
+            luma_scale, luma_scale, luma_scale, 0.0,
+            0.0, -2.0 * kb * (1.0 - kb) / kg * chroma_scale, 2.0 * (1.0 - kb) * chroma_scale, 0.0,
+            2.0 * (1.0 - kr) * chroma_scale, -2.0 * kr * (1.0 - kr) / kg * chroma_scale, 0.0, 0.0,
+            luma_offset, chroma_offset, chroma_offset, 0.0,
+            chroma_position(self.x_chroma_location), chroma_position(self.y_chroma_location), f32::from_bits(planes), 0.0,
+        ];
+        let uniform_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("wgpu::util::YuvConverter::uniform_buffer"),
+            contents: bytemuck::cast_slice(&conversion),
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let plane_entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: false },
+                view_dimension: TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let bind_group_layout = self
+            .device
+            .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("wgpu::util::YuvConverter::bind_group_layout"),
+                entries: &[
+                    plane_entry(0),
+                    plane_entry(1),
+                    plane_entry(2),
+                    BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let pipeline_layout = self
+            .device
+            .create_pipeline_layout(&PipelineLayoutDescriptor {
+                label: Some("wgpu::util::YuvConverter::pipeline_layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            });
+
+        let shader = self.device.create_shader_module(include_wgsl!("yuv.wgsl"));
+        let pipeline = self
+            .device
+            .create_render_pipeline(&RenderPipelineDescriptor {
+                label: Some("wgpu::util::YuvConverter::pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    compilation_options: PipelineCompilationOptions::default(),
+                    buffers: &[],
+                },
+                primitive: PrimitiveState {
+                    topology: PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    primitive_restart: true,
+                    front_face: FrontFace::Ccw,
+                    cull_mode: None,
+                    unclipped_depth: false,
+                    polygon_mode: wgt::PolygonMode::Fill,
+                    conservative: false,
+                    line_width: 1.0,
+                    line_stipple: None,
+                },
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+                fragment: Some(FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    compilation_options: PipelineCompilationOptions::default(),
+                    targets: &[Some(ColorTargetState {
+                        format: self.format,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                        logic_op: None,
+                    })],
+                }),
+                multiview: None,
+                cache: None,
+            });
+
+        YuvConverter {
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            source_format: self.source_format,
+        }
+    }
+}
+
+/// YUV to RGB Conversion Utility
+///
+/// Renders a texture of a multi-planar YUV format, such as [`TextureFormat::NV12`],
+/// [`TextureFormat::P010`] or [`TextureFormat::I420`], to an RGB texture. Video frames
+/// from decoders and cameras usually come in one of these formats.
+///
+/// The planes are read separately through plane views and combined in a fragment
+/// shader, with the chroma planes upsampled with linear filtering.
+pub struct YuvConverter {
+    pipeline: RenderPipeline,
+    bind_group_layout: BindGroupLayout,
+    uniform_buffer: Buffer,
+    source_format: TextureFormat,
+}
+
+impl YuvConverter {
+    /// Returns a [`YuvConverter`] with default settings.
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `source_format` - The multi-planar [`TextureFormat`] of the textures that will be converted.
+    /// - `format` - The [`TextureFormat`] of the texture that will be converted to. This has to have the `RENDER_TARGET` usage.
+    ///
+    /// Properties of the conversion (such as the [`YuvMatrix`]) can be customised by using [`YuvConverterBuilder`] instead.
+    pub fn new(device: &Device, source_format: TextureFormat, format: TextureFormat) -> Self {
+        YuvConverterBuilder::new(device, source_format, format).build()
+    }
+
+    /// Converts the `source` [`Texture`] to RGB, writing the result to the `target` [`TextureView`].
+    ///
+    /// # Arguments
+    /// - `device` - A [`Device`]
+    /// - `encoder` - A [`CommandEncoder`]
+    /// - `source` - A 2D [`Texture`] with the `TEXTURE_BINDING` usage. It has to be the same format as the source format specified in [`YuvConverter::new`]
+    /// - `target` - A [`TextureView`] that gets the converted data. It has to be the same format as the format specified in [`YuvConverter::new`]. If it is not the same size as `source`, luma is point sampled.
+    pub fn convert(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        source: &Texture,
+        target: &TextureView,
+    ) {
+        assert_eq!(
+            source.format(),
+            self.source_format,
+            "source format must match the source format of the YuvConverter"
+        );
+
+        let plane_view = |aspect| {
+            source.create_view(&TextureViewDescriptor {
+                label: Some("wgpu::util::YuvConverter::plane_view"),
+                dimension: Some(TextureViewDimension::D2),
+                aspect,
+                base_mip_level: 0,
+                mip_level_count: Some(1),
+                base_array_layer: 0,
+                array_layer_count: Some(1),
+                ..Default::default()
+            })
+        };
+        let luma = plane_view(TextureAspect::Plane0);
+        let chroma_b = plane_view(TextureAspect::Plane1);
+        // With two planes, both chroma channels are read from the second plane.
+        let chroma_r = if self.source_format.planes() == Some(3) {
+            plane_view(TextureAspect::Plane2)
+        } else {
+            chroma_b.clone()
+        };
+
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::YuvConverter::bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: crate::BindingResource::TextureView(&luma),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: crate::BindingResource::TextureView(&chroma_b),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: crate::BindingResource::TextureView(&chroma_r),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("wgpu::util::YuvConverter::pass"),
+            color_attachments: &[Some(crate::RenderPassColorAttachment {
+                view: target,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgt::Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            pipeline_statistics_query: None,
+            flags: RenderPassFlags::empty(),
+            multiview: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,164 @@
+//! Hardware accelerated video decoding into [`Texture`]s.
+//!
+//! Nothing in this module is a part of the WebGPU API specification;
+//! it is unique to the `wgpu` library.
+//!
+//! Decoding is currently only supported on the Metal backend, through VideoToolbox.
+//! Creating a [`VideoDecoder`] on any other backend fails with
+//! [`VideoDecodeError::Unsupported`].
+
+use core::{error, fmt};
+
+use crate::{Device, Texture};
+#[cfg(metal)]
+use crate::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages};
+
+pub use wgt::VideoCodec;
+
+/// Error when creating a [`VideoDecoder`] or decoding video with it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VideoDecodeError {
+    /// The device's backend can't decode video.
+    Unsupported,
+    /// Coded data was submitted before the stream's parameter sets.
+    MissingParameterSets,
+    /// The bitstream is invalid.
+    InvalidBitstream,
+    /// The platform's decoder failed with the given status code.
+    Decoder(i32),
+}
+static_assertions::assert_impl_all!(VideoDecodeError: Send, Sync);
+
+impl fmt::Display for VideoDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "Video decoding is not supported by this device"),
+            Self::MissingParameterSets => write!(
+                f,
+                "Coded data was submitted before the stream's parameter sets"
+            ),
+            Self::InvalidBitstream => write!(f, "The bitstream is invalid"),
+            Self::Decoder(status) => write!(f, "The video decoder failed with status {status}"),
+        }
+    }
+}
+
+impl error::Error for VideoDecodeError {}
+
+#[cfg(metal)]
+impl From<hal::metal::VideoDecodeError> for VideoDecodeError {
+    fn from(error: hal::metal::VideoDecodeError) -> Self {
+        match error {
+            hal::metal::VideoDecodeError::MissingParameterSets => Self::MissingParameterSets,
+            hal::metal::VideoDecodeError::InvalidBitstream => Self::InvalidBitstream,
+            hal::metal::VideoDecodeError::Status(status) => Self::Decoder(status),
+        }
+    }
+}
+
+/// A decoded video frame, in 4:2:0 YCbCr with limited range.
+///
+/// The textures alias the decoder's output, which is reused for later frames once
+/// the frame is dropped. Keep the frame alive until all work reading from its
+/// textures has completed.
+#[derive(Debug)]
+pub struct VideoFrame {
+    /// The luma plane, as an [`TextureFormat::R8Unorm`] texture of the frame's size.
+    pub luma: Texture,
+    /// The chroma plane, as an [`TextureFormat::Rg8Unorm`] texture of half the frame's
+    /// width and height, with Cb in the red channel and Cr in the green channel.
+    pub chroma: Texture,
+    #[cfg(metal)]
+    _frame: hal::metal::DecodedFrame,
+}
+
+/// A hardware accelerated video decoder.
+///
+/// Streams are decoded one access unit at a time, with [`VideoDecoder::decode`]. Frames
+/// come out in decode order, so streams with B-frames must be reordered by
+/// presentation time by the caller.
+#[derive(Debug)]
+pub struct VideoDecoder {
+    #[cfg(metal)]
+    inner: hal::metal::VideoDecoder,
+}
+
+impl VideoDecoder {
+    /// Creates a decoder for `codec`, producing textures for `device`.
+    pub fn new(device: &Device, codec: VideoCodec) -> Result<Self, VideoDecodeError> {
+        #[cfg(metal)]
+        if unsafe { device.as_hal::<hal::api::Metal>() }.is_some() {
+            return Ok(Self {
+                inner: hal::metal::VideoDecoder::new(codec),
+            });
+        }
+
+        let _ = (device, codec);
+        Err(VideoDecodeError::Unsupported)
+    }
+
+    /// Decodes an access unit in Annex B format, containing the coded data of a single
+    /// frame, and any parameter sets before it.
+    ///
+    /// Returns `Ok(None)` if the access unit contained no coded data, such as when it
+    /// only held parameter sets, or if the decoder dropped the frame.
+    ///
+    /// # Arguments
+    /// - `device` - The [`Device`] this decoder was created for.
+    /// - `access_unit` - The coded data, with each NAL unit preceded by a start code.
+    pub fn decode(
+        &mut self,
+        device: &Device,
+        access_unit: &[u8],
+    ) -> Result<Option<VideoFrame>, VideoDecodeError> {
+        #[cfg(metal)]
+        {
+            let Some(frame) = self.inner.decode(access_unit)? else {
+                return Ok(None);
+            };
+            let (width, height) = frame.size();
+            let plane_texture = |plane, label, format, width, height| {
+                let desc = TextureDescriptor {
+                    label: Some(label),
+                    size: Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format,
+                    usage: TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                };
+                // The decoder outputs NV12 surfaces, whose planes match these formats.
+                unsafe { device.create_texture_from_io_surface(frame.io_surface(), plane, &desc) }
+            };
+            Ok(Some(VideoFrame {
+                luma: plane_texture(
+                    0,
+                    "wgpu::video::VideoFrame::luma",
+                    TextureFormat::R8Unorm,
+                    width,
+                    height,
+                ),
+                chroma: plane_texture(
+                    1,
+                    "wgpu::video::VideoFrame::chroma",
+                    TextureFormat::Rg8Unorm,
+                    width.div_ceil(2),
+                    height.div_ceil(2),
+                ),
+                _frame: frame,
+            }))
+        }
+
+        #[cfg(not(metal))]
+        {
+            let _ = (device, access_unit);
+            unreachable!("video decoders can only be created on Metal")
+        }
+    }
+}